@@ -0,0 +1,93 @@
+/**
+ * Event Journal - Persists session events with sequence numbers so a client
+ * that was disconnected when they were broadcast can replay them on reconnect.
+ *
+ * The journal:
+ * - Numbers every session event with a monotonically increasing sequence
+ * - Keeps status transitions (everything except streaming 'output' events)
+ * - Appends them to a JSONL file and keeps the newest ones on restart
+ */
+
+import * as fs from 'fs';
+
+import type { SessionEventParams } from './protocol.js';
+
+/** Events kept in memory and on disk */
+const CAPACITY = 500;
+
+export class EventJournal {
+  private events: SessionEventParams[] = [];
+  private nextSeq = 1;
+  /** Lines in the file, compacted once it holds twice the capacity */
+  private fileLines = 0;
+
+  constructor(private readonly filePath: string) {
+    this.load();
+  }
+
+  /** Sequence number of the most recent event (0 if none yet) */
+  get latestSeq(): number {
+    return this.nextSeq - 1;
+  }
+
+  /** Number the event and, if it's a status transition, journal it */
+  record(event: SessionEventParams): SessionEventParams {
+    const numbered = { ...event, seq: this.nextSeq++ };
+    if (numbered.event === 'output') {
+      return numbered;
+    }
+
+    this.events.push(numbered);
+    if (this.events.length > CAPACITY) {
+      this.events.splice(0, this.events.length - CAPACITY);
+    }
+
+    try {
+      if (this.fileLines >= CAPACITY * 2) {
+        this.rewrite();
+      } else {
+        fs.appendFileSync(this.filePath, JSON.stringify(numbered) + '\n');
+        this.fileLines++;
+      }
+    } catch (err) {
+      console.error('Failed to write event journal:', err);
+    }
+    return numbered;
+  }
+
+  /** Journaled events with a sequence number greater than `since` */
+  since(since: number): SessionEventParams[] {
+    return this.events.filter((e) => (e.seq ?? 0) > since);
+  }
+
+  private load(): void {
+    if (!fs.existsSync(this.filePath)) {
+      return;
+    }
+    try {
+      const lines = fs.readFileSync(this.filePath, 'utf8').split('\n');
+      for (const line of lines) {
+        if (!line.trim()) continue;
+        try {
+          const event = JSON.parse(line) as SessionEventParams;
+          if (typeof event.seq === 'number') {
+            this.events.push(event);
+            this.nextSeq = Math.max(this.nextSeq, event.seq + 1);
+          }
+        } catch {
+          // Skip a torn last line from a crash mid-write
+        }
+      }
+      this.events = this.events.slice(-CAPACITY);
+      this.rewrite();
+    } catch (err) {
+      console.error('Failed to load event journal:', err);
+    }
+  }
+
+  private rewrite(): void {
+    const body = this.events.map((e) => JSON.stringify(e) + '\n').join('');
+    fs.writeFileSync(this.filePath, body);
+    this.fileLines = this.events.length;
+  }
+}
@@ -1,16 +1,23 @@
 /**
  * Kanblam Sidecar - Claude Code Agent SDK IPC server
  *
- * Communicates with the Rust TUI via Unix domain socket using JSON-RPC 2.0
+ * Communicates with the Rust TUI via Unix domain socket using JSON-RPC 2.0.
+ * Setting KANBLAM_SIDECAR_LISTEN (host:port) and KANBLAM_SIDECAR_TOKEN also
+ * serves it over TCP, so the TUI can run on another machine. TCP clients must
+ * send an `auth` request with the token before anything else. The TCP stream
+ * isn't encrypted, so only loopback addresses are accepted unless
+ * KANBLAM_SIDECAR_ALLOW_REMOTE=1 says the network (a VPN, say) is trusted.
  */
 
 import * as net from 'net';
+import * as crypto from 'crypto';
 import * as fs from 'fs';
 import * as path from 'path';
 import * as os from 'os';
 
 import { SessionManager } from './session-manager.js';
 import { WatcherSession, type WatcherComment } from './watcher.js';
+import { EventJournal } from './journal.js';
 import {
   type JsonRpcRequest,
   type JsonRpcResponse,
@@ -20,10 +27,14 @@ import {
   type SendPromptParams,
   type StopSessionParams,
   type SummarizeTitleParams,
+  type CompletePromptParams,
   type StartWatcherParams,
   type StopWatcherParams,
+  type TriggerWatcherParams,
   type WatcherCommentParams,
   type WatcherObservingParams,
+  type ReplayEventsParams,
+  type AuthParams,
   createResponse,
   createSessionEvent,
   createWatcherComment,
@@ -34,27 +45,66 @@ import {
 // Socket path - in user's runtime directory
 const SOCKET_DIR = path.join(os.homedir(), '.kanblam');
 const SOCKET_PATH = path.join(SOCKET_DIR, 'sidecar.sock');
+const JOURNAL_PATH = path.join(SOCKET_DIR, 'sidecar-journal.jsonl');
+
+// Optional TCP listener for a TUI on another host
+const TCP_LISTEN = process.env.KANBLAM_SIDECAR_LISTEN;
+const AUTH_TOKEN = process.env.KANBLAM_SIDECAR_TOKEN;
+/** Opt-in to listening on a non-loopback address, where the token and prompts travel in the clear */
+const ALLOW_REMOTE = process.env.KANBLAM_SIDECAR_ALLOW_REMOTE === '1';
+/** How long a TCP client has to authenticate before it's dropped */
+const AUTH_TIMEOUT_MS = 10_000;
+
+/** Whether `host` only accepts connections from this machine */
+function isLoopback(host: string): boolean {
+  const bare = host.replace(/^\[(.*)\]$/, '$1');
+  return bare === 'localhost' || bare === '::1' || (net.isIPv4(bare) && bare.startsWith('127.'));
+}
+
+/** Compare tokens without leaking their contents through timing */
+function tokenMatches(given: unknown): boolean {
+  if (typeof given !== 'string' || !AUTH_TOKEN) return false;
+  const digest = (s: string) => crypto.createHash('sha256').update(s).digest();
+  return crypto.timingSafeEqual(digest(given), digest(AUTH_TOKEN));
+}
 
 class SidecarServer {
   private server: net.Server;
+  private tcpServer: net.Server | null = null;
   private sessionManager: SessionManager;
   private watchers: Map<string, WatcherSession> = new Map();
+  /** Connections that receive broadcasts: local ones, and TCP ones once authenticated */
   private clients: Set<net.Socket> = new Set();
+  private journal: EventJournal;
 
   constructor() {
+    if (!fs.existsSync(SOCKET_DIR)) {
+      fs.mkdirSync(SOCKET_DIR, { recursive: true });
+    }
+    this.journal = new EventJournal(JOURNAL_PATH);
+
     // Initialize session manager with event callback
     this.sessionManager = new SessionManager((event) => {
       this.broadcastEvent(event);
     });
 
     this.server = net.createServer((socket) => {
-      this.handleConnection(socket);
+      this.handleConnection(socket, false);
     });
   }
 
-  private handleConnection(socket: net.Socket): void {
-    console.log('Client connected');
-    this.clients.add(socket);
+  private handleConnection(socket: net.Socket, remote: boolean): void {
+    if (remote) {
+      console.log(`Remote client connected from ${socket.remoteAddress}`);
+      // Not a client until it authenticates
+      const timer = setTimeout(() => {
+        if (!this.clients.has(socket)) socket.destroy();
+      }, AUTH_TIMEOUT_MS);
+      socket.on('close', () => clearTimeout(timer));
+    } else {
+      console.log('Client connected');
+      this.clients.add(socket);
+    }
 
     let buffer = '';
 
@@ -97,11 +147,39 @@ class SidecarServer {
       return;
     }
 
+    if (request.method === 'auth') {
+      this.handleAuth(socket, request);
+      return;
+    }
+    if (!this.clients.has(socket)) {
+      this.send(socket, createResponse(request.id, undefined, {
+        code: ErrorCodes.UNAUTHORIZED,
+        message: 'Not authenticated',
+      }));
+      socket.end();
+      return;
+    }
+
     // Handle the request
     const response = await this.handleRequest(request);
     this.send(socket, response);
   }
 
+  private handleAuth(socket: net.Socket, request: JsonRpcRequest): void {
+    // Local connections are trusted already
+    if (this.clients.has(socket) || tokenMatches((request.params as AuthParams)?.token)) {
+      this.clients.add(socket);
+      this.send(socket, createResponse(request.id, { ok: true }));
+      return;
+    }
+    console.error(`Rejected remote client ${socket.remoteAddress}: bad token`);
+    this.send(socket, createResponse(request.id, undefined, {
+      code: ErrorCodes.UNAUTHORIZED,
+      message: 'Invalid auth token',
+    }));
+    socket.end();
+  }
+
   private async handleRequest(request: JsonRpcRequest): Promise<JsonRpcResponse> {
     const { id, method, params } = request;
 
@@ -200,6 +278,18 @@ class SidecarServer {
           return createResponse(id, result);
         }
 
+        case 'complete_prompt': {
+          const p = params as CompletePromptParams;
+          if (!p?.prompt) {
+            return createResponse(id, undefined, {
+              code: ErrorCodes.INVALID_PARAMS,
+              message: 'Missing required param: prompt',
+            });
+          }
+          const result = await this.sessionManager.completePrompt(p);
+          return createResponse(id, result);
+        }
+
         case 'stop_all_sessions': {
           this.sessionManager.stopAllSessions();
           return createResponse(id, { success: true });
@@ -209,6 +299,20 @@ class SidecarServer {
           return createResponse(id, { pong: true });
         }
 
+        case 'replay_events': {
+          const p = params as ReplayEventsParams;
+          if (typeof p?.since !== 'number') {
+            return createResponse(id, undefined, {
+              code: ErrorCodes.INVALID_PARAMS,
+              message: 'Missing required param: since',
+            });
+          }
+          return createResponse(id, {
+            events: this.journal.since(p.since),
+            latest_seq: this.journal.latestSeq,
+          });
+        }
+
         case 'start_watcher': {
           const p = params as StartWatcherParams;
           if (!p?.project_path) {
@@ -259,7 +363,7 @@ class SidecarServer {
 
         case 'trigger_watcher': {
           // Force an immediate observation - fire and forget, don't await
-          const p = params as StopWatcherParams;
+          const p = params as TriggerWatcherParams;
           if (!p?.project_path) {
             return createResponse(id, undefined, {
               code: ErrorCodes.INVALID_PARAMS,
@@ -271,7 +375,12 @@ class SidecarServer {
           if (watcher) {
             // Don't await - let observation run in background
             // Response is sent immediately, notifications come async
-            watcher.observeNow().catch(err => {
+            watcher.observeNow({
+              mutedCategories: p.muted_categories ?? [],
+              persona: p.persona,
+              metadataOnly: p.metadata_only ?? false,
+              tasks: p.tasks ?? [],
+            }).catch(err => {
               console.error('[Watcher] Background observation failed:', err);
             });
             return createResponse(id, { success: true });
@@ -299,7 +408,8 @@ class SidecarServer {
   }
 
   private broadcastEvent(event: SessionEventParams): void {
-    const notification = createSessionEvent(event);
+    // Journal first so clients that are disconnected right now can replay it
+    const notification = createSessionEvent(this.journal.record(event));
     const message = JSON.stringify(notification) + '\n';
 
     for (const client of this.clients) {
@@ -319,6 +429,7 @@ class SidecarServer {
       mood: comment.mood || 'happy',
       timestamp: comment.timestamp.toISOString(),
       insight: comment.insight,
+      category: comment.category,
     };
     const notification = createWatcherComment(params);
     const message = JSON.stringify(notification) + '\n';
@@ -367,7 +478,7 @@ class SidecarServer {
       fs.unlinkSync(SOCKET_PATH);
     }
 
-    return new Promise((resolve, reject) => {
+    await new Promise<void>((resolve, reject) => {
       this.server.listen(SOCKET_PATH, () => {
         console.log(`Sidecar listening on ${SOCKET_PATH}`);
         resolve();
@@ -375,6 +486,43 @@ class SidecarServer {
 
       this.server.on('error', reject);
     });
+
+    if (TCP_LISTEN) {
+      await this.startTcp(TCP_LISTEN);
+    }
+  }
+
+  /** Also listen on TCP ("host:port" or just a port) for remote TUIs */
+  private startTcp(listen: string): Promise<void> {
+    if (!AUTH_TOKEN) {
+      throw new Error('KANBLAM_SIDECAR_LISTEN requires KANBLAM_SIDECAR_TOKEN');
+    }
+    const sep = listen.lastIndexOf(':');
+    const host = sep >= 0 ? listen.slice(0, sep) : '127.0.0.1';
+    const port = Number(sep >= 0 ? listen.slice(sep + 1) : listen);
+    if (!Number.isInteger(port) || port <= 0 || port > 65535) {
+      throw new Error(`Invalid KANBLAM_SIDECAR_LISTEN: ${listen}`);
+    }
+    if (!isLoopback(host) && !ALLOW_REMOTE) {
+      throw new Error(
+        `Refusing to listen on ${host}: the TCP connection isn't encrypted. ` +
+          'Set KANBLAM_SIDECAR_ALLOW_REMOTE=1 to listen there anyway (on a trusted network or VPN), ' +
+          'or listen on 127.0.0.1 and forward the port over ssh'
+      );
+    }
+
+    const tcpServer = net.createServer((socket) => {
+      this.handleConnection(socket, true);
+    });
+    this.tcpServer = tcpServer;
+    return new Promise((resolve, reject) => {
+      tcpServer.listen(port, host, () => {
+        console.log(`Sidecar listening on tcp://${host}:${port}`);
+        resolve();
+      });
+
+      tcpServer.on('error', reject);
+    });
   }
 
   stop(): void {
@@ -385,6 +533,7 @@ class SidecarServer {
     this.clients.clear();
 
     this.server.close();
+    this.tcpServer?.close();
 
     // Clean up socket file
     if (fs.existsSync(SOCKET_PATH)) {
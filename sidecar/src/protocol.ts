@@ -35,6 +35,8 @@ export interface StartSessionParams {
   worktree_path: string;
   prompt: string;
   images?: string[];
+  // Task environment variables (secrets already resolved)
+  env?: Record<string, string>;
 }
 
 export interface ResumeSessionParams {
@@ -42,6 +44,7 @@ export interface ResumeSessionParams {
   session_id: string;
   worktree_path: string;
   prompt?: string;
+  env?: Record<string, string>;
 }
 
 export interface SendPromptParams {
@@ -59,6 +62,11 @@ export interface SummarizeTitleParams {
   title: string;
 }
 
+export interface CompletePromptParams {
+  prompt: string;
+  cwd?: string;
+}
+
 // Response types
 export interface StartSessionResult {
   session_id: string;
@@ -68,6 +76,10 @@ export interface ResumeSessionResult {
   session_id: string; // New session ID (may differ from input)
 }
 
+export interface CompletePromptResult {
+  text: string;
+}
+
 export interface SummarizeTitleResult {
   short_title: string;
   abbreviation?: string;
@@ -82,7 +94,9 @@ export type SessionEventType =
   | 'needs_input'
   | 'working'
   | 'tool_use'
-  | 'output';
+  | 'output'
+  | 'context'
+  | 'compacted';
 
 /** Token usage data from SDK result */
 export interface TokenUsage {
@@ -106,6 +120,27 @@ export interface SessionEventParams {
   usage?: TokenUsage;
   /** Total cost in USD (for 'stopped' and 'ended' events) */
   cost_usd?: number;
+  /** Tokens in the context window ('context' events), or before compaction ('compacted' events) */
+  context_tokens?: number;
+  /** Journal sequence number, assigned when the event is broadcast */
+  seq?: number;
+}
+
+export interface AuthParams {
+  /** Shared token (KANBLAM_SIDECAR_TOKEN), required before anything else over TCP */
+  token: string;
+}
+
+export interface ReplayEventsParams {
+  /** Last sequence number the client has handled */
+  since: number;
+}
+
+export interface ReplayEventsResult {
+  /** Journaled events after `since`, oldest first */
+  events: SessionEventParams[];
+  /** Sequence number of the most recent event */
+  latest_seq: number;
 }
 
 // Watcher types
@@ -124,6 +159,8 @@ export interface WatcherCommentParams {
   timestamp: string;
   /** Full insight data if available */
   insight?: WatcherInsight;
+  /** What the watcher was looking for (bug, security, refactor, ...) */
+  category?: string;
 }
 
 export interface StartWatcherParams {
@@ -135,6 +172,25 @@ export interface StopWatcherParams {
   project_path: string;
 }
 
+export interface TriggerWatcherParams {
+  project_path: string;
+  /** Categories the user muted; the observation picks among the rest */
+  muted_categories?: string[];
+  /** Who the watcher speaks as (default: a supportive coding buddy) */
+  persona?: string;
+  /** Only git log, diff stats and task titles; no file contents */
+  metadata_only?: boolean;
+  /** Tasks in the columns the project has the watcher look at */
+  tasks?: WatchedTask[];
+}
+
+export interface WatchedTask {
+  title: string;
+  column: string;
+  /** Branch whose diff may be read (absent when only metadata may be) */
+  branch?: string;
+}
+
 export interface WatcherObservingParams {
   project_path: string;
   is_observing: boolean;
@@ -193,4 +249,5 @@ export const ErrorCodes = {
   SESSION_NOT_FOUND: -32000,
   SESSION_ALREADY_EXISTS: -32001,
   SDK_ERROR: -32002,
+  UNAUTHORIZED: -32003,
 } as const;
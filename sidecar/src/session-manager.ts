@@ -10,6 +10,8 @@ import {
   type SendPromptParams,
   type SummarizeTitleParams,
   type SummarizeTitleResult,
+  type CompletePromptParams,
+  type CompletePromptResult,
 } from './protocol.js';
 import * as path from 'path';
 
@@ -25,6 +27,8 @@ export type EventCallback = (event: SessionEventParams) => void;
 
 export class SessionManager {
   private sessions: Map<string, Session> = new Map();
+  // Environment each task's sessions run with, kept for prompts sent without one
+  private taskEnv: Map<string, Record<string, string>> = new Map();
   private onEvent: EventCallback;
 
   constructor(onEvent: EventCallback) {
@@ -68,7 +72,8 @@ export class SessionManager {
   }
 
   async startSession(params: StartSessionParams): Promise<string> {
-    const { task_id, worktree_path, prompt, images } = params;
+    const { task_id, worktree_path, prompt, images, env } = params;
+    this.taskEnv.set(task_id, env ?? {});
 
     // If session already exists for this task, send the new prompt to it
     const existing = this.sessions.get(task_id);
@@ -107,7 +112,7 @@ export class SessionManager {
       cwd: worktree_path,
       abortController,
       pathToClaudeCodeExecutable: claudePath,
-      env: { ...process.env, KANBLAM_SDK_SESSION: '1' },  // Tag SDK sessions for hook detection
+      env: { ...process.env, ...env, KANBLAM_SDK_SESSION: '1' },  // Tag SDK sessions for hook detection
     };
 
     // Create a promise that resolves when session ID is captured
@@ -140,6 +145,8 @@ export class SessionManager {
 
   async resumeSession(params: ResumeSessionParams): Promise<string> {
     const { task_id, session_id, worktree_path, prompt } = params;
+    const env = params.env ?? this.taskEnv.get(task_id) ?? {};
+    this.taskEnv.set(task_id, env);
 
     // Remove any existing session for this task
     const existing = this.sessions.get(task_id);
@@ -158,7 +165,7 @@ export class SessionManager {
       cwd: worktree_path,
       abortController,
       pathToClaudeCodeExecutable: claudePath,
-      env: { ...process.env, KANBLAM_SDK_SESSION: '1' },  // Tag SDK sessions for hook detection
+      env: { ...process.env, ...env, KANBLAM_SDK_SESSION: '1' },  // Tag SDK sessions for hook detection
     };
 
     // Start processing with resume
@@ -342,6 +349,48 @@ Task: ${title}`;
     return { short_title: shortTitle, abbreviation, spec };
   }
 
+  /**
+   * Run a single-turn prompt and return the text reply (used for release notes,
+   * summaries and other one-shot generation that doesn't need a task session)
+   */
+  async completePrompt(params: CompletePromptParams): Promise<CompletePromptResult> {
+    const { prompt, cwd } = params;
+
+    const claudePath = process.env.CLAUDE_PATH || (await this.findClaudePath());
+    const abortController = new AbortController();
+
+    const options: Options = {
+      abortController,
+      pathToClaudeCodeExecutable: claudePath,
+      maxTurns: 1,
+      ...(cwd ? { cwd } : {}),
+    };
+
+    let text = '';
+
+    try {
+      const response = query({ prompt, options });
+
+      for await (const message of response) {
+        if (message.type === 'assistant') {
+          const apiMessage = message.message;
+          if (apiMessage && apiMessage.content) {
+            for (const block of apiMessage.content) {
+              if (block.type === 'text' && 'text' in block) {
+                text += (block as { type: 'text'; text: string }).text;
+              }
+            }
+          }
+        }
+      }
+    } finally {
+      abortController.abort();
+    }
+
+    console.log(`[SessionManager] Completed one-shot prompt (${text.length} chars)`);
+    return { text: text.trim() };
+  }
+
   stopSession(taskId: string): void {
     const session = this.sessions.get(taskId);
     if (session) {
@@ -410,6 +459,8 @@ Task: ${title}`;
 
       // Accumulate full output for QA marker detection
       let fullOutput = '';
+      // Last context size reported, so unchanged turns don't flood the TUI
+      let lastContextTokens = 0;
 
       for await (const message of response) {
         // Capture session ID from init message
@@ -442,10 +493,42 @@ Task: ${title}`;
           }
         }
 
+        // The SDK summarized the conversation to free up context
+        if (message.type === 'system' && message.subtype === 'compact_boundary') {
+          const meta = (message as { compact_metadata?: { pre_tokens?: number } }).compact_metadata;
+          this.onEvent({
+            task_id: taskId,
+            event: 'compacted',
+            session_id: sessionId,
+            context_tokens: meta?.pre_tokens,
+          });
+        }
+
         // Handle different message types
         if (message.type === 'assistant') {
           // Claude is responding - content is in message.message.content
           const apiMessage = message.message;
+
+          // Everything sent with this turn (prompt + cache) is what occupies the context window
+          const usage = (apiMessage as { usage?: {
+            input_tokens?: number;
+            cache_read_input_tokens?: number;
+            cache_creation_input_tokens?: number;
+          } } | undefined)?.usage;
+          if (usage) {
+            const contextTokens = (usage.input_tokens ?? 0)
+              + (usage.cache_read_input_tokens ?? 0)
+              + (usage.cache_creation_input_tokens ?? 0);
+            if (contextTokens > 0 && contextTokens !== lastContextTokens) {
+              lastContextTokens = contextTokens;
+              this.onEvent({
+                task_id: taskId,
+                event: 'context',
+                session_id: sessionId,
+                context_tokens: contextTokens,
+              });
+            }
+          }
           if (apiMessage && apiMessage.content) {
             // Extract text from content blocks
             let textContent = '';
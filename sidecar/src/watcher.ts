@@ -62,8 +62,25 @@ export interface WatcherComment {
   insight?: WatcherInsight;
   /** Optional mood/expression for the mascot */
   mood?: 'happy' | 'thinking' | 'concerned' | 'excited' | 'sleepy';
+  /** Focus the observation had */
+  category?: FocusType;
 }
 
+/** What an observation looks at, and as whom (set per project in the TUI) */
+export interface ObservationScope {
+  /** Categories the user muted; the observation picks among the rest */
+  mutedCategories?: string[];
+  /** Who the watcher speaks as */
+  persona?: string;
+  /** Only git log, diff stats and task titles; no file contents */
+  metadataOnly?: boolean;
+  /** Tasks in the watched columns, with the branch to look at when code may be read */
+  tasks?: { title: string; column: string; branch?: string }[];
+}
+
+/** Tools an observation limited to metadata may use */
+const METADATA_TOOLS = ['Bash(git log:*)', 'Bash(git diff --stat:*)', 'Bash(git status:*)'];
+
 export type WatcherEventCallback = (comment: WatcherComment) => void;
 export type WatcherObservingCallback = (isObserving: boolean) => void;
 
@@ -144,16 +161,17 @@ export class WatcherSession {
   }
 
   /**
-   * Force an immediate observation (for testing or manual trigger).
+   * Force an immediate observation (for testing or manual trigger),
+   * focusing on a category the user hasn't muted.
    */
-  async observeNow(): Promise<void> {
-    await this.observe();
+  async observeNow(scope: ObservationScope = {}): Promise<void> {
+    await this.observe(scope);
   }
 
   /**
    * Build the prompt for a specific focus type
    */
-  private buildPrompt(focusType: FocusType): string {
+  private buildPrompt(focusType: FocusType, scope: ObservationScope = {}): string {
     const focusDescriptions: Record<FocusType, string> = {
       bug: 'Find a potential bug or edge case that could cause issues',
       security: 'Identify a security concern or vulnerability',
@@ -163,9 +181,19 @@ export class WatcherSession {
       refactor: 'Suggest an impactful refactoring opportunity',
     };
 
-    return `You are a supportive coding buddy reviewing this project. Your focus: ${focusDescriptions[focusType]}.
+    const persona = scope.persona?.trim() || 'a supportive coding buddy';
+    const exploration = scope.metadataOnly
+      ? 'Quick exploration: Run "git diff --stat HEAD~5" and "git log --oneline -5" to see recent activity. Do not read file contents: judge from commit messages, changed paths and task titles only.'
+      : 'Quick exploration: Run "git diff --stat HEAD~5" and "git log --oneline -5" to see recent activity, then read 1-2 key files.';
+    const tasks = scope.tasks ?? [];
+    const taskList = tasks.length === 0 ? '' : `
+
+Work in flight on the board (you may focus on it instead of main):
+${tasks.map(t => `- [${t.column}] ${t.title}${t.branch ? ` (branch ${t.branch}; "git diff --stat HEAD...${t.branch}" or "git diff HEAD...${t.branch} -- <file>" shows it)` : ''}`).join('\n')}`;
+
+    return `You are ${persona} reviewing this project. Your focus: ${focusDescriptions[focusType]}.
 
-Quick exploration: Run "git diff --stat HEAD~5" and "git log --oneline -5" to see recent activity, then read 1-2 key files.
+${exploration}${taskList}
 
 After exploring, you MUST output your insight in this EXACT XML format (this is required):
 
@@ -211,7 +239,7 @@ CRITICAL RULES:
   /**
    * Perform a single observation of the project.
    */
-  private async observe(): Promise<void> {
+  private async observe(scope: ObservationScope = {}): Promise<void> {
     if (!this.isRunning) return;
 
     // Prevent concurrent observations
@@ -226,11 +254,14 @@ CRITICAL RULES:
     // Notify that we're starting to observe (for UI feedback)
     this.onObserving?.(true);
 
-    // Pick a random focus type
-    const focusType = FOCUS_TYPES[Math.floor(Math.random() * FOCUS_TYPES.length)];
+    // Pick a random focus type among the unmuted ones (all of them if everything is muted)
+    const muted = scope.mutedCategories ?? [];
+    const unmuted = FOCUS_TYPES.filter(focus => !muted.includes(focus));
+    const choices = unmuted.length > 0 ? unmuted : FOCUS_TYPES;
+    const focusType = choices[Math.floor(Math.random() * choices.length)];
     console.log(`[Watcher] Focus: ${focusType}`);
 
-    const prompt = this.buildPrompt(focusType);
+    const prompt = this.buildPrompt(focusType, scope);
 
     // Get claude path - use provided or find it
     const claudePath = this.claudePath || findClaudePath();
@@ -244,7 +275,8 @@ CRITICAL RULES:
       abortController: this.abortController,
       pathToClaudeCodeExecutable: claudePath,
       maxTurns: 10, // Allow plenty of turns for thorough exploration
-      allowedTools: ['Bash', 'Read', 'Glob', 'Grep'],
+      allowedTools: scope.metadataOnly ? METADATA_TOOLS : ['Bash', 'Read', 'Glob', 'Grep'],
+      disallowedTools: scope.metadataOnly ? ['Read', 'Glob', 'Grep'] : [],
     };
 
     console.log(`[Watcher] Starting query with focus: ${focusType}`);
@@ -298,6 +330,7 @@ CRITICAL RULES:
           comment: insight.remark,
           insight,
           mood,
+          category: focusType,
         });
 
         console.log(`[Watcher] Comment: "${insight.remark}" (mood: ${mood})`);
@@ -309,6 +342,7 @@ CRITICAL RULES:
             timestamp: new Date(),
             comment,
             mood: 'happy',
+            category: focusType,
           });
           console.log(`[Watcher] Fallback comment: "${comment}"`);
         }
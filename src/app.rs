@@ -1,18 +1,26 @@
 #![allow(dead_code)]
 
+use crate::capabilities::Capability;
 use crate::message::Message;
 use crate::model::{AppModel, FocusArea, MainWorktreeOperation, PendingAction, PendingConfirmation, Project, Task, TaskStatus};
 use crate::notify;
 use crate::sidecar::SidecarClient;
+use crate::timing::TimedCommand;
 use crate::ui::logo::EyeAnimation;
+use crate::worktree::fswatch::GitStatusWatcher;
 use anyhow::Result;
 use chrono::Utc;
-use std::path::PathBuf;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use tokio::sync::mpsc;
 
 /// Channel sender for async task results
 pub type AsyncTaskSender = mpsc::UnboundedSender<Message>;
 
+/// Sent to SDK sessions that were working when the sidecar went down
+const SIDECAR_RESUME_PROMPT: &str = "Your session was interrupted because the process running it restarted. \
+Continue the task from where you left off.";
+
 /// Check if a project is the "bootstrap" project (i.e., we're developing KanBlam itself).
 /// Returns true if the currently running executable lives within the project's directory.
 fn is_bootstrap_project(project: &Project) -> bool {
@@ -44,6 +52,16 @@ pub struct App {
     pub async_sender: Option<AsyncTaskSender>,
     /// Custom state file path (if specified via --state-file)
     pub state_file_path: Option<PathBuf>,
+    /// Filesystem watchers driving incremental git status refresh, per project dir
+    git_watchers: HashMap<PathBuf, GitStatusWatcher>,
+    /// Something visible changed since the last draw
+    pub needs_redraw: bool,
+    /// Started with --safe-mode: no sidecar, hooks, tmux scanning or background git
+    pub safe_mode: bool,
+    /// A new sidecar instance is up: the main loop reconnects its event receiver
+    pub sidecar_resubscribe: bool,
+    /// Lua board automation rules (None in safe mode and tests)
+    pub rules: Option<crate::rules::RuleEngine>,
 }
 
 impl App {
@@ -55,9 +73,73 @@ impl App {
             sidecar_client: None,
             async_sender: None,
             state_file_path: None,
+            git_watchers: HashMap::new(),
+            needs_redraw: true,
+            safe_mode: false,
+            sidecar_resubscribe: false,
+            rules: None,
+        }
+    }
+
+    /// The file the new-task input is saved to between runs
+    fn draft_path(&self) -> PathBuf {
+        let state_file = self.state_file_path.clone().unwrap_or_else(default_state_file_path);
+        crate::model::draft::draft_path(&state_file)
+    }
+
+    /// Write the new-task input to the draft file if it changed since it was
+    /// last written (text typed for an edit, feedback or note isn't kept)
+    pub fn save_draft(&mut self) {
+        let ui = &self.model.ui_state;
+        if ui.editing_task_id.is_some() || ui.feedback_task_id.is_some() || ui.note_task_id.is_some()
+            || ui.broadcast_feedback || ui.input_history_cursor.is_some()
+        {
+            return;
+        }
+        let text = ui.get_input_text();
+        if text == ui.saved_draft {
+            return;
+        }
+        if crate::model::draft::save(&self.draft_path(), &text).is_ok() {
+            self.model.ui_state.saved_draft = text;
         }
     }
 
+    /// Put the draft left by the last run back into the input
+    pub fn restore_draft(&mut self) {
+        let Some(text) = crate::model::draft::load(&self.draft_path()) else {
+            return;
+        };
+        self.model.ui_state.set_input_text(&text);
+        self.model.ui_state.saved_draft = text;
+        self.model.ui_state.status_message = Some("Restored your unsent task draft".to_string());
+    }
+
+    /// Slash command completions for the word at the cursor of the new-task input
+    pub fn slash_completions(&self) -> Vec<crate::slash::Completion> {
+        let ui = &self.model.ui_state;
+        if ui.focus != FocusArea::TaskInput
+            || ui.editing_task_id.is_some() || ui.feedback_task_id.is_some() || ui.note_task_id.is_some()
+            || ui.broadcast_feedback
+            || (ui.vim_mode_enabled && ui.editor_state.mode != edtui::EditorMode::Insert)
+        {
+            return Vec::new();
+        }
+        let before = ui.input_before_cursor();
+        let Some(project) = self.model.active_project().filter(|_| before.contains('/')) else {
+            return Vec::new();
+        };
+        let mut tags: Vec<String> = project.tasks.iter().flat_map(|t| t.labels()).collect();
+        tags.sort();
+        tags.dedup();
+        let templates = if before.contains("/template") {
+            crate::slash::template_names(&crate::slash::template_dirs(&project.scope_dir()))
+        } else {
+            Vec::new()
+        };
+        crate::slash::completions(&before, &tags, &templates)
+    }
+
     /// Look up a task's display_id by its UUID, searching all projects.
     /// Returns the display_id string (e.g., "ABBR-xyz") or falls back to UUID prefix.
     fn get_task_display_id(&self, task_id: uuid::Uuid) -> String {
@@ -70,6 +152,273 @@ impl App {
         format!("{}-???", &task_id.to_string()[..4])
     }
 
+    /// Add time from a focus block to the task it was for
+    fn log_focus_time(&mut self, task_id: uuid::Uuid, secs: i64) {
+        if secs < 60 {
+            return;
+        }
+        let task = self.model.projects.iter_mut()
+            .flat_map(|p| p.tasks.iter_mut())
+            .find(|t| t.id == task_id);
+        if let Some(task) = task {
+            task.time_entries.push(crate::model::time_tracking::TimeEntry::ending_now(
+                secs,
+                crate::model::time_tracking::TimeSource::Focus,
+                None,
+            ));
+            task.log_activity(format!("Focused for {} min", secs / 60));
+        }
+    }
+
+    /// Configured worktree pool size for the project at `project_dir` (0 if none)
+    fn worktree_pool_size(&self, project_dir: &PathBuf) -> usize {
+        self.model.projects.iter()
+            .find(|p| &p.working_dir == project_dir)
+            .map(|p| p.worktree_pool_size)
+            .unwrap_or(0)
+    }
+
+    /// Why the active project can't touch its repository's main worktree because
+    /// another project scoped to the same repository is applying, accepting or
+    /// has changes applied there
+    fn shared_main_worktree_busy(&self) -> Option<String> {
+        let active = self.model.active_project()?;
+        self.model.projects.iter().enumerate()
+            .filter(|(idx, p)| *idx != self.model.active_project_idx && p.working_dir == active.working_dir)
+            .find_map(|(_, p)| {
+                let task_id = p.main_worktree_lock.as_ref().map(|lock| lock.task_id).or(p.applied_task_id)?;
+                Some(format!(
+                    "'{}' shares this repository and is using its main worktree ({}) - try again when it's done",
+                    p.name,
+                    p.format_task_ref(task_id)
+                ))
+            })
+    }
+
+    /// Start tasks held back by a focus block, once starts are allowed again.
+    /// Only tasks in the active project are started; the rest wait until it's switched to.
+    fn release_deferred_starts(&mut self) -> Vec<Message> {
+        let deferring = self.model.ui_state.focus_timer.as_ref()
+            .is_some_and(|t| t.defers_starts(&self.model.global_settings));
+        if deferring || self.model.ui_state.deferred_starts.is_empty() {
+            return Vec::new();
+        }
+
+        let projects = &self.model.projects;
+        // Drop tasks that were deleted or started some other way meanwhile
+        self.model.ui_state.deferred_starts.retain(|id| {
+            projects.iter().flat_map(|p| &p.tasks).any(|t| t.id == *id && t.status == TaskStatus::Planned)
+        });
+
+        let Some(project) = self.model.active_project() else {
+            return Vec::new();
+        };
+        let (ready, waiting): (Vec<uuid::Uuid>, Vec<uuid::Uuid>) = self.model.ui_state.deferred_starts.iter()
+            .copied()
+            .partition(|id| project.tasks.iter().any(|t| t.id == *id));
+        self.model.ui_state.deferred_starts = waiting;
+        ready.into_iter().map(Message::StartTaskWithWorktree).collect()
+    }
+
+    /// What dropping a card from one column onto another does: the same action (and
+    /// confirmation) as the keyboard shortcut for that move, or a hint when there is none.
+    fn card_drop_messages(&self, task_id: uuid::Uuid, from: TaskStatus, to: TaskStatus) -> Vec<Message> {
+        let Some(project) = self.model.active_project() else {
+            return Vec::new();
+        };
+        let Some(task) = project.tasks.iter().find(|t| t.id == task_id) else {
+            return Vec::new();
+        };
+        // Merges and rebases in flight can't be interrupted by a drop
+        if matches!(task.status, TaskStatus::Accepting | TaskStatus::Updating | TaskStatus::Applying) {
+            return vec![Message::SetStatusMessage(Some(
+                format!("'{}' is busy ({})", task.title, task.status.label())
+            ))];
+        }
+
+        match (from, to) {
+            (TaskStatus::Planned, TaskStatus::InProgress) => {
+                if project.is_git_repo() {
+                    vec![Message::StartTaskWithWorktree(task_id)]
+                } else {
+                    vec![Message::StartTask(task_id)]
+                }
+            }
+            (TaskStatus::InProgress | TaskStatus::NeedsWork | TaskStatus::Testing | TaskStatus::Done, TaskStatus::Review)
+            | (TaskStatus::Review, TaskStatus::NeedsWork) => {
+                vec![Message::MoveTask { task_id, to_status: to }]
+            }
+            (TaskStatus::Review, TaskStatus::Done) => {
+                if project.applied_task_id == Some(task_id) {
+                    vec![Message::ShowConfirmation {
+                        message: "Commit applied changes and mark done? (y/n)".to_string(),
+                        action: PendingAction::CommitAppliedChanges(task_id),
+                    }]
+                } else {
+                    vec![Message::ShowConfirmation {
+                        message: "Merge all changes and mark done? (y/n)".to_string(),
+                        action: PendingAction::AcceptTask(task_id),
+                    }]
+                }
+            }
+            (_, TaskStatus::Planned) => {
+                let title = task.short_title.as_ref().unwrap_or(&task.title);
+                let title = if title.len() > 30 {
+                    format!("{}...", &title[..27])
+                } else {
+                    title.clone()
+                };
+                vec![Message::ShowConfirmation {
+                    message: format!("Reset '{}'? This will clean up worktree and move to Planned. (y/n)", title),
+                    action: PendingAction::ResetTask(task_id),
+                }]
+            }
+            _ => vec![Message::SetStatusMessage(Some(
+                format!("Can't move a task from {} to {}", from.label(), to.label())
+            ))],
+        }
+    }
+
+    /// Scroll the column under a dragged card while the pointer rests near its top or bottom edge
+    fn auto_scroll_drag(&mut self) {
+        let Some(drag) = self.model.ui_state.card_drag.as_ref() else {
+            return;
+        };
+        if let (Some(column), step @ (-1 | 1)) = (drag.over, drag.scroll) {
+            let height = drag.over_height;
+            self.scroll_column(column, isize::from(step), height);
+        }
+    }
+
+    /// Scroll a column's task list by `delta` rows, given how many rows it shows.
+    /// Columns are drawn with their remembered row (the selection, for the selected
+    /// column) kept in view at the bottom, so scrolling moves that row along with the
+    /// first visible one.
+    fn scroll_column(&mut self, column: TaskStatus, delta: isize, visible_rows: u16) {
+        let height = usize::from(visible_rows.max(1));
+        let len = self.model.active_project()
+            .map(|p| p.tasks_by_status(column).len())
+            .unwrap_or(0);
+        if delta == 0 || len <= height {
+            return;
+        }
+
+        let ui = &mut self.model.ui_state;
+        let row = if column == ui.selected_column {
+            ui.selected_task_idx.get_or_insert(0)
+        } else {
+            &mut ui.column_scroll_offsets[column.index()]
+        };
+        let top = row.saturating_sub(height - 1)
+            .saturating_add_signed(delta)
+            .min(len - height);
+        *row = top + height - 1;
+    }
+
+    /// Mark whatever is on screen as read: the detail modal's Activity/Git tab and the
+    /// watcher insight modal. Called every tick so content arriving while open counts too.
+    fn mark_viewed_content(&mut self) {
+        use crate::model::TaskDetailTab;
+
+        let ui = &mut self.model.ui_state;
+        let Some(project) = self.model.projects.get_mut(self.model.active_project_idx) else {
+            return;
+        };
+
+        if ui.show_watcher_insight_modal {
+            if let Some(ref mut comment) = project.watcher_comment {
+                comment.insight_seen = true;
+            }
+        }
+
+        let viewed_task = ui.selected_task_id.filter(|_| ui.show_task_preview);
+        let Some(task) = viewed_task.and_then(|id| project.tasks.iter_mut().find(|t| t.id == id)) else {
+            ui.read_marker = None;
+            return;
+        };
+        if ui.read_marker.as_ref().is_none_or(|(id, _)| *id != task.id) {
+            ui.read_marker = Some((task.id, task.read_state.clone()));
+        }
+
+        match ui.task_detail_tab {
+            TaskDetailTab::Activity => {
+                if let Some(last) = task.activity_log.last() {
+                    task.read_state.activity_seen_at = Some(last.timestamp);
+                }
+            }
+            TaskDetailTab::Git if ui.git_diff_cache.current(task.id).is_some() => {
+                task.read_state.diff_seen_at = Some(Utc::now());
+            }
+            _ => {}
+        }
+    }
+
+    /// Register the environment variables of tasks with a worktree, for the
+    /// sidecar and tmux panes started for those worktrees
+    fn sync_task_env(&self) {
+        let envs = self.model.projects.iter()
+            .flat_map(|p| p.tasks.iter())
+            .filter(|t| !t.env.is_empty() || t.ports.is_some())
+            .filter_map(|t| {
+                // The task's own variables win over its ports
+                let mut env = t.ports.map(crate::ports::env_vars).unwrap_or_default();
+                env.extend(t.env.clone());
+                Some((t.worktree_path.clone()?, env))
+            })
+            .collect();
+        crate::secrets::set_task_env(envs);
+    }
+
+    /// Give tasks that have a worktree a block of ports and take it back from
+    /// tasks that are done or lost their worktree
+    fn sync_task_ports(&mut self) {
+        let mut taken = Vec::new();
+        for task in self.model.projects.iter_mut().flat_map(|p| p.tasks.iter_mut()) {
+            // Also drop a block another task got while this one's project wasn't loaded
+            if task.worktree_path.is_none() || task.status == TaskStatus::Done
+                || task.ports.is_some_and(|first| taken.contains(&first))
+            {
+                task.ports = None;
+            }
+            taken.extend(task.ports);
+        }
+        for task in self.model.projects.iter_mut().flat_map(|p| p.tasks.iter_mut()) {
+            if task.ports.is_none() && task.worktree_path.is_some() && task.status != TaskStatus::Done {
+                task.ports = crate::ports::allocate(&taken, crate::ports::is_free);
+                taken.extend(task.ports);
+            }
+        }
+    }
+
+    /// Start/stop git status watchers so they match open projects and their worktrees
+    pub fn sync_git_watchers(&mut self) {
+        let Some(sender) = self.async_sender.clone() else {
+            return;
+        };
+        self.git_watchers.retain(|dir, _| self.model.projects.iter().any(|p| &p.working_dir == dir));
+
+        for project in &self.model.projects {
+            let project_dir = project.working_dir.clone();
+            if !self.git_watchers.contains_key(&project_dir) {
+                let sender = sender.clone();
+                let dir = project_dir.clone();
+                let watcher = GitStatusWatcher::new(&project_dir, move |display_ids| {
+                    let _ = sender.send(Message::GitStatusChanged { project_dir: dir.clone(), display_ids });
+                });
+                // No watcher (e.g. not a plain .git dir) - the timer refresh covers it
+                let Ok(watcher) = watcher else { continue };
+                self.git_watchers.insert(project_dir.clone(), watcher);
+            }
+
+            let worktrees: Vec<(String, PathBuf)> = project.tasks.iter()
+                .filter_map(|t| t.worktree_path.clone().map(|wt| (t.display_id(), wt)))
+                .collect();
+            if let Some(watcher) = self.git_watchers.get_mut(&project_dir) {
+                watcher.sync(&worktrees);
+            }
+        }
+    }
+
     pub fn with_model(model: AppModel) -> Self {
         Self {
             model,
@@ -78,6 +427,11 @@ impl App {
             sidecar_client: None,
             async_sender: None,
             state_file_path: None,
+            git_watchers: HashMap::new(),
+            needs_redraw: true,
+            safe_mode: false,
+            sidecar_resubscribe: false,
+            rules: None,
         }
     }
 
@@ -87,6 +441,10 @@ impl App {
     }
 
     pub fn with_sidecar(mut self, client: Option<SidecarClient>) -> Self {
+        // Without a connection the supervisor starts trying right away
+        if client.is_none() {
+            self.model.ui_state.sidecar_health.mark_down();
+        }
         self.sidecar_client = client;
         self
     }
@@ -96,6 +454,11 @@ impl App {
         self
     }
 
+    /// Lines of output the live output pane currently has to show
+    fn output_pane_line_count(&self) -> usize {
+        self.model.output_pane_task().map_or(0, |t| t.live_output_text().lines().count())
+    }
+
     /// Sync selected_task_idx based on selected_task_id
     /// Call this after any operation that might change task order/status
     /// If the selected task moved to a different column, follows it there
@@ -164,6 +527,310 @@ impl App {
         }
     }
 
+    /// Session events the sidecar journaled after the last one handled here,
+    /// to replay whenever the event receiver (re)connects - events broadcast
+    /// while it was disconnected never arrived
+    pub fn missed_sidecar_events(&mut self) -> Vec<Message> {
+        let Some(ref client) = self.sidecar_client else {
+            return Vec::new();
+        };
+        let since = self.model.last_sidecar_seq;
+        let Ok((events, latest_seq)) = client.replay_events(since) else {
+            return Vec::new();
+        };
+        if since == 0 {
+            // Nothing handled yet: start at the journal's current position
+            // rather than replaying another run's history
+            self.model.last_sidecar_seq = latest_seq;
+            return Vec::new();
+        }
+        if latest_seq < since {
+            // The journal was reset, so everything in it is new
+            self.model.last_sidecar_seq = 0;
+        }
+        events.into_iter().map(Message::SidecarEvent).collect()
+    }
+
+    /// Banner for the first of `needed` that's unavailable, shown instead of
+    /// silently doing nothing
+    pub fn capability_banner(&self, needed: &[Capability]) -> Option<String> {
+        self.model.ui_state.capabilities.banner(needed, self.sidecar_client.is_some())
+    }
+
+    /// Diffs to load for the quick diff popup: the task's own (a cache hit
+    /// unless its branch moved) and the next task's in the column, so
+    /// stepping through a column shows each diff straight away
+    fn quick_diff_loads(&self, task_id: uuid::Uuid) -> Vec<Message> {
+        let mut loads = vec![Message::LoadGitDiff(task_id)];
+        let next = self.model.active_project().and_then(|p| {
+            let tasks = p.tasks_by_status(self.model.ui_state.selected_column);
+            let idx = tasks.iter().position(|t| t.id == task_id)?;
+            tasks.get(idx + 1).filter(|t| t.git_branch.is_some()).map(|t| t.id)
+        });
+        if let Some(next) = next {
+            loads.push(Message::LoadGitDiff(next));
+        }
+        loads
+    }
+
+    /// Connect to a new sidecar instance: a fresh client, the event receiver
+    /// re-subscribed by the main loop, and the SDK sessions that were running
+    /// resumed in the background (the old instance took them down with it)
+    fn reconnect_sidecar(&mut self) -> Vec<Message> {
+        self.sidecar_client = SidecarClient::connect().ok();
+        self.sidecar_resubscribe = true;
+
+        let mut commands = Vec::new();
+        // The watcher lives in the sidecar too
+        if self.model.global_settings.mascot_advice_enabled == Some(true) {
+            commands.push(Message::StartWatcher);
+        }
+
+        let sessions: Vec<_> = self.model.projects.iter()
+            .flat_map(|p| p.tasks.iter())
+            .filter(|t| matches!(t.status, TaskStatus::InProgress | TaskStatus::Testing))
+            .filter(|t| t.session_mode == crate::model::SessionMode::SdkManaged)
+            .filter_map(|t| {
+                let was_working = matches!(t.session_state,
+                    crate::model::ClaudeSessionState::Working | crate::model::ClaudeSessionState::Continuing);
+                Some((t.id, t.claude_session_id.clone()?, t.worktree_path.clone()?, was_working))
+            })
+            .collect();
+        let Some(sender) = self.async_sender.clone() else {
+            return commands;
+        };
+        if sessions.is_empty() {
+            return commands;
+        }
+        tokio::spawn(async move {
+            let results = tokio::task::spawn_blocking(move || {
+                let client = match SidecarClient::connect() {
+                    Ok(client) => client,
+                    Err(e) => {
+                        return sessions.into_iter().map(|(task_id, ..)| (task_id, Err(e.to_string()))).collect();
+                    }
+                };
+                sessions.into_iter()
+                    // Sessions the sidecar still knows about (only the connection dropped) stay as they are
+                    .filter(|(task_id, ..)| !client.get_session(*task_id).is_ok_and(|s| s.is_some()))
+                    .map(|(task_id, session_id, worktree_path, was_working)| {
+                        let prompt = was_working.then_some(SIDECAR_RESUME_PROMPT);
+                        let result = client.resume_session(task_id, &session_id, &worktree_path, prompt)
+                            .map_err(|e| e.to_string());
+                        (task_id, result)
+                    })
+                    .collect()
+            }).await.unwrap_or_default();
+            let _ = sender.send(Message::SidecarSessionsReattached(results));
+        });
+        commands
+    }
+
+    /// Record a polled CI status for a task's pushed branch: log changes and
+    /// tell the user when a run finishes. For a task waiting to auto-merge,
+    /// merge once it's green and stop waiting once it can't be read
+    fn apply_ci_status(&mut self, task_id: uuid::Uuid, result: Result<crate::ci::CiStatus, String>) -> Vec<Message> {
+        use crate::ci::CiState;
+
+        let mut commands = Vec::new();
+        let active_project_id = self.model.active_project().map(|p| p.id);
+        let Some((project_id, project_dir, task)) = self.model.projects.iter_mut()
+            .find_map(|p| {
+                let (project_id, project_dir) = (p.id, p.working_dir.clone());
+                p.tasks.iter_mut().find(|t| t.id == task_id).map(|t| (project_id, project_dir, t))
+            })
+        else {
+            return commands;
+        };
+        let waiting_to_merge = task.auto_merge && task.status == TaskStatus::Review;
+
+        let mut status = match result {
+            Ok(status) => status,
+            Err(e) if waiting_to_merge => {
+                task.auto_merge = false;
+                task.log_activity(format!("Auto-merge off: couldn't read CI ({})", e));
+                commands.push(Message::Error(format!("Auto-merge off for {}: {}", task.display_id(), e)));
+                return commands;
+            }
+            // Only shown: keep what was last read
+            Err(_) => return commands,
+        };
+        let last = task.ci_status.take();
+        // State of the same push at the last poll (None after a new push)
+        let previous = last.as_ref().filter(|s| s.sha == status.sha).map(|s| s.state);
+        if let Some(last) = last.as_ref().filter(|_| previous.is_some()) {
+            status.polls = last.polls + 1;
+        }
+        // Without a PR the merge is local, so it may only take the commit CI checked:
+        // newer local work waits (as running) until a push of it is checked too
+        let unchecked = (waiting_to_merge && status.state == CiState::Passed && status.pr.is_none())
+            .then(|| {
+                let branch = task.git_branch.clone().unwrap_or_else(|| format!("claude/{}", task.display_id()));
+                crate::ci::local_divergence(&project_dir, &branch, task.worktree_path.as_deref(), &status.sha)
+            })
+            .flatten();
+        if unchecked.is_some() {
+            status.state = CiState::Pending;
+        }
+        task.ci_status = Some(status.clone());
+
+        if previous != Some(status.state) {
+            let failed = status.failed();
+            if let Some(reason) = unchecked {
+                task.log_activity(format!("CI passed, but {} - waiting for CI on a push of it", reason));
+                commands.push(Message::SetStatusMessage(Some(format!(
+                    "CI passed for {}, but {} - push it to auto-merge", task.display_id(), reason
+                ))));
+            } else if failed.is_empty() {
+                task.log_activity(format!("CI {}", status.state.label()));
+            } else {
+                task.log_activity(format!("CI failed: {}", failed.join(", ")));
+            }
+        }
+
+        if !waiting_to_merge {
+            // A run finished since the last poll (not one found finished at startup)
+            let was_running = previous.is_none_or(|s| matches!(s, CiState::Queued | CiState::Pending));
+            if last.is_some() && was_running {
+                match status.state {
+                    CiState::Passed => {
+                        notify::play_task_cue(task.id, notify::SoundCue::Review);
+                        commands.push(Message::SetStatusMessage(Some(
+                            format!("CI passed for {}", task.display_id())
+                        )));
+                    }
+                    CiState::Failed => {
+                        notify::play_task_cue(task.id, notify::SoundCue::MergeFailed);
+                        commands.push(Message::Error(format!(
+                            "CI failed for {} ({})", task.display_id(), status.failed().join(", ")
+                        )));
+                    }
+                    _ => {}
+                }
+            }
+            return commands;
+        }
+
+        match status.state {
+            CiState::Passed => match status.pr {
+                Some(pr) if pr.state == "MERGED" => {
+                    commands.extend(self.finish_remote_merge(task_id, pr.number));
+                }
+                Some(pr) if pr.state == "OPEN" => {
+                    task.log_activity(format!("CI passed, merging PR #{}", pr.number));
+                    let Some(sender) = self.async_sender.clone() else {
+                        return commands;
+                    };
+                    let Some(project_dir) = self.model.projects.iter()
+                        .find(|p| p.id == project_id)
+                        .map(|p| p.working_dir.clone())
+                    else {
+                        return commands;
+                    };
+                    commands.push(Message::SetStatusMessage(Some(format!("CI passed - merging PR #{}...", pr.number))));
+                    tokio::spawn(async move {
+                        let result = tokio::task::spawn_blocking(move || {
+                            crate::ci::merge_pr(&project_dir, pr.number).map_err(|e| e.to_string())
+                        }).await.unwrap_or_else(|e| Err(format!("Task panicked: {}", e)));
+                        let _ = sender.send(Message::AutoMergePrMerged { task_id, pr: pr.number, result });
+                    });
+                }
+                Some(pr) => {
+                    task.auto_merge = false;
+                    task.log_activity(format!("Auto-merge off: PR #{} is {}", pr.number, pr.state.to_lowercase()));
+                    commands.push(Message::SetStatusMessage(Some(
+                        format!("Auto-merge off: PR #{} is {}", pr.number, pr.state.to_lowercase())
+                    )));
+                }
+                None if active_project_id == Some(project_id) => {
+                    // No PR: merge locally the same way as accepting by hand
+                    task.auto_merge = false;
+                    task.log_activity("CI passed, merging locally");
+                    commands.push(Message::ComposeMergeCommit { task_id, merge_only: false, interactive: false });
+                }
+                None => {
+                    // Local merges work on the active project's main worktree
+                    if previous != Some(CiState::Passed) {
+                        commands.push(Message::SetStatusMessage(Some(format!(
+                            "CI passed for {} - switch to its project to merge it", task.display_id()
+                        ))));
+                    }
+                }
+            },
+            CiState::Failed if previous != Some(CiState::Failed) => {
+                notify::play_task_cue(task.id, notify::SoundCue::MergeFailed);
+                commands.push(Message::Error(format!(
+                    "CI failed for {} ({}) - still waiting to auto-merge", task.display_id(), status.failed().join(", ")
+                )));
+            }
+            _ => {}
+        }
+        commands
+    }
+
+    /// Clean up after a task's pull request was merged on the remote: close
+    /// its windows and sessions, release the worktree, delete the local branch
+    /// and complete the task. Local work the PR didn't have is kept instead.
+    fn finish_remote_merge(&mut self, task_id: uuid::Uuid, pr: u64) -> Vec<Message> {
+        let mut commands = Vec::new();
+        let Some(project) = self.model.projects.iter_mut()
+            .find(|p| p.tasks.iter().any(|t| t.id == task_id))
+        else {
+            return commands;
+        };
+        let project_dir = project.working_dir.clone();
+        let project_slug = project.slug();
+        let pool_size = project.worktree_pool_size;
+        let Some(task) = project.tasks.iter_mut().find(|t| t.id == task_id) else {
+            return commands;
+        };
+        let display_id = task.display_id();
+        task.auto_merge = false;
+        task.log_activity(format!("Merged PR #{} after CI passed", pr));
+
+        // Cleanup force-checks-out the worktree and deletes the branch: only when
+        // they hold exactly what the PR merged
+        let branch = task.git_branch.clone().unwrap_or_else(|| format!("claude/{}", display_id));
+        let pr_head = task.ci_status.as_ref()
+            .and_then(|s| s.pr.as_ref())
+            .map(|pr| pr.head_sha.clone())
+            .unwrap_or_default();
+        if let Some(reason) = crate::ci::local_divergence(&project_dir, &branch, task.worktree_path.as_deref(), &pr_head) {
+            task.log_activity(format!("Kept the worktree and branch: {}", reason));
+            commands.push(Message::Error(format!(
+                "Merged PR #{} for {}, but {} - kept its worktree and branch; merge or discard them by hand",
+                pr, display_id, reason
+            )));
+            return commands;
+        }
+
+        if let Some(ref window) = task.tmux_window {
+            let _ = crate::tmux::kill_task_window(&project_slug, window);
+        }
+        crate::tmux::kill_task_sessions(&display_id);
+        if let Some(ref wt_path) = task.worktree_path {
+            if let Err(e) = crate::worktree::release_worktree(&project_dir, wt_path, pool_size) {
+                commands.push(Message::SetStatusMessage(Some(
+                    format!("Warning: Could not remove worktree: {}", e)
+                )));
+            }
+            let _ = crate::worktree::remove_worktree_trust(wt_path);
+        }
+        let _ = crate::worktree::delete_branch(&project_dir, &display_id);
+
+        project.mark_task_merged(task_id);
+        project.complete_task(task_id);
+        project.needs_attention = project.review_count() > 0;
+        if !project.needs_attention {
+            notify::clear_attention_indicator();
+        }
+        notify::play_attention_sound();
+        commands.push(Message::SetStatusMessage(Some(
+            format!("Merged PR #{} for {} - pull main to get it", pr, display_id)
+        )));
+        commands
+    }
+
     /// Build the QA validation prompt for a task
     fn build_qa_prompt(description: &str, spec: Option<&str>) -> String {
         let mut prompt = String::from(
@@ -225,19 +892,521 @@ Do not ask for permission - run tests and fix any issues you find."#);
 
     /// Update application state based on message (TEA pattern)
     pub fn update(&mut self, msg: Message) -> Vec<Message> {
+        // Any real message may change the screen; a tick only if it moved something visible
+        let tick_before = matches!(msg, Message::Tick).then(|| self.tick_fingerprint());
+        let active_before = self.model.active_project().map(|p| p.id);
+        let selection_before = self.selection_anchor();
+        let mut commands = self.handle_message(msg);
+        self.follow_sorted_selection(selection_before);
+        self.needs_redraw |= tick_before.is_none_or(|before| before != self.tick_fingerprint());
+        self.track_applied_head();
+        commands.extend(self.record_status_changes());
+        self.sync_task_ports();
+        self.sync_task_env();
+
+        // Projects are read from their own state file when first activated
+        if self.model.active_project().map(|p| p.id) != active_before {
+            if let Err(e) = self.load_project(self.model.active_project_idx) {
+                commands.push(Message::SetStatusMessage(Some(format!("Couldn't load project: {:#}", e))));
+            }
+            // Offered again in the open-project dialog
+            if let Some(dir) = self.model.active_project().map(Project::location) {
+                crate::model::recent::record(&mut self.model.global_settings.recent_projects, &dir, Utc::now());
+            }
+        }
+        commands
+    }
+
+    /// Show a merge in the status bar as the running git operation, with the
+    /// token X cancels it through, on the project owning `task_id`. A background
+    /// fetch makes way for it; a pull, push or other merge in progress is an error.
+    fn begin_git_merge(&mut self, task_id: uuid::Uuid) -> Result<crate::worktree::CancelToken, String> {
+        use crate::model::GitOperation;
+        let project = self.model.projects.iter_mut()
+            .find(|p| p.tasks.iter().any(|t| t.id == task_id))
+            .ok_or("Task not found")?;
+        match project.git_operation_in_progress {
+            Some(GitOperation::Fetching) => {
+                if let Some(cancel) = project.git_cancel.take() {
+                    cancel.cancel();
+                }
+            }
+            Some(_) => return Err("Another git operation is running - try again when it finishes".to_string()),
+            None => {}
+        }
+        let cancel = crate::worktree::CancelToken::default();
+        project.git_operation_in_progress = Some(GitOperation::Merging);
+        project.git_progress = None;
+        project.git_cancel = Some(cancel.clone());
+        Ok(cancel)
+    }
+
+    /// Merge a task's branch into main off the UI thread, then release its
+    /// worktree and delete its branch; `TaskBranchMerged` reports back
+    fn spawn_task_merge(
+        &mut self,
+        task_id: uuid::Uuid,
+        accepting: bool,
+        project_dir: PathBuf,
+        display_id: String,
+        worktree_path: Option<PathBuf>,
+        merge_message: Option<String>,
+    ) -> Vec<Message> {
+        let cancel = match self.begin_git_merge(task_id) {
+            Ok(cancel) => cancel,
+            Err(error) => {
+                // Nothing started, so the operation already running keeps the status bar
+                if accepting {
+                    if let Some(project) = self.model.projects.iter_mut().find(|p| p.tasks.iter().any(|t| t.id == task_id)) {
+                        if let Some(task) = project.tasks.iter_mut().find(|t| t.id == task_id) {
+                            task.move_to_review();
+                        }
+                        project.release_main_worktree_lock(task_id);
+                    }
+                }
+                return vec![Message::Error(error)];
+            }
+        };
+        let Some(sender) = self.async_sender.clone() else {
+            return vec![Message::TaskBranchMerged {
+                task_id,
+                accepting,
+                result: Err("Internal error: async_sender not configured.".to_string()),
+            }];
+        };
+        let pool_size = self.worktree_pool_size(&project_dir);
+        tokio::spawn(async move {
+            let progress_sender = sender.clone();
+            let result = tokio::task::spawn_blocking(move || -> Result<Vec<String>, String> {
+                crate::worktree::merge_branch(&project_dir, &display_id, merge_message.as_deref(), &cancel, &mut |progress| {
+                    let _ = progress_sender.send(Message::GitOperationProgress(progress));
+                }).map_err(|e| e.to_string())?;
+
+                let mut warnings = Vec::new();
+                if let Some(ref wt_path) = worktree_path {
+                    if let Err(e) = crate::worktree::release_worktree(&project_dir, wt_path, pool_size) {
+                        warnings.push(format!("Warning: Could not remove worktree: {}", e));
+                    }
+                    // Clean up trust entry from Claude's config
+                    let _ = crate::worktree::remove_worktree_trust(wt_path);
+                }
+                if let Err(e) = crate::worktree::delete_branch(&project_dir, &display_id) {
+                    warnings.push(format!("Warning: Could not delete branch: {}", e));
+                }
+                Ok(warnings)
+            }).await;
+
+            let result = result.unwrap_or_else(|e| Err(format!("Task panicked: {}", e)));
+            let _ = sender.send(Message::TaskBranchMerged { task_id, accepting, result });
+        });
+        Vec::new()
+    }
+
+    /// Why `+/-` can't reorder: the columns are sorted by something other than the manual order
+    fn sorted_column_hint(&self) -> Option<String> {
+        let sort = self.model.active_project()?.column_sort;
+        (sort != crate::model::ColumnSort::Manual).then(|| format!(
+            "Columns are sorted by {} - set Column Sort to Manual in settings to reorder with +/-",
+            sort.name().to_lowercase()
+        ))
+    }
+
+    /// The selected column, row and task, and whether the column was sorted or grouped
+    fn selection_anchor(&self) -> Option<(TaskStatus, usize, uuid::Uuid, bool)> {
+        let project = self.model.active_project()?;
+        let column = self.model.ui_state.selected_column;
+        let idx = self.model.ui_state.selected_task_idx?;
+        let id = project.tasks_by_status(column).get(idx)?.id;
+        Some((column, idx, id, project.is_sorted()))
+    }
+
+    /// Sorted and grouped columns reorder themselves as tasks change (new
+    /// activity, new labels, a new sort); keep the selection on the task it
+    /// was on, unless the message itself moved the selection
+    fn follow_sorted_selection(&mut self, anchor: Option<(TaskStatus, usize, uuid::Uuid, bool)>) {
+        let Some((column, idx, id, was_sorted)) = anchor else {
+            return;
+        };
+        let ui = &self.model.ui_state;
+        if ui.selected_column != column || ui.selected_task_idx != Some(idx) {
+            return;
+        }
+        let new_idx = self.model.active_project()
+            .filter(|p| was_sorted || p.is_sorted())
+            .and_then(|p| p.tasks_by_status(column).iter().position(|t| t.id == id));
+        if let Some(new_idx) = new_idx {
+            self.model.ui_state.selected_task_idx = Some(new_idx);
+        }
+    }
+
+    /// Log tasks moving between columns in their activity logs, for the session replay,
+    /// fire the webhooks and plugins of the lifecycle events among the moves and run
+    /// the board rules. Returns what the rules asked for.
+    fn record_status_changes(&mut self) -> Vec<Message> {
+        let mut events = Vec::new();
+        let mut prompts = Vec::new();
+        let mut plugin_events = Vec::new();
+        let mut rule_events = Vec::new();
+        for (project_idx, project) in self.model.projects.iter_mut().enumerate() {
+            let bridge = project.chat_bridge.as_deref().and_then(crate::chat::ChatBridge::parse);
+            for task in &mut project.tasks {
+                // A task first seen right after it was made was just created (loaded ones are older)
+                let created = task.logged_status.is_none()
+                    && Utc::now().signed_duration_since(task.created_at) < chrono::Duration::seconds(5);
+                let change = task.record_status_change();
+                let plugin_event = match change {
+                    _ if created => Some(crate::plugin::PluginEvent::Created),
+                    Some((from, to)) => crate::plugin::PluginEvent::from_transition(from, to),
+                    None => None,
+                };
+                if let Some(event) = plugin_event {
+                    let payload = event.payload(&project.name, &project.working_dir, task);
+                    plugin_events.push((project.working_dir.clone(), task.id, payload));
+                }
+                if created || change.is_some() {
+                    let from = change.filter(|_| !created).map(|(from, _)| from);
+                    rule_events.push(crate::rules::RuleEvent { project_idx, task_id: task.id, from });
+                }
+                let Some((from, to)) = change else {
+                    continue;
+                };
+                if from == TaskStatus::NeedsWork {
+                    // Answered some other way: stop waiting for a reply in chat
+                    task.chat_thread = None;
+                }
+                if to == TaskStatus::Review {
+                    // Claude finished the turn the review comments went out in
+                    for comment in task.review_comments.iter_mut().filter(|c| c.sent_at.is_some() && c.resolved_at.is_none()) {
+                        comment.resolved_at = Some(Utc::now());
+                    }
+                }
+                if let (Some(bridge), TaskStatus::NeedsWork) = (&bridge, to) {
+                    let text = bridge.prompt_text(&project.name, &task.display_id(), &task.title, task.live_output_text());
+                    prompts.push((task.id, bridge.clone(), text));
+                }
+                if let Some(event) = crate::webhook::WebhookEvent::from_transition(from, to) {
+                    events.push(crate::webhook::EventInfo {
+                        event,
+                        project: project.name.clone(),
+                        task_id: task.display_id(),
+                        title: task.title.clone(),
+                        status: to,
+                        branch: task.git_branch.clone(),
+                        at: Utc::now(),
+                    });
+                }
+            }
+        }
+        // Signals replayed at startup happened while kanblam was closed
+        if self.safe_mode || self.model.ui_state.replaying_signals {
+            return Vec::new();
+        }
+        if let (Some(sender), false) = (self.async_sender.clone(), prompts.is_empty()) {
+            tokio::spawn(async move {
+                for (task_id, bridge, text) in prompts {
+                    let posted = tokio::task::spawn_blocking(move || bridge.post(&text, None)).await;
+                    let result = match posted {
+                        Ok(result) => result.map_err(|e| format!("{:#}", e)),
+                        Err(e) => Err(e.to_string()),
+                    };
+                    let _ = sender.send(Message::ChatPromptPosted { task_id, result });
+                }
+            });
+        }
+        if let (Some(sender), false) = (self.async_sender.clone(), plugin_events.is_empty()) {
+            tokio::spawn(async move {
+                let plugins = crate::plugin::discover();
+                for (project_dir, task_id, payload) in plugin_events {
+                    for plugin in &plugins {
+                        let result = crate::plugin::run(plugin, &project_dir, &payload).await
+                            .map_err(|e| format!("{:#}", e));
+                        let _ = sender.send(Message::PluginActions {
+                            project_dir: project_dir.clone(),
+                            task_id,
+                            plugin: crate::plugin::name(plugin),
+                            result,
+                        });
+                    }
+                }
+            });
+        }
+        let webhooks = self.model.global_settings.webhooks.as_deref()
+            .and_then(crate::webhook::Webhook::parse_all)
+            .unwrap_or_default();
+        let requests: Vec<(String, String)> = events.iter()
+            .flat_map(|info| webhooks.iter()
+                .filter(|hook| hook.wants(info.event))
+                .map(|hook| (hook.url.clone(), hook.payload(info))))
+            .collect();
+        if let (Some(sender), false) = (self.async_sender.clone(), requests.is_empty()) {
+            tokio::spawn(async move {
+                for (url, body) in requests {
+                    let sent = tokio::task::spawn_blocking({
+                        let url = url.clone();
+                        move || crate::webhook::send(&url, &body)
+                    }).await;
+                    if let Ok(Err(e)) = sent {
+                        let _ = sender.send(Message::SetStatusMessage(Some(format!("Webhook to {} failed: {:#}", url, e))));
+                    }
+                }
+            });
+        }
+
+        let Some(rules) = self.rules.as_mut().filter(|_| !rule_events.is_empty()) else {
+            return Vec::new();
+        };
+        let (actions, errors) = rules.evaluate(&self.model.projects, &rule_events);
+        let mut commands: Vec<Message> = actions.into_iter()
+            .filter_map(|action| self.run_rule_action(action))
+            .collect();
+        if let Some(error) = errors.first() {
+            commands.push(Message::SetStatusMessage(Some(format!("Rule failed: {}", error))));
+        }
+        commands
+    }
+
+    /// Carry out what a board rule asked for. Board actions (apply, merge, start,
+    /// move) go through the open project, like the keys that do the same.
+    fn run_rule_action(&mut self, action: crate::rules::RuleAction) -> Option<Message> {
+        use crate::rules::RuleAction;
+
+        let in_active_project = |app: &Self, task_id: uuid::Uuid| {
+            app.model.active_project().is_some_and(|p| p.tasks.iter().any(|t| t.id == task_id))
+        };
+        match action {
+            RuleAction::Apply(task_id) | RuleAction::Merge(task_id) | RuleAction::Start(task_id) | RuleAction::Move(task_id, _)
+                if !in_active_project(self, task_id) =>
+            {
+                Some(Message::SetStatusMessage(Some(format!(
+                    "Rules can only apply, merge, start or move tasks of the open project ({})",
+                    self.get_task_display_id(task_id)
+                ))))
+            }
+            RuleAction::Apply(task_id) => Some(Message::SmartApplyTask(task_id)),
+            RuleAction::Merge(task_id) => Some(Message::ComposeMergeCommit { task_id, merge_only: false, interactive: false }),
+            RuleAction::Start(task_id) => Some(Message::StartTaskWithWorktree(task_id)),
+            RuleAction::Move(task_id, to_status) => Some(Message::MoveTask { task_id, to_status }),
+            RuleAction::Feedback(task_id, feedback) => Some(Message::DoSendFeedback { task_id, feedback }),
+            RuleAction::Tag(task_id, label) => {
+                let task = self.model.projects.iter_mut().flat_map(|p| p.tasks.iter_mut()).find(|t| t.id == task_id)?;
+                if task.add_label(&label) {
+                    task.log_activity(format!("Rules labeled it #{}", label.trim_start_matches('#')));
+                }
+                None
+            }
+            RuleAction::Comment(task_id, text) => {
+                let task = self.model.projects.iter_mut().flat_map(|p| p.tasks.iter_mut()).find(|t| t.id == task_id)?;
+                task.activity_notes.push(crate::model::ActivityLogEntry::note(format!("rules: {}", text.trim())));
+                None
+            }
+            RuleAction::Run(task_id, command) => Some(Message::RunTaskCommand { task_id, command }),
+            RuleAction::Notify(text) => Some(Message::SetStatusMessage(Some(text))),
+        }
+    }
+
+    /// Remember main's HEAD when task changes go onto it, forget it when they come off
+    fn track_applied_head(&mut self) {
+        if self.safe_mode {
+            return;
+        }
+        let Some(project) = self.model.active_project_mut() else {
+            return;
+        };
+        match (project.applied_task_id, &project.applied_head) {
+            (Some(_), None) => project.applied_head = crate::worktree::main_head(&project.working_dir),
+            (None, Some(_)) => {
+                project.applied_head = None;
+                project.applied_head_moved = false;
+            }
+            _ => {}
+        }
+    }
+
+    /// Warn (once) when main's HEAD moves while task changes are applied: the
+    /// new commit most likely swept up the task's uncommitted changes
+    fn check_applied_head(&mut self, project_dir: &Path) -> Option<Message> {
+        let project = self.model.projects.iter_mut()
+            .find(|p| p.working_dir == project_dir && p.applied_head.is_some())?;
+        if project.applied_head_moved {
+            return None;
+        }
+        let recorded = project.applied_head.as_deref()?;
+        let head = crate::worktree::main_head(project_dir)?;
+        if head == recorded {
+            return None;
+        }
+        project.applied_head_moved = true;
+        let display_id = project.applied_task_id
+            .and_then(|id| project.tasks.iter().find(|t| t.id == id))
+            .map(|t| t.display_id())
+            .unwrap_or_default();
+        crate::notify::play_attention_sound();
+        Some(Message::Error(format!(
+            "main moved while {} is applied - the new commit may contain its changes. Check git log before pushing",
+            display_id
+        )))
+    }
+
+    /// Point the active workspace at the projects open now
+    fn sync_active_workspace(&mut self) {
+        let settings = &mut self.model.global_settings;
+        let Some(active) = settings.active_workspace.as_deref() else {
+            return;
+        };
+        if let Some(workspace) = settings.workspaces.iter_mut().find(|w| w.name == active) {
+            workspace.projects = self.model.projects.iter().map(Project::index_entry).collect();
+        }
+    }
+
+    /// Unload the open projects that aren't in the workspace and open the ones that are
+    fn switch_workspace(&mut self, name: &str) -> Vec<Message> {
+        use crate::model::workspace;
+
+        self.sync_active_workspace();
+        let Some(target) = workspace::find(&self.model.global_settings.workspaces, name).cloned() else {
+            return vec![Message::Error(format!("No workspace named '{}'", name))];
+        };
+        // Write the state files of projects about to be unloaded
+        if let Err(e) = save_state(&self.model, self.state_file_path.as_ref()) {
+            return vec![Message::Error(format!("Couldn't save state before switching workspace: {:#}", e))];
+        }
+
+        let plan = workspace::plan_switch(&self.model.projects, &target);
+        let state_file = self.state_file_path.clone().unwrap_or_else(default_state_file_path);
+        let mut open: Vec<Project> = std::mem::take(&mut self.model.projects);
+        for &idx in plan.unload.iter().rev() {
+            open.remove(idx).stop_error_log_watch();
+        }
+        let mut missing = 0;
+        for &idx in &plan.open {
+            let entry = &target.projects[idx];
+            if let Some(ssh_target) = &entry.ssh_target {
+                crate::remote::register(&entry.working_dir, ssh_target);
+            }
+            if entry.ssh_target.is_none() && !entry.working_dir.is_dir() {
+                missing += 1;
+            } else if project_state_file(&state_file, entry.id).exists() {
+                // Read in full when activated, like at startup
+                open.push(Project::from_index(entry.clone()));
+            } else {
+                let mut project = Project::new(entry.name.clone(), entry.working_dir.clone());
+                project.scope = entry.scope.clone();
+                project.load_tasks();
+                open.push(project);
+            }
+        }
+        // Tabs in the workspace's order, projects kept open for running tasks after them
+        let rank = |p: &Project| target.projects.iter().position(|e| e.scope_dir() == p.scope_dir()).unwrap_or(usize::MAX);
+        open.sort_by_key(rank);
+        self.model.projects = open;
+        self.model.active_project_idx = 0;
+        self.model.global_settings.active_workspace = Some(target.name.clone());
+        self.model.ui_state.selected_task_idx = None;
+        self.model.ui_state.selected_project_tab_idx = 0;
+        self.model.ui_state.focus = FocusArea::KanbanBoard;
+
+        if let Err(e) = save_state(&self.model, self.state_file_path.as_ref()) {
+            crate::diagnostics::warn(crate::diagnostics::Source::Io, format!("Failed to save state after switching workspace: {}", e));
+        }
+
+        let mut status = format!("Workspace '{}': {} project{}", target.name, self.model.projects.len(),
+            if self.model.projects.len() == 1 { "" } else { "s" });
+        if missing > 0 {
+            status.push_str(&format!(", {} missing", missing));
+        }
+        if !plan.kept.is_empty() {
+            status.push_str(&format!(", {} kept open for running tasks", plan.kept.len()));
+        }
+        vec![
+            Message::SetStatusMessage(Some(status)),
+            Message::ConnectRemoteProjects,
+            Message::RefreshGitStatus,
+            Message::StartGitFetch,
+        ]
+    }
+
+    /// Read a project's state file if it's still only an index entry
+    fn load_project(&mut self, idx: usize) -> Result<()> {
+        let state_file = self.state_file_path.clone().unwrap_or_else(default_state_file_path);
+        match self.model.projects.get_mut(idx) {
+            Some(project) => load_project_isolated(project, &state_file),
+            None => Ok(()),
+        }
+    }
+
+    /// Whether something on screen animates every tick (spinners, live modal output)
+    fn has_running_animation(&self) -> bool {
+        let ui = &self.model.ui_state;
+        ui.interactive_modal.is_some()
+            || !ui.probing_git.is_empty()
+            || ui.code_search.as_ref().is_some_and(|s| s.searching)
+            || ui.todo_scan.as_ref().is_some_and(|s| s.scanning)
+            || self.model.active_project().is_some_and(|p| {
+                p.git_operation_in_progress.is_some()
+                    || p.watcher_observing
+                    || p.tasks.iter().any(|t| {
+                        t.generating_spec
+                            || matches!(t.status,
+                                TaskStatus::InProgress | TaskStatus::Testing | TaskStatus::NeedsWork
+                                | TaskStatus::Accepting | TaskStatus::Updating | TaskStatus::Applying)
+                    })
+            })
+    }
+
+    /// Hash of the tick-driven state that shows up on screen
+    fn tick_fingerprint(&self) -> u64 {
+        use std::hash::{Hash, Hasher};
+
+        let ui = &self.model.ui_state;
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        if self.has_running_animation() {
+            ui.animation_frame.hash(&mut hasher);
+        }
+        ui.logo_shimmer_frame.hash(&mut hasher);
+        ui.merge_celebration.as_ref().map(|c| c.frame).hash(&mut hasher);
+        ui.eye_animation_ticks_remaining.hash(&mut hasher);
+        ui.welcome_message_idx.hash(&mut hasher);
+        ui.startup_hint_until_tick.hash(&mut hasher);
+        ui.pending_confirmation.as_ref().map(|c| c.animation_tick).hash(&mut hasher);
+        ui.status_message.is_some().hash(&mut hasher);
+        ui.title_scroll_offset.hash(&mut hasher);
+        ui.activity_scroll_offset.hash(&mut hasher);
+        ui.selected_task_idx.hash(&mut hasher);
+        ui.column_scroll_offsets.hash(&mut hasher);
+        if let Some(project) = self.model.active_project() {
+            project.watcher_comment.as_ref().map(|c| c.scroll_offset).hash(&mut hasher);
+            project.unseen_attention_count().hash(&mut hasher);
+        }
+        if let Some(ref timer) = ui.focus_timer {
+            timer.remaining_secs(&self.model.global_settings).hash(&mut hasher);
+        }
+        if self.model.global_settings.status_clock {
+            Utc::now().timestamp().div_euclid(60).hash(&mut hasher);
+        }
+        hasher.finish()
+    }
+
+    fn handle_message(&mut self, msg: Message) -> Vec<Message> {
         let mut commands = Vec::new();
 
         match msg {
             Message::CreateTask(title) => {
-                // Take pending images before borrowing project
+                // The project isn't saved until its state file loads, so the task would be lost
+                if let Some(error) = self.model.active_project().and_then(|p| p.load_error.as_ref()) {
+                    return vec![Message::Error(format!("Can't add tasks to a project whose state can't be read: {}", error))];
+                }
+                // Take pending images and files before borrowing project
                 let pending_images = std::mem::take(&mut self.model.ui_state.pending_images);
+                let pending_attachments = std::mem::take(&mut self.model.ui_state.pending_attachments);
+                let audio = audio_files(&pending_attachments);
                 let task_id;
                 let title_len = title.len();
                 if let Some(project) = self.model.active_project_mut() {
                     let mut task = Task::new(title);
                     task_id = task.id;
-                    // Attach pending images
+                    // Attach pending images and files
                     task.images = pending_images;
+                    task.attachments = pending_attachments;
                     // Insert at beginning so newest tasks appear first in Planned
                     project.tasks.insert(0, task);
                 } else {
@@ -257,6 +1426,9 @@ Do not ask for permission - run tests and fix any issues you find."#);
                 if title_len > 40 && !task_id.is_nil() {
                     commands.push(Message::RequestTitleSummary { task_id });
                 }
+                if !audio.is_empty() && !task_id.is_nil() {
+                    commands.push(Message::TranscribeAudio { task_id, files: audio });
+                }
             }
 
             Message::EditTask(task_id) => {
@@ -333,7 +1505,7 @@ Do not ask for permission - run tests and fix any issues you find."#);
 
                     // Remove worktree
                     if let Some(ref wt_path) = worktree_path {
-                        if let Err(e) = crate::worktree::remove_worktree(&project_dir, wt_path) {
+                        if let Err(e) = crate::worktree::release_worktree(&project_dir, wt_path, self.worktree_pool_size(&project_dir)) {
                             commands.push(Message::SetStatusMessage(Some(
                                 format!("Warning: Could not remove worktree: {}", e)
                             )));
@@ -350,12 +1522,17 @@ Do not ask for permission - run tests and fix any issues you find."#);
                             )));
                         }
                     }
+
+                    crate::audit::record(crate::audit::AuditEntry::new("delete_task", &project_dir)
+                        .with_task(&display_id)
+                        .with_detail(task_id.to_string()));
                 }
 
                 // Remove the task from the project
                 if let Some(project) = self.model.active_project_mut() {
                     project.tasks.retain(|t| t.id != task_id);
                 }
+                crate::model::activity::remove_task_spills(task_id);
             }
 
             Message::MoveTask { task_id, to_status } => {
@@ -426,6 +1603,10 @@ Do not ask for permission - run tests and fix any issues you find."#);
             }
 
             Message::MoveTaskUp => {
+                if let Some(sort) = self.sorted_column_hint() {
+                    self.model.ui_state.status_message = Some(sort);
+                    return commands;
+                }
                 // Move selected task up within its column
                 if let Some(selected_idx) = self.model.ui_state.selected_task_idx {
                     if selected_idx > 0 {
@@ -434,7 +1615,8 @@ Do not ask for permission - run tests and fix any issues you find."#);
                         let (task_id, above_task_id) = {
                             if let Some(project) = self.model.active_project() {
                                 let tasks = project.tasks_by_status(status);
-                                if selected_idx < tasks.len() {
+                                // Tasks only move within their tag group
+                                if selected_idx < tasks.len() && project.same_group(tasks[selected_idx], tasks[selected_idx - 1]) {
                                     (Some(tasks[selected_idx].id), Some(tasks[selected_idx - 1].id))
                                 } else {
                                     (None, None)
@@ -461,6 +1643,10 @@ Do not ask for permission - run tests and fix any issues you find."#);
             }
 
             Message::MoveTaskDown => {
+                if let Some(sort) = self.sorted_column_hint() {
+                    self.model.ui_state.status_message = Some(sort);
+                    return commands;
+                }
                 // Move selected task down within its column
                 if let Some(selected_idx) = self.model.ui_state.selected_task_idx {
                     let status = self.model.ui_state.selected_column;
@@ -468,7 +1654,7 @@ Do not ask for permission - run tests and fix any issues you find."#);
                     let (task_id, below_task_id) = {
                         if let Some(project) = self.model.active_project() {
                             let tasks = project.tasks_by_status(status);
-                            if selected_idx + 1 < tasks.len() {
+                            if selected_idx + 1 < tasks.len() && project.same_group(tasks[selected_idx], tasks[selected_idx + 1]) {
                                 (Some(tasks[selected_idx].id), Some(tasks[selected_idx + 1].id))
                             } else {
                                 (None, None)
@@ -533,13 +1719,48 @@ Do not ask for permission - run tests and fix any issues you find."#);
             // === Worktree-based task lifecycle ===
 
             Message::StartTaskWithWorktree(task_id) => {
-                // Check if spec exists or is being generated
-                // We need the spec before starting the SDK session
-                let spec_status = self.model.active_project_mut()
-                    .and_then(|p| p.tasks.iter_mut().find(|t| t.id == task_id))
-                    .map(|task| {
-                        if task.generating_spec {
-                            // Already generating - just queue the start
+                // During a focus block, new sessions wait for the next break
+                let defer = self.model.ui_state.focus_timer.as_ref()
+                    .is_some_and(|t| t.defers_starts(&self.model.global_settings));
+                if defer {
+                    let is_new = self.model.active_project()
+                        .and_then(|p| p.tasks.iter().find(|t| t.id == task_id))
+                        .is_some_and(|t| t.status == TaskStatus::Planned && t.worktree_path.is_none());
+                    if is_new {
+                        if !self.model.ui_state.deferred_starts.contains(&task_id) {
+                            self.model.ui_state.deferred_starts.push(task_id);
+                        }
+                        commands.push(Message::SetStatusMessage(Some(format!(
+                            "Focus block: start deferred until the break ({} waiting, B stops the timer)",
+                            self.model.ui_state.deferred_starts.len()
+                        ))));
+                        return commands;
+                    }
+                }
+
+                // Projects with a sparse profile ask which paths this task needs (once per task)
+                let needs_sparse_prompt = self.model.active_project().and_then(|p| {
+                    let task = p.tasks.iter().find(|t| t.id == task_id)?;
+                    match (&p.sparse_paths, &task.sparse_paths) {
+                        (Some(profile), None) => Some(profile.clone()),
+                        _ => None,
+                    }
+                });
+                if let Some(profile) = needs_sparse_prompt {
+                    self.model.ui_state.sparse_prompt = Some(crate::model::SparsePromptState {
+                        task_id,
+                        input: profile,
+                    });
+                    return commands;
+                }
+
+                // Check if spec exists or is being generated
+                // We need the spec before starting the SDK session
+                let spec_status = self.model.active_project_mut()
+                    .and_then(|p| p.tasks.iter_mut().find(|t| t.id == task_id))
+                    .map(|task| {
+                        if task.generating_spec {
+                            // Already generating - just queue the start
                             task.start_after_spec = true;
                             "generating"
                         } else if task.spec.is_none() {
@@ -611,12 +1832,21 @@ Do not ask for permission - run tests and fix any issues you find."#);
 
                     // Defer the actual worktree creation to allow UI to render first
                     if let Some(display_id) = display_id {
-                        commands.push(Message::CreateWorktree { task_id, display_id, project_dir });
+                        let sparse_patterns = self.model.active_project()
+                            .map(|p| p.sparse_patterns_for(task_id))
+                            .unwrap_or_default();
+                        let base_ref = self.model.active_project()
+                            .and_then(|p| p.tasks.iter().find(|t| t.id == task_id))
+                            .and_then(|t| t.base_ref.clone());
+                        commands.push(Message::CreateWorktree { task_id, display_id, project_dir, sparse_patterns, base_ref });
                     }
                 }
             }
 
-            Message::CreateWorktree { task_id, display_id, project_dir } => {
+            Message::CreateWorktree { task_id, display_id, project_dir, sparse_patterns, base_ref } => {
+                // Applied task changes aren't part of the folder's state
+                let applied = self.model.projects.iter()
+                    .any(|p| p.working_dir == project_dir && p.applied_task_id.is_some());
                 // Spawn worktree creation in background to keep UI responsive
                 if let Some(sender) = self.async_sender.clone() {
                     let project_dir_clone = project_dir.clone();
@@ -624,7 +1854,20 @@ Do not ask for permission - run tests and fix any issues you find."#);
                     tokio::spawn(async move {
                         // Run blocking git operations in a separate thread
                         let result = tokio::task::spawn_blocking(move || {
-                            crate::worktree::create_worktree(&project_dir_clone, &display_id_clone)
+                            // Tasks in snapshot projects start from what's on disk now
+                            if !applied {
+                                crate::worktree::snapshot::take(&project_dir_clone)?;
+                            }
+                            // Prefer a pre-warmed worktree (they're all at HEAD, so not with
+                            // another base); fall back to creating one
+                            let pooled = match base_ref {
+                                None => crate::worktree::take_pooled_worktree(&project_dir_clone, &display_id_clone, &sparse_patterns),
+                                Some(_) => Ok(None),
+                            };
+                            match pooled {
+                                Ok(Some(worktree_path)) => Ok(worktree_path),
+                                _ => crate::worktree::create_worktree(&project_dir_clone, &display_id_clone, &sparse_patterns, base_ref.as_deref()),
+                            }
                         }).await;
 
                         let msg = match result {
@@ -643,7 +1886,7 @@ Do not ask for permission - run tests and fix any issues you find."#);
                     });
                 } else {
                     // Fallback to sync if no async sender (shouldn't happen in normal operation)
-                    match crate::worktree::create_worktree(&project_dir, &display_id) {
+                    match crate::worktree::create_worktree(&project_dir, &display_id, &sparse_patterns, base_ref.as_deref()) {
                         Ok(worktree_path) => {
                             commands.push(Message::WorktreeCreated { task_id, display_id, worktree_path, project_dir });
                         }
@@ -654,6 +1897,40 @@ Do not ask for permission - run tests and fix any issues you find."#);
                 }
             }
 
+            Message::WarmWorktreePool { project_dir, size } => {
+                if self.safe_mode {
+                    return commands;
+                }
+                let sparse_patterns = self.model.projects.iter()
+                    .find(|p| p.working_dir == project_dir)
+                    .and_then(|p| p.sparse_paths.as_deref())
+                    .map(crate::worktree::parse_sparse_patterns)
+                    .unwrap_or_default();
+                if let Some(sender) = self.async_sender.clone() {
+                    tokio::spawn(async move {
+                        let result = tokio::task::spawn_blocking(move || {
+                            if size == 0 {
+                                crate::worktree::drain_worktree_pool(&project_dir)
+                            } else {
+                                crate::worktree::warm_worktree_pool(&project_dir, size, &sparse_patterns).map(|_| ())
+                            }
+                        }).await;
+
+                        // Pool failures only cost start latency - report, don't interrupt
+                        let error = match result {
+                            Ok(Ok(())) => None,
+                            Ok(Err(e)) => Some(e.to_string()),
+                            Err(e) => Some(format!("Task panicked: {}", e)),
+                        };
+                        if let Some(error) = error {
+                            let _ = sender.send(Message::SetStatusMessage(Some(
+                                format!("Worktree pool: {}", error)
+                            )));
+                        }
+                    });
+                }
+            }
+
             Message::UpdateTaskSessionState { task_id, state } => {
                 if let Some(project) = self.model.active_project_mut() {
                     if let Some(task) = project.tasks.iter_mut().find(|t| t.id == task_id) {
@@ -713,10 +1990,11 @@ Do not ask for permission - run tests and fix any issues you find."#);
                             t.tmux_window.clone(),
                             t.worktree_path.clone(),
                             t.display_id(),
+                            t.merge_message.clone(),
                         ))
                 });
 
-                if let Some((project_slug, project_dir, window_name, worktree_path, display_id)) = task_info {
+                if let Some((project_slug, project_dir, window_name, worktree_path, display_id, merge_message)) = task_info {
                     // CRITICAL: Commit any uncommitted changes in the worktree FIRST
                     // This ensures we don't lose work that Claude did but didn't commit
                     if let Some(ref wt_path) = worktree_path {
@@ -761,6 +2039,15 @@ Do not ask for permission - run tests and fix any issues you find."#);
                         let _ = crate::tmux::kill_task_window(&project_slug, window);
                     }
 
+                    // Snapshot projects have no commits of their own: record what's on
+                    // disk (unless it holds another task's applied changes)
+                    let applied = self.model.active_project().is_some_and(|p| p.applied_task_id.is_some());
+                    let snapshot = if applied { Ok(false) } else { crate::worktree::snapshot::take(&project_dir) };
+                    if let Err(e) = snapshot {
+                        commands.push(Message::Error(format!("Failed to snapshot the project: {}", e)));
+                        return commands;
+                    }
+
                     // Check for uncommitted changes on main - ask user what to do
                     match crate::worktree::has_uncommitted_changes(&project_dir) {
                         Ok(true) => {
@@ -788,77 +2075,8 @@ Do not ask for permission - run tests and fix any issues you find."#);
                     // Kill any detached Claude/test sessions for this task (uses display_id as session name)
                     crate::tmux::kill_task_sessions(&display_id);
 
-                    // Merge branch to main
-                    if let Err(e) = crate::worktree::merge_branch(&project_dir, &display_id) {
-                        commands.push(Message::Error(format!(
-                            "Merge failed: {}. Resolve manually in the worktree, then discard.",
-                            e
-                        )));
-                        return commands;
-                    }
-
-                    // Remove worktree
-                    if let Some(ref wt_path) = worktree_path {
-                        if let Err(e) = crate::worktree::remove_worktree(&project_dir, wt_path) {
-                            commands.push(Message::SetStatusMessage(Some(
-                                format!("Warning: Could not remove worktree: {}", e)
-                            )));
-                        }
-                        // Clean up trust entry from Claude's config
-                        let _ = crate::worktree::remove_worktree_trust(wt_path);
-                    }
-
-                    // Delete branch
-                    if let Err(e) = crate::worktree::delete_branch(&project_dir, &display_id) {
-                        commands.push(Message::SetStatusMessage(Some(
-                            format!("Warning: Could not delete branch: {}", e)
-                        )));
-                    }
-
-                    // Capture celebration info for animation (task stays in place during animation)
-                    let celebration_info = self.model.active_project().and_then(|project| {
-                        let tasks_in_review = project.tasks_by_status(TaskStatus::Review);
-                        tasks_in_review.iter().enumerate()
-                            .find(|(_, t)| t.id == task_id)
-                            .map(|(idx, t)| {
-                                let task_id_short = &t.id.to_string()[..4];
-                                let title = t.short_title.as_ref().unwrap_or(&t.title);
-                                let display_text = format!("[{}] {}", task_id_short, title);
-                                (display_text, idx)
-                            })
-                    });
-
-                    // Log activity before animation starts (task completion deferred until animation ends)
-                    if let Some(project) = self.model.active_project_mut() {
-                        if let Some(task) = project.tasks.iter_mut().find(|t| t.id == task_id) {
-                            task.log_activity("User merged changes");
-                        }
-                    }
-
-                    // Trigger celebratory animations - task completion deferred until animation ends
-                    commands.push(Message::TriggerLogoShimmer);
-                    if let Some((display_text, task_index)) = celebration_info {
-                        commands.push(Message::TriggerMergeCelebration {
-                            task_id,
-                            display_text,
-                            column_status: TaskStatus::Review,
-                            task_index,
-                            pending_completion: true,
-                        });
-                    } else {
-                        // No animation - complete immediately
-                        if let Some(project) = self.model.active_project_mut() {
-                            project.complete_task(task_id);
-                            project.needs_attention = project.review_count() > 0;
-                            if !project.needs_attention {
-                                notify::clear_attention_indicator();
-                            }
-                        }
-                    }
-
-                    commands.push(Message::SetStatusMessage(Some(
-                        "Task accepted and merged to main.".to_string()
-                    )));
+                    // Merge branch to main in the background, then remove the worktree and branch
+                    commands.extend(self.spawn_task_merge(task_id, false, project_dir, display_id, worktree_path, merge_message));
                 }
             }
 
@@ -906,6 +2124,10 @@ Do not ask for permission - run tests and fix any issues you find."#);
                     };
 
                     // Try to acquire exclusive lock on main worktree
+                    if let Some(reason) = self.shared_main_worktree_busy() {
+                        commands.push(Message::Error(reason));
+                        return commands;
+                    }
                     if let Some(project) = self.model.active_project_mut() {
                         if let Err(reason) = project.try_lock_main_worktree(task_id, MainWorktreeOperation::Accepting) {
                             commands.push(Message::Error(reason));
@@ -1003,6 +2225,7 @@ Do not ask for permission - run tests and fix any issues you find."#);
                         task.move_to_review();
                     }
                 }
+                notify::play_cue(notify::SoundCue::MergeFailed);
                 commands.push(Message::Error(error));
             }
 
@@ -1018,10 +2241,11 @@ Do not ask for permission - run tests and fix any issues you find."#);
                             t.worktree_path.clone(),
                             t.status,
                             t.display_id(),
+                            t.merge_message.clone(),
                         ))
                 });
 
-                if let Some((project_slug, project_dir, window_name, worktree_path, status, display_id)) = task_info {
+                if let Some((project_slug, project_dir, window_name, worktree_path, status, display_id, merge_message)) = task_info {
                     // If was accepting, verify rebase succeeded
                     if status == TaskStatus::Accepting {
                         // Check if rebase is still in progress
@@ -1128,94 +2352,245 @@ Do not ask for permission - run tests and fix any issues you find."#);
                     // Kill any detached Claude/test sessions for this task (uses display_id as session name)
                     crate::tmux::kill_task_sessions(&display_id);
 
-                    // Merge branch to main (should be fast-forward now)
-                    if let Err(e) = crate::worktree::merge_branch(&project_dir, &display_id) {
-                        // Return to Review status on error
-                        if let Some(project) = self.model.active_project_mut() {
+                    // Merge branch to main in the background (should be fast-forward now),
+                    // then remove the worktree and branch
+                    commands.extend(self.spawn_task_merge(task_id, true, project_dir, display_id, worktree_path, merge_message));
+                }
+            }
+
+            Message::TaskBranchMerged { task_id, accepting, result } => {
+                // The merge belongs to the task's project, which may no longer be the active tab
+                let Some(project_idx) = self.model.projects.iter()
+                    .position(|p| p.tasks.iter().any(|t| t.id == task_id))
+                else {
+                    return commands;
+                };
+                let is_active = project_idx == self.model.active_project_idx;
+                let project = &mut self.model.projects[project_idx];
+                let cancelled = project.git_operation_in_progress == Some(crate::model::GitOperation::Merging)
+                    && project.finish_git_operation();
+                let warnings = match result {
+                    Ok(warnings) => warnings,
+                    Err(error) => {
+                        // A smart accept returns to Review and gives up the main worktree
+                        if accepting {
                             if let Some(task) = project.tasks.iter_mut().find(|t| t.id == task_id) {
                                 task.move_to_review();
                             }
                             project.release_main_worktree_lock(task_id);
                         }
-                        commands.push(Message::Error(format!(
-                            "Merge failed: {}. Try accepting again or resolve manually.",
-                            e
-                        )));
+                        if cancelled {
+                            commands.push(Message::SetStatusMessage(Some("Merge cancelled".to_string())));
+                        } else {
+                            notify::play_cue(notify::SoundCue::MergeFailed);
+                            commands.push(Message::Error(if accepting {
+                                format!("Merge failed: {}. Try accepting again or resolve manually.", error)
+                            } else {
+                                format!("Merge failed: {}. Resolve manually in the worktree, then discard.", error)
+                            }));
+                        }
                         return commands;
                     }
+                };
+                for warning in warnings {
+                    commands.push(Message::SetStatusMessage(Some(warning)));
+                }
 
-                    // Remove worktree
-                    if let Some(ref wt_path) = worktree_path {
-                        if let Err(e) = crate::worktree::remove_worktree(&project_dir, wt_path) {
-                            commands.push(Message::SetStatusMessage(Some(
-                                format!("Warning: Could not remove worktree: {}", e)
-                            )));
-                        }
-                        let _ = crate::worktree::remove_worktree_trust(wt_path);
+                // Capture celebration info for animation (task stays in place during animation).
+                // Only the active board shows it; another tab's task completes right away.
+                // Note: Task might be in Accepting status (shown in Review column)
+                let celebration_info = is_active.then(|| {
+                    let tasks_in_review = project.tasks_by_status(TaskStatus::Review);
+                    tasks_in_review.iter().enumerate()
+                        .find(|(_, t)| t.id == task_id)
+                        .map(|(idx, t)| {
+                            let task_id_short = &t.id.to_string()[..4];
+                            let title = t.short_title.as_ref().unwrap_or(&t.title);
+                            let display_text = format!("[{}] {}", task_id_short, title);
+                            (display_text, idx)
+                        })
+                }).flatten();
+
+                // Log activity before animation starts (task completion deferred until animation ends)
+                if accepting {
+                    // The merge completed, so the lock goes (before the animation)
+                    project.release_main_worktree_lock(task_id);
+                }
+                project.mark_task_merged(task_id);
+                if !accepting {
+                    if let Some(task) = project.tasks.iter_mut().find(|t| t.id == task_id) {
+                        task.log_activity("User merged changes");
                     }
+                }
+                commands.push(Message::CheckReleaseAfterMerge(task_id));
+                commands.push(Message::RunPostMergeScript(task_id));
 
-                    // Delete branch
-                    if let Err(e) = crate::worktree::delete_branch(&project_dir, &display_id) {
-                        commands.push(Message::SetStatusMessage(Some(
-                            format!("Warning: Could not delete branch: {}", e)
-                        )));
+                // Trigger celebratory animations - task completion deferred until animation ends
+                commands.push(Message::TriggerLogoShimmer);
+                if let Some((display_text, task_index)) = celebration_info {
+                    commands.push(Message::TriggerMergeCelebration {
+                        task_id,
+                        display_text,
+                        column_status: TaskStatus::Review,
+                        task_index,
+                        pending_completion: true,
+                    });
+                } else {
+                    // No animation - complete immediately
+                    project.complete_task(task_id);
+                    project.needs_attention = project.review_count() > 0;
+                    if !project.needs_attention {
+                        notify::clear_attention_indicator();
                     }
+                }
 
-                    // Capture celebration info for animation (task stays in place during animation)
-                    // Note: Task might be in Accepting status (shown in Review column)
-                    let celebration_info = self.model.active_project().and_then(|project| {
-                        // For Accepting tasks, they appear in Review column
-                        let tasks_in_review = project.tasks_by_status(TaskStatus::Review);
-                        tasks_in_review.iter().enumerate()
-                            .find(|(_, t)| t.id == task_id)
-                            .map(|(idx, t)| {
-                                let task_id_short = &t.id.to_string()[..4];
-                                let title = t.short_title.as_ref().unwrap_or(&t.title);
-                                let display_text = format!("[{}] {}", task_id_short, title);
-                                (display_text, idx)
-                            })
+                // After a smart accept, offer back stashes kept from the main worktree
+                let offer_stash = project.tracked_stashes.first()
+                    .filter(|_| accepting && is_active)
+                    .cloned();
+
+                if let Some(stash) = offer_stash {
+                    commands.push(Message::OfferPopStash {
+                        stash_sha: stash.stash_sha,
+                        context: "merge".to_string(),
                     });
+                } else {
+                    commands.push(Message::SetStatusMessage(Some(
+                        "Task accepted and merged to main.".to_string()
+                    )));
+                }
+            }
 
-                    // Release the lock - merge completed successfully (do this before animation)
-                    if let Some(project) = self.model.active_project_mut() {
-                        project.release_main_worktree_lock(task_id);
-                    }
+            Message::ComposeMergeCommit { task_id, merge_only, interactive } => {
+                use crate::model::{CommitEditorState, CommitMessages};
+                let merge = if merge_only { Message::MergeOnlyTask(task_id) } else { Message::SmartAcceptTask(task_id) };
+                let display_id = self.get_task_display_id(task_id);
+                let Some((mode, project_dir, task)) = self.model.projects.iter_mut().find_map(|p| {
+                    let (mode, dir) = (p.commit_messages, p.working_dir.clone());
+                    Some((mode, dir, p.tasks.iter_mut().find(|t| t.id == task_id)?))
+                }) else {
+                    return commands;
+                };
+                if task.composing_commit {
+                    commands.push(Message::SetStatusMessage(Some("Still writing the commit message...".to_string())));
+                    return commands;
+                }
+                let ask = interactive && mode == CommitMessages::Ask;
+                let editor = |input: String, generating: bool, note: Option<String>| {
+                    Some(CommitEditorState { task_id, merge_only, input, generating, note })
+                };
 
-                    // Trigger celebratory animations - task completion deferred until animation ends
-                    commands.push(Message::TriggerLogoShimmer);
-                    if let Some((display_text, task_index)) = celebration_info {
-                        commands.push(Message::TriggerMergeCelebration {
-                            task_id,
-                            display_text,
-                            column_status: TaskStatus::Review,
-                            task_index,
-                            pending_completion: true,
-                        });
+                // A message settled on for an earlier attempt is kept (and offered again)
+                if let Some(message) = task.merge_message.clone() {
+                    if ask {
+                        self.model.ui_state.commit_editor = editor(message, false, None);
                     } else {
-                        // No animation - complete immediately
-                        if let Some(project) = self.model.active_project_mut() {
-                            project.complete_task(task_id);
-                            project.needs_attention = project.review_count() > 0;
-                            if !project.needs_attention {
-                                notify::clear_attention_indicator();
-                            }
-                        }
+                        commands.push(merge);
+                    }
+                    return commands;
+                }
+                if mode == CommitMessages::Never || (mode == CommitMessages::Ask && !interactive) {
+                    commands.push(merge);
+                    return commands;
+                }
+                let sender = match (&self.sidecar_client, self.async_sender.clone()) {
+                    (Some(_), Some(sender)) => sender,
+                    _ if ask => {
+                        let note = "Sidecar not connected, so no message was written".to_string();
+                        self.model.ui_state.commit_editor = editor(crate::commit_message::fallback(&display_id), false, Some(note));
+                        return commands;
                     }
+                    _ => {
+                        commands.push(merge);
+                        return commands;
+                    }
+                };
 
-                    // Check if there are tracked stashes to offer popping
-                    let offer_stash = self.model.active_project()
-                        .and_then(|p| p.tracked_stashes.first().cloned());
+                task.composing_commit = true;
+                let brief = task.session_prompt();
+                let cwd = task.worktree_path.clone().unwrap_or_else(|| project_dir.clone());
+                if ask {
+                    self.model.ui_state.commit_editor = editor(String::new(), true, None);
+                } else {
+                    commands.push(Message::SetStatusMessage(Some("Writing commit message...".to_string())));
+                }
+                tokio::spawn(async move {
+                    let result = tokio::task::spawn_blocking(move || {
+                        let diff = crate::worktree::get_task_diff(&project_dir, &display_id, &[])
+                            .map_err(|e| e.to_string())?;
+                        let prompt = crate::commit_message::prompt(&brief, &diff);
+                        let reply = crate::sidecar::SidecarClient::complete_prompt_standalone(prompt, Some(cwd))
+                            .map_err(|e| e.to_string())?;
+                        crate::commit_message::clean(&reply).ok_or_else(|| "the reply was empty".to_string())
+                    }).await.unwrap_or_else(|e| Err(format!("Task panicked: {}", e)));
+                    let _ = sender.send(Message::CommitMessageGenerated { task_id, merge_only, ask, result });
+                });
+            }
 
-                    if let Some(stash) = offer_stash {
-                        commands.push(Message::OfferPopStash {
-                            stash_sha: stash.stash_sha,
-                            context: "merge".to_string(),
-                        });
-                    } else {
-                        commands.push(Message::SetStatusMessage(Some(
-                            "Task accepted and merged to main.".to_string()
-                        )));
+            Message::CommitMessageGenerated { task_id, merge_only, ask, result } => {
+                let display_id = self.get_task_display_id(task_id);
+                let Some(task) = self.model.projects.iter_mut()
+                    .flat_map(|p| p.tasks.iter_mut())
+                    .find(|t| t.id == task_id)
+                else {
+                    return commands;
+                };
+                task.composing_commit = false;
+                if ask {
+                    // The editor may have been closed meanwhile, cancelling the merge
+                    let Some(editor) = self.model.ui_state.commit_editor.as_mut()
+                        .filter(|e| e.task_id == task_id && e.generating)
+                    else {
+                        return commands;
+                    };
+                    editor.generating = false;
+                    match result {
+                        Ok(message) => editor.input = message,
+                        Err(e) => {
+                            editor.input = crate::commit_message::fallback(&display_id);
+                            editor.note = Some(format!("Could not write a commit message: {}", e));
+                        }
                     }
+                    return commands;
+                }
+                match result {
+                    Ok(message) => task.merge_message = Some(message),
+                    Err(e) => task.log_activity(format!("Merging with the plain commit message: {}", e)),
+                }
+                commands.push(if merge_only { Message::MergeOnlyTask(task_id) } else { Message::SmartAcceptTask(task_id) });
+            }
+
+            Message::CommitEditorChanged(input) => {
+                if let Some(ref mut editor) = self.model.ui_state.commit_editor {
+                    editor.input = input;
+                }
+            }
+
+            Message::ConfirmMergeCommit => {
+                let Some(editor) = self.model.ui_state.commit_editor.take() else {
+                    return commands;
+                };
+                let message = editor.input.trim().to_string();
+                if editor.generating || message.is_empty() {
+                    self.model.ui_state.commit_editor = Some(editor);
+                    return commands;
+                }
+                if let Some(task) = self.model.projects.iter_mut()
+                    .flat_map(|p| p.tasks.iter_mut())
+                    .find(|t| t.id == editor.task_id)
+                {
+                    task.merge_message = Some(message);
+                }
+                commands.push(if editor.merge_only {
+                    Message::MergeOnlyTask(editor.task_id)
+                } else {
+                    Message::SmartAcceptTask(editor.task_id)
+                });
+            }
+
+            Message::CancelMergeCommit => {
+                if self.model.ui_state.commit_editor.take().is_some() {
+                    commands.push(Message::SetStatusMessage(Some("Merge cancelled".to_string())));
                 }
             }
 
@@ -1229,10 +2604,11 @@ Do not ask for permission - run tests and fix any issues you find."#);
                             t.worktree_path.clone(),
                             t.status,
                             t.display_id(),
+                            t.merge_message.clone(),
                         ))
                 });
 
-                if let Some((project_dir, worktree_path, current_status, display_id)) = task_info {
+                if let Some((project_dir, worktree_path, current_status, display_id, merge_message)) = task_info {
                     // Don't process if already accepting
                     if current_status == TaskStatus::Accepting {
                         return commands;
@@ -1245,6 +2621,10 @@ Do not ask for permission - run tests and fix any issues you find."#);
                     };
 
                     // Try to acquire exclusive lock on main worktree
+                    if let Some(reason) = self.shared_main_worktree_busy() {
+                        commands.push(Message::Error(reason));
+                        return commands;
+                    }
                     if let Some(project) = self.model.active_project_mut() {
                         if let Err(reason) = project.try_lock_main_worktree(task_id, MainWorktreeOperation::Accepting) {
                             commands.push(Message::Error(reason));
@@ -1259,11 +2639,12 @@ Do not ask for permission - run tests and fix any issues you find."#);
                         display_id,
                         worktree_path: wt_path,
                         project_dir,
+                        merge_message,
                     });
                 }
             }
 
-            Message::StartMergeOnlyGitOps { task_id, display_id, worktree_path, project_dir } => {
+            Message::StartMergeOnlyGitOps { task_id, display_id, worktree_path, project_dir, merge_message } => {
                 // Run git operations in background to keep UI responsive
                 let sender = match self.async_sender.clone() {
                     Some(s) => s,
@@ -1275,8 +2656,20 @@ Do not ask for permission - run tests and fix any issues you find."#);
                         return commands;
                     }
                 };
+                let cancel = match self.begin_git_merge(task_id) {
+                    Ok(cancel) => cancel,
+                    Err(error) => {
+                        // Not through MergeOnlyFailed: that would finish the operation already running
+                        if let Some(project) = self.model.active_project_mut() {
+                            project.release_main_worktree_lock(task_id);
+                        }
+                        commands.push(Message::Error(error));
+                        return commands;
+                    }
+                };
 
                 tokio::spawn(async move {
+                    let progress_sender = sender.clone();
                     let result = tokio::task::spawn_blocking(move || -> Result<(), String> {
                         // Commit any uncommitted changes in the worktree
                         if let Err(e) = crate::worktree::commit_worktree_changes(&worktree_path, &display_id) {
@@ -1308,7 +2701,10 @@ Do not ask for permission - run tests and fix any issues you find."#);
                         }
 
                         // Merge branch to main (should be fast-forward now)
-                        if let Err(e) = crate::worktree::merge_branch(&project_dir, &display_id) {
+                        let merged = crate::worktree::merge_branch(&project_dir, &display_id, merge_message.as_deref(), &cancel, &mut |progress| {
+                            let _ = progress_sender.send(Message::GitOperationProgress(progress));
+                        });
+                        if let Err(e) = merged {
                             return Err(format!("Merge failed: {}", e));
                         }
 
@@ -1336,8 +2732,12 @@ Do not ask for permission - run tests and fix any issues you find."#);
             Message::MergeOnlyReadyToMerge { task_id } => {
                 // Release the lock - merge completed successfully
                 if let Some(project) = self.model.active_project_mut() {
+                    project.finish_git_operation();
                     project.release_main_worktree_lock(task_id);
+                    project.mark_task_merged(task_id);
                 }
+                commands.push(Message::CheckReleaseAfterMerge(task_id));
+                commands.push(Message::RunPostMergeScript(task_id));
 
                 // Capture celebration info BEFORE clearing applied state
                 let celebration_info = self.model.active_project().and_then(|project| {
@@ -1415,6 +2815,7 @@ Do not ask for permission - run tests and fix any issues you find."#);
 
             Message::MergeOnlyConflicts { task_id } => {
                 if let Some(project) = self.model.active_project_mut() {
+                    project.finish_git_operation();
                     project.release_main_worktree_lock(task_id);
                 }
                 commands.push(Message::Error(
@@ -1423,12 +2824,20 @@ Do not ask for permission - run tests and fix any issues you find."#);
             }
 
             Message::MergeOnlyFailed { task_id, error } => {
+                let mut cancelled = false;
                 if let Some(project) = self.model.active_project_mut() {
+                    // Merging is only the running git operation once the background work started
+                    if project.git_operation_in_progress == Some(crate::model::GitOperation::Merging) {
+                        cancelled = project.finish_git_operation();
+                    }
                     project.release_main_worktree_lock(task_id);
                 }
-                if error.contains("Nothing to merge") {
+                if cancelled {
+                    commands.push(Message::SetStatusMessage(Some("Merge cancelled".to_string())));
+                } else if error.contains("Nothing to merge") {
                     commands.push(Message::SetStatusMessage(Some(error)));
                 } else {
+                    notify::play_cue(notify::SoundCue::MergeFailed);
                     commands.push(Message::Error(error));
                 }
             }
@@ -1463,7 +2872,7 @@ Do not ask for permission - run tests and fix any issues you find."#);
 
                     // Remove worktree (don't merge)
                     if let Some(ref wt_path) = worktree_path {
-                        if let Err(e) = crate::worktree::remove_worktree(&project_dir, wt_path) {
+                        if let Err(e) = crate::worktree::release_worktree(&project_dir, wt_path, self.worktree_pool_size(&project_dir)) {
                             commands.push(Message::SetStatusMessage(Some(
                                 format!("Warning: Could not remove worktree: {}", e)
                             )));
@@ -1534,7 +2943,7 @@ Do not ask for permission - run tests and fix any issues you find."#);
 
                     // Remove worktree if exists
                     if let Some(ref wt_path) = worktree_path {
-                        let _ = crate::worktree::remove_worktree(&project_dir, wt_path);
+                        let _ = crate::worktree::release_worktree(&project_dir, wt_path, self.worktree_pool_size(&project_dir));
                         // Clean up trust entry
                         let _ = crate::worktree::remove_worktree_trust(wt_path);
                     }
@@ -1544,6 +2953,10 @@ Do not ask for permission - run tests and fix any issues you find."#);
                         let _ = crate::worktree::delete_branch(&project_dir, &display_id);
                     }
 
+                    crate::audit::record(crate::audit::AuditEntry::new("reset_task", &project_dir)
+                        .with_task(&display_id)
+                        .with_detail(task_id.to_string()));
+
                     // Clean up signal files for this task to prevent stale signals
                     // from affecting state when the app restarts
                     // Note: Signal files use task_id as the session identifier
@@ -1614,7 +3027,7 @@ Do not ask for permission - run tests and fix any issues you find."#);
                     let branch_exists = std::process::Command::new("git")
                         .current_dir(&project_dir)
                         .args(["rev-parse", "--verify", &branch_name])
-                        .output()
+                        .timed_output()
                         .map(|o| o.status.success())
                         .unwrap_or(false);
 
@@ -1636,7 +3049,7 @@ Do not ask for permission - run tests and fix any issues you find."#);
                     let commits_output = std::process::Command::new("git")
                         .current_dir(&project_dir)
                         .args(["log", "--oneline", &format!("HEAD..{}", branch_name)])
-                        .output();
+                        .timed_output();
 
                     let has_commits = match &commits_output {
                         Ok(o) if o.status.success() => {
@@ -1660,7 +3073,7 @@ Do not ask for permission - run tests and fix any issues you find."#);
                     let has_diff = std::process::Command::new("git")
                         .current_dir(&project_dir)
                         .args(["diff", "--quiet", "HEAD", &branch_name])
-                        .status()
+                        .timed_status()
                         .map(|s| !s.success())
                         .unwrap_or(true);
 
@@ -1669,7 +3082,7 @@ Do not ask for permission - run tests and fix any issues you find."#);
                         let diff_stat = std::process::Command::new("git")
                             .current_dir(&project_dir)
                             .args(["diff", "--shortstat", "HEAD", &branch_name])
-                            .output()
+                            .timed_output()
                             .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string())
                             .unwrap_or_default();
                         report_lines.push(format!("Diff with main: YES - {}", diff_stat));
@@ -1778,6 +3191,10 @@ Do not ask for permission - run tests and fix any issues you find."#);
             }
 
             Message::OpenInteractiveDetached(task_id) => {
+                if let Some(banner) = self.capability_banner(&[Capability::Tmux, Capability::ClaudeCli]) {
+                    commands.push(Message::SetStatusMessage(Some(banner)));
+                    return commands;
+                }
                 // Gather task info
                 let task_info = self.model.active_project().and_then(|project| {
                     project.tasks.iter().find(|t| t.id == task_id).map(|task| {
@@ -1799,14 +3216,17 @@ Do not ask for permission - run tests and fix any issues you find."#);
                     // Stop SDK session first (if running) before CLI takeover
                     if let Some(ref client) = self.sidecar_client {
                         if let Err(e) = client.stop_session(task_id) {
-                            eprintln!("Note: Could not stop SDK session: {}", e);
+                            crate::diagnostics::warn(crate::diagnostics::Source::Sidecar, format!("Could not stop SDK session: {}", e));
                         }
                     }
 
                     let resume_session_id = session_id.as_deref();
                     let parent_session = crate::tmux::get_current_session_name();
+                    let layout = crate::tmux::SessionLayout::for_project(
+                        self.model.active_project().and_then(|p| p.session_layout.as_deref()),
+                    );
 
-                    match crate::tmux::open_popup_detached(&worktree_path, resume_session_id, parent_session.as_deref()) {
+                    match crate::tmux::open_popup_detached(&worktree_path, resume_session_id, parent_session.as_deref(), &layout) {
                         Ok(result) => {
                             let status = if result.was_created {
                                 format!("Created session '{}'", result.session_name)
@@ -1846,6 +3266,10 @@ Do not ask for permission - run tests and fix any issues you find."#);
                 }
 
                 // Try to acquire exclusive lock on main worktree
+                if let Some(reason) = self.shared_main_worktree_busy() {
+                    commands.push(Message::Error(reason));
+                    return commands;
+                }
                 if let Some(project) = self.model.active_project_mut() {
                     if let Err(reason) = project.try_lock_main_worktree(task_id, MainWorktreeOperation::Applying) {
                         commands.push(Message::Error(reason));
@@ -1924,7 +3348,7 @@ Do not ask for permission - run tests and fix any issues you find."#);
                     let branch_exists = std::process::Command::new("git")
                         .current_dir(&project_dir)
                         .args(["rev-parse", "--verify", &branch_name])
-                        .output()
+                        .timed_output()
                         .map(|o| o.status.success())
                         .unwrap_or(false);
 
@@ -1960,6 +3384,16 @@ Do not ask for permission - run tests and fix any issues you find."#);
                     }
 
                     // STEP 1: Try fast apply first
+                    // Snapshot projects record what's on disk first, so edits made
+                    // since the task started aren't treated as work to stash
+                    if let Err(e) = crate::worktree::snapshot::take(&project_dir) {
+                        if let Some(project) = self.model.active_project_mut() {
+                            project.release_main_worktree_lock(task_id);
+                        }
+                        commands.push(Message::Error(format!("Failed to snapshot the project: {}", e)));
+                        return commands;
+                    }
+
                     match crate::worktree::apply_task_changes(&project_dir, &display_id, &branch_name) {
                         Ok(stash_warning) => {
                             // Fast apply succeeded - stash was immediately popped
@@ -2055,7 +3489,7 @@ Do not ask for permission - run tests and fix any issues you find."#);
                                 let _ = std::process::Command::new("git")
                                     .current_dir(&project_dir)
                                     .args(["reset", "--hard", "HEAD"])
-                                    .output();
+                                    .timed_output();
                                 commands.push(Message::SetStatusMessage(Some(
                                     "Cleared stale conflict markers. Press 'a' to apply again.".to_string()
                                 )));
@@ -2236,7 +3670,7 @@ Do not ask for permission - run tests and fix any issues you find."#);
                             let pop_result = std::process::Command::new("git")
                                 .current_dir(&project_dir)
                                 .args(["stash", "pop", &stash_sha])
-                                .output();
+                                .timed_output();
 
                             match pop_result {
                                 Ok(output) if output.status.success() => {
@@ -2378,10 +3812,23 @@ Do not ask for permission - run tests and fix any issues you find."#);
             }
 
             Message::FastRebaseNeedsSmartRebase { task_id } => {
-                commands.push(Message::SetStatusMessage(Some(
-                    "Conflicts detected, starting smart update...".to_string()
-                )));
-                commands.push(Message::StartUpdateRebaseSession { task_id });
+                // Let the user pick how to deal with the conflicts
+                let display_id = self.get_task_display_id(task_id);
+                self.model.ui_state.confirmation_scroll_offset = 0;
+                self.model.ui_state.pending_confirmation = Some(PendingConfirmation {
+                    message: format!(
+                        "=== Rebase Conflict ===\n\n\
+                        Rebasing {} onto main hit conflicts (or broke the build).\n\
+                        The worktree is back where it was.\n\n\
+                        Smart update: Claude redoes the whole rebase in the task's session.\n\
+                        Hand off: the rebase stops at the conflicts and a session gets\n\
+                        the conflicting hunks to resolve and continue it.\n\n\
+                        [Y] Smart update  [H] Hand off conflicts  [N] Leave it in Review",
+                        display_id
+                    ),
+                    action: PendingAction::RebaseConflict(task_id),
+                    animation_tick: 20,
+                });
             }
 
             Message::FastRebaseFailed { task_id, error } => {
@@ -2487,33 +3934,219 @@ Do not ask for permission - run tests and fix any issues you find."#);
             }
 
             Message::RefreshGitStatus => {
+                if self.safe_mode {
+                    return commands;
+                }
+                if let Some(project_dir) = self.model.active_project().map(|p| p.working_dir.clone()) {
+                    commands.extend(self.check_applied_head(&project_dir));
+                }
                 // Refresh git status for all tasks with worktrees in the active project
                 if let Some(project) = self.model.active_project_mut() {
                     let project_dir = project.working_dir.clone();
+                    let ignore = project.status_ignore_patterns();
 
                     for task in project.tasks.iter_mut() {
                         // Only need worktree_path - branch name is derived from display_id
                         if task.worktree_path.is_some() {
                             // Update git status cache
                             let display_id = task.display_id();
-                            if let Ok(status) = crate::worktree::get_worktree_git_status(&project_dir, &display_id) {
-                                task.git_additions = status.additions;
-                                task.git_deletions = status.deletions;
-                                task.git_files_changed = status.files_changed;
-                                task.git_commits_ahead = status.commits_ahead;
-                                task.git_commits_behind = status.commits_behind;
-                                task.git_status_updated_at = Some(chrono::Utc::now());
+                            if let Ok(status) = crate::worktree::get_worktree_git_status(&project_dir, &display_id, &ignore) {
+                                task.set_git_status(&status);
                             }
                         }
                     }
                 }
-            }
 
-            // === Git remote operations (fetch/pull/push) ===
+                // Worktrees may have come or gone - keep the fs watchers in step
+                self.sync_git_watchers();
+            }
 
-            Message::StartGitFetch => {
-                // Check if there's already an operation in progress
-                if let Some(project) = self.model.active_project() {
+            Message::GitStatusChanged { project_dir, display_ids } => {
+                if self.safe_mode {
+                    return commands;
+                }
+                // Main moved: make sure it didn't take applied changes with it
+                if display_ids.is_empty() {
+                    commands.extend(self.check_applied_head(&project_dir));
+                }
+                // Recompute only the tasks whose refs moved, off the UI thread
+                // (projects scoped to parts of one repository share its watcher)
+                let sharing: Vec<&Project> = self.model.projects.iter().filter(|p| p.working_dir == project_dir).collect();
+                let Some(project) = sharing.first() else {
+                    return commands;
+                };
+                let targets: Vec<String> = sharing.iter()
+                    .flat_map(|p| p.tasks.iter())
+                    .filter(|t| t.worktree_path.is_some())
+                    .map(|t| t.display_id())
+                    .filter(|id| display_ids.is_empty() || display_ids.contains(id))
+                    .collect();
+                if targets.is_empty() {
+                    return commands;
+                }
+                let ignore = project.status_ignore_patterns();
+
+                if let Some(sender) = self.async_sender.clone() {
+                    if display_ids.is_empty() {
+                        self.model.ui_state.probing_git.insert(project_dir.clone());
+                    }
+                    tokio::spawn(async move {
+                        let dir = project_dir.clone();
+                        let result = tokio::task::spawn_blocking(move || {
+                            targets.into_iter()
+                                .filter_map(|id| {
+                                    crate::worktree::get_worktree_git_status(&dir, &id, &ignore).ok().map(|s| (id, s))
+                                })
+                                .collect::<Vec<_>>()
+                        }).await;
+
+                        // Sent even when it failed, so the cards stop showing a spinner
+                        let statuses = result.unwrap_or_default();
+                        let _ = sender.send(Message::GitStatusComputed { project_dir, statuses });
+                    });
+                }
+            }
+
+            Message::DetectIdleTasks => {
+                use crate::idle::{Backend, Candidate, Strategy};
+
+                let Some(sender) = self.async_sender.clone() else {
+                    return commands;
+                };
+                let now = Utc::now();
+                // What the probe needs to know about each task, so it can run off the UI thread
+                let tasks: Vec<_> = self.model.projects.iter()
+                    .flat_map(|p| p.tasks.iter().map(move |t| (p.slug(), t)))
+                    // Both could have finished while kanblam was closed
+                    .filter(|(_, t)| matches!(t.status, TaskStatus::InProgress | TaskStatus::NeedsWork))
+                    .map(|(slug, t)| (
+                        t.id, slug, t.tmux_window.clone(), t.session_mode, t.claude_session_id.is_some(), t.display_id(),
+                        Candidate {
+                            backend: Backend::Sdk,
+                            worktree_path: t.worktree_path.clone(),
+                            session_state: t.session_state,
+                            since_activity: t.last_activity_at.and_then(|at| (now - at).to_std().ok()),
+                        },
+                    ))
+                    .collect();
+                if tasks.is_empty() {
+                    return commands;
+                }
+                tokio::spawn(async move {
+                    let idle = tokio::task::spawn_blocking(move || {
+                        let (ids, candidates): (Vec<_>, Vec<_>) = tasks.into_iter()
+                            .filter_map(|(task_id, slug, window, mode, has_session, display_id, mut candidate)| {
+                                candidate.backend = match (window, mode) {
+                                    (Some(window), _) if crate::tmux::task_window_exists(&slug, &window) => {
+                                        Backend::TaskWindow(format!("kc-{}:{}", slug, window))
+                                    }
+                                    (_, crate::model::SessionMode::SdkManaged) if has_session => Backend::Sdk,
+                                    (_, crate::model::SessionMode::SdkManaged) => return None,
+                                    // Handoff sessions are named after the task, Claude in the top-left pane
+                                    _ => Backend::CliSession(format!("{}:.{{top-left}}", display_id)),
+                                };
+                                Some((task_id, candidate))
+                            })
+                            .unzip();
+                        let observations = crate::idle::observe_all(&candidates);
+                        ids.into_iter().zip(&candidates).zip(&observations)
+                            .filter_map(|((task_id, candidate), observation)| {
+                                let verdict = Strategy::for_backend(&candidate.backend).evaluate(observation);
+                                verdict.is_idle().then(|| (task_id, verdict.describe()))
+                            })
+                            .collect::<Vec<_>>()
+                    }).await.unwrap_or_default();
+                    let _ = sender.send(Message::IdleTasksDetected(idle));
+                });
+            }
+
+            Message::IdleTasksDetected(idle) => {
+                for (task_id, why) in idle {
+                    let Some(task) = self.model.projects.iter_mut()
+                        .flat_map(|p| p.tasks.iter_mut())
+                        .find(|t| t.id == task_id)
+                    else {
+                        continue;
+                    };
+                    // A signal may have moved it on while the probe ran
+                    if !matches!(task.status, TaskStatus::InProgress | TaskStatus::NeedsWork) {
+                        continue;
+                    }
+                    // Claude is waiting for input - move to Review
+                    task.status = TaskStatus::Review;
+                    task.session_state = crate::model::ClaudeSessionState::Paused;
+                    task.log_activity(format!("Found idle at startup ({})", why));
+                }
+            }
+
+            Message::GitStatusComputed { project_dir, statuses } => {
+                self.model.ui_state.probing_git.remove(&project_dir);
+                for project in self.model.projects.iter_mut().filter(|p| p.working_dir == project_dir) {
+                    for (display_id, status) in &statuses {
+                        if let Some(task) = project.tasks.iter_mut().find(|t| &t.display_id() == display_id) {
+                            task.set_git_status(status);
+                        }
+                    }
+                }
+            }
+
+            Message::BenchmarkGitBackend => {
+                let Some(project) = self.model.active_project() else {
+                    return commands;
+                };
+                let project_dir = project.working_dir.clone();
+                let display_ids: Vec<String> = project.tasks.iter()
+                    .filter(|t| t.worktree_path.is_some())
+                    .map(|t| t.display_id())
+                    .collect();
+                commands.push(Message::SetStatusMessage(Some(
+                    "Benchmarking native git backend...".to_string()
+                )));
+
+                if let Some(sender) = self.async_sender.clone() {
+                    tokio::spawn(async move {
+                        let result = tokio::task::spawn_blocking(move || {
+                            crate::worktree::native::benchmark(&project_dir, &display_ids)
+                        }).await;
+
+                        let msg = match result {
+                            Ok(result) => Message::GitBackendBenchmarked { result },
+                            Err(e) => Message::Error(format!("Git backend benchmark panicked: {}", e)),
+                        };
+                        let _ = sender.send(msg);
+                    });
+                }
+            }
+
+            Message::GitBackendBenchmarked { result } => {
+                let enable = result.native_wins();
+                self.model.global_settings.native_git = enable;
+                crate::worktree::native::set_enabled(enable);
+
+                let verdict = if enable {
+                    "Native git backend on".to_string()
+                } else if !result.mismatches.is_empty() {
+                    format!("Native git backend off - results differ ({})", result.mismatches.join(", "))
+                } else {
+                    "Native git backend off - not faster here".to_string()
+                };
+                commands.push(Message::SetStatusMessage(Some(format!("{}: {}", verdict, result.summary()))));
+                if enable {
+                    commands.push(Message::RefreshGitStatus);
+                }
+            }
+
+            // === Git remote operations (fetch/pull/push) ===
+
+            Message::StartGitFetch => {
+                if self.safe_mode {
+                    commands.push(Message::SetStatusMessage(Some(
+                        "Git operations are off in safe mode".to_string()
+                    )));
+                    return commands;
+                }
+                // Check if there's already an operation in progress
+                if let Some(project) = self.model.active_project() {
                     if project.git_operation_in_progress.is_some() {
                         commands.push(Message::SetStatusMessage(Some(
                             "Git operation already in progress".to_string()
@@ -2523,8 +4156,11 @@ Do not ask for permission - run tests and fix any issues you find."#);
                 }
 
                 // Set operation in progress
+                let cancel = crate::worktree::CancelToken::default();
                 if let Some(project) = self.model.active_project_mut() {
                     project.git_operation_in_progress = Some(crate::model::GitOperation::Fetching);
+                    project.git_progress = None;
+                    project.git_cancel = Some(cancel.clone());
                 }
 
                 // Get project dir for async operation
@@ -2533,10 +4169,13 @@ Do not ask for permission - run tests and fix any issues you find."#);
 
                 if let (Some(sender), Some(project_dir)) = (self.async_sender.clone(), project_dir) {
                     tokio::spawn(async move {
-                        // First fetch from remote
+                        // First fetch from remote (reporting progress, cancellable)
                         let fetch_result = tokio::task::spawn_blocking({
                             let dir = project_dir.clone();
-                            move || crate::worktree::git_fetch(&dir)
+                            let progress_sender = sender.clone();
+                            move || crate::worktree::git_fetch(&dir, &cancel, &mut |progress| {
+                                let _ = progress_sender.send(Message::GitOperationProgress(progress));
+                            })
                         }).await;
 
                         // Then get remote status
@@ -2573,19 +4212,26 @@ Do not ask for permission - run tests and fix any issues you find."#);
                     project.remote_ahead = ahead;
                     project.remote_behind = behind;
                     project.has_remote = true;
-                    project.git_operation_in_progress = None;
+                    // A merge may have taken over the status bar meanwhile
+                    if project.git_operation_in_progress == Some(crate::model::GitOperation::Fetching) {
+                        project.finish_git_operation();
+                    }
                 }
                 // Silent update - no status message for fetch
             }
 
             Message::GitFetchFailed { error } => {
                 if let Some(project) = self.model.active_project_mut() {
-                    project.git_operation_in_progress = None;
-                    // Don't show error for "no remote" case - it's expected
-                    if !error.contains("No remote") && !error.contains("no upstream") {
-                        commands.push(Message::SetStatusMessage(Some(
-                            format!("Fetch failed: {}", error)
-                        )));
+                    // A fetch cancelled to make way for a merge stays quiet
+                    if project.git_operation_in_progress == Some(crate::model::GitOperation::Fetching) {
+                        if project.finish_git_operation() {
+                            commands.push(Message::SetStatusMessage(Some("Fetch cancelled".to_string())));
+                        } else if !error.contains("No remote") && !error.contains("no upstream") {
+                            // Don't show error for "no remote" case - it's expected
+                            commands.push(Message::SetStatusMessage(Some(
+                                format!("Fetch failed: {}", error)
+                            )));
+                        }
                     }
                 }
             }
@@ -2609,8 +4255,11 @@ Do not ask for permission - run tests and fix any issues you find."#);
                 }
 
                 // Set operation in progress
+                let cancel = crate::worktree::CancelToken::default();
                 if let Some(project) = self.model.active_project_mut() {
                     project.git_operation_in_progress = Some(crate::model::GitOperation::Pulling);
+                    project.git_progress = None;
+                    project.git_cancel = Some(cancel.clone());
                 }
 
                 commands.push(Message::SetStatusMessage(Some(
@@ -2621,10 +4270,13 @@ Do not ask for permission - run tests and fix any issues you find."#);
                     .map(|p| p.working_dir.clone());
 
                 if let (Some(sender), Some(project_dir)) = (self.async_sender.clone(), project_dir) {
+                    let progress_sender = sender.clone();
                     tokio::spawn(async move {
                         let result = tokio::task::spawn_blocking(move || {
                             // Use smart_git_pull which handles .kanblam/tasks.json gracefully
-                            crate::worktree::smart_git_pull(&project_dir)
+                            crate::worktree::smart_git_pull(&project_dir, &cancel, &mut |progress| {
+                                let _ = progress_sender.send(Message::GitOperationProgress(progress));
+                            })
                         }).await;
 
                         let msg = match result {
@@ -2640,7 +4292,7 @@ Do not ask for permission - run tests and fix any issues you find."#);
 
             Message::GitPullCompleted { summary } => {
                 if let Some(project) = self.model.active_project_mut() {
-                    project.git_operation_in_progress = None;
+                    project.finish_git_operation();
                     project.remote_behind = 0; // We pulled, so we're up to date
                 }
                 commands.push(Message::SetStatusMessage(Some(
@@ -2651,12 +4303,13 @@ Do not ask for permission - run tests and fix any issues you find."#);
             }
 
             Message::GitPullFailed { error } => {
-                if let Some(project) = self.model.active_project_mut() {
-                    project.git_operation_in_progress = None;
-                }
-                commands.push(Message::SetStatusMessage(Some(
+                let cancelled = self.model.active_project_mut()
+                    .is_some_and(|project| project.finish_git_operation());
+                commands.push(Message::SetStatusMessage(Some(if cancelled {
+                    "Pull cancelled".to_string()
+                } else {
                     format!("Pull failed: {}", error)
-                )));
+                })));
             }
 
             Message::StartGitPush => {
@@ -2668,6 +4321,18 @@ Do not ask for permission - run tests and fix any issues you find."#);
                         )));
                         return commands;
                     }
+                    // Applied task changes are uncommitted work on main; a push
+                    // now would publish whatever was committed on top of them
+                    if let Some(applied_id) = project.applied_task_id {
+                        let display_id = project.tasks.iter().find(|t| t.id == applied_id)
+                            .map(|t| t.display_id())
+                            .unwrap_or_else(|| "a task".to_string());
+                        commands.push(Message::SetStatusMessage(Some(format!(
+                            "Push blocked: {} is applied to main. Unapply it (u) or accept it first",
+                            display_id
+                        ))));
+                        return commands;
+                    }
                     // Only skip if we've confirmed with remote that there's nothing to push
                     // (has_remote means we've successfully fetched at least once)
                     if project.has_remote && project.remote_ahead == 0 {
@@ -2679,8 +4344,11 @@ Do not ask for permission - run tests and fix any issues you find."#);
                 }
 
                 // Set operation in progress
+                let cancel = crate::worktree::CancelToken::default();
                 if let Some(project) = self.model.active_project_mut() {
                     project.git_operation_in_progress = Some(crate::model::GitOperation::Pushing);
+                    project.git_progress = None;
+                    project.git_cancel = Some(cancel.clone());
                 }
 
                 commands.push(Message::SetStatusMessage(Some(
@@ -2691,9 +4359,12 @@ Do not ask for permission - run tests and fix any issues you find."#);
                     .map(|p| p.working_dir.clone());
 
                 if let (Some(sender), Some(project_dir)) = (self.async_sender.clone(), project_dir) {
+                    let progress_sender = sender.clone();
                     tokio::spawn(async move {
                         let result = tokio::task::spawn_blocking(move || {
-                            crate::worktree::git_push(&project_dir)
+                            crate::worktree::git_push(&project_dir, &cancel, &mut |progress| {
+                                let _ = progress_sender.send(Message::GitOperationProgress(progress));
+                            })
                         }).await;
 
                         let msg = match result {
@@ -2709,7 +4380,7 @@ Do not ask for permission - run tests and fix any issues you find."#);
 
             Message::GitPushCompleted => {
                 if let Some(project) = self.model.active_project_mut() {
-                    project.git_operation_in_progress = None;
+                    project.finish_git_operation();
                     project.remote_ahead = 0; // We pushed, so we're up to date
                 }
                 commands.push(Message::SetStatusMessage(Some(
@@ -2719,12 +4390,40 @@ Do not ask for permission - run tests and fix any issues you find."#);
             }
 
             Message::GitPushFailed { error } => {
+                let cancelled = self.model.active_project_mut()
+                    .is_some_and(|project| project.finish_git_operation());
+                commands.push(Message::SetStatusMessage(Some(if cancelled {
+                    "Push cancelled".to_string()
+                } else {
+                    format!("Push failed: {}", error)
+                })));
+            }
+
+            Message::GitOperationProgress(progress) => {
                 if let Some(project) = self.model.active_project_mut() {
-                    project.git_operation_in_progress = None;
+                    if project.git_operation_in_progress.is_some() {
+                        project.git_progress = Some(progress);
+                    }
+                }
+            }
+
+            Message::CancelGitOperation => {
+                // The background task notices the flag, kills git and reports a *Failed message
+                let cancel = self.model.active_project().and_then(|p| p.git_cancel.clone());
+                match cancel {
+                    Some(cancel) if !cancel.is_cancelled() => {
+                        cancel.cancel();
+                        commands.push(Message::SetStatusMessage(Some(
+                            "Cancelling git operation...".to_string()
+                        )));
+                    }
+                    Some(_) => {}
+                    None => {
+                        commands.push(Message::SetStatusMessage(Some(
+                            "No git operation to cancel".to_string()
+                        )));
+                    }
                 }
-                commands.push(Message::SetStatusMessage(Some(
-                    format!("Push failed: {}", error)
-                )));
             }
 
             // === Task queueing ===
@@ -2834,7 +4533,7 @@ Do not ask for permission - run tests and fix any issues you find."#);
                 let next_task_info = self.model.active_project().and_then(|p| {
                     p.next_queued_for(finished_task_id).map(|t| (
                         t.id,
-                        t.title.clone(),
+                        t.title.clone() + &t.attachments_prompt(),
                         t.short_title.clone().unwrap_or_else(|| t.title.clone()), // For status display
                         t.images.clone(),
                         p.slug(),
@@ -2862,6 +4561,7 @@ Do not ask for permission - run tests and fix any issues you find."#);
                                 next_task.session_state = crate::model::ClaudeSessionState::Working;
                                 next_task.started_at = Some(Utc::now());
                                 next_task.queued_for_session = None; // Clear queue reference
+                                next_task.stacked_on = Some(finished_task_id); // Remember the chain for the graph
                             }
                             // Move to end of InProgress column so newly active tasks appear at bottom
                             project.move_task_to_start_of_status(next_task_id, TaskStatus::InProgress);
@@ -2893,588 +4593,1513 @@ Do not ask for permission - run tests and fix any issues you find."#);
                 }
             }
 
-            // === End of worktree-based task lifecycle ===
+            Message::ToggleDependencyGraph => {
+                self.model.ui_state.show_dependency_graph = !self.model.ui_state.show_dependency_graph;
+                if self.model.ui_state.show_dependency_graph {
+                    // Start on the currently selected task if it's part of the graph
+                    let selected_id = self.model.ui_state.selected_task_id;
+                    self.model.ui_state.dependency_graph_selected_idx = self.model.active_project()
+                        .and_then(|p| p.dependency_graph().iter().position(|row| Some(row.task_id) == selected_id))
+                        .unwrap_or(0);
+                }
+            }
 
-            Message::SelectTask(idx) => {
-                self.model.ui_state.selected_task_idx = idx;
-                self.model.ui_state.title_scroll_offset = 0;
-                self.model.ui_state.title_scroll_delay = 0;
+            Message::DependencyGraphNavigate(delta) => {
+                let count = self.model.active_project()
+                    .map(|p| p.dependency_graph().len())
+                    .unwrap_or(0);
+                if count > 0 {
+                    let current = self.model.ui_state.dependency_graph_selected_idx as i32;
+                    self.model.ui_state.dependency_graph_selected_idx = (current + delta).clamp(0, count as i32 - 1) as usize;
+                }
             }
 
-            Message::SelectColumn(status) => {
-                self.model.ui_state.selected_column = status;
-                self.model.ui_state.focus = FocusArea::KanbanBoard;
-                // Select first task in column if any exist
-                let has_tasks = self.model.active_project()
-                    .map(|p| !p.tasks_by_status(status).is_empty())
-                    .unwrap_or(false);
-                self.model.ui_state.selected_task_idx = if has_tasks { Some(0) } else { None };
-                self.model.ui_state.title_scroll_offset = 0;
-                self.model.ui_state.title_scroll_delay = 0;
+            Message::DependencyGraphSelect => {
+                let task_id = self.model.active_project()
+                    .and_then(|p| p.dependency_graph().get(self.model.ui_state.dependency_graph_selected_idx).map(|row| row.task_id));
+                if let Some(task_id) = task_id {
+                    self.model.ui_state.show_dependency_graph = false;
+                    self.save_scroll_offset();
+                    // sync_selection follows the task into whichever column it lives in
+                    self.model.ui_state.selected_task_id = Some(task_id);
+                    self.sync_selection();
+                    self.model.ui_state.focus = FocusArea::KanbanBoard;
+                    self.model.ui_state.title_scroll_offset = 0;
+                    self.model.ui_state.title_scroll_delay = 0;
+                }
             }
 
-            Message::ClickedTask { status, task_idx } => {
-                self.model.ui_state.selected_column = status;
-                self.model.ui_state.selected_task_idx = Some(task_idx);
-                self.model.ui_state.focus = FocusArea::KanbanBoard;
-                self.model.ui_state.title_scroll_offset = 0;
-                self.model.ui_state.title_scroll_delay = 0;
+            Message::ShowReleaseNotes => {
+                if let Some(project) = self.model.active_project() {
+                    let since_input = crate::worktree::latest_tag(&project.working_dir).unwrap_or_default();
+                    self.model.ui_state.release_notes = Some(crate::model::ReleaseNotesState {
+                        since_input,
+                        ..Default::default()
+                    });
+                }
             }
 
-            Message::SwitchProject(idx) => {
-                if idx < self.model.projects.len() {
-                    self.model.active_project_idx = idx;
-                    self.model.ui_state.selected_task_idx = None;
-                    self.model.ui_state.focus = FocusArea::KanbanBoard;
+            Message::CloseReleaseNotes => {
+                self.model.ui_state.release_notes = None;
+            }
 
-                    // Refresh git status for the new project
-                    commands.push(Message::RefreshGitStatus);
-                    // Also fetch from remote to update ahead/behind indicators
-                    commands.push(Message::StartGitFetch);
+            Message::ReleaseNotesSinceChanged(since) => {
+                // Editing the starting point discards the compiled preview
+                if let Some(ref mut notes) = self.model.ui_state.release_notes {
+                    notes.since_input = since;
+                    notes.markdown = None;
+                    notes.polished = None;
+                    notes.show_polished = false;
                 }
             }
 
-            Message::AddProject { name, working_dir } => {
-                let project = Project::new(name, working_dir);
-                self.model.projects.push(project);
+            Message::CompileReleaseNotes => {
+                let Some(since_input) = self.model.ui_state.release_notes.as_ref().map(|n| n.since_input.trim().to_string()) else {
+                    return commands;
+                };
+                let Some(project) = self.model.active_project() else {
+                    return commands;
+                };
+
+                match crate::release::resolve_since(&project.working_dir, &since_input) {
+                    Ok(since) => {
+                        let markdown = crate::release::compile_markdown(&project.name, &since_input, since, &project.tasks);
+                        if let Some(ref mut notes) = self.model.ui_state.release_notes {
+                            notes.markdown = Some(markdown);
+                            notes.polished = None;
+                            notes.show_polished = false;
+                            notes.scroll_offset = 0;
+                        }
+                    }
+                    Err(e) => {
+                        commands.push(Message::SetStatusMessage(Some(format!("Release notes: {}", e))));
+                    }
+                }
             }
 
-            Message::ShowOpenProjectDialog { slot } => {
-                self.model.ui_state.open_project_dialog_slot = Some(slot);
-                // Create a directory browser starting at home directory
-                let start_dir = dirs::home_dir().unwrap_or_else(|| std::path::PathBuf::from("/"));
-                if let Ok(browser) = crate::model::DirectoryBrowser::new(start_dir) {
-                    self.model.ui_state.directory_browser = Some(browser);
+            Message::PolishReleaseNotes => {
+                let markdown = self.model.ui_state.release_notes.as_ref()
+                    .filter(|n| !n.polishing)
+                    .and_then(|n| n.markdown.clone());
+                let project_dir = self.model.active_project().map(|p| p.working_dir.clone());
+
+                if let (Some(markdown), Some(sender)) = (markdown, self.async_sender.clone()) {
+                    if let Some(ref mut notes) = self.model.ui_state.release_notes {
+                        notes.polishing = true;
+                    }
+                    commands.push(Message::SetStatusMessage(Some("Polishing release notes...".to_string())));
+
+                    let prompt = crate::release::polish_prompt(&markdown);
+                    tokio::spawn(async move {
+                        let result = tokio::task::spawn_blocking(move || {
+                            crate::sidecar::SidecarClient::complete_prompt_standalone(prompt, project_dir)
+                        }).await;
+
+                        let msg = match result {
+                            Ok(Ok(text)) => Message::ReleaseNotesPolished { text },
+                            Ok(Err(e)) => Message::ReleaseNotesPolishFailed { error: e.to_string() },
+                            Err(e) => Message::ReleaseNotesPolishFailed { error: format!("Task panicked: {}", e) },
+                        };
+
+                        let _ = sender.send(msg);
+                    });
                 }
             }
 
-            Message::CloseOpenProjectDialog => {
-                self.model.ui_state.open_project_dialog_slot = None;
-                self.model.ui_state.directory_browser = None;
-                self.model.ui_state.create_folder_input = None;
+            Message::ReleaseNotesPolished { text } => {
+                if let Some(ref mut notes) = self.model.ui_state.release_notes {
+                    notes.polishing = false;
+                    notes.polished = Some(text);
+                    notes.show_polished = true;
+                    notes.scroll_offset = 0;
+                }
+                commands.push(Message::SetStatusMessage(Some("Release notes polished".to_string())));
             }
 
-            Message::EnterCreateFolderMode => {
-                // Enter create folder mode with empty input
-                self.model.ui_state.create_folder_input = Some(String::new());
+            Message::ReleaseNotesPolishFailed { error } => {
+                if let Some(ref mut notes) = self.model.ui_state.release_notes {
+                    notes.polishing = false;
+                }
+                commands.push(Message::SetStatusMessage(Some(format!("Polish failed: {}", error))));
             }
 
-            Message::CancelCreateFolderMode => {
-                // Exit create folder mode
-                self.model.ui_state.create_folder_input = None;
+            Message::ToggleReleaseNotesPolished => {
+                if let Some(ref mut notes) = self.model.ui_state.release_notes {
+                    if notes.polished.is_some() {
+                        notes.show_polished = !notes.show_polished;
+                        notes.scroll_offset = 0;
+                    }
+                }
             }
 
-            Message::CreateFolder { name } => {
-                // Create a new folder with git init in the current directory
-                if let Some(ref mut browser) = self.model.ui_state.directory_browser {
-                    match browser.create_folder(&name) {
-                        Ok(path) => {
-                            // Clear create mode
-                            self.model.ui_state.create_folder_input = None;
+            Message::ScrollReleaseNotes(delta) => {
+                if let Some(ref mut notes) = self.model.ui_state.release_notes {
+                    let max_scroll = notes.current_text().map(|t| t.lines().count()).unwrap_or(0).saturating_sub(1);
+                    notes.scroll_offset = (notes.scroll_offset as i32 + delta).clamp(0, max_scroll as i32) as usize;
+                }
+            }
 
-                            // Show success message
-                            commands.push(Message::SetStatusMessage(Some(
-                                format!("Created folder '{}' with git init", name)
-                            )));
+            Message::WriteReleaseNotes => {
+                let text = self.model.ui_state.release_notes.as_ref()
+                    .and_then(|n| n.current_text().map(str::to_string));
+                if let (Some(text), Some(project)) = (text, self.model.active_project()) {
+                    let path = project.working_dir.join(crate::release::RELEASE_NOTES_FILE);
+                    let status = match std::fs::write(&path, text) {
+                        Ok(()) => format!("Release notes written to {}", crate::release::RELEASE_NOTES_FILE),
+                        Err(e) => format!("Failed to write release notes: {}", e),
+                    };
+                    commands.push(Message::SetStatusMessage(Some(status)));
+                }
+            }
 
-                            // Optionally, we could auto-open the new folder as a project
-                            // For now, just leave it selected in the browser
-                            let _ = path; // The browser is already updated with the new folder selected
-                        }
-                        Err(e) => {
-                            // Show error message but stay in create mode for retry
-                            commands.push(Message::SetStatusMessage(Some(
-                                format!("Failed to create folder: {}", e)
-                            )));
-                        }
+            Message::CopyReleaseNotes => {
+                let text = self.model.ui_state.release_notes.as_ref()
+                    .and_then(|n| n.current_text().map(str::to_string));
+                if let Some(text) = text {
+                    let status = match crate::image::copy_text_to_clipboard(&text) {
+                        Ok(()) => "Release notes copied to clipboard".to_string(),
+                        Err(e) => format!("Failed to copy release notes: {}", e),
+                    };
+                    commands.push(Message::SetStatusMessage(Some(status)));
+                }
+            }
+
+            Message::SparsePromptChanged(input) => {
+                if let Some(ref mut prompt) = self.model.ui_state.sparse_prompt {
+                    prompt.input = input;
+                }
+            }
+
+            Message::ConfirmSparsePrompt => {
+                if let Some(prompt) = self.model.ui_state.sparse_prompt.take() {
+                    let input = prompt.input.trim().to_string();
+                    if let Some(task) = self.model.active_project_mut()
+                        .and_then(|p| p.tasks.iter_mut().find(|t| t.id == prompt.task_id))
+                    {
+                        task.log_activity(if input.is_empty() {
+                            "Sparse checkout: full".to_string()
+                        } else {
+                            format!("Sparse checkout: {}", input)
+                        });
+                        task.sparse_paths = Some(input);
                     }
+                    commands.push(Message::StartTaskWithWorktree(prompt.task_id));
                 }
             }
 
-            Message::ConfirmOpenProject => {
-                if let Some(slot) = self.model.ui_state.open_project_dialog_slot {
-                    if let Some(ref browser) = self.model.ui_state.directory_browser {
-                        // Use the selected (cursor) directory as the project path
-                        if let Some(selected) = browser.selected() {
-                            // Don't allow selecting ".." or special entries as project
-                            if selected.special != crate::model::SpecialEntry::None {
-                                commands.push(Message::SetStatusMessage(Some(
-                                    "Cannot select this item - use [New Project Here] or navigate into a directory".to_string()
-                                )));
-                            } else {
-                                let path = selected.path.clone();
+            Message::CancelSparsePrompt => {
+                if self.model.ui_state.sparse_prompt.take().is_some() {
+                    commands.push(Message::SetStatusMessage(Some("Start cancelled.".to_string())));
+                }
+            }
 
-                                // Check if this project is already open
-                                if let Some(existing_project) = self.model.projects.iter().find(|p| p.working_dir == path) {
-                                    commands.push(Message::SetStatusMessage(Some(
-                                        format!("Project '{}' is already open", existing_project.name)
-                                    )));
-                                    // Close the dialog
-                                    self.model.ui_state.open_project_dialog_slot = None;
-                                    self.model.ui_state.directory_browser = None;
-                                } else {
-                                    // Use the directory name as the project name
-                                    let name = path
-                                        .file_name()
-                                        .and_then(|n| n.to_str())
-                                        .unwrap_or("project")
-                                        .to_string();
-
-                                    // Check git status and offer to initialize if needed
-                                    let is_git = crate::worktree::git::is_git_repo(&path);
-                                    let has_commits = is_git && crate::worktree::git::has_commits(&path);
+            Message::CheckReleaseAfterMerge(task_id) => {
+                use crate::release::version::{plan_release, VersionRules, DEFAULT_VERSION_RULES, RELEASE_LABEL};
 
-                                    if !is_git {
-                                        // Not a git repo - offer to initialize
-                                        commands.push(Message::ShowConfirmation {
-                                            message: format!(
-                                                "'{}' is not a git repository.\n\nInitialize git? (y/n)",
-                                                name
-                                            ),
-                                            action: PendingAction::InitGit {
-                                                path: path.clone(),
-                                                name: name.clone(),
-                                                slot,
-                                            },
-                                        });
-                                        // Close the browser dialog (confirmation will handle opening)
-                                        self.model.ui_state.open_project_dialog_slot = None;
-                                        self.model.ui_state.directory_browser = None;
-                                    } else if !has_commits {
-                                        // Git repo but no commits - offer to create initial commit
-                                        commands.push(Message::ShowConfirmation {
-                                            message: format!(
-                                                "'{}' has no commits.\n\nCreate initial commit? (y/n)",
-                                                name
-                                            ),
-                                            action: PendingAction::CreateInitialCommit {
-                                                path: path.clone(),
-                                                name: name.clone(),
-                                                slot,
-                                            },
-                                        });
-                                        // Close the browser dialog (confirmation will handle opening)
-                                        self.model.ui_state.open_project_dialog_slot = None;
-                                        self.model.ui_state.directory_browser = None;
-                                    } else {
-                                        // Valid git repo with commits - check .gitignore
-                                        let missing_entries =
-                                            crate::worktree::git::gitignore_missing_kanblam_entries(&path);
-                                        if !missing_entries.is_empty() {
-                                            // Ask permission to add missing entries
-                                            commands.push(Message::ShowConfirmation {
-                                                message: format!(
-                                                    "'{}' .gitignore is missing KanBlam entries:\n  {}\n\nAdd them? (y/n)",
-                                                    name,
-                                                    missing_entries.join(", ")
-                                                ),
-                                                action: PendingAction::UpdateGitignore {
-                                                    path: path.clone(),
-                                                    name: name.clone(),
-                                                    slot,
-                                                    missing_entries,
-                                                },
-                                            });
-                                            // Close the browser dialog (confirmation will handle opening)
-                                            self.model.ui_state.open_project_dialog_slot = None;
-                                            self.model.ui_state.directory_browser = None;
-                                        } else {
-                                            // All good - open directly
-                                            let mut project = Project::new(name, path);
-                                            // Load any existing tasks from the project's .kanblam/tasks.json
-                                            project.load_tasks();
-                                            let has_tasks = !project.tasks.is_empty();
-                                            self.model.projects.push(project);
-                                            self.model.active_project_idx = slot;
-                                            self.model.ui_state.selected_task_idx = None;
-                                            // Focus TaskInput if project has no tasks, otherwise KanbanBoard
-                                            self.model.ui_state.focus = if has_tasks {
-                                                FocusArea::KanbanBoard
-                                            } else {
-                                                FocusArea::TaskInput
-                                            };
+                let Some(project) = self.model.active_project() else {
+                    return commands;
+                };
+                let is_release = project.tasks.iter()
+                    .find(|t| t.id == task_id)
+                    .is_some_and(|t| t.labels().iter().any(|l| l == RELEASE_LABEL));
+                if !project.auto_version || !is_release {
+                    return commands;
+                }
 
-                                            // Close the dialog
-                                            self.model.ui_state.open_project_dialog_slot = None;
-                                            self.model.ui_state.directory_browser = None;
-                                        }
-                                    }
-                                }
-                            }
+                // Labels of everything merged since the last tag decide the bump
+                let since = crate::worktree::latest_tag(&project.working_dir)
+                    .and_then(|tag| crate::worktree::ref_commit_date(&project.working_dir, &tag).ok());
+                let mut labels: Vec<String> = Vec::new();
+                for task in project.tasks.iter().filter(|t| match (t.merged_at, since) {
+                    (Some(at), Some(since)) => at >= since,
+                    (Some(_), None) => true,
+                    (None, _) => false,
+                }) {
+                    for label in task.labels() {
+                        if !labels.contains(&label) {
+                            labels.push(label);
                         }
                     }
                 }
+
+                let rules = VersionRules::parse(project.version_rules.as_deref().unwrap_or(DEFAULT_VERSION_RULES));
+                let plan = plan_release(&project.working_dir, &rules, labels);
+                commands.push(Message::ShowConfirmation {
+                    message: plan.preview(),
+                    action: PendingAction::ReleaseVersion { plan },
+                });
             }
 
-            Message::ConfirmOpenProjectPath(path) => {
-                if let Some(slot) = self.model.ui_state.open_project_dialog_slot {
-                    // Check if this project is already open
-                    if let Some(existing_project) = self.model.projects.iter().find(|p| p.working_dir == path) {
-                        commands.push(Message::SetStatusMessage(Some(
-                            format!("Project '{}' is already open", existing_project.name)
-                        )));
-                        // Close the dialog
-                        self.model.ui_state.open_project_dialog_slot = None;
-                        self.model.ui_state.directory_browser = None;
-                    } else {
-                        // Use the directory name as the project name
-                        let name = path
-                            .file_name()
-                            .and_then(|n| n.to_str())
-                            .unwrap_or("project")
-                            .to_string();
+            Message::ShowCodeSearch => {
+                if self.model.active_project().is_some() {
+                    self.model.ui_state.code_search = Some(crate::model::CodeSearchState {
+                        editing_query: true,
+                        ..Default::default()
+                    });
+                }
+            }
 
-                        // Check git status and offer to initialize if needed
-                        let is_git = crate::worktree::git::is_git_repo(&path);
-                        let has_commits = is_git && crate::worktree::git::has_commits(&path);
+            Message::CloseCodeSearch => {
+                self.model.ui_state.code_search = None;
+            }
 
-                        if !is_git {
-                            // Not a git repo - offer to initialize
-                            commands.push(Message::ShowConfirmation {
-                                message: format!(
-                                    "'{}' is not a git repository.\n\nInitialize git? (y/n)",
-                                    name
-                                ),
-                                action: PendingAction::InitGit {
-                                    path: path.clone(),
-                                    name: name.clone(),
-                                    slot,
-                                },
-                            });
-                            // Close the browser dialog (confirmation will handle opening)
-                            self.model.ui_state.open_project_dialog_slot = None;
-                            self.model.ui_state.directory_browser = None;
-                        } else if !has_commits {
-                            // Git repo but no commits - offer to create initial commit
-                            commands.push(Message::ShowConfirmation {
-                                message: format!(
-                                    "'{}' has no commits.\n\nCreate initial commit? (y/n)",
-                                    name
-                                ),
-                                action: PendingAction::CreateInitialCommit {
-                                    path: path.clone(),
-                                    name: name.clone(),
-                                    slot,
-                                },
-                            });
-                            // Close the browser dialog (confirmation will handle opening)
-                            self.model.ui_state.open_project_dialog_slot = None;
-                            self.model.ui_state.directory_browser = None;
-                        } else {
-                            // Valid git repo with commits - check .gitignore
-                            let missing_entries =
-                                crate::worktree::git::gitignore_missing_kanblam_entries(&path);
-                            if !missing_entries.is_empty() {
-                                // Ask permission to add missing entries
-                                commands.push(Message::ShowConfirmation {
-                                    message: format!(
-                                        "'{}' .gitignore is missing KanBlam entries:\n  {}\n\nAdd them? (y/n)",
-                                        name,
-                                        missing_entries.join(", ")
-                                    ),
-                                    action: PendingAction::UpdateGitignore {
-                                        path: path.clone(),
-                                        name: name.clone(),
-                                        slot,
-                                        missing_entries,
-                                    },
-                                });
-                                // Close the browser dialog (confirmation will handle opening)
-                                self.model.ui_state.open_project_dialog_slot = None;
-                                self.model.ui_state.directory_browser = None;
-                            } else {
-                                // All good - open directly
-                                let mut project = Project::new(name, path);
-                                // Load any existing tasks from the project's .kanblam/tasks.json
-                                project.load_tasks();
-                                let has_tasks = !project.tasks.is_empty();
-                                self.model.projects.push(project);
-                                self.model.active_project_idx = slot;
-                                self.model.ui_state.selected_task_idx = None;
-                                // Focus TaskInput if project has no tasks, otherwise KanbanBoard
-                                self.model.ui_state.focus = if has_tasks {
-                                    FocusArea::KanbanBoard
-                                } else {
-                                    FocusArea::TaskInput
-                                };
+            Message::CodeSearchQueryChanged(query) => {
+                if let Some(ref mut search) = self.model.ui_state.code_search {
+                    search.query = query;
+                    search.editing_query = true;
+                    search.error = None;
+                }
+            }
 
-                                // Close the dialog
-                                self.model.ui_state.open_project_dialog_slot = None;
-                                self.model.ui_state.directory_browser = None;
-                            }
+            Message::RunCodeSearch => {
+                let Some(query) = self.model.ui_state.code_search.as_ref().map(|s| s.query.clone()) else {
+                    return commands;
+                };
+                let Some(project_dir) = self.model.active_project().map(|p| p.working_dir.clone()) else {
+                    return commands;
+                };
+                if let Some(ref mut search) = self.model.ui_state.code_search {
+                    search.searching = true;
+                    search.editing_query = false;
+                    search.error = None;
+                }
+
+                if let Some(sender) = self.async_sender.clone() {
+                    tokio::spawn(async move {
+                        let result = tokio::task::spawn_blocking(move || {
+                            crate::search::search(&project_dir, &query)
+                        }).await;
+
+                        let msg = match result {
+                            Ok(Ok(results)) => Message::CodeSearchResults { results },
+                            Ok(Err(e)) => Message::CodeSearchFailed { error: e.to_string() },
+                            Err(e) => Message::CodeSearchFailed { error: format!("Task panicked: {}", e) },
+                        };
+                        let _ = sender.send(msg);
+                    });
+                }
+            }
+
+            Message::CodeSearchResults { results } => {
+                if let Some(ref mut search) = self.model.ui_state.code_search {
+                    search.searching = false;
+                    search.results = results;
+                    search.selected.clear();
+                    search.cursor = 0;
+                }
+            }
+
+            Message::CodeSearchFailed { error } => {
+                if let Some(ref mut search) = self.model.ui_state.code_search {
+                    search.searching = false;
+                    search.editing_query = true;
+                    search.error = Some(error);
+                }
+            }
+
+            Message::CodeSearchNavigate(delta) => {
+                if let Some(ref mut search) = self.model.ui_state.code_search {
+                    let max = search.results.len().saturating_sub(1) as i32;
+                    search.cursor = (search.cursor as i32 + delta).clamp(0, max) as usize;
+                }
+            }
+
+            Message::CodeSearchToggleSelect => {
+                if let Some(ref mut search) = self.model.ui_state.code_search {
+                    if search.cursor < search.results.len() {
+                        let cursor = search.cursor;
+                        if let Some(pos) = search.selected.iter().position(|&i| i == cursor) {
+                            search.selected.remove(pos);
+                        } else {
+                            search.selected.push(cursor);
                         }
                     }
                 }
             }
 
-            Message::CloseProject(idx) => {
-                if idx < self.model.projects.len() {
-                    let project = &self.model.projects[idx];
-                    // Check if project has active tasks
-                    if project.has_active_task() {
-                        let name = project.name.clone();
-                        commands.push(Message::ShowConfirmation {
-                            message: format!(
-                                "Project '{}' has active tasks. Close anyway? (y/n)",
-                                name
-                            ),
-                            action: PendingAction::CloseProject(idx),
-                        });
+            Message::CodeSearchToggleAll => {
+                if let Some(ref mut search) = self.model.ui_state.code_search {
+                    if search.selected.len() == search.results.len() {
+                        search.selected.clear();
                     } else {
-                        // No active tasks, close directly
-                        // Save tasks before closing
-                        if let Err(e) = self.model.projects[idx].save_tasks() {
-                            eprintln!("Warning: Failed to save tasks before closing: {}", e);
-                        }
-                        self.model.projects.remove(idx);
-                        // Adjust active project index
-                        if self.model.projects.is_empty() {
-                            self.model.active_project_idx = 0;
-                        } else if self.model.active_project_idx >= self.model.projects.len() {
-                            self.model.active_project_idx = self.model.projects.len() - 1;
-                        }
-                        // Reset selection
-                        self.model.ui_state.selected_task_idx = None;
-                        // Save global state so closed project doesn't reappear
-                        if let Err(e) = save_state(&self.model, self.state_file_path.as_ref()) {
-                            eprintln!("Warning: Failed to save state after closing project: {}", e);
-                        }
+                        search.selected = (0..search.results.len()).collect();
                     }
                 }
             }
 
-            Message::ShowConfirmation { message, action } => {
-                self.model.ui_state.pending_confirmation = Some(PendingConfirmation {
-                    message,
-                    action,
-                    animation_tick: 20, // Start sweep animation (same duration as startup hints)
-                });
-                // Reset scroll offset for new confirmation
-                self.model.ui_state.confirmation_scroll_offset = 0;
-            }
+            Message::CreateTaskFromSearch => {
+                let Some(search) = self.model.ui_state.code_search.take() else {
+                    return commands;
+                };
+                // Selected locations in result order, or just the highlighted one
+                let mut picked: Vec<&crate::search::SearchMatch> = search.results.iter()
+                    .enumerate()
+                    .filter(|(i, _)| search.selected.contains(i))
+                    .map(|(_, m)| m)
+                    .collect();
+                if picked.is_empty() {
+                    picked.extend(search.results.get(search.cursor));
+                }
+                if picked.is_empty() {
+                    return commands;
+                }
 
-            Message::ConfirmAction => {
-                // Reset scroll offset when confirmation is dismissed
-                self.model.ui_state.confirmation_scroll_offset = 0;
-                if let Some(confirmation) = self.model.ui_state.pending_confirmation.take() {
-                    match confirmation.action {
-                        PendingAction::DeleteTask(task_id) => {
-                            // Actually delete the task
-                            commands.push(Message::DeleteTask(task_id));
-                        }
-                        PendingAction::MarkDoneNoMerge(task_id) => {
-                            // Mark task as done and clean up worktree without merging
-                            // Stop SDK session first (if running)
-                            if let Some(ref client) = self.sidecar_client {
-                                let _ = client.stop_session(task_id);
-                            }
+                let description = crate::search::task_description(&search.query, &picked);
+                self.model.ui_state.set_input_text(&description);
+                self.model.ui_state.focus = FocusArea::TaskInput;
+                commands.push(Message::SetStatusMessage(Some(format!(
+                    "{} location(s) added - describe the change on the first line",
+                    picked.len()
+                ))));
+            }
 
-                            // Get task info needed for cleanup
-                            let task_info = self.model.active_project().and_then(|p| {
-                                p.tasks.iter()
-                                    .find(|t| t.id == task_id)
-                                    .map(|t| (
-                                        p.slug(),
-                                        p.working_dir.clone(),
-                                        t.tmux_window.clone(),
-                                        t.worktree_path.clone(),
-                                        t.display_id(),
-                                    ))
-                            });
+            Message::ShowTodoScan => {
+                let Some(project_dir) = self.model.active_project().map(|p| p.working_dir.clone()) else {
+                    return commands;
+                };
+                self.model.ui_state.todo_scan = Some(crate::model::TodoScanState {
+                    scanning: true,
+                    ..Default::default()
+                });
 
-                            if let Some((project_slug, project_dir, window_name, worktree_path, display_id)) = task_info {
-                                // Kill tmux window if exists
-                                if let Some(ref window) = window_name {
-                                    let _ = crate::tmux::kill_task_window(&project_slug, window);
-                                }
+                if let Some(sender) = self.async_sender.clone() {
+                    tokio::spawn(async move {
+                        let result = tokio::task::spawn_blocking(move || {
+                            crate::search::todo::scan(&project_dir)
+                        }).await;
 
-                                // Kill any detached Claude/test sessions for this task (uses display_id as session name)
-                                crate::tmux::kill_task_sessions(&display_id);
+                        let msg = match result {
+                            Ok(Ok(items)) => Message::TodoScanResults { items },
+                            Ok(Err(e)) => Message::TodoScanFailed { error: e.to_string() },
+                            Err(e) => Message::TodoScanFailed { error: format!("Task panicked: {}", e) },
+                        };
+                        let _ = sender.send(msg);
+                    });
+                }
+            }
 
-                                // Remove worktree
-                                if let Some(ref wt_path) = worktree_path {
-                                    if let Err(e) = crate::worktree::remove_worktree(&project_dir, wt_path) {
-                                        commands.push(Message::SetStatusMessage(Some(
-                                            format!("Warning: Could not remove worktree: {}", e)
-                                        )));
-                                    }
-                                    // Clean up trust entry from Claude's config
-                                    let _ = crate::worktree::remove_worktree_trust(wt_path);
-                                }
+            Message::CloseTodoScan => {
+                self.model.ui_state.todo_scan = None;
+            }
 
-                                // Delete branch
-                                if let Err(e) = crate::worktree::delete_branch(&project_dir, &display_id) {
-                                    commands.push(Message::SetStatusMessage(Some(
-                                        format!("Warning: Could not delete branch: {}", e)
-                                    )));
-                                }
+            Message::TodoScanResults { items } => {
+                if let Some(ref mut scan) = self.model.ui_state.todo_scan {
+                    scan.scanning = false;
+                    scan.selected = vec![false; items.len()];
+                    scan.items = items;
+                    scan.sort_items();
+                }
+            }
 
-                                // Complete task (records stats) and move to Done
-                                if let Some(project) = self.model.active_project_mut() {
-                                    project.complete_task(task_id);
-                                    project.needs_attention = project.review_count() > 0;
-                                    if !project.needs_attention {
-                                        notify::clear_attention_indicator();
-                                    }
-                                }
+            Message::TodoScanFailed { error } => {
+                if let Some(ref mut scan) = self.model.ui_state.todo_scan {
+                    scan.scanning = false;
+                    scan.error = Some(error);
+                }
+            }
 
-                                commands.push(Message::SetStatusMessage(Some(
-                                    "Task marked as done. Worktree cleaned up.".to_string()
-                                )));
-                            }
-                        }
-                        PendingAction::CloseProject(idx) => {
-                            // Close the project (user confirmed)
-                            if idx < self.model.projects.len() {
-                                // Save tasks before closing
-                                if let Err(e) = self.model.projects[idx].save_tasks() {
-                                    eprintln!("Warning: Failed to save tasks before closing: {}", e);
-                                }
-                                self.model.projects.remove(idx);
-                                // Adjust active project index
-                                if self.model.projects.is_empty() {
-                                    self.model.active_project_idx = 0;
-                                } else if self.model.active_project_idx >= self.model.projects.len() {
-                                    self.model.active_project_idx = self.model.projects.len() - 1;
-                                }
-                                // Reset selection
-                                self.model.ui_state.selected_task_idx = None;
-                                // Save global state so closed project doesn't reappear
-                                if let Err(e) = save_state(&self.model, self.state_file_path.as_ref()) {
-                                    eprintln!("Warning: Failed to save state after closing project: {}", e);
-                                }
-                            }
-                        }
-                        PendingAction::AcceptTask(task_id) => {
-                            // Accept task: merge changes and mark as done
-                            // This reuses the SmartAcceptTask logic
-                            commands.push(Message::SmartAcceptTask(task_id));
-                        }
-                        PendingAction::MergeOnlyTask(task_id) => {
-                            // Merge only: merge changes but keep worktree and task in Review
-                            commands.push(Message::MergeOnlyTask(task_id));
-                        }
-                        PendingAction::DeclineTask(task_id) => {
-                            // Decline task: discard all changes and mark as done
-                            // Stop SDK session first (if running)
-                            if let Some(ref client) = self.sidecar_client {
-                                let _ = client.stop_session(task_id);
-                            }
+            Message::TodoScanNavigate(delta) => {
+                if let Some(ref mut scan) = self.model.ui_state.todo_scan {
+                    let max = scan.items.len().saturating_sub(1) as i32;
+                    scan.cursor = (scan.cursor as i32 + delta).clamp(0, max) as usize;
+                }
+            }
 
-                            // Get task info needed for cleanup
-                            let task_info = self.model.active_project().and_then(|p| {
-                                p.tasks.iter()
-                                    .find(|t| t.id == task_id)
-                                    .map(|t| (
-                                        p.slug(),
-                                        p.working_dir.clone(),
-                                        t.tmux_window.clone(),
-                                        t.worktree_path.clone(),
-                                        t.display_id(),
-                                    ))
-                            });
+            Message::TodoScanToggleSelect => {
+                if let Some(ref mut scan) = self.model.ui_state.todo_scan {
+                    if let Some(selected) = scan.selected.get_mut(scan.cursor) {
+                        *selected = !*selected;
+                    }
+                }
+            }
 
-                            if let Some((project_slug, project_dir, window_name, worktree_path, display_id)) = task_info {
-                                // Kill tmux window if exists
-                                if let Some(ref window) = window_name {
-                                    let _ = crate::tmux::kill_task_window(&project_slug, window);
-                                }
+            Message::TodoScanToggleAll => {
+                if let Some(ref mut scan) = self.model.ui_state.todo_scan {
+                    let all = scan.selected.iter().all(|&s| s);
+                    scan.selected.iter_mut().for_each(|s| *s = !all);
+                }
+            }
 
-                                // Remove worktree (discards all changes)
-                                if let Some(ref wt_path) = worktree_path {
-                                    if let Err(e) = crate::worktree::remove_worktree(&project_dir, wt_path) {
-                                        commands.push(Message::SetStatusMessage(Some(
-                                            format!("Warning: Could not remove worktree: {}", e)
-                                        )));
-                                    }
-                                    // Clean up trust entry from Claude's config
-                                    let _ = crate::worktree::remove_worktree_trust(wt_path);
-                                }
+            Message::TodoScanToggleGrouping => {
+                if let Some(ref mut scan) = self.model.ui_state.todo_scan {
+                    scan.by_age = !scan.by_age;
+                    scan.sort_items();
+                }
+            }
 
-                                // Delete branch (discards all commits)
-                                if let Err(e) = crate::worktree::delete_branch(&project_dir, &display_id) {
-                                    commands.push(Message::SetStatusMessage(Some(
-                                        format!("Warning: Could not delete branch: {}", e)
-                                    )));
-                                }
+            Message::CreateTasksFromTodos => {
+                let Some(scan) = self.model.ui_state.todo_scan.take() else {
+                    return commands;
+                };
+                // Selected items in display order, or just the highlighted one
+                let mut picked: Vec<&crate::search::todo::TodoItem> = scan.items.iter()
+                    .zip(&scan.selected)
+                    .filter(|(_, &selected)| selected)
+                    .map(|(item, _)| item)
+                    .collect();
+                if picked.is_empty() {
+                    picked.extend(scan.items.get(scan.cursor));
+                }
+                if picked.is_empty() {
+                    return commands;
+                }
 
-                                // Complete task (records stats) and move to Done
-                                // Note: We still record stats for declined tasks since work was done
-                                if let Some(project) = self.model.active_project_mut() {
-                                    project.complete_task(task_id);
-                                    project.needs_attention = project.review_count() > 0;
-                                    if !project.needs_attention {
-                                        notify::clear_attention_indicator();
-                                    }
-                                }
+                let mut created = Vec::new();
+                if let Some(project) = self.model.active_project_mut() {
+                    // Insert in reverse so the first picked item ends up on top of Planned
+                    for item in picked.iter().rev() {
+                        let mut task = Task::new(item.task_title());
+                        task.log_activity(format!("Created from {} at {}", item.marker, item.location.location()));
+                        created.push((task.id, task.title.len()));
+                        project.tasks.insert(0, task);
+                    }
+                }
 
-                                commands.push(Message::SetStatusMessage(Some(
-                                    "Task declined. Changes discarded.".to_string()
-                                )));
-                            }
-                        }
-                        PendingAction::ViewMergeReport => {
-                            // View-only modal - just dismiss, no action needed
-                        }
-                        PendingAction::CleanupMergedTask(task_id) => {
-                            // User confirmed cleanup of an already-merged task
-                            let task_info = self.model.active_project().and_then(|p| {
-                                p.tasks.iter()
-                                    .find(|t| t.id == task_id)
-                                    .map(|t| (
-                                        p.slug(),
-                                        p.working_dir.clone(),
-                                        t.tmux_window.clone(),
-                                        t.worktree_path.clone(),
-                                        t.display_id(),
-                                    ))
-                            });
+                self.model.ui_state.focus = FocusArea::KanbanBoard;
+                self.model.ui_state.selected_column = TaskStatus::Planned;
+                self.model.ui_state.selected_task_idx = Some(0);
+                self.model.ui_state.title_scroll_offset = 0;
+                self.model.ui_state.title_scroll_delay = 0;
 
-                            if let Some((project_slug, project_dir, window_name, worktree_path, display_id)) = task_info {
-                                // Kill tmux window if exists
-                                if let Some(ref window) = window_name {
-                                    let _ = crate::tmux::kill_task_window(&project_slug, window);
-                                }
+                for (task_id, title_len) in &created {
+                    if *title_len > 40 {
+                        commands.push(Message::RequestTitleSummary { task_id: *task_id });
+                    }
+                }
+                commands.push(Message::SetStatusMessage(Some(format!(
+                    "{} task(s) created from TODOs",
+                    created.len()
+                ))));
+            }
 
-                                // Kill any detached Claude/test sessions for this task (uses display_id as session name)
-                                crate::tmux::kill_task_sessions(&display_id);
+            Message::StartErrorLogWatch { project_dir } => {
+                if self.safe_mode {
+                    return commands;
+                }
+                let Some(project) = self.model.projects.iter_mut().find(|p| p.working_dir == project_dir) else {
+                    return commands;
+                };
+                project.stop_error_log_watch();
+                let Some(source) = project.error_log_source.as_deref()
+                    .and_then(|spec| crate::errorlog::LogSource::parse(spec, &project_dir))
+                else {
+                    return commands;
+                };
+                let Some(sender) = self.async_sender.clone() else {
+                    return commands;
+                };
 
-                                // Remove worktree if still around
-                                if let Some(ref wt_path) = worktree_path {
-                                    if wt_path.exists() {
-                                        if let Err(e) = crate::worktree::remove_worktree(&project_dir, wt_path) {
-                                            commands.push(Message::SetStatusMessage(Some(
-                                                format!("Warning: Could not remove worktree: {}", e)
-                                            )));
-                                        }
-                                        let _ = crate::worktree::remove_worktree_trust(wt_path);
-                                    }
-                                }
+                let cancel = crate::worktree::CancelToken::default();
+                project.error_log_cancel = Some(cancel.clone());
+
+                let label = source.describe();
+                let error_sender = sender.clone();
+                let error_dir = project_dir.clone();
+                crate::errorlog::spawn_watcher(
+                    source,
+                    project_dir.clone(),
+                    cancel,
+                    move |error| {
+                        let _ = error_sender.send(Message::ErrorLogCaptured {
+                            project_dir: error_dir.clone(),
+                            source: label.clone(),
+                            error,
+                        });
+                    },
+                    move |error| {
+                        let _ = sender.send(Message::ErrorLogWatchFailed { project_dir, error });
+                    },
+                );
+            }
 
-                                // Delete branch
-                                let _ = crate::worktree::delete_branch(&project_dir, &display_id);
+            Message::ErrorLogCaptured { project_dir, source, error } => {
+                let Some(project) = self.model.projects.iter_mut().find(|p| p.working_dir == project_dir) else {
+                    return commands;
+                };
+                // Each signature is offered once; repeats of a known error are dropped
+                if !project.record_error_signature(&error.signature) {
+                    return commands;
+                }
 
-                                // Complete task (records stats) and move to Done
-                                if let Some(project) = self.model.active_project_mut() {
-                                    project.complete_task(task_id);
-                                    project.needs_attention = project.review_count() > 0;
-                                    if !project.needs_attention {
-                                        notify::clear_attention_indicator();
-                                    }
-                                }
+                if project.error_log_auto_start {
+                    let mut task = Task::new(error.task_title(&source));
+                    task.restricted_agent = true;
+                    // Skip the sparse prompt - auto-started tasks use the project profile
+                    task.sparse_paths = Some(project.sparse_paths.clone().unwrap_or_default());
+                    task.log_activity_with_output(format!("Captured from {}", source), Some(error.trace.clone()));
+                    let task_id = task.id;
+                    project.tasks.insert(0, task);
 
-                                commands.push(Message::SetStatusMessage(Some(
-                                    "Merged task cleaned up and moved to Done.".to_string()
-                                )));
-                            }
-                        }
-                        PendingAction::CommitAppliedChanges(task_id) => {
-                            // Commit applied changes to main and complete the task
-                            let task_info = self.model.active_project().and_then(|p| {
-                                p.tasks.iter()
-                                    .find(|t| t.id == task_id)
-                                    .map(|t| (
-                                        p.slug(),
+                    commands.push(Message::RequestTitleSummary { task_id });
+                    let is_active = self.model.active_project().is_some_and(|p| p.working_dir == project_dir);
+                    if is_active {
+                        commands.push(Message::StartTaskWithWorktree(task_id));
+                    }
+                    commands.push(Message::SetStatusMessage(Some(format!(
+                        "New error in {}: bug task {}",
+                        source,
+                        if is_active { "started (restricted agent)" } else { "created in Planned" }
+                    ))));
+                } else {
+                    self.model.ui_state.error_offers.push(PendingAction::CreateErrorTask {
+                        project_dir,
+                        source,
+                        error,
+                    });
+                    commands.push(Message::ShowNextErrorOffer);
+                }
+            }
+
+            Message::ErrorLogWatchFailed { project_dir, error } => {
+                if let Some(project) = self.model.projects.iter_mut().find(|p| p.working_dir == project_dir) {
+                    project.error_log_cancel = None;
+                }
+                commands.push(Message::SetStatusMessage(Some(format!("Error-log watcher: {}", error))));
+            }
+
+            Message::ShowNextErrorOffer => {
+                if self.model.ui_state.pending_confirmation.is_some() || self.model.ui_state.error_offers.is_empty() {
+                    return commands;
+                }
+                let action = self.model.ui_state.error_offers.remove(0);
+                if let PendingAction::CreateErrorTask { ref source, ref error, .. } = action {
+                    commands.push(Message::ShowConfirmation {
+                        message: format!(
+                            "New error in {}:\n\n{}\n\nCreate a bug task with the stack trace? (y/n)",
+                            source, error.trace
+                        ),
+                        action,
+                    });
+                }
+            }
+
+            // === End of worktree-based task lifecycle ===
+
+            Message::SelectTask(idx) => {
+                self.model.ui_state.selected_task_idx = idx;
+                self.model.ui_state.title_scroll_offset = 0;
+                self.model.ui_state.title_scroll_delay = 0;
+            }
+
+            Message::SelectColumn(status) => {
+                self.model.ui_state.selected_column = status;
+                self.model.ui_state.focus = FocusArea::KanbanBoard;
+                // Select first task in column if any exist
+                let has_tasks = self.model.active_project()
+                    .map(|p| !p.tasks_by_status(status).is_empty())
+                    .unwrap_or(false);
+                self.model.ui_state.selected_task_idx = if has_tasks { Some(0) } else { None };
+                self.model.ui_state.title_scroll_offset = 0;
+                self.model.ui_state.title_scroll_delay = 0;
+            }
+
+            Message::ClickedTask { status, task_idx } => {
+                self.model.ui_state.selected_column = status;
+                self.model.ui_state.selected_task_idx = Some(task_idx);
+                self.model.ui_state.focus = FocusArea::KanbanBoard;
+                self.model.ui_state.title_scroll_offset = 0;
+                self.model.ui_state.title_scroll_delay = 0;
+            }
+
+            Message::StartCardDrag { task_id, from, x, y } => {
+                self.model.ui_state.card_drag = Some(crate::model::CardDrag {
+                    task_id,
+                    from,
+                    over: Some(from),
+                    x,
+                    y,
+                    moved: false,
+                    scroll: 0,
+                    over_height: 0,
+                });
+            }
+
+            Message::DragCard { x, y, over, scroll, over_height } => {
+                if let Some(ref mut drag) = self.model.ui_state.card_drag {
+                    drag.moved |= (x, y) != (drag.x, drag.y);
+                    drag.x = x;
+                    drag.y = y;
+                    drag.over = over;
+                    drag.scroll = scroll;
+                    drag.over_height = over_height;
+                }
+            }
+
+            Message::ScrollColumn { status, delta, visible_rows } => {
+                self.scroll_column(status, delta, visible_rows);
+            }
+
+            Message::DropCard => {
+                let Some(drag) = self.model.ui_state.card_drag.take() else {
+                    return commands;
+                };
+                // A press and release in place was just a click (already selected the card)
+                if !drag.moved {
+                    return commands;
+                }
+                if let Some(to) = drag.over.filter(|to| *to != drag.from) {
+                    commands.extend(self.card_drop_messages(drag.task_id, drag.from, to));
+                }
+            }
+
+            Message::SwitchProject(idx) => {
+                if idx < self.model.projects.len() {
+                    self.model.active_project_idx = idx;
+                    self.model.ui_state.selected_task_idx = None;
+                    self.model.ui_state.focus = FocusArea::KanbanBoard;
+
+                    // Refresh git status for the new project
+                    commands.push(Message::RefreshGitStatus);
+                    // Also fetch from remote to update ahead/behind indicators
+                    commands.push(Message::StartGitFetch);
+                    // Starts deferred by a focus block in this project can go now (if on a break)
+                    commands.extend(self.release_deferred_starts());
+                }
+            }
+
+            Message::MoveProject { from, to } => {
+                let len = self.model.projects.len();
+                if from < len && to < len && from != to {
+                    let active_id = self.model.active_project().map(|p| p.id);
+                    let project = self.model.projects.remove(from);
+                    self.model.projects.insert(to, project);
+                    // The active project keeps being active wherever it ended up
+                    if let Some(idx) = active_id.and_then(|id| self.model.projects.iter().position(|p| p.id == id)) {
+                        self.model.active_project_idx = idx;
+                    }
+                    // Keep the moved tab selected so it can be moved again
+                    if self.model.ui_state.focus == FocusArea::ProjectTabs {
+                        self.model.ui_state.selected_project_tab_idx = to + 1;
+                    }
+                }
+            }
+
+            Message::StartProjectTabDrag(idx) => {
+                self.model.ui_state.project_tab_drag = Some(idx);
+            }
+
+            Message::DropProjectTab { to } => {
+                if let (Some(from), Some(to)) = (self.model.ui_state.project_tab_drag.take(), to) {
+                    commands.push(Message::MoveProject { from, to });
+                }
+            }
+
+            Message::ShowProjectPicker => {
+                if !self.model.projects.is_empty() {
+                    self.model.ui_state.project_picker = Some(crate::model::ProjectPicker {
+                        query: String::new(),
+                        selected: self.model.active_project_idx,
+                    });
+                }
+            }
+
+            Message::ProjectPickerQueryChanged(query) => {
+                if let Some(ref mut picker) = self.model.ui_state.project_picker {
+                    picker.query = query;
+                    picker.selected = 0;
+                }
+            }
+
+            Message::ProjectPickerNavigate(delta) => {
+                if let Some(ref mut picker) = self.model.ui_state.project_picker {
+                    let count = picker.matches(&self.model.projects).len();
+                    if count > 0 {
+                        picker.selected = (picker.selected as i64 + i64::from(delta)).clamp(0, count as i64 - 1) as usize;
+                    }
+                }
+            }
+
+            Message::ProjectPickerSelect => {
+                if let Some(picker) = self.model.ui_state.project_picker.take() {
+                    if let Some(&idx) = picker.matches(&self.model.projects).get(picker.selected) {
+                        commands.push(Message::SwitchProject(idx));
+                    }
+                }
+            }
+
+            Message::CloseProjectPicker => {
+                self.model.ui_state.project_picker = None;
+            }
+
+            Message::ShowWorkspacePicker => {
+                self.sync_active_workspace();
+                let settings = &self.model.global_settings;
+                let selected = settings.active_workspace.as_deref()
+                    .and_then(|name| settings.workspaces.iter().position(|w| w.name == name))
+                    .unwrap_or(0);
+                self.model.ui_state.workspace_picker = Some(crate::model::WorkspacePicker {
+                    selected,
+                    // Nothing saved yet: go straight to naming one
+                    naming: settings.workspaces.is_empty().then(String::new),
+                });
+            }
+
+            Message::CloseWorkspacePicker => {
+                self.model.ui_state.workspace_picker = None;
+            }
+
+            Message::WorkspacePickerNavigate(delta) => {
+                let count = self.model.global_settings.workspaces.len();
+                if let Some(ref mut picker) = self.model.ui_state.workspace_picker {
+                    if count > 0 {
+                        picker.selected = (picker.selected as i64 + i64::from(delta)).clamp(0, count as i64 - 1) as usize;
+                    }
+                }
+            }
+
+            Message::StartWorkspaceName => {
+                if let Some(ref mut picker) = self.model.ui_state.workspace_picker {
+                    picker.naming = Some(String::new());
+                }
+            }
+
+            Message::CancelWorkspaceName => {
+                if let Some(ref mut picker) = self.model.ui_state.workspace_picker {
+                    picker.naming = None;
+                }
+            }
+
+            Message::WorkspaceNameChanged(name) => {
+                if let Some(ref mut picker) = self.model.ui_state.workspace_picker {
+                    picker.naming = Some(name);
+                }
+            }
+
+            Message::SaveWorkspace(name) => {
+                let name = name.trim().to_string();
+                if name.is_empty() {
+                    return commands;
+                }
+                if self.model.projects.is_empty() {
+                    commands.push(Message::Error("Open some projects to save as a workspace".to_string()));
+                    return commands;
+                }
+                let workspace = crate::model::workspace::Workspace::from_projects(name.clone(), &self.model.projects);
+                let count = workspace.projects.len();
+                let workspaces = &mut self.model.global_settings.workspaces;
+                let idx = match workspaces.iter().position(|w| w.name.eq_ignore_ascii_case(&name)) {
+                    Some(idx) => {
+                        workspaces[idx] = workspace;
+                        idx
+                    }
+                    None => {
+                        workspaces.push(workspace);
+                        workspaces.len() - 1
+                    }
+                };
+                self.model.global_settings.active_workspace = Some(name.clone());
+                if let Some(ref mut picker) = self.model.ui_state.workspace_picker {
+                    picker.selected = idx;
+                    picker.naming = None;
+                }
+                if let Err(e) = save_state(&self.model, self.state_file_path.as_ref()) {
+                    crate::diagnostics::warn(crate::diagnostics::Source::Io, format!("Failed to save state after saving workspace: {}", e));
+                }
+                commands.push(Message::SetStatusMessage(Some(format!(
+                    "Saved workspace '{}' ({} project{})",
+                    name, count, if count == 1 { "" } else { "s" }
+                ))));
+            }
+
+            Message::DeleteWorkspace(name) => {
+                let settings = &mut self.model.global_settings;
+                settings.workspaces.retain(|w| w.name != name);
+                if settings.active_workspace.as_deref() == Some(name.as_str()) {
+                    settings.active_workspace = None;
+                }
+                let count = settings.workspaces.len();
+                if let Some(ref mut picker) = self.model.ui_state.workspace_picker {
+                    picker.selected = picker.selected.min(count.saturating_sub(1));
+                }
+                // Drops the state files of projects that were only kept for this workspace
+                if let Err(e) = save_state(&self.model, self.state_file_path.as_ref()) {
+                    crate::diagnostics::warn(crate::diagnostics::Source::Io, format!("Failed to save state after deleting workspace: {}", e));
+                }
+                commands.push(Message::SetStatusMessage(Some(format!("Deleted workspace '{}'", name))));
+            }
+
+            Message::SwitchWorkspace(name) => {
+                self.model.ui_state.workspace_picker = None;
+                commands.extend(self.switch_workspace(&name));
+            }
+
+            Message::AddProject { name, working_dir } => {
+                let project = Project::new(name, working_dir);
+                self.model.projects.push(project);
+            }
+
+            Message::ShowOpenProjectDialog { slot } => {
+                self.model.ui_state.open_project_dialog_slot = Some(slot);
+                self.model.ui_state.quick_open = Default::default();
+                // Create a directory browser starting at home directory
+                let start_dir = dirs::home_dir().unwrap_or_else(|| std::path::PathBuf::from("/"));
+                if let Ok(browser) = crate::model::DirectoryBrowser::new(start_dir) {
+                    self.model.ui_state.directory_browser = Some(browser);
+                }
+            }
+
+            Message::CloseOpenProjectDialog => {
+                self.model.ui_state.open_project_dialog_slot = None;
+                self.model.ui_state.directory_browser = None;
+                self.model.ui_state.create_folder_input = None;
+                self.model.ui_state.quick_open = Default::default();
+            }
+
+            Message::QuickOpenPath(path) => {
+                if let Some(idx) = self.model.projects.iter().position(|p| p.location() == path) {
+                    commands.push(Message::CloseOpenProjectDialog);
+                    commands.push(Message::SwitchProject(idx));
+                } else if crate::remote::RemoteSpec::parse(&path.to_string_lossy()).is_some() {
+                    commands.push(Message::OpenRemoteProject(path.to_string_lossy().to_string()));
+                } else if !path.is_dir() {
+                    commands.push(Message::Error(format!("{} is not a directory", path.display())));
+                } else {
+                    commands.push(Message::ConfirmOpenProjectPath(path));
+                }
+            }
+
+            Message::OpenRemoteProject(input) => {
+                let Some(spec) = crate::remote::RemoteSpec::parse(&input) else {
+                    commands.push(Message::Error(format!("'{}' isn't a remote path (user@host:/path)", input)));
+                    return commands;
+                };
+                if self.safe_mode {
+                    commands.push(Message::SetStatusMessage(Some(
+                        "Remote projects are off in safe mode".to_string()
+                    )));
+                    return commands;
+                }
+                commands.push(Message::SetStatusMessage(Some(format!("Connecting to {}...", spec.target))));
+                if let Some(sender) = self.async_sender.clone() {
+                    tokio::spawn(async move {
+                        let result = tokio::task::spawn_blocking(move || {
+                            crate::remote::connect(&spec).map_err(|e| format!("{:#}", e))
+                        }).await;
+                        let result = result.unwrap_or_else(|e| Err(e.to_string()));
+                        let _ = sender.send(Message::RemoteProjectConnected(result));
+                    });
+                }
+            }
+
+            Message::RemoteProjectConnected(result) => {
+                match result {
+                    Ok(spec) => {
+                        // The path is registered now, so the usual checks (git repo,
+                        // commits, .gitignore) run on the remote host
+                        if self.model.ui_state.open_project_dialog_slot.is_none() {
+                            self.model.ui_state.open_project_dialog_slot = Some(self.model.projects.len());
+                        }
+                        commands.push(Message::SetStatusMessage(Some(format!("Connected to {}", spec.target))));
+                        commands.push(Message::ConfirmOpenProjectPath(spec.path));
+                    }
+                    Err(e) => commands.push(Message::Error(e)),
+                }
+            }
+
+            Message::ConnectRemoteProjects => {
+                if self.safe_mode {
+                    return commands;
+                }
+                let specs: Vec<crate::remote::RemoteSpec> = self.model.projects.iter()
+                    .filter_map(|p| Some(crate::remote::RemoteSpec {
+                        target: p.ssh_target.clone()?,
+                        path: p.working_dir.clone(),
+                    }))
+                    .collect();
+                if let (Some(sender), false) = (self.async_sender.clone(), specs.is_empty()) {
+                    tokio::spawn(async move {
+                        for spec in specs {
+                            let connected = tokio::task::spawn_blocking({
+                                let spec = spec.clone();
+                                move || crate::remote::connect(&spec)
+                            }).await;
+                            if let Ok(Err(e)) = connected {
+                                let _ = sender.send(Message::Error(format!("{}: {:#}", spec, e)));
+                            }
+                        }
+                    });
+                }
+            }
+
+            Message::ToggleRecentPin(path) => {
+                crate::model::recent::toggle_pin(&mut self.model.global_settings.recent_projects, &path);
+            }
+
+            Message::ForgetRecentProject(path) => {
+                crate::model::recent::forget(&mut self.model.global_settings.recent_projects, &path);
+                let shown = self.model.global_settings.recent_projects.len().min(crate::model::QuickOpen::MAX_SHOWN);
+                let quick_open = &mut self.model.ui_state.quick_open;
+                quick_open.recent_selected = quick_open.recent_selected
+                    .filter(|_| shown > 0)
+                    .map(|idx| idx.min(shown - 1));
+            }
+
+            Message::EnterCreateFolderMode => {
+                // Enter create folder mode with empty input
+                self.model.ui_state.create_folder_input = Some(String::new());
+            }
+
+            Message::CancelCreateFolderMode => {
+                // Exit create folder mode
+                self.model.ui_state.create_folder_input = None;
+            }
+
+            Message::CreateFolder { name } => {
+                // Create a new folder with git init in the current directory
+                if let Some(ref mut browser) = self.model.ui_state.directory_browser {
+                    match browser.create_folder(&name) {
+                        Ok(path) => {
+                            // Clear create mode
+                            self.model.ui_state.create_folder_input = None;
+
+                            // Show success message
+                            commands.push(Message::SetStatusMessage(Some(
+                                format!("Created folder '{}' with git init", name)
+                            )));
+
+                            // Optionally, we could auto-open the new folder as a project
+                            // For now, just leave it selected in the browser
+                            let _ = path; // The browser is already updated with the new folder selected
+                        }
+                        Err(e) => {
+                            // Show error message but stay in create mode for retry
+                            commands.push(Message::SetStatusMessage(Some(
+                                format!("Failed to create folder: {}", e)
+                            )));
+                        }
+                    }
+                }
+            }
+
+            Message::ConfirmOpenProject => {
+                if let Some(slot) = self.model.ui_state.open_project_dialog_slot {
+                    if let Some(ref browser) = self.model.ui_state.directory_browser {
+                        // Use the selected (cursor) directory as the project path
+                        if let Some(selected) = browser.selected() {
+                            // Don't allow selecting ".." or special entries as project
+                            if selected.special != crate::model::SpecialEntry::None {
+                                commands.push(Message::SetStatusMessage(Some(
+                                    "Cannot select this item - use [New Project Here] or navigate into a directory".to_string()
+                                )));
+                            } else {
+                                // A directory inside a repository opens as a scoped project of that repository
+                                let (path, scope) = crate::worktree::git::split_scope(&selected.path);
+
+                                // Check if this project is already open
+                                if let Some(existing_project) = self.model.projects.iter().find(|p| p.working_dir == path && p.scope == scope) {
+                                    commands.push(Message::SetStatusMessage(Some(
+                                        format!("Project '{}' is already open", existing_project.name)
+                                    )));
+                                    // Close the dialog
+                                    self.model.ui_state.open_project_dialog_slot = None;
+                                    self.model.ui_state.directory_browser = None;
+                                } else {
+                                    // Use the directory name as the project name
+                                    let name = scope.as_deref().unwrap_or(&path)
+                                        .file_name()
+                                        .and_then(|n| n.to_str())
+                                        .unwrap_or("project")
+                                        .to_string();
+
+                                    // Check git status and offer to initialize if needed
+                                    let is_git = crate::worktree::git::is_git_repo(&path);
+                                    let has_commits = is_git && crate::worktree::git::has_commits(&path);
+
+                                    if !is_git {
+                                        // Not a git repo - offer to initialize
+                                        commands.push(Message::ShowConfirmation {
+                                            message: format!(
+                                                "'{}' is not a git repository.\n\n\
+                                                 y=initialize git, s=snapshot mode (history kept outside the folder), n=cancel",
+                                                name
+                                            ),
+                                            action: PendingAction::InitGit {
+                                                path: path.clone(),
+                                                name: name.clone(),
+                                                slot,
+                                            },
+                                        });
+                                        // Close the browser dialog (confirmation will handle opening)
+                                        self.model.ui_state.open_project_dialog_slot = None;
+                                        self.model.ui_state.directory_browser = None;
+                                    } else if !has_commits {
+                                        // Git repo but no commits - offer to create initial commit
+                                        commands.push(Message::ShowConfirmation {
+                                            message: format!(
+                                                "'{}' has no commits.\n\nCreate initial commit? (y/n)",
+                                                name
+                                            ),
+                                            action: PendingAction::CreateInitialCommit {
+                                                path: path.clone(),
+                                                name: name.clone(),
+                                                slot,
+                                            },
+                                        });
+                                        // Close the browser dialog (confirmation will handle opening)
+                                        self.model.ui_state.open_project_dialog_slot = None;
+                                        self.model.ui_state.directory_browser = None;
+                                    } else {
+                                        // Valid git repo with commits - check .gitignore
+                                        // (snapshot projects exclude kanblam's files in their shadow repository)
+                                        let missing_entries = if crate::worktree::snapshot::is_snapshot_project(&path) {
+                                            Vec::new()
+                                        } else {
+                                            crate::worktree::git::gitignore_missing_kanblam_entries(&path)
+                                        };
+                                        if !missing_entries.is_empty() {
+                                            // Ask permission to add missing entries
+                                            commands.push(Message::ShowConfirmation {
+                                                message: format!(
+                                                    "'{}' .gitignore is missing KanBlam entries:\n  {}\n\nAdd them? (y/n)",
+                                                    name,
+                                                    missing_entries.join(", ")
+                                                ),
+                                                action: PendingAction::UpdateGitignore {
+                                                    path: path.clone(),
+                                                    scope: scope.clone(),
+                                                    name: name.clone(),
+                                                    slot,
+                                                    missing_entries,
+                                                },
+                                            });
+                                            // Close the browser dialog (confirmation will handle opening)
+                                            self.model.ui_state.open_project_dialog_slot = None;
+                                            self.model.ui_state.directory_browser = None;
+                                        } else {
+                                            // All good - open directly
+                                            let mut project = Project::new(name, path);
+                                            project.scope = scope;
+                                            // Load any existing tasks from the project's .kanblam/tasks.json
+                                            project.load_tasks();
+                                            let has_tasks = !project.tasks.is_empty();
+                                            self.model.projects.push(project);
+                                            self.model.active_project_idx = slot;
+                                            self.model.ui_state.selected_task_idx = None;
+                                            // Focus TaskInput if project has no tasks, otherwise KanbanBoard
+                                            self.model.ui_state.focus = if has_tasks {
+                                                FocusArea::KanbanBoard
+                                            } else {
+                                                FocusArea::TaskInput
+                                            };
+
+                                            // Close the dialog
+                                            self.model.ui_state.open_project_dialog_slot = None;
+                                            self.model.ui_state.directory_browser = None;
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+
+            Message::ConfirmOpenProjectPath(path) => {
+                if let Some(slot) = self.model.ui_state.open_project_dialog_slot {
+                    // A directory inside a repository opens as a scoped project of that repository
+                    let (path, scope) = crate::worktree::git::split_scope(&path);
+                    // Check if this project is already open
+                    if let Some(existing_project) = self.model.projects.iter().find(|p| p.working_dir == path && p.scope == scope) {
+                        commands.push(Message::SetStatusMessage(Some(
+                            format!("Project '{}' is already open", existing_project.name)
+                        )));
+                        // Close the dialog
+                        self.model.ui_state.open_project_dialog_slot = None;
+                        self.model.ui_state.directory_browser = None;
+                    } else {
+                        // Use the directory name as the project name
+                        let name = scope.as_deref().unwrap_or(&path)
+                            .file_name()
+                            .and_then(|n| n.to_str())
+                            .unwrap_or("project")
+                            .to_string();
+
+                        // Check git status and offer to initialize if needed
+                        let is_git = crate::worktree::git::is_git_repo(&path);
+                        let has_commits = is_git && crate::worktree::git::has_commits(&path);
+
+                        if !is_git {
+                            // Not a git repo - offer to initialize
+                            commands.push(Message::ShowConfirmation {
+                                message: format!(
+                                    "'{}' is not a git repository.\n\n\
+                                     y=initialize git, s=snapshot mode (history kept outside the folder), n=cancel",
+                                    name
+                                ),
+                                action: PendingAction::InitGit {
+                                    path: path.clone(),
+                                    name: name.clone(),
+                                    slot,
+                                },
+                            });
+                            // Close the browser dialog (confirmation will handle opening)
+                            self.model.ui_state.open_project_dialog_slot = None;
+                            self.model.ui_state.directory_browser = None;
+                        } else if !has_commits {
+                            // Git repo but no commits - offer to create initial commit
+                            commands.push(Message::ShowConfirmation {
+                                message: format!(
+                                    "'{}' has no commits.\n\nCreate initial commit? (y/n)",
+                                    name
+                                ),
+                                action: PendingAction::CreateInitialCommit {
+                                    path: path.clone(),
+                                    name: name.clone(),
+                                    slot,
+                                },
+                            });
+                            // Close the browser dialog (confirmation will handle opening)
+                            self.model.ui_state.open_project_dialog_slot = None;
+                            self.model.ui_state.directory_browser = None;
+                        } else {
+                            // Valid git repo with commits - check .gitignore
+                            // (snapshot projects exclude kanblam's files in their shadow repository)
+                            let missing_entries = if crate::worktree::snapshot::is_snapshot_project(&path) {
+                                Vec::new()
+                            } else {
+                                crate::worktree::git::gitignore_missing_kanblam_entries(&path)
+                            };
+                            if !missing_entries.is_empty() {
+                                // Ask permission to add missing entries
+                                commands.push(Message::ShowConfirmation {
+                                    message: format!(
+                                        "'{}' .gitignore is missing KanBlam entries:\n  {}\n\nAdd them? (y/n)",
+                                        name,
+                                        missing_entries.join(", ")
+                                    ),
+                                    action: PendingAction::UpdateGitignore {
+                                        path: path.clone(),
+                                        scope: scope.clone(),
+                                        name: name.clone(),
+                                        slot,
+                                        missing_entries,
+                                    },
+                                });
+                                // Close the browser dialog (confirmation will handle opening)
+                                self.model.ui_state.open_project_dialog_slot = None;
+                                self.model.ui_state.directory_browser = None;
+                            } else {
+                                // All good - open directly
+                                let mut project = Project::new(name, path);
+                                project.scope = scope;
+                                // Load any existing tasks from the project's .kanblam/tasks.json
+                                project.load_tasks();
+                                let has_tasks = !project.tasks.is_empty();
+                                self.model.projects.push(project);
+                                self.model.active_project_idx = slot;
+                                self.model.ui_state.selected_task_idx = None;
+                                // Focus TaskInput if project has no tasks, otherwise KanbanBoard
+                                self.model.ui_state.focus = if has_tasks {
+                                    FocusArea::KanbanBoard
+                                } else {
+                                    FocusArea::TaskInput
+                                };
+
+                                // Close the dialog
+                                self.model.ui_state.open_project_dialog_slot = None;
+                                self.model.ui_state.directory_browser = None;
+                            }
+                        }
+                    }
+                }
+            }
+
+            Message::OpenSnapshotProject { path, name, slot } => {
+                self.model.ui_state.pending_confirmation = None;
+                match crate::worktree::snapshot::init(&path) {
+                    Ok(_) => {
+                        let mut project = Project::new(name.clone(), path);
+                        project.load_tasks();
+                        let has_tasks = !project.tasks.is_empty();
+                        self.model.projects.push(project);
+                        self.model.active_project_idx = slot;
+                        self.model.ui_state.selected_task_idx = None;
+                        // Focus TaskInput if project has no tasks, otherwise KanbanBoard
+                        self.model.ui_state.focus = if has_tasks {
+                            FocusArea::KanbanBoard
+                        } else {
+                            FocusArea::TaskInput
+                        };
+                        commands.push(Message::SetStatusMessage(Some(
+                            format!("Opened '{}' in snapshot mode", name)
+                        )));
+                    }
+                    Err(e) => {
+                        commands.push(Message::Error(format!(
+                            "Failed to set up snapshots: {}", e
+                        )));
+                    }
+                }
+            }
+
+            Message::CloseProject(idx) => {
+                if idx < self.model.projects.len() {
+                    let project = &self.model.projects[idx];
+                    // Check if project has active tasks
+                    if project.has_active_task() {
+                        let name = project.name.clone();
+                        commands.push(Message::ShowConfirmation {
+                            message: format!(
+                                "Project '{}' has active tasks. Close anyway? (y/n)",
+                                name
+                            ),
+                            action: PendingAction::CloseProject(idx),
+                        });
+                    } else {
+                        // No active tasks, close directly
+                        // Save tasks before closing
+                        if let Err(e) = self.model.projects[idx].save_tasks() {
+                            crate::diagnostics::warn(crate::diagnostics::Source::Io, format!("Failed to save tasks before closing: {}", e));
+                        }
+                        self.model.projects.remove(idx).stop_error_log_watch();
+                        // Adjust active project index
+                        if self.model.projects.is_empty() {
+                            self.model.active_project_idx = 0;
+                        } else if self.model.active_project_idx >= self.model.projects.len() {
+                            self.model.active_project_idx = self.model.projects.len() - 1;
+                        }
+                        // Reset selection
+                        self.model.ui_state.selected_task_idx = None;
+                        // Save global state so closed project doesn't reappear
+                        if let Err(e) = save_state(&self.model, self.state_file_path.as_ref()) {
+                            crate::diagnostics::warn(crate::diagnostics::Source::Io, format!("Failed to save state after closing project: {}", e));
+                        }
+                    }
+                }
+            }
+
+            Message::ShowConfirmation { message, action } => {
+                self.model.ui_state.pending_confirmation = Some(PendingConfirmation {
+                    message,
+                    action,
+                    animation_tick: 20, // Start sweep animation (same duration as startup hints)
+                });
+                // Reset scroll offset for new confirmation
+                self.model.ui_state.confirmation_scroll_offset = 0;
+            }
+
+            Message::ConfirmAction => {
+                // Reset scroll offset when confirmation is dismissed
+                self.model.ui_state.confirmation_scroll_offset = 0;
+                if let Some(confirmation) = self.model.ui_state.pending_confirmation.take() {
+                    match confirmation.action {
+                        PendingAction::DeleteTask(task_id) => {
+                            // Actually delete the task
+                            commands.push(Message::DeleteTask(task_id));
+                        }
+                        PendingAction::MarkDoneNoMerge(task_id) => {
+                            // Mark task as done and clean up worktree without merging
+                            // Stop SDK session first (if running)
+                            if let Some(ref client) = self.sidecar_client {
+                                let _ = client.stop_session(task_id);
+                            }
+
+                            // Get task info needed for cleanup
+                            let task_info = self.model.active_project().and_then(|p| {
+                                p.tasks.iter()
+                                    .find(|t| t.id == task_id)
+                                    .map(|t| (
+                                        p.slug(),
+                                        p.working_dir.clone(),
+                                        t.tmux_window.clone(),
+                                        t.worktree_path.clone(),
+                                        t.display_id(),
+                                    ))
+                            });
+
+                            if let Some((project_slug, project_dir, window_name, worktree_path, display_id)) = task_info {
+                                // Kill tmux window if exists
+                                if let Some(ref window) = window_name {
+                                    let _ = crate::tmux::kill_task_window(&project_slug, window);
+                                }
+
+                                // Kill any detached Claude/test sessions for this task (uses display_id as session name)
+                                crate::tmux::kill_task_sessions(&display_id);
+
+                                // Remove worktree
+                                if let Some(ref wt_path) = worktree_path {
+                                    if let Err(e) = crate::worktree::release_worktree(&project_dir, wt_path, self.worktree_pool_size(&project_dir)) {
+                                        commands.push(Message::SetStatusMessage(Some(
+                                            format!("Warning: Could not remove worktree: {}", e)
+                                        )));
+                                    }
+                                    // Clean up trust entry from Claude's config
+                                    let _ = crate::worktree::remove_worktree_trust(wt_path);
+                                }
+
+                                // Delete branch
+                                if let Err(e) = crate::worktree::delete_branch(&project_dir, &display_id) {
+                                    commands.push(Message::SetStatusMessage(Some(
+                                        format!("Warning: Could not delete branch: {}", e)
+                                    )));
+                                }
+
+                                // Complete task (records stats) and move to Done
+                                if let Some(project) = self.model.active_project_mut() {
+                                    project.complete_task(task_id);
+                                    project.needs_attention = project.review_count() > 0;
+                                    if !project.needs_attention {
+                                        notify::clear_attention_indicator();
+                                    }
+                                }
+
+                                commands.push(Message::SetStatusMessage(Some(
+                                    "Task marked as done. Worktree cleaned up.".to_string()
+                                )));
+                            }
+                        }
+                        PendingAction::CloseProject(idx) => {
+                            // Close the project (user confirmed)
+                            if idx < self.model.projects.len() {
+                                // Save tasks before closing
+                                if let Err(e) = self.model.projects[idx].save_tasks() {
+                                    crate::diagnostics::warn(crate::diagnostics::Source::Io, format!("Failed to save tasks before closing: {}", e));
+                                }
+                                self.model.projects.remove(idx).stop_error_log_watch();
+                                // Adjust active project index
+                                if self.model.projects.is_empty() {
+                                    self.model.active_project_idx = 0;
+                                } else if self.model.active_project_idx >= self.model.projects.len() {
+                                    self.model.active_project_idx = self.model.projects.len() - 1;
+                                }
+                                // Reset selection
+                                self.model.ui_state.selected_task_idx = None;
+                                // Save global state so closed project doesn't reappear
+                                if let Err(e) = save_state(&self.model, self.state_file_path.as_ref()) {
+                                    crate::diagnostics::warn(crate::diagnostics::Source::Io, format!("Failed to save state after closing project: {}", e));
+                                }
+                            }
+                        }
+                        PendingAction::AcceptTask(task_id) => {
+                            // Accept task: merge changes and mark as done
+                            // This reuses the SmartAcceptTask logic, once the commit message is settled
+                            commands.push(Message::ComposeMergeCommit { task_id, merge_only: false, interactive: true });
+                        }
+                        PendingAction::MergeOnlyTask(task_id) => {
+                            // Merge only: merge changes but keep worktree and task in Review
+                            commands.push(Message::ComposeMergeCommit { task_id, merge_only: true, interactive: true });
+                        }
+                        PendingAction::DeclineTask(task_id) => {
+                            // Decline task: discard all changes and mark as done
+                            // Stop SDK session first (if running)
+                            if let Some(ref client) = self.sidecar_client {
+                                let _ = client.stop_session(task_id);
+                            }
+
+                            // Get task info needed for cleanup
+                            let task_info = self.model.active_project().and_then(|p| {
+                                p.tasks.iter()
+                                    .find(|t| t.id == task_id)
+                                    .map(|t| (
+                                        p.slug(),
+                                        p.working_dir.clone(),
+                                        t.tmux_window.clone(),
+                                        t.worktree_path.clone(),
+                                        t.display_id(),
+                                    ))
+                            });
+
+                            if let Some((project_slug, project_dir, window_name, worktree_path, display_id)) = task_info {
+                                // Kill tmux window if exists
+                                if let Some(ref window) = window_name {
+                                    let _ = crate::tmux::kill_task_window(&project_slug, window);
+                                }
+
+                                // Remove worktree (discards all changes)
+                                if let Some(ref wt_path) = worktree_path {
+                                    if let Err(e) = crate::worktree::release_worktree(&project_dir, wt_path, self.worktree_pool_size(&project_dir)) {
+                                        commands.push(Message::SetStatusMessage(Some(
+                                            format!("Warning: Could not remove worktree: {}", e)
+                                        )));
+                                    }
+                                    // Clean up trust entry from Claude's config
+                                    let _ = crate::worktree::remove_worktree_trust(wt_path);
+                                }
+
+                                // Delete branch (discards all commits)
+                                if let Err(e) = crate::worktree::delete_branch(&project_dir, &display_id) {
+                                    commands.push(Message::SetStatusMessage(Some(
+                                        format!("Warning: Could not delete branch: {}", e)
+                                    )));
+                                }
+
+                                // Complete task (records stats) and move to Done
+                                // Note: We still record stats for declined tasks since work was done
+                                if let Some(project) = self.model.active_project_mut() {
+                                    project.complete_task(task_id);
+                                    project.needs_attention = project.review_count() > 0;
+                                    if !project.needs_attention {
+                                        notify::clear_attention_indicator();
+                                    }
+                                }
+
+                                commands.push(Message::SetStatusMessage(Some(
+                                    "Task declined. Changes discarded.".to_string()
+                                )));
+                            }
+                        }
+                        PendingAction::ViewMergeReport => {
+                            // View-only modal - just dismiss, no action needed
+                        }
+                        PendingAction::CleanupMergedTask(task_id) => {
+                            // User confirmed cleanup of an already-merged task
+                            let task_info = self.model.active_project().and_then(|p| {
+                                p.tasks.iter()
+                                    .find(|t| t.id == task_id)
+                                    .map(|t| (
+                                        p.slug(),
+                                        p.working_dir.clone(),
+                                        t.tmux_window.clone(),
+                                        t.worktree_path.clone(),
+                                        t.display_id(),
+                                    ))
+                            });
+
+                            if let Some((project_slug, project_dir, window_name, worktree_path, display_id)) = task_info {
+                                // Kill tmux window if exists
+                                if let Some(ref window) = window_name {
+                                    let _ = crate::tmux::kill_task_window(&project_slug, window);
+                                }
+
+                                // Kill any detached Claude/test sessions for this task (uses display_id as session name)
+                                crate::tmux::kill_task_sessions(&display_id);
+
+                                // Remove worktree if still around
+                                if let Some(ref wt_path) = worktree_path {
+                                    if wt_path.exists() {
+                                        if let Err(e) = crate::worktree::release_worktree(&project_dir, wt_path, self.worktree_pool_size(&project_dir)) {
+                                            commands.push(Message::SetStatusMessage(Some(
+                                                format!("Warning: Could not remove worktree: {}", e)
+                                            )));
+                                        }
+                                        let _ = crate::worktree::remove_worktree_trust(wt_path);
+                                    }
+                                }
+
+                                // Delete branch
+                                let _ = crate::worktree::delete_branch(&project_dir, &display_id);
+
+                                // Complete task (records stats) and move to Done
+                                if let Some(project) = self.model.active_project_mut() {
+                                    project.complete_task(task_id);
+                                    project.needs_attention = project.review_count() > 0;
+                                    if !project.needs_attention {
+                                        notify::clear_attention_indicator();
+                                    }
+                                }
+
+                                commands.push(Message::SetStatusMessage(Some(
+                                    "Merged task cleaned up and moved to Done.".to_string()
+                                )));
+                            }
+                        }
+                        PendingAction::CommitAppliedChanges(task_id) => {
+                            // Commit applied changes to main and complete the task
+                            let task_info = self.model.active_project().and_then(|p| {
+                                p.tasks.iter()
+                                    .find(|t| t.id == task_id)
+                                    .map(|t| (
+                                        p.slug(),
                                         p.working_dir.clone(),
                                         t.tmux_window.clone(),
                                         t.worktree_path.clone(),
@@ -3483,2345 +6108,3819 @@ Do not ask for permission - run tests and fix any issues you find."#);
                                     ))
                             });
 
-                            // Capture celebration info BEFORE moving the task
-                            let celebration_info = self.model.active_project().and_then(|project| {
-                                let tasks_in_review = project.tasks_by_status(TaskStatus::Review);
-                                tasks_in_review.iter().enumerate()
-                                    .find(|(_, t)| t.id == task_id)
-                                    .map(|(idx, t)| {
-                                        let display_id = t.display_id();
-                                        let title = t.short_title.as_ref().unwrap_or(&t.title);
-                                        let display_text = format!("[{}] {}", display_id, title);
-                                        (display_text, idx)
-                                    })
-                            });
+                            // Capture celebration info BEFORE moving the task
+                            let celebration_info = self.model.active_project().and_then(|project| {
+                                let tasks_in_review = project.tasks_by_status(TaskStatus::Review);
+                                tasks_in_review.iter().enumerate()
+                                    .find(|(_, t)| t.id == task_id)
+                                    .map(|(idx, t)| {
+                                        let display_id = t.display_id();
+                                        let title = t.short_title.as_ref().unwrap_or(&t.title);
+                                        let display_text = format!("[{}] {}", display_id, title);
+                                        (display_text, idx)
+                                    })
+                            });
+
+                            if let Some((project_slug, project_dir, window_name, worktree_path, task_title, display_id)) = task_info {
+                                // Commit the applied changes to main
+                                match crate::worktree::commit_applied_changes(&project_dir, &task_title, &display_id) {
+                                    Ok(_) => {
+                                        // Clean up patch file (stash was already popped during apply)
+                                        crate::worktree::cleanup_applied_state(&display_id);
+
+                                        // Clear applied state
+                                        if let Some(project) = self.model.active_project_mut() {
+                                            project.applied_task_id = None;
+                                            project.applied_stash_ref = None;
+                                            project.applied_with_conflict_resolution = false;
+                                            project.mark_task_merged(task_id);
+                                        }
+                                        commands.push(Message::CheckReleaseAfterMerge(task_id));
+                                        commands.push(Message::RunPostMergeScript(task_id));
+
+                                        // Kill tmux window if exists
+                                        if let Some(ref window) = window_name {
+                                            let _ = crate::tmux::kill_task_window(&project_slug, window);
+                                        }
+
+                                        // Stop SDK session
+                                        if let Some(ref client) = self.sidecar_client {
+                                            let _ = client.stop_session(task_id);
+                                        }
+
+                                        // Kill any detached sessions (uses display_id as session name)
+                                        crate::tmux::kill_task_sessions(&display_id);
+
+                                        // Remove worktree
+                                        if let Some(ref wt_path) = worktree_path {
+                                            let _ = crate::worktree::release_worktree(&project_dir, wt_path, self.worktree_pool_size(&project_dir));
+                                            let _ = crate::worktree::remove_worktree_trust(wt_path);
+                                        }
+
+                                        // Delete branch
+                                        let _ = crate::worktree::delete_branch(&project_dir, &display_id);
+
+                                        // Trigger celebratory animations - task completion deferred until animation ends
+                                        commands.push(Message::TriggerLogoShimmer);
+                                        if let Some((display_text, task_index)) = celebration_info {
+                                            commands.push(Message::TriggerMergeCelebration {
+                                                task_id,
+                                                display_text,
+                                                column_status: TaskStatus::Review,
+                                                task_index,
+                                                pending_completion: true,
+                                            });
+                                        } else {
+                                            // No animation - complete immediately
+                                            if let Some(project) = self.model.active_project_mut() {
+                                                project.complete_task(task_id);
+                                                project.needs_attention = project.review_count() > 0;
+                                                if !project.needs_attention {
+                                                    notify::clear_attention_indicator();
+                                                }
+                                            }
+                                        }
+
+                                        commands.push(Message::SetStatusMessage(Some(
+                                            "✓ Changes committed to main. Task complete!".to_string()
+                                        )));
+                                    }
+                                    Err(e) => {
+                                        commands.push(Message::Error(format!(
+                                            "Failed to commit changes: {}", e
+                                        )));
+                                    }
+                                }
+                            }
+                        }
+                        PendingAction::ResetTask(task_id) => {
+                            // Reset the task (cleanup and move to Planned)
+                            commands.push(Message::ResetTask(task_id));
+                        }
+                        PendingAction::ForceUnapply(task_id) => {
+                            // User confirmed destructive unapply
+                            commands.push(Message::ForceUnapplyTaskChanges(task_id));
+                        }
+                        PendingAction::StashConflict { task_id, stash_sha } => {
+                            // User pressed 'y' - solve conflicts with Claude
+                            commands.push(Message::StartStashConflictSession { task_id, stash_sha });
+                        }
+                        PendingAction::InterruptSdkForCli(task_id) => {
+                            // User confirmed interrupting SDK to open CLI
+                            commands.push(Message::DoOpenInteractiveModal(task_id));
+                        }
+                        PendingAction::InterruptSdkForFeedback { task_id, feedback } => {
+                            // User confirmed interrupting SDK to send feedback (i=interrupt)
+                            commands.push(Message::DoSendFeedback { task_id, feedback });
+                        }
+                        PendingAction::InterruptCliForFeedback { task_id, feedback } => {
+                            // User confirmed interrupting CLI to send feedback via SDK (i=interrupt)
+                            commands.push(Message::DoSendFeedback { task_id, feedback });
+                        }
+                        PendingAction::BroadcastFeedback { task_ids, feedback } => {
+                            commands.push(Message::BroadcastFeedback { task_ids, feedback });
+                        }
+                        PendingAction::BroadcastReport => {
+                            // View-only report - just dismiss
+                        }
+                        PendingAction::LargePaste { text } => {
+                            self.model.ui_state.insert_input_text(&text);
+                        }
+                        PendingAction::AttachClipboardFiles { paths, .. } => {
+                            commands.push(Message::AttachFiles(paths));
+                        }
+                        PendingAction::DirtyMainBeforeMerge { task_id } => {
+                            // User chose to commit (y) - commit changes then proceed with merge
+                            let project_dir = self.model.active_project()
+                                .map(|p| p.working_dir.clone());
+                            if let Some(project_dir) = project_dir {
+                                if let Err(e) = crate::worktree::commit_main_changes(&project_dir) {
+                                    commands.push(Message::Error(format!(
+                                        "Failed to commit changes: {}", e
+                                    )));
+                                } else {
+                                    // Now proceed with the merge
+                                    commands.push(Message::AcceptTask(task_id));
+                                }
+                            }
+                        }
+                        PendingAction::PopTrackedStash { stash_sha } => {
+                            // User confirmed popping the stash
+                            commands.push(Message::PopTrackedStash { stash_sha });
+                        }
+                        PendingAction::RestartSessionWithSummary(task_id) => {
+                            commands.push(Message::RestartSessionWithSummary(task_id));
+                        }
+                        PendingAction::InitGit { path, name, slot } => {
+                            // Initialize git repository
+                            match crate::worktree::git::init_repo(&path) {
+                                Ok(()) => {
+                                    // After git init, create initial commit
+                                    match crate::worktree::git::create_initial_commit(&path) {
+                                        Ok(()) => {
+                                            // Now open the project
+                                            let mut project = Project::new(name.clone(), path);
+                                            project.load_tasks();
+                                            let has_tasks = !project.tasks.is_empty();
+                                            self.model.projects.push(project);
+                                            self.model.active_project_idx = slot;
+                                            self.model.ui_state.selected_task_idx = None;
+                                            // Focus TaskInput if project has no tasks, otherwise KanbanBoard
+                                            self.model.ui_state.focus = if has_tasks {
+                                                FocusArea::KanbanBoard
+                                            } else {
+                                                FocusArea::TaskInput
+                                            };
+                                            commands.push(Message::SetStatusMessage(Some(
+                                                format!("Initialized git and created initial commit for '{}'", name)
+                                            )));
+                                        }
+                                        Err(e) => {
+                                            commands.push(Message::Error(format!(
+                                                "Git initialized but failed to create initial commit: {}", e
+                                            )));
+                                        }
+                                    }
+                                }
+                                Err(e) => {
+                                    commands.push(Message::Error(format!(
+                                        "Failed to initialize git: {}", e
+                                    )));
+                                }
+                            }
+                        }
+                        PendingAction::CreateInitialCommit { path, name, slot } => {
+                            // Create initial commit in existing git repo
+                            match crate::worktree::git::create_initial_commit(&path) {
+                                Ok(()) => {
+                                    // Now open the project
+                                    let mut project = Project::new(name.clone(), path);
+                                    project.load_tasks();
+                                    let has_tasks = !project.tasks.is_empty();
+                                    self.model.projects.push(project);
+                                    self.model.active_project_idx = slot;
+                                    self.model.ui_state.selected_task_idx = None;
+                                    // Focus TaskInput if project has no tasks, otherwise KanbanBoard
+                                    self.model.ui_state.focus = if has_tasks {
+                                        FocusArea::KanbanBoard
+                                    } else {
+                                        FocusArea::TaskInput
+                                    };
+                                    commands.push(Message::SetStatusMessage(Some(
+                                        format!("Created initial commit for '{}'", name)
+                                    )));
+                                }
+                                Err(e) => {
+                                    commands.push(Message::Error(format!(
+                                        "Failed to create initial commit: {}", e
+                                    )));
+                                }
+                            }
+                        }
+                        PendingAction::ApplyConflict { task_id, .. } => {
+                            // User chose to use smart apply with Claude
+                            commands.push(Message::StartApplySession { task_id });
+                        }
+                        PendingAction::RebaseConflict(task_id) => {
+                            commands.push(Message::SetStatusMessage(Some(
+                                "Conflicts detected, starting smart update...".to_string()
+                            )));
+                            commands.push(Message::StartUpdateRebaseSession { task_id });
+                        }
+                        PendingAction::ReconcileWorktrees(issues) => {
+                            let Some(sender) = self.async_sender.clone() else {
+                                return commands;
+                            };
+                            commands.push(Message::SetStatusMessage(Some(
+                                "Reconciling worktrees...".to_string()
+                            )));
+                            tokio::spawn(async move {
+                                let results = tokio::task::spawn_blocking(move || {
+                                    issues.into_iter()
+                                        .map(|issue| {
+                                            let result = crate::worktree::integrity::fix_issue(&issue)
+                                                .map_err(|e| e.to_string());
+                                            (issue, result)
+                                        })
+                                        .collect()
+                                }).await.unwrap_or_default();
+                                let _ = sender.send(Message::WorktreesReconciled(results));
+                            });
+                        }
+                        PendingAction::RebaseForApplyReady { task_id } => {
+                            // User confirmed apply after rebase (bootstrap mode)
+                            commands.push(Message::SetStatusMessage(Some(
+                                "Applying...".to_string()
+                            )));
+                            commands.push(Message::SmartApplyTask(task_id));
+                        }
+                        PendingAction::UpdateGitignore { path, scope, name, slot, .. } => {
+                            // User confirmed adding KanBlam entries to .gitignore
+                            match crate::worktree::git::ensure_gitignore_has_kanblam_entries(&path) {
+                                Ok(()) => {
+                                    // Now open the project
+                                    let mut project = Project::new(name.clone(), path);
+                                    project.scope = scope;
+                                    project.load_tasks();
+                                    self.model.projects.push(project);
+                                    self.model.active_project_idx = slot;
+                                    self.model.ui_state.selected_task_idx = None;
+                                    self.model.ui_state.focus = FocusArea::KanbanBoard;
+                                    commands.push(Message::SetStatusMessage(Some(
+                                        format!("Updated .gitignore and opened '{}'", name)
+                                    )));
+                                }
+                                Err(e) => {
+                                    commands.push(Message::Error(format!(
+                                        "Failed to update .gitignore: {}", e
+                                    )));
+                                }
+                            }
+                        }
+                        PendingAction::ReleaseVersion { plan } => {
+                            // User confirmed the previewed release: bump, commit and tag
+                            let Some(project_dir) = self.model.active_project().map(|p| p.working_dir.clone()) else {
+                                return commands;
+                            };
+                            match crate::release::version::execute_release(&project_dir, &plan) {
+                                Ok(()) => {
+                                    commands.push(Message::SetStatusMessage(Some(
+                                        format!("Released {} ({} → {})", plan.tag, plan.from, plan.to)
+                                    )));
+                                }
+                                Err(e) => {
+                                    commands.push(Message::Error(format!(
+                                        "Release failed: {}", e
+                                    )));
+                                }
+                            }
+                        }
+                        PendingAction::CreateErrorTask { project_dir, source, error } => {
+                            // File the error as a Planned bug task in the project it came from
+                            if let Some(project) = self.model.projects.iter_mut().find(|p| p.working_dir == project_dir) {
+                                let mut task = Task::new(error.task_title(&source));
+                                task.log_activity_with_output(format!("Captured from {}", source), Some(error.trace));
+                                let task_id = task.id;
+                                project.tasks.insert(0, task);
+                                commands.push(Message::RequestTitleSummary { task_id });
+                                commands.push(Message::SetStatusMessage(Some(
+                                    format!("Bug task created from {}", source)
+                                )));
+                            }
+                        }
+                    }
+                }
+                // More errors may have arrived while this prompt was open
+                commands.push(Message::ShowNextErrorOffer);
+            }
+
+            Message::CancelAction => {
+                // Reset scroll offset when confirmation is dismissed
+                self.model.ui_state.confirmation_scroll_offset = 0;
+                if let Some(confirmation) = self.model.ui_state.pending_confirmation.take() {
+                    // Show manual instructions when user cancels
+                    match confirmation.action {
+                        PendingAction::DeleteTask(_) => {
+                            // Just clear the confirmation, no message needed
+                        }
+                        PendingAction::MarkDoneNoMerge(_) => {
+                            // Just clear the confirmation, task stays in Review
+                            commands.push(Message::SetStatusMessage(Some(
+                                "Task left in Review.".to_string()
+                            )));
+                        }
+                        PendingAction::CloseProject(_) => {
+                            // User cancelled closing project, no message needed
+                        }
+                        PendingAction::AcceptTask(_) | PendingAction::DeclineTask(_) | PendingAction::CommitAppliedChanges(_) | PendingAction::MergeOnlyTask(_) => {
+                            // User cancelled, task stays in Review
+                            commands.push(Message::SetStatusMessage(Some(
+                                "Cancelled. Task left in Review.".to_string()
+                            )));
+                        }
+                        PendingAction::CleanupMergedTask(_) => {
+                            // User cancelled cleanup, task stays in Review
+                            commands.push(Message::SetStatusMessage(Some(
+                                "Cleanup cancelled. Task left in Review.".to_string()
+                            )));
+                        }
+                        PendingAction::ViewMergeReport => {
+                            // View-only modal dismissed - no message needed
+                        }
+                        PendingAction::ResetTask(_) => {
+                            // User cancelled reset - no message needed
+                        }
+                        PendingAction::ForceUnapply(_) => {
+                            // User declined destructive unapply - changes remain applied
+                            commands.push(Message::SetStatusMessage(Some(
+                                "Changes still applied. Use 'u' to try surgical unapply again.".to_string()
+                            )));
+                        }
+                        PendingAction::StashConflict { task_id, stash_sha } => {
+                            // User pressed 'n' - unapply task changes and restore user's original work
+                            commands.push(Message::ForceUnapplyWithStashRestore { task_id, stash_sha });
+                        }
+                        PendingAction::InterruptSdkForCli(_) => {
+                            // User cancelled opening CLI - SDK continues running
+                            commands.push(Message::SetStatusMessage(Some(
+                                "Cancelled. Claude continues working via SDK.".to_string()
+                            )));
+                        }
+                        PendingAction::InterruptSdkForFeedback { .. } => {
+                            // User cancelled sending feedback - SDK continues working
+                            commands.push(Message::SetStatusMessage(Some(
+                                "Cancelled. Claude continues working.".to_string()
+                            )));
+                        }
+                        PendingAction::InterruptCliForFeedback { .. } => {
+                            // User cancelled sending feedback - CLI continues
+                            commands.push(Message::SetStatusMessage(Some(
+                                "Cancelled. Press 'o' to view CLI.".to_string()
+                            )));
+                        }
+                        PendingAction::BroadcastFeedback { .. } => {
+                            commands.push(Message::SetStatusMessage(Some(
+                                "Broadcast cancelled. Nothing was sent.".to_string()
+                            )));
+                        }
+                        PendingAction::BroadcastReport => {
+                            // Report dismissed - no message needed
+                        }
+                        PendingAction::LargePaste { .. } | PendingAction::AttachClipboardFiles { .. } => {
+                            commands.push(Message::SetStatusMessage(Some("Paste cancelled".to_string())));
+                        }
+                        PendingAction::DirtyMainBeforeMerge { .. } => {
+                            // User cancelled merge due to dirty worktree
+                            commands.push(Message::SetStatusMessage(Some(
+                                "Merge cancelled. Commit or stash your changes first.".to_string()
+                            )));
+                        }
+                        PendingAction::RestartSessionWithSummary(_) => {
+                            commands.push(Message::SetStatusMessage(Some(
+                                "Session continues; the SDK compacts its context on its own".to_string()
+                            )));
+                        }
+                        PendingAction::PopTrackedStash { .. } => {
+                            // User declined to pop stash - no action needed
+                            commands.push(Message::SetStatusMessage(Some(
+                                "Stash preserved. Press 'S' to manage stashes.".to_string()
+                            )));
+                        }
+                        PendingAction::InitGit { .. } => {
+                            // User declined to initialize git - project not opened
+                            commands.push(Message::SetStatusMessage(Some(
+                                "Project not opened. Initialize git manually to use with KanBlam.".to_string()
+                            )));
+                        }
+                        PendingAction::CreateInitialCommit { .. } => {
+                            // User declined to create initial commit - project not opened
+                            commands.push(Message::SetStatusMessage(Some(
+                                "Project not opened. Create an initial commit to use with KanBlam.".to_string()
+                            )));
+                        }
+                        PendingAction::ApplyConflict { .. } => {
+                            // User cancelled smart apply - nothing to do
+                            commands.push(Message::SetStatusMessage(Some(
+                                "Apply cancelled. Use 'p' to try again.".to_string()
+                            )));
+                        }
+                        PendingAction::RebaseConflict(task_id) => {
+                            // Leave the task as it was before the update
+                            if let Some(project) = self.model.active_project_mut() {
+                                if let Some(task) = project.tasks.iter_mut().find(|t| t.id == task_id) {
+                                    task.move_to_review();
+                                }
+                            }
+                            commands.push(Message::SetStatusMessage(Some(
+                                "Update cancelled. Use 'r' to try again.".to_string()
+                            )));
+                        }
+                        PendingAction::ReconcileWorktrees(issues) => {
+                            commands.push(Message::SetStatusMessage(Some(format!(
+                                "Left {} worktree problem(s) as they are",
+                                issues.len()
+                            ))));
+                        }
+                        PendingAction::RebaseForApplyReady { .. } => {
+                            // User cancelled apply after rebase - task stays rebased in Review
+                            commands.push(Message::SetStatusMessage(Some(
+                                "Cancelled. Task rebased and ready in Review.".to_string()
+                            )));
+                        }
+                        PendingAction::UpdateGitignore { path, scope, name, slot, .. } => {
+                            // User declined to update .gitignore - open anyway but warn
+                            let mut project = Project::new(name.clone(), path);
+                            project.scope = scope;
+                            project.load_tasks();
+                            self.model.projects.push(project);
+                            self.model.active_project_idx = slot;
+                            self.model.ui_state.selected_task_idx = None;
+                            self.model.ui_state.focus = FocusArea::KanbanBoard;
+                            commands.push(Message::SetStatusMessage(Some(
+                                format!("Opened '{}' (warning: .gitignore not updated)", name)
+                            )));
+                        }
+                        PendingAction::ReleaseVersion { plan } => {
+                            // User declined the release - merge stays as is
+                            commands.push(Message::SetStatusMessage(Some(
+                                format!("Release {} skipped.", plan.tag)
+                            )));
+                        }
+                        PendingAction::CreateErrorTask { .. } => {
+                            // Ignored - the signature stays recorded so it isn't offered again
+                        }
+                    }
+                }
+                commands.push(Message::ShowNextErrorOffer);
+            }
+
+            Message::RestartConfirmationAnimation => {
+                // Restart the highlight sweep animation when user presses an unrecognized key
+                // This signals that they need to respond to the prompt first
+                if let Some(ref mut confirmation) = self.model.ui_state.pending_confirmation {
+                    confirmation.animation_tick = 20;
+                }
+            }
 
-                            if let Some((project_slug, project_dir, window_name, worktree_path, task_title, display_id)) = task_info {
-                                // Commit the applied changes to main
-                                match crate::worktree::commit_applied_changes(&project_dir, &task_title, &display_id) {
-                                    Ok(_) => {
-                                        // Clean up patch file (stash was already popped during apply)
-                                        crate::worktree::cleanup_applied_state(&display_id);
+            Message::ScrollConfirmationUp => {
+                // Scroll up in multiline confirmation modal
+                if self.model.ui_state.pending_confirmation.is_some() {
+                    self.model.ui_state.confirmation_scroll_offset =
+                        self.model.ui_state.confirmation_scroll_offset.saturating_sub(1);
+                }
+            }
 
-                                        // Clear applied state
-                                        if let Some(project) = self.model.active_project_mut() {
-                                            project.applied_task_id = None;
-                                            project.applied_stash_ref = None;
-                                            project.applied_with_conflict_resolution = false;
-                                        }
+            Message::ScrollConfirmationDown => {
+                // Scroll down in multiline confirmation modal
+                if let Some(ref confirmation) = self.model.ui_state.pending_confirmation {
+                    let line_count = confirmation.message.lines().count();
+                    // Allow scrolling up to line_count - 1 (so at least one line is visible)
+                    let max_offset = line_count.saturating_sub(1);
+                    if self.model.ui_state.confirmation_scroll_offset < max_offset {
+                        self.model.ui_state.confirmation_scroll_offset += 1;
+                    }
+                }
+            }
 
-                                        // Kill tmux window if exists
-                                        if let Some(ref window) = window_name {
-                                            let _ = crate::tmux::kill_task_window(&project_slug, window);
-                                        }
+            Message::SetStatusMessage(msg) => {
+                self.model.ui_state.status_message = msg.clone();
+                // Set decay timer: ~5 seconds (50 ticks at 100ms each)
+                // Longer messages get more time to read
+                self.model.ui_state.status_message_decay = if msg.is_some() {
+                    50 + (msg.as_ref().map(|m| m.len() as u16 / 3).unwrap_or(0))
+                } else {
+                    0
+                };
+            }
 
-                                        // Stop SDK session
-                                        if let Some(ref client) = self.sidecar_client {
-                                            let _ = client.stop_session(task_id);
-                                        }
+            Message::ToggleFocusTimer => {
+                if self.model.ui_state.focus_picker.take().is_some() {
+                    return commands;
+                }
+                if let Some(timer) = self.model.ui_state.focus_timer.take() {
+                    let focused = timer.focused_secs(&self.model.global_settings);
+                    if let Some(task_id) = timer.task_id {
+                        self.log_focus_time(task_id, focused);
+                    }
+                    notify::set_focus_task(None);
+                    commands.push(Message::SetStatusMessage(Some("Focus timer stopped".to_string())));
+                    commands.extend(self.release_deferred_starts());
+                } else {
+                    // Pick the task and length first
+                    let selected = self.model.ui_state.selected_task_id
+                        .filter(|id| self.model.active_project().is_some_and(|p| p.tasks.iter().any(|t| t.id == *id)));
+                    self.model.ui_state.focus_picker = Some(crate::model::FocusPicker {
+                        task_id: selected,
+                        selected_task_id: selected,
+                        minutes: self.model.global_settings.focus_minutes,
+                    });
+                }
+            }
 
-                                        // Kill any detached sessions (uses display_id as session name)
-                                        crate::tmux::kill_task_sessions(&display_id);
+            Message::FocusPickerAdjust(steps) => {
+                if let Some(ref mut picker) = self.model.ui_state.focus_picker {
+                    picker.adjust(steps);
+                }
+            }
 
-                                        // Remove worktree
-                                        if let Some(ref wt_path) = worktree_path {
-                                            let _ = crate::worktree::remove_worktree(&project_dir, wt_path);
-                                            let _ = crate::worktree::remove_worktree_trust(wt_path);
-                                        }
+            Message::FocusPickerToggleTask => {
+                if let Some(ref mut picker) = self.model.ui_state.focus_picker {
+                    picker.toggle_task();
+                }
+            }
 
-                                        // Delete branch
-                                        let _ = crate::worktree::delete_branch(&project_dir, &display_id);
+            Message::StartFocusSession => {
+                let Some(picker) = self.model.ui_state.focus_picker.take() else {
+                    return commands;
+                };
+                self.model.ui_state.focus_timer = Some(crate::model::FocusTimer::start(picker.task_id, picker.minutes));
+                notify::set_focus_task(picker.task_id);
+                let on = picker.task_id
+                    .map(|id| format!(" on {}", self.get_task_display_id(id)))
+                    .unwrap_or_default();
+                commands.push(Message::SetStatusMessage(Some(format!(
+                    "Focus block started{} ({} min)",
+                    on, picker.minutes
+                ))));
+            }
+
+            Message::FocusPhaseElapsed => {
+                use crate::model::FocusPhase;
+
+                let settings = &self.model.global_settings;
+                let Some(ref mut timer) = self.model.ui_state.focus_timer else {
+                    return commands;
+                };
+                // Several ticks may have queued this before the first one was handled
+                if timer.remaining_secs(settings) > 0 {
+                    return commands;
+                }
+
+                let focused = timer.focused_secs(settings);
+                let task_id = timer.task_id;
+                let message = match timer.advance() {
+                    FocusPhase::Break => format!(
+                        "Focus block {} done - take a {} min break",
+                        timer.completed_blocks, settings.break_minutes
+                    ),
+                    FocusPhase::Focus => format!("Break over - focus for {} min", timer.focus_minutes),
+                };
+                // Other tasks may notify during the break
+                match timer.phase {
+                    FocusPhase::Break => {
+                        notify::set_focus_task(None);
+                        if let Some(task_id) = task_id {
+                            self.log_focus_time(task_id, focused);
+                        }
+                    }
+                    FocusPhase::Focus => notify::set_focus_task(task_id),
+                }
+                notify::play_attention_sound();
+                commands.push(Message::SetStatusMessage(Some(message)));
+                commands.extend(self.release_deferred_starts());
+            }
+
+            Message::CheckSla => {
+                let sla = crate::sla::SlaThresholds::from_setting(self.model.global_settings.sla_thresholds.as_deref());
+                let now = Utc::now();
+                let mut first_nudge = None;
+                for project in &mut self.model.projects {
+                    let mut stuck = Vec::new();
+                    for task in &mut project.tasks {
+                        let Some(elapsed) = task.time_in_status(now) else { continue };
+                        if task.sla_notified || !sla.is_overdue(task.status, elapsed) {
+                            continue;
+                        }
+                        task.sla_notified = true;
+                        let age = crate::sla::format_age(elapsed);
+                        task.log_event(crate::model::ActivityKind::Event, format!("In {} for {}, past its time limit", task.status.label(), age), None);
+                        stuck.push((task.id, format!("{} has been in {} for {}", task.display_id(), task.status.label(), age)));
+                    }
+                    let Some((task_id, nudge)) = stuck.first() else { continue };
+
+                    // Let the mascot mention it, unless it's busy saying something else
+                    if project.watcher_enabled && project.watcher_comment.is_none() && !project.watcher_observing {
+                        project.watcher_comment = Some(crate::model::WatcherCommentDisplay::new(
+                            format!("{} - is it stuck?", nudge),
+                            crate::sidecar::protocol::WatcherMood::Concerned,
+                            None,
+                        ));
+                    }
+                    let others = if stuck.len() > 1 { format!(" (+{} more)", stuck.len() - 1) } else { String::new() };
+                    first_nudge.get_or_insert((*task_id, project.name.clone(), format!("⏰ {}{}", nudge, others)));
+                }
+
+                if let Some((task_id, project_name, message)) = first_nudge {
+                    notify::play_task_cue(task_id, notify::SoundCue::Attention);
+                    notify::set_task_attention_indicator(task_id, &project_name);
+                    commands.push(Message::SetStatusMessage(Some(message)));
+                }
+            }
+
+            Message::TriggerLogoShimmer => {
+                // Start the shimmer animation (frame 1 = bottom row lit)
+                self.model.ui_state.logo_shimmer_frame = 1;
+                // Use animated star eyes for commit/merge celebrations
+                // Longer duration (10 ticks = ~1 second) to show the sparkle animation
+                self.model.ui_state.eye_animation = EyeAnimation::StarEyes;
+                self.model.ui_state.eye_animation_ticks_remaining = 10;
+            }
+
+            Message::TriggerMergeCelebration { task_id, display_text, column_status, task_index, pending_completion } => {
+                // Start the "gold dust sweep" celebration animation
+                self.model.ui_state.merge_celebration = Some(crate::model::MergeCelebrationState {
+                    task_id,
+                    original_text: display_text,
+                    frame: 0,
+                    column_status,
+                    task_index,
+                    pending_completion,
+                });
+            }
+
+            Message::FinishMergeCelebration(task_id) => {
+                // Animation complete - now actually complete the task
+                if let Some(project) = self.model.active_project_mut() {
+                    project.complete_task(task_id);
+                    project.needs_attention = project.review_count() > 0;
+                    if !project.needs_attention {
+                        notify::clear_attention_indicator();
+                    }
+                }
+            }
+
+            Message::TriggerMascotBlink => {
+                // Trigger a random eye animation when clicking the mascot
+                self.model.ui_state.eye_animation = EyeAnimation::random();
+                self.model.ui_state.eye_animation_ticks_remaining = 2;
+                // Also trigger watcher observation if enabled
+                if let Some(project) = self.model.active_project() {
+                    if project.watcher_enabled {
+                        commands.push(Message::TriggerWatcher);
+                    }
+                }
+            }
+
+            Message::ShowStartupHints => {
+                // Show the startup hints bar again (triggered by pressing ESC multiple times)
+                // Reset to 100 ticks (10 seconds) to match initial display
+                self.model.ui_state.startup_hint_until_tick = Some(100);
+                // Reset the ESC counter so they need to press ESC twice again
+                self.model.ui_state.consecutive_esc_count = 0;
+            }
+
+            Message::WelcomeBubbleFocus => {
+                self.model.ui_state.welcome_bubble_focused = true;
+            }
+
+            Message::WelcomeBubbleUnfocus => {
+                self.model.ui_state.welcome_bubble_focused = false;
+            }
+
+            Message::WelcomeMessagePrev => {
+                let count = crate::ui::welcome_message_count();
+                self.model.ui_state.welcome_message_idx =
+                    (self.model.ui_state.welcome_message_idx + count - 1) % count;
+                // Reset cooldown so it doesn't immediately rotate
+                self.model.ui_state.welcome_message_cooldown = 80;
+            }
+
+            Message::WelcomeMessageNext => {
+                let count = crate::ui::welcome_message_count();
+                self.model.ui_state.welcome_message_idx =
+                    (self.model.ui_state.welcome_message_idx + 1) % count;
+                // Reset cooldown so it doesn't immediately rotate
+                self.model.ui_state.welcome_message_cooldown = 80;
+            }
+
+            Message::HookSignalReceived(signal) => {
+                // Try to find task by task_id first (worktree-based tasks use task UUID as session_id)
+                let task_uuid = uuid::Uuid::parse_str(&signal.session_id).ok();
+
+                // Find the task either by UUID or by worktree path
+                let signal_dir = signal.project_dir.canonicalize().unwrap_or(signal.project_dir.clone());
+
+                // Capture replay flag before mutable borrow of projects
+                // During signal replay on startup, we suppress audio notifications
+                let replaying_signals = self.model.ui_state.replaying_signals;
+
+                // The signal may be for a project that hasn't been opened yet this run
+                for idx in 0..self.model.projects.len() {
+                    let project = &self.model.projects[idx];
+                    let project_dir = project.working_dir.canonicalize().unwrap_or(project.working_dir.clone());
+                    if !project.loaded && signal_dir.starts_with(&project_dir) {
+                        let _ = self.load_project(idx);
+                    }
+                }
+
+                let mut found_task = None;
+
+                for project in &mut self.model.projects {
+                    // Find task by UUID or by worktree path
+                    let task_idx = project.tasks.iter().position(|t| {
+                        // Match by UUID (for worktree-based tasks)
+                        if let Some(uuid) = task_uuid {
+                            if t.id == uuid {
+                                return true;
+                            }
+                        }
+                        // Match by worktree path
+                        if let Some(ref wt_path) = t.worktree_path {
+                            let wt_canonical = wt_path.canonicalize().unwrap_or(wt_path.clone());
+                            if wt_canonical == signal_dir {
+                                return true;
+                            }
+                        }
+                        false
+                    });
+
+                    if let Some(idx) = task_idx {
+                        let task_id = project.tasks[idx].id;
+                        // Check if there's a queued task before getting mutable ref
+                        let has_queued = project.next_queued_for(task_id).is_some();
+                        let was_accepting = project.tasks[idx].status == TaskStatus::Accepting;
+                        let was_updating = project.tasks[idx].status == TaskStatus::Updating;
+                        let was_applying = project.tasks[idx].status == TaskStatus::Applying;
+                        // Terminal states - tasks that are Done should not be moved back
+                        let is_terminal = project.tasks[idx].status == TaskStatus::Done;
+                        let was_waiting_for_cli = project.tasks[idx].session_mode == crate::model::SessionMode::WaitingForCliExit;
+                        let project_name = project.name.clone();
+                        let project_slug = project.slug();
 
-                                        // Trigger celebratory animations - task completion deferred until animation ends
-                                        commands.push(Message::TriggerLogoShimmer);
-                                        if let Some((display_text, task_index)) = celebration_info {
-                                            commands.push(Message::TriggerMergeCelebration {
-                                                task_id,
-                                                display_text,
-                                                column_status: TaskStatus::Review,
-                                                task_index,
-                                                pending_completion: true,
-                                            });
-                                        } else {
-                                            // No animation - complete immediately
-                                            if let Some(project) = self.model.active_project_mut() {
-                                                project.complete_task(task_id);
-                                                project.needs_attention = project.review_count() > 0;
-                                                if !project.needs_attention {
-                                                    notify::clear_attention_indicator();
-                                                }
-                                            }
-                                        }
+                        let was_cli_actively_working = project.tasks[idx].session_mode == crate::model::SessionMode::CliActivelyWorking;
 
-                                        commands.push(Message::SetStatusMessage(Some(
-                                            "✓ Changes committed to main. Task complete!".to_string()
-                                        )));
-                                    }
-                                    Err(e) => {
-                                        commands.push(Message::Error(format!(
-                                            "Failed to commit changes: {}", e
-                                        )));
-                                    }
+                        let task = &mut project.tasks[idx];
+                        found_task = Some(task_id);
+
+                        // Track CLI activity state for SDK/CLI handoff coordination
+                        // When CLI is in CliInteractive or CliActivelyWorking mode, update state based on hooks
+                        if matches!(task.session_mode, crate::model::SessionMode::CliInteractive | crate::model::SessionMode::CliActivelyWorking) {
+                            match signal.event.as_str() {
+                                "working" | "input-provided" | "tool-start" | "tool-finish" | "tool-error"
+                                | "subagent-start" | "subagent-stop" => {
+                                    // CLI is actively working (user submitted input or tool is running)
+                                    task.session_mode = crate::model::SessionMode::CliActivelyWorking;
+                                }
+                                "stop" | "end" | "needs-input" => {
+                                    // CLI finished its turn, back to waiting for input
+                                    task.session_mode = crate::model::SessionMode::CliInteractive;
                                 }
+                                _ => {}
                             }
                         }
-                        PendingAction::ResetTask(task_id) => {
-                            // Reset the task (cleanup and move to Planned)
-                            commands.push(Message::ResetTask(task_id));
-                        }
-                        PendingAction::ForceUnapply(task_id) => {
-                            // User confirmed destructive unapply
-                            commands.push(Message::ForceUnapplyTaskChanges(task_id));
-                        }
-                        PendingAction::StashConflict { task_id, stash_sha } => {
-                            // User pressed 'y' - solve conflicts with Claude
-                            commands.push(Message::StartStashConflictSession { task_id, stash_sha });
-                        }
-                        PendingAction::InterruptSdkForCli(task_id) => {
-                            // User confirmed interrupting SDK to open CLI
-                            commands.push(Message::DoOpenInteractiveModal(task_id));
-                        }
-                        PendingAction::InterruptSdkForFeedback { task_id, feedback } => {
-                            // User confirmed interrupting SDK to send feedback (i=interrupt)
-                            commands.push(Message::DoSendFeedback { task_id, feedback });
+
+                        // Check if we're waiting for CLI to exit (SDK handoff case)
+                        // Only trigger SDK resume if CLI is NOT actively working
+                        // Skip during signal replay - we don't want to auto-resume sessions on startup
+                        if !replaying_signals && was_waiting_for_cli && matches!(signal.event.as_str(), "stop" | "end") {
+                            // CLI exited - resume SDK session
+                            // Note: Don't overwrite claude_session_id here - the signal uses task_id,
+                            // but we want to keep the real SDK session_id that was set when session started
+                            task.session_mode = crate::model::SessionMode::SdkManaged;
+                            commands.push(Message::CliSessionEnded { task_id });
+                            break;
                         }
-                        PendingAction::InterruptCliForFeedback { task_id, feedback } => {
-                            // User confirmed interrupting CLI to send feedback via SDK (i=interrupt)
-                            commands.push(Message::DoSendFeedback { task_id, feedback });
+
+                        // If we were actively working and got a stop/end, transition to WaitingForCliExit
+                        // so the SDK can pick up (unless user closes the modal)
+                        if was_cli_actively_working && matches!(signal.event.as_str(), "stop" | "end") {
+                            task.session_mode = crate::model::SessionMode::CliInteractive;
                         }
-                        PendingAction::DirtyMainBeforeMerge { task_id } => {
-                            // User chose to commit (y) - commit changes then proceed with merge
-                            let project_dir = self.model.active_project()
-                                .map(|p| p.working_dir.clone());
-                            if let Some(project_dir) = project_dir {
-                                if let Err(e) = crate::worktree::commit_main_changes(&project_dir) {
-                                    commands.push(Message::Error(format!(
-                                        "Failed to commit changes: {}", e
-                                    )));
+
+                        match signal.event.as_str() {
+                            "stop" => {
+                                // Skip terminal tasks - these are stale signals from before task was completed
+                                if is_terminal {
+                                    // Don't modify Done/Discarded tasks
+                                } else if was_accepting {
+                                    // Task was rebasing for accept - try to complete the accept
+                                    // Keep status as Accepting, CompleteAcceptTask will verify and update
+                                    commands.push(Message::CompleteAcceptTask(task_id));
+                                } else if was_updating {
+                                    // Task was rebasing for update - complete update (no merge!)
+                                    commands.push(Message::CompleteUpdateTask(task_id));
+                                } else if was_applying {
+                                    // Task was rebasing for apply - complete the apply
+                                    commands.push(Message::CompleteApplyTask(task_id));
+                                } else if has_queued {
+                                    // Don't move to review - send the queued task instead
+                                    // Move to end of Review tasks so first-finished appears at top
+                                    // (only if not already in Review from a duplicate hook)
+                                    task.session_state = crate::model::ClaudeSessionState::Paused;
+                                    if task.status != TaskStatus::Review {
+                                        project.move_task_to_start_of_status(task_id, TaskStatus::Review);
+                                    }
+                                    // Don't play attention sound - we're continuing automatically
+                                    commands.push(Message::SendQueuedTask { finished_task_id: task_id });
+                                } else if signal.source == "sdk" {
+                                    // SDK-sourced signal - ignore, SDK Stopped event handles QA/Review
+                                    // (SDK events include session output for QA marker detection)
                                 } else {
-                                    // Now proceed with the merge
-                                    commands.push(Message::AcceptTask(task_id));
+                                    // CLI-sourced signal - move to review and notify (no QA for CLI)
+                                    task.session_state = crate::model::ClaudeSessionState::Paused;
+                                    if task.status != TaskStatus::Review {
+                                        project.move_task_to_start_of_status(task_id, TaskStatus::Review);
+                                        project.needs_attention = true;
+                                        if !replaying_signals {
+                                            notify::play_task_cue(task_id, notify::SoundCue::Review);
+                                        }
+                                        notify::set_task_attention_indicator(task_id, &project_name);
+                                    }
                                 }
                             }
-                        }
-                        PendingAction::PopTrackedStash { stash_sha } => {
-                            // User confirmed popping the stash
-                            commands.push(Message::PopTrackedStash { stash_sha });
-                        }
-                        PendingAction::InitGit { path, name, slot } => {
-                            // Initialize git repository
-                            match crate::worktree::git::init_repo(&path) {
-                                Ok(()) => {
-                                    // After git init, create initial commit
-                                    match crate::worktree::git::create_initial_commit(&path) {
-                                        Ok(()) => {
-                                            // Now open the project
-                                            let mut project = Project::new(name.clone(), path);
-                                            project.load_tasks();
-                                            let has_tasks = !project.tasks.is_empty();
-                                            self.model.projects.push(project);
-                                            self.model.active_project_idx = slot;
-                                            self.model.ui_state.selected_task_idx = None;
-                                            // Focus TaskInput if project has no tasks, otherwise KanbanBoard
-                                            self.model.ui_state.focus = if has_tasks {
-                                                FocusArea::KanbanBoard
-                                            } else {
-                                                FocusArea::TaskInput
-                                            };
-                                            commands.push(Message::SetStatusMessage(Some(
-                                                format!("Initialized git and created initial commit for '{}'", name)
-                                            )));
+                            "end" => {
+                                // Skip terminal tasks - these are stale signals from before task was completed
+                                if is_terminal {
+                                    // Don't modify Done/Discarded tasks
+                                } else {
+                                    if was_accepting {
+                                        task.log_activity("Accept cancelled");
+                                        task.move_to_review();
+                                        task.session_state = crate::model::ClaudeSessionState::Ended;
+                                        commands.push(Message::SetStatusMessage(Some(
+                                            "Accept cancelled: Claude session ended during rebase.".to_string()
+                                        )));
+                                    } else if was_updating {
+                                        task.log_activity("Update cancelled");
+                                        task.move_to_review();
+                                        task.session_state = crate::model::ClaudeSessionState::Ended;
+                                        commands.push(Message::SetStatusMessage(Some(
+                                            "Update cancelled: Claude session ended during rebase.".to_string()
+                                        )));
+                                    } else if was_applying {
+                                        task.log_activity("Apply cancelled");
+                                        task.move_to_review();
+                                        task.session_state = crate::model::ClaudeSessionState::Ended;
+                                        commands.push(Message::SetStatusMessage(Some(
+                                            "Apply cancelled: Claude session ended during rebase.".to_string()
+                                        )));
+                                        project.needs_attention = true;
+                                        if !replaying_signals {
+                                            notify::play_task_cue(task_id, notify::SoundCue::Attention);
                                         }
-                                        Err(e) => {
-                                            commands.push(Message::Error(format!(
-                                                "Git initialized but failed to create initial commit: {}", e
-                                            )));
+                                        notify::set_task_attention_indicator(task_id, &project.name);
+                                    } else if signal.source == "sdk" {
+                                        // SDK-sourced signal - ignore, SDK Ended event handles it
+                                    } else if task.status != TaskStatus::Review {
+                                        // CLI-sourced signal - move to review and notify (no QA for CLI)
+                                        task.session_state = crate::model::ClaudeSessionState::Ended;
+                                        project.move_task_to_start_of_status(task_id, TaskStatus::Review);
+                                        project.needs_attention = true;
+                                        if !replaying_signals {
+                                            notify::play_task_cue(task_id, notify::SoundCue::Review);
                                         }
+                                        notify::set_task_attention_indicator(task_id, &project.name);
                                     }
                                 }
-                                Err(e) => {
-                                    commands.push(Message::Error(format!(
-                                        "Failed to initialize git: {}", e
-                                    )));
-                                }
                             }
-                        }
-                        PendingAction::CreateInitialCommit { path, name, slot } => {
-                            // Create initial commit in existing git repo
-                            match crate::worktree::git::create_initial_commit(&path) {
-                                Ok(()) => {
-                                    // Now open the project
-                                    let mut project = Project::new(name.clone(), path);
-                                    project.load_tasks();
-                                    let has_tasks = !project.tasks.is_empty();
-                                    self.model.projects.push(project);
-                                    self.model.active_project_idx = slot;
-                                    self.model.ui_state.selected_task_idx = None;
-                                    // Focus TaskInput if project has no tasks, otherwise KanbanBoard
-                                    self.model.ui_state.focus = if has_tasks {
-                                        FocusArea::KanbanBoard
-                                    } else {
-                                        FocusArea::TaskInput
-                                    };
-                                    commands.push(Message::SetStatusMessage(Some(
-                                        format!("Created initial commit for '{}'", name)
-                                    )));
-                                }
-                                Err(e) => {
-                                    commands.push(Message::Error(format!(
-                                        "Failed to create initial commit: {}", e
-                                    )));
+                            "needs-input" => {
+                                // Don't change status if task is in a special state
+                                if is_terminal {
+                                    // Skip - task already completed, this is a replayed signal
+                                } else if was_accepting || was_updating || was_applying {
+                                    // Skip - we're in the middle of a rebase operation
+                                } else if signal.input_type == "permission" {
+                                    // permission_prompt means Claude is blocked waiting for tool approval.
+                                    // Always move to NeedsWork, even from Review - this is unambiguous.
+                                    task.log_activity("Waiting for permission...");
+                                    task.status = TaskStatus::NeedsWork;
+                                    task.session_state = crate::model::ClaudeSessionState::Paused;
+                                    project.needs_attention = true;
+                                    if !replaying_signals {
+                                        notify::play_task_cue(task_id, notify::SoundCue::NeedsInput);
+                                    }
+                                    notify::set_task_attention_indicator(task_id, &project.name);
+                                } else if signal.input_type == "idle" && task.status == TaskStatus::Review {
+                                    // idle_prompt fires after 60+ seconds of Claude being idle.
+                                    // Task is already in Review (from Stop hook). Check if Claude
+                                    // actually asked a question by examining tmux pane content.
+                                    if let Some(ref window_name) = task.tmux_window {
+                                        if crate::tmux::claude_output_contains_question(&project_slug, window_name) {
+                                            task.log_activity("Waiting for answer...");
+                                            task.status = TaskStatus::NeedsWork;
+                                            task.session_state = crate::model::ClaudeSessionState::Paused;
+                                            project.needs_attention = true;
+                                            if !replaying_signals {
+                                                notify::play_task_cue(task_id, notify::SoundCue::NeedsInput);
+                                            }
+                                            notify::set_task_attention_indicator(task_id, &project.name);
+                                        }
+                                        // Otherwise, Claude is just idle after finishing - stay in Review
+                                    }
+                                } else if task.status != TaskStatus::Review {
+                                    // idle_prompt or unknown type - only move to NeedsWork if NOT
+                                    // already in Review. The idle_prompt fires both when Claude asks
+                                    // a question AND when Claude is done but sitting at an idle prompt.
+                                    // We can't distinguish these cases, so trust the Review state.
+                                    task.log_activity("Waiting for input...");
+                                    task.status = TaskStatus::NeedsWork;
+                                    task.session_state = crate::model::ClaudeSessionState::Paused;
+                                    project.needs_attention = true;
+                                    if !replaying_signals {
+                                        notify::play_task_cue(task_id, notify::SoundCue::NeedsInput);
+                                    }
+                                    notify::set_task_attention_indicator(task_id, &project.name);
                                 }
                             }
-                        }
-                        PendingAction::ApplyConflict { task_id, .. } => {
-                            // User chose to use smart apply with Claude
-                            commands.push(Message::StartApplySession { task_id });
-                        }
-                        PendingAction::RebaseForApplyReady { task_id } => {
-                            // User confirmed apply after rebase (bootstrap mode)
-                            commands.push(Message::SetStatusMessage(Some(
-                                "Applying...".to_string()
-                            )));
-                            commands.push(Message::SmartApplyTask(task_id));
-                        }
-                        PendingAction::UpdateGitignore { path, name, slot, .. } => {
-                            // User confirmed adding KanBlam entries to .gitignore
-                            match crate::worktree::git::ensure_gitignore_has_kanblam_entries(&path) {
-                                Ok(()) => {
-                                    // Now open the project
-                                    let mut project = Project::new(name.clone(), path);
-                                    project.load_tasks();
-                                    self.model.projects.push(project);
-                                    self.model.active_project_idx = slot;
-                                    self.model.ui_state.selected_task_idx = None;
-                                    self.model.ui_state.focus = FocusArea::KanbanBoard;
-                                    commands.push(Message::SetStatusMessage(Some(
-                                        format!("Updated .gitignore and opened '{}'", name)
-                                    )));
+                            "input-provided" => {
+                                task.log_activity("Input received, continuing...");
+                                // Don't change status if task is in a special state (including QA/Testing)
+                                // For Review: only protect SDK-sourced signals (QA completion) - CLI signals
+                                // mean user is actively continuing work and should move back to InProgress
+                                let is_protected_review = task.status == TaskStatus::Review && signal.source == "sdk";
+                                if !was_accepting && !was_updating && !was_applying && !is_terminal
+                                    && task.status != TaskStatus::Testing
+                                    && !is_protected_review
+                                {
+                                    // Move to end of InProgress column so newly active tasks appear at bottom
+                                    project.move_task_to_start_of_status(task_id, TaskStatus::InProgress);
                                 }
-                                Err(e) => {
-                                    commands.push(Message::Error(format!(
-                                        "Failed to update .gitignore: {}", e
-                                    )));
+                                // Only set Working state if not in a protected/completed state
+                                // Late SDK signals shouldn't override Paused state for Review tasks
+                                if !is_terminal && !is_protected_review {
+                                    if let Some(task) = project.tasks.iter_mut().find(|t| t.id == task_id) {
+                                        task.session_state = crate::model::ClaudeSessionState::Working;
+                                    }
+                                    project.needs_attention = false;
+                                    notify::clear_attention_indicator();
+                                }
+                            }
+                            "working" | "tool-start" | "tool-finish" | "tool-error" | "subagent-start" | "subagent-stop" => {
+                                // PreToolUse/PostToolUse signal - Claude is using a tool (a
+                                // finished tool, e.g. after a permission prompt, means it's working again)
+                                // Don't process for terminal tasks (Done/Discarded) - these are stale signals
+                                if is_terminal {
+                                    // Skip - task already completed, this is a replayed signal
+                                } else {
+                                    let description = signal.payload.describe(&signal.event);
+                                    match signal.event.as_str() {
+                                        "working" => task.log_activity("Working..."),
+                                        // SDK sessions log their tool calls from sidecar events;
+                                        // finishes only matter for the session state
+                                        "tool-start" if signal.source != "sdk" => {
+                                            task.log_event(crate::model::ActivityKind::ToolCall, description, None)
+                                        }
+                                        "tool-start" | "tool-finish" => {}
+                                        "tool-error" => task.log_event(crate::model::ActivityKind::Error, description, None),
+                                        _ => task.log_event(crate::model::ActivityKind::Subagent, description, None),
+                                    }
+                                    // Don't override special statuses (rebase, QA, or Review from SDK)
+                                    // For Review: only protect SDK-sourced signals (QA completion) - CLI signals
+                                    // mean user is actively continuing work and should move back to InProgress
+                                    let is_protected_review = task.status == TaskStatus::Review && signal.source == "sdk";
+
+                                    // Grace period for CLI startup: ignore CLI working signals within 3 seconds
+                                    // of opening the terminal. Claude CLI may fire PreToolUse during session
+                                    // restore (e.g., reading context files) which isn't actual user work.
+                                    let is_cli_startup_grace_period = signal.source == "cli"
+                                        && task.status == TaskStatus::Review
+                                        && task.cli_opened_at.map_or(false, |opened_at| {
+                                            let grace_period = chrono::Duration::seconds(3);
+                                            chrono::Utc::now() - opened_at < grace_period
+                                        });
+
+                                    if !was_accepting && !was_updating && !was_applying
+                                        && task.status != TaskStatus::Testing
+                                        && !is_protected_review
+                                        && !is_cli_startup_grace_period
+                                    {
+                                        // Move to end of InProgress column so newly active tasks appear at bottom
+                                        project.move_task_to_start_of_status(task_id, TaskStatus::InProgress);
+                                    }
+                                    // Re-find task since move_task_to_start_of_status may have repositioned it
+                                    // Only set Working state if not in a protected Review state from SDK
+                                    // Late SDK signals shouldn't override Paused state for Review tasks
+                                    if !is_protected_review && !is_cli_startup_grace_period {
+                                        if let Some(task) = project.tasks.iter_mut().find(|t| t.id == task_id) {
+                                            task.session_state = crate::model::ClaudeSessionState::Working;
+                                        }
+                                        project.needs_attention = false;
+                                        notify::clear_attention_indicator();
+                                    }
                                 }
                             }
+                            _ => {}
                         }
+
+                        break;
                     }
                 }
-            }
 
-            Message::CancelAction => {
-                // Reset scroll offset when confirmation is dismissed
-                self.model.ui_state.confirmation_scroll_offset = 0;
-                if let Some(confirmation) = self.model.ui_state.pending_confirmation.take() {
-                    // Show manual instructions when user cancels
-                    match confirmation.action {
-                        PendingAction::DeleteTask(_) => {
-                            // Just clear the confirmation, no message needed
-                        }
-                        PendingAction::MarkDoneNoMerge(_) => {
-                            // Just clear the confirmation, task stays in Review
-                            commands.push(Message::SetStatusMessage(Some(
-                                "Task left in Review.".to_string()
-                            )));
-                        }
-                        PendingAction::CloseProject(_) => {
-                            // User cancelled closing project, no message needed
-                        }
-                        PendingAction::AcceptTask(_) | PendingAction::DeclineTask(_) | PendingAction::CommitAppliedChanges(_) | PendingAction::MergeOnlyTask(_) => {
-                            // User cancelled, task stays in Review
-                            commands.push(Message::SetStatusMessage(Some(
-                                "Cancelled. Task left in Review.".to_string()
-                            )));
-                        }
-                        PendingAction::CleanupMergedTask(_) => {
-                            // User cancelled cleanup, task stays in Review
-                            commands.push(Message::SetStatusMessage(Some(
-                                "Cleanup cancelled. Task left in Review.".to_string()
-                            )));
-                        }
-                        PendingAction::ViewMergeReport => {
-                            // View-only modal dismissed - no message needed
-                        }
-                        PendingAction::ResetTask(_) => {
-                            // User cancelled reset - no message needed
-                        }
-                        PendingAction::ForceUnapply(_) => {
-                            // User declined destructive unapply - changes remain applied
-                            commands.push(Message::SetStatusMessage(Some(
-                                "Changes still applied. Use 'u' to try surgical unapply again.".to_string()
-                            )));
-                        }
-                        PendingAction::StashConflict { task_id, stash_sha } => {
-                            // User pressed 'n' - unapply task changes and restore user's original work
-                            commands.push(Message::ForceUnapplyWithStashRestore { task_id, stash_sha });
-                        }
-                        PendingAction::InterruptSdkForCli(_) => {
-                            // User cancelled opening CLI - SDK continues running
-                            commands.push(Message::SetStatusMessage(Some(
-                                "Cancelled. Claude continues working via SDK.".to_string()
-                            )));
-                        }
-                        PendingAction::InterruptSdkForFeedback { .. } => {
-                            // User cancelled sending feedback - SDK continues working
-                            commands.push(Message::SetStatusMessage(Some(
-                                "Cancelled. Claude continues working.".to_string()
-                            )));
-                        }
-                        PendingAction::InterruptCliForFeedback { .. } => {
-                            // User cancelled sending feedback - CLI continues
-                            commands.push(Message::SetStatusMessage(Some(
-                                "Cancelled. Press 'o' to view CLI.".to_string()
-                            )));
-                        }
-                        PendingAction::DirtyMainBeforeMerge { .. } => {
-                            // User cancelled merge due to dirty worktree
-                            commands.push(Message::SetStatusMessage(Some(
-                                "Merge cancelled. Commit or stash your changes first.".to_string()
-                            )));
-                        }
-                        PendingAction::PopTrackedStash { .. } => {
-                            // User declined to pop stash - no action needed
-                            commands.push(Message::SetStatusMessage(Some(
-                                "Stash preserved. Press 'S' to manage stashes.".to_string()
-                            )));
-                        }
-                        PendingAction::InitGit { .. } => {
-                            // User declined to initialize git - project not opened
-                            commands.push(Message::SetStatusMessage(Some(
-                                "Project not opened. Initialize git manually to use with KanBlam.".to_string()
-                            )));
-                        }
-                        PendingAction::CreateInitialCommit { .. } => {
-                            // User declined to create initial commit - project not opened
-                            commands.push(Message::SetStatusMessage(Some(
-                                "Project not opened. Create an initial commit to use with KanBlam.".to_string()
-                            )));
-                        }
-                        PendingAction::ApplyConflict { .. } => {
-                            // User cancelled smart apply - nothing to do
-                            commands.push(Message::SetStatusMessage(Some(
-                                "Apply cancelled. Use 'p' to try again.".to_string()
-                            )));
-                        }
-                        PendingAction::RebaseForApplyReady { .. } => {
-                            // User cancelled apply after rebase - task stays rebased in Review
-                            commands.push(Message::SetStatusMessage(Some(
-                                "Cancelled. Task rebased and ready in Review.".to_string()
-                            )));
-                        }
-                        PendingAction::UpdateGitignore { path, name, slot, .. } => {
-                            // User declined to update .gitignore - open anyway but warn
-                            let mut project = Project::new(name.clone(), path);
-                            project.load_tasks();
-                            self.model.projects.push(project);
-                            self.model.active_project_idx = slot;
-                            self.model.ui_state.selected_task_idx = None;
-                            self.model.ui_state.focus = FocusArea::KanbanBoard;
-                            commands.push(Message::SetStatusMessage(Some(
-                                format!("Opened '{}' (warning: .gitignore not updated)", name)
-                            )));
+                // Handle pending feedback after the main loop (avoid borrow conflicts)
+                // We need to re-find the task since the previous borrow ended
+                if matches!(signal.event.as_str(), "stop" | "needs-input") {
+                    if let Some(task_uuid) = task_uuid {
+                        for project in &mut self.model.projects {
+                            if let Some(task) = project.tasks.iter_mut().find(|t| t.id == task_uuid) {
+                                if !task.queued_feedback.is_empty() {
+                                    let feedback = task.queued_feedback.remove(0);
+                                    // Claude finished - send the queued feedback
+                                    task.log_activity(&format!("Sending queued feedback: {}...",
+                                        if feedback.len() > 20 { &feedback[..20] } else { &feedback }));
+                                    task.session_mode = crate::model::SessionMode::SdkManaged;
+                                    commands.push(Message::DoSendFeedback { task_id: task_uuid, feedback });
+                                }
+                                break;
+                            }
                         }
                     }
                 }
-            }
 
-            Message::RestartConfirmationAnimation => {
-                // Restart the highlight sweep animation when user presses an unrecognized key
-                // This signals that they need to respond to the prompt first
-                if let Some(ref mut confirmation) = self.model.ui_state.pending_confirmation {
-                    confirmation.animation_tick = 20;
+                // Only process signals that match a specific task (by UUID or worktree path)
+                // Signals from the main project's Claude are silently ignored - use worktree isolation
+                tracing::debug!(
+                    "Hook {} ({}) for session {} in {} -> {}",
+                    signal.event, signal.source, signal.session_id, signal.project_dir.display(),
+                    found_task.map_or("no task".to_string(), |id| id.to_string())
+                );
+                if found_task.is_none() {
+                    // Check if this signal is from the main project directory (not a worktree)
+                    // This is expected when developing on the project itself with Claude
+                    let is_main_project = self.model.projects.iter().any(|p| {
+                        let main_dir = p.working_dir.canonicalize().unwrap_or(p.working_dir.clone());
+                        main_dir == signal_dir
+                    });
+
+                    if !is_main_project {
+                        // Only warn for unexpected signals (not from main project or known worktrees)
+                        commands.push(Message::SetStatusMessage(Some(
+                            format!("Hook '{}' received but no matching task for: {} (session: {})",
+                                signal.event,
+                                signal.project_dir.display(),
+                                signal.session_id)
+                        )));
+                    }
+                    // Silently ignore signals from main project - they're from the dev Claude session
                 }
+
+                // The worktree may have changed; keep the interactive modal's diff current
+                let modal_task = self.model.ui_state.interactive_modal.as_ref().map(|m| m.task_id);
+                if found_task.is_some() && found_task == modal_task {
+                    commands.push(Message::RefreshInteractiveDiff);
+                }
+
+                // Sync selection after task status changes
+                self.sync_selection();
             }
 
-            Message::ScrollConfirmationUp => {
-                // Scroll up in multiline confirmation modal
-                if self.model.ui_state.pending_confirmation.is_some() {
-                    self.model.ui_state.confirmation_scroll_offset =
-                        self.model.ui_state.confirmation_scroll_offset.saturating_sub(1);
+            Message::ClaudeOutputUpdated { project_id, output } => {
+                // Store captured output for display
+                if let Some(project) = self.model.projects.iter_mut().find(|p| p.id == project_id) {
+                    project.captured_output = output;
                 }
             }
 
-            Message::ScrollConfirmationDown => {
-                // Scroll down in multiline confirmation modal
-                if let Some(ref confirmation) = self.model.ui_state.pending_confirmation {
-                    let line_count = confirmation.message.lines().count();
-                    // Allow scrolling up to line_count - 1 (so at least one line is visible)
-                    let max_offset = line_count.saturating_sub(1);
-                    if self.model.ui_state.confirmation_scroll_offset < max_offset {
-                        self.model.ui_state.confirmation_scroll_offset += 1;
+            // === Async Background Task Results ===
+
+            Message::WorktreeCreated { task_id, display_id, worktree_path, project_dir } => {
+                // Update task with worktree info immediately for UI feedback
+                if let Some(project) = self.model.active_project_mut() {
+                    if let Some(task) = project.tasks.iter_mut().find(|t| t.id == task_id) {
+                        task.worktree_path = Some(worktree_path.clone());
+                        task.git_branch = Some(format!("claude/{}", display_id));
+                        task.session_state = crate::model::ClaudeSessionState::Starting;
                     }
                 }
-            }
 
-            Message::SetStatusMessage(msg) => {
-                self.model.ui_state.status_message = msg.clone();
-                // Set decay timer: ~5 seconds (50 ticks at 100ms each)
-                // Longer messages get more time to read
-                self.model.ui_state.status_message_decay = if msg.is_some() {
-                    50 + (msg.as_ref().map(|m| m.len() as u16 / 3).unwrap_or(0))
+                // Replace the pooled worktree this task may have taken
+                let pool_size = self.worktree_pool_size(&project_dir);
+                if pool_size > 0 {
+                    commands.push(Message::WarmWorktreePool { project_dir: project_dir.clone(), size: pool_size });
+                }
+
+                // Auto-started bug tasks run with the restricted agent profile
+                let task = self.model.active_project()
+                    .and_then(|p| p.tasks.iter().find(|t| t.id == task_id));
+                let restricted = task.is_some_and(|t| t.restricted_agent);
+                let model = task.and_then(|t| t.model.clone());
+                // The project's pre-start script readies the worktree before Claude starts
+                let pre_start = self.model.active_project()
+                    .and_then(|p| Some((p.commands.pre_start.clone()?, p.scoped(&worktree_path))));
+                // Shared caches go in first, so the pre-start script finds them
+                let shared_caches = self.model.active_project()
+                    .filter(|_| !task.is_some_and(|t| t.fresh_worktree))
+                    .and_then(|p| Some((p.shared_caches.clone()?, p.scope.clone())));
+
+                // Spawn settings setup in background, then start SDK session
+                if let Some(sender) = self.async_sender.clone() {
+                    if let Some((command, _)) = &pre_start {
+                        commands.push(Message::SetStatusMessage(Some(format!("Running pre-start script `{}`...", command))));
+                    }
+                    let wt_path = worktree_path.clone();
+                    let proj_dir = project_dir.clone();
+                    tokio::spawn(async move {
+                        // Run settings setup in background thread
+                        let setup_result = tokio::task::spawn_blocking(move || {
+                            let caches = shared_caches.map(|(setting, scope)| {
+                                crate::worktree::caches::share_caches(&proj_dir, &wt_path, scope.as_deref(), &setting)
+                            }).unwrap_or_default();
+
+                            // Set up Claude settings (non-fatal if fails)
+                            let settings_err = crate::worktree::merge_with_project_settings(
+                                &wt_path,
+                                &proj_dir,
+                                task_id,
+                                restricted,
+                                model.as_deref(),
+                            ).err();
+
+                            // Pre-trust the worktree (non-fatal if fails)
+                            let trust_err = crate::worktree::pre_trust_worktree(&wt_path).err();
+
+                            (caches, settings_err, trust_err)
+                        }).await;
+
+                        // Report warnings but continue to start SDK session
+                        if let Ok((caches, settings_err, trust_err)) = setup_result {
+                            if !caches.is_empty() {
+                                let _ = sender.send(Message::SetStatusMessage(Some(
+                                    format!("Shared caches: {}", caches.join(", "))
+                                )));
+                            }
+                            if let Some(e) = settings_err {
+                                let _ = sender.send(Message::SetStatusMessage(Some(
+                                    format!("Warning: Could not set up Claude settings: {}", e)
+                                )));
+                            }
+                            if let Some(e) = trust_err {
+                                let _ = sender.send(Message::SetStatusMessage(Some(
+                                    format!("Warning: Could not pre-trust worktree: {}", e)
+                                )));
+                            }
+                        }
+
+                        // A failed pre-start script keeps Claude from starting
+                        if let Some((command, dir)) = pre_start {
+                            let result = tokio::task::spawn_blocking({
+                                let command = command.clone();
+                                move || crate::rules::run_command(&dir, &command)
+                            }).await.unwrap_or_else(|e| Err(e.to_string()));
+                            let passed = result.is_ok();
+                            let stage = crate::model::ScriptStage::PreStart;
+                            let _ = sender.send(Message::LifecycleScriptFinished { task_id, stage, command, result });
+                            if !passed {
+                                return;
+                            }
+                        }
+
+                        // Start SDK session
+                        let _ = sender.send(Message::StartSdkSession { task_id });
+                    });
                 } else {
-                    0
-                };
-            }
+                    // Fallback to sync if no async sender
+                    if let Some((setting, scope)) = shared_caches {
+                        let caches = crate::worktree::caches::share_caches(&project_dir, &worktree_path, scope.as_deref(), &setting);
+                        if !caches.is_empty() {
+                            commands.push(Message::SetStatusMessage(Some(
+                                format!("Shared caches: {}", caches.join(", "))
+                            )));
+                        }
+                    }
+                    if let Err(e) = crate::worktree::merge_with_project_settings(
+                        &worktree_path,
+                        &project_dir,
+                        task_id,
+                        restricted,
+                        model.as_deref(),
+                    ) {
+                        commands.push(Message::SetStatusMessage(Some(
+                            format!("Warning: Could not set up Claude settings: {}", e)
+                        )));
+                    }
 
-            Message::TriggerLogoShimmer => {
-                // Start the shimmer animation (frame 1 = bottom row lit)
-                self.model.ui_state.logo_shimmer_frame = 1;
-                // Use animated star eyes for commit/merge celebrations
-                // Longer duration (10 ticks = ~1 second) to show the sparkle animation
-                self.model.ui_state.eye_animation = EyeAnimation::StarEyes;
-                self.model.ui_state.eye_animation_ticks_remaining = 10;
-            }
+                    if let Err(e) = crate::worktree::pre_trust_worktree(&worktree_path) {
+                        commands.push(Message::SetStatusMessage(Some(
+                            format!("Warning: Could not pre-trust worktree: {}", e)
+                        )));
+                    }
 
-            Message::TriggerMergeCelebration { task_id, display_text, column_status, task_index, pending_completion } => {
-                // Start the "gold dust sweep" celebration animation
-                self.model.ui_state.merge_celebration = Some(crate::model::MergeCelebrationState {
-                    task_id,
-                    original_text: display_text,
-                    frame: 0,
-                    column_status,
-                    task_index,
-                    pending_completion,
-                });
+                    commands.push(Message::StartSdkSession { task_id });
+                }
             }
 
-            Message::FinishMergeCelebration(task_id) => {
-                // Animation complete - now actually complete the task
+            Message::WorktreeCreationFailed { task_id, error } => {
+                // Reset task state on failure
                 if let Some(project) = self.model.active_project_mut() {
-                    project.complete_task(task_id);
-                    project.needs_attention = project.review_count() > 0;
-                    if !project.needs_attention {
-                        notify::clear_attention_indicator();
+                    if let Some(task) = project.tasks.iter_mut().find(|t| t.id == task_id) {
+                        task.session_state = crate::model::ClaudeSessionState::NotStarted;
+                        task.status = TaskStatus::Planned;
+                        task.started_at = None;
+                        task.record_start_failure(crate::model::StartPhase::Worktree, error.clone(), None);
                     }
                 }
+                commands.push(Message::Error(format!("Failed to create worktree: {} (C for details)", error)));
             }
 
-            Message::TriggerMascotBlink => {
-                // Trigger a random eye animation when clicking the mascot
-                self.model.ui_state.eye_animation = EyeAnimation::random();
-                self.model.ui_state.eye_animation_ticks_remaining = 2;
-                // Also trigger watcher observation if enabled
-                if let Some(project) = self.model.active_project() {
-                    if project.watcher_enabled {
-                        commands.push(Message::TriggerWatcher);
+            Message::SdkSessionFailed { task_id, error, phase, output } => {
+                // Keep the worktree so a retry picks up where this left off; x in the
+                // failure modal discards it, and deleting the task cleans it up
+                if let Some(project) = self.model.active_project_mut() {
+                    if let Some(task) = project.tasks.iter_mut().find(|t| t.id == task_id) {
+                        task.session_state = crate::model::ClaudeSessionState::NotStarted;
+                        task.status = TaskStatus::Planned;
+                        task.started_at = None;
+                        task.record_start_failure(phase, error.clone(), output);
                     }
                 }
+                commands.push(Message::Error(format!(
+                    "{} failed: {} (C for details, s retries, x starts fresh)", phase.label(), error
+                )));
             }
 
-            Message::ShowStartupHints => {
-                // Show the startup hints bar again (triggered by pressing ESC multiple times)
-                // Reset to 100 ticks (10 seconds) to match initial display
-                self.model.ui_state.startup_hint_until_tick = Some(100);
-                // Reset the ESC counter so they need to press ESC twice again
-                self.model.ui_state.consecutive_esc_count = 0;
-            }
-
-            Message::WelcomeBubbleFocus => {
-                self.model.ui_state.welcome_bubble_focused = true;
-            }
-
-            Message::WelcomeBubbleUnfocus => {
-                self.model.ui_state.welcome_bubble_focused = false;
-            }
+            // === Sidecar/SDK Events ===
 
-            Message::WelcomeMessagePrev => {
-                let count = crate::ui::welcome_message_count();
-                self.model.ui_state.welcome_message_idx =
-                    (self.model.ui_state.welcome_message_idx + count - 1) % count;
-                // Reset cooldown so it doesn't immediately rotate
-                self.model.ui_state.welcome_message_cooldown = 80;
-            }
+            Message::StartSdkSession { task_id } => {
+                // Get task info for SDK call
+                let task_info = self.model.active_project().and_then(|project| {
+                    project.tasks.iter().find(|t| t.id == task_id).map(|task| {
+                        (
+                            task.session_prompt() + &project.scope_prompt(),
+                            task.images.clone(),
+                            task.worktree_path.clone(),
+                        )
+                    })
+                });
 
-            Message::WelcomeMessageNext => {
-                let count = crate::ui::welcome_message_count();
-                self.model.ui_state.welcome_message_idx =
-                    (self.model.ui_state.welcome_message_idx + 1) % count;
-                // Reset cooldown so it doesn't immediately rotate
-                self.model.ui_state.welcome_message_cooldown = 80;
-            }
+                if let Some((prompt, images, Some(worktree_path))) = task_info {
+                    // Check if sidecar is available before spawning background task
+                    if self.sidecar_client.is_none() {
+                        // No sidecar available - cannot start task
+                        commands.push(Message::SdkSessionFailed {
+                            task_id,
+                            error: "Sidecar not connected. Ensure sidecar is running.".to_string(),
+                            phase: crate::model::StartPhase::Session,
+                            output: None,
+                        });
+                    } else if let Some(sender) = self.async_sender.clone() {
+                        // Spawn SDK session start in background to keep UI responsive
+                        let images_str: Option<Vec<String>> = if !images.is_empty() {
+                            Some(images.iter().map(|p| p.to_string_lossy().to_string()).collect())
+                        } else {
+                            None
+                        };
 
-            Message::HookSignalReceived(signal) => {
-                // Try to find task by task_id first (worktree-based tasks use task UUID as session_id)
-                let task_uuid = uuid::Uuid::parse_str(&signal.session_id).ok();
+                        let worktree_path_for_call = worktree_path.clone();
 
-                // Find the task either by UUID or by worktree path
-                let signal_dir = signal.project_dir.canonicalize().unwrap_or(signal.project_dir.clone());
+                        tokio::spawn(async move {
+                            // Run blocking sidecar call in a separate thread
+                            let result = tokio::task::spawn_blocking(move || {
+                                crate::sidecar::SidecarClient::start_session_standalone(
+                                    task_id,
+                                    worktree_path_for_call,
+                                    prompt,
+                                    images_str,
+                                )
+                            }).await;
 
-                // Capture replay flag before mutable borrow of projects
-                // During signal replay on startup, we suppress audio notifications
-                let replaying_signals = self.model.ui_state.replaying_signals;
+                            let msg = match result {
+                                Ok(Ok(session_id)) => {
+                                    Message::SdkSessionStarted { task_id, session_id }
+                                }
+                                Ok(Err(e)) => {
+                                    Message::SdkSessionFailed { task_id, error: e.to_string(), phase: crate::model::StartPhase::Session, output: None }
+                                }
+                                Err(e) => {
+                                    Message::SdkSessionFailed { task_id, error: format!("Task panicked: {}", e), phase: crate::model::StartPhase::Session, output: None }
+                                }
+                            };
 
-                let mut found_task = false;
+                            let _ = sender.send(msg);
+                        });
+                    } else {
+                        // Fallback to sync if no async sender (shouldn't happen in normal operation)
+                        if let Some(ref client) = self.sidecar_client {
+                            let images_str: Option<Vec<String>> = if !images.is_empty() {
+                                Some(images.iter().map(|p| p.to_string_lossy().to_string()).collect())
+                            } else {
+                                None
+                            };
 
-                for project in &mut self.model.projects {
-                    // Find task by UUID or by worktree path
-                    let task_idx = project.tasks.iter().position(|t| {
-                        // Match by UUID (for worktree-based tasks)
-                        if let Some(uuid) = task_uuid {
-                            if t.id == uuid {
-                                return true;
-                            }
-                        }
-                        // Match by worktree path
-                        if let Some(ref wt_path) = t.worktree_path {
-                            let wt_canonical = wt_path.canonicalize().unwrap_or(wt_path.clone());
-                            if wt_canonical == signal_dir {
-                                return true;
+                            match client.start_session(task_id, &worktree_path, &prompt, images_str) {
+                                Ok(session_id) => {
+                                    commands.push(Message::SdkSessionStarted { task_id, session_id });
+                                }
+                                Err(e) => {
+                                    commands.push(Message::SdkSessionFailed { task_id, error: e.to_string(), phase: crate::model::StartPhase::Session, output: None });
+                                }
                             }
                         }
-                        false
-                    });
+                    }
+                }
+            }
 
-                    if let Some(idx) = task_idx {
-                        let task_id = project.tasks[idx].id;
-                        // Check if there's a queued task before getting mutable ref
-                        let has_queued = project.next_queued_for(task_id).is_some();
-                        let was_accepting = project.tasks[idx].status == TaskStatus::Accepting;
-                        let was_updating = project.tasks[idx].status == TaskStatus::Updating;
-                        let was_applying = project.tasks[idx].status == TaskStatus::Applying;
-                        // Terminal states - tasks that are Done should not be moved back
-                        let is_terminal = project.tasks[idx].status == TaskStatus::Done;
-                        let was_waiting_for_cli = project.tasks[idx].session_mode == crate::model::SessionMode::WaitingForCliExit;
-                        let project_name = project.name.clone();
-                        let project_slug = project.slug();
+            Message::SidecarEvent(event) => {
+                // Handle events from the SDK sidecar
+                use crate::sidecar::SessionEventType;
 
-                        let was_cli_actively_working = project.tasks[idx].session_mode == crate::model::SessionMode::CliActivelyWorking;
+                // Already handled, live or from a journal replay
+                if event.seq.is_some_and(|seq| seq <= self.model.last_sidecar_seq) {
+                    return commands;
+                }
 
-                        let task = &mut project.tasks[idx];
-                        found_task = true;
+                let task_id = event.task_id;
 
-                        // Track CLI activity state for SDK/CLI handoff coordination
-                        // When CLI is in CliInteractive or CliActivelyWorking mode, update state based on hooks
-                        if matches!(task.session_mode, crate::model::SessionMode::CliInteractive | crate::model::SessionMode::CliActivelyWorking) {
-                            match signal.event.as_str() {
-                                "working" | "input-provided" => {
-                                    // CLI is actively working (user submitted input or tool is running)
-                                    task.session_mode = crate::model::SessionMode::CliActivelyWorking;
+                // The task may be in a project that hasn't been opened yet this run
+                for idx in 0..self.model.projects.len() {
+                    if self.model.projects.iter().any(|p| p.tasks.iter().any(|t| t.id == task_id)) {
+                        break;
+                    }
+                    if !self.model.projects[idx].loaded {
+                        let _ = self.load_project(idx);
+                    }
+                }
+                // Only mark it handled once a task took it, so a replay can still deliver it
+                if self.model.projects.iter().any(|p| p.tasks.iter().any(|t| t.id == task_id)) {
+                    if let Some(seq) = event.seq {
+                        self.model.last_sidecar_seq = seq;
+                    }
+                }
+
+                // Track if this was an Accepting/Updating/Applying task that stopped/ended (for completion)
+                let mut was_accepting = false;
+                let mut was_updating = false;
+                let mut was_applying = false;
+
+                for project in &mut self.model.projects {
+                    if let Some(task) = project.tasks.iter_mut().find(|t| t.id == task_id) {
+                        // Check if task was in Accepting/Updating/Applying status (rebase in progress)
+                        was_accepting = task.status == TaskStatus::Accepting;
+                        was_updating = task.status == TaskStatus::Updating;
+                        was_applying = task.status == TaskStatus::Applying;
+
+                        match event.event_type {
+                            SessionEventType::Started => {
+                                // Update session_id from Started event (safe - it's a new session)
+                                if let Some(ref session_id) = event.session_id {
+                                    task.claude_session_id = Some(session_id.clone());
                                 }
-                                "stop" | "end" | "needs-input" => {
-                                    // CLI finished its turn, back to waiting for input
-                                    task.session_mode = crate::model::SessionMode::CliInteractive;
+                                // Don't override special statuses (rebase sessions, QA, or Review)
+                                // Review is protected because QA completion moves to Review, and
+                                // a late Started event from the QA session shouldn't undo that
+                                if task.status != TaskStatus::Accepting
+                                    && task.status != TaskStatus::Updating
+                                    && task.status != TaskStatus::Applying
+                                    && task.status != TaskStatus::Testing
+                                    && task.status != TaskStatus::Review
+                                {
+                                    task.status = TaskStatus::InProgress; // Session started, Claude is now working
+                                    task.session_state = crate::model::ClaudeSessionState::Working;
+                                    task.session_mode = crate::model::SessionMode::SdkManaged;
                                 }
-                                _ => {}
+                                task.log_activity("Session started");
                             }
-                        }
+                            SessionEventType::Stopped => {
+                                task.log_activity_with_output("Session stopped", event.full_output.clone());
 
-                        // Check if we're waiting for CLI to exit (SDK handoff case)
-                        // Only trigger SDK resume if CLI is NOT actively working
-                        // Skip during signal replay - we don't want to auto-resume sessions on startup
-                        if !replaying_signals && was_waiting_for_cli && matches!(signal.event.as_str(), "stop" | "end") {
-                            // CLI exited - resume SDK session
-                            // Note: Don't overwrite claude_session_id here - the signal uses task_id,
-                            // but we want to keep the real SDK session_id that was set when session started
-                            task.session_mode = crate::model::SessionMode::SdkManaged;
-                            commands.push(Message::CliSessionEnded { task_id });
-                            break;
-                        }
+                                // Record token usage from this session
+                                if let Some(ref usage) = event.usage {
+                                    task.add_token_usage(
+                                        usage.input_tokens,
+                                        usage.output_tokens,
+                                        usage.cache_read_tokens,
+                                        usage.cache_creation_tokens,
+                                        event.cost_usd.unwrap_or(0.0),
+                                    );
+                                }
 
-                        // If we were actively working and got a stop/end, transition to WaitingForCliExit
-                        // so the SDK can pick up (unless user closes the modal)
-                        if was_cli_actively_working && matches!(signal.event.as_str(), "stop" | "end") {
-                            task.session_mode = crate::model::SessionMode::CliInteractive;
-                        }
+                                // Skip if terminal state or special operations in progress
+                                if was_accepting || was_updating || was_applying || task.status == TaskStatus::Done
+                                    || task.restarting_session
+                                {
+                                    // Let CompleteAcceptTask/etc handlers take care of it
+                                } else if task.in_qa_session && task.status == TaskStatus::Testing {
+                                    // QA session ending - check for result markers in output
+                                    let output = event.output.as_deref().unwrap_or("");
+                                    let task_id = task.id;
 
-                        match signal.event.as_str() {
-                            "stop" => {
-                                // Skip terminal tasks - these are stale signals from before task was completed
-                                if is_terminal {
-                                    // Don't modify Done/Discarded tasks
-                                } else if was_accepting {
-                                    // Task was rebasing for accept - try to complete the accept
-                                    // Keep status as Accepting, CompleteAcceptTask will verify and update
-                                    commands.push(Message::CompleteAcceptTask(task_id));
-                                } else if was_updating {
-                                    // Task was rebasing for update - complete update (no merge!)
-                                    commands.push(Message::CompleteUpdateTask(task_id));
-                                } else if was_applying {
-                                    // Task was rebasing for apply - complete the apply
-                                    commands.push(Message::CompleteApplyTask(task_id));
-                                } else if has_queued {
-                                    // Don't move to review - send the queued task instead
-                                    // Move to end of Review tasks so first-finished appears at top
-                                    // (only if not already in Review from a duplicate hook)
-                                    task.session_state = crate::model::ClaudeSessionState::Paused;
-                                    if task.status != TaskStatus::Review {
-                                        project.move_task_to_start_of_status(task_id, TaskStatus::Review);
-                                    }
-                                    // Don't play attention sound - we're continuing automatically
-                                    commands.push(Message::SendQueuedTask { finished_task_id: task_id });
-                                } else if signal.source == "sdk" {
-                                    // SDK-sourced signal - ignore, SDK Stopped event handles QA/Review
-                                    // (SDK events include session output for QA marker detection)
-                                } else {
-                                    // CLI-sourced signal - move to review and notify (no QA for CLI)
-                                    task.session_state = crate::model::ClaudeSessionState::Paused;
-                                    if task.status != TaskStatus::Review {
-                                        project.move_task_to_start_of_status(task_id, TaskStatus::Review);
-                                        project.needs_attention = true;
-                                        if !replaying_signals {
-                                            notify::play_attention_sound();
-                                        }
-                                        notify::set_attention_indicator(&project_name);
+                                    if output.contains("[QA:PASS]") {
+                                        commands.push(Message::QaValidationPassed(task_id));
+                                    } else if output.contains("[QA:FAIL]") {
+                                        commands.push(Message::QaValidationNeedsWork {
+                                            task_id,
+                                            feedback: output.to_string()
+                                        });
                                     }
-                                }
-                            }
-                            "end" => {
-                                // Skip terminal tasks - these are stale signals from before task was completed
-                                if is_terminal {
-                                    // Don't modify Done/Discarded tasks
-                                } else {
-                                    if was_accepting {
-                                        task.log_activity("Accept cancelled");
-                                        task.move_to_review();
-                                        task.session_state = crate::model::ClaudeSessionState::Ended;
-                                        commands.push(Message::SetStatusMessage(Some(
-                                            "Accept cancelled: Claude session ended during rebase.".to_string()
-                                        )));
-                                    } else if was_updating {
-                                        task.log_activity("Update cancelled");
-                                        task.move_to_review();
-                                        task.session_state = crate::model::ClaudeSessionState::Ended;
-                                        commands.push(Message::SetStatusMessage(Some(
-                                            "Update cancelled: Claude session ended during rebase.".to_string()
-                                        )));
-                                    } else if was_applying {
-                                        task.log_activity("Apply cancelled");
-                                        task.move_to_review();
-                                        task.session_state = crate::model::ClaudeSessionState::Ended;
-                                        commands.push(Message::SetStatusMessage(Some(
-                                            "Apply cancelled: Claude session ended during rebase.".to_string()
-                                        )));
-                                        project.needs_attention = true;
-                                        if !replaying_signals {
-                                            notify::play_attention_sound();
-                                        }
-                                        notify::set_attention_indicator(&project.name);
-                                    } else if signal.source == "sdk" {
-                                        // SDK-sourced signal - ignore, SDK Ended event handles it
-                                    } else if task.status != TaskStatus::Review {
-                                        // CLI-sourced signal - move to review and notify (no QA for CLI)
-                                        task.session_state = crate::model::ClaudeSessionState::Ended;
+                                    // No markers = stale event from before QA started, ignore
+                                } else if task.status == TaskStatus::InProgress {
+                                    // Work finished - start QA or move to Review
+                                    let should_qa = project.qa_enabled && !task.skip_qa;
+                                    let task_id = task.id;
+
+                                    if should_qa {
+                                        task.status = TaskStatus::Testing;
+                                        commands.push(Message::StartQaValidation(task_id));
+                                    } else {
+                                        task.session_state = crate::model::ClaudeSessionState::Paused;
                                         project.move_task_to_start_of_status(task_id, TaskStatus::Review);
                                         project.needs_attention = true;
-                                        if !replaying_signals {
-                                            notify::play_attention_sound();
-                                        }
-                                        notify::set_attention_indicator(&project.name);
+                                        notify::play_task_cue(task_id, notify::SoundCue::Review);
+                                        notify::set_task_attention_indicator(task_id, &project.name);
                                     }
                                 }
+                                // Other statuses (Testing w/o in_qa_session, NeedsWork, Review): do nothing
                             }
-                            "needs-input" => {
-                                // Don't change status if task is in a special state
-                                if is_terminal {
-                                    // Skip - task already completed, this is a replayed signal
-                                } else if was_accepting || was_updating || was_applying {
-                                    // Skip - we're in the middle of a rebase operation
-                                } else if signal.input_type == "permission" {
-                                    // permission_prompt means Claude is blocked waiting for tool approval.
-                                    // Always move to NeedsWork, even from Review - this is unambiguous.
-                                    task.log_activity("Waiting for permission...");
-                                    task.status = TaskStatus::NeedsWork;
-                                    task.session_state = crate::model::ClaudeSessionState::Paused;
-                                    project.needs_attention = true;
-                                    if !replaying_signals {
-                                        notify::play_attention_sound();
-                                    }
-                                    notify::set_attention_indicator(&project.name);
-                                } else if signal.input_type == "idle" && task.status == TaskStatus::Review {
-                                    // idle_prompt fires after 60+ seconds of Claude being idle.
-                                    // Task is already in Review (from Stop hook). Check if Claude
-                                    // actually asked a question by examining tmux pane content.
-                                    if let Some(ref window_name) = task.tmux_window {
-                                        if crate::tmux::claude_output_contains_question(&project_slug, window_name) {
-                                            task.log_activity("Waiting for answer...");
-                                            task.status = TaskStatus::NeedsWork;
-                                            task.session_state = crate::model::ClaudeSessionState::Paused;
-                                            project.needs_attention = true;
-                                            if !replaying_signals {
-                                                notify::play_attention_sound();
-                                            }
-                                            notify::set_attention_indicator(&project.name);
-                                        }
-                                        // Otherwise, Claude is just idle after finishing - stay in Review
-                                    }
-                                } else if task.status != TaskStatus::Review {
-                                    // idle_prompt or unknown type - only move to NeedsWork if NOT
-                                    // already in Review. The idle_prompt fires both when Claude asks
-                                    // a question AND when Claude is done but sitting at an idle prompt.
-                                    // We can't distinguish these cases, so trust the Review state.
-                                    task.log_activity("Waiting for input...");
-                                    task.status = TaskStatus::NeedsWork;
-                                    task.session_state = crate::model::ClaudeSessionState::Paused;
+                            SessionEventType::Ended => {
+                                task.log_activity_with_output("Session ended", event.full_output.clone());
+                                // Ended is a fallback - Stopped handler is primary for QA logic
+                                // Only act if task is still InProgress (Stopped may have already handled it)
+                                if was_accepting || was_updating || was_applying
+                                    || task.status == TaskStatus::Done
+                                    || task.status == TaskStatus::Review
+                                    || task.status == TaskStatus::Testing
+                                    || task.restarting_session
+                                {
+                                    // Already handled or in QA - skip
+                                } else if task.status == TaskStatus::InProgress {
+                                    // Ended without Stopped handling it - move to Review
+                                    // (QA start is only triggered by Stopped which has the output)
+                                    task.session_state = crate::model::ClaudeSessionState::Ended;
+                                    let task_id = task.id;
+                                    project.move_task_to_start_of_status(task_id, TaskStatus::Review);
                                     project.needs_attention = true;
-                                    if !replaying_signals {
-                                        notify::play_attention_sound();
-                                    }
-                                    notify::set_attention_indicator(&project.name);
+                                    notify::play_task_cue(task_id, notify::SoundCue::Review);
+                                    notify::set_task_attention_indicator(task_id, &project.name);
                                 }
                             }
-                            "input-provided" => {
-                                task.log_activity("Input received, continuing...");
-                                // Don't change status if task is in a special state (including QA/Testing)
-                                // For Review: only protect SDK-sourced signals (QA completion) - CLI signals
-                                // mean user is actively continuing work and should move back to InProgress
-                                let is_protected_review = task.status == TaskStatus::Review && signal.source == "sdk";
-                                if !was_accepting && !was_updating && !was_applying && !is_terminal
+                            SessionEventType::NeedsInput => {
+                                task.log_activity_with_output("Waiting for input...", event.full_output.clone());
+                                // Don't change status if task is Accepting/Updating/Applying/Testing (mid-rebase or QA)
+                                if !was_accepting && !was_updating && !was_applying
                                     && task.status != TaskStatus::Testing
-                                    && !is_protected_review
                                 {
-                                    // Move to end of InProgress column so newly active tasks appear at bottom
-                                    project.move_task_to_start_of_status(task_id, TaskStatus::InProgress);
+                                    task.status = TaskStatus::NeedsWork;
+                                    task.session_state = crate::model::ClaudeSessionState::Paused;
+                                    project.needs_attention = true;
+                                    notify::play_task_cue(task_id, notify::SoundCue::NeedsInput);
+                                    notify::set_task_attention_indicator(task_id, &project.name);
                                 }
-                                // Only set Working state if not in a protected/completed state
-                                // Late SDK signals shouldn't override Paused state for Review tasks
-                                if !is_terminal && !is_protected_review {
-                                    if let Some(task) = project.tasks.iter_mut().find(|t| t.id == task_id) {
-                                        task.session_state = crate::model::ClaudeSessionState::Working;
-                                    }
+                            }
+                            SessionEventType::Working => {
+                                task.log_activity_with_output("Working...", event.full_output.clone());
+                                // Don't override special statuses (rebase, QA, or completed Review)
+                                // Review is protected because QA completion moves to Review, and
+                                // late Working events from the QA session shouldn't undo that
+                                if task.status != TaskStatus::Accepting && task.status != TaskStatus::Updating && task.status != TaskStatus::Applying && task.status != TaskStatus::Testing && task.status != TaskStatus::Review {
+                                    task.status = TaskStatus::InProgress;
+                                    task.session_state = crate::model::ClaudeSessionState::Working;
                                     project.needs_attention = false;
                                     notify::clear_attention_indicator();
+                                    task.last_activity_at = Some(chrono::Utc::now());
                                 }
                             }
-                            "working" => {
-                                // PreToolUse signal - Claude is using a tool
-                                // Don't process for terminal tasks (Done/Discarded) - these are stale signals
-                                if is_terminal {
-                                    // Skip - task already completed, this is a replayed signal
+                            SessionEventType::ToolUse => {
+                                // Log the tool being used
+                                let tool_msg = if let Some(ref tool_name) = event.tool_name {
+                                    format!("Using {}", tool_name)
                                 } else {
-                                    task.log_activity("Working...");
-                                    // Don't override special statuses (rebase, QA, or Review from SDK)
-                                    // For Review: only protect SDK-sourced signals (QA completion) - CLI signals
-                                    // mean user is actively continuing work and should move back to InProgress
-                                    let is_protected_review = task.status == TaskStatus::Review && signal.source == "sdk";
-
-                                    // Grace period for CLI startup: ignore CLI working signals within 3 seconds
-                                    // of opening the terminal. Claude CLI may fire PreToolUse during session
-                                    // restore (e.g., reading context files) which isn't actual user work.
-                                    let is_cli_startup_grace_period = signal.source == "cli"
-                                        && task.status == TaskStatus::Review
-                                        && task.cli_opened_at.map_or(false, |opened_at| {
-                                            let grace_period = chrono::Duration::seconds(3);
-                                            chrono::Utc::now() - opened_at < grace_period
-                                        });
-
-                                    if !was_accepting && !was_updating && !was_applying
-                                        && task.status != TaskStatus::Testing
-                                        && !is_protected_review
-                                        && !is_cli_startup_grace_period
-                                    {
-                                        // Move to end of InProgress column so newly active tasks appear at bottom
-                                        project.move_task_to_start_of_status(task_id, TaskStatus::InProgress);
+                                    "Using tool...".to_string()
+                                };
+                                task.log_event(crate::model::ActivityKind::ToolCall, &tool_msg, event.full_output.clone());
+                                // Don't override special statuses (rebase, QA, or completed Review)
+                                // Review is protected because QA completion moves to Review, and
+                                // late ToolUse events from the QA session shouldn't undo that
+                                if task.status != TaskStatus::Accepting && task.status != TaskStatus::Updating && task.status != TaskStatus::Applying && task.status != TaskStatus::Testing && task.status != TaskStatus::Review {
+                                    task.status = TaskStatus::InProgress;
+                                    task.session_state = crate::model::ClaudeSessionState::Working;
+                                    project.needs_attention = false;
+                                    notify::clear_attention_indicator();
+                                    // Track activity for merge feedback
+                                    task.last_activity_at = Some(chrono::Utc::now());
+                                    if let Some(ref tool_name) = event.tool_name {
+                                        task.last_tool_name = Some(tool_name.clone());
                                     }
-                                    // Re-find task since move_task_to_start_of_status may have repositioned it
-                                    // Only set Working state if not in a protected Review state from SDK
-                                    // Late SDK signals shouldn't override Paused state for Review tasks
-                                    if !is_protected_review && !is_cli_startup_grace_period {
-                                        if let Some(task) = project.tasks.iter_mut().find(|t| t.id == task_id) {
-                                            task.session_state = crate::model::ClaudeSessionState::Working;
-                                        }
-                                        project.needs_attention = false;
-                                        notify::clear_attention_indicator();
+                                }
+                            }
+                            SessionEventType::Context => {
+                                if let Some(tokens) = event.context_tokens {
+                                    task.context_tokens = tokens;
+                                }
+                                // Offer (or start) a fresh session once per session near the limit
+                                if task.context_usage() >= crate::model::CONTEXT_RESTART_AT
+                                    && !task.context_restart_offered
+                                    && task.status == TaskStatus::InProgress
+                                {
+                                    use crate::model::ContextRestart;
+                                    match project.context_restart {
+                                        ContextRestart::Ask => commands.push(Message::OfferSessionRestart(task.id)),
+                                        ContextRestart::Auto => commands.push(Message::RestartSessionWithSummary(task.id)),
+                                        ContextRestart::Off => {}
+                                    }
+                                    task.context_restart_offered = true;
+                                }
+                            }
+                            SessionEventType::Compacted => {
+                                task.context_compactions += 1;
+                                let before = event.context_tokens
+                                    .map(|t| format!(" (was {}k tokens)", t / 1000))
+                                    .unwrap_or_default();
+                                task.log_activity(format!("Context compacted{}", before));
+                            }
+                            SessionEventType::Output => {
+                                // Store output for display (could be used by output panel)
+                                if let Some(ref output) = event.output {
+                                    project.captured_output.push_str(output);
+                                    task.push_live_output(output);
+                                    // Log first line of output if it's meaningful
+                                    let first_line = output.lines().next().unwrap_or("").trim();
+                                    if !first_line.is_empty() && first_line.len() <= 60 {
+                                        task.log_event(crate::model::ActivityKind::Output, first_line, event.full_output.clone());
                                     }
                                 }
                             }
-                            _ => {}
                         }
-
                         break;
                     }
                 }
+                self.sync_selection();
 
-                // Handle pending feedback after the main loop (avoid borrow conflicts)
-                // We need to re-find the task since the previous borrow ended
-                if matches!(signal.event.as_str(), "stop" | "needs-input") {
-                    if let Some(task_uuid) = task_uuid {
-                        for project in &mut self.model.projects {
-                            if let Some(task) = project.tasks.iter_mut().find(|t| t.id == task_uuid) {
-                                if let Some(feedback) = task.pending_feedback.take() {
-                                    // Claude finished - send the queued feedback
-                                    task.log_activity(&format!("Sending queued feedback: {}...",
-                                        if feedback.len() > 20 { &feedback[..20] } else { &feedback }));
-                                    task.session_mode = crate::model::SessionMode::SdkManaged;
-                                    commands.push(Message::DoSendFeedback { task_id: task_uuid, feedback });
-                                }
-                                break;
-                            }
+                // If an Accepting task's session stopped/ended, try to complete the smart merge
+                if was_accepting && matches!(event.event_type, SessionEventType::Stopped | SessionEventType::Ended) {
+                    commands.push(Message::CompleteAcceptTask(task_id));
+                }
+                // If an Updating task's session stopped/ended, complete the update (no merge!)
+                if was_updating && matches!(event.event_type, SessionEventType::Stopped | SessionEventType::Ended) {
+                    commands.push(Message::CompleteUpdateTask(task_id));
+                }
+                // If an Applying task's session stopped/ended, complete the apply
+                if was_applying && matches!(event.event_type, SessionEventType::Stopped | SessionEventType::Ended) {
+                    // Check if this was a stash conflict resolution (runs in main worktree)
+                    let is_conflict_resolution = self.model.active_project()
+                        .map(|p| p.applied_with_conflict_resolution)
+                        .unwrap_or(false);
+
+                    if is_conflict_resolution {
+                        commands.push(Message::CompleteStashConflictResolution(task_id));
+                    } else {
+                        commands.push(Message::CompleteApplyTask(task_id));
+                    }
+                }
+            }
+
+            Message::SdkSessionStarted { task_id, session_id } => {
+                // Update task with session ID from SDK
+                let mut worktree_display = String::new();
+                if let Some(project) = self.model.active_project_mut() {
+                    if let Some(task) = project.tasks.iter_mut().find(|t| t.id == task_id) {
+                        task.claude_session_id = Some(session_id);
+                        task.start_failure = None;
+                        // Don't override session state for completed tasks (Review, Done)
+                        // A late SdkSessionStarted from QA shouldn't undo the Paused state set by QaValidationPassed
+                        if task.status != TaskStatus::Review && task.status != TaskStatus::Done {
+                            task.session_state = crate::model::ClaudeSessionState::Working;
+                            task.session_mode = crate::model::SessionMode::SdkManaged;
+                        }
+                        // Increment SDK command count for CLI staleness detection
+                        task.sdk_command_count = task.sdk_command_count.saturating_add(1);
+                        if let Some(ref wt) = task.worktree_path {
+                            worktree_display = wt.display().to_string();
                         }
                     }
                 }
+                if !worktree_display.is_empty() {
+                    commands.push(Message::SetStatusMessage(Some(
+                        format!("Task started via SDK in worktree: {}", worktree_display)
+                    )));
+                }
+            }
 
-                // Only process signals that match a specific task (by UUID or worktree path)
-                // Signals from the main project's Claude are silently ignored - use worktree isolation
-                if !found_task {
-                    // Check if this signal is from the main project directory (not a worktree)
-                    // This is expected when developing on the project itself with Claude
-                    let is_main_project = self.model.projects.iter().any(|p| {
-                        let main_dir = p.working_dir.canonicalize().unwrap_or(p.working_dir.clone());
-                        main_dir == signal_dir
+            Message::OfferSessionRestart(task_id) => {
+                let Some(task) = self.model.projects.iter_mut()
+                    .flat_map(|p| p.tasks.iter_mut())
+                    .find(|t| t.id == task_id)
+                else {
+                    return commands;
+                };
+                if self.model.ui_state.pending_confirmation.is_some() {
+                    // Another question is open - ask again on the next context update
+                    task.context_restart_offered = false;
+                    return commands;
+                }
+                let title: String = task.short_title.as_ref().unwrap_or(&task.title).chars().take(30).collect();
+                commands.push(Message::ShowConfirmation {
+                    message: format!(
+                        "'{}' has used {:.0}% of its context.\nRestart the session with a summary of the progress so far? (y/n)",
+                        title, task.context_usage() * 100.0
+                    ),
+                    action: PendingAction::RestartSessionWithSummary(task_id),
+                });
+            }
+
+            Message::RestartSessionWithSummary(task_id) => {
+                let Some(task) = self.model.projects.iter_mut()
+                    .flat_map(|p| p.tasks.iter_mut())
+                    .find(|t| t.id == task_id)
+                else {
+                    return commands;
+                };
+                if task.status != TaskStatus::InProgress || task.restarting_session {
+                    return commands;
+                }
+                let Some(worktree_path) = task.worktree_path.clone() else {
+                    return commands;
+                };
+                if self.sidecar_client.is_none() {
+                    commands.push(Message::Error("Cannot restart session: sidecar not connected".to_string()));
+                    return commands;
+                }
+                task.context_restart_offered = true;
+                task.log_activity(format!(
+                    "Summarizing progress to restart the session ({:.0}% context)",
+                    task.context_usage() * 100.0
+                ));
+                let prompt = task.restart_summary_prompt();
+                if let Some(sender) = self.async_sender.clone() {
+                    tokio::spawn(async move {
+                        let result = tokio::task::spawn_blocking(move || {
+                            crate::sidecar::SidecarClient::complete_prompt_standalone(prompt, Some(worktree_path))
+                        }).await;
+                        let result = match result {
+                            Ok(Ok(summary)) => Ok(summary),
+                            Ok(Err(e)) => Err(e.to_string()),
+                            Err(e) => Err(format!("Task panicked: {}", e)),
+                        };
+                        let _ = sender.send(Message::SessionSummaryReady { task_id, result });
                     });
+                }
+            }
 
-                    if !is_main_project {
-                        // Only warn for unexpected signals (not from main project or known worktrees)
-                        commands.push(Message::SetStatusMessage(Some(
-                            format!("Hook '{}' received but no matching task for: {} (session: {})",
-                                signal.event,
-                                signal.project_dir.display(),
-                                signal.session_id)
-                        )));
+            Message::SessionSummaryReady { task_id, result } => {
+                let Some((task, scope_prompt)) = self.model.projects.iter_mut().find_map(|p| {
+                    let scope_prompt = p.scope_prompt();
+                    Some((p.tasks.iter_mut().find(|t| t.id == task_id)?, scope_prompt))
+                }) else {
+                    return commands;
+                };
+                let summary = match result {
+                    Ok(summary) if !summary.trim().is_empty() => summary,
+                    Ok(_) => {
+                        task.log_activity("Session restart skipped: the summary came back empty");
+                        return commands;
                     }
-                    // Silently ignore signals from main project - they're from the dev Claude session
+                    Err(e) => {
+                        task.log_activity(format!("Session restart skipped: summary failed ({})", e));
+                        commands.push(Message::SetStatusMessage(Some(format!("Could not summarize the session: {}", e))));
+                        return commands;
+                    }
+                };
+                // The old session kept working while the summary was written
+                if task.status != TaskStatus::InProgress {
+                    task.log_activity("Session finished before the restart; summary discarded");
+                    return commands;
+                }
+                let Some(worktree_path) = task.worktree_path.clone() else {
+                    return commands;
+                };
+                let prompt = format!(
+                    "{}{}\n\n# Progress so far\nAn earlier session worked on this task and was restarted to free up context. \
+                     Its summary:\n\n{}\n\nContinue from there. Check `git status` and `git diff` for the exact changes.",
+                    task.session_prompt(), scope_prompt, summary.trim()
+                );
+                let images: Option<Vec<String>> = if task.images.is_empty() {
+                    None
+                } else {
+                    Some(task.images.iter().map(|p| p.to_string_lossy().to_string()).collect())
+                };
+                task.restarting_session = true;
+                task.log_activity("Restarting the session with the summary");
+
+                if let Some(sender) = self.async_sender.clone() {
+                    tokio::spawn(async move {
+                        let result = tokio::task::spawn_blocking(move || {
+                            let client = crate::sidecar::SidecarClient::connect()?;
+                            client.stop_session(task_id)?;
+                            client.start_session(task_id, &worktree_path, &prompt, images)
+                        }).await;
+                        let result = match result {
+                            Ok(Ok(session_id)) => Ok(session_id),
+                            Ok(Err(e)) => Err(e.to_string()),
+                            Err(e) => Err(format!("Task panicked: {}", e)),
+                        };
+                        let _ = sender.send(Message::SessionRestarted { task_id, result });
+                    });
                 }
+            }
 
-                // Sync selection after task status changes
-                self.sync_selection();
+            Message::SessionRestarted { task_id, result } => {
+                let Some(project) = self.model.projects.iter_mut()
+                    .find(|p| p.tasks.iter().any(|t| t.id == task_id))
+                else {
+                    return commands;
+                };
+                let Some(task) = project.tasks.iter_mut().find(|t| t.id == task_id) else {
+                    return commands;
+                };
+                task.restarting_session = false;
+                match result {
+                    Ok(session_id) => {
+                        let before = task.context_tokens;
+                        task.claude_session_id = Some(session_id);
+                        task.session_state = crate::model::ClaudeSessionState::Working;
+                        task.session_mode = crate::model::SessionMode::SdkManaged;
+                        task.sdk_command_count = task.sdk_command_count.saturating_add(1);
+                        task.last_activity_at = Some(chrono::Utc::now());
+                        task.context_tokens = 0;
+                        task.context_restart_offered = false;
+                        task.context_compactions += 1;
+                        task.log_activity(format!(
+                            "Context compacted: restarted with a summary (was {}k tokens)",
+                            before / 1000
+                        ));
+                    }
+                    Err(e) => {
+                        // The old session is gone; leave the task where feedback can resume it
+                        task.log_activity(format!("Session restart failed: {}", e));
+                        task.session_state = crate::model::ClaudeSessionState::Paused;
+                        project.move_task_to_start_of_status(task_id, TaskStatus::Review);
+                        project.needs_attention = true;
+                        notify::play_task_cue(task_id, notify::SoundCue::Attention);
+                        notify::set_task_attention_indicator(task_id, &project.name);
+                        commands.push(Message::Error(format!("Session restart failed: {}", e)));
+                    }
+                }
             }
 
-            Message::ClaudeOutputUpdated { project_id, output } => {
-                // Store captured output for display
-                if let Some(project) = self.model.projects.iter_mut().find(|p| p.id == project_id) {
-                    project.captured_output = output;
+            Message::SdkSessionOutput { task_id, output } => {
+                // Store SDK output for display
+                for project in &mut self.model.projects {
+                    if project.tasks.iter().any(|t| t.id == task_id) {
+                        // Append to captured output
+                        project.captured_output.push_str(&output);
+                        break;
+                    }
                 }
             }
 
-            // === Async Background Task Results ===
+            Message::RequestTitleSummary { task_id } => {
+                // Get the task title for summarization and mark as generating
+                let title = self.model.active_project_mut()
+                    .and_then(|p| p.tasks.iter_mut().find(|t| t.id == task_id))
+                    .map(|t| {
+                        t.generating_spec = true;
+                        t.title.clone()
+                    });
 
-            Message::WorktreeCreated { task_id, display_id, worktree_path, project_dir } => {
-                // Update task with worktree info immediately for UI feedback
-                if let Some(project) = self.model.active_project_mut() {
-                    if let Some(task) = project.tasks.iter_mut().find(|t| t.id == task_id) {
-                        task.worktree_path = Some(worktree_path.clone());
-                        task.git_branch = Some(format!("claude/{}", display_id));
-                        task.session_state = crate::model::ClaudeSessionState::Starting;
-                    }
-                }
+                if let Some(title) = title {
+                    // Show status message
+                    commands.push(Message::SetStatusMessage(Some(
+                        "Generating spec...".to_string()
+                    )));
 
-                // Spawn settings setup in background, then start SDK session
-                if let Some(sender) = self.async_sender.clone() {
-                    let wt_path = worktree_path.clone();
-                    let proj_dir = project_dir.clone();
-                    tokio::spawn(async move {
-                        // Run settings setup in background thread
-                        let setup_result = tokio::task::spawn_blocking(move || {
-                            // Set up Claude settings (non-fatal if fails)
-                            let settings_err = crate::worktree::merge_with_project_settings(
-                                &wt_path,
-                                &proj_dir,
-                                task_id,
-                            ).err();
+                    // Spawn the summarization request in background
+                    if let Some(sender) = self.async_sender.clone() {
+                        tokio::spawn(async move {
+                            // Run blocking sidecar call in a separate thread
+                            let result = tokio::task::spawn_blocking(move || {
+                                crate::sidecar::SidecarClient::summarize_title_standalone(task_id, title)
+                            }).await;
 
-                            // Pre-trust the worktree (non-fatal if fails)
-                            let trust_err = crate::worktree::pre_trust_worktree(&wt_path).err();
+                            let msg = match result {
+                                Ok(Ok((short_title, abbreviation, spec))) => {
+                                    Message::TitleSummaryReceived { task_id, short_title, abbreviation, spec }
+                                }
+                                Ok(Err(e)) => {
+                                    // Log error but don't show to user - summarization is optional
+                                    crate::diagnostics::warn(crate::diagnostics::Source::Sidecar, format!("Failed for task {}: {}", task_id, e));
+                                    // Still send a message to clear the generating flag
+                                    Message::TitleSummaryReceived { task_id, short_title: String::new(), abbreviation: None, spec: None }
+                                }
+                                Err(e) => {
+                                    crate::diagnostics::warn(crate::diagnostics::Source::Sidecar, format!("Task panicked for {}: {}", task_id, e));
+                                    return;
+                                }
+                            };
 
-                            (settings_err, trust_err)
-                        }).await;
+                            let _ = sender.send(msg);
+                        });
+                    }
+                }
+            }
 
-                        // Report warnings but continue to start SDK session
-                        if let Ok((settings_err, trust_err)) = setup_result {
-                            if let Some(e) = settings_err {
-                                let _ = sender.send(Message::SetStatusMessage(Some(
-                                    format!("Warning: Could not set up Claude settings: {}", e)
-                                )));
-                            }
-                            if let Some(e) = trust_err {
-                                let _ = sender.send(Message::SetStatusMessage(Some(
-                                    format!("Warning: Could not pre-trust worktree: {}", e)
-                                )));
-                            }
+            Message::TitleSummaryReceived { task_id, short_title, abbreviation, spec } => {
+                // Update the task with the short title, abbreviation, and spec
+                let mut should_start = false;
+                for project in &mut self.model.projects {
+                    if let Some(task) = project.tasks.iter_mut().find(|t| t.id == task_id) {
+                        // Only update if we got a meaningful short title
+                        if !short_title.is_empty() {
+                            task.short_title = Some(short_title);
                         }
+                        // Only set abbreviation if not already set (preserves match with branch/worktree names)
+                        if abbreviation.is_some() && task.abbreviation.is_none() {
+                            task.abbreviation = abbreviation;
+                        }
+                        task.spec = spec;
+                        task.generating_spec = false;
 
-                        // Start SDK session
-                        let _ = sender.send(Message::StartSdkSession { task_id });
-                    });
-                } else {
-                    // Fallback to sync if no async sender
-                    if let Err(e) = crate::worktree::merge_with_project_settings(
-                        &worktree_path,
-                        &project_dir,
-                        task_id,
-                    ) {
-                        commands.push(Message::SetStatusMessage(Some(
-                            format!("Warning: Could not set up Claude settings: {}", e)
-                        )));
+                        // Check if we should auto-start the task
+                        if task.start_after_spec {
+                            task.start_after_spec = false;
+                            should_start = true;
+                        }
+                        break;
                     }
+                }
 
-                    if let Err(e) = crate::worktree::pre_trust_worktree(&worktree_path) {
-                        commands.push(Message::SetStatusMessage(Some(
-                            format!("Warning: Could not pre-trust worktree: {}", e)
-                        )));
-                    }
+                // Clear the status message
+                commands.push(Message::SetStatusMessage(None));
 
-                    commands.push(Message::StartSdkSession { task_id });
+                // Auto-start the task if it was waiting for spec
+                if should_start {
+                    commands.push(Message::StartTaskWithWorktree(task_id));
                 }
             }
 
-            Message::WorktreeCreationFailed { task_id, error } => {
-                // Reset task state on failure
-                if let Some(project) = self.model.active_project_mut() {
-                    if let Some(task) = project.tasks.iter_mut().find(|t| t.id == task_id) {
-                        task.session_state = crate::model::ClaudeSessionState::NotStarted;
-                        task.status = TaskStatus::Planned;
-                        task.started_at = None;
-                    }
+            Message::OpenInteractiveModal(task_id) => {
+                if let Some(banner) = self.capability_banner(&[Capability::Tmux, Capability::ClaudeCli]) {
+                    commands.push(Message::SetStatusMessage(Some(banner)));
+                    return commands;
                 }
-                commands.push(Message::Error(format!("Failed to create worktree: {}", error)));
-            }
+                // Gather task info including SDK command count for staleness check
+                let task_info = self.model.active_project().and_then(|project| {
+                    project.tasks.iter().find(|t| t.id == task_id).map(|task| {
+                        (
+                            task.worktree_path.clone(),
+                            task.claude_session_id.clone(),
+                            task.session_state.clone(),
+                            task.session_mode,
+                            task.sdk_command_count,
+                            task.cli_opened_at_command_count,
+                        )
+                    })
+                });
 
-            Message::SdkSessionFailed { task_id, error, project_dir, worktree_path } => {
-                // Clean up worktree since SDK failed
-                let _ = crate::worktree::remove_worktree(&project_dir, &worktree_path);
-                // Reset task state
-                if let Some(project) = self.model.active_project_mut() {
-                    if let Some(task) = project.tasks.iter_mut().find(|t| t.id == task_id) {
-                        task.session_state = crate::model::ClaudeSessionState::NotStarted;
-                        task.status = TaskStatus::Planned;
-                        task.started_at = None;
-                        task.worktree_path = None;
-                        task.git_branch = None;
+                if let Some((worktree_path, _session_id, session_state, session_mode, _sdk_count, _cli_opened_at)) = task_info {
+                    // Require worktree path
+                    let Some(_worktree_path) = worktree_path else {
+                        commands.push(Message::Error(
+                            "Cannot open interactive mode: no worktree path.".to_string()
+                        ));
+                        return commands;
+                    };
+
+                    // Check if SDK is actively working - if so, ask for confirmation before interrupting
+                    let sdk_is_working = session_mode == crate::model::SessionMode::SdkManaged
+                        && session_state == crate::model::ClaudeSessionState::Working;
+
+                    if sdk_is_working {
+                        // Show confirmation dialog before interrupting SDK
+                        commands.push(Message::ShowConfirmation {
+                            message: "Claude is working via SDK. Interrupt to open terminal? (y/n)".to_string(),
+                            action: PendingAction::InterruptSdkForCli(task_id),
+                        });
+                        return commands;
                     }
+
+                    // SDK not actively working - proceed with opening CLI
+                    commands.push(Message::DoOpenInteractiveModal(task_id));
                 }
-                commands.push(Message::Error(format!("Failed to start SDK session: {}", error)));
             }
 
-            // === Sidecar/SDK Events ===
-
-            Message::StartSdkSession { task_id } => {
-                // Get task info for SDK call
+            Message::DoOpenInteractiveModal(task_id) => {
+                // Actually open the interactive modal (after confirmation or if SDK was idle)
                 let task_info = self.model.active_project().and_then(|project| {
                     project.tasks.iter().find(|t| t.id == task_id).map(|task| {
-                        // Build prompt from title and spec
-                        let prompt = if let Some(ref spec) = task.spec {
-                            format!("# Task\n{}\n\n# Spec\n{}", task.title, spec)
-                        } else {
-                            task.title.clone()
-                        };
                         (
-                            prompt,
-                            task.images.clone(),
                             task.worktree_path.clone(),
-                            project.working_dir.clone(),
+                            task.claude_session_id.clone(),
+                            task.sdk_command_count,
+                            task.cli_opened_at_command_count,
+                            task.session_mode.clone(),
                         )
                     })
                 });
 
-                if let Some((prompt, images, Some(worktree_path), project_dir)) = task_info {
-                    // Check if sidecar is available before spawning background task
-                    if self.sidecar_client.is_none() {
-                        // No sidecar available - cannot start task
-                        commands.push(Message::Error(
-                            "Cannot start task: Sidecar not connected. Ensure sidecar is running.".to_string()
-                        ));
-                        // Reset task state
-                        if let Some(project) = self.model.active_project_mut() {
-                            if let Some(task) = project.tasks.iter_mut().find(|t| t.id == task_id) {
-                                task.session_state = crate::model::ClaudeSessionState::NotStarted;
-                                task.status = TaskStatus::Planned;
-                                task.worktree_path = None;
-                                task.git_branch = None;
-                            }
+                if let Some((worktree_path, session_id, sdk_count, cli_opened_at, session_mode)) = task_info {
+                    let Some(worktree_path) = worktree_path else {
+                        return commands;
+                    };
+
+                    // Stop SDK session first (if running) before CLI takeover
+                    if let Some(ref client) = self.sidecar_client {
+                        if let Err(e) = client.stop_session(task_id) {
+                            crate::diagnostics::warn(crate::diagnostics::Source::Sidecar, format!("Could not stop SDK session: {}", e));
                         }
-                        // Clean up worktree since we can't start
-                        let _ = crate::worktree::remove_worktree(&project_dir, &worktree_path);
-                    } else if let Some(sender) = self.async_sender.clone() {
-                        // Spawn SDK session start in background to keep UI responsive
-                        let images_str: Option<Vec<String>> = if !images.is_empty() {
-                            Some(images.iter().map(|p| p.to_string_lossy().to_string()).collect())
-                        } else {
-                            None
-                        };
+                    }
 
-                        // Clone paths for use in closures
-                        let worktree_path_for_call = worktree_path.clone();
-                        let worktree_path_for_error = worktree_path.clone();
+                    // Check if existing CLI terminal has stale state (SDK ran commands since it was opened)
+                    let cli_is_stale = sdk_count > cli_opened_at;
 
-                        tokio::spawn(async move {
-                            // Run blocking sidecar call in a separate thread
-                            let result = tokio::task::spawn_blocking(move || {
-                                crate::sidecar::SidecarClient::start_session_standalone(
-                                    task_id,
-                                    worktree_path_for_call,
-                                    prompt,
-                                    images_str,
-                                )
-                            }).await;
+                    if cli_is_stale {
+                        // Check if CLI is currently working (using session_mode updated by hooks)
+                        if session_mode == crate::model::SessionMode::CliActivelyWorking {
+                            // CLI is actively working - don't interrupt, let user see it
+                            // Just switch to the existing session
+                        } else {
+                            // CLI is idle or not running - restart Claude in place so the
+                            // other panes (and the scratch shell) are re-attached, not recreated
+                            if let Err(e) = crate::tmux::respawn_claude_pane(&worktree_path, session_id.as_deref()) {
+                                crate::diagnostics::warn(crate::diagnostics::Source::Sidecar, format!("Could not restart stale CLI session: {}", e));
+                            }
+                        }
+                    }
 
-                            let msg = match result {
-                                Ok(Ok(session_id)) => {
-                                    Message::SdkSessionStarted { task_id, session_id }
-                                }
-                                Ok(Err(e)) => {
-                                    Message::SdkSessionFailed { task_id, error: e.to_string(), project_dir, worktree_path: worktree_path_for_error }
-                                }
-                                Err(e) => {
-                                    Message::SdkSessionFailed { task_id, error: format!("Task panicked: {}", e), project_dir, worktree_path: worktree_path_for_error }
-                                }
-                            };
+                    // Always try to resume if we have a session_id
+                    // This shows conversation history even for completed sessions
+                    let resume_session_id = session_id.as_deref();
+                    let parent_session = crate::tmux::get_current_session_name();
+                    let layout = crate::tmux::SessionLayout::for_project(
+                        self.model.active_project().and_then(|p| p.session_layout.as_deref()),
+                    );
+
+                    // Open tmux popup with Claude (creates the session, or switches to the existing one)
+                    if let Err(e) = crate::tmux::open_popup(&worktree_path, resume_session_id, parent_session.as_deref(), &layout) {
+                        commands.push(Message::Error(format!(
+                            "Failed to open interactive popup: {}", e
+                        )));
+                        return commands;
+                    }
 
-                            let _ = sender.send(msg);
-                        });
-                    } else {
-                        // Fallback to sync if no async sender (shouldn't happen in normal operation)
-                        if let Some(ref client) = self.sidecar_client {
-                            let images_str: Option<Vec<String>> = if !images.is_empty() {
-                                Some(images.iter().map(|p| p.to_string_lossy().to_string()).collect())
-                            } else {
-                                None
-                            };
+                    // Update session mode to CLI and record when CLI was opened
+                    if let Some(project) = self.model.active_project_mut() {
+                        if let Some(task) = project.tasks.iter_mut().find(|t| t.id == task_id) {
+                            task.session_mode = crate::model::SessionMode::CliInteractive;
+                            // Record current SDK command count so we can detect future staleness
+                            task.cli_opened_at_command_count = task.sdk_command_count;
+                            task.log_activity("User opened terminal");
+                        }
+                    }
+                }
+            }
 
-                            match client.start_session(task_id, &worktree_path, &prompt, images_str) {
-                                Ok(session_id) => {
-                                    commands.push(Message::SdkSessionStarted { task_id, session_id });
-                                }
-                                Err(e) => {
-                                    commands.push(Message::SdkSessionFailed { task_id, error: e.to_string(), project_dir, worktree_path });
-                                }
-                            }
+            Message::CloseInteractiveModal => {
+                // Get the task_id before closing the modal
+                if let Some(modal) = &self.model.ui_state.interactive_modal {
+                    let task_id = modal.task_id;
+
+                    // Mark task as waiting for CLI to exit
+                    if let Some(project) = self.model.active_project_mut() {
+                        if let Some(task) = project.tasks.iter_mut().find(|t| t.id == task_id) {
+                            task.session_mode = crate::model::SessionMode::WaitingForCliExit;
                         }
                     }
                 }
+
+                // Close the modal, keep Claude running in background
+                self.model.ui_state.interactive_modal = None;
             }
 
-            Message::SidecarEvent(event) => {
-                // Handle events from the SDK sidecar
-                use crate::sidecar::SessionEventType;
+            Message::EnterCopyMode => {
+                let Some(ref mut modal) = self.model.ui_state.interactive_modal else {
+                    return commands;
+                };
+                match crate::tmux::capture_pane_with_escapes(&modal.tmux_target) {
+                    Ok(content) => {
+                        let width = crate::tmux::get_pane_size(&modal.tmux_target)
+                            .map_or(80, |(w, _)| w as usize);
+                        modal.copy_mode = Some(crate::model::copy_mode::CopyMode::from_capture(&content, width));
+                    }
+                    Err(e) => commands.push(Message::SetStatusMessage(Some(format!("Nothing to copy: {}", e)))),
+                }
+            }
 
-                let task_id = event.task_id;
+            Message::YankCopySelection => {
+                let Some(copy) = self.model.ui_state.interactive_modal.as_mut().and_then(|m| m.copy_mode.take()) else {
+                    return commands;
+                };
+                let text = copy.selected_text();
+                let status = match crate::image::copy_text_to_clipboard(&text) {
+                    Ok(()) => format!("Copied {} line(s) to clipboard", text.lines().count().max(1)),
+                    Err(e) => format!("Failed to copy: {}", e),
+                };
+                commands.push(Message::SetStatusMessage(Some(status)));
+            }
 
-                // Track if this was an Accepting/Updating/Applying task that stopped/ended (for completion)
-                let mut was_accepting = false;
-                let mut was_updating = false;
-                let mut was_applying = false;
+            Message::ExitCopyMode => {
+                if let Some(ref mut modal) = self.model.ui_state.interactive_modal {
+                    modal.copy_mode = None;
+                }
+            }
 
-                for project in &mut self.model.projects {
-                    if let Some(task) = project.tasks.iter_mut().find(|t| t.id == task_id) {
-                        // Check if task was in Accepting/Updating/Applying status (rebase in progress)
-                        was_accepting = task.status == TaskStatus::Accepting;
-                        was_updating = task.status == TaskStatus::Updating;
-                        was_applying = task.status == TaskStatus::Applying;
+            Message::RefreshInteractiveDiff => {
+                let Some(task_id) = self.model.ui_state.interactive_modal.as_ref()
+                    .filter(|m| !m.diff_loading)
+                    .map(|m| m.task_id)
+                else {
+                    return commands;
+                };
+                let target = self.model.projects.iter().find_map(|p| {
+                    let task = p.tasks.iter().find(|t| t.id == task_id)?;
+                    Some((p.working_dir.clone(), task.worktree_path.clone()?, p.status_ignore_patterns()))
+                });
+                let (Some((project_dir, worktree_path, ignore)), Some(sender)) = (target, self.async_sender.clone()) else {
+                    return commands;
+                };
+                if let Some(ref mut modal) = self.model.ui_state.interactive_modal {
+                    modal.diff_loading = true;
+                }
+                tokio::spawn(async move {
+                    let result = tokio::task::spawn_blocking(move || {
+                        crate::worktree::get_worktree_diff(&project_dir, &worktree_path, &ignore)
+                    }).await;
 
-                        match event.event_type {
-                            SessionEventType::Started => {
-                                // Update session_id from Started event (safe - it's a new session)
-                                if let Some(ref session_id) = event.session_id {
-                                    task.claude_session_id = Some(session_id.clone());
-                                }
-                                // Don't override special statuses (rebase sessions, QA, or Review)
-                                // Review is protected because QA completion moves to Review, and
-                                // a late Started event from the QA session shouldn't undo that
-                                if task.status != TaskStatus::Accepting
-                                    && task.status != TaskStatus::Updating
-                                    && task.status != TaskStatus::Applying
-                                    && task.status != TaskStatus::Testing
-                                    && task.status != TaskStatus::Review
-                                {
-                                    task.status = TaskStatus::InProgress; // Session started, Claude is now working
-                                    task.session_state = crate::model::ClaudeSessionState::Working;
-                                    task.session_mode = crate::model::SessionMode::SdkManaged;
-                                }
-                                task.log_activity("Session started");
-                            }
-                            SessionEventType::Stopped => {
-                                task.log_activity_with_output("Session stopped", event.full_output.clone());
+                    let result = match result {
+                        Ok(Ok(diff)) => Ok(diff),
+                        Ok(Err(e)) => Err(e.to_string()),
+                        Err(e) => Err(format!("Task panicked: {}", e)),
+                    };
+                    let _ = sender.send(Message::InteractiveDiffLoaded { task_id, result });
+                });
+            }
 
-                                // Record token usage from this session
-                                if let Some(ref usage) = event.usage {
-                                    task.add_token_usage(
-                                        usage.input_tokens,
-                                        usage.output_tokens,
-                                        usage.cache_read_tokens,
-                                        usage.cache_creation_tokens,
-                                        event.cost_usd.unwrap_or(0.0),
-                                    );
-                                }
+            Message::InteractiveDiffLoaded { task_id, result } => {
+                if let Some(ref mut modal) = self.model.ui_state.interactive_modal {
+                    if modal.task_id == task_id {
+                        modal.diff_loading = false;
+                        match result {
+                            Ok(diff) => {
+                                modal.diff = Some(crate::worktree::diff_cache::CachedDiff::parse(diff));
+                                modal.diff_refreshed_at = Some(chrono::Utc::now());
+                            }
+                            // Keep showing the last diff
+                            Err(e) => commands.push(Message::SetStatusMessage(Some(format!("Couldn't refresh diff: {}", e)))),
+                        }
+                    }
+                }
+            }
 
-                                // Skip if terminal state or special operations in progress
-                                if was_accepting || was_updating || was_applying || task.status == TaskStatus::Done {
-                                    // Let CompleteAcceptTask/etc handlers take care of it
-                                } else if task.in_qa_session && task.status == TaskStatus::Testing {
-                                    // QA session ending - check for result markers in output
-                                    let output = event.output.as_deref().unwrap_or("");
-                                    let task_id = task.id;
+            Message::CliSessionEnded { task_id } => {
+                // CLI session ended, resume with SDK
+                commands.push(Message::ResumeSdkSession { task_id });
+            }
 
-                                    if output.contains("[QA:PASS]") {
-                                        commands.push(Message::QaValidationPassed(task_id));
-                                    } else if output.contains("[QA:FAIL]") {
-                                        commands.push(Message::QaValidationNeedsWork {
-                                            task_id,
-                                            feedback: output.to_string()
-                                        });
-                                    }
-                                    // No markers = stale event from before QA started, ignore
-                                } else if task.status == TaskStatus::InProgress {
-                                    // Work finished - start QA or move to Review
-                                    let should_qa = project.qa_enabled && !task.skip_qa;
-                                    let task_id = task.id;
+            Message::ResumeSdkSession { task_id } => {
+                // Get the session_id and worktree_path from the task first (immutable borrow)
+                let task_info = self.model.active_project().and_then(|project| {
+                    project.tasks.iter().find(|t| t.id == task_id)
+                        .and_then(|task| {
+                            task.claude_session_id.clone().and_then(|sid| {
+                                task.worktree_path.clone().map(|wt| (sid, wt))
+                            })
+                        })
+                });
 
-                                    if should_qa {
-                                        task.status = TaskStatus::Testing;
-                                        commands.push(Message::StartQaValidation(task_id));
-                                    } else {
-                                        task.session_state = crate::model::ClaudeSessionState::Paused;
-                                        project.move_task_to_start_of_status(task_id, TaskStatus::Review);
-                                        project.needs_attention = true;
-                                        notify::play_attention_sound();
-                                        notify::set_attention_indicator(&project.name);
+                // Resume the SDK session via sidecar
+                if let Some((session_id, worktree_path)) = task_info {
+                    if let Some(ref client) = self.sidecar_client {
+                        match client.resume_session(task_id, &session_id, &worktree_path, None) {
+                            Ok(new_session_id) => {
+                                // Update task with new session ID and mode
+                                if let Some(project) = self.model.active_project_mut() {
+                                    if let Some(task) = project.tasks.iter_mut().find(|t| t.id == task_id) {
+                                        task.claude_session_id = Some(new_session_id);
+                                        task.session_mode = crate::model::SessionMode::SdkManaged;
+                                        // Only set Working state if task is actively being worked on
+                                        // For Review tasks, the session might just be idle - let actual
+                                        // Working/ToolUse events update the state if SDK starts working
+                                        if task.status != TaskStatus::Review && task.status != TaskStatus::Done {
+                                            task.session_state = crate::model::ClaudeSessionState::Working;
+                                        }
+                                        // Increment SDK command count for CLI staleness detection
+                                        task.sdk_command_count = task.sdk_command_count.saturating_add(1);
                                     }
                                 }
-                                // Other statuses (Testing w/o in_qa_session, NeedsWork, Review): do nothing
-                            }
-                            SessionEventType::Ended => {
-                                task.log_activity_with_output("Session ended", event.full_output.clone());
-                                // Ended is a fallback - Stopped handler is primary for QA logic
-                                // Only act if task is still InProgress (Stopped may have already handled it)
-                                if was_accepting || was_updating || was_applying
-                                    || task.status == TaskStatus::Done
-                                    || task.status == TaskStatus::Review
-                                    || task.status == TaskStatus::Testing
-                                {
-                                    // Already handled or in QA - skip
-                                } else if task.status == TaskStatus::InProgress {
-                                    // Ended without Stopped handling it - move to Review
-                                    // (QA start is only triggered by Stopped which has the output)
-                                    task.session_state = crate::model::ClaudeSessionState::Ended;
-                                    let task_id = task.id;
-                                    project.move_task_to_start_of_status(task_id, TaskStatus::Review);
-                                    project.needs_attention = true;
-                                    notify::play_attention_sound();
-                                    notify::set_attention_indicator(&project.name);
-                                }
-                            }
-                            SessionEventType::NeedsInput => {
-                                task.log_activity_with_output("Waiting for input...", event.full_output.clone());
-                                // Don't change status if task is Accepting/Updating/Applying/Testing (mid-rebase or QA)
-                                if !was_accepting && !was_updating && !was_applying
-                                    && task.status != TaskStatus::Testing
-                                {
-                                    task.status = TaskStatus::NeedsWork;
-                                    task.session_state = crate::model::ClaudeSessionState::Paused;
-                                    project.needs_attention = true;
-                                    notify::play_attention_sound();
-                                    notify::set_attention_indicator(&project.name);
-                                }
+                                commands.push(Message::SetStatusMessage(Some(
+                                    "SDK session resumed".to_string()
+                                )));
                             }
-                            SessionEventType::Working => {
-                                task.log_activity_with_output("Working...", event.full_output.clone());
-                                // Don't override special statuses (rebase, QA, or completed Review)
-                                // Review is protected because QA completion moves to Review, and
-                                // late Working events from the QA session shouldn't undo that
-                                if task.status != TaskStatus::Accepting && task.status != TaskStatus::Updating && task.status != TaskStatus::Applying && task.status != TaskStatus::Testing && task.status != TaskStatus::Review {
-                                    task.status = TaskStatus::InProgress;
-                                    task.session_state = crate::model::ClaudeSessionState::Working;
-                                    project.needs_attention = false;
-                                    notify::clear_attention_indicator();
-                                    task.last_activity_at = Some(chrono::Utc::now());
+                            Err(e) => {
+                                commands.push(Message::Error(format!("Failed to resume SDK session: {}", e)));
+                                // Fallback: just mark as SDK managed and hope events come in
+                                if let Some(project) = self.model.active_project_mut() {
+                                    if let Some(task) = project.tasks.iter_mut().find(|t| t.id == task_id) {
+                                        task.session_mode = crate::model::SessionMode::SdkManaged;
+                                    }
                                 }
                             }
-                            SessionEventType::ToolUse => {
-                                // Log the tool being used
-                                let tool_msg = if let Some(ref tool_name) = event.tool_name {
-                                    format!("Using {}", tool_name)
-                                } else {
-                                    "Using tool...".to_string()
-                                };
-                                task.log_activity_with_output(&tool_msg, event.full_output.clone());
-                                // Don't override special statuses (rebase, QA, or completed Review)
-                                // Review is protected because QA completion moves to Review, and
-                                // late ToolUse events from the QA session shouldn't undo that
-                                if task.status != TaskStatus::Accepting && task.status != TaskStatus::Updating && task.status != TaskStatus::Applying && task.status != TaskStatus::Testing && task.status != TaskStatus::Review {
-                                    task.status = TaskStatus::InProgress;
-                                    task.session_state = crate::model::ClaudeSessionState::Working;
-                                    project.needs_attention = false;
-                                    notify::clear_attention_indicator();
-                                    // Track activity for merge feedback
-                                    task.last_activity_at = Some(chrono::Utc::now());
-                                    if let Some(ref tool_name) = event.tool_name {
-                                        task.last_tool_name = Some(tool_name.clone());
+                        }
+                    } else {
+                        // No sidecar client available
+                        commands.push(Message::Error("Cannot resume: sidecar not connected".to_string()));
+                    }
+                } else {
+                    // No session or worktree path to resume
+                    commands.push(Message::Error("Cannot resume: no session ID or worktree path found".to_string()));
+                }
+            }
+
+            Message::StartRebaseSession { task_id } => {
+                // Start an SDK session specifically for rebasing during smart merge
+                let task_info = self.model.active_project().and_then(|project| {
+                    project.tasks.iter().find(|t| t.id == task_id).map(|task| {
+                        (
+                            task.worktree_path.clone(),
+                            project.working_dir.clone(),
+                        )
+                    })
+                });
+
+                if let Some((Some(worktree_path), project_dir)) = task_info {
+                    // Detect main branch name (master or main)
+                    let main_branch = std::process::Command::new("git")
+                        .current_dir(&project_dir)
+                        .args(["rev-parse", "--abbrev-ref", "HEAD"])
+                        .timed_output()
+                        .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string())
+                        .unwrap_or_else(|_| "master".to_string());
+
+                    // Generate the rebase prompt
+                    let prompt = crate::worktree::generate_rebase_prompt(&main_branch);
+
+                    if let Some(ref client) = self.sidecar_client {
+                        match client.start_session(task_id, &worktree_path, &prompt, None) {
+                            Ok(session_id) => {
+                                // Update task with session ID and Accepting status
+                                if let Some(project) = self.model.active_project_mut() {
+                                    if let Some(task) = project.tasks.iter_mut().find(|t| t.id == task_id) {
+                                        task.claude_session_id = Some(session_id);
+                                        task.status = TaskStatus::Accepting;
+                                        task.session_state = crate::model::ClaudeSessionState::Working;
+                                        task.session_mode = crate::model::SessionMode::SdkManaged;
+                                        // Track when merge started for elapsed time display
+                                        task.accepting_started_at = Some(chrono::Utc::now());
+                                        task.last_activity_at = Some(chrono::Utc::now());
+                                        task.last_tool_name = None;
+                                        // Clear and start activity log
+                                        task.clear_activity_log();
+                                        task.log_activity("Starting smart merge...");
+                                        task.log_activity("Rebasing onto main branch");
                                     }
                                 }
+                                commands.push(Message::SetStatusMessage(Some(
+                                    "Rebasing onto main... Claude is resolving any conflicts.".to_string()
+                                )));
                             }
-                            SessionEventType::Output => {
-                                // Store output for display (could be used by output panel)
-                                if let Some(ref output) = event.output {
-                                    project.captured_output.push_str(output);
-                                    // Log first line of output if it's meaningful
-                                    let first_line = output.lines().next().unwrap_or("").trim();
-                                    if !first_line.is_empty() && first_line.len() <= 60 {
-                                        task.log_activity_with_output(first_line, event.full_output.clone());
+                            Err(e) => {
+                                commands.push(Message::Error(format!("Failed to start rebase session: {}", e)));
+                                // Reset task to Review status
+                                if let Some(project) = self.model.active_project_mut() {
+                                    if let Some(task) = project.tasks.iter_mut().find(|t| t.id == task_id) {
+                                        task.move_to_review();
                                     }
                                 }
                             }
                         }
-                        break;
+                    } else {
+                        commands.push(Message::Error(
+                            "Cannot start rebase: sidecar not connected.".to_string()
+                        ));
                     }
                 }
-                self.sync_selection();
+            }
 
-                // If an Accepting task's session stopped/ended, try to complete the smart merge
-                if was_accepting && matches!(event.event_type, SessionEventType::Stopped | SessionEventType::Ended) {
-                    commands.push(Message::CompleteAcceptTask(task_id));
-                }
-                // If an Updating task's session stopped/ended, complete the update (no merge!)
-                if was_updating && matches!(event.event_type, SessionEventType::Stopped | SessionEventType::Ended) {
-                    commands.push(Message::CompleteUpdateTask(task_id));
-                }
-                // If an Applying task's session stopped/ended, complete the apply
-                if was_applying && matches!(event.event_type, SessionEventType::Stopped | SessionEventType::Ended) {
-                    // Check if this was a stash conflict resolution (runs in main worktree)
-                    let is_conflict_resolution = self.model.active_project()
-                        .map(|p| p.applied_with_conflict_resolution)
-                        .unwrap_or(false);
+            Message::StartApplySession { task_id } => {
+                // Start an SDK session for rebasing before apply (different from accept)
+                let task_info = self.model.active_project().and_then(|project| {
+                    project.tasks.iter().find(|t| t.id == task_id).map(|task| {
+                        (
+                            task.worktree_path.clone(),
+                            project.working_dir.clone(),
+                        )
+                    })
+                });
 
-                    if is_conflict_resolution {
-                        commands.push(Message::CompleteStashConflictResolution(task_id));
+                if let Some((Some(worktree_path), project_dir)) = task_info {
+                    // Detect main branch name
+                    let main_branch = std::process::Command::new("git")
+                        .current_dir(&project_dir)
+                        .args(["rev-parse", "--abbrev-ref", "HEAD"])
+                        .timed_output()
+                        .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string())
+                        .unwrap_or_else(|_| "master".to_string());
+
+                    // Generate apply prompt (rebase with apply context)
+                    let prompt = crate::worktree::generate_apply_prompt(&main_branch);
+
+                    if let Some(ref client) = self.sidecar_client {
+                        match client.start_session(task_id, &worktree_path, &prompt, None) {
+                            Ok(session_id) => {
+                                // Update task with session ID and Applying status
+                                if let Some(project) = self.model.active_project_mut() {
+                                    if let Some(task) = project.tasks.iter_mut().find(|t| t.id == task_id) {
+                                        task.claude_session_id = Some(session_id);
+                                        task.status = TaskStatus::Applying;
+                                        task.session_state = crate::model::ClaudeSessionState::Working;
+                                        task.session_mode = crate::model::SessionMode::SdkManaged;
+                                        task.accepting_started_at = Some(chrono::Utc::now());
+                                        task.last_activity_at = Some(chrono::Utc::now());
+                                        task.last_tool_name = None;
+                                        task.clear_activity_log();
+                                        task.log_activity("Starting smart apply...");
+                                        task.log_activity("Rebasing onto main for apply");
+                                    }
+                                }
+                                commands.push(Message::SetStatusMessage(Some(
+                                    "Rebasing for apply... Claude is resolving conflicts.".to_string()
+                                )));
+                            }
+                            Err(e) => {
+                                commands.push(Message::Error(format!("Failed to start apply session: {}", e)));
+                                // Task stays in Review status
+                            }
+                        }
                     } else {
-                        commands.push(Message::CompleteApplyTask(task_id));
+                        commands.push(Message::Error(
+                            "Cannot start apply: sidecar not connected.".to_string()
+                        ));
                     }
                 }
             }
 
-            Message::SdkSessionStarted { task_id, session_id } => {
-                // Update task with session ID from SDK
-                let mut worktree_display = String::new();
-                if let Some(project) = self.model.active_project_mut() {
-                    if let Some(task) = project.tasks.iter_mut().find(|t| t.id == task_id) {
-                        task.claude_session_id = Some(session_id);
-                        // Don't override session state for completed tasks (Review, Done)
-                        // A late SdkSessionStarted from QA shouldn't undo the Paused state set by QaValidationPassed
-                        if task.status != TaskStatus::Review && task.status != TaskStatus::Done {
-                            task.session_state = crate::model::ClaudeSessionState::Working;
-                            task.session_mode = crate::model::SessionMode::SdkManaged;
+            Message::CompleteApplyTask(task_id) => {
+                // Complete the apply after rebase - do the actual apply
+                let task_info = self.model.active_project().and_then(|p| {
+                    p.tasks.iter()
+                        .find(|t| t.id == task_id)
+                        .map(|t| (
+                            p.working_dir.clone(),
+                            t.worktree_path.clone(),
+                            t.git_branch.clone(),
+                            t.status,
+                            t.display_id(),
+                        ))
+                });
+
+                if let Some((project_dir, worktree_path, git_branch, status, display_id)) = task_info {
+                    // Check if rebase is still in progress
+                    if let Some(ref wt_path) = worktree_path {
+                        if crate::worktree::is_rebase_in_progress(wt_path) {
+                            commands.push(Message::Error(
+                                "Rebase still in progress. Wait for Claude to finish.".to_string()
+                            ));
+                            return commands;
+                        }
+                    }
+
+                    // Get the branch name (should exist if we got here via rebase)
+                    let branch_name = match git_branch {
+                        Some(ref b) => b.clone(),
+                        None => {
+                            commands.push(Message::Error(
+                                "Task has no git branch reference.".to_string()
+                            ));
+                            return commands;
+                        }
+                    };
+
+                    // Verify rebase succeeded
+                    match crate::worktree::verify_rebase_success(&project_dir, &display_id) {
+                        Ok(true) => {
+                            // Rebase successful, now do the apply
+                            match crate::worktree::apply_task_changes(&project_dir, &display_id, &branch_name) {
+                                Ok(stash_warning) => {
+                                    // Apply succeeded - stash was immediately popped
+                                    if let Some(ref warning) = stash_warning {
+                                        if warning.starts_with("STASH_") {
+                                            commands.push(Message::SetStatusMessage(Some(warning.clone())));
+                                        }
+                                    }
+                                    if let Some(project) = self.model.active_project_mut() {
+                                        project.applied_task_id = Some(task_id);
+                                        project.applied_stash_ref = None; // No longer tracked
+
+                                        // Return task to Review status
+                                        if let Some(task) = project.tasks.iter_mut().find(|t| t.id == task_id) {
+                                            task.move_to_review();
+                                            task.session_state = crate::model::ClaudeSessionState::Paused;
+                                            task.accepting_started_at = None;
+                                        }
+                                    }
+
+                                    // Release lock and trigger async build + restart
+                                    // Build check happens async in TriggerRestart - if it fails,
+                                    // user is prompted to unapply
+                                    if let Some(project) = self.model.active_project_mut() {
+                                        project.release_main_worktree_lock(task_id);
+                                    }
+                                    commands.push(Message::SetStatusMessage(Some(
+                                        "✓ Changes applied. Building...".to_string()
+                                    )));
+                                    commands.push(Message::RefreshGitStatus);
+                                    commands.push(Message::TriggerRestart);
+                                }
+                                Err(e) => {
+                                    let err_msg = e.to_string();
+                                    if let Some(project) = self.model.active_project_mut() {
+                                        if let Some(task) = project.tasks.iter_mut().find(|t| t.id == task_id) {
+                                            task.move_to_review();
+                                            task.session_state = crate::model::ClaudeSessionState::Paused;
+                                        }
+                                        project.release_main_worktree_lock(task_id);
+                                    }
+                                    // Check for "already merged" case
+                                    if err_msg.contains("Nothing to apply") || err_msg.contains("No valid patches") {
+                                        commands.push(Message::Error(
+                                            "Nothing to apply - task changes are already in main. Mark as done with 'm'.".to_string()
+                                        ));
+                                    } else {
+                                        commands.push(Message::Error(format!(
+                                            "Apply failed: {}",
+                                            err_msg
+                                        )));
+                                    }
+                                }
+                            }
+                        }
+                        Ok(false) => {
+                            // Rebase failed
+                            if let Some(project) = self.model.active_project_mut() {
+                                if let Some(task) = project.tasks.iter_mut().find(|t| t.id == task_id) {
+                                    // Only reset if currently Applying
+                                    if status == TaskStatus::Applying {
+                                        task.move_to_review();
+                                        task.session_state = crate::model::ClaudeSessionState::Paused;
+                                    }
+                                }
+                                project.release_main_worktree_lock(task_id);
+                            }
+                            commands.push(Message::Error(
+                                "Rebase failed. Check the Claude session for errors.".to_string()
+                            ));
                         }
-                        // Increment SDK command count for CLI staleness detection
-                        task.sdk_command_count = task.sdk_command_count.saturating_add(1);
-                        if let Some(ref wt) = task.worktree_path {
-                            worktree_display = wt.display().to_string();
+                        Err(e) => {
+                            if let Some(project) = self.model.active_project_mut() {
+                                if let Some(task) = project.tasks.iter_mut().find(|t| t.id == task_id) {
+                                    if status == TaskStatus::Applying {
+                                        task.move_to_review();
+                                    }
+                                }
+                                project.release_main_worktree_lock(task_id);
+                            }
+                            commands.push(Message::Error(format!("Error verifying rebase: {}", e)));
                         }
                     }
                 }
-                if !worktree_display.is_empty() {
-                    commands.push(Message::SetStatusMessage(Some(
-                        format!("Task started via SDK in worktree: {}", worktree_display)
-                    )));
-                }
-            }
-
-            Message::SdkSessionOutput { task_id, output } => {
-                // Store SDK output for display
-                for project in &mut self.model.projects {
-                    if project.tasks.iter().any(|t| t.id == task_id) {
-                        // Append to captured output
-                        project.captured_output.push_str(&output);
-                        break;
-                    }
-                }
             }
 
-            Message::RequestTitleSummary { task_id } => {
-                // Get the task title for summarization and mark as generating
-                let title = self.model.active_project_mut()
-                    .and_then(|p| p.tasks.iter_mut().find(|t| t.id == task_id))
-                    .map(|t| {
-                        t.generating_spec = true;
-                        t.title.clone()
-                    });
+            Message::StartStashConflictSession { task_id, stash_sha } => {
+                // Start a Claude session to resolve stash conflicts in the main worktree
+                let project_info = self.model.active_project()
+                    .map(|p| p.working_dir.clone());
 
-                if let Some(title) = title {
-                    // Show status message
-                    commands.push(Message::SetStatusMessage(Some(
-                        "Generating spec...".to_string()
-                    )));
+                if let Some(project_dir) = project_info {
+                    // Generate the stash conflict prompt
+                    let prompt = crate::worktree::generate_stash_conflict_prompt(&stash_sha);
 
-                    // Spawn the summarization request in background
-                    if let Some(sender) = self.async_sender.clone() {
-                        tokio::spawn(async move {
-                            // Run blocking sidecar call in a separate thread
-                            let result = tokio::task::spawn_blocking(move || {
-                                crate::sidecar::SidecarClient::summarize_title_standalone(task_id, title)
-                            }).await;
+                    // Start session in MAIN worktree (not task worktree)
+                    if let Some(client) = &self.sidecar_client {
+                        match client.start_session(task_id, &project_dir, &prompt, None) {
+                            Ok(session_id) => {
+                                if let Some(project) = self.model.active_project_mut() {
+                                    // Track that we're in conflict resolution mode
+                                    project.applied_task_id = Some(task_id);
+                                    project.applied_with_conflict_resolution = true;
 
-                            let msg = match result {
-                                Ok(Ok((short_title, abbreviation, spec))) => {
-                                    Message::TitleSummaryReceived { task_id, short_title, abbreviation, spec }
-                                }
-                                Ok(Err(e)) => {
-                                    // Log error but don't show to user - summarization is optional
-                                    eprintln!("[Summarization] Failed for task {}: {}", task_id, e);
-                                    // Still send a message to clear the generating flag
-                                    Message::TitleSummaryReceived { task_id, short_title: String::new(), abbreviation: None, spec: None }
+                                    if let Some(task) = project.tasks.iter_mut().find(|t| t.id == task_id) {
+                                        task.status = TaskStatus::Applying;
+                                        task.session_state = crate::model::ClaudeSessionState::Working;
+                                        task.session_mode = crate::model::SessionMode::SdkManaged;
+                                        task.claude_session_id = Some(session_id);
+                                        task.log_activity("Resolving stash conflicts in main worktree...");
+                                    }
                                 }
-                                Err(e) => {
-                                    eprintln!("[Summarization] Task panicked for {}: {}", task_id, e);
-                                    return;
+                                commands.push(Message::SetStatusMessage(Some(
+                                    "Claude is resolving conflicts...".to_string()
+                                )));
+                            }
+                            Err(e) => {
+                                // Release lock on error
+                                if let Some(project) = self.model.active_project_mut() {
+                                    project.release_main_worktree_lock(task_id);
+                                    project.applied_task_id = None;
+                                    project.applied_with_conflict_resolution = false;
                                 }
-                            };
-
-                            let _ = sender.send(msg);
-                        });
+                                commands.push(Message::Error(format!(
+                                    "Failed to start conflict resolution session: {}", e
+                                )));
+                            }
+                        }
+                    } else {
+                        commands.push(Message::Error("Sidecar not connected".to_string()));
                     }
                 }
             }
 
-            Message::TitleSummaryReceived { task_id, short_title, abbreviation, spec } => {
-                // Update the task with the short title, abbreviation, and spec
-                let mut should_start = false;
-                for project in &mut self.model.projects {
-                    if let Some(task) = project.tasks.iter_mut().find(|t| t.id == task_id) {
-                        // Only update if we got a meaningful short title
-                        if !short_title.is_empty() {
-                            task.short_title = Some(short_title);
+            Message::CompleteStashConflictResolution(task_id) => {
+                // Complete stash conflict resolution - check if conflicts are resolved and save combined patch
+                let display_id = self.get_task_display_id(task_id);
+                let project_dir = self.model.active_project()
+                    .map(|p| p.working_dir.clone());
+
+                if let Some(project_dir) = project_dir {
+                    // Check if conflicts are resolved (no conflict markers)
+                    let conflict_check = std::process::Command::new("git")
+                        .current_dir(&project_dir)
+                        .args(["diff", "--check"])
+                        .timed_output();
+
+                    let has_conflicts = match conflict_check {
+                        Ok(output) => !output.status.success(),
+                        Err(_) => true, // Assume conflicts if check fails
+                    };
+
+                    if has_conflicts {
+                        // Conflicts not resolved - return to Review with error
+                        if let Some(project) = self.model.active_project_mut() {
+                            if let Some(task) = project.tasks.iter_mut().find(|t| t.id == task_id) {
+                                task.move_to_review();
+                                task.session_state = crate::model::ClaudeSessionState::Paused;
+                            }
+                            project.release_main_worktree_lock(task_id);
                         }
-                        // Only set abbreviation if not already set (preserves match with branch/worktree names)
-                        if abbreviation.is_some() && task.abbreviation.is_none() {
-                            task.abbreviation = abbreviation;
+                        commands.push(Message::Error(
+                            "Conflict resolution incomplete. Some conflict markers remain.".to_string()
+                        ));
+                    } else {
+                        // Conflicts resolved!
+                        // CRITICAL: Save combined patch for surgical unapply
+                        if let Err(e) = crate::worktree::save_current_changes_as_patch(&project_dir, &display_id) {
+                            // Log warning but don't fail - surgical unapply just won't work
+                            commands.push(Message::SetStatusMessage(Some(
+                                format!("Warning: Could not save patch for surgical unapply: {}", e)
+                            )));
                         }
-                        task.spec = spec;
-                        task.generating_spec = false;
 
-                        // Check if we should auto-start the task
-                        if task.start_after_spec {
-                            task.start_after_spec = false;
-                            should_start = true;
+                        // Proceed with build check
+                        if let Some(project) = self.model.active_project_mut() {
+                            if let Some(task) = project.tasks.iter_mut().find(|t| t.id == task_id) {
+                                task.move_to_review();
+                                task.session_state = crate::model::ClaudeSessionState::Paused;
+                            }
+                            project.release_main_worktree_lock(task_id);
+                            // Keep applied_task_id and applied_with_conflict_resolution set
                         }
-                        break;
+                        commands.push(Message::SetStatusMessage(Some(
+                            "Conflicts resolved. Building...".to_string()
+                        )));
+                        commands.push(Message::RefreshGitStatus);
+                        commands.push(Message::TriggerRestart);
                     }
                 }
+            }
 
-                // Clear the status message
-                commands.push(Message::SetStatusMessage(None));
-
-                // Auto-start the task if it was waiting for spec
-                if should_start {
-                    commands.push(Message::StartTaskWithWorktree(task_id));
+            Message::KeepStashConflictMarkers(task_id) => {
+                // User chose to keep conflict markers for manual resolution
+                if let Some(project) = self.model.active_project_mut() {
+                    project.applied_task_id = Some(task_id);
+                    project.applied_with_conflict_resolution = true; // Track that conflicts exist
+                    project.release_main_worktree_lock(task_id);
                 }
+                self.model.ui_state.pending_confirmation = None;
+                commands.push(Message::SetStatusMessage(Some(
+                    "Conflict markers kept. Resolve manually, then press 'a' to re-apply or 'u' to unapply.".to_string()
+                )));
             }
 
-            Message::OpenInteractiveModal(task_id) => {
-                // Gather task info including SDK command count for staleness check
-                let task_info = self.model.active_project().and_then(|project| {
-                    project.tasks.iter().find(|t| t.id == task_id).map(|task| {
-                        (
-                            task.worktree_path.clone(),
-                            task.claude_session_id.clone(),
-                            task.session_state.clone(),
-                            task.session_mode,
-                            task.sdk_command_count,
-                            task.cli_opened_at_command_count,
-                        )
-                    })
-                });
-
-                if let Some((worktree_path, _session_id, session_state, session_mode, _sdk_count, _cli_opened_at)) = task_info {
-                    // Require worktree path
-                    let Some(_worktree_path) = worktree_path else {
-                        commands.push(Message::Error(
-                            "Cannot open interactive mode: no worktree path.".to_string()
-                        ));
-                        return commands;
-                    };
-
-                    // Check if SDK is actively working - if so, ask for confirmation before interrupting
-                    let sdk_is_working = session_mode == crate::model::SessionMode::SdkManaged
-                        && session_state == crate::model::ClaudeSessionState::Working;
+            Message::StashUserChangesAndApply(task_id) => {
+                // User chose to stash their changes and apply task cleanly
+                let display_id = self.get_task_display_id(task_id);
+                let project_info = self.model.active_project()
+                    .map(|p| (p.working_dir.clone(), p.applied_stash_ref.clone()));
 
-                    if sdk_is_working {
-                        // Show confirmation dialog before interrupting SDK
-                        commands.push(Message::ShowConfirmation {
-                            message: "Claude is working via SDK. Interrupt to open terminal? (y/n)".to_string(),
-                            action: PendingAction::InterruptSdkForCli(task_id),
-                        });
-                        return commands;
+                if let Some((project_dir, stash_ref)) = project_info {
+                    // Abort the stash pop while keeping task changes
+                    match crate::worktree::abort_stash_pop_keep_task_changes(&project_dir, &display_id) {
+                        Ok(()) => {
+                            // The stash already exists from the failed pop - track it
+                            if let Some(ref sha) = stash_ref {
+                                if let Some(project) = self.model.active_project_mut() {
+                                    // Add to tracked stashes with info from the apply stash
+                                    if let Ok((files_changed, files_summary)) =
+                                        crate::worktree::get_stash_details(&project_dir, sha)
+                                    {
+                                        project.tracked_stashes.push(crate::model::TrackedStash {
+                                            stash_ref: "stash@{0}".to_string(),
+                                            description: "Uncommitted changes before task apply".to_string(),
+                                            created_at: chrono::Utc::now(),
+                                            files_changed,
+                                            files_summary,
+                                            stash_sha: sha.clone(),
+                                        });
+                                    }
+                                    project.applied_task_id = Some(task_id);
+                                    project.applied_with_conflict_resolution = false;
+                                    project.release_main_worktree_lock(task_id);
+                                }
+                            }
+                            commands.push(Message::SetStatusMessage(Some(
+                                "Changes stashed. Task applied cleanly. Press 'S' to manage stashes.".to_string()
+                            )));
+                            commands.push(Message::TriggerRestart);
+                        }
+                        Err(e) => {
+                            commands.push(Message::Error(format!(
+                                "Failed to abort stash pop: {}", e
+                            )));
+                        }
                     }
-
-                    // SDK not actively working - proceed with opening CLI
-                    commands.push(Message::DoOpenInteractiveModal(task_id));
                 }
             }
 
-            Message::DoOpenInteractiveModal(task_id) => {
-                // Actually open the interactive modal (after confirmation or if SDK was idle)
-                let task_info = self.model.active_project().and_then(|project| {
-                    project.tasks.iter().find(|t| t.id == task_id).map(|task| {
-                        (
-                            task.worktree_path.clone(),
-                            task.claude_session_id.clone(),
-                            task.sdk_command_count,
-                            task.cli_opened_at_command_count,
-                            task.session_mode.clone(),
-                        )
-                    })
-                });
-
-                if let Some((worktree_path, session_id, sdk_count, cli_opened_at, session_mode)) = task_info {
-                    let Some(worktree_path) = worktree_path else {
-                        return commands;
-                    };
+            Message::ToggleStashModal => {
+                self.model.ui_state.show_stash_modal = !self.model.ui_state.show_stash_modal;
+                if self.model.ui_state.show_stash_modal {
+                    self.model.ui_state.stash_modal_selected_idx = 0;
+                }
+            }
 
-                    // Stop SDK session first (if running) before CLI takeover
-                    if let Some(ref client) = self.sidecar_client {
-                        if let Err(e) = client.stop_session(task_id) {
-                            eprintln!("Note: Could not stop SDK session: {}", e);
-                        }
+            Message::StashModalNavigate(delta) => {
+                if let Some(project) = self.model.active_project() {
+                    let count = project.tracked_stashes.len();
+                    if count > 0 {
+                        let current = self.model.ui_state.stash_modal_selected_idx as i32;
+                        let new_idx = (current + delta).rem_euclid(count as i32) as usize;
+                        self.model.ui_state.stash_modal_selected_idx = new_idx;
                     }
+                }
+            }
 
-                    // Check if existing CLI terminal has stale state (SDK ran commands since it was opened)
-                    let cli_is_stale = sdk_count > cli_opened_at;
-                    let task_id_str = task_id.to_string();
+            Message::PopSelectedStash => {
+                let stash_sha = self.model.active_project()
+                    .and_then(|p| p.tracked_stashes.get(self.model.ui_state.stash_modal_selected_idx))
+                    .map(|s| s.stash_sha.clone());
 
-                    if cli_is_stale {
-                        // Check if CLI is currently working (using session_mode updated by hooks)
-                        if session_mode == crate::model::SessionMode::CliActivelyWorking {
-                            // CLI is actively working - don't interrupt, let user see it
-                            // Just switch to the existing session
-                        } else {
-                            // CLI is idle or not running - safe to kill and restart
-                            if let Err(e) = crate::tmux::kill_claude_cli_session(&task_id_str) {
-                                eprintln!("Note: Could not kill stale CLI session: {}", e);
-                            }
-                        }
-                    }
+                if let Some(sha) = stash_sha {
+                    commands.push(Message::PopTrackedStash { stash_sha: sha });
+                    self.model.ui_state.show_stash_modal = false;
+                }
+            }
 
-                    // Always try to resume if we have a session_id
-                    // This shows conversation history even for completed sessions
-                    let resume_session_id = session_id.as_deref();
-                    let parent_session = crate::tmux::get_current_session_name();
+            Message::DropSelectedStash => {
+                let stash_info = self.model.active_project()
+                    .and_then(|p| p.tracked_stashes.get(self.model.ui_state.stash_modal_selected_idx))
+                    .map(|s| (s.stash_sha.clone(), s.description.clone()));
 
-                    // Open tmux popup with Claude (will create new if killed above, or switch to existing)
-                    if let Err(e) = crate::tmux::open_popup(&worktree_path, resume_session_id, parent_session.as_deref()) {
-                        commands.push(Message::Error(format!(
-                            "Failed to open interactive popup: {}", e
-                        )));
-                        return commands;
-                    }
+                if let Some((sha, desc)) = stash_info {
+                    self.model.ui_state.confirmation_scroll_offset = 0;
+                    self.model.ui_state.pending_confirmation = Some(PendingConfirmation {
+                        message: format!("Delete stash '{}'?\nThis cannot be undone.", desc),
+                        action: PendingAction::PopTrackedStash { stash_sha: sha },
+                        animation_tick: 20,
+                    });
+                    self.model.ui_state.show_stash_modal = false;
+                }
+            }
 
-                    // Update session mode to CLI and record when CLI was opened
-                    if let Some(project) = self.model.active_project_mut() {
-                        if let Some(task) = project.tasks.iter_mut().find(|t| t.id == task_id) {
-                            task.session_mode = crate::model::SessionMode::CliInteractive;
-                            // Record current SDK command count so we can detect future staleness
-                            task.cli_opened_at_command_count = task.sdk_command_count;
-                            task.log_activity("User opened terminal");
+            Message::ConfirmDropStash { stash_sha } => {
+                let project_dir = self.model.active_project()
+                    .map(|p| p.working_dir.clone());
+
+                if let Some(project_dir) = project_dir {
+                    match crate::worktree::drop_tracked_stash(&project_dir, &stash_sha) {
+                        Ok(()) => {
+                            // Remove from tracked stashes
+                            if let Some(project) = self.model.active_project_mut() {
+                                project.tracked_stashes.retain(|s| s.stash_sha != stash_sha);
+                            }
+                            commands.push(Message::SetStatusMessage(Some(
+                                "Stash deleted.".to_string()
+                            )));
+                        }
+                        Err(e) => {
+                            commands.push(Message::Error(format!("Failed to drop stash: {}", e)));
                         }
                     }
                 }
             }
 
-            Message::CloseInteractiveModal => {
-                // Get the task_id before closing the modal
-                if let Some(modal) = &self.model.ui_state.interactive_modal {
-                    let task_id = modal.task_id;
+            Message::OfferPopStash { stash_sha, context } => {
+                // Show confirmation dialog to pop stash
+                self.model.ui_state.confirmation_scroll_offset = 0;
+                self.model.ui_state.pending_confirmation = Some(PendingConfirmation {
+                    message: format!("{}\n\nRestore your stashed changes now? (y/n)", context),
+                    action: PendingAction::PopTrackedStash { stash_sha },
+                    animation_tick: 20,
+                });
+            }
 
-                    // Mark task as waiting for CLI to exit
-                    if let Some(project) = self.model.active_project_mut() {
-                        if let Some(task) = project.tasks.iter_mut().find(|t| t.id == task_id) {
-                            task.session_mode = crate::model::SessionMode::WaitingForCliExit;
+            Message::PopTrackedStash { stash_sha } => {
+                let project_dir = self.model.active_project()
+                    .map(|p| p.working_dir.clone());
+
+                if let Some(project_dir) = project_dir {
+                    match crate::worktree::pop_tracked_stash(&project_dir, &stash_sha) {
+                        Ok(_) => {
+                            // Remove from tracked stashes
+                            if let Some(project) = self.model.active_project_mut() {
+                                project.tracked_stashes.retain(|s| s.stash_sha != stash_sha);
+                            }
+                            commands.push(Message::SetStatusMessage(Some(
+                                "Stashed changes restored.".to_string()
+                            )));
+                        }
+                        Err(e) => {
+                            let err_msg = e.to_string();
+                            if err_msg.starts_with("STASH_CONFLICT:") {
+                                // Stash pop had conflicts
+                                commands.push(Message::HandleStashPopConflict { stash_sha });
+                            } else {
+                                commands.push(Message::Error(format!("Failed to pop stash: {}", e)));
+                            }
                         }
                     }
                 }
-
-                // Close the modal, keep Claude running in background
-                self.model.ui_state.interactive_modal = None;
             }
 
-            Message::CliSessionEnded { task_id } => {
-                // CLI session ended, resume with SDK
-                commands.push(Message::ResumeSdkSession { task_id });
+            Message::HandleStashPopConflict { stash_sha } => {
+                // Stash pop resulted in conflict - offer to resolve with Claude
+                self.model.ui_state.confirmation_scroll_offset = 0;
+                self.model.ui_state.pending_confirmation = Some(PendingConfirmation {
+                    message: "Stash pop resulted in conflicts.\n\nResolve with Claude? (y=resolve, n=abort)".to_string(),
+                    action: PendingAction::StashConflict {
+                        task_id: uuid::Uuid::nil(), // No task involved, just stash conflict
+                        stash_sha,
+                    },
+                    animation_tick: 20,
+                });
             }
 
-            Message::ResumeSdkSession { task_id } => {
-                // Get the session_id and worktree_path from the task first (immutable borrow)
-                let task_info = self.model.active_project().and_then(|project| {
-                    project.tasks.iter().find(|t| t.id == task_id)
-                        .and_then(|task| {
-                            task.claude_session_id.clone().and_then(|sid| {
-                                task.worktree_path.clone().map(|wt| (sid, wt))
-                            })
-                        })
-                });
+            Message::StashThenMerge { task_id } => {
+                // Stash uncommitted changes, track them, then proceed with merge
+                let project_dir = self.model.active_project()
+                    .map(|p| p.working_dir.clone());
 
-                // Resume the SDK session via sidecar
-                if let Some((session_id, worktree_path)) = task_info {
-                    if let Some(ref client) = self.sidecar_client {
-                        match client.resume_session(task_id, &session_id, &worktree_path, None) {
-                            Ok(new_session_id) => {
-                                // Update task with new session ID and mode
-                                if let Some(project) = self.model.active_project_mut() {
-                                    if let Some(task) = project.tasks.iter_mut().find(|t| t.id == task_id) {
-                                        task.claude_session_id = Some(new_session_id);
-                                        task.session_mode = crate::model::SessionMode::SdkManaged;
-                                        // Only set Working state if task is actively being worked on
-                                        // For Review tasks, the session might just be idle - let actual
-                                        // Working/ToolUse events update the state if SDK starts working
-                                        if task.status != TaskStatus::Review && task.status != TaskStatus::Done {
-                                            task.session_state = crate::model::ClaudeSessionState::Working;
-                                        }
-                                        // Increment SDK command count for CLI staleness detection
-                                        task.sdk_command_count = task.sdk_command_count.saturating_add(1);
-                                    }
-                                }
-                                commands.push(Message::SetStatusMessage(Some(
-                                    "SDK session resumed".to_string()
-                                )));
-                            }
-                            Err(e) => {
-                                commands.push(Message::Error(format!("Failed to resume SDK session: {}", e)));
-                                // Fallback: just mark as SDK managed and hope events come in
-                                if let Some(project) = self.model.active_project_mut() {
-                                    if let Some(task) = project.tasks.iter_mut().find(|t| t.id == task_id) {
-                                        task.session_mode = crate::model::SessionMode::SdkManaged;
-                                    }
-                                }
+                if let Some(project_dir) = project_dir {
+                    match crate::worktree::create_tracked_stash(&project_dir, "Uncommitted changes before merge") {
+                        Ok(Some(tracked)) => {
+                            // Track the stash
+                            if let Some(project) = self.model.active_project_mut() {
+                                project.tracked_stashes.push(tracked);
                             }
+                            // Now proceed with the merge
+                            commands.push(Message::AcceptTask(task_id));
+                        }
+                        Ok(None) => {
+                            // Nothing to stash, proceed directly
+                            commands.push(Message::AcceptTask(task_id));
+                        }
+                        Err(e) => {
+                            commands.push(Message::Error(format!(
+                                "Failed to stash changes: {}", e
+                            )));
                         }
-                    } else {
-                        // No sidecar client available
-                        commands.push(Message::Error("Cannot resume: sidecar not connected".to_string()));
                     }
+                }
+            }
+
+            Message::EnterFeedbackMode(task_id) => {
+                // Verify task exists and is in Review or InProgress status
+                let task_status = self.model.active_project().and_then(|project| {
+                    project.tasks.iter().find(|t| t.id == task_id).map(|t| t.status)
+                });
+
+                let can_enter = matches!(task_status, Some(TaskStatus::Review) | Some(TaskStatus::InProgress));
+
+                if can_enter {
+                    let is_live = task_status == Some(TaskStatus::InProgress);
+                    // Enter feedback mode: set the feedback task and focus the input
+                    self.model.ui_state.feedback_task_id = Some(task_id);
+                    self.model.ui_state.focus = crate::model::FocusArea::TaskInput;
+                    self.model.ui_state.clear_input();
+                    // Ensure we're in insert mode for typing
+                    self.model.ui_state.editor_state.mode = edtui::EditorMode::Insert;
+                    let msg = if is_live {
+                        "Enter live feedback (Esc to cancel, Enter to send)"
+                    } else {
+                        "Enter feedback (Esc to cancel, Enter to send)"
+                    };
+                    commands.push(Message::SetStatusMessage(Some(msg.to_string())));
                 } else {
-                    // No session or worktree path to resume
-                    commands.push(Message::Error("Cannot resume: no session ID or worktree path found".to_string()));
+                    commands.push(Message::SetStatusMessage(Some(
+                        "Task must be in Review or InProgress status to send feedback".to_string()
+                    )));
                 }
             }
 
-            Message::StartRebaseSession { task_id } => {
-                // Start an SDK session specifically for rebasing during smart merge
+            Message::CancelFeedbackMode => {
+                if self.model.ui_state.feedback_task_id.is_some() || self.model.ui_state.broadcast_feedback {
+                    self.model.ui_state.feedback_task_id = None;
+                    self.model.ui_state.broadcast_feedback = false;
+                    self.model.ui_state.snippet_picker = None;
+                    self.model.ui_state.clear_input();
+                    self.model.ui_state.focus = crate::model::FocusArea::KanbanBoard;
+                    commands.push(Message::SetStatusMessage(None));
+                }
+            }
+
+            Message::EnterBroadcastMode => {
+                let count = self.model.active_project()
+                    .map(|p| p.tasks_by_status(TaskStatus::InProgress).len())
+                    .unwrap_or(0);
+                if count == 0 {
+                    commands.push(Message::SetStatusMessage(Some(
+                        "No tasks in progress to broadcast to".to_string()
+                    )));
+                    return commands;
+                }
+                self.model.ui_state.feedback_task_id = None;
+                self.model.ui_state.broadcast_feedback = true;
+                self.model.ui_state.focus = crate::model::FocusArea::TaskInput;
+                self.model.ui_state.clear_input();
+                self.model.ui_state.editor_state.mode = edtui::EditorMode::Insert;
+                commands.push(Message::SetStatusMessage(Some(format!(
+                    "Enter feedback for all {} task(s) in progress (Esc to cancel, Enter to review)",
+                    count
+                ))));
+            }
+
+            Message::SendFeedback { task_id, feedback } => {
+                // Always clear feedback mode first, regardless of outcome
+                self.model.ui_state.feedback_task_id = None;
+                self.model.ui_state.clear_input();
+                self.model.ui_state.focus = crate::model::FocusArea::KanbanBoard;
+
+                // Get task info needed for sending feedback
                 let task_info = self.model.active_project().and_then(|project| {
                     project.tasks.iter().find(|t| t.id == task_id).map(|task| {
                         (
-                            task.worktree_path.clone(),
-                            project.working_dir.clone(),
+                            task.session_mode,
+                            task.session_state,
+                            task.status,
                         )
                     })
                 });
 
-                if let Some((Some(worktree_path), project_dir)) = task_info {
-                    // Detect main branch name (master or main)
-                    let main_branch = std::process::Command::new("git")
-                        .current_dir(&project_dir)
-                        .args(["rev-parse", "--abbrev-ref", "HEAD"])
-                        .output()
-                        .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string())
-                        .unwrap_or_else(|_| "master".to_string());
-
-                    // Generate the rebase prompt
-                    let prompt = crate::worktree::generate_rebase_prompt(&main_branch);
+                if let Some((session_mode, session_state, task_status)) = task_info {
+                    // Check if CLI is currently controlling the session
+                    let cli_is_active = matches!(
+                        session_mode,
+                        crate::model::SessionMode::CliInteractive |
+                        crate::model::SessionMode::CliActivelyWorking |
+                        crate::model::SessionMode::WaitingForCliExit
+                    );
 
-                    if let Some(ref client) = self.sidecar_client {
-                        match client.start_session(task_id, &worktree_path, &prompt, None) {
-                            Ok(session_id) => {
-                                // Update task with session ID and Accepting status
-                                if let Some(project) = self.model.active_project_mut() {
-                                    if let Some(task) = project.tasks.iter_mut().find(|t| t.id == task_id) {
-                                        task.claude_session_id = Some(session_id);
-                                        task.status = TaskStatus::Accepting;
-                                        task.session_state = crate::model::ClaudeSessionState::Working;
-                                        task.session_mode = crate::model::SessionMode::SdkManaged;
-                                        // Track when merge started for elapsed time display
-                                        task.accepting_started_at = Some(chrono::Utc::now());
-                                        task.last_activity_at = Some(chrono::Utc::now());
-                                        task.last_tool_name = None;
-                                        // Clear and start activity log
-                                        task.clear_activity_log();
-                                        task.log_activity("Starting smart merge...");
-                                        task.log_activity("Rebasing onto main branch");
-                                    }
-                                }
-                                commands.push(Message::SetStatusMessage(Some(
-                                    "Rebasing onto main... Claude is resolving any conflicts.".to_string()
-                                )));
-                            }
-                            Err(e) => {
-                                commands.push(Message::Error(format!("Failed to start rebase session: {}", e)));
-                                // Reset task to Review status
-                                if let Some(project) = self.model.active_project_mut() {
-                                    if let Some(task) = project.tasks.iter_mut().find(|t| t.id == task_id) {
-                                        task.move_to_review();
-                                    }
-                                }
-                            }
+                    if cli_is_active {
+                        // CLI has control - check session_mode (updated by hooks) to see if working
+                        if session_mode == crate::model::SessionMode::CliActivelyWorking {
+                            // CLI is actively working (hooks told us) - ask user what to do
+                            commands.push(Message::ShowConfirmation {
+                                message: "CLI working. i=interrupt, w=wait (auto-send when ready), o=open CLI, n=cancel".to_string(),
+                                action: PendingAction::InterruptCliForFeedback { task_id, feedback },
+                            });
+                        } else {
+                            // CLI is idle (CliInteractive) or closing (WaitingForCliExit) - kill it and send via SDK
+                            commands.push(Message::DoSendFeedback { task_id, feedback });
+                        }
+                    } else if task_status == TaskStatus::InProgress {
+                        // SDK mode and InProgress - check if SDK is actively working
+                        if session_state == crate::model::ClaudeSessionState::Working {
+                            // SDK is actively working - ask user what to do
+                            commands.push(Message::ShowConfirmation {
+                                message: "SDK working. i=interrupt, w=wait (auto-send when ready), n=cancel".to_string(),
+                                action: PendingAction::InterruptSdkForFeedback { task_id, feedback },
+                            });
+                        } else {
+                            // SDK is idle (WaitingForInput) - send live feedback
+                            commands.push(Message::DoSendFeedback { task_id, feedback });
                         }
                     } else {
-                        commands.push(Message::Error(
-                            "Cannot start rebase: sidecar not connected.".to_string()
-                        ));
+                        // Paused session (Review) with SDK mode - resume with feedback
+                        commands.push(Message::DoSendFeedback { task_id, feedback });
                     }
+                } else {
+                    commands.push(Message::Error("Task not found".to_string()));
                 }
             }
 
-            Message::StartApplySession { task_id } => {
-                // Start an SDK session for rebasing before apply (different from accept)
-                let task_info = self.model.active_project().and_then(|project| {
+            Message::DoSendFeedback { task_id, feedback } => {
+                // Actually send feedback (after confirmation or if CLI was idle).
+                // Queued and chat feedback can be for tasks of other projects.
+                let task_info = self.model.projects.iter().find_map(|project| {
                     project.tasks.iter().find(|t| t.id == task_id).map(|task| {
                         (
+                            task.claude_session_id.clone(),
+                            task.tmux_window.clone(),
                             task.worktree_path.clone(),
-                            project.working_dir.clone(),
+                            project.slug(),
+                            task.status,
+                            task.session_mode.clone(),
                         )
                     })
                 });
 
-                if let Some((Some(worktree_path), project_dir)) = task_info {
-                    // Detect main branch name
-                    let main_branch = std::process::Command::new("git")
-                        .current_dir(&project_dir)
-                        .args(["rev-parse", "--abbrev-ref", "HEAD"])
-                        .output()
-                        .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string())
-                        .unwrap_or_else(|_| "master".to_string());
-
-                    // Generate apply prompt (rebase with apply context)
-                    let prompt = crate::worktree::generate_apply_prompt(&main_branch);
-
-                    if let Some(ref client) = self.sidecar_client {
-                        match client.start_session(task_id, &worktree_path, &prompt, None) {
-                            Ok(session_id) => {
-                                // Update task with session ID and Applying status
-                                if let Some(project) = self.model.active_project_mut() {
-                                    if let Some(task) = project.tasks.iter_mut().find(|t| t.id == task_id) {
-                                        task.claude_session_id = Some(session_id);
-                                        task.status = TaskStatus::Applying;
-                                        task.session_state = crate::model::ClaudeSessionState::Working;
-                                        task.session_mode = crate::model::SessionMode::SdkManaged;
-                                        task.accepting_started_at = Some(chrono::Utc::now());
-                                        task.last_activity_at = Some(chrono::Utc::now());
-                                        task.last_tool_name = None;
-                                        task.clear_activity_log();
-                                        task.log_activity("Starting smart apply...");
-                                        task.log_activity("Rebasing onto main for apply");
-                                    }
-                                }
-                                commands.push(Message::SetStatusMessage(Some(
-                                    "Rebasing for apply... Claude is resolving conflicts.".to_string()
-                                )));
-                            }
-                            Err(e) => {
-                                commands.push(Message::Error(format!("Failed to start apply session: {}", e)));
-                                // Task stays in Review status
-                            }
-                        }
-                    } else {
-                        commands.push(Message::Error(
-                            "Cannot start apply: sidecar not connected.".to_string()
-                        ));
-                    }
-                }
-            }
-
-            Message::CompleteApplyTask(task_id) => {
-                // Complete the apply after rebase - do the actual apply
-                let task_info = self.model.active_project().and_then(|p| {
-                    p.tasks.iter()
-                        .find(|t| t.id == task_id)
-                        .map(|t| (
-                            p.working_dir.clone(),
-                            t.worktree_path.clone(),
-                            t.git_branch.clone(),
-                            t.status,
-                            t.display_id(),
-                        ))
-                });
-
-                if let Some((project_dir, worktree_path, git_branch, status, display_id)) = task_info {
-                    // Check if rebase is still in progress
-                    if let Some(ref wt_path) = worktree_path {
-                        if crate::worktree::is_rebase_in_progress(wt_path) {
-                            commands.push(Message::Error(
-                                "Rebase still in progress. Wait for Claude to finish.".to_string()
-                            ));
-                            return commands;
-                        }
-                    }
-
-                    // Get the branch name (should exist if we got here via rebase)
-                    let branch_name = match git_branch {
-                        Some(ref b) => b.clone(),
-                        None => {
-                            commands.push(Message::Error(
-                                "Task has no git branch reference.".to_string()
-                            ));
-                            return commands;
-                        }
-                    };
+                if let Some((session_id_opt, tmux_window_opt, worktree_path_opt, project_slug, task_status, session_mode)) = task_info {
+                    // Kill any CLI session that might be running
+                    let task_id_str = task_id.to_string();
+                    let _ = crate::tmux::kill_claude_cli_session(&task_id_str);
 
-                    // Verify rebase succeeded
-                    match crate::worktree::verify_rebase_success(&project_dir, &display_id) {
-                        Ok(true) => {
-                            // Rebase successful, now do the apply
-                            match crate::worktree::apply_task_changes(&project_dir, &display_id, &branch_name) {
-                                Ok(stash_warning) => {
-                                    // Apply succeeded - stash was immediately popped
-                                    if let Some(ref warning) = stash_warning {
-                                        if warning.starts_with("STASH_") {
-                                            commands.push(Message::SetStatusMessage(Some(warning.clone())));
-                                        }
-                                    }
-                                    if let Some(project) = self.model.active_project_mut() {
-                                        project.applied_task_id = Some(task_id);
-                                        project.applied_stash_ref = None; // No longer tracked
+                    // Check if CLI had control - if so, we need to resume the SDK session
+                    let cli_had_control = matches!(
+                        session_mode,
+                        crate::model::SessionMode::CliInteractive | crate::model::SessionMode::CliActivelyWorking
+                    );
 
-                                        // Return task to Review status
+                    if task_status == TaskStatus::InProgress && !cli_had_control {
+                        // SDK was in control - send live feedback to active SDK session
+                        if let Some(ref client) = self.sidecar_client {
+                            match client.send_prompt(task_id, &feedback, None) {
+                                Ok(()) => {
+                                    if let Some(project) = self.model.projects.iter_mut().find(|p| p.tasks.iter().any(|t| t.id == task_id)) {
                                         if let Some(task) = project.tasks.iter_mut().find(|t| t.id == task_id) {
-                                            task.move_to_review();
-                                            task.session_state = crate::model::ClaudeSessionState::Paused;
-                                            task.accepting_started_at = None;
+                                            let truncated = if feedback.len() > 50 {
+                                                format!("{}...", &feedback[..50])
+                                            } else {
+                                                feedback.clone()
+                                            };
+                                            task.log_activity(&format!("Live feedback: {}", truncated));
+                                            task.add_feedback(&feedback);
+                                            task.last_activity_at = Some(chrono::Utc::now());
+                                            task.sdk_command_count = task.sdk_command_count.saturating_add(1);
+                                            task.session_mode = crate::model::SessionMode::SdkManaged;
                                         }
                                     }
-
-                                    // Release lock and trigger async build + restart
-                                    // Build check happens async in TriggerRestart - if it fails,
-                                    // user is prompted to unapply
-                                    if let Some(project) = self.model.active_project_mut() {
-                                        project.release_main_worktree_lock(task_id);
-                                    }
                                     commands.push(Message::SetStatusMessage(Some(
-                                        "✓ Changes applied. Building...".to_string()
+                                        "Live feedback sent".to_string()
                                     )));
-                                    commands.push(Message::RefreshGitStatus);
-                                    commands.push(Message::TriggerRestart);
                                 }
                                 Err(e) => {
-                                    let err_msg = e.to_string();
-                                    if let Some(project) = self.model.active_project_mut() {
-                                        if let Some(task) = project.tasks.iter_mut().find(|t| t.id == task_id) {
-                                            task.move_to_review();
-                                            task.session_state = crate::model::ClaudeSessionState::Paused;
-                                        }
-                                        project.release_main_worktree_lock(task_id);
-                                    }
-                                    // Check for "already merged" case
-                                    if err_msg.contains("Nothing to apply") || err_msg.contains("No valid patches") {
-                                        commands.push(Message::Error(
-                                            "Nothing to apply - task changes are already in main. Mark as done with 'm'.".to_string()
-                                        ));
-                                    } else {
-                                        commands.push(Message::Error(format!(
-                                            "Apply failed: {}",
-                                            err_msg
-                                        )));
-                                    }
+                                    commands.push(Message::Error(format!("Failed to send live feedback: {}", e)));
                                 }
                             }
+                        } else {
+                            commands.push(Message::Error("Cannot send feedback: sidecar not connected".to_string()));
                         }
-                        Ok(false) => {
-                            // Rebase failed
-                            if let Some(project) = self.model.active_project_mut() {
-                                if let Some(task) = project.tasks.iter_mut().find(|t| t.id == task_id) {
-                                    // Only reset if currently Applying
-                                    if status == TaskStatus::Applying {
-                                        task.move_to_review();
-                                        task.session_state = crate::model::ClaudeSessionState::Paused;
-                                    }
-                                }
-                                project.release_main_worktree_lock(task_id);
-                            }
-                            commands.push(Message::Error(
-                                "Rebase failed. Check the Claude session for errors.".to_string()
-                            ));
+                    } else {
+                        // Paused session (Review) OR CLI had control - resume SDK with feedback
+                        if let Some(ref window_name) = tmux_window_opt {
+                            let _ = crate::tmux::kill_task_window(&project_slug, window_name);
                         }
-                        Err(e) => {
-                            if let Some(project) = self.model.active_project_mut() {
-                                if let Some(task) = project.tasks.iter_mut().find(|t| t.id == task_id) {
-                                    if status == TaskStatus::Applying {
-                                        task.move_to_review();
+
+                        if let (Some(ref session_id), Some(ref worktree_path)) = (&session_id_opt, &worktree_path_opt) {
+                            if let Some(ref client) = self.sidecar_client {
+                                match client.resume_session(task_id, session_id, worktree_path, Some(&feedback)) {
+                                    Ok(new_session_id) => {
+                                        if let Some(project) = self.model.projects.iter_mut().find(|p| p.tasks.iter().any(|t| t.id == task_id)) {
+                                            if let Some(task) = project.tasks.iter_mut().find(|t| t.id == task_id) {
+                                                task.claude_session_id = Some(new_session_id);
+                                                task.status = TaskStatus::InProgress;
+                                                task.session_state = crate::model::ClaudeSessionState::Working;
+                                                task.session_mode = crate::model::SessionMode::SdkManaged;
+                                                task.last_activity_at = Some(chrono::Utc::now());
+                                                task.sdk_command_count = task.sdk_command_count.saturating_add(1);
+                                                task.tmux_window = None;
+                                                let truncated = if feedback.len() > 50 {
+                                                    format!("{}...", &feedback[..50])
+                                                } else {
+                                                    feedback.clone()
+                                                };
+                                                task.log_activity(&format!("Feedback sent: {}", truncated));
+                                                task.add_feedback(&feedback);
+                                            }
+                                            project.needs_attention = false;
+                                            notify::clear_attention_indicator();
+                                        }
+                                        commands.push(Message::SelectColumn(TaskStatus::InProgress));
+                                        commands.push(Message::SetStatusMessage(Some(
+                                            "Feedback sent - task resumed".to_string()
+                                        )));
+                                    }
+                                    Err(e) => {
+                                        commands.push(Message::Error(format!("Failed to send feedback: {}", e)));
                                     }
                                 }
-                                project.release_main_worktree_lock(task_id);
+                            } else {
+                                commands.push(Message::Error("Cannot send feedback: sidecar not connected".to_string()));
                             }
-                            commands.push(Message::Error(format!("Error verifying rebase: {}", e)));
+                        } else {
+                            let reason = match (&session_id_opt, &worktree_path_opt) {
+                                (None, _) => "no session ID (task has no prior Claude session)",
+                                (_, None) => "no worktree path",
+                                _ => "unknown reason",
+                            };
+                            commands.push(Message::Error(format!("Cannot send feedback: {}", reason)));
                         }
                     }
+                } else {
+                    commands.push(Message::Error("Task not found".to_string()));
                 }
             }
 
-            Message::StartStashConflictSession { task_id, stash_sha } => {
-                // Start a Claude session to resolve stash conflicts in the main worktree
-                let project_info = self.model.active_project()
-                    .map(|p| p.working_dir.clone());
-
-                if let Some(project_dir) = project_info {
-                    // Generate the stash conflict prompt
-                    let prompt = crate::worktree::generate_stash_conflict_prompt(&stash_sha);
-
-                    // Start session in MAIN worktree (not task worktree)
-                    if let Some(client) = &self.sidecar_client {
-                        match client.start_session(task_id, &project_dir, &prompt, None) {
-                            Ok(session_id) => {
-                                if let Some(project) = self.model.active_project_mut() {
-                                    // Track that we're in conflict resolution mode
-                                    project.applied_task_id = Some(task_id);
-                                    project.applied_with_conflict_resolution = true;
+            Message::QueueFeedback { task_id, feedback } => {
+                // Clear the confirmation dialog
+                self.model.ui_state.pending_confirmation = None;
 
-                                    if let Some(task) = project.tasks.iter_mut().find(|t| t.id == task_id) {
-                                        task.status = TaskStatus::Applying;
-                                        task.session_state = crate::model::ClaudeSessionState::Working;
-                                        task.session_mode = crate::model::SessionMode::SdkManaged;
-                                        task.claude_session_id = Some(session_id);
-                                        task.log_activity("Resolving stash conflicts in main worktree...");
-                                    }
-                                }
-                                commands.push(Message::SetStatusMessage(Some(
-                                    "Claude is resolving conflicts...".to_string()
-                                )));
-                            }
-                            Err(e) => {
-                                // Release lock on error
-                                if let Some(project) = self.model.active_project_mut() {
-                                    project.release_main_worktree_lock(task_id);
-                                    project.applied_task_id = None;
-                                    project.applied_with_conflict_resolution = false;
-                                }
-                                commands.push(Message::Error(format!(
-                                    "Failed to start conflict resolution session: {}", e
-                                )));
-                            }
-                        }
+                // Queue feedback to be sent when Claude finishes current work
+                if let Some(project) = self.model.active_project_mut() {
+                    if let Some(task) = project.tasks.iter_mut().find(|t| t.id == task_id) {
+                        task.queued_feedback.push(feedback.clone());
+                        let truncated = if feedback.len() > 30 {
+                            format!("{}...", &feedback[..30])
+                        } else {
+                            feedback
+                        };
+                        commands.push(Message::SetStatusMessage(Some(
+                            format!("Feedback queued: {}", truncated)
+                        )));
                     } else {
-                        commands.push(Message::Error("Sidecar not connected".to_string()));
+                        commands.push(Message::Error("Task not found".to_string()));
                     }
                 }
             }
 
-            Message::CompleteStashConflictResolution(task_id) => {
-                // Complete stash conflict resolution - check if conflicts are resolved and save combined patch
-                let display_id = self.get_task_display_id(task_id);
-                let project_dir = self.model.active_project()
-                    .map(|p| p.working_dir.clone());
+            Message::ConfirmBroadcast(feedback) => {
+                self.model.ui_state.broadcast_feedback = false;
+                self.model.ui_state.clear_input();
+                self.model.ui_state.focus = crate::model::FocusArea::KanbanBoard;
 
-                if let Some(project_dir) = project_dir {
-                    // Check if conflicts are resolved (no conflict markers)
-                    let conflict_check = std::process::Command::new("git")
-                        .current_dir(&project_dir)
-                        .args(["diff", "--check"])
-                        .output();
+                let targets: Vec<(uuid::Uuid, String, bool)> = self.model.active_project()
+                    .map(|p| {
+                        p.tasks_by_status(TaskStatus::InProgress)
+                            .into_iter()
+                            .map(|t| (t.id, broadcast_label(t), t.is_busy()))
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                if targets.is_empty() {
+                    commands.push(Message::SetStatusMessage(Some(
+                        "No tasks in progress to broadcast to".to_string()
+                    )));
+                    return commands;
+                }
 
-                    let has_conflicts = match conflict_check {
-                        Ok(output) => !output.status.success(),
-                        Err(_) => true, // Assume conflicts if check fails
-                    };
+                let mut message = format!(
+                    "=== Broadcast Feedback ===\n\n{}\n\nGoes to these {} task(s) in progress:\n",
+                    feedback,
+                    targets.len()
+                );
+                for (_, label, busy) in &targets {
+                    message.push_str(&format!(
+                        "\n  {}  ({})",
+                        label,
+                        if *busy { "working: queued until it finishes" } else { "sent now" }
+                    ));
+                }
+                message.push_str("\n\n[Y] Send to all  [N] Cancel");
+                commands.push(Message::ShowConfirmation {
+                    message,
+                    action: PendingAction::BroadcastFeedback {
+                        task_ids: targets.into_iter().map(|(id, _, _)| id).collect(),
+                        feedback,
+                    },
+                });
+            }
 
-                    if has_conflicts {
-                        // Conflicts not resolved - return to Review with error
-                        if let Some(project) = self.model.active_project_mut() {
-                            if let Some(task) = project.tasks.iter_mut().find(|t| t.id == task_id) {
-                                task.move_to_review();
-                                task.session_state = crate::model::ClaudeSessionState::Paused;
-                            }
-                            project.release_main_worktree_lock(task_id);
+            Message::BroadcastFeedback { task_ids, feedback } => {
+                let (mut sent, mut queued, mut failed) = (0, 0, 0);
+                let mut report = Vec::new();
+                for task_id in task_ids {
+                    let Some(task) = self.model.active_project_mut()
+                        .and_then(|p| p.tasks.iter_mut().find(|t| t.id == task_id))
+                    else {
+                        continue;
+                    };
+                    let label = broadcast_label(task);
+                    if task.status != TaskStatus::InProgress {
+                        report.push(format!("  {}  skipped: no longer in progress", label));
+                        continue;
+                    }
+                    if task.is_busy() {
+                        // Don't interrupt: it's read once Claude finishes its turn
+                        task.queued_feedback.push(feedback.clone());
+                        task.log_activity("Broadcast feedback queued");
+                        queued += 1;
+                        report.push(format!("  {}  queued until it finishes", label));
+                        continue;
+                    }
+                    let results = self.handle_message(Message::DoSendFeedback {
+                        task_id,
+                        feedback: feedback.clone(),
+                    });
+                    let error = results.iter().find_map(|m| match m {
+                        Message::Error(e) => Some(e.clone()),
+                        _ => None,
+                    });
+                    // Per-task status lines are replaced by the report
+                    commands.extend(results.into_iter().filter(|m| {
+                        !matches!(m, Message::Error(_) | Message::SetStatusMessage(_))
+                    }));
+                    match error {
+                        Some(e) => {
+                            failed += 1;
+                            report.push(format!("  {}  failed: {}", label, e));
                         }
-                        commands.push(Message::Error(
-                            "Conflict resolution incomplete. Some conflict markers remain.".to_string()
-                        ));
-                    } else {
-                        // Conflicts resolved!
-                        // CRITICAL: Save combined patch for surgical unapply
-                        if let Err(e) = crate::worktree::save_current_changes_as_patch(&project_dir, &display_id) {
-                            // Log warning but don't fail - surgical unapply just won't work
-                            commands.push(Message::SetStatusMessage(Some(
-                                format!("Warning: Could not save patch for surgical unapply: {}", e)
-                            )));
+                        None => {
+                            sent += 1;
+                            report.push(format!("  {}  sent", label));
                         }
+                    }
+                }
 
-                        // Proceed with build check
-                        if let Some(project) = self.model.active_project_mut() {
-                            if let Some(task) = project.tasks.iter_mut().find(|t| t.id == task_id) {
-                                task.move_to_review();
-                                task.session_state = crate::model::ClaudeSessionState::Paused;
-                            }
-                            project.release_main_worktree_lock(task_id);
-                            // Keep applied_task_id and applied_with_conflict_resolution set
-                        }
+                let summary = format!("Broadcast: {} sent, {} queued, {} failed", sent, queued, failed);
+                commands.push(Message::SetStatusMessage(Some(summary.clone())));
+                commands.push(Message::ShowConfirmation {
+                    message: format!(
+                        "=== Broadcast Feedback ===\n\n{}\n\n{}\n\n[Y] Close",
+                        summary,
+                        report.join("\n")
+                    ),
+                    action: PendingAction::BroadcastReport,
+                });
+            }
+
+            Message::EnterNoteMode(task_id) => {
+                // Verify task exists
+                let task_exists = self.model.active_project().map(|project| {
+                    project.tasks.iter().any(|t| t.id == task_id)
+                }).unwrap_or(false);
+
+                if task_exists {
+                    // Enter note mode: set the note task and focus the input
+                    self.model.ui_state.note_task_id = Some(task_id);
+                    self.model.ui_state.focus = crate::model::FocusArea::TaskInput;
+                    self.model.ui_state.clear_input();
+                    // Ensure we're in insert mode for typing
+                    self.model.ui_state.editor_state.mode = edtui::EditorMode::Insert;
+                    commands.push(Message::SetStatusMessage(Some(
+                        "Enter note (Esc to cancel, Enter to save)".to_string()
+                    )));
+                } else {
+                    commands.push(Message::SetStatusMessage(Some(
+                        "Task not found".to_string()
+                    )));
+                }
+            }
+
+            Message::CancelNoteMode => {
+                if self.model.ui_state.note_task_id.is_some() {
+                    self.model.ui_state.note_task_id = None;
+                    self.model.ui_state.clear_input();
+                    self.model.ui_state.focus = crate::model::FocusArea::KanbanBoard;
+                    commands.push(Message::SetStatusMessage(None));
+                }
+            }
+
+            Message::AddNote { task_id, note } => {
+                // Clear note mode
+                self.model.ui_state.note_task_id = None;
+                self.model.ui_state.clear_input();
+                self.model.ui_state.focus = crate::model::FocusArea::KanbanBoard;
+
+                // Add the note to the task
+                if let Some(project) = self.model.active_project_mut() {
+                    if let Some(task) = project.tasks.iter_mut().find(|t| t.id == task_id) {
+                        task.notes.push(note);
                         commands.push(Message::SetStatusMessage(Some(
-                            "Conflicts resolved. Building...".to_string()
+                            "Note added".to_string()
                         )));
-                        commands.push(Message::RefreshGitStatus);
-                        commands.push(Message::TriggerRestart);
+                    } else {
+                        commands.push(Message::Error("Task not found".to_string()));
                     }
                 }
             }
 
-            Message::KeepStashConflictMarkers(task_id) => {
-                // User chose to keep conflict markers for manual resolution
-                if let Some(project) = self.model.active_project_mut() {
-                    project.applied_task_id = Some(task_id);
-                    project.applied_with_conflict_resolution = true; // Track that conflicts exist
-                    project.release_main_worktree_lock(task_id);
-                }
-                self.model.ui_state.pending_confirmation = None;
-                commands.push(Message::SetStatusMessage(Some(
-                    "Conflict markers kept. Resolve manually, then press 'a' to re-apply or 'u' to unapply.".to_string()
-                )));
+            Message::StartActivityNote(task_id) => {
+                self.model.ui_state.activity_note = Some(crate::model::ActivityNoteState {
+                    task_id,
+                    input: String::new(),
+                });
             }
 
-            Message::StashUserChangesAndApply(task_id) => {
-                // User chose to stash their changes and apply task cleanly
-                let display_id = self.get_task_display_id(task_id);
-                let project_info = self.model.active_project()
-                    .map(|p| (p.working_dir.clone(), p.applied_stash_ref.clone()));
+            Message::ActivityNoteChanged(input) => {
+                if let Some(ref mut note) = self.model.ui_state.activity_note {
+                    note.input = input;
+                }
+            }
 
-                if let Some((project_dir, stash_ref)) = project_info {
-                    // Abort the stash pop while keeping task changes
-                    match crate::worktree::abort_stash_pop_keep_task_changes(&project_dir, &display_id) {
-                        Ok(()) => {
-                            // The stash already exists from the failed pop - track it
-                            if let Some(ref sha) = stash_ref {
-                                if let Some(project) = self.model.active_project_mut() {
-                                    // Add to tracked stashes with info from the apply stash
-                                    if let Ok((files_changed, files_summary)) =
-                                        crate::worktree::get_stash_details(&project_dir, sha)
-                                    {
-                                        project.tracked_stashes.push(crate::model::TrackedStash {
-                                            stash_ref: "stash@{0}".to_string(),
-                                            description: "Uncommitted changes before task apply".to_string(),
-                                            created_at: chrono::Utc::now(),
-                                            files_changed,
-                                            files_summary,
-                                            stash_sha: sha.clone(),
-                                        });
-                                    }
-                                    project.applied_task_id = Some(task_id);
-                                    project.applied_with_conflict_resolution = false;
-                                    project.release_main_worktree_lock(task_id);
-                                }
-                            }
-                            commands.push(Message::SetStatusMessage(Some(
-                                "Changes stashed. Task applied cleanly. Press 'S' to manage stashes.".to_string()
-                            )));
-                            commands.push(Message::TriggerRestart);
-                        }
-                        Err(e) => {
-                            commands.push(Message::Error(format!(
-                                "Failed to abort stash pop: {}", e
-                            )));
-                        }
-                    }
+            Message::SaveActivityNote => {
+                let Some(note) = self.model.ui_state.activity_note.take() else {
+                    return commands;
+                };
+                let text = note.input.trim();
+                if text.is_empty() {
+                    return commands;
+                }
+                if let Some(task) = self.model.active_project_mut()
+                    .and_then(|p| p.tasks.iter_mut().find(|t| t.id == note.task_id))
+                {
+                    task.activity_notes.push(crate::model::ActivityLogEntry::note(text));
+                    // Follow the log down to the new note
+                    self.model.ui_state.activity_auto_scroll = true;
                 }
             }
 
-            Message::ToggleStashModal => {
-                self.model.ui_state.show_stash_modal = !self.model.ui_state.show_stash_modal;
-                if self.model.ui_state.show_stash_modal {
-                    self.model.ui_state.stash_modal_selected_idx = 0;
+            Message::CancelActivityNote => {
+                self.model.ui_state.activity_note = None;
+            }
+
+            Message::StartDiffComment(task_id) => {
+                let offset = self.model.ui_state.git_diff_scroll_offset;
+                let location = self.model.ui_state.git_diff_cache.current(task_id)
+                    .and_then(|diff| diff.locations(offset, 1).pop().flatten());
+                let Some(location) = location else {
+                    commands.push(Message::SetStatusMessage(Some(
+                        "Scroll a changed or context line to the top of the diff to comment on it".to_string(),
+                    )));
+                    return commands;
+                };
+                // The line's comment, if it hasn't gone to Claude yet, is edited in place
+                let input = self.model.projects.iter()
+                    .flat_map(|p| p.tasks.iter())
+                    .find(|t| t.id == task_id)
+                    .and_then(|t| t.unsent_review_comments().find(|c| c.is_at(&location)))
+                    .map(|c| c.text.clone())
+                    .unwrap_or_default();
+                self.model.ui_state.diff_comment = Some(crate::model::DiffCommentState { task_id, location, input });
+            }
+
+            Message::DiffCommentChanged(input) => {
+                if let Some(ref mut comment) = self.model.ui_state.diff_comment {
+                    comment.input = input;
                 }
             }
 
-            Message::StashModalNavigate(delta) => {
-                if let Some(project) = self.model.active_project() {
-                    let count = project.tracked_stashes.len();
-                    if count > 0 {
-                        let current = self.model.ui_state.stash_modal_selected_idx as i32;
-                        let new_idx = (current + delta).rem_euclid(count as i32) as usize;
-                        self.model.ui_state.stash_modal_selected_idx = new_idx;
-                    }
+            Message::SaveDiffComment => {
+                let Some(comment) = self.model.ui_state.diff_comment.take() else {
+                    return commands;
+                };
+                let Some(task) = self.model.projects.iter_mut()
+                    .flat_map(|p| p.tasks.iter_mut())
+                    .find(|t| t.id == comment.task_id)
+                else {
+                    return commands;
+                };
+                let text = comment.input.trim();
+                let existing = task.review_comments.iter()
+                    .position(|c| c.sent_at.is_none() && c.is_at(&comment.location));
+                match existing {
+                    Some(idx) if text.is_empty() => {
+                        task.review_comments.remove(idx);
+                    }
+                    Some(idx) => task.review_comments[idx].text = text.to_string(),
+                    None if text.is_empty() => {}
+                    None => task.review_comments.push(crate::model::ReviewComment {
+                        path: comment.location.path,
+                        line: comment.location.line,
+                        removed: comment.location.removed,
+                        text: text.to_string(),
+                        created_at: Utc::now(),
+                        sent_at: None,
+                        resolved_at: None,
+                    }),
+                }
+            }
+
+            Message::CancelDiffComment => {
+                self.model.ui_state.diff_comment = None;
+            }
+
+            Message::SendReviewComments(task_id) => {
+                let Some(task) = self.model.projects.iter_mut()
+                    .flat_map(|p| p.tasks.iter_mut())
+                    .find(|t| t.id == task_id)
+                else {
+                    return commands;
+                };
+                let Some(feedback) = task.review_feedback() else {
+                    commands.push(Message::SetStatusMessage(Some(
+                        "No review comments to send (c comments on the line at the top of the diff)".to_string(),
+                    )));
+                    return commands;
+                };
+                if task.worktree_path.is_none()
+                    || !matches!(task.status, TaskStatus::InProgress | TaskStatus::NeedsWork | TaskStatus::Review)
+                {
+                    commands.push(Message::SetStatusMessage(Some(
+                        "Review comments go to tasks with a session (In Progress, Needs Work or Review)".to_string(),
+                    )));
+                    return commands;
                 }
+                let now = Utc::now();
+                let mut sent = 0;
+                for comment in task.review_comments.iter_mut().filter(|c| c.sent_at.is_none()) {
+                    comment.sent_at = Some(now);
+                    sent += 1;
+                }
+                task.log_activity(format!("Sent {} review comment{} to Claude", sent, if sent == 1 { "" } else { "s" }));
+                commands.push(Message::SendFeedback { task_id, feedback });
             }
 
-            Message::PopSelectedStash => {
-                let stash_sha = self.model.active_project()
-                    .and_then(|p| p.tracked_stashes.get(self.model.ui_state.stash_modal_selected_idx))
-                    .map(|s| s.stash_sha.clone());
+            Message::SelectQueuedFeedback { task_id, delta } => {
+                let len = self.model.active_project()
+                    .and_then(|p| p.tasks.iter().find(|t| t.id == task_id))
+                    .map_or(0, |t| t.queued_feedback.len());
+                let selected = self.model.ui_state.queue_selected as i32 + delta;
+                self.model.ui_state.queue_selected = selected.clamp(0, len.saturating_sub(1) as i32) as usize;
+            }
 
-                if let Some(sha) = stash_sha {
-                    commands.push(Message::PopTrackedStash { stash_sha: sha });
-                    self.model.ui_state.show_stash_modal = false;
+            Message::MoveQueuedFeedback { task_id, delta } => {
+                let selected = self.model.ui_state.queue_selected;
+                let Some(task) = self.model.active_project_mut()
+                    .and_then(|p| p.tasks.iter_mut().find(|t| t.id == task_id))
+                else {
+                    return commands;
+                };
+                let target = selected as i32 + delta;
+                if selected < task.queued_feedback.len() && (0..task.queued_feedback.len() as i32).contains(&target) {
+                    task.queued_feedback.swap(selected, target as usize);
+                    self.model.ui_state.queue_selected = target as usize;
                 }
             }
 
-            Message::DropSelectedStash => {
-                let stash_info = self.model.active_project()
-                    .and_then(|p| p.tracked_stashes.get(self.model.ui_state.stash_modal_selected_idx))
-                    .map(|s| (s.stash_sha.clone(), s.description.clone()));
+            Message::DeleteQueuedFeedback(task_id) => {
+                let selected = self.model.ui_state.queue_selected;
+                let Some(task) = self.model.active_project_mut()
+                    .and_then(|p| p.tasks.iter_mut().find(|t| t.id == task_id))
+                else {
+                    return commands;
+                };
+                if selected < task.queued_feedback.len() {
+                    task.queued_feedback.remove(selected);
+                    task.log_activity("Queued feedback removed before delivery");
+                    self.model.ui_state.queue_selected = selected.min(task.queued_feedback.len().saturating_sub(1));
+                    commands.push(Message::SetStatusMessage(Some("Queued feedback removed".to_string())));
+                }
+            }
 
-                if let Some((sha, desc)) = stash_info {
-                    self.model.ui_state.confirmation_scroll_offset = 0;
-                    self.model.ui_state.pending_confirmation = Some(PendingConfirmation {
-                        message: format!("Delete stash '{}'?\nThis cannot be undone.", desc),
-                        action: PendingAction::PopTrackedStash { stash_sha: sha },
-                        animation_tick: 20,
+            Message::StartQueueEdit(task_id) => {
+                let index = self.model.ui_state.queue_selected;
+                let message = self.model.active_project()
+                    .and_then(|p| p.tasks.iter().find(|t| t.id == task_id))
+                    .and_then(|t| t.queued_feedback.get(index).cloned());
+                if let Some(message) = message {
+                    self.model.ui_state.queue_edit = Some(crate::model::QueueEditState {
+                        task_id,
+                        index,
+                        original: message.clone(),
+                        input: message,
                     });
-                    self.model.ui_state.show_stash_modal = false;
                 }
             }
 
-            Message::ConfirmDropStash { stash_sha } => {
-                let project_dir = self.model.active_project()
-                    .map(|p| p.working_dir.clone());
-
-                if let Some(project_dir) = project_dir {
-                    match crate::worktree::drop_tracked_stash(&project_dir, &stash_sha) {
-                        Ok(()) => {
-                            // Remove from tracked stashes
-                            if let Some(project) = self.model.active_project_mut() {
-                                project.tracked_stashes.retain(|s| s.stash_sha != stash_sha);
-                            }
-                            commands.push(Message::SetStatusMessage(Some(
-                                "Stash deleted.".to_string()
-                            )));
-                        }
-                        Err(e) => {
-                            commands.push(Message::Error(format!("Failed to drop stash: {}", e)));
-                        }
-                    }
+            Message::QueueEditChanged(input) => {
+                if let Some(ref mut edit) = self.model.ui_state.queue_edit {
+                    edit.input = input;
                 }
             }
 
-            Message::OfferPopStash { stash_sha, context } => {
-                // Show confirmation dialog to pop stash
-                self.model.ui_state.confirmation_scroll_offset = 0;
-                self.model.ui_state.pending_confirmation = Some(PendingConfirmation {
-                    message: format!("{}\n\nRestore your stashed changes now? (y/n)", context),
-                    action: PendingAction::PopTrackedStash { stash_sha },
-                    animation_tick: 20,
-                });
+            Message::SaveQueueEdit => {
+                let Some(edit) = self.model.ui_state.queue_edit.take() else {
+                    return commands;
+                };
+                let Some(task) = self.model.active_project_mut()
+                    .and_then(|p| p.tasks.iter_mut().find(|t| t.id == edit.task_id))
+                else {
+                    return commands;
+                };
+                // Claude may have finished and taken messages off the front meanwhile
+                let position = if task.queued_feedback.get(edit.index) == Some(&edit.original) {
+                    Some(edit.index)
+                } else {
+                    task.queued_feedback.iter().position(|f| *f == edit.original)
+                };
+                let Some(position) = position else {
+                    commands.push(Message::SetStatusMessage(Some(
+                        "That message was already delivered".to_string()
+                    )));
+                    return commands;
+                };
+                let text = edit.input.trim();
+                if text.is_empty() {
+                    task.queued_feedback.remove(position);
+                    task.log_activity("Queued feedback removed before delivery");
+                    self.model.ui_state.queue_selected = position.min(task.queued_feedback.len().saturating_sub(1));
+                } else {
+                    task.queued_feedback[position] = text.to_string();
+                }
             }
 
-            Message::PopTrackedStash { stash_sha } => {
-                let project_dir = self.model.active_project()
-                    .map(|p| p.working_dir.clone());
+            Message::CancelQueueEdit => {
+                self.model.ui_state.queue_edit = None;
+            }
 
-                if let Some(project_dir) = project_dir {
-                    match crate::worktree::pop_tracked_stash(&project_dir, &stash_sha) {
-                        Ok(_) => {
-                            // Remove from tracked stashes
-                            if let Some(project) = self.model.active_project_mut() {
-                                project.tracked_stashes.retain(|s| s.stash_sha != stash_sha);
-                            }
-                            commands.push(Message::SetStatusMessage(Some(
-                                "Stashed changes restored.".to_string()
-                            )));
-                        }
-                        Err(e) => {
-                            let err_msg = e.to_string();
-                            if err_msg.starts_with("STASH_CONFLICT:") {
-                                // Stash pop had conflicts
-                                commands.push(Message::HandleStashPopConflict { stash_sha });
-                            } else {
-                                commands.push(Message::Error(format!("Failed to pop stash: {}", e)));
-                            }
-                        }
-                    }
-                }
+            Message::StartTimeEntry { task_id, edit_last } => {
+                let task = self.model.active_project()
+                    .and_then(|p| p.tasks.iter().find(|t| t.id == task_id));
+                let Some(task) = task else {
+                    return commands;
+                };
+                let (editing, input) = if edit_last {
+                    let last = task.time_entries.iter()
+                        .rposition(|e| e.source == crate::model::time_tracking::TimeSource::Manual);
+                    let Some(idx) = last else {
+                        commands.push(Message::SetStatusMessage(Some("No manual time entry to edit - press t to add one".to_string())));
+                        return commands;
+                    };
+                    (Some(idx), task.time_entries[idx].to_input())
+                } else {
+                    (None, String::new())
+                };
+                self.model.ui_state.time_entry_input = Some(crate::model::TimeEntryInputState { task_id, editing, input });
             }
 
-            Message::HandleStashPopConflict { stash_sha } => {
-                // Stash pop resulted in conflict - offer to resolve with Claude
-                self.model.ui_state.confirmation_scroll_offset = 0;
-                self.model.ui_state.pending_confirmation = Some(PendingConfirmation {
-                    message: "Stash pop resulted in conflicts.\n\nResolve with Claude? (y=resolve, n=abort)".to_string(),
-                    action: PendingAction::StashConflict {
-                        task_id: uuid::Uuid::nil(), // No task involved, just stash conflict
-                        stash_sha,
-                    },
-                    animation_tick: 20,
-                });
+            Message::TimeEntryInputChanged(input) => {
+                if let Some(ref mut entry) = self.model.ui_state.time_entry_input {
+                    entry.input = input;
+                }
             }
 
-            Message::StashThenMerge { task_id } => {
-                // Stash uncommitted changes, track them, then proceed with merge
-                let project_dir = self.model.active_project()
-                    .map(|p| p.working_dir.clone());
+            Message::SaveTimeEntry => {
+                use crate::model::time_tracking::{self, TimeEntry, TimeSource};
 
-                if let Some(project_dir) = project_dir {
-                    match crate::worktree::create_tracked_stash(&project_dir, "Uncommitted changes before merge") {
-                        Ok(Some(tracked)) => {
-                            // Track the stash
-                            if let Some(project) = self.model.active_project_mut() {
-                                project.tracked_stashes.push(tracked);
-                            }
-                            // Now proceed with the merge
-                            commands.push(Message::AcceptTask(task_id));
-                        }
-                        Ok(None) => {
-                            // Nothing to stash, proceed directly
-                            commands.push(Message::AcceptTask(task_id));
+                let Some(entry) = self.model.ui_state.time_entry_input.take() else {
+                    return commands;
+                };
+                let parsed = time_tracking::parse_manual(&entry.input);
+                let Some(task) = self.model.active_project_mut()
+                    .and_then(|p| p.tasks.iter_mut().find(|t| t.id == entry.task_id))
+                else {
+                    return commands;
+                };
+                match (entry.editing, parsed) {
+                    // Clearing an entry removes it
+                    (Some(idx), None) if entry.input.trim().is_empty() => {
+                        if idx < task.time_entries.len() {
+                            task.time_entries.remove(idx);
                         }
-                        Err(e) => {
-                            commands.push(Message::Error(format!(
-                                "Failed to stash changes: {}", e
-                            )));
+                    }
+                    (_, None) => {
+                        commands.push(Message::Error(format!(
+                            "Couldn't read '{}' - use e.g. 1h30m, 45m or 2h, then an optional note",
+                            entry.input.trim()
+                        )));
+                        self.model.ui_state.time_entry_input = Some(entry);
+                    }
+                    (Some(idx), Some((secs, note))) => {
+                        if let Some(existing) = task.time_entries.get_mut(idx) {
+                            existing.secs = secs;
+                            existing.note = note;
                         }
                     }
+                    (None, Some((secs, note))) => {
+                        task.time_entries.push(TimeEntry::ending_now(secs, TimeSource::Manual, note));
+                    }
                 }
             }
 
-            Message::EnterFeedbackMode(task_id) => {
-                // Verify task exists and is in Review or InProgress status
-                let task_status = self.model.active_project().and_then(|project| {
-                    project.tasks.iter().find(|t| t.id == task_id).map(|t| t.status)
-                });
+            Message::CancelTimeEntry => {
+                self.model.ui_state.time_entry_input = None;
+            }
 
-                let can_enter = matches!(task_status, Some(TaskStatus::Review) | Some(TaskStatus::InProgress));
+            Message::ExportActivity(task_id) => {
+                let task = self.model.projects.iter()
+                    .flat_map(|p| p.tasks.iter())
+                    .find(|t| t.id == task_id);
+                if let Some(task) = task {
+                    match crate::model::activity::export(task) {
+                        Ok(path) => commands.push(Message::SetStatusMessage(Some(
+                            format!("Activity log written to {}", path.display())
+                        ))),
+                        Err(e) => commands.push(Message::Error(format!("Failed to export activity log: {}", e))),
+                    }
+                }
+            }
 
-                if can_enter {
-                    let is_live = task_status == Some(TaskStatus::InProgress);
-                    // Enter feedback mode: set the feedback task and focus the input
-                    self.model.ui_state.feedback_task_id = Some(task_id);
-                    self.model.ui_state.focus = crate::model::FocusArea::TaskInput;
-                    self.model.ui_state.clear_input();
-                    // Ensure we're in insert mode for typing
-                    self.model.ui_state.editor_state.mode = edtui::EditorMode::Insert;
-                    let msg = if is_live {
-                        "Enter live feedback (Esc to cancel, Enter to send)"
-                    } else {
-                        "Enter feedback (Esc to cancel, Enter to send)"
-                    };
-                    commands.push(Message::SetStatusMessage(Some(msg.to_string())));
-                } else {
-                    commands.push(Message::SetStatusMessage(Some(
-                        "Task must be in Review or InProgress status to send feedback".to_string()
-                    )));
+            Message::SnapshotBoard => {
+                let buffer = crate::ui::snapshot::board(self);
+                match crate::ui::snapshot::export("board", &buffer) {
+                    Ok(path) => commands.push(Message::SetStatusMessage(Some(
+                        format!("Board snapshot written to {} (and .ans)", path.display())
+                    ))),
+                    Err(e) => commands.push(Message::Error(format!("Failed to write board snapshot: {}", e))),
                 }
             }
 
-            Message::CancelFeedbackMode => {
-                if self.model.ui_state.feedback_task_id.is_some() {
-                    self.model.ui_state.feedback_task_id = None;
-                    self.model.ui_state.clear_input();
-                    self.model.ui_state.focus = crate::model::FocusArea::KanbanBoard;
-                    commands.push(Message::SetStatusMessage(None));
+            Message::SnapshotDiffSummary(task_id) => {
+                let task = self.model.projects.iter()
+                    .flat_map(|p| p.tasks.iter())
+                    .find(|t| t.id == task_id);
+                if let Some(task) = task {
+                    let diff = self.model.ui_state.git_diff_cache.current(task_id);
+                    let buffer = crate::ui::snapshot::diff_summary(task, diff);
+                    match crate::ui::snapshot::export(&format!("{}-diff", task.display_id()), &buffer) {
+                        Ok(path) => commands.push(Message::SetStatusMessage(Some(
+                            format!("Diff summary written to {} (and .ans)", path.display())
+                        ))),
+                        Err(e) => commands.push(Message::Error(format!("Failed to write diff summary: {}", e))),
+                    }
                 }
             }
 
-            Message::SendFeedback { task_id, feedback } => {
-                // Always clear feedback mode first, regardless of outcome
-                self.model.ui_state.feedback_task_id = None;
-                self.model.ui_state.clear_input();
-                self.model.ui_state.focus = crate::model::FocusArea::KanbanBoard;
+            Message::GenerateHandoff(task_id) => {
+                let target = self.model.projects.iter().find_map(|p| {
+                    let task = p.tasks.iter().find(|t| t.id == task_id)?;
+                    Some((p, task.clone()))
+                });
+                let Some((project, task)) = target else {
+                    return commands;
+                };
+                if task.worktree_path.is_none() {
+                    commands.push(Message::SetStatusMessage(Some(
+                        "No worktree to hand off yet - start the task first".to_string()
+                    )));
+                    return commands;
+                }
+                let Some(sender) = self.async_sender.clone() else {
+                    return commands;
+                };
+                let project_dir = project.working_dir.clone();
+                let ignore = project.status_ignore_patterns();
+                let scope_dir = project.scope_dir();
+                let run_commands = crate::handoff::RunCommands {
+                    check: project.commands.effective_check(&scope_dir),
+                    test: project.commands.effective_test(&scope_dir),
+                    run: project.commands.effective_run(&scope_dir),
+                };
+                commands.push(Message::SetStatusMessage(Some("Compiling handoff document...".to_string())));
+                tokio::spawn(async move {
+                    let result = tokio::task::spawn_blocking(move || {
+                        // Committed and uncommitted changes alike
+                        let file_stats = task.worktree_path.as_ref()
+                            .and_then(|worktree| crate::worktree::get_worktree_diff(&project_dir, worktree, &ignore).ok())
+                            .map(|diff| crate::worktree::diff_cache::CachedDiff::parse(diff).file_stats().to_vec())
+                            .unwrap_or_default();
+                        let doc = crate::handoff::compose(&task, &file_stats, &run_commands);
+                        let written = crate::handoff::write(&task, &doc).map_err(|e| e.to_string());
+                        let copied = crate::image::copy_text_to_clipboard(&doc).map_err(|e| e.to_string());
+                        (written, copied)
+                    }).await;
 
-                // Get task info needed for sending feedback
-                let task_info = self.model.active_project().and_then(|project| {
-                    project.tasks.iter().find(|t| t.id == task_id).map(|task| {
-                        (
-                            task.session_mode,
-                            task.session_state,
-                            task.status,
-                        )
-                    })
+                    let (written, copied) = result.unwrap_or_else(|e| {
+                        (Err(format!("Task panicked: {}", e)), Err("not compiled".to_string()))
+                    });
+                    let _ = sender.send(Message::HandoffGenerated { written, copied });
                 });
+            }
 
-                if let Some((session_mode, session_state, task_status)) = task_info {
-                    // Check if CLI is currently controlling the session
-                    let cli_is_active = matches!(
-                        session_mode,
-                        crate::model::SessionMode::CliInteractive |
-                        crate::model::SessionMode::CliActivelyWorking |
-                        crate::model::SessionMode::WaitingForCliExit
-                    );
+            Message::HandoffGenerated { written, copied } => {
+                match (written, copied) {
+                    (Ok(path), Ok(())) => commands.push(Message::SetStatusMessage(Some(
+                        format!("Handoff written to {} and copied to clipboard", path.display())
+                    ))),
+                    (Ok(path), Err(e)) => commands.push(Message::SetStatusMessage(Some(
+                        format!("Handoff written to {} (clipboard unavailable: {})", path.display(), e)
+                    ))),
+                    (Err(e), Ok(())) => commands.push(Message::SetStatusMessage(Some(
+                        format!("Handoff copied to clipboard (couldn't write {}: {})", crate::handoff::HANDOFF_FILE, e)
+                    ))),
+                    (Err(e), Err(_)) => commands.push(Message::Error(format!("Failed to write handoff document: {}", e))),
+                }
+            }
 
-                    if cli_is_active {
-                        // CLI has control - check session_mode (updated by hooks) to see if working
-                        if session_mode == crate::model::SessionMode::CliActivelyWorking {
-                            // CLI is actively working (hooks told us) - ask user what to do
-                            commands.push(Message::ShowConfirmation {
-                                message: "CLI working. i=interrupt, w=wait (auto-send when ready), o=open CLI, n=cancel".to_string(),
-                                action: PendingAction::InterruptCliForFeedback { task_id, feedback },
-                            });
-                        } else {
-                            // CLI is idle (CliInteractive) or closing (WaitingForCliExit) - kill it and send via SDK
-                            commands.push(Message::DoSendFeedback { task_id, feedback });
-                        }
-                    } else if task_status == TaskStatus::InProgress {
-                        // SDK mode and InProgress - check if SDK is actively working
-                        if session_state == crate::model::ClaudeSessionState::Working {
-                            // SDK is actively working - ask user what to do
-                            commands.push(Message::ShowConfirmation {
-                                message: "SDK working. i=interrupt, w=wait (auto-send when ready), n=cancel".to_string(),
-                                action: PendingAction::InterruptSdkForFeedback { task_id, feedback },
-                            });
-                        } else {
-                            // SDK is idle (WaitingForInput) - send live feedback
-                            commands.push(Message::DoSendFeedback { task_id, feedback });
-                        }
-                    } else {
-                        // Paused session (Review) with SDK mode - resume with feedback
-                        commands.push(Message::DoSendFeedback { task_id, feedback });
-                    }
+            Message::OpenSessionReplay(task_id) => {
+                let has_activity = self.model.projects.iter()
+                    .flat_map(|p| p.tasks.iter())
+                    .find(|t| t.id == task_id)
+                    .is_some_and(|t| !t.activity_timeline().is_empty());
+                if has_activity {
+                    self.model.ui_state.session_replay = Some(crate::model::SessionReplayState {
+                        task_id,
+                        position: 0,
+                        output_scroll: 0,
+                    });
                 } else {
-                    commands.push(Message::Error("Task not found".to_string()));
+                    commands.push(Message::SetStatusMessage(Some("No activity to replay yet".to_string())));
                 }
             }
 
-            Message::DoSendFeedback { task_id, feedback } => {
-                // Actually send feedback (after confirmation or if CLI was idle)
-                let task_info = self.model.active_project().and_then(|project| {
-                    project.tasks.iter().find(|t| t.id == task_id).map(|task| {
-                        (
-                            task.claude_session_id.clone(),
-                            task.tmux_window.clone(),
-                            task.worktree_path.clone(),
-                            project.slug(),
-                            task.status,
-                            task.session_mode.clone(),
-                        )
-                    })
+            Message::ReplayStep(delta) => {
+                let Some(ref mut replay) = self.model.ui_state.session_replay else {
+                    return commands;
+                };
+                let events = self.model.projects.iter()
+                    .flat_map(|p| p.tasks.iter())
+                    .find(|t| t.id == replay.task_id)
+                    .map_or(0, |t| t.activity_timeline().len());
+                replay.position = replay.position
+                    .saturating_add_signed(delta as isize)
+                    .min(events.saturating_sub(1));
+                replay.output_scroll = 0;
+            }
+
+            Message::ScrollReplayOutput(delta) => {
+                // The render function caps it based on the output's length
+                if let Some(ref mut replay) = self.model.ui_state.session_replay {
+                    replay.output_scroll = replay.output_scroll.saturating_add_signed(delta as isize);
+                }
+            }
+
+            Message::CloseSessionReplay => {
+                self.model.ui_state.session_replay = None;
+            }
+
+            Message::CaptureScrollback(task_id) => {
+                let target = self.model.projects.iter().find_map(|p| {
+                    let task = p.tasks.iter().find(|t| t.id == task_id)?;
+                    Some((p.slug(), task.tmux_window.clone(), task.display_id()))
                 });
+                let Some((project_slug, window, display_id)) = target else {
+                    return commands;
+                };
+                // The task's own window if it has one, else its interactive session
+                let target = match window {
+                    Some(window) if crate::tmux::task_window_exists(&project_slug, &window) => {
+                        format!("kc-{}:{}", project_slug, window)
+                    }
+                    _ => display_id.clone(),
+                };
+                let saved = crate::tmux::scrollback::capture(&target)
+                    .and_then(|text| Ok((crate::tmux::scrollback::save(&display_id, &text)?, text)));
+                let (path, text) = match saved {
+                    Ok(saved) => saved,
+                    Err(e) => {
+                        commands.push(Message::SetStatusMessage(Some(format!("No scrollback captured: {}", e))));
+                        return commands;
+                    }
+                };
+                if let Some(task) = self.model.projects.iter_mut()
+                    .flat_map(|p| p.tasks.iter_mut())
+                    .find(|t| t.id == task_id)
+                {
+                    task.log_activity(format!("Captured session scrollback ({} lines) to {}", text.lines().count(), path.display()));
+                    task.scrollback_captures.push(path.clone());
+                }
+                self.model.ui_state.scrollback_viewer = Some(crate::model::ScrollbackViewerState::new(task_id, path, text));
+            }
 
-                if let Some((session_id_opt, tmux_window_opt, worktree_path_opt, project_slug, task_status, session_mode)) = task_info {
-                    // Kill any CLI session that might be running
-                    let task_id_str = task_id.to_string();
-                    let _ = crate::tmux::kill_claude_cli_session(&task_id_str);
+            Message::OpenScrollback(task_id) => {
+                let latest = self.model.projects.iter()
+                    .flat_map(|p| p.tasks.iter())
+                    .find(|t| t.id == task_id)
+                    .and_then(|t| t.scrollback_captures.last().cloned());
+                let Some(path) = latest else {
+                    commands.push(Message::SetStatusMessage(Some("No scrollback captured yet (c to capture)".to_string())));
+                    return commands;
+                };
+                match std::fs::read_to_string(&path) {
+                    Ok(text) => {
+                        self.model.ui_state.scrollback_viewer = Some(crate::model::ScrollbackViewerState::new(task_id, path, text));
+                    }
+                    Err(e) => commands.push(Message::Error(format!("Failed to read {}: {}", path.display(), e))),
+                }
+            }
 
-                    // Check if CLI had control - if so, we need to resume the SDK session
-                    let cli_had_control = matches!(
-                        session_mode,
-                        crate::model::SessionMode::CliInteractive | crate::model::SessionMode::CliActivelyWorking
-                    );
+            Message::CloseScrollback => {
+                self.model.ui_state.scrollback_viewer = None;
+            }
 
-                    if task_status == TaskStatus::InProgress && !cli_had_control {
-                        // SDK was in control - send live feedback to active SDK session
-                        if let Some(ref client) = self.sidecar_client {
-                            match client.send_prompt(task_id, &feedback, None) {
-                                Ok(()) => {
-                                    if let Some(project) = self.model.active_project_mut() {
-                                        if let Some(task) = project.tasks.iter_mut().find(|t| t.id == task_id) {
-                                            let truncated = if feedback.len() > 50 {
-                                                format!("{}...", &feedback[..50])
-                                            } else {
-                                                feedback.clone()
-                                            };
-                                            task.log_activity(&format!("Live feedback: {}", truncated));
-                                            task.add_feedback(&feedback);
-                                            task.last_activity_at = Some(chrono::Utc::now());
-                                            task.sdk_command_count = task.sdk_command_count.saturating_add(1);
-                                            task.session_mode = crate::model::SessionMode::SdkManaged;
-                                        }
-                                    }
-                                    commands.push(Message::SetStatusMessage(Some(
-                                        "Live feedback sent".to_string()
-                                    )));
-                                }
-                                Err(e) => {
-                                    commands.push(Message::Error(format!("Failed to send live feedback: {}", e)));
-                                }
-                            }
-                        } else {
-                            commands.push(Message::Error("Cannot send feedback: sidecar not connected".to_string()));
-                        }
-                    } else {
-                        // Paused session (Review) OR CLI had control - resume SDK with feedback
-                        if let Some(ref window_name) = tmux_window_opt {
-                            let _ = crate::tmux::kill_task_window(&project_slug, window_name);
-                        }
+            Message::ScrollScrollback(delta) => {
+                if let Some(ref mut viewer) = self.model.ui_state.scrollback_viewer {
+                    // Stop once the first line is at the top (the pager takes 90% of the screen)
+                    let page = crossterm::terminal::size()
+                        .map_or(20, |(_, rows)| (rows as usize * 9 / 10).saturating_sub(2));
+                    let line_count = viewer.text.lines().count();
+                    viewer.view.scroll_back = viewer.view.scroll_back
+                        .saturating_add_signed(-(delta as isize))
+                        .min(line_count.saturating_sub(page));
+                }
+            }
 
-                        if let (Some(ref session_id), Some(ref worktree_path)) = (&session_id_opt, &worktree_path_opt) {
-                            if let Some(ref client) = self.sidecar_client {
-                                match client.resume_session(task_id, session_id, worktree_path, Some(&feedback)) {
-                                    Ok(new_session_id) => {
-                                        if let Some(project) = self.model.active_project_mut() {
-                                            if let Some(task) = project.tasks.iter_mut().find(|t| t.id == task_id) {
-                                                task.claude_session_id = Some(new_session_id);
-                                                task.status = TaskStatus::InProgress;
-                                                task.session_state = crate::model::ClaudeSessionState::Working;
-                                                task.session_mode = crate::model::SessionMode::SdkManaged;
-                                                task.last_activity_at = Some(chrono::Utc::now());
-                                                task.sdk_command_count = task.sdk_command_count.saturating_add(1);
-                                                task.tmux_window = None;
-                                                let truncated = if feedback.len() > 50 {
-                                                    format!("{}...", &feedback[..50])
-                                                } else {
-                                                    feedback.clone()
-                                                };
-                                                task.log_activity(&format!("Feedback sent: {}", truncated));
-                                                task.add_feedback(&feedback);
-                                            }
-                                            project.needs_attention = false;
-                                            notify::clear_attention_indicator();
-                                        }
-                                        commands.push(Message::SelectColumn(TaskStatus::InProgress));
-                                        commands.push(Message::SetStatusMessage(Some(
-                                            "Feedback sent - task resumed".to_string()
-                                        )));
-                                    }
-                                    Err(e) => {
-                                        commands.push(Message::Error(format!("Failed to send feedback: {}", e)));
-                                    }
-                                }
-                            } else {
-                                commands.push(Message::Error("Cannot send feedback: sidecar not connected".to_string()));
-                            }
-                        } else {
-                            let reason = match (&session_id_opt, &worktree_path_opt) {
-                                (None, _) => "no session ID (task has no prior Claude session)",
-                                (_, None) => "no worktree path",
-                                _ => "unknown reason",
-                            };
-                            commands.push(Message::Error(format!("Cannot send feedback: {}", reason)));
-                        }
+            Message::StartScrollbackSearch => {
+                if let Some(ref mut viewer) = self.model.ui_state.scrollback_viewer {
+                    viewer.view.search_editing = true;
+                    viewer.view.search.clear();
+                    viewer.view.current_match = None;
+                }
+            }
+
+            Message::ScrollbackSearchInput(query) => {
+                if let Some(ref mut viewer) = self.model.ui_state.scrollback_viewer {
+                    viewer.view.search = query;
+                }
+            }
+
+            Message::ConfirmScrollbackSearch => {
+                if let Some(ref mut viewer) = self.model.ui_state.scrollback_viewer {
+                    viewer.view.search_editing = false;
+                    viewer.view.current_match = None;
+                }
+                // Start from the last match, nearest the end of the session
+                commands.push(Message::ScrollbackNextMatch(-1));
+            }
+
+            Message::ClearScrollbackSearch => {
+                if let Some(ref mut viewer) = self.model.ui_state.scrollback_viewer {
+                    viewer.view.search_editing = false;
+                    viewer.view.search.clear();
+                    viewer.view.current_match = None;
+                }
+            }
+
+            Message::ScrollbackNextMatch(delta) => {
+                let Some(ref mut viewer) = self.model.ui_state.scrollback_viewer else {
+                    return commands;
+                };
+                let matches = viewer.view.matching_lines(&viewer.text);
+                if matches.is_empty() {
+                    if !viewer.view.search.is_empty() {
+                        commands.push(Message::SetStatusMessage(Some(format!("No scrollback matches \"{}\"", viewer.view.search))));
                     }
-                } else {
-                    commands.push(Message::Error("Task not found".to_string()));
+                    return commands;
                 }
+                let current = match viewer.view.current_match {
+                    Some(idx) => idx.saturating_add_signed(delta as isize).min(matches.len() - 1),
+                    None => matches.len() - 1,
+                };
+                // Show the match near the bottom, a few lines of context below it
+                let line_count = viewer.text.lines().count();
+                let end = (matches[current] + 4).min(line_count);
+                viewer.view.scroll_back = line_count - end;
+                viewer.view.current_match = Some(current);
             }
 
-            Message::QueueFeedback { task_id, feedback } => {
-                // Clear the confirmation dialog
-                self.model.ui_state.pending_confirmation = None;
+            Message::ToggleOutputPane => {
+                if self.model.ui_state.output_pane.take().is_none() {
+                    self.model.ui_state.output_pane = Some(crate::model::OutputPaneState::default());
+                } else if self.model.ui_state.focus == crate::model::FocusArea::OutputViewer {
+                    self.model.ui_state.focus = crate::model::FocusArea::KanbanBoard;
+                }
+            }
 
-                // Queue feedback to be sent when Claude finishes current work
-                if let Some(project) = self.model.active_project_mut() {
-                    if let Some(task) = project.tasks.iter_mut().find(|t| t.id == task_id) {
-                        task.pending_feedback = Some(feedback.clone());
-                        let truncated = if feedback.len() > 30 {
-                            format!("{}...", &feedback[..30])
-                        } else {
-                            feedback
-                        };
-                        commands.push(Message::SetStatusMessage(Some(
-                            format!("Feedback queued: {}", truncated)
-                        )));
+            Message::ScrollOutputPane(delta) => {
+                let line_count = self.output_pane_line_count();
+                if let Some(ref mut pane) = self.model.ui_state.output_pane {
+                    // Already at the newest output
+                    if delta > 0 && pane.paused_at.is_none() {
+                        return commands;
+                    }
+                    let held_at = *pane.paused_at.get_or_insert(line_count);
+                    pane.scroll_back = pane.scroll_back
+                        .saturating_add_signed(-delta as isize)
+                        .min(held_at.saturating_sub(1));
+                }
+            }
+
+            Message::ToggleOutputPanePause => {
+                let line_count = self.output_pane_line_count();
+                if let Some(ref mut pane) = self.model.ui_state.output_pane {
+                    if pane.paused_at.is_some() {
+                        pane.paused_at = None;
+                        pane.scroll_back = 0;
                     } else {
-                        commands.push(Message::Error("Task not found".to_string()));
+                        pane.paused_at = Some(line_count);
                     }
                 }
             }
 
-            Message::EnterNoteMode(task_id) => {
-                // Verify task exists
-                let task_exists = self.model.active_project().map(|project| {
-                    project.tasks.iter().any(|t| t.id == task_id)
-                }).unwrap_or(false);
+            Message::StartOutputPaneSearch => {
+                if let Some(ref mut pane) = self.model.ui_state.output_pane {
+                    pane.search_editing = true;
+                    pane.search.clear();
+                    pane.current_match = None;
+                }
+            }
 
-                if task_exists {
-                    // Enter note mode: set the note task and focus the input
-                    self.model.ui_state.note_task_id = Some(task_id);
-                    self.model.ui_state.focus = crate::model::FocusArea::TaskInput;
-                    self.model.ui_state.clear_input();
-                    // Ensure we're in insert mode for typing
-                    self.model.ui_state.editor_state.mode = edtui::EditorMode::Insert;
-                    commands.push(Message::SetStatusMessage(Some(
-                        "Enter note (Esc to cancel, Enter to save)".to_string()
-                    )));
-                } else {
-                    commands.push(Message::SetStatusMessage(Some(
-                        "Task not found".to_string()
-                    )));
+            Message::OutputPaneSearchInput(query) => {
+                if let Some(ref mut pane) = self.model.ui_state.output_pane {
+                    pane.search = query;
                 }
             }
 
-            Message::CancelNoteMode => {
-                if self.model.ui_state.note_task_id.is_some() {
-                    self.model.ui_state.note_task_id = None;
-                    self.model.ui_state.clear_input();
-                    self.model.ui_state.focus = crate::model::FocusArea::KanbanBoard;
-                    commands.push(Message::SetStatusMessage(None));
+            Message::ConfirmOutputPaneSearch => {
+                if let Some(ref mut pane) = self.model.ui_state.output_pane {
+                    pane.search_editing = false;
+                    pane.current_match = None;
                 }
+                // Start from the newest match
+                commands.push(Message::OutputPaneNextMatch(-1));
             }
 
-            Message::AddNote { task_id, note } => {
-                // Clear note mode
-                self.model.ui_state.note_task_id = None;
-                self.model.ui_state.clear_input();
-                self.model.ui_state.focus = crate::model::FocusArea::KanbanBoard;
+            Message::ClearOutputPaneSearch => {
+                if let Some(ref mut pane) = self.model.ui_state.output_pane {
+                    pane.search_editing = false;
+                    pane.search.clear();
+                    pane.current_match = None;
+                }
+            }
 
-                // Add the note to the task
-                if let Some(project) = self.model.active_project_mut() {
-                    if let Some(task) = project.tasks.iter_mut().find(|t| t.id == task_id) {
-                        task.notes.push(note);
-                        commands.push(Message::SetStatusMessage(Some(
-                            "Note added".to_string()
-                        )));
-                    } else {
-                        commands.push(Message::Error("Task not found".to_string()));
+            Message::OutputPaneNextMatch(delta) => {
+                let Some(task) = self.model.output_pane_task() else {
+                    return commands;
+                };
+                let output = task.live_output_text();
+                let line_count = output.lines().count();
+                let Some(ref pane) = self.model.ui_state.output_pane else {
+                    return commands;
+                };
+                let matches = pane.matching_lines(output);
+                if matches.is_empty() {
+                    if !pane.search.is_empty() {
+                        commands.push(Message::SetStatusMessage(Some(format!("No output matches \"{}\"", pane.search))));
                     }
+                    return commands;
+                }
+                let current = match pane.current_match {
+                    Some(idx) => idx.saturating_add_signed(delta as isize).min(matches.len() - 1),
+                    None => matches.len() - 1,
+                };
+                // Hold the view with the match near the bottom, a few lines of context below it
+                if let Some(ref mut pane) = self.model.ui_state.output_pane {
+                    let held_at = *pane.paused_at.get_or_insert(line_count);
+                    let end = (matches[current] + 4).min(held_at);
+                    pane.scroll_back = held_at - end;
+                    pane.current_match = Some(current);
+                }
+            }
+
+            Message::ToggleMonitor => {
+                if self.model.ui_state.focus == FocusArea::Monitor {
+                    self.model.ui_state.focus = FocusArea::KanbanBoard;
+                } else {
+                    self.model.ui_state.focus = FocusArea::Monitor;
+                    // Start on the selected task when it's one of the tiles
+                    let selected = self.model.ui_state.selected_task_id;
+                    self.model.ui_state.monitor.selected = self.model.monitor_tasks().iter()
+                        .position(|(_, t)| Some(t.id) == selected)
+                        .unwrap_or(0);
+                    commands.push(Message::RefreshMonitor);
+                }
+            }
+
+            Message::MonitorMove { dx, dy } => {
+                let tiles = self.model.monitor_tasks().len();
+                if tiles == 0 {
+                    return commands;
+                }
+                let columns = crate::model::MonitorState::columns(tiles) as i32;
+                let target = self.model.ui_state.monitor.selected as i32 + dx + dy * columns;
+                // Rows that don't exist leave the highlight where it is
+                if (0..tiles as i32).contains(&target) {
+                    self.model.ui_state.monitor.selected = target as usize;
+                }
+            }
+
+            Message::MonitorJump | Message::MonitorAttach => {
+                let attach = matches!(msg, Message::MonitorAttach);
+                let tiles = self.model.monitor_tasks();
+                let Some(&(project_idx, task)) = tiles.get(self.model.ui_state.monitor.selected) else {
+                    return commands;
+                };
+                let (task_id, has_window) = (task.id, task.tmux_window.is_some());
+                if attach && !has_window {
+                    commands.push(Message::SetStatusMessage(Some(
+                        "No terminal window for this task - Enter shows it on the board".to_string()
+                    )));
+                    return commands;
+                }
+
+                if project_idx != self.model.active_project_idx {
+                    self.model.active_project_idx = project_idx;
+                    commands.push(Message::RefreshGitStatus);
+                    commands.push(Message::StartGitFetch);
+                    commands.extend(self.release_deferred_starts());
+                }
+                self.model.ui_state.focus = FocusArea::KanbanBoard;
+                self.model.ui_state.selected_column = TaskStatus::InProgress;
+                let idx = self.model.active_project()
+                    .and_then(|p| p.tasks_by_status(TaskStatus::InProgress).iter().position(|t| t.id == task_id));
+                self.select_task(idx);
+                if attach {
+                    commands.push(Message::SwitchToTaskWindow(task_id));
+                }
+            }
+
+            Message::RefreshMonitor => {
+                if self.model.ui_state.monitor.refreshing {
+                    return commands;
                 }
+                let Some(sender) = self.async_sender.clone() else {
+                    return commands;
+                };
+                let windows: Vec<(uuid::Uuid, String, String)> = self.model.projects.iter()
+                    .flat_map(|p| {
+                        p.tasks_by_status(TaskStatus::InProgress).into_iter()
+                            .filter_map(move |t| Some((t.id, p.slug(), t.tmux_window.clone()?)))
+                    })
+                    .collect();
+                self.model.ui_state.monitor.refreshing = true;
+                tokio::spawn(async move {
+                    let captures = tokio::task::spawn_blocking(move || {
+                        windows.into_iter()
+                            .filter_map(|(task_id, slug, window)| {
+                                Some((task_id, crate::tmux::capture_task_tail(&slug, &window, 40)?))
+                            })
+                            .collect()
+                    }).await.unwrap_or_default();
+                    let _ = sender.send(Message::MonitorCapturesLoaded(captures));
+                });
+            }
+
+            Message::MonitorCapturesLoaded(captures) => {
+                // Tasks leave the monitor as they finish
+                let tiles = self.model.monitor_tasks().len();
+                let monitor = &mut self.model.ui_state.monitor;
+                monitor.selected = monitor.selected.min(tiles.saturating_sub(1));
+                monitor.refreshing = false;
+                monitor.refreshed_at = Some(std::time::Instant::now());
+                monitor.captures = captures.into_iter().collect();
             }
 
             Message::StartQaValidation(task_id) => {
@@ -5901,8 +10000,8 @@ Do not ask for permission - run tests and fix any issues you find."#);
                         task.log_activity("QA validation passed");
                         project.move_task_to_start_of_status(task_id, TaskStatus::Review);
                         project.needs_attention = true;
-                        notify::play_attention_sound();
-                        notify::set_attention_indicator(&project.name);
+                        notify::play_task_cue(task_id, notify::SoundCue::Review);
+                        notify::set_task_attention_indicator(task_id, &project.name);
                         break;
                     }
                 }
@@ -5997,8 +10096,8 @@ Do not ask for permission - run tests and fix any issues you find."#);
                         task.log_activity("QA max attempts exceeded - needs manual review");
                         project.move_task_to_start_of_status(task_id, TaskStatus::NeedsWork);
                         project.needs_attention = true;
-                        notify::play_attention_sound();
-                        notify::set_attention_indicator(&project.name);
+                        notify::play_task_cue(task_id, notify::SoundCue::NeedsInput);
+                        notify::set_task_attention_indicator(task_id, &project.name);
                         break;
                     }
                 }
@@ -6022,7 +10121,7 @@ Do not ask for permission - run tests and fix any issues you find."#);
                     let main_branch = std::process::Command::new("git")
                         .current_dir(&project_dir)
                         .args(["rev-parse", "--abbrev-ref", "HEAD"])
-                        .output()
+                        .timed_output()
                         .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string())
                         .unwrap_or_else(|_| "master".to_string());
 
@@ -6069,6 +10168,215 @@ Do not ask for permission - run tests and fix any issues you find."#);
                 }
             }
 
+            Message::CheckWorktreeIntegrity => {
+                let projects: Vec<_> = self.model.projects.iter()
+                    .filter(|p| p.is_git_repo())
+                    .map(|p| {
+                        let tasks: Vec<_> = p.tasks.iter()
+                            .filter(|t| t.status != TaskStatus::Done)
+                            .filter_map(|t| {
+                                let busy = matches!(t.status,
+                                    TaskStatus::Accepting | TaskStatus::Updating | TaskStatus::Applying);
+                                Some((t.id, t.display_id(), t.worktree_path.clone()?, t.git_branch.clone()?, busy))
+                            })
+                            .collect();
+                        (p.working_dir.clone(), tasks)
+                    })
+                    .filter(|(_, tasks)| !tasks.is_empty())
+                    .collect();
+                let Some(sender) = self.async_sender.clone() else {
+                    return commands;
+                };
+                if projects.is_empty() {
+                    return commands;
+                }
+                tokio::spawn(async move {
+                    let issues = tokio::task::spawn_blocking(move || {
+                        use crate::worktree::integrity::{check_worktree, WorktreeIssue};
+                        let mut issues = Vec::new();
+                        for (project_dir, tasks) in projects {
+                            let registered = crate::worktree::git::list_worktrees(&project_dir).unwrap_or_default();
+                            for (task_id, task_label, worktree_path, branch, busy) in tasks {
+                                if let Some(problem) = check_worktree(&project_dir, &worktree_path, &branch, &registered, busy) {
+                                    issues.push(WorktreeIssue {
+                                        task_id,
+                                        task_label,
+                                        project_dir: project_dir.clone(),
+                                        worktree_path,
+                                        branch,
+                                        problem,
+                                    });
+                                }
+                            }
+                        }
+                        issues
+                    }).await.unwrap_or_default();
+                    let _ = sender.send(Message::WorktreeIntegrityChecked(issues));
+                });
+            }
+
+            Message::WorktreeIntegrityChecked(issues) => {
+                if issues.is_empty() {
+                    return commands;
+                }
+                if self.model.ui_state.pending_confirmation.is_some() {
+                    commands.push(Message::Error(format!(
+                        "{} task worktree(s) don't match the board; restart kanblam to reconcile",
+                        issues.len()
+                    )));
+                    return commands;
+                }
+                let mut message = String::from("=== Worktree Check ===\n\nThese task worktrees don't match the board:\n");
+                for issue in &issues {
+                    message.push_str(&format!(
+                        "\n  {}  {}\n      → {}\n",
+                        issue.task_label,
+                        issue.problem.describe(),
+                        issue.problem.fix_label()
+                    ));
+                }
+                message.push_str("\n[Y] Apply these fixes  [N] Leave them");
+                commands.push(Message::ShowConfirmation {
+                    message,
+                    action: PendingAction::ReconcileWorktrees(issues),
+                });
+            }
+
+            Message::WorktreesReconciled(results) => {
+                use crate::worktree::integrity::WorktreeProblem;
+                let mut fixed = 0;
+                for (issue, result) in results {
+                    match result {
+                        Ok(()) => {
+                            fixed += 1;
+                            if issue.problem != (WorktreeProblem::Missing { branch_exists: false }) {
+                                continue;
+                            }
+                            // The task's work is gone: start it over from Planned
+                            if let Some(task) = self.model.projects.iter_mut()
+                                .flat_map(|p| p.tasks.iter_mut())
+                                .find(|t| t.id == issue.task_id)
+                            {
+                                task.status = TaskStatus::Planned;
+                                task.worktree_path = None;
+                                task.git_branch = None;
+                                task.tmux_window = None;
+                                task.claude_session_id = None;
+                                task.session_state = crate::model::ClaudeSessionState::NotStarted;
+                                task.started_at = None;
+                                task.completed_at = None;
+                            }
+                        }
+                        Err(e) => commands.push(Message::Error(format!(
+                            "Couldn't reconcile {}'s worktree: {}", issue.task_label, e
+                        ))),
+                    }
+                }
+                if fixed > 0 {
+                    commands.push(Message::SetStatusMessage(Some(format!(
+                        "Reconciled {} worktree(s)", fixed
+                    ))));
+                }
+                commands.push(Message::RefreshGitStatus);
+            }
+
+            Message::HandOffRebaseConflicts(task_id) => {
+                self.model.ui_state.pending_confirmation = None;
+                let task_info = self.model.active_project().and_then(|project| {
+                    let task = project.tasks.iter().find(|t| t.id == task_id)?;
+                    Some((task.worktree_path.clone()?, project.working_dir.clone()))
+                });
+                let Some((worktree_path, project_dir)) = task_info else {
+                    return commands;
+                };
+                let Some(sender) = self.async_sender.clone() else {
+                    return commands;
+                };
+                commands.push(Message::SetStatusMessage(Some(
+                    "Rebasing up to the conflicts...".to_string()
+                )));
+                tokio::spawn(async move {
+                    let result = tokio::task::spawn_blocking(move || {
+                        crate::worktree::begin_rebase_for_handoff(&worktree_path, &project_dir)
+                            .map_err(|e| e.to_string())
+                    }).await.unwrap_or_else(|e| Err(format!("Task panicked: {}", e)));
+                    let _ = sender.send(Message::RebaseConflictsReady { task_id, result });
+                });
+            }
+
+            Message::RebaseConflictsReady { task_id, result } => {
+                let task_info = self.model.active_project().and_then(|project| {
+                    let task = project.tasks.iter().find(|t| t.id == task_id)?;
+                    Some((task.worktree_path.clone()?, project.working_dir.clone()))
+                });
+                let Some((worktree_path, project_dir)) = task_info else {
+                    return commands;
+                };
+                let conflict = match result {
+                    Ok(conflict) => conflict,
+                    Err(e) => {
+                        if let Some(task) = self.model.active_project_mut()
+                            .and_then(|p| p.tasks.iter_mut().find(|t| t.id == task_id))
+                        {
+                            task.move_to_review();
+                        }
+                        commands.push(Message::Error(format!("Couldn't stop the rebase at its conflicts: {}", e)));
+                        return commands;
+                    }
+                };
+
+                // Detect main branch name (master or main)
+                let main_branch = std::process::Command::new("git")
+                    .current_dir(&project_dir)
+                    .args(["rev-parse", "--abbrev-ref", "HEAD"])
+                    .timed_output()
+                    .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string())
+                    .unwrap_or_else(|_| "master".to_string());
+                let prompt = crate::worktree::generate_conflict_handoff_prompt(&main_branch, &conflict);
+
+                let started = match self.sidecar_client {
+                    Some(ref client) => client.start_session(task_id, &worktree_path, &prompt, None)
+                        .map_err(|e| e.to_string()),
+                    None => Err("sidecar not connected".to_string()),
+                };
+                let Some(task) = self.model.active_project_mut()
+                    .and_then(|p| p.tasks.iter_mut().find(|t| t.id == task_id))
+                else {
+                    return commands;
+                };
+                match started {
+                    Ok(session_id) => {
+                        task.claude_session_id = Some(session_id);
+                        task.status = TaskStatus::Updating;
+                        task.session_state = crate::model::ClaudeSessionState::Working;
+                        task.session_mode = crate::model::SessionMode::SdkManaged;
+                        task.last_activity_at = Some(chrono::Utc::now());
+                        task.last_tool_name = None;
+                        let summary = if conflict.files.is_empty() {
+                            "Conflict hand-off: rebased cleanly, build needs fixing".to_string()
+                        } else {
+                            format!("Conflict hand-off: {} file(s) to resolve ({})", conflict.files.len(), conflict.files.join(", "))
+                        };
+                        let hunks = (!conflict.hunks.is_empty()).then_some(conflict.hunks);
+                        task.log_event(crate::model::ActivityKind::ConflictResolution, summary, hunks);
+                        commands.push(Message::SetStatusMessage(Some(
+                            "Claude is resolving the rebase conflicts...".to_string()
+                        )));
+                    }
+                    Err(e) => {
+                        // Don't leave a half-done rebase behind
+                        if crate::worktree::is_rebase_in_progress(&worktree_path) {
+                            let _ = std::process::Command::new("git")
+                                .current_dir(&worktree_path)
+                                .args(["rebase", "--abort"])
+                                .timed_output();
+                        }
+                        task.move_to_review();
+                        commands.push(Message::Error(format!("Failed to start conflict session: {}", e)));
+                    }
+                }
+            }
+
             Message::CompleteUpdateTask(task_id) => {
                 // Complete an update operation - verify rebase succeeded, then return to Review
                 // Does NOT merge to main and does NOT mark as done!
@@ -6175,9 +10483,192 @@ Do not ask for permission - run tests and fix any issues you find."#);
                         }
                     }
                     Err(e) => {
-                        commands.push(Message::SetStatusMessage(Some(
-                            format!("No image in clipboard: {}", e)
-                        )));
+                        let message = self.capability_banner(&[Capability::Clipboard])
+                            .unwrap_or_else(|| format!("No image in clipboard: {}", e));
+                        commands.push(Message::SetStatusMessage(Some(message)));
+                    }
+                }
+            }
+
+            Message::PasteClipboard => {
+                // Images keep going through PasteImage, which also reports an empty clipboard
+                let text = match crate::image::clipboard_text() {
+                    Ok(text) if !text.is_empty() && !crate::image::clipboard_has_image() => text,
+                    _ => return self.handle_message(Message::PasteImage),
+                };
+                self.model.ui_state.focus = FocusArea::TaskInput;
+                // Copied files (from a file manager) can be attached instead of typed in
+                match crate::image::parse_dropped_paths(&text) {
+                    Some(paths) if self.model.ui_state.note_task_id.is_none() => {
+                        let mut message = format!(
+                            "The clipboard holds {} file{}:\n",
+                            paths.len(),
+                            if paths.len() == 1 { "" } else { "s" }
+                        );
+                        for path in &paths {
+                            message.push_str(&format!("\n  {}", path.display()));
+                        }
+                        message.push_str("\n\n[Y] Attach to the task  [T] Paste as text  [N] Cancel");
+                        commands.push(Message::ShowConfirmation {
+                            message,
+                            action: PendingAction::AttachClipboardFiles { paths, text },
+                        });
+                    }
+                    _ => commands.extend(self.handle_message(Message::PasteText(text))),
+                }
+            }
+
+            Message::PasteText(text) => {
+                let text = crate::image::normalize_paste(&text);
+                if crate::image::is_large_paste(&text) {
+                    let message = format!(
+                        "Paste {} lines ({} characters) into the input?\n\n[Y] Paste  [F] Attach as a text file  [N] Cancel",
+                        text.lines().count(),
+                        text.chars().count()
+                    );
+                    commands.push(Message::ShowConfirmation {
+                        message,
+                        action: PendingAction::LargePaste { text },
+                    });
+                } else {
+                    self.model.ui_state.insert_input_text(&text);
+                }
+            }
+
+            Message::PasteAlternative => {
+                let action = self.model.ui_state.pending_confirmation.as_ref().map(|c| c.action.clone());
+                match action {
+                    Some(PendingAction::LargePaste { text }) => {
+                        match crate::image::store_text_attachment(&text) {
+                            Ok(path) => commands.push(Message::AttachFiles(vec![path])),
+                            Err(e) => commands.push(Message::SetStatusMessage(Some(
+                                format!("Couldn't attach paste: {}", e)
+                            ))),
+                        }
+                    }
+                    Some(PendingAction::AttachClipboardFiles { text, .. }) => {
+                        self.model.ui_state.insert_input_text(&text);
+                    }
+                    // Only the paste confirmations have a second choice
+                    _ => return commands,
+                }
+                self.model.ui_state.pending_confirmation = None;
+            }
+
+            Message::AttachFiles(paths) => {
+                // Store copies, then attach to the task being edited or given feedback,
+                // or hold them for the next created task
+                let target = self.model.ui_state.editing_task_id.or(self.model.ui_state.feedback_task_id);
+                let mut attached = 0;
+                let mut audio = Vec::new();
+                for path in paths {
+                    let stored = match crate::image::store_attachment(&path) {
+                        Ok(stored) => stored,
+                        Err(e) => {
+                            commands.push(Message::SetStatusMessage(Some(format!("Couldn't attach file: {}", e))));
+                            continue;
+                        }
+                    };
+                    let is_image = crate::image::is_image_file(&stored);
+                    // Feedback goes to a running session, so name the file in the message
+                    if self.model.ui_state.feedback_task_id.is_some() && !is_image {
+                        self.model.ui_state.insert_input_text(&format!("{} ", stored.display()));
+                    }
+                    let task = target.and_then(|id| {
+                        self.model.active_project_mut()?.tasks.iter_mut().find(|t| t.id == id)
+                    });
+                    let (images, files) = match task {
+                        Some(task) => (&mut task.images, &mut task.attachments),
+                        None => (&mut self.model.ui_state.pending_images, &mut self.model.ui_state.pending_attachments),
+                    };
+                    if crate::transcribe::is_audio_file(&stored) {
+                        audio.push(stored.clone());
+                    }
+                    if is_image { images.push(stored) } else { files.push(stored) }
+                    attached += 1;
+                }
+                // Audio held for a new task is transcribed once the task exists
+                if let (Some(task_id), false) = (target, audio.is_empty()) {
+                    commands.push(Message::TranscribeAudio { task_id, files: audio });
+                }
+                if attached > 0 {
+                    commands.push(Message::SetStatusMessage(Some(
+                        format!("Attached {} file{}", attached, if attached == 1 { "" } else { "s" })
+                    )));
+                }
+            }
+
+            Message::TranscribeAudio { task_id, files } => {
+                let Some(project) = self.model.active_project_mut() else {
+                    return commands;
+                };
+                let Some(template) = project.transcribe_command.clone() else {
+                    commands.push(Message::SetStatusMessage(Some(
+                        "Audio attached. Set a Transcribe Command in settings to transcribe it".to_string()
+                    )));
+                    return commands;
+                };
+                let project_dir = project.working_dir.clone();
+                let Some(task) = project.tasks.iter_mut().find(|t| t.id == task_id) else {
+                    return commands;
+                };
+                for file in files {
+                    let file_name = file.file_name()
+                        .map(|n| n.to_string_lossy().to_string())
+                        .unwrap_or_default();
+                    task.log_activity(format!("Transcribing {}...", file_name));
+                    if let Some(sender) = self.async_sender.clone() {
+                        let template = template.clone();
+                        let project_dir = project_dir.clone();
+                        tokio::spawn(async move {
+                            let result = tokio::task::spawn_blocking(move || {
+                                crate::transcribe::transcribe(&template, &file, &project_dir)
+                            }).await;
+                            let result = match result {
+                                Ok(Ok(text)) => Ok(text),
+                                Ok(Err(e)) => Err(e.to_string()),
+                                Err(e) => Err(format!("Transcription panicked: {}", e)),
+                            };
+                            let _ = sender.send(Message::TranscriptionFinished { task_id, file_name, result });
+                        });
+                    }
+                }
+            }
+
+            Message::TranscriptionFinished { task_id, file_name, result } => {
+                let Some(task) = self.model.projects.iter_mut()
+                    .flat_map(|p| p.tasks.iter_mut())
+                    .find(|t| t.id == task_id)
+                else {
+                    return commands;
+                };
+                match result {
+                    Ok(text) => {
+                        // The spec goes into the prompt when there is one, otherwise the title does
+                        let section = format!("\n\n[Transcript of {}]\n{}", file_name, text);
+                        match task.spec {
+                            Some(ref mut spec) => spec.push_str(&section),
+                            None => task.title.push_str(&section),
+                        }
+                        task.log_activity(format!("Transcribed {} ({} words)", file_name, text.split_whitespace().count()));
+                    }
+                    Err(e) => {
+                        task.log_activity(format!("Transcription of {} failed: {}", file_name, e));
+                        commands.push(Message::SetStatusMessage(Some(format!("Transcription failed: {}", e))));
+                    }
+                }
+            }
+
+            Message::ShowAttachFilePicker => {
+                use crate::model::MdFilePickerState;
+
+                if let Some(project) = self.model.active_project() {
+                    let files = scan_repo_files(&project.working_dir, None);
+                    if files.is_empty() {
+                        self.model.ui_state.status_message = Some("No files found in repository".to_string());
+                        self.model.ui_state.status_message_decay = 30;
+                    } else {
+                        self.model.ui_state.md_file_picker = Some(MdFilePickerState::for_attachment(files));
                     }
                 }
             }
@@ -6191,52 +10682,29 @@ Do not ask for permission - run tests and fix any issues you find."#);
             }
 
             Message::ClearImages => {
-                // Clear images from the appropriate source based on mode
-                if let Some(task_id) = self.model.ui_state.editing_task_id {
-                    if let Some(project) = self.model.active_project_mut() {
-                        if let Some(task) = project.tasks.iter_mut().find(|t| t.id == task_id) {
-                            let count = task.images.len();
-                            task.images.clear();
-                            if count > 0 {
-                                commands.push(Message::SetStatusMessage(Some(
-                                    format!("Cleared {} image{}", count, if count == 1 { "" } else { "s" })
-                                )));
-                            } else {
-                                commands.push(Message::SetStatusMessage(Some(
-                                    "No images to clear".to_string()
-                                )));
-                            }
-                        }
-                    }
-                } else if let Some(task_id) = self.model.ui_state.feedback_task_id {
-                    if let Some(project) = self.model.active_project_mut() {
-                        if let Some(task) = project.tasks.iter_mut().find(|t| t.id == task_id) {
-                            let count = task.images.len();
+                // Clear images and attached files from the appropriate source based on mode
+                let count = match self.model.ui_state.editing_task_id.or(self.model.ui_state.feedback_task_id) {
+                    Some(task_id) => self.model.active_project_mut()
+                        .and_then(|p| p.tasks.iter_mut().find(|t| t.id == task_id))
+                        .map_or(0, |task| {
+                            let count = task.images.len() + task.attachments.len();
                             task.images.clear();
-                            if count > 0 {
-                                commands.push(Message::SetStatusMessage(Some(
-                                    format!("Cleared {} image{}", count, if count == 1 { "" } else { "s" })
-                                )));
-                            } else {
-                                commands.push(Message::SetStatusMessage(Some(
-                                    "No images to clear".to_string()
-                                )));
-                            }
-                        }
+                            task.attachments.clear();
+                            count
+                        }),
+                    None => {
+                        let ui = &mut self.model.ui_state;
+                        let count = ui.pending_images.len() + ui.pending_attachments.len();
+                        ui.pending_images.clear();
+                        ui.pending_attachments.clear();
+                        count
                     }
+                };
+                commands.push(Message::SetStatusMessage(Some(if count > 0 {
+                    format!("Cleared {} attachment{}", count, if count == 1 { "" } else { "s" })
                 } else {
-                    let count = self.model.ui_state.pending_images.len();
-                    self.model.ui_state.pending_images.clear();
-                    if count > 0 {
-                        commands.push(Message::SetStatusMessage(Some(
-                            format!("Cleared {} image{}", count, if count == 1 { "" } else { "s" })
-                        )));
-                    } else {
-                        commands.push(Message::SetStatusMessage(Some(
-                            "No images to clear".to_string()
-                        )));
-                    }
-                }
+                    "No attachments to clear".to_string()
+                })));
             }
 
             Message::RemoveLastImage => {
@@ -6306,9 +10774,18 @@ Do not ask for permission - run tests and fix any issues you find."#);
             Message::InputSubmit => {
                 // Get text from editor
                 let input = self.model.ui_state.get_input_text().trim().to_string();
+                crate::model::draft::record(&mut self.model.global_settings.input_history, &input);
 
+                // Check if we're in broadcast mode
+                if self.model.ui_state.broadcast_feedback {
+                    if !input.is_empty() {
+                        commands.push(Message::ConfirmBroadcast(input));
+                    } else {
+                        commands.push(Message::CancelFeedbackMode);
+                    }
+                }
                 // Check if we're in feedback mode
-                if let Some(task_id) = self.model.ui_state.feedback_task_id {
+                else if let Some(task_id) = self.model.ui_state.feedback_task_id {
                     if !input.is_empty() {
                         commands.push(Message::SendFeedback { task_id, feedback: input });
                     } else {
@@ -6329,6 +10806,8 @@ Do not ask for permission - run tests and fix any issues you find."#);
                     // Check if we're editing an existing task or creating a new one
                     if let Some(task_id) = self.model.ui_state.editing_task_id {
                         commands.push(Message::UpdateTask { task_id, title: input });
+                    } else if crate::slash::has_commands(&input) {
+                        commands.push(Message::CreateTaskWithCommands { input, start: false });
                     } else {
                         commands.push(Message::CreateTask(input));
                     }
@@ -6338,9 +10817,18 @@ Do not ask for permission - run tests and fix any issues you find."#);
             Message::InputSubmitAndStart => {
                 // Get text from editor
                 let input = self.model.ui_state.get_input_text().trim().to_string();
+                crate::model::draft::record(&mut self.model.global_settings.input_history, &input);
 
+                // Check if we're in broadcast mode - Ctrl+S reviews it same as Enter
+                if self.model.ui_state.broadcast_feedback {
+                    if !input.is_empty() {
+                        commands.push(Message::ConfirmBroadcast(input));
+                    } else {
+                        commands.push(Message::CancelFeedbackMode);
+                    }
+                }
                 // Check if we're in feedback mode - Ctrl+S submits feedback same as Enter
-                if let Some(task_id) = self.model.ui_state.feedback_task_id {
+                else if let Some(task_id) = self.model.ui_state.feedback_task_id {
                     if !input.is_empty() {
                         commands.push(Message::SendFeedback { task_id, feedback: input });
                     } else {
@@ -6373,10 +10861,16 @@ Do not ask for permission - run tests and fix any issues you find."#);
                         }
                     }
                 }
+                // New task with slash commands - parsed, then started
+                else if crate::slash::has_commands(&input) {
+                    commands.push(Message::CreateTaskWithCommands { input, start: true });
+                }
                 // New task creation - create and immediately start
                 else if !input.is_empty() {
-                    // Take pending images before borrowing project
+                    // Take pending images and files before borrowing project
                     let pending_images = std::mem::take(&mut self.model.ui_state.pending_images);
+                    let pending_attachments = std::mem::take(&mut self.model.ui_state.pending_attachments);
+                    let audio = audio_files(&pending_attachments);
                     let title_len = input.len();
 
                     // Check if git repo before mutable borrow
@@ -6387,8 +10881,9 @@ Do not ask for permission - run tests and fix any issues you find."#);
                     if let Some(project) = self.model.active_project_mut() {
                         let mut task = Task::new(input);
                         let task_id = task.id;
-                        // Attach pending images
+                        // Attach pending images and files
                         task.images = pending_images;
+                        task.attachments = pending_attachments;
                         // Insert at beginning so newest tasks appear first in Planned
                         project.tasks.insert(0, task);
 
@@ -6405,6 +10900,9 @@ Do not ask for permission - run tests and fix any issues you find."#);
                         if title_len > 40 {
                             commands.push(Message::RequestTitleSummary { task_id });
                         }
+                        if !audio.is_empty() {
+                            commands.push(Message::TranscribeAudio { task_id, files: audio });
+                        }
 
                         // Immediately start the task (use worktree isolation for git repos)
                         if is_git_repo {
@@ -6450,6 +10948,74 @@ Do not ask for permission - run tests and fix any issues you find."#);
                 }
             }
 
+            Message::InputHistoryOlder | Message::InputHistoryNewer => {
+                use crate::model::draft;
+                let ui = &mut self.model.ui_state;
+                let history = &self.model.global_settings.input_history;
+                let cursor = if matches!(msg, Message::InputHistoryOlder) {
+                    draft::older(history, ui.input_history_cursor)
+                } else {
+                    draft::newer(ui.input_history_cursor)
+                };
+                let text = cursor.and_then(|c| draft::entry(history, c)).unwrap_or_default().to_string();
+                // Recalled text keeps the editor in the mode it was in
+                if ui.editor_state.mode == edtui::EditorMode::Normal {
+                    ui.set_input_text_normal_mode(&text);
+                } else {
+                    ui.set_input_text(&text);
+                }
+                ui.input_history_cursor = cursor;
+            }
+
+            Message::CreateTaskWithCommands { input, start } => {
+                let Some(project_dir) = self.model.active_project().map(|p| p.scope_dir()) else {
+                    return commands;
+                };
+                let parsed = match crate::slash::parse(&input, &crate::slash::template_dirs(&project_dir)) {
+                    Ok(parsed) => parsed,
+                    Err(e) => {
+                        // Leave the input as it is so the command can be fixed
+                        commands.push(Message::SetStatusMessage(Some(e)));
+                        return commands;
+                    }
+                };
+                commands.extend(self.handle_message(Message::CreateTask(parsed.text)));
+
+                let is_git_repo = self.model.active_project().is_some_and(|p| p.is_git_repo());
+                // The new task is the first one of the project
+                let Some(task) = self.model.active_project_mut().and_then(|p| p.tasks.first_mut()) else {
+                    return commands;
+                };
+                for tag in &parsed.tags {
+                    task.add_label(tag);
+                }
+                task.model = parsed.model;
+                task.base_ref = parsed.base;
+                task.fresh_worktree = parsed.fresh;
+                task.env = parsed.env;
+                let task_id = task.id;
+                if start || parsed.start {
+                    if is_git_repo {
+                        commands.push(Message::StartTaskWithWorktree(task_id));
+                    } else {
+                        commands.push(Message::StartTask(task_id));
+                    }
+                }
+            }
+
+            Message::AcceptSlashCompletion => {
+                use edtui::actions::{DeleteChar, Execute};
+                let Some(completion) = self.slash_completions().into_iter().next() else {
+                    return commands;
+                };
+                let before = self.model.ui_state.input_before_cursor();
+                let typed = before.rsplit(char::is_whitespace).next().unwrap_or_default().chars().count();
+                if typed > 0 {
+                    DeleteChar(typed).execute(&mut self.model.ui_state.editor_state);
+                }
+                self.model.ui_state.insert_input_text(&format!("{} ", completion.word));
+            }
+
             Message::FocusChanged(area) => {
                 self.model.ui_state.focus = area;
             }
@@ -6458,6 +11024,7 @@ Do not ask for permission - run tests and fix any issues you find."#);
                 // Clear any edit/feedback/note mode and reset to new task input
                 self.model.ui_state.editing_task_id = None;
                 self.model.ui_state.feedback_task_id = None;
+                self.model.ui_state.broadcast_feedback = false;
                 self.model.ui_state.note_task_id = None;
                 self.model.ui_state.clear_input();
                 self.model.ui_state.focus = FocusArea::TaskInput;
@@ -6472,14 +11039,8 @@ Do not ask for permission - run tests and fix any issues you find."#);
 
                 // Gather info first to avoid borrow issues
                 let current_column = self.model.ui_state.selected_column;
-                // 2x3 grid: Row1 = Planned|InProgress, Row2 = Testing|NeedsWork, Row3 = Review|Done
-                let above_status = match current_column {
-                    TaskStatus::Testing => Some(TaskStatus::Planned),
-                    TaskStatus::NeedsWork => Some(TaskStatus::InProgress),
-                    TaskStatus::Review => Some(TaskStatus::Testing),
-                    TaskStatus::Done => Some(TaskStatus::NeedsWork),
-                    _ => None, // Planned and InProgress have nothing above
-                };
+                // The column above in the layout the board is drawn in
+                let above_status = self.model.ui_state.board_layout.above(current_column);
                 let above_tasks_len = above_status
                     .and_then(|s| self.model.active_project().map(|p| p.tasks_by_status(s).len()))
                     .unwrap_or(0);
@@ -6498,8 +11059,8 @@ Do not ask for permission - run tests and fix any issues you find."#);
                     self.model.ui_state.selected_task_idx = idx;
                 }
 
-                // Check if we're at the top of Planned or InProgress and should move to ProjectTabs
-                let is_top_row = matches!(current_column, TaskStatus::Planned | TaskStatus::InProgress);
+                // Check if we're at the top of a top-row column and should move to ProjectTabs
+                let is_top_row = above_status.is_none();
                 let at_top_of_column = match idx {
                     None => true, // Empty column
                     Some(0) => true, // At first task
@@ -6553,8 +11114,8 @@ Do not ask for permission - run tests and fix any issues you find."#);
                 // Handle ProjectTabs navigation - down returns to KanbanBoard
                 if self.model.ui_state.focus == FocusArea::ProjectTabs {
                     self.model.ui_state.focus = FocusArea::KanbanBoard;
-                    // Ensure we're in one of the top row columns (Planned or InProgress)
-                    if !matches!(self.model.ui_state.selected_column, TaskStatus::Planned | TaskStatus::InProgress) {
+                    // Ensure we're in one of the top row columns (Planned, or InProgress in the grid)
+                    if self.model.ui_state.board_layout.above(self.model.ui_state.selected_column).is_some() {
                         self.model.ui_state.selected_column = TaskStatus::Planned;
                     }
                     // Select the first item in the column
@@ -6576,15 +11137,8 @@ Do not ask for permission - run tests and fix any issues you find."#);
                             Some(i) => (i, false),
                             None => (0, false),
                         };
-                        // 2x3 grid navigation - move down in same column
-                        // Row1 = Planned|InProgress, Row2 = Testing|NeedsWork, Row3 = Review|Done
-                        let below = match self.model.ui_state.selected_column {
-                            TaskStatus::Planned => Some(TaskStatus::Testing),
-                            TaskStatus::InProgress => Some(TaskStatus::NeedsWork),
-                            TaskStatus::Testing => Some(TaskStatus::Review),
-                            TaskStatus::NeedsWork => Some(TaskStatus::Done),
-                            _ => None, // Review and Done have nothing below
-                        };
+                        // The column below in the layout the board is drawn in
+                        let below = self.model.ui_state.board_layout.below(self.model.ui_state.selected_column);
                         let below_len = below
                             .map(|s| project.tasks_by_status(s).len())
                             .unwrap_or(0);
@@ -6739,6 +11293,99 @@ Do not ask for permission - run tests and fix any issues you find."#);
                 }
             }
 
+            Message::ToggleDiagnostics => {
+                self.model.ui_state.show_diagnostics = !self.model.ui_state.show_diagnostics;
+                self.model.ui_state.diagnostics_scroll_offset = 0;
+                self.model.ui_state.diagnostics_slow_ops = false;
+                // Opening or closing the modal acknowledges the problems in the status bar
+                crate::diagnostics::mark_seen();
+            }
+
+            Message::SwitchDiagnosticsPage => {
+                self.model.ui_state.diagnostics_slow_ops = !self.model.ui_state.diagnostics_slow_ops;
+                self.model.ui_state.diagnostics_scroll_offset = 0;
+            }
+
+            Message::CopyDiagnostics => {
+                let problems = crate::diagnostics::problems();
+                let status = if problems.is_empty() {
+                    "No problems to copy".to_string()
+                } else {
+                    match crate::image::copy_text_to_clipboard(&crate::diagnostics::to_text(&problems)) {
+                        Ok(()) => format!("Copied {} problem(s) to clipboard", problems.len()),
+                        Err(e) => format!("Failed to copy: {}", e),
+                    }
+                };
+                commands.push(Message::SetStatusMessage(Some(status)));
+            }
+
+            Message::ClearDiagnostics => {
+                crate::diagnostics::clear();
+                self.model.ui_state.diagnostics_scroll_offset = 0;
+            }
+
+            Message::ToggleLogViewer => {
+                self.model.ui_state.log_viewer = match self.model.ui_state.log_viewer {
+                    Some(_) => None,
+                    None => Some(crate::model::LogViewerState::default()),
+                };
+            }
+
+            Message::ScrollLogViewer(delta) => {
+                if let Some(ref mut viewer) = self.model.ui_state.log_viewer {
+                    // Scrolling back pauses, so new lines don't move what's being read
+                    if delta > 0 && viewer.until.is_none() {
+                        viewer.until = crate::logging::latest_seq();
+                    }
+                    // The render function caps it based on actual content
+                    viewer.scroll = viewer.scroll.saturating_add_signed(delta as isize);
+                }
+            }
+
+            Message::CycleLogLevel => {
+                use tracing::Level;
+                if let Some(ref mut viewer) = self.model.ui_state.log_viewer {
+                    viewer.min_level = match viewer.min_level {
+                        Level::TRACE => Level::DEBUG,
+                        Level::DEBUG => Level::INFO,
+                        Level::INFO => Level::WARN,
+                        Level::WARN => Level::ERROR,
+                        _ => Level::TRACE,
+                    };
+                    viewer.scroll = 0;
+                }
+            }
+
+            Message::CycleLogTarget => {
+                if let Some(ref mut viewer) = self.model.ui_state.log_viewer {
+                    let targets = crate::logging::targets();
+                    viewer.target = match viewer.target {
+                        None => targets.first().cloned(),
+                        Some(ref current) => targets.iter()
+                            .skip_while(|t| *t != current)
+                            .nth(1)
+                            .cloned(),
+                    };
+                    viewer.scroll = 0;
+                }
+            }
+
+            Message::ToggleLogFollow => {
+                if let Some(ref mut viewer) = self.model.ui_state.log_viewer {
+                    viewer.until = match viewer.until {
+                        Some(_) => None,
+                        None => crate::logging::latest_seq(),
+                    };
+                    viewer.scroll = 0;
+                }
+            }
+
+            Message::ScrollDiagnostics(delta) => {
+                // The render function caps it based on actual content
+                let offset = &mut self.model.ui_state.diagnostics_scroll_offset;
+                *offset = offset.saturating_add_signed(delta as isize).min(200);
+            }
+
             Message::ScrollHelpUp(lines) => {
                 self.model.ui_state.help_scroll_offset =
                     self.model.ui_state.help_scroll_offset.saturating_sub(lines);
@@ -6774,8 +11421,24 @@ Do not ask for permission - run tests and fix any issues you find."#);
                     .min(MAX_STATS_SCROLL);
             }
 
+            Message::ToggleMarkdownPreview => {
+                let ui = &mut self.model.ui_state;
+                ui.markdown_preview = !ui.markdown_preview;
+                ui.status_message = Some(if ui.markdown_preview {
+                    "Markdown preview on".to_string()
+                } else {
+                    "Markdown preview off".to_string()
+                });
+            }
+
             Message::ToggleTaskPreview => {
                 self.model.ui_state.show_task_preview = !self.model.ui_state.show_task_preview;
+                self.model.ui_state.activity_note = None;
+                self.model.ui_state.diff_comment = None;
+                self.model.ui_state.time_entry_input = None;
+                self.model.ui_state.queue_edit = None;
+                self.model.ui_state.queue_selected = 0;
+                self.model.ui_state.session_replay = None;
                 // Reset to general tab and scroll position when opening the modal
                 if self.model.ui_state.show_task_preview {
                     self.model.ui_state.task_detail_tab = crate::model::TaskDetailTab::default();
@@ -6801,17 +11464,10 @@ Do not ask for permission - run tests and fix any issues you find."#);
                     self.model.ui_state.activity_auto_scroll = true;
                 }
 
-                // Load git diff when switching to Git tab
+                // Load git diff when switching to Git tab (served from cache if the branch hasn't moved)
                 if new_tab == crate::model::TaskDetailTab::Git {
                     if let Some(task_id) = self.model.ui_state.selected_task_id {
-                        // Check if we need to load the diff (not cached for this task)
-                        let needs_load = self.model.ui_state.git_diff_cache
-                            .as_ref()
-                            .map(|(id, _)| *id != task_id)
-                            .unwrap_or(true);
-                        if needs_load {
-                            return vec![Message::LoadGitDiff(task_id)];
-                        }
+                        return vec![Message::LoadGitDiff(task_id)];
                     }
                 }
             }
@@ -6830,17 +11486,10 @@ Do not ask for permission - run tests and fix any issues you find."#);
                     self.model.ui_state.activity_auto_scroll = true;
                 }
 
-                // Load git diff when switching to Git tab
+                // Load git diff when switching to Git tab (served from cache if the branch hasn't moved)
                 if new_tab == crate::model::TaskDetailTab::Git {
                     if let Some(task_id) = self.model.ui_state.selected_task_id {
-                        // Check if we need to load the diff (not cached for this task)
-                        let needs_load = self.model.ui_state.git_diff_cache
-                            .as_ref()
-                            .map(|(id, _)| *id != task_id)
-                            .unwrap_or(true);
-                        if needs_load {
-                            return vec![Message::LoadGitDiff(task_id)];
-                        }
+                        return vec![Message::LoadGitDiff(task_id)];
                     }
                 }
             }
@@ -6851,12 +11500,21 @@ Do not ask for permission - run tests and fix any issues you find."#);
             }
 
             Message::ScrollGitDiffDown(lines) => {
-                // Get the number of lines in the cached diff to cap scrolling
-                let max_lines = self.model.ui_state.git_diff_cache
-                    .as_ref()
-                    .map(|(_, diff)| diff.lines().count())
-                    .unwrap_or(0);
-                let max_scroll = max_lines.saturating_sub(10); // Leave some visible lines
+                // Get the number of lines in the cached diff (or history) to cap scrolling
+                let max_lines = if self.model.ui_state.git_history_view {
+                    // Summary and blank line, then per file its heading, blame and blank lines plus its commits
+                    match &self.model.ui_state.git_history {
+                        Some((_, Some(Ok(history)))) => 2 + history.files.iter().map(|f| f.commits.len() + 3).sum::<usize>(),
+                        _ => 0,
+                    }
+                } else {
+                    self.model.ui_state.selected_task_id
+                        .and_then(|id| self.model.ui_state.git_diff_cache.current(id))
+                        .map(|diff| diff.total_lines())
+                        .unwrap_or(0)
+                };
+                // Any line can be scrolled to the top, where review comments attach
+                let max_scroll = max_lines.saturating_sub(1);
                 self.model.ui_state.git_diff_scroll_offset = self
                     .model
                     .ui_state
@@ -6868,21 +11526,215 @@ Do not ask for permission - run tests and fix any issues you find."#);
             Message::LoadGitDiff(task_id) => {
                 // Reset scroll when loading new diff
                 self.model.ui_state.git_diff_scroll_offset = 0;
+                if self.model.ui_state.git_history_view {
+                    commands.push(Message::LoadGitHistory(task_id));
+                }
 
-                // Load the diff for this task
+                // Reuse the cached diff unless the task branch has moved
                 let display_id = self.get_task_display_id(task_id);
-                if let Some(project) = self.model.active_project() {
-                    match crate::worktree::get_task_diff(&project.working_dir, &display_id) {
-                        Ok(diff) => {
-                            self.model.ui_state.git_diff_cache = Some((task_id, diff));
-                        }
-                        Err(e) => {
-                            // Store empty diff with error message
-                            self.model.ui_state.git_diff_cache = Some((
-                                task_id,
-                                format!("Error loading diff: {}", e),
-                            ));
-                        }
+                let Some((project_dir, ignore)) = self.model.active_project()
+                    .map(|p| (p.working_dir.clone(), p.status_ignore_patterns()))
+                else {
+                    return commands;
+                };
+                let head = crate::worktree::branch_head(&project_dir, &display_id).unwrap_or_default();
+                let key = crate::worktree::diff_cache::DiffKey { task_id, head };
+                if self.model.ui_state.git_diff_cache.select(&key) {
+                    return commands;
+                }
+
+                // Large diffs take a while - fetch off the UI thread ("Loading diff..." meanwhile)
+                if let Some(sender) = self.async_sender.clone() {
+                    tokio::spawn(async move {
+                        let result = tokio::task::spawn_blocking(move || {
+                            crate::worktree::get_task_diff(&project_dir, &display_id, &ignore)
+                        }).await;
+
+                        let result = match result {
+                            Ok(Ok(diff)) => Ok(diff),
+                            Ok(Err(e)) => Err(e.to_string()),
+                            Err(e) => Err(format!("Task panicked: {}", e)),
+                        };
+                        let _ = sender.send(Message::GitDiffLoaded { key, result });
+                    });
+                }
+            }
+
+            Message::SummarizeChanges(task_id) => {
+                let display_id = self.get_task_display_id(task_id);
+                let Some((project_dir, task)) = self.model.projects.iter_mut().find_map(|p| {
+                    let dir = p.working_dir.clone();
+                    Some((dir, p.tasks.iter_mut().find(|t| t.id == task_id)?))
+                }) else {
+                    return commands;
+                };
+                if task.summarizing_changes || task.git_branch.is_none() {
+                    return commands;
+                }
+                if self.sidecar_client.is_none() {
+                    commands.push(Message::Error("Cannot summarize changes: sidecar not connected".to_string()));
+                    return commands;
+                }
+                let Some(sender) = self.async_sender.clone() else {
+                    return commands;
+                };
+                task.summarizing_changes = true;
+                let brief = task.session_prompt();
+                let cwd = task.worktree_path.clone().unwrap_or_else(|| project_dir.clone());
+                // Unfiltered diff: lockfiles and generated code are part of the risk
+                tokio::spawn(async move {
+                    let result = tokio::task::spawn_blocking(move || {
+                        let head = crate::worktree::branch_head(&project_dir, &display_id).unwrap_or_default();
+                        let diff = crate::worktree::get_task_diff(&project_dir, &display_id, &[])
+                            .map_err(|e| e.to_string())?;
+                        if diff.trim().is_empty() {
+                            return Err("the branch has no changes".to_string());
+                        }
+                        let prompt = crate::model::ChangeSummary::prompt(&brief, &diff);
+                        crate::sidecar::SidecarClient::complete_prompt_standalone(prompt, Some(cwd))
+                            .map(|text| (head, text))
+                            .map_err(|e| e.to_string())
+                    }).await.unwrap_or_else(|e| Err(format!("Task panicked: {}", e)));
+                    let (head, result) = match result {
+                        Ok((head, text)) => (head, Ok(text)),
+                        Err(e) => (String::new(), Err(e)),
+                    };
+                    let _ = sender.send(Message::ChangesSummarized { task_id, head, result });
+                });
+            }
+
+            Message::ChangesSummarized { task_id, head, result } => {
+                let Some(task) = self.model.projects.iter_mut()
+                    .flat_map(|p| p.tasks.iter_mut())
+                    .find(|t| t.id == task_id)
+                else {
+                    return commands;
+                };
+                task.summarizing_changes = false;
+                match result {
+                    Ok(text) if !text.trim().is_empty() => {
+                        task.change_summary = Some(crate::model::ChangeSummary {
+                            text: text.trim().to_string(),
+                            head,
+                            created_at: Utc::now(),
+                        });
+                        task.log_activity("Summarized the changes for review");
+                        commands.push(Message::SetStatusMessage(Some("Change summary ready".to_string())));
+                    }
+                    Ok(_) => {
+                        commands.push(Message::SetStatusMessage(Some("The change summary came back empty".to_string())));
+                    }
+                    Err(e) => {
+                        commands.push(Message::SetStatusMessage(Some(format!("Could not summarize the changes: {}", e))));
+                    }
+                }
+            }
+
+            Message::ToggleGitHistory(task_id) => {
+                let ui = &mut self.model.ui_state;
+                ui.git_history_view = !ui.git_history_view;
+                ui.git_diff_scroll_offset = 0;
+                if ui.git_history_view {
+                    commands.push(Message::LoadGitHistory(task_id));
+                }
+            }
+
+            Message::LoadGitHistory(task_id) => {
+                let Some((project_dir, worktree)) = self.model.projects.iter()
+                    .find_map(|p| {
+                        let task = p.tasks.iter().find(|t| t.id == task_id)?;
+                        Some((p.working_dir.clone(), task.worktree_path.clone()?))
+                    })
+                else {
+                    return commands;
+                };
+                let Some(sender) = self.async_sender.clone() else {
+                    return commands;
+                };
+                // Blame and log per file take a moment - "Loading history..." meanwhile
+                self.model.ui_state.git_history = Some((task_id, None));
+                tokio::spawn(async move {
+                    let result = tokio::task::spawn_blocking(move || {
+                        crate::worktree::history::task_history(&project_dir, &worktree).map_err(|e| e.to_string())
+                    }).await.unwrap_or_else(|e| Err(format!("Task panicked: {}", e)));
+                    let _ = sender.send(Message::GitHistoryLoaded { task_id, result });
+                });
+            }
+
+            Message::GitHistoryLoaded { task_id, result } => {
+                // Only the latest load for the viewed task counts
+                if self.model.ui_state.git_history.as_ref().is_some_and(|(id, _)| *id == task_id) {
+                    self.model.ui_state.git_history = Some((task_id, Some(result)));
+                }
+            }
+
+            Message::ToggleIntegrations => {
+                self.model.ui_state.show_integrations = !self.model.ui_state.show_integrations;
+            }
+
+            Message::CheckIntegrations => {
+                let Some(sender) = self.async_sender.clone() else {
+                    return commands;
+                };
+                tokio::spawn(async move {
+                    let capabilities = tokio::task::spawn_blocking(crate::capabilities::Capabilities::detect)
+                        .await
+                        .unwrap_or_default();
+                    let _ = sender.send(Message::IntegrationsChecked(capabilities));
+                });
+            }
+
+            Message::IntegrationsChecked(capabilities) => {
+                self.model.ui_state.capabilities = capabilities;
+            }
+
+            Message::ToggleQuickDiff => {
+                if self.model.ui_state.quick_diff.take().is_some() {
+                    return commands;
+                }
+                if let Some(task_id) = self.model.ui_state.selected_task_id {
+                    self.model.ui_state.quick_diff = Some(task_id);
+                    commands.extend(self.quick_diff_loads(task_id));
+                }
+            }
+
+            Message::QuickDiffNavigate(delta) => {
+                let count = self.model.active_project()
+                    .map(|p| p.tasks_by_status(self.model.ui_state.selected_column).len())
+                    .unwrap_or(0);
+                let Some(current) = self.model.ui_state.selected_task_idx.filter(|_| count > 0) else {
+                    return commands;
+                };
+                let idx = (current as i32 + delta).clamp(0, count as i32 - 1) as usize;
+                self.select_task(Some(idx));
+                if let Some(task_id) = self.model.ui_state.selected_task_id {
+                    self.model.ui_state.quick_diff = Some(task_id);
+                    commands.extend(self.quick_diff_loads(task_id));
+                }
+            }
+
+            Message::QuickDiffOpenFull => {
+                let Some(task_id) = self.model.ui_state.quick_diff.take() else {
+                    return commands;
+                };
+                self.model.ui_state.show_task_preview = true;
+                self.model.ui_state.task_detail_tab = crate::model::TaskDetailTab::Git;
+                self.model.ui_state.activity_note = None;
+                self.model.ui_state.diff_comment = None;
+                self.model.ui_state.time_entry_input = None;
+                self.model.ui_state.session_replay = None;
+                self.model.ui_state.spec_scroll_offset = 0;
+                commands.push(Message::LoadGitDiff(task_id));
+            }
+
+            Message::GitDiffLoaded { key, result } => {
+                match result {
+                    Ok(diff) => {
+                        let parsed = crate::worktree::diff_cache::CachedDiff::parse(diff);
+                        self.model.ui_state.git_diff_cache.insert(key, parsed);
+                    }
+                    Err(e) => {
+                        self.model.ui_state.git_diff_cache.set_error(key.task_id, format!("Error loading diff: {}", e));
                     }
                 }
             }
@@ -6952,7 +11804,7 @@ Do not ask for permission - run tests and fix any issues you find."#);
                         self.model.ui_state.selected_task_idx
                             .and_then(|idx| tasks.get(idx).copied())
                     })
-                    .map(|task| task.activity_log.len())
+                    .map(|task| task.activity_timeline().len())
                     .unwrap_or(0);
                 let max_scroll = max_entries.saturating_sub(10); // Leave some visible entries
                 self.model.ui_state.activity_scroll_offset = self
@@ -6985,6 +11837,80 @@ Do not ask for permission - run tests and fix any issues you find."#);
                 // Increment animation frame for spinners
                 self.model.ui_state.animation_frame = self.model.ui_state.animation_frame.wrapping_add(1);
 
+                // Track what the user has actually looked at
+                self.mark_viewed_content();
+
+                // Keep scrolling while a dragged card rests near a column edge
+                self.auto_scroll_drag();
+
+                // Sidecar supervision: periodic health checks, restarts with backoff
+                // (held off while the sidecar modal is open to kill/rebuild it by hand)
+                if !self.safe_mode && self.model.ui_state.sidecar_modal.is_none()
+                    && self.model.ui_state.sidecar_health.is_due()
+                {
+                    if let Some(ref sender) = self.async_sender {
+                        let _ = sender.send(Message::SuperviseSidecar);
+                    }
+                }
+
+                // Poll CI for pushed task branches
+                let ui = &self.model.ui_state;
+                if !ui.ci_polling
+                    && ui.ci_polled_at.is_none_or(|at| at.elapsed() >= crate::ci::POLL_INTERVAL)
+                    && self.model.projects.iter().flat_map(|p| p.tasks.iter()).any(|t| t.watches_ci())
+                {
+                    if let Some(ref sender) = self.async_sender {
+                        let _ = sender.send(Message::PollCi);
+                    }
+                }
+
+                // Check chat threads of tasks waiting for input for replies
+                let ui = &self.model.ui_state;
+                if !ui.chat_polling
+                    && ui.chat_polled_at.is_none_or(|at| at.elapsed() >= crate::chat::POLL_INTERVAL)
+                    && self.model.projects.iter().flat_map(|p| p.tasks.iter())
+                        .any(|t| t.chat_thread.is_some() && t.status == TaskStatus::NeedsWork)
+                {
+                    if let Some(ref sender) = self.async_sender {
+                        let _ = sender.send(Message::PollChat);
+                    }
+                }
+
+                // Keep the unsent task description on disk in case kanblam quits or crashes
+                if self.model.ui_state.draft_checked_at.is_none_or(|at| at.elapsed() >= crate::model::draft::SAVE_INTERVAL) {
+                    self.model.ui_state.draft_checked_at = Some(std::time::Instant::now());
+                    self.save_draft();
+                }
+
+                // Look for tasks stuck in a column past its time limit
+                if self.model.ui_state.sla_checked_at.is_none_or(|at| at.elapsed() >= crate::sla::CHECK_INTERVAL) {
+                    self.model.ui_state.sla_checked_at = Some(std::time::Instant::now());
+                    if let Some(ref sender) = self.async_sender {
+                        let _ = sender.send(Message::CheckSla);
+                    }
+                }
+
+                // Keep the monitor's captures fresh while it's shown
+                let monitor = &self.model.ui_state.monitor;
+                let monitor_due = self.model.ui_state.focus == FocusArea::Monitor
+                    && !monitor.refreshing
+                    && monitor.refreshed_at.is_none_or(|at| at.elapsed() >= crate::model::MonitorState::REFRESH_INTERVAL);
+                if monitor_due {
+                    if let Some(ref sender) = self.async_sender {
+                        let _ = sender.send(Message::RefreshMonitor);
+                    }
+                }
+
+                // Focus timer phase boundary. Tick's own commands aren't dispatched,
+                // so the transition goes through the async channel.
+                let phase_over = self.model.ui_state.focus_timer.as_ref()
+                    .is_some_and(|t| t.remaining_secs(&self.model.global_settings) <= 0);
+                if phase_over {
+                    if let Some(ref sender) = self.async_sender {
+                        let _ = sender.send(Message::FocusPhaseElapsed);
+                    }
+                }
+
                 // Advance logo highlight animation if active (frames 1-5, then back to 0)
                 // Frame 1 = lead-in (absorbs timing variance), frames 2-5 = highlight glides up
                 // (frame 2 = feet, frame 3 = body, frame 4 = face, frame 5 = head)
@@ -7093,7 +12019,7 @@ Do not ask for permission - run tests and fix any issues you find."#);
                             self.model.ui_state.selected_task_idx
                                 .and_then(|idx| tasks.get(idx).copied())
                         })
-                        .map(|task| task.activity_log.len())
+                        .map(|task| task.activity_timeline().len())
                         .unwrap_or(0);
 
                     // Scroll to bottom (same formula as ScrollActivityDown)
@@ -7185,10 +12111,10 @@ Do not ask for permission - run tests and fix any issues you find."#);
                         .unwrap_or(false);
                     if should_start {
                         // Check if enough time has passed since last interaction (based on configured interval)
-                        let interval_secs = (self.model.global_settings.mascot_advice_interval_minutes * 60) as u64;
+                        let global_minutes = self.model.global_settings.mascot_advice_interval_minutes;
                         let should_trigger = self.model.active_project()
-                            .and_then(|p| p.watcher_last_interaction)
-                            .map(|t| t.elapsed().as_secs() >= interval_secs)
+                            .and_then(|p| Some((p.watcher_last_interaction?, p.watcher_interval_minutes(global_minutes))))
+                            .map(|(t, minutes)| t.elapsed().as_secs() >= minutes as u64 * 60)
                             .unwrap_or(false); // No interaction yet = wait for timer to be set
 
                         if should_trigger {
@@ -7223,8 +12149,11 @@ Do not ask for permission - run tests and fix any issues you find."#);
                     }
                 }
 
-                // Refresh git status every ~5 seconds (50 ticks at 100ms per tick)
-                if self.model.ui_state.animation_frame % 50 == 0 {
+                // Refresh git status every ~5 seconds (50 ticks at 100ms per tick),
+                // unless a filesystem watcher is already pushing changes for this project
+                let watched = self.model.active_project()
+                    .is_some_and(|p| self.git_watchers.contains_key(&p.working_dir));
+                if !watched && self.model.ui_state.animation_frame % 50 == 0 {
                     commands.push(Message::RefreshGitStatus);
                 }
 
@@ -7244,6 +12173,10 @@ Do not ask for permission - run tests and fix any issues you find."#);
             // === Quick Claude CLI Pane ===
 
             Message::OpenClaudeCliPane => {
+                if let Some(banner) = self.capability_banner(&[Capability::Tmux, Capability::ClaudeCli]) {
+                    commands.push(Message::SetStatusMessage(Some(banner)));
+                    return commands;
+                }
                 // Get the working directory of the active project
                 if let Some(project) = self.model.active_project() {
                     let working_dir = project.working_dir.clone();
@@ -7262,6 +12195,52 @@ Do not ask for permission - run tests and fix any issues you find."#);
 
             // === Configuration Modal ===
 
+            Message::ShowWhatsNew => {
+                use crate::changelog::{releases_since, CURRENT_VERSION};
+
+                let settings = &mut self.model.global_settings;
+                let last_seen = settings.last_seen_version.replace(CURRENT_VERSION.to_string());
+                // A fresh install has nothing "new" to catch up on
+                if last_seen.is_none() && self.model.projects.is_empty() {
+                    return commands;
+                }
+                let features: Vec<_> = releases_since(last_seen.as_deref())
+                    .into_iter()
+                    .flat_map(|release| release.features.iter().map(|f| (release.version, f)))
+                    .collect();
+                if !features.is_empty() {
+                    self.model.ui_state.whats_new = Some(crate::model::WhatsNewState { features, cursor: 0 });
+                }
+            }
+
+            Message::CloseWhatsNew => {
+                self.model.ui_state.whats_new = None;
+            }
+
+            Message::WhatsNewMove(delta) => {
+                if let Some(ref mut whats_new) = self.model.ui_state.whats_new {
+                    let last = whats_new.features.len().saturating_sub(1);
+                    whats_new.cursor = whats_new.cursor.saturating_add_signed(delta as isize).min(last);
+                }
+            }
+
+            Message::WhatsNewOpenSetting => {
+                let setting = self.model.ui_state.whats_new.as_ref()
+                    .and_then(|w| w.features.get(w.cursor))
+                    .and_then(|(_, feature)| feature.setting);
+                if let Some(field) = setting {
+                    self.model.ui_state.whats_new = None;
+                    commands.push(Message::ShowConfigModalAt(field));
+                }
+            }
+
+            Message::ShowConfigModalAt(field) => {
+                commands.extend(self.handle_message(Message::ShowConfigModal));
+                if let Some(ref mut modal) = self.model.ui_state.config_modal {
+                    modal.selected_field = field;
+                }
+            }
+
             Message::ShowConfigModal => {
                 use crate::model::{ConfigModalState, ConfigField, ApplyStrategy};
 
@@ -7269,10 +12248,52 @@ Do not ask for permission - run tests and fix any issues you find."#);
                 let (temp_commands, temp_qa_enabled, temp_max_qa_attempts, temp_apply_strategy) = self.model.active_project()
                     .map(|p| (p.commands.clone(), p.qa_enabled, p.max_qa_attempts, p.apply_strategy))
                     .unwrap_or_else(|| (Default::default(), true, 3, ApplyStrategy::default()));
+                let (temp_worktree_pool_size, temp_sparse_paths, temp_auto_version, temp_version_rules) = self.model.active_project()
+                    .map(|p| (p.worktree_pool_size, p.sparse_paths.clone(), p.auto_version, p.version_rules.clone()))
+                    .unwrap_or_default();
+                let (temp_error_log_source, temp_error_log_auto_start) = self.model.active_project()
+                    .map(|p| (p.error_log_source.clone(), p.error_log_auto_start))
+                    .unwrap_or_default();
+                let temp_feedback_snippets = self.model.active_project()
+                    .map(|p| p.feedback_snippets.clone())
+                    .unwrap_or_default();
+                let temp_status_ignore = self.model.active_project()
+                    .and_then(|p| p.status_ignore.clone());
+                let temp_shared_caches = self.model.active_project()
+                    .and_then(|p| p.shared_caches.clone());
+                let temp_transcribe_command = self.model.active_project()
+                    .and_then(|p| p.transcribe_command.clone());
+                let temp_chat_bridge = self.model.active_project()
+                    .and_then(|p| p.chat_bridge.clone());
+                let temp_session_layout = self.model.active_project()
+                    .and_then(|p| p.session_layout.clone());
+                let temp_commit_messages = self.model.active_project()
+                    .map(|p| p.commit_messages)
+                    .unwrap_or_default();
+                let temp_context_restart = self.model.active_project()
+                    .map(|p| p.context_restart)
+                    .unwrap_or_default();
+                let (temp_column_sort, temp_group_by_tag) = self.model.active_project()
+                    .map(|p| (p.column_sort, p.group_by_tag))
+                    .unwrap_or_default();
+                let (temp_watcher_columns, temp_watcher_persona, temp_watcher_interval, temp_watcher_reads) = self.model.active_project()
+                    .map(|p| (p.watcher_columns.clone(), p.watcher_persona.clone(), p.watcher_interval, p.watcher_reads))
+                    .unwrap_or_default();
                 let temp_editor = self.model.global_settings.default_editor;
                 let temp_vim_mode_enabled = self.model.global_settings.vim_mode_enabled;
                 let temp_mascot_advice = self.model.global_settings.mascot_advice_enabled;
                 let temp_mascot_interval = self.model.global_settings.mascot_advice_interval_minutes;
+                let temp_native_git = self.model.global_settings.native_git;
+                let temp_status_clock = self.model.global_settings.status_clock;
+                let temp_focus_minutes = (self.model.global_settings.focus_minutes, self.model.global_settings.break_minutes);
+                let temp_focus_defer_starts = self.model.global_settings.focus_defer_starts;
+                let temp_sound_cues = self.model.global_settings.sound_cues;
+                let temp_quiet_hours = self.model.global_settings.quiet_hours.clone();
+                let temp_sla_thresholds = self.model.global_settings.sla_thresholds.clone()
+                    .unwrap_or_else(|| crate::sla::DEFAULT_THRESHOLDS.to_string());
+                let temp_idle_fps = self.model.global_settings.idle_fps;
+                let temp_activity_retention = (self.model.global_settings.activity_max_entries, self.model.global_settings.activity_max_kb);
+                let temp_palette = self.model.global_settings.palette;
 
                 self.model.ui_state.config_modal = Some(ConfigModalState {
                     selected_field: ConfigField::default(),
@@ -7283,9 +12304,43 @@ Do not ask for permission - run tests and fix any issues you find."#);
                     temp_vim_mode_enabled,
                     temp_mascot_advice,
                     temp_mascot_interval,
+                    temp_native_git,
+                    temp_status_clock,
+                    temp_focus_minutes,
+                    temp_focus_defer_starts,
+                    temp_sound_cues,
+                    temp_quiet_hours,
+                    temp_sla_thresholds,
+                    temp_webhooks: self.model.global_settings.webhooks.clone(),
+                    temp_idle_fps,
+                    temp_activity_retention,
+                    temp_palette,
+                    temp_board_layout: self.model.global_settings.board_layout,
+                    temp_spell_check: self.model.global_settings.spell_check.clone(),
+                    temp_state_sync: self.model.global_settings.state_sync.clone(),
                     temp_qa_enabled,
                     temp_max_qa_attempts,
                     temp_apply_strategy,
+                    temp_context_restart,
+                    temp_commit_messages,
+                    temp_column_sort,
+                    temp_group_by_tag,
+                    temp_watcher_columns,
+                    temp_watcher_persona,
+                    temp_watcher_interval,
+                    temp_watcher_reads,
+                    temp_worktree_pool_size,
+                    temp_sparse_paths,
+                    temp_shared_caches,
+                    temp_status_ignore,
+                    temp_auto_version,
+                    temp_version_rules,
+                    temp_error_log_source,
+                    temp_error_log_auto_start,
+                    temp_feedback_snippets,
+                    temp_transcribe_command,
+                    temp_chat_bridge,
+                    temp_session_layout,
                 });
             }
 
@@ -7297,7 +12352,7 @@ Do not ask for permission - run tests and fix any issues you find."#);
                 if let Some(ref mut config) = self.model.ui_state.config_modal {
                     let mascot_enabled = config.temp_mascot_advice.unwrap_or(true);
                     let qa_enabled = config.temp_qa_enabled;
-                    config.selected_field = config.selected_field.next_visible(mascot_enabled, qa_enabled);
+                    config.selected_field = config.selected_field.next_visible(mascot_enabled, qa_enabled, config.temp_auto_version);
                 }
             }
 
@@ -7305,7 +12360,7 @@ Do not ask for permission - run tests and fix any issues you find."#);
                 if let Some(ref mut config) = self.model.ui_state.config_modal {
                     let mascot_enabled = config.temp_mascot_advice.unwrap_or(true);
                     let qa_enabled = config.temp_qa_enabled;
-                    config.selected_field = config.selected_field.prev_visible(mascot_enabled, qa_enabled);
+                    config.selected_field = config.selected_field.prev_visible(mascot_enabled, qa_enabled, config.temp_auto_version);
                 }
             }
 
@@ -7335,6 +12390,90 @@ Do not ask for permission - run tests and fix any issues you find."#);
                             config.edit_buffer = config.temp_mascot_interval.to_string();
                             config.editing = true;
                         }
+                    } else if config.selected_field == ConfigField::WatcherColumns {
+                        // Columns field - enter text edit mode
+                        if !config.editing {
+                            config.edit_buffer = crate::watcher_scope::format_columns(&config.temp_watcher_columns);
+                            config.editing = true;
+                        }
+                    } else if config.selected_field == ConfigField::WatcherPersona {
+                        // Persona field - enter text edit mode
+                        if !config.editing {
+                            config.edit_buffer = config.temp_watcher_persona.clone().unwrap_or_default();
+                            config.editing = true;
+                        }
+                    } else if config.selected_field == ConfigField::WatcherInterval {
+                        // Project interval field - enter text edit mode
+                        if !config.editing {
+                            config.edit_buffer = config.temp_watcher_interval.map(|m| m.to_string()).unwrap_or_default();
+                            config.editing = true;
+                        }
+                    } else if config.selected_field == ConfigField::WatcherReads {
+                        let modes = crate::watcher_scope::WatcherReads::all();
+                        let idx = modes.iter().position(|m| *m == config.temp_watcher_reads).unwrap_or(0);
+                        config.temp_watcher_reads = modes[(idx + 1) % modes.len()];
+                    } else if config.selected_field == ConfigField::NativeGit {
+                        // Toggle native git backend on/off
+                        config.temp_native_git = !config.temp_native_git;
+                    } else if config.selected_field == ConfigField::StatusClock {
+                        // Toggle status bar clock on/off
+                        config.temp_status_clock = !config.temp_status_clock;
+                    } else if config.selected_field == ConfigField::FocusMinutes {
+                        // Focus/break field - enter text edit mode
+                        if !config.editing {
+                            let (focus, brk) = config.temp_focus_minutes;
+                            config.edit_buffer = format!("{}/{}", focus, brk);
+                            config.editing = true;
+                        }
+                    } else if config.selected_field == ConfigField::FocusDeferStarts {
+                        // Toggle deferring starts during focus blocks
+                        config.temp_focus_defer_starts = !config.temp_focus_defer_starts;
+                    } else if config.selected_field == ConfigField::SoundCues {
+                        // Toggle per-event sound cues
+                        config.temp_sound_cues = !config.temp_sound_cues;
+                    } else if config.selected_field == ConfigField::QuietHours {
+                        // Quiet hours field - enter text edit mode
+                        if !config.editing {
+                            config.edit_buffer = config.temp_quiet_hours.clone().unwrap_or_default();
+                            config.editing = true;
+                        }
+                    } else if config.selected_field == ConfigField::SlaThresholds {
+                        // Column time limits field - enter text edit mode
+                        if !config.editing {
+                            config.edit_buffer = config.temp_sla_thresholds.clone();
+                            config.editing = true;
+                        }
+                    } else if config.selected_field == ConfigField::Webhooks {
+                        // Webhooks field - enter text edit mode
+                        if !config.editing {
+                            config.edit_buffer = config.temp_webhooks.clone().unwrap_or_default();
+                            config.editing = true;
+                        }
+                    } else if config.selected_field == ConfigField::IdleFps {
+                        // Idle redraw rate field - enter text edit mode
+                        if !config.editing {
+                            config.edit_buffer = config.temp_idle_fps.to_string();
+                            config.editing = true;
+                        }
+                    } else if config.selected_field == ConfigField::ActivityRetention {
+                        // Activity log limit field - enter text edit mode
+                        if !config.editing {
+                            let (entries, kb) = config.temp_activity_retention;
+                            config.edit_buffer = format!("{}/{}", entries, kb);
+                            config.editing = true;
+                        }
+                    } else if config.selected_field == ConfigField::SpellCheck {
+                        // Spell check language field - enter text edit mode
+                        if !config.editing {
+                            config.edit_buffer = config.temp_spell_check.clone().unwrap_or_default();
+                            config.editing = true;
+                        }
+                    } else if config.selected_field == ConfigField::StateSync {
+                        // State sync remote field - enter text edit mode
+                        if !config.editing {
+                            config.edit_buffer = config.temp_state_sync.clone().unwrap_or_default();
+                            config.editing = true;
+                        }
                     } else if config.selected_field == ConfigField::QaEnabled {
                         // Toggle QA on/off
                         config.temp_qa_enabled = !config.temp_qa_enabled;
@@ -7350,6 +12489,100 @@ Do not ask for permission - run tests and fix any issues you find."#);
                         let strategies = ApplyStrategy::all();
                         let idx = strategies.iter().position(|s| *s == config.temp_apply_strategy).unwrap_or(0);
                         config.temp_apply_strategy = strategies[(idx + 1) % strategies.len()];
+                    } else if config.selected_field == ConfigField::ContextRestart {
+                        let modes = crate::model::ContextRestart::all();
+                        let idx = modes.iter().position(|m| *m == config.temp_context_restart).unwrap_or(0);
+                        config.temp_context_restart = modes[(idx + 1) % modes.len()];
+                    } else if config.selected_field == ConfigField::CommitMessages {
+                        let modes = crate::model::CommitMessages::all();
+                        let idx = modes.iter().position(|m| *m == config.temp_commit_messages).unwrap_or(0);
+                        config.temp_commit_messages = modes[(idx + 1) % modes.len()];
+                    } else if config.selected_field == ConfigField::ColumnSort {
+                        let sorts = crate::model::ColumnSort::all();
+                        let idx = sorts.iter().position(|s| *s == config.temp_column_sort).unwrap_or(0);
+                        config.temp_column_sort = sorts[(idx + 1) % sorts.len()];
+                    } else if config.selected_field == ConfigField::GroupByTag {
+                        // Toggle grouping on/off
+                        config.temp_group_by_tag = !config.temp_group_by_tag;
+                    } else if config.selected_field == ConfigField::Palette {
+                        let palettes = crate::model::Palette::all();
+                        let idx = palettes.iter().position(|p| *p == config.temp_palette).unwrap_or(0);
+                        config.temp_palette = palettes[(idx + 1) % palettes.len()];
+                    } else if config.selected_field == ConfigField::BoardLayout {
+                        let layouts = crate::model::BoardLayout::all();
+                        let idx = layouts.iter().position(|l| *l == config.temp_board_layout).unwrap_or(0);
+                        config.temp_board_layout = layouts[(idx + 1) % layouts.len()];
+                    } else if config.selected_field == ConfigField::WorktreePool {
+                        // Pool size field - enter text edit mode
+                        if !config.editing {
+                            config.edit_buffer = config.temp_worktree_pool_size.to_string();
+                            config.editing = true;
+                        }
+                    } else if config.selected_field == ConfigField::SparsePaths {
+                        // Sparse profile field - enter text edit mode
+                        if !config.editing {
+                            config.edit_buffer = config.temp_sparse_paths.clone().unwrap_or_default();
+                            config.editing = true;
+                        }
+                    } else if config.selected_field == ConfigField::SharedCaches {
+                        // Shared caches field - enter text edit mode
+                        if !config.editing {
+                            config.edit_buffer = config.temp_shared_caches.clone().unwrap_or_default();
+                            config.editing = true;
+                        }
+                    } else if config.selected_field == ConfigField::StatusIgnore {
+                        // Ignore patterns field - enter text edit mode
+                        if !config.editing {
+                            config.edit_buffer = config.temp_status_ignore.clone().unwrap_or_default();
+                            config.editing = true;
+                        }
+                    } else if config.selected_field == ConfigField::AutoVersion {
+                        // Toggle auto-versioning on/off
+                        config.temp_auto_version = !config.temp_auto_version;
+                    } else if config.selected_field == ConfigField::VersionRules {
+                        // Rules field - enter text edit mode (prefilled with defaults when unset)
+                        if !config.editing {
+                            config.edit_buffer = config.temp_version_rules.clone()
+                                .unwrap_or_else(|| crate::release::version::DEFAULT_VERSION_RULES.to_string());
+                            config.editing = true;
+                        }
+                    } else if config.selected_field == ConfigField::ErrorLog {
+                        // Log source field - enter text edit mode
+                        if !config.editing {
+                            config.edit_buffer = config.temp_error_log_source.clone().unwrap_or_default();
+                            config.editing = true;
+                        }
+                    } else if config.selected_field == ConfigField::ErrorLogAutoStart {
+                        // Toggle auto-start on/off
+                        config.temp_error_log_auto_start = !config.temp_error_log_auto_start;
+                    } else if config.selected_field == ConfigField::TranscribeCommand {
+                        // Command field - enter text edit mode
+                        if !config.editing {
+                            config.edit_buffer = config.temp_transcribe_command.clone().unwrap_or_default();
+                            config.editing = true;
+                        }
+                    } else if config.selected_field == ConfigField::ChatBridge {
+                        // Chat bridge field - enter text edit mode
+                        if !config.editing {
+                            config.edit_buffer = config.temp_chat_bridge.clone().unwrap_or_default();
+                            config.editing = true;
+                        }
+                    } else if config.selected_field == ConfigField::SessionLayout {
+                        // Layout field - enter text edit mode
+                        if !config.editing {
+                            config.edit_buffer = config.temp_session_layout.clone().unwrap_or_default();
+                            config.editing = true;
+                        }
+                    } else if config.selected_field == ConfigField::FeedbackSnippets {
+                        // Snippets field - enter text edit mode (prefilled with defaults when unset)
+                        if !config.editing {
+                            config.edit_buffer = if config.temp_feedback_snippets.is_empty() {
+                                crate::model::DEFAULT_FEEDBACK_SNIPPETS.join(" | ")
+                            } else {
+                                config.temp_feedback_snippets.join(" | ")
+                            };
+                            config.editing = true;
+                        }
                     } else {
                         // Command field - enter text edit mode
                         if !config.editing {
@@ -7360,8 +12593,18 @@ Do not ask for permission - run tests and fix any issues you find."#);
                                 ConfigField::TestCommand => config.temp_commands.test.clone().unwrap_or_default(),
                                 ConfigField::FormatCommand => config.temp_commands.format.clone().unwrap_or_default(),
                                 ConfigField::LintCommand => config.temp_commands.lint.clone().unwrap_or_default(),
+                                ConfigField::PreStartScript => config.temp_commands.pre_start.clone().unwrap_or_default(),
+                                ConfigField::PostMergeScript => config.temp_commands.post_merge.clone().unwrap_or_default(),
+                                ConfigField::PreviewCommand => config.temp_commands.preview.clone().unwrap_or_default(),
                                 ConfigField::DefaultEditor | ConfigField::VimModeEnabled | ConfigField::MascotAdvice | ConfigField::MascotAdviceInterval
-                                | ConfigField::QaEnabled | ConfigField::MaxQaAttempts | ConfigField::ApplyStrategy => String::new(),
+                                | ConfigField::WatcherColumns | ConfigField::WatcherPersona | ConfigField::WatcherInterval | ConfigField::WatcherReads
+                                | ConfigField::NativeGit | ConfigField::StatusClock | ConfigField::FocusMinutes | ConfigField::FocusDeferStarts
+                                | ConfigField::SoundCues | ConfigField::QuietHours | ConfigField::SlaThresholds | ConfigField::Webhooks
+                                | ConfigField::IdleFps | ConfigField::ActivityRetention | ConfigField::Palette | ConfigField::BoardLayout | ConfigField::SpellCheck | ConfigField::StateSync | ConfigField::QaEnabled | ConfigField::MaxQaAttempts | ConfigField::ApplyStrategy
+                                | ConfigField::ContextRestart | ConfigField::CommitMessages | ConfigField::ColumnSort | ConfigField::GroupByTag
+                                | ConfigField::WorktreePool | ConfigField::SparsePaths | ConfigField::SharedCaches | ConfigField::StatusIgnore | ConfigField::AutoVersion | ConfigField::VersionRules
+                                | ConfigField::ErrorLog | ConfigField::ErrorLogAutoStart | ConfigField::FeedbackSnippets
+                                | ConfigField::TranscribeCommand | ConfigField::ChatBridge | ConfigField::SessionLayout => String::new(),
                             };
                             config.editing = true;
                         }
@@ -7383,6 +12626,30 @@ Do not ask for permission - run tests and fix any issues you find."#);
                         let strategies = ApplyStrategy::all();
                         let idx = strategies.iter().position(|s| *s == config.temp_apply_strategy).unwrap_or(0);
                         config.temp_apply_strategy = strategies[(idx + strategies.len() - 1) % strategies.len()];
+                    } else if config.selected_field == ConfigField::ContextRestart {
+                        let modes = crate::model::ContextRestart::all();
+                        let idx = modes.iter().position(|m| *m == config.temp_context_restart).unwrap_or(0);
+                        config.temp_context_restart = modes[(idx + modes.len() - 1) % modes.len()];
+                    } else if config.selected_field == ConfigField::CommitMessages {
+                        let modes = crate::model::CommitMessages::all();
+                        let idx = modes.iter().position(|m| *m == config.temp_commit_messages).unwrap_or(0);
+                        config.temp_commit_messages = modes[(idx + modes.len() - 1) % modes.len()];
+                    } else if config.selected_field == ConfigField::ColumnSort {
+                        let sorts = crate::model::ColumnSort::all();
+                        let idx = sorts.iter().position(|s| *s == config.temp_column_sort).unwrap_or(0);
+                        config.temp_column_sort = sorts[(idx + sorts.len() - 1) % sorts.len()];
+                    } else if config.selected_field == ConfigField::WatcherReads {
+                        let modes = crate::watcher_scope::WatcherReads::all();
+                        let idx = modes.iter().position(|m| *m == config.temp_watcher_reads).unwrap_or(0);
+                        config.temp_watcher_reads = modes[(idx + modes.len() - 1) % modes.len()];
+                    } else if config.selected_field == ConfigField::Palette {
+                        let palettes = crate::model::Palette::all();
+                        let idx = palettes.iter().position(|p| *p == config.temp_palette).unwrap_or(0);
+                        config.temp_palette = palettes[(idx + palettes.len() - 1) % palettes.len()];
+                    } else if config.selected_field == ConfigField::BoardLayout {
+                        let layouts = crate::model::BoardLayout::all();
+                        let idx = layouts.iter().position(|l| *l == config.temp_board_layout).unwrap_or(0);
+                        config.temp_board_layout = layouts[(idx + layouts.len() - 1) % layouts.len()];
                     }
                 }
             }
@@ -7412,6 +12679,104 @@ Do not ask for permission - run tests and fix any issues you find."#);
                         // If parse fails, keep previous value
                         config.editing = false;
                         config.edit_buffer.clear();
+                    } else if config.selected_field == ConfigField::WatcherColumns {
+                        // Empty means main checkout only; an unknown column keeps the previous value
+                        if let Some(columns) = crate::watcher_scope::parse_columns(&config.edit_buffer) {
+                            config.temp_watcher_columns = columns;
+                        }
+                        config.editing = false;
+                        config.edit_buffer.clear();
+                    } else if config.selected_field == ConfigField::WatcherPersona {
+                        let persona = config.edit_buffer.trim();
+                        config.temp_watcher_persona = (!persona.is_empty()).then(|| persona.to_string());
+                        config.editing = false;
+                        config.edit_buffer.clear();
+                    } else if config.selected_field == ConfigField::WatcherInterval {
+                        // Empty falls back to the global interval; anything unparsable keeps the previous value
+                        let value = config.edit_buffer.trim();
+                        if value.is_empty() {
+                            config.temp_watcher_interval = None;
+                        } else if let Ok(interval) = value.parse::<u32>() {
+                            config.temp_watcher_interval = Some(interval.clamp(1, 120));
+                        }
+                        config.editing = false;
+                        config.edit_buffer.clear();
+                    } else if config.selected_field == ConfigField::NativeGit {
+                        // NativeGit is toggled directly, no edit mode
+                    } else if config.selected_field == ConfigField::StatusClock {
+                        // StatusClock is toggled directly, no edit mode
+                    } else if config.selected_field == ConfigField::FocusMinutes {
+                        // Parse "focus/break" and validate (focus 5-120, break 1-60 minutes)
+                        let parsed = config.edit_buffer.split_once('/')
+                            .and_then(|(f, b)| Some((f.trim().parse::<u32>().ok()?, b.trim().parse::<u32>().ok()?)));
+                        if let Some((focus, brk)) = parsed {
+                            config.temp_focus_minutes = (focus.clamp(5, 120), brk.clamp(1, 60));
+                        }
+                        // If parse fails, keep previous value
+                        config.editing = false;
+                        config.edit_buffer.clear();
+                    } else if config.selected_field == ConfigField::FocusDeferStarts {
+                        // FocusDeferStarts is toggled directly, no edit mode
+                    } else if config.selected_field == ConfigField::SoundCues {
+                        // SoundCues is toggled directly, no edit mode
+                    } else if config.selected_field == ConfigField::QuietHours {
+                        // Empty clears the quiet hours; anything unparsable keeps the previous value
+                        let value = config.edit_buffer.trim();
+                        if value.is_empty() {
+                            config.temp_quiet_hours = None;
+                        } else if crate::notify::parse_quiet_hours(value).is_some() {
+                            config.temp_quiet_hours = Some(value.to_string());
+                        }
+                        config.editing = false;
+                        config.edit_buffer.clear();
+                    } else if config.selected_field == ConfigField::SlaThresholds {
+                        // Empty turns the limits off; anything unparsable keeps the previous value
+                        let value = config.edit_buffer.trim();
+                        if crate::sla::SlaThresholds::parse(value).is_some() {
+                            config.temp_sla_thresholds = value.to_string();
+                        }
+                        config.editing = false;
+                        config.edit_buffer.clear();
+                    } else if config.selected_field == ConfigField::Webhooks {
+                        // Empty removes the webhooks; anything unparsable keeps the previous value
+                        let value = config.edit_buffer.trim();
+                        if value.is_empty() {
+                            config.temp_webhooks = None;
+                        } else if crate::webhook::Webhook::parse_all(value).is_some() {
+                            config.temp_webhooks = Some(value.to_string());
+                        }
+                        config.editing = false;
+                        config.edit_buffer.clear();
+                    } else if config.selected_field == ConfigField::IdleFps {
+                        // Parse and validate idle redraw rate (1-10 per second)
+                        if let Ok(fps) = config.edit_buffer.parse::<u32>() {
+                            config.temp_idle_fps = fps.clamp(1, 10);
+                        }
+                        // If parse fails, keep previous value
+                        config.editing = false;
+                        config.edit_buffer.clear();
+                    } else if config.selected_field == ConfigField::ActivityRetention {
+                        // Parse "entries/KB" and validate (10-5000 entries, 64 KB-64 MB)
+                        let parsed = config.edit_buffer.split_once('/')
+                            .and_then(|(e, k)| Some((e.trim().parse::<usize>().ok()?, k.trim().parse::<usize>().ok()?)));
+                        if let Some((entries, kb)) = parsed {
+                            config.temp_activity_retention = (entries.clamp(10, 5000), kb.clamp(64, 65536));
+                        }
+                        // If parse fails, keep previous value
+                        config.editing = false;
+                        config.edit_buffer.clear();
+                    } else if config.selected_field == ConfigField::SpellCheck {
+                        // Empty turns spell checking off
+                        let value = config.edit_buffer.trim();
+                        config.temp_spell_check = (!value.is_empty()).then(|| value.to_string());
+                        config.editing = false;
+                        config.edit_buffer.clear();
+                    } else if config.selected_field == ConfigField::StateSync {
+                        // Empty turns sync off
+                        let value = config.edit_buffer.trim();
+                        config.temp_state_sync = (!value.is_empty()).then(|| value.to_string());
+                        config.editing = false;
+                        config.edit_buffer.clear();
                     } else if config.selected_field == ConfigField::QaEnabled {
                         // QaEnabled is toggled directly, no edit mode
                     } else if config.selected_field == ConfigField::MaxQaAttempts {
@@ -7422,8 +12787,116 @@ Do not ask for permission - run tests and fix any issues you find."#);
                         // If parse fails, keep previous value
                         config.editing = false;
                         config.edit_buffer.clear();
-                    } else if config.selected_field == ConfigField::ApplyStrategy {
-                        // ApplyStrategy is cycled directly, no edit mode
+                    } else if config.selected_field == ConfigField::ApplyStrategy
+                        || config.selected_field == ConfigField::ContextRestart
+                        || config.selected_field == ConfigField::CommitMessages
+                        || config.selected_field == ConfigField::ColumnSort
+                        || config.selected_field == ConfigField::GroupByTag
+                        || config.selected_field == ConfigField::WatcherReads
+                        || config.selected_field == ConfigField::Palette
+                        || config.selected_field == ConfigField::BoardLayout
+                    {
+                        // Cycled directly, no edit mode
+                    } else if config.selected_field == ConfigField::WorktreePool {
+                        // Parse and validate pool size (0-8)
+                        if let Ok(size) = config.edit_buffer.parse::<usize>() {
+                            config.temp_worktree_pool_size = size.min(8);
+                        }
+                        // If parse fails, keep previous value
+                        config.editing = false;
+                        config.edit_buffer.clear();
+                    } else if config.selected_field == ConfigField::SparsePaths {
+                        // Empty profile means full checkout
+                        let profile = config.edit_buffer.trim();
+                        config.temp_sparse_paths = if profile.is_empty() {
+                            None
+                        } else {
+                            Some(profile.to_string())
+                        };
+                        config.editing = false;
+                        config.edit_buffer.clear();
+                    } else if config.selected_field == ConfigField::SharedCaches {
+                        let caches = config.edit_buffer.trim();
+                        config.temp_shared_caches = if caches.is_empty() {
+                            None
+                        } else {
+                            Some(caches.to_string())
+                        };
+                        config.editing = false;
+                        config.edit_buffer.clear();
+                    } else if config.selected_field == ConfigField::StatusIgnore {
+                        let patterns = config.edit_buffer.trim();
+                        config.temp_status_ignore = if patterns.is_empty() {
+                            None
+                        } else {
+                            Some(patterns.to_string())
+                        };
+                        config.editing = false;
+                        config.edit_buffer.clear();
+                    } else if config.selected_field == ConfigField::AutoVersion {
+                        // AutoVersion is toggled directly, no edit mode
+                    } else if config.selected_field == ConfigField::VersionRules {
+                        // Empty or default rules are stored as None (follow defaults)
+                        let rules = config.edit_buffer.trim();
+                        config.temp_version_rules = if rules.is_empty() || rules == crate::release::version::DEFAULT_VERSION_RULES {
+                            None
+                        } else {
+                            Some(rules.to_string())
+                        };
+                        config.editing = false;
+                        config.edit_buffer.clear();
+                    } else if config.selected_field == ConfigField::ErrorLog {
+                        // Empty source turns the watcher off
+                        let source = config.edit_buffer.trim();
+                        config.temp_error_log_source = if source.is_empty() {
+                            None
+                        } else {
+                            Some(source.to_string())
+                        };
+                        config.editing = false;
+                        config.edit_buffer.clear();
+                    } else if config.selected_field == ConfigField::ErrorLogAutoStart {
+                        // ErrorLogAutoStart is toggled directly, no edit mode
+                    } else if config.selected_field == ConfigField::TranscribeCommand {
+                        // Empty command turns transcription off
+                        let command = config.edit_buffer.trim();
+                        config.temp_transcribe_command = if command.is_empty() {
+                            None
+                        } else {
+                            Some(command.to_string())
+                        };
+                        config.editing = false;
+                        config.edit_buffer.clear();
+                    } else if config.selected_field == ConfigField::ChatBridge {
+                        // Empty turns chat approvals off; anything unparsable keeps the previous value
+                        let value = config.edit_buffer.trim();
+                        if value.is_empty() {
+                            config.temp_chat_bridge = None;
+                        } else if crate::chat::ChatBridge::parse(value).is_some() {
+                            config.temp_chat_bridge = Some(value.to_string());
+                        }
+                        config.editing = false;
+                        config.edit_buffer.clear();
+                    } else if config.selected_field == ConfigField::SessionLayout {
+                        // Empty restores the default layout; anything unparsable keeps the previous value
+                        let value = config.edit_buffer.trim();
+                        if value.is_empty() {
+                            config.temp_session_layout = None;
+                        } else if crate::tmux::SessionLayout::parse(value).is_some() {
+                            config.temp_session_layout = Some(value.to_string());
+                        }
+                        config.editing = false;
+                        config.edit_buffer.clear();
+                    } else if config.selected_field == ConfigField::FeedbackSnippets {
+                        // The defaults are stored as an empty list (follow defaults)
+                        let snippets: Vec<String> = config.edit_buffer.split('|')
+                            .map(|s| s.trim().to_string())
+                            .filter(|s| !s.is_empty())
+                            .collect();
+                        let is_default = snippets.iter().map(String::as_str).eq(crate::model::DEFAULT_FEEDBACK_SNIPPETS.iter().copied());
+                        config.temp_feedback_snippets = if is_default { Vec::new() } else { snippets };
+                        config.editing = false;
+                        config.edit_buffer.clear();
                     } else {
                         // Command field - save buffer to temp_commands
                         let value = if config.edit_buffer.is_empty() {
@@ -7438,8 +12911,18 @@ Do not ask for permission - run tests and fix any issues you find."#);
                             ConfigField::TestCommand => config.temp_commands.test = value,
                             ConfigField::FormatCommand => config.temp_commands.format = value,
                             ConfigField::LintCommand => config.temp_commands.lint = value,
+                            ConfigField::PreStartScript => config.temp_commands.pre_start = value,
+                            ConfigField::PostMergeScript => config.temp_commands.post_merge = value,
+                            ConfigField::PreviewCommand => config.temp_commands.preview = value,
                             ConfigField::DefaultEditor | ConfigField::VimModeEnabled | ConfigField::MascotAdvice | ConfigField::MascotAdviceInterval
-                            | ConfigField::QaEnabled | ConfigField::MaxQaAttempts | ConfigField::ApplyStrategy => {}
+                            | ConfigField::WatcherColumns | ConfigField::WatcherPersona | ConfigField::WatcherInterval | ConfigField::WatcherReads
+                            | ConfigField::NativeGit | ConfigField::StatusClock | ConfigField::FocusMinutes | ConfigField::FocusDeferStarts
+                                | ConfigField::SoundCues | ConfigField::QuietHours | ConfigField::SlaThresholds | ConfigField::Webhooks
+                            | ConfigField::IdleFps | ConfigField::ActivityRetention | ConfigField::Palette | ConfigField::BoardLayout | ConfigField::SpellCheck | ConfigField::StateSync | ConfigField::QaEnabled | ConfigField::MaxQaAttempts | ConfigField::ApplyStrategy
+                            | ConfigField::ContextRestart | ConfigField::CommitMessages | ConfigField::ColumnSort | ConfigField::GroupByTag
+                            | ConfigField::WorktreePool | ConfigField::SparsePaths | ConfigField::SharedCaches | ConfigField::StatusIgnore | ConfigField::AutoVersion | ConfigField::VersionRules
+                            | ConfigField::ErrorLog | ConfigField::ErrorLogAutoStart | ConfigField::FeedbackSnippets
+                            | ConfigField::TranscribeCommand | ConfigField::ChatBridge | ConfigField::SessionLayout => {}
                         }
 
                         config.editing = false;
@@ -7464,6 +12947,49 @@ Do not ask for permission - run tests and fix any issues you find."#);
                 } else {
                     (self.model.global_settings.default_editor, self.model.global_settings.vim_mode_enabled, crate::model::ProjectCommands::default(), self.model.global_settings.mascot_advice_enabled, self.model.global_settings.mascot_advice_interval_minutes, true, 3, ApplyStrategy::default())
                 };
+                let (temp_worktree_pool_size, temp_sparse_paths, temp_auto_version, temp_version_rules) = self.model.ui_state.config_modal.as_ref()
+                    .map(|c| (c.temp_worktree_pool_size, c.temp_sparse_paths.clone(), c.temp_auto_version, c.temp_version_rules.clone()))
+                    .unwrap_or_default();
+                let (temp_error_log_source, temp_error_log_auto_start) = self.model.ui_state.config_modal.as_ref()
+                    .map(|c| (c.temp_error_log_source.clone(), c.temp_error_log_auto_start))
+                    .unwrap_or_default();
+                let temp_feedback_snippets = self.model.ui_state.config_modal.as_ref()
+                    .map(|c| c.temp_feedback_snippets.clone())
+                    .unwrap_or_default();
+                let temp_transcribe_command = self.model.ui_state.config_modal.as_ref()
+                    .and_then(|c| c.temp_transcribe_command.clone());
+                let temp_chat_bridge = self.model.ui_state.config_modal.as_ref()
+                    .and_then(|c| c.temp_chat_bridge.clone());
+                let temp_session_layout = self.model.ui_state.config_modal.as_ref()
+                    .and_then(|c| c.temp_session_layout.clone());
+                let temp_status_ignore = self.model.ui_state.config_modal.as_ref()
+                    .and_then(|c| c.temp_status_ignore.clone());
+                let temp_shared_caches = self.model.ui_state.config_modal.as_ref()
+                    .and_then(|c| c.temp_shared_caches.clone());
+                let temp_context_restart = self.model.ui_state.config_modal.as_ref()
+                    .map(|c| c.temp_context_restart)
+                    .unwrap_or_default();
+                let temp_commit_messages = self.model.ui_state.config_modal.as_ref()
+                    .map(|c| c.temp_commit_messages)
+                    .unwrap_or_default();
+                let (temp_column_sort, temp_group_by_tag) = self.model.ui_state.config_modal.as_ref()
+                    .map(|c| (c.temp_column_sort, c.temp_group_by_tag))
+                    .unwrap_or_default();
+                let (temp_watcher_columns, temp_watcher_persona, temp_watcher_interval, temp_watcher_reads) = self.model.ui_state.config_modal.as_ref()
+                    .map(|c| (c.temp_watcher_columns.clone(), c.temp_watcher_persona.clone(), c.temp_watcher_interval, c.temp_watcher_reads))
+                    .unwrap_or_default();
+                let temp_native_git = self.model.ui_state.config_modal.as_ref()
+                    .map(|c| c.temp_native_git)
+                    .unwrap_or(self.model.global_settings.native_git);
+
+                // Turning the native git backend on goes through a benchmark first;
+                // turning it off takes effect right away
+                if temp_native_git && !self.model.global_settings.native_git {
+                    commands.push(Message::BenchmarkGitBackend);
+                } else if !temp_native_git {
+                    self.model.global_settings.native_git = false;
+                    crate::worktree::native::set_enabled(false);
+                }
 
                 // Check if mascot advice setting changed
                 let mascot_changed = self.model.global_settings.mascot_advice_enabled != temp_mascot_advice;
@@ -7475,16 +13001,95 @@ Do not ask for permission - run tests and fix any issues you find."#);
                 self.model.global_settings.vim_mode_enabled = temp_vim_mode_enabled;
                 self.model.global_settings.mascot_advice_enabled = temp_mascot_advice;
                 self.model.global_settings.mascot_advice_interval_minutes = temp_mascot_interval;
+                if let Some(ref config) = self.model.ui_state.config_modal {
+                    self.model.global_settings.status_clock = config.temp_status_clock;
+                    (self.model.global_settings.focus_minutes, self.model.global_settings.break_minutes) = config.temp_focus_minutes;
+                    self.model.global_settings.focus_defer_starts = config.temp_focus_defer_starts;
+                    self.model.global_settings.sound_cues = config.temp_sound_cues;
+                    self.model.global_settings.quiet_hours = config.temp_quiet_hours.clone();
+                    crate::notify::configure_sounds(config.temp_sound_cues, config.temp_quiet_hours.as_deref());
+                    self.model.global_settings.sla_thresholds = (config.temp_sla_thresholds != crate::sla::DEFAULT_THRESHOLDS)
+                        .then(|| config.temp_sla_thresholds.clone());
+                    self.model.ui_state.sla_checked_at = None;
+                    self.model.global_settings.webhooks = config.temp_webhooks.clone();
+                    self.model.global_settings.idle_fps = config.temp_idle_fps;
+                    (self.model.global_settings.activity_max_entries, self.model.global_settings.activity_max_kb) = config.temp_activity_retention;
+                    crate::model::activity::set_retention(config.temp_activity_retention.0, config.temp_activity_retention.1);
+                    self.model.global_settings.palette = config.temp_palette;
+                    crate::ui::palette::set(config.temp_palette);
+                    self.model.global_settings.board_layout = config.temp_board_layout;
+                    if let Some(ref language) = config.temp_spell_check {
+                        if crate::spell::find(language).is_none() {
+                            self.model.ui_state.status_message = Some(format!(
+                                "No hunspell dictionary {}.aff/.dic found - put one in ~/.config/kanblam/dictionaries",
+                                language
+                            ));
+                        }
+                    }
+                    self.model.global_settings.spell_check = config.temp_spell_check.clone();
+                    if config.temp_state_sync.is_some() && self.model.global_settings.state_sync != config.temp_state_sync {
+                        self.model.ui_state.status_message = Some(
+                            "State sync is on: the board is pushed on exit and pulled on the next start".to_string()
+                        );
+                    }
+                    self.model.global_settings.state_sync = config.temp_state_sync.clone();
+                }
+                // Turning deferral off releases anything held back
+                commands.extend(self.release_deferred_starts());
 
                 // Update UI state's editor mode if changed
                 self.model.ui_state.set_vim_mode(temp_vim_mode_enabled);
 
                 // Save project commands, QA settings, and apply strategy
+                let mut status_ignore_changed = false;
                 if let Some(project) = self.model.active_project_mut() {
                     project.commands = temp_commands;
                     project.qa_enabled = temp_qa_enabled;
                     project.max_qa_attempts = temp_max_qa_attempts;
                     project.apply_strategy = temp_apply_strategy;
+                    project.context_restart = temp_context_restart;
+                    project.commit_messages = temp_commit_messages;
+                    project.column_sort = temp_column_sort;
+                    project.group_by_tag = temp_group_by_tag;
+                    project.watcher_columns = temp_watcher_columns;
+                    project.watcher_persona = temp_watcher_persona;
+                    project.watcher_interval = temp_watcher_interval;
+                    project.watcher_reads = temp_watcher_reads;
+                    project.auto_version = temp_auto_version;
+                    project.version_rules = temp_version_rules;
+                    project.error_log_auto_start = temp_error_log_auto_start;
+                    project.feedback_snippets = temp_feedback_snippets;
+                    project.transcribe_command = temp_transcribe_command;
+                    project.chat_bridge = temp_chat_bridge;
+                    project.session_layout = temp_session_layout;
+                    project.shared_caches = temp_shared_caches;
+                    if project.status_ignore != temp_status_ignore {
+                        project.status_ignore = temp_status_ignore;
+                        status_ignore_changed = true;
+                    }
+
+                    // Restart the error-log watcher when its source changed
+                    if project.error_log_source != temp_error_log_source {
+                        project.error_log_source = temp_error_log_source;
+                        commands.push(Message::StartErrorLogWatch { project_dir: project.working_dir.clone() });
+                    }
+
+                    // Resize the worktree pool in the background if its size changed
+                    // (a new sparse profile only applies to slots created from now on)
+                    project.sparse_paths = temp_sparse_paths;
+                    if project.worktree_pool_size != temp_worktree_pool_size {
+                        project.worktree_pool_size = temp_worktree_pool_size;
+                        commands.push(Message::WarmWorktreePool {
+                            project_dir: project.working_dir.clone(),
+                            size: temp_worktree_pool_size,
+                        });
+                    }
+                }
+
+                // Cached diffs and stats were computed with the old patterns
+                if status_ignore_changed {
+                    self.model.ui_state.git_diff_cache = Default::default();
+                    commands.push(Message::RefreshGitStatus);
                 }
 
                 // If mascot advice setting changed, update all projects and start/stop watcher
@@ -7512,7 +13117,7 @@ Do not ask for permission - run tests and fix any issues you find."#);
 
                 // Get the project working dir first
                 let detected = self.model.active_project()
-                    .map(|p| ProjectCommands::detect(&p.working_dir))
+                    .map(|p| ProjectCommands::detect(&p.scope_dir()))
                     .unwrap_or_default();
 
                 if let Some(ref mut config) = self.model.ui_state.config_modal {
@@ -7527,8 +13132,8 @@ Do not ask for permission - run tests and fix any issues you find."#);
                 // Run build/check for all projects; only restart for bootstrap mode
                 let project_info = self.model.active_project().map(|p| {
                     let is_boot = is_bootstrap_project(p);
-                    let check_cmd = p.commands.effective_check(&p.working_dir);
-                    let working_dir = p.working_dir.clone();
+                    let working_dir = p.scope_dir();
+                    let check_cmd = p.commands.effective_check(&working_dir);
                     let apply_strategy = p.apply_strategy;
                     (is_boot, check_cmd, working_dir, apply_strategy)
                 });
@@ -7708,8 +13313,9 @@ Do not ask for permission - run tests and fix any issues you find."#);
                 // Update global setting to remember preference
                 self.model.global_settings.mascot_advice_enabled = Some(true);
 
-                let interval_minutes = self.model.global_settings.mascot_advice_interval_minutes;
+                let global_minutes = self.model.global_settings.mascot_advice_interval_minutes;
                 if let Some(project) = self.model.active_project_mut() {
+                    let interval_minutes = project.watcher_interval_minutes(global_minutes);
                     project.watcher_enabled = true;
                     // Set timer to now - user must wait full interval before first advice
                     // (The only exception is right after intro dismissal, handled in DismissWatcherComment)
@@ -7756,14 +13362,15 @@ Do not ask for permission - run tests and fix any issues you find."#);
                 if let Some(project) = self.model.active_project_mut() {
                     if project.watcher_enabled && !project.watcher_observing {
                         project.watcher_observing = true; // Start animation immediately
-                        working_dir = Some(project.working_dir.clone());
+                        let scope = crate::watcher_scope::scope(project);
+                        working_dir = Some((project.working_dir.clone(), project.watcher_muted.clone(), scope));
                     }
                 }
 
                 // Now trigger sidecar (separate borrow scope)
-                if let Some(dir) = working_dir {
+                if let Some((dir, muted, scope)) = working_dir {
                     if let Some(ref client) = self.sidecar_client {
-                        if let Err(e) = client.trigger_watcher(&dir) {
+                        if let Err(e) = client.trigger_watcher(&dir, &muted, scope) {
                             // Revert animation on error
                             if let Some(project) = self.model.active_project_mut() {
                                 project.watcher_observing = false;
@@ -7793,17 +13400,31 @@ Do not ask for permission - run tests and fix any issues you find."#);
                 // Find the project that matches this comment's path
                 for project in &mut self.model.projects {
                     if paths_match(&project.working_dir, &comment.project_path) {
+                        // Every comment goes to the feed; muted ones only there
+                        let muted = comment.category.is_some_and(|c| project.watcher_muted.contains(&c));
+                        crate::model::watcher_feed::record(
+                            &mut project.watcher_feed,
+                            crate::model::watcher_feed::FeedEntry::new(&comment, muted),
+                        );
+                        if muted {
+                            project.watcher_observing = false;
+                            project.watcher_last_interaction = Some(std::time::Instant::now());
+                            break;
+                        }
+
                         // Don't overwrite existing comment (e.g., intro) that's awaiting dismissal
                         if project.watcher_awaiting_dismissal && project.watcher_comment.is_some() {
                             project.watcher_observing = false;
                             break;
                         }
 
-                        project.watcher_comment = Some(crate::model::WatcherCommentDisplay::new(
+                        let mut display = crate::model::WatcherCommentDisplay::new(
                             comment.comment.clone(),
                             comment.mood,
                             comment.insight.clone(),
-                        ));
+                        );
+                        display.category = comment.category;
+                        project.watcher_comment = Some(display);
                         project.watcher_observing = false;
                         // Wait for user to dismiss/open before generating next comment
                         project.watcher_awaiting_dismissal = true;
@@ -7845,8 +13466,9 @@ Do not ask for permission - run tests and fix any issues you find."#);
                     .map(|c| c.is_intro)
                     .unwrap_or(false);
 
-                let interval_minutes = self.model.global_settings.mascot_advice_interval_minutes;
+                let global_minutes = self.model.global_settings.mascot_advice_interval_minutes;
                 if let Some(project) = self.model.active_project_mut() {
+                    let interval_minutes = project.watcher_interval_minutes(global_minutes);
                     project.watcher_comment = None;
                     project.watcher_awaiting_dismissal = false;
 
@@ -7876,29 +13498,171 @@ Do not ask for permission - run tests and fix any issues you find."#);
                     self.model.global_settings.mascot_advice_enabled = Some(true);
                 }
 
-                // Also close the insight modal if open
-                self.model.ui_state.show_watcher_insight_modal = false;
+                // Also close the insight modal if open
+                self.model.ui_state.show_watcher_insight_modal = false;
+            }
+
+            Message::OpenWatcherInsightModal => {
+                // Only open if we have a watcher comment
+                if self.model.active_project().and_then(|p| p.watcher_comment.as_ref()).is_some() {
+                    self.model.ui_state.show_watcher_insight_modal = true;
+                    self.model.ui_state.watcher_insight_scroll_offset = 0;
+                    // Mark interaction to restart 15min timer
+                    if let Some(project) = self.model.active_project_mut() {
+                        project.watcher_awaiting_dismissal = false;
+                        project.watcher_last_interaction = Some(std::time::Instant::now());
+                    }
+                }
+            }
+
+            Message::CloseWatcherInsightModal => {
+                self.model.ui_state.show_watcher_insight_modal = false;
+                // Also dismiss the watcher comment when modal is closed
+                if let Some(project) = self.model.active_project_mut() {
+                    project.watcher_comment = None;
+                    // Timer already restarted when modal was opened
+                }
+            }
+
+            Message::OpenWatcherFeed => {
+                if self.model.active_project().is_some() {
+                    self.model.ui_state.watcher_feed = Some(0);
+                }
+            }
+
+            Message::CloseWatcherFeed => {
+                self.model.ui_state.watcher_feed = None;
+            }
+
+            Message::WatcherFeedMove(delta) => {
+                let len = self.model.active_project().map_or(0, |p| p.watcher_feed.len());
+                if let Some(ref mut selected) = self.model.ui_state.watcher_feed {
+                    *selected = (*selected as i64 + delta as i64).clamp(0, len.saturating_sub(1) as i64) as usize;
+                }
+            }
+
+            Message::ToggleWatcherMute(category) => {
+                if let Some(project) = self.model.active_project_mut() {
+                    crate::model::watcher_feed::toggle_mute(&mut project.watcher_muted, category);
+                    let muted = project.watcher_muted.contains(&category);
+                    // A balloon from a category just muted goes away
+                    if muted && project.watcher_comment.as_ref().is_some_and(|c| c.category == Some(category)) {
+                        project.watcher_comment = None;
+                        project.watcher_awaiting_dismissal = false;
+                        project.watcher_last_interaction = Some(std::time::Instant::now());
+                        self.model.ui_state.show_watcher_insight_modal = false;
+                    }
+                    commands.push(Message::SetStatusMessage(Some(format!(
+                        "Watcher {}: {}",
+                        if muted { "muted" } else { "unmuted" },
+                        category.label(),
+                    ))));
+                }
+            }
+
+            Message::CreateTaskFromWatcherFeed => {
+                let Some(selected) = self.model.ui_state.watcher_feed else {
+                    return commands;
+                };
+                let Some((remark, title, linked)) = self.model.active_project()
+                    .and_then(|p| p.watcher_feed.iter().rev().nth(selected))
+                    .map(|e| (e.remark.clone(), e.task.clone().unwrap_or_else(|| e.remark.clone()), e.task_id))
+                else {
+                    return commands;
+                };
+                if linked.is_some() {
+                    commands.push(Message::SetStatusMessage(Some("A task was already made from this one (Enter shows it)".to_string())));
+                    return commands;
+                }
+                self.model.ui_state.watcher_feed = None;
+                commands.extend(self.handle_message(Message::CreateTask(title)));
+                if let Some(project) = self.model.active_project_mut() {
+                    if let Some(task_id) = project.tasks.first().map(|t| t.id) {
+                        crate::model::watcher_feed::link_task(&mut project.watcher_feed, &remark, task_id);
+                    }
+                }
+            }
+
+            Message::OpenWatcherFeedTask => {
+                let task_id = self.model.ui_state.watcher_feed.and_then(|selected| {
+                    let project = self.model.active_project()?;
+                    let task_id = project.watcher_feed.iter().rev().nth(selected)?.task_id?;
+                    project.tasks.iter().any(|t| t.id == task_id).then_some(task_id)
+                });
+                let Some(task_id) = task_id else {
+                    commands.push(Message::SetStatusMessage(Some("No task on the board for this entry".to_string())));
+                    return commands;
+                };
+                self.model.ui_state.watcher_feed = None;
+                self.save_scroll_offset();
+                // sync_selection follows the task into whichever column it lives in
+                self.model.ui_state.selected_task_id = Some(task_id);
+                self.sync_selection();
+                self.model.ui_state.focus = FocusArea::KanbanBoard;
+                self.model.ui_state.title_scroll_offset = 0;
+                self.model.ui_state.title_scroll_delay = 0;
+            }
+
+            Message::ShowStartFailure(task_id) => {
+                let failed = self.model.active_project()
+                    .and_then(|p| p.tasks.iter().find(|t| t.id == task_id))
+                    .is_some_and(|t| t.start_failure.is_some());
+                if failed {
+                    self.model.ui_state.start_failure = Some((task_id, 0));
+                }
+            }
+
+            Message::CloseStartFailure => {
+                self.model.ui_state.start_failure = None;
             }
 
-            Message::OpenWatcherInsightModal => {
-                // Only open if we have a watcher comment
-                if self.model.active_project().and_then(|p| p.watcher_comment.as_ref()).is_some() {
-                    self.model.ui_state.show_watcher_insight_modal = true;
-                    self.model.ui_state.watcher_insight_scroll_offset = 0;
-                    // Mark interaction to restart 15min timer
-                    if let Some(project) = self.model.active_project_mut() {
-                        project.watcher_awaiting_dismissal = false;
-                        project.watcher_last_interaction = Some(std::time::Instant::now());
-                    }
+            Message::ScrollStartFailure(delta) => {
+                if let Some((_, scroll)) = self.model.ui_state.start_failure.as_mut() {
+                    // The UI clamps the upper end
+                    *scroll = (*scroll as i32 + delta).clamp(0, u16::MAX as i32) as u16;
                 }
             }
 
-            Message::CloseWatcherInsightModal => {
-                self.model.ui_state.show_watcher_insight_modal = false;
-                // Also dismiss the watcher comment when modal is closed
-                if let Some(project) = self.model.active_project_mut() {
-                    project.watcher_comment = None;
-                    // Timer already restarted when modal was opened
+            Message::RetryStart { task_id, fresh } => {
+                self.model.ui_state.start_failure = None;
+                let Some(project) = self.model.active_project_mut() else {
+                    return commands;
+                };
+                let project_dir = project.working_dir.clone();
+                let Some(task) = project.tasks.iter_mut()
+                    .find(|t| t.id == task_id && t.status == TaskStatus::Planned)
+                else {
+                    return commands;
+                };
+                if !fresh {
+                    commands.push(Message::StartTaskWithWorktree(task_id));
+                    return commands;
+                }
+
+                // Drop the half-started worktree and its branch, then start over
+                let worktree_path = task.worktree_path.take();
+                task.git_branch = None;
+                task.log_activity("Discarded the failed start's worktree");
+                let display_id = task.display_id();
+                let pool_size = self.worktree_pool_size(&project_dir);
+                if let Some(sender) = self.async_sender.clone() {
+                    tokio::spawn(async move {
+                        let result = tokio::task::spawn_blocking(move || {
+                            if let Some(worktree_path) = worktree_path {
+                                crate::worktree::release_worktree(&project_dir, &worktree_path, pool_size)
+                                    .map_err(|e| e.to_string())?;
+                            }
+                            crate::worktree::delete_branch(&project_dir, &display_id).map_err(|e| e.to_string())
+                        }).await.unwrap_or_else(|e| Err(format!("Task panicked: {}", e)));
+                        let _ = sender.send(Message::StartWorktreeDiscarded { task_id, result });
+                    });
+                }
+            }
+
+            Message::StartWorktreeDiscarded { task_id, result } => {
+                match result {
+                    Ok(()) => commands.push(Message::StartTaskWithWorktree(task_id)),
+                    Err(e) => commands.push(Message::Error(format!("Couldn't discard the worktree: {}", e))),
                 }
             }
 
@@ -7928,8 +13692,14 @@ Do not ask for permission - run tests and fix any issues you find."#);
                         project.watcher_comment = None;
                     }
 
-                    // Create a new task with the insight task instructions
-                    commands.push(Message::CreateTask(task_title));
+                    // Create a new task with the insight task instructions (inserted
+                    // first in the project), and link the feed entry to it
+                    commands.extend(self.handle_message(Message::CreateTask(task_title)));
+                    if let Some(project) = self.model.active_project_mut() {
+                        if let Some(task_id) = project.tasks.first().map(|t| t.id) {
+                            crate::model::watcher_feed::link_task(&mut project.watcher_feed, &insight.remark, task_id);
+                        }
+                    }
                 }
             }
 
@@ -7957,6 +13727,7 @@ Do not ask for permission - run tests and fix any issues you find."#);
                         let task = Task::new(task_title);
                         task_id = task.id;
                         project.tasks.insert(0, task);
+                        crate::model::watcher_feed::link_task(&mut project.watcher_feed, &insight.remark, task_id);
                     } else {
                         return commands;
                     }
@@ -7981,7 +13752,15 @@ Do not ask for permission - run tests and fix any issues you find."#);
             }
 
             Message::Error(err) => {
-                // Display error in status bar so user actually sees it
+                // Display error in status bar so user actually sees it, and keep it
+                // in the diagnostics once the next message replaces it
+                let context = self.model.active_project().map(|p| p.name.clone());
+                crate::diagnostics::report(
+                    crate::diagnostics::Severity::Error,
+                    crate::diagnostics::classify(&err),
+                    err.clone(),
+                    context,
+                );
                 self.model.ui_state.status_message = Some(format!("❌ {}", err));
             }
 
@@ -8001,120 +13780,696 @@ Do not ask for permission - run tests and fix any issues you find."#);
                     } else {
                         SidecarConnectionStatus::Unresponsive
                     }
-                } else {
-                    SidecarConnectionStatus::NotRunning
-                };
-
-                // Count running sidecar processes
-                let process_count = count_sidecar_processes();
-
-                // Get build timestamp from sidecar binary
-                let build_timestamp = get_sidecar_build_timestamp();
+                } else {
+                    SidecarConnectionStatus::NotRunning
+                };
+
+                // Count running sidecar processes
+                let process_count = count_sidecar_processes();
+
+                // Get build timestamp from sidecar binary
+                let build_timestamp = get_sidecar_build_timestamp();
+
+                self.model.ui_state.sidecar_modal = Some(SidecarModalState {
+                    connection_status,
+                    process_count,
+                    build_timestamp,
+                    selected_action: 0,
+                    action_status: None,
+                    action_in_progress: false,
+                });
+            }
+
+            Message::CloseSidecarModal => {
+                self.model.ui_state.sidecar_modal = None;
+            }
+
+            Message::SidecarModalNavigate(delta) => {
+                if let Some(ref mut modal) = self.model.ui_state.sidecar_modal {
+                    let new_idx = (modal.selected_action as i32 + delta).clamp(0, 2) as usize;
+                    modal.selected_action = new_idx;
+                }
+            }
+
+            Message::SidecarModalExecuteAction => {
+                if let Some(ref mut modal) = self.model.ui_state.sidecar_modal {
+                    if modal.action_in_progress {
+                        return commands;
+                    }
+
+                    modal.action_in_progress = true;
+                    modal.action_status = Some("Working...".to_string());
+
+                    // A remote sidecar's process lives on its own host
+                    if crate::sidecar::client::is_remote() && modal.selected_action != 1 {
+                        commands.push(Message::SidecarActionCompleted {
+                            success: false,
+                            message: "Sidecar is remote (KANBLAM_SIDECAR_ADDR) - manage it on its host".to_string(),
+                        });
+                        return commands;
+                    }
+
+                    match modal.selected_action {
+                        0 => {
+                            // Kill sidecar
+                            let result = kill_sidecar_processes();
+                            commands.push(Message::SidecarActionCompleted {
+                                success: result.is_ok(),
+                                message: result.unwrap_or_else(|e| e),
+                            });
+                        }
+                        1 => {
+                            // Compile sidecar (npm run build)
+                            let result = compile_sidecar();
+                            commands.push(Message::SidecarActionCompleted {
+                                success: result.is_ok(),
+                                message: result.unwrap_or_else(|e| e),
+                            });
+                        }
+                        2 => {
+                            // Start sidecar
+                            let result = start_sidecar();
+                            commands.push(Message::SidecarActionCompleted {
+                                success: result.is_ok(),
+                                message: result.unwrap_or_else(|e| e),
+                            });
+                        }
+                        _ => {}
+                    }
+                }
+            }
+
+            Message::SidecarModalUpdateStatus { connection_status, process_count, build_timestamp } => {
+                if let Some(ref mut modal) = self.model.ui_state.sidecar_modal {
+                    modal.connection_status = connection_status;
+                    modal.process_count = process_count;
+                    modal.build_timestamp = build_timestamp;
+                }
+            }
+
+            Message::SidecarModalSetActionStatus(status) => {
+                if let Some(ref mut modal) = self.model.ui_state.sidecar_modal {
+                    modal.action_status = status;
+                }
+            }
+
+            Message::SidecarActionCompleted { success, message } => {
+                use crate::model::SidecarConnectionStatus;
+                use crate::sidecar::SidecarClient;
+
+                if let Some(ref mut modal) = self.model.ui_state.sidecar_modal {
+                    modal.action_in_progress = false;
+                    modal.action_status = Some(if success {
+                        format!("✓ {}", message)
+                    } else {
+                        format!("✗ {}", message)
+                    });
+
+                    // Refresh status after action
+                    let connection_status = if SidecarClient::is_available() {
+                        if let Ok(client) = SidecarClient::connect() {
+                            if client.ping().is_ok() {
+                                SidecarConnectionStatus::Connected
+                            } else {
+                                SidecarConnectionStatus::Unresponsive
+                            }
+                        } else {
+                            SidecarConnectionStatus::Unresponsive
+                        }
+                    } else {
+                        SidecarConnectionStatus::NotRunning
+                    };
+
+                    modal.connection_status = connection_status;
+                    modal.process_count = count_sidecar_processes();
+                    modal.build_timestamp = get_sidecar_build_timestamp();
+                }
+            }
+
+            Message::SuperviseSidecar => {
+                let Some(sender) = self.async_sender.clone() else {
+                    return commands;
+                };
+                let health = &mut self.model.ui_state.sidecar_health;
+                if self.safe_mode || health.in_flight {
+                    return commands;
+                }
+                health.in_flight = true;
+                if health.status != crate::model::SidecarConnectionStatus::Connected {
+                    health.status = crate::model::SidecarConnectionStatus::Checking;
+                }
+                tokio::spawn(async move {
+                    let check = tokio::task::spawn_blocking(crate::sidecar::check_and_restart).await
+                        .unwrap_or_else(|e| crate::sidecar::HealthCheck::Down(format!("Task panicked: {}", e)));
+                    let _ = sender.send(Message::SidecarSupervised(check));
+                });
+            }
+
+            Message::StartSidecar => {
+                let Some(sender) = self.async_sender.clone() else {
+                    return commands;
+                };
+                let health = &mut self.model.ui_state.sidecar_health;
+                if self.safe_mode || health.in_flight {
+                    return commands;
+                }
+                health.in_flight = true;
+                health.status = crate::model::SidecarConnectionStatus::Checking;
+                tokio::spawn(async move {
+                    let started = tokio::task::spawn_blocking(crate::sidecar::ensure_sidecar_running).await
+                        .map_err(|e| format!("Task panicked: {}", e))
+                        .and_then(|started| started.map(|_| ()).map_err(|e| e.to_string()));
+                    let _ = sender.send(Message::SidecarStarted(started));
+                });
+            }
+
+            Message::SidecarStarted(result) => {
+                use crate::model::{SidecarConnectionStatus, SidecarHealth};
+
+                let now = std::time::Instant::now();
+                let health = &mut self.model.ui_state.sidecar_health;
+                health.in_flight = false;
+                match result {
+                    Ok(()) => {
+                        health.status = SidecarConnectionStatus::Connected;
+                        health.next_check = now + SidecarHealth::CHECK_INTERVAL;
+                        self.sidecar_client = SidecarClient::connect().ok();
+                        self.sidecar_resubscribe = true;
+                        // The watcher lives in the sidecar; the setting is global since
+                        // project.watcher_enabled isn't persisted
+                        if self.model.global_settings.mascot_advice_enabled == Some(true) {
+                            commands.push(Message::StartWatcher);
+                        }
+                    }
+                    Err(e) => {
+                        // Supervision retries with backoff and reports it
+                        health.status = SidecarConnectionStatus::NotRunning;
+                        health.next_check = now;
+                        crate::diagnostics::warn(crate::diagnostics::Source::Sidecar, format!("Sidecar didn't start: {}", e));
+                    }
+                }
+            }
+
+            Message::SidecarSupervised(check) => {
+                use crate::model::{SidecarConnectionStatus, SidecarHealth};
+                use crate::sidecar::HealthCheck;
+
+                self.model.ui_state.sidecar_health.in_flight = false;
+                let now = std::time::Instant::now();
+                match check {
+                    HealthCheck::Healthy => {
+                        // Restarted from elsewhere (the sidecar modal, a shell): the
+                        // sidecar answers, but not on our old connection
+                        let reconnect = self.sidecar_client.as_ref().is_none_or(|c| c.ping().is_err());
+                        let health = &mut self.model.ui_state.sidecar_health;
+                        let was_down = health.status != SidecarConnectionStatus::Connected;
+                        health.status = SidecarConnectionStatus::Connected;
+                        health.failures = 0;
+                        health.next_check = now + SidecarHealth::CHECK_INTERVAL;
+                        if reconnect {
+                            commands.extend(self.reconnect_sidecar());
+                        }
+                        if was_down {
+                            commands.push(Message::SetStatusMessage(Some("Sidecar connected".to_string())));
+                        }
+                    }
+                    HealthCheck::Restarted => {
+                        let health = &mut self.model.ui_state.sidecar_health;
+                        health.status = SidecarConnectionStatus::Connected;
+                        health.failures = 0;
+                        health.next_check = now + SidecarHealth::CHECK_INTERVAL;
+                        commands.extend(self.reconnect_sidecar());
+                        commands.push(Message::SetStatusMessage(Some(
+                            "Sidecar restarted - reattaching SDK sessions".to_string()
+                        )));
+                    }
+                    HealthCheck::Down(e) => {
+                        let health = &mut self.model.ui_state.sidecar_health;
+                        health.status = SidecarConnectionStatus::NotRunning;
+                        health.failures += 1;
+                        health.next_check = now + SidecarHealth::backoff(health.failures);
+                        // Report the first failure; the status bar shows the rest
+                        if health.failures == 1 {
+                            commands.push(Message::Error(format!("Sidecar is down, retrying: {}", e)));
+                        }
+                    }
+                }
+            }
+
+            Message::SidecarSessionsReattached(results) => {
+                for (task_id, result) in results {
+                    let Some(task) = self.model.projects.iter_mut()
+                        .flat_map(|p| p.tasks.iter_mut())
+                        .find(|t| t.id == task_id)
+                    else {
+                        continue;
+                    };
+                    match result {
+                        Ok(session_id) => {
+                            task.claude_session_id = Some(session_id);
+                            task.sdk_command_count = task.sdk_command_count.saturating_add(1);
+                            task.log_activity("Sidecar restarted, session reattached");
+                        }
+                        Err(e) => {
+                            task.log_activity(format!("Sidecar restarted, couldn't reattach session: {}", e));
+                        }
+                    }
+                }
+            }
+
+            Message::ToggleAutoMerge(task_id) => {
+                let Some(task) = self.model.projects.iter_mut()
+                    .flat_map(|p| p.tasks.iter_mut())
+                    .find(|t| t.id == task_id)
+                else {
+                    return commands;
+                };
+                if task.status != TaskStatus::Review {
+                    commands.push(Message::SetStatusMessage(Some(
+                        "Auto-merge is for tasks in Review".to_string()
+                    )));
+                    return commands;
+                }
+                task.auto_merge = !task.auto_merge;
+                if task.auto_merge {
+                    task.log_activity("Auto-merge on: waiting for CI to pass");
+                    commands.push(Message::SetStatusMessage(Some(
+                        "Auto-merge on - merges once CI passes".to_string()
+                    )));
+                    commands.push(Message::PollCi);
+                } else {
+                    task.log_activity("Auto-merge off");
+                    commands.push(Message::SetStatusMessage(Some("Auto-merge off".to_string())));
+                }
+            }
+
+            Message::PollCi => {
+                if self.model.ui_state.ci_polling {
+                    return commands;
+                }
+                // (task, project dir, branch, waiting to auto-merge, commit whose checks are settled)
+                type Target = (uuid::Uuid, PathBuf, String, bool, Option<String>);
+                let targets: Vec<Target> = self.model.projects.iter()
+                    .flat_map(|p| p.tasks.iter()
+                        .filter(|t| t.watches_ci())
+                        .map(move |t| (
+                            t.id,
+                            p.working_dir.clone(),
+                            t.git_branch.clone().unwrap_or_else(|| format!("claude/{}", t.display_id())),
+                            t.auto_merge && t.status == TaskStatus::Review,
+                            t.ci_status.as_ref().filter(|s| s.is_settled()).map(|s| s.sha.clone()),
+                        )))
+                    .collect();
+                if targets.is_empty() {
+                    return commands;
+                }
+                let Some(sender) = self.async_sender.clone() else {
+                    return commands;
+                };
+                self.model.ui_state.ci_polling = true;
+                self.model.ui_state.ci_polled_at = Some(std::time::Instant::now());
+                tokio::spawn(async move {
+                    let result = tokio::task::spawn_blocking(move || {
+                        targets.into_iter()
+                            .filter_map(|(task_id, project_dir, branch, waiting_to_merge, settled)| {
+                                let status = match crate::ci::remote_branch_sha(&project_dir, &branch) {
+                                    // Nothing new pushed since the checks finished
+                                    Some(sha) if settled.as_ref() == Some(&sha) && !waiting_to_merge => return None,
+                                    Some(sha) => crate::ci::poll(&project_dir, &branch, &sha).map_err(|e| e.to_string()),
+                                    None if waiting_to_merge => Err(format!("Branch {} isn't pushed to origin", branch)),
+                                    None => return None,
+                                };
+                                Some((task_id, status))
+                            })
+                            .collect()
+                    }).await;
+                    let _ = sender.send(Message::CiPolled(result.unwrap_or_default()));
+                });
+            }
+
+            Message::CiPolled(results) => {
+                self.model.ui_state.ci_polling = false;
+                for (task_id, result) in results {
+                    commands.extend(self.apply_ci_status(task_id, result));
+                }
+            }
+
+            Message::ChatPromptPosted { task_id, result } => {
+                let Some(task) = self.model.projects.iter_mut()
+                    .flat_map(|p| p.tasks.iter_mut())
+                    .find(|t| t.id == task_id)
+                else {
+                    return commands;
+                };
+                match result {
+                    // Answered while the prompt was being posted
+                    Ok(_) if task.status != TaskStatus::NeedsWork => {}
+                    Ok(message_id) => {
+                        task.chat_thread = Some(crate::chat::ChatThread { message_id, posted_at: Utc::now() });
+                        task.log_activity("Posted to chat, waiting for a reply");
+                    }
+                    Err(e) => commands.push(Message::SetStatusMessage(Some(format!(
+                        "Couldn't post {} to chat: {}", task.display_id(), e
+                    )))),
+                }
+            }
+
+            Message::PollChat => {
+                if self.model.ui_state.chat_polling {
+                    return commands;
+                }
+                let targets: Vec<(uuid::Uuid, crate::chat::ChatBridge, crate::chat::ChatThread)> = self.model.projects.iter()
+                    .filter_map(|p| Some((p, p.chat_bridge.as_deref().and_then(crate::chat::ChatBridge::parse)?)))
+                    .flat_map(|(p, bridge)| p.tasks.iter()
+                        .filter(|t| t.status == TaskStatus::NeedsWork)
+                        .filter_map(move |t| Some((t.id, bridge.clone(), t.chat_thread.clone()?))))
+                    .collect();
+                if targets.is_empty() {
+                    return commands;
+                }
+                let Some(sender) = self.async_sender.clone() else {
+                    return commands;
+                };
+                self.model.ui_state.chat_polling = true;
+                self.model.ui_state.chat_polled_at = Some(std::time::Instant::now());
+                tokio::spawn(async move {
+                    let result = tokio::task::spawn_blocking(move || {
+                        targets.into_iter()
+                            .map(|(task_id, bridge, thread)| (task_id, bridge.replies(&thread).map_err(|e| format!("{:#}", e))))
+                            .collect()
+                    }).await;
+                    let _ = sender.send(Message::ChatPolled(result.unwrap_or_default()));
+                });
+            }
+
+            Message::ChatPolled(results) => {
+                self.model.ui_state.chat_polling = false;
+                for (task_id, result) in results {
+                    let Some(project) = self.model.projects.iter_mut()
+                        .find(|p| p.tasks.iter().any(|t| t.id == task_id))
+                    else {
+                        continue;
+                    };
+                    let bridge = project.chat_bridge.as_deref().and_then(crate::chat::ChatBridge::parse);
+                    let Some(task) = project.tasks.iter_mut().find(|t| t.id == task_id) else {
+                        continue;
+                    };
+                    let replies = match result {
+                        Ok(replies) => replies,
+                        Err(e) => {
+                            commands.push(Message::SetStatusMessage(Some(format!("Chat poll failed: {}", e))));
+                            continue;
+                        }
+                    };
+                    if replies.is_empty() || task.status != TaskStatus::NeedsWork {
+                        continue;
+                    }
+                    let Some(thread) = task.chat_thread.take() else {
+                        continue;
+                    };
+                    let authors: Vec<&str> = replies.iter().map(|r| r.author.as_str()).collect();
+                    task.log_activity(format!("Reply from {} in chat", authors.join(", ")));
+                    let feedback = replies.iter().map(|r| r.text.as_str()).collect::<Vec<_>>().join("\n\n");
+                    // Let whoever answered know it went through
+                    if let (Some(bridge), Some(sender)) = (bridge, self.async_sender.clone()) {
+                        let ack = format!("Sent to {}", task.display_id());
+                        tokio::spawn(async move {
+                            let posted = tokio::task::spawn_blocking(move || bridge.post(&ack, Some(&thread))).await;
+                            if let Ok(Err(e)) = posted {
+                                let _ = sender.send(Message::SetStatusMessage(Some(format!("Couldn't reply in chat: {:#}", e))));
+                            }
+                        });
+                    }
+                    commands.push(Message::DoSendFeedback { task_id, feedback });
+                }
+            }
 
-                self.model.ui_state.sidecar_modal = Some(SidecarModalState {
-                    connection_status,
-                    process_count,
-                    build_timestamp,
-                    selected_action: 0,
-                    action_status: None,
-                    action_in_progress: false,
+            Message::RunTaskCommand { task_id, command } => {
+                let target = self.model.projects.iter()
+                    .find_map(|p| Some((p, p.tasks.iter().find(|t| t.id == task_id)?)));
+                let Some((project, task)) = target else {
+                    return commands;
+                };
+                let Some(command) = command.or_else(|| project.commands.effective_test(&project.scope_dir())) else {
+                    commands.push(Message::SetStatusMessage(Some(format!(
+                        "No test command for {} - set one in the project settings", project.name
+                    ))));
+                    return commands;
+                };
+                let Some(sender) = self.async_sender.clone() else {
+                    return commands;
+                };
+                let dir = project.scoped(task.worktree_path.as_ref().unwrap_or(&project.working_dir));
+                commands.push(Message::SetStatusMessage(Some(format!("Running `{}` for {}...", command, task.display_id()))));
+                tokio::spawn(async move {
+                    let result = tokio::task::spawn_blocking({
+                        let command = command.clone();
+                        move || crate::rules::run_command(&dir, &command)
+                    }).await.unwrap_or_else(|e| Err(e.to_string()));
+                    let _ = sender.send(Message::TaskCommandFinished { task_id, command, result });
                 });
             }
 
-            Message::CloseSidecarModal => {
-                self.model.ui_state.sidecar_modal = None;
+            Message::TaskCommandFinished { task_id, command, result } => {
+                let Some(task) = self.model.projects.iter_mut()
+                    .flat_map(|p| p.tasks.iter_mut())
+                    .find(|t| t.id == task_id)
+                else {
+                    return commands;
+                };
+                let (outcome, output) = match result {
+                    Ok(output) => ("passed", output),
+                    Err(output) => ("failed", output),
+                };
+                task.log_activity_with_output(format!("`{}` {}", command, outcome), Some(output));
+                commands.push(Message::SetStatusMessage(Some(format!("{}: `{}` {}", task.display_id(), command, outcome))));
             }
 
-            Message::SidecarModalNavigate(delta) => {
-                if let Some(ref mut modal) = self.model.ui_state.sidecar_modal {
-                    let new_idx = (modal.selected_action as i32 + delta).clamp(0, 2) as usize;
-                    modal.selected_action = new_idx;
-                }
+            Message::RunPostMergeScript(task_id) => {
+                let Some(project) = self.model.projects.iter()
+                    .find(|p| p.tasks.iter().any(|t| t.id == task_id))
+                else {
+                    return commands;
+                };
+                let (Some(command), Some(sender)) = (project.commands.post_merge.clone(), self.async_sender.clone()) else {
+                    return commands;
+                };
+                let dir = project.scope_dir();
+                commands.push(Message::SetStatusMessage(Some(format!("Running post-merge script `{}`...", command))));
+                tokio::spawn(async move {
+                    let result = tokio::task::spawn_blocking({
+                        let command = command.clone();
+                        move || crate::rules::run_command(&dir, &command)
+                    }).await.unwrap_or_else(|e| Err(e.to_string()));
+                    let stage = crate::model::ScriptStage::PostMerge;
+                    let _ = sender.send(Message::LifecycleScriptFinished { task_id, stage, command, result });
+                });
             }
 
-            Message::SidecarModalExecuteAction => {
-                if let Some(ref mut modal) = self.model.ui_state.sidecar_modal {
-                    if modal.action_in_progress {
-                        return commands;
-                    }
+            Message::LifecycleScriptFinished { task_id, stage, command, result } => {
+                use crate::model::ScriptStage;
 
-                    modal.action_in_progress = true;
-                    modal.action_status = Some("Working...".to_string());
+                let Some(project) = self.model.projects.iter_mut()
+                    .find(|p| p.tasks.iter().any(|t| t.id == task_id))
+                else {
+                    return commands;
+                };
+                let Some(task) = project.tasks.iter_mut().find(|t| t.id == task_id) else {
+                    return commands;
+                };
+                let passed = result.is_ok();
+                let (outcome, output) = match result {
+                    Ok(output) => ("passed", output),
+                    Err(output) => ("failed", output),
+                };
+                task.log_activity_with_output(format!("{} `{}` {}", stage.label(), command, outcome), Some(output.clone()));
+                let display_id = task.display_id();
 
-                    match modal.selected_action {
-                        0 => {
-                            // Kill sidecar
-                            let result = kill_sidecar_processes();
-                            commands.push(Message::SidecarActionCompleted {
-                                success: result.is_ok(),
-                                message: result.unwrap_or_else(|e| e),
-                            });
-                        }
-                        1 => {
-                            // Compile sidecar (npm run build)
-                            let result = compile_sidecar();
-                            commands.push(Message::SidecarActionCompleted {
-                                success: result.is_ok(),
-                                message: result.unwrap_or_else(|e| e),
-                            });
-                        }
-                        2 => {
-                            // Start sidecar
-                            let result = start_sidecar();
-                            commands.push(Message::SidecarActionCompleted {
-                                success: result.is_ok(),
-                                message: result.unwrap_or_else(|e| e),
-                            });
+                match (stage, passed) {
+                    (_, true) => {
+                        commands.push(Message::SetStatusMessage(Some(format!(
+                            "{}: {} `{}` passed", display_id, stage.label(), command
+                        ))));
+                    }
+                    (ScriptStage::PreStart, false) => {
+                        commands.push(Message::SdkSessionFailed {
+                            task_id,
+                            error: format!("`{}` failed", command),
+                            phase: crate::model::StartPhase::PreStart,
+                            output: Some(output),
+                        });
+                    }
+                    (ScriptStage::PostMerge, false) => {
+                        // The merge stands, but the task isn't done until main passes the script
+                        task.status = TaskStatus::NeedsWork;
+                        task.completed_at = None;
+                        project.needs_attention = true;
+                        if self.model.ui_state.merge_celebration.as_ref().is_some_and(|c| c.task_id == task_id) {
+                            self.model.ui_state.merge_celebration = None;
                         }
-                        _ => {}
+                        commands.push(Message::Error(format!(
+                            "{}: post-merge script `{}` failed - moved to Needs Work (output in the task's activity)",
+                            display_id, command
+                        )));
                     }
                 }
             }
 
-            Message::SidecarModalUpdateStatus { connection_status, process_count, build_timestamp } => {
-                if let Some(ref mut modal) = self.model.ui_state.sidecar_modal {
-                    modal.connection_status = connection_status;
-                    modal.process_count = process_count;
-                    modal.build_timestamp = build_timestamp;
+            Message::DeployPreview(task_id) => {
+                let Some(project) = self.model.projects.iter_mut()
+                    .find(|p| p.tasks.iter().any(|t| t.id == task_id))
+                else {
+                    return commands;
+                };
+                let Some(command) = project.commands.preview.clone() else {
+                    commands.push(Message::SetStatusMessage(Some(
+                        "No preview command set for this project (Ctrl+S to add one)".to_string()
+                    )));
+                    return commands;
+                };
+                let scope = project.scope.clone();
+                let Some(task) = project.tasks.iter_mut().find(|t| t.id == task_id) else {
+                    return commands;
+                };
+                let (Some(worktree_path), Some(sender)) = (task.worktree_path.clone(), self.async_sender.clone()) else {
+                    return commands;
+                };
+                if task.status != TaskStatus::Review || task.preview_deploying {
+                    return commands;
                 }
+                task.preview_deploying = true;
+                let dir = match scope {
+                    Some(scope) => worktree_path.join(scope),
+                    None => worktree_path,
+                };
+                commands.push(Message::SetStatusMessage(Some(format!(
+                    "{}: deploying preview with `{}`...", task.display_id(), command
+                ))));
+                tokio::spawn(async move {
+                    let result = tokio::task::spawn_blocking({
+                        let command = command.clone();
+                        move || crate::rules::run_command(&dir, &command)
+                    }).await.unwrap_or_else(|e| Err(e.to_string()));
+                    let _ = sender.send(Message::PreviewDeployed { task_id, command, result });
+                });
             }
 
-            Message::SidecarModalSetActionStatus(status) => {
-                if let Some(ref mut modal) = self.model.ui_state.sidecar_modal {
-                    modal.action_status = status;
-                }
+            Message::PreviewDeployed { task_id, command, result } => {
+                let Some(task) = self.model.projects.iter_mut()
+                    .flat_map(|p| p.tasks.iter_mut())
+                    .find(|t| t.id == task_id)
+                else {
+                    return commands;
+                };
+                task.preview_deploying = false;
+                let display_id = task.display_id();
+                let status = match result {
+                    Ok(output) => match crate::links::deployed_url(&output).map(str::to_string) {
+                        Some(url) => {
+                            task.log_activity_with_output(format!("Preview deployed to {}", url), Some(output));
+                            task.preview_url = Some(url.clone());
+                            format!("{}: preview at {} (y in the details copies it)", display_id, url)
+                        }
+                        None => {
+                            task.log_activity_with_output(format!("Preview `{}` printed no URL", command), Some(output));
+                            format!("{}: `{}` finished but printed no URL (output in the task's activity)", display_id, command)
+                        }
+                    },
+                    Err(output) => {
+                        task.log_activity_with_output(format!("Preview `{}` failed", command), Some(output));
+                        format!("{}: preview `{}` failed (output in the task's activity)", display_id, command)
+                    }
+                };
+                commands.push(Message::SetStatusMessage(Some(status)));
             }
 
-            Message::SidecarActionCompleted { success, message } => {
-                use crate::model::SidecarConnectionStatus;
-                use crate::sidecar::SidecarClient;
+            Message::CopyPreviewUrl(task_id) => {
+                let url = self.model.projects.iter()
+                    .flat_map(|p| p.tasks.iter())
+                    .find(|t| t.id == task_id)
+                    .and_then(|t| t.preview_url.clone());
+                if let Some(url) = url {
+                    let status = match crate::image::copy_text_to_clipboard(&url) {
+                        Ok(()) => format!("Copied {}", url),
+                        Err(e) => format!("Failed to copy preview URL: {}", e),
+                    };
+                    commands.push(Message::SetStatusMessage(Some(status)));
+                }
+            }
 
-                if let Some(ref mut modal) = self.model.ui_state.sidecar_modal {
-                    modal.action_in_progress = false;
-                    modal.action_status = Some(if success {
-                        format!("✓ {}", message)
-                    } else {
-                        format!("✗ {}", message)
-                    });
+            Message::PluginActions { project_dir, task_id, plugin, result } => {
+                use crate::plugin::PluginAction;
 
-                    // Refresh status after action
-                    let connection_status = if SidecarClient::is_available() {
-                        if let Ok(client) = SidecarClient::connect() {
-                            if client.ping().is_ok() {
-                                SidecarConnectionStatus::Connected
-                            } else {
-                                SidecarConnectionStatus::Unresponsive
+                let actions = match result {
+                    Ok(actions) => actions,
+                    Err(e) => {
+                        commands.push(Message::SetStatusMessage(Some(format!("Plugin {} failed: {}", plugin, e))));
+                        return commands;
+                    }
+                };
+                let Some(project) = self.model.projects.iter_mut().find(|p| p.working_dir == project_dir) else {
+                    return commands;
+                };
+                for action in actions {
+                    // Actions on a task name it by display id or UUID; the event's task by default
+                    let target = match &action {
+                        PluginAction::AddTask { .. } => None,
+                        PluginAction::SetTag { task, .. } | PluginAction::Comment { task, .. } => Some(task.as_deref()),
+                    };
+                    let task = target.and_then(|target| project.tasks.iter_mut().find(|t| match target {
+                        Some(id) => t.id.to_string() == id || t.display_id().eq_ignore_ascii_case(id),
+                        None => t.id == task_id,
+                    }));
+                    match action {
+                        PluginAction::AddTask { title } => {
+                            let title = title.trim().to_string();
+                            if title.is_empty() {
+                                continue;
+                            }
+                            let mut task = Task::new(title);
+                            task.log_activity(format!("Added by plugin {}", plugin));
+                            // Seen now, so it doesn't raise a created event of its own
+                            task.record_status_change();
+                            if task.title.len() > 40 {
+                                commands.push(Message::RequestTitleSummary { task_id: task.id });
+                            }
+                            project.tasks.insert(0, task);
+                            commands.push(Message::SetStatusMessage(Some(format!(
+                                "Plugin {} added a task to {}", plugin, project.name
+                            ))));
+                        }
+                        PluginAction::SetTag { tag, .. } => {
+                            if let Some(task) = task {
+                                if task.add_label(&tag) {
+                                    task.log_activity(format!("Plugin {} labeled it #{}", plugin, tag.trim_start_matches('#')));
+                                }
                             }
-                        } else {
-                            SidecarConnectionStatus::Unresponsive
                         }
-                    } else {
-                        SidecarConnectionStatus::NotRunning
-                    };
+                        PluginAction::Comment { text, .. } => {
+                            if let (Some(task), false) = (task, text.trim().is_empty()) {
+                                task.activity_notes.push(crate::model::ActivityLogEntry::note(
+                                    format!("{}: {}", plugin, text.trim())
+                                ));
+                            }
+                        }
+                    }
+                }
+            }
 
-                    modal.connection_status = connection_status;
-                    modal.process_count = count_sidecar_processes();
-                    modal.build_timestamp = get_sidecar_build_timestamp();
+            Message::AutoMergePrMerged { task_id, pr, result } => {
+                match result {
+                    Ok(()) => commands.extend(self.finish_remote_merge(task_id, pr)),
+                    Err(e) => {
+                        if let Some(task) = self.model.projects.iter_mut()
+                            .flat_map(|p| p.tasks.iter_mut())
+                            .find(|t| t.id == task_id)
+                        {
+                            task.auto_merge = false;
+                            task.log_activity(format!("Auto-merge of PR #{} failed: {}", pr, e));
+                        }
+                        notify::play_cue(notify::SoundCue::MergeFailed);
+                        commands.push(Message::Error(format!("Couldn't merge PR #{}: {}", pr, e)));
+                    }
                 }
             }
 
@@ -8125,7 +14480,7 @@ Do not ask for permission - run tests and fix any issues you find."#);
                 // Get the project directory to scan for .md files
                 if let Some(project) = self.model.active_project() {
                     let project_dir = project.working_dir.clone();
-                    let md_files = scan_markdown_files(&project_dir);
+                    let md_files = scan_repo_files(&project_dir, Some("md"));
 
                     if md_files.is_empty() {
                         self.model.ui_state.status_message = Some("No .md files found in repository".to_string());
@@ -8175,8 +14530,14 @@ Do not ask for permission - run tests and fix any issues you find."#);
                 let file_to_load = self.model.ui_state.md_file_picker
                     .as_ref()
                     .and_then(|p| p.selected_file().cloned());
+                let attach = self.model.ui_state.md_file_picker.as_ref().is_some_and(|p| p.attach);
 
-                if let Some(relative_path) = file_to_load {
+                if attach {
+                    // Attach picker: hand the file over instead of loading it
+                    if let (Some(relative_path), Some(project)) = (file_to_load, self.model.active_project()) {
+                        commands.push(Message::AttachFiles(vec![project.working_dir.join(relative_path)]));
+                    }
+                } else if let Some(relative_path) = file_to_load {
                     if let Some(project) = self.model.active_project() {
                         let full_path = project.working_dir.join(&relative_path);
                         match std::fs::read_to_string(&full_path) {
@@ -8205,6 +14566,84 @@ Do not ask for permission - run tests and fix any issues you find."#);
                 // Close picker even if no selection
                 self.model.ui_state.md_file_picker = None;
             }
+
+            Message::ToggleSnippetPicker => {
+                if self.model.ui_state.snippet_picker.is_some() {
+                    self.model.ui_state.snippet_picker = None;
+                } else if self.model.ui_state.feedback_task_id.is_some() {
+                    self.model.ui_state.snippet_picker = Some(0);
+                }
+            }
+
+            Message::SnippetPickerMove(delta) => {
+                let count = self.model.active_project().map_or(0, |p| p.snippets().len());
+                if let Some(ref mut cursor) = self.model.ui_state.snippet_picker {
+                    if count > 0 {
+                        *cursor = (*cursor as i32 + delta).clamp(0, count as i32 - 1) as usize;
+                    }
+                }
+            }
+
+            Message::OpenLinkPicker(task_id) => {
+                let links = self.model.projects.iter()
+                    .flat_map(|p| p.tasks.iter())
+                    .find(|t| t.id == task_id)
+                    .map(|t| t.links())
+                    .unwrap_or_default();
+                match links.as_slice() {
+                    [] => commands.push(Message::SetStatusMessage(Some(
+                        "No links for this task (PR, preview, CI run or URLs in its description)".to_string(),
+                    ))),
+                    [(_, url)] => commands.push(Message::OpenUrl(url.clone())),
+                    _ => self.model.ui_state.link_picker = Some((task_id, 0)),
+                }
+            }
+
+            Message::LinkPickerMove(delta) => {
+                if let Some((task_id, ref mut cursor)) = self.model.ui_state.link_picker {
+                    let count = self.model.projects.iter()
+                        .flat_map(|p| p.tasks.iter())
+                        .find(|t| t.id == task_id)
+                        .map_or(0, |t| t.links().len());
+                    if count > 0 {
+                        *cursor = (*cursor as i32 + delta).clamp(0, count as i32 - 1) as usize;
+                    }
+                }
+            }
+
+            Message::CloseLinkPicker => {
+                self.model.ui_state.link_picker = None;
+            }
+
+            Message::OpenUrl(url) => {
+                let status = match crate::links::open(&url) {
+                    Ok(()) => format!("Opened {}", url),
+                    Err(e) => format!("Failed to open {}: {}", url, e),
+                };
+                commands.push(Message::SetStatusMessage(Some(status)));
+            }
+
+            Message::CopyUrl(url) => {
+                let status = match crate::image::copy_text_to_clipboard(&url) {
+                    Ok(()) => format!("Copied {}", url),
+                    Err(e) => format!("Failed to copy URL: {}", e),
+                };
+                commands.push(Message::SetStatusMessage(Some(status)));
+            }
+
+            Message::InsertFeedbackSnippet(idx) => {
+                self.model.ui_state.snippet_picker = None;
+                // Snippets only go into feedback, filled from the task receiving it
+                let text = self.model.ui_state.feedback_task_id.and_then(|task_id| {
+                    let project = self.model.active_project()?;
+                    let snippet = *project.snippets().get(idx)?;
+                    let task = project.tasks.iter().find(|t| t.id == task_id)?;
+                    Some(task.fill_snippet(snippet))
+                });
+                if let Some(text) = text {
+                    self.model.ui_state.insert_input_text(&text);
+                }
+            }
         }
 
         // Keep selected_task_id in sync with selected_task_idx
@@ -8223,15 +14662,30 @@ Do not ask for permission - run tests and fix any issues you find."#);
 }
 
 /// Scan a directory recursively for .md files, returning paths relative to the directory
-fn scan_markdown_files(dir: &PathBuf) -> Vec<PathBuf> {
+/// The attachments that are voice notes to transcribe
+fn audio_files(attachments: &[PathBuf]) -> Vec<PathBuf> {
+    attachments.iter()
+        .filter(|p| crate::transcribe::is_audio_file(p))
+        .cloned()
+        .collect()
+}
+
+/// A task as listed in broadcast confirmations and reports
+fn broadcast_label(task: &Task) -> String {
+    let title: String = task.title.lines().next().unwrap_or_default().chars().take(40).collect();
+    format!("{}  {}", task.display_id(), title)
+}
+
+/// Files under `dir` (relative paths), optionally only those with `extension`
+fn scan_repo_files(dir: &PathBuf, extension: Option<&str>) -> Vec<PathBuf> {
     let mut files = Vec::new();
-    scan_markdown_files_recursive(dir, dir, &mut files);
+    scan_repo_files_recursive(dir, dir, extension, &mut files);
     // Sort files by path for consistent display
     files.sort();
     files
 }
 
-fn scan_markdown_files_recursive(base_dir: &PathBuf, current_dir: &PathBuf, files: &mut Vec<PathBuf>) {
+fn scan_repo_files_recursive(base_dir: &PathBuf, current_dir: &PathBuf, extension: Option<&str>, files: &mut Vec<PathBuf>) {
     let read_dir = match std::fs::read_dir(current_dir) {
         Ok(rd) => rd,
         Err(_) => return,
@@ -8248,15 +14702,16 @@ fn scan_markdown_files_recursive(base_dir: &PathBuf, current_dir: &PathBuf, file
 
         if path.is_dir() {
             // Recurse into subdirectories
-            scan_markdown_files_recursive(base_dir, &path, files);
+            scan_repo_files_recursive(base_dir, &path, extension, files);
         } else if path.is_file() {
-            // Check if it's a .md file
-            if let Some(ext) = path.extension() {
-                if ext.eq_ignore_ascii_case("md") {
-                    // Store relative path
-                    if let Ok(relative) = path.strip_prefix(base_dir) {
-                        files.push(relative.to_path_buf());
-                    }
+            // Check the extension when filtering (e.g. only .md files)
+            let matches = extension.is_none_or(|wanted| {
+                path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case(wanted))
+            });
+            if matches {
+                // Store relative path
+                if let Ok(relative) = path.strip_prefix(base_dir) {
+                    files.push(relative.to_path_buf());
                 }
             }
         }
@@ -8445,10 +14900,23 @@ pub fn default_state_file_path() -> PathBuf {
         .join("state.json")
 }
 
+/// Directory of per-project state files belonging to a global state file
+pub fn project_state_dir(state_file: &Path) -> PathBuf {
+    let stem = state_file.file_stem().unwrap_or_default().to_string_lossy();
+    state_file.with_file_name(format!("{}-projects", stem))
+}
+
+fn project_state_file(state_file: &Path, project_id: uuid::Uuid) -> PathBuf {
+    project_state_dir(state_file).join(format!("{}.json", project_id))
+}
+
 /// Load application state from disk
-/// If custom_path is provided, uses that file; otherwise uses the default location
+/// If custom_path is provided, uses that file; otherwise uses the default location.
+/// Only the active project is read in full; the others stay index entries until
+/// they're activated (see `load_project_state`).
 pub fn load_state(custom_path: Option<&PathBuf>) -> Result<AppModel> {
-    use crate::model::ProjectTaskData;
+    use crate::model::ProjectIndexEntry;
+    use anyhow::Context;
 
     let state_file = match custom_path {
         Some(path) => path.clone(),
@@ -8457,17 +14925,69 @@ pub fn load_state(custom_path: Option<&PathBuf>) -> Result<AppModel> {
 
     if state_file.exists() {
         let content = std::fs::read_to_string(&state_file)?;
-        let mut model: AppModel = serde_json::from_str(&content)?;
-
-        // Load tasks from per-project files (with migration from global state)
-        for project in &mut model.projects {
-            let project_file = ProjectTaskData::file_path(&project.working_dir);
-            if project_file.exists() {
-                // New way: load from project directory
-                project.load_tasks();
+        let mut value: serde_json::Value = serde_json::from_str(&content)
+            .with_context(|| format!("{} is not valid JSON", state_file.display()))?;
+        // Upgrade older state (keeping a backup) and refuse state from a newer kanblam
+        crate::model::schema::upgrade_state(&state_file, &mut value)?;
+
+        let entries = value.as_object_mut()
+            .and_then(|object| object.remove("projects"))
+            .unwrap_or_else(|| serde_json::Value::Array(Vec::new()));
+        let mut model: AppModel = serde_json::from_value(value)
+            .with_context(|| format!("Failed to read {}", state_file.display()))?;
+
+        let serde_json::Value::Array(entries) = entries else {
+            anyhow::bail!("{} has no project list", state_file.display());
+        };
+        for entry in entries {
+            let index: ProjectIndexEntry = match serde_json::from_value(entry.clone()) {
+                Ok(index) => index,
+                Err(e) => {
+                    // Keep it as a closed tab showing the error; the entry is saved back as it was
+                    let text = |key: &str| entry[key].as_str().unwrap_or_default().to_string();
+                    let mut project = Project::new(text("name"), PathBuf::from(text("working_dir")));
+                    if let Ok(id) = uuid::Uuid::parse_str(&text("id")) {
+                        project.id = id;
+                    }
+                    project.loaded = false;
+                    let error = anyhow::Error::from(e).context(format!("Bad project entry in {}", state_file.display()));
+                    record_load_error(&mut project, &error);
+                    project.unreadable_entry = Some(entry);
+                    model.projects.push(project);
+                    continue;
+                }
+            };
+            // Route the remote project's files and commands over SSH before anything reads them
+            if let Some(target) = &index.ssh_target {
+                crate::remote::register(&index.working_dir, target);
+            }
+            if project_state_file(&state_file, index.id).exists() {
+                model.projects.push(Project::from_index(index));
+            } else {
+                // Stored whole in the global file (before per-project files): move it out on save
+                let mut project = match serde_json::from_value::<Project>(entry.clone()) {
+                    Ok(project) => project,
+                    Err(e) => {
+                        let mut project = Project::from_index(index.clone());
+                        let error = anyhow::Error::from(e).context(format!("Failed to read project '{}'", index.name));
+                        record_load_error(&mut project, &error);
+                        project.unreadable_entry = Some(entry);
+                        model.projects.push(project);
+                        continue;
+                    }
+                };
+                project.loaded = true;
+                // Its tasks file is protected by the recorded error; the other boards still load
+                if let Err(e) = load_project_tasks(&mut project) {
+                    record_load_error(&mut project, &e);
+                }
+                model.projects.push(project);
             }
-            // else: keep tasks from global state (migration path)
-            // They'll be saved to project dir on next save
+        }
+
+        // A project whose file can't be read stays closed; the others still load
+        if let Some(project) = model.projects.get_mut(model.active_project_idx) {
+            let _ = load_project_isolated(project, &state_file);
         }
 
         // Initialize UI state's vim mode from persisted global settings
@@ -8479,8 +14999,67 @@ pub fn load_state(custom_path: Option<&PathBuf>) -> Result<AppModel> {
     }
 }
 
+/// Read a project's own state file into its index entry, if that hasn't happened yet
+pub fn load_project_state(project: &mut Project, state_file: &Path) -> Result<()> {
+    use anyhow::Context;
+
+    if project.loaded {
+        return Ok(());
+    }
+    let path = project_state_file(state_file, project.id);
+    let content = std::fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    let mut loaded: Project = serde_json::from_str(&content)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    loaded.loaded = true;
+    load_project_tasks(&mut loaded)?;
+    *project = loaded;
+    Ok(())
+}
+
+/// `load_project_state`, recording a failure on the project (and in diagnostics)
+/// instead of letting it stop everything else
+fn load_project_isolated(project: &mut Project, state_file: &Path) -> Result<()> {
+    // An entry that couldn't be read has nothing to load (and was reported already)
+    if project.unreadable_entry.is_some() {
+        anyhow::bail!("{}", project.load_error.clone().unwrap_or_default());
+    }
+    let result = load_project_state(project, state_file);
+    if let Err(ref e) = result {
+        record_load_error(project, e);
+    }
+    result
+}
+
+/// Record why a project couldn't be loaded on it (and in diagnostics)
+fn record_load_error(project: &mut Project, error: &anyhow::Error) {
+    crate::diagnostics::report(
+        crate::diagnostics::Severity::Error,
+        crate::diagnostics::Source::Io,
+        format!("Couldn't load project: {:#}", error),
+        Some(project.name.clone()),
+    );
+    project.load_error = Some(format!("{:#}", error));
+}
+
+/// Prefer the tasks in the project's .kanblam directory over the copy in its state file
+fn load_project_tasks(project: &mut Project) -> Result<()> {
+    let project_file = crate::model::ProjectTaskData::file_path(&project.scope_dir());
+    if crate::remote::exists(&project_file) {
+        // Tasks saved by a newer kanblam would be overwritten on the next save
+        crate::model::schema::check_tasks_writable(&project_file)?;
+        project.load_tasks();
+        if let Some(error) = &project.load_error {
+            anyhow::bail!("{}", error);
+        }
+    }
+    Ok(())
+}
+
 /// Save application state to disk
-/// Also saves tasks to per-project .kanblam/tasks.json files
+/// The global file gets settings and the project index; each loaded project is written
+/// to its own state file and its tasks to .kanblam/tasks.json in the project directory.
+/// Projects that were never activated are left as they are on disk.
 /// If custom_path is provided, uses that file; otherwise uses the default location
 pub fn save_state(model: &AppModel, custom_path: Option<&PathBuf>) -> Result<()> {
     let state_file = match custom_path {
@@ -8488,33 +15067,73 @@ pub fn save_state(model: &AppModel, custom_path: Option<&PathBuf>) -> Result<()>
         None => default_state_file_path(),
     };
 
-    // Ensure parent directory exists
-    if let Some(parent) = state_file.parent() {
-        std::fs::create_dir_all(parent)?;
+    // Keep a copy of what's about to be overwritten (hourly at most)
+    if let Err(e) = crate::model::backup::backup_if_due(&state_file) {
+        crate::diagnostics::warn(crate::diagnostics::Source::Io, format!("Failed to back up state: {}", e));
     }
 
-    // Save tasks to each project's .kanblam directory
-    for project in &model.projects {
-        if let Err(e) = project.save_tasks() {
-            eprintln!("Warning: Failed to save tasks for {}: {}", project.name, e);
+    // Ensure the state directories exist
+    let project_dir = project_state_dir(&state_file);
+    std::fs::create_dir_all(&project_dir)?;
+
+    for project in model.projects.iter().filter(|p| p.loaded) {
+        // Save tasks to the project's .kanblam directory (tasks that couldn't be read stay as they are)
+        let saved = if project.load_error.is_none() { project.save_tasks() } else { Ok(()) };
+        if let Err(e) = saved {
+            crate::diagnostics::warn(crate::diagnostics::Source::Io, format!("Failed to save tasks for {}: {}", project.name, e));
         }
+        // Project state file (still includes tasks as a fallback copy)
+        let content = serde_json::to_string_pretty(project)?;
+        write_replacing(&project_state_file(&state_file, project.id), &content)?;
     }
 
-    // Save global state (still includes tasks for backwards compatibility,
-    // but we prefer loading from project dirs)
-    let content = serde_json::to_string_pretty(model)?;
-    std::fs::write(state_file, content)?;
+    let mut value = serde_json::to_value(model)?;
+    let index = model.projects.iter()
+        .map(|p| match &p.unreadable_entry {
+            Some(entry) => Ok(entry.clone()),
+            None => serde_json::to_value(p.index_entry()),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    value["projects"] = serde_json::Value::Array(index);
+    write_replacing(&state_file, &serde_json::to_string_pretty(&value)?)?;
+
+    // Drop state files of projects that were closed (and aren't in a workspace)
+    if let Ok(entries) = std::fs::read_dir(&project_dir) {
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let known = path.file_stem()
+                .and_then(|stem| uuid::Uuid::parse_str(&stem.to_string_lossy()).ok())
+                .is_some_and(|id| {
+                    model.projects.iter().any(|p| p.id == id)
+                        // Projects of unloaded workspaces come back with their state
+                        || model.global_settings.workspaces.iter().flat_map(|w| &w.projects).any(|p| p.id == id)
+                });
+            if !known && path.extension().is_some_and(|ext| ext == "json") {
+                let _ = std::fs::remove_file(path);
+            }
+        }
+    }
 
     Ok(())
 }
 
+/// Write a state file through a temporary file and a rename, so a crash
+/// mid-write leaves the previous file rather than half of the new one
+fn write_replacing(path: &Path, content: &str) -> std::io::Result<()> {
+    let mut temp = path.as_os_str().to_os_string();
+    temp.push(".tmp");
+    std::fs::write(&temp, content)?;
+    std::fs::rename(&temp, path)
+}
+
 /// Run a project's check command to verify applied changes compile
 /// Returns Ok(()) if check passes or no check command is configured,
 /// Err with error message if check fails
 pub fn run_project_check(project: &Project) -> Result<(), String> {
     use std::process::Command;
 
-    let check_cmd = project.commands.effective_check(&project.working_dir);
+    let check_dir = project.scope_dir();
+    let check_cmd = project.commands.effective_check(&check_dir);
 
     match check_cmd {
         None => Ok(()), // No check command configured or detected
@@ -8531,7 +15150,7 @@ pub fn run_project_check(project: &Project) -> Result<(), String> {
             // Run the check command
             let output = Command::new(program)
                 .args(args)
-                .current_dir(&project.working_dir)
+                .current_dir(&check_dir)
                 .output();
 
             match output {
@@ -8556,3 +15175,66 @@ pub fn run_project_check(project: &Project) -> Result<(), String> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unreadable_project_file_leaves_others_loadable() {
+        let dir = tempfile::tempdir().unwrap();
+        let state_file = dir.path().join("state.json");
+        let mut model = AppModel::default();
+        for name in ["broken", "fine"] {
+            let project_dir = dir.path().join(name);
+            std::fs::create_dir_all(&project_dir).unwrap();
+            model.projects.push(Project::new(name.to_string(), project_dir));
+        }
+        save_state(&model, Some(&state_file)).unwrap();
+
+        let broken = project_state_file(&state_file, model.projects[0].id);
+        std::fs::write(&broken, "{ not json").unwrap();
+        let mut loaded = load_state(Some(&state_file)).unwrap();
+        assert_eq!(loaded.projects.len(), 2);
+        assert!(!loaded.projects[0].loaded);
+        assert!(loaded.projects[0].load_error.is_some());
+
+        // Saving leaves the unreadable file for the user to fix
+        save_state(&loaded, Some(&state_file)).unwrap();
+        assert_eq!(std::fs::read_to_string(&broken).unwrap(), "{ not json");
+
+        load_project_isolated(&mut loaded.projects[1], &state_file).unwrap();
+        assert!(loaded.projects[1].loaded);
+        assert_eq!(loaded.projects[1].name, "fine");
+    }
+
+    #[test]
+    fn test_corrupt_tasks_and_index_entry_are_kept() {
+        let dir = tempfile::tempdir().unwrap();
+        let state_file = dir.path().join("state.json");
+        let mut model = AppModel::default();
+        let project_dir = dir.path().join("board");
+        std::fs::create_dir_all(&project_dir).unwrap();
+        model.projects.push(Project::new("board".to_string(), project_dir.clone()));
+        save_state(&model, Some(&state_file)).unwrap();
+
+        let tasks = crate::model::ProjectTaskData::file_path(&project_dir);
+        std::fs::write(&tasks, "{ not json").unwrap();
+        let mut state: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&state_file).unwrap()).unwrap();
+        state["projects"].as_array_mut().unwrap().push(serde_json::json!({ "name": "odd" }));
+        std::fs::write(&state_file, state.to_string()).unwrap();
+
+        let mut loaded = load_state(Some(&state_file)).unwrap();
+        assert_eq!(loaded.projects.len(), 2);
+        assert!(loaded.projects[0].load_error.is_some());
+        assert!(loaded.projects[1].load_error.is_some());
+        assert_eq!(loaded.projects[1].name, "odd");
+
+        // Neither the tasks file nor the odd entry is lost on save
+        loaded.projects[0].loaded = true;
+        save_state(&loaded, Some(&state_file)).unwrap();
+        assert_eq!(std::fs::read_to_string(&tasks).unwrap(), "{ not json");
+        let state: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&state_file).unwrap()).unwrap();
+        assert_eq!(state["projects"][1], serde_json::json!({ "name": "odd" }));
+    }
+}
@@ -0,0 +1,201 @@
+//! Append-only audit log of repo-mutating actions
+//!
+//! Every merge, commit to main, rebase, push, apply/unapply, branch or
+//! worktree deletion and task reset/delete is appended as one JSON line to
+//! `audit.jsonl` in kanblam's data directory, with the git SHAs before and
+//! after where there are any. The file is only ever appended to.
+//! `kanblam audit export` copies it out, optionally from a given date.
+
+use anyhow::{anyhow, Context, Result};
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::timing::TimedCommand;
+
+/// One audited action
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuditEntry {
+    pub timestamp: DateTime<Utc>,
+    /// What was done, e.g. "merge", "push", "delete_branch"
+    pub action: String,
+    pub project: PathBuf,
+    /// Display id of the task it was done for
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub task: Option<String>,
+    /// Branch, tag or path acted on
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target: Option<String>,
+    /// Commit the target pointed at before
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sha_before: Option<String>,
+    /// Commit the target points at after
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sha_after: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+}
+
+impl AuditEntry {
+    pub fn new(action: &str, project: &Path) -> Self {
+        Self {
+            timestamp: Utc::now(),
+            action: action.to_string(),
+            project: project.to_path_buf(),
+            task: None,
+            target: None,
+            sha_before: None,
+            sha_after: None,
+            detail: None,
+        }
+    }
+
+    pub fn with_task(mut self, display_id: &str) -> Self {
+        self.task = Some(display_id.to_string());
+        self
+    }
+
+    pub fn with_target(mut self, target: &str) -> Self {
+        self.target = Some(target.to_string());
+        self
+    }
+
+    pub fn with_shas(mut self, before: Option<String>, after: Option<String>) -> Self {
+        self.sha_before = before;
+        self.sha_after = after;
+        self
+    }
+
+    pub fn with_detail(mut self, detail: impl Into<String>) -> Self {
+        self.detail = Some(detail.into());
+        self
+    }
+}
+
+fn log_path() -> PathBuf {
+    dirs::data_local_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("kanblam")
+        .join("audit.jsonl")
+}
+
+/// Append an entry to the audit log. Best-effort: a log that can't be
+/// written never stops the action itself.
+pub fn record(entry: AuditEntry) {
+    if let Err(e) = append(&log_path(), &entry) {
+        crate::diagnostics::warn(crate::diagnostics::Source::Io, format!("Audit log write failed: {}", e));
+    }
+}
+
+fn append(path: &Path, entry: &AuditEntry) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let mut line = serde_json::to_string(entry)?;
+    line.push('\n');
+    let mut file = std::fs::OpenOptions::new().create(true).append(true).open(path)?;
+    // One write per line, so concurrent appends don't interleave
+    file.write_all(line.as_bytes())?;
+    Ok(())
+}
+
+/// Commit `rev` resolves to in `dir`, for the before/after SHAs
+pub fn rev(dir: &Path, rev: &str) -> Option<String> {
+    let output = Command::new("git")
+        .current_dir(dir)
+        .args(["rev-parse", "--verify", "--quiet", rev])
+        .timed_output()
+        .ok()?;
+    output.status.success().then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Copy the entries at or after `since` from `log` to `out`, returning how many
+fn export(log: &Path, since: Option<DateTime<Utc>>, out: &mut dyn Write) -> Result<usize> {
+    let file = match std::fs::File::open(log) {
+        Ok(file) => file,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(0),
+        Err(e) => return Err(e).context("Failed to open audit log"),
+    };
+    let mut count = 0;
+    for line in BufReader::new(file).lines() {
+        let line = line?;
+        let Ok(entry) = serde_json::from_str::<AuditEntry>(&line) else {
+            continue;
+        };
+        if since.is_some_and(|since| entry.timestamp < since) {
+            continue;
+        }
+        writeln!(out, "{}", line)?;
+        count += 1;
+    }
+    Ok(count)
+}
+
+/// `kanblam audit export [--since YYYY-MM-DD] [--output FILE]`
+pub fn main(args: &[String]) -> Result<()> {
+    const USAGE: &str = "Usage: kanblam audit export [--since YYYY-MM-DD] [--output FILE]";
+    if args.first().map(String::as_str) != Some("export") {
+        return Err(anyhow!(USAGE));
+    }
+
+    let mut since = None;
+    let mut output = None;
+    let mut iter = args[1..].iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--since" => {
+                let date = iter.next().ok_or_else(|| anyhow!(USAGE))?;
+                let date = NaiveDate::parse_from_str(date, "%Y-%m-%d")
+                    .with_context(|| format!("Invalid date '{}' (expected YYYY-MM-DD)", date))?;
+                since = date.and_hms_opt(0, 0, 0).map(|t| t.and_utc());
+            }
+            "--output" => output = Some(PathBuf::from(iter.next().ok_or_else(|| anyhow!(USAGE))?)),
+            _ => return Err(anyhow!(USAGE)),
+        }
+    }
+
+    match output {
+        Some(path) => {
+            let mut file = std::fs::File::create(&path)
+                .with_context(|| format!("Failed to create {}", path.display()))?;
+            let count = export(&log_path(), since, &mut file)?;
+            eprintln!("Exported {} audit entries to {}", count, path.display());
+        }
+        None => {
+            export(&log_path(), since, &mut std::io::stdout().lock())?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_append_and_export_since() {
+        let dir = tempfile::tempdir().unwrap();
+        let log = dir.path().join("audit.jsonl");
+        let project = Path::new("/work/repo");
+
+        let mut old = AuditEntry::new("merge", project).with_task("TSKB-a7x");
+        old.timestamp = "2026-01-05T10:00:00Z".parse().unwrap();
+        append(&log, &old).unwrap();
+        let new = AuditEntry::new("push", project)
+            .with_target("main")
+            .with_shas(Some("abc".to_string()), Some("def".to_string()));
+        append(&log, &new).unwrap();
+
+        let mut out = Vec::new();
+        assert_eq!(export(&log, None, &mut out).unwrap(), 2);
+
+        let mut out = Vec::new();
+        let since = "2026-02-01T00:00:00Z".parse().unwrap();
+        assert_eq!(export(&log, Some(since), &mut out).unwrap(), 1);
+        let exported: AuditEntry = serde_json::from_slice(out.trim_ascii_end()).unwrap();
+        assert_eq!(exported, new);
+        assert!(!String::from_utf8(out).unwrap().contains("\"task\""));
+    }
+}
@@ -0,0 +1,120 @@
+//! Which external integrations are available on this machine
+//!
+//! tmux, the claude CLI and clipboard access are probed once at startup (and
+//! again from the integrations panel); the sidecar is available whenever it's
+//! connected. Features that need a missing integration show an inline banner
+//! saying so instead of silently doing nothing.
+
+use std::process::Command;
+
+/// An external integration a feature depends on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Capability {
+    Tmux,
+    ClaudeCli,
+    Sidecar,
+    Clipboard,
+}
+
+impl Capability {
+    pub const ALL: [Capability; 4] = [Capability::Tmux, Capability::ClaudeCli, Capability::Sidecar, Capability::Clipboard];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Capability::Tmux => "tmux",
+            Capability::ClaudeCli => "claude CLI",
+            Capability::Sidecar => "sidecar",
+            Capability::Clipboard => "clipboard",
+        }
+    }
+
+    /// The features that stop working without it
+    pub fn feature(&self) -> &'static str {
+        match self {
+            Capability::Tmux => "interactive sessions",
+            Capability::ClaudeCli => "CLI sessions",
+            Capability::Sidecar => "SDK sessions",
+            Capability::Clipboard => "image paste",
+        }
+    }
+
+    /// How to make it available
+    pub fn fix_hint(&self) -> &'static str {
+        match self {
+            Capability::Tmux => "Install tmux (brew install tmux / apt install tmux) and run kanblam inside it",
+            Capability::ClaudeCli => "Install Claude Code (npm install -g @anthropic-ai/claude-code) or set CLAUDE_PATH",
+            Capability::Sidecar => "Build it with `npm run build` in sidecar/, then start it from the sidecar controls",
+            Capability::Clipboard => "Run under a desktop session (X11/Wayland) or a terminal with clipboard access",
+        }
+    }
+}
+
+/// What was found missing, with why
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Capabilities {
+    /// Probed integrations that aren't available (empty until probed)
+    missing: Vec<(Capability, String)>,
+}
+
+impl Capabilities {
+    /// Probe tmux, the claude CLI and the clipboard (blocking; run off the UI thread)
+    pub fn detect() -> Self {
+        let mut missing = Vec::new();
+        if !runs("tmux", &["-V"]) {
+            missing.push((Capability::Tmux, "tmux not found".to_string()));
+        }
+        let claude = std::env::var("CLAUDE_PATH").unwrap_or_else(|_| "claude".to_string());
+        if !runs(&claude, &["--version"]) {
+            missing.push((Capability::ClaudeCli, "claude CLI not found".to_string()));
+        }
+        if let Err(e) = arboard::Clipboard::new() {
+            missing.push((Capability::Clipboard, format!("no clipboard access ({})", e)));
+        }
+        Self { missing }
+    }
+
+    /// Why `capability` is unavailable, or None if it's there. The sidecar
+    /// isn't probed; `sidecar_connected` says whether it is.
+    pub fn unavailable(&self, capability: Capability, sidecar_connected: bool) -> Option<String> {
+        if capability == Capability::Sidecar {
+            return (!sidecar_connected).then(|| "sidecar not connected".to_string());
+        }
+        self.missing.iter().find(|(c, _)| *c == capability).map(|(_, why)| why.clone())
+    }
+
+    /// Inline banner for the first of `needed` that is unavailable
+    pub fn banner(&self, needed: &[Capability], sidecar_connected: bool) -> Option<String> {
+        needed.iter().find_map(|&capability| {
+            let why = self.unavailable(capability, sidecar_connected)?;
+            Some(format!("{} disabled: {} — press E to fix", capability.feature(), why))
+        })
+    }
+}
+
+fn runs(program: &str, args: &[&str]) -> bool {
+    Command::new(program)
+        .args(args)
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .status()
+        .map(|s| s.success())
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_banner_names_first_missing_capability() {
+        let caps = Capabilities { missing: vec![(Capability::Tmux, "tmux not found".to_string())] };
+        assert_eq!(
+            caps.banner(&[Capability::ClaudeCli, Capability::Tmux], true).as_deref(),
+            Some("interactive sessions disabled: tmux not found — press E to fix")
+        );
+        assert_eq!(caps.banner(&[Capability::ClaudeCli, Capability::Clipboard], true), None);
+        assert!(caps.banner(&[Capability::Sidecar], false).unwrap().starts_with("SDK sessions disabled"));
+        // Nothing is reported missing before the probe has run
+        assert_eq!(Capabilities::default().banner(&Capability::ALL[..3], true), None);
+    }
+}
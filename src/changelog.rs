@@ -0,0 +1,468 @@
+//! Changelog for the "what's new" screen shown once after an update
+//!
+//! Add a `Release` (newest first) for each version with user-visible changes.
+//! A feature with a `setting` gets a shortcut that opens the settings modal
+//! at that field.
+
+use crate::model::ConfigField;
+use crate::release::version::Version;
+
+/// Version of this build
+pub const CURRENT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// One user-visible change
+#[derive(Debug)]
+pub struct Feature {
+    pub title: &'static str,
+    pub summary: &'static str,
+    /// Setting that controls the feature, if any
+    pub setting: Option<ConfigField>,
+}
+
+#[derive(Debug)]
+pub struct Release {
+    pub version: &'static str,
+    pub features: &'static [Feature],
+}
+
+/// Newest release first
+pub const CHANGELOG: &[Release] = &[Release {
+    version: "0.1.0",
+    features: &[
+        Feature {
+            title: "Drag and drop cards",
+            summary: "Drag a card to another column with the mouse; the wheel scrolls columns, the task preview and modals.",
+            setting: None,
+        },
+        Feature {
+            title: "Focus timer",
+            summary: "Press B for a pomodoro timer in the status bar. New task starts can wait for the break.",
+            setting: Some(ConfigField::FocusMinutes),
+        },
+        Feature {
+            title: "Status bar clock",
+            summary: "Show the time of day in the status bar.",
+            setting: Some(ConfigField::StatusClock),
+        },
+        Feature {
+            title: "Unread markers",
+            summary: "Project tabs, Review cards and the Activity/Git tabs mark what changed since you last looked.",
+            setting: None,
+        },
+        Feature {
+            title: "Lighter redraws",
+            summary: "The screen is only redrawn when something changed, with a configurable idle rate.",
+            setting: Some(ConfigField::IdleFps),
+        },
+        Feature {
+            title: "Feedback snippets",
+            summary: "Ctrl+T in feedback mode picks a canned response, Alt+1-9 inserts one directly. {title}, {id} and {branch} are filled in.",
+            setting: Some(ConfigField::FeedbackSnippets),
+        },
+        Feature {
+            title: "Voice notes",
+            summary: "Attach an audio file to a task and its transcript is added to the description, via a command of your choice.",
+            setting: Some(ConfigField::TranscribeCommand),
+        },
+        Feature {
+            title: "Context restarts",
+            summary: "When a session's context is 85% full, continue in a fresh session seeded with a summary of the progress so far.",
+            setting: Some(ConfigField::ContextRestart),
+        },
+        Feature {
+            title: "Activity notes",
+            summary: "Press n in a task's Activity tab to add a timestamped note next to the session's own events.",
+            setting: None,
+        },
+        Feature {
+            title: "Saved activity logs",
+            summary: "Activity logs survive restarts within a per-task limit, with output kept in files on disk. Press w in the Activity tab to export one.",
+            setting: Some(ConfigField::ActivityRetention),
+        },
+        Feature {
+            title: "Status ignore patterns",
+            summary: "Leave generated paths such as dist/ or *.lock out of a project's diff stats and diffs.",
+            setting: Some(ConfigField::StatusIgnore),
+        },
+        Feature {
+            title: "Slow operation diagnostics",
+            summary: "Press ~ to see latency histograms and the slowest git commands, sidecar calls and redraws of this session.",
+            setting: None,
+        },
+        Feature {
+            title: "Session replay",
+            summary: "Press p in a task's Activity tab to step through its tool calls, output and status changes in order.",
+            setting: None,
+        },
+        Feature {
+            title: "Colorblind and monochrome palettes",
+            summary: "Pick a deuteranopia, protanopia or tritanopia safe palette, or monochrome with status shown by glyphs and text styles.",
+            setting: Some(ConfigField::Palette),
+        },
+        Feature {
+            title: "Live output pane",
+            summary: "Press L to tail the selected in-progress task's output beside the board, with pause and search.",
+            setting: None,
+        },
+        Feature {
+            title: "Board snapshots",
+            summary: "Press W to write the board, or w in the Git tab for a diff summary, as ANSI text and an SVG image to share.",
+            setting: None,
+        },
+        Feature {
+            title: "Handoff documents",
+            summary: "Press H in task details to write HANDOFF.md into the worktree (spec, changes, open criteria and questions, how to run) and copy it.",
+            setting: None,
+        },
+        Feature {
+            title: "Task monitor",
+            summary: "Press V to tile every in-progress task with a live capture of its terminal, and jump into any of them.",
+            setting: None,
+        },
+        Feature {
+            title: "Sidecar supervision",
+            summary: "A sidecar that stops answering is restarted with backoff and running SDK sessions are reattached; the status bar shows when it's down.",
+            setting: None,
+        },
+        Feature {
+            title: "Auto-merge when CI passes",
+            summary: "Press A on a Review task with a pushed branch to poll its CI and merge the PR (or merge locally) once checks pass, then clean up.",
+            setting: None,
+        },
+        Feature {
+            title: "Rebase conflict hand-off",
+            summary: "When updating to main hits conflicts, press H in the dialog to stop at the conflicts and have a Claude session resolve them and continue the rebase.",
+            setting: None,
+        },
+        Feature {
+            title: "Sound cues and quiet hours",
+            summary: "Turn on Sound Cues for a chime when a task reaches Review, a double beep when it needs input and a buzz when a merge fails; Quiet Hours keeps kanblam silent overnight.",
+            setting: Some(ConfigField::SoundCues),
+        },
+        Feature {
+            title: "Sidecar event journal",
+            summary: "The sidecar journals session events, and kanblam replays the ones it missed after a reconnect or restart, so no task completion gets lost.",
+            setting: None,
+        },
+        Feature {
+            title: "Worktree check at startup",
+            summary: "On launch, task worktrees that went missing, fell off git's list, sit on the wrong branch or were left mid-rebase are listed with a fix for each.",
+            setting: None,
+        },
+        Feature {
+            title: "Audit log",
+            summary: "Merges, pushes, rebases, applies, resets and deletes are appended to an audit log with task ids and git SHAs; export it with `kanblam audit export`.",
+            setting: None,
+        },
+        Feature {
+            title: "Instant hook signals",
+            summary: "Hooks hand their signals to kanblam over a unix socket instead of a file the watcher has to notice; files remain the fallback when kanblam isn't listening.",
+            setting: None,
+        },
+        Feature {
+            title: "Quick diff",
+            summary: "Press D on the board for a popup with a task's diff stat and first hunks, served from the diff cache; j/k step through the column for fast review triage.",
+            setting: None,
+        },
+        Feature {
+            title: "Remote sidecar",
+            summary: "Run the sidecar on another host or in a container: it also listens on TCP with KANBLAM_SIDECAR_LISTEN, and kanblam connects with KANBLAM_SIDECAR_ADDR and a shared KANBLAM_SIDECAR_TOKEN. Addresses other than loopback need KANBLAM_SIDECAR_ALLOW_REMOTE=1, as the connection isn't encrypted.",
+            setting: None,
+        },
+        Feature {
+            title: "Tool and subagent events",
+            summary: "Hooks now report each tool call, failed tool calls and subagent launches with their details, shown in the Activity tab; a finished tool after a permission prompt moves the task back to In Progress.",
+            setting: None,
+        },
+        Feature {
+            title: "Integration banners",
+            summary: "When tmux, the claude CLI, the sidecar or clipboard access is missing, the features that need it say so in an inline banner instead of doing nothing; press E for what's missing and how to fix it.",
+            setting: None,
+        },
+        Feature {
+            title: "Smarter idle detection",
+            summary: "Sessions that finished while kanblam was closed are found by weighing the pane's status line, CPU use, transcript activity and hook state, and only moved to Review when they agree.",
+            setting: None,
+        },
+        Feature {
+            title: "Column time limits",
+            summary: "Tasks that sit in a column too long (In Progress over 2h, Review over a day by default) turn red with their age, and kanblam chimes and the mascot nudges once when they cross the limit.",
+            setting: Some(ConfigField::SlaThresholds),
+        },
+        Feature {
+            title: "Focus sessions on a task",
+            summary: "B now asks which task to focus on and for how long. Other tasks stay quiet until the break, and the focused time is added to the task.",
+            setting: Some(ConfigField::FocusMinutes),
+        },
+        Feature {
+            title: "Time tracking",
+            summary: "Session work and focus blocks are logged as time entries on each task. Add or correct time with t/T in the task preview; totals show on cards, in stats and in activity exports.",
+            setting: None,
+        },
+        Feature {
+            title: "Any number of projects",
+            summary: "The project bar scrolls when tabs don't fit and shows how many are hidden; ) or a click on the count lists every project. Reorder tabs with Shift-←/→ or by dragging.",
+            setting: None,
+        },
+        Feature {
+            title: "Workspaces",
+            summary: "Save the open projects as a named workspace and switch between workspaces with Ctrl-O; projects outside the new workspace are unloaded and its own are opened. Start in one with --workspace <name>.",
+            setting: None,
+        },
+        Feature {
+            title: "Recent projects",
+            summary: "The open-project dialog lists recently opened projects and pinned favorites on keys 1-9 (Tab to pin or forget them), and / or ~ starts typing a path with Tab completion.",
+            setting: None,
+        },
+        Feature {
+            title: "Remote projects",
+            summary: "Press @ in the open-project dialog and type user@host:/path to run a project's git, worktrees and agents on another machine over SSH, with hook signals forwarded back.",
+            setting: None,
+        },
+        Feature {
+            title: "Webhooks",
+            summary: "POST task started, needs-input, completed and merged events to your own URLs (Slack, Discord, n8n), optionally filtered and with a payload template.",
+            setting: Some(ConfigField::Webhooks),
+        },
+        Feature {
+            title: "Chat approvals",
+            summary: "Post needs-input prompts to a Slack or Discord channel and answer them from your phone: replies go to the session as feedback.",
+            setting: Some(ConfigField::ChatBridge),
+        },
+        Feature {
+            title: "Board rules",
+            summary: "Automate the board in Lua (~/.config/kanblam/rules.lua): react to tasks being created or changing column by applying, merging, moving, tagging or running tests.",
+            setting: None,
+        },
+        Feature {
+            title: "Plugins",
+            summary: "Executables in ~/.config/kanblam/plugins/ get task created, started, review and merged events as JSON and can reply with tasks to add, labels and comments.",
+            setting: None,
+        },
+        Feature {
+            title: "Safe mode",
+            summary: "Start with --safe-mode to load only the saved board when an integration crashes at startup.",
+            setting: None,
+        },
+        Feature {
+            title: "Session layouts",
+            summary: "Choose the panes of a task's interactive tmux session per project: dev servers, test watchers or logs beside Claude, with sizes and the pane that gets the keyboard.",
+            setting: Some(ConfigField::SessionLayout),
+        },
+        Feature {
+            title: "Scratch shell",
+            summary: "A task's shell pane keeps its history, directory and exported variables between opens, and its latest commands show in the Activity tab.",
+            setting: None,
+        },
+        Feature {
+            title: "Copy mode",
+            summary: "Ctrl+[ in the interactive terminal freezes its output so you can select it with vim keys (v, V) and copy it with y.",
+            setting: None,
+        },
+        Feature {
+            title: "Scrollback capture",
+            summary: "Press c in a task's Activity tab to save its session's full scrollback to a file that outlives the window, and v to read it in a searchable pager.",
+            setting: None,
+        },
+        Feature {
+            title: "Broadcast feedback",
+            summary: "Press A in the In Progress column to send one message to every running task. You confirm the list first; busy sessions get it when they finish, and a report shows how each delivery went.",
+            setting: None,
+        },
+        Feature {
+            title: "Feedback queue",
+            summary: "Messages queued while Claude works are listed in the task's queue tab, where they can be edited, reordered or dropped before delivery. Cards show how many are waiting.",
+            setting: None,
+        },
+        Feature {
+            title: "Draft autosave",
+            summary: "A half-written task description is saved as you type and restored after a restart or crash. Up at an empty input recalls earlier submissions.",
+            setting: None,
+        },
+        Feature {
+            title: "Slash commands",
+            summary: "/tag, /model, /base, /template and /start in the task input set up the new task instead of becoming part of its text. Tab completes them.",
+            setting: None,
+        },
+        Feature {
+            title: "Markdown preview",
+            summary: "Ctrl+P shows the task input rendered as markdown beside it; P in the task preview renders the description and spec, with headings, lists, code blocks and links.",
+            setting: None,
+        },
+        Feature {
+            title: "Spell check",
+            summary: "Misspelled words in the task input are underlined using a hunspell dictionary for the language set in settings. The input border shows word and character counts.",
+            setting: Some(ConfigField::SpellCheck),
+        },
+        Feature {
+            title: "Text paste",
+            summary: "Ctrl+V pastes clipboard text as well as images, and pasted newlines no longer submit the task. Long pastes ask first and can go in as a text file; copied files can be attached instead of pasted as paths.",
+            setting: None,
+        },
+        Feature {
+            title: "Snapshot mode",
+            summary: "Folders that aren't git repositories can be opened in snapshot mode (s when asked to initialize git). A shadow repository outside the folder gives tasks their own worktrees, diffs, apply and merge.",
+            setting: None,
+        },
+        Feature {
+            title: "Monorepo scopes",
+            summary: "Open a directory inside a repository (like apps/web) to get a project tab scoped to it: its own tasks, commands and prompts limited to that path, sharing the repository's worktrees with the other scopes.",
+            setting: None,
+        },
+        Feature {
+            title: "Task environment",
+            summary: "/env KEY=VALUE in the new-task input gives the task's Claude sessions and tmux panes an environment variable. KEY=secret:NAME takes the value from the OS keychain; store secrets with `kanblam secret set NAME`.",
+            setting: None,
+        },
+        Feature {
+            title: "Lifecycle scripts",
+            summary: "Projects can run a pre-start script in each new worktree before Claude starts (npm install, direnv allow) and a post-merge script on main (cargo fmt && cargo test). Output goes to the task's activity; a failed pre-start keeps the task from starting and a failed post-merge moves it to Needs Work.",
+            setting: Some(ConfigField::PreStartScript),
+        },
+        Feature {
+            title: "Shared caches",
+            summary: "New worktrees can start with the main checkout's node_modules, .venv or target instead of installing from scratch: symlinked, or hard-linked as a copy with :copy. Directories git doesn't ignore, or whose lockfile changed, are skipped; /fresh starts a task without them.",
+            setting: Some(ConfigField::SharedCaches),
+        },
+        Feature {
+            title: "Task ports",
+            summary: "Each task with a worktree gets its own block of ten ports, exported to its sessions and panes as $PORT and $KANBLAM_PORT_RANGE, so parallel dev servers don't collide. The card shows the address; the ports are freed when the task is done.",
+            setting: None,
+        },
+        Feature {
+            title: "Preview deploys",
+            summary: "Set a preview command (vercel deploy, flyctl deploy) and press b on a Review task to deploy its worktree. The URL it prints is kept on the task and shown in the General tab, where y copies it.",
+            setting: Some(ConfigField::PreviewCommand),
+        },
+        Feature {
+            title: "Task links",
+            summary: "U lists a task's pull request, preview deploy, CI run, issue links and dev server, and opens the one you pick in the browser (y copies it). URLs on screen are clickable in terminals that support hyperlinks.",
+            setting: None,
+        },
+        Feature {
+            title: "CI status",
+            summary: "Pushed task branches are polled on GitHub (gh) or GitLab (glab): the card shows whether CI is queued, running, passed or failed, the Git tab lists the jobs, and a notification plays when a run finishes.",
+            setting: None,
+        },
+        Feature {
+            title: "File history",
+            summary: "Press v in a task's Git tab for the History view: each changed file's latest commits on main, who wrote it, and a warning when main changed it since the task branched off or it has uncommitted edits in the main checkout.",
+            setting: None,
+        },
+        Feature {
+            title: "Review comments",
+            summary: "In a task's Git tab, c comments on the diff line at the top of the view and F sends the comments to Claude as feedback (\"in src/foo.rs line 120: ...\"). They show under their lines and are marked resolved once Claude's turn ends.",
+            setting: None,
+        },
+        Feature {
+            title: "Change summaries",
+            summary: "Press S in a task's Git tab to have the changes summarized: what changed, the risky areas and the files that need a human look. The summary is kept on the task, shown above the diff, and flagged once the branch moves on.",
+            setting: None,
+        },
+        Feature {
+            title: "Commit messages",
+            summary: "Merges can be committed with a message written from the task's title, spec and diff. With Commit Messages set to Ask (the default) it opens in an editor before the merge goes ahead; Always merges with it, Never keeps \"Merge task ... from Claude session\".",
+            setting: Some(ConfigField::CommitMessages),
+        },
+        Feature {
+            title: "Watcher feed",
+            summary: "Z lists the watcher's past comments for the project with their insights and the tasks made from them. 1-6 mute a category (bugs, security, feature ideas, UX, praise, refactors) so the watcher stops looking for it; m in an insight mutes its category.",
+            setting: None,
+        },
+        Feature {
+            title: "Watcher scope",
+            summary: "Under Mascot Advice, each project can point the watcher at the tasks in some columns (and their branches), keep it to git log and diff stats instead of file contents, give it its own interval, and give it a persona to speak as.",
+            setting: Some(ConfigField::WatcherColumns),
+        },
+        Feature {
+            title: "Start failures",
+            summary: "When a task fails to start, it stays in Planned marked \"start failed\" with its worktree kept. C shows which step failed, the error and the pre-start script's output; s retries in the same worktree and x throws it away and starts fresh.",
+            setting: None,
+        },
+        Feature {
+            title: "Problems in diagnostics",
+            summary: "Errors and warnings from git, tmux, the sidecar, hooks and file access are kept for the session. A ! badge in the status bar counts new ones; ~ lists them with where they happened, and y copies them.",
+            setting: None,
+        },
+        Feature {
+            title: "Debug log",
+            summary: "kanblam now keeps a debug log file (rotated at 5 MB) that records every hook signal and the task it reached. Press ` to read it in the app, filtered by level and module, following new lines as they arrive.",
+            setting: None,
+        },
+        Feature {
+            title: "State backups",
+            summary: "The saved board is backed up hourly (the ten newest are kept) before a save replaces it. If it can't be read at startup, kanblam offers the newest backup that loads instead of stopping.",
+            setting: None,
+        },
+        Feature {
+            title: "State sync",
+            summary: "Set a git remote and the board follows you across machines: it is committed to branch kanblam-state and pushed on exit, then pulled and merged at the next start. Tasks edited on two machines keep the newest copy.",
+            setting: Some(ConfigField::StateSync),
+        },
+        Feature {
+            title: "Project isolation",
+            summary: "A project whose state file can't be read no longer stops kanblam. Its tab explains what went wrong and loads again when you switch back, and the other projects work as usual. State files are replaced in one step, so a crash mid-save can't leave half a file.",
+            setting: None,
+        },
+        Feature {
+            title: "Faster startup",
+            summary: "The board appears before kanblam checks tmux, git and the sidecar. Those checks now run in the background, and cards show a spinner until their git status is in. The diagnostics (~) show how long each startup phase took.",
+            setting: None,
+        },
+        Feature {
+            title: "Fewer tmux calls",
+            summary: "Checking which task windows exist and what runs in them now takes a single tmux call shared by all tasks, instead of one per task, so boards with many tasks stay light.",
+            setting: None,
+        },
+        Feature {
+            title: "Less idle CPU",
+            summary: "kanblam now sleeps until a key press, a hook signal or a sidecar event arrives instead of waking ten times a second, and reacts to each the moment it lands. Animations still run at full speed while something on screen moves.",
+            setting: None,
+        },
+        Feature {
+            title: "Narrow terminal layouts",
+            summary: "Below 100 columns the board lists its columns beside the selected one, and below 60 it stacks them with only the selected column open. Arrow keys, clicks and drag and drop follow the layout. Pick one yourself in settings.",
+            setting: Some(ConfigField::BoardLayout),
+        },
+        Feature {
+            title: "Column sorting and tag groups",
+            summary: "Each project can sort its columns by creation time, last activity, priority (#p0, #p1... labels) or time in the column, and group tasks under their first #label. Manual order with +/- stays the default.",
+            setting: Some(ConfigField::ColumnSort),
+        },
+    ],
+}];
+
+fn sort_key(version: &str) -> Option<(u64, u64, u64)> {
+    Version::parse(version).map(|v| (v.major, v.minor, v.patch))
+}
+
+/// Releases newer than `last_seen` up to this build, newest first.
+/// `None` (never recorded) shows only this build's release.
+pub fn releases_since(last_seen: Option<&str>) -> Vec<&'static Release> {
+    let current = sort_key(CURRENT_VERSION);
+    let seen = last_seen.and_then(sort_key);
+    CHANGELOG.iter()
+        .filter(|release| {
+            let key = sort_key(release.version);
+            key <= current && match seen {
+                Some(seen) => key > Some(seen),
+                None => release.version == CURRENT_VERSION,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_releases_since() {
+        assert!(releases_since(Some(CURRENT_VERSION)).is_empty());
+        assert!(releases_since(Some("0.0.1")).iter().any(|r| r.version == CURRENT_VERSION));
+        assert!(releases_since(None).iter().all(|r| r.version == CURRENT_VERSION));
+        // Changelog stays newest first
+        let keys: Vec<_> = CHANGELOG.iter().map(|r| sort_key(r.version)).collect();
+        assert!(keys.windows(2).all(|w| w[0] > w[1]));
+    }
+}
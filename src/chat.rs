@@ -0,0 +1,265 @@
+//! Needs-input prompts answered from a Slack or Discord channel
+//!
+//! With a chat bridge set for a project, a task that stops to ask something is
+//! posted to the channel with the end of its output. Replies in the message's
+//! thread (Slack) or to the message (Discord) are sent to the session as
+//! feedback, so agents can be unblocked from a phone. The setting names the
+//! service, the channel id and the bot token, preferably as an environment
+//! variable so it isn't saved with the project:
+//!
+//!   slack C0123ABCD $SLACK_BOT_TOKEN
+//!   discord 112233445566778899 $DISCORD_BOT_TOKEN
+//!
+//! Waiting threads are polled with `curl` off the UI thread.
+
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+/// How often threads of waiting tasks are checked for replies
+pub const POLL_INTERVAL: std::time::Duration = std::time::Duration::from_secs(20);
+
+/// Seconds a request may take
+const TIMEOUT_SECS: u32 = 10;
+
+/// Characters of session output posted with a prompt (Discord caps messages at 2000)
+const OUTPUT_TAIL_CHARS: usize = 1500;
+
+const DISCORD_API: &str = "https://discord.com/api/v10";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChatService {
+    Slack,
+    Discord,
+}
+
+/// A project's channel for needs-input prompts
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChatBridge {
+    pub service: ChatService,
+    pub channel: String,
+    /// Bot token, or "$NAME" to read it from the environment
+    token: String,
+}
+
+/// A posted prompt whose replies are waited for
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChatThread {
+    /// Slack message timestamp or Discord message id
+    pub message_id: String,
+    pub posted_at: DateTime<Utc>,
+}
+
+/// A person's answer to a prompt
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChatReply {
+    pub author: String,
+    pub text: String,
+}
+
+impl ChatBridge {
+    /// Parse the setting: "slack|discord <channel id> <token or $ENV_VAR>"
+    pub fn parse(spec: &str) -> Option<Self> {
+        let mut parts = spec.split_whitespace();
+        let service = match parts.next()?.to_lowercase().as_str() {
+            "slack" => ChatService::Slack,
+            "discord" => ChatService::Discord,
+            _ => return None,
+        };
+        let channel = parts.next()?;
+        let token = parts.next()?;
+        if parts.next().is_some() || !channel.chars().all(|c| c.is_ascii_alphanumeric()) {
+            return None;
+        }
+        Some(Self { service, channel: channel.to_string(), token: token.to_string() })
+    }
+
+    /// The setting with a literal token hidden, for display
+    pub fn masked(&self) -> String {
+        let service = match self.service {
+            ChatService::Slack => "slack",
+            ChatService::Discord => "discord",
+        };
+        let token = if self.token.starts_with('$') { self.token.as_str() } else { "••••" };
+        format!("{} {} {}", service, self.channel, token)
+    }
+
+    fn token(&self) -> Result<String> {
+        match self.token.strip_prefix('$') {
+            Some(var) => std::env::var(var).map_err(|_| anyhow!("${} isn't set", var)),
+            None => Ok(self.token.clone()),
+        }
+    }
+
+    /// The prompt posted for a task that needs input
+    pub fn prompt_text(&self, project: &str, task_id: &str, title: &str, output: &str) -> String {
+        let skip = output.chars().count().saturating_sub(OUTPUT_TAIL_CHARS);
+        let tail: String = output.chars().skip(skip).collect();
+        let (header, hint) = match self.service {
+            ChatService::Slack => (format!("*{}* {} needs input ({})", task_id, title, project), "Reply in this thread to answer."),
+            ChatService::Discord => (format!("**{}** {} needs input ({})", task_id, title, project), "Reply to this message to answer."),
+        };
+        let tail = tail.trim().replace("```", "'''");
+        if tail.is_empty() {
+            format!("{}\n{}", header, hint)
+        } else {
+            format!("{}\n```\n{}\n```\n{}", header, tail, hint)
+        }
+    }
+
+    /// Post `text`, in reply to `thread` if given. Returns the new message's id.
+    /// Blocks until curl is done, so call it off the UI thread.
+    pub fn post(&self, text: &str, thread: Option<&ChatThread>) -> Result<String> {
+        match self.service {
+            ChatService::Slack => {
+                let mut body = json!({ "channel": self.channel, "text": text });
+                if let Some(thread) = thread {
+                    body["thread_ts"] = json!(thread.message_id);
+                }
+                let response = slack_result(self.request("https://slack.com/api/chat.postMessage", Some(&body))?)?;
+                response["ts"].as_str().map(str::to_string).ok_or_else(|| anyhow!("Slack returned no message timestamp"))
+            }
+            ChatService::Discord => {
+                let mut body = json!({ "content": text });
+                if let Some(thread) = thread {
+                    body["message_reference"] = json!({ "message_id": thread.message_id });
+                }
+                let url = format!("{}/channels/{}/messages", DISCORD_API, self.channel);
+                let response = discord_result(self.request(&url, Some(&body))?)?;
+                response["id"].as_str().map(str::to_string).ok_or_else(|| anyhow!("Discord returned no message id"))
+            }
+        }
+    }
+
+    /// Replies people have made to `thread`, oldest first
+    pub fn replies(&self, thread: &ChatThread) -> Result<Vec<ChatReply>> {
+        match self.service {
+            ChatService::Slack => {
+                let url = format!(
+                    "https://slack.com/api/conversations.replies?channel={}&ts={}&limit=100",
+                    self.channel, thread.message_id,
+                );
+                Ok(slack_replies(&slack_result(self.request(&url, None)?)?, &thread.message_id))
+            }
+            ChatService::Discord => {
+                let url = format!(
+                    "{}/channels/{}/messages?after={}&limit=100",
+                    DISCORD_API, self.channel, thread.message_id,
+                );
+                Ok(discord_replies(&discord_result(self.request(&url, None)?)?, &thread.message_id))
+            }
+        }
+    }
+
+    /// GET `url`, or POST `body` to it. The token goes to curl on stdin so it
+    /// doesn't show up in the process list.
+    fn request(&self, url: &str, body: Option<&Value>) -> Result<Value> {
+        let scheme = match self.service {
+            ChatService::Slack => "Bearer",
+            ChatService::Discord => "Bot",
+        };
+        let header = format!("Authorization: {} {}\n", scheme, self.token()?);
+        let mut command = Command::new("curl");
+        command.args(["-sS", "-m", &TIMEOUT_SECS.to_string(), "-H", "@-"]);
+        if let Some(body) = body {
+            command.args(["-H", "Content-Type: application/json; charset=utf-8", "--data-binary", &body.to_string()]);
+        }
+        let mut child = command
+            .arg(url)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| anyhow!("Failed to run curl: {}", e))?;
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin.write_all(header.as_bytes())?;
+        }
+        let output = child.wait_with_output()?;
+        if !output.status.success() {
+            return Err(anyhow!("{}", String::from_utf8_lossy(&output.stderr).trim()));
+        }
+        serde_json::from_slice(&output.stdout).map_err(|e| anyhow!("Unexpected response: {}", e))
+    }
+}
+
+/// Slack answers 200 with `ok: false` and an error code on failure
+fn slack_result(response: Value) -> Result<Value> {
+    if response["ok"].as_bool() == Some(true) {
+        Ok(response)
+    } else {
+        Err(anyhow!("Slack: {}", response["error"].as_str().unwrap_or("request failed")))
+    }
+}
+
+/// Discord errors are objects with a `message` and a numeric `code`
+fn discord_result(response: Value) -> Result<Value> {
+    match (response["message"].as_str(), response["code"].as_u64()) {
+        (Some(message), Some(_)) => Err(anyhow!("Discord: {}", message)),
+        _ => Ok(response),
+    }
+}
+
+/// Thread messages other than the prompt itself and bot posts
+fn slack_replies(response: &Value, thread_ts: &str) -> Vec<ChatReply> {
+    response["messages"].as_array().into_iter().flatten()
+        .filter(|m| m["ts"].as_str() != Some(thread_ts) && m.get("bot_id").is_none())
+        .filter_map(|m| Some(ChatReply {
+            author: m["user"].as_str().unwrap_or("someone").to_string(),
+            text: m["text"].as_str().filter(|t| !t.trim().is_empty())?.to_string(),
+        }))
+        .collect()
+}
+
+/// Channel messages replying to the prompt, not from bots. Discord lists them
+/// newest first.
+fn discord_replies(response: &Value, message_id: &str) -> Vec<ChatReply> {
+    let mut replies: Vec<ChatReply> = response.as_array().into_iter().flatten()
+        .filter(|m| m["message_reference"]["message_id"].as_str() == Some(message_id))
+        .filter(|m| m["author"]["bot"].as_bool() != Some(true))
+        .filter_map(|m| Some(ChatReply {
+            author: m["author"]["username"].as_str().unwrap_or("someone").to_string(),
+            text: m["content"].as_str().filter(|t| !t.trim().is_empty())?.to_string(),
+        }))
+        .collect();
+    replies.reverse();
+    replies
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_bridge_and_pick_replies() {
+        let slack = ChatBridge::parse("slack C0123ABCD $SLACK_BOT_TOKEN").unwrap();
+        assert_eq!(slack.service, ChatService::Slack);
+        assert_eq!(slack.masked(), "slack C0123ABCD $SLACK_BOT_TOKEN");
+        let discord = ChatBridge::parse("Discord 1122 secret-token").unwrap();
+        assert_eq!(discord.masked(), "discord 1122 ••••");
+        assert_eq!(ChatBridge::parse("irc #dev token"), None);
+        assert_eq!(ChatBridge::parse("slack C0123ABCD"), None);
+        assert_eq!(ChatBridge::parse("slack C01/../x token"), None);
+
+        let thread = json!({ "ok": true, "messages": [
+            { "ts": "1.0", "bot_id": "B1", "text": "prompt" },
+            { "ts": "2.0", "user": "U1", "text": "use the v2 endpoint" },
+            { "ts": "3.0", "bot_id": "B1", "text": "Sent" },
+        ]});
+        assert_eq!(slack_replies(&thread, "1.0"), vec![ChatReply { author: "U1".into(), text: "use the v2 endpoint".into() }]);
+
+        let channel = json!([
+            { "id": "13", "content": "second", "author": { "username": "ana" }, "message_reference": { "message_id": "10" } },
+            { "id": "12", "content": "unrelated", "author": { "username": "bo" } },
+            { "id": "11", "content": "first", "author": { "username": "ana" }, "message_reference": { "message_id": "10" } },
+        ]);
+        let texts: Vec<String> = discord_replies(&channel, "10").into_iter().map(|r| r.text).collect();
+        assert_eq!(texts, vec!["first", "second"]);
+        assert!(discord_result(json!({ "message": "Missing Access", "code": 50001 })).is_err());
+
+        let prompt = discord.prompt_text("api", "API-a7x", "Fix login", "Should I drop the table?");
+        assert!(prompt.starts_with("**API-a7x** Fix login needs input (api)") && prompt.contains("drop the table"));
+    }
+}
@@ -0,0 +1,401 @@
+//! CI status of pushed task branches, read through the GitHub CLI (`gh`) or,
+//! for projects whose origin is on GitLab, the GitLab CLI (`glab`)
+//!
+//! On GitHub, a branch with an open pull request is checked through the PR's
+//! status check rollup and merged with `gh pr merge`. A pushed branch without
+//! one is checked through the check runs of its remote head commit. On GitLab,
+//! the jobs of the latest pipeline for the remote head commit are checked.
+
+use anyhow::{anyhow, Result};
+use serde_json::Value;
+use std::path::Path;
+use std::process::Command;
+
+use crate::timing::TimedCommand;
+use crate::worktree::git::git_stdout;
+
+/// How often pushed branches are polled
+pub const POLL_INTERVAL: std::time::Duration = std::time::Duration::from_secs(60);
+
+/// Polls of one push that may report no checks before it's taken as having
+/// none (checks can take a moment to show up after a push)
+const NO_CHECKS_POLLS: u32 = 3;
+
+/// Where a project's CI runs, going by its origin remote
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Provider {
+    GitHub,
+    GitLab,
+}
+
+impl Provider {
+    /// GitLab for origins on a gitlab host, GitHub otherwise
+    pub fn of(project_dir: &Path) -> Provider {
+        let origin = Command::new("git")
+            .current_dir(project_dir)
+            .args(["remote", "get-url", "origin"])
+            .output()
+            .map(|o| String::from_utf8_lossy(&o.stdout).to_lowercase())
+            .unwrap_or_default();
+        if origin.contains("gitlab") {
+            Provider::GitLab
+        } else {
+            Provider::GitHub
+        }
+    }
+
+    fn cli(&self) -> &'static str {
+        match self {
+            Provider::GitHub => "gh",
+            Provider::GitLab => "glab",
+        }
+    }
+}
+
+/// Combined state of a branch's checks
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CiState {
+    /// Checks waiting for a runner
+    Queued,
+    /// Checks running
+    Pending,
+    /// Every check passed (or was skipped)
+    Passed,
+    /// At least one check failed
+    Failed,
+    /// Nothing reported for the branch yet
+    NoChecks,
+}
+
+impl CiState {
+    pub fn label(&self) -> &'static str {
+        match self {
+            CiState::Queued => "queued",
+            CiState::Pending => "running",
+            CiState::Passed => "passed",
+            CiState::Failed => "failed",
+            CiState::NoChecks => "no checks",
+        }
+    }
+
+    /// Icon on the task's card (none when there are no checks)
+    pub fn glyph(&self) -> &'static str {
+        match self {
+            CiState::Queued => "◌",
+            CiState::Pending => "⧗",
+            CiState::Passed => "✓",
+            CiState::Failed => "✗",
+            CiState::NoChecks => "",
+        }
+    }
+}
+
+/// Pull request opened for a branch
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PullRequest {
+    pub number: u64,
+    pub url: String,
+    /// OPEN, CLOSED or MERGED
+    pub state: String,
+    /// Head commit of the pull request
+    pub head_sha: String,
+}
+
+/// CI status of a pushed branch
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CiStatus {
+    pub state: CiState,
+    /// (check name, state) for each check
+    pub checks: Vec<(String, CiState)>,
+    /// The branch's pull request, if it has one
+    pub pr: Option<PullRequest>,
+    /// Page of the first failed check, else of the first check
+    pub run_url: Option<String>,
+    /// Remote head commit the checks ran on
+    pub sha: String,
+    /// Times this commit has been polled in a row
+    pub polls: u32,
+}
+
+impl CiStatus {
+    fn new(items: &[Value], pr: Option<PullRequest>, sha: &str, parse: fn(&[Value]) -> Vec<(String, CiState)>) -> Self {
+        let checks = parse(items);
+        CiStatus {
+            state: combine(&checks),
+            run_url: run_url(items, &checks),
+            checks,
+            pr,
+            sha: sha.to_string(),
+            polls: 1,
+        }
+    }
+
+    /// Whether the checks of this commit are settled, so polling it again
+    /// can't change anything (only a new push can)
+    pub fn is_settled(&self) -> bool {
+        match self.state {
+            CiState::Passed | CiState::Failed => true,
+            CiState::NoChecks => self.polls >= NO_CHECKS_POLLS,
+            CiState::Queued | CiState::Pending => false,
+        }
+    }
+
+    /// Names of the failed checks
+    pub fn failed(&self) -> Vec<&str> {
+        self.checks.iter()
+            .filter(|(_, state)| *state == CiState::Failed)
+            .map(|(name, _)| name.as_str())
+            .collect()
+    }
+}
+
+/// Head commit of a branch on `origin` (None if it isn't pushed)
+pub fn remote_branch_sha(project_dir: &Path, branch: &str) -> Option<String> {
+    let output = Command::new("git")
+        .current_dir(project_dir)
+        .args(["ls-remote", "--heads", "origin", branch])
+        .timed_output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    String::from_utf8_lossy(&output.stdout)
+        .split_whitespace()
+        .next()
+        .map(str::to_string)
+}
+
+/// Why a task's local work isn't the commit `sha` that CI checked or the pull
+/// request merged: its branch has moved on, or its worktree has uncommitted
+/// edits. None when it is exactly that commit.
+pub fn local_divergence(project_dir: &Path, branch: &str, worktree: Option<&Path>, sha: &str) -> Option<String> {
+    let head = git_stdout(project_dir, &["rev-parse", "--verify", "--quiet", branch]).ok();
+    if head.as_deref().map(str::trim) != Some(sha) {
+        return Some(format!("{} isn't at the checked commit {}", branch, &sha[..sha.len().min(7)]));
+    }
+    let dirty = worktree.filter(|dir| dir.exists()).map(|dir| git_stdout(dir, &["status", "--porcelain"]));
+    match dirty {
+        Some(Ok(status)) if status.trim().is_empty() => None,
+        Some(Ok(_)) => Some("its worktree has uncommitted changes".to_string()),
+        Some(Err(e)) => Some(format!("couldn't check its worktree ({})", e)),
+        None => None,
+    }
+}
+
+/// CI status of a pushed branch at its remote head commit `sha`: on GitHub
+/// through its pull request when it has one, otherwise through the commit's
+/// check runs; on GitLab through the commit's latest pipeline
+pub fn poll(project_dir: &Path, branch: &str, sha: &str) -> Result<CiStatus> {
+    if Provider::of(project_dir) == Provider::GitLab {
+        return poll_gitlab(project_dir, branch, sha);
+    }
+
+    let github = Provider::GitHub;
+    if let Ok(pr) = cli_json(github, project_dir, &["pr", "view", branch, "--json", "number,url,state,headRefOid,statusCheckRollup"]) {
+        let items = pr["statusCheckRollup"].as_array().map(Vec::as_slice).unwrap_or_default();
+        let pr_info = PullRequest {
+            number: pr["number"].as_u64().unwrap_or_default(),
+            url: pr["url"].as_str().unwrap_or_default().to_string(),
+            state: pr["state"].as_str().unwrap_or_default().to_string(),
+            head_sha: pr["headRefOid"].as_str().unwrap_or_default().to_string(),
+        };
+        return Ok(CiStatus::new(items, Some(pr_info), sha, parse_checks));
+    }
+
+    let runs = cli_json(github, project_dir, &["api", &format!("repos/{{owner}}/{{repo}}/commits/{}/check-runs", sha)])?;
+    let items = runs["check_runs"].as_array().map(Vec::as_slice).unwrap_or_default();
+    Ok(CiStatus::new(items, None, sha, parse_checks))
+}
+
+/// Jobs of the latest GitLab pipeline for the branch at `sha`
+fn poll_gitlab(project_dir: &Path, branch: &str, sha: &str) -> Result<CiStatus> {
+    let gitlab = Provider::GitLab;
+    let branch = branch.replace('%', "%25").replace('/', "%2F");
+    let pipelines = cli_json(gitlab, project_dir, &[
+        "api", &format!("projects/:id/pipelines?ref={}&sha={}&per_page=1", branch, sha),
+    ])?;
+    let Some(id) = pipelines.get(0).and_then(|p| p["id"].as_u64()) else {
+        return Ok(CiStatus::new(&[], None, sha, parse_gitlab_jobs));
+    };
+    let jobs = cli_json(gitlab, project_dir, &["api", &format!("projects/:id/pipelines/{}/jobs?per_page=100", id)])?;
+    let items = jobs.as_array().map(Vec::as_slice).unwrap_or_default();
+    let mut status = CiStatus::new(items, None, sha, parse_gitlab_jobs);
+    if status.run_url.is_none() {
+        status.run_url = pipelines[0]["web_url"].as_str().map(str::to_string);
+    }
+    Ok(status)
+}
+
+/// Merge a branch's pull request on the remote
+pub fn merge_pr(project_dir: &Path, number: u64) -> Result<()> {
+    let output = Command::new("gh")
+        .current_dir(project_dir)
+        .args(["pr", "merge", &number.to_string(), "--merge"])
+        .timed_output()
+        .map_err(|e| anyhow!("Failed to run gh: {}", e))?;
+    if !output.status.success() {
+        return Err(anyhow!("gh pr merge failed: {}", String::from_utf8_lossy(&output.stderr).trim()));
+    }
+    crate::audit::record(crate::audit::AuditEntry::new("merge_pr", project_dir)
+        .with_target(&format!("#{}", number)));
+    Ok(())
+}
+
+fn cli_json(provider: Provider, project_dir: &Path, args: &[&str]) -> Result<Value> {
+    let cli = provider.cli();
+    let output = Command::new(cli)
+        .current_dir(project_dir)
+        .args(args)
+        .timed_output()
+        .map_err(|e| anyhow!("Failed to run {} (is the {:?} CLI installed?): {}", cli, provider, e))?;
+    if !output.status.success() {
+        return Err(anyhow!("{} {} failed: {}", cli, args[0], String::from_utf8_lossy(&output.stderr).trim()));
+    }
+    Ok(serde_json::from_slice(&output.stdout)?)
+}
+
+/// Checks from a PR's status check rollup (check runs and commit statuses)
+/// or from the check runs API
+fn parse_checks(items: &[Value]) -> Vec<(String, CiState)> {
+    items.iter()
+        .map(|item| {
+            let name = item["name"].as_str().or_else(|| item["context"].as_str()).unwrap_or("check");
+            let text = |key: &str| item[key].as_str().unwrap_or_default().to_uppercase();
+            let state = if item.get("status").is_some_and(|s| !s.is_null()) {
+                // Check run: still going until completed, then its conclusion counts
+                match (text("status").as_str(), text("conclusion").as_str()) {
+                    ("COMPLETED", "SUCCESS" | "NEUTRAL" | "SKIPPED") => CiState::Passed,
+                    ("COMPLETED", _) => CiState::Failed,
+                    ("QUEUED" | "PENDING" | "WAITING" | "REQUESTED", _) => CiState::Queued,
+                    _ => CiState::Pending,
+                }
+            } else {
+                // Commit status
+                match text("state").as_str() {
+                    "SUCCESS" => CiState::Passed,
+                    "FAILURE" | "ERROR" => CiState::Failed,
+                    _ => CiState::Pending,
+                }
+            };
+            (name.to_string(), state)
+        })
+        .collect()
+}
+
+/// Jobs of a GitLab pipeline
+fn parse_gitlab_jobs(items: &[Value]) -> Vec<(String, CiState)> {
+    items.iter()
+        .map(|item| {
+            let name = item["name"].as_str().unwrap_or("job");
+            let state = match item["status"].as_str().unwrap_or_default() {
+                // Manual jobs wait for someone to start them; they don't hold the pipeline
+                "success" | "skipped" | "manual" => CiState::Passed,
+                "failed" | "canceled" => CiState::Failed,
+                "running" => CiState::Pending,
+                _ => CiState::Queued,
+            };
+            (name.to_string(), state)
+        })
+        .collect()
+}
+
+/// Page of the first failed check, else of the first check with one
+/// (`detailsUrl`/`targetUrl` in a rollup, `html_url` from the check runs API,
+/// `web_url` for GitLab jobs)
+fn run_url(items: &[Value], checks: &[(String, CiState)]) -> Option<String> {
+    let url = |item: &Value| {
+        ["detailsUrl", "targetUrl", "html_url", "web_url"]
+            .iter()
+            .find_map(|key| item[*key].as_str().filter(|url| !url.is_empty()))
+            .map(str::to_string)
+    };
+    let failed = items.iter().zip(checks).filter(|(_, (_, state))| *state == CiState::Failed);
+    failed.filter_map(|(item, _)| url(item)).next().or_else(|| items.iter().find_map(url))
+}
+
+/// Combined state: failed if any check failed, running while any runs,
+/// queued while any waits
+fn combine(checks: &[(String, CiState)]) -> CiState {
+    if checks.is_empty() {
+        CiState::NoChecks
+    } else if checks.iter().any(|(_, s)| *s == CiState::Failed) {
+        CiState::Failed
+    } else if checks.iter().any(|(_, s)| *s == CiState::Pending) {
+        CiState::Pending
+    } else if checks.iter().any(|(_, s)| *s == CiState::Queued) {
+        CiState::Queued
+    } else {
+        CiState::Passed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_checks_from_rollup_and_check_runs() {
+        let rollup: Vec<Value> = serde_json::from_str(r#"[
+            {"__typename": "CheckRun", "name": "test", "status": "COMPLETED", "conclusion": "SUCCESS"},
+            {"__typename": "CheckRun", "name": "lint", "status": "IN_PROGRESS", "conclusion": ""},
+            {"__typename": "StatusContext", "context": "ci/deploy", "state": "SUCCESS"}
+        ]"#).unwrap();
+        let checks = parse_checks(&rollup);
+        assert_eq!(checks[1], ("lint".to_string(), CiState::Pending));
+        assert_eq!(checks[2], ("ci/deploy".to_string(), CiState::Passed));
+        assert_eq!(combine(&checks), CiState::Pending);
+        assert_eq!(run_url(&rollup, &checks), None);
+
+        let runs: Vec<Value> = serde_json::from_str(r#"[
+            {"name": "build", "status": "completed", "conclusion": "success", "html_url": "https://github.com/a/b/runs/1"},
+            {"name": "docs", "status": "completed", "conclusion": "skipped", "html_url": "https://github.com/a/b/runs/2"},
+            {"name": "test", "status": "completed", "conclusion": "failure", "html_url": "https://github.com/a/b/runs/3"}
+        ]"#).unwrap();
+        assert_eq!(run_url(&runs, &parse_checks(&runs)).as_deref(), Some("https://github.com/a/b/runs/3"));
+        assert_eq!(run_url(&runs[..2], &parse_checks(&runs[..2])).as_deref(), Some("https://github.com/a/b/runs/1"));
+        assert_eq!(combine(&parse_checks(&runs[..2])), CiState::Passed);
+        assert_eq!(combine(&parse_checks(&runs)), CiState::Failed);
+        assert_eq!(combine(&[]), CiState::NoChecks);
+    }
+
+    #[test]
+    fn test_local_divergence() {
+        use crate::worktree::git::test_git;
+
+        let repo = tempfile::tempdir().unwrap();
+        let dir = repo.path();
+        test_git(dir, &["init", "--quiet", "-b", "main"]);
+        test_git(dir, &["config", "user.email", "test@test.com"]);
+        test_git(dir, &["config", "user.name", "Test"]);
+        std::fs::write(dir.join("a.txt"), "a").unwrap();
+        test_git(dir, &["add", "a.txt"]);
+        test_git(dir, &["commit", "--quiet", "-m", "a"]);
+        let checked = git_stdout(dir, &["rev-parse", "HEAD"]).unwrap().trim().to_string();
+
+        assert_eq!(local_divergence(dir, "main", Some(dir), &checked), None);
+        std::fs::write(dir.join("a.txt"), "edited").unwrap();
+        assert!(local_divergence(dir, "main", Some(dir), &checked).unwrap().contains("uncommitted"));
+        test_git(dir, &["commit", "--quiet", "-am", "unpushed"]);
+        assert!(local_divergence(dir, "main", Some(dir), &checked).unwrap().contains("isn't at"));
+    }
+
+    #[test]
+    fn test_parse_gitlab_jobs() {
+        let jobs: Vec<Value> = serde_json::from_str(r#"[
+            {"name": "build", "status": "success", "web_url": "https://gitlab.com/a/b/-/jobs/1"},
+            {"name": "test", "status": "pending", "web_url": "https://gitlab.com/a/b/-/jobs/2"},
+            {"name": "deploy", "status": "manual", "web_url": "https://gitlab.com/a/b/-/jobs/3"}
+        ]"#).unwrap();
+        let status = CiStatus::new(&jobs, None, "abc", parse_gitlab_jobs);
+        assert_eq!(status.checks[1], ("test".to_string(), CiState::Queued));
+        assert_eq!(status.state, CiState::Queued);
+        assert!(!status.is_settled());
+        assert_eq!(status.run_url.as_deref(), Some("https://gitlab.com/a/b/-/jobs/1"));
+
+        // No pipeline yet: settled only after a few polls of the same push
+        let none = CiStatus::new(&[], None, "abc", parse_gitlab_jobs);
+        assert_eq!(none.state, CiState::NoChecks);
+        assert!(!none.is_settled());
+        assert!(CiStatus { polls: NO_CHECKS_POLLS, ..none }.is_settled());
+    }
+}
@@ -0,0 +1,79 @@
+//! Commit messages for merging a task, written from its title, spec and diff
+//!
+//! The sidecar drafts the message when a merge starts; depending on the
+//! project's Commit Messages setting it's used as is, offered in an editor
+//! first, or not asked for at all (the plain "Merge task ..." message).
+
+/// Diff included when asking for a commit message (the rest is cut)
+const DIFF_CHARS: usize = 40000;
+
+/// Longest subject line kept before it's cut at a word
+const SUBJECT_CHARS: usize = 72;
+
+/// The message merges fall back to
+pub fn fallback(display_id: &str) -> String {
+    format!("Merge task {} from Claude session", display_id)
+}
+
+/// One-shot prompt asking for a commit message for `diff`, given the task's
+/// own description (`brief`)
+pub fn prompt(brief: &str, diff: &str) -> String {
+    let mut prompt = String::from(
+        "Write a git commit message for the change below, made for the task that \
+         follows. Use an imperative subject line of at most 72 characters, a blank \
+         line, then a short body saying what changed and why, wrapped at 72 \
+         characters. Reply with the message only, without quotes or code fences.\n\n",
+    );
+    prompt.push_str(brief);
+    prompt.push_str("\n\n# Diff\n");
+    prompt.push_str(&crate::model::diff_excerpt(diff, DIFF_CHARS));
+    prompt
+}
+
+/// The reply as a commit message: fences, quotes and surrounding blank lines
+/// dropped, an overlong subject cut at a word (None if nothing is left)
+pub fn clean(reply: &str) -> Option<String> {
+    let lines: Vec<&str> = reply.trim()
+        .lines()
+        .filter(|line| !line.trim_start().starts_with("```"))
+        .collect();
+    let text = lines.join("\n");
+    let text = text.trim().trim_matches(|c| c == '"' || c == '`').trim();
+    let (subject, body) = text.split_once('\n').unwrap_or((text, ""));
+    let subject = subject.trim().trim_start_matches("Subject:").trim();
+    if subject.is_empty() {
+        return None;
+    }
+
+    let subject = if subject.chars().count() > SUBJECT_CHARS {
+        let cut: String = subject.chars().take(SUBJECT_CHARS).collect();
+        cut.rsplit_once(' ').map_or(cut.clone(), |(head, _)| head.to_string())
+    } else {
+        subject.to_string()
+    };
+    let body = body.trim();
+    Some(if body.is_empty() { subject } else { format!("{}\n\n{}", subject, body) })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clean() {
+        assert_eq!(
+            clean("```\nAdd retry to uploads\n\nUploads retry twice on timeouts.\n```\n").as_deref(),
+            Some("Add retry to uploads\n\nUploads retry twice on timeouts."),
+        );
+        assert_eq!(clean("\"Fix typo\"").as_deref(), Some("Fix typo"));
+        assert_eq!(clean("  \n```\n```").as_deref(), None);
+
+        let long = "Rework the way merges pick their commit message so that every merge path shares it";
+        let subject = clean(long).unwrap();
+        assert!(subject.len() <= SUBJECT_CHARS && long.starts_with(&subject) && !subject.ends_with(' '));
+
+        let prompt = prompt("# Task\nRetry uploads", &"+x\n".repeat(20000));
+        assert!(prompt.contains("# Task\nRetry uploads\n\n# Diff\n+x"));
+        assert!(prompt.ends_with("more bytes not shown]"));
+    }
+}
@@ -0,0 +1,206 @@
+//! Problems of the current session: git, tmux, sidecar, hook and file errors
+//!
+//! Errors used to go to the status bar, where the next message replaced them,
+//! or to stderr, which the TUI hides. Everything reported here stays in a
+//! session log instead. The status bar shows `!` with the number of problems
+//! nobody has looked at yet, and the diagnostics modal (~) lists them.
+
+use chrono::{DateTime, Local};
+use std::collections::VecDeque;
+use std::sync::Mutex;
+
+/// Problems kept for the session (oldest dropped first)
+const MAX_PROBLEMS: usize = 200;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+impl Severity {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        }
+    }
+}
+
+/// The part of kanblam (or the tool it drives) a problem came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Source {
+    Git,
+    Tmux,
+    Sidecar,
+    Hooks,
+    Io,
+    Other,
+}
+
+impl Source {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Source::Git => "git",
+            Source::Tmux => "tmux",
+            Source::Sidecar => "sidecar",
+            Source::Hooks => "hooks",
+            Source::Io => "io",
+            Source::Other => "other",
+        }
+    }
+}
+
+/// One reported problem; repeats of the same problem are counted, not listed again
+#[derive(Debug, Clone)]
+pub struct Problem {
+    pub severity: Severity,
+    pub source: Source,
+    pub message: String,
+    /// Where it happened (project, task, file)
+    pub context: Option<String>,
+    pub at: DateTime<Local>,
+    pub count: u32,
+}
+
+#[derive(Debug)]
+struct Log {
+    problems: VecDeque<Problem>,
+    /// Problems reported since the modal was last opened
+    unseen: usize,
+}
+
+impl Log {
+    const fn new() -> Self {
+        Self { problems: VecDeque::new(), unseen: 0 }
+    }
+
+    fn record(&mut self, severity: Severity, source: Source, message: String, context: Option<String>) {
+        self.unseen += 1;
+        if let Some(last) = self.problems.back_mut() {
+            if last.source == source && last.message == message && last.context == context {
+                last.count += 1;
+                last.at = Local::now();
+                last.severity = last.severity.max(severity);
+                return;
+            }
+        }
+        if self.problems.len() == MAX_PROBLEMS {
+            self.problems.pop_front();
+        }
+        self.problems.push_back(Problem { severity, source, message, context, at: Local::now(), count: 1 });
+    }
+
+    /// Unseen reports, and whether any of them is an error
+    fn unseen(&self) -> (usize, bool) {
+        let any_error = self.problems.iter().rev()
+            .take(self.unseen.min(self.problems.len()))
+            .any(|p| p.severity == Severity::Error);
+        (self.unseen, any_error)
+    }
+}
+
+static LOG: Mutex<Log> = Mutex::new(Log::new());
+
+/// Record a problem
+pub fn report(severity: Severity, source: Source, message: impl Into<String>, context: Option<String>) {
+    let message = message.into();
+    let logged = match context {
+        Some(ref context) => format!("{} ({})", message, context),
+        None => message.clone(),
+    };
+    match severity {
+        Severity::Warning => tracing::warn!(source = source.label(), "{}", logged),
+        Severity::Error => tracing::error!(source = source.label(), "{}", logged),
+    }
+    if let Ok(mut log) = LOG.lock() {
+        log.record(severity, source, message, context);
+    }
+}
+
+/// Shorthand for a warning with no context
+pub fn warn(source: Source, message: impl Into<String>) {
+    report(Severity::Warning, source, message, None);
+}
+
+/// The session's problems, newest first
+pub fn problems() -> Vec<Problem> {
+    LOG.lock().map(|log| log.problems.iter().rev().cloned().collect()).unwrap_or_default()
+}
+
+/// Problems reported since the modal was last opened, and whether any is an error
+pub fn unseen() -> (usize, bool) {
+    LOG.lock().map(|log| log.unseen()).unwrap_or((0, false))
+}
+
+pub fn mark_seen() {
+    if let Ok(mut log) = LOG.lock() {
+        log.unseen = 0;
+    }
+}
+
+pub fn clear() {
+    if let Ok(mut log) = LOG.lock() {
+        log.problems.clear();
+        log.unseen = 0;
+    }
+}
+
+/// Guess where an error shown to the user came from, by its wording
+pub fn classify(message: &str) -> Source {
+    let lower = message.to_lowercase();
+    let mentions = |words: &[&str]| words.iter().any(|w| lower.contains(w));
+    if mentions(&["tmux"]) {
+        Source::Tmux
+    } else if mentions(&["sidecar", "sdk", "session"]) {
+        Source::Sidecar
+    } else if mentions(&["hook"]) {
+        Source::Hooks
+    } else if mentions(&["git", "worktree", "branch", "merge", "rebase", "commit", "stash", "push", "pull"]) {
+        Source::Git
+    } else if mentions(&["read", "write", "save", "load", "file", "directory", "permission"]) {
+        Source::Io
+    } else {
+        Source::Other
+    }
+}
+
+/// The problems as plain text, for the clipboard
+pub fn to_text(problems: &[Problem]) -> String {
+    problems.iter()
+        .map(|p| {
+            let mut line = format!("{} {} [{}] {}", p.at.format("%H:%M:%S"), p.severity.label(), p.source.label(), p.message);
+            if p.count > 1 {
+                line.push_str(&format!(" (x{})", p.count));
+            }
+            if let Some(ref context) = p.context {
+                line.push_str(&format!(" ({})", context));
+            }
+            line
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_collapses_repeats() {
+        let mut log = Log::new();
+        log.record(Severity::Warning, Source::Git, "fetch failed".to_string(), None);
+        log.record(Severity::Error, Source::Git, "fetch failed".to_string(), None);
+        log.record(Severity::Warning, Source::Tmux, "no server".to_string(), Some("proj".to_string()));
+        assert_eq!(log.problems.len(), 2);
+        assert_eq!(log.problems[0].count, 2);
+        assert_eq!(log.problems[0].severity, Severity::Error);
+        assert_eq!(log.unseen(), (3, true));
+        log.unseen = 1;
+        assert_eq!(log.unseen(), (1, false));
+
+        assert_eq!(classify("Failed to create worktree: exists"), Source::Git);
+        assert_eq!(classify("Cannot start task: Sidecar not connected"), Source::Sidecar);
+        assert_eq!(classify("Failed to write handoff document"), Source::Io);
+    }
+}
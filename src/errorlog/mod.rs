@@ -0,0 +1,389 @@
+//! Error-log watcher that proposes bug tasks
+//!
+//! Tails a log file (or the output of a command such as a dev server),
+//! groups error lines with the stack trace that follows them, and reports
+//! each error with a normalized signature so repeats can be deduplicated.
+
+use crate::worktree::CancelToken;
+use std::io::{BufRead, BufReader, Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::mpsc;
+use std::time::Duration;
+
+/// How long the source must stay quiet before a pending trace is reported
+const QUIET_PERIOD: Duration = Duration::from_millis(500);
+
+/// Longest trace kept per error
+const MAX_TRACE_LINES: usize = 60;
+
+/// Where error output comes from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LogSource {
+    /// Tail a file (new lines only)
+    File(PathBuf),
+    /// Run a shell command and watch its stdout + stderr
+    Command(String),
+}
+
+impl LogSource {
+    /// Parse the project setting: "$ cmd" runs a command, anything else is a file path
+    /// (relative paths resolve against the project dir)
+    pub fn parse(spec: &str, project_dir: &Path) -> Option<Self> {
+        let spec = spec.trim();
+        if spec.is_empty() {
+            return None;
+        }
+        if let Some(cmd) = spec.strip_prefix('$') {
+            let cmd = cmd.trim();
+            return (!cmd.is_empty()).then(|| LogSource::Command(cmd.to_string()));
+        }
+        Some(LogSource::File(project_dir.join(spec)))
+    }
+
+    /// Short label used in task titles and status messages
+    pub fn describe(&self) -> String {
+        match self {
+            LogSource::File(path) => path
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_else(|| path.display().to_string()),
+            LogSource::Command(cmd) => format!("`{}`", cmd),
+        }
+    }
+}
+
+/// An error block captured from the log
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CapturedError {
+    /// Normalized headline used for deduplication
+    pub signature: String,
+    /// The line that best describes the error
+    pub headline: String,
+    /// Headline plus the stack trace lines around it
+    pub trace: String,
+}
+
+impl CapturedError {
+    /// Title for a bug task, with the trace attached below the first line
+    pub fn task_title(&self, source: &str) -> String {
+        format!(
+            "Fix error from {}: {}\n\nCaptured stack trace:\n```\n{}\n```",
+            source, self.headline, self.trace
+        )
+    }
+}
+
+/// Groups log lines into error blocks
+#[derive(Debug, Default)]
+pub struct ErrorExtractor {
+    block: Vec<String>,
+    headline: String,
+}
+
+impl ErrorExtractor {
+    /// Feed one line; returns an error when this line completes a block
+    pub fn push_line(&mut self, line: &str) -> Option<CapturedError> {
+        let line = line.trim_end();
+
+        if !self.block.is_empty() {
+            if self.block.len() < MAX_TRACE_LINES && is_trace_line(line) {
+                self.block.push(line.to_string());
+                return None;
+            }
+            // Rust panics print the message on the line after "panicked at ...:"
+            if self.block.len() == 1 && self.headline.ends_with(':') && !line.trim().is_empty() {
+                self.headline = format!("{} {}", self.headline, line.trim());
+                self.block.push(line.to_string());
+                return None;
+            }
+            // Python tracebacks end with the exception line, unindented
+            if self.block[0].trim_start().starts_with("Traceback") && !line.trim().is_empty() {
+                self.headline = line.trim().to_string();
+                self.block.push(line.to_string());
+                return self.flush();
+            }
+        }
+
+        let finished = self.flush();
+        if is_error_line(line) {
+            self.headline = line.trim().to_string();
+            self.block.push(line.to_string());
+        }
+        finished
+    }
+
+    /// Report the pending block, if any (call when the source goes quiet)
+    pub fn flush(&mut self) -> Option<CapturedError> {
+        if self.block.is_empty() {
+            return None;
+        }
+        let trace = std::mem::take(&mut self.block).join("\n");
+        let headline = std::mem::take(&mut self.headline);
+        Some(CapturedError { signature: signature(&headline), headline, trace })
+    }
+}
+
+/// Whether a line starts an error block
+fn is_error_line(line: &str) -> bool {
+    // ASCII lowercasing keeps byte offsets aligned with `line`
+    let lower = line.to_ascii_lowercase();
+    if lower.contains("panicked at") || lower.trim_start().starts_with("traceback (most recent call last)") {
+        return true;
+    }
+    ["error", "exception", "fatal"].iter().any(|word| {
+        lower.match_indices(word).any(|(idx, _)| {
+            let after = &lower[idx + word.len()..];
+            // "TypeError:", "error[E0382]", "[ERROR] ..."
+            if after.starts_with([':', ']', '[']) {
+                return true;
+            }
+            // Uppercase log levels: "ERROR db unreachable", "... FATAL out of memory"
+            let is_level = line[idx..idx + word.len()].chars().all(|c| c.is_ascii_uppercase());
+            is_level && after.starts_with(' ') && (idx == 0 || lower[..idx].ends_with(' '))
+        })
+    })
+}
+
+/// Whether a line continues the current stack trace
+fn is_trace_line(line: &str) -> bool {
+    let trimmed = line.trim_start();
+    if trimmed.is_empty() {
+        return false;
+    }
+    line.starts_with([' ', '\t'])
+        || trimmed.starts_with("at ")
+        || trimmed.starts_with("File \"")
+        || trimmed.starts_with("Caused by")
+        || trimmed.starts_with("stack backtrace")
+        || trimmed.starts_with("note:")
+        || trimmed.starts_with("--> ")
+        || trimmed.starts_with('|')
+        || trimmed.starts_with("...")
+}
+
+/// Normalize a headline so the same error with different numbers, ids or
+/// quoted values gets the same signature
+pub fn signature(headline: &str) -> String {
+    let mut out = String::new();
+    let mut chars = headline.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c.is_ascii_digit() {
+            // Collapse numbers, including hex ("0x7ffe") and dotted/colon runs ("10.0.0.1:5432")
+            while chars.peek().is_some_and(|n| n.is_ascii_hexdigit() || matches!(n, 'x' | '.' | ':' | '-')) {
+                chars.next();
+            }
+            out.push('#');
+        } else if c == '"' || c == '\'' || c == '`' {
+            // Drop quoted values
+            for n in chars.by_ref() {
+                if n == c {
+                    break;
+                }
+            }
+            out.push_str("\"…\"");
+        } else if c.is_whitespace() {
+            if !out.ends_with(' ') {
+                out.push(' ');
+            }
+        } else {
+            out.push(c);
+        }
+    }
+    out.trim().to_string()
+}
+
+/// Watch `source` on a background thread until `cancel` is set.
+/// `on_error` is called for every captured error (duplicates included);
+/// `on_failure` once if the source can't be read.
+pub fn spawn_watcher(
+    source: LogSource,
+    project_dir: PathBuf,
+    cancel: CancelToken,
+    mut on_error: impl FnMut(CapturedError) + Send + 'static,
+    on_failure: impl FnOnce(String) + Send + 'static,
+) {
+    std::thread::spawn(move || {
+        let (tx, rx) = mpsc::channel::<String>();
+        let result = match &source {
+            LogSource::File(path) => tail_file(path.clone(), cancel.clone(), tx),
+            LogSource::Command(cmd) => run_command(cmd, &project_dir, cancel.clone(), tx),
+        };
+        if let Err(e) = result {
+            on_failure(e);
+            return;
+        }
+
+        let mut extractor = ErrorExtractor::default();
+        loop {
+            if cancel.is_cancelled() {
+                return;
+            }
+            match rx.recv_timeout(QUIET_PERIOD) {
+                Ok(line) => {
+                    if let Some(error) = extractor.push_line(&line) {
+                        on_error(error);
+                    }
+                }
+                Err(mpsc::RecvTimeoutError::Timeout) => {
+                    if let Some(error) = extractor.flush() {
+                        on_error(error);
+                    }
+                }
+                Err(mpsc::RecvTimeoutError::Disconnected) => {
+                    if let Some(error) = extractor.flush() {
+                        on_error(error);
+                    }
+                    if !cancel.is_cancelled() {
+                        on_failure(format!("{} stopped", source.describe()));
+                    }
+                    return;
+                }
+            }
+        }
+    });
+}
+
+/// Send lines appended to `path` from now on (handles truncation/rotation)
+fn tail_file(path: PathBuf, cancel: CancelToken, tx: mpsc::Sender<String>) -> Result<(), String> {
+    let mut offset = std::fs::metadata(&path)
+        .map(|m| m.len())
+        .map_err(|e| format!("Cannot watch {}: {}", path.display(), e))?;
+
+    std::thread::spawn(move || {
+        let mut partial = String::new();
+        while !cancel.is_cancelled() {
+            std::thread::sleep(QUIET_PERIOD / 2);
+            let Ok(mut file) = std::fs::File::open(&path) else {
+                continue;
+            };
+            let len = file.metadata().map(|m| m.len()).unwrap_or(0);
+            if len < offset {
+                // Rotated or truncated - start over from the top
+                offset = 0;
+                partial.clear();
+            }
+            if len == offset || file.seek(SeekFrom::Start(offset)).is_err() {
+                continue;
+            }
+
+            let mut buf = Vec::new();
+            if file.take(len - offset).read_to_end(&mut buf).is_err() {
+                continue;
+            }
+            offset += buf.len() as u64;
+            partial.push_str(&String::from_utf8_lossy(&buf));
+            while let Some(pos) = partial.find('\n') {
+                let line: String = partial.drain(..=pos).collect();
+                if tx.send(line.trim_end_matches(['\r', '\n']).to_string()).is_err() {
+                    return;
+                }
+            }
+        }
+    });
+    Ok(())
+}
+
+/// Run `cmd` through the shell, sending its stdout and stderr lines
+fn run_command(cmd: &str, project_dir: &Path, cancel: CancelToken, tx: mpsc::Sender<String>) -> Result<(), String> {
+    let mut child = Command::new("sh")
+        .current_dir(project_dir)
+        .args(["-c", cmd])
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Cannot run `{}`: {}", cmd, e))?;
+
+    for pipe in [
+        child.stdout.take().map(|p| Box::new(p) as Box<dyn Read + Send>),
+        child.stderr.take().map(|p| Box::new(p) as Box<dyn Read + Send>),
+    ]
+    .into_iter()
+    .flatten()
+    {
+        let tx = tx.clone();
+        std::thread::spawn(move || {
+            for line in BufReader::new(pipe).lines() {
+                let Ok(line) = line else { break };
+                if tx.send(line).is_err() {
+                    break;
+                }
+            }
+        });
+    }
+
+    // Kill the command when the watcher is stopped
+    std::thread::spawn(move || loop {
+        if cancel.is_cancelled() {
+            let _ = child.kill();
+            let _ = child.wait();
+            return;
+        }
+        if matches!(child.try_wait(), Ok(Some(_))) {
+            return;
+        }
+        std::thread::sleep(QUIET_PERIOD);
+    });
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn extract(lines: &[&str]) -> Vec<CapturedError> {
+        let mut extractor = ErrorExtractor::default();
+        let mut out: Vec<CapturedError> = lines.iter().filter_map(|l| extractor.push_line(l)).collect();
+        out.extend(extractor.flush());
+        out
+    }
+
+    #[test]
+    fn test_extracts_trace_after_error_line() {
+        let errors = extract(&[
+            "GET /health 200",
+            "TypeError: Cannot read properties of undefined (reading 'id')",
+            "    at handler (/app/src/routes.js:42:17)",
+            "    at next (/app/node_modules/express/router.js:95:5)",
+            "GET /health 200",
+        ]);
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].headline.starts_with("TypeError:"));
+        assert_eq!(errors[0].trace.lines().count(), 3);
+    }
+
+    #[test]
+    fn test_python_traceback_uses_exception_line() {
+        let errors = extract(&[
+            "Traceback (most recent call last):",
+            "  File \"app.py\", line 3, in <module>",
+            "    main()",
+            "ValueError: bad input 42",
+        ]);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].headline, "ValueError: bad input 42");
+        assert_eq!(errors[0].trace.lines().count(), 4);
+    }
+
+    #[test]
+    fn test_signature_ignores_numbers_and_quoted_values() {
+        let a = signature("2024-05-01T10:00:00Z ERROR user 'alice' not found (id=123)");
+        let b = signature("2024-05-02T11:30:12Z ERROR user 'bob' not found (id=987)");
+        assert_eq!(a, b);
+        assert_ne!(a, signature("2024-05-02T11:30:12Z ERROR user 'bob' was deleted"));
+    }
+
+    #[test]
+    fn test_ignores_ordinary_lines() {
+        assert!(extract(&["Compiled successfully", "0 errors, 0 warnings", "error_count=0"]).is_empty());
+    }
+
+    #[test]
+    fn test_parse_source() {
+        let dir = Path::new("/proj");
+        assert_eq!(LogSource::parse("$ npm run dev", dir), Some(LogSource::Command("npm run dev".to_string())));
+        assert_eq!(LogSource::parse("logs/dev.log", dir), Some(LogSource::File(PathBuf::from("/proj/logs/dev.log"))));
+        assert_eq!(LogSource::parse("  ", dir), None);
+    }
+}
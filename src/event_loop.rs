@@ -0,0 +1,142 @@
+//! What the main loop sleeps on
+//!
+//! The loop used to wake every 100ms to poll the terminal and check the other
+//! sources in between. It now waits in a `tokio::select!` on terminal input,
+//! async results, hook signals and sidecar notifications, and wakes for ticks
+//! only when one is due. Ticks come every `TICK` while they change something
+//! on screen; once a tick changes nothing visible the loop sleeps up to
+//! `IDLE_TICK` and then runs the ticks it skipped, so tick-counted timers
+//! (status message decay, git refresh, the mascot) keep their pace.
+
+use std::io::IsTerminal;
+use std::os::fd::{AsRawFd, RawFd};
+use std::time::{Duration, Instant};
+use tokio::io::unix::AsyncFd;
+use tokio::signal::unix::{signal, Signal, SignalKind};
+
+/// Tick period while something on screen is moving
+pub const TICK: Duration = Duration::from_millis(100);
+
+/// Tick period while the interactive modal shows a live terminal
+pub const MODAL_TICK: Duration = Duration::from_millis(50);
+
+/// Longest sleep between ticks when nothing is moving
+pub const IDLE_TICK: Duration = Duration::from_secs(1);
+
+/// Most skipped ticks caught up at once (after the terminal was suspended)
+const MAX_CATCH_UP: u32 = 50;
+
+/// The terminal's input file descriptor
+struct Tty(RawFd);
+
+impl AsRawFd for Tty {
+    fn as_raw_fd(&self) -> RawFd {
+        self.0
+    }
+}
+
+/// Wakes when the terminal has input (or was resized) for crossterm to read
+pub struct Input {
+    /// None when stdin isn't a terminal tokio can watch; input is then polled every `TICK`
+    tty: Option<AsyncFd<Tty>>,
+    resized: Option<Signal>,
+}
+
+impl Input {
+    pub fn new() -> Self {
+        let stdin = std::io::stdin();
+        let tty = stdin.is_terminal()
+            .then(|| AsyncFd::new(Tty(stdin.as_raw_fd())).ok())
+            .flatten();
+        Self {
+            tty,
+            resized: signal(SignalKind::window_change()).ok(),
+        }
+    }
+
+    /// Wait until there may be input; read it with `event::poll(Duration::ZERO)`
+    pub async fn ready(&mut self) {
+        let Self { tty, resized } = self;
+        let Some(tty) = tty else {
+            tokio::time::sleep(TICK).await;
+            return;
+        };
+        let resized = async {
+            match resized {
+                Some(resized) => { resized.recv().await; }
+                None => std::future::pending().await,
+            }
+        };
+        tokio::select! {
+            // Cleared before crossterm reads, so input arriving meanwhile wakes the next wait
+            guard = tty.readable() => if let Ok(mut guard) = guard {
+                guard.clear_ready();
+            },
+            _ = resized => {}
+        }
+    }
+}
+
+/// When the next tick is due, and how many ticks a late wakeup stands for
+pub struct Ticks {
+    last: Instant,
+    next: Instant,
+}
+
+impl Ticks {
+    pub fn new() -> Self {
+        let now = Instant::now();
+        Self { last: now, next: now + TICK }
+    }
+
+    pub fn next(&self) -> Instant {
+        self.next
+    }
+
+    /// Something happened: tick again soon in case it started an animation
+    pub fn hurry(&mut self, period: Duration) {
+        self.next = self.next.min(Instant::now() + period);
+    }
+
+    /// Ticks owed at `now` (0 if none is due yet)
+    pub fn due(&self, now: Instant) -> u32 {
+        if now < self.next {
+            return 0;
+        }
+        let owed = now.duration_since(self.last).as_millis() / TICK.as_millis();
+        (owed as u32).clamp(1, MAX_CATCH_UP)
+    }
+
+    /// The owed ticks ran; the next comes after `period` if they moved
+    /// anything on screen, otherwise after `IDLE_TICK`
+    pub fn ran(&mut self, now: Instant, period: Duration, moved: bool) {
+        self.last = now;
+        self.next = now + if moved { period } else { IDLE_TICK };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ticks_slow_down_when_idle_and_catch_up() {
+        let mut ticks = Ticks::new();
+        let start = ticks.last;
+        assert_eq!(ticks.due(start), 0);
+        assert_eq!(ticks.due(start + TICK), 1);
+
+        // A tick that moved nothing: sleep long, then owe the skipped ticks
+        ticks.ran(start + TICK, TICK, false);
+        assert_eq!(ticks.next(), start + TICK + IDLE_TICK);
+        assert_eq!(ticks.due(start + TICK * 5), 0);
+        assert_eq!(ticks.due(start + TICK + IDLE_TICK), 10);
+
+        // Input brings the next tick closer
+        ticks.hurry(TICK);
+        assert!(ticks.next() <= Instant::now() + TICK);
+
+        // Long suspensions are capped
+        assert_eq!(ticks.due(start + TICK * 1000), MAX_CATCH_UP);
+    }
+}
@@ -0,0 +1,186 @@
+//! Handoff documents for passing a task on to a person
+//!
+//! Compiles what someone picking up a task needs into one Markdown file:
+//! the spec, a summary of the changes so far, the acceptance criteria that
+//! are still open, questions raised in the session, and how to run it.
+
+use chrono::Utc;
+use std::fmt::Write;
+use std::path::PathBuf;
+
+use crate::model::Task;
+
+/// File the handoff document is written to (relative to the task's worktree)
+pub const HANDOFF_FILE: &str = "HANDOFF.md";
+
+/// Most questions from the session listed in a handoff document
+const MAX_QUESTIONS: usize = 10;
+
+/// Spec headings the acceptance criteria are listed under
+const CRITERIA_HEADINGS: [&str; 2] = ["definition of done", "acceptance criteria"];
+
+/// How to check, test and run the project (already resolved from the
+/// project's configured or detected commands)
+#[derive(Debug, Clone, Default)]
+pub struct RunCommands {
+    pub check: Option<String>,
+    pub test: Option<String>,
+    pub run: Option<String>,
+}
+
+/// Compose the handoff document for a task.
+/// `file_stats` holds (path, added, removed) per changed file.
+pub fn compose(task: &Task, file_stats: &[(String, usize, usize)], commands: &RunCommands) -> String {
+    let mut doc = String::new();
+    let _ = writeln!(doc, "# Handoff: {}\n", task.title);
+    let _ = writeln!(
+        doc,
+        "Task {} · {} · generated {}",
+        task.display_id(),
+        task.status.label(),
+        Utc::now().format("%Y-%m-%d %H:%M UTC")
+    );
+    if let Some(ref branch) = task.git_branch {
+        let _ = writeln!(doc, "Branch: `{}`", branch);
+    }
+    if task.tracked_secs() > 0 {
+        let _ = writeln!(doc, "Time tracked: {}", crate::model::time_tracking::format_tracked(task.tracked_secs()));
+    }
+
+    let _ = writeln!(doc, "\n## Spec\n");
+    let spec = task.spec.as_deref().filter(|s| !s.trim().is_empty()).unwrap_or(&task.description);
+    let _ = writeln!(doc, "{}", spec.trim());
+
+    let _ = writeln!(doc, "\n## Changes so far\n");
+    if file_stats.is_empty() {
+        let _ = writeln!(doc, "No changes yet.");
+    } else {
+        let added: usize = file_stats.iter().map(|(_, a, _)| a).sum();
+        let removed: usize = file_stats.iter().map(|(_, _, r)| r).sum();
+        let _ = writeln!(doc, "{} files changed, +{} -{}\n", file_stats.len(), added, removed);
+        for (path, added, removed) in file_stats {
+            let _ = writeln!(doc, "- `{}` (+{} -{})", path, added, removed);
+        }
+    }
+
+    let _ = writeln!(doc, "\n## Remaining acceptance criteria\n");
+    let criteria = remaining_criteria(task.spec.as_deref().unwrap_or(""));
+    if criteria.is_empty() {
+        let _ = writeln!(doc, "None listed in the spec.");
+    }
+    for item in criteria {
+        let _ = writeln!(doc, "- [ ] {}", item);
+    }
+
+    let _ = writeln!(doc, "\n## Open questions\n");
+    let questions = open_questions(task.live_output_text());
+    if questions.is_empty() {
+        let _ = writeln!(doc, "None raised in the session.");
+    }
+    for question in questions {
+        let _ = writeln!(doc, "- {}", question);
+    }
+
+    if !task.notes.is_empty() {
+        let _ = writeln!(doc, "\n## Notes\n");
+        for note in &task.notes {
+            let _ = writeln!(doc, "- {}", note);
+        }
+    }
+
+    let _ = writeln!(doc, "\n## How to run\n");
+    let _ = writeln!(doc, "```sh");
+    if let Some(ref worktree) = task.worktree_path {
+        let _ = writeln!(doc, "cd {}", worktree.display());
+    }
+    for (label, command) in [("check", &commands.check), ("test", &commands.test), ("run", &commands.run)] {
+        if let Some(command) = command {
+            let _ = writeln!(doc, "{:<40} # {}", command, label);
+        }
+    }
+    if let Some(ref session_id) = task.claude_session_id {
+        let _ = writeln!(doc, "{:<40} # continue the Claude session", format!("claude --resume {}", session_id));
+    }
+    let _ = writeln!(doc, "```");
+    doc
+}
+
+/// Acceptance criteria from the spec that aren't ticked off yet
+fn remaining_criteria(spec: &str) -> Vec<String> {
+    let mut in_section = false;
+    let mut items = Vec::new();
+    for line in spec.lines() {
+        let trimmed = line.trim();
+        if let Some(heading) = trimmed.strip_prefix('#') {
+            let heading = heading.trim_start_matches('#').trim().to_lowercase();
+            in_section = CRITERIA_HEADINGS.iter().any(|h| heading.starts_with(h));
+            continue;
+        }
+        if !in_section || trimmed.is_empty() {
+            continue;
+        }
+        let lower = trimmed.to_lowercase();
+        if lower.starts_with("- [x]") || lower.starts_with("* [x]") {
+            continue;
+        }
+        let item = trimmed
+            .strip_prefix("- [ ]").or_else(|| trimmed.strip_prefix("* [ ]"))
+            .or_else(|| trimmed.strip_prefix("- ")).or_else(|| trimmed.strip_prefix("* "))
+            .or_else(|| {
+                // Numbered items ("1. ...")
+                let digits = trimmed.find(|c: char| !c.is_ascii_digit())?;
+                (digits > 0).then(|| trimmed[digits..].strip_prefix(". ")).flatten()
+            })
+            .unwrap_or(trimmed);
+        items.push(item.trim().to_string());
+    }
+    items
+}
+
+/// Questions raised in the session output, newest last
+fn open_questions(transcript: &str) -> Vec<String> {
+    let mut questions: Vec<String> = Vec::new();
+    for line in transcript.lines() {
+        let line = line.trim().trim_start_matches(['-', '*', '>']).trim();
+        if !line.ends_with('?') || line.len() < 10 || line.len() > 300 {
+            continue;
+        }
+        questions.retain(|q| q != line);
+        questions.push(line.to_string());
+    }
+    let skip = questions.len().saturating_sub(MAX_QUESTIONS);
+    questions.split_off(skip)
+}
+
+/// Write a task's handoff document into its worktree, returning its path
+pub fn write(task: &Task, doc: &str) -> std::io::Result<PathBuf> {
+    let Some(ref worktree) = task.worktree_path else {
+        return Err(std::io::Error::new(std::io::ErrorKind::NotFound, "task has no worktree"));
+    };
+    let path = worktree.join(HANDOFF_FILE);
+    std::fs::write(&path, doc)?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_remaining_criteria_reads_definition_of_done() {
+        let spec = "## Objective\n- not a criterion\n\n## Definition of Done\n- [x] parser added\n- [ ] tests pass\n* docs updated\n2. no warnings\n\n## Notes\n- ignored";
+        assert_eq!(remaining_criteria(spec), vec!["tests pass", "docs updated", "no warnings"]);
+        assert!(remaining_criteria("## Objective\nShip it").is_empty());
+    }
+
+    #[test]
+    fn test_open_questions_dedupes_and_keeps_newest() {
+        let transcript = "Working on it.\nShould the cache be per project?\nWhy?\n- Should the cache be per project?\nDone.";
+        assert_eq!(open_questions(transcript), vec!["Should the cache be per project?"]);
+
+        let many: String = (0..15).map(|i| format!("Is question number {} open?\n", i)).collect();
+        let questions = open_questions(&many);
+        assert_eq!(questions.len(), MAX_QUESTIONS);
+        assert_eq!(questions.last().map(String::as_str), Some("Is question number 14 open?"));
+    }
+}
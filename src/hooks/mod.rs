@@ -1,3 +1,5 @@
+mod socket;
 mod watcher;
 
-pub use watcher::{cleanup_signals_for_session, write_signal, HookWatcher, WatcherEvent};
+pub use socket::socket_path;
+pub use watcher::{classify_hook_input, cleanup_signals_for_session, write_signal, HookWatcher, WatcherEvent};
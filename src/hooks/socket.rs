@@ -0,0 +1,146 @@
+//! Unix-socket delivery of hook signals
+//!
+//! `kanblam signal` hands its signal straight to the running TUI over
+//! `~/.kanblam/signal.sock` instead of waiting for the file watcher to notice
+//! a new file. The signal file is still written: it's how a closed TUI
+//! catches up, and only the first instance gets the socket, so any other one
+//! only sees signals as files. The socket's owner skips the files of the
+//! signals it already received.
+
+use anyhow::{anyhow, Result};
+use std::io::{BufRead, BufReader, Write};
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{channel, Receiver};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use super::watcher::HookSignalFile;
+
+/// How long a hook waits on the socket before falling back to a signal file
+const SEND_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// Path of the signal socket
+pub fn socket_path() -> Result<PathBuf> {
+    let home = dirs::home_dir().ok_or_else(|| anyhow!("No home directory"))?;
+    Ok(home.join(".kanblam").join("signal.sock"))
+}
+
+/// Listens on the signal socket and queues the signals it receives
+pub struct SignalSocket {
+    path: PathBuf,
+    receiver: Receiver<HookSignalFile>,
+}
+
+impl SignalSocket {
+    /// Listen at `path`. Fails if another kanblam instance is already
+    /// listening there; a socket file left behind by a crash is replaced.
+    /// `wake` is notified after each received signal.
+    pub fn bind(path: &Path, wake: Arc<tokio::sync::Notify>) -> Result<Self> {
+        if path.exists() {
+            if UnixStream::connect(path).is_ok() {
+                return Err(anyhow!("Another kanblam instance is listening on {}", path.display()));
+            }
+            std::fs::remove_file(path)?;
+        }
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let listener = UnixListener::bind(path)?;
+
+        let (tx, rx) = channel();
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let Ok(stream) = stream else { continue };
+                let Some(signal) = read_signal(stream) else { continue };
+                if tx.send(signal).is_err() {
+                    break; // Watcher dropped
+                }
+                wake.notify_one();
+            }
+        });
+
+        Ok(Self { path: path.to_path_buf(), receiver: rx })
+    }
+
+    /// Next received signal (non-blocking)
+    pub fn try_recv(&self) -> Option<HookSignalFile> {
+        self.receiver.try_recv().ok()
+    }
+}
+
+impl Drop for SignalSocket {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+/// Read one signal line from a hook and acknowledge it
+fn read_signal(stream: UnixStream) -> Option<HookSignalFile> {
+    stream.set_read_timeout(Some(SEND_TIMEOUT)).ok()?;
+    let mut reader = BufReader::new(&stream);
+    let mut line = String::new();
+    reader.read_line(&mut line).ok()?;
+    let signal: HookSignalFile = match serde_json::from_str(&line) {
+        Ok(signal) => signal,
+        Err(e) => {
+            tracing::warn!("Dropped an unreadable socket signal: {} ({:?})", e, line.trim());
+            return None;
+        }
+    };
+    tracing::debug!("Socket signal {} for session {} in {}", signal.event, signal.session_id, signal.project_dir.display());
+    (&stream).write_all(b"ok\n").ok()?;
+    Some(signal)
+}
+
+/// Send a signal to the listening TUI. Errors when nothing acknowledged it.
+pub fn send(path: &Path, signal: &HookSignalFile) -> Result<()> {
+    let mut stream = UnixStream::connect(path)?;
+    stream.set_read_timeout(Some(SEND_TIMEOUT))?;
+    stream.set_write_timeout(Some(SEND_TIMEOUT))?;
+    let mut line = serde_json::to_string(signal)?;
+    line.push('\n');
+    stream.write_all(line.as_bytes())?;
+
+    let mut ack = String::new();
+    BufReader::new(&stream).read_line(&mut ack)?;
+    if ack.trim() != "ok" {
+        return Err(anyhow!("Signal not acknowledged"));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_signal_delivered_over_socket() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("signal.sock");
+        let signal = HookSignalFile {
+            event: "stop".to_string(),
+            session_id: "task-1".to_string(),
+            project_dir: PathBuf::from("/work/repo"),
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            reason: String::new(),
+            input_type: String::new(),
+            source: "cli".to_string(),
+            payload: Default::default(),
+        };
+
+        // Nothing listening yet: the caller falls back to a file
+        assert!(send(&path, &signal).is_err());
+
+        let socket = SignalSocket::bind(&path, Default::default()).unwrap();
+        assert!(SignalSocket::bind(&path, Default::default()).is_err());
+        send(&path, &signal).unwrap();
+        let event = (0..50)
+            .find_map(|_| socket.try_recv().or_else(|| { thread::sleep(Duration::from_millis(10)); None }));
+        assert!(matches!(event, Some(HookSignalFile { ref session_id, .. }) if session_id == "task-1"));
+
+        drop(socket);
+        assert!(!path.exists());
+    }
+}
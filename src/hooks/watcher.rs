@@ -3,11 +3,15 @@
 use anyhow::Result;
 use notify::{Config, Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
 use std::sync::mpsc::{channel, Receiver};
+use std::sync::Arc;
 use std::time::{Duration, SystemTime};
 
+use super::socket::{send, socket_path, SignalSocket};
+use crate::model::HookPayload;
+
 /// Event received from hook watcher
 #[derive(Debug, Clone)]
 pub enum WatcherEvent {
@@ -37,12 +41,47 @@ pub enum WatcherEvent {
         project_dir: PathBuf,
         source: String,
     },
-    /// Claude is working/using a tool (PreToolUse hook)
+    /// Claude is working/using a tool (PreToolUse hook, worktrees set up before tool events)
     Working {
         session_id: String,
         project_dir: PathBuf,
         source: String,
     },
+    /// A tool call began (PreToolUse hook)
+    ToolStarted {
+        session_id: String,
+        project_dir: PathBuf,
+        source: String,
+        payload: HookPayload,
+    },
+    /// A tool call returned (PostToolUse hook)
+    ToolFinished {
+        session_id: String,
+        project_dir: PathBuf,
+        source: String,
+        payload: HookPayload,
+    },
+    /// A tool call returned an error (PostToolUse hook)
+    ToolFailed {
+        session_id: String,
+        project_dir: PathBuf,
+        source: String,
+        payload: HookPayload,
+    },
+    /// A subagent was launched (PreToolUse hook for the Task tool)
+    SubagentStarted {
+        session_id: String,
+        project_dir: PathBuf,
+        source: String,
+        payload: HookPayload,
+    },
+    /// A subagent returned (PostToolUse hook for the Task tool)
+    SubagentStopped {
+        session_id: String,
+        project_dir: PathBuf,
+        source: String,
+        payload: HookPayload,
+    },
     /// Error occurred
     Error(String),
 }
@@ -61,19 +100,160 @@ pub struct HookSignalFile {
     /// Source of the signal: "sdk" or "cli" (defaults to "cli" for backwards compatibility)
     #[serde(default = "default_source")]
     pub source: String,
+    #[serde(default, skip_serializing_if = "HookPayload::is_empty")]
+    pub payload: HookPayload,
 }
 
 fn default_source() -> String {
     "cli".to_string()
 }
 
-/// Watches the signal directory for hook notifications
+impl HookSignalFile {
+    /// Identifies the signal whichever way it arrives (socket or file)
+    pub fn key(&self) -> String {
+        format!("{}/{}/{}", self.session_id, self.event, self.timestamp)
+    }
+
+    /// The watcher event for this signal (None for unknown event names)
+    pub fn into_event(self) -> Option<WatcherEvent> {
+        match self.event.as_str() {
+            "stop" => Some(WatcherEvent::ClaudeStopped {
+                session_id: self.session_id,
+                project_dir: self.project_dir,
+                source: self.source,
+            }),
+            "end" => Some(WatcherEvent::SessionEnded {
+                session_id: self.session_id,
+                project_dir: self.project_dir,
+                reason: self.reason,
+                source: self.source,
+            }),
+            "needs-input" => Some(WatcherEvent::NeedsWork {
+                session_id: self.session_id,
+                project_dir: self.project_dir,
+                input_type: self.input_type,
+                source: self.source,
+            }),
+            "input-provided" => Some(WatcherEvent::InputProvided {
+                session_id: self.session_id,
+                project_dir: self.project_dir,
+                source: self.source,
+            }),
+            "working" => Some(WatcherEvent::Working {
+                session_id: self.session_id,
+                project_dir: self.project_dir,
+                source: self.source,
+            }),
+            "tool-start" => Some(WatcherEvent::ToolStarted {
+                session_id: self.session_id,
+                project_dir: self.project_dir,
+                source: self.source,
+                payload: self.payload,
+            }),
+            "tool-finish" => Some(WatcherEvent::ToolFinished {
+                session_id: self.session_id,
+                project_dir: self.project_dir,
+                source: self.source,
+                payload: self.payload,
+            }),
+            "tool-error" => Some(WatcherEvent::ToolFailed {
+                session_id: self.session_id,
+                project_dir: self.project_dir,
+                source: self.source,
+                payload: self.payload,
+            }),
+            "subagent-start" => Some(WatcherEvent::SubagentStarted {
+                session_id: self.session_id,
+                project_dir: self.project_dir,
+                source: self.source,
+                payload: self.payload,
+            }),
+            "subagent-stop" => Some(WatcherEvent::SubagentStopped {
+                session_id: self.session_id,
+                project_dir: self.project_dir,
+                source: self.source,
+                payload: self.payload,
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// Longest tool detail or error kept in a signal
+const PAYLOAD_TEXT_LIMIT: usize = 120;
+
+/// Event name and payload for a hook, from the JSON Claude Code passes it on
+/// stdin. Tool hooks for the Task tool are subagent lifecycle events, and a
+/// finished tool whose result carries an error is a failure.
+pub fn classify_hook_input(event: &str, input: &serde_json::Value) -> (String, HookPayload) {
+    if !matches!(event, "tool-start" | "tool-finish") {
+        return (event.to_string(), HookPayload::default());
+    }
+
+    let tool = input.get("tool_name").and_then(|v| v.as_str()).map(str::to_string);
+    let tool_input = input.get("tool_input").unwrap_or(&serde_json::Value::Null);
+    let text = |key: &str| tool_input.get(key).and_then(|v| v.as_str()).map(clip);
+
+    if matches!(tool.as_deref(), Some("Task" | "Agent")) {
+        let payload = HookPayload {
+            tool,
+            detail: text("description"),
+            agent: text("subagent_type").or_else(|| Some("general-purpose".to_string())),
+            ..HookPayload::default()
+        };
+        let event = if event == "tool-start" { "subagent-start" } else { "subagent-stop" };
+        return (event.to_string(), payload);
+    }
+
+    let detail = ["file_path", "notebook_path", "command", "pattern", "url", "query"]
+        .iter()
+        .find_map(|key| text(key));
+    let error = (event == "tool-finish").then(|| tool_error(input)).flatten();
+    let event = if error.is_some() { "tool-error" } else { event };
+    (event.to_string(), HookPayload { tool, detail, error, agent: None })
+}
+
+/// Error message in a PostToolUse input, if the tool call failed
+fn tool_error(input: &serde_json::Value) -> Option<String> {
+    if let Some(error) = input.get("error").and_then(|v| v.as_str()) {
+        return Some(clip(error));
+    }
+    let response = input.get("tool_response")?;
+    if let Some(error) = response.get("error").and_then(|v| v.as_str()) {
+        return Some(clip(error));
+    }
+    if response.get("is_error").and_then(|v| v.as_bool()) == Some(true) {
+        let content = response.get("content").and_then(|v| v.as_str()).unwrap_or("tool error");
+        return Some(clip(content));
+    }
+    None
+}
+
+/// First line of `text`, shortened to `PAYLOAD_TEXT_LIMIT` characters
+fn clip(text: &str) -> String {
+    let line = text.trim().lines().next().unwrap_or("");
+    if line.chars().count() > PAYLOAD_TEXT_LIMIT {
+        line.chars().take(PAYLOAD_TEXT_LIMIT - 1).collect::<String>() + "…"
+    } else {
+        line.to_string()
+    }
+}
+
+/// Receives hook notifications over the signal socket, and watches the
+/// signal directory for the ones written as files
 pub struct HookWatcher {
     signal_dir: PathBuf,
+    /// None when another instance owns the socket (signals then arrive as files)
+    socket: Option<SignalSocket>,
     _watcher: RecommendedWatcher,
     receiver: Receiver<notify::Result<Event>>,
+    /// Notified whenever a file event or socket signal is queued
+    wake: Arc<tokio::sync::Notify>,
     /// Track processed signal filenames to avoid re-processing
     processed_signals: HashSet<String>,
+    /// Signals received over the socket (by `HookSignalFile::key`), whose
+    /// files are skipped when they show up
+    socket_signals: HashMap<String, SystemTime>,
     /// Last cleanup time
     last_cleanup: std::time::Instant,
 }
@@ -89,25 +269,38 @@ impl HookWatcher {
         std::fs::create_dir_all(&signal_dir)?;
 
         let (tx, rx) = channel();
+        let wake = Arc::new(tokio::sync::Notify::new());
 
+        let file_wake = Arc::clone(&wake);
         let mut watcher = RecommendedWatcher::new(
             move |res| {
                 let _ = tx.send(res);
+                file_wake.notify_one();
             },
             Config::default().with_poll_interval(Duration::from_millis(100)),
         )?;
 
         watcher.watch(&signal_dir, RecursiveMode::NonRecursive)?;
 
+        let socket = socket_path().ok().and_then(|path| SignalSocket::bind(&path, Arc::clone(&wake)).ok());
+
         Ok(Self {
             signal_dir,
+            socket,
             _watcher: watcher,
             receiver: rx,
+            wake,
             processed_signals: HashSet::new(),
+            socket_signals: HashMap::new(),
             last_cleanup: std::time::Instant::now(),
         })
     }
 
+    /// Wait until something may be queued for `poll`
+    pub async fn changed(&self) {
+        self.wake.notified().await;
+    }
+
     /// Check for new events (non-blocking)
     pub fn poll(&mut self) -> Option<WatcherEvent> {
         // Periodic cleanup of old signals (every 30 seconds)
@@ -116,6 +309,14 @@ impl HookWatcher {
             self.last_cleanup = std::time::Instant::now();
         }
 
+        while let Some(signal) = self.socket.as_ref().and_then(|s| s.try_recv()) {
+            self.socket_signals.insert(signal.key(), SystemTime::now());
+            match signal.into_event() {
+                Some(event) => return Some(event),
+                None => tracing::warn!("Dropped a socket signal with an unknown event"),
+            }
+        }
+
         match self.receiver.try_recv() {
             Ok(Ok(event)) => self.process_event(event),
             Ok(Err(e)) => Some(WatcherEvent::Error(e.to_string())),
@@ -140,42 +341,21 @@ impl HookWatcher {
                     continue;
                 }
 
-                if let Ok(content) = std::fs::read_to_string(&path) {
-                    if let Ok(signal) = serde_json::from_str::<HookSignalFile>(&content) {
+                match std::fs::read_to_string(&path).map(|content| serde_json::from_str::<HookSignalFile>(&content)) {
+                    Ok(Ok(signal)) => {
                         // Mark as processed (don't delete - other instances may need it)
+                        tracing::debug!("Signal file {}: {} for session {}", filename, signal.event, signal.session_id);
                         self.processed_signals.insert(filename);
 
-                        return match signal.event.as_str() {
-                            "stop" => Some(WatcherEvent::ClaudeStopped {
-                                session_id: signal.session_id,
-                                project_dir: signal.project_dir,
-                                source: signal.source,
-                            }),
-                            "end" => Some(WatcherEvent::SessionEnded {
-                                session_id: signal.session_id,
-                                project_dir: signal.project_dir,
-                                reason: signal.reason,
-                                source: signal.source,
-                            }),
-                            "needs-input" => Some(WatcherEvent::NeedsWork {
-                                session_id: signal.session_id,
-                                project_dir: signal.project_dir,
-                                input_type: signal.input_type,
-                                source: signal.source,
-                            }),
-                            "input-provided" => Some(WatcherEvent::InputProvided {
-                                session_id: signal.session_id,
-                                project_dir: signal.project_dir,
-                                source: signal.source,
-                            }),
-                            "working" => Some(WatcherEvent::Working {
-                                session_id: signal.session_id,
-                                project_dir: signal.project_dir,
-                                source: signal.source,
-                            }),
-                            _ => None,
-                        };
+                        // Already handled when it came over the socket
+                        if self.socket_signals.contains_key(&signal.key()) {
+                            continue;
+                        }
+                        return signal.into_event();
                     }
+                    // Possibly still being written; the next event for it retries
+                    Ok(Err(e)) => tracing::debug!("Signal file {} not readable yet: {}", filename, e),
+                    Err(e) => tracing::warn!("Couldn't read signal file {}: {}", filename, e),
                 }
             }
         }
@@ -254,48 +434,19 @@ impl HookWatcher {
 
             if let Ok(content) = std::fs::read_to_string(&path) {
                 if let Ok(signal) = serde_json::from_str::<HookSignalFile>(&content) {
+                    tracing::debug!("Replaying signal file {}: {} for session {}", filename, signal.event, signal.session_id);
                     // Mark as processed (don't delete - other instances may need it)
                     self.processed_signals.insert(filename);
 
                     // Track max timestamp
                     max_ts = Some(max_ts.unwrap_or(file_ts).max(file_ts));
 
-                    let event = match signal.event.as_str() {
-                        "stop" => Some(WatcherEvent::ClaudeStopped {
-                            session_id: signal.session_id,
-                            project_dir: signal.project_dir,
-                            source: signal.source,
-                        }),
-                        "end" => Some(WatcherEvent::SessionEnded {
-                            session_id: signal.session_id,
-                            project_dir: signal.project_dir,
-                            reason: signal.reason,
-                            source: signal.source,
-                        }),
-                        "needs-input" => Some(WatcherEvent::NeedsWork {
-                            session_id: signal.session_id,
-                            project_dir: signal.project_dir,
-                            input_type: signal.input_type,
-                            source: signal.source,
-                        }),
-                        "input-provided" => Some(WatcherEvent::InputProvided {
-                            session_id: signal.session_id,
-                            project_dir: signal.project_dir,
-                            source: signal.source,
-                        }),
-                        "working" => Some(WatcherEvent::Working {
-                            session_id: signal.session_id,
-                            project_dir: signal.project_dir,
-                            source: signal.source,
-                        }),
-                        _ => None,
-                    };
-
-                    if let Some(e) = event {
+                    if let Some(e) = signal.into_event() {
                         events.push(e);
                     }
                 } else {
                     // Invalid JSON - mark as processed so we don't retry
+                    tracing::warn!("Skipped invalid signal file {}", filename);
                     self.processed_signals.insert(filename);
                 }
             }
@@ -314,6 +465,7 @@ impl HookWatcher {
 
         let now = SystemTime::now();
         let ttl = Duration::from_secs(SIGNAL_TTL_SECS);
+        self.socket_signals.retain(|_, received| now.duration_since(*received).is_ok_and(|age| age <= ttl));
 
         for entry in entries.filter_map(|e| e.ok()) {
             let path = entry.path();
@@ -372,12 +524,17 @@ pub fn cleanup_signals_for_session(session_id: &str) -> Result<()> {
     Ok(())
 }
 
-/// Write a signal file (called by hook script via CLI)
+/// Deliver a signal (called by hook script via CLI): straight to the running
+/// TUI over the signal socket, and as a signal file for the other instances
+/// and for replay on startup
 /// Automatically detects SDK vs CLI source based on KANBLAM_SDK_SESSION env var
-pub fn write_signal(event: &str, session_id: &str, project_dir: &PathBuf, input_type: Option<&str>) -> Result<()> {
-    let signal_dir = get_signal_dir()?;
-    std::fs::create_dir_all(&signal_dir)?;
-
+pub fn write_signal(
+    event: &str,
+    session_id: &str,
+    project_dir: &PathBuf,
+    input_type: Option<&str>,
+    payload: HookPayload,
+) -> Result<()> {
     // Detect source: if KANBLAM_SDK_SESSION=1 is set, this is an SDK-driven session
     let source = if std::env::var("KANBLAM_SDK_SESSION").map(|v| v == "1").unwrap_or(false) {
         "sdk"
@@ -393,8 +550,16 @@ pub fn write_signal(event: &str, session_id: &str, project_dir: &PathBuf, input_
         reason: String::new(),
         input_type: input_type.unwrap_or("").to_string(),
         source: source.to_string(),
+        payload,
     };
 
+    // Sent first, so the socket's owner knows the signal before its file appears
+    if let Ok(path) = socket_path() {
+        let _ = send(&path, &signal);
+    }
+
+    let signal_dir = get_signal_dir()?;
+    std::fs::create_dir_all(&signal_dir)?;
     let filename = format!("signal-{}-{}.json", event, chrono::Utc::now().timestamp_millis());
     let path = signal_dir.join(filename);
 
@@ -403,3 +568,42 @@ pub fn write_signal(event: &str, session_id: &str, project_dir: &PathBuf, input_
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_classify_hook_input() {
+        let (event, payload) = classify_hook_input(
+            "tool-start",
+            &json!({"tool_name": "Edit", "tool_input": {"file_path": "src/main.rs", "old_string": "a"}}),
+        );
+        assert_eq!(event, "tool-start");
+        assert_eq!(payload.describe(&event), "Using Edit: src/main.rs");
+
+        let (event, payload) = classify_hook_input(
+            "tool-finish",
+            &json!({"tool_name": "Bash", "tool_input": {"command": "cargo test\necho done"},
+                    "tool_response": {"is_error": true, "content": "exit code 101"}}),
+        );
+        assert_eq!(event, "tool-error");
+        assert_eq!(payload.detail.as_deref(), Some("cargo test"));
+        assert_eq!(payload.describe(&event), "Bash failed: exit code 101");
+
+        let (event, payload) = classify_hook_input(
+            "tool-start",
+            &json!({"tool_name": "Task", "tool_input": {"subagent_type": "Explore", "description": "Find callers"}}),
+        );
+        assert_eq!(event, "subagent-start");
+        assert_eq!(payload.describe(&event), "Subagent Explore started: Find callers");
+
+        // Other hooks carry no payload, and older signal files parse without one
+        assert_eq!(classify_hook_input("stop", &json!({"tool_name": "Edit"})), ("stop".to_string(), HookPayload::default()));
+        let file: HookSignalFile = serde_json::from_str(
+            r#"{"event":"working","session_id":"s","project_dir":"/p","timestamp":"t"}"#,
+        ).unwrap();
+        assert!(file.payload.is_empty());
+    }
+}
@@ -0,0 +1,383 @@
+//! Idle detection for sessions whose hook signals may have been missed
+//!
+//! Each heuristic gives an opinion from one source of evidence: the hook
+//! state, the pane content, CPU use of the pane's processes, and how recently
+//! the session transcript was written. Every session backend weighs them with
+//! its own strategy. A task is only treated as idle when the heuristics that
+//! could tell have enough weight between them and their combined confidence
+//! clears `CONFIDENCE_THRESHOLD`.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{Duration, SystemTime};
+
+use crate::model::ClaudeSessionState;
+
+/// Combined confidence needed before a session counts as idle
+pub const CONFIDENCE_THRESHOLD: f32 = 0.6;
+
+/// Share of a strategy's total weight that must come from heuristics that
+/// voted, so one source alone can't decide
+const MIN_EVIDENCE: f32 = 0.6;
+
+/// How long CPU use is sampled for
+const CPU_SAMPLE: Duration = Duration::from_millis(300);
+
+/// Where a session runs, which decides what can be observed about it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Backend {
+    /// Claude CLI in the task's window of the project session (tmux target)
+    TaskWindow(String),
+    /// Claude CLI in the task's own handoff session (tmux target)
+    CliSession(String),
+    /// SDK session in the sidecar: no pane or process of its own
+    Sdk,
+}
+
+impl Backend {
+    fn pane_target(&self) -> Option<&str> {
+        match self {
+            Backend::TaskWindow(target) | Backend::CliSession(target) => Some(target),
+            Backend::Sdk => None,
+        }
+    }
+}
+
+/// What was observed about one session
+#[derive(Debug, Clone, Default)]
+pub struct Observation {
+    /// Session state as the hooks last left it
+    pub session_state: ClaudeSessionState,
+    /// Time since the last hook or sidecar activity
+    pub since_activity: Option<Duration>,
+    /// Last lines of the session's pane
+    pub pane: Option<String>,
+    /// CPU use of the pane's process tree, in percent of one core
+    pub cpu_percent: Option<f32>,
+    /// Time since the session transcript was last written
+    pub transcript_age: Option<Duration>,
+}
+
+/// One heuristic's opinion: +1.0 is certainly idle, -1.0 certainly busy
+#[derive(Debug, Clone, PartialEq)]
+pub struct Vote {
+    pub idle: f32,
+    pub reason: String,
+}
+
+impl Vote {
+    fn new(idle: f32, reason: impl Into<String>) -> Option<Self> {
+        Some(Self { idle, reason: reason.into() })
+    }
+}
+
+/// A source of evidence about whether a session is idle
+pub trait Heuristic {
+    /// None when the observation says nothing either way
+    fn vote(&self, observation: &Observation) -> Option<Vote>;
+}
+
+/// What the hooks and sidecar last reported
+pub struct HookState;
+
+impl Heuristic for HookState {
+    fn vote(&self, obs: &Observation) -> Option<Vote> {
+        match (obs.session_state, obs.since_activity) {
+            (ClaudeSessionState::Paused | ClaudeSessionState::Ended, _) => Vote::new(1.0, "hooks reported a stop"),
+            (_, Some(age)) if age < Duration::from_secs(60) => Vote::new(-0.8, "recent hook activity"),
+            (_, Some(age)) if age > Duration::from_secs(300) => {
+                Vote::new(0.5, format!("no hook activity for {}m", age.as_secs() / 60))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Claude's status line: "esc to interrupt" only shows while it works
+pub struct PaneContent;
+
+impl Heuristic for PaneContent {
+    fn vote(&self, obs: &Observation) -> Option<Vote> {
+        let pane = obs.pane.as_deref()?;
+        let tail: Vec<&str> = pane.lines().rev().filter(|l| !l.trim().is_empty()).take(8).collect();
+        if tail.iter().any(|l| l.contains("esc to interrupt")) {
+            return Vote::new(-1.0, "status line says working");
+        }
+        let prompt = tail.iter().any(|l| {
+            let trimmed = l.trim_start_matches(['│', ' ']);
+            (trimmed.starts_with('❯') || trimmed.starts_with('>')) && !trimmed.contains("...")
+        });
+        if prompt {
+            return Vote::new(0.8, "prompt shown");
+        }
+        None
+    }
+}
+
+/// CPU use of the process tree running in the pane
+pub struct CpuUsage;
+
+impl Heuristic for CpuUsage {
+    fn vote(&self, obs: &Observation) -> Option<Vote> {
+        match obs.cpu_percent? {
+            cpu if cpu < 2.0 => Vote::new(0.8, format!("{:.0}% CPU", cpu)),
+            cpu if cpu > 10.0 => Vote::new(-1.0, format!("{:.0}% CPU", cpu)),
+            _ => None,
+        }
+    }
+}
+
+/// How recently the session transcript was written
+pub struct TranscriptActivity;
+
+impl Heuristic for TranscriptActivity {
+    fn vote(&self, obs: &Observation) -> Option<Vote> {
+        match obs.transcript_age? {
+            age if age < Duration::from_secs(10) => Vote::new(-1.0, "transcript just written"),
+            age if age > Duration::from_secs(60) => {
+                Vote::new(0.8, format!("transcript quiet for {}s", age.as_secs()))
+            }
+            _ => Vote::new(0.2, "transcript quiet"),
+        }
+    }
+}
+
+/// Weighted heuristics for one backend
+pub struct Strategy {
+    heuristics: Vec<(Box<dyn Heuristic>, f32)>,
+}
+
+impl Strategy {
+    pub fn for_backend(backend: &Backend) -> Self {
+        let heuristics: Vec<(Box<dyn Heuristic>, f32)> = match backend {
+            Backend::TaskWindow(_) | Backend::CliSession(_) => vec![
+                (Box::new(PaneContent), 1.0),
+                (Box::new(TranscriptActivity), 0.8),
+                (Box::new(CpuUsage), 0.6),
+                (Box::new(HookState), 0.4),
+            ],
+            // The sidecar's process is shared, so only the session's own traces count
+            Backend::Sdk => vec![(Box::new(TranscriptActivity), 1.0), (Box::new(HookState), 1.0)],
+        };
+        Self { heuristics }
+    }
+
+    pub fn evaluate(&self, obs: &Observation) -> Verdict {
+        let total: f32 = self.heuristics.iter().map(|(_, w)| w).sum();
+        let mut weight = 0.0;
+        let mut score = 0.0;
+        let mut reasons = Vec::new();
+        for (heuristic, w) in &self.heuristics {
+            if let Some(vote) = heuristic.vote(obs) {
+                weight += w;
+                score += w * vote.idle;
+                reasons.push(vote.reason);
+            }
+        }
+        let confidence = if weight >= total * MIN_EVIDENCE { score / weight } else { 0.0 };
+        Verdict { confidence, reasons }
+    }
+}
+
+/// Combined opinion about one session
+#[derive(Debug, Clone, PartialEq)]
+pub struct Verdict {
+    /// Weighted confidence that the session is idle (-1.0 to 1.0)
+    pub confidence: f32,
+    pub reasons: Vec<String>,
+}
+
+impl Verdict {
+    pub fn is_idle(&self) -> bool {
+        self.confidence >= CONFIDENCE_THRESHOLD
+    }
+
+    /// "87% confident: prompt shown, 0% CPU"
+    pub fn describe(&self) -> String {
+        format!("{:.0}% confident: {}", self.confidence * 100.0, self.reasons.join(", "))
+    }
+}
+
+/// A session to observe
+pub struct Candidate {
+    pub backend: Backend,
+    pub worktree_path: Option<PathBuf>,
+    pub session_state: ClaudeSessionState,
+    pub since_activity: Option<Duration>,
+}
+
+/// Observe each candidate, sampling CPU for all of them over one shared window
+pub fn observe_all(candidates: &[Candidate]) -> Vec<Observation> {
+    let pids: Vec<Option<u32>> = candidates
+        .iter()
+        .map(|c| c.backend.pane_target().and_then(crate::tmux::pane_pid))
+        .collect();
+    let cpu = sample_cpu(&pids);
+
+    candidates
+        .iter()
+        .zip(cpu)
+        .map(|(candidate, cpu_percent)| Observation {
+            session_state: candidate.session_state,
+            since_activity: candidate.since_activity,
+            pane: candidate.backend.pane_target().and_then(|t| crate::tmux::capture_pane_tail(t, 20)),
+            cpu_percent,
+            transcript_age: candidate.worktree_path.as_deref().and_then(transcript_age),
+        })
+        .collect()
+}
+
+/// Directory Claude Code keeps a working directory's transcripts in
+fn transcript_dir(cwd: &Path) -> Option<PathBuf> {
+    let encoded: String = cwd
+        .to_string_lossy()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect();
+    Some(dirs::home_dir()?.join(".claude").join("projects").join(encoded))
+}
+
+/// Time since the newest transcript for `cwd` was written
+fn transcript_age(cwd: &Path) -> Option<Duration> {
+    let newest = std::fs::read_dir(transcript_dir(cwd)?)
+        .ok()?
+        .filter_map(|e| e.ok())
+        .filter(|e| e.path().extension().is_some_and(|ext| ext == "jsonl"))
+        .filter_map(|e| e.metadata().ok()?.modified().ok())
+        .max()?;
+    SystemTime::now().duration_since(newest).ok()
+}
+
+/// CPU use of each root's process tree over `CPU_SAMPLE`, from /proc where
+/// there is one (Linux) and `ps` otherwise
+fn sample_cpu(roots: &[Option<u32>]) -> Vec<Option<f32>> {
+    if roots.iter().all(Option::is_none) {
+        return vec![None; roots.len()];
+    }
+    let children = process_children();
+    let trees: Vec<Option<Vec<u32>>> = roots.iter().map(|root| root.map(|r| descendants(r, &children))).collect();
+
+    if Path::new("/proc/self/stat").exists() {
+        let ticks = |tree: &Vec<u32>| tree.iter().filter_map(|&pid| cpu_ticks(pid)).sum::<u64>();
+        let before: Vec<Option<u64>> = trees.iter().map(|t| t.as_ref().map(ticks)).collect();
+        std::thread::sleep(CPU_SAMPLE);
+        // Clock ticks are 1/100 s on every Linux kanblam runs on
+        return trees
+            .iter()
+            .zip(before)
+            .map(|(tree, before)| {
+                let delta = ticks(tree.as_ref()?).saturating_sub(before?);
+                Some(delta as f32 / CPU_SAMPLE.as_secs_f32())
+            })
+            .collect();
+    }
+
+    // ps reports a recent average on macOS/BSD
+    let percent = process_cpu_percent();
+    trees
+        .iter()
+        .map(|tree| Some(tree.as_ref()?.iter().filter_map(|pid| percent.get(pid)).sum()))
+        .collect()
+}
+
+/// Parent PID -> child PIDs for every process
+fn process_children() -> HashMap<u32, Vec<u32>> {
+    let mut children: HashMap<u32, Vec<u32>> = HashMap::new();
+    let Ok(output) = Command::new("ps").args(["-A", "-o", "pid=,ppid="]).output() else {
+        return children;
+    };
+    for line in String::from_utf8_lossy(&output.stdout).lines() {
+        let mut fields = line.split_whitespace().filter_map(|f| f.parse::<u32>().ok());
+        if let (Some(pid), Some(ppid)) = (fields.next(), fields.next()) {
+            children.entry(ppid).or_default().push(pid);
+        }
+    }
+    children
+}
+
+fn descendants(root: u32, children: &HashMap<u32, Vec<u32>>) -> Vec<u32> {
+    let mut tree = vec![root];
+    let mut i = 0;
+    while i < tree.len() {
+        if let Some(kids) = children.get(&tree[i]) {
+            tree.extend(kids);
+        }
+        i += 1;
+    }
+    tree
+}
+
+/// User + system clock ticks a process has used (Linux)
+fn cpu_ticks(pid: u32) -> Option<u64> {
+    let stat = std::fs::read_to_string(format!("/proc/{}/stat", pid)).ok()?;
+    // Fields after the parenthesised command name; utime and stime are the 12th and 13th
+    let mut fields = stat.rsplit_once(')')?.1.split_whitespace().skip(11);
+    Some(fields.next()?.parse::<u64>().ok()? + fields.next()?.parse::<u64>().ok()?)
+}
+
+fn process_cpu_percent() -> HashMap<u32, f32> {
+    let Ok(output) = Command::new("ps").args(["-A", "-o", "pid=,pcpu="]).output() else {
+        return HashMap::new();
+    };
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            Some((fields.next()?.parse().ok()?, fields.next()?.parse().ok()?))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn observation(pane: &str, cpu: f32, transcript_secs: u64) -> Observation {
+        Observation {
+            session_state: ClaudeSessionState::Working,
+            since_activity: Some(Duration::from_secs(600)),
+            pane: Some(pane.to_string()),
+            cpu_percent: Some(cpu),
+            transcript_age: Some(Duration::from_secs(transcript_secs)),
+        }
+    }
+
+    #[test]
+    fn test_strategy_needs_agreement_before_idle() {
+        let tmux = Strategy::for_backend(&Backend::TaskWindow("kc-app:TSK-1".to_string()));
+
+        let idle = tmux.evaluate(&observation("● Done.\n\n> \n  ? for shortcuts", 0.0, 300));
+        assert!(idle.is_idle(), "{:?}", idle);
+        assert!(idle.describe().contains("prompt shown"));
+
+        // The input box is always drawn; the status line gives work away
+        let working = tmux.evaluate(&observation("✻ Thinking… (esc to interrupt)\n> ", 0.5, 300));
+        assert!(!working.is_idle(), "{:?}", working);
+
+        // Busy CPU outweighs a quiet-looking pane
+        assert!(!tmux.evaluate(&observation("> ", 40.0, 300)).is_idle());
+
+        // A single source of evidence isn't enough
+        let pane_only = Observation { pane: Some("> ".to_string()), ..Observation::default() };
+        assert_eq!(tmux.evaluate(&pane_only).confidence, 0.0);
+
+        let sdk = Strategy::for_backend(&Backend::Sdk);
+        let stale = Observation {
+            session_state: ClaudeSessionState::Working,
+            since_activity: Some(Duration::from_secs(900)),
+            transcript_age: Some(Duration::from_secs(900)),
+            ..Observation::default()
+        };
+        assert!(sdk.evaluate(&stale).is_idle());
+        assert!(!sdk.evaluate(&Observation { transcript_age: Some(Duration::from_secs(2)), ..stale }).is_idle());
+    }
+
+    #[test]
+    fn test_process_tree_and_transcript_dir() {
+        let children = HashMap::from([(1, vec![2, 3]), (3, vec![4])]);
+        assert_eq!(descendants(1, &children), [1, 2, 3, 4]);
+        let dir = transcript_dir(Path::new("/work/my.app/worktrees/TSK-1")).unwrap();
+        assert!(dir.ends_with(".claude/projects/-work-my-app-worktrees-TSK-1"));
+    }
+}
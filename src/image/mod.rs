@@ -7,7 +7,7 @@ use ratatui::{
     style::{Color, Style},
     text::{Line, Span},
 };
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 /// Get image directory for storing pasted images
 pub fn get_image_dir() -> Result<PathBuf> {
@@ -29,6 +29,46 @@ pub fn clipboard_has_image() -> bool {
     }
 }
 
+/// Put plain text on the clipboard
+pub fn copy_text_to_clipboard(text: &str) -> Result<()> {
+    let mut clipboard = Clipboard::new()
+        .map_err(|e| anyhow!("Failed to access clipboard: {}", e))?;
+    clipboard.set_text(text.to_string())
+        .map_err(|e| anyhow!("Failed to set clipboard text: {}", e))
+}
+
+/// Get plain text from the clipboard, with line endings made `\n`
+pub fn clipboard_text() -> Result<String> {
+    let mut clipboard = Clipboard::new()
+        .map_err(|e| anyhow!("Failed to access clipboard: {}", e))?;
+    let text = clipboard.get_text()
+        .map_err(|e| anyhow!("No text in clipboard: {}", e))?;
+    Ok(normalize_paste(&text))
+}
+
+/// Pastes longer than this many lines or characters ask before going into the input
+const LARGE_PASTE_LINES: usize = 200;
+const LARGE_PASTE_CHARS: usize = 20_000;
+
+/// Turn `\r\n` and lone `\r` line endings into `\n`
+pub fn normalize_paste(text: &str) -> String {
+    text.replace("\r\n", "\n").replace('\r', "\n")
+}
+
+/// Whether pasted text is long enough to ask before inserting it
+pub fn is_large_paste(text: &str) -> bool {
+    text.lines().count() > LARGE_PASTE_LINES || text.chars().count() > LARGE_PASTE_CHARS
+}
+
+/// Save pasted text as a text file attachment
+pub fn store_text_attachment(text: &str) -> Result<PathBuf> {
+    let timestamp = chrono::Utc::now().format("%Y%m%d_%H%M%S_%3f");
+    let path = get_attachment_dir()?.join(format!("paste_{}.txt", timestamp));
+    std::fs::write(&path, text)
+        .map_err(|e| anyhow!("Failed to save paste: {}", e))?;
+    Ok(path)
+}
+
 /// Get image from clipboard and save to disk
 /// Returns the path where the image was saved
 pub fn paste_image_from_clipboard() -> Result<PathBuf> {
@@ -59,6 +99,84 @@ pub fn paste_image_from_clipboard() -> Result<PathBuf> {
     Ok(image_path)
 }
 
+/// Extensions sent to Claude as images rather than as file attachments
+const IMAGE_EXTENSIONS: &[&str] = &["png", "jpg", "jpeg", "gif", "webp"];
+
+/// Whether a file would be attached as an image
+pub fn is_image_file(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| IMAGE_EXTENSIONS.iter().any(|i| e.eq_ignore_ascii_case(i)))
+}
+
+/// Get directory for storing attached files (next to the images directory)
+pub fn get_attachment_dir() -> Result<PathBuf> {
+    let dir = get_image_dir()?
+        .parent()
+        .map(|p| p.join("attachments"))
+        .ok_or_else(|| anyhow!("No data directory for attachments"))?;
+    std::fs::create_dir_all(&dir)?;
+    Ok(dir)
+}
+
+/// Copy a file into kanblam's storage so the task keeps it if the original moves.
+/// Images go next to pasted images, everything else into the attachments directory.
+pub fn store_attachment(source: &Path) -> Result<PathBuf> {
+    let name = source.file_name()
+        .ok_or_else(|| anyhow!("{} is not a file", source.display()))?;
+    let dir = if is_image_file(source) { get_image_dir()? } else { get_attachment_dir()? };
+    // Already stored (a paste saved as a file)
+    if source.parent() == Some(dir.as_path()) {
+        return Ok(source.to_path_buf());
+    }
+    let timestamp = chrono::Utc::now().format("%Y%m%d_%H%M%S_%3f");
+    let dest = dir.join(format!("{}_{}", timestamp, name.to_string_lossy()));
+    std::fs::copy(source, &dest)
+        .map_err(|e| anyhow!("Failed to copy {}: {}", source.display(), e))?;
+    Ok(dest)
+}
+
+/// Interpret pasted text as files dropped onto the terminal.
+/// Terminals paste dropped files as paths separated by spaces or newlines,
+/// quoted or with backslash-escaped spaces, sometimes as file:// URLs.
+/// Returns None unless every entry is an existing file.
+pub fn parse_dropped_paths(text: &str) -> Option<Vec<PathBuf>> {
+    let mut entries = Vec::new();
+    let mut current = String::new();
+    let mut quote = None;
+    let mut chars = text.trim().chars();
+    while let Some(ch) = chars.next() {
+        match (ch, quote) {
+            ('\\', None) => current.extend(chars.next()),
+            ('\'' | '"', None) => quote = Some(ch),
+            (c, Some(q)) if c == q => quote = None,
+            (c, None) if c.is_whitespace() => {
+                if !current.is_empty() {
+                    entries.push(std::mem::take(&mut current));
+                }
+            }
+            (c, _) => current.push(c),
+        }
+    }
+    if !current.is_empty() {
+        entries.push(current);
+    }
+    if entries.is_empty() {
+        return None;
+    }
+
+    entries.into_iter()
+        .map(|entry| {
+            let entry = entry.strip_prefix("file://").unwrap_or(&entry);
+            let path = match entry.strip_prefix("~/") {
+                Some(rest) => dirs::home_dir()?.join(rest),
+                None => PathBuf::from(entry),
+            };
+            (path.is_absolute() && path.is_file()).then_some(path)
+        })
+        .collect()
+}
+
 /// Configuration for ANSI image rendering
 pub struct AnsiRenderConfig {
     /// Maximum width in characters
@@ -173,6 +291,34 @@ mod tests {
         assert!(dir.is_ok());
     }
 
+    #[test]
+    fn test_parse_dropped_paths() {
+        let dir = tempfile::tempdir().unwrap();
+        let plain = dir.path().join("notes.txt");
+        let spaced = dir.path().join("my log.txt");
+        std::fs::write(&plain, "x").unwrap();
+        std::fs::write(&spaced, "x").unwrap();
+
+        let escaped = format!("{} {}", plain.display(), spaced.display().to_string().replace(' ', "\\ "));
+        assert_eq!(parse_dropped_paths(&escaped), Some(vec![plain.clone(), spaced.clone()]));
+        let quoted = format!("'{}'\n", spaced.display());
+        assert_eq!(parse_dropped_paths(&quoted), Some(vec![spaced]));
+
+        // Ordinary text, relative or missing paths are left to be typed
+        assert_eq!(parse_dropped_paths("fix the login bug"), None);
+        assert_eq!(parse_dropped_paths(&format!("see {}", plain.display())), None);
+        assert_eq!(parse_dropped_paths("/no/such/file"), None);
+        assert_eq!(parse_dropped_paths("  "), None);
+    }
+
+    #[test]
+    fn test_large_paste() {
+        assert_eq!(normalize_paste("a\r\nb\rc\n"), "a\nb\nc\n");
+        assert!(!is_large_paste("fn main() {}\n"));
+        assert!(is_large_paste(&"line\n".repeat(LARGE_PASTE_LINES + 1)));
+        assert!(is_large_paste(&"x".repeat(LARGE_PASTE_CHARS + 1)));
+    }
+
     #[test]
     fn test_pixel_to_color() {
         let pixel = image::Rgba([255, 128, 64, 255]);
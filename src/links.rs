@@ -0,0 +1,79 @@
+//! URLs in command output and task text, and opening them in the browser
+//!
+//! Deploy commands print the address of what they deployed among progress
+//! lines and dashboard links; the preview URL is the last one printed.
+
+use std::process::{Command, Stdio};
+
+/// Characters that end a URL when they trail it (sentence punctuation, closing brackets)
+const TRAILING: &[char] = &['.', ',', ';', ':', '!', '?', ')', ']', '}', '>', '\'', '"', '`'];
+
+/// The http(s) URLs in `text`, in order
+pub fn find_urls(text: &str) -> Vec<&str> {
+    let mut urls = Vec::new();
+    let mut rest = text;
+    while let Some(start) = rest.find("http") {
+        let candidate = &rest[start..];
+        let len = candidate
+            .find(|c: char| c.is_whitespace() || c.is_control() || c == '"' || c == '<')
+            .unwrap_or(candidate.len());
+        let url = candidate[..len].trim_end_matches(TRAILING);
+        let is_url = ["http://", "https://"].iter().any(|scheme| url.len() > scheme.len() && url.starts_with(scheme));
+        if is_url {
+            urls.push(url);
+        }
+        rest = &candidate[len..];
+    }
+    urls
+}
+
+/// Where a deploy command says the preview went: the last URL it printed
+pub fn deployed_url(output: &str) -> Option<&str> {
+    find_urls(output).pop()
+}
+
+/// What a URL written in a task points at, going by its path
+pub fn kind(url: &str) -> &'static str {
+    // GitHub/GitLab issues, Jira and YouTrack tickets
+    if ["/issues/", "/browse/", "/issue/"].iter().any(|path| url.contains(path)) {
+        "Issue"
+    } else {
+        "Link"
+    }
+}
+
+/// Open `url` with the platform's opener (`open` on macOS, `xdg-open` elsewhere)
+pub fn open(url: &str) -> std::io::Result<()> {
+    let opener = if cfg!(target_os = "macos") { "open" } else { "xdg-open" };
+    let mut child = Command::new(opener)
+        .arg(url)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()?;
+    // Reap the opener once it hands the URL over
+    std::thread::spawn(move || child.wait());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_urls() {
+        let vercel = "Vercel CLI 33.0.1\n🔍  Inspect: https://vercel.com/acme/app/8xk2 [2s]\n\
+                      ✅  Preview: https://app-git-fix-acme.vercel.app [2s]\n";
+        assert_eq!(find_urls(vercel), vec!["https://vercel.com/acme/app/8xk2", "https://app-git-fix-acme.vercel.app"]);
+        assert_eq!(deployed_url(vercel), Some("https://app-git-fix-acme.vercel.app"));
+        assert_eq!(
+            deployed_url("Visit your newly deployed app at https://app.fly.dev/."),
+            Some("https://app.fly.dev/"),
+        );
+        assert_eq!(find_urls("see (http://localhost:41000), or httpd"), vec!["http://localhost:41000"]);
+        assert_eq!(deployed_url("Deployed!"), None);
+        assert_eq!(kind("https://github.com/a/b/issues/12"), "Issue");
+        assert_eq!(kind("https://acme.atlassian.net/browse/APP-7"), "Issue");
+        assert_eq!(kind("https://docs.rs/ratatui"), "Link");
+    }
+}
@@ -0,0 +1,229 @@
+//! Debug log: `tracing` events go to a rotating file and to an in-memory tail
+//!
+//! The TUI owns the terminal, so anything printed to stderr is lost behind the
+//! alternate screen. Events are written to `kanblam.log` in the data directory
+//! instead (rotated at `MAX_FILE_BYTES`, keeping `KEEP_FILES` old files), and
+//! the newest ones stay in memory for the log viewer (`). `KANBLAM_LOG` sets
+//! the most verbose level recorded (default `debug`).
+
+use chrono::{DateTime, Local};
+use std::collections::VecDeque;
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use tracing::field::{Field, Visit};
+use tracing::{Event, Level, Metadata, Subscriber};
+use tracing_subscriber::layer::{Context, Layer, SubscriberExt};
+use tracing_subscriber::util::SubscriberInitExt;
+
+/// Lines kept in memory for the viewer
+const TAIL_LINES: usize = 2000;
+
+/// Size at which the log file is rotated
+const MAX_FILE_BYTES: u64 = 5 * 1024 * 1024;
+
+/// Rotated files kept next to the current one (kanblam.log.1 is the newest)
+const KEEP_FILES: usize = 3;
+
+/// One recorded event
+#[derive(Debug, Clone)]
+pub struct LogLine {
+    /// Increases by one per event, so a paused viewer can hold its place
+    pub seq: u64,
+    pub at: DateTime<Local>,
+    pub level: Level,
+    /// Module the event came from, without the crate name
+    pub target: String,
+    pub message: String,
+}
+
+struct Tail {
+    lines: VecDeque<LogLine>,
+    next_seq: u64,
+}
+
+static TAIL: Mutex<Tail> = Mutex::new(Tail { lines: VecDeque::new(), next_seq: 0 });
+
+struct LogFile {
+    path: PathBuf,
+    file: File,
+    written: u64,
+}
+
+impl LogFile {
+    fn open(path: PathBuf) -> std::io::Result<Self> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+        let written = file.metadata().map(|m| m.len()).unwrap_or(0);
+        Ok(Self { path, file, written })
+    }
+
+    fn write_line(&mut self, line: &str) {
+        if self.written + line.len() as u64 + 1 > MAX_FILE_BYTES {
+            self.rotate();
+        }
+        if writeln!(self.file, "{}", line).is_ok() {
+            self.written += line.len() as u64 + 1;
+        }
+    }
+
+    /// kanblam.log -> kanblam.log.1 -> ... -> kanblam.log.KEEP_FILES (dropped)
+    fn rotate(&mut self) {
+        let numbered = |n: usize| self.path.with_extension(format!("log.{}", n));
+        for n in (1..KEEP_FILES).rev() {
+            let _ = std::fs::rename(numbered(n), numbered(n + 1));
+        }
+        let _ = std::fs::rename(&self.path, numbered(1));
+        if let Ok(file) = OpenOptions::new().create(true).append(true).open(&self.path) {
+            self.file = file;
+        }
+        self.written = 0;
+    }
+}
+
+static FILE: Mutex<Option<LogFile>> = Mutex::new(None);
+
+/// Where the current log file lives
+pub fn log_path() -> PathBuf {
+    dirs::data_local_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("kanblam")
+        .join("logs")
+        .join("kanblam.log")
+}
+
+/// Start recording `tracing` events (call once, before the TUI starts)
+pub fn init() {
+    let max_level = std::env::var("KANBLAM_LOG").ok()
+        .and_then(|level| level.parse::<Level>().ok())
+        .unwrap_or(Level::DEBUG);
+    if let (Ok(file), Ok(mut slot)) = (LogFile::open(log_path()), FILE.lock()) {
+        *slot = Some(file);
+    }
+    let _ = tracing_subscriber::registry().with(LogLayer { max_level }).try_init();
+    tracing::info!("kanblam {} started, logging up to {}", env!("CARGO_PKG_VERSION"), max_level);
+}
+
+/// Lines at `min_level` or more severe, from `target` (all modules if None),
+/// up to `until` (the newest if None), oldest first
+pub fn lines(min_level: Level, target: Option<&str>, until: Option<u64>) -> Vec<LogLine> {
+    let Ok(tail) = TAIL.lock() else {
+        return Vec::new();
+    };
+    tail.lines.iter()
+        .filter(|line| line.level <= min_level)
+        .filter(|line| target.is_none_or(|t| line.target == t))
+        .filter(|line| until.is_none_or(|seq| line.seq <= seq))
+        .cloned()
+        .collect()
+}
+
+/// Sequence number of the newest line
+pub fn latest_seq() -> Option<u64> {
+    TAIL.lock().ok().and_then(|tail| tail.lines.back().map(|line| line.seq))
+}
+
+/// Modules that logged something, sorted
+pub fn targets() -> Vec<String> {
+    let Ok(tail) = TAIL.lock() else {
+        return Vec::new();
+    };
+    let mut targets: Vec<String> = tail.lines.iter().map(|line| line.target.clone()).collect();
+    targets.sort();
+    targets.dedup();
+    targets
+}
+
+/// "hooks::watcher" for "kanblam::hooks::watcher"
+fn short_target(target: &str) -> String {
+    target.strip_prefix("kanblam::").unwrap_or(target).to_string()
+}
+
+struct LogLayer {
+    max_level: Level,
+}
+
+impl<S: Subscriber> Layer<S> for LogLayer {
+    fn enabled(&self, metadata: &Metadata<'_>, _ctx: Context<'_, S>) -> bool {
+        // Only our own events: dependencies log through `tracing` too
+        *metadata.level() <= self.max_level && metadata.target().starts_with("kanblam")
+    }
+
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let mut visitor = MessageVisitor::default();
+        event.record(&mut visitor);
+        let metadata = event.metadata();
+        let at = Local::now();
+        let target = short_target(metadata.target());
+
+        if let Ok(mut file) = FILE.lock() {
+            if let Some(file) = file.as_mut() {
+                file.write_line(&format!(
+                    "{} {:>5} {}: {}",
+                    at.format("%Y-%m-%dT%H:%M:%S%.3f"),
+                    metadata.level(),
+                    target,
+                    visitor.message
+                ));
+            }
+        }
+
+        if let Ok(mut tail) = TAIL.lock() {
+            let seq = tail.next_seq;
+            tail.next_seq += 1;
+            if tail.lines.len() == TAIL_LINES {
+                tail.lines.pop_front();
+            }
+            tail.lines.push_back(LogLine { seq, at, level: *metadata.level(), target, message: visitor.message });
+        }
+    }
+}
+
+/// The event's message followed by its other fields as `key=value`
+#[derive(Default)]
+struct MessageVisitor {
+    message: String,
+}
+
+impl Visit for MessageVisitor {
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        if field.name() == "message" {
+            self.message.insert_str(0, &format!("{:?}", value));
+        } else {
+            self.message.push_str(&format!(" {}={:?}", field.name(), value));
+        }
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            self.message.insert_str(0, value);
+        } else {
+            self.message.push_str(&format!(" {}={}", field.name(), value));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rotate_keeps_numbered_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut log = LogFile::open(dir.path().join("kanblam.log")).unwrap();
+        for n in 0..5 {
+            log.write_line(&format!("line {}", n));
+            log.rotate();
+        }
+        let mut names: Vec<String> = std::fs::read_dir(dir.path()).unwrap()
+            .map(|e| e.unwrap().file_name().to_string_lossy().to_string())
+            .collect();
+        names.sort();
+        assert_eq!(names, vec!["kanblam.log", "kanblam.log.1", "kanblam.log.2", "kanblam.log.3"]);
+        assert_eq!(std::fs::read_to_string(dir.path().join("kanblam.log.1")).unwrap(), "line 4\n");
+        assert_eq!(short_target("kanblam::hooks::watcher"), "hooks::watcher");
+    }
+}
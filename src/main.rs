@@ -3,26 +3,54 @@
 // This application follows The Elm Architecture (TEA) pattern
 // Entry point for the KanBlam TUI application
 mod app;
+mod audit;
+mod capabilities;
+mod changelog;
+mod chat;
+mod ci;
+mod commit_message;
+mod diagnostics;
+mod errorlog;
+mod event_loop;
+mod handoff;
 mod hooks;
+mod idle;
 mod image;
+mod links;
+mod logging;
 mod message;
 mod model;
 mod notify;
+mod plugin;
+mod ports;
+mod release;
+mod remote;
+mod rules;
+mod search;
+mod secrets;
+mod sla;
+mod slash;
+mod spell;
 mod sidecar;
 mod statusbar;
+mod sync;
+mod timing;
 mod tmux;
+mod transcribe;
 mod ui;
+mod watcher_scope;
+mod webhook;
 mod worktree; // Handles git worktree isolation for parallel task execution
 
 use app::{load_state, save_state, App};
 use chrono::Utc;
 use hooks::{HookWatcher, WatcherEvent};
 use message::Message;
-use model::{EnterResult, FocusArea, HookSignal, TaskStatus};
+use model::{EnterResult, FocusArea, HookPayload, HookSignal, TaskStatus};
 use ratatui::{
     backend::CrosstermBackend,
     crossterm::{
-        event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEventKind, KeyModifiers, MouseEventKind, MouseButton},
+        event::{self, DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste, EnableMouseCapture, Event, KeyCode, KeyEventKind, KeyModifiers, MouseEventKind, MouseButton},
         execute,
         terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
     },
@@ -44,6 +72,16 @@ fn process_commands_recursively(app: &mut App, commands: Vec<Message>) {
     }
 }
 
+/// Replay the session events the sidecar journaled while its event receiver
+/// was disconnected. Call after subscribing, so nothing falls in between;
+/// events that then also arrive live are dropped by sequence number.
+fn replay_sidecar_events(app: &mut App) {
+    for msg in app.missed_sidecar_events() {
+        let commands = app.update(msg);
+        process_commands_recursively(app, commands);
+    }
+}
+
 /// Channel for receiving results from async background tasks
 type AsyncResultReceiver = mpsc::UnboundedReceiver<Message>;
 
@@ -62,6 +100,24 @@ fn parse_state_file_arg(args: &[String]) -> Option<PathBuf> {
     None
 }
 
+/// Whether --safe-mode was passed (load the board without any integrations)
+fn parse_safe_mode_arg(args: &[String]) -> bool {
+    args.iter().any(|arg| arg == "--safe-mode")
+}
+
+/// Parse --workspace argument: start with the named workspace's projects open
+fn parse_workspace_arg(args: &[String]) -> Option<String> {
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        if arg == "--workspace" {
+            return iter.next().cloned();
+        } else if let Some(name) = arg.strip_prefix("--workspace=") {
+            return Some(name.to_string());
+        }
+    }
+    None
+}
+
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     // Check for CLI subcommands (used by hooks)
@@ -74,38 +130,78 @@ async fn main() -> anyhow::Result<()> {
         return handle_signal_command(&args[2..]);
     }
 
+    // Audit log export: kanblam audit export [--since YYYY-MM-DD] [--output FILE]
+    if args.len() > 1 && args[1] == "audit" {
+        return audit::main(&args[2..]);
+    }
+
+    // Keychain secrets for task environments: kanblam secret set|rm NAME
+    if args.len() > 1 && args[1] == "secret" {
+        return secrets::main(&args[2..]);
+    }
+
     // Statusbar subcommand: kanblam statusbar <task-id>
     // Runs a minimal TUI in a tmux pane alongside the shell for developer tools
     if args.len() > 1 && args[1] == "statusbar" {
         return statusbar::main(&args[2..]);
     }
 
+    // Debug log for the TUI (stderr is hidden behind the alternate screen)
+    logging::init();
+    timing::begin_startup();
+
     // Parse --state-file option
     let state_file_path = parse_state_file_arg(&args);
 
-    // Load saved state (from custom file if specified)
-    let model = load_state(state_file_path.as_ref()).unwrap_or_default();
+    // Safe mode: only load the persisted board, so state can be fixed or exported
+    // even when an integration crashes at startup
+    let safe_mode = parse_safe_mode_arg(&args);
 
-    // Start sidecar and connect (keep handle to kill on exit)
-    let _sidecar_child = match sidecar::ensure_sidecar_running() {
-        Ok(child) => child, // Store handle to keep process alive
-        Err(_) => None,
-    };
-    let sidecar_client = sidecar::SidecarClient::connect().ok();
+    // Merge in the board from other machines before reading it (see `sync`)
+    let pulled = pull_state(state_file_path.as_ref(), safe_mode);
 
-    // Create event receiver for sidecar notifications
-    let sidecar_receiver = sidecar::SidecarEventReceiver::connect().ok();
+    // Load saved state (from custom file if specified). A file that can't be read is
+    // reported rather than replaced with an empty board on exit, and the newest
+    // backup that loads is offered instead.
+    let model = match load_state(state_file_path.as_ref()) {
+        Ok(model) => model,
+        Err(e) => recover_state(state_file_path.as_ref(), e)?,
+    };
+    timing::startup_phase("load state");
 
     // Create async task channel for background operations
     let (async_sender, async_receiver) = mpsc::unbounded_channel::<Message>();
 
     let mut app = App::with_model(model)
         .with_state_file(state_file_path)
-        .with_sidecar(sidecar_client)
+        // Started and connected in the background after the first frame (StartSidecar)
+        .with_sidecar(None)
         .with_async_sender(async_sender);
+    app.safe_mode = safe_mode;
+    app.restore_draft();
+    if let Some(sync::Pulled::Updated { conflicts }) = pulled {
+        app.model.ui_state.status_message = Some(match conflicts {
+            0 => "Synced the board from the state remote".to_string(),
+            n => format!("Synced the board from the state remote ({} item{} edited on both machines, newest kept)",
+                n, if n == 1 { "" } else { "s" }),
+        });
+    }
+    if let Some(name) = parse_workspace_arg(&args) {
+        let commands = app.update(Message::SwitchWorkspace(name));
+        process_commands_recursively(&mut app, commands);
+    }
+    model::activity::set_retention(
+        app.model.global_settings.activity_max_entries,
+        app.model.global_settings.activity_max_kb,
+    );
+    ui::palette::set(app.model.global_settings.palette);
+    notify::configure_sounds(
+        app.model.global_settings.sound_cues,
+        app.model.global_settings.quiet_hours.as_deref(),
+    );
 
     // Create hook watcher for completion detection
-    let mut hook_watcher = HookWatcher::new().ok();
+    let mut hook_watcher = if safe_mode { None } else { HookWatcher::new().ok() };
 
     // Process any signals that arrived while app was not running
     // Signals are sorted chronologically and replayed in order
@@ -126,59 +222,124 @@ async fn main() -> anyhow::Result<()> {
         }
         app.model.ui_state.replaying_signals = false;
     }
+    timing::startup_phase("replay hook signals");
 
-    // Fallback: Check tmux windows for InProgress tasks that are actually idle
-    // This catches cases where signals were lost or had wrong session IDs
-    detect_idle_tasks_from_tmux(&mut app);
-
-    // Initial git status refresh for all tasks with worktrees
-    let commands = app.update(Message::RefreshGitStatus);
-    process_commands_recursively(&mut app, commands);
-
-    // Initial git fetch to get remote status (ahead/behind indicators)
-    let commands = app.update(Message::StartGitFetch);
-    process_commands_recursively(&mut app, commands);
-
-    // Start watcher if it was enabled (sidecar doesn't persist state between restarts)
-    // Check global setting since project.watcher_enabled is not persisted
-    if app.model.global_settings.mascot_advice_enabled == Some(true) {
-        let commands = app.update(Message::StartWatcher);
-        process_commands_recursively(&mut app, commands);
+    if safe_mode {
+        app.model.ui_state.status_message = Some(
+            "Safe mode: sidecar, hooks, tmux scanning and background git are off".to_string()
+        );
+    } else {
+        // First start after an update: show what changed
+        app.update(Message::ShowWhatsNew);
     }
 
     // Setup terminal
     enable_raw_mode()?;
     let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
+    execute!(stdout, EnterAlternateScreen, EnableMouseCapture, EnableBracketedPaste)?;
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
     terminal.clear()?; // Clear screen to remove any cargo-watch output artifacts
+    timing::startup_phase("set up terminal");
 
-    // Run the main loop
-    let result = run_app(&mut terminal, &mut app, hook_watcher, sidecar_receiver, async_receiver);
+    // Run the main loop (the sidecar's event receiver connects once it's up)
+    let result = run_app(&mut terminal, &mut app, hook_watcher, None, async_receiver).await;
 
     // Restore terminal
     disable_raw_mode()?;
     execute!(
         terminal.backend_mut(),
         LeaveAlternateScreen,
-        DisableMouseCapture
+        DisableMouseCapture,
+        DisableBracketedPaste
     )?;
     terminal.show_cursor()?;
 
     // Save state on exit
+    app.save_draft();
     if let Err(e) = save_state(&app.model, app.state_file_path.as_ref()) {
         eprintln!("Failed to save state: {}", e);
+    } else if let Err(e) = push_state(&app) {
+        eprintln!("Failed to sync state: {:#}", e);
     }
+    remote::disconnect_all();
 
     result
 }
 
-fn run_app<B: ratatui::backend::Backend + std::io::Write>(
+/// Startup work that talks to tmux, git and the sidecar (skipped in safe mode).
+/// Runs after the first frame; anything slow reports back through the async channel.
+fn start_integrations(app: &mut App) {
+    // Start the sidecar if it isn't running, then connect (and start the watcher)
+    let commands = app.update(Message::StartSidecar);
+    process_commands_recursively(app, commands);
+
+    // Fallback: Check sessions of InProgress tasks that are actually idle
+    // (pane, CPU, transcript and hook evidence combined, see `idle`)
+    // This catches cases where signals were lost or had wrong session IDs
+    let commands = app.update(Message::DetectIdleTasks);
+    process_commands_recursively(app, commands);
+
+    // Use libgit2 for status/diff if it won its benchmark
+    worktree::native::set_enabled(app.model.global_settings.native_git);
+
+    // Board automation rules, reloaded whenever the file changes
+    app.rules = rules::RuleEngine::default_path().map(rules::RuleEngine::new);
+
+    // Reopen the SSH connections of remote projects (and their hook signal forwards)
+    let commands = app.update(Message::ConnectRemoteProjects);
+    process_commands_recursively(app, commands);
+
+    // Find out which of tmux, the claude CLI and the clipboard are missing
+    let commands = app.update(Message::CheckIntegrations);
+    process_commands_recursively(app, commands);
+
+    // Reconcile worktrees that went missing or off-branch while kanblam was closed
+    let commands = app.update(Message::CheckWorktreeIntegrity);
+    process_commands_recursively(app, commands);
+
+    // Initial git status of the active project's worktrees (cards show a spinner until it's in)
+    if let Some(project_dir) = app.model.active_project().map(|p| p.working_dir.clone()) {
+        let commands = app.update(Message::GitStatusChanged { project_dir, display_ids: Vec::new() });
+        process_commands_recursively(app, commands);
+    }
+    app.sync_git_watchers();
+
+    // Pre-warm worktree pools for projects that use them
+    let pools: Vec<_> = app.model.projects.iter()
+        .filter(|p| p.worktree_pool_size > 0)
+        .map(|p| (p.working_dir.clone(), p.worktree_pool_size))
+        .collect();
+    for (project_dir, size) in pools {
+        let commands = app.update(Message::WarmWorktreePool { project_dir, size });
+        process_commands_recursively(app, commands);
+    }
+
+    // Start error-log watchers for projects that have one configured
+    let watched: Vec<_> = app.model.projects.iter()
+        .filter(|p| p.error_log_source.is_some())
+        .map(|p| p.working_dir.clone())
+        .collect();
+    for project_dir in watched {
+        let commands = app.update(Message::StartErrorLogWatch { project_dir });
+        process_commands_recursively(app, commands);
+    }
+
+    // Initial git fetch to get remote status (ahead/behind indicators)
+    let commands = app.update(Message::StartGitFetch);
+    process_commands_recursively(app, commands);
+}
+
+/// Sidecar notifications, read on a background thread (see `SidecarEventReceiver::into_channel`)
+type SidecarNotifications = mpsc::UnboundedReceiver<sidecar::SidecarNotification>;
+
+/// The main loop. It renders, handles whatever arrived, then sleeps until input,
+/// an async result, a hook signal, a sidecar notification or the next tick (see `event_loop`).
+async fn run_app<B: ratatui::backend::Backend + std::io::Write>(
     terminal: &mut Terminal<B>,
     app: &mut App,
     mut hook_watcher: Option<HookWatcher>,
-    mut sidecar_receiver: Option<sidecar::SidecarEventReceiver>,
+    mut sidecar_receiver: Option<SidecarNotifications>,
     mut async_receiver: AsyncResultReceiver,
 ) -> anyhow::Result<()>
 where
@@ -190,9 +351,47 @@ where
     // Track last reconnection attempt for sidecar event receiver
     let mut last_sidecar_reconnect = std::time::Instant::now();
 
+    // Last draw, for the idle redraw rate
+    let mut last_draw: Option<std::time::Instant> = None;
+
+    let mut input = event_loop::Input::new();
+    let mut ticks = event_loop::Ticks::new();
+
     loop {
-        // Render first for responsive UI
-        terminal.draw(|frame| ui::view(frame, app))?;
+        // Render first for responsive UI - but only when something changed, plus a
+        // slow idle redraw so anything not tracked by the dirty flag still catches up
+        let idle_interval = Duration::from_millis(1000 / u64::from(app.model.global_settings.idle_fps.max(1)));
+        if app.needs_redraw || last_draw.is_none_or(|t| t.elapsed() >= idle_interval) {
+            let started = std::time::Instant::now();
+            let mut links = Vec::new();
+            let area = terminal.draw(|frame| {
+                ui::view(frame, app);
+                ui::palette::apply(frame.buffer_mut());
+                if ui::hyperlinks::enabled() {
+                    links = ui::hyperlinks::capture(frame.buffer_mut());
+                }
+            })?.area;
+            // Clickable URLs where the terminal supports OSC 8
+            ui::hyperlinks::write(terminal.backend_mut(), &links)?;
+            timing::record(timing::OpKind::Render, || {
+                let ui = &app.model.ui_state;
+                let view = if ui.show_task_preview {
+                    format!(", task preview ({:?} tab)", ui.task_detail_tab)
+                } else {
+                    String::new()
+                };
+                format!("redraw {}x{}{}", area.width, area.height, view)
+            }, started.elapsed());
+            app.needs_redraw = false;
+            // The board is on screen: now probe tmux, git and the sidecar
+            if last_draw.is_none() {
+                timing::startup_phase("first frame");
+                if !app.safe_mode {
+                    start_integrations(app);
+                }
+            }
+            last_draw = Some(std::time::Instant::now());
+        }
 
         // Process ONE deferred command per iteration (after render)
         // This ensures the UI stays responsive during multi-step operations
@@ -227,158 +426,259 @@ where
             }
         }
 
-        // Poll sidecar notifications (SDK session events + watcher comments)
+        // A restarted sidecar needs a new event subscription
+        if std::mem::take(&mut app.sidecar_resubscribe) {
+            sidecar_receiver = sidecar::SidecarEventReceiver::connect().ok().map(|r| r.into_channel());
+            last_sidecar_reconnect = std::time::Instant::now();
+            if sidecar_receiver.is_some() {
+                replay_sidecar_events(app);
+            }
+        }
+
+        // Handle queued sidecar notifications (SDK session events + watcher comments)
         if let Some(ref mut receiver) = sidecar_receiver {
-            // Poll multiple times to catch queued events
-            for _ in 0..10 {
-                match receiver.try_recv_notification(Duration::from_millis(1)) {
-                    Ok(Some(notification)) => {
-                        let msg = match notification {
-                            sidecar::SidecarNotification::SessionEvent(event) => {
-                                Message::SidecarEvent(event)
-                            }
-                            sidecar::SidecarNotification::WatcherComment(comment) => {
-                                Message::WatcherCommentReceived(comment)
-                            }
-                            sidecar::SidecarNotification::WatcherObserving(status) => {
-                                Message::WatcherObservingChanged(status)
-                            }
-                        };
-                        let commands = app.update(msg);
-                        // Process commands recursively to handle nested commands
-                        // (e.g., CompleteAcceptTask returning ShowConfirmation)
-                        process_commands_recursively(app, commands);
-                    }
-                    Ok(None) => break, // No more events
-                    Err(_) => {
+            loop {
+                match receiver.try_recv() {
+                    Ok(notification) => handle_sidecar_notification(app, notification),
+                    Err(mpsc::error::TryRecvError::Empty) => break,
+                    Err(mpsc::error::TryRecvError::Disconnected) => {
                         // Connection lost, clear receiver to trigger reconnect
                         sidecar_receiver = None;
                         break;
                     }
                 }
             }
-        } else if last_sidecar_reconnect.elapsed() >= Duration::from_secs(5) {
+        } else if !app.safe_mode && last_sidecar_reconnect.elapsed() >= Duration::from_secs(5) {
             // Try to reconnect to sidecar if receiver is None
             last_sidecar_reconnect = std::time::Instant::now();
             if let Ok(receiver) = sidecar::SidecarEventReceiver::connect() {
-                sidecar_receiver = Some(receiver);
+                sidecar_receiver = Some(receiver.into_channel());
+                replay_sidecar_events(app);
             }
         }
 
-        // Handle events with timeout for tick
-        // Use shorter timeout when modal is open for responsive rendering
-        let poll_timeout = if app.model.ui_state.interactive_modal.is_some() {
-            Duration::from_millis(50)
+        // Sleep until something arrives or the next tick (or idle redraw) is due.
+        // Still-deferred commands go on right away.
+        let tick_period = if app.model.ui_state.interactive_modal.is_some() {
+            event_loop::MODAL_TICK
         } else {
-            Duration::from_millis(100)
+            event_loop::TICK
         };
-
-        if event::poll(poll_timeout)? {
-            match event::read()? {
-                Event::Key(key) => {
-                    // Only handle Press events, ignore Release and Repeat
-                    if key.kind != KeyEventKind::Press {
-                        continue;
-                    }
-
-                    // Track consecutive ESC presses for showing help hints
-                    // ESC increments counter, any other key resets it
-                    if key.code == KeyCode::Esc {
-                        app.model.ui_state.consecutive_esc_count =
-                            app.model.ui_state.consecutive_esc_count.saturating_add(1);
-                    } else {
-                        app.model.ui_state.consecutive_esc_count = 0;
+        let idle_redraw = last_draw.map(|t| t + idle_interval);
+        let wake_at = idle_redraw.map_or(ticks.next(), |at| at.min(ticks.next()));
+        if deferred_commands.is_empty() && wake_at > std::time::Instant::now() {
+            let hook_changed = async {
+                match hook_watcher {
+                    Some(ref watcher) => watcher.changed().await,
+                    None => std::future::pending().await,
+                }
+            };
+            let sidecar_notification = async {
+                match sidecar_receiver {
+                    Some(ref mut receiver) => receiver.recv().await,
+                    None => std::future::pending().await,
+                }
+            };
+            tokio::select! {
+                _ = input.ready() => {}
+                Some(msg) = async_receiver.recv() => {
+                    let commands = app.update(msg);
+                    deferred_commands.extend(commands);
+                    ticks.hurry(tick_period);
+                }
+                _ = hook_changed => ticks.hurry(tick_period),
+                notification = sidecar_notification => {
+                    match notification {
+                        Some(notification) => handle_sidecar_notification(app, notification),
+                        None => sidecar_receiver = None,
                     }
+                    ticks.hurry(tick_period);
+                }
+                _ = tokio::time::sleep_until(wake_at.into()) => {}
+            }
+        }
 
-                    // Check if interactive modal is active
-                    if app.model.ui_state.interactive_modal.is_some() {
-                        let messages = handle_interactive_modal_input(key, app);
-                        for msg in messages {
-                            let commands = app.update(msg);
-                            process_commands_recursively(app, commands);
+        // Handle terminal input (anything crossterm has read or can read without blocking)
+        while event::poll(Duration::ZERO)? {
+            let event = event::read()?;
+            ticks.hurry(tick_period);
+            // Input (and resizes) always redraw, even when no message comes of it
+            app.needs_redraw = true;
+            // Files dropped onto the input become attachments and other text
+            // goes in as written (newlines don't submit). Elsewhere pasted text
+            // is typed in, as it was before bracketed paste
+            let pasting_into_input = app.model.ui_state.focus == FocusArea::TaskInput
+                && app.model.ui_state.interactive_modal.is_none()
+                && app.model.ui_state.pending_confirmation.is_none()
+                && app.model.ui_state.md_file_picker.is_none()
+                && app.model.ui_state.snippet_picker.is_none();
+            let events = match event {
+                Event::Paste(text) if pasting_into_input => {
+                    let message = match crate::image::parse_dropped_paths(&text) {
+                        Some(paths) if app.model.ui_state.note_task_id.is_none() => Message::AttachFiles(paths),
+                        _ => Message::PasteText(text),
+                    };
+                    let commands = app.update(message);
+                    process_commands_recursively(app, commands);
+                    Vec::new()
+                }
+                Event::Paste(text) => pasted_key_events(&text),
+                event => vec![event],
+            };
+            for event in events {
+                    match event {
+                    Event::Key(key) => {
+                        // Only handle Press events, ignore Release and Repeat
+                        if key.kind != KeyEventKind::Press {
+                            continue;
                         }
-                    } else if app.model.ui_state.is_open_project_dialog_open() {
-                        // Handle open project dialog input directly
-                        let messages = handle_open_project_dialog_input(key, app);
-                        for msg in messages {
-                            let commands = app.update(msg);
-                            process_commands_recursively(app, commands);
+
+                        // Track consecutive ESC presses for showing help hints
+                        // ESC increments counter, any other key resets it
+                        if key.code == KeyCode::Esc {
+                            app.model.ui_state.consecutive_esc_count =
+                                app.model.ui_state.consecutive_esc_count.saturating_add(1);
+                        } else {
+                            app.model.ui_state.consecutive_esc_count = 0;
                         }
-                    } else if key.code == KeyCode::Char('z')
-                        && app.model.ui_state.focus != FocusArea::TaskInput
-                        && app.model.ui_state.editing_task_id.is_none()
-                        && app.model.active_project().map_or(false, |p| {
-                            p.watcher_comment.as_ref().map_or(false, |c| c.insight.is_some())
-                        })
-                    {
-                        // Global watcher insight shortcut - works when not editing text
-                        let commands = app.update(Message::OpenWatcherInsightModal);
-                        process_commands_recursively(app, commands);
-                    } else if app.model.ui_state.md_file_picker.is_some() {
-                        // Handle markdown file picker input (intercept before TaskInput)
-                        let messages = handle_md_file_picker_key(key);
-                        for msg in messages {
-                            let commands = app.update(msg);
+
+                        // Check if interactive modal is active
+                        if app.model.ui_state.interactive_modal.is_some() {
+                            let messages = handle_interactive_modal_input(key, app);
+                            for msg in messages {
+                                let commands = app.update(msg);
+                                process_commands_recursively(app, commands);
+                            }
+                        } else if app.model.ui_state.is_open_project_dialog_open() {
+                            // Handle open project dialog input directly
+                            let messages = handle_open_project_dialog_input(key, app);
+                            for msg in messages {
+                                let commands = app.update(msg);
+                                process_commands_recursively(app, commands);
+                            }
+                        } else if key.code == KeyCode::Char('z')
+                            && app.model.ui_state.focus != FocusArea::TaskInput
+                            && app.model.ui_state.editing_task_id.is_none()
+                            && app.model.active_project().map_or(false, |p| {
+                                p.watcher_comment.as_ref().map_or(false, |c| c.insight.is_some())
+                            })
+                        {
+                            // Global watcher insight shortcut - works when not editing text
+                            let commands = app.update(Message::OpenWatcherInsightModal);
                             process_commands_recursively(app, commands);
-                        }
-                    } else if app.model.ui_state.focus == FocusArea::TaskInput {
-                        // Handle input mode directly with textarea
-                        let messages = handle_textarea_input(key, app);
-                        for msg in messages {
-                            // Handle external editor specially - needs terminal access
-                            if matches!(msg, Message::OpenExternalEditor) {
-                                if let Some(result) = open_external_editor(terminal, app) {
-                                    let commands = app.update(Message::ExternalEditorFinished(result));
-                                    process_commands_recursively(app, commands);
-                                }
-                            } else {
+                        } else if app.model.ui_state.md_file_picker.is_some() {
+                            // Handle markdown file picker input (intercept before TaskInput)
+                            let messages = handle_md_file_picker_key(key);
+                            for msg in messages {
                                 let commands = app.update(msg);
                                 process_commands_recursively(app, commands);
                             }
-                        }
-                    } else {
-                        let messages = handle_key_event(key, app);
-                        for msg in messages {
-                            // Handle spec editor specially - needs terminal access
-                            if let Message::OpenSpecEditor(task_id) = msg {
-                                // Get the spec content for the task
-                                let spec_content = app.model.active_project()
-                                    .and_then(|p| p.tasks.iter().find(|t| t.id == task_id))
-                                    .and_then(|t| t.spec.clone())
-                                    .unwrap_or_default();
-
-                                if let Some(result) = open_spec_editor(terminal, &spec_content) {
-                                    let commands = app.update(Message::SpecEditorFinished {
-                                        task_id,
-                                        spec: result
-                                    });
+                        } else if app.model.ui_state.snippet_picker.is_some() {
+                            // Handle feedback snippet picker input (intercept before TaskInput)
+                            let messages = handle_snippet_picker_key(key, app);
+                            for msg in messages {
+                                let commands = app.update(msg);
+                                process_commands_recursively(app, commands);
+                            }
+                        } else if app.model.ui_state.link_picker.is_some() {
+                            // Handle link picker input (over the board or the task detail modal)
+                            let messages = handle_link_picker_key(key, app);
+                            for msg in messages {
+                                let commands = app.update(msg);
+                                process_commands_recursively(app, commands);
+                            }
+                        } else if app.model.ui_state.watcher_feed.is_some() {
+                            // Handle watcher feed input
+                            let messages = handle_watcher_feed_key(key);
+                            for msg in messages {
+                                let commands = app.update(msg);
+                                process_commands_recursively(app, commands);
+                            }
+                        } else if let Some((task_id, _)) = app.model.ui_state.start_failure {
+                            // Handle the start failure modal
+                            let messages = handle_start_failure_key(key, task_id);
+                            for msg in messages {
+                                let commands = app.update(msg);
+                                process_commands_recursively(app, commands);
+                            }
+                        } else if let Some(ref editor) = app.model.ui_state.commit_editor {
+                            // Handle commit message editor input (before a merge)
+                            let messages = handle_commit_editor_key(key, editor);
+                            for msg in messages {
+                                let commands = app.update(msg);
+                                process_commands_recursively(app, commands);
+                            }
+                        } else if app.model.ui_state.focus == FocusArea::TaskInput
+                            && app.model.ui_state.pending_confirmation.is_none()
+                        {
+                            // Handle input mode directly with textarea
+                            let messages = handle_textarea_input(key, app);
+                            for msg in messages {
+                                // Handle external editor specially - needs terminal access
+                                if matches!(msg, Message::OpenExternalEditor) {
+                                    if let Some(result) = open_external_editor(terminal, app) {
+                                        let commands = app.update(Message::ExternalEditorFinished(result));
+                                        process_commands_recursively(app, commands);
+                                    }
+                                } else {
+                                    let commands = app.update(msg);
                                     process_commands_recursively(app, commands);
                                 }
-                            } else {
-                                let commands = app.update(msg);
-                                // Defer commands to next iteration for responsive UI
-                                deferred_commands.extend(commands);
+                            }
+                        } else {
+                            let messages = handle_key_event(key, app);
+                            for msg in messages {
+                                // Handle spec editor specially - needs terminal access
+                                if let Message::OpenSpecEditor(task_id) = msg {
+                                    // Get the spec content for the task
+                                    let spec_content = app.model.active_project()
+                                        .and_then(|p| p.tasks.iter().find(|t| t.id == task_id))
+                                        .and_then(|t| t.spec.clone())
+                                        .unwrap_or_default();
+
+                                    if let Some(result) = open_spec_editor(terminal, &spec_content) {
+                                        let commands = app.update(Message::SpecEditorFinished {
+                                            task_id,
+                                            spec: result
+                                        });
+                                        process_commands_recursively(app, commands);
+                                    }
+                                } else {
+                                    let commands = app.update(msg);
+                                    // Defer commands to next iteration for responsive UI
+                                    deferred_commands.extend(commands);
+                                }
                             }
                         }
                     }
-                }
-                Event::Mouse(mouse) => {
-                    // Ignore mouse events when modal is open
-                    if app.model.ui_state.interactive_modal.is_some() {
-                        continue;
-                    }
-                    let size = terminal.size()?;
-                    let rect = Rect::new(0, 0, size.width, size.height);
-                    if let Some(msg) = handle_mouse_event(mouse, app, rect) {
-                        let commands = app.update(msg);
-                        process_commands_recursively(app, commands);
+                    Event::Mouse(mouse) => {
+                        // Ignore mouse events when modal is open
+                        if app.model.ui_state.interactive_modal.is_some() {
+                            continue;
+                        }
+                        let size = terminal.size()?;
+                        let rect = Rect::new(0, 0, size.width, size.height);
+                        for msg in handle_mouse_event(mouse, app, rect) {
+                            let commands = app.update(msg);
+                            process_commands_recursively(app, commands);
+                        }
                     }
+                    _ => {}
                 }
-                _ => {}
             }
-        } else {
-            // Tick for background updates
-            app.update(Message::Tick);
+        }
+
+        // Tick for background updates, catching up the ticks skipped while idle
+        let now = std::time::Instant::now();
+        let owed = ticks.due(now);
+        if owed > 0 {
+            let redraw_before = std::mem::take(&mut app.needs_redraw);
+            for _ in 0..owed {
+                app.update(Message::Tick);
+            }
+            let moved = app.needs_redraw;
+            app.needs_redraw |= redraw_before;
+            ticks.ran(now, tick_period, moved);
         }
 
         if app.should_quit {
@@ -387,8 +687,11 @@ where
 
         if app.should_restart {
             // Save state before restart
+            app.save_draft();
             if let Err(e) = save_state(&app.model, app.state_file_path.as_ref()) {
-                eprintln!("Warning: Failed to save state before restart: {}", e);
+                tracing::warn!("Failed to save state before restart: {}", e);
+            } else if let Err(e) = push_state(app) {
+                tracing::warn!("Failed to sync state before restart: {:#}", e);
             }
 
             // Restore terminal before restart
@@ -404,6 +707,19 @@ where
     Ok(())
 }
 
+/// Turn a sidecar notification into its message and handle it
+fn handle_sidecar_notification(app: &mut App, notification: sidecar::SidecarNotification) {
+    let msg = match notification {
+        sidecar::SidecarNotification::SessionEvent(event) => Message::SidecarEvent(event),
+        sidecar::SidecarNotification::WatcherComment(comment) => Message::WatcherCommentReceived(comment),
+        sidecar::SidecarNotification::WatcherObserving(status) => Message::WatcherObservingChanged(status),
+    };
+    let commands = app.update(msg);
+    // Process commands recursively to handle nested commands
+    // (e.g., CompleteAcceptTask returning ShowConfirmation)
+    process_commands_recursively(app, commands);
+}
+
 /// Handle hot restart by exec-ing the same binary
 fn handle_restart() -> anyhow::Result<()> {
     let current_exe = std::env::current_exe()?;
@@ -437,7 +753,7 @@ fn open_external_editor<B: ratatui::backend::Backend + std::io::Write>(
 
     // Write current content to temp file
     if let Err(e) = fs::write(&temp_file, &current_text) {
-        eprintln!("Failed to create temp file: {}", e);
+        crate::diagnostics::warn(crate::diagnostics::Source::Io, format!("Failed to create temp file: {}", e));
         return None;
     }
 
@@ -446,7 +762,8 @@ fn open_external_editor<B: ratatui::backend::Backend + std::io::Write>(
     let _ = execute!(
         terminal.backend_mut(),
         LeaveAlternateScreen,
-        DisableMouseCapture
+        DisableMouseCapture,
+        DisableBracketedPaste
     );
     let _ = terminal.show_cursor();
 
@@ -470,7 +787,8 @@ fn open_external_editor<B: ratatui::backend::Backend + std::io::Write>(
     let _ = execute!(
         terminal.backend_mut(),
         EnterAlternateScreen,
-        EnableMouseCapture
+        EnableMouseCapture,
+        EnableBracketedPaste
     );
     let _ = terminal.hide_cursor();
     // Force a full redraw
@@ -515,7 +833,7 @@ fn open_spec_editor<B: ratatui::backend::Backend + std::io::Write>(
 
     // Write spec content to temp file
     if let Err(e) = fs::write(&temp_file, spec_content) {
-        eprintln!("Failed to create temp file: {}", e);
+        crate::diagnostics::warn(crate::diagnostics::Source::Io, format!("Failed to create temp file: {}", e));
         return None;
     }
 
@@ -524,7 +842,8 @@ fn open_spec_editor<B: ratatui::backend::Backend + std::io::Write>(
     let _ = execute!(
         terminal.backend_mut(),
         LeaveAlternateScreen,
-        DisableMouseCapture
+        DisableMouseCapture,
+        DisableBracketedPaste
     );
     let _ = terminal.show_cursor();
 
@@ -548,7 +867,8 @@ fn open_spec_editor<B: ratatui::backend::Backend + std::io::Write>(
     let _ = execute!(
         terminal.backend_mut(),
         EnterAlternateScreen,
-        EnableMouseCapture
+        EnableMouseCapture,
+        EnableBracketedPaste
     );
     let _ = terminal.hide_cursor();
     // Force a full redraw
@@ -578,7 +898,8 @@ fn open_spec_editor<B: ratatui::backend::Backend + std::io::Write>(
 }
 
 /// Handle keyboard input when the interactive modal is active
-/// Ctrl-Esc closes the modal, PageUp/PageDown scroll, other keys are forwarded to tmux
+/// Ctrl-Esc closes the modal, PageUp/PageDown scroll, Ctrl+[ enters copy mode,
+/// other keys are forwarded to tmux
 fn handle_interactive_modal_input(key: event::KeyEvent, app: &mut App) -> Vec<Message> {
     let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
 
@@ -587,6 +908,16 @@ fn handle_interactive_modal_input(key: event::KeyEvent, app: &mut App) -> Vec<Me
         return vec![Message::CloseInteractiveModal];
     }
 
+    // In copy mode keys select from the frozen pane instead of reaching tmux
+    if let Some(copy) = app.model.ui_state.interactive_modal.as_mut().and_then(|m| m.copy_mode.as_mut()) {
+        return handle_copy_mode_input(key, copy);
+    }
+
+    // Ctrl+[: freeze the pane to select and copy its output
+    if ctrl && key.code == KeyCode::Char('[') {
+        return vec![Message::EnterCopyMode];
+    }
+
     // PageUp/PageDown: scroll the modal view (don't forward to tmux)
     match key.code {
         KeyCode::PageUp => {
@@ -621,6 +952,49 @@ fn handle_interactive_modal_input(key: event::KeyEvent, app: &mut App) -> Vec<Me
     vec![]
 }
 
+/// Handle keyboard input in the interactive modal's copy mode (vim keys)
+fn handle_copy_mode_input(key: event::KeyEvent, copy: &mut model::copy_mode::CopyMode) -> Vec<Message> {
+    use model::copy_mode::Motion;
+
+    let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+    let motion = match key.code {
+        KeyCode::Char('u') if ctrl => Motion::HalfPageUp(10),
+        KeyCode::Char('d') if ctrl => Motion::HalfPageDown(10),
+        KeyCode::Char('h') | KeyCode::Left => Motion::Left,
+        KeyCode::Char('l') | KeyCode::Right => Motion::Right,
+        KeyCode::Char('k') | KeyCode::Up => Motion::Up,
+        KeyCode::Char('j') | KeyCode::Down => Motion::Down,
+        KeyCode::Char('w') => Motion::WordForward,
+        KeyCode::Char('b') => Motion::WordBack,
+        KeyCode::Char('0') | KeyCode::Home => Motion::LineStart,
+        KeyCode::Char('$') | KeyCode::End => Motion::LineEnd,
+        KeyCode::Char('g') => Motion::Top,
+        KeyCode::Char('G') => Motion::Bottom,
+        KeyCode::PageUp => Motion::HalfPageUp(20),
+        KeyCode::PageDown => Motion::HalfPageDown(20),
+        KeyCode::Char('v') => {
+            copy.toggle_selection(false);
+            return vec![];
+        }
+        KeyCode::Char('V') => {
+            copy.toggle_selection(true);
+            return vec![];
+        }
+        KeyCode::Char('y') | KeyCode::Enter => return vec![Message::YankCopySelection],
+        KeyCode::Esc | KeyCode::Char('q') => {
+            // Esc drops a selection first, like vim's visual mode
+            if copy.anchor.is_some() && key.code == KeyCode::Esc {
+                copy.anchor = None;
+                return vec![];
+            }
+            return vec![Message::ExitCopyMode];
+        }
+        _ => return vec![],
+    };
+    copy.apply(motion);
+    vec![]
+}
+
 /// Convert a crossterm KeyEvent to a tmux send-keys sequence
 fn key_event_to_tmux_sequence(key: event::KeyEvent) -> String {
     let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
@@ -669,15 +1043,19 @@ fn key_event_to_tmux_sequence(key: event::KeyEvent) -> String {
     }
 }
 
-/// Handle mouse events - clicks on columns and tasks
+/// Handle mouse events - clicks on columns and tasks, and dragging cards between columns
 fn handle_mouse_event(
     mouse: event::MouseEvent,
     app: &App,
     size: Rect,
-) -> Option<Message> {
-    // Only handle left clicks and taps
-    if !matches!(mouse.kind, MouseEventKind::Down(MouseButton::Left)) {
-        return None;
+) -> Vec<Message> {
+    // Only handle the wheel and the left button: clicks and taps, plus drag and release for cards
+    if !matches!(
+        mouse.kind,
+        MouseEventKind::Down(MouseButton::Left) | MouseEventKind::Drag(MouseButton::Left) | MouseEventKind::Up(MouseButton::Left)
+            | MouseEventKind::ScrollUp | MouseEventKind::ScrollDown
+    ) {
+        return vec![];
     }
 
     let x = mouse.column;
@@ -697,18 +1075,54 @@ fn handle_mouse_event(
 
     // Calculate layout regions (project bar at top now)
     // Header height is dynamic based on terminal size (must match ui/mod.rs exactly)
-    // The renderer uses get_logo_size_for_project_bar with project_bar_width, but for mouse
-    // handling we can use should_show_full_logo which is equivalent for determining header height
-    let show_full_logo = crate::ui::logo::should_show_full_logo(size.width, size.height);
+    let logo_size = crate::ui::header_logo_size(app, size.width, size.height);
+    let show_full_logo = matches!(logo_size, crate::ui::logo::LogoSize::Full | crate::ui::logo::LogoSize::Medium);
+    let project_bar_width = crate::ui::project_bar_area_width(app, logo_size, size.width);
     // IMPORTANT: header_height must match ui/mod.rs: 3 for full/medium logo, 1 for compact
     let header_height = if show_full_logo { 3u16 } else { 1u16 };
     let status_height = 1u16;
-    let kanban_height = size.height.saturating_sub(header_height + input_height + status_height);
+    // The missing-integration banner sits between the board and the input
+    let banner_height = if is_welcome_screen { 0 } else { crate::ui::capability_banner(app).is_some() as u16 };
+    let kanban_height = size.height.saturating_sub(header_height + banner_height + input_height + status_height);
 
     let header_y = 0u16;
     let kanban_y = header_height;
-    let input_y = header_height + kanban_height;
-    let status_y = header_height + kanban_height + input_height;
+    let input_y = header_height + kanban_height + banner_height;
+    let status_y = input_y + input_height;
+
+    // Use the exact same layout calculation as the renderer
+    let board_area = Rect::new(0, kanban_y, size.width, kanban_height);
+    let (kanban_area, output_pane_area) = if app.model.ui_state.output_pane.is_some() {
+        let (kanban_area, pane_area) = crate::ui::split_output_pane(board_area);
+        (kanban_area, Some(pane_area))
+    } else {
+        (board_area, None)
+    };
+
+    // A held card follows the pointer anywhere; releasing it drops it
+    match mouse.kind {
+        MouseEventKind::ScrollUp | MouseEventKind::ScrollDown if app.model.ui_state.focus == FocusArea::Monitor => {
+            return vec![];
+        }
+        MouseEventKind::ScrollUp | MouseEventKind::ScrollDown => {
+            return handle_mouse_scroll(mouse, app, size, kanban_area, output_pane_area);
+        }
+        MouseEventKind::Drag(_) if app.model.ui_state.card_drag.is_some() => {
+            return vec![drag_card_message(app, kanban_area, x, y)];
+        }
+        MouseEventKind::Up(_) if app.model.ui_state.card_drag.is_some() => {
+            return vec![Message::DropCard];
+        }
+        MouseEventKind::Up(_) if app.model.ui_state.project_tab_drag.is_some() => {
+            let to = match crate::ui::hit_test_project_bar(app, project_bar_width, x) {
+                Some(crate::ui::ProjectBarHitResult::SwitchProject(idx)) if y == 0 => Some(idx),
+                _ => None,
+            };
+            return vec![Message::DropProjectTab { to }];
+        }
+        MouseEventKind::Down(_) => {}
+        _ => return vec![],
+    }
 
     // Check if click is in header area (project bar + logo)
     if y < kanban_y {
@@ -723,7 +1137,7 @@ fn handle_mouse_event(
                     if let Some(ref comment) = project.watcher_comment {
                         // Click on balloon opens insight modal if available
                         if comment.insight.is_some() {
-                            return Some(Message::OpenWatcherInsightModal);
+                            return vec![Message::OpenWatcherInsightModal];
                         }
                     }
                 }
@@ -731,120 +1145,317 @@ fn handle_mouse_event(
 
             if x >= logo_start_x && y < 4 {
                 // Click on the mascot/logo - trigger blink animation
-                return Some(Message::TriggerMascotBlink);
+                return vec![Message::TriggerMascotBlink];
             }
         }
 
         // Use the exact same layout calculation as the renderer for project tabs
-        if let Some(hit) = crate::ui::hit_test_project_bar(app, x) {
+        if let Some(hit) = crate::ui::hit_test_project_bar(app, project_bar_width, x).filter(|_| y == 0) {
             return match hit {
                 crate::ui::ProjectBarHitResult::AddProject => {
                     let num_projects = app.model.projects.len();
-                    Some(Message::ShowOpenProjectDialog { slot: num_projects })
+                    vec![Message::ShowOpenProjectDialog { slot: num_projects }]
+                }
+                // Switch to the tab, and pick it up in case it gets dragged onto another
+                crate::ui::ProjectBarHitResult::SwitchProject(idx) => {
+                    vec![Message::SwitchProject(idx), Message::StartProjectTabDrag(idx)]
                 }
-                crate::ui::ProjectBarHitResult::SwitchProject(idx) => Some(Message::SwitchProject(idx)),
+                crate::ui::ProjectBarHitResult::ShowProjectPicker => vec![Message::ShowProjectPicker],
             };
         }
         // Click on top line (project tabs row) but not on a specific tab - focus the project bar
         if y == 0 {
-            return Some(Message::FocusChanged(FocusArea::ProjectTabs));
+            return vec![Message::FocusChanged(FocusArea::ProjectTabs)];
         }
-        return None;
+        return vec![];
+    }
+
+    // The monitor takes the board's place: a click highlights a tile, a
+    // second click shows its task on the board
+    if app.model.ui_state.focus == FocusArea::Monitor && y >= kanban_y && y < input_y {
+        let selected = app.model.ui_state.monitor.selected;
+        return match crate::ui::hit_test_monitor(board_area, app.model.monitor_tasks().len(), x, y) {
+            Some(idx) if idx == selected => vec![Message::MonitorJump],
+            Some(idx) => vec![Message::MonitorMove { dx: idx as i32 - selected as i32, dy: 0 }],
+            None => vec![],
+        };
+    }
+
+    // Click in the live output pane focuses it
+    if output_pane_area.is_some_and(|area| area.contains(ratatui::layout::Position::new(x, y))) {
+        return vec![Message::FocusChanged(FocusArea::OutputViewer)];
     }
 
     // Check if click is in kanban area
     if y >= kanban_y && y < input_y {
-        // Use the exact same layout calculation as the renderer
-        let kanban_area = Rect::new(0, kanban_y, size.width, kanban_height);
-
-        if let Some(hit) = crate::ui::hit_test_kanban(kanban_area, x, y) {
-            if let Some(task_idx) = hit.task_idx {
+        let (layout, selected) = (app.model.ui_state.board_layout, app.model.ui_state.selected_column);
+        if let Some(hit) = crate::ui::hit_test_kanban(kanban_area, layout, selected, x, y) {
+            if let Some(row) = hit.task_idx {
                 // Validate task index against actual task count
                 if let Some(project) = app.model.active_project() {
                     let tasks = project.tasks_by_status(hit.status);
-                    if task_idx < tasks.len() {
-                        return Some(Message::ClickedTask { status: hit.status, task_idx });
+                    // Rows include tag group headings when grouping by tag
+                    let clicked = match project.column_rows(hit.status).get(row) {
+                        Some(model::ColumnRow::Task(idx)) => tasks.get(*idx).map(|task| (*idx, task)),
+                        _ => None,
+                    };
+                    if let Some((task_idx, task)) = clicked {
+                        // Select the card, and pick it up in case it gets dragged
+                        return vec![
+                            Message::ClickedTask { status: hit.status, task_idx },
+                            Message::StartCardDrag { task_id: task.id, from: hit.status, x, y },
+                        ];
                     }
                 }
             }
             // Click on column header, border, or empty task area - just select the column
-            return Some(Message::SelectColumn(hit.status));
+            return vec![Message::SelectColumn(hit.status)];
         }
-        return None;
+        return vec![];
     }
 
     // Check if click is in input area
     if y >= input_y && y < status_y {
-        return Some(Message::FocusChanged(FocusArea::TaskInput));
+        return vec![Message::FocusChanged(FocusArea::TaskInput)];
     }
 
     // Click in status bar - could add session switching here in the future
     // For now, status bar shows session info but isn't clickable
     let _ = (header_y, status_y); // Suppress unused variable warnings
 
-    None
+    vec![]
+}
+
+/// Mouse wheel: scroll whatever is under the pointer. An open modal takes the wheel
+/// (outside it nothing scrolls); otherwise the live output pane or the board column
+/// under the pointer scrolls.
+fn handle_mouse_scroll(
+    mouse: event::MouseEvent,
+    app: &App,
+    size: Rect,
+    kanban_area: Rect,
+    output_pane_area: Option<Rect>,
+) -> Vec<Message> {
+    use crate::model::TaskDetailTab;
+
+    let up = mouse.kind == MouseEventKind::ScrollUp;
+    let inside = |area: Rect| area.contains(ratatui::layout::Position::new(mouse.column, mouse.row));
+    let ui = &app.model.ui_state;
+
+    if let Some(ref confirmation) = ui.pending_confirmation {
+        let multiline = confirmation.message.contains('\n');
+        if multiline && inside(crate::ui::confirmation_modal_area(&confirmation.message, size)) {
+            return vec![if up { Message::ScrollConfirmationUp } else { Message::ScrollConfirmationDown }];
+        }
+        return vec![];
+    }
+    if ui.show_help {
+        if inside(crate::ui::help_modal_area(size)) {
+            return vec![if up { Message::ScrollHelpUp(3) } else { Message::ScrollHelpDown(3) }];
+        }
+        return vec![];
+    }
+    if ui.show_stats {
+        return vec![if up { Message::ScrollStatsUp(3) } else { Message::ScrollStatsDown(3) }];
+    }
+    if ui.show_diagnostics {
+        return vec![Message::ScrollDiagnostics(if up { -3 } else { 3 })];
+    }
+    if ui.log_viewer.is_some() {
+        return vec![Message::ScrollLogViewer(if up { 3 } else { -3 })];
+    }
+    if ui.show_watcher_insight_modal {
+        return vec![if up { Message::ScrollWatcherInsightUp } else { Message::ScrollWatcherInsightDown }];
+    }
+    if ui.session_replay.is_some() {
+        return vec![Message::ScrollReplayOutput(if up { -3 } else { 3 })];
+    }
+    if ui.scrollback_viewer.is_some() {
+        return vec![Message::ScrollScrollback(if up { -3 } else { 3 })];
+    }
+    if ui.show_task_preview {
+        if !inside(crate::ui::task_preview_modal_area(size)) {
+            return vec![];
+        }
+        let msg = match (ui.task_detail_tab, up) {
+            (TaskDetailTab::Git, true) => Message::ScrollGitDiffUp(3),
+            (TaskDetailTab::Git, false) => Message::ScrollGitDiffDown(3),
+            (TaskDetailTab::Spec, true) => Message::ScrollSpecUp(3),
+            (TaskDetailTab::Spec, false) => Message::ScrollSpecDown(3),
+            (TaskDetailTab::Notes, true) => Message::ScrollNotesUp(3),
+            (TaskDetailTab::Notes, false) => Message::ScrollNotesDown(3),
+            (TaskDetailTab::Activity, true) => Message::ScrollActivityUp(1),
+            (TaskDetailTab::Activity, false) => Message::ScrollActivityDown(1),
+            _ => return vec![],
+        };
+        return vec![msg];
+    }
+
+    if output_pane_area.is_some_and(inside) {
+        return vec![Message::ScrollOutputPane(if up { -3 } else { 3 })];
+    }
+    let (layout, selected) = (app.model.ui_state.board_layout, app.model.ui_state.selected_column);
+    let Some(hit) = crate::ui::hit_test_kanban(kanban_area, layout, selected, mouse.column, mouse.row) else {
+        return vec![];
+    };
+    let Some(cell) = crate::ui::calculate_kanban_cells(kanban_area, layout, selected)
+        .into_iter()
+        .find(|(status, _)| *status == hit.status)
+        .map(|(_, rect)| rect)
+        .filter(|rect| !crate::ui::is_header_cell(*rect))
+    else {
+        // Collapsed columns have nothing to scroll
+        return vec![];
+    };
+    let visible_rows = cell.height.saturating_sub(2);
+    vec![Message::ScrollColumn { status: hit.status, delta: if up { -3 } else { 3 }, visible_rows }]
+}
+
+/// Follow a held card: the column under the pointer, and whether the pointer sits on
+/// that column's top or bottom edge (which scrolls it)
+fn drag_card_message(app: &App, kanban_area: Rect, x: u16, y: u16) -> Message {
+    let (layout, selected) = (app.model.ui_state.board_layout, app.model.ui_state.selected_column);
+    let over = crate::ui::hit_test_kanban(kanban_area, layout, selected, x, y).map(|hit| hit.status);
+    // Collapsed columns take the card but don't scroll
+    let cell = over.and_then(|status| {
+        crate::ui::calculate_kanban_cells(kanban_area, layout, selected)
+            .into_iter()
+            .find(|(s, _)| *s == status)
+            .map(|(_, rect)| rect)
+            .filter(|rect| !crate::ui::is_header_cell(*rect))
+    });
+
+    let (scroll, over_height) = match cell {
+        Some(rect) => {
+            // Top border or first row scrolls up, last row or bottom border scrolls down
+            let rows = rect.height.saturating_sub(2);
+            let scroll = if y <= rect.y + 1 {
+                -1
+            } else if y + 2 >= rect.y + rect.height {
+                1
+            } else {
+                0
+            };
+            (scroll, rows)
+        }
+        None => (0, 0),
+    };
+
+    Message::DragCard { x, y, over, scroll, over_height }
+}
+
+/// Pull the synced board into the state file, if sync is on. Failures are
+/// reported in diagnostics and the local state is used as it is.
+fn pull_state(custom_path: Option<&PathBuf>, safe_mode: bool) -> Option<sync::Pulled> {
+    let state_file = custom_path.cloned().unwrap_or_else(app::default_state_file_path);
+    let remote = sync::remote(&state_file).filter(|_| !safe_mode)?;
+    match sync::pull(&state_file, &remote) {
+        Ok(pulled) => Some(pulled),
+        Err(e) => {
+            diagnostics::report(diagnostics::Severity::Error, diagnostics::Source::Git,
+                format!("State sync failed, using the local board: {:#}", e), Some(remote));
+            None
+        }
+    }
+}
+
+/// Commit the saved board and push it to the sync remote, if sync is on
+fn push_state(app: &App) -> anyhow::Result<()> {
+    let state_file = app.state_file_path.clone().unwrap_or_else(app::default_state_file_path);
+    match sync::remote(&state_file) {
+        Some(remote) if !app.safe_mode => sync::push(&state_file, &remote),
+        _ => Ok(()),
+    }
+}
+
+/// Offer the newest backup that parses when the state file can't be read.
+/// Declining (or having no usable backup) stops kanblam with the original error.
+fn recover_state(custom_path: Option<&PathBuf>, error: anyhow::Error) -> anyhow::Result<model::AppModel> {
+    use std::io::{BufRead, IsTerminal, Write};
+
+    let state_file = custom_path.cloned().unwrap_or_else(app::default_state_file_path);
+    if !io::stdin().is_terminal() || model::schema::state_is_too_new(&state_file) {
+        return Err(error);
+    }
+    let Some((backup, project_count)) = model::backup::list(&state_file).into_iter().find_map(|backup| {
+        model::backup::probe(&backup, &state_file).ok().map(|count| (backup, count))
+    }) else {
+        return Err(error);
+    };
+
+    let taken = model::backup::taken_at(&backup)
+        .map(|at| at.format("%Y-%m-%d %H:%M").to_string())
+        .unwrap_or_default();
+    eprintln!("Couldn't read {}:\n  {:#}\n", state_file.display(), error);
+    eprint!(
+        "The newest usable backup is from {} ({} project(s)).\n\
+         Unreadable .kanblam/tasks.json files are restored too; readable ones are newer and kept.\n\
+         Remote projects' tasks aren't backed up.\n\
+         Restore it? The current files are kept as *.broken-*. [y/N] ",
+        taken, project_count
+    );
+    let _ = io::stderr().flush();
+    let mut answer = String::new();
+    io::stdin().lock().read_line(&mut answer)?;
+    if !answer.trim().eq_ignore_ascii_case("y") {
+        return Err(error);
+    }
+
+    let tasks_restored = model::backup::restore(&state_file, &backup)?;
+    tracing::warn!(
+        "Restored state (and {} tasks file(s)) from the backup in {}",
+        tasks_restored, backup.display()
+    );
+    load_state(Some(&state_file))
 }
 
 /// Convert a watcher event to a message
 fn convert_watcher_event(event: WatcherEvent) -> Option<Message> {
+    let signal = |event: &str, session_id, project_dir, input_type, source, payload| {
+        Some(Message::HookSignalReceived(HookSignal {
+            event: event.to_string(),
+            session_id,
+            project_dir,
+            timestamp: Utc::now(),
+            transcript_path: None,
+            input_type,
+            source,
+            payload,
+        }))
+    };
     match event {
         WatcherEvent::ClaudeStopped { session_id, project_dir, source } => {
-            Some(Message::HookSignalReceived(HookSignal {
-                event: "stop".to_string(),
-                session_id,
-                project_dir,
-                timestamp: Utc::now(),
-                transcript_path: None,
-                input_type: String::new(),
-                source,
-            }))
+            signal("stop", session_id, project_dir, String::new(), source, HookPayload::default())
         }
         WatcherEvent::SessionEnded { session_id, project_dir, source, .. } => {
-            Some(Message::HookSignalReceived(HookSignal {
-                event: "end".to_string(),
-                session_id,
-                project_dir,
-                timestamp: Utc::now(),
-                transcript_path: None,
-                input_type: String::new(),
-                source,
-            }))
+            signal("end", session_id, project_dir, String::new(), source, HookPayload::default())
         }
         WatcherEvent::NeedsWork { session_id, project_dir, input_type, source } => {
-            Some(Message::HookSignalReceived(HookSignal {
-                event: "needs-input".to_string(),
-                session_id,
-                project_dir,
-                timestamp: Utc::now(),
-                transcript_path: None,
-                input_type,
-                source,
-            }))
+            signal("needs-input", session_id, project_dir, input_type, source, HookPayload::default())
         }
         WatcherEvent::InputProvided { session_id, project_dir, source } => {
-            Some(Message::HookSignalReceived(HookSignal {
-                event: "input-provided".to_string(),
-                session_id,
-                project_dir,
-                timestamp: Utc::now(),
-                transcript_path: None,
-                input_type: String::new(),
-                source,
-            }))
+            signal("input-provided", session_id, project_dir, String::new(), source, HookPayload::default())
         }
         WatcherEvent::Working { session_id, project_dir, source } => {
-            Some(Message::HookSignalReceived(HookSignal {
-                event: "working".to_string(),
-                session_id,
-                project_dir,
-                timestamp: Utc::now(),
-                transcript_path: None,
-                input_type: String::new(),
-                source,
-            }))
+            signal("working", session_id, project_dir, String::new(), source, HookPayload::default())
+        }
+        WatcherEvent::ToolStarted { session_id, project_dir, source, payload } => {
+            signal("tool-start", session_id, project_dir, String::new(), source, payload)
+        }
+        WatcherEvent::ToolFinished { session_id, project_dir, source, payload } => {
+            signal("tool-finish", session_id, project_dir, String::new(), source, payload)
+        }
+        WatcherEvent::ToolFailed { session_id, project_dir, source, payload } => {
+            signal("tool-error", session_id, project_dir, String::new(), source, payload)
+        }
+        WatcherEvent::SubagentStarted { session_id, project_dir, source, payload } => {
+            signal("subagent-start", session_id, project_dir, String::new(), source, payload)
+        }
+        WatcherEvent::SubagentStopped { session_id, project_dir, source, payload } => {
+            signal("subagent-stop", session_id, project_dir, String::new(), source, payload)
         }
         WatcherEvent::Error(e) => {
-            eprintln!("Hook watcher error: {}", e);
+            crate::diagnostics::warn(crate::diagnostics::Source::Hooks, format!("Hook watcher error: {}", e));
             None
         }
     }
@@ -891,6 +1502,16 @@ fn handle_textarea_input(key: event::KeyEvent, app: &mut App) -> Vec<Message> {
         }
     }
 
+    // Any other key leaves history: the recalled text is now just input
+    if !matches!(key.code, KeyCode::Up | KeyCode::Down) {
+        app.model.ui_state.input_history_cursor = None;
+    }
+    let ui = &app.model.ui_state;
+    let recalling = ui.input_history_cursor.is_some();
+    let can_recall = (recalling || ui.get_input_text().is_empty())
+        && !app.model.global_settings.input_history.is_empty()
+        && !(ui.vim_mode_enabled && ui.editor_state.mode == edtui::EditorMode::Insert);
+
     match key.code {
         // Ctrl+S: Submit and immediately start the task
         KeyCode::Char('s') if ctrl => {
@@ -971,7 +1592,7 @@ fn handle_textarea_input(key: event::KeyEvent, app: &mut App) -> Vec<Message> {
                 vec![]
             } else {
                 // Regular mode: Escape unfocuses or cancels
-                if app.model.ui_state.feedback_task_id.is_some() {
+                if app.model.ui_state.feedback_task_id.is_some() || app.model.ui_state.broadcast_feedback {
                     vec![Message::CancelFeedbackMode]
                 } else if app.model.ui_state.editing_task_id.is_some() {
                     vec![Message::CancelEdit]
@@ -986,7 +1607,7 @@ fn handle_textarea_input(key: event::KeyEvent, app: &mut App) -> Vec<Message> {
         KeyCode::Char('c') if ctrl => {
             // Clear pending replace mode if active
             app.model.ui_state.pending_replace_char = false;
-            if app.model.ui_state.feedback_task_id.is_some() {
+            if app.model.ui_state.feedback_task_id.is_some() || app.model.ui_state.broadcast_feedback {
                 vec![Message::CancelFeedbackMode]
             } else if app.model.ui_state.note_task_id.is_some() {
                 vec![Message::CancelNoteMode]
@@ -998,13 +1619,13 @@ fn handle_textarea_input(key: event::KeyEvent, app: &mut App) -> Vec<Message> {
             }
         }
 
-        // Ctrl+V pastes image from clipboard
+        // Ctrl+V pastes the clipboard's image or text
         // Also handle raw control character (ASCII 22) that some terminals send
         KeyCode::Char('v') if ctrl => {
-            vec![Message::PasteImage]
+            vec![Message::PasteClipboard]
         }
         KeyCode::Char('\x16') => {
-            vec![Message::PasteImage]
+            vec![Message::PasteClipboard]
         }
 
         // Ctrl+U clears all images (pending or from edit/feedback task)
@@ -1027,6 +1648,7 @@ fn handle_textarea_input(key: event::KeyEvent, app: &mut App) -> Vec<Message> {
             // Only show file picker when creating a new task (not editing, feedback, or note mode)
             if app.model.ui_state.editing_task_id.is_none()
                 && app.model.ui_state.feedback_task_id.is_none()
+                && !app.model.ui_state.broadcast_feedback
                 && app.model.ui_state.note_task_id.is_none()
             {
                 vec![Message::ShowMdFilePicker]
@@ -1035,6 +1657,25 @@ fn handle_textarea_input(key: event::KeyEvent, app: &mut App) -> Vec<Message> {
             }
         }
 
+        // Ctrl+L attaches (links) a repository file (not for notes)
+        KeyCode::Char('l') if ctrl => {
+            if app.model.ui_state.note_task_id.is_none() {
+                vec![Message::ShowAttachFilePicker]
+            } else {
+                vec![]
+            }
+        }
+
+        // Ctrl+T opens the feedback snippet picker (feedback mode only)
+        KeyCode::Char('t') if ctrl && app.model.ui_state.feedback_task_id.is_some() => {
+            vec![Message::ToggleSnippetPicker]
+        }
+
+        // Alt+1-9 inserts a feedback snippet directly (feedback mode only)
+        KeyCode::Char(c @ '1'..='9') if alt && app.model.ui_state.feedback_task_id.is_some() => {
+            vec![Message::InsertFeedbackSnippet(c as usize - '1' as usize)]
+        }
+
         // Ctrl+I - pass to editor
         KeyCode::Char('i') if ctrl => {
             app.model.ui_state.editor_event_handler.on_key_event(
@@ -1044,11 +1685,30 @@ fn handle_textarea_input(key: event::KeyEvent, app: &mut App) -> Vec<Message> {
             vec![]
         }
 
-        // Up arrow at position 0 moves focus to Kanban board (keeps content)
-        KeyCode::Up => {
-            let cursor = app.model.ui_state.editor_state.cursor;
-            if cursor.row == 0 && cursor.col == 0 {
-                // Clear pending replace mode if active
+        // Ctrl+P shows the input rendered as markdown beside it
+        KeyCode::Char('p') if ctrl => {
+            vec![Message::ToggleMarkdownPreview]
+        }
+
+        // Tab completes the slash command (or its value) being typed
+        KeyCode::Tab if !app.slash_completions().is_empty() => {
+            vec![Message::AcceptSlashCompletion]
+        }
+
+        // Up at an empty input steps back through past submissions, Down forward again
+        // (not while typing in vim Insert mode)
+        KeyCode::Up if can_recall => {
+            vec![Message::InputHistoryOlder]
+        }
+        KeyCode::Down if recalling => {
+            vec![Message::InputHistoryNewer]
+        }
+
+        // Up arrow at position 0 moves focus to Kanban board (keeps content)
+        KeyCode::Up => {
+            let cursor = app.model.ui_state.editor_state.cursor;
+            if cursor.row == 0 && cursor.col == 0 {
+                // Clear pending replace mode if active
                 app.model.ui_state.pending_replace_char = false;
                 // Just unfocus, keep the content
                 vec![Message::FocusChanged(FocusArea::KanbanBoard)]
@@ -1150,7 +1810,8 @@ fn handle_key_event(key: event::KeyEvent, app: &App) -> Vec<Message> {
                     _ => vec![Message::RestartConfirmationAnimation],
                 }
             }
-            // 's' key for stash changes - available for StashConflict and DirtyMainBeforeMerge dialogs
+            // 's' key for stash changes - available for StashConflict and DirtyMainBeforeMerge dialogs,
+            // and for snapshot mode when opening a folder without git
             KeyCode::Char('s') | KeyCode::Char('S') => {
                 match &confirmation.action {
                     model::PendingAction::StashConflict { task_id, .. } => {
@@ -1161,6 +1822,32 @@ fn handle_key_event(key: event::KeyEvent, app: &App) -> Vec<Message> {
                         // Stash changes before merge, then proceed
                         vec![Message::StashThenMerge { task_id: *task_id }]
                     }
+                    model::PendingAction::InitGit { path, name, slot } => {
+                        // Isolate tasks with a shadow repository instead of a .git in the folder
+                        vec![Message::OpenSnapshotProject { path: path.clone(), name: name.clone(), slot: *slot }]
+                    }
+                    _ => vec![Message::RestartConfirmationAnimation],
+                }
+            }
+            // 'f' attaches a large paste as a file, 't' pastes copied file paths as text
+            KeyCode::Char('f') | KeyCode::Char('F') => {
+                match &confirmation.action {
+                    model::PendingAction::LargePaste { .. } => vec![Message::PasteAlternative],
+                    _ => vec![Message::RestartConfirmationAnimation],
+                }
+            }
+            KeyCode::Char('t') | KeyCode::Char('T') => {
+                match &confirmation.action {
+                    model::PendingAction::AttachClipboardFiles { .. } => vec![Message::PasteAlternative],
+                    _ => vec![Message::RestartConfirmationAnimation],
+                }
+            }
+            // 'h' key for hand off - available for RebaseConflict dialogs
+            KeyCode::Char('h') | KeyCode::Char('H') => {
+                match &confirmation.action {
+                    model::PendingAction::RebaseConflict(task_id) => {
+                        vec![Message::HandOffRebaseConflicts(*task_id)]
+                    }
                     _ => vec![Message::RestartConfirmationAnimation],
                 }
             }
@@ -1221,16 +1908,90 @@ fn handle_key_event(key: event::KeyEvent, app: &App) -> Vec<Message> {
         return handle_stats_modal_key(key);
     }
 
+    // Handle diagnostics modal - scroll with j/k/arrows, close with others
+    if app.model.ui_state.show_diagnostics {
+        return handle_diagnostics_modal_key(key);
+    }
+
+    // Handle debug log viewer
+    if app.model.ui_state.log_viewer.is_some() {
+        return handle_log_viewer_key(key);
+    }
+
     // Handle stash modal if open
     if app.model.ui_state.show_stash_modal {
         return handle_stash_modal_key(key);
     }
 
+    // Handle integrations panel if open
+    if app.model.ui_state.show_integrations {
+        return handle_integrations_key(key);
+    }
+
+    // Handle quick diff popup if open
+    if app.model.ui_state.quick_diff.is_some() {
+        return handle_quick_diff_key(key);
+    }
+
+    // Handle focus session picker if open
+    if app.model.ui_state.focus_picker.is_some() {
+        return handle_focus_picker_key(key);
+    }
+
+    // Handle project picker if open
+    if let Some(ref picker) = app.model.ui_state.project_picker {
+        return handle_project_picker_key(key, &picker.query);
+    }
+
+    // Handle workspace picker if open
+    if let Some(ref picker) = app.model.ui_state.workspace_picker {
+        return handle_workspace_picker_key(key, picker, app);
+    }
+
+    // Handle dependency graph modal if open
+    if app.model.ui_state.show_dependency_graph {
+        return handle_dependency_graph_key(key);
+    }
+
+    // Handle release notes modal if open
+    if let Some(ref notes) = app.model.ui_state.release_notes {
+        return handle_release_notes_key(key, notes);
+    }
+
+    if app.model.ui_state.whats_new.is_some() {
+        return handle_whats_new_key(key);
+    }
+
+    // Handle code search modal if open
+    if let Some(ref search) = app.model.ui_state.code_search {
+        return handle_code_search_key(key, search);
+    }
+
+    // Handle TODO scanner modal if open
+    if let Some(ref scan) = app.model.ui_state.todo_scan {
+        return handle_todo_scan_key(key, scan);
+    }
+
+    // Handle sparse-checkout start prompt if open
+    if let Some(ref prompt) = app.model.ui_state.sparse_prompt {
+        return handle_sparse_prompt_key(key, &prompt.input);
+    }
+
     // Handle watcher insight modal if open
     if app.model.ui_state.show_watcher_insight_modal {
         return handle_watcher_insight_modal_key(key, app);
     }
 
+    // Handle session replay (opened from the task preview's Activity tab)
+    if app.model.ui_state.session_replay.is_some() {
+        return handle_session_replay_key(key);
+    }
+
+    // Handle the scrollback pager (also opened from the Activity tab)
+    if let Some(ref viewer) = app.model.ui_state.scrollback_viewer {
+        return handle_scrollback_key(key, &viewer.view);
+    }
+
     // Handle task preview modal - allow action keys to work, only close on Esc/Enter/Space/?
     if app.model.ui_state.show_task_preview {
         return handle_task_preview_modal_key(key, app);
@@ -1251,6 +2012,18 @@ fn handle_key_event(key: event::KeyEvent, app: &App) -> Vec<Message> {
         return handle_sidecar_modal_key(key);
     }
 
+    // Handle the monitor screen while it has focus
+    if app.model.ui_state.focus == FocusArea::Monitor {
+        return handle_monitor_key(key);
+    }
+
+    // Handle live output pane while it has focus
+    if let Some(ref pane) = app.model.ui_state.output_pane {
+        if app.model.ui_state.focus == FocusArea::OutputViewer {
+            return handle_output_pane_key(key, pane);
+        }
+    }
+
     // Normal mode keybindings
     match key.code {
         // Quit
@@ -1272,12 +2045,21 @@ fn handle_key_event(key: event::KeyEvent, app: &App) -> Vec<Message> {
         // Stats
         KeyCode::Char('/') => vec![Message::ToggleStats],
 
+        // Problems and slow operations (diagnostics)
+        KeyCode::Char('~') => vec![Message::ToggleDiagnostics],
+
+        // Debug log
+        KeyCode::Char('`') => vec![Message::ToggleLogViewer],
+
         // Sidecar control
         KeyCode::Char('>') => vec![Message::ShowSidecarModal],
 
         // Settings/Config (Ctrl-P)
         KeyCode::Char('p') if key.modifiers.contains(KeyModifiers::CONTROL) => vec![Message::ShowConfigModal],
 
+        // Workspaces: saved sets of projects (Ctrl-O)
+        KeyCode::Char('o') if key.modifiers.contains(KeyModifiers::CONTROL) => vec![Message::ShowWorkspacePicker],
+
         // Quick Claude CLI pane (Ctrl-T)
         KeyCode::Char('t') if key.modifiers.contains(KeyModifiers::CONTROL) => {
             vec![Message::OpenClaudeCliPane]
@@ -1306,6 +2088,56 @@ fn handle_key_event(key: event::KeyEvent, app: &App) -> Vec<Message> {
         // S = Toggle stash modal (uppercase)
         KeyCode::Char('S') => vec![Message::ToggleStashModal],
 
+        // T = Toggle dependency graph (queued/stacked tasks)
+        KeyCode::Char('T') => vec![Message::ToggleDependencyGraph],
+
+        // E = Integrations panel (what's missing and how to fix it)
+        KeyCode::Char('E') => vec![Message::ToggleIntegrations],
+
+        // D = Quick diff of the selected task (diff stat and first hunks)
+        KeyCode::Char('D') => vec![Message::ToggleQuickDiff],
+
+        // U = Open one of the selected task's links (PR, preview, CI run, issue)
+        KeyCode::Char('U') => app.model.ui_state.selected_task_id
+            .map(|task_id| vec![Message::OpenLinkPicker(task_id)])
+            .unwrap_or_default(),
+
+        // Z = Watcher feed (past comments, muting by category)
+        KeyCode::Char('Z') => vec![Message::OpenWatcherFeed],
+
+        // C = Why the selected task's start failed (retry from there)
+        KeyCode::Char('C') if app.model.ui_state.selected_column == TaskStatus::Planned => {
+            app.model.active_project()
+                .zip(app.model.ui_state.selected_task_idx)
+                .and_then(|(project, idx)| project.tasks_by_status(TaskStatus::Planned).get(idx).map(|t| t.id))
+                .map(|task_id| vec![Message::ShowStartFailure(task_id)])
+                .unwrap_or_default()
+        }
+
+        // R = Release notes from merged tasks
+        KeyCode::Char('R') => vec![Message::ShowReleaseNotes],
+
+        // F = Find in code (search main checkout, create task from matches)
+        KeyCode::Char('F') => vec![Message::ShowCodeSearch],
+
+        // I = TODO/FIXME scanner (propose Planned tasks from comments)
+        KeyCode::Char('I') => vec![Message::ShowTodoScan],
+
+        // X = Cancel running git fetch/pull/push
+        KeyCode::Char('X') => vec![Message::CancelGitOperation],
+
+        // B = Start/stop the focus timer
+        KeyCode::Char('B') => vec![Message::ToggleFocusTimer],
+
+        // W = Write a board snapshot for sharing
+        KeyCode::Char('W') => vec![Message::SnapshotBoard],
+
+        // V = Monitor: all in-progress tasks tiled side by side
+        KeyCode::Char('V') => vec![Message::ToggleMonitor],
+
+        // L = Live output pane for the selected in-progress task
+        KeyCode::Char('L') => vec![Message::ToggleOutputPane],
+
         // Welcome screen speech bubble navigation
         KeyCode::Char('j') | KeyCode::Down if app.model.projects.is_empty() && !app.model.ui_state.welcome_bubble_focused => {
             // Focus the speech bubble
@@ -1324,6 +2156,20 @@ fn handle_key_event(key: event::KeyEvent, app: &App) -> Vec<Message> {
             vec![Message::WelcomeMessageNext]
         }
 
+        // Shift+←/→ on the project bar moves the selected tab
+        KeyCode::Left | KeyCode::Right
+            if key.modifiers.contains(KeyModifiers::SHIFT) && app.model.ui_state.focus == FocusArea::ProjectTabs =>
+        {
+            let selected = app.model.ui_state.selected_project_tab_idx;
+            let from = selected.wrapping_sub(1);
+            let to = if key.code == KeyCode::Left { from.wrapping_sub(1) } else { from + 1 };
+            if selected > 0 && to < app.model.projects.len() {
+                vec![Message::MoveProject { from, to }]
+            } else {
+                vec![]
+            }
+        }
+
         // Navigation
         KeyCode::Char('h') | KeyCode::Left => vec![Message::NavigateLeft],
         KeyCode::Char('l') | KeyCode::Right => vec![Message::NavigateRight],
@@ -1342,8 +2188,9 @@ fn handle_key_event(key: event::KeyEvent, app: &App) -> Vec<Message> {
             let next_focus = match app.model.ui_state.focus {
                 FocusArea::KanbanBoard => FocusArea::TaskInput,
                 FocusArea::TaskInput => FocusArea::ProjectTabs,
+                FocusArea::ProjectTabs if app.model.ui_state.output_pane.is_some() => FocusArea::OutputViewer,
                 FocusArea::ProjectTabs => FocusArea::KanbanBoard,
-                FocusArea::OutputViewer => FocusArea::KanbanBoard, // Legacy
+                FocusArea::OutputViewer | FocusArea::Monitor => FocusArea::KanbanBoard,
             };
             vec![Message::FocusChanged(next_focus)]
         }
@@ -1385,6 +2232,20 @@ fn handle_key_event(key: event::KeyEvent, app: &App) -> Vec<Message> {
             vec![]
         }
 
+        // Deploy a preview of the worktree - 'b' in Review column
+        KeyCode::Char('b') => {
+            let column = app.model.ui_state.selected_column;
+            if column == TaskStatus::Review {
+                if let Some(project) = app.model.active_project() {
+                    let tasks = project.tasks_by_status(column);
+                    if let Some(task) = app.model.ui_state.selected_task_idx.and_then(|idx| tasks.get(idx)) {
+                        return vec![Message::DeployPreview(task.id)];
+                    }
+                }
+            }
+            vec![]
+        }
+
         // Apply task changes to main worktree for testing - 'a' in Review column
         KeyCode::Char('a') => {
             let column = app.model.ui_state.selected_column;
@@ -1503,10 +2364,7 @@ fn handle_key_event(key: event::KeyEvent, app: &App) -> Vec<Message> {
                     // 0 = +project button - open the dialog
                     // Find the next available slot (for consistency with existing behavior)
                     let num_projects = app.model.projects.len();
-                    if num_projects < 9 {
-                        return vec![Message::ShowOpenProjectDialog { slot: num_projects }];
-                    }
-                    return vec![];
+                    return vec![Message::ShowOpenProjectDialog { slot: num_projects }];
                 } else {
                     // 1+ = actual projects (idx 1 = project 0, etc.)
                     let project_idx = selected_idx - 1;
@@ -1568,6 +2426,22 @@ fn handle_key_event(key: event::KeyEvent, app: &App) -> Vec<Message> {
             vec![]
         }
 
+        // Broadcast feedback to every running task - 'A' in InProgress column
+        KeyCode::Char('A') if app.model.ui_state.selected_column == TaskStatus::InProgress => {
+            vec![Message::EnterBroadcastMode]
+        }
+
+        // Auto-merge once CI passes - 'A' in Review column
+        KeyCode::Char('A') if app.model.ui_state.selected_column == TaskStatus::Review => {
+            if let Some(project) = app.model.active_project() {
+                let tasks = project.tasks_by_status(TaskStatus::Review);
+                if let Some(task) = app.model.ui_state.selected_task_idx.and_then(|idx| tasks.get(idx)) {
+                    return vec![Message::ToggleAutoMerge(task.id)];
+                }
+            }
+            vec![]
+        }
+
         // Send feedback to a task in Review or InProgress - 'f' key
         KeyCode::Char('f') if matches!(
             app.model.ui_state.selected_column,
@@ -1735,20 +2609,15 @@ fn handle_key_event(key: event::KeyEvent, app: &App) -> Vec<Message> {
         KeyCode::Char('5') => vec![Message::SelectColumn(model::TaskStatus::Review)],
         KeyCode::Char('6') => vec![Message::SelectColumn(model::TaskStatus::Done)],
 
-        // Project switching (Shift+1-9: !@#$%^&*() )
-        // ! = open new project dialog, @=project 0, #=project 1, etc.
+        // Project switching (Shift+1-0: !@#$%^&*() )
+        // ! = open new project dialog, @=project 0, #=project 1, etc., ) = pick from all projects
         KeyCode::Char('!') => {
-            // Open new project dialog (if under 9 projects)
             let num_projects = app.model.projects.len();
-            if num_projects < 9 {
-                vec![Message::ShowOpenProjectDialog { slot: num_projects }]
-            } else {
-                vec![]
-            }
+            vec![Message::ShowOpenProjectDialog { slot: num_projects }]
         }
-        KeyCode::Char(c) if "@#$%^&*(".contains(c) => {
-            let shift_chars = ['@', '#', '$', '%', '^', '&', '*', '('];
-            let idx = shift_chars.iter().position(|&ch| ch == c).unwrap();
+        KeyCode::Char(')') => vec![Message::ShowProjectPicker],
+        KeyCode::Char(c) if crate::ui::PROJECT_KEYS.contains(&c) => {
+            let idx = crate::ui::PROJECT_KEYS.iter().position(|&ch| ch == c).unwrap();
             if idx < app.model.projects.len() {
                 // Switch to existing project
                 vec![Message::SwitchProject(idx)]
@@ -1758,9 +2627,9 @@ fn handle_key_event(key: event::KeyEvent, app: &App) -> Vec<Message> {
             }
         }
 
-        // Paste image (Ctrl+V)
+        // Paste image or text (Ctrl+V)
         KeyCode::Char('v') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-            vec![Message::PasteImage]
+            vec![Message::PasteClipboard]
         }
 
         // View task details (Space)
@@ -1942,6 +2811,90 @@ fn handle_stats_modal_key(key: event::KeyEvent) -> Vec<Message> {
     }
 }
 
+/// Handle key events while the live output pane has focus
+/// j/k scroll (scrolling back pauses), space pauses/resumes, G follows again,
+/// / searches and n/N jump between matches; Esc/Tab return to the board
+fn handle_output_pane_key(key: event::KeyEvent, pane: &model::OutputPaneState) -> Vec<Message> {
+    if pane.search_editing {
+        return match key.code {
+            KeyCode::Esc => vec![Message::ClearOutputPaneSearch],
+            KeyCode::Enter => vec![Message::ConfirmOutputPaneSearch],
+            KeyCode::Backspace => {
+                let mut query = pane.search.clone();
+                query.pop();
+                vec![Message::OutputPaneSearchInput(query)]
+            }
+            KeyCode::Char(c) => vec![Message::OutputPaneSearchInput(format!("{}{}", pane.search, c))],
+            _ => vec![],
+        };
+    }
+    match key.code {
+        KeyCode::Esc | KeyCode::Tab => vec![Message::FocusChanged(FocusArea::KanbanBoard)],
+        KeyCode::Char('L') => vec![Message::ToggleOutputPane],
+        KeyCode::Char('j') | KeyCode::Down => vec![Message::ScrollOutputPane(1)],
+        KeyCode::Char('k') | KeyCode::Up => vec![Message::ScrollOutputPane(-1)],
+        KeyCode::PageDown => vec![Message::ScrollOutputPane(10)],
+        KeyCode::PageUp => vec![Message::ScrollOutputPane(-10)],
+        KeyCode::Char(' ') => vec![Message::ToggleOutputPanePause],
+        KeyCode::Char('G') | KeyCode::End if pane.paused_at.is_some() => vec![Message::ToggleOutputPanePause],
+        KeyCode::Char('/') => vec![Message::StartOutputPaneSearch],
+        KeyCode::Char('n') => vec![Message::OutputPaneNextMatch(1)],
+        KeyCode::Char('N') => vec![Message::OutputPaneNextMatch(-1)],
+        KeyCode::Char('q') => vec![Message::Quit],
+        _ => vec![],
+    }
+}
+
+/// Handle key events on the monitor screen
+/// h/j/k/l move between tiles, Enter shows the task on its board,
+/// o switches to its terminal window; Esc/V return to the board
+fn handle_monitor_key(key: event::KeyEvent) -> Vec<Message> {
+    match key.code {
+        KeyCode::Esc | KeyCode::Tab | KeyCode::Char('V') => vec![Message::ToggleMonitor],
+        KeyCode::Char('h') | KeyCode::Left => vec![Message::MonitorMove { dx: -1, dy: 0 }],
+        KeyCode::Char('l') | KeyCode::Right => vec![Message::MonitorMove { dx: 1, dy: 0 }],
+        KeyCode::Char('k') | KeyCode::Up => vec![Message::MonitorMove { dx: 0, dy: -1 }],
+        KeyCode::Char('j') | KeyCode::Down => vec![Message::MonitorMove { dx: 0, dy: 1 }],
+        KeyCode::Enter | KeyCode::Char(' ') => vec![Message::MonitorJump],
+        KeyCode::Char('o') => vec![Message::MonitorAttach],
+        KeyCode::Char('r') => vec![Message::RefreshMonitor],
+        KeyCode::Char('q') => vec![Message::Quit],
+        _ => vec![],
+    }
+}
+
+/// Handle key events when the diagnostics modal is open
+/// j/k/Up/Down scroll, Tab switches pages, y copies and c clears the problems,
+/// any other key closes the modal
+fn handle_diagnostics_modal_key(key: event::KeyEvent) -> Vec<Message> {
+    match key.code {
+        KeyCode::Char('j') | KeyCode::Down => vec![Message::ScrollDiagnostics(1)],
+        KeyCode::Char('k') | KeyCode::Up => vec![Message::ScrollDiagnostics(-1)],
+        KeyCode::PageDown => vec![Message::ScrollDiagnostics(10)],
+        KeyCode::PageUp => vec![Message::ScrollDiagnostics(-10)],
+        KeyCode::Tab => vec![Message::SwitchDiagnosticsPage],
+        KeyCode::Char('y') => vec![Message::CopyDiagnostics],
+        KeyCode::Char('c') => vec![Message::ClearDiagnostics],
+        _ => vec![Message::ToggleDiagnostics],
+    }
+}
+
+/// Handle key events in the debug log viewer
+/// j/k scroll, l cycles the level, m the module, f follows new lines; Esc, q or ` close
+fn handle_log_viewer_key(key: event::KeyEvent) -> Vec<Message> {
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('`') => vec![Message::ToggleLogViewer],
+        KeyCode::Char('k') | KeyCode::Up => vec![Message::ScrollLogViewer(1)],
+        KeyCode::Char('j') | KeyCode::Down => vec![Message::ScrollLogViewer(-1)],
+        KeyCode::PageUp => vec![Message::ScrollLogViewer(10)],
+        KeyCode::PageDown => vec![Message::ScrollLogViewer(-10)],
+        KeyCode::Char('l') => vec![Message::CycleLogLevel],
+        KeyCode::Char('m') => vec![Message::CycleLogTarget],
+        KeyCode::Char('f') => vec![Message::ToggleLogFollow],
+        _ => vec![],
+    }
+}
+
 /// Handle key events when the stash modal is open
 /// j/k/Up/Down navigate, p pops the selected stash, d deletes with confirmation
 /// Esc or S closes the modal
@@ -1976,6 +2929,434 @@ fn handle_stash_modal_key(key: event::KeyEvent) -> Vec<Message> {
     }
 }
 
+/// Handle key events when the integrations panel is open
+/// r = check again, s = sidecar controls, Esc/q/E = close
+fn handle_integrations_key(key: event::KeyEvent) -> Vec<Message> {
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('E') | KeyCode::Char('q') => vec![Message::ToggleIntegrations],
+        KeyCode::Char('r') => vec![Message::CheckIntegrations],
+        KeyCode::Char('s') => vec![Message::ToggleIntegrations, Message::ShowSidecarModal],
+        _ => vec![],
+    }
+}
+
+/// Handle key events when the quick diff popup is open
+/// j/k = previous/next task in the column, Enter = full preview, Esc/q/D = close
+fn handle_quick_diff_key(key: event::KeyEvent) -> Vec<Message> {
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('D') | KeyCode::Char('q') => vec![Message::ToggleQuickDiff],
+        KeyCode::Char('k') | KeyCode::Up => vec![Message::QuickDiffNavigate(-1)],
+        KeyCode::Char('j') | KeyCode::Down => vec![Message::QuickDiffNavigate(1)],
+        KeyCode::Enter => vec![Message::QuickDiffOpenFull],
+        _ => vec![],
+    }
+}
+
+/// Handle key events when the focus session picker is open
+/// ←/→ or -/+ = length, t = task or none, Enter = start, Esc/B = cancel
+fn handle_focus_picker_key(key: event::KeyEvent) -> Vec<Message> {
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('B') | KeyCode::Char('q') => vec![Message::ToggleFocusTimer],
+        KeyCode::Left | KeyCode::Char('h') | KeyCode::Char('-') => vec![Message::FocusPickerAdjust(-1)],
+        KeyCode::Right | KeyCode::Char('l') | KeyCode::Char('+') | KeyCode::Char('=') => {
+            vec![Message::FocusPickerAdjust(1)]
+        }
+        KeyCode::Char('t') => vec![Message::FocusPickerToggleTask],
+        KeyCode::Enter => vec![Message::StartFocusSession],
+        _ => vec![],
+    }
+}
+
+/// Handle key events when the dependency graph modal is open
+/// j/k = navigate, Enter = jump to task, Esc/q/T = close
+fn handle_dependency_graph_key(key: event::KeyEvent) -> Vec<Message> {
+    match key.code {
+        // Close modal
+        KeyCode::Esc | KeyCode::Char('T') | KeyCode::Char('q') => {
+            vec![Message::ToggleDependencyGraph]
+        }
+
+        // Navigate up
+        KeyCode::Char('k') | KeyCode::Up => {
+            vec![Message::DependencyGraphNavigate(-1)]
+        }
+
+        // Navigate down
+        KeyCode::Char('j') | KeyCode::Down => {
+            vec![Message::DependencyGraphNavigate(1)]
+        }
+
+        // Jump to the selected task on the board
+        KeyCode::Enter => {
+            vec![Message::DependencyGraphSelect]
+        }
+
+        _ => vec![],
+    }
+}
+
+/// Handle key events when the "what's new" screen is open
+/// j/k = move, Enter = open the feature's setting, Esc/q = close
+fn handle_whats_new_key(key: event::KeyEvent) -> Vec<Message> {
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('q') => vec![Message::CloseWhatsNew],
+        KeyCode::Char('k') | KeyCode::Up => vec![Message::WhatsNewMove(-1)],
+        KeyCode::Char('j') | KeyCode::Down => vec![Message::WhatsNewMove(1)],
+        KeyCode::Enter => vec![Message::WhatsNewOpenSetting],
+        _ => vec![],
+    }
+}
+
+/// Handle key events when the release notes modal is open
+/// While entering the starting point: type/Backspace, Enter = compile, Esc = close
+/// In the preview: w = write file, y = copy, p = polish, Tab = raw/polished, e = edit start
+fn handle_release_notes_key(key: event::KeyEvent, notes: &model::ReleaseNotesState) -> Vec<Message> {
+    if notes.markdown.is_none() {
+        return match key.code {
+            KeyCode::Esc => vec![Message::CloseReleaseNotes],
+            KeyCode::Enter => vec![Message::CompileReleaseNotes],
+            KeyCode::Backspace => {
+                let mut since = notes.since_input.clone();
+                since.pop();
+                vec![Message::ReleaseNotesSinceChanged(since)]
+            }
+            KeyCode::Char(c) => {
+                let mut since = notes.since_input.clone();
+                since.push(c);
+                vec![Message::ReleaseNotesSinceChanged(since)]
+            }
+            _ => vec![],
+        };
+    }
+
+    match key.code {
+        // Close modal
+        KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('R') => vec![Message::CloseReleaseNotes],
+
+        // Scroll preview
+        KeyCode::Char('k') | KeyCode::Up => vec![Message::ScrollReleaseNotes(-1)],
+        KeyCode::Char('j') | KeyCode::Down => vec![Message::ScrollReleaseNotes(1)],
+        KeyCode::PageUp => vec![Message::ScrollReleaseNotes(-10)],
+        KeyCode::PageDown => vec![Message::ScrollReleaseNotes(10)],
+
+        // Output actions
+        KeyCode::Char('w') => vec![Message::WriteReleaseNotes],
+        KeyCode::Char('y') => vec![Message::CopyReleaseNotes],
+        KeyCode::Char('p') => vec![Message::PolishReleaseNotes],
+        KeyCode::Tab => vec![Message::ToggleReleaseNotesPolished],
+
+        // Back to editing the starting point
+        KeyCode::Char('e') => vec![Message::ReleaseNotesSinceChanged(notes.since_input.clone())],
+
+        _ => vec![],
+    }
+}
+
+/// Handle key events when the code search modal is open
+/// Query input: type + Enter to search. Results: j/k move, Space select, a all, Enter create task
+fn handle_code_search_key(key: event::KeyEvent, search: &model::CodeSearchState) -> Vec<Message> {
+    if search.editing_query {
+        return match key.code {
+            KeyCode::Esc => vec![Message::CloseCodeSearch],
+            KeyCode::Enter => vec![Message::RunCodeSearch],
+            KeyCode::Backspace => {
+                let mut query = search.query.clone();
+                query.pop();
+                vec![Message::CodeSearchQueryChanged(query)]
+            }
+            KeyCode::Char(c) => {
+                let mut query = search.query.clone();
+                query.push(c);
+                vec![Message::CodeSearchQueryChanged(query)]
+            }
+            _ => vec![],
+        };
+    }
+
+    match key.code {
+        // Close modal
+        KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('F') => vec![Message::CloseCodeSearch],
+
+        // Navigate results
+        KeyCode::Char('k') | KeyCode::Up => vec![Message::CodeSearchNavigate(-1)],
+        KeyCode::Char('j') | KeyCode::Down => vec![Message::CodeSearchNavigate(1)],
+        KeyCode::PageUp => vec![Message::CodeSearchNavigate(-10)],
+        KeyCode::PageDown => vec![Message::CodeSearchNavigate(10)],
+
+        // Selection
+        KeyCode::Char(' ') => vec![Message::CodeSearchToggleSelect, Message::CodeSearchNavigate(1)],
+        KeyCode::Char('a') => vec![Message::CodeSearchToggleAll],
+
+        // Create a task from the selected locations
+        KeyCode::Enter => vec![Message::CreateTaskFromSearch],
+
+        // Back to editing the query
+        KeyCode::Char('/') | KeyCode::Char('e') => vec![Message::CodeSearchQueryChanged(search.query.clone())],
+
+        _ => vec![],
+    }
+}
+
+/// Handle key events when the TODO scanner modal is open
+/// j/k move, Space select, a all, g group by file/age, Enter create tasks
+fn handle_todo_scan_key(key: event::KeyEvent, scan: &model::TodoScanState) -> Vec<Message> {
+    match key.code {
+        // Close modal
+        KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('I') => vec![Message::CloseTodoScan],
+
+        // Ignore the rest until the scan finishes
+        _ if scan.scanning => vec![],
+
+        // Navigate items
+        KeyCode::Char('k') | KeyCode::Up => vec![Message::TodoScanNavigate(-1)],
+        KeyCode::Char('j') | KeyCode::Down => vec![Message::TodoScanNavigate(1)],
+        KeyCode::PageUp => vec![Message::TodoScanNavigate(-10)],
+        KeyCode::PageDown => vec![Message::TodoScanNavigate(10)],
+
+        // Selection
+        KeyCode::Char(' ') => vec![Message::TodoScanToggleSelect, Message::TodoScanNavigate(1)],
+        KeyCode::Char('a') => vec![Message::TodoScanToggleAll],
+
+        // Group by file / oldest first
+        KeyCode::Char('g') => vec![Message::TodoScanToggleGrouping],
+
+        // Create Planned tasks from the selected items
+        KeyCode::Enter => vec![Message::CreateTasksFromTodos],
+
+        _ => vec![],
+    }
+}
+
+/// Handle key events in the sparse-checkout start prompt
+/// Typing edits the patterns, Enter starts the task, Esc cancels the start
+fn handle_sparse_prompt_key(key: event::KeyEvent, input: &str) -> Vec<Message> {
+    match key.code {
+        KeyCode::Esc => vec![Message::CancelSparsePrompt],
+        KeyCode::Enter => vec![Message::ConfirmSparsePrompt],
+        // Ctrl-U clears the patterns (full checkout)
+        KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            vec![Message::SparsePromptChanged(String::new())]
+        }
+        KeyCode::Backspace => {
+            let mut input = input.to_string();
+            input.pop();
+            vec![Message::SparsePromptChanged(input)]
+        }
+        KeyCode::Char(c) => {
+            let mut input = input.to_string();
+            input.push(c);
+            vec![Message::SparsePromptChanged(input)]
+        }
+        _ => vec![],
+    }
+}
+
+/// Handle key events while typing a time entry in the General tab
+fn handle_time_entry_key(key: event::KeyEvent, input: &str) -> Vec<Message> {
+    match key.code {
+        KeyCode::Esc => vec![Message::CancelTimeEntry],
+        KeyCode::Enter => vec![Message::SaveTimeEntry],
+        KeyCode::Backspace => {
+            let mut input = input.to_string();
+            input.pop();
+            vec![Message::TimeEntryInputChanged(input)]
+        }
+        KeyCode::Char(c) => {
+            let mut input = input.to_string();
+            input.push(c);
+            vec![Message::TimeEntryInputChanged(input)]
+        }
+        _ => vec![],
+    }
+}
+
+/// Handle key events in the project picker: typing filters the list
+fn handle_project_picker_key(key: event::KeyEvent, query: &str) -> Vec<Message> {
+    match key.code {
+        KeyCode::Esc => vec![Message::CloseProjectPicker],
+        KeyCode::Enter => vec![Message::ProjectPickerSelect],
+        KeyCode::Up | KeyCode::BackTab => vec![Message::ProjectPickerNavigate(-1)],
+        KeyCode::Down | KeyCode::Tab => vec![Message::ProjectPickerNavigate(1)],
+        KeyCode::Backspace => {
+            let mut query = query.to_string();
+            query.pop();
+            vec![Message::ProjectPickerQueryChanged(query)]
+        }
+        KeyCode::Char(c) => {
+            let mut query = query.to_string();
+            query.push(c);
+            vec![Message::ProjectPickerQueryChanged(query)]
+        }
+        _ => vec![],
+    }
+}
+
+/// Handle key events in the workspace picker (or while naming a new workspace)
+fn handle_workspace_picker_key(key: event::KeyEvent, picker: &model::WorkspacePicker, app: &App) -> Vec<Message> {
+    if let Some(ref name) = picker.naming {
+        return match key.code {
+            // Nothing to go back to when no workspace is saved yet
+            KeyCode::Esc if app.model.global_settings.workspaces.is_empty() => vec![Message::CloseWorkspacePicker],
+            KeyCode::Esc => vec![Message::CancelWorkspaceName],
+            KeyCode::Enter => vec![Message::SaveWorkspace(name.clone())],
+            KeyCode::Backspace => {
+                let mut name = name.clone();
+                name.pop();
+                vec![Message::WorkspaceNameChanged(name)]
+            }
+            KeyCode::Char(c) => {
+                let mut name = name.clone();
+                name.push(c);
+                vec![Message::WorkspaceNameChanged(name)]
+            }
+            _ => vec![],
+        };
+    }
+    let selected = app.model.global_settings.workspaces.get(picker.selected).map(|w| w.name.clone());
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('q') => vec![Message::CloseWorkspacePicker],
+        KeyCode::Char('j') | KeyCode::Down => vec![Message::WorkspacePickerNavigate(1)],
+        KeyCode::Char('k') | KeyCode::Up => vec![Message::WorkspacePickerNavigate(-1)],
+        KeyCode::Char('n') => vec![Message::StartWorkspaceName],
+        KeyCode::Enter => selected.map(Message::SwitchWorkspace).into_iter().collect(),
+        KeyCode::Char('u') => selected.map(Message::SaveWorkspace).into_iter().collect(),
+        KeyCode::Char('d') => selected.map(Message::DeleteWorkspace).into_iter().collect(),
+        _ => vec![],
+    }
+}
+
+/// Handle key events while typing a note in the Activity tab
+fn handle_activity_note_key(key: event::KeyEvent, input: &str) -> Vec<Message> {
+    match key.code {
+        KeyCode::Esc => vec![Message::CancelActivityNote],
+        KeyCode::Enter => vec![Message::SaveActivityNote],
+        KeyCode::Backspace => {
+            let mut input = input.to_string();
+            input.pop();
+            vec![Message::ActivityNoteChanged(input)]
+        }
+        KeyCode::Char(c) => {
+            let mut input = input.to_string();
+            input.push(c);
+            vec![Message::ActivityNoteChanged(input)]
+        }
+        _ => vec![],
+    }
+}
+
+/// Handle key events while commenting on a diff line in the Git tab
+fn handle_diff_comment_key(key: event::KeyEvent, input: &str) -> Vec<Message> {
+    match key.code {
+        KeyCode::Esc => vec![Message::CancelDiffComment],
+        KeyCode::Enter => vec![Message::SaveDiffComment],
+        KeyCode::Backspace => {
+            let mut input = input.to_string();
+            input.pop();
+            vec![Message::DiffCommentChanged(input)]
+        }
+        KeyCode::Char(c) => {
+            let mut input = input.to_string();
+            input.push(c);
+            vec![Message::DiffCommentChanged(input)]
+        }
+        _ => vec![],
+    }
+}
+
+/// Handle key events in the commit message editor
+/// Enter merges, Alt+Enter or Ctrl+J starts a new line, Ctrl+U clears it and
+/// Esc cancels the merge; only Esc works while the message is being written
+fn handle_commit_editor_key(key: event::KeyEvent, editor: &model::CommitEditorState) -> Vec<Message> {
+    if key.code == KeyCode::Esc {
+        return vec![Message::CancelMergeCommit];
+    }
+    if editor.generating {
+        return vec![];
+    }
+    let mut input = editor.input.clone();
+    match key.code {
+        KeyCode::Enter if key.modifiers.contains(KeyModifiers::ALT) => input.push('\n'),
+        KeyCode::Char('j') if key.modifiers.contains(KeyModifiers::CONTROL) => input.push('\n'),
+        KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => input.clear(),
+        KeyCode::Enter => return vec![Message::ConfirmMergeCommit],
+        KeyCode::Backspace => {
+            input.pop();
+        }
+        KeyCode::Char(c) => input.push(c),
+        _ => return vec![],
+    }
+    vec![Message::CommitEditorChanged(input)]
+}
+
+/// Handle key events while rewriting a queued message in the Queue tab
+fn handle_queue_edit_key(key: event::KeyEvent, input: &str) -> Vec<Message> {
+    match key.code {
+        KeyCode::Esc => vec![Message::CancelQueueEdit],
+        KeyCode::Enter => vec![Message::SaveQueueEdit],
+        KeyCode::Backspace => {
+            let mut input = input.to_string();
+            input.pop();
+            vec![Message::QueueEditChanged(input)]
+        }
+        KeyCode::Char(c) => {
+            let mut input = input.to_string();
+            input.push(c);
+            vec![Message::QueueEditChanged(input)]
+        }
+        _ => vec![],
+    }
+}
+
+/// Handle key events in the session replay
+/// h/l step one event, H/L ten, g/G jump to the first/last, j/k scroll the output
+fn handle_session_replay_key(key: event::KeyEvent) -> Vec<Message> {
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('p') => vec![Message::CloseSessionReplay],
+        KeyCode::Char('l') | KeyCode::Right => vec![Message::ReplayStep(1)],
+        KeyCode::Char('h') | KeyCode::Left => vec![Message::ReplayStep(-1)],
+        KeyCode::Char('L') | KeyCode::PageDown => vec![Message::ReplayStep(10)],
+        KeyCode::Char('H') | KeyCode::PageUp => vec![Message::ReplayStep(-10)],
+        KeyCode::Char('g') | KeyCode::Home => vec![Message::ReplayStep(i32::MIN)],
+        KeyCode::Char('G') | KeyCode::End => vec![Message::ReplayStep(i32::MAX)],
+        KeyCode::Char('j') | KeyCode::Down => vec![Message::ScrollReplayOutput(1)],
+        KeyCode::Char('k') | KeyCode::Up => vec![Message::ScrollReplayOutput(-1)],
+        _ => vec![],
+    }
+}
+
+/// Handle key events in the scrollback pager
+/// j/k and PgUp/PgDn scroll, g/G jump to the start/end, / searches and n/N
+/// jump between matches; Esc/q close it
+fn handle_scrollback_key(key: event::KeyEvent, view: &model::OutputPaneState) -> Vec<Message> {
+    if view.search_editing {
+        return match key.code {
+            KeyCode::Esc => vec![Message::ClearScrollbackSearch],
+            KeyCode::Enter => vec![Message::ConfirmScrollbackSearch],
+            KeyCode::Backspace => {
+                let mut query = view.search.clone();
+                query.pop();
+                vec![Message::ScrollbackSearchInput(query)]
+            }
+            KeyCode::Char(c) => vec![Message::ScrollbackSearchInput(format!("{}{}", view.search, c))],
+            _ => vec![],
+        };
+    }
+    match key.code {
+        KeyCode::Esc if !view.search.is_empty() => vec![Message::ClearScrollbackSearch],
+        KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('v') => vec![Message::CloseScrollback],
+        KeyCode::Char('j') | KeyCode::Down => vec![Message::ScrollScrollback(1)],
+        KeyCode::Char('k') | KeyCode::Up => vec![Message::ScrollScrollback(-1)],
+        KeyCode::PageDown | KeyCode::Char(' ') => vec![Message::ScrollScrollback(20)],
+        KeyCode::PageUp => vec![Message::ScrollScrollback(-20)],
+        KeyCode::Char('g') | KeyCode::Home => vec![Message::ScrollScrollback(i32::MIN)],
+        KeyCode::Char('G') | KeyCode::End => vec![Message::ScrollScrollback(i32::MAX)],
+        KeyCode::Char('/') => vec![Message::StartScrollbackSearch],
+        KeyCode::Char('n') => vec![Message::ScrollbackNextMatch(1)],
+        KeyCode::Char('N') => vec![Message::ScrollbackNextMatch(-1)],
+        _ => vec![],
+    }
+}
+
 /// Handle key events when the sidecar control modal is open
 /// j/k = navigate actions, Enter = execute, Esc/q/> = close
 fn handle_sidecar_modal_key(key: event::KeyEvent) -> Vec<Message> {
@@ -2087,9 +3468,110 @@ fn handle_md_file_picker_key(key: event::KeyEvent) -> Vec<Message> {
     }
 }
 
+/// Key presses that type `text`, for pastes that aren't file drops
+fn pasted_key_events(text: &str) -> Vec<Event> {
+    let text = text.replace("\r\n", "\n");
+    text.chars()
+        .map(|ch| {
+            let code = match ch {
+                '\n' | '\r' => KeyCode::Enter,
+                '\t' => KeyCode::Tab,
+                ch => KeyCode::Char(ch),
+            };
+            Event::Key(event::KeyEvent::new(code, KeyModifiers::NONE))
+        })
+        .collect()
+}
+
+/// Handle key events when the feedback snippet picker is open
+/// j/k/arrows to navigate, Enter or 1-9 to insert, Esc to close
+fn handle_snippet_picker_key(key: event::KeyEvent, app: &App) -> Vec<Message> {
+    match key.code {
+        KeyCode::Esc => vec![Message::ToggleSnippetPicker],
+        KeyCode::Char('t') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            vec![Message::ToggleSnippetPicker]
+        }
+        KeyCode::Char('k') | KeyCode::Up => vec![Message::SnippetPickerMove(-1)],
+        KeyCode::Char('j') | KeyCode::Down => vec![Message::SnippetPickerMove(1)],
+        KeyCode::Char(c @ '1'..='9') => {
+            vec![Message::InsertFeedbackSnippet(c as usize - '1' as usize)]
+        }
+        KeyCode::Enter => {
+            let cursor = app.model.ui_state.snippet_picker.unwrap_or(0);
+            vec![Message::InsertFeedbackSnippet(cursor)]
+        }
+        _ => vec![],
+    }
+}
+
+/// Handle key events when the link picker is open
+/// j/k/arrows to navigate, Enter or 1-9 to open, y to copy, Esc to close
+fn handle_link_picker_key(key: event::KeyEvent, app: &App) -> Vec<Message> {
+    let Some((task_id, cursor)) = app.model.ui_state.link_picker else {
+        return vec![];
+    };
+    let links = app.model.projects.iter()
+        .flat_map(|p| p.tasks.iter())
+        .find(|t| t.id == task_id)
+        .map(|t| t.links())
+        .unwrap_or_default();
+    let url = |idx: usize| links.get(idx).map(|(_, url)| url.clone());
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('U') => vec![Message::CloseLinkPicker],
+        KeyCode::Char('k') | KeyCode::Up => vec![Message::LinkPickerMove(-1)],
+        KeyCode::Char('j') | KeyCode::Down => vec![Message::LinkPickerMove(1)],
+        KeyCode::Char(c @ '1'..='9') => url(c as usize - '1' as usize)
+            .map(|url| vec![Message::CloseLinkPicker, Message::OpenUrl(url)])
+            .unwrap_or_default(),
+        KeyCode::Enter => url(cursor)
+            .map(|url| vec![Message::CloseLinkPicker, Message::OpenUrl(url)])
+            .unwrap_or_default(),
+        KeyCode::Char('y') => url(cursor)
+            .map(|url| vec![Message::CloseLinkPicker, Message::CopyUrl(url)])
+            .unwrap_or_default(),
+        _ => vec![],
+    }
+}
+
+/// Handle key events when the watcher feed is open
+/// j/k to move, 1-6 to mute/unmute a category, p to create a task,
+/// Enter to show the entry's task, Esc to close
+fn handle_watcher_feed_key(key: event::KeyEvent) -> Vec<Message> {
+    use crate::sidecar::protocol::WatcherCategory;
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('Z') => vec![Message::CloseWatcherFeed],
+        KeyCode::Char('k') | KeyCode::Up => vec![Message::WatcherFeedMove(-1)],
+        KeyCode::Char('j') | KeyCode::Down => vec![Message::WatcherFeedMove(1)],
+        KeyCode::PageUp => vec![Message::WatcherFeedMove(-10)],
+        KeyCode::PageDown => vec![Message::WatcherFeedMove(10)],
+        KeyCode::Char(c @ '1'..='9') => WatcherCategory::all()
+            .get(c as usize - '1' as usize)
+            .map(|category| vec![Message::ToggleWatcherMute(*category)])
+            .unwrap_or_default(),
+        KeyCode::Char('p') => vec![Message::CreateTaskFromWatcherFeed],
+        KeyCode::Enter => vec![Message::OpenWatcherFeedTask],
+        _ => vec![],
+    }
+}
+
+/// Handle key events in the start failure modal
+/// s/r = retry in the same worktree, x = discard the worktree and start fresh, j/k scroll
+fn handle_start_failure_key(key: event::KeyEvent, task_id: uuid::Uuid) -> Vec<Message> {
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('C') => vec![Message::CloseStartFailure],
+        KeyCode::Char('k') | KeyCode::Up => vec![Message::ScrollStartFailure(-1)],
+        KeyCode::Char('j') | KeyCode::Down => vec![Message::ScrollStartFailure(1)],
+        KeyCode::PageUp => vec![Message::ScrollStartFailure(-10)],
+        KeyCode::PageDown => vec![Message::ScrollStartFailure(10)],
+        KeyCode::Char('s') | KeyCode::Char('r') => vec![Message::RetryStart { task_id, fresh: false }],
+        KeyCode::Char('x') => vec![Message::RetryStart { task_id, fresh: true }],
+        _ => vec![],
+    }
+}
+
 /// Handle key events when the watcher insight modal is open
 /// p = create task in Planned, Ctrl+S = start task immediately, Esc = close
-/// j/k/Up/Down scroll the description
+/// m = mute the comment's category, j/k/Up/Down scroll the description
 fn handle_watcher_insight_modal_key(key: event::KeyEvent, app: &App) -> Vec<Message> {
     let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
 
@@ -2098,6 +3580,9 @@ fn handle_watcher_insight_modal_key(key: event::KeyEvent, app: &App) -> Vec<Mess
         .and_then(|p| p.watcher_comment.as_ref())
         .and_then(|c| c.insight.as_ref())
         .is_some();
+    let category = app.model.active_project()
+        .and_then(|p| p.watcher_comment.as_ref())
+        .and_then(|c| c.category);
 
     match key.code {
         // Close modal
@@ -2115,6 +3600,11 @@ fn handle_watcher_insight_modal_key(key: event::KeyEvent, app: &App) -> Vec<Mess
             vec![Message::StartTaskFromWatcherInsight]
         }
 
+        // Mute this kind of comment (m key)
+        KeyCode::Char('m') => category
+            .map(|category| vec![Message::ToggleWatcherMute(category)])
+            .unwrap_or_default(),
+
         // Scroll up
         KeyCode::Char('k') | KeyCode::Up => {
             vec![Message::ScrollWatcherInsightUp]
@@ -2142,11 +3632,33 @@ fn handle_task_preview_modal_key(key: event::KeyEvent, app: &App) -> Vec<Message
         return vec![Message::ToggleTaskPreview];
     };
 
+    // The note input in the Activity tab takes every key while open
+    if let Some(ref note) = app.model.ui_state.activity_note {
+        return handle_activity_note_key(key, &note.input);
+    }
+
+    // So does the comment input on a diff line in the Git tab
+    if let Some(ref comment) = app.model.ui_state.diff_comment {
+        return handle_diff_comment_key(key, &comment.input);
+    }
+
+    // So does the time entry input in the General tab
+    if let Some(ref entry) = app.model.ui_state.time_entry_input {
+        return handle_time_entry_key(key, &entry.input);
+    }
+
+    // And rewriting a queued message in the Queue tab
+    if let Some(ref edit) = app.model.ui_state.queue_edit {
+        return handle_queue_edit_key(key, &edit.input);
+    }
+
     // Check which tab we're on for scroll handling
     let on_git_tab = app.model.ui_state.task_detail_tab == crate::model::TaskDetailTab::Git;
     let on_spec_tab = app.model.ui_state.task_detail_tab == crate::model::TaskDetailTab::Spec;
     let on_notes_tab = app.model.ui_state.task_detail_tab == crate::model::TaskDetailTab::Notes;
     let on_activity_tab = app.model.ui_state.task_detail_tab == crate::model::TaskDetailTab::Activity;
+    let on_general_tab = app.model.ui_state.task_detail_tab == crate::model::TaskDetailTab::General;
+    let on_queue_tab = app.model.ui_state.task_detail_tab == crate::model::TaskDetailTab::Queue;
 
     match key.code {
         // Close modal on Esc, Space (but Enter toggles expand on activity tab)
@@ -2156,6 +3668,8 @@ fn handle_task_preview_modal_key(key: event::KeyEvent, app: &App) -> Vec<Message
         KeyCode::Enter => {
             if on_activity_tab {
                 vec![Message::ToggleActivityExpand]
+            } else if on_queue_tab {
+                vec![Message::StartQueueEdit(task.id)]
             } else {
                 vec![Message::ToggleTaskPreview]
             }
@@ -2169,6 +3683,26 @@ fn handle_task_preview_modal_key(key: event::KeyEvent, app: &App) -> Vec<Message
             vec![Message::TaskDetailNextTab]
         }
 
+        // Queue tab: j/k select, J/K reorder, e rewrites, d drops a queued message
+        KeyCode::Char('j') | KeyCode::Down if on_queue_tab => {
+            vec![Message::SelectQueuedFeedback { task_id: task.id, delta: 1 }]
+        }
+        KeyCode::Char('k') | KeyCode::Up if on_queue_tab => {
+            vec![Message::SelectQueuedFeedback { task_id: task.id, delta: -1 }]
+        }
+        KeyCode::Char('J') if on_queue_tab => {
+            vec![Message::MoveQueuedFeedback { task_id: task.id, delta: 1 }]
+        }
+        KeyCode::Char('K') if on_queue_tab => {
+            vec![Message::MoveQueuedFeedback { task_id: task.id, delta: -1 }]
+        }
+        KeyCode::Char('e') if on_queue_tab => {
+            vec![Message::StartQueueEdit(task.id)]
+        }
+        KeyCode::Char('d') | KeyCode::Delete if on_queue_tab => {
+            vec![Message::DeleteQueuedFeedback(task.id)]
+        }
+
         // Scroll content (j/k on scrollable tabs, or arrow keys)
         KeyCode::Char('j') | KeyCode::Down => {
             if on_git_tab {
@@ -2342,6 +3876,90 @@ fn handle_task_preview_modal_key(key: event::KeyEvent, app: &App) -> Vec<Message
             vec![Message::ToggleTaskPreview, Message::EnterNoteMode(task.id)]
         }
 
+        // Add a note to the activity log
+        KeyCode::Char('n') if on_activity_tab => {
+            vec![Message::StartActivityNote(task.id)]
+        }
+
+        // Render the description or spec as markdown
+        KeyCode::Char('P') if on_general_tab || on_spec_tab => {
+            vec![Message::ToggleMarkdownPreview]
+        }
+
+        // Add time spent on the task by hand, or correct the last manual entry
+        KeyCode::Char('t') if on_general_tab => {
+            vec![Message::StartTimeEntry { task_id: task.id, edit_last: false }]
+        }
+        KeyCode::Char('T') if on_general_tab => {
+            vec![Message::StartTimeEntry { task_id: task.id, edit_last: true }]
+        }
+
+        // Write the complete activity log to a file
+        KeyCode::Char('w') if on_activity_tab => {
+            vec![Message::ExportActivity(task.id)]
+        }
+
+        // Write the diff summary as a shareable snapshot
+        KeyCode::Char('w') if on_git_tab => {
+            vec![Message::SnapshotDiffSummary(task.id)]
+        }
+
+        // Main's history and blame of the changed files instead of the diff
+        KeyCode::Char('v') if on_git_tab => {
+            vec![Message::ToggleGitHistory(task.id)]
+        }
+
+        // Comment on the diff line at the top; send the comments to Claude
+        KeyCode::Char('c') if on_git_tab && !app.model.ui_state.git_history_view => {
+            vec![Message::StartDiffComment(task.id)]
+        }
+        KeyCode::Char('F') if on_git_tab => {
+            vec![Message::SendReviewComments(task.id)]
+        }
+
+        // Summarize the changes and their risks
+        KeyCode::Char('S') if on_git_tab => {
+            vec![Message::SummarizeChanges(task.id)]
+        }
+
+        // Merge once CI passes
+        KeyCode::Char('A') if task.status == TaskStatus::Review => {
+            vec![Message::ToggleAutoMerge(task.id)]
+        }
+
+        // Deploy a preview of the worktree; copy where it went
+        KeyCode::Char('b') if task.status == TaskStatus::Review => {
+            vec![Message::DeployPreview(task.id)]
+        }
+        KeyCode::Char('y') if on_general_tab && task.preview_url.is_some() => {
+            vec![Message::CopyPreviewUrl(task.id)]
+        }
+
+        // Open one of the task's links in the browser
+        KeyCode::Char('U') => {
+            vec![Message::OpenLinkPicker(task.id)]
+        }
+
+        // Compile a handoff document for a person taking over
+        KeyCode::Char('H') => {
+            vec![Message::GenerateHandoff(task.id)]
+        }
+
+        // Step through the session event by event
+        KeyCode::Char('p') if on_activity_tab => {
+            vec![Message::OpenSessionReplay(task.id)]
+        }
+
+        // Save the session's scrollback so it outlives the window
+        KeyCode::Char('c') if on_activity_tab => {
+            vec![Message::CaptureScrollback(task.id)]
+        }
+
+        // Read the latest scrollback capture
+        KeyCode::Char('v') if on_activity_tab => {
+            vec![Message::OpenScrollback(task.id)]
+        }
+
         // Decline (Review) or Delete (other statuses) - with confirmation
         KeyCode::Char('d') => {
             if task.status == TaskStatus::Review {
@@ -2465,12 +4083,65 @@ fn handle_open_project_dialog_input(key: event::KeyEvent, app: &mut App) -> Vec<
         return handle_create_folder_input(key, input.clone(), app);
     }
 
+    // Typing a path, or moving through the Recent list
+    if app.model.ui_state.quick_open.path_input.is_some() {
+        return handle_path_input(key, app);
+    }
+    let recent: Vec<PathBuf> = model::recent::ordered(&app.model.global_settings.recent_projects)
+        .into_iter()
+        .take(model::QuickOpen::MAX_SHOWN)
+        .map(|r| r.path.clone())
+        .collect();
+    if let Some(selected) = app.model.ui_state.quick_open.recent_selected {
+        let quick_open = &mut app.model.ui_state.quick_open;
+        let path = recent.get(selected).cloned();
+        match key.code {
+            KeyCode::Esc => return vec![Message::CloseOpenProjectDialog],
+            KeyCode::Tab => quick_open.recent_selected = None,
+            KeyCode::Up | KeyCode::Char('k') => quick_open.recent_selected = Some(selected.saturating_sub(1)),
+            KeyCode::Down | KeyCode::Char('j') => {
+                quick_open.recent_selected = Some((selected + 1).min(recent.len().saturating_sub(1)));
+            }
+            KeyCode::Enter | KeyCode::Char(' ') => return path.map(Message::QuickOpenPath).into_iter().collect(),
+            KeyCode::Char('p') => return path.map(Message::ToggleRecentPin).into_iter().collect(),
+            KeyCode::Char('d') => return path.map(Message::ForgetRecentProject).into_iter().collect(),
+            KeyCode::Char(c @ '1'..='9') => {
+                return recent.get(c as usize - '1' as usize).cloned().map(Message::QuickOpenPath).into_iter().collect();
+            }
+            _ => {}
+        }
+        return vec![];
+    }
+
     match key.code {
         // Close dialog
         KeyCode::Esc => {
             vec![Message::CloseOpenProjectDialog]
         }
 
+        // Tab moves to the Recent list
+        KeyCode::Tab if !recent.is_empty() => {
+            app.model.ui_state.quick_open.recent_selected = Some(0);
+            vec![]
+        }
+
+        // 1-9 open a recent project
+        KeyCode::Char(c @ '1'..='9') => {
+            recent.get(c as usize - '1' as usize).cloned().map(Message::QuickOpenPath).into_iter().collect()
+        }
+
+        // Start typing a path
+        KeyCode::Char(c @ ('/' | '~')) => {
+            app.model.ui_state.quick_open.path_input = Some(c.to_string());
+            vec![]
+        }
+
+        // Start typing a remote path (user@host:/path)
+        KeyCode::Char('@') => {
+            app.model.ui_state.quick_open.path_input = Some(String::new());
+            vec![]
+        }
+
         // Navigate up in active column
         KeyCode::Up | KeyCode::Char('k') => {
             if let Some(ref mut browser) = app.model.ui_state.directory_browser {
@@ -2565,6 +4236,42 @@ fn handle_open_project_dialog_input(key: event::KeyEvent, app: &mut App) -> Vec<
     }
 }
 
+/// Handle key events while typing a path in the open-project dialog
+fn handle_path_input(key: event::KeyEvent, app: &mut App) -> Vec<Message> {
+    let quick_open = &mut app.model.ui_state.quick_open;
+    let Some(ref mut input) = quick_open.path_input else {
+        return vec![];
+    };
+    match key.code {
+        KeyCode::Esc => {
+            quick_open.path_input = None;
+            quick_open.candidates.clear();
+        }
+        KeyCode::Tab => {
+            let (completed, candidates) = model::recent::complete_path(input);
+            *input = completed;
+            quick_open.candidates = candidates;
+        }
+        KeyCode::Enter => {
+            let path = model::recent::expand_home(input.trim_end_matches('/'));
+            return vec![Message::QuickOpenPath(if path.as_os_str().is_empty() { PathBuf::from("/") } else { path })];
+        }
+        KeyCode::Backspace => {
+            input.pop();
+            quick_open.candidates.clear();
+            if input.is_empty() {
+                quick_open.path_input = None;
+            }
+        }
+        KeyCode::Char(c) => {
+            input.push(c);
+            quick_open.candidates.clear();
+        }
+        _ => {}
+    }
+    vec![]
+}
+
 /// Handle key events when in create folder mode
 fn handle_create_folder_input(key: event::KeyEvent, current_input: String, app: &mut App) -> Vec<Message> {
     match key.code {
@@ -2642,7 +4349,8 @@ fn handle_hook_signal(args: &[String]) -> anyhow::Result<()> {
         .unwrap_or_else(|| std::env::current_dir().unwrap_or_default());
 
     // Write signal file for the watcher
-    hooks::write_signal(&event, &session_id, &cwd, input_type.as_deref())?;
+    let (event, payload) = hooks::classify_hook_input(&event, &hook_input);
+    hooks::write_signal(&event, &session_id, &cwd, input_type.as_deref(), payload)?;
 
     Ok(())
 }
@@ -2661,65 +4369,30 @@ fn handle_signal_command(args: &[String]) -> anyhow::Result<()> {
     // Get current working directory (the worktree)
     let cwd = std::env::current_dir().unwrap_or_default();
 
+    // Claude Code passes tool and subagent details as JSON on stdin
+    let hook_input = read_hook_input();
+    let (event, payload) = hooks::classify_hook_input(event, &hook_input);
+
     // Write signal file with task_id as the session identifier
     // The watcher will pick this up and process it
-    hooks::write_signal(event, task_id, &cwd, input_type)?;
+    hooks::write_signal(&event, task_id, &cwd, input_type, payload)?;
 
     Ok(())
 }
 
-/// Detect tasks whose Claude sessions are actually idle (waiting for input)
-/// This is a fallback for when signals are lost or have wrong session IDs
-fn detect_idle_tasks_from_tmux(app: &mut App) {
-    use std::process::Command;
+/// JSON a hook was given on stdin (Null when run by hand from a terminal)
+fn read_hook_input() -> serde_json::Value {
+    use std::io::{IsTerminal, Read};
 
-    for project in &mut app.model.projects {
-        let project_slug = project.slug();
-
-        for task in &mut project.tasks {
-            // Check InProgress and NeedsWork tasks with tmux windows
-            // Both could have finished while app was closed
-            if task.status != model::TaskStatus::InProgress
-                && task.status != model::TaskStatus::NeedsWork {
-                continue;
-            }
-            let Some(ref window_name) = task.tmux_window else {
-                continue;
-            };
-
-            // Check if window exists
-            if !tmux::task_window_exists(&project_slug, window_name) {
-                continue;
-            }
-
-            // Capture the last 15 lines of the pane
-            let target = format!("kc-{}:{}", project_slug, window_name);
-            let output = Command::new("tmux")
-                .args(["capture-pane", "-t", &target, "-p", "-S", "-15"])
-                .output();
-
-            if let Ok(output) = output {
-                if output.status.success() {
-                    let content = String::from_utf8_lossy(&output.stdout);
-
-                    // Check for Claude's prompt indicators (idle state)
-                    let is_idle = content.lines().rev().take(5).any(|line| {
-                        let trimmed = line.trim();
-                        // Claude's prompt character is ❯ (U+276F)
-                        // Also check for > as fallback
-                        (trimmed.starts_with('❯') || trimmed.starts_with('>'))
-                            && !trimmed.contains("...")  // Skip loading indicators
-                    });
-
-                    if is_idle {
-                        // Claude is waiting for input - move to Review
-                        task.status = model::TaskStatus::Review;
-                        task.session_state = model::ClaudeSessionState::Paused;
-                    }
-                }
-            }
-        }
+    let mut stdin = std::io::stdin();
+    if stdin.is_terminal() {
+        return serde_json::Value::Null;
+    }
+    let mut content = String::new();
+    if stdin.read_to_string(&mut content).is_err() {
+        return serde_json::Value::Null;
     }
+    serde_json::from_str(&content).unwrap_or(serde_json::Value::Null)
 }
 
 #[cfg(test)]
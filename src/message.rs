@@ -21,6 +21,14 @@ pub enum Message {
     SelectTask(Option<usize>),
     SelectColumn(TaskStatus),
     ClickedTask { status: TaskStatus, task_idx: usize },
+    /// Mouse pressed on a card; it becomes a drag once the pointer moves
+    StartCardDrag { task_id: Uuid, from: TaskStatus, x: u16, y: u16 },
+    /// Pointer moved while a card is held (`over` is the column under it)
+    DragCard { x: u16, y: u16, over: Option<TaskStatus>, scroll: i8, over_height: u16 },
+    /// Mouse released: move the held card to the column under the pointer
+    DropCard,
+    /// Mouse wheel over a column: scroll its task list by `delta` rows
+    ScrollColumn { status: TaskStatus, delta: isize, visible_rows: u16 },
 
     // Worktree-based task lifecycle
     /// Start a task with worktree isolation (creates worktree, tmux window, starts Claude)
@@ -37,6 +45,17 @@ pub enum Message {
     CompleteAcceptTask(Uuid),
     /// Merge only - merge changes to main but keep worktree and task in Review
     MergeOnlyTask(Uuid),
+    /// Pick the commit message for a merge per the project's Commit Messages
+    /// setting, then merge (only `interactive` merges wait for the editor)
+    ComposeMergeCommit { task_id: Uuid, merge_only: bool, interactive: bool },
+    /// The sidecar wrote a commit message (or failed); `ask` = it goes to the editor
+    CommitMessageGenerated { task_id: Uuid, merge_only: bool, ask: bool, result: Result<String, String> },
+    /// Text typed in the commit message editor
+    CommitEditorChanged(String),
+    /// Merge with the message in the editor
+    ConfirmMergeCommit,
+    /// Close the commit message editor without merging
+    CancelMergeCommit,
     /// Discard a task - delete worktree and branch without merging
     DiscardTask(Uuid),
     /// Reset a task - discard all changes and start fresh (moved to top of Planned)
@@ -92,10 +111,28 @@ pub enum Message {
     UpdateWorktreeToMain(Uuid),
     /// Start SDK update rebase session (internal - smart update with conflict resolution)
     StartUpdateRebaseSession { task_id: Uuid },
+    /// Stop a rebase onto main at its conflicts and start a session to resolve them (h in the rebase conflict dialog)
+    HandOffRebaseConflicts(Uuid),
+    /// Rebase stopped at its conflicts (or went through cleanly), ready to hand off
+    RebaseConflictsReady { task_id: Uuid, result: Result<crate::worktree::RebaseConflict, String> },
+    /// Check every task's worktree against git (startup, runs in the background)
+    CheckWorktreeIntegrity,
+    /// Worktrees that don't match the board, to offer for reconciliation
+    WorktreeIntegrityChecked(Vec<crate::worktree::integrity::WorktreeIssue>),
+    /// Reconciliation fixes applied, with each one's result
+    WorktreesReconciled(Vec<(crate::worktree::integrity::WorktreeIssue, Result<(), String>)>),
     /// Complete update after rebase verification (internal - no merge, just refresh status)
     CompleteUpdateTask(Uuid),
     /// Refresh git status (additions/deletions/behind) for all tasks with worktrees
     RefreshGitStatus,
+    /// Git metadata changed on disk (debounced); refresh these tasks (empty = all)
+    GitStatusChanged { project_dir: PathBuf, display_ids: Vec<String> },
+    /// Find in-progress tasks whose Claude went idle while kanblam was closed (startup)
+    DetectIdleTasks,
+    /// Tasks found idle, with why (internal)
+    IdleTasksDetected(Vec<(Uuid, String)>),
+    /// Background status refresh finished (internal)
+    GitStatusComputed { project_dir: PathBuf, statuses: Vec<(String, crate::worktree::git::WorktreeGitStatus)> },
 
     // Git remote operations (pull/push)
     /// Start git fetch to check remote status (background)
@@ -116,6 +153,14 @@ pub enum Message {
     GitPushCompleted,
     /// Git push failed
     GitPushFailed { error: String },
+    /// A task's branch was merged into main in the background (and its worktree and
+    /// branch removed; Ok holds warnings from that). `accepting` = the task holds
+    /// the main worktree lock from a smart accept.
+    TaskBranchMerged { task_id: Uuid, accepting: bool, result: Result<Vec<String>, String> },
+    /// Progress update from the running fetch/pull/push/merge (from background task)
+    GitOperationProgress(crate::worktree::GitProgress),
+    /// Cancel the running fetch/pull/push/merge
+    CancelGitOperation,
 
     // Task queueing
     /// Show the queue dialog to select a session to queue the task for
@@ -135,8 +180,146 @@ pub enum Message {
     /// Send the next queued task to a session (internal, called when a task stops)
     SendQueuedTask { finished_task_id: Uuid },
 
+    // Dependency graph
+    /// Show/hide the dependency graph modal
+    ToggleDependencyGraph,
+    /// Navigate up/down in the dependency graph
+    DependencyGraphNavigate(i32),
+    /// Jump to the selected graph node on the board (closes the graph)
+    DependencyGraphSelect,
+
+    // Release notes
+    /// Open the release notes modal (starting point prefilled with the latest tag)
+    ShowReleaseNotes,
+    /// Close the release notes modal
+    CloseReleaseNotes,
+    /// Update the starting point (tag or YYYY-MM-DD) being typed (returns to input)
+    ReleaseNotesSinceChanged(String),
+    /// Resolve the starting point and compile notes from merged tasks
+    CompileReleaseNotes,
+    /// Ask the sidecar to polish the compiled notes
+    PolishReleaseNotes,
+    /// Sidecar returned polished notes (internal)
+    ReleaseNotesPolished { text: String },
+    /// Sidecar failed to polish the notes (internal)
+    ReleaseNotesPolishFailed { error: String },
+    /// Switch the preview between raw and polished notes
+    ToggleReleaseNotesPolished,
+    /// Scroll the release notes preview
+    ScrollReleaseNotes(i32),
+    /// Write the shown notes to RELEASE_NOTES.md in the project dir
+    WriteReleaseNotes,
+    /// Copy the shown notes to the clipboard
+    CopyReleaseNotes,
+    /// After a merge: offer a version bump + tag if the task is labeled #release
+    /// and auto-versioning is enabled for the project
+    CheckReleaseAfterMerge(Uuid),
+
+    // Code search
+    /// Open the code search modal
+    ShowCodeSearch,
+    /// Close the code search modal
+    CloseCodeSearch,
+    /// Update the pattern being typed (returns to query input)
+    CodeSearchQueryChanged(String),
+    /// Run the search against the main checkout in the background
+    RunCodeSearch,
+    /// Search finished (internal)
+    CodeSearchResults { results: Vec<crate::search::SearchMatch> },
+    /// Search failed (internal)
+    CodeSearchFailed { error: String },
+    /// Move the result cursor
+    CodeSearchNavigate(i32),
+    /// Select/deselect the result under the cursor
+    CodeSearchToggleSelect,
+    /// Select all results, or clear the selection if everything is selected
+    CodeSearchToggleAll,
+    /// Pre-fill the task input with the selected locations (or the cursor's)
+    CreateTaskFromSearch,
+
+    // Native git backend
+    /// Time libgit2 against git on the active project; enables the backend if it wins
+    BenchmarkGitBackend,
+    /// Benchmark finished (internal)
+    GitBackendBenchmarked { result: crate::worktree::native::BenchmarkResult },
+
+    // Error-log watcher
+    /// (Re)start the project's error-log watcher from its settings (stops it when unset)
+    StartErrorLogWatch { project_dir: PathBuf },
+    /// The watcher captured an error (internal; duplicates are filtered here)
+    ErrorLogCaptured { project_dir: PathBuf, source: String, error: crate::errorlog::CapturedError },
+    /// The watcher couldn't read its source or the command exited (internal)
+    ErrorLogWatchFailed { project_dir: PathBuf, error: String },
+    /// Show the next queued error offer if no confirmation is open
+    ShowNextErrorOffer,
+
+    // Focus timer
+    /// Open the focus session picker, or stop the running timer
+    ToggleFocusTimer,
+    /// Lengthen/shorten the session being set up, in five-minute steps
+    FocusPickerAdjust(i32),
+    /// Tie the session being set up to the selected task, or to none
+    FocusPickerToggleTask,
+    /// Start the focus session set up in the picker
+    StartFocusSession,
+    /// The current focus/break phase ran out (internal, from Tick)
+    FocusPhaseElapsed,
+
+    // TODO scanner
+    /// Open the TODO/FIXME scanner and start scanning the main checkout
+    ShowTodoScan,
+    /// Close the TODO/FIXME scanner
+    CloseTodoScan,
+    /// Scan finished (internal)
+    TodoScanResults { items: Vec<crate::search::todo::TodoItem> },
+    /// Scan failed (internal)
+    TodoScanFailed { error: String },
+    /// Move the item cursor
+    TodoScanNavigate(i32),
+    /// Select/deselect the item under the cursor
+    TodoScanToggleSelect,
+    /// Select all items, or clear the selection if everything is selected
+    TodoScanToggleAll,
+    /// Switch between grouping by file and oldest-first
+    TodoScanToggleGrouping,
+    /// Create Planned tasks from the selected items (or the cursor's)
+    CreateTasksFromTodos,
+
     // Project operations
     SwitchProject(usize),
+    /// Move a project tab to a new position in the tab bar
+    MoveProject { from: usize, to: usize },
+    /// Mouse pressed on a project tab; dropping it on another tab moves it there
+    StartProjectTabDrag(usize),
+    /// Mouse released over the project bar (`to` is the tab under the pointer)
+    DropProjectTab { to: Option<usize> },
+    /// Open the picker listing every project (for tabs scrolled out of view)
+    ShowProjectPicker,
+    /// The picker's filter text changed
+    ProjectPickerQueryChanged(String),
+    /// Move the picker's selection up/down
+    ProjectPickerNavigate(i32),
+    /// Switch to the project selected in the picker
+    ProjectPickerSelect,
+    CloseProjectPicker,
+
+    // Workspaces
+    /// Open the workspace picker (Ctrl-O)
+    ShowWorkspacePicker,
+    CloseWorkspacePicker,
+    /// Move the workspace picker's selection up/down
+    WorkspacePickerNavigate(i32),
+    /// Start typing a name for a new workspace of the open projects
+    StartWorkspaceName,
+    /// The new workspace's name changed
+    WorkspaceNameChanged(String),
+    CancelWorkspaceName,
+    /// Save the open projects as the named workspace (replacing one of that name)
+    SaveWorkspace(String),
+    /// Forget a saved workspace
+    DeleteWorkspace(String),
+    /// Unload the projects outside the named workspace and open the ones in it
+    SwitchWorkspace(String),
     AddProject { name: String, working_dir: PathBuf },
     /// Show the open project dialog (triggered by pressing an unused project number)
     ShowOpenProjectDialog { slot: usize },
@@ -146,6 +329,20 @@ pub enum Message {
     ConfirmOpenProject,
     /// Confirm opening a specific path as project (from Miller columns [New Project Here])
     ConfirmOpenProjectPath(PathBuf),
+    /// Open a recent or typed project path from the dialog (switches to it if already open)
+    QuickOpenPath(PathBuf),
+    /// Pin or unpin a recent project in the open-project dialog
+    ToggleRecentPin(PathBuf),
+    /// Remove a project from the recent list
+    ForgetRecentProject(PathBuf),
+    /// Connect to `[user@]host:/path` and open it as a remote project
+    OpenRemoteProject(String),
+    /// The SSH connection for a project being opened is up (or failed)
+    RemoteProjectConnected(Result<crate::remote::RemoteSpec, String>),
+    /// Open the SSH connections of the open remote projects in the background
+    ConnectRemoteProjects,
+    /// Open a folder that isn't a git repository, isolating tasks with a shadow repository
+    OpenSnapshotProject { path: PathBuf, name: String, slot: usize },
     /// Close a project (with confirmation if it has active tasks)
     CloseProject(usize),
     /// Enter create folder mode in the open project dialog
@@ -159,13 +356,23 @@ pub enum Message {
     HookSignalReceived(HookSignal),
     ClaudeOutputUpdated { project_id: Uuid, output: String },
 
+    // Sparse-checkout start prompt
+    /// Edit the sparse-checkout patterns in the start prompt
+    SparsePromptChanged(String),
+    /// Start the task with the patterns in the prompt (empty = full checkout)
+    ConfirmSparsePrompt,
+    /// Close the sparse-checkout prompt without starting the task
+    CancelSparsePrompt,
+
     // Async background task results
     /// Create worktree for a task (deferred to allow UI render first)
-    CreateWorktree { task_id: Uuid, display_id: String, project_dir: PathBuf },
+    CreateWorktree { task_id: Uuid, display_id: String, project_dir: PathBuf, sparse_patterns: Vec<String>, base_ref: Option<String> },
     /// Worktree creation completed successfully (from background task)
     WorktreeCreated { task_id: Uuid, display_id: String, worktree_path: PathBuf, project_dir: PathBuf },
     /// Worktree creation failed (from background task)
     WorktreeCreationFailed { task_id: Uuid, error: String },
+    /// Top up (or drain, when size is 0) a project's pool of pre-warmed worktrees in the background
+    WarmWorktreePool { project_dir: PathBuf, size: usize },
 
     // Async fast rebase
     /// Start fast rebase in background (deferred to allow UI render first)
@@ -199,7 +406,7 @@ pub enum Message {
 
     // Async merge-only (M command)
     /// Start merge-only git operations in background
-    StartMergeOnlyGitOps { task_id: Uuid, display_id: String, worktree_path: PathBuf, project_dir: PathBuf },
+    StartMergeOnlyGitOps { task_id: Uuid, display_id: String, worktree_path: PathBuf, project_dir: PathBuf, merge_message: Option<String> },
     /// Merge-only git ops done - ready to merge
     MergeOnlyReadyToMerge { task_id: Uuid },
     /// Merge-only failed (conflicts - needs full 'm' for Claude resolution)
@@ -233,7 +440,7 @@ pub enum Message {
     /// SDK session started successfully
     SdkSessionStarted { task_id: Uuid, session_id: String },
     /// SDK session start failed
-    SdkSessionFailed { task_id: Uuid, error: String, project_dir: PathBuf, worktree_path: PathBuf },
+    SdkSessionFailed { task_id: Uuid, error: String, phase: crate::model::StartPhase, output: Option<String> },
     /// SDK session output received
     SdkSessionOutput { task_id: Uuid, output: String },
     /// Open interactive modal for a task (hand off to CLI)
@@ -244,6 +451,12 @@ pub enum Message {
     DoSendFeedback { task_id: Uuid, feedback: String },
     /// Close interactive modal (return control to app)
     CloseInteractiveModal,
+    /// Freeze the interactive modal's pane for selecting and copying (Ctrl+[)
+    EnterCopyMode,
+    /// Copy the copy mode selection (or the cursor's line) and leave copy mode
+    YankCopySelection,
+    /// Leave copy mode without copying
+    ExitCopyMode,
     /// CLI session ended, hand back to SDK
     CliSessionEnded { task_id: Uuid },
     /// Resume SDK session after CLI handoff
@@ -258,6 +471,26 @@ pub enum Message {
     SendFeedback { task_id: Uuid, feedback: String },
     /// Queue feedback to be sent when Claude finishes current work
     QueueFeedback { task_id: Uuid, feedback: String },
+    /// Move the selection in the task preview's Queue tab
+    SelectQueuedFeedback { task_id: Uuid, delta: i32 },
+    /// Move the selected queued message earlier (-1) or later (+1)
+    MoveQueuedFeedback { task_id: Uuid, delta: i32 },
+    /// Drop the selected queued message before it's delivered
+    DeleteQueuedFeedback(Uuid),
+    /// Start rewriting the selected queued message
+    StartQueueEdit(Uuid),
+    /// Update the queued message being rewritten
+    QueueEditChanged(String),
+    /// Save the rewritten queued message (an empty one is dropped)
+    SaveQueueEdit,
+    /// Cancel rewriting the queued message
+    CancelQueueEdit,
+    /// Enter broadcast mode: one feedback for every InProgress task of the project
+    EnterBroadcastMode,
+    /// Ask to confirm a broadcast, listing the tasks it goes to
+    ConfirmBroadcast(String),
+    /// Deliver a confirmed broadcast and report how it went for each task
+    BroadcastFeedback { task_ids: Vec<Uuid>, feedback: String },
 
     // Notes
     /// Enter note-adding mode for a task (focus input for note text)
@@ -266,6 +499,101 @@ pub enum Message {
     CancelNoteMode,
     /// Add a note to a task
     AddNote { task_id: Uuid, note: String },
+    /// Open the note input in the Activity tab (n)
+    StartActivityNote(Uuid),
+    /// Edit the activity note being typed
+    ActivityNoteChanged(String),
+    /// Add the typed note to the task's activity log
+    SaveActivityNote,
+    /// Close the activity note input without saving
+    CancelActivityNote,
+    /// Open the comment input on the diff line at the top of the Git tab (c)
+    StartDiffComment(Uuid),
+    /// Edit the diff comment being typed
+    DiffCommentChanged(String),
+    /// Save the typed comment on its line (an empty one removes the line's comment)
+    SaveDiffComment,
+    /// Close the diff comment input without saving
+    CancelDiffComment,
+    /// Send a task's unsent review comments to Claude as feedback (F in the Git tab)
+    SendReviewComments(Uuid),
+    /// Ask the sidecar to summarize a task's changes and their risks (S in the Git tab)
+    SummarizeChanges(Uuid),
+    /// Change summary for the branch at `head` is ready (or failed)
+    ChangesSummarized { task_id: Uuid, head: String, result: Result<String, String> },
+    /// Write a task's complete activity log to a text file (w in the Activity tab)
+    ExportActivity(Uuid),
+    /// Start typing a manual time entry for a task (t), or correct its last one (T)
+    StartTimeEntry { task_id: Uuid, edit_last: bool },
+    /// The time entry input changed
+    TimeEntryInputChanged(String),
+    /// Save the typed time entry (an emptied correction removes the entry)
+    SaveTimeEntry,
+    /// Discard the typed time entry
+    CancelTimeEntry,
+    /// Step through a task's activity event by event (p in the Activity tab)
+    OpenSessionReplay(Uuid),
+    /// Move the replay by N events (clamped to the first/last event)
+    ReplayStep(i32),
+    /// Scroll the output shown for the current replay event
+    ScrollReplayOutput(i32),
+    CloseSessionReplay,
+    /// Save the full scrollback of a task's session to a file and show it (c in the Activity tab)
+    CaptureScrollback(Uuid),
+    /// Show a task's latest scrollback capture (v in the Activity tab)
+    OpenScrollback(Uuid),
+    CloseScrollback,
+    /// Scroll the scrollback pager by N lines (negative = up)
+    ScrollScrollback(i32),
+    StartScrollbackSearch,
+    ScrollbackSearchInput(String),
+    /// Finish typing the search and jump to the last match
+    ConfirmScrollbackSearch,
+    ClearScrollbackSearch,
+    /// Jump N matches forward (positive) or back (negative)
+    ScrollbackNextMatch(i32),
+    /// Reload the worktree diff shown beside the interactive modal's terminal
+    /// (sent when the modal opens and on each hook signal for its task)
+    RefreshInteractiveDiff,
+    InteractiveDiffLoaded { task_id: Uuid, result: Result<String, String> },
+    /// Write the board as ANSI text and an SVG image for sharing (W)
+    SnapshotBoard,
+    /// Write a task's diff summary as ANSI text and an SVG image (w in the Git tab)
+    SnapshotDiffSummary(Uuid),
+    /// Write a handoff document into a task's worktree and copy it (H in task details)
+    GenerateHandoff(Uuid),
+    /// Result of writing a handoff document to its file and to the clipboard
+    HandoffGenerated { written: Result<PathBuf, String>, copied: Result<(), String> },
+
+    // Live output pane
+    /// Show/hide the pane tailing the selected in-progress task's output (L)
+    ToggleOutputPane,
+    /// Scroll the live output by N lines (negative = back); scrolling back pauses it
+    ScrollOutputPane(i32),
+    /// Hold the live output where it is, or resume following new output
+    ToggleOutputPanePause,
+    /// Start typing a search in the live output
+    StartOutputPaneSearch,
+    OutputPaneSearchInput(String),
+    /// Finish typing the search and jump to the newest match
+    ConfirmOutputPaneSearch,
+    ClearOutputPaneSearch,
+    /// Jump N matches forward (positive) or back (negative)
+    OutputPaneNextMatch(i32),
+
+    // Monitor
+    /// Show/hide the tiled monitor of all in-progress tasks (V)
+    ToggleMonitor,
+    /// Move the highlighted tile by columns/rows
+    MonitorMove { dx: i32, dy: i32 },
+    /// Leave the monitor with the highlighted task selected on its board
+    MonitorJump,
+    /// Switch to the highlighted task's tmux window
+    MonitorAttach,
+    /// Capture the tmux windows of the tiled tasks in the background
+    RefreshMonitor,
+    /// Captures of the tiled tasks' windows (task, last lines)
+    MonitorCapturesLoaded(Vec<(Uuid, String)>),
 
     // QA validation
     /// Start QA validation for a task (run tests, AI review)
@@ -279,8 +607,31 @@ pub enum Message {
 
     // Image handling
     PasteImage,
+    /// Ctrl+V: the clipboard's image, else its text (file paths offer to attach the files)
+    PasteClipboard,
+    /// Put pasted text in the input as written: newlines don't submit. Large pastes ask first
+    PasteText(String),
+    /// The second choice of a paste confirmation: attach a large paste as a file,
+    /// or paste copied file paths as text
+    PasteAlternative,
     AttachImage { task_id: Uuid, path: PathBuf },
-    /// Clear all images (from pending or active edit/feedback task)
+    /// Attach files (dropped onto the terminal or picked), copying them into kanblam's storage
+    AttachFiles(Vec<PathBuf>),
+    /// Open the file picker to attach any repository file (Ctrl+L in the input)
+    ShowAttachFilePicker,
+    /// Run the project's transcription command on a task's audio attachments
+    TranscribeAudio { task_id: Uuid, files: Vec<PathBuf> },
+    /// A transcription finished: the transcript, or why it failed
+    TranscriptionFinished { task_id: Uuid, file_name: String, result: Result<String, String> },
+    /// A session's context is nearly full: ask whether to restart it with a summary
+    OfferSessionRestart(Uuid),
+    /// Summarize a session's progress and continue in a fresh session
+    RestartSessionWithSummary(Uuid),
+    /// The progress summary for a session restart is ready (or failed)
+    SessionSummaryReady { task_id: Uuid, result: Result<String, String> },
+    /// The fresh session replacing a full one started: its session id, or why not
+    SessionRestarted { task_id: Uuid, result: Result<String, String> },
+    /// Clear all images and attached files (from pending or active edit/feedback task)
     ClearImages,
     /// Remove the last image (from pending or active edit/feedback task)
     RemoveLastImage,
@@ -289,6 +640,14 @@ pub enum Message {
     InputSubmit,
     /// Submit input and immediately start the task (Ctrl+S)
     InputSubmitAndStart,
+    /// Show the previous submission in the input (Up at an empty input)
+    InputHistoryOlder,
+    /// Show the next submission, or the empty input past the newest
+    InputHistoryNewer,
+    /// Create a task from input using slash commands (`start`: submitted with Ctrl+S)
+    CreateTaskWithCommands { input: String, start: bool },
+    /// Replace the word being typed with the first slash command completion
+    AcceptSlashCompletion,
     /// Open current input in external editor (vim), submit on save
     OpenExternalEditor,
     /// External editor finished - set the input text and submit
@@ -308,12 +667,38 @@ pub enum Message {
     ScrollHelpDown(usize), // Scroll help modal down by N lines
     ScrollStatsUp(usize),  // Scroll stats modal up by N lines
     ScrollStatsDown(usize), // Scroll stats modal down by N lines
+    ToggleDiagnostics,     // Show/hide the diagnostics modal (~)
+    ScrollDiagnostics(i32), // Scroll diagnostics modal by N lines
+    SwitchDiagnosticsPage, // Problems <-> slow operations in the diagnostics modal
+    CopyDiagnostics,       // Copy the session's problems to the clipboard
+    ClearDiagnostics,      // Forget the session's problems
+    ToggleLogViewer,       // Show/hide the debug log viewer (`)
+    ScrollLogViewer(i32),  // Scroll the log viewer by N lines (positive = older)
+    CycleLogLevel,         // Least severe level shown: trace, debug, info, warn, error
+    CycleLogTarget,        // Show one module's lines after another, then all again
+    ToggleLogFollow,       // Follow new lines, or pause where the viewer is
+    ToggleIntegrations,    // Show/hide the integrations panel (E)
+    CheckIntegrations,     // Probe tmux, the claude CLI and the clipboard in the background
+    /// Result of probing the integrations (internal)
+    IntegrationsChecked(crate::capabilities::Capabilities),
     ToggleTaskPreview,     // Show/hide task preview modal (v/space)
+    ToggleMarkdownPreview, // Rendered markdown beside the input and in the task preview
     TaskDetailNextTab,     // Move to next tab in task detail modal
     TaskDetailPrevTab,     // Move to previous tab in task detail modal
     ScrollGitDiffUp(usize),   // Scroll git diff up by N lines
     ScrollGitDiffDown(usize), // Scroll git diff down by N lines
     LoadGitDiff(Uuid),        // Load/refresh git diff for a task
+    ToggleQuickDiff,          // Show/hide the quick diff popup for the selected task (D)
+    QuickDiffNavigate(i32),   // Quick diff of the previous/next task in the column
+    QuickDiffOpenFull,        // Open the task preview on its Git tab from the quick diff
+    /// Diff fetched in the background (internal)
+    GitDiffLoaded { key: crate::worktree::diff_cache::DiffKey, result: Result<String, String> },
+    /// Switch the Git tab between the diff and the History view (v)
+    ToggleGitHistory(Uuid),
+    /// Read main's history of the files a task changed
+    LoadGitHistory(Uuid),
+    /// History read in the background (internal)
+    GitHistoryLoaded { task_id: Uuid, result: Result<crate::worktree::history::TaskHistory, String> },
     ScrollSpecUp(usize),      // Scroll spec tab up by N lines
     ScrollSpecDown(usize),    // Scroll spec tab down by N lines
     ScrollNotesUp(usize),     // Scroll notes tab up by N lines
@@ -392,10 +777,44 @@ pub enum Message {
     ScrollWatcherInsightUp,
     /// Scroll watcher insight modal down
     ScrollWatcherInsightDown,
+    /// Open the project's watcher feed of past comments (Z)
+    OpenWatcherFeed,
+    CloseWatcherFeed,
+    /// Move the feed's highlight by N entries
+    WatcherFeedMove(i32),
+    /// Mute a watcher category for the project, or unmute it
+    ToggleWatcherMute(crate::sidecar::protocol::WatcherCategory),
+    /// Create a task from the highlighted feed entry
+    CreateTaskFromWatcherFeed,
+    /// Select the task made from the highlighted feed entry on the board
+    OpenWatcherFeedTask,
+
+    // Failed task starts
+    /// Show why a task's start failed
+    ShowStartFailure(Uuid),
+    CloseStartFailure,
+    /// Scroll the failure's output by N lines
+    ScrollStartFailure(i32),
+    /// Start a task again, in its existing worktree or (fresh) after discarding it
+    RetryStart { task_id: Uuid, fresh: bool },
+    /// The failed start's worktree and branch were removed ahead of a fresh retry
+    StartWorktreeDiscarded { task_id: Uuid, result: Result<(), String> },
+
+    // What's new
+    /// Open the "what's new" screen if this build has changes not shown yet
+    ShowWhatsNew,
+    /// Close the "what's new" screen
+    CloseWhatsNew,
+    /// Move the highlight in the "what's new" screen
+    WhatsNewMove(i32),
+    /// Open the settings at the highlighted feature's setting
+    WhatsNewOpenSetting,
 
     // Configuration modal
     /// Open the configuration modal
     ShowConfigModal,
+    /// Open the configuration modal with a field selected
+    ShowConfigModalAt(crate::model::ConfigField),
     /// Close the configuration modal without saving
     CloseConfigModal,
     /// Navigate to next field in config modal
@@ -437,6 +856,70 @@ pub enum Message {
     /// Sidecar action completed
     SidecarActionCompleted { success: bool, message: String },
 
+    // Sidecar supervision
+    /// Health-check the sidecar in the background, restarting it if it's gone
+    SuperviseSidecar,
+    /// Result of a health check
+    SidecarSupervised(crate::sidecar::HealthCheck),
+    /// Start the sidecar (if it isn't running) and connect, in the background (startup)
+    StartSidecar,
+    /// The startup connection to the sidecar is up, or why it isn't (internal)
+    SidecarStarted(Result<(), String>),
+    /// SDK sessions resumed in a new sidecar (task, new session id or why not)
+    SidecarSessionsReattached(Vec<(Uuid, Result<String, String>)>),
+
+    // Auto-merge when CI passes
+    /// Toggle auto-merge once CI is green for a task in Review (A)
+    ToggleAutoMerge(Uuid),
+    /// Poll CI for every task waiting to auto-merge
+    PollCi,
+    /// CI status for each polled task (or why it couldn't be read)
+    CiPolled(Vec<(Uuid, Result<crate::ci::CiStatus, String>)>),
+    /// Result of merging a task's pull request after CI passed
+    AutoMergePrMerged { task_id: Uuid, pr: u64, result: Result<(), String> },
+
+    // Chat approvals
+    /// A needs-input prompt was posted to the project's chat (message id or why not)
+    ChatPromptPosted { task_id: Uuid, result: Result<String, String> },
+    /// Check the chat threads of waiting tasks for replies
+    PollChat,
+    /// Replies in each polled thread (or why it couldn't be read)
+    ChatPolled(Vec<(Uuid, Result<Vec<crate::chat::ChatReply>, String>)>),
+
+    // Board rules
+    /// Run a command in a task's worktree and log how it went (None = the project's tests)
+    RunTaskCommand { task_id: Uuid, command: Option<String> },
+    /// A task command finished with its output (Err if it failed)
+    TaskCommandFinished { task_id: Uuid, command: String, result: Result<String, String> },
+    /// Run the project's post-merge script on main for a task that just merged
+    RunPostMergeScript(Uuid),
+    /// A project lifecycle script finished with its output (Err if it failed)
+    LifecycleScriptFinished {
+        task_id: Uuid,
+        stage: crate::model::ScriptStage,
+        command: String,
+        result: Result<String, String>,
+    },
+    /// Deploy a Review task's worktree with the project's preview command
+    DeployPreview(Uuid),
+    /// The preview command finished with its output (Err if it failed)
+    PreviewDeployed { task_id: Uuid, command: String, result: Result<String, String> },
+    /// Copy a task's preview URL to the clipboard
+    CopyPreviewUrl(Uuid),
+
+    // Plugins
+    /// Actions a plugin asked for in reply to an event about a task (or why it failed)
+    PluginActions {
+        project_dir: PathBuf,
+        task_id: Uuid,
+        plugin: String,
+        result: Result<Vec<crate::plugin::PluginAction>, String>,
+    },
+
+    // Column time limits
+    /// Flag tasks that have been in their column longer than its limit
+    CheckSla,
+
     // Markdown file picker (Ctrl+O in new task input)
     /// Open the markdown file picker (scans repo for .md files)
     ShowMdFilePicker,
@@ -454,4 +937,24 @@ pub enum Message {
     MdFilePickerPopChar,
     /// Confirm selection - load file contents into task description
     MdFilePickerConfirm,
+
+    // Feedback snippets (Ctrl+T / Alt+1-9 in feedback mode)
+    /// Open or close the snippet picker
+    ToggleSnippetPicker,
+    /// Move the picker selection
+    SnippetPickerMove(i32),
+    /// Insert a snippet (index into the project's snippets) at the cursor
+    InsertFeedbackSnippet(usize),
+
+    // Task links (U on the board and in the task detail modal)
+    /// List a task's links to pick from (or open the only one)
+    OpenLinkPicker(Uuid),
+    /// Move the picker selection
+    LinkPickerMove(i32),
+    /// Close the link picker
+    CloseLinkPicker,
+    /// Open a URL in the browser
+    OpenUrl(String),
+    /// Copy a URL to the clipboard
+    CopyUrl(String),
 }
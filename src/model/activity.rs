@@ -0,0 +1,169 @@
+//! Retention, spill files and export for task activity logs
+//!
+//! Activity logs are saved with their tasks, but the session output attached
+//! to an entry can be large (it is the whole output accumulated so far), so
+//! it is written to a spill file under the data directory instead of the
+//! state file. Each task keeps at most a configured number of entries and
+//! bytes; older entries are dropped together with their spill files.
+
+use super::{ActivityLogEntry, Task};
+use chrono::{DateTime, Utc};
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use uuid::Uuid;
+
+/// Entries kept per task unless configured otherwise
+pub const DEFAULT_MAX_ENTRIES: usize = 200;
+
+/// Kilobytes of messages and output kept per task unless configured otherwise
+pub const DEFAULT_MAX_KB: usize = 1024;
+
+static MAX_ENTRIES: AtomicUsize = AtomicUsize::new(DEFAULT_MAX_ENTRIES);
+static MAX_BYTES: AtomicUsize = AtomicUsize::new(DEFAULT_MAX_KB * 1024);
+
+/// Apply the retention policy from the global settings
+pub fn set_retention(max_entries: usize, max_kb: usize) {
+    MAX_ENTRIES.store(max_entries, Ordering::Relaxed);
+    MAX_BYTES.store(max_kb * 1024, Ordering::Relaxed);
+}
+
+/// Current (max entries, max bytes) per task
+pub fn retention() -> (usize, usize) {
+    (MAX_ENTRIES.load(Ordering::Relaxed), MAX_BYTES.load(Ordering::Relaxed))
+}
+
+fn data_dir() -> PathBuf {
+    dirs::data_local_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("kanblam")
+}
+
+/// Directory holding a task's spilled outputs
+pub fn spill_dir(task_id: Uuid) -> PathBuf {
+    data_dir().join("activity").join(task_id.to_string())
+}
+
+/// Write an entry's output to its spill file, returning the path (None if it couldn't be written)
+pub fn spill_output(task_id: Uuid, timestamp: DateTime<Utc>, output: &str) -> Option<PathBuf> {
+    let dir = spill_dir(task_id);
+    std::fs::create_dir_all(&dir).ok()?;
+    let path = dir.join(format!("{}.txt", timestamp.timestamp_nanos_opt().unwrap_or_default()));
+    std::fs::write(&path, output).ok()?;
+    Some(path)
+}
+
+/// Remove a spill file that no entry refers to anymore
+pub fn remove_spill(path: &Path) {
+    let _ = std::fs::remove_file(path);
+}
+
+/// Remove all spilled outputs of a deleted task
+pub fn remove_task_spills(task_id: Uuid) {
+    let _ = std::fs::remove_dir_all(spill_dir(task_id));
+}
+
+/// Bytes an entry counts against the retention limit
+pub fn entry_size(entry: &ActivityLogEntry) -> usize {
+    entry.message.len() + entry.full_output.as_ref().map_or(0, String::len)
+}
+
+/// Number of oldest entries to drop so `entries` fits the retention policy.
+/// The newest entry is always kept.
+pub fn excess(entries: &[ActivityLogEntry], max_entries: usize, max_bytes: usize) -> usize {
+    let mut bytes: usize = entries.iter().map(entry_size).sum();
+    let mut drop = 0;
+    while drop + 1 < entries.len() && (entries.len() - drop > max_entries || bytes > max_bytes) {
+        bytes -= entry_size(&entries[drop]);
+        drop += 1;
+    }
+    drop
+}
+
+/// The complete activity log of a task as plain text. Outputs accumulate
+/// over a session, so each entry only shows what was added since the last one.
+pub fn export_text(task: &Task) -> String {
+    let mut text = String::new();
+    let _ = writeln!(text, "Activity log: {}", task.title);
+    let _ = writeln!(text, "Task {} · exported {}", task.display_id(), Utc::now().format("%Y-%m-%d %H:%M:%S UTC"));
+
+    if !task.time_entries.is_empty() {
+        let _ = write!(text, "\n{}", super::time_tracking::export_text(&task.time_entries));
+    }
+
+    let timeline = task.activity_timeline();
+    for (idx, entry) in timeline.iter().enumerate() {
+        let kind = if entry.note { "Note: " } else { "" };
+        let _ = writeln!(text, "\n[{}] {}{}", entry.timestamp.format("%Y-%m-%d %H:%M:%S"), kind, entry.message);
+        if let Some(new_output) = output_added(&timeline, idx) {
+            for line in new_output.trim_matches('\n').lines() {
+                let _ = writeln!(text, "    | {}", line);
+            }
+        }
+    }
+    text
+}
+
+/// Output the entry at `idx` added since the last earlier entry with output
+/// (None if the entry carries no output)
+pub fn output_added<'a>(timeline: &[&'a ActivityLogEntry], idx: usize) -> Option<&'a str> {
+    let output = timeline.get(idx)?.full_output.as_deref()?;
+    let previous = timeline[..idx].iter().rev().find_map(|e| e.full_output.as_deref()).unwrap_or("");
+    Some(output.strip_prefix(previous).unwrap_or(output))
+}
+
+/// Write the complete activity log of a task to a text file, returning its path
+pub fn export(task: &Task) -> std::io::Result<PathBuf> {
+    let dir = data_dir().join("exports");
+    std::fs::create_dir_all(&dir)?;
+    let path = dir.join(format!(
+        "{}-activity-{}.txt",
+        task.display_id(),
+        Utc::now().format("%Y%m%d-%H%M%S")
+    ));
+    std::fs::write(&path, export_text(task))?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(message: &str, output: Option<&str>) -> ActivityLogEntry {
+        ActivityLogEntry::with_output(message, output.map(str::to_string))
+    }
+
+    #[test]
+    fn test_excess_keeps_limits_and_newest_entry() {
+        let entries = vec![entry("one", None), entry("two", None), entry("three", Some("xxxxxxxxxx"))];
+        assert_eq!(excess(&entries, 10, 1000), 0);
+        assert_eq!(excess(&entries, 2, 1000), 1);
+        // "three" + its output is 15 bytes, over the limit on its own but still kept
+        assert_eq!(excess(&entries, 10, 12), 2);
+    }
+
+    #[test]
+    fn test_output_added_skips_entries_without_output() {
+        let entries = [entry("Using Read", Some("a\n")), entry("Working...", None), entry("Using Edit", Some("a\nb\n"))];
+        let timeline: Vec<&ActivityLogEntry> = entries.iter().collect();
+        assert_eq!(output_added(&timeline, 0), Some("a\n"));
+        assert_eq!(output_added(&timeline, 1), None);
+        assert_eq!(output_added(&timeline, 2), Some("b\n"));
+    }
+
+    #[test]
+    fn test_export_shows_only_new_output() {
+        let mut task = Task::new("Fix login".to_string());
+        task.activity_log = vec![
+            entry("Using Read", Some("first line")),
+            entry("Session stopped", Some("first line\nsecond line")),
+        ];
+        task.activity_notes = vec![ActivityLogEntry::note("checked on staging")];
+
+        let text = export_text(&task);
+        assert!(text.starts_with("Activity log: Fix login\n"));
+        assert_eq!(text.matches("first line").count(), 1);
+        assert!(text.contains("    | second line"));
+        assert!(text.contains("] Note: checked on staging"));
+    }
+}
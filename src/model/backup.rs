@@ -0,0 +1,242 @@
+//! Timestamped backups of the persisted state
+//!
+//! Before a save overwrites `state.json` (at most once per `BACKUP_INTERVAL`),
+//! the global file and the per-project state files are copied into
+//! `<stem>-backups/<timestamp>/`, laid out like the originals so a backup
+//! loads with `--state-file`, along with each local project's
+//! `.kanblam/tasks.json` under `tasks/<project id>.json`. (Remote projects'
+//! tasks stay on their host.) The newest `KEEP_BACKUPS` are kept. When the
+//! state can't be read at startup, the newest backup that parses is offered
+//! in its place. (Migrations keep their own copy, see `schema`.)
+
+use anyhow::{bail, Context, Result};
+use chrono::{Local, NaiveDateTime};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use uuid::Uuid;
+
+use super::{ProjectIndexEntry, ProjectTaskData};
+
+/// Backups kept; older ones are deleted
+const KEEP_BACKUPS: usize = 10;
+
+/// Least time between backups taken on save
+const BACKUP_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// Backup directory names sort by time
+const STAMP_FORMAT: &str = "%Y%m%d-%H%M%S";
+
+/// Where backups of `state_file` are kept
+pub fn backups_dir(state_file: &Path) -> PathBuf {
+    let stem = state_file.file_stem().unwrap_or_default().to_string_lossy();
+    state_file.with_file_name(format!("{}-backups", stem))
+}
+
+/// Backups of `state_file`, newest first
+pub fn list(state_file: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(backups_dir(state_file)) else {
+        return Vec::new();
+    };
+    let mut backups: Vec<PathBuf> = entries.flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir() && taken_at(path).is_some())
+        .collect();
+    backups.sort();
+    backups.reverse();
+    backups
+}
+
+/// When a backup was taken, from its directory name
+pub fn taken_at(backup: &Path) -> Option<NaiveDateTime> {
+    let name = backup.file_name()?.to_string_lossy();
+    NaiveDateTime::parse_from_str(&name, STAMP_FORMAT).ok()
+}
+
+/// The backed-up copy of `state_file` inside `backup` (load it with `load_state`)
+pub fn state_file_in(backup: &Path, state_file: &Path) -> PathBuf {
+    backup.join(state_file.file_name().unwrap_or_default())
+}
+
+/// Projects in the backed-up copy of `state_file` inside `backup`, read
+/// without loading it: loading migrates the file, which would write into the backup
+pub fn probe(backup: &Path, state_file: &Path) -> Result<usize> {
+    let candidate = state_file_in(backup, state_file);
+    if super::schema::state_is_too_new(&candidate) {
+        bail!("{} is from a newer kanblam", candidate.display());
+    }
+    let content = std::fs::read_to_string(&candidate)
+        .with_context(|| format!("Failed to read {}", candidate.display()))?;
+    let value: serde_json::Value = serde_json::from_str(&content)
+        .with_context(|| format!("{} is not valid JSON", candidate.display()))?;
+    match value.get("projects").and_then(serde_json::Value::as_array) {
+        Some(projects) => Ok(projects.len()),
+        None => bail!("{} has no project list", candidate.display()),
+    }
+}
+
+/// Back up the state on disk unless the newest backup is recent enough
+pub fn backup_if_due(state_file: &Path) -> Result<()> {
+    let due = list(state_file).first()
+        .and_then(|newest| taken_at(newest))
+        .is_none_or(|at| {
+            let age = Local::now().naive_local() - at;
+            age.to_std().map_or(true, |age| age >= BACKUP_INTERVAL)
+        });
+    if due && state_file.exists() {
+        backup(state_file)?;
+    }
+    Ok(())
+}
+
+/// Copy the state on disk into a new timestamped backup, dropping the oldest past `KEEP_BACKUPS`
+pub fn backup(state_file: &Path) -> Result<PathBuf> {
+    let dir = backups_dir(state_file).join(Local::now().format(STAMP_FORMAT).to_string());
+    // Two backups within a second (a migration right before a save) share one
+    if dir.exists() {
+        return Ok(dir);
+    }
+    std::fs::create_dir_all(&dir)
+        .with_context(|| format!("Failed to create {}", dir.display()))?;
+    copy_state(state_file, &dir)?;
+
+    for old in list(state_file).into_iter().skip(KEEP_BACKUPS) {
+        let _ = std::fs::remove_dir_all(old);
+    }
+    Ok(dir)
+}
+
+/// Put a backup in place of the current state, keeping the current files
+/// next to it as `*.broken` so nothing is lost. Tasks files are only put back
+/// where the current one is missing or unreadable (a readable one is newer).
+/// Returns how many tasks files were restored.
+pub fn restore(state_file: &Path, backup: &Path) -> Result<usize> {
+    let projects_dir = crate::app::project_state_dir(state_file);
+    for path in [state_file, projects_dir.as_path()] {
+        move_aside(path)?;
+    }
+
+    let backup_state = state_file_in(backup, state_file);
+    std::fs::copy(&backup_state, state_file)
+        .with_context(|| format!("Failed to restore {}", state_file.display()))?;
+    let backup_projects = crate::app::project_state_dir(&backup_state);
+    if backup_projects.exists() {
+        std::fs::create_dir_all(&projects_dir)?;
+        for entry in std::fs::read_dir(&backup_projects)?.flatten() {
+            std::fs::copy(entry.path(), projects_dir.join(entry.file_name()))?;
+        }
+    }
+
+    let mut restored = 0;
+    for (id, tasks) in local_tasks_files(state_file) {
+        let saved = tasks_file_in(backup, id);
+        let readable = std::fs::read_to_string(&tasks).ok()
+            .is_some_and(|content| serde_json::from_str::<serde_json::Value>(&content).is_ok());
+        if saved.exists() && !readable {
+            move_aside(&tasks)?;
+            std::fs::copy(&saved, &tasks)
+                .with_context(|| format!("Failed to restore {}", tasks.display()))?;
+            restored += 1;
+        }
+    }
+    Ok(restored)
+}
+
+/// Rename `path` (if it exists) to `<path>.broken-<timestamp>`
+fn move_aside(path: &Path) -> Result<()> {
+    if path.exists() {
+        let mut broken = path.as_os_str().to_os_string();
+        broken.push(format!(".broken-{}", Local::now().format(STAMP_FORMAT)));
+        std::fs::rename(path, &broken)
+            .with_context(|| format!("Failed to move {} aside", path.display()))?;
+    }
+    Ok(())
+}
+
+/// Where a backup keeps the tasks file of project `id`
+fn tasks_file_in(backup: &Path, id: Uuid) -> PathBuf {
+    backup.join("tasks").join(format!("{}.json", id))
+}
+
+/// The `.kanblam/tasks.json` of each local project listed in `state_file`
+fn local_tasks_files(state_file: &Path) -> Vec<(Uuid, PathBuf)> {
+    let value = std::fs::read_to_string(state_file).ok()
+        .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok())
+        .unwrap_or_default();
+    value.get("projects").and_then(serde_json::Value::as_array).into_iter().flatten()
+        .filter_map(|entry| serde_json::from_value::<ProjectIndexEntry>(entry.clone()).ok())
+        .filter(|entry| entry.ssh_target.is_none())
+        .map(|entry| (entry.id, ProjectTaskData::file_path(&entry.scope_dir())))
+        .collect()
+}
+
+/// Copy `state_file` and its per-project files into `dir`
+fn copy_state(state_file: &Path, dir: &Path) -> Result<()> {
+    let target = state_file_in(dir, state_file);
+    std::fs::copy(state_file, &target)
+        .with_context(|| format!("Failed to back up {}", state_file.display()))?;
+
+    let projects_dir = crate::app::project_state_dir(state_file);
+    if let Ok(entries) = std::fs::read_dir(&projects_dir) {
+        let target_projects = crate::app::project_state_dir(&target);
+        std::fs::create_dir_all(&target_projects)?;
+        for entry in entries.flatten() {
+            std::fs::copy(entry.path(), target_projects.join(entry.file_name()))
+                .with_context(|| format!("Failed to back up {}", entry.path().display()))?;
+        }
+    }
+
+    for (id, tasks) in local_tasks_files(state_file) {
+        if tasks.exists() {
+            let target = tasks_file_in(dir, id);
+            std::fs::create_dir_all(dir.join("tasks"))?;
+            std::fs::copy(&tasks, &target)
+                .with_context(|| format!("Failed to back up {}", tasks.display()))?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_backup_and_restore() {
+        let dir = tempdir().unwrap();
+        let state_file = dir.path().join("state.json");
+        let projects = crate::app::project_state_dir(&state_file);
+        std::fs::create_dir_all(&projects).unwrap();
+        let repo = dir.path().join("repo");
+        let tasks = ProjectTaskData::file_path(&repo);
+        std::fs::create_dir_all(tasks.parent().unwrap()).unwrap();
+        std::fs::write(&tasks, "{\"tasks\": []}").unwrap();
+        let good = serde_json::json!({"projects": [{
+            "id": Uuid::new_v4(),
+            "name": "repo",
+            "working_dir": repo,
+            "created_at": "2026-01-01T00:00:00Z",
+        }]}).to_string();
+        std::fs::write(&state_file, &good).unwrap();
+        std::fs::write(projects.join("p.json"), "{}").unwrap();
+
+        backup_if_due(&state_file).unwrap();
+        // Not due again within the interval
+        backup_if_due(&state_file).unwrap();
+        let backups = list(&state_file);
+        assert_eq!(backups.len(), 1);
+        assert!(crate::app::project_state_dir(&state_file_in(&backups[0], &state_file)).join("p.json").exists());
+        assert_eq!(probe(&backups[0], &state_file).unwrap(), 1);
+
+        std::fs::write(&state_file, "not json").unwrap();
+        std::fs::write(&tasks, "not json either").unwrap();
+        assert_eq!(restore(&state_file, &backups[0]).unwrap(), 1);
+        assert_eq!(std::fs::read_to_string(&state_file).unwrap(), good);
+        assert_eq!(std::fs::read_to_string(&tasks).unwrap(), "{\"tasks\": []}");
+        assert!(projects.join("p.json").exists());
+        let broken = std::fs::read_dir(dir.path()).unwrap().flatten()
+            .filter(|e| e.file_name().to_string_lossy().starts_with("state.json.broken-"))
+            .count();
+        assert_eq!(broken, 1);
+    }
+}
@@ -0,0 +1,216 @@
+//! Copy mode of the interactive modal
+//!
+//! Ctrl+[ freezes the captured pane as plain text so output can be selected
+//! with vim keys (v for characters, V for lines) and yanked to the clipboard
+//! without leaving kanblam. Keys stop going to the session until it's left.
+
+/// A frozen pane with a cursor and an optional selection
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CopyMode {
+    /// The pane's rows, trailing blanks trimmed
+    pub rows: Vec<String>,
+    /// Cursor (row, column in characters)
+    pub cursor: (usize, usize),
+    /// Where the selection started, if one is being made
+    pub anchor: Option<(usize, usize)>,
+    /// Whether the selection covers whole lines (V) rather than characters (v)
+    pub line_wise: bool,
+}
+
+/// A cursor motion
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Motion {
+    Left,
+    Right,
+    Up,
+    Down,
+    /// Start of the next word (w)
+    WordForward,
+    /// Start of the previous word (b)
+    WordBack,
+    LineStart,
+    LineEnd,
+    Top,
+    Bottom,
+    /// Half a view up or down (Ctrl-u / Ctrl-d)
+    HalfPageUp(usize),
+    HalfPageDown(usize),
+}
+
+impl CopyMode {
+    /// Freeze `rows`, with the cursor on the last non-empty one
+    pub fn new(rows: Vec<String>) -> Self {
+        let mut rows = rows;
+        while rows.last().is_some_and(|r| r.trim().is_empty()) {
+            rows.pop();
+        }
+        if rows.is_empty() {
+            rows.push(String::new());
+        }
+        let cursor = (rows.len() - 1, 0);
+        Self { rows, cursor, anchor: None, line_wise: false }
+    }
+
+    /// Freeze captured pane output (with escapes) that was `width` columns wide
+    pub fn from_capture(content: &str, width: usize) -> Self {
+        let width = width.clamp(1, u16::MAX as usize) as u16;
+        let mut parser = vt100::Parser::new(500, width, 0);
+        parser.process(content.as_bytes());
+        Self::new(parser.screen().rows(0, width).map(|r| r.trim_end().to_string()).collect())
+    }
+
+    fn row_len(&self, row: usize) -> usize {
+        self.rows.get(row).map_or(0, |r| r.chars().count())
+    }
+
+    /// Keep the column on the row (one past the end is not a position)
+    fn clamp_column(&mut self) {
+        let len = self.row_len(self.cursor.0);
+        self.cursor.1 = self.cursor.1.min(len.saturating_sub(1));
+    }
+
+    pub fn apply(&mut self, motion: Motion) {
+        let last = self.rows.len() - 1;
+        let (row, col) = self.cursor;
+        match motion {
+            Motion::Left => self.cursor.1 = col.saturating_sub(1),
+            Motion::Right => self.cursor.1 = col + 1,
+            Motion::Up => self.cursor.0 = row.saturating_sub(1),
+            Motion::Down => self.cursor.0 = (row + 1).min(last),
+            Motion::LineStart => self.cursor.1 = 0,
+            Motion::LineEnd => self.cursor.1 = usize::MAX,
+            Motion::Top => self.cursor = (0, 0),
+            Motion::Bottom => self.cursor = (last, 0),
+            Motion::HalfPageUp(n) => self.cursor.0 = row.saturating_sub(n.max(1)),
+            Motion::HalfPageDown(n) => self.cursor.0 = (row + n.max(1)).min(last),
+            Motion::WordForward => self.cursor = self.next_word(row, col),
+            Motion::WordBack => self.cursor = self.previous_word(row, col),
+        }
+        self.clamp_column();
+    }
+
+    /// Positions after (row, col) in reading order, newlines counted as spaces
+    fn chars_from(&self, row: usize, col: usize) -> impl Iterator<Item = ((usize, usize), char)> + '_ {
+        self.rows.iter().enumerate().skip(row).flat_map(move |(r, text)| {
+            let skip = if r == row { col } else { 0 };
+            text.chars().chain(std::iter::once(' ')).enumerate().skip(skip).map(move |(c, ch)| ((r, c), ch))
+        })
+    }
+
+    fn next_word(&self, row: usize, col: usize) -> (usize, usize) {
+        let mut seen_space = false;
+        for (pos, ch) in self.chars_from(row, col).skip(1) {
+            if ch.is_whitespace() {
+                seen_space = true;
+            } else if seen_space {
+                return pos;
+            }
+        }
+        self.cursor
+    }
+
+    fn previous_word(&self, row: usize, col: usize) -> (usize, usize) {
+        // Walk back over spaces, then to the start of the word before them
+        let mut positions: Vec<((usize, usize), char)> = self.chars_from(0, 0)
+            .take_while(|(pos, _)| *pos < (row, col))
+            .collect();
+        while positions.last().is_some_and(|(_, ch)| ch.is_whitespace()) {
+            positions.pop();
+        }
+        let mut start = positions.last().map_or((0, 0), |(pos, _)| *pos);
+        while let Some((pos, ch)) = positions.pop() {
+            if ch.is_whitespace() {
+                break;
+            }
+            start = pos;
+        }
+        start
+    }
+
+    /// Start or stop a selection (v / V); switching kind keeps it going
+    pub fn toggle_selection(&mut self, line_wise: bool) {
+        if self.anchor.is_some() && self.line_wise == line_wise {
+            self.anchor = None;
+        } else {
+            self.anchor.get_or_insert(self.cursor);
+            self.line_wise = line_wise;
+        }
+    }
+
+    /// Selected (start, end) positions, inclusive and in order
+    pub fn selection(&self) -> Option<((usize, usize), (usize, usize))> {
+        let anchor = self.anchor?;
+        let (start, end) = if anchor <= self.cursor { (anchor, self.cursor) } else { (self.cursor, anchor) };
+        if self.line_wise {
+            Some(((start.0, 0), (end.0, usize::MAX)))
+        } else {
+            Some((start, end))
+        }
+    }
+
+    /// Whether the character at (row, col) is selected
+    pub fn is_selected(&self, row: usize, col: usize) -> bool {
+        self.selection().is_some_and(|(start, end)| (row, col) >= start && (row, col) <= end)
+    }
+
+    /// The selection's text, or the cursor's line when nothing is selected
+    pub fn selected_text(&self) -> String {
+        let ((start_row, start_col), (end_row, end_col)) = self.selection()
+            .unwrap_or(((self.cursor.0, 0), (self.cursor.0, usize::MAX)));
+        (start_row..=end_row)
+            .map(|row| {
+                let from = if row == start_row { start_col } else { 0 };
+                let text: String = self.rows[row].chars()
+                    .enumerate()
+                    .filter(|(c, _)| *c >= from && (row != end_row || *c <= end_col))
+                    .map(|(_, ch)| ch)
+                    .collect();
+                text.trim_end().to_string()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_select_and_yank() {
+        let rows = vec![
+            "$ cargo test".to_string(),
+            "test result: ok. 3 passed".to_string(),
+            String::new(),
+            String::new(),
+        ];
+        let mut copy = CopyMode::new(rows);
+        assert_eq!(copy.cursor, (1, 0));
+        assert_eq!(copy.selected_text(), "test result: ok. 3 passed");
+
+        copy.apply(Motion::WordForward);
+        assert_eq!(copy.cursor, (1, 5));
+        copy.toggle_selection(false);
+        copy.apply(Motion::WordForward);
+        copy.apply(Motion::WordForward);
+        copy.apply(Motion::Left);
+        assert_eq!(copy.selected_text(), "result: ok.");
+        assert!(copy.is_selected(1, 5) && !copy.is_selected(1, 4));
+
+        copy.apply(Motion::WordBack);
+        assert_eq!(copy.cursor, (1, 13));
+        copy.apply(Motion::WordBack);
+        copy.apply(Motion::WordBack);
+        assert_eq!(copy.cursor, (1, 0));
+        copy.apply(Motion::Up);
+        copy.apply(Motion::LineEnd);
+        assert_eq!(copy.cursor, (0, 11));
+        copy.toggle_selection(true);
+        assert_eq!(copy.selected_text(), "$ cargo test\ntest result: ok. 3 passed");
+        copy.toggle_selection(true);
+        assert_eq!(copy.selection(), None);
+
+        copy.apply(Motion::WordForward);
+        assert_eq!(copy.cursor, (1, 0));
+    }
+}
@@ -0,0 +1,105 @@
+//! Draft autosave and submission history for the task input
+//!
+//! What's typed into the new-task input is written next to the state file
+//! every couple of seconds and put back on the next start, so quitting by
+//! accident or a crash doesn't lose a half-written description. Submitted
+//! text (tasks and feedback) is kept in a history that Up at an empty input
+//! steps back through, Down forward again.
+
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Submissions remembered
+pub const MAX_HISTORY: usize = 100;
+
+/// How often the input is compared with the saved draft
+pub const SAVE_INTERVAL: Duration = Duration::from_secs(2);
+
+/// The draft file belonging to a global state file
+pub fn draft_path(state_file: &Path) -> PathBuf {
+    let stem = state_file.file_stem().unwrap_or_default().to_string_lossy();
+    state_file.with_file_name(format!("{}-draft.md", stem))
+}
+
+/// Write the draft, or remove the file when there's nothing left to keep
+pub fn save(path: &Path, text: &str) -> std::io::Result<()> {
+    if text.trim().is_empty() {
+        return match std::fs::remove_file(path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e),
+            _ => Ok(()),
+        };
+    }
+    std::fs::write(path, text)
+}
+
+/// The draft left by the last run, if any
+pub fn load(path: &Path) -> Option<String> {
+    std::fs::read_to_string(path).ok().filter(|text| !text.trim().is_empty())
+}
+
+/// Remember a submission as the newest entry (an earlier copy moves up)
+pub fn record(history: &mut Vec<String>, text: &str) {
+    let text = text.trim();
+    if text.is_empty() {
+        return;
+    }
+    history.retain(|entry| entry != text);
+    history.push(text.to_string());
+    if history.len() > MAX_HISTORY {
+        history.drain(..history.len() - MAX_HISTORY);
+    }
+}
+
+/// Step back one submission. `cursor` counts back from the newest (0); None
+/// means the input isn't showing history. Stays on the oldest entry.
+pub fn older(history: &[String], cursor: Option<usize>) -> Option<usize> {
+    match cursor {
+        _ if history.is_empty() => None,
+        None => Some(0),
+        Some(i) => Some((i + 1).min(history.len() - 1)),
+    }
+}
+
+/// Step forward one submission; past the newest the input is empty again
+pub fn newer(cursor: Option<usize>) -> Option<usize> {
+    cursor.and_then(|i| i.checked_sub(1))
+}
+
+/// The submission at `cursor`
+pub fn entry(history: &[String], cursor: usize) -> Option<&str> {
+    history.len().checked_sub(cursor + 1).map(|idx| history[idx].as_str())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_history_and_draft_path() {
+        let mut history = Vec::new();
+        record(&mut history, "Add login page");
+        record(&mut history, "  ");
+        record(&mut history, "Fix flaky test\n");
+        record(&mut history, "Add login page");
+        assert_eq!(history, vec!["Fix flaky test", "Add login page"]);
+
+        let cursor = older(&history, None);
+        assert_eq!(cursor.and_then(|c| entry(&history, c)), Some("Add login page"));
+        let cursor = older(&history, cursor);
+        assert_eq!(cursor.and_then(|c| entry(&history, c)), Some("Fix flaky test"));
+        assert_eq!(older(&history, cursor), cursor);
+        assert_eq!(newer(newer(cursor)), None);
+        assert_eq!(older(&[], None), None);
+
+        for n in 0..MAX_HISTORY + 5 {
+            record(&mut history, &format!("task {}", n));
+        }
+        assert_eq!(history.len(), MAX_HISTORY);
+        assert_eq!(entry(&history, 0), Some(format!("task {}", MAX_HISTORY + 4).as_str()));
+
+        assert_eq!(
+            draft_path(Path::new("/data/kanblam/state.json")),
+            PathBuf::from("/data/kanblam/state-draft.md")
+        );
+    }
+}
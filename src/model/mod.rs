@@ -1,6 +1,17 @@
 #![allow(dead_code)]
 
+pub mod activity;
+pub mod backup;
+pub mod copy_mode;
+pub mod draft;
+pub mod recent;
+pub mod schema;
+pub mod time_tracking;
+pub mod watcher_feed;
+pub mod workspace;
+
 use crate::sidecar::protocol::{WatcherMood, WatcherInsight};
+use crate::timing::TimedCommand;
 use crate::ui::logo::EyeAnimation;
 use chrono::{DateTime, Utc};
 use ratatui::style::Color;
@@ -11,7 +22,7 @@ use edtui::{
     events::{KeyEvent, KeyEventHandler, KeyEventRegister},
 };
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use uuid::Uuid;
 
 /// Available editors for external editing
@@ -83,6 +94,96 @@ pub struct GlobalSettings {
     /// Vim mode enabled for text input editor (default: false = regular mode)
     #[serde(default)]
     pub vim_mode_enabled: bool,
+    /// Use libgit2 for status/diff queries (only kept on after it benchmarks faster)
+    #[serde(default)]
+    pub native_git: bool,
+    /// Show a wall clock in the status bar
+    #[serde(default)]
+    pub status_clock: bool,
+    /// Focus block length in minutes (default: 25)
+    #[serde(default = "default_focus_minutes")]
+    pub focus_minutes: u32,
+    /// Break length in minutes (default: 5)
+    #[serde(default = "default_break_minutes")]
+    pub break_minutes: u32,
+    /// Hold back new task starts while a focus block is running (default: true)
+    #[serde(default = "default_focus_defer_starts")]
+    pub focus_defer_starts: bool,
+    /// Play a distinct sound per event (Review, needs input, merge failed)
+    /// instead of the one attention sound
+    #[serde(default)]
+    pub sound_cues: bool,
+    /// Do-not-disturb window, e.g. "22:00-07:30" (no sounds in it; None = never)
+    #[serde(default)]
+    pub quiet_hours: Option<String>,
+    /// Per-column time limits, e.g. "in-progress=2h, review=1d"
+    /// (None = the defaults, empty = no limits)
+    #[serde(default)]
+    pub sla_thresholds: Option<String>,
+    /// Outgoing webhooks for task events, e.g. "https://n8n.local/hook on=merged"
+    /// (see `crate::webhook`; None = none)
+    #[serde(default)]
+    pub webhooks: Option<String>,
+    /// Redraws per second when nothing on screen is changing (default: 2)
+    #[serde(default = "default_idle_fps")]
+    pub idle_fps: u32,
+    /// Activity log entries kept per task (default: 200)
+    #[serde(default = "default_activity_max_entries")]
+    pub activity_max_entries: usize,
+    /// Kilobytes of activity log and output kept per task (default: 1024)
+    #[serde(default = "default_activity_max_kb")]
+    pub activity_max_kb: usize,
+    /// Color palette every screen is drawn in (default: standard colors)
+    #[serde(default)]
+    pub palette: Palette,
+    /// How the board's columns are arranged (default: by terminal width)
+    #[serde(default)]
+    pub board_layout: BoardLayout,
+    /// Hunspell dictionary the input is spell-checked with, e.g. "en_US" (None = off)
+    #[serde(default)]
+    pub spell_check: Option<String>,
+    /// Git remote the board state is synced through (None = no sync, see `sync`)
+    #[serde(default)]
+    pub state_sync: Option<String>,
+    /// Version whose "what's new" screen was last shown (None = never recorded)
+    #[serde(default)]
+    pub last_seen_version: Option<String>,
+    /// Saved sets of projects to switch between (Ctrl-O)
+    #[serde(default)]
+    pub workspaces: Vec<workspace::Workspace>,
+    /// Workspace the open projects belong to (None = none chosen)
+    #[serde(default)]
+    pub active_workspace: Option<String>,
+    /// Project directories opened before, offered in the open-project dialog
+    #[serde(default)]
+    pub recent_projects: Vec<recent::RecentProject>,
+    /// Text submitted in the input, oldest first (Up at an empty input recalls it)
+    #[serde(default)]
+    pub input_history: Vec<String>,
+}
+
+fn default_idle_fps() -> u32 {
+    2
+}
+
+fn default_activity_max_entries() -> usize {
+    activity::DEFAULT_MAX_ENTRIES
+}
+
+fn default_activity_max_kb() -> usize {
+    activity::DEFAULT_MAX_KB
+}
+
+fn default_focus_minutes() -> u32 {
+    25
+}
+
+fn default_break_minutes() -> u32 {
+    5
+}
+
+fn default_focus_defer_starts() -> bool {
+    true
 }
 
 fn default_mascot_interval() -> u32 {
@@ -143,6 +244,331 @@ impl ApplyStrategy {
     }
 }
 
+/// What happens when a task's SDK session nears the end of its context window.
+///
+/// The SDK compacts long conversations on its own, but a fresh session seeded
+/// with a summary of the progress so far tends to lose less of the task.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum ContextRestart {
+    /// Ask before restarting the session with a summary
+    #[default]
+    Ask,
+    /// Restart the session with a summary without asking
+    Auto,
+    /// Leave it to the SDK's own compaction
+    Off,
+}
+
+impl ContextRestart {
+    /// Get all modes for UI selection
+    pub fn all() -> &'static [ContextRestart] {
+        &[ContextRestart::Ask, ContextRestart::Auto, ContextRestart::Off]
+    }
+
+    /// Get the display name for the mode
+    pub fn name(&self) -> &'static str {
+        match self {
+            ContextRestart::Ask => "Ask",
+            ContextRestart::Auto => "Automatic",
+            ContextRestart::Off => "Off",
+        }
+    }
+
+    /// Get a short description of the mode
+    pub fn description(&self) -> &'static str {
+        match self {
+            ContextRestart::Ask => "Offer a restart with a progress summary when context is nearly full",
+            ContextRestart::Auto => "Restart with a progress summary when context is nearly full",
+            ContextRestart::Off => "Let the SDK compact the conversation on its own",
+        }
+    }
+}
+
+/// Where the commit message of a task's merge comes from.
+///
+/// The sidecar can write one from the task's title, spec and diff; merges
+/// started without anyone at the keyboard (auto-merge, rules) never wait for
+/// the editor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum CommitMessages {
+    /// Write a message and open it in an editor before merging
+    #[default]
+    Ask,
+    /// Write a message and merge with it
+    Always,
+    /// Merge with the plain "Merge task ..." message
+    Never,
+}
+
+impl CommitMessages {
+    /// Get all modes for UI selection
+    pub fn all() -> &'static [CommitMessages] {
+        &[CommitMessages::Ask, CommitMessages::Always, CommitMessages::Never]
+    }
+
+    /// Get the display name for the mode
+    pub fn name(&self) -> &'static str {
+        match self {
+            CommitMessages::Ask => "Ask",
+            CommitMessages::Always => "Always",
+            CommitMessages::Never => "Never",
+        }
+    }
+
+    /// Get a short description of the mode
+    pub fn description(&self) -> &'static str {
+        match self {
+            CommitMessages::Ask => "Write a commit message from the diff and spec, and edit it before merging",
+            CommitMessages::Always => "Write a commit message from the diff and spec, and merge with it",
+            CommitMessages::Never => "Merge with \"Merge task ... from Claude session\"",
+        }
+    }
+}
+
+/// Order of the tasks within each column of a project's board.
+///
+/// Sorting is stable, so tasks that compare equal keep their manual order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum ColumnSort {
+    /// The order set with `+/-`
+    #[default]
+    Manual,
+    /// Oldest task first
+    Created,
+    /// Most recently active first; tasks with no activity last
+    LastActivity,
+    /// `#p0` before `#p1` and so on; tasks without a priority label last
+    Priority,
+    /// Longest in the column first
+    TimeInColumn,
+}
+
+impl ColumnSort {
+    /// Get all orders for UI selection
+    pub fn all() -> &'static [ColumnSort] {
+        &[
+            ColumnSort::Manual,
+            ColumnSort::Created,
+            ColumnSort::LastActivity,
+            ColumnSort::Priority,
+            ColumnSort::TimeInColumn,
+        ]
+    }
+
+    /// Get the display name for the order
+    pub fn name(&self) -> &'static str {
+        match self {
+            ColumnSort::Manual => "Manual",
+            ColumnSort::Created => "Created",
+            ColumnSort::LastActivity => "Last Activity",
+            ColumnSort::Priority => "Priority",
+            ColumnSort::TimeInColumn => "Time in Column",
+        }
+    }
+
+    /// Get a short description of the order
+    pub fn description(&self) -> &'static str {
+        match self {
+            ColumnSort::Manual => "Keep the order set with +/-",
+            ColumnSort::Created => "Oldest tasks first",
+            ColumnSort::LastActivity => "Most recently active tasks first",
+            ColumnSort::Priority => "#p0 first, then #p1, #p2...; unlabeled tasks last",
+            ColumnSort::TimeInColumn => "Tasks waiting longest in the column first",
+        }
+    }
+
+    /// Put `tasks` (one column, in manual order) in this order
+    fn sort(&self, tasks: &mut [&Task]) {
+        match self {
+            ColumnSort::Manual => {}
+            ColumnSort::Created => tasks.sort_by_key(|t| t.created_at),
+            ColumnSort::LastActivity => tasks.sort_by_key(|t| std::cmp::Reverse(t.last_activity_at)),
+            ColumnSort::Priority => tasks.sort_by_key(|t| t.priority().unwrap_or(u8::MAX)),
+            ColumnSort::TimeInColumn => tasks.sort_by_key(|t| t.status_entered_at.unwrap_or(t.created_at)),
+        }
+    }
+}
+
+/// A row of a column's task list when tasks are grouped by tag
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ColumnRow {
+    /// Heading of a tag group (None = tasks without labels)
+    Group(Option<String>),
+    /// The task at this index of `tasks_by_status`
+    Task(usize),
+}
+
+/// Color palette the UI is drawn in.
+///
+/// The colorblind palettes keep hues that would look alike apart; monochrome
+/// drops color and relies on glyphs, labels and text styles alone.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum Palette {
+    /// The regular colors
+    #[default]
+    Standard,
+    /// Red-green safe, for reduced green sensitivity
+    Deuteranopia,
+    /// Red-green safe, for reduced red sensitivity
+    Protanopia,
+    /// Blue-yellow safe
+    Tritanopia,
+    /// No color: status shown by glyphs, labels and text styles
+    Monochrome,
+}
+
+impl Palette {
+    /// Get all palettes for UI selection
+    pub fn all() -> &'static [Palette] {
+        &[
+            Palette::Standard,
+            Palette::Deuteranopia,
+            Palette::Protanopia,
+            Palette::Tritanopia,
+            Palette::Monochrome,
+        ]
+    }
+
+    /// Get the display name for the palette
+    pub fn name(&self) -> &'static str {
+        match self {
+            Palette::Standard => "Standard",
+            Palette::Deuteranopia => "Deuteranopia",
+            Palette::Protanopia => "Protanopia",
+            Palette::Tritanopia => "Tritanopia",
+            Palette::Monochrome => "Monochrome",
+        }
+    }
+
+    /// Get a short description of the palette
+    pub fn description(&self) -> &'static str {
+        match self {
+            Palette::Standard => "The regular colors",
+            Palette::Deuteranopia => "Red-green safe colors (reduced green sensitivity)",
+            Palette::Protanopia => "Red-green safe colors with brighter reds (reduced red sensitivity)",
+            Palette::Tritanopia => "Blue-yellow safe colors",
+            Palette::Monochrome => "No color; status shown by glyphs, labels and bold/underline/reverse",
+        }
+    }
+
+    /// Lowercase name passed to the status bar process
+    pub fn key(&self) -> &'static str {
+        match self {
+            Palette::Standard => "standard",
+            Palette::Deuteranopia => "deuteranopia",
+            Palette::Protanopia => "protanopia",
+            Palette::Tritanopia => "tritanopia",
+            Palette::Monochrome => "monochrome",
+        }
+    }
+
+    /// Parse a name produced by `key`
+    pub fn from_key(key: &str) -> Option<Palette> {
+        Palette::all().iter().copied().find(|p| p.key() == key)
+    }
+}
+
+/// How the board's six columns are arranged.
+///
+/// The 2x3 grid needs a wide terminal; narrower boards show the columns as a
+/// list beside the selected column, or stacked with only the selected one open.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum BoardLayout {
+    /// Chosen by the board's width
+    #[default]
+    Auto,
+    /// Two columns by three rows
+    Grid,
+    /// Column list on the left, the selected column's tasks on the right
+    TwoColumn,
+    /// One line per column, the selected one open below its header
+    Stacked,
+}
+
+impl BoardLayout {
+    /// Narrowest board the grid is used for when automatic
+    pub const GRID_MIN_WIDTH: u16 = 100;
+    /// Narrowest board the two-column view is used for when automatic
+    pub const TWO_COLUMN_MIN_WIDTH: u16 = 60;
+
+    /// Get all layouts for UI selection
+    pub fn all() -> &'static [BoardLayout] {
+        &[
+            BoardLayout::Auto,
+            BoardLayout::Grid,
+            BoardLayout::TwoColumn,
+            BoardLayout::Stacked,
+        ]
+    }
+
+    /// Get the display name for the layout
+    pub fn name(&self) -> &'static str {
+        match self {
+            BoardLayout::Auto => "Automatic",
+            BoardLayout::Grid => "Grid",
+            BoardLayout::TwoColumn => "Two Column",
+            BoardLayout::Stacked => "Stacked",
+        }
+    }
+
+    /// Get a short description of the layout
+    pub fn description(&self) -> &'static str {
+        match self {
+            BoardLayout::Auto => "Grid on wide terminals, two-column below 100 columns, stacked below 60",
+            BoardLayout::Grid => "All six columns in a 2x3 grid",
+            BoardLayout::TwoColumn => "A list of the columns beside the selected column's tasks",
+            BoardLayout::Stacked => "One line per column, the selected column open at full width",
+        }
+    }
+
+    /// The layout used for a board `width` cells wide (never `Auto`)
+    pub fn resolve(self, width: u16) -> BoardLayout {
+        match self {
+            BoardLayout::Auto if width >= Self::GRID_MIN_WIDTH => BoardLayout::Grid,
+            BoardLayout::Auto if width >= Self::TWO_COLUMN_MIN_WIDTH => BoardLayout::TwoColumn,
+            BoardLayout::Auto => BoardLayout::Stacked,
+            layout => layout,
+        }
+    }
+
+    /// Column Up leaves `status` for at its first task
+    pub fn above(self, status: TaskStatus) -> Option<TaskStatus> {
+        match self {
+            // Row1 = Planned|InProgress, Row2 = Testing|NeedsWork, Row3 = Review|Done
+            BoardLayout::Auto | BoardLayout::Grid => match status {
+                TaskStatus::Testing => Some(TaskStatus::Planned),
+                TaskStatus::NeedsWork => Some(TaskStatus::InProgress),
+                TaskStatus::Review => Some(TaskStatus::Testing),
+                TaskStatus::Done => Some(TaskStatus::NeedsWork),
+                _ => None,
+            },
+            BoardLayout::TwoColumn | BoardLayout::Stacked => {
+                let columns = TaskStatus::all();
+                let idx = columns.iter().position(|&s| s == status)?;
+                idx.checked_sub(1).map(|i| columns[i])
+            }
+        }
+    }
+
+    /// Column Down leaves `status` for at its last task
+    pub fn below(self, status: TaskStatus) -> Option<TaskStatus> {
+        match self {
+            BoardLayout::Auto | BoardLayout::Grid => match status {
+                TaskStatus::Planned => Some(TaskStatus::Testing),
+                TaskStatus::InProgress => Some(TaskStatus::NeedsWork),
+                TaskStatus::Testing => Some(TaskStatus::Review),
+                TaskStatus::NeedsWork => Some(TaskStatus::Done),
+                _ => None,
+            },
+            BoardLayout::TwoColumn | BoardLayout::Stacked => {
+                let columns = TaskStatus::all();
+                let idx = columns.iter().position(|&s| s == status)?;
+                columns.get(idx + 1).copied()
+            }
+        }
+    }
+}
+
 impl Default for GlobalSettings {
     fn default() -> Self {
         Self {
@@ -150,6 +576,27 @@ impl Default for GlobalSettings {
             mascot_advice_enabled: None, // Will show intro message on first run
             mascot_advice_interval_minutes: 15,
             vim_mode_enabled: false, // Default to regular editor mode
+            native_git: false,
+            status_clock: false,
+            focus_minutes: 25,
+            break_minutes: 5,
+            focus_defer_starts: true,
+            sound_cues: false,
+            quiet_hours: None,
+            sla_thresholds: None,
+            webhooks: None,
+            idle_fps: 2,
+            activity_max_entries: activity::DEFAULT_MAX_ENTRIES,
+            activity_max_kb: activity::DEFAULT_MAX_KB,
+            palette: Palette::default(),
+            board_layout: BoardLayout::default(),
+            spell_check: None,
+            state_sync: None,
+            last_seen_version: None,
+            workspaces: Vec::new(),
+            active_workspace: None,
+            recent_projects: Vec::new(),
+            input_history: Vec::new(),
         }
     }
 }
@@ -651,7 +1098,7 @@ impl DirectoryBrowser {
         let output = std::process::Command::new("git")
             .args(["init"])
             .current_dir(&folder_path)
-            .output()?;
+            .timed_output()?;
 
         if !output.status.success() {
             // Clean up the folder if git init fails
@@ -682,6 +1129,11 @@ impl DirectoryBrowser {
 /// Application state following The Elm Architecture
 #[derive(Serialize, Deserialize)]
 pub struct AppModel {
+    /// Format version of the state file (see `schema`)
+    #[serde(default = "default_schema_version")]
+    pub schema_version: u32,
+    /// Saved separately: an index here, plus one state file per project (see `save_state`)
+    #[serde(default, skip_serializing)]
     pub projects: Vec<Project>,
     pub active_project_idx: usize,
     /// Global settings (shared across all projects)
@@ -691,17 +1143,26 @@ pub struct AppModel {
     /// Used to avoid replaying already-processed signals on restart
     #[serde(default)]
     pub last_processed_signal_ts: Option<i64>,
+    /// Sequence number of the last sidecar session event handled
+    /// Events after it are replayed from the sidecar's journal on (re)connect
+    #[serde(default)]
+    pub last_sidecar_seq: u64,
     #[serde(skip)]
     pub ui_state: UiState,
 }
 
+/// Files from before versioning are v1
+fn default_schema_version() -> u32 { 1 }
+
 impl Default for AppModel {
     fn default() -> Self {
         Self {
+            schema_version: schema::STATE_VERSION,
             projects: Vec::new(),
             active_project_idx: 0,
             global_settings: GlobalSettings::default(),
             last_processed_signal_ts: None,
+            last_sidecar_seq: 0,
             ui_state: UiState::default(),
         }
     }
@@ -716,6 +1177,22 @@ impl AppModel {
         self.projects.get_mut(self.active_project_idx)
     }
 
+    /// The task the live output pane follows: the selected task, if it is in progress
+    pub fn output_pane_task(&self) -> Option<&Task> {
+        let task_id = self.ui_state.selected_task_id?;
+        self.active_project()?
+            .tasks.iter()
+            .find(|t| t.id == task_id && t.status == TaskStatus::InProgress)
+    }
+
+    /// In-progress tasks of every project, as tiled in the monitor
+    /// (project index alongside each task)
+    pub fn monitor_tasks(&self) -> Vec<(usize, &Task)> {
+        self.projects.iter().enumerate()
+            .flat_map(|(idx, p)| p.tasks_by_status(TaskStatus::InProgress).into_iter().map(move |t| (idx, t)))
+            .collect()
+    }
+
 }
 
 /// A stash that we created and are tracking for the user
@@ -735,6 +1212,33 @@ pub struct TrackedStash {
     pub stash_sha: String,
 }
 
+/// Error signatures remembered per project for deduplication
+const MAX_ERROR_SIGNATURES: usize = 200;
+
+/// Context window of the models sessions run on, in tokens
+pub const CONTEXT_WINDOW_TOKENS: u64 = 200_000;
+
+/// Context usage at which a session restart with a summary is offered or triggered
+pub const CONTEXT_RESTART_AT: f64 = 0.85;
+
+/// Bytes of streamed session output kept per task for the live output pane
+pub const LIVE_OUTPUT_LIMIT: usize = 256 * 1024;
+
+/// Trailing session output included when asking for a progress summary
+const SUMMARY_OUTPUT_CHARS: usize = 8000;
+
+/// Diff included when asking for a change summary (the rest is cut)
+const CHANGE_SUMMARY_CHARS: usize = 60000;
+
+/// Feedback snippets for projects that haven't configured their own
+pub const DEFAULT_FEEDBACK_SNIPPETS: &[&str] = &[
+    "Add tests covering this change",
+    "Run the formatter and fix any lint warnings",
+    "Split this into smaller, focused commits",
+    "Rebase {branch} onto main and resolve conflicts",
+    "Update the docs for \"{title}\"",
+];
+
 /// A project represents a working directory with Claude Code sessions
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Project {
@@ -744,6 +1248,15 @@ pub struct Project {
     pub tasks: Vec<Task>,
     pub needs_attention: bool,
     pub created_at: DateTime<Utc>,
+    /// SSH destination (`user@host`) when the project lives on another machine;
+    /// `working_dir` is then a path on that host (see `crate::remote`)
+    #[serde(default)]
+    pub ssh_target: Option<String>,
+    /// Subdirectory the project is limited to when it's one of several projects
+    /// in a monorepo; `working_dir` is then the repository root, whose worktree
+    /// machinery the projects share
+    #[serde(default)]
+    pub scope: Option<PathBuf>,
     #[serde(skip)]
     pub captured_output: String,
 
@@ -759,6 +1272,13 @@ pub struct Project {
     /// When true, the patch file contains the combined changes (task + resolution)
     #[serde(default)]
     pub applied_with_conflict_resolution: bool,
+    /// Main's HEAD when the changes were applied; a different HEAD later means
+    /// something was committed on top of the applied (uncommitted) changes
+    #[serde(default)]
+    pub applied_head: Option<String>,
+    /// Main's HEAD moved while changes were applied (warned about once)
+    #[serde(skip)]
+    pub applied_head_moved: bool,
 
     /// Stashes we created that the user may want to restore
     /// Tracked so we can show an indicator and offer to pop/delete them
@@ -788,6 +1308,73 @@ pub struct Project {
     #[serde(default)]
     pub apply_strategy: ApplyStrategy,
 
+    /// What to do when a session's context is nearly full (default: Ask)
+    #[serde(default)]
+    pub context_restart: ContextRestart,
+
+    /// Where merge commit messages come from (default: Ask)
+    #[serde(default)]
+    pub commit_messages: CommitMessages,
+
+    /// Order of tasks within each column (default: Manual)
+    #[serde(default)]
+    pub column_sort: ColumnSort,
+
+    /// Group each column's tasks under their first `#label`
+    #[serde(default)]
+    pub group_by_tag: bool,
+
+    /// Offer a version bump + annotated tag when a task labeled #release is merged
+    #[serde(default)]
+    pub auto_version: bool,
+
+    /// Label -> semver bump rules (e.g. "major:breaking,minor:feature|feat"); None = defaults
+    #[serde(default)]
+    pub version_rules: Option<String>,
+
+    /// Number of pre-warmed worktrees kept ready for new tasks (0 = pool disabled)
+    #[serde(default)]
+    pub worktree_pool_size: usize,
+
+    /// Sparse-checkout profile for task worktrees (e.g. "services/api/** shared/"); None = full checkout
+    #[serde(default)]
+    pub sparse_paths: Option<String>,
+
+    /// Directories new worktrees take from the main checkout (e.g. "node_modules target:copy"),
+    /// see `crate::worktree::caches`; None = none
+    #[serde(default)]
+    pub shared_caches: Option<String>,
+
+    /// Extra paths left out of git status and diff stats (e.g. "dist/ *.lock"); None = none
+    #[serde(default)]
+    pub status_ignore: Option<String>,
+
+    /// Log file (or "$ command") watched for new errors; None = watcher off
+    #[serde(default)]
+    pub error_log_source: Option<String>,
+
+    /// Start bug tasks from new errors right away (restricted agent profile) instead of asking
+    #[serde(default)]
+    pub error_log_auto_start: bool,
+
+    /// Signatures of errors already offered as tasks (oldest first, capped)
+    #[serde(default)]
+    pub error_signatures: Vec<String>,
+    /// Canned feedback for this project (empty = the built-in defaults)
+    #[serde(default)]
+    pub feedback_snippets: Vec<String>,
+    /// Shell command printing the transcript of an audio file ({file} = the file)
+    #[serde(default)]
+    pub transcribe_command: Option<String>,
+    /// Slack/Discord channel needs-input prompts are posted to and answered from
+    /// ("slack <channel> <token or $VAR>"); None = off
+    #[serde(default)]
+    pub chat_bridge: Option<String>,
+    /// Panes of the interactive tmux session ("claude 60% | *npm run dev | shell");
+    /// None = Claude beside a shell
+    #[serde(default)]
+    pub session_layout: Option<String>,
+
     // Remote tracking status (transient - not persisted)
     /// Number of commits ahead of remote (local commits not pushed)
     #[serde(skip)]
@@ -798,9 +1385,18 @@ pub struct Project {
     /// Whether there's a configured remote tracking branch
     #[serde(skip)]
     pub has_remote: bool,
-    /// Whether a git operation (fetch/pull/push) is currently in progress
+    /// Whether a git operation (fetch/pull/push/merge) is currently in progress
     #[serde(skip)]
     pub git_operation_in_progress: Option<GitOperation>,
+    /// Latest progress reported by the running git operation
+    #[serde(skip)]
+    pub git_progress: Option<crate::worktree::GitProgress>,
+    /// Cancels the running git operation (set while one is in progress)
+    #[serde(skip)]
+    pub git_cancel: Option<crate::worktree::CancelToken>,
+    /// Stops the error-log watcher (set while it runs)
+    #[serde(skip)]
+    pub error_log_cancel: Option<crate::worktree::CancelToken>,
 
     // Watcher state (transient - not persisted)
     /// Whether the watcher is enabled for this project
@@ -824,10 +1420,70 @@ pub struct Project {
     /// Startup time for this session (used to delay intro by 1 minute)
     #[serde(skip)]
     pub watcher_startup_time: Option<std::time::Instant>,
+    /// Past watcher comments, oldest first (Z opens the feed)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub watcher_feed: Vec<watcher_feed::FeedEntry>,
+    /// Watcher categories not to be told about
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub watcher_muted: Vec<crate::sidecar::protocol::WatcherCategory>,
+    /// Columns whose tasks the watcher looks at besides the main checkout
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub watcher_columns: Vec<TaskStatus>,
+    /// Who the watcher speaks as (None = a supportive coding buddy)
+    #[serde(default)]
+    pub watcher_persona: Option<String>,
+    /// Minutes between observations on this project (None = the global Advice Interval)
+    #[serde(default)]
+    pub watcher_interval: Option<u32>,
+    /// Whether observations may read files and diffs or only metadata
+    #[serde(default)]
+    pub watcher_reads: crate::watcher_scope::WatcherReads,
 
     /// Aggregated statistics for completed tasks (loaded from ProjectTaskData)
     #[serde(default)]
     pub statistics: TaskStatistics,
+
+    /// The project's own state file has been read (false for an index entry
+    /// that hasn't been activated yet; such projects are never saved)
+    #[serde(skip)]
+    pub loaded: bool,
+    /// Why the project's state file couldn't be read (it stays an index entry,
+    /// its file is left as it is, and loading is retried when the tab is activated)
+    #[serde(skip)]
+    pub load_error: Option<String>,
+    /// Index entry that couldn't be read at all, written back as it was so
+    /// saving doesn't drop the project
+    #[serde(skip)]
+    pub unreadable_entry: Option<serde_json::Value>,
+}
+
+/// What the global state file keeps about a project; everything else lives in the
+/// project's own state file and is read when the project is first activated
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectIndexEntry {
+    pub id: Uuid,
+    pub name: String,
+    pub working_dir: PathBuf,
+    pub created_at: DateTime<Utc>,
+    /// Shown on the project tab before the project is loaded
+    #[serde(default)]
+    pub needs_attention: bool,
+    /// SSH destination of a remote project
+    #[serde(default)]
+    pub ssh_target: Option<String>,
+    /// Subdirectory of a monorepo the project is limited to
+    #[serde(default)]
+    pub scope: Option<PathBuf>,
+}
+
+impl ProjectIndexEntry {
+    /// Same as `Project::scope_dir`
+    pub fn scope_dir(&self) -> PathBuf {
+        match &self.scope {
+            Some(scope) => self.working_dir.join(scope),
+            None => self.working_dir.clone(),
+        }
+    }
 }
 
 /// Custom commands for a project. All fields are optional - when None,
@@ -849,6 +1505,87 @@ pub struct ProjectCommands {
 
     /// Command to lint code (e.g., "cargo clippy", "npm run lint", "ruff check .")
     pub lint: Option<String>,
+
+    /// Script run in a new task's worktree before Claude starts (e.g., "npm install", "direnv allow").
+    /// Never auto-detected; a failure keeps the task from starting.
+    #[serde(default)]
+    pub pre_start: Option<String>,
+
+    /// Script run on main after a task is merged (e.g., "cargo fmt && cargo test").
+    /// Never auto-detected; a failure moves the task to Needs Work.
+    #[serde(default)]
+    pub post_merge: Option<String>,
+
+    /// Command deploying a Review task's worktree as a preview (e.g., "vercel deploy", "flyctl deploy").
+    /// Never auto-detected; the last URL it prints becomes the task's preview URL.
+    #[serde(default)]
+    pub preview: Option<String>,
+}
+
+/// When a project lifecycle script runs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScriptStage {
+    PreStart,
+    PostMerge,
+}
+
+impl ScriptStage {
+    pub fn label(&self) -> &'static str {
+        match self {
+            ScriptStage::PreStart => "Pre-start script",
+            ScriptStage::PostMerge => "Post-merge script",
+        }
+    }
+}
+
+/// Filters and position of the debug log viewer (`)
+#[derive(Debug, Clone)]
+pub struct LogViewerState {
+    /// Least severe level shown
+    pub min_level: tracing::Level,
+    /// Only this module's lines, if set
+    pub target: Option<String>,
+    /// Paused at this line (see `crate::logging::LogLine::seq`); None follows new lines
+    pub until: Option<u64>,
+    /// Lines scrolled back from the newest shown
+    pub scroll: usize,
+}
+
+impl Default for LogViewerState {
+    fn default() -> Self {
+        Self { min_level: tracing::Level::DEBUG, target: None, until: None, scroll: 0 }
+    }
+}
+
+/// The step of starting a task that went wrong
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum StartPhase {
+    Worktree,
+    PreStart,
+    Session,
+}
+
+impl StartPhase {
+    pub fn label(&self) -> &'static str {
+        match self {
+            StartPhase::Worktree => "Creating the worktree",
+            StartPhase::PreStart => "Pre-start script",
+            StartPhase::Session => "Starting the Claude session",
+        }
+    }
+}
+
+/// The last failed attempt to start a task, kept until a start succeeds
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StartFailure {
+    pub phase: StartPhase,
+    pub error: String,
+    /// What the failing step printed (the pre-start script's output)
+    #[serde(default)]
+    pub output: Option<String>,
+    pub failed_at: DateTime<Utc>,
+    /// Failed starts in a row
+    pub attempts: u32,
 }
 
 impl ProjectCommands {
@@ -986,6 +1723,10 @@ pub struct WatcherCommentDisplay {
     pub scroll_delay: usize,
     /// Whether this is the intro message (shows different hints)
     pub is_intro: bool,
+    /// Whether the insight modal has been opened for this comment
+    pub insight_seen: bool,
+    /// What the observation looked for (m in the insight modal mutes it)
+    pub category: Option<crate::sidecar::protocol::WatcherCategory>,
 }
 
 impl WatcherCommentDisplay {
@@ -999,6 +1740,8 @@ impl WatcherCommentDisplay {
             scroll_offset: 0,
             scroll_delay: 0,
             is_intro: false,
+            insight_seen: false,
+            category: None,
         }
     }
 
@@ -1012,11 +1755,13 @@ impl WatcherCommentDisplay {
             scroll_offset: 0,
             scroll_delay: 0,
             is_intro: true,
+            insight_seen: false,
+            category: None,
         }
     }
 }
 
-/// Git remote operations (fetch/pull/push)
+/// Long-running git operations shown in the status bar (fetch/pull/push/merge)
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum GitOperation {
     /// Fetching from remote to check status
@@ -1025,6 +1770,8 @@ pub enum GitOperation {
     Pulling,
     /// Pushing changes to remote
     Pushing,
+    /// Merging a task's branch into main
+    Merging,
 }
 
 impl GitOperation {
@@ -1033,6 +1780,7 @@ impl GitOperation {
             GitOperation::Fetching => "Fetching...",
             GitOperation::Pulling => "Pulling...",
             GitOperation::Pushing => "Pushing...",
+            GitOperation::Merging => "Merging...",
         }
     }
 }
@@ -1046,35 +1794,147 @@ impl Project {
             tasks: Vec::new(),
             needs_attention: false,
             created_at: Utc::now(),
+            // Remote project paths are registered when their host is connected
+            ssh_target: crate::remote::target_for(&working_dir),
+            scope: None,
             captured_output: String::new(),
             applied_task_id: None,
             applied_stash_ref: None,
             applied_with_conflict_resolution: false,
+            applied_head: None,
+            applied_head_moved: false,
             tracked_stashes: Vec::new(),
             main_worktree_lock: None,
             commands: ProjectCommands::default(), // Will auto-detect when needed
             max_qa_attempts: default_max_qa_attempts(),
             qa_enabled: default_qa_enabled(),
             apply_strategy: ApplyStrategy::default(),
+            context_restart: ContextRestart::default(),
+            commit_messages: CommitMessages::default(),
+            column_sort: ColumnSort::default(),
+            group_by_tag: false,
+            auto_version: false,
+            version_rules: None,
+            worktree_pool_size: 0,
+            sparse_paths: None,
+            shared_caches: None,
+            status_ignore: None,
+            error_log_source: None,
+            error_log_auto_start: false,
+            error_signatures: Vec::new(),
+            feedback_snippets: Vec::new(),
+            transcribe_command: None,
+            chat_bridge: None,
+            session_layout: None,
             remote_ahead: 0,
             remote_behind: 0,
             has_remote: false,
             git_operation_in_progress: None,
+            git_progress: None,
+            git_cancel: None,
+            error_log_cancel: None,
             watcher_enabled: false,
             watcher_comment: None,
             watcher_observing: false,
             watcher_awaiting_dismissal: false,
             watcher_last_interaction: None,
             watcher_intro_shown: false,
+            watcher_feed: Vec::new(),
+            watcher_muted: Vec::new(),
+            watcher_columns: Vec::new(),
+            watcher_persona: None,
+            watcher_interval: None,
+            watcher_reads: Default::default(),
             watcher_startup_time: None,
             statistics: TaskStatistics::default(),
+            loaded: true,
+            load_error: None,
+            unreadable_entry: None,
+        }
+    }
+
+    /// A project known only from the index, waiting to be loaded
+    pub fn from_index(entry: ProjectIndexEntry) -> Self {
+        Self {
+            id: entry.id,
+            created_at: entry.created_at,
+            needs_attention: entry.needs_attention,
+            ssh_target: entry.ssh_target,
+            scope: entry.scope,
+            loaded: false,
+            ..Self::new(entry.name, entry.working_dir)
+        }
+    }
+
+    /// Canned feedback offered in feedback mode (configured, or the defaults)
+    pub fn snippets(&self) -> Vec<&str> {
+        if self.feedback_snippets.is_empty() {
+            DEFAULT_FEEDBACK_SNIPPETS.to_vec()
+        } else {
+            self.feedback_snippets.iter().map(String::as_str).collect()
+        }
+    }
+
+    /// Where the project is, as typed in the open-project dialog:
+    /// its directory, or `user@host:/path` for remote projects
+    pub fn location(&self) -> PathBuf {
+        match &self.ssh_target {
+            Some(target) => PathBuf::from(format!("{}:{}", target, self.scope_dir().display())),
+            None => self.scope_dir(),
+        }
+    }
+
+    /// The directory the project covers: its scope in a monorepo, otherwise
+    /// `working_dir`. Tasks, commands and templates live here.
+    pub fn scope_dir(&self) -> PathBuf {
+        match &self.scope {
+            Some(scope) => self.working_dir.join(scope),
+            None => self.working_dir.clone(),
+        }
+    }
+
+    /// Minutes between watcher observations: the project's own interval, else `global`
+    pub fn watcher_interval_minutes(&self, global: u32) -> u32 {
+        self.watcher_interval.unwrap_or(global)
+    }
+
+    /// `dir` (the repository root or a task's worktree) narrowed to the project's scope
+    pub fn scoped(&self, dir: &Path) -> PathBuf {
+        match &self.scope {
+            Some(scope) => dir.join(scope),
+            None => dir.to_path_buf(),
+        }
+    }
+
+    /// Prompt section keeping a session inside the project's scope, empty when unscoped
+    pub fn scope_prompt(&self) -> String {
+        let Some(scope) = &self.scope else {
+            return String::new();
+        };
+        format!(
+            "\n\n# Scope\nThis task belongs to `{0}/` of a larger repository. Keep your changes inside \
+             `{0}/` and run its commands from there, unless the task can't be done without touching \
+             something outside it.",
+            scope.display()
+        )
+    }
+
+    pub fn index_entry(&self) -> ProjectIndexEntry {
+        ProjectIndexEntry {
+            id: self.id,
+            name: self.name.clone(),
+            working_dir: self.working_dir.clone(),
+            created_at: self.created_at,
+            needs_attention: self.needs_attention,
+            ssh_target: self.ssh_target.clone(),
+            scope: self.scope.clone(),
         }
     }
 
     /// Format a task reference for display in messages: "[abc123] title truncat..."
     /// Short ID (6 chars) + truncated title (max 20 chars)
     /// Uses short_title if available, otherwise truncates the full title
-    fn format_task_ref(&self, task_id: Uuid) -> String {
+    pub fn format_task_ref(&self, task_id: Uuid) -> String {
         let short_id = &task_id.to_string()[..6];
         let title = self.tasks.iter()
             .find(|t| t.id == task_id)
@@ -1166,12 +2026,50 @@ impl Project {
     }
 
     pub fn tasks_by_status(&self, status: TaskStatus) -> Vec<&Task> {
-        // Return tasks in Vec order - allows manual reordering with +/-
+        // Vec order is the manual order set with +/-, then the column sort and tag groups apply
         // Accepting, Updating, and Applying tasks appear in the Review column
-        self.tasks.iter().filter(|t| {
+        let mut tasks: Vec<&Task> = self.tasks.iter().filter(|t| {
             t.status == status ||
             (status == TaskStatus::Review && (t.status == TaskStatus::Accepting || t.status == TaskStatus::Updating || t.status == TaskStatus::Applying))
-        }).collect()
+        }).collect();
+        self.column_sort.sort(&mut tasks);
+        if self.group_by_tag {
+            // Alphabetical by tag, untagged tasks last
+            tasks.sort_by_key(|t| {
+                let group = t.tag_group();
+                (group.is_none(), group)
+            });
+        }
+        tasks
+    }
+
+    /// Whether columns are in some order other than the manual one
+    pub fn is_sorted(&self) -> bool {
+        self.column_sort != ColumnSort::Manual || self.group_by_tag
+    }
+
+    /// Whether two tasks of a column fall in the same tag group (always, when not grouping)
+    pub fn same_group(&self, a: &Task, b: &Task) -> bool {
+        !self.group_by_tag || a.tag_group() == b.tag_group()
+    }
+
+    /// Rows of a column's task list: the tasks of `tasks_by_status`, with a
+    /// heading before each tag group when grouping by tag
+    pub fn column_rows(&self, status: TaskStatus) -> Vec<ColumnRow> {
+        let tasks = self.tasks_by_status(status);
+        let mut rows = Vec::with_capacity(tasks.len());
+        let mut current = None;
+        for (idx, task) in tasks.iter().enumerate() {
+            if self.group_by_tag {
+                let group = task.tag_group();
+                if idx == 0 || current != group {
+                    rows.push(ColumnRow::Group(group.clone()));
+                    current = group;
+                }
+            }
+            rows.push(ColumnRow::Task(idx));
+        }
+        rows
     }
 
     pub fn in_progress_task(&self) -> Option<&Task> {
@@ -1327,6 +2225,60 @@ impl Project {
         }
     }
 
+    /// Sparse-checkout patterns for a task's worktree: its override if set, else the project profile
+    pub fn sparse_patterns_for(&self, task_id: Uuid) -> Vec<String> {
+        let task_override = self.tasks.iter()
+            .find(|t| t.id == task_id)
+            .and_then(|t| t.sparse_paths.as_deref());
+        task_override
+            .or(self.sparse_paths.as_deref())
+            .map(crate::worktree::parse_sparse_patterns)
+            .unwrap_or_default()
+    }
+
+    /// Patterns left out of git status and diff stats
+    pub fn status_ignore_patterns(&self) -> Vec<String> {
+        self.status_ignore.as_deref()
+            .map(crate::worktree::ignore::parse_patterns)
+            .unwrap_or_default()
+    }
+
+    /// Clear the running git operation's state.
+    /// Returns true if the operation ended because it was cancelled.
+    pub fn finish_git_operation(&mut self) -> bool {
+        self.git_operation_in_progress = None;
+        self.git_progress = None;
+        self.git_cancel.take().is_some_and(|cancel| cancel.is_cancelled())
+    }
+
+    /// Stop the error-log watcher, if running
+    pub fn stop_error_log_watch(&mut self) {
+        if let Some(cancel) = self.error_log_cancel.take() {
+            cancel.cancel();
+        }
+    }
+
+    /// Remember an error signature. Returns false if it was already seen.
+    pub fn record_error_signature(&mut self, signature: &str) -> bool {
+        if self.error_signatures.iter().any(|s| s == signature) {
+            return false;
+        }
+        self.error_signatures.push(signature.to_string());
+        if self.error_signatures.len() > MAX_ERROR_SIGNATURES {
+            self.error_signatures.remove(0);
+        }
+        true
+    }
+
+    /// Record that a task's changes landed on main (used by release notes)
+    pub fn mark_task_merged(&mut self, task_id: Uuid) {
+        if let Some(task) = self.tasks.iter_mut().find(|t| t.id == task_id) {
+            task.merged_at = Some(Utc::now());
+            // The next merge (after merge-only) writes its own
+            task.merge_message = None;
+        }
+    }
+
     pub fn needs_work_count(&self) -> usize {
         self.tasks.iter().filter(|t| t.status == TaskStatus::NeedsWork).count()
     }
@@ -1344,17 +2296,166 @@ impl Project {
             )
         }).count()
     }
+
+    /// Tasks needing attention that still have content the user hasn't viewed
+    /// (drives the project tab badges)
+    pub fn unseen_attention_count(&self) -> usize {
+        // Not loaded yet: all that's known is whether it wanted attention
+        if !self.loaded {
+            return usize::from(self.needs_attention);
+        }
+        self.tasks.iter().filter(|t| {
+            matches!(t.status,
+                TaskStatus::Review |
+                TaskStatus::Accepting |
+                TaskStatus::Updating |
+                TaskStatus::Applying |
+                TaskStatus::NeedsWork
+            ) && t.has_unseen_content()
+        }).count()
+    }
+
+    /// Find the task this task depends on, and how (queued behind it or stacked on it).
+    /// A pending queue edge takes precedence over a historical stack edge.
+    fn dependency_parent(&self, task: &Task) -> Option<(Uuid, DependencyEdge)> {
+        let parent = task.queued_for_session.map(|id| (id, DependencyEdge::Queued))
+            .or_else(|| task.stacked_on.map(|id| (id, DependencyEdge::Stacked)))?;
+        // Ignore edges to tasks that no longer exist (e.g. deleted)
+        self.tasks.iter().any(|t| t.id == parent.0).then_some(parent)
+    }
+
+    /// Build the dependency graph as a flattened forest, in depth-first order.
+    /// Only tasks that take part in at least one queue/stack edge are included.
+    /// Roots keep their board order; children are listed under their parent.
+    pub fn dependency_graph(&self) -> Vec<DependencyGraphRow> {
+        let parents: Vec<Option<(Uuid, DependencyEdge)>> = self.tasks.iter()
+            .map(|t| self.dependency_parent(t))
+            .collect();
+
+        let children_of = |id: Uuid| -> Vec<usize> {
+            parents.iter().enumerate()
+                .filter(|(_, p)| p.map(|(pid, _)| pid) == Some(id))
+                .map(|(idx, _)| idx)
+                .collect()
+        };
+
+        let mut rows = Vec::new();
+        let mut visited = vec![false; self.tasks.len()];
+        let roots: Vec<usize> = (0..self.tasks.len())
+            .filter(|&idx| parents[idx].is_none() && !children_of(self.tasks[idx].id).is_empty())
+            .collect();
+
+        // Iterative DFS: (task index, depth, ancestor "is last sibling" flags)
+        let mut stack: Vec<(usize, Vec<bool>)> = roots.iter().rev()
+            .map(|&idx| (idx, Vec::new()))
+            .collect();
+        while let Some((idx, ancestors_last)) = stack.pop() {
+            // Guard against cycles in hand-edited state files
+            if visited[idx] {
+                continue;
+            }
+            visited[idx] = true;
+
+            let task = &self.tasks[idx];
+            rows.push(DependencyGraphRow {
+                task_id: task.id,
+                edge: parents[idx].map(|(_, edge)| edge),
+                ancestors_last: ancestors_last.clone(),
+            });
+
+            let children = children_of(task.id);
+            let last = children.len().saturating_sub(1);
+            for (i, child) in children.into_iter().enumerate().rev() {
+                let mut flags = ancestors_last.clone();
+                flags.push(i == last);
+                stack.push((child, flags));
+            }
+        }
+
+        rows
+    }
 }
 
-/// A single entry in the task activity log
+/// How a task depends on its parent in the dependency graph
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DependencyEdge {
+    /// Waiting to run in the parent's session once it finishes
+    Queued,
+    /// Continued in the parent's worktree after it finished (stacked work)
+    Stacked,
+}
+
+/// A single row of the flattened dependency graph
 #[derive(Debug, Clone)]
+pub struct DependencyGraphRow {
+    pub task_id: Uuid,
+    /// Edge to the parent (None for root tasks)
+    pub edge: Option<DependencyEdge>,
+    /// For each ancestor level (and this node last): whether that node is the last sibling.
+    /// Used to draw the tree connectors (│ vs blank, ├ vs └).
+    pub ancestors_last: Vec<bool>,
+}
+
+impl DependencyGraphRow {
+    /// Depth of this node in the tree (0 = root)
+    pub fn depth(&self) -> usize {
+        self.ancestors_last.len()
+    }
+}
+
+/// What an activity entry records, for the session replay
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ActivityKind {
+    /// Session lifecycle and everything else
+    #[default]
+    Event,
+    /// The agent used a tool
+    ToolCall,
+    /// The agent produced output
+    Output,
+    /// The task moved to another column
+    StatusChange,
+    /// Rebase conflicts handed to a session to resolve
+    ConflictResolution,
+    /// A tool call failed
+    Error,
+    /// A subagent was launched or returned
+    Subagent,
+}
+
+impl ActivityKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            ActivityKind::Event => "Event",
+            ActivityKind::ToolCall => "Tool call",
+            ActivityKind::Output => "Output",
+            ActivityKind::StatusChange => "Status",
+            ActivityKind::ConflictResolution => "Conflict resolution",
+            ActivityKind::Error => "Error",
+            ActivityKind::Subagent => "Subagent",
+        }
+    }
+}
+
+/// A single entry in the task activity log
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ActivityLogEntry {
     /// When this activity occurred
     pub timestamp: DateTime<Utc>,
     /// Short description of the activity
     pub message: String,
-    /// Full accumulated output at this point (if available)
+    /// Full accumulated output at this point (if available; saved to `output_file`)
+    #[serde(skip)]
     pub full_output: Option<String>,
+    /// Spill file holding `full_output` between runs
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output_file: Option<PathBuf>,
+    /// Written by the user rather than logged by a session
+    #[serde(default)]
+    pub note: bool,
+    /// What the entry records
+    #[serde(default)]
+    pub kind: ActivityKind,
 }
 
 impl ActivityLogEntry {
@@ -1363,15 +2464,25 @@ impl ActivityLogEntry {
             timestamp: Utc::now(),
             message: message.into(),
             full_output: None,
+            output_file: None,
+            note: false,
+            kind: ActivityKind::Event,
         }
     }
 
     /// Create an activity log entry with full output
     pub fn with_output(message: impl Into<String>, full_output: Option<String>) -> Self {
         Self {
-            timestamp: Utc::now(),
-            message: message.into(),
             full_output,
+            ..Self::new(message)
+        }
+    }
+
+    /// Create a manual note
+    pub fn note(text: impl Into<String>) -> Self {
+        Self {
+            note: true,
+            ..Self::new(text)
         }
     }
 }
@@ -1473,6 +2584,9 @@ pub struct Task {
     pub spec: Option<String>,
     pub status: TaskStatus,
     pub images: Vec<PathBuf>,
+    /// Other attached files (stored copies), referenced in the prompt when the task starts
+    #[serde(default)]
+    pub attachments: Vec<PathBuf>,
     pub claude_session_id: Option<String>,
     pub created_at: DateTime<Utc>,
     pub started_at: Option<DateTime<Utc>>,
@@ -1509,13 +2623,17 @@ pub struct Task {
     /// Used to ignore spurious "working" signals from Claude CLI startup
     #[serde(default)]
     pub cli_opened_at: Option<DateTime<Utc>>,
-    /// Feedback queued to be sent when Claude finishes current work
-    /// Used when user sends feedback while SDK/CLI is actively working
+    /// Feedback queued to be sent when Claude finishes current work, oldest
+    /// first; one is delivered each time Claude stops. Used when user sends
+    /// feedback while SDK/CLI is actively working
     #[serde(skip)]
-    pub pending_feedback: Option<String>,
+    pub queued_feedback: Vec<String>,
     /// History of all feedback sent to Claude (persisted)
     #[serde(default)]
     pub feedback_history: Vec<FeedbackEntry>,
+    /// Notes added to the activity log by hand (persisted)
+    #[serde(default)]
+    pub activity_notes: Vec<ActivityLogEntry>,
 
     // === Task queueing ===
 
@@ -1523,6 +2641,10 @@ pub struct Task {
     /// (in the same Claude session/worktree)
     #[serde(default)]
     pub queued_for_session: Option<Uuid>,
+    /// If set, this task continued in the worktree/session of the specified task
+    /// (recorded when a queued task takes over a finished task's session)
+    #[serde(default)]
+    pub stacked_on: Option<Uuid>,
 
     // === Activity tracking (for merge/rebase feedback) ===
 
@@ -1538,9 +2660,12 @@ pub struct Task {
 
     // === Activity log (for UI feedback during Accepting/Updating) ===
 
-    /// Recent activity log entries (not persisted)
-    #[serde(skip)]
+    /// Recent activity log entries (persisted, trimmed by the retention policy in `activity`)
+    #[serde(default)]
     pub activity_log: Vec<ActivityLogEntry>,
+    /// Files holding captures of the session's scrollback, oldest first
+    #[serde(default)]
+    pub scrollback_captures: Vec<PathBuf>,
 
     // === Git status cache (updated periodically) ===
 
@@ -1610,12 +2735,205 @@ pub struct Task {
     /// Total cost in USD for this task
     #[serde(default)]
     pub total_cost_usd: f64,
+    /// Tokens in the SDK session's context window after its latest turn (0 = unknown)
+    #[serde(default)]
+    pub context_tokens: u64,
+    /// Times the SDK compacted this task's conversation
+    #[serde(default)]
+    pub context_compactions: u32,
+    /// A context restart was offered or started for the current session (not persisted)
+    #[serde(skip)]
+    pub context_restart_offered: bool,
+    /// The session is being replaced by a fresh one seeded with a summary (not persisted)
+    #[serde(skip)]
+    pub restarting_session: bool,
+    /// Status last recorded in the activity log (not persisted; None until first seen)
+    #[serde(skip)]
+    pub logged_status: Option<TaskStatus>,
+    /// Session output streamed since kanblam started (not persisted; capped at LIVE_OUTPUT_LIMIT)
+    #[serde(skip)]
+    pub live_output: String,
 
     // === Time tracking ===
 
     /// When the task first entered Review status (for QA time tracking)
     #[serde(default)]
     pub review_started_at: Option<DateTime<Utc>>,
+    /// When the task moved into its current column (for SLA warnings)
+    #[serde(default)]
+    pub status_entered_at: Option<DateTime<Utc>>,
+    /// The SLA warning for the current column was already raised
+    #[serde(default)]
+    pub sla_notified: bool,
+    /// Time spent on the task: session work, focus blocks and manual entries
+    #[serde(default)]
+    pub time_entries: Vec<time_tracking::TimeEntry>,
+    /// When the task's branch was merged into main (None for discarded tasks)
+    #[serde(default)]
+    pub merged_at: Option<DateTime<Utc>>,
+    /// Sparse-checkout override chosen when starting (None = project default, "" = full checkout)
+    #[serde(default)]
+    pub sparse_paths: Option<String>,
+    /// Run with the restricted agent profile (no blanket Bash/Write approval)
+    #[serde(default)]
+    pub restricted_agent: bool,
+    /// Claude model for the task's sessions (None = the default), set with `/model`
+    #[serde(default)]
+    pub model: Option<String>,
+    /// Ref the worktree branches off (None = HEAD), set with `/base`
+    #[serde(default)]
+    pub base_ref: Option<String>,
+    /// Start without the project's shared caches, set with `/fresh`
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub fresh_worktree: bool,
+    /// Why the last start failed (C on the board shows it; cleared once a start succeeds)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub start_failure: Option<StartFailure>,
+    /// First of the ports the task's dev servers get while it has a worktree (see `crate::ports`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ports: Option<u16>,
+    /// Where the project's preview command last deployed the task's worktree
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub preview_url: Option<String>,
+    /// The preview command is running for this task
+    #[serde(skip)]
+    pub preview_deploying: bool,
+    /// Environment variables for the task's sessions and panes, set with `/env`
+    /// (`secret:NAME` values are looked up in the keychain, see `crate::secrets`)
+    #[serde(default, skip_serializing_if = "std::collections::BTreeMap::is_empty")]
+    pub env: std::collections::BTreeMap<String, String>,
+
+    // === Auto-merge ===
+
+    /// Merge once CI passes on the pushed branch (toggled in Review)
+    #[serde(default)]
+    pub auto_merge: bool,
+    /// Last polled CI status of the pushed branch (not persisted, see `watches_ci`)
+    #[serde(skip)]
+    pub ci_status: Option<crate::ci::CiStatus>,
+
+    // === Chat approvals ===
+
+    /// Needs-input prompt posted to the project's chat, waiting for a reply
+    #[serde(default)]
+    pub chat_thread: Option<crate::chat::ChatThread>,
+
+    // === Review ===
+
+    /// Comments left on lines of the task's diff in the Git tab
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub review_comments: Vec<ReviewComment>,
+    /// Summary and risk assessment of the task's diff, shown atop the Git tab
+    #[serde(default)]
+    pub change_summary: Option<ChangeSummary>,
+    /// A change summary is being generated (not persisted)
+    #[serde(skip)]
+    pub summarizing_changes: bool,
+    /// Commit message for the next merge, chosen when it started (not persisted)
+    #[serde(skip)]
+    pub merge_message: Option<String>,
+    /// A commit message for a merge is being written (not persisted)
+    #[serde(skip)]
+    pub composing_commit: bool,
+
+    // === Read state ===
+
+    /// What the user has already looked at (drives "new" markers)
+    #[serde(default)]
+    pub read_state: ReadState,
+    /// When the branch diff last changed (set from git status refreshes)
+    #[serde(default)]
+    pub diff_changed_at: Option<DateTime<Utc>>,
+}
+
+/// A review comment on a line of a task's diff
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReviewComment {
+    pub path: String,
+    /// Line in the task's version of the file, or in main's for a removed line
+    pub line: u32,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub removed: bool,
+    pub text: String,
+    pub created_at: DateTime<Utc>,
+    /// When the comment went to Claude as feedback
+    #[serde(default)]
+    pub sent_at: Option<DateTime<Utc>>,
+    /// When Claude finished the turn it was sent in
+    #[serde(default)]
+    pub resolved_at: Option<DateTime<Utc>>,
+}
+
+impl ReviewComment {
+    /// Whether the comment sits on this diff line
+    pub fn is_at(&self, location: &crate::worktree::diff_cache::LineLocation) -> bool {
+        self.path == location.path && self.line == location.line && self.removed == location.removed
+    }
+
+    /// "src/foo.rs line 120" (removed lines numbered as on main)
+    pub fn anchor(&self) -> String {
+        if self.removed {
+            format!("{} line {} (removed, as numbered on main)", self.path, self.line)
+        } else {
+            format!("{} line {}", self.path, self.line)
+        }
+    }
+}
+
+/// What a task's diff changes, where it's risky and which files need a
+/// human look, as summarized by the sidecar
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChangeSummary {
+    pub text: String,
+    /// Branch head the summary was made for
+    pub head: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl ChangeSummary {
+    /// The branch has moved on since the summary was made
+    pub fn is_stale(&self, head: &str) -> bool {
+        !head.is_empty() && self.head != head
+    }
+
+    /// One-shot prompt asking for a review summary of `diff`, given the task's
+    /// own description (`brief`)
+    pub fn prompt(brief: &str, diff: &str) -> String {
+        let mut prompt = String::from(
+            "A coding agent made the changes below for the task that follows. Help a \
+             reviewer triage them. Answer in plain text with three short sections:\n\
+             Changes: what changed, in a few bullets.\n\
+             Risks: risky areas (behavior changes, error handling, concurrency, data \
+             migrations, security), or \"none\".\n\
+             Review: the files a human should read closely, one bullet each with why.\n\
+             Stay under 250 words.\n\n",
+        );
+        prompt.push_str(brief);
+        prompt.push_str("\n\n# Diff\n");
+        prompt.push_str(&diff_excerpt(diff, CHANGE_SUMMARY_CHARS));
+        prompt
+    }
+}
+
+/// The first `limit` bytes of a diff for a prompt, noting how much was cut
+pub fn diff_excerpt(diff: &str, limit: usize) -> String {
+    let end = (0..=limit.min(diff.len())).rev()
+        .find(|i| diff.is_char_boundary(*i))
+        .unwrap_or(0);
+    if end < diff.len() {
+        format!("{}\n[diff cut: {} more bytes not shown]", &diff[..end], diff.len() - end)
+    } else {
+        diff.to_string()
+    }
+}
+
+/// How far the user has read a task's activity and diff
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReadState {
+    /// Activity entries up to this time have been seen
+    pub activity_seen_at: Option<DateTime<Utc>>,
+    /// The diff as of this time has been seen
+    pub diff_seen_at: Option<DateTime<Utc>>,
 }
 
 impl Task {
@@ -1629,6 +2947,7 @@ impl Task {
             spec: None,
             status: TaskStatus::Planned,
             images: Vec::new(),
+            attachments: Vec::new(),
             claude_session_id: None,
             created_at: Utc::now(),
             started_at: None,
@@ -1643,15 +2962,18 @@ impl Task {
             sdk_command_count: 0,
             cli_opened_at_command_count: 0,
             cli_opened_at: None,
-            pending_feedback: None,
+            queued_feedback: Vec::new(),
             feedback_history: Vec::new(),
+            activity_notes: Vec::new(),
             // Queueing
             queued_for_session: None,
+            stacked_on: None,
             // Activity tracking
             accepting_started_at: None,
             last_activity_at: None,
             last_tool_name: None,
             activity_log: Vec::new(),
+            scrollback_captures: Vec::new(),
             // Git status cache
             git_additions: 0,
             git_deletions: 0,
@@ -1675,9 +2997,180 @@ impl Task {
             total_cache_read_tokens: 0,
             total_cache_creation_tokens: 0,
             total_cost_usd: 0.0,
+            context_tokens: 0,
+            context_compactions: 0,
+            context_restart_offered: false,
+            restarting_session: false,
+            logged_status: None,
+            live_output: String::new(),
             // Time tracking
             review_started_at: None,
+            status_entered_at: None,
+            sla_notified: false,
+            time_entries: Vec::new(),
+            merged_at: None,
+            sparse_paths: None,
+            restricted_agent: false,
+            model: None,
+            base_ref: None,
+            fresh_worktree: false,
+            start_failure: None,
+            ports: None,
+            preview_url: None,
+            preview_deploying: false,
+            env: std::collections::BTreeMap::new(),
+            auto_merge: false,
+            ci_status: None,
+            review_comments: Vec::new(),
+            change_summary: None,
+            summarizing_changes: false,
+            merge_message: None,
+            composing_commit: false,
+            chat_thread: None,
+            read_state: ReadState::default(),
+            diff_changed_at: None,
+        }
+    }
+
+    /// Store freshly computed git status (diff stats, ahead/behind)
+    pub fn set_git_status(&mut self, status: &crate::worktree::git::WorktreeGitStatus) {
+        // The first refresh after startup only fills the cache, it isn't a change
+        let changed = (self.git_additions, self.git_deletions, self.git_files_changed, self.git_commits_ahead)
+            != (status.additions, status.deletions, status.files_changed, status.commits_ahead);
+        if changed && self.git_status_updated_at.is_some() {
+            self.diff_changed_at = Some(Utc::now());
+        }
+        self.git_additions = status.additions;
+        self.git_deletions = status.deletions;
+        self.git_files_changed = status.files_changed;
+        self.git_commits_ahead = status.commits_ahead;
+        self.git_commits_behind = status.commits_behind;
+        self.git_status_updated_at = Some(Utc::now());
+    }
+
+    /// Activity entries logged since the user last looked at the Activity tab
+    pub fn unseen_activity_count(&self) -> usize {
+        match self.read_state.activity_seen_at {
+            Some(seen) => self.activity_log.iter().filter(|e| e.timestamp > seen).count(),
+            None => self.activity_log.len(),
+        }
+    }
+
+    /// Whether the diff changed since the user last looked at the Git tab
+    pub fn has_unseen_diff(&self) -> bool {
+        match (self.diff_changed_at, self.read_state.diff_seen_at) {
+            (Some(changed), Some(seen)) => changed > seen,
+            (Some(_), None) => true,
+            (None, _) => false,
+        }
+    }
+
+    /// Anything new to look at (activity or diff)
+    pub fn has_unseen_content(&self) -> bool {
+        self.unseen_activity_count() > 0 || self.has_unseen_diff()
+    }
+
+    /// Labels attached to this task, written as `#label` tokens in the title.
+    /// Lowercased and deduplicated; purely numeric tokens (issue refs like `#42`) are ignored.
+    pub fn labels(&self) -> Vec<String> {
+        let mut labels: Vec<String> = Vec::new();
+        for word in self.title.split_whitespace() {
+            let Some(tag) = word.strip_prefix('#') else {
+                continue;
+            };
+            let tag: String = tag
+                .chars()
+                .take_while(|c| c.is_alphanumeric() || *c == '-' || *c == '_')
+                .collect::<String>()
+                .to_lowercase();
+            if tag.is_empty() || tag.chars().all(|c| c.is_ascii_digit()) {
+                continue;
+            }
+            if !labels.contains(&tag) {
+                labels.push(tag);
+            }
+        }
+        labels
+    }
+
+    /// The group a task falls in when grouping by tag: its first label
+    pub fn tag_group(&self) -> Option<String> {
+        self.labels().into_iter().next()
+    }
+
+    /// Priority from a `#p0`..`#p9` label (0 = most urgent); the most urgent wins if several
+    pub fn priority(&self) -> Option<u8> {
+        self.labels().iter()
+            .filter_map(|label| label.strip_prefix('p')?.parse::<u8>().ok())
+            .filter(|p| *p <= 9)
+            .min()
+    }
+
+    /// Add a `#label` to the title. False if it's not a valid label or already there.
+    pub fn add_label(&mut self, label: &str) -> bool {
+        let label = label.trim().trim_start_matches('#').to_lowercase();
+        let valid = !label.is_empty()
+            && label.chars().all(|c| c.is_alphanumeric() || c == '-' || c == '_')
+            && !label.chars().all(|c| c.is_ascii_digit());
+        if !valid || self.labels().contains(&label) {
+            return false;
+        }
+        self.title = format!("{} #{}", self.title.trim_end(), label);
+        true
+    }
+
+    /// The task's URLs with what they are, for the link picker: its pull
+    /// request, preview deploy and CI run, the links written in its title and
+    /// description, then its dev server. Each URL appears once.
+    pub fn links(&self) -> Vec<(String, String)> {
+        let mut candidates = Vec::new();
+        if let Some(pr) = self.ci_status.as_ref().and_then(|ci| ci.pr.as_ref()) {
+            candidates.push((format!("Pull request #{}", pr.number), pr.url.clone()));
+        }
+        if let Some(url) = &self.preview_url {
+            candidates.push(("Preview deploy".to_string(), url.clone()));
+        }
+        if let Some(url) = self.ci_status.as_ref().and_then(|ci| ci.run_url.clone()) {
+            candidates.push(("CI run".to_string(), url));
+        }
+        for url in crate::links::find_urls(&self.title).into_iter().chain(crate::links::find_urls(&self.description)) {
+            candidates.push((crate::links::kind(url).to_string(), url.to_string()));
+        }
+        if let Some(port) = self.ports {
+            candidates.push(("Dev server".to_string(), crate::ports::url(port)));
+        }
+
+        let mut links: Vec<(String, String)> = Vec::new();
+        for (label, url) in candidates {
+            if !url.is_empty() && !links.iter().any(|(_, seen)| *seen == url) {
+                links.push((label, url));
+            }
         }
+        links
+    }
+
+    /// Review comments that haven't gone to Claude yet
+    pub fn unsent_review_comments(&self) -> impl Iterator<Item = &ReviewComment> {
+        self.review_comments.iter().filter(|c| c.sent_at.is_none())
+    }
+
+    /// Feedback for Claude with each unsent review comment on its own line
+    /// ("in src/foo.rs line 120: ..."), or None if there are none
+    pub fn review_feedback(&self) -> Option<String> {
+        let items: Vec<String> = self.unsent_review_comments()
+            .map(|c| format!("- in {}: {}", c.anchor(), c.text))
+            .collect();
+        if items.is_empty() {
+            return None;
+        }
+        Some(format!("Please address these review comments on your changes:\n{}", items.join("\n")))
+    }
+
+    /// Whether CI is polled for the task's branch: it's waiting to
+    /// auto-merge, or its worktree is live and the branch may be pushed
+    pub fn watches_ci(&self) -> bool {
+        (self.auto_merge && self.status == TaskStatus::Review)
+            || (self.worktree_path.is_some() && !matches!(self.status, TaskStatus::Planned | TaskStatus::Done))
     }
 
     /// Check if this task has an active worktree session
@@ -1694,6 +3187,85 @@ impl Task {
         }
     }
 
+    /// Phases the task has been through, from its recorded timestamps, as
+    /// (phase, start, end). The current phase runs until `now`; empty until the task starts.
+    pub fn timeline(&self, now: DateTime<Utc>) -> Vec<(TaskStatus, DateTime<Utc>, DateTime<Utc>)> {
+        let Some(started) = self.started_at.filter(|_| self.status != TaskStatus::Planned) else {
+            return Vec::new();
+        };
+        let end = self.completed_at.unwrap_or(now);
+        let mut phases = vec![
+            (TaskStatus::Planned, self.created_at, started),
+            (TaskStatus::InProgress, started, self.review_started_at.unwrap_or(end)),
+        ];
+        if let Some(review_started) = self.review_started_at {
+            phases.push((TaskStatus::Review, review_started, end));
+        }
+        // Clock skew or edited state shouldn't produce negative segments
+        for (_, start, end) in &mut phases {
+            *end = (*end).max(*start);
+        }
+        phases
+    }
+
+    /// Prompt section listing attached files, empty when there are none
+    pub fn attachments_prompt(&self) -> String {
+        if self.attachments.is_empty() {
+            return String::new();
+        }
+        let list: Vec<String> = self.attachments.iter()
+            .map(|p| format!("- {}", p.display()))
+            .collect();
+        format!("\n\n# Attached files\n{}", list.join("\n"))
+    }
+
+    /// Prompt that starts an SDK session on this task
+    pub fn session_prompt(&self) -> String {
+        let prompt = if let Some(ref spec) = self.spec {
+            format!("# Task\n{}\n\n# Spec\n{}", self.title, spec)
+        } else {
+            self.title.clone()
+        };
+        prompt + &self.attachments_prompt()
+    }
+
+    /// One-shot prompt asking for a summary of the session's progress, so a
+    /// fresh session can pick the task up without the old conversation
+    pub fn restart_summary_prompt(&self) -> String {
+        let mut prompt = String::from(
+            "A coding agent has been working on the task below and its conversation is \
+             about to be replaced by a fresh session. Summarize its progress so the new \
+             session can continue: what is done, which files were touched, decisions \
+             made, what remains and any open problems. Be concrete and stay under 400 words.\n\n"
+        );
+        prompt.push_str(&self.session_prompt());
+        if !self.feedback_history.is_empty() {
+            prompt.push_str("\n\n# Feedback from the user");
+            for entry in &self.feedback_history {
+                prompt.push_str(&format!("\n- {}", entry.content));
+            }
+        }
+        let timeline = self.activity_timeline();
+        if !timeline.is_empty() {
+            prompt.push_str("\n\n# Recent activity");
+            for entry in timeline {
+                let who = if entry.note { "Note from the user: " } else { "" };
+                prompt.push_str(&format!("\n- {} {}{}", entry.timestamp.format("%H:%M"), who, entry.message));
+            }
+        }
+        if let Some(output) = self.activity_log.iter().rev().find_map(|e| e.full_output.as_deref()) {
+            let start = output.len().saturating_sub(SUMMARY_OUTPUT_CHARS);
+            let start = (start..output.len()).find(|i| output.is_char_boundary(*i)).unwrap_or(output.len());
+            prompt.push_str(&format!("\n\n# Latest output from the agent\n{}", &output[start..]));
+        }
+        prompt
+    }
+
+    /// Share of the model's context window the session is using (0.0 when unknown)
+    pub fn context_usage(&self) -> f64 {
+        self.context_tokens as f64 / CONTEXT_WINDOW_TOKENS as f64
+    }
+
     /// Add token usage from a session to this task's totals
     pub fn add_token_usage(&mut self, input: u64, output: u64, cache_read: u64, cache_creation: u64, cost: f64) {
         self.total_input_tokens += input;
@@ -1703,6 +3275,24 @@ impl Task {
         self.total_cost_usd += cost;
     }
 
+    /// Fill a feedback snippet's placeholders ({title}, {id}, {branch}) from this task
+    pub fn fill_snippet(&self, snippet: &str) -> String {
+        snippet
+            .replace("{title}", self.short_title.as_deref().unwrap_or(&self.title))
+            .replace("{id}", &self.display_id())
+            .replace("{branch}", self.git_branch.as_deref().unwrap_or("the branch"))
+    }
+
+    /// Whether Claude is mid-turn (SDK or CLI), so feedback sent now would
+    /// interrupt it rather than be read when it's ready
+    pub fn is_busy(&self) -> bool {
+        match self.session_mode {
+            SessionMode::CliActivelyWorking => true,
+            SessionMode::CliInteractive | SessionMode::WaitingForCliExit => false,
+            _ => self.session_state == ClaudeSessionState::Working,
+        }
+    }
+
     /// Get a short display ID for the task.
     /// Format: "{4-char-abbrev}-{3-char-suffix}" (e.g., "TSKB-a7x")
     /// Falls back to first 4 chars of UUID if no abbreviation is set.
@@ -1725,27 +3315,154 @@ impl Task {
         hex_chars.iter().rev().take(3).rev().collect()
     }
 
-    /// Add an entry to the activity log (keeps last 30 entries)
+    /// Add an entry to the activity log (trimmed by the retention policy)
     pub fn log_activity(&mut self, message: impl Into<String>) {
-        const MAX_LOG_ENTRIES: usize = 30;
         self.activity_log.push(ActivityLogEntry::new(message));
-        if self.activity_log.len() > MAX_LOG_ENTRIES {
-            self.activity_log.remove(0);
-        }
+        self.trim_activity_log();
+    }
+
+    /// Remember why a start failed, counting it against any earlier failed attempts
+    pub fn record_start_failure(&mut self, phase: StartPhase, error: String, output: Option<String>) {
+        let attempts = self.start_failure.as_ref().map_or(0, |f| f.attempts) + 1;
+        self.log_activity(format!("Start failed ({}): {}", phase.label(), error));
+        self.start_failure = Some(StartFailure {
+            phase,
+            error,
+            output,
+            failed_at: Utc::now(),
+            attempts,
+        });
     }
 
-    /// Add an entry to the activity log with full output (keeps last 30 entries)
+    /// Add an entry to the activity log with full output, spilling the output to disk
     pub fn log_activity_with_output(&mut self, message: impl Into<String>, full_output: Option<String>) {
-        const MAX_LOG_ENTRIES: usize = 30;
-        self.activity_log.push(ActivityLogEntry::with_output(message, full_output));
-        if self.activity_log.len() > MAX_LOG_ENTRIES {
-            self.activity_log.remove(0);
+        self.log_event(ActivityKind::Event, message, full_output);
+    }
+
+    /// Append streamed session output, dropping the oldest text past `LIVE_OUTPUT_LIMIT`
+    pub fn push_live_output(&mut self, output: &str) {
+        self.live_output.push_str(output);
+        if self.live_output.len() > LIVE_OUTPUT_LIMIT {
+            let mut cut = self.live_output.len() - LIVE_OUTPUT_LIMIT;
+            while !self.live_output.is_char_boundary(cut) {
+                cut += 1;
+            }
+            // Start at a line boundary so the first line isn't a fragment
+            if let Some(newline) = self.live_output[cut..].find('\n') {
+                cut += newline + 1;
+            }
+            self.live_output.drain(..cut);
+        }
+    }
+
+    /// Output for the live output pane: what streamed since startup, or else
+    /// the last output saved in the activity log
+    pub fn live_output_text(&self) -> &str {
+        if !self.live_output.is_empty() {
+            return &self.live_output;
+        }
+        self.activity_log.iter().rev()
+            .find_map(|e| e.full_output.as_deref())
+            .unwrap_or("")
+    }
+
+    /// Add a structured session event (tool call, output, status change) to the activity log
+    pub fn log_event(&mut self, kind: ActivityKind, message: impl Into<String>, full_output: Option<String>) {
+        let mut entry = ActivityLogEntry::with_output(message, full_output);
+        entry.kind = kind;
+        entry.output_file = entry.full_output.as_deref()
+            .and_then(|output| activity::spill_output(self.id, entry.timestamp, output));
+        self.activity_log.push(entry);
+        self.trim_activity_log();
+    }
+
+    /// Drop the oldest entries beyond the retention policy, with their spill files
+    fn trim_activity_log(&mut self) {
+        let (max_entries, max_bytes) = activity::retention();
+        let excess = activity::excess(&self.activity_log, max_entries, max_bytes);
+        for entry in self.activity_log.drain(..excess) {
+            if let Some(ref path) = entry.output_file {
+                activity::remove_spill(path);
+            }
+        }
+    }
+
+    /// Read spilled outputs back into the log after loading
+    pub fn load_activity_outputs(&mut self) {
+        for entry in &mut self.activity_log {
+            if entry.full_output.is_none() {
+                entry.full_output = entry.output_file.as_ref()
+                    .and_then(|path| std::fs::read_to_string(path).ok());
+            }
         }
     }
 
     /// Clear the activity log (e.g., when starting a new accept/update)
     pub fn clear_activity_log(&mut self) {
-        self.activity_log.clear();
+        for entry in self.activity_log.drain(..) {
+            if let Some(ref path) = entry.output_file {
+                activity::remove_spill(path);
+            }
+        }
+    }
+
+    /// Activity log and manual notes, oldest first
+    pub fn activity_timeline(&self) -> Vec<&ActivityLogEntry> {
+        let mut entries: Vec<&ActivityLogEntry> = self.activity_log.iter()
+            .chain(&self.activity_notes)
+            .collect();
+        entries.sort_by_key(|e| e.timestamp);
+        entries
+    }
+
+    /// Log a status change if the status moved since it was last recorded, and
+    /// return it as (from, to). The first status seen after loading is taken as
+    /// the starting point.
+    pub fn record_status_change(&mut self) -> Option<(TaskStatus, TaskStatus)> {
+        let mut change = None;
+        match self.logged_status {
+            Some(logged) if logged != self.status => {
+                let message = format!("{} → {}", logged.label(), self.status.label());
+                self.log_event(ActivityKind::StatusChange, message, None);
+                // A session working on the task counts as tracked time
+                if matches!(logged, TaskStatus::InProgress | TaskStatus::Testing) {
+                    let worked = self.time_in_status(Utc::now()).map_or(0, |d| d.num_seconds());
+                    if worked >= 60 {
+                        self.time_entries.push(time_tracking::TimeEntry::ending_now(
+                            worked,
+                            time_tracking::TimeSource::Session,
+                            None,
+                        ));
+                    }
+                }
+                self.status_entered_at = Some(Utc::now());
+                self.sla_notified = false;
+                change = Some((logged, self.status));
+            }
+            Some(_) => return None,
+            None => {
+                // Boards saved before column times were tracked: best guess
+                if self.status_entered_at.is_none() {
+                    self.status_entered_at = Some(match self.status {
+                        TaskStatus::Review => self.review_started_at,
+                        TaskStatus::InProgress => self.started_at,
+                        _ => None,
+                    }.unwrap_or(self.created_at));
+                }
+            }
+        }
+        self.logged_status = Some(self.status);
+        change
+    }
+
+    /// Total time tracked against the task, in seconds
+    pub fn tracked_secs(&self) -> i64 {
+        time_tracking::total_secs(&self.time_entries, None)
+    }
+
+    /// How long the task has been in its current column
+    pub fn time_in_status(&self, now: DateTime<Utc>) -> Option<chrono::Duration> {
+        self.status_entered_at.map(|at| now.signed_duration_since(at))
     }
 
     /// Add feedback to the history (persisted)
@@ -1833,6 +3550,38 @@ impl TaskStatus {
     }
 }
 
+/// What a task moving between columns means for webhooks, plugins and rules
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LifecycleEvent {
+    /// Planned to In Progress
+    Started,
+    /// Into Needs Work: Claude is waiting for input
+    NeedsInput,
+    /// Into Review: Claude finished
+    Review,
+    /// Into Done
+    Merged,
+    /// Any other move to another column
+    Moved,
+}
+
+impl LifecycleEvent {
+    /// The event a task moving from `from` to `to` stands for. None for moves
+    /// within a column (Review to Updating and back, a failed or cancelled merge).
+    pub fn from_transition(from: TaskStatus, to: TaskStatus) -> Option<Self> {
+        if from.index() == to.index() {
+            return None;
+        }
+        Some(match to {
+            TaskStatus::InProgress if from == TaskStatus::Planned => LifecycleEvent::Started,
+            TaskStatus::NeedsWork => LifecycleEvent::NeedsInput,
+            TaskStatus::Review | TaskStatus::Accepting | TaskStatus::Updating | TaskStatus::Applying => LifecycleEvent::Review,
+            TaskStatus::Done => LifecycleEvent::Merged,
+            _ => LifecycleEvent::Moved,
+        })
+    }
+}
+
 /// UI state (not persisted)
 pub struct UiState {
     pub focus: FocusArea,
@@ -1849,6 +3598,14 @@ pub struct UiState {
     pub help_scroll_offset: usize,
     /// If true, show the project statistics modal
     pub show_stats: bool,
+    /// If true, show the diagnostics modal (problems, or slow operations)
+    pub show_diagnostics: bool,
+    /// Scroll offset for the diagnostics modal
+    pub diagnostics_scroll_offset: usize,
+    /// The diagnostics modal shows slow operations instead of problems (Tab)
+    pub diagnostics_slow_ops: bool,
+    /// Debug log viewer (`), if open
+    pub log_viewer: Option<LogViewerState>,
     pub pending_confirmation: Option<PendingConfirmation>,
     /// Scroll offset for confirmation modal (when content is large)
     pub confirmation_scroll_offset: usize,
@@ -1863,6 +3620,8 @@ pub struct UiState {
     pub title_scroll_delay: usize,
     /// Pending images to attach to next created task
     pub pending_images: Vec<PathBuf>,
+    /// Pending files (other than images) to attach to next created task
+    pub pending_attachments: Vec<PathBuf>,
     /// Animation frame counter for spinners
     pub animation_frame: usize,
     /// Last scroll position (visual index) for each column, preserved when leaving
@@ -1899,11 +3658,15 @@ pub struct UiState {
     pub directory_browser: Option<DirectoryBrowser>,
     /// If Some, we're in create folder mode with the current input text
     pub create_folder_input: Option<String>,
+    /// Recent list and path typing in the open-project dialog
+    pub quick_open: QuickOpen,
 
     // Feedback mode
     /// If set, we're entering feedback for this task (task must be in Review status)
     /// The input area will be used to capture feedback text
     pub feedback_task_id: Option<Uuid>,
+    /// If true, the input is feedback for every InProgress task of the project
+    pub broadcast_feedback: bool,
 
     // Note-adding mode
     /// If set, we're adding a note to this task
@@ -1951,8 +3714,12 @@ pub struct UiState {
     // Git diff view in task detail modal
     /// Scroll offset for the git diff view (lines scrolled from top)
     pub git_diff_scroll_offset: usize,
-    /// Cached git diff content for the currently viewed task
-    pub git_diff_cache: Option<(Uuid, String)>,
+    /// Recently viewed task diffs (LRU, keyed by task and branch head)
+    pub git_diff_cache: crate::worktree::diff_cache::DiffCache,
+    /// The Git tab shows the History view instead of the diff
+    pub git_history_view: bool,
+    /// Main's history of the files the viewed task changed (None while loading)
+    pub git_history: Option<(Uuid, Option<Result<crate::worktree::history::TaskHistory, String>>)>,
 
     // Spec tab scrolling
     /// Scroll offset for the spec tab (lines scrolled from top)
@@ -1992,6 +3759,26 @@ pub struct UiState {
     // Sidecar control modal
     /// If set, the sidecar control modal is open with its state
     pub sidecar_modal: Option<SidecarModalState>,
+    /// Health checks and restart backoff of the sidecar process
+    pub sidecar_health: SidecarHealth,
+    /// When pushed task branches were last polled for CI
+    pub ci_polled_at: Option<std::time::Instant>,
+    /// When the board was last checked for tasks past their column's time limit
+    pub sla_checked_at: Option<std::time::Instant>,
+    /// When the input was last compared with the saved draft
+    pub draft_checked_at: Option<std::time::Instant>,
+    /// The draft as last written to disk
+    pub saved_draft: String,
+    /// Submission shown in the input while stepping through history (0 = newest)
+    pub input_history_cursor: Option<usize>,
+    /// Show markdown rendered: beside the input, and for descriptions and specs
+    pub markdown_preview: bool,
+    /// Whether a CI poll is running
+    pub ci_polling: bool,
+    /// When the chat threads of waiting tasks were last checked for replies
+    pub chat_polled_at: Option<std::time::Instant>,
+    /// Whether a chat poll is running
+    pub chat_polling: bool,
 
     // Build check animation
     /// If true, a build/type check is in progress (show animation in status bar)
@@ -2004,6 +3791,506 @@ pub struct UiState {
     // Markdown file picker (Ctrl+O in new task input)
     /// If set, the markdown file picker is open
     pub md_file_picker: Option<MdFilePickerState>,
+
+    // Feedback snippet picker (Ctrl+T in feedback mode)
+    /// Highlighted snippet, if the picker is open
+    pub snippet_picker: Option<usize>,
+
+    // Link picker (U on the board and in the task detail modal)
+    /// Task whose links are listed and the highlighted one, if the picker is open
+    pub link_picker: Option<(Uuid, usize)>,
+
+    // Watcher feed (Z on the board)
+    /// Highlighted entry counted from the newest, if the feed is open
+    pub watcher_feed: Option<usize>,
+
+    // Start failure details (C on a task whose start failed)
+    /// The task shown and the scroll offset
+    pub start_failure: Option<(Uuid, u16)>,
+
+    // Commit message editor (before a merge, with Commit Messages set to Ask)
+    pub commit_editor: Option<CommitEditorState>,
+
+    // Quick diff popup (D on the board)
+    /// Task whose diff stat and first hunks are shown, if the popup is open
+    pub quick_diff: Option<Uuid>,
+
+    /// Integrations found missing at startup (drives the inline banners)
+    pub capabilities: crate::capabilities::Capabilities,
+    /// Projects whose worktrees' git status is being computed for the first
+    /// time (their cards show a spinner in place of the sync indicator)
+    pub probing_git: std::collections::HashSet<PathBuf>,
+    /// Layout the board was last drawn in (never `Auto`); arrow keys and
+    /// clicks follow it
+    pub board_layout: BoardLayout,
+    /// If true, show the integrations panel (what's missing and how to fix it)
+    pub show_integrations: bool,
+
+    // Dependency graph modal
+    /// If true, show the dependency graph modal (queue/stack edges between tasks)
+    pub show_dependency_graph: bool,
+    /// Selected row in the dependency graph
+    pub dependency_graph_selected_idx: usize,
+
+    // Release notes modal
+    /// If set, the release notes modal is open
+    pub release_notes: Option<ReleaseNotesState>,
+
+    /// If set, the "what's new" screen is open
+    pub whats_new: Option<WhatsNewState>,
+
+    // Sparse-checkout prompt
+    /// If set, asking which paths to check out before starting a task
+    pub sparse_prompt: Option<SparsePromptState>,
+    /// If set, typing a note for the Activity tab of the previewed task
+    pub activity_note: Option<ActivityNoteState>,
+    /// Comment being written on a diff line in the Git tab (c)
+    pub diff_comment: Option<DiffCommentState>,
+    /// Time entry being added or corrected in the task preview
+    pub time_entry_input: Option<TimeEntryInputState>,
+    /// Selected item in the Queue tab of the task preview
+    pub queue_selected: usize,
+    /// If set, rewriting a queued feedback message in the Queue tab
+    pub queue_edit: Option<QueueEditState>,
+    /// If set, the session replay modal is stepping through this task's activity
+    pub session_replay: Option<SessionReplayState>,
+    /// Pager showing a captured session scrollback
+    pub scrollback_viewer: Option<ScrollbackViewerState>,
+    /// If set, the live output pane is shown next to the board
+    pub output_pane: Option<OutputPaneState>,
+    /// Tiles and captures of the monitor screen (shown while focus is Monitor)
+    pub monitor: MonitorState,
+
+    // Code search modal
+    /// If set, the code search modal is open
+    pub code_search: Option<CodeSearchState>,
+
+    // TODO scanner modal
+    /// If set, the TODO/FIXME scanner modal is open
+    pub todo_scan: Option<TodoScanState>,
+
+    /// Errors waiting to be offered as bug tasks (shown one confirmation at a time)
+    pub error_offers: Vec<PendingAction>,
+
+    /// Running pomodoro timer (None = stopped)
+    pub focus_timer: Option<FocusTimer>,
+    /// Focus session being set up before its timer starts (B)
+    pub focus_picker: Option<FocusPicker>,
+    /// Tasks whose start was held back during a focus block (started on the next break)
+    pub deferred_starts: Vec<Uuid>,
+
+    /// Read state of the task open in the detail modal, as it was before this view
+    /// (so "new" markers stay visible while the modal is open)
+    pub read_marker: Option<(Uuid, ReadState)>,
+
+    /// Card being dragged with the mouse
+    pub card_drag: Option<CardDrag>,
+    /// Project tab being dragged to a new position (index into projects)
+    pub project_tab_drag: Option<usize>,
+    /// Project picker listing every open project, including tabs scrolled out of view
+    pub project_picker: Option<ProjectPicker>,
+    /// Workspace picker (Ctrl-O)
+    pub workspace_picker: Option<WorkspacePicker>,
+}
+
+/// A card held with the mouse, from button press until release
+#[derive(Debug, Clone)]
+pub struct CardDrag {
+    pub task_id: Uuid,
+    /// Column the card was picked up from
+    pub from: TaskStatus,
+    /// Column under the pointer (None when outside the board)
+    pub over: Option<TaskStatus>,
+    /// Pointer position, where the ghost card is drawn
+    pub x: u16,
+    pub y: u16,
+    /// The pointer moved since the press (a press and release in place is a click)
+    pub moved: bool,
+    /// Auto-scroll of the hovered column: -1 up, 1 down, 0 none
+    pub scroll: i8,
+    /// Visible task rows in the hovered column
+    pub over_height: u16,
+}
+
+/// Phase of the pomodoro-style focus timer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FocusPhase {
+    Focus,
+    Break,
+}
+
+/// Pomodoro-style focus timer shown in the status bar
+#[derive(Debug, Clone)]
+pub struct FocusTimer {
+    pub phase: FocusPhase,
+    pub phase_started_at: DateTime<Utc>,
+    /// Focus blocks finished since the timer was started
+    pub completed_blocks: u32,
+    /// Task the session is for: only its notifications get through during
+    /// focus blocks, and the focused time is added to it
+    pub task_id: Option<Uuid>,
+    /// Length of a focus block
+    pub focus_minutes: u32,
+}
+
+impl FocusTimer {
+    pub fn start(task_id: Option<Uuid>, focus_minutes: u32) -> Self {
+        Self {
+            phase: FocusPhase::Focus,
+            phase_started_at: Utc::now(),
+            completed_blocks: 0,
+            task_id,
+            focus_minutes,
+        }
+    }
+
+    /// Length of the current phase in seconds
+    pub fn phase_secs(&self, settings: &GlobalSettings) -> i64 {
+        let minutes = match self.phase {
+            FocusPhase::Focus => self.focus_minutes,
+            FocusPhase::Break => settings.break_minutes,
+        };
+        i64::from(minutes.max(1)) * 60
+    }
+
+    /// Seconds spent in the current focus block so far (0 during a break)
+    pub fn focused_secs(&self, settings: &GlobalSettings) -> i64 {
+        match self.phase {
+            FocusPhase::Focus => (Utc::now() - self.phase_started_at).num_seconds().clamp(0, self.phase_secs(settings)),
+            FocusPhase::Break => 0,
+        }
+    }
+
+    /// Seconds left in the current phase (negative once it's over)
+    pub fn remaining_secs(&self, settings: &GlobalSettings) -> i64 {
+        self.phase_secs(settings) - (Utc::now() - self.phase_started_at).num_seconds()
+    }
+
+    /// Move to the next phase, returning the phase just entered
+    pub fn advance(&mut self) -> FocusPhase {
+        self.phase = match self.phase {
+            FocusPhase::Focus => {
+                self.completed_blocks += 1;
+                FocusPhase::Break
+            }
+            FocusPhase::Break => FocusPhase::Focus,
+        };
+        self.phase_started_at = Utc::now();
+        self.phase
+    }
+
+    /// Whether new task starts should be held back right now
+    pub fn defers_starts(&self, settings: &GlobalSettings) -> bool {
+        self.phase == FocusPhase::Focus && settings.focus_defer_starts
+    }
+}
+
+/// A focus session being set up: which task and how long
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FocusPicker {
+    /// Task to focus on (None = no particular task)
+    pub task_id: Option<Uuid>,
+    /// The task selected on the board when the picker opened
+    pub selected_task_id: Option<Uuid>,
+    pub minutes: u32,
+}
+
+impl FocusPicker {
+    pub const STEP_MINUTES: u32 = 5;
+    pub const MAX_MINUTES: u32 = 180;
+
+    /// Lengthen or shorten the session by `steps` of five minutes
+    pub fn adjust(&mut self, steps: i32) {
+        let minutes = self.minutes as i64 + i64::from(steps) * i64::from(Self::STEP_MINUTES);
+        self.minutes = minutes.clamp(Self::STEP_MINUTES as i64, Self::MAX_MINUTES as i64) as u32;
+    }
+
+    /// Tie the session to the selected task, or to no task
+    pub fn toggle_task(&mut self) {
+        self.task_id = if self.task_id.is_some() { None } else { self.selected_task_id };
+    }
+}
+
+/// Recent projects and typed paths in the open-project dialog
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct QuickOpen {
+    /// Selected entry while the Recent list has focus (None = the browser has focus)
+    pub recent_selected: Option<usize>,
+    /// Path being typed (None = not typing one)
+    pub path_input: Option<String>,
+    /// Directory names the last Tab completion couldn't choose between
+    pub candidates: Vec<String>,
+}
+
+impl QuickOpen {
+    /// Recent entries shown in the dialog (one per number key)
+    pub const MAX_SHOWN: usize = 9;
+}
+
+/// Picker over all open projects, filtered by what's typed
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProjectPicker {
+    pub query: String,
+    /// Index into the filtered list
+    pub selected: usize,
+}
+
+impl ProjectPicker {
+    /// Indices of projects whose name or path contains the query (case-insensitive)
+    pub fn matches(&self, projects: &[Project]) -> Vec<usize> {
+        let query = self.query.to_lowercase();
+        projects.iter()
+            .enumerate()
+            .filter(|(_, p)| {
+                p.name.to_lowercase().contains(&query)
+                    || p.working_dir.to_string_lossy().to_lowercase().contains(&query)
+            })
+            .map(|(idx, _)| idx)
+            .collect()
+    }
+}
+
+/// Workspace picker: saved workspaces, and the name being typed for a new one
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WorkspacePicker {
+    pub selected: usize,
+    /// Name for a new workspace of the open projects (None = not naming)
+    pub naming: Option<String>,
+}
+
+/// State for the TODO/FIXME scanner modal
+#[derive(Debug, Clone, Default)]
+pub struct TodoScanState {
+    /// Scan running in the background
+    pub scanning: bool,
+    /// Items in display order (by file, or oldest first)
+    pub items: Vec<crate::search::todo::TodoItem>,
+    /// Parallel to `items`: picked for task creation
+    pub selected: Vec<bool>,
+    /// Highlighted item
+    pub cursor: usize,
+    /// Order by age (oldest first) instead of grouping by file
+    pub by_age: bool,
+    pub error: Option<String>,
+}
+
+impl TodoScanState {
+    /// Reorder items for the current grouping, keeping selections attached
+    pub fn sort_items(&mut self) {
+        let mut rows: Vec<_> = self.items.drain(..).zip(self.selected.drain(..)).collect();
+        if self.by_age {
+            // Oldest first; uncommitted (undated) items last
+            rows.sort_by_key(|(item, _)| (item.authored_at.is_none(), item.authored_at));
+        } else {
+            rows.sort_by(|(a, _), (b, _)| {
+                a.location.path.cmp(&b.location.path).then(a.location.line.cmp(&b.location.line))
+            });
+        }
+        (self.items, self.selected) = rows.into_iter().unzip();
+        self.cursor = 0;
+    }
+}
+
+/// State for the code search modal (query input, then selectable results)
+#[derive(Debug, Clone, Default)]
+pub struct CodeSearchState {
+    /// Pattern being typed / last searched
+    pub query: String,
+    /// True while typing the query (false once results are shown)
+    pub editing_query: bool,
+    /// Search running in the background
+    pub searching: bool,
+    pub results: Vec<crate::search::SearchMatch>,
+    /// Indices into `results` picked for the new task
+    pub selected: Vec<usize>,
+    /// Highlighted result
+    pub cursor: usize,
+    pub error: Option<String>,
+}
+
+/// State for the per-task sparse-checkout prompt shown when starting a task
+#[derive(Debug, Clone)]
+pub struct SparsePromptState {
+    pub task_id: Uuid,
+    /// Patterns being edited (prefilled with the project profile; empty = full checkout)
+    pub input: String,
+}
+
+/// State for the time entry input in the task preview (t / T)
+#[derive(Debug, Clone)]
+pub struct TimeEntryInputState {
+    pub task_id: Uuid,
+    /// Index of the manual entry being corrected (None = adding a new one)
+    pub editing: Option<usize>,
+    pub input: String,
+}
+
+/// State for the commit message editor shown before a merge
+#[derive(Debug, Clone)]
+pub struct CommitEditorState {
+    pub task_id: Uuid,
+    /// Merge only (task stays in Review) rather than merge and mark done
+    pub merge_only: bool,
+    pub input: String,
+    /// The sidecar is still writing the message
+    pub generating: bool,
+    /// Why the plain message is offered instead (the sidecar failed)
+    pub note: Option<String>,
+}
+
+/// State for the comment input on a diff line in the task preview's Git tab
+#[derive(Debug, Clone)]
+pub struct DiffCommentState {
+    pub task_id: Uuid,
+    pub location: crate::worktree::diff_cache::LineLocation,
+    pub input: String,
+}
+
+/// State for the note input in the task preview's Activity tab
+#[derive(Debug, Clone)]
+pub struct ActivityNoteState {
+    pub task_id: Uuid,
+    pub input: String,
+}
+
+/// State for editing a queued feedback message in the task preview's Queue tab
+#[derive(Debug, Clone)]
+pub struct QueueEditState {
+    pub task_id: Uuid,
+    /// Position of the message in the task's queue
+    pub index: usize,
+    /// The message as it was, to find it again if the queue moved meanwhile
+    pub original: String,
+    pub input: String,
+}
+
+/// State for the session replay modal
+#[derive(Debug, Clone)]
+pub struct SessionReplayState {
+    pub task_id: Uuid,
+    /// Index into the task's activity timeline
+    pub position: usize,
+    /// Scroll offset in the current event's output
+    pub output_scroll: usize,
+}
+
+/// State for the pager showing a task's captured scrollback
+#[derive(Debug, Clone)]
+pub struct ScrollbackViewerState {
+    pub task_id: Uuid,
+    /// The capture file
+    pub path: PathBuf,
+    pub text: String,
+    /// Scroll position and search, as in the live output pane (always held)
+    pub view: OutputPaneState,
+}
+
+impl ScrollbackViewerState {
+    /// Show `text`, starting at its end
+    pub fn new(task_id: Uuid, path: PathBuf, text: String) -> Self {
+        let view = OutputPaneState { paused_at: Some(text.lines().count()), ..Default::default() };
+        Self { task_id, path, text, view }
+    }
+}
+
+/// State for the live output pane (L), which tails the selected in-progress task
+#[derive(Debug, Clone, Default)]
+pub struct OutputPaneState {
+    /// Line count the view is held at while paused (None = following new output)
+    pub paused_at: Option<usize>,
+    /// Lines scrolled up from where the view is held
+    pub scroll_back: usize,
+    /// Search query (case-insensitive; empty = no search)
+    pub search: String,
+    /// Whether the search query is being typed
+    pub search_editing: bool,
+    /// Index into the matching lines of the match last jumped to
+    pub current_match: Option<usize>,
+}
+
+impl OutputPaneState {
+    /// Indices of the lines of `output` containing the search query
+    pub fn matching_lines(&self, output: &str) -> Vec<usize> {
+        if self.search.is_empty() {
+            return Vec::new();
+        }
+        let query = self.search.to_lowercase();
+        output.lines()
+            .enumerate()
+            .filter(|(_, line)| line.to_lowercase().contains(&query))
+            .map(|(idx, _)| idx)
+            .collect()
+    }
+
+    /// Line the view ends at (exclusive), given the current line count
+    pub fn view_end(&self, line_count: usize) -> usize {
+        self.paused_at.unwrap_or(line_count).min(line_count).saturating_sub(self.scroll_back)
+    }
+}
+
+/// State for the tiled monitor of in-progress tasks (FocusArea::Monitor)
+#[derive(Debug, Clone, Default)]
+pub struct MonitorState {
+    /// Index of the highlighted tile
+    pub selected: usize,
+    /// Latest capture of each task's tmux window
+    pub captures: std::collections::HashMap<Uuid, String>,
+    /// When the captures were last refreshed
+    pub refreshed_at: Option<std::time::Instant>,
+    /// Whether a refresh is running
+    pub refreshing: bool,
+}
+
+impl MonitorState {
+    /// How often captures are refreshed while the monitor is shown
+    pub const REFRESH_INTERVAL: std::time::Duration = std::time::Duration::from_secs(2);
+
+    /// Columns of the tile grid for a number of tiles (as square as possible)
+    pub fn columns(tiles: usize) -> usize {
+        let mut columns = 1;
+        while columns * columns < tiles {
+            columns += 1;
+        }
+        columns
+    }
+}
+
+/// State for the "what's new" screen shown after an update
+#[derive(Debug, Clone)]
+pub struct WhatsNewState {
+    /// Features to show with the version they shipped in, newest first
+    pub features: Vec<(&'static str, &'static crate::changelog::Feature)>,
+    /// Highlighted feature
+    pub cursor: usize,
+}
+
+/// State for the release notes modal
+#[derive(Debug, Clone, Default)]
+pub struct ReleaseNotesState {
+    /// Tag or date (YYYY-MM-DD) the notes start from
+    pub since_input: String,
+    /// Compiled Markdown (None while the starting point is still being entered)
+    pub markdown: Option<String>,
+    /// Sidecar-polished version of the notes, if requested
+    pub polished: Option<String>,
+    /// True while waiting for the sidecar to polish the notes
+    pub polishing: bool,
+    /// Show the polished version instead of the raw Markdown
+    pub show_polished: bool,
+    /// Scroll offset for the preview (lines scrolled from top)
+    pub scroll_offset: usize,
+}
+
+impl ReleaseNotesState {
+    /// The notes currently shown in the preview (polished if toggled and available)
+    pub fn current_text(&self) -> Option<&str> {
+        if self.show_polished {
+            if let Some(ref polished) = self.polished {
+                return Some(polished);
+            }
+        }
+        self.markdown.as_deref()
+    }
 }
 
 /// State for the markdown file picker modal
@@ -2017,6 +4304,8 @@ pub struct MdFilePickerState {
     pub filtered_indices: Vec<(usize, i64)>,
     /// Selected index in the filtered list
     pub selected_idx: usize,
+    /// Picking any file to attach (Ctrl+L) rather than a .md file to insert
+    pub attach: bool,
 }
 
 impl MdFilePickerState {
@@ -2028,9 +4317,15 @@ impl MdFilePickerState {
             filter_text: String::new(),
             filtered_indices: all_indices,
             selected_idx: 0,
+            attach: false,
         }
     }
 
+    /// Create a picker over any repository files, for attaching one to a task
+    pub fn for_attachment(files: Vec<PathBuf>) -> Self {
+        Self { attach: true, ..Self::new(files) }
+    }
+
     /// Update the filter text and recompute filtered results with fuzzy matching
     pub fn set_filter(&mut self, filter: String) {
         self.filter_text = filter;
@@ -2215,6 +4510,54 @@ impl SidecarConnectionStatus {
     }
 }
 
+/// Supervision of the sidecar process: the last health check and when the
+/// next check (or restart attempt, backing off while it keeps failing) is due
+#[derive(Debug, Clone)]
+pub struct SidecarHealth {
+    /// Connection state shown in the status bar
+    pub status: SidecarConnectionStatus,
+    /// Failed restart attempts in a row
+    pub failures: u32,
+    /// When the next check is due
+    pub next_check: std::time::Instant,
+    /// Whether a check or restart is running in the background
+    pub in_flight: bool,
+}
+
+impl SidecarHealth {
+    /// How often a healthy sidecar is checked
+    pub const CHECK_INTERVAL: std::time::Duration = std::time::Duration::from_secs(10);
+    /// Longest wait between restart attempts
+    pub const MAX_BACKOFF: std::time::Duration = std::time::Duration::from_secs(60);
+
+    /// Wait before the next restart attempt after `failures` failed ones (1s, 2s, 4s, ...)
+    pub fn backoff(failures: u32) -> std::time::Duration {
+        std::time::Duration::from_secs(1u64 << failures.min(6)).min(Self::MAX_BACKOFF)
+    }
+
+    /// Whether a check is due now
+    pub fn is_due(&self) -> bool {
+        !self.in_flight && std::time::Instant::now() >= self.next_check
+    }
+
+    /// Not connected: check (and restart) right away
+    pub fn mark_down(&mut self) {
+        self.status = SidecarConnectionStatus::NotRunning;
+        self.next_check = std::time::Instant::now();
+    }
+}
+
+impl Default for SidecarHealth {
+    fn default() -> Self {
+        Self {
+            status: SidecarConnectionStatus::Connected,
+            failures: 0,
+            next_check: std::time::Instant::now() + Self::CHECK_INTERVAL,
+            in_flight: false,
+        }
+    }
+}
+
 /// State for the merge celebration "gold dust sweep" animation
 /// Phase 1: Confirmation pulse (green/cyan tint) - frames 0-4
 /// Phase 2: Sparkle substitution right→left - frames 5-N (N depends on text length)
@@ -2317,6 +4660,14 @@ pub struct InteractiveModal {
     pub terminal_buffer: String,
     /// Scroll offset in the terminal output
     pub scroll_offset: usize,
+    /// The task worktree's changes, shown beside the terminal (None until loaded)
+    pub diff: Option<crate::worktree::diff_cache::CachedDiff>,
+    /// When `diff` was last refreshed
+    pub diff_refreshed_at: Option<DateTime<Utc>>,
+    /// Whether a refresh is running (further hook signals wait for it)
+    pub diff_loading: bool,
+    /// Frozen pane being selected from (Ctrl+[), None while keys go to the session
+    pub copy_mode: Option<copy_mode::CopyMode>,
 }
 
 /// Which field is selected in the config modal
@@ -2327,14 +4678,51 @@ pub enum ConfigField {
     VimModeEnabled,
     MascotAdvice,
     MascotAdviceInterval,
+    WatcherColumns,
+    WatcherPersona,
+    WatcherInterval,
+    WatcherReads,
+    NativeGit,
+    StatusClock,
+    FocusMinutes,
+    FocusDeferStarts,
+    SoundCues,
+    QuietHours,
+    SlaThresholds,
+    Webhooks,
+    IdleFps,
+    ActivityRetention,
+    Palette,
+    BoardLayout,
+    SpellCheck,
+    StateSync,
     QaEnabled,
     MaxQaAttempts,
     ApplyStrategy,
+    ContextRestart,
+    CommitMessages,
+    ColumnSort,
+    GroupByTag,
+    WorktreePool,
+    SparsePaths,
+    SharedCaches,
+    StatusIgnore,
+    AutoVersion,
+    VersionRules,
+    ErrorLog,
+    ErrorLogAutoStart,
+    FeedbackSnippets,
+    TranscribeCommand,
+    ChatBridge,
+    SessionLayout,
     CheckCommand,
     RunCommand,
     TestCommand,
     FormatCommand,
     LintCommand,
+    PreStartScript,
+    PostMergeScript,
+    PreviewCommand,
 }
 
 impl ConfigField {
@@ -2345,38 +4733,116 @@ impl ConfigField {
             ConfigField::VimModeEnabled,
             ConfigField::MascotAdvice,
             ConfigField::MascotAdviceInterval,
+            ConfigField::WatcherColumns,
+            ConfigField::WatcherPersona,
+            ConfigField::WatcherInterval,
+            ConfigField::WatcherReads,
+            ConfigField::NativeGit,
+            ConfigField::StatusClock,
+            ConfigField::FocusMinutes,
+            ConfigField::FocusDeferStarts,
+            ConfigField::SoundCues,
+            ConfigField::QuietHours,
+            ConfigField::SlaThresholds,
+            ConfigField::Webhooks,
+            ConfigField::IdleFps,
+            ConfigField::ActivityRetention,
+            ConfigField::Palette,
+            ConfigField::BoardLayout,
+            ConfigField::SpellCheck,
+            ConfigField::StateSync,
             ConfigField::QaEnabled,
             ConfigField::MaxQaAttempts,
             ConfigField::ApplyStrategy,
+            ConfigField::ContextRestart,
+            ConfigField::CommitMessages,
+            ConfigField::ColumnSort,
+            ConfigField::GroupByTag,
+            ConfigField::WorktreePool,
+            ConfigField::SparsePaths,
+            ConfigField::SharedCaches,
+            ConfigField::StatusIgnore,
+            ConfigField::AutoVersion,
+            ConfigField::VersionRules,
+            ConfigField::ErrorLog,
+            ConfigField::ErrorLogAutoStart,
+            ConfigField::FeedbackSnippets,
+            ConfigField::TranscribeCommand,
+            ConfigField::ChatBridge,
+            ConfigField::SessionLayout,
             ConfigField::CheckCommand,
             ConfigField::RunCommand,
             ConfigField::TestCommand,
             ConfigField::FormatCommand,
             ConfigField::LintCommand,
+            ConfigField::PreStartScript,
+            ConfigField::PostMergeScript,
+            ConfigField::PreviewCommand,
         ]
     }
 
-    /// Get visible fields based on mascot advice and QA being enabled
-    pub fn visible_fields(mascot_enabled: bool, qa_enabled: bool) -> Vec<ConfigField> {
+    /// Get visible fields based on mascot advice, QA and auto-versioning being enabled
+    pub fn visible_fields(mascot_enabled: bool, qa_enabled: bool, auto_version: bool) -> Vec<ConfigField> {
         let mut fields = vec![
             ConfigField::DefaultEditor,
             ConfigField::VimModeEnabled,
             ConfigField::MascotAdvice,
         ];
         if mascot_enabled {
-            fields.push(ConfigField::MascotAdviceInterval);
+            fields.extend([
+                ConfigField::MascotAdviceInterval,
+                ConfigField::WatcherColumns,
+                ConfigField::WatcherPersona,
+                ConfigField::WatcherInterval,
+                ConfigField::WatcherReads,
+            ]);
         }
+        fields.push(ConfigField::NativeGit);
+        fields.push(ConfigField::StatusClock);
+        fields.push(ConfigField::FocusMinutes);
+        fields.push(ConfigField::FocusDeferStarts);
+        fields.push(ConfigField::SoundCues);
+        fields.push(ConfigField::QuietHours);
+        fields.push(ConfigField::SlaThresholds);
+        fields.push(ConfigField::Webhooks);
+        fields.push(ConfigField::IdleFps);
+        fields.push(ConfigField::ActivityRetention);
+        fields.push(ConfigField::Palette);
+        fields.push(ConfigField::BoardLayout);
+        fields.push(ConfigField::SpellCheck);
+        fields.push(ConfigField::StateSync);
         fields.push(ConfigField::QaEnabled);
         if qa_enabled {
             fields.push(ConfigField::MaxQaAttempts);
         }
         fields.push(ConfigField::ApplyStrategy);
+        fields.push(ConfigField::ContextRestart);
+        fields.push(ConfigField::CommitMessages);
+        fields.push(ConfigField::ColumnSort);
+        fields.push(ConfigField::GroupByTag);
+        fields.push(ConfigField::WorktreePool);
+        fields.push(ConfigField::SparsePaths);
+        fields.push(ConfigField::SharedCaches);
+        fields.push(ConfigField::StatusIgnore);
+        fields.push(ConfigField::AutoVersion);
+        if auto_version {
+            fields.push(ConfigField::VersionRules);
+        }
+        fields.push(ConfigField::ErrorLog);
+        fields.push(ConfigField::ErrorLogAutoStart);
+        fields.push(ConfigField::FeedbackSnippets);
+        fields.push(ConfigField::TranscribeCommand);
+        fields.push(ConfigField::ChatBridge);
+        fields.push(ConfigField::SessionLayout);
         fields.extend([
             ConfigField::CheckCommand,
             ConfigField::RunCommand,
             ConfigField::TestCommand,
             ConfigField::FormatCommand,
             ConfigField::LintCommand,
+            ConfigField::PreStartScript,
+            ConfigField::PostMergeScript,
+            ConfigField::PreviewCommand,
         ]);
         fields
     }
@@ -2391,6 +4857,7 @@ pub enum TaskDetailTab {
     Notes,
     Git,
     Activity,
+    Queue,
     Help,
 }
 
@@ -2403,6 +4870,7 @@ impl TaskDetailTab {
             TaskDetailTab::Notes,
             TaskDetailTab::Git,
             TaskDetailTab::Activity,
+            TaskDetailTab::Queue,
             TaskDetailTab::Help,
         ]
     }
@@ -2415,6 +4883,7 @@ impl TaskDetailTab {
             TaskDetailTab::Notes => "notes",
             TaskDetailTab::Git => "git",
             TaskDetailTab::Activity => "activity",
+            TaskDetailTab::Queue => "queue",
             TaskDetailTab::Help => "help",
         }
     }
@@ -2426,7 +4895,8 @@ impl TaskDetailTab {
             TaskDetailTab::Spec => TaskDetailTab::Notes,
             TaskDetailTab::Notes => TaskDetailTab::Git,
             TaskDetailTab::Git => TaskDetailTab::Activity,
-            TaskDetailTab::Activity => TaskDetailTab::Help,
+            TaskDetailTab::Activity => TaskDetailTab::Queue,
+            TaskDetailTab::Queue => TaskDetailTab::Help,
             TaskDetailTab::Help => TaskDetailTab::General,
         }
     }
@@ -2439,7 +4909,8 @@ impl TaskDetailTab {
             TaskDetailTab::Notes => TaskDetailTab::Spec,
             TaskDetailTab::Git => TaskDetailTab::Notes,
             TaskDetailTab::Activity => TaskDetailTab::Git,
-            TaskDetailTab::Help => TaskDetailTab::Activity,
+            TaskDetailTab::Queue => TaskDetailTab::Activity,
+            TaskDetailTab::Help => TaskDetailTab::Queue,
         }
     }
 }
@@ -2452,14 +4923,51 @@ impl ConfigField {
             ConfigField::VimModeEnabled => "Vim Mode",
             ConfigField::MascotAdvice => "Mascot Advice",
             ConfigField::MascotAdviceInterval => "  Advice Interval",
+            ConfigField::WatcherColumns => "  Watched Columns",
+            ConfigField::WatcherPersona => "  Watcher Persona",
+            ConfigField::WatcherInterval => "  Project Interval",
+            ConfigField::WatcherReads => "  Watcher Reads",
+            ConfigField::NativeGit => "Native Git Backend",
+            ConfigField::StatusClock => "Status Bar Clock",
+            ConfigField::FocusMinutes => "Focus/Break Minutes",
+            ConfigField::FocusDeferStarts => "  Defer Starts in Focus",
+            ConfigField::SoundCues => "Sound Cues",
+            ConfigField::QuietHours => "Quiet Hours",
+            ConfigField::SlaThresholds => "Column Time Limits",
+            ConfigField::Webhooks => "Webhooks",
+            ConfigField::IdleFps => "Idle Redraw Rate",
+            ConfigField::ActivityRetention => "Activity Log Limit",
+            ConfigField::Palette => "Color Palette",
+            ConfigField::BoardLayout => "Board Layout",
+            ConfigField::SpellCheck => "Spell Check",
+            ConfigField::StateSync => "State Sync Remote",
             ConfigField::QaEnabled => "QA Validation",
             ConfigField::MaxQaAttempts => "  Max QA Attempts",
             ConfigField::ApplyStrategy => "Apply Strategy",
+            ConfigField::ContextRestart => "Context Restart",
+            ConfigField::CommitMessages => "Commit Messages",
+            ConfigField::ColumnSort => "Column Sort",
+            ConfigField::GroupByTag => "Group by Tag",
+            ConfigField::WorktreePool => "Worktree Pool",
+            ConfigField::SparsePaths => "Sparse Checkout",
+            ConfigField::SharedCaches => "Shared Caches",
+            ConfigField::StatusIgnore => "Status Ignore",
+            ConfigField::AutoVersion => "Auto Versioning",
+            ConfigField::VersionRules => "  Version Rules",
+            ConfigField::ErrorLog => "Error Log",
+            ConfigField::ErrorLogAutoStart => "  Auto-start Bug Tasks",
+            ConfigField::FeedbackSnippets => "Feedback Snippets",
+            ConfigField::TranscribeCommand => "Transcribe Command",
+            ConfigField::ChatBridge => "Chat Approvals",
+            ConfigField::SessionLayout => "Session Layout",
             ConfigField::CheckCommand => "Check Command",
             ConfigField::RunCommand => "Run Command",
             ConfigField::TestCommand => "Test Command",
             ConfigField::FormatCommand => "Format Command",
             ConfigField::LintCommand => "Lint Command",
+            ConfigField::PreStartScript => "Pre-start Script",
+            ConfigField::PostMergeScript => "Post-merge Script",
+            ConfigField::PreviewCommand => "Preview Command",
         }
     }
 
@@ -2470,32 +4978,72 @@ impl ConfigField {
             ConfigField::VimModeEnabled => "Enable vim keybindings in task input editor",
             ConfigField::MascotAdvice => "Toggle with Ctrl-W (uses Claude tokens)",
             ConfigField::MascotAdviceInterval => "How often mascot gives advice (1-120 minutes)",
+            ConfigField::WatcherColumns => "Columns whose tasks the watcher also looks at in this project, e.g. review, in-progress (empty = main checkout only)",
+            ConfigField::WatcherPersona => "Who the watcher speaks as in this project, e.g. a terse senior Rust reviewer (empty = supportive coding buddy)",
+            ConfigField::WatcherInterval => "Minutes between advice on this project, 1-120 (empty = Advice Interval)",
+            ConfigField::WatcherReads => "Whether observations may read files and diffs, or only metadata",
+            ConfigField::NativeGit => "libgit2 for status/diff (benchmarked on save; merge/rebase still use git)",
+            ConfigField::StatusClock => "Show the time in the status bar",
+            ConfigField::FocusMinutes => "Pomodoro lengths as focus/break, e.g. 25/5 (start/stop with B)",
+            ConfigField::FocusDeferStarts => "Hold new task starts until the next break",
+            ConfigField::SoundCues => "Chime for Review, double beep for input needed, buzz for failed merges (files in sounds/ override)",
+            ConfigField::QuietHours => "No sounds in this window, e.g. 22:00-07:30 (empty = never quiet)",
+            ConfigField::SlaThresholds => "Flag tasks stuck in a column, e.g. in-progress=2h, review=1d (empty = off)",
+            ConfigField::Webhooks => "POST task events: url [on=started,needs-input,completed,merged] [{\"text\": \"{{task}} {{event}}\"}], separated by | (empty = off)",
+            ConfigField::IdleFps => "Redraws per second when nothing is animating (1-10)",
+            ConfigField::ActivityRetention => "Kept per task as entries/KB, e.g. 200/1024; older entries and their output are dropped",
+            ConfigField::Palette => "Colorblind-safe colors, or monochrome with status shown by glyphs and text styles",
+            ConfigField::BoardLayout => "How the six columns are arranged; automatic picks by terminal width",
+            ConfigField::SpellCheck => "Hunspell dictionary for the input, e.g. en_US, de_DE (in ~/.config/kanblam/dictionaries or /usr/share/hunspell; empty = off)",
+            ConfigField::StateSync => "Git remote the board is synced through on branch kanblam-state: pulled at start, pushed on exit (empty = off)",
             ConfigField::QaEnabled => "Auto-validate Claude's work when it stops",
             ConfigField::MaxQaAttempts => "Retries before moving to Needs Work (1-10)",
             ConfigField::ApplyStrategy => "How to test changes after applying to main",
+            ConfigField::ContextRestart => "Start a fresh session with a progress summary at 85% context",
+            ConfigField::CommitMessages => "Write merge commit messages from the task's diff and spec",
+            ConfigField::ColumnSort => "Order of tasks in each column; +/- reorders in Manual",
+            ConfigField::GroupByTag => "Group each column's tasks under their first #label",
+            ConfigField::WorktreePool => "Pre-warmed worktrees reused across tasks for fast starts (0 = off, max 8)",
+            ConfigField::SparsePaths => "Paths for task worktrees, e.g. services/api/** shared/ (empty = full)",
+            ConfigField::SharedCaches => "Ignored dirs new worktrees take from main: symlinked, or hard-linked with :copy, e.g. node_modules .venv target:copy",
+            ConfigField::StatusIgnore => "Paths left out of diff stats and diffs, e.g. dist/ *.lock src/gen/** (gitignore-style)",
+            ConfigField::AutoVersion => "Bump version + tag when merging a #release task (previewed first)",
+            ConfigField::VersionRules => "Labels per bump, e.g. major:breaking,minor:feature|feat (else patch)",
+            ConfigField::ErrorLog => "Log file to watch, or $ command (e.g. $ npm run dev); new errors offer bug tasks",
+            ConfigField::ErrorLogAutoStart => "Start bug tasks immediately with a restricted agent (no blanket Bash)",
+            ConfigField::FeedbackSnippets => "Canned feedback separated by |, may use {title} {id} {branch} (empty = defaults)",
+            ConfigField::TranscribeCommand => "Prints the transcript of attached audio, {file} = the file (e.g. whisper-cli -nt -f {file})",
+            ConfigField::ChatBridge => "Post needs-input prompts, replies become feedback: slack|discord <channel id> <bot token or $ENV_VAR>",
+            ConfigField::SessionLayout => "Interactive session panes, Claude first, *focused, NN% sizes (e.g. claude 60% | *npm run dev | shell)",
             ConfigField::CheckCommand => "e.g. cargo check, npm run build, tsc --noEmit",
             ConfigField::RunCommand => "e.g. cargo run, npm start, python main.py",
             ConfigField::TestCommand => "e.g. cargo test, npm test, pytest",
             ConfigField::FormatCommand => "e.g. cargo fmt, npm run format, black .",
             ConfigField::LintCommand => "e.g. cargo clippy, npm run lint, ruff check .",
+            ConfigField::PreStartScript => "Run in a new worktree before Claude starts, e.g. npm install (failure = task not started)",
+            ConfigField::PostMergeScript => "Run on main after a merge, e.g. cargo fmt && cargo test (failure = task to Needs Work)",
+            ConfigField::PreviewCommand => "Deploys a Review task's worktree (b), e.g. vercel deploy; the last URL printed is the preview",
         }
     }
 
     /// Whether this field is a global setting (vs project-specific)
     pub fn is_global(&self) -> bool {
-        matches!(self, ConfigField::DefaultEditor | ConfigField::VimModeEnabled | ConfigField::MascotAdvice | ConfigField::MascotAdviceInterval)
+        matches!(self, ConfigField::DefaultEditor | ConfigField::VimModeEnabled | ConfigField::MascotAdvice | ConfigField::MascotAdviceInterval | ConfigField::NativeGit
+            | ConfigField::StatusClock | ConfigField::FocusMinutes | ConfigField::FocusDeferStarts | ConfigField::SoundCues
+            | ConfigField::QuietHours | ConfigField::SlaThresholds | ConfigField::Webhooks | ConfigField::IdleFps | ConfigField::ActivityRetention | ConfigField::Palette
+            | ConfigField::BoardLayout | ConfigField::SpellCheck | ConfigField::StateSync)
     }
 
     /// Get the next field (wrapping), respecting visible fields based on enabled toggles
-    pub fn next_visible(&self, mascot_enabled: bool, qa_enabled: bool) -> ConfigField {
-        let visible = Self::visible_fields(mascot_enabled, qa_enabled);
+    pub fn next_visible(&self, mascot_enabled: bool, qa_enabled: bool, auto_version: bool) -> ConfigField {
+        let visible = Self::visible_fields(mascot_enabled, qa_enabled, auto_version);
         let idx = visible.iter().position(|f| f == self).unwrap_or(0);
         visible[(idx + 1) % visible.len()]
     }
 
     /// Get the previous field (wrapping), respecting visible fields based on enabled toggles
-    pub fn prev_visible(&self, mascot_enabled: bool, qa_enabled: bool) -> ConfigField {
-        let visible = Self::visible_fields(mascot_enabled, qa_enabled);
+    pub fn prev_visible(&self, mascot_enabled: bool, qa_enabled: bool, auto_version: bool) -> ConfigField {
+        let visible = Self::visible_fields(mascot_enabled, qa_enabled, auto_version);
         let idx = visible.iter().position(|f| f == self).unwrap_or(0);
         visible[(idx + visible.len() - 1) % visible.len()]
     }
@@ -2534,12 +5082,80 @@ pub struct ConfigModalState {
     pub temp_mascot_advice: Option<bool>,
     /// Temporary mascot advice interval in minutes
     pub temp_mascot_interval: u32,
+    /// Temporary native git backend setting
+    pub temp_native_git: bool,
+    /// Temporary status bar clock setting
+    pub temp_status_clock: bool,
+    /// Temporary focus/break lengths in minutes
+    pub temp_focus_minutes: (u32, u32),
+    /// Temporary defer-starts-in-focus setting
+    pub temp_focus_defer_starts: bool,
+    /// Temporary sound cues setting
+    pub temp_sound_cues: bool,
+    /// Temporary quiet hours (None = never quiet)
+    pub temp_quiet_hours: Option<String>,
+    /// Temporary column time limits (empty = no limits)
+    pub temp_sla_thresholds: String,
+    /// Temporary webhooks (None = none)
+    pub temp_webhooks: Option<String>,
+    /// Temporary idle redraw rate (frames per second)
+    pub temp_idle_fps: u32,
+    /// Temporary activity log limit as (entries, KB)
+    pub temp_activity_retention: (usize, usize),
+    /// Temporary color palette
+    pub temp_palette: Palette,
+    /// Temporary board layout
+    pub temp_board_layout: BoardLayout,
+    /// Temporary spell check language (None = off)
+    pub temp_spell_check: Option<String>,
+    /// Temporary state sync remote (None = no sync)
+    pub temp_state_sync: Option<String>,
     /// Temporary QA enabled setting
     pub temp_qa_enabled: bool,
     /// Temporary max QA attempts setting
     pub temp_max_qa_attempts: u32,
     /// Temporary apply strategy setting
     pub temp_apply_strategy: ApplyStrategy,
+    /// Temporary context restart mode
+    pub temp_context_restart: ContextRestart,
+    /// Temporary commit message mode
+    pub temp_commit_messages: CommitMessages,
+    /// Temporary column sort
+    pub temp_column_sort: ColumnSort,
+    /// Temporary group-by-tag setting
+    pub temp_group_by_tag: bool,
+    /// Temporary columns the watcher looks at
+    pub temp_watcher_columns: Vec<TaskStatus>,
+    /// Temporary watcher persona (None = default)
+    pub temp_watcher_persona: Option<String>,
+    /// Temporary per-project watcher interval (None = global)
+    pub temp_watcher_interval: Option<u32>,
+    /// Temporary watcher read scope
+    pub temp_watcher_reads: crate::watcher_scope::WatcherReads,
+    /// Temporary worktree pool size
+    pub temp_worktree_pool_size: usize,
+    /// Temporary sparse-checkout profile (None = full checkout)
+    pub temp_sparse_paths: Option<String>,
+    /// Temporary shared caches (None = none)
+    pub temp_shared_caches: Option<String>,
+    /// Temporary status ignore patterns (None = none)
+    pub temp_status_ignore: Option<String>,
+    /// Temporary auto-versioning setting
+    pub temp_auto_version: bool,
+    /// Temporary version rules (None = defaults)
+    pub temp_version_rules: Option<String>,
+    /// Temporary error-log source (None = off)
+    pub temp_error_log_source: Option<String>,
+    /// Temporary error-log auto-start setting
+    pub temp_error_log_auto_start: bool,
+    /// Temporary feedback snippets
+    pub temp_feedback_snippets: Vec<String>,
+    /// Temporary transcription command
+    pub temp_transcribe_command: Option<String>,
+    /// Temporary chat bridge (None = off)
+    pub temp_chat_bridge: Option<String>,
+    /// Temporary session layout (None = default)
+    pub temp_session_layout: Option<String>,
 }
 
 /// Create regular (non-vim) mode handler with standard text editing keybindings
@@ -2655,6 +5271,10 @@ impl Default for UiState {
             show_help: false,
             help_scroll_offset: 0,
             show_stats: false,
+            show_diagnostics: false,
+            diagnostics_scroll_offset: 0,
+            diagnostics_slow_ops: false,
+            log_viewer: None,
             pending_confirmation: None,
             confirmation_scroll_offset: 0,
             status_message: None,
@@ -2663,6 +5283,7 @@ impl Default for UiState {
             title_scroll_offset: 0,
             title_scroll_delay: 0,
             pending_images: Vec::new(),
+            pending_attachments: Vec::new(),
             animation_frame: 0,
             column_scroll_offsets: [0; 6],
             queue_dialog_task_id: None,
@@ -2676,7 +5297,9 @@ impl Default for UiState {
             open_project_dialog_slot: None,
             directory_browser: None,
             create_folder_input: None,
+            quick_open: QuickOpen::default(),
             feedback_task_id: None,
+            broadcast_feedback: false,
             note_task_id: None,
             logo_shimmer_frame: 0,
             // Mascot eye animation: start with normal eyes, trigger first animation in ~30-90 seconds
@@ -2694,7 +5317,9 @@ impl Default for UiState {
             show_stash_modal: false,
             stash_modal_selected_idx: 0,
             git_diff_scroll_offset: 0,
-            git_diff_cache: None,
+            git_diff_cache: Default::default(),
+            git_history_view: false,
+            git_history: None,
             spec_scroll_offset: 0,
             notes_scroll_offset: 0,
             // Welcome panel: start at first message, rotate every ~8 seconds
@@ -2712,12 +5337,58 @@ impl Default for UiState {
             pending_replace_char: false,
             // Sidecar control modal
             sidecar_modal: None,
+            sidecar_health: SidecarHealth::default(),
+            ci_polled_at: None,
+            sla_checked_at: None,
+            draft_checked_at: None,
+            saved_draft: String::new(),
+            input_history_cursor: None,
+            markdown_preview: false,
+            ci_polling: false,
+            chat_polled_at: None,
+            chat_polling: false,
             // Build check animation
             build_check_in_progress: false,
             // Stats modal scrolling
             stats_scroll_offset: 0,
             // Markdown file picker
             md_file_picker: None,
+            snippet_picker: None,
+            link_picker: None,
+            watcher_feed: None,
+            start_failure: None,
+            commit_editor: None,
+            // Dependency graph modal
+            quick_diff: None,
+            capabilities: Default::default(),
+            probing_git: Default::default(),
+            board_layout: BoardLayout::Grid,
+            show_integrations: false,
+            show_dependency_graph: false,
+            dependency_graph_selected_idx: 0,
+            release_notes: None,
+            whats_new: None,
+            sparse_prompt: None,
+            activity_note: None,
+            diff_comment: None,
+            time_entry_input: None,
+            queue_selected: 0,
+            queue_edit: None,
+            session_replay: None,
+            scrollback_viewer: None,
+            output_pane: None,
+            monitor: MonitorState::default(),
+            code_search: None,
+            todo_scan: None,
+            error_offers: Vec::new(),
+            focus_timer: None,
+            focus_picker: None,
+            deferred_starts: Vec::new(),
+            read_marker: None,
+            card_drag: None,
+            project_tab_drag: None,
+            project_picker: None,
+            workspace_picker: None,
         }
     }
 }
@@ -2762,8 +5433,32 @@ impl UiState {
         self.editor_state.mode = EditorMode::Normal;
     }
 
+    /// Insert text at the cursor
+    pub fn insert_input_text(&mut self, text: &str) {
+        use edtui::actions::{Execute, InsertChar, LineBreak};
+
+        for ch in text.chars() {
+            if ch == '\n' {
+                LineBreak(1).execute(&mut self.editor_state);
+            } else {
+                InsertChar(ch).execute(&mut self.editor_state);
+            }
+        }
+    }
+
+    /// The input's current line up to the cursor
+    pub fn input_before_cursor(&self) -> String {
+        let cursor = self.editor_state.cursor;
+        self.get_input_text()
+            .lines()
+            .nth(cursor.row)
+            .map(|line| line.chars().take(cursor.col).collect())
+            .unwrap_or_default()
+    }
+
     /// Clear the editor text
     pub fn clear_input(&mut self) {
+        self.input_history_cursor = None;
         self.editor_state = EditorState::default();
         // Ensure we're in insert mode
         self.editor_state.mode = EditorMode::Insert;
@@ -2836,28 +5531,54 @@ pub enum PendingAction {
     /// CLI is working, user wants to send feedback (i=interrupt, w=wait, o=open CLI, n=cancel)
     /// Stores task_id and the feedback text to send
     InterruptCliForFeedback { task_id: Uuid, feedback: String },
+    /// Send the same feedback to every listed InProgress task
+    /// Options: y=send (busy sessions get it queued), n=cancel
+    BroadcastFeedback { task_ids: Vec<Uuid>, feedback: String },
+    /// How a broadcast went for each task (view-only, just dismiss)
+    BroadcastReport,
     /// Main worktree has uncommitted changes before merge
     /// Options: c=commit, s=stash, n=cancel
     DirtyMainBeforeMerge { task_id: Uuid },
+    /// A session's context is nearly full: restart it with a progress summary
+    RestartSessionWithSummary(Uuid),
     /// Offer to pop a tracked stash (after unapply or merge)
     /// Options: y=pop, n=skip
     PopTrackedStash { stash_sha: String },
     /// Project directory is not a git repository
-    /// Options: y=initialize git, n=cancel
+    /// Options: y=initialize git, s=snapshot mode (shadow repository), n=cancel
     InitGit { path: PathBuf, name: String, slot: usize },
     /// Git repository has no commits
     /// Options: y=create initial commit, n=cancel
     CreateInitialCommit { path: PathBuf, name: String, slot: usize },
+    /// Updating to main hit conflicts
+    /// Options: y=smart update (Claude redoes the rebase), h=hand the conflicts to a session, n=leave in Review
+    RebaseConflict(Uuid),
+    /// Task worktrees found out of step with the board at startup
+    /// Options: y=apply the listed fixes, n=leave them
+    ReconcileWorktrees(Vec<crate::worktree::integrity::WorktreeIssue>),
     /// Apply conflict - show conflict details in scrollable modal
     /// Options: y=try smart apply with Claude, n=cancel
     ApplyConflict { task_id: Uuid, conflict_output: String },
     /// Task was rebased for apply, ready to restart? (bootstrap mode only)
     /// Options: y=restart to apply, n=cancel (stays rebased)
     RebaseForApplyReady { task_id: Uuid },
+    /// Bump version and create a release tag after merging a #release task
+    /// Options: y=bump/commit/tag as previewed, n=skip
+    ReleaseVersion { plan: crate::release::version::ReleasePlan },
+    /// New error captured by the error-log watcher
+    /// Options: y=create a Planned bug task with the trace, n=ignore (stays deduplicated)
+    CreateErrorTask { project_dir: PathBuf, source: String, error: crate::errorlog::CapturedError },
+    /// A paste too long to drop into the input unasked
+    /// Options: y=paste, f=attach as a text file, n=cancel
+    LargePaste { text: String },
+    /// Ctrl+V found file paths on the clipboard
+    /// Options: y=attach the files, t=paste the paths as text, n=cancel
+    AttachClipboardFiles { paths: Vec<PathBuf>, text: String },
     /// Project .gitignore is missing KanBlam entries (.claude/, worktrees/)
     /// Options: y=add entries, n=open anyway without adding
     UpdateGitignore {
         path: PathBuf,
+        scope: Option<PathBuf>,
         name: String,
         slot: usize,
         missing_entries: Vec<String>,
@@ -2872,6 +5593,8 @@ pub enum FocusArea {
     TaskInput,
     ProjectTabs,
     OutputViewer,
+    /// Tiled monitor of all in-progress tasks (replaces the board while focused)
+    Monitor,
 }
 
 /// Signal received from Claude Code hooks
@@ -2888,6 +5611,47 @@ pub struct HookSignal {
     /// Source of the signal: "sdk" or "cli" (defaults to "cli" for backwards compatibility)
     #[serde(default)]
     pub source: String,
+    /// Details of tool-use, tool-error and subagent events
+    #[serde(default)]
+    pub payload: HookPayload,
+}
+
+/// Details carried by tool-use, tool-error and subagent hook signals
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HookPayload {
+    /// Tool that was called ("Edit", "Bash", ...)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tool: Option<String>,
+    /// What the tool was called on: a file path, command or pattern
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+    /// Error a failed tool call returned
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Subagent type, for subagent events
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub agent: Option<String>,
+}
+
+impl HookPayload {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Activity log line for a signal of `event` carrying this payload
+    pub fn describe(&self, event: &str) -> String {
+        let tool = self.tool.as_deref().unwrap_or("tool");
+        let agent = self.agent.as_deref().unwrap_or("agent");
+        let detail = self.detail.as_deref().map(|d| format!(": {}", d)).unwrap_or_default();
+        match event {
+            "tool-start" => format!("Using {}{}", tool, detail),
+            "tool-finish" => format!("Finished {}{}", tool, detail),
+            "tool-error" => format!("{} failed: {}", tool, self.error.as_deref().unwrap_or("unknown error")),
+            "subagent-start" => format!("Subagent {} started{}", agent, detail),
+            "subagent-stop" => format!("Subagent {} finished{}", agent, detail),
+            other => other.to_string(),
+        }
+    }
 }
 
 // ============================================================================
@@ -3074,7 +5838,7 @@ fn default_version() -> u32 { 1 }
 impl Default for ProjectTaskData {
     fn default() -> Self {
         Self {
-            version: 1,
+            version: schema::TASKS_VERSION,
             tasks: Vec::new(),
             applied_task_id: None,
             applied_stash_ref: None,
@@ -3092,45 +5856,52 @@ impl ProjectTaskData {
     }
 
     /// Load task data from a project directory.
-    /// Returns default data if file doesn't exist.
-    pub fn load(project_dir: &PathBuf) -> Self {
+    /// Returns default data if file doesn't exist, and an error if it can't be read.
+    pub fn load(project_dir: &PathBuf) -> anyhow::Result<Self> {
+        use anyhow::Context;
+
         let path = Self::file_path(project_dir);
-        if path.exists() {
-            match std::fs::read_to_string(&path) {
-                Ok(content) => {
-                    match serde_json::from_str(&content) {
-                        Ok(data) => return data,
-                        Err(e) => {
-                            eprintln!("Warning: Failed to parse {}: {}", path.display(), e);
-                        }
-                    }
-                }
-                Err(e) => {
-                    eprintln!("Warning: Failed to read {}: {}", path.display(), e);
-                }
-            }
+        if !crate::remote::exists(&path) {
+            return Ok(Self::default());
         }
-        Self::default()
+        let content = crate::remote::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let mut value = serde_json::from_str(&content)
+            .with_context(|| format!("{} is not valid JSON", path.display()))?;
+        schema::upgrade_tasks(&path, &mut value)?;
+        serde_json::from_value(value).with_context(|| format!("Failed to load {}", path.display()))
     }
 
     /// Save task data to the project directory.
     /// Creates the .kanblam directory if it doesn't exist.
     pub fn save(&self, project_dir: &PathBuf) -> std::io::Result<()> {
         let kanblam_dir = project_dir.join(".kanblam");
-        std::fs::create_dir_all(&kanblam_dir)?;
+        crate::remote::create_dir_all(&kanblam_dir)?;
 
         let path = Self::file_path(project_dir);
+        // Never overwrite tasks saved by a newer kanblam with what this build understood of them
+        schema::check_tasks_writable(&path)
+            .map_err(|e| std::io::Error::other(e.to_string()))?;
         let content = serde_json::to_string_pretty(self)
             .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))?;
-        std::fs::write(path, content)
+        crate::remote::write(&path, content)
     }
 }
 
 impl Project {
     /// Load tasks and related data from the project's .kanblam directory.
-    /// Call this when opening or switching to a project.
+    /// Call this when opening or switching to a project. A file that can't be
+    /// read is recorded in `load_error`, which keeps it from being saved over.
     pub fn load_tasks(&mut self) {
-        let data = ProjectTaskData::load(&self.working_dir);
+        let data = match ProjectTaskData::load(&self.scope_dir()) {
+            Ok(data) => data,
+            Err(e) => {
+                // Keep the board empty and the file untouched until the user fixes it
+                crate::diagnostics::warn(crate::diagnostics::Source::Io, format!("{:#}", e));
+                self.load_error = Some(format!("{:#}", e));
+                return;
+            }
+        };
         self.tasks = data.tasks;
         self.applied_task_id = data.applied_task_id;
         self.applied_stash_ref = data.applied_stash_ref;
@@ -3138,27 +5909,30 @@ impl Project {
         self.statistics = data.statistics;
         self.apply_strategy = data.apply_strategy;
 
-        // Regenerate worktree paths (they're not persisted, derived from project_dir + display_id)
+        // Regenerate worktree paths (they're not persisted, derived from project_dir + display_id):
+        // the new naming convention (just display_id), else the old one (task-{uuid}) for migration.
+        // Checked in one go, so a remote project costs a single ssh round trip.
+        let worktrees = self.working_dir.join("worktrees");
+        let candidates: Vec<PathBuf> = self.tasks.iter()
+            .filter(|task| task.git_branch.is_some())
+            .flat_map(|task| [worktrees.join(task.display_id()), worktrees.join(format!("task-{}", task.id))])
+            .collect();
+        let found = crate::remote::exists_all(&candidates);
+        let mut candidates = candidates.into_iter().zip(found);
         for task in &mut self.tasks {
+            task.load_activity_outputs();
             if task.git_branch.is_some() {
-                // Try new naming convention first (just display_id)
-                let new_worktree_path = self.working_dir
-                    .join("worktrees")
-                    .join(task.display_id());
-                // Fall back to old naming convention (task-{uuid}) for migration
-                let old_worktree_path = self.working_dir
-                    .join("worktrees")
-                    .join(format!("task-{}", task.id));
-
-                if new_worktree_path.exists() {
-                    task.worktree_path = Some(new_worktree_path);
-                } else if old_worktree_path.exists() {
-                    task.worktree_path = Some(old_worktree_path);
-                } else {
-                    // Worktree was deleted, clear the reference
-                    task.worktree_path = None;
-                    task.git_branch = None;
-                }
+                let (Some(new_worktree), Some(old_worktree)) = (candidates.next(), candidates.next()) else {
+                    continue;
+                };
+                task.worktree_path = match (new_worktree, old_worktree) {
+                    ((path, true), _) | (_, (path, true)) => Some(path),
+                    _ => {
+                        // Worktree was deleted, clear the reference
+                        task.git_branch = None;
+                        None
+                    }
+                };
             }
         }
     }
@@ -3166,8 +5940,11 @@ impl Project {
     /// Save tasks and related data to the project's .kanblam directory.
     /// Call this periodically and when closing a project.
     pub fn save_tasks(&self) -> std::io::Result<()> {
+        if let Some(error) = &self.load_error {
+            return Err(std::io::Error::other(format!("Not overwriting tasks that couldn't be read: {}", error)));
+        }
         let data = ProjectTaskData {
-            version: 1,
+            version: schema::TASKS_VERSION,
             tasks: self.tasks.clone(),
             applied_task_id: self.applied_task_id,
             applied_stash_ref: self.applied_stash_ref.clone(),
@@ -3175,6 +5952,79 @@ impl Project {
             statistics: self.statistics.clone(),
             apply_strategy: self.apply_strategy,
         };
-        data.save(&self.working_dir)
+        data.save(&self.scope_dir())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dependency_graph_stack_and_cycle() {
+        let mut project = Project::new("graph".to_string(), PathBuf::from("/tmp/graph"));
+        for title in ["root", "stacked", "stacked twice", "queued", "cycle a", "cycle b", "alone"] {
+            project.tasks.push(Task::new(title.to_string()));
+        }
+        let id = |project: &Project, i: usize| project.tasks[i].id;
+        project.tasks[1].stacked_on = Some(id(&project, 0));
+        project.tasks[2].stacked_on = Some(id(&project, 1));
+        project.tasks[3].queued_for_session = Some(id(&project, 0));
+        // A cycle from a hand-edited state file has no root, so it's left out
+        project.tasks[4].stacked_on = Some(id(&project, 5));
+        project.tasks[5].stacked_on = Some(id(&project, 4));
+
+        let rows: Vec<_> = project.dependency_graph().into_iter()
+            .map(|row| (project.tasks.iter().position(|t| t.id == row.task_id).unwrap(), row.edge, row.ancestors_last))
+            .collect();
+        assert_eq!(rows, [
+            (0, None, vec![]),
+            (1, Some(DependencyEdge::Stacked), vec![false]),
+            (2, Some(DependencyEdge::Stacked), vec![false, true]),
+            (3, Some(DependencyEdge::Queued), vec![true]),
+        ]);
+    }
+
+    #[test]
+    fn test_column_sort_and_tag_groups() {
+        let mut project = Project::new("sorted".to_string(), PathBuf::from("/tmp/sorted"));
+        let start = Utc::now();
+        for (i, title) in ["old #ui #p2", "mid #api", "new #ui #p0", "newest"].into_iter().enumerate() {
+            let mut task = Task::new(title.to_string());
+            task.created_at = start + chrono::Duration::minutes(i as i64);
+            project.tasks.push(task);
+        }
+        project.tasks[1].last_activity_at = Some(start);
+        project.tasks[0].status_entered_at = Some(start + chrono::Duration::hours(1));
+        let titles = |project: &Project| project.tasks_by_status(TaskStatus::Planned).iter()
+            .map(|t| t.title.split(' ').next().unwrap().to_string())
+            .collect::<Vec<_>>();
+
+        // Manual keeps the Vec order; the other sorts are stable over it
+        assert_eq!(titles(&project), ["old", "mid", "new", "newest"]);
+        assert!(!project.is_sorted());
+        project.column_sort = ColumnSort::Priority;
+        assert_eq!(titles(&project), ["new", "old", "mid", "newest"]);
+        project.column_sort = ColumnSort::LastActivity;
+        assert_eq!(titles(&project), ["mid", "old", "new", "newest"]);
+        project.column_sort = ColumnSort::TimeInColumn;
+        assert_eq!(titles(&project), ["mid", "new", "newest", "old"]);
+
+        // Groups by first label, alphabetically, untagged last, with a heading each
+        project.column_sort = ColumnSort::Created;
+        project.group_by_tag = true;
+        assert_eq!(titles(&project), ["mid", "old", "new", "newest"]);
+        assert_eq!(project.column_rows(TaskStatus::Planned), [
+            ColumnRow::Group(Some("api".to_string())),
+            ColumnRow::Task(0),
+            ColumnRow::Group(Some("ui".to_string())),
+            ColumnRow::Task(1),
+            ColumnRow::Task(2),
+            ColumnRow::Group(None),
+            ColumnRow::Task(3),
+        ]);
+        let tasks = project.tasks_by_status(TaskStatus::Planned);
+        assert!(project.same_group(tasks[1], tasks[2]));
+        assert!(!project.same_group(tasks[0], tasks[1]));
     }
 }
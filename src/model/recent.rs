@@ -0,0 +1,164 @@
+//! Recently opened projects and pinned favorites
+//!
+//! The open-project dialog lists these above the directory browser so a
+//! project opened before is a number key away. Typing a path ("/" or "~")
+//! opens it directly, with Tab completing directory names fuzzily.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// Unpinned entries kept (pinned ones are always kept)
+pub const MAX_RECENT: usize = 20;
+
+/// A project directory opened before
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecentProject {
+    pub path: PathBuf,
+    pub opened_at: DateTime<Utc>,
+    /// Favorites stay at the top and are never dropped
+    #[serde(default)]
+    pub pinned: bool,
+}
+
+/// Note that `path` was opened now
+pub fn record(recent: &mut Vec<RecentProject>, path: &Path, now: DateTime<Utc>) {
+    match recent.iter_mut().find(|r| r.path == path) {
+        Some(entry) => entry.opened_at = now,
+        None => recent.push(RecentProject { path: path.to_path_buf(), opened_at: now, pinned: false }),
+    }
+    // Drop the oldest unpinned entries past the limit
+    let mut unpinned: Vec<DateTime<Utc>> = recent.iter().filter(|r| !r.pinned).map(|r| r.opened_at).collect();
+    if unpinned.len() > MAX_RECENT {
+        unpinned.sort_unstable_by(|a, b| b.cmp(a));
+        let cutoff = unpinned[MAX_RECENT - 1];
+        recent.retain(|r| r.pinned || r.opened_at >= cutoff);
+    }
+}
+
+/// Entries as listed: pinned first, then the most recently opened
+pub fn ordered(recent: &[RecentProject]) -> Vec<&RecentProject> {
+    let mut list: Vec<&RecentProject> = recent.iter().collect();
+    list.sort_by(|a, b| b.pinned.cmp(&a.pinned).then(b.opened_at.cmp(&a.opened_at)));
+    list
+}
+
+/// Pin or unpin `path`
+pub fn toggle_pin(recent: &mut [RecentProject], path: &Path) {
+    if let Some(entry) = recent.iter_mut().find(|r| r.path == path) {
+        entry.pinned = !entry.pinned;
+    }
+}
+
+/// Remove `path` from the list
+pub fn forget(recent: &mut Vec<RecentProject>, path: &Path) {
+    recent.retain(|r| r.path != path);
+}
+
+/// Expand a leading "~" to the home directory
+pub fn expand_home(input: &str) -> PathBuf {
+    match input.strip_prefix('~') {
+        Some(rest) => {
+            let home = dirs::home_dir().unwrap_or_else(|| PathBuf::from("/"));
+            home.join(rest.trim_start_matches('/'))
+        }
+        None => PathBuf::from(input),
+    }
+}
+
+/// Complete the last segment of a typed path against the directories there.
+/// Returns the completed input and the candidate names when it's ambiguous.
+/// Names starting with the typed text win; otherwise they're matched fuzzily.
+pub fn complete_path(input: &str) -> (String, Vec<String>) {
+    let (dir_part, partial) = match input.rfind('/') {
+        Some(idx) => input.split_at(idx + 1),
+        None if input == "~" => return ("~/".to_string(), Vec::new()),
+        None => return (input.to_string(), Vec::new()),
+    };
+    let Ok(entries) = std::fs::read_dir(expand_home(dir_part)) else {
+        return (input.to_string(), Vec::new());
+    };
+    let mut names: Vec<String> = entries
+        .flatten()
+        .filter(|e| e.path().is_dir())
+        .map(|e| e.file_name().to_string_lossy().to_string())
+        .filter(|name| !name.starts_with('.') || partial.starts_with('.'))
+        .collect();
+    names.sort_by_key(|name| name.to_lowercase());
+
+    let partial_lower = partial.to_lowercase();
+    let prefixed: Vec<String> = names.iter()
+        .filter(|name| name.to_lowercase().starts_with(&partial_lower))
+        .cloned()
+        .collect();
+    let is_prefix = !prefixed.is_empty();
+    let candidates = if !is_prefix {
+        let mut scored: Vec<(i64, String)> = names.into_iter()
+            .filter_map(|name| super::fuzzy_match(&name.to_lowercase(), &partial_lower).map(|score| (score, name)))
+            .collect();
+        scored.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+        scored.into_iter().map(|(_, name)| name).collect()
+    } else {
+        prefixed
+    };
+
+    match candidates.as_slice() {
+        [] => (input.to_string(), Vec::new()),
+        [only] => (format!("{}{}/", dir_part, only), Vec::new()),
+        _ if !is_prefix => (input.to_string(), candidates),
+        _ => {
+            // Extend to what all prefix matches share
+            let common = candidates.iter().skip(1).fold(candidates[0].clone(), |common, name| {
+                common.chars().zip(name.chars()).take_while(|(a, b)| a == b).map(|(a, _)| a).collect()
+            });
+            let completed = if common.len() > partial.len() {
+                format!("{}{}", dir_part, common)
+            } else {
+                input.to_string()
+            };
+            (completed, candidates)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recent_order_and_limit() {
+        let start = Utc::now();
+        let mut recent = Vec::new();
+        for idx in 0..(MAX_RECENT + 3) {
+            record(&mut recent, Path::new(&format!("/src/p{}", idx)), start + chrono::Duration::minutes(idx as i64));
+        }
+        assert_eq!(recent.len(), MAX_RECENT);
+        assert!(!recent.iter().any(|r| r.path == Path::new("/src/p0")));
+
+        // A pinned entry goes first and reopening moves an entry up
+        toggle_pin(&mut recent, Path::new("/src/p5"));
+        record(&mut recent, Path::new("/src/p4"), start + chrono::Duration::hours(2));
+        let list = ordered(&recent);
+        assert_eq!(list[0].path, Path::new("/src/p5"));
+        assert_eq!(list[1].path, Path::new("/src/p4"));
+    }
+
+    #[test]
+    fn test_complete_path() {
+        let dir = tempfile::tempdir().unwrap();
+        for name in ["kanblam", "kanban-old", "notes"] {
+            std::fs::create_dir(dir.path().join(name)).unwrap();
+        }
+        let base = format!("{}/", dir.path().display());
+
+        // Unique prefix completes the name
+        assert_eq!(complete_path(&format!("{}no", base)), (format!("{}notes/", base), Vec::new()));
+        // Shared prefix completes as far as it goes and lists the rest
+        let (completed, candidates) = complete_path(&format!("{}k", base));
+        assert_eq!(completed, format!("{}kanb", base));
+        assert_eq!(candidates, vec!["kanban-old", "kanblam"]);
+        // No prefix match falls back to fuzzy matching
+        assert_eq!(complete_path(&format!("{}nts", base)).0, format!("{}notes/", base));
+        assert_eq!(complete_path(&format!("{}kbl", base)).1.len(), 2);
+    }
+}
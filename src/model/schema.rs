@@ -0,0 +1,153 @@
+//! Schema versions of the persisted state
+//!
+//! `state.json` and each project's `.kanblam/tasks.json` carry a version
+//! number. Older files are upgraded one version at a time by the migrations
+//! registered below, after a timestamped copy of the original is written next
+//! to the file. Files from a newer kanblam are refused instead of being loaded
+//! with their unknown fields silently dropped (and then saved over).
+
+use anyhow::{bail, Context, Result};
+use serde::Deserialize;
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+
+/// Version of `state.json` written by this build
+pub const STATE_VERSION: u32 = 3;
+
+/// Version of `.kanblam/tasks.json` written by this build
+pub const TASKS_VERSION: u32 = 1;
+
+/// Upgrades a file's JSON from version `from` to `from + 1`
+struct Migration {
+    from: u32,
+    apply: fn(&mut Value),
+}
+
+/// `state.json` migrations, oldest first
+const STATE_MIGRATIONS: &[Migration] = &[
+    // v1 files predate the version field; nothing else changed
+    Migration { from: 1, apply: |_| {} },
+    // v3 keeps only an index of projects, each with its own state file. Projects
+    // still stored whole are read as they are and moved out on the next save.
+    Migration { from: 2, apply: |_| {} },
+];
+
+/// `tasks.json` migrations, oldest first
+const TASKS_MIGRATIONS: &[Migration] = &[];
+
+/// A persisted file kind: where its version lives and how to upgrade it
+struct Schema {
+    field: &'static str,
+    current: u32,
+    migrations: &'static [Migration],
+}
+
+const STATE: Schema = Schema { field: "schema_version", current: STATE_VERSION, migrations: STATE_MIGRATIONS };
+const TASKS: Schema = Schema { field: "version", current: TASKS_VERSION, migrations: TASKS_MIGRATIONS };
+
+/// Bring parsed `state.json` up to the current version
+pub fn upgrade_state(path: &Path, value: &mut Value) -> Result<()> {
+    upgrade(&STATE, path, value)
+}
+
+/// Bring parsed `tasks.json` up to the current version
+pub fn upgrade_tasks(path: &Path, value: &mut Value) -> Result<()> {
+    upgrade(&TASKS, path, value)
+}
+
+/// Fail if the `tasks.json` at `path` was written by a newer kanblam (so it mustn't be overwritten)
+pub fn check_tasks_writable(path: &Path) -> Result<()> {
+    #[derive(Deserialize)]
+    struct Version {
+        #[serde(default)]
+        version: u32,
+    }
+
+    let Ok(content) = crate::remote::read_to_string(path) else {
+        return Ok(());
+    };
+    match serde_json::from_str::<Version>(&content) {
+        Ok(Version { version }) if version > TASKS_VERSION => Err(too_new(path, version, TASKS_VERSION)),
+        _ => Ok(()),
+    }
+}
+
+/// Whether the `state.json` at `path` was written by a newer kanblam (so restoring
+/// a backup over it would lose data this build can't read)
+pub fn state_is_too_new(path: &Path) -> bool {
+    std::fs::read_to_string(path).ok()
+        .and_then(|content| serde_json::from_str::<Value>(&content).ok())
+        .and_then(|value| value.get(STATE.field).and_then(Value::as_u64))
+        .is_some_and(|version| version > STATE_VERSION as u64)
+}
+
+fn upgrade(schema: &Schema, path: &Path, value: &mut Value) -> Result<()> {
+    let Some(object) = value.as_object() else {
+        bail!("{} is not a kanblam state file", path.display());
+    };
+    // Files written before versioning have no version field
+    let found = object.get(schema.field)
+        .and_then(Value::as_u64)
+        .map_or(1, |v| v as u32);
+
+    if found > schema.current {
+        return Err(too_new(path, found, schema.current));
+    }
+    if found == schema.current {
+        return Ok(());
+    }
+
+    let backup = backup_path(path, found);
+    crate::remote::copy(path, &backup)
+        .with_context(|| format!("Failed to back up {} before migrating it", path.display()))?;
+
+    for version in found..schema.current {
+        let step = schema.migrations.iter()
+            .find(|m| m.from == version)
+            .with_context(|| format!("No migration from v{} for {}", version, path.display()))?;
+        (step.apply)(value);
+    }
+    value[schema.field] = schema.current.into();
+    Ok(())
+}
+
+fn too_new(path: &Path, found: u32, supported: u32) -> anyhow::Error {
+    anyhow::anyhow!(
+        "{} was written by a newer kanblam (schema v{}, this build reads up to v{}).\n\
+         Upgrade kanblam, or use --state-file to start with a different file. The file was not modified.",
+        path.display(), found, supported,
+    )
+}
+
+/// Copy of the file as it was before migrating from `version`
+/// (e.g. `state.json.v2-20250101-120000.bak`)
+fn backup_path(path: &Path, version: u32) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(format!(".v{}-{}.bak", version, chrono::Local::now().format("%Y%m%d-%H%M%S")));
+    path.with_file_name(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_upgrade_backs_up_and_refuses_newer() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("state.json");
+        let old = json!({ "projects": [], "active_project_idx": 0 });
+        std::fs::write(&path, old.to_string()).unwrap();
+
+        let mut value = old.clone();
+        upgrade_state(&path, &mut value).unwrap();
+        assert_eq!(value["schema_version"], STATE_VERSION);
+        let backed_up = std::fs::read_dir(dir.path()).unwrap().flatten()
+            .any(|e| e.file_name().to_string_lossy().starts_with("state.json.v1-"));
+        assert!(backed_up);
+
+        let mut newer = json!({ "schema_version": STATE_VERSION + 1 });
+        assert!(upgrade_state(&path, &mut newer).is_err());
+    }
+}
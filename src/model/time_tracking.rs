@@ -0,0 +1,166 @@
+//! Time tracked against a task
+//!
+//! Each task keeps a log of time entries. Time a session spends working
+//! (In Progress or Testing) and focus blocks on the task are added
+//! automatically; anything else is added or corrected by hand from the
+//! task preview as e.g. "1h30m pairing on the API".
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt::Write as _;
+
+/// Where a time entry came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TimeSource {
+    /// A session working on the task
+    Session,
+    /// A focus block on the task
+    Focus,
+    /// Added by hand
+    Manual,
+}
+
+impl TimeSource {
+    pub fn label(&self) -> &'static str {
+        match self {
+            TimeSource::Session => "session",
+            TimeSource::Focus => "focus",
+            TimeSource::Manual => "manual",
+        }
+    }
+}
+
+/// A stretch of time spent on a task
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TimeEntry {
+    pub started_at: DateTime<Utc>,
+    pub secs: i64,
+    pub source: TimeSource,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+}
+
+impl TimeEntry {
+    /// An entry for time that ends now
+    pub fn ending_now(secs: i64, source: TimeSource, note: Option<String>) -> Self {
+        Self {
+            started_at: Utc::now() - chrono::Duration::seconds(secs),
+            secs,
+            source,
+            note,
+        }
+    }
+
+    /// The entry as typed when adding it by hand ("1h30m note")
+    pub fn to_input(&self) -> String {
+        match self.note {
+            Some(ref note) => format!("{} {}", format_tracked(self.secs), note),
+            None => format_tracked(self.secs),
+        }
+    }
+}
+
+/// Total seconds of `entries`, optionally only from one source
+pub fn total_secs(entries: &[TimeEntry], source: Option<TimeSource>) -> i64 {
+    entries.iter()
+        .filter(|e| source.is_none_or(|s| e.source == s))
+        .map(|e| e.secs)
+        .sum()
+}
+
+/// Parse a manual entry: a duration like "1h30m", "45m" or "2h", then an
+/// optional note
+pub fn parse_manual(input: &str) -> Option<(i64, Option<String>)> {
+    let input = input.trim();
+    let (duration, note) = input.split_once(char::is_whitespace).unwrap_or((input, ""));
+    let secs = parse_duration(duration)?;
+    let note = note.trim();
+    Some((secs, (!note.is_empty()).then(|| note.to_string())))
+}
+
+fn parse_duration(text: &str) -> Option<i64> {
+    let mut secs = 0;
+    let mut number = String::new();
+    for c in text.chars() {
+        if c.is_ascii_digit() {
+            number.push(c);
+            continue;
+        }
+        let amount: i64 = number.parse().ok()?;
+        number.clear();
+        secs += match c {
+            'h' => amount * 3600,
+            'm' => amount * 60,
+            _ => return None,
+        };
+    }
+    // A bare number is minutes
+    if !number.is_empty() {
+        secs += number.parse::<i64>().ok()? * 60;
+    }
+    (secs > 0).then_some(secs)
+}
+
+/// Tracked time as hours and minutes, e.g. "1h30m", "45m"
+pub fn format_tracked(secs: i64) -> String {
+    let (hours, mins) = (secs / 3600, (secs % 3600) / 60);
+    match (hours, mins) {
+        (0, m) => format!("{}m", m),
+        (h, 0) => format!("{}h", h),
+        (h, m) => format!("{}h{}m", h, m),
+    }
+}
+
+/// The time log as text, for exports
+pub fn export_text(entries: &[TimeEntry]) -> String {
+    let mut text = String::new();
+    let _ = writeln!(text, "Time tracked: {}", format_tracked(total_secs(entries, None)));
+    for entry in entries {
+        let _ = write!(
+            text,
+            "  {}  {:>7}  {}",
+            entry.started_at.format("%Y-%m-%d %H:%M"),
+            format_tracked(entry.secs),
+            entry.source.label()
+        );
+        if let Some(ref note) = entry.note {
+            let _ = write!(text, "  {}", note);
+        }
+        text.push('\n');
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_manual_entries() {
+        assert_eq!(parse_manual("1h30m pairing on the API"), Some((5400, Some("pairing on the API".to_string()))));
+        assert_eq!(parse_manual("45m"), Some((2700, None)));
+        assert_eq!(parse_manual(" 2h "), Some((7200, None)));
+        assert_eq!(parse_manual("20 review"), Some((1200, Some("review".to_string()))));
+        assert_eq!(parse_manual("review 20m"), None);
+        assert_eq!(parse_manual("0m"), None);
+        assert_eq!(parse_manual(""), None);
+
+        let entry = TimeEntry::ending_now(5400, TimeSource::Manual, Some("pairing".to_string()));
+        assert_eq!(entry.to_input(), "1h30m pairing");
+        assert_eq!(parse_manual(&entry.to_input()), Some((5400, Some("pairing".to_string()))));
+    }
+
+    #[test]
+    fn test_totals_by_source() {
+        let entries = vec![
+            TimeEntry::ending_now(3600, TimeSource::Session, None),
+            TimeEntry::ending_now(1500, TimeSource::Focus, None),
+            TimeEntry::ending_now(600, TimeSource::Manual, Some("call".to_string())),
+        ];
+        assert_eq!(total_secs(&entries, None), 5700);
+        assert_eq!(total_secs(&entries, Some(TimeSource::Focus)), 1500);
+        assert_eq!(format_tracked(5700), "1h35m");
+        assert!(export_text(&entries).starts_with("Time tracked: 1h35m\n"));
+    }
+}
@@ -0,0 +1,110 @@
+//! Past watcher comments of a project
+//!
+//! The mascot's balloon shows one comment at a time and forgets it once it's
+//! dismissed. The feed keeps them (newest last) with their insight and the
+//! task made from it, so advice can be read later; comments in categories the
+//! user muted land here without popping up.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::sidecar::protocol::{WatcherCategory, WatcherComment};
+
+/// Entries kept per project (the oldest go first)
+pub const MAX_ENTRIES: usize = 100;
+
+/// A watcher comment as kept in the feed
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FeedEntry {
+    pub received_at: DateTime<Utc>,
+    #[serde(default)]
+    pub category: Option<WatcherCategory>,
+    pub remark: String,
+    /// The insight's explanation (None for plain comments)
+    #[serde(default)]
+    pub description: Option<String>,
+    /// The insight's task instructions (None for plain comments)
+    #[serde(default)]
+    pub task: Option<String>,
+    /// Task created from the insight
+    #[serde(default)]
+    pub task_id: Option<Uuid>,
+    /// Arrived while its category was muted (never shown in the balloon)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub muted: bool,
+}
+
+impl FeedEntry {
+    pub fn new(comment: &WatcherComment, muted: bool) -> Self {
+        Self {
+            received_at: comment.timestamp,
+            category: comment.category,
+            remark: comment.comment.clone(),
+            description: comment.insight.as_ref().map(|i| i.description.clone()),
+            task: comment.insight.as_ref().map(|i| i.task.clone()),
+            task_id: None,
+            muted,
+        }
+    }
+}
+
+/// Add an entry, dropping the oldest past MAX_ENTRIES
+pub fn record(feed: &mut Vec<FeedEntry>, entry: FeedEntry) {
+    feed.push(entry);
+    let excess = feed.len().saturating_sub(MAX_ENTRIES);
+    feed.drain(..excess);
+}
+
+/// Link the task made from the entry with `remark` (the latest one) to it
+pub fn link_task(feed: &mut [FeedEntry], remark: &str, task_id: Uuid) {
+    if let Some(entry) = feed.iter_mut().rev().find(|e| e.remark == remark) {
+        entry.task_id = Some(task_id);
+    }
+}
+
+/// Mute `category` if it isn't, unmute it if it is
+pub fn toggle_mute(muted: &mut Vec<WatcherCategory>, category: WatcherCategory) {
+    match muted.iter().position(|c| *c == category) {
+        Some(idx) => {
+            muted.remove(idx);
+        }
+        None => muted.push(category),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(remark: &str) -> FeedEntry {
+        FeedEntry {
+            received_at: Utc::now(),
+            category: Some(WatcherCategory::Bug),
+            remark: remark.to_string(),
+            description: None,
+            task: None,
+            task_id: None,
+            muted: false,
+        }
+    }
+
+    #[test]
+    fn test_record_and_mute() {
+        let mut feed = Vec::new();
+        for n in 0..MAX_ENTRIES + 2 {
+            record(&mut feed, entry(&n.to_string()));
+        }
+        assert_eq!(feed.len(), MAX_ENTRIES);
+        assert_eq!(feed[0].remark, "2");
+
+        let task_id = Uuid::new_v4();
+        link_task(&mut feed, "50", task_id);
+        assert_eq!(feed.iter().find(|e| e.remark == "50").unwrap().task_id, Some(task_id));
+
+        let mut muted = vec![WatcherCategory::Refactor];
+        toggle_mute(&mut muted, WatcherCategory::Bug);
+        toggle_mute(&mut muted, WatcherCategory::Refactor);
+        assert_eq!(muted, vec![WatcherCategory::Bug]);
+    }
+}
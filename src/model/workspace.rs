@@ -0,0 +1,110 @@
+//! Workspaces: named sets of projects opened together
+//!
+//! A workspace remembers the projects that were open when it was saved, e.g.
+//! "work" vs "oss". Switching to one unloads the open projects that aren't in
+//! it and opens the ones that are, so each session starts with the right set
+//! instead of opening projects one by one. While a workspace is active it
+//! follows the projects opened and closed in it.
+
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+use super::{Project, ProjectIndexEntry};
+
+/// A named set of projects
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Workspace {
+    pub name: String,
+    /// Index entries of the workspace's projects, in tab order. Their state files
+    /// are kept while the workspace is unloaded.
+    #[serde(default)]
+    pub projects: Vec<ProjectIndexEntry>,
+}
+
+impl Workspace {
+    /// A workspace of the given projects
+    pub fn from_projects(name: String, projects: &[Project]) -> Self {
+        Self {
+            name,
+            projects: projects.iter().map(Project::index_entry).collect(),
+        }
+    }
+
+    /// Whether the workspace has the project covering `scope_dir`
+    /// (projects scoped to parts of one repository are told apart)
+    pub fn contains(&self, scope_dir: &Path) -> bool {
+        self.projects.iter().any(|p| p.scope_dir() == scope_dir)
+    }
+}
+
+/// Find a workspace by name (case-insensitive)
+pub fn find<'a>(workspaces: &'a [Workspace], name: &str) -> Option<&'a Workspace> {
+    workspaces.iter().find(|w| w.name.eq_ignore_ascii_case(name))
+}
+
+/// What switching workspaces does to the open projects
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SwitchPlan {
+    /// Open projects to unload (indices into the open projects)
+    pub unload: Vec<usize>,
+    /// Open projects kept open although they aren't in the workspace (running tasks)
+    pub kept: Vec<usize>,
+    /// Workspace projects to open (indices into the workspace's projects)
+    pub open: Vec<usize>,
+}
+
+/// Plan a switch from the open projects to `workspace`. Projects with running
+/// tasks stay open so their sessions aren't lost.
+pub fn plan_switch(open: &[Project], workspace: &Workspace) -> SwitchPlan {
+    let mut plan = SwitchPlan::default();
+    for (idx, project) in open.iter().enumerate() {
+        if workspace.contains(&project.scope_dir()) {
+            continue;
+        }
+        if project.has_active_task() {
+            plan.kept.push(idx);
+        } else {
+            plan.unload.push(idx);
+        }
+    }
+    plan.open = workspace.projects.iter()
+        .enumerate()
+        .filter(|(_, entry)| !open.iter().any(|p| p.scope_dir() == entry.scope_dir()))
+        .map(|(idx, _)| idx)
+        .collect();
+    plan
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plan_switch_keeps_shared_and_busy_projects() {
+        let scoped = |name: &str| {
+            let mut project = Project::new(name.to_string(), "/src/mono".into());
+            project.scope = Some(format!("apps/{}", name).into());
+            project
+        };
+        let open = vec![
+            Project::new("api".to_string(), "/src/api".into()),
+            Project::new("blog".to_string(), "/src/blog".into()),
+            Project::new("web".to_string(), "/src/web".into()),
+            scoped("docs"),
+        ];
+        let workspace = Workspace::from_projects(
+            "work".to_string(),
+            &[
+                Project::new("web".to_string(), "/src/web".into()),
+                Project::new("infra".to_string(), "/src/infra".into()),
+                scoped("docs"),
+                scoped("admin"),
+            ],
+        );
+        let plan = plan_switch(&open, &workspace);
+        assert_eq!(plan.unload, vec![0, 1]);
+        assert!(plan.kept.is_empty());
+        assert_eq!(plan.open, vec![1, 3]);
+        assert!(find(&[workspace], "WORK").is_some());
+    }
+}
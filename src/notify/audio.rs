@@ -1,20 +1,135 @@
+use rodio::source::{SineWave, Source, Zero};
 use rodio::{Decoder, OutputStream, Sink};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
 use std::thread;
+use std::time::Duration;
+
+/// Whether events play their own cue instead of the one attention sound
+static CUES_ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Do-not-disturb window as (start, end) minutes after midnight, local time
+static QUIET_HOURS: Mutex<Option<(u32, u32)>> = Mutex::new(None);
+
+/// Event a sound is played for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SoundCue {
+    /// Anything else that wants attention
+    Attention,
+    /// A task reached Review (chime)
+    Review,
+    /// A task is waiting for input or permission (double beep)
+    NeedsInput,
+    /// A merge failed (buzz)
+    MergeFailed,
+}
+
+impl SoundCue {
+    /// Custom sound file for the cue, in the kanblam sounds directory
+    fn file_name(&self) -> &'static str {
+        match self {
+            SoundCue::Attention => "attention.mp3",
+            SoundCue::Review => "review.mp3",
+            SoundCue::NeedsInput => "needs-input.mp3",
+            SoundCue::MergeFailed => "merge-failed.mp3",
+        }
+    }
+
+    /// Built-in tone sequence as (frequency in Hz, milliseconds); 0 Hz is a pause
+    fn tones(&self) -> &'static [(f32, u64)] {
+        match self {
+            // Keeps to the attention sound file or the terminal bell
+            SoundCue::Attention => &[],
+            SoundCue::Review => &[(784.0, 120), (1047.0, 120), (1319.0, 220)],
+            SoundCue::NeedsInput => &[(988.0, 90), (0.0, 70), (988.0, 90)],
+            SoundCue::MergeFailed => &[(110.0, 380)],
+        }
+    }
+
+    /// Terminal bells rung when there's no audio device
+    fn bells(&self) -> usize {
+        match self {
+            SoundCue::Attention | SoundCue::Review => 1,
+            SoundCue::NeedsInput => 2,
+            SoundCue::MergeFailed => 3,
+        }
+    }
+}
+
+/// Apply the sound settings: per-event cues on/off and the quiet hours
+/// ("22:00-07:30"; None or unparsable = never quiet)
+pub fn configure_sounds(cues_enabled: bool, quiet_hours: Option<&str>) {
+    CUES_ENABLED.store(cues_enabled, Ordering::Relaxed);
+    if let Ok(mut quiet) = QUIET_HOURS.lock() {
+        *quiet = quiet_hours.and_then(parse_quiet_hours);
+    }
+}
+
+/// Parse quiet hours written as "HH:MM-HH:MM" into minutes after midnight
+pub fn parse_quiet_hours(text: &str) -> Option<(u32, u32)> {
+    let minutes = |hm: &str| -> Option<u32> {
+        let (h, m) = hm.trim().split_once(':')?;
+        let (h, m) = (h.parse::<u32>().ok()?, m.parse::<u32>().ok()?);
+        (h < 24 && m < 60).then_some(h * 60 + m)
+    };
+    let (start, end) = text.split_once('-')?;
+    let (start, end) = (minutes(start)?, minutes(end)?);
+    (start != end).then_some((start, end))
+}
+
+/// Whether `now` (minutes after midnight) falls in the quiet hours, which may
+/// run past midnight
+fn in_quiet_hours((start, end): (u32, u32), now: u32) -> bool {
+    if start < end {
+        now >= start && now < end
+    } else {
+        now >= start || now < end
+    }
+}
+
+fn is_quiet_now() -> bool {
+    use chrono::Timelike;
+    let Some(window) = QUIET_HOURS.lock().ok().and_then(|q| *q) else {
+        return false;
+    };
+    let now = chrono::Local::now();
+    in_quiet_hours(window, now.hour() * 60 + now.minute())
+}
 
 /// Play the attention notification sound
 /// Plays asynchronously so it doesn't block the UI
 pub fn play_attention_sound() {
-    thread::spawn(|| {
-        if let Err(e) = play_sound_internal() {
-            // Silently ignore audio errors - notification is best-effort
-            eprintln!("Audio notification failed: {}", e);
+    play_cue(SoundCue::Attention);
+}
+
+/// Play the sound for an event: its own cue when cues are on, otherwise the
+/// attention sound. Silent during quiet hours.
+pub fn play_cue(cue: SoundCue) {
+    if is_quiet_now() {
+        return;
+    }
+    let cue = if CUES_ENABLED.load(Ordering::Relaxed) { cue } else { SoundCue::Attention };
+    thread::spawn(move || {
+        if let Err(e) = play_sound_internal(cue) {
+            // Notification is best-effort: the error only goes to the diagnostics
+            crate::diagnostics::warn(crate::diagnostics::Source::Other, format!("Audio notification failed: {}", e));
         }
     });
 }
 
-fn play_sound_internal() -> anyhow::Result<()> {
+fn play_sound_internal(cue: SoundCue) -> anyhow::Result<()> {
+    let bell = || print!("{}", "\x07".repeat(cue.bells())); // ASCII BEL character(s)
+
     // Try to get audio output stream
-    let (_stream, stream_handle) = OutputStream::try_default()?;
+    let (_stream, stream_handle) = match OutputStream::try_default() {
+        Ok(output) => output,
+        // Without an audio device a cue still rings its bells
+        Err(_) if cue != SoundCue::Attention => {
+            bell();
+            return Ok(());
+        }
+        Err(e) => return Err(e.into()),
+    };
     let sink = Sink::try_new(&stream_handle)?;
 
     // Try custom sound file first
@@ -22,18 +137,49 @@ fn play_sound_internal() -> anyhow::Result<()> {
         .unwrap_or_else(|| std::path::PathBuf::from("."))
         .join("kanblam")
         .join("sounds")
-        .join("attention.mp3");
+        .join(cue.file_name());
 
     if sound_path.exists() {
         let file = std::fs::File::open(&sound_path)?;
         let source = Decoder::new(std::io::BufReader::new(file))?;
         sink.append(source);
         sink.sleep_until_end();
-    } else {
+    } else if cue == SoundCue::Attention {
         // Fall back to system bell via terminal
-        print!("\x07"); // ASCII BEL character
+        bell();
+    } else {
+        for &(freq, ms) in cue.tones() {
+            let duration = Duration::from_millis(ms);
+            if freq > 0.0 {
+                sink.append(SineWave::new(freq).take_duration(duration).amplify(0.2));
+            } else {
+                sink.append(Zero::<f32>::new(1, 48_000).take_duration(duration));
+            }
+        }
+        sink.sleep_until_end();
     }
 
     Ok(())
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quiet_hours_parse_and_wrap_past_midnight() {
+        assert_eq!(parse_quiet_hours("22:00-07:30"), Some((1320, 450)));
+        assert_eq!(parse_quiet_hours(" 9:15 - 12:00 "), Some((555, 720)));
+        assert_eq!(parse_quiet_hours("25:00-07:00"), None);
+        assert_eq!(parse_quiet_hours("08:00-08:00"), None);
+        assert_eq!(parse_quiet_hours("evenings"), None);
+
+        let overnight = (1320, 450);
+        assert!(in_quiet_hours(overnight, 23 * 60));
+        assert!(in_quiet_hours(overnight, 3 * 60));
+        assert!(!in_quiet_hours(overnight, 12 * 60));
+        let daytime = (555, 720);
+        assert!(in_quiet_hours(daytime, 600));
+        assert!(!in_quiet_hours(daytime, 720));
+    }
+}
@@ -0,0 +1,55 @@
+//! Muting every task's notifications but the one being focused on
+//!
+//! While a focus block tied to a task runs, sounds and the tmux attention
+//! indicator for other tasks are held back. Notifications that aren't about
+//! a task (the focus timer itself, failed merges) always get through.
+
+use std::sync::Mutex;
+use uuid::Uuid;
+
+use super::{play_cue, set_attention_indicator, SoundCue};
+
+/// Task of the running focus block (None = nothing muted)
+static FOCUS_TASK: Mutex<Option<Uuid>> = Mutex::new(None);
+
+/// Mute every task but `task` (None lifts the mute)
+pub fn set_focus_task(task: Option<Uuid>) {
+    if let Ok(mut focus) = FOCUS_TASK.lock() {
+        *focus = task;
+    }
+}
+
+/// Whether notifications about `task` are held back by a focus block
+pub fn is_muted(task: Uuid) -> bool {
+    FOCUS_TASK.lock().ok().and_then(|f| *f).is_some_and(|focus| focus != task)
+}
+
+/// Play `cue` for an event on `task`, unless it's muted
+pub fn play_task_cue(task: Uuid, cue: SoundCue) {
+    if !is_muted(task) {
+        play_cue(cue);
+    }
+}
+
+/// Flag the project in tmux for an event on `task`, unless it's muted
+pub fn set_task_attention_indicator(task: Uuid, project_name: &str) {
+    if !is_muted(task) {
+        set_attention_indicator(project_name);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_focus_mutes_other_tasks() {
+        let (focused, other) = (Uuid::new_v4(), Uuid::new_v4());
+        assert!(!is_muted(other));
+        set_focus_task(Some(focused));
+        assert!(is_muted(other));
+        assert!(!is_muted(focused));
+        set_focus_task(None);
+        assert!(!is_muted(other));
+    }
+}
@@ -1,5 +1,7 @@
 mod audio;
+mod focus;
 mod tmux_status;
 
-pub use audio::play_attention_sound;
+pub use audio::{configure_sounds, parse_quiet_hours, play_attention_sound, play_cue, SoundCue};
+pub use focus::{play_task_cue, set_focus_task, set_task_attention_indicator};
 pub use tmux_status::{set_attention_indicator, clear_attention_indicator};
@@ -0,0 +1,195 @@
+//! Plugins: executables in `~/.config/kanblam/plugins/` that automate the board
+//!
+//! Every executable there is run once per task lifecycle event (a task is
+//! created, started, reaches Review or is merged) with the event as JSON on
+//! stdin. Lines it prints that are JSON objects are actions to take:
+//!
+//!   {"action": "add_task", "title": "Update the changelog"}
+//!   {"action": "set_tag", "tag": "needs-docs"}
+//!   {"action": "comment", "text": "Deployed to staging"}
+//!
+//! `set_tag` and `comment` act on the event's task unless a `task` (display id
+//! or UUID) names another one in the same project. Anything else printed is
+//! ignored. Tasks added by plugins don't raise created events, so a plugin
+//! can't feed on its own tasks.
+
+use anyhow::{anyhow, Result};
+use chrono::Utc;
+use serde::Deserialize;
+use serde_json::json;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::time::Duration;
+use tokio::io::AsyncWriteExt;
+
+use crate::model::{LifecycleEvent, Task, TaskStatus};
+
+/// How long a plugin may take per event before it's killed
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+/// A task lifecycle event plugins are told about
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PluginEvent {
+    Created,
+    Started,
+    Review,
+    Merged,
+}
+
+impl PluginEvent {
+    /// Name used in the event JSON
+    pub fn name(self) -> &'static str {
+        match self {
+            PluginEvent::Created => "created",
+            PluginEvent::Started => "started",
+            PluginEvent::Review => "review",
+            PluginEvent::Merged => "merged",
+        }
+    }
+
+    /// The plugin event for a column move (plugins aren't told about Needs Work)
+    pub fn from_transition(from: TaskStatus, to: TaskStatus) -> Option<Self> {
+        match LifecycleEvent::from_transition(from, to)? {
+            LifecycleEvent::Started => Some(PluginEvent::Started),
+            LifecycleEvent::Review => Some(PluginEvent::Review),
+            LifecycleEvent::Merged => Some(PluginEvent::Merged),
+            LifecycleEvent::NeedsInput | LifecycleEvent::Moved => None,
+        }
+    }
+
+    /// The JSON sent to plugins on stdin
+    pub fn payload(self, project_name: &str, project_dir: &Path, task: &Task) -> String {
+        json!({
+            "event": self.name(),
+            "project": {
+                "name": project_name,
+                "dir": project_dir,
+            },
+            "task": {
+                "id": task.id,
+                "display_id": task.display_id(),
+                "title": task.title,
+                "status": task.status.label(),
+                "labels": task.labels(),
+                "branch": task.git_branch,
+                "worktree": task.worktree_path,
+            },
+            "timestamp": Utc::now().to_rfc3339(),
+        }).to_string()
+    }
+}
+
+/// Something a plugin asks kanblam to do
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum PluginAction {
+    /// Add a task to Planned in the event's project
+    AddTask { title: String },
+    /// Label a task (appends `#tag` to its title)
+    SetTag {
+        tag: String,
+        #[serde(default)]
+        task: Option<String>,
+    },
+    /// Add a note to a task's activity log
+    Comment {
+        text: String,
+        #[serde(default)]
+        task: Option<String>,
+    },
+}
+
+/// Where plugins are installed
+pub fn plugins_dir() -> Option<PathBuf> {
+    dirs::home_dir().map(|home| home.join(".config").join("kanblam").join("plugins"))
+}
+
+/// Installed plugins (executable files, dotfiles skipped), by name
+pub fn discover() -> Vec<PathBuf> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let Some(entries) = plugins_dir().and_then(|dir| std::fs::read_dir(dir).ok()) else {
+        return Vec::new();
+    };
+    let mut plugins: Vec<PathBuf> = entries
+        .flatten()
+        .filter(|e| !e.file_name().to_string_lossy().starts_with('.'))
+        .filter(|e| e.metadata().is_ok_and(|m| m.is_file() && m.permissions().mode() & 0o111 != 0))
+        .map(|e| e.path())
+        .collect();
+    plugins.sort();
+    plugins
+}
+
+/// A plugin's name as shown in messages
+pub fn name(plugin: &Path) -> String {
+    plugin.file_name().map_or_else(|| plugin.display().to_string(), |n| n.to_string_lossy().to_string())
+}
+
+/// Actions in a plugin's output: every line that parses as one
+pub fn parse_actions(output: &str) -> Vec<PluginAction> {
+    output.lines()
+        .map(str::trim)
+        .filter(|line| line.starts_with('{'))
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect()
+}
+
+/// Run `plugin` in `project_dir` with `payload` on stdin and collect its actions
+pub async fn run(plugin: &Path, project_dir: &Path, payload: &str) -> Result<Vec<PluginAction>> {
+    let mut command = tokio::process::Command::new(plugin);
+    // Remote projects have no local directory to run in
+    if project_dir.is_dir() {
+        command.current_dir(project_dir);
+    }
+    let mut child = command
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|e| anyhow!("couldn't start: {}", e))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        // A plugin that doesn't read its input closes the pipe early; that's fine
+        let _ = stdin.write_all(payload.as_bytes()).await;
+        let _ = stdin.write_all(b"\n").await;
+    }
+    let output = tokio::time::timeout(TIMEOUT, child.wait_with_output())
+        .await
+        .map_err(|_| anyhow!("timed out after {}s", TIMEOUT.as_secs()))??;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let reason = stderr.lines().rev().find(|l| !l.trim().is_empty()).unwrap_or("").trim().to_string();
+        return Err(anyhow!("{} {}", output.status, reason));
+    }
+    Ok(parse_actions(&String::from_utf8_lossy(&output.stdout)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_actions_and_events() {
+        let output = "deploying...\n\
+            {\"action\": \"comment\", \"text\": \"Deployed to staging\"}\n\
+            {\"action\": \"set_tag\", \"tag\": \"qa\", \"task\": \"API-a7x\"}\n\
+            {\"action\": \"launch_rockets\"}\n\
+            {\"action\": \"add_task\", \"title\": \"Update the changelog\"}\n";
+        assert_eq!(parse_actions(output), vec![
+            PluginAction::Comment { text: "Deployed to staging".to_string(), task: None },
+            PluginAction::SetTag { tag: "qa".to_string(), task: Some("API-a7x".to_string()) },
+            PluginAction::AddTask { title: "Update the changelog".to_string() },
+        ]);
+
+        assert_eq!(PluginEvent::from_transition(TaskStatus::Planned, TaskStatus::InProgress), Some(PluginEvent::Started));
+        assert_eq!(PluginEvent::from_transition(TaskStatus::NeedsWork, TaskStatus::InProgress), None);
+        assert_eq!(PluginEvent::from_transition(TaskStatus::Review, TaskStatus::Done), Some(PluginEvent::Merged));
+        assert_eq!(PluginEvent::from_transition(TaskStatus::Updating, TaskStatus::Review), None);
+
+        let task = Task::new("Fix login #auth".to_string());
+        let payload: serde_json::Value = serde_json::from_str(&PluginEvent::Created.payload("api", Path::new("/src/api"), &task)).unwrap();
+        assert_eq!(payload["event"], "created");
+        assert_eq!(payload["task"]["labels"][0], "auth");
+    }
+}
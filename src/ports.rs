@@ -0,0 +1,66 @@
+//! Ports for the dev servers of tasks running side by side
+//!
+//! Each task with a worktree gets its own block of ports, so dev servers
+//! started in different worktrees don't fight over 3000. The block's first
+//! port is exported as `PORT` to the task's Claude sessions and tmux panes,
+//! along with the whole range, and shown on the task's card. The block goes
+//! back to the pool when the task is done or its worktree goes away.
+
+use std::collections::BTreeMap;
+use std::net::TcpListener;
+
+/// First port handed out
+const FIRST_PORT: u16 = 41000;
+
+/// Ports per task
+pub const BLOCK_SIZE: u16 = 10;
+
+/// Blocks available (41000-48999)
+const BLOCKS: u16 = 800;
+
+/// Whether nothing listens on `port` on this machine
+pub fn is_free(port: u16) -> bool {
+    TcpListener::bind(("127.0.0.1", port)).is_ok()
+}
+
+/// The first port of the lowest block that no task holds (`taken`) and whose
+/// ports `is_free` says nothing else uses
+pub fn allocate(taken: &[u16], is_free: impl Fn(u16) -> bool) -> Option<u16> {
+    (0..BLOCKS)
+        .map(|block| FIRST_PORT + block * BLOCK_SIZE)
+        .filter(|first| !taken.contains(first))
+        .find(|first| (*first..*first + BLOCK_SIZE).all(&is_free))
+}
+
+/// Where the task's dev server is expected
+pub fn url(first: u16) -> String {
+    format!("http://localhost:{}", first)
+}
+
+/// The variables a task's sessions and panes get for its block
+pub fn env_vars(first: u16) -> BTreeMap<String, String> {
+    BTreeMap::from([
+        ("PORT".to_string(), first.to_string()),
+        ("KANBLAM_PORT_RANGE".to_string(), format!("{}-{}", first, first + BLOCK_SIZE - 1)),
+        ("KANBLAM_URL".to_string(), url(first)),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_allocate() {
+        assert_eq!(allocate(&[], |_| true), Some(41000));
+        assert_eq!(allocate(&[41000, 41020], |_| true), Some(41010));
+        // A block with any port in use elsewhere is passed over
+        assert_eq!(allocate(&[41000], |port| port != 41015), Some(41020));
+        assert_eq!(allocate(&[], |_| false), None);
+
+        let vars = env_vars(41010);
+        assert_eq!(vars["PORT"], "41010");
+        assert_eq!(vars["KANBLAM_PORT_RANGE"], "41010-41019");
+        assert_eq!(vars["KANBLAM_URL"], "http://localhost:41010");
+    }
+}
@@ -0,0 +1,164 @@
+//! Release notes compilation from merged tasks
+//!
+//! Collects tasks merged since a tag or date, groups them by `#label`
+//! and renders Markdown that can be written to a file, copied, or handed
+//! to the sidecar for polishing.
+
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, NaiveDate, Utc};
+use std::path::PathBuf;
+
+use crate::model::Task;
+
+pub mod version;
+
+/// File the release notes are written to (relative to the project dir)
+pub const RELEASE_NOTES_FILE: &str = "RELEASE_NOTES.md";
+
+/// Heading used for merged tasks without any label
+const UNLABELED_GROUP: &str = "Other";
+
+/// Resolve a starting point typed by the user to a timestamp.
+/// Accepts a date (`YYYY-MM-DD`, midnight UTC) or any git ref (usually a tag).
+pub fn resolve_since(project_dir: &PathBuf, since: &str) -> Result<DateTime<Utc>> {
+    let since = since.trim();
+    if since.is_empty() {
+        return Err(anyhow!("Enter a tag or a date (YYYY-MM-DD)"));
+    }
+
+    if let Ok(date) = NaiveDate::parse_from_str(since, "%Y-%m-%d") {
+        if let Some(midnight) = date.and_hms_opt(0, 0, 0) {
+            return Ok(midnight.and_utc());
+        }
+    }
+
+    crate::worktree::ref_commit_date(project_dir, since)
+}
+
+/// Compile Markdown release notes for all tasks merged after `since`.
+/// Tasks are grouped by their first label (alphabetically, unlabeled last),
+/// most recently merged first within each group. A `#other` label joins the
+/// unlabeled tasks rather than getting a second "Other" section.
+pub fn compile_markdown(project_name: &str, since_label: &str, since: DateTime<Utc>, tasks: &[Task]) -> String {
+    let mut merged: Vec<&Task> = tasks
+        .iter()
+        .filter(|t| t.merged_at.is_some_and(|at| at >= since))
+        .collect();
+    merged.sort_by_key(|t| std::cmp::Reverse(t.merged_at));
+
+    let mut groups: Vec<(String, Vec<&Task>)> = Vec::new();
+    for task in merged {
+        let group = task.labels().into_iter().next()
+            .filter(|label| !label.eq_ignore_ascii_case(UNLABELED_GROUP))
+            .unwrap_or_else(|| UNLABELED_GROUP.to_string());
+        match groups.iter_mut().find(|(name, _)| *name == group) {
+            Some((_, list)) => list.push(task),
+            None => groups.push((group, vec![task])),
+        }
+    }
+    groups.sort_by(|(a, _), (b, _)| {
+        (a == UNLABELED_GROUP).cmp(&(b == UNLABELED_GROUP)).then_with(|| a.cmp(b))
+    });
+
+    let mut out = format!("# {} release notes\n\nChanges merged since {}.\n", project_name, since_label);
+
+    if groups.is_empty() {
+        out.push_str("\nNo tasks were merged in this range.\n");
+        return out;
+    }
+
+    for (group, tasks) in groups {
+        out.push_str(&format!("\n## {}\n\n", heading_for(&group)));
+        for task in tasks {
+            let title = task.short_title.clone().unwrap_or_else(|| strip_labels(&task.title));
+            out.push_str(&format!("- {} ({})\n", title, task.display_id()));
+        }
+    }
+
+    out
+}
+
+/// Build the prompt asking the sidecar to polish raw release notes
+pub fn polish_prompt(markdown: &str) -> String {
+    format!(
+        "OUTPUT ONLY THE RELEASE NOTES. NO introduction, NO explanation.\n\n\
+         Rewrite these release notes for end users: keep the section structure, \
+         merge duplicate entries, use concise past-tense bullet points and drop the \
+         task IDs in parentheses. Reply with Markdown only.\n\n{}",
+        markdown
+    )
+}
+
+/// Turn a label into a section heading ("ui-polish" -> "Ui polish")
+fn heading_for(label: &str) -> String {
+    let words = label.replace(['-', '_'], " ");
+    let mut chars = words.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// Remove `#label` tokens from a title (first line only)
+fn strip_labels(title: &str) -> String {
+    title
+        .lines()
+        .next()
+        .unwrap_or("")
+        .split_whitespace()
+        .filter(|w| !w.starts_with('#') || w.len() == 1)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn merged_task(title: &str, merged_at: DateTime<Utc>) -> Task {
+        let mut task = Task::new(title.to_string());
+        task.merged_at = Some(merged_at);
+        task
+    }
+
+    #[test]
+    fn test_labels_parsed_from_title() {
+        let task = Task::new("Fix login #Bug #ui-polish #42 #bug".to_string());
+        assert_eq!(task.labels(), vec!["bug".to_string(), "ui-polish".to_string()]);
+    }
+
+    #[test]
+    fn test_compile_groups_by_label_and_filters_by_date() {
+        let now = Utc::now();
+        let since = now - Duration::days(7);
+        let tasks = vec![
+            merged_task("Add dark mode #feature", now),
+            merged_task("Fix crash on exit #bug", now - Duration::days(1)),
+            merged_task("Tidy README", now - Duration::days(2)),
+            merged_task("Bump deps #other", now - Duration::days(3)),
+            merged_task("Old change #feature", now - Duration::days(30)),
+            Task::new("Never merged #feature".to_string()),
+        ];
+
+        let notes = compile_markdown("demo", "v1.0.0", since, &tasks);
+
+        assert!(notes.contains("since v1.0.0"));
+        assert!(notes.contains("- Add dark mode ("));
+        assert!(!notes.contains("Old change"));
+        assert!(!notes.contains("Never merged"));
+        // Groups sorted alphabetically with unlabeled tasks last
+        let bug = notes.find("## Bug").unwrap();
+        let feature = notes.find("## Feature").unwrap();
+        let other = notes.find("## Other").unwrap();
+        assert!(bug < feature && feature < other);
+        assert_eq!(notes.matches("## Other").count(), 1);
+        assert!(notes[other..].contains("- Bump deps ("));
+    }
+
+    #[test]
+    fn test_compile_empty_range() {
+        let notes = compile_markdown("demo", "2024-01-01", Utc::now(), &[]);
+        assert!(notes.contains("No tasks were merged"));
+    }
+}
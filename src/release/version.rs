@@ -0,0 +1,386 @@
+//! Semver bumping and tagging for releases
+//!
+//! When a task labeled `release` is merged, the labels of everything merged
+//! since the last tag decide the bump (via configurable rules), the version
+//! file (Cargo.toml or package.json) is updated and an annotated tag created.
+
+use anyhow::{anyhow, Context, Result};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::timing::TimedCommand;
+
+/// Label that marks a task as a release trigger
+pub const RELEASE_LABEL: &str = "release";
+
+/// Rules used when the project doesn't configure its own
+pub const DEFAULT_VERSION_RULES: &str = "major:breaking,minor:feature|feat";
+
+/// Which part of the version to increment
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Bump {
+    Patch,
+    Minor,
+    Major,
+}
+
+impl Bump {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Bump::Patch => "patch",
+            Bump::Minor => "minor",
+            Bump::Major => "major",
+        }
+    }
+}
+
+/// Label -> bump mapping, parsed from e.g. "major:breaking,minor:feature|feat".
+/// Labels not mentioned in any rule result in a patch bump.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct VersionRules {
+    pub major: Vec<String>,
+    pub minor: Vec<String>,
+}
+
+impl VersionRules {
+    /// Parse a rules string; unknown bump names and empty labels are ignored
+    pub fn parse(rules: &str) -> Self {
+        let mut parsed = VersionRules::default();
+        for rule in rules.split(',') {
+            let Some((bump, labels)) = rule.split_once(':') else {
+                continue;
+            };
+            let labels = labels
+                .split('|')
+                .map(|l| l.trim().trim_start_matches('#').to_lowercase())
+                .filter(|l| !l.is_empty());
+            match bump.trim().to_lowercase().as_str() {
+                "major" => parsed.major.extend(labels),
+                "minor" => parsed.minor.extend(labels),
+                _ => {}
+            }
+        }
+        parsed
+    }
+
+    /// The largest bump requested by any of the labels
+    pub fn bump_for<'a>(&self, labels: impl IntoIterator<Item = &'a String>) -> Bump {
+        let mut bump = Bump::Patch;
+        for label in labels {
+            if self.major.contains(label) {
+                return Bump::Major;
+            }
+            if self.minor.contains(label) {
+                bump = Bump::Minor;
+            }
+        }
+        bump
+    }
+}
+
+/// A plain MAJOR.MINOR.PATCH version (pre-release/build suffixes are dropped)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Version {
+    pub major: u64,
+    pub minor: u64,
+    pub patch: u64,
+}
+
+impl Version {
+    /// Parse "1.2.3" or "v1.2.3" (suffixes like "-rc.1" are ignored)
+    pub fn parse(s: &str) -> Option<Self> {
+        let s = s.trim().trim_start_matches('v');
+        let core = s.split(['-', '+']).next()?;
+        let mut parts = core.split('.');
+        let major = parts.next()?.parse().ok()?;
+        let minor = parts.next().unwrap_or("0").parse().ok()?;
+        let patch = parts.next().unwrap_or("0").parse().ok()?;
+        Some(Version { major, minor, patch })
+    }
+
+    pub fn bumped(&self, bump: Bump) -> Self {
+        match bump {
+            Bump::Major => Version { major: self.major + 1, minor: 0, patch: 0 },
+            Bump::Minor => Version { major: self.major, minor: self.minor + 1, patch: 0 },
+            Bump::Patch => Version { patch: self.patch + 1, ..*self },
+        }
+    }
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+/// Everything that will happen when the release is confirmed (shown as a preview)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReleasePlan {
+    /// Version file to rewrite (relative to the project dir), if the project has one
+    pub version_file: Option<String>,
+    pub from: Version,
+    pub to: Version,
+    pub bump: Bump,
+    /// Annotated tag to create
+    pub tag: String,
+    /// Labels that decided the bump
+    pub labels: Vec<String>,
+}
+
+impl ReleasePlan {
+    /// Multi-line preview for the confirmation dialog
+    pub fn preview(&self) -> String {
+        let mut lines = vec![format!("Release {} → {} ({} bump)", self.from, self.to, self.bump.label())];
+        match self.version_file {
+            Some(ref file) => lines.push(format!("• Update version in {} and commit", file)),
+            None => lines.push("• No Cargo.toml/package.json version found - tag only".to_string()),
+        }
+        lines.push(format!("• Create annotated tag {}", self.tag));
+        if !self.labels.is_empty() {
+            lines.push(format!("• Labels since last tag: {}", self.labels.join(", ")));
+        }
+        lines.push(String::new());
+        lines.push("Proceed? (y/n)".to_string());
+        lines.join("\n")
+    }
+}
+
+/// Version files we know how to bump, in lookup order
+const VERSION_FILES: [&str; 2] = ["Cargo.toml", "package.json"];
+
+/// Find the project's version file and current version
+pub fn detect_version(project_dir: &Path) -> Option<(String, Version)> {
+    VERSION_FILES.iter().find_map(|name| {
+        let content = std::fs::read_to_string(project_dir.join(name)).ok()?;
+        let (_, version) = find_version(name, &content)?;
+        Some((name.to_string(), version))
+    })
+}
+
+/// Locate the version string in a version file: (byte range of the value, parsed version)
+fn find_version(file_name: &str, content: &str) -> Option<(std::ops::Range<usize>, Version)> {
+    let mut offset = 0;
+    let mut in_package = false;
+    for line in content.split_inclusive('\n') {
+        let trimmed = line.trim();
+        let candidate = if file_name == "Cargo.toml" {
+            if trimmed.starts_with('[') {
+                in_package = trimmed == "[package]";
+            }
+            in_package && trimmed.starts_with("version") && trimmed.contains('=')
+        } else {
+            trimmed.starts_with("\"version\"")
+        };
+
+        if candidate {
+            // The value is the first quoted string after the key/separator
+            let sep = if file_name == "Cargo.toml" { '=' } else { ':' };
+            let value_start = line.find(sep)? + 1;
+            let open = value_start + line[value_start..].find('"')? + 1;
+            let close = open + line[open..].find('"')?;
+            let version = Version::parse(&line[open..close])?;
+            return Some((offset + open..offset + close, version));
+        }
+        offset += line.len();
+    }
+    None
+}
+
+/// Rewrite the version in a version file's contents, preserving formatting
+pub fn set_version(file_name: &str, content: &str, version: Version) -> Option<String> {
+    let (range, _) = find_version(file_name, content)?;
+    let mut updated = content.to_string();
+    updated.replace_range(range, &version.to_string());
+    Some(updated)
+}
+
+/// Work out the release plan for the project.
+/// `labels` are the labels of every task merged since the last tag.
+pub fn plan_release(project_dir: &PathBuf, rules: &VersionRules, labels: Vec<String>) -> ReleasePlan {
+    let last_tag = crate::worktree::latest_tag(project_dir);
+    let tag_version = last_tag.as_deref().and_then(Version::parse);
+    let file_version = detect_version(project_dir);
+
+    // Prefer the version file; fall back to the last tag, then 0.0.0
+    let from = file_version
+        .as_ref()
+        .map(|(_, v)| *v)
+        .or(tag_version)
+        .unwrap_or_default();
+    let bump = rules.bump_for(labels.iter().filter(|l| *l != RELEASE_LABEL));
+    let to = from.bumped(bump);
+
+    // Keep the project's tag style ("v1.2.3" vs "1.2.3"), defaulting to the v prefix
+    let prefix = match last_tag {
+        Some(ref tag) if !tag.starts_with('v') && tag_version.is_some() => "",
+        _ => "v",
+    };
+
+    ReleasePlan {
+        version_file: file_version.map(|(name, _)| name),
+        from,
+        to,
+        bump,
+        tag: format!("{}{}", prefix, to),
+        labels,
+    }
+}
+
+/// The `name` of the `[package]` section of a Cargo.toml
+fn cargo_package_name(content: &str) -> Option<String> {
+    let mut in_package = false;
+    for line in content.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with('[') {
+            in_package = trimmed == "[package]";
+        } else if let Some((key, value)) = trimmed.split_once('=') {
+            if in_package && key.trim() == "name" {
+                return Some(value.trim().trim_matches('"').to_string());
+            }
+        }
+    }
+    None
+}
+
+/// Apply a release plan: bump + commit the version file (and Cargo.lock, which
+/// records the package's version too), then create the annotated tag.
+/// Only those files are committed, whatever else the user has staged.
+pub fn execute_release(project_dir: &PathBuf, plan: &ReleasePlan) -> Result<()> {
+    let before = crate::audit::rev(project_dir, "HEAD");
+    if let Some(ref file) = plan.version_file {
+        let path = project_dir.join(file);
+        let content = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", file))?;
+        let updated = set_version(file, &content, plan.to)
+            .ok_or_else(|| anyhow!("Could not find version in {}", file))?;
+        std::fs::write(&path, updated)?;
+
+        let mut files = vec![file.as_str()];
+        if file == "Cargo.toml" && is_tracked(project_dir, "Cargo.lock") {
+            if let Err(e) = update_lockfile(project_dir, &content) {
+                let _ = std::fs::write(&path, &content);
+                return Err(e);
+            }
+            files.push("Cargo.lock");
+        }
+
+        let message = format!("Release {}", plan.tag);
+        let mut args = vec!["commit", "-m", &message, "--"];
+        args.extend(files);
+        run_git(project_dir, &args)?;
+    }
+
+    run_git(project_dir, &["tag", "-a", &plan.tag, "-m", &format!("Release {}", plan.tag)])?;
+    crate::audit::record(crate::audit::AuditEntry::new("release", project_dir)
+        .with_target(&plan.tag)
+        .with_shas(before, crate::audit::rev(project_dir, "HEAD"))
+        .with_detail(format!("{} → {}", plan.from, plan.to)));
+    Ok(())
+}
+
+/// Record the bumped package version in Cargo.lock without touching its dependencies
+fn update_lockfile(project_dir: &Path, cargo_toml: &str) -> Result<()> {
+    let name = cargo_package_name(cargo_toml)
+        .ok_or_else(|| anyhow!("Could not find the package name in Cargo.toml"))?;
+    let output = Command::new("cargo")
+        .current_dir(project_dir)
+        .args(["update", "-p", &name, "--offline"])
+        .timed_output()
+        .context("Failed to run cargo to update Cargo.lock")?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(anyhow!("cargo update failed: {}", stderr.trim()));
+    }
+    Ok(())
+}
+
+fn is_tracked(project_dir: &Path, file: &str) -> bool {
+    Command::new("git")
+        .current_dir(project_dir)
+        .args(["ls-files", "--error-unmatch", file])
+        .timed_output()
+        .is_ok_and(|output| output.status.success())
+}
+
+fn run_git(project_dir: &PathBuf, args: &[&str]) -> Result<()> {
+    let output = Command::new("git")
+        .current_dir(project_dir)
+        .args(args)
+        .timed_output()?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(anyhow!("git {} failed: {}", args.first().unwrap_or(&""), stderr.trim()));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rules_pick_largest_bump() {
+        let rules = VersionRules::parse(DEFAULT_VERSION_RULES);
+        let labels = |l: &[&str]| l.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        assert_eq!(rules.bump_for(&labels(&["bug"])), Bump::Patch);
+        assert_eq!(rules.bump_for(&labels(&["bug", "feat"])), Bump::Minor);
+        assert_eq!(rules.bump_for(&labels(&["feature", "breaking"])), Bump::Major);
+    }
+
+    #[test]
+    fn test_version_parse_and_bump() {
+        let v = Version::parse("v1.4.2-rc.1").unwrap();
+        assert_eq!(v, Version { major: 1, minor: 4, patch: 2 });
+        assert_eq!(v.bumped(Bump::Patch).to_string(), "1.4.3");
+        assert_eq!(v.bumped(Bump::Minor).to_string(), "1.5.0");
+        assert_eq!(v.bumped(Bump::Major).to_string(), "2.0.0");
+    }
+
+    #[test]
+    fn test_set_version_cargo_only_touches_package() {
+        let cargo = "[package]\nname = \"x\"\nversion = \"0.1.0\"\n\n[dependencies]\nserde = { version = \"1.0\" }\n";
+        let updated = set_version("Cargo.toml", cargo, Version { major: 0, minor: 2, patch: 0 }).unwrap();
+        assert!(updated.contains("version = \"0.2.0\""));
+        assert!(updated.contains("serde = { version = \"1.0\" }"));
+    }
+
+    #[test]
+    fn test_release_commits_only_the_version_file() {
+        use crate::worktree::git::{git_stdout, test_git};
+
+        let repo = tempfile::tempdir().unwrap();
+        let dir = repo.path().to_path_buf();
+        test_git(&dir, &["init", "--quiet", "-b", "main"]);
+        test_git(&dir, &["config", "user.email", "test@test.com"]);
+        test_git(&dir, &["config", "user.name", "Test"]);
+        std::fs::write(dir.join("package.json"), "{\n  \"version\": \"1.0.0\"\n}\n").unwrap();
+        std::fs::write(dir.join("notes.txt"), "a").unwrap();
+        test_git(&dir, &["add", "."]);
+        test_git(&dir, &["commit", "--quiet", "-m", "init"]);
+        // Staged by the user, not part of the release
+        std::fs::write(dir.join("notes.txt"), "b").unwrap();
+        test_git(&dir, &["add", "notes.txt"]);
+
+        let plan = plan_release(&dir, &VersionRules::parse(DEFAULT_VERSION_RULES), vec!["bug".into()]);
+        execute_release(&dir, &plan).unwrap();
+        let committed = git_stdout(&dir, &["show", "--name-only", "--format=", "HEAD"]).unwrap();
+        assert_eq!(committed.trim(), "package.json");
+        let staged = git_stdout(&dir, &["diff", "--cached", "--name-only"]).unwrap();
+        assert_eq!(staged.trim(), "notes.txt");
+    }
+
+    #[test]
+    fn test_cargo_package_name() {
+        let cargo = "[package]\nname = \"kanblam\"\nversion = \"0.1.0\"\n\n[dependencies]\nname = \"x\"\n";
+        assert_eq!(cargo_package_name(cargo).as_deref(), Some("kanblam"));
+    }
+
+    #[test]
+    fn test_set_version_package_json() {
+        let json = "{\n  \"name\": \"x\",\n  \"version\": \"1.0.0\",\n  \"private\": true\n}\n";
+        let updated = set_version("package.json", json, Version { major: 1, minor: 0, patch: 1 }).unwrap();
+        assert!(updated.contains("\"version\": \"1.0.1\","));
+    }
+}
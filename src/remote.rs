@@ -0,0 +1,398 @@
+//! Remote projects over SSH
+//!
+//! A project opened as `user@host:/path` lives on another machine. Its
+//! directory is registered here with its SSH target, and git commands, task
+//! files and tmux panes under it are routed through one persistent SSH control
+//! connection per host, so each command skips the handshake. The connection
+//! also forwards the remote `~/.kanblam/signal.sock` to the local one, so the
+//! hooks of agents running on the remote reach this TUI.
+//!
+//! kanblam must be installed on the remote (the hooks run `kanblam signal`)
+//! and the host must accept key-based auth, since commands can't prompt.
+
+use anyhow::{bail, Context, Result};
+use std::collections::HashMap;
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::Mutex;
+
+/// Registered remote roots and the SSH target serving each
+static REMOTES: Mutex<Vec<(PathBuf, String)>> = Mutex::new(Vec::new());
+
+/// Targets whose hook signals are forwarded, with their home directory
+static CONNECTED: Mutex<Vec<(String, PathBuf)>> = Mutex::new(Vec::new());
+
+/// A remote project location: `[user@]host:/path`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RemoteSpec {
+    /// SSH destination (`user@host` or a host alias from ~/.ssh/config)
+    pub target: String,
+    /// Project directory on the remote (may start with "~" until connected)
+    pub path: PathBuf,
+}
+
+impl RemoteSpec {
+    /// Parse `[user@]host:/path` (or `host:~/path`). Local paths return None.
+    pub fn parse(input: &str) -> Option<Self> {
+        let input = input.trim();
+        if input.starts_with(['/', '~', '.']) {
+            return None;
+        }
+        let (target, path) = input.split_once(':')?;
+        let valid_target = !target.is_empty()
+            && !target.starts_with('-')
+            && !target.contains(|c: char| c == '/' || c.is_whitespace());
+        if !valid_target || !(path.starts_with('/') || path.starts_with('~')) {
+            return None;
+        }
+        Some(Self { target: target.to_string(), path: PathBuf::from(path) })
+    }
+}
+
+impl fmt::Display for RemoteSpec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.target, self.path.display())
+    }
+}
+
+/// Quote `s` for a POSIX shell
+pub fn quote(s: &str) -> String {
+    format!("'{}'", s.replace('\'', "'\\''"))
+}
+
+/// Route commands and files under `root` to `target`
+pub fn register(root: &Path, target: &str) {
+    if let Ok(mut remotes) = REMOTES.lock() {
+        remotes.retain(|(r, _)| r != root);
+        remotes.push((root.to_path_buf(), target.to_string()));
+    }
+}
+
+/// SSH target serving `path`, when it's under a remote project
+pub fn target_for(path: &Path) -> Option<String> {
+    let remotes = REMOTES.lock().ok()?;
+    remotes.iter()
+        .filter(|(root, _)| path.starts_with(root))
+        .max_by_key(|(root, _)| root.as_os_str().len())
+        .map(|(_, target)| target.clone())
+}
+
+pub fn is_remote(path: &Path) -> bool {
+    target_for(path).is_some()
+}
+
+/// Options for commands riding on the host's control connection. They never
+/// start one themselves (a backgrounded master would hold their pipes open)
+/// and connect directly while `connect` hasn't run.
+fn ssh_options() -> Vec<String> {
+    let control_path = dirs::home_dir()
+        .unwrap_or_else(|| PathBuf::from("/tmp"))
+        .join(".kanblam")
+        .join("ssh-%C");
+    vec![
+        "-o".to_string(), "ControlMaster=no".to_string(),
+        "-o".to_string(), format!("ControlPath={}", control_path.display()),
+        "-o".to_string(), "BatchMode=yes".to_string(),
+        "-o".to_string(), "ConnectTimeout=10".to_string(),
+    ]
+}
+
+fn ssh() -> Command {
+    let mut command = Command::new("ssh");
+    command.args(ssh_options());
+    command
+}
+
+/// Run `script` on `target` through its control connection
+fn run(target: &str, script: &str) -> io::Result<std::process::Output> {
+    ssh().arg(target).arg("--").arg(script).stdin(Stdio::null()).output()
+}
+
+/// The SSH command running `command` on the remote, when its working
+/// directory is under a remote project. Stdio isn't carried over.
+pub fn over_ssh(command: &Command) -> Option<Command> {
+    let dir = command.get_current_dir()?;
+    let target = target_for(dir)?;
+
+    let mut script = format!("cd {} &&", quote(&dir.to_string_lossy()));
+    let envs: Vec<String> = command.get_envs()
+        .filter_map(|(key, value)| Some(format!("{}={}", key.to_string_lossy(), quote(&value?.to_string_lossy()))))
+        .collect();
+    if !envs.is_empty() {
+        script.push_str(" env ");
+        script.push_str(&envs.join(" "));
+    }
+    script.push(' ');
+    script.push_str(&quote(&command.get_program().to_string_lossy()));
+    for arg in command.get_args() {
+        script.push(' ');
+        script.push_str(&quote(&arg.to_string_lossy()));
+    }
+
+    let mut ssh = ssh();
+    ssh.arg(target).arg("--").arg(script);
+    Some(ssh)
+}
+
+/// `command`, or the SSH command running it on the remote
+pub fn routed(command: Command) -> Command {
+    over_ssh(&command).unwrap_or(command)
+}
+
+/// Shell command for a tmux pane in remote `dir` (tmux itself runs locally):
+/// `command` over SSH, or a login shell when it's None. None for local dirs.
+pub fn pane_command(dir: &Path, command: Option<&str>) -> Option<String> {
+    let target = target_for(dir)?;
+    let script = format!(
+        "cd {} && {}",
+        quote(&dir.to_string_lossy()),
+        command.unwrap_or("exec \"$SHELL\" -l"),
+    );
+    let options: Vec<String> = ssh_options().iter().map(|o| quote(o)).collect();
+    Some(format!("ssh -t {} {} -- {}", options.join(" "), quote(&target), quote(&script)))
+}
+
+fn master_running(target: &str) -> bool {
+    ssh().args(["-O", "check"]).arg(target)
+        .stdin(Stdio::null()).stdout(Stdio::null()).stderr(Stdio::null())
+        .status()
+        .is_ok_and(|status| status.success())
+}
+
+/// Start the control connection to `target` (unless one is up) and forward
+/// its hook signal socket here. Returns the remote home directory.
+fn start(target: &str) -> Result<PathBuf> {
+    if !master_running(target) {
+        // The backgrounded master keeps its stdio open, so nothing can be read from it.
+        // The first value of an option wins, so these override `ControlMaster=no`.
+        let status = Command::new("ssh")
+            .args(["-o", "ControlMaster=yes", "-o", "ControlPersist=yes", "-N", "-f"])
+            .args(ssh_options())
+            .arg(target)
+            .stdin(Stdio::null()).stdout(Stdio::null()).stderr(Stdio::null())
+            .status()
+            .context("Failed to run ssh")?;
+        if !status.success() {
+            bail!("Could not connect to {} over SSH (key-based auth is required)", target);
+        }
+    }
+
+    // Clear a socket left by an earlier connection, or the forward can't bind
+    let output = run(target, "mkdir -p ~/.kanblam && rm -f ~/.kanblam/signal.sock && echo \"$HOME\"")?;
+    if !output.status.success() {
+        bail!("{}: {}", target, String::from_utf8_lossy(&output.stderr).trim());
+    }
+    let home = PathBuf::from(String::from_utf8_lossy(&output.stdout).trim());
+
+    let local_socket = crate::hooks::socket_path()?;
+    let forward = format!("{}:{}", home.join(".kanblam").join("signal.sock").display(), local_socket.display());
+    let output = ssh().args(["-O", "forward", "-R", &forward]).arg(target)
+        .stdin(Stdio::null())
+        .output()?;
+    if !output.status.success() {
+        bail!("Failed to forward hook signals from {}: {}", target, String::from_utf8_lossy(&output.stderr).trim());
+    }
+
+    if let Ok(mut connected) = CONNECTED.lock() {
+        connected.retain(|(t, _)| t != target);
+        connected.push((target.to_string(), home.clone()));
+    }
+    Ok(home)
+}
+
+/// Open (or reuse) the control connection to `spec.target`, forward hook
+/// signals back, and register the project. Returns the spec with "~" expanded.
+pub fn connect(spec: &RemoteSpec) -> Result<RemoteSpec> {
+    let forwarded = CONNECTED.lock().ok()
+        .and_then(|connected| connected.iter().find(|(t, _)| *t == spec.target).map(|(_, home)| home.clone()));
+    let home = match forwarded {
+        Some(home) if master_running(&spec.target) => home,
+        _ => start(&spec.target)?,
+    };
+    let path = match spec.path.strip_prefix("~") {
+        Ok(rest) => home.join(rest),
+        Err(_) => spec.path.clone(),
+    };
+
+    let output = run(&spec.target, &format!("test -d {}", quote(&path.to_string_lossy())))?;
+    if !output.status.success() {
+        bail!("{}:{} is not a directory", spec.target, path.display());
+    }
+
+    register(&path, &spec.target);
+    Ok(RemoteSpec { target: spec.target.clone(), path })
+}
+
+/// Close the control connections (and with them the signal forwards)
+pub fn disconnect_all() {
+    let targets: Vec<String> = match CONNECTED.lock() {
+        Ok(mut connected) => connected.drain(..).map(|(target, _)| target).collect(),
+        Err(_) => return,
+    };
+    for target in targets {
+        let _ = ssh().args(["-O", "exit"]).arg(&target)
+            .stdin(Stdio::null()).stdout(Stdio::null()).stderr(Stdio::null())
+            .status();
+    }
+}
+
+fn remote_io(target: &str, script: String, input: Option<&[u8]>) -> io::Result<Vec<u8>> {
+    let mut child = ssh().arg(target).arg("--").arg(script)
+        .stdin(if input.is_some() { Stdio::piped() } else { Stdio::null() })
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    if let (Some(input), Some(mut stdin)) = (input, child.stdin.take()) {
+        io::Write::write_all(&mut stdin, input)?;
+    }
+    let output = child.wait_with_output()?;
+    if output.status.success() {
+        Ok(output.stdout)
+    } else {
+        Err(io::Error::other(String::from_utf8_lossy(&output.stderr).trim().to_string()))
+    }
+}
+
+fn path_arg(path: &Path) -> String {
+    quote(&path.to_string_lossy())
+}
+
+/// Read `name` in the home directory of the machine holding `dir`.
+/// None when it doesn't exist.
+pub fn read_home_file(dir: &Path, name: &str) -> io::Result<Option<String>> {
+    match target_for(dir) {
+        Some(target) => {
+            let bytes = remote_io(&target, format!("cat ~/{} 2>/dev/null || true", quote(name)), None)?;
+            let content = String::from_utf8(bytes).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+            Ok((!content.is_empty()).then_some(content))
+        }
+        None => {
+            let path = dirs::home_dir().unwrap_or_else(|| PathBuf::from(".")).join(name);
+            if path.exists() { std::fs::read_to_string(path).map(Some) } else { Ok(None) }
+        }
+    }
+}
+
+/// Write `name` in the home directory of the machine holding `dir`
+pub fn write_home_file(dir: &Path, name: &str, contents: &str) -> io::Result<()> {
+    match target_for(dir) {
+        Some(target) => remote_io(&target, format!("cat > ~/{}", quote(name)), Some(contents.as_bytes())).map(|_| ()),
+        None => std::fs::write(dirs::home_dir().unwrap_or_else(|| PathBuf::from(".")).join(name), contents),
+    }
+}
+
+/// `std::fs::read_to_string` that reads remote project files over SSH
+pub fn read_to_string(path: &Path) -> io::Result<String> {
+    match target_for(path) {
+        Some(target) => {
+            let bytes = remote_io(&target, format!("cat {}", path_arg(path)), None)?;
+            String::from_utf8(bytes).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+        }
+        None => std::fs::read_to_string(path),
+    }
+}
+
+/// `std::fs::write` for local or remote paths
+pub fn write(path: &Path, contents: impl AsRef<[u8]>) -> io::Result<()> {
+    match target_for(path) {
+        Some(target) => remote_io(&target, format!("cat > {}", path_arg(path)), Some(contents.as_ref())).map(|_| ()),
+        None => std::fs::write(path, contents),
+    }
+}
+
+/// `std::fs::create_dir_all` for local or remote paths
+pub fn create_dir_all(path: &Path) -> io::Result<()> {
+    match target_for(path) {
+        Some(target) => remote_io(&target, format!("mkdir -p {}", path_arg(path)), None).map(|_| ()),
+        None => std::fs::create_dir_all(path),
+    }
+}
+
+/// `std::fs::remove_dir_all` for local or remote paths
+pub fn remove_dir_all(path: &Path) -> io::Result<()> {
+    match target_for(path) {
+        Some(target) => remote_io(&target, format!("rm -rf {}", path_arg(path)), None).map(|_| ()),
+        None => std::fs::remove_dir_all(path),
+    }
+}
+
+/// `std::fs::copy` within one machine (sizes aren't reported for remote copies)
+pub fn copy(from: &Path, to: &Path) -> io::Result<u64> {
+    match target_for(from) {
+        Some(target) => remote_io(&target, format!("cp {} {}", path_arg(from), path_arg(to)), None).map(|_| 0),
+        None => std::fs::copy(from, to),
+    }
+}
+
+/// `Path::exists` for local or remote paths
+pub fn exists(path: &Path) -> bool {
+    match target_for(path) {
+        Some(target) => remote_io(&target, format!("test -e {}", path_arg(path)), None).is_ok(),
+        None => path.exists(),
+    }
+}
+
+/// `exists` for each of `paths`, with one ssh round trip per host
+pub fn exists_all(paths: &[PathBuf]) -> Vec<bool> {
+    let mut found: Vec<bool> = paths.iter().map(|path| !is_remote(path) && path.exists()).collect();
+    let mut by_target: HashMap<String, Vec<usize>> = HashMap::new();
+    for (index, path) in paths.iter().enumerate() {
+        if let Some(target) = target_for(path) {
+            by_target.entry(target).or_default().push(index);
+        }
+    }
+    for (target, indices) in by_target {
+        let args: Vec<_> = indices.iter().map(|&i| path_arg(&paths[i])).collect();
+        let script = format!("for p in {}; do test -e \"$p\" && echo 1 || echo 0; done", args.join(" "));
+        if let Ok(stdout) = remote_io(&target, script, None) {
+            for (&index, line) in indices.iter().zip(String::from_utf8_lossy(&stdout).lines()) {
+                found[index] = line == "1";
+            }
+        }
+    }
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_remote_spec() {
+        let spec = RemoteSpec::parse("dev@build-box:/srv/api").unwrap();
+        assert_eq!(spec.target, "dev@build-box");
+        assert_eq!(spec.path, PathBuf::from("/srv/api"));
+        assert_eq!(spec.to_string(), "dev@build-box:/srv/api");
+        assert_eq!(RemoteSpec::parse("box:~/src/web").unwrap().path, PathBuf::from("~/src/web"));
+
+        for local in ["/home/me/api", "~/api", "./api", "api", "box:relative", "-oProxyCommand=x:/srv", "a/b:/srv"] {
+            assert_eq!(RemoteSpec::parse(local), None, "{}", local);
+        }
+    }
+
+    #[test]
+    fn test_commands_under_remote_roots_go_over_ssh() {
+        register(Path::new("/srv/remote-test"), "dev@box");
+        assert_eq!(quote("it's"), "'it'\\''s'");
+
+        let mut git = Command::new("git");
+        git.current_dir("/srv/remote-test/worktrees/T-1").args(["commit", "-m", "it's done"]);
+        let ssh = over_ssh(&git).unwrap();
+        let args: Vec<String> = ssh.get_args().map(|a| a.to_string_lossy().to_string()).collect();
+        assert_eq!(ssh.get_program(), "ssh");
+        assert_eq!(&args[args.len() - 3..], [
+            "dev@box",
+            "--",
+            "cd '/srv/remote-test/worktrees/T-1' && 'git' 'commit' '-m' 'it'\\''s done'",
+        ]);
+
+        // Sibling paths with the same prefix stay local
+        let mut local = Command::new("git");
+        local.current_dir("/srv/remote-test-2");
+        assert!(over_ssh(&local).is_none());
+        assert!(pane_command(Path::new("/srv/remote-test-2"), None).is_none());
+        assert!(pane_command(Path::new("/srv/remote-test"), Some("claude")).unwrap().ends_with("'cd '\\''/srv/remote-test'\\'' && claude'"));
+    }
+}
@@ -0,0 +1,334 @@
+//! Board automation rules written in Lua (`~/.config/kanblam/rules.lua`)
+//!
+//! Rules register handlers for task events and answer them with actions:
+//!
+//!   kanblam.on("review", function(task, from)
+//!     if task.tags.hotfix then
+//!       kanblam.apply(task)
+//!       kanblam.run_tests(task)
+//!     end
+//!   end)
+//!
+//! Events are "created" and the column a task moves into: "planned",
+//! "in_progress", "testing", "needs_work", "review" and "done". Moves within
+//! the Review column (accepting, applying, updating) aren't events. Handlers
+//! get the task and the column it came from (nil for "created").
+//!
+//! A task is a table of `id`, `display_id`, `title`, `status`, `tags` (a set,
+//! e.g. `task.tags.hotfix`), `branch`, `worktree`, `auto_merge` and `project`.
+//! `kanblam.project` is the event's project: `name`, `dir` and `tasks`.
+//!
+//! Actions take a task (or its id) and run after the handlers return:
+//! `apply`, `merge`, `start`, `move(task, column)`, `feedback(task, text)`,
+//! `tag(task, name)`, `comment(task, text)`, `run_tests(task)` and
+//! `run(task, command)` (in the task's worktree), plus `notify(text)`.
+//! The file is reloaded when it changes.
+
+use mlua::{Function, HookTriggers, Lua, Table, Value};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{Duration, Instant, SystemTime};
+use uuid::Uuid;
+
+use crate::model::{LifecycleEvent, Project, Task, TaskStatus};
+
+/// How long the handlers of one batch of events may run
+const TIME_BUDGET: Duration = Duration::from_millis(250);
+
+/// Characters of a command's output kept in the activity log
+const OUTPUT_TAIL_CHARS: usize = 8000;
+
+/// Sets up the `kanblam` table; actions are added from Rust
+const PRELUDE: &str = r#"
+kanblam = { handlers = {} }
+function kanblam.on(event, handler)
+  assert(type(handler) == "function", "kanblam.on: handler must be a function")
+  kanblam.handlers[event] = kanblam.handlers[event] or {}
+  table.insert(kanblam.handlers[event], handler)
+end
+"#;
+
+/// A task entering a column (or being created), for rules to react to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuleEvent {
+    pub project_idx: usize,
+    pub task_id: Uuid,
+    /// None when the task was just created
+    pub from: Option<TaskStatus>,
+}
+
+/// Something a rule asked for
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RuleAction {
+    Apply(Uuid),
+    Merge(Uuid),
+    Start(Uuid),
+    Move(Uuid, TaskStatus),
+    Feedback(Uuid, String),
+    Tag(Uuid, String),
+    Comment(Uuid, String),
+    /// Run a command in the task's worktree (None = the project's tests)
+    Run(Uuid, Option<String>),
+    Notify(String),
+}
+
+/// Column name used in rules
+pub fn status_name(status: TaskStatus) -> &'static str {
+    match status {
+        TaskStatus::Planned => "planned",
+        TaskStatus::InProgress => "in_progress",
+        TaskStatus::Testing => "testing",
+        TaskStatus::NeedsWork => "needs_work",
+        TaskStatus::Review | TaskStatus::Accepting | TaskStatus::Updating | TaskStatus::Applying => "review",
+        TaskStatus::Done => "done",
+    }
+}
+
+fn parse_status(name: &str) -> Option<TaskStatus> {
+    TaskStatus::all().into_iter().find(|s| status_name(*s) == name)
+}
+
+impl RuleEvent {
+    /// The event's name, or None for moves that aren't events
+    pub fn name(&self, to: TaskStatus) -> Option<&'static str> {
+        match self.from {
+            None => Some("created"),
+            Some(from) => LifecycleEvent::from_transition(from, to).map(|_| status_name(to)),
+        }
+    }
+}
+
+/// The rules file and the Lua state it was last loaded into
+pub struct RuleEngine {
+    path: PathBuf,
+    /// When the loaded version was modified, and its state (None if it failed to load)
+    loaded: Option<(SystemTime, Option<Lua>)>,
+}
+
+impl RuleEngine {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path, loaded: None }
+    }
+
+    /// `~/.config/kanblam/rules.lua`
+    pub fn default_path() -> Option<PathBuf> {
+        dirs::home_dir().map(|home| home.join(".config").join("kanblam").join("rules.lua"))
+    }
+
+    /// Load the file again if it changed (or drop the rules if it's gone)
+    fn refresh(&mut self) -> mlua::Result<()> {
+        let Ok(modified) = std::fs::metadata(&self.path).and_then(|m| m.modified()) else {
+            self.loaded = None;
+            return Ok(());
+        };
+        if self.loaded.as_ref().is_some_and(|(at, _)| *at == modified) {
+            return Ok(());
+        }
+        // Remember the attempt so a broken file is reported once, not on every event
+        self.loaded = Some((modified, None));
+        let source = std::fs::read_to_string(&self.path).map_err(mlua::Error::external)?;
+        self.loaded = Some((modified, Some(load(&source, &self.path)?)));
+        Ok(())
+    }
+
+    /// Run the handlers of `events` and collect the actions they asked for,
+    /// along with any errors (a failing handler doesn't stop the others)
+    pub fn evaluate(&mut self, projects: &[Project], events: &[RuleEvent]) -> (Vec<RuleAction>, Vec<String>) {
+        let mut errors = Vec::new();
+        if let Err(e) = self.refresh() {
+            errors.push(format!("{}: {}", self.path.display(), e));
+        }
+        let Some((_, Some(lua))) = &self.loaded else {
+            return (Vec::new(), errors);
+        };
+        lua.set_app_data(Vec::<RuleAction>::new());
+        lua.set_app_data(Instant::now() + TIME_BUDGET);
+        for event in events {
+            if let Err(e) = dispatch(lua, projects, event) {
+                errors.push(e.to_string());
+            }
+        }
+        let actions = lua.remove_app_data::<Vec<RuleAction>>().unwrap_or_default();
+        (actions, errors)
+    }
+}
+
+/// A Lua state with the `kanblam` API and the rules in `source` run
+fn load(source: &str, path: &Path) -> mlua::Result<Lua> {
+    let lua = Lua::new();
+    lua.load(PRELUDE).set_name("prelude").exec()?;
+    {
+        let api: Table = lua.globals().get("kanblam")?;
+        api.set("apply", task_action(&lua, RuleAction::Apply)?)?;
+        api.set("merge", task_action(&lua, RuleAction::Merge)?)?;
+        api.set("start", task_action(&lua, RuleAction::Start)?)?;
+        api.set("run_tests", task_action(&lua, |id| RuleAction::Run(id, None))?)?;
+        api.set("move", lua.create_function(|lua, (task, column): (Value, String)| {
+            let status = parse_status(&column)
+                .ok_or_else(|| mlua::Error::runtime(format!("kanblam.move: unknown column \"{}\"", column)))?;
+            push(lua, RuleAction::Move(task_id(task)?, status))
+        })?)?;
+        api.set("feedback", text_action(&lua, RuleAction::Feedback)?)?;
+        api.set("tag", text_action(&lua, RuleAction::Tag)?)?;
+        api.set("comment", text_action(&lua, RuleAction::Comment)?)?;
+        api.set("run", text_action(&lua, |id, command| RuleAction::Run(id, Some(command)))?)?;
+        api.set("notify", lua.create_function(|lua, text: String| push(lua, RuleAction::Notify(text)))?)?;
+    }
+
+    // Stop runaway handlers instead of freezing the board
+    lua.set_hook(HookTriggers::new().every_nth_instruction(10_000), |lua, _| {
+        match lua.app_data_ref::<Instant>() {
+            Some(deadline) if Instant::now() > *deadline => Err(mlua::Error::runtime("rules took too long")),
+            _ => Ok(()),
+        }
+    });
+    lua.set_app_data(Instant::now() + TIME_BUDGET);
+    lua.load(source).set_name(path.display().to_string()).exec()?;
+    Ok(lua)
+}
+
+/// An action taking just a task
+fn task_action(lua: &Lua, make: fn(Uuid) -> RuleAction) -> mlua::Result<Function<'_>> {
+    lua.create_function(move |lua, task: Value| push(lua, make(task_id(task)?)))
+}
+
+/// An action taking a task and some text
+fn text_action(lua: &Lua, make: fn(Uuid, String) -> RuleAction) -> mlua::Result<Function<'_>> {
+    lua.create_function(move |lua, (task, text): (Value, String)| push(lua, make(task_id(task)?, text)))
+}
+
+fn push(lua: &Lua, action: RuleAction) -> mlua::Result<()> {
+    if let Some(mut actions) = lua.app_data_mut::<Vec<RuleAction>>() {
+        actions.push(action);
+    }
+    Ok(())
+}
+
+/// Run `command` with `sh` in `dir`. Returns the end of its output, as an
+/// error if it failed. Blocks until it's done, so call it off the UI thread.
+pub fn run_command(dir: &Path, command: &str) -> Result<String, String> {
+    let mut cmd = Command::new("sh");
+    cmd.args(["-c", command]).current_dir(dir);
+    let output = crate::remote::routed(cmd).output().map_err(|e| format!("Couldn't run sh: {}", e))?;
+    let text = format!("{}{}", String::from_utf8_lossy(&output.stdout), String::from_utf8_lossy(&output.stderr));
+    let skip = text.chars().count().saturating_sub(OUTPUT_TAIL_CHARS);
+    let tail: String = text.chars().skip(skip).collect();
+    if output.status.success() { Ok(tail) } else { Err(tail) }
+}
+
+/// A task argument: the task table or its id
+fn task_id(task: Value) -> mlua::Result<Uuid> {
+    let id: String = match task {
+        Value::Table(table) => table.get("id")?,
+        Value::String(id) => id.to_str()?.to_string(),
+        _ => return Err(mlua::Error::runtime("expected a task or task id")),
+    };
+    Uuid::parse_str(&id).map_err(|_| mlua::Error::runtime(format!("not a task id: {}", id)))
+}
+
+fn task_table<'lua>(lua: &'lua Lua, project: &Project, task: &Task) -> mlua::Result<Table<'lua>> {
+    let table = lua.create_table()?;
+    table.set("id", task.id.to_string())?;
+    table.set("display_id", task.display_id())?;
+    table.set("title", task.title.as_str())?;
+    table.set("status", status_name(task.status))?;
+    let tags = lua.create_table()?;
+    for label in task.labels() {
+        tags.set(label, true)?;
+    }
+    table.set("tags", tags)?;
+    table.set("branch", task.git_branch.as_deref())?;
+    table.set("worktree", task.worktree_path.as_ref().map(|p| p.display().to_string()))?;
+    table.set("auto_merge", task.auto_merge)?;
+    table.set("project", project.name.as_str())?;
+    Ok(table)
+}
+
+/// Call the handlers of one event
+fn dispatch(lua: &Lua, projects: &[Project], event: &RuleEvent) -> mlua::Result<()> {
+    let Some(project) = projects.get(event.project_idx) else {
+        return Ok(());
+    };
+    let Some(task) = project.tasks.iter().find(|t| t.id == event.task_id) else {
+        return Ok(());
+    };
+    let Some(name) = event.name(task.status) else {
+        return Ok(());
+    };
+    let api: Table = lua.globals().get("kanblam")?;
+    let handlers: Option<Table> = api.get::<_, Table>("handlers")?.get(name)?;
+    let Some(handlers) = handlers else {
+        return Ok(());
+    };
+
+    let snapshot = lua.create_table()?;
+    snapshot.set("name", project.name.as_str())?;
+    snapshot.set("dir", project.working_dir.display().to_string())?;
+    let tasks = lua.create_table()?;
+    for (idx, t) in project.tasks.iter().enumerate() {
+        tasks.set(idx + 1, task_table(lua, project, t)?)?;
+    }
+    snapshot.set("tasks", tasks)?;
+    api.set("project", snapshot)?;
+
+    let from = event.from.map(status_name);
+    for handler in handlers.sequence_values::<Function>() {
+        handler?.call::<_, ()>((task_table(lua, project, task)?, from))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rules_turn_events_into_actions() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("rules.lua");
+        std::fs::write(&path, r#"
+            kanblam.on("review", function(task, from)
+              if task.tags.hotfix then
+                kanblam.apply(task)
+                kanblam.run_tests(task.id)
+              end
+              kanblam.notify(task.display_id .. " from " .. from .. " of " .. #kanblam.project.tasks)
+            end)
+            kanblam.on("created", function(task) kanblam.move(task, "in_progress") end)
+            kanblam.on("done", function() while true do end end)
+        "#).unwrap();
+
+        let mut project = Project::new("api".to_string(), "/src/api".into());
+        let mut hotfix = Task::new("Fix login #hotfix".to_string());
+        hotfix.status = TaskStatus::Review;
+        let mut done = Task::new("Ship it".to_string());
+        done.status = TaskStatus::Done;
+        let ids = [hotfix.id, done.id];
+        project.tasks = vec![hotfix, done];
+        let projects = vec![project];
+
+        let mut engine = RuleEngine::new(path);
+        let events = [
+            RuleEvent { project_idx: 0, task_id: ids[0], from: Some(TaskStatus::InProgress) },
+            // Applying -> Review stays in the column: no event
+            RuleEvent { project_idx: 0, task_id: ids[0], from: Some(TaskStatus::Applying) },
+            RuleEvent { project_idx: 0, task_id: ids[1], from: None },
+        ];
+        let (actions, errors) = engine.evaluate(&projects, &events);
+        assert!(errors.is_empty(), "{:?}", errors);
+        let display_id = projects[0].tasks[0].display_id();
+        assert_eq!(actions, vec![
+            RuleAction::Apply(ids[0]),
+            RuleAction::Run(ids[0], None),
+            RuleAction::Notify(format!("{} from in_progress of 2", display_id)),
+            RuleAction::Move(ids[1], TaskStatus::InProgress),
+        ]);
+
+        // A runaway handler is stopped and reported
+        let (actions, errors) = engine.evaluate(&projects, &[
+            RuleEvent { project_idx: 0, task_id: ids[1], from: Some(TaskStatus::Review) },
+        ]);
+        assert!(actions.is_empty());
+        assert!(errors[0].contains("took too long"));
+    }
+}
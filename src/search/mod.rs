@@ -0,0 +1,125 @@
+//! Project-wide code search for task creation
+//!
+//! Searches the main checkout with ripgrep (falling back to `git grep` when
+//! `rg` isn't installed) and turns selected matches into `file:line`
+//! references for a new task's description.
+
+use anyhow::{anyhow, Result};
+use std::path::PathBuf;
+use std::process::Command;
+
+use crate::timing::TimedCommand;
+
+pub mod todo;
+
+/// Stop collecting after this many matches (keeps the modal responsive)
+pub const MAX_RESULTS: usize = 500;
+
+/// Longest snippet kept per match
+const MAX_SNIPPET_CHARS: usize = 160;
+
+/// A single match location in the main checkout
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchMatch {
+    /// Path relative to the project dir
+    pub path: String,
+    pub line: usize,
+    pub column: usize,
+    /// The matching line, trimmed
+    pub text: String,
+}
+
+impl SearchMatch {
+    /// "path:line" reference used in task descriptions
+    pub fn location(&self) -> String {
+        format!("{}:{}", self.path, self.line)
+    }
+}
+
+/// Search the project for `query` (a regex, smart-case).
+/// Worktrees and gitignored files are skipped.
+pub fn search(project_dir: &PathBuf, query: &str) -> Result<Vec<SearchMatch>> {
+    if query.trim().is_empty() {
+        return Err(anyhow!("Enter a search pattern"));
+    }
+
+    let output = match Command::new("rg")
+        .current_dir(project_dir)
+        .args(["--vimgrep", "--smart-case", "--max-columns", "500", "--glob", "!worktrees/", "-e", query])
+        .output()
+    {
+        Ok(output) => output,
+        // No ripgrep - git grep gives the same path:line:column:text format
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Command::new("git")
+            .current_dir(project_dir)
+            .args(["grep", "-n", "--column", "-I", "-E", "-e", query, "--", ".", ":!worktrees"])
+            .timed_output()?,
+        Err(e) => return Err(e.into()),
+    };
+
+    // Exit code 1 means "no matches" for both tools
+    if !output.status.success() && output.status.code() != Some(1) {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(anyhow!("Search failed: {}", stderr.trim()));
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut results: Vec<SearchMatch> = stdout
+        .lines()
+        .filter_map(parse_match_line)
+        .take(MAX_RESULTS)
+        .collect();
+    results.sort_by(|a, b| a.path.cmp(&b.path).then(a.line.cmp(&b.line)));
+    Ok(results)
+}
+
+/// Parse a `path:line:column:text` line (ripgrep --vimgrep / git grep --column)
+fn parse_match_line(line: &str) -> Option<SearchMatch> {
+    let mut parts = line.splitn(4, ':');
+    let path = parts.next()?.trim_start_matches("./").to_string();
+    let line_no = parts.next()?.parse().ok()?;
+    let column = parts.next()?.parse().ok()?;
+    let text: String = parts.next().unwrap_or("").trim().chars().take(MAX_SNIPPET_CHARS).collect();
+    Some(SearchMatch { path, line: line_no, column, text })
+}
+
+/// Task description listing the selected locations.
+/// The first line is left empty for the user to type the instruction.
+pub fn task_description(query: &str, matches: &[&SearchMatch]) -> String {
+    let mut out = format!("\n\nLocations matching `{}`:\n", query);
+    for m in matches {
+        out.push_str(&format!("- {} `{}`\n", m.location(), m.text));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_match_line_keeps_colons_in_text() {
+        let m = parse_match_line("./src/app.rs:42:9:    let x = a::b(c);").unwrap();
+        assert_eq!(m.path, "src/app.rs");
+        assert_eq!(m.line, 42);
+        assert_eq!(m.column, 9);
+        assert_eq!(m.text, "let x = a::b(c);");
+        assert_eq!(m.location(), "src/app.rs:42");
+    }
+
+    #[test]
+    fn test_parse_match_line_rejects_garbage() {
+        assert!(parse_match_line("Binary file matches").is_none());
+        assert!(parse_match_line("src/app.rs:x:1:text").is_none());
+    }
+
+    #[test]
+    fn test_task_description_lists_locations() {
+        let a = parse_match_line("src/a.rs:1:1:foo()").unwrap();
+        let b = parse_match_line("src/b.rs:7:3:bar(foo)").unwrap();
+        let desc = task_description("foo", &[&a, &b]);
+        assert!(desc.starts_with("\n\n"));
+        assert!(desc.contains("- src/a.rs:1 `foo()`"));
+        assert!(desc.contains("- src/b.rs:7 `bar(foo)`"));
+    }
+}
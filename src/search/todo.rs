@@ -0,0 +1,153 @@
+//! TODO/FIXME/HACK comment scanner
+//!
+//! Finds marker comments in the main checkout (gitignored files are skipped
+//! by the search backend), dates each one with `git blame`, and formats
+//! task titles that link back to the source location.
+
+use anyhow::Result;
+use chrono::{DateTime, TimeZone, Utc};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::process::Command;
+
+use super::SearchMatch;
+use crate::timing::TimedCommand;
+
+/// Comment markers the scanner looks for
+pub const MARKERS: [&str; 3] = ["TODO", "FIXME", "HACK"];
+
+/// A marker comment found in the repo
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TodoItem {
+    pub location: SearchMatch,
+    /// Which marker matched ("TODO", "FIXME" or "HACK")
+    pub marker: String,
+    /// Comment text after the marker
+    pub text: String,
+    /// When the line was last changed (None for uncommitted lines)
+    pub authored_at: Option<DateTime<Utc>>,
+}
+
+impl TodoItem {
+    /// Title for a Planned task created from this item
+    pub fn task_title(&self) -> String {
+        let text = if self.text.is_empty() { "(no description)" } else { &self.text };
+        format!("{}: {} ({})", self.marker, text, self.location.location())
+    }
+
+    /// Age in whole days, if known
+    pub fn age_days(&self, now: DateTime<Utc>) -> Option<i64> {
+        self.authored_at.map(|at| (now - at).num_days())
+    }
+}
+
+/// Scan the project for marker comments, oldest-changed information included
+pub fn scan(project_dir: &PathBuf) -> Result<Vec<TodoItem>> {
+    let pattern = format!(r"\b({})\b", MARKERS.join("|"));
+    let matches = super::search(project_dir, &pattern)?;
+
+    let mut items: Vec<TodoItem> = matches.into_iter().filter_map(parse_item).collect();
+
+    // One blame per file rather than per line
+    let mut dates: HashMap<String, HashMap<usize, DateTime<Utc>>> = HashMap::new();
+    for item in &mut items {
+        let file_dates = dates
+            .entry(item.location.path.clone())
+            .or_insert_with(|| blame_dates(project_dir, &item.location.path));
+        item.authored_at = file_dates.get(&item.location.line).copied();
+    }
+
+    Ok(items)
+}
+
+/// Split a matched line into marker + comment text
+fn parse_item(location: SearchMatch) -> Option<TodoItem> {
+    let (idx, marker) = MARKERS
+        .iter()
+        .filter_map(|m| location.text.find(m).map(|idx| (idx, *m)))
+        .min_by_key(|(idx, _)| *idx)?;
+
+    // "TODO(alice): fix this" / "FIXME - fix this" -> "fix this"
+    let after = &location.text[idx + marker.len()..];
+    let after = match after.strip_prefix('(') {
+        Some(rest) => rest.split_once(')').map(|(_, r)| r).unwrap_or(rest),
+        None => after,
+    };
+    let text = after
+        .trim_start_matches([':', '-', ' ', '!'])
+        .trim_end_matches("*/")
+        .trim_end_matches("-->")
+        .trim()
+        .to_string();
+
+    Some(TodoItem {
+        marker: marker.to_string(),
+        text,
+        authored_at: None,
+        location,
+    })
+}
+
+/// Line number -> author time for every committed line of a file
+fn blame_dates(project_dir: &PathBuf, path: &str) -> HashMap<usize, DateTime<Utc>> {
+    let mut dates = HashMap::new();
+    let Ok(output) = Command::new("git")
+        .current_dir(project_dir)
+        .args(["blame", "--line-porcelain", "--", path])
+        .timed_output()
+    else {
+        return dates;
+    };
+    if !output.status.success() {
+        return dates;
+    }
+
+    // Each line's block starts with "<sha> <orig_line> <final_line> [<count>]"
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut current_line: Option<usize> = None;
+    for line in stdout.lines() {
+        let mut parts = line.split(' ');
+        let first = parts.next().unwrap_or("");
+        if first.len() == 40 && first.chars().all(|c| c.is_ascii_hexdigit()) {
+            current_line = parts.nth(1).and_then(|n| n.parse().ok());
+            // Uncommitted lines are attributed to the all-zero sha
+            if first.chars().all(|c| c == '0') {
+                current_line = None;
+            }
+        } else if let (Some(line_no), Some(time)) = (current_line, line.strip_prefix("author-time ")) {
+            if let Some(at) = time.parse().ok().and_then(|t| Utc.timestamp_opt(t, 0).single()) {
+                dates.insert(line_no, at);
+            }
+        }
+    }
+    dates
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(text: &str) -> Option<TodoItem> {
+        parse_item(SearchMatch { path: "src/lib.rs".to_string(), line: 12, column: 1, text: text.to_string() })
+    }
+
+    #[test]
+    fn test_parse_item_strips_marker_and_owner() {
+        let todo = item("// TODO(alice): handle empty input").unwrap();
+        assert_eq!(todo.marker, "TODO");
+        assert_eq!(todo.text, "handle empty input");
+
+        let fixme = item("/* FIXME - leaks on error */").unwrap();
+        assert_eq!(fixme.marker, "FIXME");
+        assert_eq!(fixme.text, "leaks on error");
+    }
+
+    #[test]
+    fn test_task_title_links_location() {
+        let hack = item("# HACK: skip flaky check").unwrap();
+        assert_eq!(hack.task_title(), "HACK: skip flaky check (src/lib.rs:12)");
+
+        let bare = item("// TODO").unwrap();
+        assert_eq!(bare.task_title(), "TODO: (no description) (src/lib.rs:12)");
+    }
+}
@@ -0,0 +1,283 @@
+//! Task environment variables and keychain secrets
+//!
+//! A task can carry environment variables (`/env KEY=VALUE` in the new-task
+//! input) that its Claude sessions and tmux panes run with, e.g. staging
+//! credentials. A value written as `secret:NAME` isn't stored with the task:
+//! it names a secret kept in the OS keychain (`security` on macOS,
+//! `secret-tool` elsewhere) and is looked up each time a session or pane
+//! starts. `kanblam secret set NAME` stores one, prompting for the value.
+//!
+//! The board registers each task's variables under its worktree here, so the
+//! sidecar client and the tmux helpers can find them from the worktree path
+//! they're given. Panes of remote projects don't get them. Looked-up secrets
+//! never go in tmux's argv, where `ps` would show them: a pane reads them from
+//! a file only the user can read, and deletes it.
+
+use anyhow::{anyhow, bail, Context, Result};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+use std::sync::Mutex;
+
+/// Keychain service the secrets are stored under
+const SERVICE: &str = "kanblam";
+
+/// Values starting with this name a keychain secret
+pub const SECRET_PREFIX: &str = "secret:";
+
+/// Environment variables of the tasks with a worktree, by worktree
+static TASK_ENV: Mutex<Vec<(PathBuf, BTreeMap<String, String>)>> = Mutex::new(Vec::new());
+
+fn valid_key(key: &str) -> bool {
+    let mut chars = key.chars();
+    chars.next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+fn valid_secret_name(name: &str) -> bool {
+    !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || "._-".contains(c))
+}
+
+/// Parse a `KEY=VALUE` assignment
+pub fn parse_assignment(assignment: &str) -> Result<(String, String), String> {
+    let (key, value) = assignment
+        .split_once('=')
+        .ok_or_else(|| format!("'{}' isn't KEY=VALUE", assignment))?;
+    if !valid_key(key) {
+        return Err(format!("'{}' isn't a valid variable name", key));
+    }
+    if let Some(name) = value.strip_prefix(SECRET_PREFIX) {
+        if !valid_secret_name(name) {
+            return Err(format!("'{}' isn't a valid secret name", name));
+        }
+    }
+    Ok((key.to_string(), value.to_string()))
+}
+
+/// How a variable's value is shown: secret references as written, literal values masked
+pub fn display_value(value: &str) -> &str {
+    if value.starts_with(SECRET_PREFIX) {
+        value
+    } else {
+        "•••"
+    }
+}
+
+/// The variables with their secrets looked up
+pub fn resolve_with(
+    env: &BTreeMap<String, String>,
+    lookup: impl Fn(&str) -> Result<String>,
+) -> Result<BTreeMap<String, String>> {
+    env.iter()
+        .map(|(key, value)| {
+            let value = match value.strip_prefix(SECRET_PREFIX) {
+                Some(name) => lookup(name).with_context(|| format!("Secret '{}' for {}", name, key))?,
+                None => value.clone(),
+            };
+            Ok((key.clone(), value))
+        })
+        .collect()
+}
+
+/// Replace the registered task variables
+pub fn set_task_env(envs: Vec<(PathBuf, BTreeMap<String, String>)>) {
+    if let Ok(mut registered) = TASK_ENV.lock() {
+        if *registered != envs {
+            *registered = envs;
+        }
+    }
+}
+
+/// The variables registered for the task whose worktree is `worktree`, as written
+fn registered_env(worktree: &Path) -> BTreeMap<String, String> {
+    TASK_ENV.lock().ok().and_then(|registered| {
+        registered.iter().find(|(path, _)| path == worktree).map(|(_, env)| env.clone())
+    }).unwrap_or_default()
+}
+
+/// The variables of the task whose worktree is `worktree`, secrets looked up
+/// (empty for anything else)
+pub fn task_env(worktree: &Path) -> Result<BTreeMap<String, String>> {
+    resolve_with(&registered_env(worktree), lookup)
+}
+
+/// Run `tmux` (a command creating a pane) for the task whose worktree is
+/// `worktree`, adding `-e KEY=VALUE` for the literal variables, then `command`.
+/// Like tmux, a single argument is a shell command, several are a program and
+/// its arguments, and none is a login shell. With secrets, the command is
+/// wrapped to read them from a 0600 file first, which it deletes; when tmux
+/// fails no pane will, so the file is deleted here instead.
+pub fn tmux_pane_output(tmux: &mut Command, worktree: &Path, command: Vec<String>) -> Result<Output> {
+    let (args, env_file) = tmux_pane_args(worktree, command)?;
+    let output = tmux.args(args).output();
+    let started = output.as_ref().is_ok_and(|output| output.status.success());
+    if let (false, Some(file)) = (started, env_file) {
+        let _ = std::fs::remove_file(file);
+    }
+    Ok(output?)
+}
+
+/// The arguments `tmux_pane_output` adds, and the secrets file they read
+fn tmux_pane_args(worktree: &Path, command: Vec<String>) -> Result<(Vec<String>, Option<PathBuf>)> {
+    if crate::remote::is_remote(worktree) {
+        return Ok((command, None));
+    }
+    let (secrets, literals): (BTreeMap<_, _>, BTreeMap<_, _>) = registered_env(worktree)
+        .into_iter()
+        .partition(|(_, value)| value.starts_with(SECRET_PREFIX));
+    let mut args: Vec<String> = literals
+        .into_iter()
+        .flat_map(|(key, value)| ["-e".to_string(), format!("{}={}", key, value)])
+        .collect();
+    if secrets.is_empty() {
+        args.extend(command);
+        return Ok((args, None));
+    }
+    let file = write_env_file(&resolve_with(&secrets, lookup)?)?;
+    args.extend(sourcing(&file, command));
+    Ok((args, Some(file)))
+}
+
+/// Write `env` as shell exports to a new file only the user can read
+fn write_env_file(env: &BTreeMap<String, String>) -> Result<PathBuf> {
+    use std::io::Write;
+    use std::os::unix::fs::OpenOptionsExt;
+
+    let path = std::env::temp_dir().join(format!("kanblam-env-{}", uuid::Uuid::new_v4()));
+    let mut file = std::fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .mode(0o600)
+        .open(&path)
+        .context("Couldn't create the secrets file")?;
+    for (key, value) in env {
+        writeln!(file, "export {}={}", key, crate::remote::quote(value))?;
+    }
+    Ok(path)
+}
+
+/// `command` (in tmux's form) run by `sh` after it sources and deletes `file`
+fn sourcing(file: &Path, command: Vec<String>) -> Vec<String> {
+    let file = crate::remote::quote(&file.to_string_lossy());
+    let prelude = format!(". {}; rm -f {};", file, file);
+    match command.len() {
+        0 => vec!["sh".to_string(), "-c".to_string(), format!("{} exec \"${{SHELL:-/bin/sh}}\" -l", prelude)],
+        1 => vec!["sh".to_string(), "-c".to_string(), format!("{} {}", prelude, command[0])],
+        _ => ["sh", "-c", &format!("{} exec \"$@\"", prelude), "sh"]
+            .into_iter()
+            .map(String::from)
+            .chain(command)
+            .collect(),
+    }
+}
+
+/// Keychain command for `action` ("store", "lookup" or "clear") on secret `name`
+fn keychain(action: &str, name: &str) -> Command {
+    if cfg!(target_os = "macos") {
+        let mut command = Command::new("security");
+        match action {
+            // Without a value after -w, security prompts for it
+            "store" => command.args(["add-generic-password", "-U", "-s", SERVICE, "-a", name, "-w"]),
+            "lookup" => command.args(["find-generic-password", "-s", SERVICE, "-a", name, "-w"]),
+            _ => command.args(["delete-generic-password", "-s", SERVICE, "-a", name]),
+        };
+        command
+    } else {
+        let mut command = Command::new("secret-tool");
+        if action == "store" {
+            command.args(["store", "--label", &format!("{} {}", SERVICE, name)]);
+        } else {
+            command.arg(action);
+        }
+        command.args(["service", SERVICE, "name", name]);
+        command
+    }
+}
+
+/// Look up a secret in the keychain
+pub fn lookup(name: &str) -> Result<String> {
+    let output = keychain("lookup", name)
+        .output()
+        .context("Couldn't run the keychain tool")?;
+    let value = String::from_utf8_lossy(&output.stdout);
+    let value = value.strip_suffix('\n').unwrap_or(&value);
+    if !output.status.success() || value.is_empty() {
+        bail!("not found in the keychain (store it with `kanblam secret set {}`)", name);
+    }
+    Ok(value.to_string())
+}
+
+/// `kanblam secret set|rm NAME`
+pub fn main(args: &[String]) -> Result<()> {
+    const USAGE: &str = "Usage: kanblam secret set NAME | kanblam secret rm NAME";
+    let (Some(action), Some(name), None) = (args.first(), args.get(1), args.get(2)) else {
+        return Err(anyhow!(USAGE));
+    };
+    if !valid_secret_name(name) {
+        bail!("'{}' isn't a valid secret name (letters, digits, '.', '_' and '-')", name);
+    }
+    let (action, done) = match action.as_str() {
+        "set" => ("store", "Stored"),
+        "rm" => ("clear", "Removed"),
+        _ => return Err(anyhow!(USAGE)),
+    };
+    // The keychain tool prompts for the value itself, so it never shows up in argv
+    let status = keychain(action, name).status().context("Couldn't run the keychain tool")?;
+    if !status.success() {
+        bail!("The keychain tool failed ({})", status);
+    }
+    eprintln!("{} secret '{}'; use it in a task with /env KEY={}{}", done, name, SECRET_PREFIX, name);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_and_resolve() {
+        assert_eq!(parse_assignment("API_URL=https://staging.x.dev/?a=b"), Ok((
+            "API_URL".to_string(),
+            "https://staging.x.dev/?a=b".to_string(),
+        )));
+        assert!(parse_assignment("API_URL").is_err());
+        assert!(parse_assignment("1KEY=x").is_err());
+        assert!(parse_assignment("TOKEN=secret:bad name").is_err());
+
+        let env = BTreeMap::from([
+            ("API_URL".to_string(), "https://staging.x.dev".to_string()),
+            ("TOKEN".to_string(), "secret:staging-token".to_string()),
+        ]);
+        let resolved = resolve_with(&env, |name| match name {
+            "staging-token" => Ok("s3cret".to_string()),
+            _ => bail!("not found"),
+        })
+        .unwrap();
+        assert_eq!(resolved["TOKEN"], "s3cret");
+        assert_eq!(resolved["API_URL"], "https://staging.x.dev");
+        assert!(resolve_with(&env, |_| bail!("not found")).is_err());
+
+        assert_eq!(display_value("secret:staging-token"), "secret:staging-token");
+        assert_eq!(display_value("https://staging.x.dev"), "•••");
+    }
+
+    #[test]
+    fn test_secrets_sourced_from_file() {
+        let run = |args: Vec<String>| {
+            let output = Command::new(&args[0]).args(&args[1..]).output().unwrap();
+            String::from_utf8_lossy(&output.stdout).to_string()
+        };
+        let env = BTreeMap::from([("TOKEN".to_string(), "s3'cret $x".to_string())]);
+
+        let file = write_env_file(&env).unwrap();
+        let args = sourcing(&file, vec!["printf %s \"$TOKEN\"".to_string()]);
+        assert!(!args.concat().contains("s3'cret"));
+        assert_eq!(run(args), "s3'cret $x");
+        assert!(!file.exists());
+
+        let file = write_env_file(&env).unwrap();
+        let command = ["sh", "-c", "printf %s \"$TOKEN\""].map(String::from).to_vec();
+        assert_eq!(run(sourcing(&file, command)), "s3'cret $x");
+        assert!(!file.exists());
+    }
+}
@@ -1,18 +1,26 @@
 //! Unix socket client for communicating with the TypeScript sidecar
+//!
+//! With `KANBLAM_SIDECAR_ADDR=host:port` set the client talks to a sidecar on
+//! another host over TCP instead, authenticating with `KANBLAM_SIDECAR_TOKEN`
+//! (the sidecar listens there when started with `KANBLAM_SIDECAR_LISTEN`).
+//! A remote sidecar is never spawned or restarted from here, and must see the
+//! worktrees at the same paths (a shared mount, or a container bind mount).
 
 #![allow(dead_code)]
 
-use std::io::{BufRead, BufReader, Write};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpStream, ToSocketAddrs};
 use std::os::unix::net::UnixStream;
 use std::path::PathBuf;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Arc, Mutex};
 use std::thread;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use anyhow::{anyhow, Context, Result};
 
 use super::protocol::*;
+use crate::timing::{self, OpKind};
 
 /// Path to the sidecar socket
 fn socket_path() -> PathBuf {
@@ -22,18 +30,132 @@ fn socket_path() -> PathBuf {
         .join("sidecar.sock")
 }
 
+/// How long connecting to a remote sidecar may take
+const TCP_CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Where the sidecar is reached
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Endpoint {
+    /// Local sidecar on the unix socket
+    Local(PathBuf),
+    /// Sidecar on another host (`KANBLAM_SIDECAR_ADDR`)
+    Remote { addr: String, token: String },
+}
+
+fn endpoint() -> Endpoint {
+    match std::env::var("KANBLAM_SIDECAR_ADDR") {
+        Ok(addr) if !addr.trim().is_empty() => Endpoint::Remote {
+            addr: addr.trim().to_string(),
+            token: std::env::var("KANBLAM_SIDECAR_TOKEN").unwrap_or_default(),
+        },
+        _ => Endpoint::Local(socket_path()),
+    }
+}
+
+/// Whether the sidecar runs on another host, and so isn't ours to start
+pub fn is_remote() -> bool {
+    matches!(endpoint(), Endpoint::Remote { .. })
+}
+
+/// Connection to the sidecar over either transport
+enum SidecarStream {
+    Unix(UnixStream),
+    Tcp(TcpStream),
+}
+
+impl SidecarStream {
+    /// Connect to `endpoint`, authenticating first when it's remote
+    fn connect(endpoint: &Endpoint) -> Result<Self> {
+        match endpoint {
+            Endpoint::Local(path) => {
+                let stream = UnixStream::connect(path)
+                    .with_context(|| format!("Failed to connect to sidecar at {:?}", path))?;
+                Ok(Self::Unix(stream))
+            }
+            Endpoint::Remote { addr, token } => {
+                let socket_addr = addr
+                    .to_socket_addrs()
+                    .with_context(|| format!("Invalid sidecar address {}", addr))?
+                    .next()
+                    .ok_or_else(|| anyhow!("Sidecar address {} did not resolve", addr))?;
+                let stream = TcpStream::connect_timeout(&socket_addr, TCP_CONNECT_TIMEOUT)
+                    .with_context(|| format!("Failed to connect to sidecar at {}", addr))?;
+                stream.set_nodelay(true)?;
+                let mut stream = Self::Tcp(stream);
+                stream.authenticate(token)?;
+                Ok(stream)
+            }
+        }
+    }
+
+    /// Send the token; the sidecar neither answers nor broadcasts to a TCP
+    /// connection until it's accepted, so the next line is the reply
+    fn authenticate(&mut self, token: &str) -> Result<()> {
+        let request = JsonRpcRequest::new(0, "auth", Some(serde_json::json!({ "token": token })));
+        writeln!(self, "{}", serde_json::to_string(&request)?)?;
+        self.flush()?;
+
+        self.set_read_timeout(Some(TCP_CONNECT_TIMEOUT))?;
+        let mut line = String::new();
+        BufReader::new(&*self).read_line(&mut line)?;
+        self.set_read_timeout(None)?;
+        let response: JsonRpcResponse =
+            serde_json::from_str(&line).context("Sidecar closed the connection during auth")?;
+        if let Some(error) = response.error {
+            return Err(anyhow!("Sidecar rejected the connection: {}", error.message));
+        }
+        Ok(())
+    }
+
+    fn set_read_timeout(&self, timeout: Option<Duration>) -> std::io::Result<()> {
+        match self {
+            Self::Unix(stream) => stream.set_read_timeout(timeout),
+            Self::Tcp(stream) => stream.set_read_timeout(timeout),
+        }
+    }
+}
+
+impl Read for &SidecarStream {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        match self {
+            SidecarStream::Unix(stream) => (&*stream).read(buf),
+            SidecarStream::Tcp(stream) => (&*stream).read(buf),
+        }
+    }
+}
+
+impl Read for SidecarStream {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        (&*self).read(buf)
+    }
+}
+
+impl Write for SidecarStream {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        match self {
+            Self::Unix(stream) => stream.write(buf),
+            Self::Tcp(stream) => stream.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        match self {
+            Self::Unix(stream) => stream.flush(),
+            Self::Tcp(stream) => stream.flush(),
+        }
+    }
+}
+
 /// Client for communicating with the sidecar
 pub struct SidecarClient {
-    stream: Arc<Mutex<UnixStream>>,
+    stream: Arc<Mutex<SidecarStream>>,
     request_id: AtomicU64,
 }
 
 impl SidecarClient {
     /// Connect to the sidecar
     pub fn connect() -> Result<Self> {
-        let path = socket_path();
-        let stream = UnixStream::connect(&path)
-            .with_context(|| format!("Failed to connect to sidecar at {:?}", path))?;
+        let stream = SidecarStream::connect(&endpoint())?;
 
         // Set read timeout for responses
         stream.set_read_timeout(Some(Duration::from_secs(30)))?;
@@ -44,9 +166,12 @@ impl SidecarClient {
         })
     }
 
-    /// Check if sidecar is available
+    /// Check if sidecar is available (a remote one is tried by connecting)
     pub fn is_available() -> bool {
-        socket_path().exists()
+        match endpoint() {
+            Endpoint::Local(path) => path.exists(),
+            Endpoint::Remote { .. } => true,
+        }
     }
 
     /// Send a ping to verify connection
@@ -68,6 +193,7 @@ impl SidecarClient {
             worktree_path: worktree_path.to_string_lossy().to_string(),
             prompt: prompt.to_string(),
             images,
+            env: crate::secrets::task_env(worktree_path)?,
         };
 
         let response = self.send_request("start_session", Some(serde_json::to_value(params)?))?;
@@ -109,6 +235,7 @@ impl SidecarClient {
             session_id: session_id.to_string(),
             worktree_path: worktree_path.to_string_lossy().to_string(),
             prompt: prompt.map(|s| s.to_string()),
+            env: crate::secrets::task_env(worktree_path)?,
         };
 
         let response = self.send_request("resume_session", Some(serde_json::to_value(params)?))?;
@@ -211,6 +338,53 @@ impl SidecarClient {
         client.summarize_title(task_id, &title)
     }
 
+    /// Fetch the session events the sidecar journaled after `since`, plus the
+    /// sequence number of its latest event
+    pub fn replay_events(&self, since: u64) -> Result<(Vec<SidecarEvent>, u64)> {
+        let params = ReplayEventsParams { since };
+
+        let response = self.send_request("replay_events", Some(serde_json::to_value(params)?))?;
+
+        if let Some(error) = response.error {
+            return Err(anyhow!("Sidecar error: {} (code {})", error.message, error.code));
+        }
+
+        let result: ReplayEventsResult = serde_json::from_value(
+            response.result.ok_or_else(|| anyhow!("No result in response"))?,
+        )?;
+
+        let events = result.events.into_iter()
+            .filter_map(|params| SidecarEvent::try_from(params).ok())
+            .collect();
+        Ok((events, result.latest_seq))
+    }
+
+    /// Run a single-turn prompt (no tools, no session) and return the text reply
+    pub fn complete_prompt(&self, prompt: &str, cwd: Option<&std::path::Path>) -> Result<String> {
+        let params = CompletePromptParams {
+            prompt: prompt.to_string(),
+            cwd: cwd.map(|p| p.to_string_lossy().to_string()),
+        };
+
+        let response = self.send_request("complete_prompt", Some(serde_json::to_value(params)?))?;
+
+        if let Some(error) = response.error {
+            return Err(anyhow!("Sidecar error: {} (code {})", error.message, error.code));
+        }
+
+        let result: CompletePromptResult = serde_json::from_value(
+            response.result.ok_or_else(|| anyhow!("No result in response"))?,
+        )?;
+
+        Ok(result.text)
+    }
+
+    /// Run a single-turn prompt using a standalone connection (for background threads)
+    pub fn complete_prompt_standalone(prompt: String, cwd: Option<PathBuf>) -> Result<String> {
+        let client = Self::connect()?;
+        client.complete_prompt(&prompt, cwd.as_deref())
+    }
+
     /// Start the watcher for a project
     pub fn start_watcher(&self, project_path: &std::path::PathBuf, interval_minutes: Option<u32>) -> Result<()> {
         let params = StartWatcherParams {
@@ -242,10 +416,12 @@ impl SidecarClient {
         Ok(())
     }
 
-    /// Trigger an immediate watcher observation (for testing)
-    pub fn trigger_watcher(&self, project_path: &std::path::PathBuf) -> Result<()> {
-        let params = StopWatcherParams {
+    /// Trigger an immediate watcher observation with the project's muted categories and scope
+    pub fn trigger_watcher(&self, project_path: &std::path::PathBuf, muted: &[WatcherCategory], scope: WatcherScope) -> Result<()> {
+        let params = TriggerWatcherParams {
             project_path: project_path.to_string_lossy().to_string(),
+            muted_categories: muted.iter().map(|c| c.key().to_string()).collect(),
+            scope,
         };
 
         let response = self.send_request("trigger_watcher", Some(serde_json::to_value(params)?))?;
@@ -266,10 +442,19 @@ impl SidecarClient {
         let id = self.request_id.fetch_add(1, Ordering::SeqCst);
         let request = JsonRpcRequest::new(id, method, params);
 
+        let started = Instant::now();
+        let response = self.exchange(&request);
+        timing::record(OpKind::Sidecar, || request_label(&request), started.elapsed());
+        response
+    }
+
+    /// Write a request and read until its response arrives
+    fn exchange(&self, request: &JsonRpcRequest) -> Result<JsonRpcResponse> {
+        let id = request.id;
         let mut stream = self.stream.lock().map_err(|_| anyhow!("Lock poisoned"))?;
 
         // Send request
-        let request_json = serde_json::to_string(&request)?;
+        let request_json = serde_json::to_string(request)?;
         writeln!(stream, "{}", request_json)?;
         stream.flush()?;
 
@@ -311,6 +496,26 @@ impl SidecarClient {
     }
 }
 
+/// "start_session(task_id=…, prompt=\"Fix the login…\")" for the diagnostics modal
+fn request_label(request: &JsonRpcRequest) -> String {
+    let Some(serde_json::Value::Object(params)) = &request.params else {
+        return request.method.to_string();
+    };
+    let args: Vec<String> = params
+        .iter()
+        .map(|(key, value)| match value {
+            serde_json::Value::String(text) if text.chars().count() > 40 => {
+                format!("{}={:?}", key, text.chars().take(39).collect::<String>() + "…")
+            }
+            serde_json::Value::String(text) => format!("{}={:?}", key, text),
+            serde_json::Value::Array(items) => format!("{}=[{} items]", key, items.len()),
+            serde_json::Value::Object(_) => format!("{}={{…}}", key),
+            other => format!("{}={}", key, other),
+        })
+        .collect();
+    format!("{}({})", request.method, args.join(", "))
+}
+
 /// Types of notifications from the sidecar
 #[derive(Debug)]
 pub enum SidecarNotification {
@@ -324,15 +529,13 @@ pub enum SidecarNotification {
 
 /// Event receiver for async notifications from sidecar
 pub struct SidecarEventReceiver {
-    reader: BufReader<UnixStream>,
+    reader: BufReader<SidecarStream>,
 }
 
 impl SidecarEventReceiver {
     /// Create a new event receiver (separate connection for notifications)
     pub fn connect() -> Result<Self> {
-        let path = socket_path();
-        let stream = UnixStream::connect(&path)
-            .with_context(|| format!("Failed to connect to sidecar at {:?}", path))?;
+        let stream = SidecarStream::connect(&endpoint())?;
 
         Ok(Self {
             reader: BufReader::new(stream),
@@ -371,6 +574,20 @@ impl SidecarEventReceiver {
         }
     }
 
+    /// Read notifications on a background thread, so the main loop can await them.
+    /// The channel closes when the connection is lost.
+    pub fn into_channel(mut self) -> tokio::sync::mpsc::UnboundedReceiver<SidecarNotification> {
+        let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
+        std::thread::spawn(move || {
+            while let Ok(notification) = self.recv_notification() {
+                if tx.send(notification).is_err() {
+                    break; // Receiver replaced after a resubscribe
+                }
+            }
+        });
+        rx
+    }
+
     /// Read the next event (blocking) - for backwards compatibility, ignores watcher notifications
     pub fn recv(&mut self) -> Result<SidecarEvent> {
         loop {
@@ -480,6 +697,12 @@ fn find_sidecar_path() -> Option<std::path::PathBuf> {
 /// Returns the Child handle if we spawned a new process (caller should kill on exit)
 /// Returns None if sidecar was already running
 pub fn ensure_sidecar_running() -> Result<Option<std::process::Child>> {
+    if let Endpoint::Remote { addr, .. } = endpoint() {
+        let client = SidecarClient::connect()?;
+        client.ping().with_context(|| format!("Remote sidecar at {} did not answer", addr))?;
+        return Ok(None);
+    }
+
     if SidecarClient::is_available() {
         // Try to ping to verify it's actually responding
         if let Ok(client) = SidecarClient::connect() {
@@ -516,3 +739,73 @@ pub fn ensure_sidecar_running() -> Result<Option<std::process::Child>> {
 
     Err(anyhow!("Sidecar failed to start within timeout"))
 }
+
+/// Outcome of one supervision round
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HealthCheck {
+    /// The sidecar answered a ping
+    Healthy,
+    /// The sidecar didn't answer and was started again
+    Restarted,
+    /// The sidecar didn't answer and couldn't be started
+    Down(String),
+}
+
+/// Ping the sidecar over a fresh connection and start it again if it doesn't answer
+pub fn check_and_restart() -> HealthCheck {
+    let responding = SidecarClient::connect().and_then(|client| client.ping()).unwrap_or(false);
+    if responding {
+        return HealthCheck::Healthy;
+    }
+    if let Endpoint::Remote { addr, .. } = endpoint() {
+        return HealthCheck::Down(format!("Remote sidecar at {} is not responding", addr));
+    }
+    match ensure_sidecar_running() {
+        Ok(_) => HealthCheck::Restarted,
+        Err(e) => HealthCheck::Down(e.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::TcpListener;
+
+    /// Fake remote sidecar that checks the auth request and answers one ping
+    fn serve_once(token: &'static str) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap().to_string();
+        thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(&stream);
+            let mut line = String::new();
+            reader.read_line(&mut line).unwrap();
+            let auth: serde_json::Value = serde_json::from_str(&line).unwrap();
+            assert_eq!(auth["method"], "auth");
+            if auth["params"]["token"] != token {
+                let reply = r#"{"jsonrpc":"2.0","id":0,"error":{"code":-32003,"message":"Invalid auth token"}}"#;
+                writeln!(&stream, "{}", reply).unwrap();
+                return;
+            }
+            writeln!(&stream, r#"{{"jsonrpc":"2.0","id":0,"result":{{"ok":true}}}}"#).unwrap();
+
+            line.clear();
+            reader.read_line(&mut line).unwrap();
+            let ping: serde_json::Value = serde_json::from_str(&line).unwrap();
+            writeln!(&stream, r#"{{"jsonrpc":"2.0","id":{},"result":{{"pong":true}}}}"#, ping["id"]).unwrap();
+        });
+        addr
+    }
+
+    #[test]
+    fn test_remote_connection_authenticates() {
+        let addr = serve_once("secret");
+        let stream = SidecarStream::connect(&Endpoint::Remote { addr, token: "secret".to_string() }).unwrap();
+        let client = SidecarClient { stream: Arc::new(Mutex::new(stream)), request_id: AtomicU64::new(1) };
+        assert!(client.ping().unwrap());
+
+        let addr = serve_once("secret");
+        let err = SidecarStream::connect(&Endpoint::Remote { addr, token: "wrong".to_string() }).err().unwrap();
+        assert!(err.to_string().contains("Invalid auth token"));
+    }
+}
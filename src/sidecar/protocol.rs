@@ -3,6 +3,7 @@
 #![allow(dead_code)]
 
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 use uuid::Uuid;
 
@@ -65,6 +66,9 @@ pub struct StartSessionParams {
     pub prompt: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub images: Option<Vec<String>>,
+    /// The task's environment variables, secrets looked up
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub env: BTreeMap<String, String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -74,6 +78,8 @@ pub struct ResumeSessionParams {
     pub worktree_path: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub prompt: Option<String>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub env: BTreeMap<String, String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -100,8 +106,21 @@ pub struct SummarizeTitleParams {
     pub title: String,
 }
 
+#[derive(Debug, Serialize)]
+pub struct CompletePromptParams {
+    pub prompt: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cwd: Option<String>,
+}
+
 // Response result types
 
+#[derive(Debug, Serialize)]
+pub struct ReplayEventsParams {
+    /// Last sequence number already handled
+    pub since: u64,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct StartSessionResult {
     pub session_id: String,
@@ -118,6 +137,11 @@ pub struct GetSessionResult {
     pub is_active: bool,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct CompletePromptResult {
+    pub text: String,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct SummarizeTitleResult {
     pub short_title: String,
@@ -127,6 +151,13 @@ pub struct SummarizeTitleResult {
     pub spec: Option<String>,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct ReplayEventsResult {
+    /// Journaled events after `since`, oldest first
+    pub events: Vec<SessionEventParams>,
+    pub latest_seq: u64,
+}
+
 // Session event types (notifications from sidecar)
 
 #[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
@@ -139,6 +170,10 @@ pub enum SessionEventType {
     Working,
     ToolUse,
     Output,
+    /// Context window usage after a turn
+    Context,
+    /// The SDK compacted the conversation
+    Compacted,
 }
 
 /// Token usage data from Claude SDK
@@ -176,6 +211,12 @@ pub struct SessionEventParams {
     /// Total cost in USD (for 'stopped' events)
     #[serde(default)]
     pub cost_usd: Option<f64>,
+    /// Tokens in the context window ('context'), or before compaction ('compacted')
+    #[serde(default)]
+    pub context_tokens: Option<u64>,
+    /// Journal sequence number (absent from sidecars without the journal)
+    #[serde(default)]
+    pub seq: Option<u64>,
 }
 
 /// Parsed session event ready for use in app logic
@@ -194,6 +235,10 @@ pub struct SidecarEvent {
     pub usage: Option<TokenUsage>,
     /// Total cost in USD (for 'stopped' events)
     pub cost_usd: Option<f64>,
+    /// Tokens in the context window ('context'), or before compaction ('compacted')
+    pub context_tokens: Option<u64>,
+    /// Journal sequence number, for dropping events already handled
+    pub seq: Option<u64>,
 }
 
 impl TryFrom<SessionEventParams> for SidecarEvent {
@@ -210,6 +255,8 @@ impl TryFrom<SessionEventParams> for SidecarEvent {
             full_output: params.full_output,
             usage: params.usage,
             cost_usd: params.cost_usd,
+            context_tokens: params.context_tokens,
+            seq: params.seq,
         })
     }
 }
@@ -228,6 +275,60 @@ pub enum WatcherMood {
     Sleepy,
 }
 
+/// What a watcher observation looked for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WatcherCategory {
+    Bug,
+    Security,
+    FeatureIdea,
+    UxImprovement,
+    ElegantCode,
+    Refactor,
+}
+
+impl WatcherCategory {
+    /// All categories, in the order the feed's mute toggles list them
+    pub fn all() -> &'static [WatcherCategory] {
+        &[
+            WatcherCategory::Bug,
+            WatcherCategory::Security,
+            WatcherCategory::FeatureIdea,
+            WatcherCategory::UxImprovement,
+            WatcherCategory::ElegantCode,
+            WatcherCategory::Refactor,
+        ]
+    }
+
+    /// Name as the sidecar sends it
+    pub fn key(&self) -> &'static str {
+        match self {
+            WatcherCategory::Bug => "bug",
+            WatcherCategory::Security => "security",
+            WatcherCategory::FeatureIdea => "feature_idea",
+            WatcherCategory::UxImprovement => "ux_improvement",
+            WatcherCategory::ElegantCode => "elegant_code",
+            WatcherCategory::Refactor => "refactor",
+        }
+    }
+
+    /// Parse a name produced by `key` (None for categories this build doesn't know)
+    pub fn from_key(key: &str) -> Option<WatcherCategory> {
+        WatcherCategory::all().iter().copied().find(|c| c.key() == key)
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            WatcherCategory::Bug => "Bugs",
+            WatcherCategory::Security => "Security",
+            WatcherCategory::FeatureIdea => "Feature ideas",
+            WatcherCategory::UxImprovement => "UX",
+            WatcherCategory::ElegantCode => "Praise",
+            WatcherCategory::Refactor => "Refactors",
+        }
+    }
+}
+
 /// Structured insight data from the watcher
 #[derive(Debug, Clone, Deserialize)]
 pub struct WatcherInsight {
@@ -249,6 +350,9 @@ pub struct WatcherCommentParams {
     /// Full insight data if available
     #[serde(default)]
     pub insight: Option<WatcherInsight>,
+    /// What the observation looked for (older sidecars don't say)
+    #[serde(default)]
+    pub category: Option<String>,
 }
 
 /// Parsed watcher comment ready for use in app logic
@@ -260,6 +364,7 @@ pub struct WatcherComment {
     pub timestamp: chrono::DateTime<chrono::Utc>,
     /// Full insight data if available
     pub insight: Option<WatcherInsight>,
+    pub category: Option<WatcherCategory>,
 }
 
 impl TryFrom<WatcherCommentParams> for WatcherComment {
@@ -276,6 +381,7 @@ impl TryFrom<WatcherCommentParams> for WatcherComment {
             mood: params.mood,
             timestamp,
             insight: params.insight,
+            category: params.category.as_deref().and_then(WatcherCategory::from_key),
         })
     }
 }
@@ -292,6 +398,40 @@ pub struct StopWatcherParams {
     pub project_path: String,
 }
 
+#[derive(Debug, Serialize)]
+pub struct TriggerWatcherParams {
+    pub project_path: String,
+    /// Categories the observation must not pick
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub muted_categories: Vec<String>,
+    #[serde(flatten)]
+    pub scope: WatcherScope,
+}
+
+/// What an observation looks at, and as whom (set per project)
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct WatcherScope {
+    /// Who the watcher speaks as (None = a supportive coding buddy)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub persona: Option<String>,
+    /// Only git log, diff stats and task titles; no file contents
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub metadata_only: bool,
+    /// Tasks in the watched columns
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub tasks: Vec<WatchedTask>,
+}
+
+/// A task in one of the columns the watcher looks at
+#[derive(Debug, Clone, Serialize)]
+pub struct WatchedTask {
+    pub title: String,
+    pub column: String,
+    /// Branch whose diff the watcher may read (None when it only reads metadata)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub branch: Option<String>,
+}
+
 /// Notification params for watcher observation status (when Claude SDK starts/stops)
 #[derive(Debug, Clone, Deserialize)]
 pub struct WatcherObservingParams {
@@ -393,6 +533,7 @@ mod tests {
             worktree_path: "/path/to/worktree".to_string(),
             prompt: "Implement feature X".to_string(),
             images: Some(vec!["/path/to/image.png".to_string()]),
+            env: BTreeMap::from([("API_URL".to_string(), "https://staging.x.dev".to_string())]),
         };
 
         let json = serde_json::to_string(&params).unwrap();
@@ -400,6 +541,7 @@ mod tests {
         assert!(json.contains("\"worktree_path\":\"/path/to/worktree\""));
         assert!(json.contains("\"prompt\":\"Implement feature X\""));
         assert!(json.contains("\"images\":[\"/path/to/image.png\"]"));
+        assert!(json.contains("\"env\":{\"API_URL\":\"https://staging.x.dev\"}"));
     }
 
     #[test]
@@ -409,10 +551,12 @@ mod tests {
             worktree_path: "/path/to/worktree".to_string(),
             prompt: "Implement feature X".to_string(),
             images: None,
+            env: BTreeMap::new(),
         };
 
         let json = serde_json::to_string(&params).unwrap();
         assert!(!json.contains("images")); // should be skipped
+        assert!(!json.contains("env"));
     }
 
     #[test]
@@ -451,6 +595,26 @@ mod tests {
         assert_eq!(params.tool_name.as_deref(), Some("Read"));
     }
 
+    #[test]
+    fn test_replay_events_result() {
+        let json = r#"{
+            "events": [{"task_id": "t", "event": "stopped", "seq": 42}],
+            "latest_seq": 57
+        }"#;
+        let result: ReplayEventsResult = serde_json::from_str(json).unwrap();
+        assert_eq!(result.latest_seq, 57);
+        assert_eq!(result.events[0].seq, Some(42));
+        assert_eq!(result.events[0].event, SessionEventType::Stopped);
+    }
+
+    #[test]
+    fn test_session_event_params_context() {
+        let json = r#"{"task_id": "t", "event": "compacted", "context_tokens": 181000}"#;
+        let params: SessionEventParams = serde_json::from_str(json).unwrap();
+        assert_eq!(params.event, SessionEventType::Compacted);
+        assert_eq!(params.context_tokens, Some(181000));
+    }
+
     #[test]
     fn test_session_event_params_minimal() {
         let json = r#"{"task_id": "task-123", "event": "started"}"#;
@@ -474,6 +638,8 @@ mod tests {
             full_output: None,
             usage: None,
             cost_usd: None,
+            context_tokens: None,
+            seq: None,
         };
 
         let event: SidecarEvent = params.try_into().unwrap();
@@ -494,6 +660,8 @@ mod tests {
             full_output: None,
             usage: None,
             cost_usd: None,
+            context_tokens: None,
+            seq: None,
         };
 
         let result: Result<SidecarEvent, _> = params.try_into();
@@ -507,6 +675,7 @@ mod tests {
             session_id: "session-456".to_string(),
             worktree_path: "/path/to/worktree".to_string(),
             prompt: Some("Continue working".to_string()),
+            env: BTreeMap::new(),
         };
 
         let json = serde_json::to_string(&params).unwrap();
@@ -0,0 +1,115 @@
+//! How long a task may sit in a column before it's flagged as stuck
+//!
+//! Limits are set per column as "in-progress=2h, review=1d" (units m, h, d).
+//! A task past its column's limit is drawn in red on the board, and the first
+//! time it crosses the limit kanblam plays the attention sound and, when the
+//! watcher is on, has the mascot nudge about it. Moving the task resets the clock.
+
+use chrono::Duration;
+
+use crate::model::TaskStatus;
+
+/// Limits used until the user sets their own
+pub const DEFAULT_THRESHOLDS: &str = "in-progress=2h, review=1d";
+
+/// How often the board is checked for tasks crossing their limit
+pub const CHECK_INTERVAL: std::time::Duration = std::time::Duration::from_secs(60);
+
+/// Per-column time limits
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SlaThresholds {
+    limits: Vec<(TaskStatus, Duration)>,
+}
+
+impl SlaThresholds {
+    /// Parse "in-progress=2h, review=1d". None if any entry is malformed;
+    /// an empty string means no limits.
+    pub fn parse(spec: &str) -> Option<Self> {
+        let mut limits = Vec::new();
+        for entry in spec.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+            let (column, limit) = entry.split_once('=')?;
+            let status = parse_column(column.trim())?;
+            let limit = parse_limit(limit.trim())?;
+            limits.retain(|(s, _)| *s != status);
+            limits.push((status, limit));
+        }
+        Some(Self { limits })
+    }
+
+    /// Limits from the setting, falling back to the defaults when unset or invalid
+    pub fn from_setting(setting: Option<&str>) -> Self {
+        setting
+            .and_then(Self::parse)
+            .or_else(|| Self::parse(DEFAULT_THRESHOLDS))
+            .unwrap_or_default()
+    }
+
+    pub fn limit_for(&self, status: TaskStatus) -> Option<Duration> {
+        self.limits.iter().find(|(s, _)| *s == status).map(|(_, limit)| *limit)
+    }
+
+    /// Whether a task that has been in `status` for `elapsed` is past its limit
+    pub fn is_overdue(&self, status: TaskStatus, elapsed: Duration) -> bool {
+        self.limit_for(status).is_some_and(|limit| elapsed >= limit)
+    }
+}
+
+pub fn parse_column(name: &str) -> Option<TaskStatus> {
+    match name.to_lowercase().replace(['_', ' '], "-").as_str() {
+        "planned" => Some(TaskStatus::Planned),
+        "in-progress" | "inprogress" => Some(TaskStatus::InProgress),
+        "testing" => Some(TaskStatus::Testing),
+        "needs-work" | "needswork" => Some(TaskStatus::NeedsWork),
+        "review" => Some(TaskStatus::Review),
+        _ => None,
+    }
+}
+
+fn parse_limit(text: &str) -> Option<Duration> {
+    let unit = text.chars().last()?;
+    let amount: i64 = text[..text.len() - unit.len_utf8()].trim().parse().ok()?;
+    if amount <= 0 {
+        return None;
+    }
+    match unit {
+        'm' => Some(Duration::minutes(amount)),
+        'h' => Some(Duration::hours(amount)),
+        'd' => Some(Duration::days(amount)),
+        _ => None,
+    }
+}
+
+/// Compact age for a card, e.g. "45m", "3h", "2d"
+pub fn format_age(elapsed: Duration) -> String {
+    if elapsed.num_days() > 0 {
+        format!("{}d", elapsed.num_days())
+    } else if elapsed.num_hours() > 0 {
+        format!("{}h", elapsed.num_hours())
+    } else {
+        format!("{}m", elapsed.num_minutes().max(0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_thresholds() {
+        let sla = SlaThresholds::parse("in-progress=2h, Review=1d,needs_work=30m").unwrap();
+        assert_eq!(sla.limit_for(TaskStatus::InProgress), Some(Duration::hours(2)));
+        assert_eq!(sla.limit_for(TaskStatus::Review), Some(Duration::days(1)));
+        assert_eq!(sla.limit_for(TaskStatus::NeedsWork), Some(Duration::minutes(30)));
+        assert_eq!(sla.limit_for(TaskStatus::Planned), None);
+        assert!(sla.is_overdue(TaskStatus::InProgress, Duration::minutes(120)));
+        assert!(!sla.is_overdue(TaskStatus::InProgress, Duration::minutes(119)));
+
+        assert_eq!(SlaThresholds::parse(""), Some(SlaThresholds::default()));
+        assert_eq!(SlaThresholds::parse("review=2x"), None);
+        assert_eq!(SlaThresholds::parse("done=1h"), None);
+        assert_eq!(SlaThresholds::parse("review=0h"), None);
+        // An invalid setting falls back to the defaults
+        assert_eq!(SlaThresholds::from_setting(Some("bogus")).limit_for(TaskStatus::Review), Some(Duration::days(1)));
+        assert_eq!(format_age(Duration::minutes(90)), "1h");
+    }
+}
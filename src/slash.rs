@@ -0,0 +1,254 @@
+//! Slash commands in the new-task input
+//!
+//! Words starting with `/` set up the task instead of becoming part of its
+//! description:
+//!
+//!   /tag backend      add the `#backend` label
+//!   /model opus       run the task's Claude session on another model
+//!   /base release/1.2 branch the worktree off this ref instead of HEAD
+//!   /fresh            start without the project's shared caches
+//!   /env KEY=VALUE    run the task's sessions and panes with this variable
+//!                     (`KEY=secret:NAME` takes the value from the keychain)
+//!   /template bugfix  fill in a template (`{text}` marks where the rest goes)
+//!   /start            start the task as soon as it's created
+//!
+//! Templates are markdown files named after the template, read from the
+//! project's `.kanblam/templates/` first and `~/.config/kanblam/templates/`
+//! after. Unknown `/words` (paths like `/etc/hosts`) are left in the text.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// The commands, with what the autocomplete popup says about them
+pub const COMMANDS: &[(&str, &str)] = &[
+    ("/tag", "add a label"),
+    ("/model", "Claude model for the session"),
+    ("/base", "branch to start the worktree from"),
+    ("/fresh", "start without the project's shared caches"),
+    ("/env", "environment variable (KEY=VALUE or KEY=secret:NAME)"),
+    ("/template", "fill in a task template"),
+    ("/start", "start the task right away"),
+];
+
+/// Models offered after `/model` (any other name or model id also works)
+const MODELS: &[&str] = &["opus", "sonnet", "haiku"];
+
+/// A new-task input with its commands taken out
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SlashInput {
+    /// The description, templates filled in
+    pub text: String,
+    pub tags: Vec<String>,
+    pub model: Option<String>,
+    pub base: Option<String>,
+    pub env: BTreeMap<String, String>,
+    pub fresh: bool,
+    pub start: bool,
+}
+
+/// A candidate shown in the autocomplete popup
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Completion {
+    /// The word that replaces the one being typed
+    pub word: String,
+    pub hint: &'static str,
+}
+
+fn is_command(word: &str) -> bool {
+    COMMANDS.iter().any(|(name, _)| *name == word)
+}
+
+/// Whether the input uses any command
+pub fn has_commands(input: &str) -> bool {
+    input.split_whitespace().any(is_command)
+}
+
+/// Where templates are looked up, the project's own first
+pub fn template_dirs(project_dir: &Path) -> Vec<PathBuf> {
+    let mut dirs = vec![project_dir.join(".kanblam").join("templates")];
+    if let Some(home) = dirs::home_dir() {
+        dirs.push(home.join(".config").join("kanblam").join("templates"));
+    }
+    dirs
+}
+
+/// Names of the templates in `dirs`, sorted
+pub fn template_names(dirs: &[PathBuf]) -> Vec<String> {
+    let mut names: Vec<String> = dirs
+        .iter()
+        .filter_map(|dir| std::fs::read_dir(dir).ok())
+        .flatten()
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            if path.extension()? != "md" {
+                return None;
+            }
+            Some(path.file_stem()?.to_string_lossy().into_owned())
+        })
+        .collect();
+    names.sort();
+    names.dedup();
+    names
+}
+
+fn load_template(dirs: &[PathBuf], name: &str) -> Option<String> {
+    dirs.iter().find_map(|dir| std::fs::read_to_string(dir.join(format!("{}.md", name))).ok())
+}
+
+fn valid_tag(tag: &str) -> bool {
+    !tag.is_empty()
+        && tag.chars().all(|c| c.is_alphanumeric() || c == '-' || c == '_')
+        && !tag.chars().all(|c| c.is_ascii_digit())
+}
+
+/// Take the commands out of `input`. Errors name the command that's wrong.
+pub fn parse(input: &str, template_dirs: &[PathBuf]) -> Result<SlashInput, String> {
+    let mut parsed = SlashInput::default();
+    let mut templates = Vec::new();
+    let mut lines = Vec::new();
+    for line in input.lines() {
+        let mut words = line.split_whitespace();
+        let mut kept = Vec::new();
+        while let Some(word) = words.next() {
+            if !is_command(word) {
+                kept.push(word);
+                continue;
+            }
+            match word {
+                "/start" => {
+                    parsed.start = true;
+                    continue;
+                }
+                "/fresh" => {
+                    parsed.fresh = true;
+                    continue;
+                }
+                _ => {}
+            }
+            let Some(arg) = words.next() else {
+                return Err(format!("{} needs a value", word));
+            };
+            match word {
+                "/tag" => {
+                    let tag = arg.trim_start_matches('#').to_lowercase();
+                    if !valid_tag(&tag) {
+                        return Err(format!("'{}' isn't a valid tag", arg));
+                    }
+                    if !parsed.tags.contains(&tag) {
+                        parsed.tags.push(tag);
+                    }
+                }
+                "/model" => parsed.model = Some(arg.to_string()),
+                "/base" => parsed.base = Some(arg.to_string()),
+                "/env" => {
+                    let (key, value) = crate::secrets::parse_assignment(arg)?;
+                    parsed.env.insert(key, value);
+                }
+                _ => {
+                    let body = load_template(template_dirs, arg)
+                        .ok_or_else(|| format!("No template named '{}'", arg))?;
+                    templates.push(body);
+                }
+            }
+        }
+        // Lines that only held commands go away
+        if kept.is_empty() && !line.trim().is_empty() {
+            continue;
+        }
+        lines.push(kept.join(" "));
+    }
+
+    let mut text = lines.join("\n").trim().to_string();
+    for body in templates {
+        let body = body.trim();
+        text = if body.contains("{text}") {
+            body.replace("{text}", &text)
+        } else if text.is_empty() {
+            body.to_string()
+        } else {
+            format!("{}\n\n{}", text, body)
+        };
+    }
+    parsed.text = text.trim().to_string();
+    if parsed.text.is_empty() {
+        return Err("The task needs a description besides the commands".to_string());
+    }
+    Ok(parsed)
+}
+
+/// Candidates for the word being typed at the end of `before_cursor`:
+/// commands after a `/`, or values for the command before it
+pub fn completions(before_cursor: &str, tags: &[String], templates: &[String]) -> Vec<Completion> {
+    let word = before_cursor.rsplit(char::is_whitespace).next().unwrap_or_default();
+    let previous = before_cursor[..before_cursor.len() - word.len()].split_whitespace().next_back();
+    let values: Vec<&str> = match previous {
+        Some("/tag") => tags.iter().map(String::as_str).collect(),
+        Some("/model") => MODELS.to_vec(),
+        Some("/template") => templates.iter().map(String::as_str).collect(),
+        Some("/base") | Some("/env") => Vec::new(),
+        _ if word.starts_with('/') => {
+            return COMMANDS
+                .iter()
+                .filter(|(name, _)| name.starts_with(word))
+                .map(|(name, hint)| Completion { word: name.to_string(), hint })
+                .collect();
+        }
+        _ => Vec::new(),
+    };
+    values
+        .into_iter()
+        .filter(|value| value.starts_with(word) && *value != word)
+        .map(|value| Completion { word: value.to_string(), hint: "" })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_and_complete() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("bugfix.md"), "## Bug\n{text}\n\n## Expected\nNo crash").unwrap();
+        let dirs = vec![dir.path().to_path_buf()];
+
+        let parsed = parse(
+            "Fix the login crash /tag backend /model opus\n/base release/1.2 /env API_KEY=secret:staging /fresh /start",
+            &dirs,
+        ).unwrap();
+        assert_eq!(parsed, SlashInput {
+            text: "Fix the login crash".to_string(),
+            tags: vec!["backend".to_string()],
+            model: Some("opus".to_string()),
+            base: Some("release/1.2".to_string()),
+            env: BTreeMap::from([("API_KEY".to_string(), "secret:staging".to_string())]),
+            fresh: true,
+            start: true,
+        });
+        assert_eq!(
+            parse("/template bugfix Login crashes on empty password", &dirs).unwrap().text,
+            "## Bug\nLogin crashes on empty password\n\n## Expected\nNo crash",
+        );
+        assert_eq!(parse("Read /etc/hosts", &dirs).unwrap().text, "Read /etc/hosts");
+        assert!(parse("Fix it /model", &dirs).is_err());
+        assert!(parse("Fix it /template missing", &dirs).is_err());
+        assert!(parse("/tag 42 Fix it", &dirs).is_err());
+        assert!(parse("Fix it /env API_KEY", &dirs).is_err());
+        assert!(parse("/start", &dirs).is_err());
+        assert!(has_commands("Fix it /start") && !has_commands("Fix /etc/hosts"));
+        assert_eq!(template_names(&dirs), vec!["bugfix"]);
+
+        let words = |before: &str| -> Vec<String> {
+            completions(before, &["backend".to_string(), "bug".to_string()], &template_names(&dirs))
+                .into_iter()
+                .map(|c| c.word)
+                .collect()
+        };
+        assert_eq!(words("Fix it /t"), vec!["/tag", "/template"]);
+        assert_eq!(words("Fix it /f"), vec!["/fresh"]);
+        assert_eq!(words("Fix it /tag b"), vec!["backend", "bug"]);
+        assert_eq!(words("/template "), vec!["bugfix"]);
+        assert_eq!(words("/model son"), vec!["sonnet"]);
+        assert!(words("Fix it ").is_empty());
+    }
+}
@@ -0,0 +1,322 @@
+//! Spell checking for the input editor, with hunspell dictionaries
+//!
+//! The language set in Settings (e.g. `en_US`) names a `<lang>.aff` and
+//! `<lang>.dic` pair, looked up in `~/.config/kanblam/dictionaries/` and the
+//! usual system directories. Every word of the dictionary is expanded with
+//! its prefix and suffix rules when it's loaded, so checking is a set lookup.
+//! Code-looking words (paths, `#tags`, snake_case, camelCase, anything with
+//! digits) and all-caps abbreviations are never flagged.
+
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+/// The loaded dictionary and the language it's for (None inside = not found)
+static LOADED: Mutex<Option<(String, Option<Arc<Dictionary>>)>> = Mutex::new(None);
+
+/// Where dictionaries are looked for, kanblam's own first
+fn dictionary_dirs() -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+    if let Some(home) = dirs::home_dir() {
+        dirs.push(home.join(".config").join("kanblam").join("dictionaries"));
+        dirs.push(home.join("Library").join("Spelling"));
+    }
+    for dir in ["/usr/share/hunspell", "/usr/share/myspell", "/usr/share/myspell/dicts", "/Library/Spelling"] {
+        dirs.push(PathBuf::from(dir));
+    }
+    dirs
+}
+
+/// The `.aff` and `.dic` files of `language`, if installed
+pub fn find(language: &str) -> Option<(PathBuf, PathBuf)> {
+    dictionary_dirs().into_iter().find_map(|dir| {
+        let aff = dir.join(format!("{}.aff", language));
+        let dic = dir.join(format!("{}.dic", language));
+        (aff.exists() && dic.exists()).then_some((aff, dic))
+    })
+}
+
+/// The dictionary of `language`, loaded on first use and kept until another
+/// language is asked for
+pub fn dictionary(language: &str) -> Option<Arc<Dictionary>> {
+    let mut loaded = LOADED.lock().ok()?;
+    if let Some((loaded_language, dictionary)) = loaded.as_ref() {
+        if loaded_language == language {
+            return dictionary.clone();
+        }
+    }
+    let dictionary = find(language).and_then(|(aff, dic)| {
+        let aff = std::fs::read(aff).ok()?;
+        let dic = std::fs::read(dic).ok()?;
+        Some(Arc::new(Dictionary::parse(&String::from_utf8_lossy(&aff), &String::from_utf8_lossy(&dic))))
+    });
+    *loaded = Some((language.to_string(), dictionary.clone()));
+    dictionary
+}
+
+/// How flags are written in the `.aff` and `.dic` files
+#[derive(Clone, Copy)]
+enum FlagFormat {
+    /// One character per flag (the default)
+    Char,
+    /// Two characters per flag (`FLAG long`)
+    Long,
+    /// Comma-separated numbers (`FLAG num`)
+    Num,
+}
+
+impl FlagFormat {
+    fn split(self, flags: &str) -> Vec<String> {
+        match self {
+            FlagFormat::Char => flags.chars().map(String::from).collect(),
+            FlagFormat::Long => {
+                let chars: Vec<char> = flags.chars().collect();
+                chars.chunks(2).map(|pair| pair.iter().collect()).collect()
+            }
+            FlagFormat::Num => flags.split(',').map(|f| f.trim().to_string()).collect(),
+        }
+    }
+}
+
+/// One element of an affix condition
+#[derive(Debug, Clone)]
+enum CharClass {
+    Any,
+    Is(char),
+    OneOf(Vec<char>, bool),
+}
+
+impl CharClass {
+    fn matches(&self, ch: char) -> bool {
+        match self {
+            CharClass::Any => true,
+            CharClass::Is(c) => *c == ch,
+            CharClass::OneOf(set, negated) => set.contains(&ch) != *negated,
+        }
+    }
+}
+
+fn parse_condition(condition: &str) -> Vec<CharClass> {
+    let mut classes = Vec::new();
+    let mut chars = condition.chars();
+    while let Some(ch) = chars.next() {
+        classes.push(match ch {
+            '.' => CharClass::Any,
+            '[' => {
+                let mut set: Vec<char> = chars.by_ref().take_while(|c| *c != ']').collect();
+                let negated = set.first() == Some(&'^');
+                if negated {
+                    set.remove(0);
+                }
+                CharClass::OneOf(set, negated)
+            }
+            c => CharClass::Is(c),
+        });
+    }
+    classes
+}
+
+/// A prefix or suffix rule
+#[derive(Debug, Clone)]
+struct Affix {
+    strip: String,
+    add: String,
+    condition: Vec<CharClass>,
+    cross: bool,
+}
+
+impl Affix {
+    fn apply_suffix(&self, word: &str) -> Option<String> {
+        let chars: Vec<char> = word.chars().collect();
+        if chars.len() < self.condition.len() || !word.ends_with(&self.strip) {
+            return None;
+        }
+        let tail = &chars[chars.len() - self.condition.len()..];
+        if !self.condition.iter().zip(tail).all(|(class, ch)| class.matches(*ch)) {
+            return None;
+        }
+        Some(format!("{}{}", &word[..word.len() - self.strip.len()], self.add))
+    }
+
+    fn apply_prefix(&self, word: &str) -> Option<String> {
+        let chars: Vec<char> = word.chars().collect();
+        if chars.len() < self.condition.len() || !word.starts_with(&self.strip) {
+            return None;
+        }
+        if !self.condition.iter().zip(&chars).all(|(class, ch)| class.matches(*ch)) {
+            return None;
+        }
+        Some(format!("{}{}", self.add, &word[self.strip.len()..]))
+    }
+}
+
+/// Every word form of a dictionary
+#[derive(Debug, Default)]
+pub struct Dictionary {
+    words: HashSet<String>,
+}
+
+impl Dictionary {
+    /// Build from the text of an `.aff` and a `.dic` file
+    pub fn parse(aff: &str, dic: &str) -> Self {
+        let mut format = FlagFormat::Char;
+        let mut prefixes: HashMap<String, Vec<Affix>> = HashMap::new();
+        let mut suffixes: HashMap<String, Vec<Affix>> = HashMap::new();
+        let mut cross: HashMap<String, bool> = HashMap::new();
+
+        for line in aff.lines() {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            match fields.as_slice() {
+                ["FLAG", "long", ..] => format = FlagFormat::Long,
+                ["FLAG", "num", ..] => format = FlagFormat::Num,
+                [kind @ ("PFX" | "SFX"), flag, crosses @ ("Y" | "N"), count, ..] if count.parse::<usize>().is_ok() => {
+                    cross.insert(format!("{}{}", kind, flag), *crosses == "Y");
+                }
+                [kind @ ("PFX" | "SFX"), flag, strip, add, rest @ ..] => {
+                    let add = add.split('/').next().unwrap_or_default();
+                    let affix = Affix {
+                        strip: if *strip == "0" { String::new() } else { strip.to_string() },
+                        add: if add == "0" { String::new() } else { add.to_string() },
+                        condition: parse_condition(rest.first().copied().unwrap_or(".")),
+                        cross: cross.get(&format!("{}{}", kind, flag)).copied().unwrap_or(false),
+                    };
+                    let rules = if *kind == "PFX" { &mut prefixes } else { &mut suffixes };
+                    rules.entry(flag.to_string()).or_default().push(affix);
+                }
+                _ => {}
+            }
+        }
+
+        let mut words = HashSet::new();
+        // The first line is the word count
+        for line in dic.lines().skip(1) {
+            let entry = line.split(['\t', ' ']).next().unwrap_or_default();
+            let (word, flags) = entry.split_once('/').unwrap_or((entry, ""));
+            if word.is_empty() {
+                continue;
+            }
+            let flags = format.split(flags);
+            let word_prefixes: Vec<&Affix> = flags.iter().filter_map(|f| prefixes.get(f)).flatten().collect();
+            let word_suffixes: Vec<&Affix> = flags.iter().filter_map(|f| suffixes.get(f)).flatten().collect();
+
+            words.insert(word.to_string());
+            for suffix in &word_suffixes {
+                let Some(form) = suffix.apply_suffix(word) else {
+                    continue;
+                };
+                if suffix.cross {
+                    for prefix in word_prefixes.iter().filter(|p| p.cross) {
+                        words.extend(prefix.apply_prefix(&form));
+                    }
+                }
+                words.insert(form);
+            }
+            for prefix in &word_prefixes {
+                words.extend(prefix.apply_prefix(word));
+            }
+        }
+        Self { words }
+    }
+
+    /// Whether `word` is spelled right. A capitalized word also matches its
+    /// lowercase form, an all-caps one any form, and a trailing 's is allowed.
+    pub fn check(&self, word: &str) -> bool {
+        let word = word.trim_end_matches("'s").trim_end_matches("’s");
+        if self.words.contains(word) {
+            return true;
+        }
+        let lower = word.to_lowercase();
+        let capitalized = word.chars().next().is_some_and(char::is_uppercase);
+        let all_caps = !word.chars().any(char::is_lowercase);
+        (capitalized && self.words.contains(&lower))
+            || (all_caps && self.words.contains(&capitalize(&lower)))
+    }
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    chars.next().map_or_else(String::new, |first| first.to_uppercase().chain(chars).collect())
+}
+
+/// Whether a word should be checked at all: plain letters (apostrophes
+/// inside), not an abbreviation or camelCase
+fn is_checkable(word: &str) -> bool {
+    let letters = word.chars().filter(|c| c.is_alphabetic()).count();
+    letters >= 2
+        && word.chars().all(|c| c.is_alphabetic() || c == '\'' || c == '’')
+        && !word.chars().skip(1).any(char::is_uppercase)
+}
+
+/// Misspelled words of `line` as (first character, length in characters)
+pub fn misspelled(line: &str, dictionary: &Dictionary) -> Vec<(usize, usize)> {
+    let chars: Vec<char> = line.chars().collect();
+    let mut found = Vec::new();
+    let mut start = 0;
+    while start < chars.len() {
+        if chars[start].is_whitespace() {
+            start += 1;
+            continue;
+        }
+        let end = (start..chars.len()).find(|&i| chars[i].is_whitespace()).unwrap_or(chars.len());
+        // Trim surrounding punctuation; a chunk that still has symbols inside
+        // (paths, urls, code) is skipped whole
+        let is_edge = |c: char| !c.is_alphanumeric() && !"/\\_@#`$%{}<>=:~".contains(c);
+        let first = (start..end).find(|&i| !is_edge(chars[i])).unwrap_or(end);
+        let last = (first..end).rev().find(|&i| !is_edge(chars[i])).map_or(first, |i| i + 1);
+        let chunk: String = chars[first..last].iter().collect();
+        let chunk_start = first;
+        let mut offset = 0;
+        for part in chunk.split('-') {
+            let len = part.chars().count();
+            if is_checkable(part) && !dictionary.check(part) {
+                found.push((chunk_start + offset, len));
+            }
+            offset += len + 1;
+        }
+        start = end;
+    }
+    found
+}
+
+/// Words and characters of the input
+pub fn counts(text: &str) -> (usize, usize) {
+    (text.split_whitespace().count(), text.chars().filter(|c| *c != '\n').count())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_affixes_and_misspellings() {
+        let aff = "SET UTF-8\n\
+                   PFX U Y 1\n\
+                   PFX U 0 un .\n\
+                   SFX S Y 2\n\
+                   SFX S y ies [^aeiou]y\n\
+                   SFX S 0 s [^y]\n\
+                   SFX G Y 1\n\
+                   SFX G e ing e\n";
+        let dic = "5\nquery/S\ntask/SU\nwrite/G\nmerge/G\nParis\n";
+        let dictionary = Dictionary::parse(aff, dic);
+        for word in ["query", "queries", "tasks", "untask", "untasks", "writing", "Merging", "Paris", "Paris's", "TASK"] {
+            assert!(dictionary.check(word), "{} should be known", word);
+        }
+        for word in ["querys", "writeing", "paris", "pARIS"] {
+            assert!(!dictionary.check(word), "{} should be unknown", word);
+        }
+
+        let line = "Writting queries: see src/main.rs, fix_this, APIs and mergeing-tasks (untaskz).";
+        let flagged: Vec<String> = misspelled(line, &dictionary)
+            .into_iter()
+            .map(|(start, len)| line.chars().skip(start).take(len).collect())
+            .collect();
+        assert!(flagged.contains(&"Writting".to_string()));
+        assert!(flagged.contains(&"mergeing".to_string()));
+        assert!(flagged.contains(&"untaskz".to_string()));
+        assert!(!flagged.iter().any(|w| w == "queries" || w == "tasks" || w.contains("main") || w.contains("fix")));
+        assert!(!flagged.contains(&"APIs".to_string()));
+
+        assert_eq!(counts("Fix the\nlogin bug"), (4, 16));
+    }
+}
@@ -23,6 +23,8 @@ use std::path::PathBuf;
 use std::process::Command;
 use std::time::{Duration, Instant};
 
+use crate::timing::TimedCommand;
+
 /// Tmux key bindings for pane navigation
 #[derive(Clone)]
 pub struct TmuxKeys {
@@ -290,7 +292,7 @@ impl StatusbarState {
         if let Ok(output) = Command::new("git")
             .current_dir(&self.worktree_path)
             .args(["rev-parse", "--abbrev-ref", "HEAD"])
-            .output()
+            .timed_output()
         {
             if output.status.success() {
                 self.branch_name = Some(
@@ -309,7 +311,7 @@ impl StatusbarState {
             if let Ok(output) = Command::new("git")
                 .current_dir(&self.worktree_path)
                 .args(["rev-list", "--count", &format!("{}..HEAD", main)])
-                .output()
+                .timed_output()
             {
                 if output.status.success() {
                     self.ahead = String::from_utf8_lossy(&output.stdout)
@@ -323,7 +325,7 @@ impl StatusbarState {
             if let Ok(output) = Command::new("git")
                 .current_dir(&self.worktree_path)
                 .args(["rev-list", "--count", &format!("HEAD..{}", main)])
-                .output()
+                .timed_output()
             {
                 if output.status.success() {
                     self.behind = String::from_utf8_lossy(&output.stdout)
@@ -403,7 +405,7 @@ fn get_main_ref(project_dir: &PathBuf) -> Option<String> {
         if let Ok(output) = Command::new("git")
             .current_dir(project_dir)
             .args(["rev-parse", "--verify", ref_name])
-            .output()
+            .timed_output()
         {
             if output.status.success() {
                 return Some(ref_name.to_string());
@@ -987,7 +989,7 @@ fn do_rebase(state: &StatusbarState) -> Result<bool> {
     let _ = Command::new("git")
         .current_dir(&state.project_dir)
         .args(["fetch", "origin", "main"])
-        .output();
+        .timed_output();
 
     // Get main ref
     let main_ref = get_main_ref(&state.project_dir)
@@ -997,7 +999,7 @@ fn do_rebase(state: &StatusbarState) -> Result<bool> {
     let result = Command::new("git")
         .current_dir(&state.worktree_path)
         .args(["rebase", &main_ref])
-        .output()?;
+        .timed_output()?;
 
     if result.status.success() {
         Ok(true)
@@ -1006,7 +1008,7 @@ fn do_rebase(state: &StatusbarState) -> Result<bool> {
         let _ = Command::new("git")
             .current_dir(&state.worktree_path)
             .args(["rebase", "--abort"])
-            .output();
+            .timed_output();
         Ok(false)
     }
 }
@@ -1094,7 +1096,7 @@ fn get_merge_base(state: &StatusbarState) -> Option<String> {
     let output = Command::new("git")
         .current_dir(&state.worktree_path)
         .args(["merge-base", "HEAD", &main_ref])
-        .output()
+        .timed_output()
         .ok()?;
 
     if !output.status.success() {
@@ -1110,7 +1112,7 @@ fn get_merge_base(state: &StatusbarState) -> Option<String> {
     let short_output = Command::new("git")
         .current_dir(&state.worktree_path)
         .args(["rev-parse", "--short", &full_sha])
-        .output()
+        .timed_output()
         .ok()?;
 
     if short_output.status.success() {
@@ -1348,7 +1350,10 @@ fn run_loop(
         state.check_pane_active();
 
         // Render
-        let area = terminal.draw(|f| render(f, state))?.area;
+        let area = terminal.draw(|f| {
+            render(f, state);
+            crate::ui::palette::apply(f.buffer_mut());
+        })?.area;
 
         // Handle events with timeout (for periodic refresh)
         if event::poll(Duration::from_millis(500))? {
@@ -1400,17 +1405,17 @@ fn run_loop(
 /// Entry point for the statusbar subcommand
 pub fn main(args: &[String]) -> Result<()> {
     if args.is_empty() {
-        return Err(anyhow!("Usage: kanblam statusbar <task-id> [--parent <session-name>]"));
+        return Err(anyhow!("Usage: kanblam statusbar <task-id> [--parent <session-name>] [--palette <name>]"));
     }
 
     let task_id = &args[0];
 
-    // Parse optional --parent argument
-    let parent_session = if args.len() >= 3 && args[1] == "--parent" {
-        Some(args[2].clone())
-    } else {
-        None
-    };
+    // Parse optional --parent and --palette arguments
+    let option = |name: &str| args.windows(2).skip(1).find(|pair| pair[0] == name).map(|pair| pair[1].clone());
+    let parent_session = option("--parent");
+    if let Some(palette) = option("--palette").as_deref().and_then(crate::model::Palette::from_key) {
+        crate::ui::palette::set(palette);
+    }
 
     // Get worktree path from current directory or construct it
     let worktree_path = std::env::current_dir()?;
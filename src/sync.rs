@@ -0,0 +1,431 @@
+//! Optional board sync through a git repository
+//!
+//! With a sync remote set (Config, or `KANBLAM_SYNC_REMOTE` for the first start
+//! on a new machine), the state files are committed to branch `kanblam-state`
+//! of a repository in `<stem>-sync/` next to `state.json`. At startup the branch
+//! is fetched and merged before the state is loaded; on exit the saved state is
+//! committed and pushed.
+//!
+//! Edits made on two machines since they last synced are merged per file, per
+//! setting, and per item in lists with ids (projects, tasks): a change on one
+//! side wins over an unchanged copy, a deletion wins over an unchanged copy, and
+//! when both sides changed the same item the copy with the newest timestamp is
+//! kept (this machine's copy on a tie).
+
+use crate::worktree::git::git_stdout;
+use anyhow::{Context, Result};
+use chrono::{DateTime, FixedOffset};
+use serde_json::{Map, Value};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Branch the state is kept on
+pub const BRANCH: &str = "kanblam-state";
+
+/// Remote to use before the state file has one (a new machine)
+const REMOTE_ENV: &str = "KANBLAM_SYNC_REMOTE";
+
+/// Names in the sync repository, independent of the local state file's name
+const STATE_NAME: &str = "state.json";
+const PROJECTS_DIR: &str = "projects";
+
+/// What a pull did to the state on disk
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Pulled {
+    UpToDate,
+    /// The state on disk was replaced; `conflicts` items were changed on both sides
+    Updated { conflicts: usize },
+}
+
+/// The sync repository of `state_file`
+pub fn sync_dir(state_file: &Path) -> PathBuf {
+    let stem = state_file.file_stem().unwrap_or_default().to_string_lossy();
+    state_file.with_file_name(format!("{}-sync", stem))
+}
+
+/// The remote to sync with: the setting in the state file, else `KANBLAM_SYNC_REMOTE`
+pub fn remote(state_file: &Path) -> Option<String> {
+    let configured = std::fs::read_to_string(state_file).ok()
+        .and_then(|content| serde_json::from_str::<Value>(&content).ok())
+        .and_then(|state| state.get("global_settings")?.get("state_sync")?.as_str().map(str::to_string));
+    configured
+        .or_else(|| std::env::var(REMOTE_ENV).ok())
+        .map(|remote| remote.trim().to_string())
+        .filter(|remote| !remote.is_empty())
+}
+
+/// Merge the remote's state into the state on disk (backing up what it replaces)
+pub fn pull(state_file: &Path, remote: &str) -> Result<Pulled> {
+    let dir = ensure_repo(state_file, remote)?;
+    commit(state_file, &dir)?;
+
+    // Nothing was pushed yet: the first push creates the branch
+    if git_stdout(&dir, &["ls-remote", "--heads", "origin", BRANCH])?.trim().is_empty() {
+        return Ok(Pulled::UpToDate);
+    }
+    git_stdout(&dir, &["fetch", "origin", BRANCH])?;
+    let theirs = rev_parse(&dir, "FETCH_HEAD").context("Fetched nothing")?;
+
+    let conflicts = match rev_parse(&dir, "HEAD") {
+        // No local state yet
+        None => {
+            git_stdout(&dir, &["reset", "--hard", &theirs])?;
+            0
+        }
+        Some(ours) => {
+            // None for unrelated histories (both machines had state before the first sync)
+            let base = git_stdout(&dir, &["merge-base", &ours, &theirs]).ok().map(|base| base.trim().to_string());
+            if base.as_deref() == Some(theirs.as_str()) {
+                return Ok(Pulled::UpToDate);
+            }
+            if base.as_deref() == Some(ours.as_str()) {
+                git_stdout(&dir, &["merge", "--ff-only", &theirs])?;
+                0
+            } else {
+                merge(&dir, base.as_deref(), &theirs)?
+            }
+        }
+    };
+    install(state_file, &dir)?;
+    Ok(Pulled::Updated { conflicts })
+}
+
+/// Commit the state on disk and push it, merging first if the remote moved on
+pub fn push(state_file: &Path, remote: &str) -> Result<()> {
+    let dir = ensure_repo(state_file, remote)?;
+    commit(state_file, &dir)?;
+    if rev_parse(&dir, "HEAD").is_none() {
+        return Ok(());
+    }
+    if git_stdout(&dir, &["push", "origin", BRANCH]).is_ok() {
+        return Ok(());
+    }
+    // Another machine pushed since our last pull
+    pull(state_file, remote)?;
+    git_stdout(&dir, &["push", "origin", BRANCH])?;
+    Ok(())
+}
+
+/// Create the sync repository if needed and point it at `remote`
+fn ensure_repo(state_file: &Path, remote: &str) -> Result<PathBuf> {
+    let dir = sync_dir(state_file);
+    if !dir.join(".git").exists() {
+        std::fs::create_dir_all(&dir)
+            .with_context(|| format!("Failed to create {}", dir.display()))?;
+        git_stdout(&dir, &["init", "--quiet"])?;
+        git_stdout(&dir, &["symbolic-ref", "HEAD", &format!("refs/heads/{}", BRANCH)])?;
+    }
+    match git_stdout(&dir, &["remote", "get-url", "origin"]) {
+        Ok(url) if url.trim() == remote => {}
+        Ok(_) => { git_stdout(&dir, &["remote", "set-url", "origin", remote])?; }
+        Err(_) => { git_stdout(&dir, &["remote", "add", "origin", remote])?; }
+    }
+    // Commits need an author even on machines without a git identity
+    if git_stdout(&dir, &["config", "user.email"]).is_err() {
+        git_stdout(&dir, &["config", "user.name", "kanblam"])?;
+        git_stdout(&dir, &["config", "user.email", "kanblam@localhost"])?;
+    }
+    Ok(dir)
+}
+
+/// Copy the state on disk into the sync repository and commit it if it changed
+fn commit(state_file: &Path, dir: &Path) -> Result<()> {
+    if !state_file.exists() {
+        return Ok(());
+    }
+    std::fs::copy(state_file, dir.join(STATE_NAME))
+        .with_context(|| format!("Failed to copy {}", state_file.display()))?;
+    mirror_projects(&crate::app::project_state_dir(state_file), &dir.join(PROJECTS_DIR))?;
+
+    git_stdout(dir, &["add", "-A"])?;
+    if git_stdout(dir, &["status", "--porcelain"])?.trim().is_empty() {
+        return Ok(());
+    }
+    git_stdout(dir, &["commit", "--quiet", "-m", &format!("Board state from {}", host())])?;
+    Ok(())
+}
+
+/// Merge `theirs` into HEAD file by file, committing the result
+fn merge(dir: &Path, base: Option<&str>, theirs: &str) -> Result<usize> {
+    // Record both parents; the files are merged below
+    git_stdout(dir, &["merge", "--quiet", "--no-commit", "--no-ff", "--allow-unrelated-histories", "-s", "ours", theirs])?;
+
+    let mut paths: Vec<String> = ["HEAD", theirs].iter()
+        .filter_map(|rev| git_stdout(dir, &["ls-tree", "-r", "--name-only", rev]).ok())
+        .flat_map(|list| list.lines().map(str::to_string).collect::<Vec<_>>())
+        .collect();
+    paths.sort();
+    paths.dedup();
+
+    let mut conflicts = 0;
+    for path in paths {
+        let show = |rev: &str| git_stdout(dir, &["show", &format!("{}:{}", rev, path)]).ok();
+        let (merged, n) = merge_file(
+            base.and_then(show).as_deref(),
+            show("HEAD").as_deref(),
+            show(theirs).as_deref(),
+        );
+        conflicts += n;
+        let target = dir.join(&path);
+        match merged {
+            Some(content) => std::fs::write(&target, content)?,
+            None => { let _ = std::fs::remove_file(&target); }
+        }
+    }
+    git_stdout(dir, &["add", "-A"])?;
+    git_stdout(dir, &["commit", "--quiet", "-m", &format!("Merge board state on {}", host())])?;
+    Ok(conflicts)
+}
+
+/// Put the synced files in place of the state on disk
+fn install(state_file: &Path, dir: &Path) -> Result<()> {
+    let synced = dir.join(STATE_NAME);
+    if !synced.exists() {
+        return Ok(());
+    }
+    if state_file.exists() {
+        crate::model::backup::backup(state_file)?;
+    } else if let Some(parent) = state_file.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::copy(&synced, state_file)
+        .with_context(|| format!("Failed to write {}", state_file.display()))?;
+
+    let projects_dir = crate::app::project_state_dir(state_file);
+    let changed = mirror_projects(&dir.join(PROJECTS_DIR), &projects_dir)?;
+    // Tasks load from the project's .kanblam directory first, so update it too
+    for path in changed {
+        let project = std::fs::read_to_string(&path).ok()
+            .and_then(|content| serde_json::from_str::<crate::model::Project>(&content).ok());
+        if let Some(project) = project.filter(|p| p.scope_dir().is_dir()) {
+            if let Err(e) = project.save_tasks() {
+                crate::diagnostics::warn(crate::diagnostics::Source::Io, format!("Failed to save synced tasks for {}: {}", project.name, e));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Make the `*.json` files in `to` match those in `from`; returns the files written
+fn mirror_projects(from: &Path, to: &Path) -> Result<Vec<PathBuf>> {
+    let is_json = |path: &Path| path.extension().is_some_and(|ext| ext == "json");
+    std::fs::create_dir_all(to)
+        .with_context(|| format!("Failed to create {}", to.display()))?;
+
+    let mut written = Vec::new();
+    let mut names = Vec::new();
+    if let Ok(entries) = std::fs::read_dir(from) {
+        for entry in entries.flatten().filter(|e| is_json(&e.path())) {
+            let target = to.join(entry.file_name());
+            let content = std::fs::read(entry.path())?;
+            if std::fs::read(&target).ok().as_ref() != Some(&content) {
+                std::fs::write(&target, content)?;
+                written.push(target);
+            }
+            names.push(entry.file_name());
+        }
+    }
+    for entry in std::fs::read_dir(to)?.flatten() {
+        if is_json(&entry.path()) && !names.contains(&entry.file_name()) {
+            let _ = std::fs::remove_file(entry.path());
+        }
+    }
+    Ok(written)
+}
+
+/// Three-way merge of one state file (None = the file doesn't exist on that side).
+/// Returns the merged file (None = deleted) and how many items both sides changed.
+pub fn merge_file(base: Option<&str>, ours: Option<&str>, theirs: Option<&str>) -> (Option<String>, usize) {
+    if ours == theirs || base == theirs {
+        return (ours.map(str::to_string), 0);
+    }
+    if base == ours {
+        return (theirs.map(str::to_string), 0);
+    }
+    let parse = |content: &str| serde_json::from_str::<Value>(content).ok();
+    match (ours, theirs) {
+        (Some(ours), Some(theirs)) => match (parse(ours), parse(theirs)) {
+            (Some(ours_value), Some(theirs_value)) => {
+                let base_value = base.and_then(parse).unwrap_or(Value::Null);
+                let mut conflicts = 0;
+                let merged = merge_value(&base_value, &ours_value, &theirs_value, &mut conflicts);
+                let content = serde_json::to_string_pretty(&merged).unwrap_or_else(|_| ours.to_string());
+                (Some(content), conflicts)
+            }
+            // Not JSON: keep this machine's copy
+            _ => (Some(ours.to_string()), 1),
+        },
+        // Deleted on one side, changed on the other: keep the change
+        (Some(kept), None) | (None, Some(kept)) => (Some(kept.to_string()), 1),
+        (None, None) => (None, 0),
+    }
+}
+
+fn merge_value(base: &Value, ours: &Value, theirs: &Value, conflicts: &mut usize) -> Value {
+    if ours == theirs || base == theirs {
+        return ours.clone();
+    }
+    if base == ours {
+        return theirs.clone();
+    }
+    match (ours, theirs) {
+        (Value::Object(ours), Value::Object(theirs)) => {
+            Value::Object(merge_objects(base.as_object(), ours, theirs, conflicts))
+        }
+        (Value::Array(ours), Value::Array(theirs)) if has_ids(ours) && has_ids(theirs) => {
+            let base = base.as_array().map(Vec::as_slice).unwrap_or_default();
+            Value::Array(merge_items(base, ours, theirs, conflicts))
+        }
+        _ => {
+            *conflicts += 1;
+            ours.clone()
+        }
+    }
+}
+
+/// Merge key by key; a key removed on one side stays removed if the other didn't change it
+fn merge_objects(base: Option<&Map<String, Value>>, ours: &Map<String, Value>, theirs: &Map<String, Value>, conflicts: &mut usize) -> Map<String, Value> {
+    let base_of = |key: &str| base.and_then(|b| b.get(key));
+    let mut merged = Map::new();
+    for (key, ours_value) in ours {
+        match theirs.get(key) {
+            Some(theirs_value) => {
+                let base_value = base_of(key).cloned().unwrap_or(Value::Null);
+                merged.insert(key.clone(), merge_value(&base_value, ours_value, theirs_value, conflicts));
+            }
+            None => match base_of(key) {
+                Some(base_value) if base_value == ours_value => {}
+                changed => {
+                    *conflicts += usize::from(changed.is_some());
+                    merged.insert(key.clone(), ours_value.clone());
+                }
+            },
+        }
+    }
+    for (key, theirs_value) in theirs.iter().filter(|(key, _)| !ours.contains_key(*key)) {
+        match base_of(key) {
+            Some(base_value) if base_value == theirs_value => {}
+            changed => {
+                *conflicts += usize::from(changed.is_some());
+                merged.insert(key.clone(), theirs_value.clone());
+            }
+        }
+    }
+    merged
+}
+
+fn has_ids(items: &[Value]) -> bool {
+    items.iter().all(|item| item.get("id").is_some())
+}
+
+/// Merge lists of items by id, in this machine's order with new items from the other after
+fn merge_items(base: &[Value], ours: &[Value], theirs: &[Value], conflicts: &mut usize) -> Vec<Value> {
+    let find = |items: &'_ [Value], id: &Value| items.iter().find(|item| item.get("id") == Some(id)).cloned();
+    let mut merged = Vec::new();
+    for item in ours {
+        let id = &item["id"];
+        let base_item = find(base, id);
+        match find(theirs, id) {
+            Some(theirs_item) => merged.push(pick(base_item.as_ref(), item, &theirs_item, conflicts)),
+            None => match base_item {
+                // Deleted there, unchanged here
+                Some(base_item) if &base_item == item => {}
+                changed => {
+                    *conflicts += usize::from(changed.is_some());
+                    merged.push(item.clone());
+                }
+            },
+        }
+    }
+    for item in theirs.iter().filter(|item| find(ours, &item["id"]).is_none()) {
+        match find(base, &item["id"]) {
+            // Deleted here, unchanged there
+            Some(base_item) if &base_item == item => {}
+            changed => {
+                *conflicts += usize::from(changed.is_some());
+                merged.push(item.clone());
+            }
+        }
+    }
+    merged
+}
+
+/// The changed copy of an item, or the newest one if both changed
+fn pick(base: Option<&Value>, ours: &Value, theirs: &Value, conflicts: &mut usize) -> Value {
+    if ours == theirs || base == Some(theirs) {
+        return ours.clone();
+    }
+    if base == Some(ours) {
+        return theirs.clone();
+    }
+    *conflicts += 1;
+    if newest_timestamp(theirs) > newest_timestamp(ours) {
+        theirs.clone()
+    } else {
+        ours.clone()
+    }
+}
+
+/// The latest of an item's `*_at` timestamps
+fn newest_timestamp(item: &Value) -> Option<DateTime<FixedOffset>> {
+    item.as_object()?.iter()
+        .filter(|(key, _)| key.ends_with("_at"))
+        .filter_map(|(_, value)| DateTime::parse_from_rfc3339(value.as_str()?).ok())
+        .max()
+}
+
+fn rev_parse(dir: &Path, rev: &str) -> Option<String> {
+    git_stdout(dir, &["rev-parse", "--verify", "--quiet", rev]).ok().map(|sha| sha.trim().to_string())
+}
+
+/// This machine's name, for commit messages
+fn host() -> String {
+    Command::new("hostname").output().ok()
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| "another machine".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_merge_file_by_task() {
+        let task = |id: &str, title: &str, at: &str| json!({ "id": id, "title": title, "created_at": at });
+        let base = json!({ "name": "p", "tasks": [
+            task("a", "A", "2025-01-01T00:00:00Z"),
+            task("b", "B", "2025-01-01T00:00:00Z"),
+            task("c", "C", "2025-01-01T00:00:00Z"),
+        ] });
+        // Here: A renamed (older), B deleted, D added
+        let ours = json!({ "name": "p", "tasks": [
+            task("a", "A here", "2025-01-02T00:00:00Z"),
+            task("c", "C", "2025-01-01T00:00:00Z"),
+            task("d", "D", "2025-01-02T00:00:00Z"),
+        ] });
+        // There: A renamed (newer), C renamed, project renamed, E added
+        let theirs = json!({ "name": "q", "tasks": [
+            task("a", "A there", "2025-01-03T00:00:00Z"),
+            task("b", "B", "2025-01-01T00:00:00Z"),
+            task("c", "C there", "2025-01-01T00:00:00Z"),
+            task("e", "E", "2025-01-02T00:00:00Z"),
+        ] });
+
+        let (merged, conflicts) = merge_file(
+            Some(&base.to_string()), Some(&ours.to_string()), Some(&theirs.to_string()),
+        );
+        let merged: Value = serde_json::from_str(&merged.unwrap()).unwrap();
+        let titles: Vec<&str> = merged["tasks"].as_array().unwrap().iter()
+            .map(|t| t["title"].as_str().unwrap())
+            .collect();
+        assert_eq!(titles, vec!["A there", "C there", "D", "E"]);
+        assert_eq!(merged["name"], "q");
+        assert_eq!(conflicts, 1);
+
+        // A file only one side changed is taken whole
+        assert_eq!(merge_file(Some("x"), Some("x"), Some("y")), (Some("y".to_string()), 0));
+        assert_eq!(merge_file(Some("x"), None, Some("x")), (None, 0));
+    }
+}
@@ -0,0 +1,274 @@
+//! Timing of git commands, sidecar calls and redraws
+//!
+//! Every instrumented operation lands in a rolling window per kind (for the
+//! latency histogram) and, if it is among the slowest so far, in a short list
+//! that keeps its arguments. The diagnostics modal (~) shows both, so "why
+//! does kanblam stutter on this repo" comes down to reading off the worst
+//! offenders of the current session. It also shows how long each phase of
+//! startup took, up to the first frame.
+
+use chrono::{DateTime, Local};
+use std::collections::VecDeque;
+use std::io;
+use std::process::{Command, ExitStatus, Output};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// What kind of operation was timed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OpKind {
+    Git,
+    Sidecar,
+    Render,
+}
+
+impl OpKind {
+    pub fn all() -> [OpKind; 3] {
+        [OpKind::Git, OpKind::Sidecar, OpKind::Render]
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            OpKind::Git => "Git commands",
+            OpKind::Sidecar => "Sidecar calls",
+            OpKind::Render => "Redraws",
+        }
+    }
+}
+
+/// Upper bounds of the histogram buckets; the last bucket is open-ended
+pub const BUCKET_BOUNDS_MS: [u64; 7] = [1, 5, 20, 50, 100, 250, 1000];
+
+/// Recent durations kept per kind for the histogram
+const WINDOW: usize = 1000;
+
+/// Slowest operations kept per kind
+const SLOWEST: usize = 8;
+
+/// Longest label kept for an operation (arguments can be huge)
+const MAX_LABEL: usize = 160;
+
+/// One slow operation with its arguments
+#[derive(Debug, Clone)]
+pub struct Sample {
+    pub label: String,
+    pub duration: Duration,
+    pub at: DateTime<Local>,
+}
+
+/// Summary of one kind of operation
+#[derive(Debug, Clone)]
+pub struct KindReport {
+    pub kind: OpKind,
+    /// Operations since startup
+    pub count: u64,
+    /// Operations in the rolling window, per bucket of `BUCKET_BOUNDS_MS`
+    pub histogram: [usize; BUCKET_BOUNDS_MS.len() + 1],
+    pub p50: Duration,
+    pub p95: Duration,
+    /// Slowest first
+    pub slowest: Vec<Sample>,
+}
+
+#[derive(Debug)]
+struct KindTimings {
+    recent: VecDeque<Duration>,
+    slowest: Vec<Sample>,
+    count: u64,
+}
+
+impl KindTimings {
+    const fn new() -> Self {
+        Self { recent: VecDeque::new(), slowest: Vec::new(), count: 0 }
+    }
+
+    fn record(&mut self, label: impl FnOnce() -> String, duration: Duration) {
+        self.count += 1;
+        if self.recent.len() == WINDOW {
+            self.recent.pop_front();
+        }
+        self.recent.push_back(duration);
+
+        // Only build the label for operations that make the list
+        if self.slowest.len() == SLOWEST && self.slowest.last().is_some_and(|s| s.duration >= duration) {
+            return;
+        }
+        let mut label = label();
+        if label.chars().count() > MAX_LABEL {
+            label = label.chars().take(MAX_LABEL - 1).collect::<String>() + "…";
+        }
+        self.slowest.push(Sample { label, duration, at: Local::now() });
+        self.slowest.sort_by_key(|s| std::cmp::Reverse(s.duration));
+        self.slowest.truncate(SLOWEST);
+    }
+
+    fn report(&self, kind: OpKind) -> KindReport {
+        let mut histogram = [0; BUCKET_BOUNDS_MS.len() + 1];
+        for duration in &self.recent {
+            let ms = duration.as_millis() as u64;
+            let bucket = BUCKET_BOUNDS_MS.iter().position(|&bound| ms < bound).unwrap_or(BUCKET_BOUNDS_MS.len());
+            histogram[bucket] += 1;
+        }
+
+        let mut sorted: Vec<Duration> = self.recent.iter().copied().collect();
+        sorted.sort();
+        let percentile = |p: usize| sorted.get((sorted.len() * p / 100).min(sorted.len().saturating_sub(1))).copied().unwrap_or_default();
+
+        KindReport {
+            kind,
+            count: self.count,
+            histogram,
+            p50: percentile(50),
+            p95: percentile(95),
+            slowest: self.slowest.clone(),
+        }
+    }
+}
+
+static TIMINGS: Mutex<[KindTimings; 3]> = Mutex::new([KindTimings::new(), KindTimings::new(), KindTimings::new()]);
+
+fn slot(kind: OpKind) -> usize {
+    match kind {
+        OpKind::Git => 0,
+        OpKind::Sidecar => 1,
+        OpKind::Render => 2,
+    }
+}
+
+/// Record an operation that took `duration`; `label` (what ran, with its
+/// arguments) is only built if the operation is among the slowest
+pub fn record(kind: OpKind, label: impl FnOnce() -> String, duration: Duration) {
+    if let Ok(mut timings) = TIMINGS.lock() {
+        timings[slot(kind)].record(label, duration);
+    }
+}
+
+/// Run `f` and record how long it took
+pub fn time<T>(kind: OpKind, label: impl FnOnce() -> String, f: impl FnOnce() -> T) -> T {
+    let started = Instant::now();
+    let result = f();
+    record(kind, label, started.elapsed());
+    result
+}
+
+/// Summary of every kind of operation since startup
+pub fn report() -> Vec<KindReport> {
+    let Ok(timings) = TIMINGS.lock() else {
+        return Vec::new();
+    };
+    OpKind::all().into_iter().map(|kind| timings[slot(kind)].report(kind)).collect()
+}
+
+/// Phases of startup, each with the time since the previous one
+struct Startup {
+    last: Option<Instant>,
+    phases: Vec<(&'static str, Duration)>,
+}
+
+static STARTUP: Mutex<Startup> = Mutex::new(Startup { last: None, phases: Vec::new() });
+
+/// Start timing startup (call first thing in `main`)
+pub fn begin_startup() {
+    if let Ok(mut startup) = STARTUP.lock() {
+        startup.last = Some(Instant::now());
+        startup.phases.clear();
+    }
+}
+
+/// Record that startup finished `phase` (the time since the previous phase ended)
+pub fn startup_phase(phase: &'static str) {
+    let Ok(mut startup) = STARTUP.lock() else {
+        return;
+    };
+    let Some(last) = startup.last else {
+        return;
+    };
+    let now = Instant::now();
+    startup.phases.push((phase, now - last));
+    startup.last = Some(now);
+    tracing::debug!("Startup: {} took {:?}", phase, now - last);
+}
+
+/// Startup phases in order, with how long each took
+pub fn startup_phases() -> Vec<(&'static str, Duration)> {
+    STARTUP.lock().map(|startup| startup.phases.clone()).unwrap_or_default()
+}
+
+/// `Command::output`/`status` for git commands, recording how long they took.
+/// Commands in remote projects run on the remote over SSH, and commands in
+/// snapshot projects use the project's shadow repository.
+pub trait TimedCommand {
+    fn timed_output(&mut self) -> io::Result<Output>;
+    fn timed_status(&mut self) -> io::Result<ExitStatus>;
+}
+
+impl TimedCommand for Command {
+    fn timed_output(&mut self) -> io::Result<Output> {
+        crate::worktree::snapshot::route(self);
+        let started = Instant::now();
+        let output = match crate::remote::over_ssh(self) {
+            Some(mut ssh) => ssh.output(),
+            None => self.output(),
+        };
+        record(OpKind::Git, || command_label(self), started.elapsed());
+        output
+    }
+
+    fn timed_status(&mut self) -> io::Result<ExitStatus> {
+        crate::worktree::snapshot::route(self);
+        let started = Instant::now();
+        let status = match crate::remote::over_ssh(self) {
+            Some(mut ssh) => ssh.status(),
+            None => self.status(),
+        };
+        record(OpKind::Git, || command_label(self), started.elapsed());
+        status
+    }
+}
+
+/// "git diff --numstat a..b (in my-repo)"
+fn command_label(command: &Command) -> String {
+    let mut label = command.get_program().to_string_lossy().to_string();
+    for arg in command.get_args() {
+        label.push(' ');
+        label.push_str(&arg.to_string_lossy());
+    }
+    if let Some(name) = command.get_current_dir().and_then(|dir| dir.file_name()) {
+        label.push_str(&format!(" (in {})", name.to_string_lossy()));
+    }
+    label
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_histogram_and_slowest_operations() {
+        let mut timings = KindTimings::new();
+        for ms in [0, 3, 3, 30, 400, 2000] {
+            timings.record(|| format!("op {}", ms), Duration::from_millis(ms));
+        }
+        for _ in 0..SLOWEST {
+            timings.record(|| "fast".to_string(), Duration::ZERO);
+        }
+
+        let report = timings.report(OpKind::Git);
+        assert_eq!(report.count, 6 + SLOWEST as u64);
+        assert_eq!(report.histogram, [1 + SLOWEST, 2, 0, 1, 0, 0, 1, 1]);
+        assert_eq!(report.slowest[0].label, "op 2000");
+        assert_eq!(report.slowest[1].label, "op 400");
+        assert_eq!(report.slowest.len(), SLOWEST);
+        assert_eq!(report.p50, Duration::ZERO);
+        assert_eq!(report.p95, Duration::from_millis(2000));
+    }
+
+    #[test]
+    fn test_startup_phases_in_order() {
+        begin_startup();
+        startup_phase("load state");
+        startup_phase("first frame");
+        let phases: Vec<&str> = startup_phases().into_iter().map(|(phase, _)| phase).collect();
+        assert_eq!(phases, vec!["load state", "first frame"]);
+    }
+}
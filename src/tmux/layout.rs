@@ -0,0 +1,117 @@
+//! Pane layout of a task's interactive tmux session
+//!
+//! By default a session has Claude on the left and a shell on the right. A
+//! project can list its own panes instead, separated by "|":
+//!
+//!   claude 55% | *npm run dev 40% | cargo watch -x test | shell
+//!
+//! Claude always comes first and takes the left column; the other panes are
+//! stacked top to bottom on the right. A trailing "NN%" sizes a pane (Claude's
+//! share of the width, the others' share of the height), `shell` is a login
+//! shell, and a leading `*` marks the pane that gets the keyboard when the
+//! session is opened (Claude unless another is marked). Commands that exit
+//! leave a shell behind so their output stays readable.
+
+/// What runs in a pane
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PaneCommand {
+    Claude,
+    Shell,
+    Run(String),
+}
+
+/// One pane of a layout
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LayoutPane {
+    pub command: PaneCommand,
+    /// Percent of the window (None = tmux's even split)
+    pub size: Option<u8>,
+}
+
+/// The panes of a session, Claude first
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SessionLayout {
+    pub panes: Vec<LayoutPane>,
+    /// Index of the pane selected when the session opens
+    pub focus: usize,
+}
+
+impl Default for SessionLayout {
+    /// Claude on the left, a shell on the right
+    fn default() -> Self {
+        Self {
+            panes: vec![
+                LayoutPane { command: PaneCommand::Claude, size: None },
+                LayoutPane { command: PaneCommand::Shell, size: None },
+            ],
+            focus: 0,
+        }
+    }
+}
+
+impl SessionLayout {
+    /// Parse the setting. None if Claude isn't the first and only Claude pane,
+    /// more than one pane is focused, or a size is out of range.
+    pub fn parse(spec: &str) -> Option<Self> {
+        let mut panes = Vec::new();
+        let mut focus = None;
+        for entry in spec.split('|').map(str::trim) {
+            let (focused, entry) = match entry.strip_prefix('*') {
+                Some(rest) => (true, rest.trim_start()),
+                None => (false, entry),
+            };
+            let (command, size) = match entry.rsplit_once(char::is_whitespace) {
+                Some((command, last)) if last.ends_with('%') => (command.trim_end(), Some(last)),
+                _ if entry.ends_with('%') && !entry.contains(char::is_whitespace) => ("", Some(entry)),
+                _ => (entry, None),
+            };
+            let size = match size {
+                Some(size) => Some(size.trim_end_matches('%').parse::<u8>().ok().filter(|s| (5..=95).contains(s))?),
+                None => None,
+            };
+            let command = match command {
+                "" => return None,
+                "claude" => PaneCommand::Claude,
+                "shell" => PaneCommand::Shell,
+                other => PaneCommand::Run(other.to_string()),
+            };
+            if (command == PaneCommand::Claude) != panes.is_empty() {
+                return None;
+            }
+            if focused && focus.replace(panes.len()).is_some() {
+                return None;
+            }
+            panes.push(LayoutPane { command, size });
+        }
+        Some(Self { panes, focus: focus.unwrap_or(0) })
+    }
+
+    /// A project's layout: its setting if it parses, else the default
+    pub fn for_project(spec: Option<&str>) -> Self {
+        spec.and_then(Self::parse).unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_layout() {
+        let layout = SessionLayout::parse("claude 55% | *npm run dev 40% | cargo watch -x test | shell").unwrap();
+        assert_eq!(layout.panes.len(), 4);
+        assert_eq!(layout.panes[0], LayoutPane { command: PaneCommand::Claude, size: Some(55) });
+        assert_eq!(layout.panes[1], LayoutPane { command: PaneCommand::Run("npm run dev".to_string()), size: Some(40) });
+        assert_eq!(layout.panes[2].size, None);
+        assert_eq!(layout.panes[3].command, PaneCommand::Shell);
+        assert_eq!(layout.focus, 1);
+
+        assert_eq!(SessionLayout::parse("claude"), Some(SessionLayout { panes: vec![LayoutPane { command: PaneCommand::Claude, size: None }], focus: 0 }));
+        assert_eq!(SessionLayout::parse("shell | claude"), None);
+        assert_eq!(SessionLayout::parse("claude | claude"), None);
+        assert_eq!(SessionLayout::parse("claude | *shell | *htop"), None);
+        assert_eq!(SessionLayout::parse("claude 100% | shell"), None);
+        assert_eq!(SessionLayout::parse("claude | "), None);
+        assert_eq!(SessionLayout::for_project(Some("nonsense")), SessionLayout::default());
+    }
+}
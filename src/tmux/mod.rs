@@ -1,6 +1,12 @@
 #[allow(dead_code)]
 mod capture;
+mod layout;
+pub mod query;
+pub mod scrollback;
 mod session;
+pub mod shell;
+
+pub use layout::SessionLayout;
 
 pub use session::{
     // Worktree-based task session management
@@ -10,9 +16,13 @@ pub use session::{
     open_popup_detached,
     // SDK/CLI handoff support
     send_key_to_pane, capture_pane_with_escapes,
+    // Tiled monitor of in-progress tasks
+    capture_task_tail,
+    // Idle detection
+    capture_pane_tail, pane_pid,
     get_pane_size, open_popup,
     // CLI state detection
-    kill_claude_cli_session,
+    kill_claude_cli_session, respawn_claude_pane,
     // Question detection for idle_prompt handling
     claude_output_contains_question,
     // Quick pane split for Ctrl-T
@@ -0,0 +1,165 @@
+//! Every tmux pane from one `list-panes -a` call
+//!
+//! Asking tmux whether a task's window exists, or which process runs in a
+//! pane, used to cost a process spawn per task. Instead one snapshot of all
+//! sessions, windows and panes is taken and shared until it is `MAX_AGE` old,
+//! so a tick's worth of checks costs a single `tmux` call. The tmux functions that
+//! create or kill windows call `invalidate` so the next check sees the change.
+
+use std::process::Command;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// How long a snapshot answers queries before tmux is asked again
+const MAX_AGE: Duration = Duration::from_millis(500);
+
+/// Fields asked of `list-panes`; the window name goes last since it may contain anything
+const FORMAT: &str = "#{session_name}\t#{window_index}\t#{window_active}\t#{pane_index}\t#{pane_id}\t#{pane_active}\t#{pane_at_top}\t#{pane_at_left}\t#{pane_pid}\t#{pane_dead}\t#{window_name}";
+
+/// One pane as tmux listed it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pane {
+    pub session: String,
+    pub window_index: u32,
+    pub window_name: String,
+    pub window_active: bool,
+    pub pane_index: u32,
+    pub pane_id: String,
+    pub pane_active: bool,
+    /// Top-left pane of its window (where Claude runs in handoff sessions)
+    pub top_left: bool,
+    pub pid: Option<u32>,
+    pub dead: bool,
+}
+
+/// All panes of the running tmux server (none if no server is running)
+#[derive(Debug, Clone, Default)]
+pub struct Snapshot {
+    pub panes: Vec<Pane>,
+}
+
+impl Snapshot {
+    /// Ask tmux for every pane
+    pub fn take() -> Self {
+        let output = Command::new("tmux")
+            .args(["list-panes", "-a", "-F", FORMAT])
+            .output();
+        match output {
+            Ok(output) if output.status.success() => Self::parse(&String::from_utf8_lossy(&output.stdout)),
+            _ => Self::default(),
+        }
+    }
+
+    fn parse(listing: &str) -> Self {
+        let flag = |value: &str| value == "1";
+        let panes = listing.lines()
+            .filter_map(|line| {
+                let fields: Vec<&str> = line.splitn(11, '\t').collect();
+                let [session, window_index, window_active, pane_index, pane_id, pane_active, at_top, at_left, pid, dead, window_name] = fields[..] else {
+                    return None;
+                };
+                Some(Pane {
+                    session: session.to_string(),
+                    window_index: window_index.parse().ok()?,
+                    window_name: window_name.to_string(),
+                    window_active: flag(window_active),
+                    pane_index: pane_index.parse().ok()?,
+                    pane_id: pane_id.to_string(),
+                    pane_active: flag(pane_active),
+                    top_left: flag(at_top) && flag(at_left),
+                    pid: pid.parse().ok(),
+                    dead: flag(dead),
+                })
+            })
+            .collect();
+        Self { panes }
+    }
+
+    pub fn has_session(&self, session: &str) -> bool {
+        self.panes.iter().any(|p| p.session == session)
+    }
+
+    pub fn has_window(&self, session: &str, window_name: &str) -> bool {
+        self.panes.iter().any(|p| p.session == session && p.window_name == window_name)
+    }
+
+    /// The pane a target names, for the target forms kanblam uses:
+    /// `session`, `session:window`, `session:.{top-left}` and `%id`
+    pub fn pane(&self, target: &str) -> Option<&Pane> {
+        if target.starts_with('%') {
+            return self.panes.iter().find(|p| p.pane_id == target);
+        }
+        let (session, rest) = target.split_once(':').unwrap_or((target, ""));
+        let (window, pane) = match rest.split_once('.') {
+            Some((window, pane)) => (window, Some(pane)),
+            None => (rest, None),
+        };
+        let in_window = |p: &&Pane| {
+            p.session == session && match window {
+                "" => p.window_active,
+                window => p.window_name == window || p.window_index.to_string() == window,
+            }
+        };
+        let mut candidates = self.panes.iter().filter(in_window);
+        match pane {
+            None | Some("") => candidates.find(|p| p.pane_active),
+            Some("{top-left}") => candidates.find(|p| p.top_left),
+            Some(index) => candidates.find(|p| p.pane_index.to_string() == index),
+        }
+    }
+}
+
+static CACHE: Mutex<Option<(Instant, Arc<Snapshot>)>> = Mutex::new(None);
+
+/// A snapshot no older than `MAX_AGE`, taking a new one if needed
+pub fn snapshot() -> Arc<Snapshot> {
+    if let Ok(cache) = CACHE.lock() {
+        if let Some((_, snapshot)) = cache.as_ref().filter(|(taken, _)| taken.elapsed() < MAX_AGE) {
+            return Arc::clone(snapshot);
+        }
+    }
+    let snapshot = Arc::new(Snapshot::take());
+    if let Ok(mut cache) = CACHE.lock() {
+        *cache = Some((Instant::now(), Arc::clone(&snapshot)));
+    }
+    snapshot
+}
+
+/// Forget the snapshot after creating or killing sessions, windows or panes
+pub fn invalidate() {
+    if let Ok(mut cache) = CACHE.lock() {
+        *cache = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_and_resolve_targets() {
+        let listing = "\
+kc-proj\t0\t1\t0\t%1\t1\t1\t1\t100\t0\tshell
+kc-proj\t1\t0\t0\t%2\t0\t1\t1\t200\t0\ttask-7
+kc-proj\t1\t0\t1\t%3\t1\t1\t0\t201\t0\ttask-7
+task-9\t0\t1\t0\t%4\t0\t1\t1\t300\t0\tclaude\twith tab
+task-9\t0\t1\t1\t%5\t1\t1\t0\t301\t1\tclaude\twith tab
+garbage line
+";
+        let snapshot = Snapshot::parse(listing);
+        assert_eq!(snapshot.panes.len(), 5);
+        assert!(snapshot.has_session("task-9"));
+        assert!(snapshot.has_window("kc-proj", "task-7"));
+        assert!(!snapshot.has_window("kc-proj", "task-8"));
+        assert_eq!(snapshot.panes[3].window_name, "claude\twith tab");
+
+        let pid = |target: &str| snapshot.pane(target).and_then(|p| p.pid);
+        assert_eq!(pid("kc-proj:task-7"), Some(201));
+        assert_eq!(pid("kc-proj:1.0"), Some(200));
+        assert_eq!(pid("task-9:.{top-left}"), Some(300));
+        assert_eq!(pid("task-9"), Some(301));
+        assert_eq!(pid("%2"), Some(200));
+        assert_eq!(pid("kc-other:task-7"), None);
+        assert!(snapshot.pane("task-9").is_some_and(|p| p.dead));
+    }
+}
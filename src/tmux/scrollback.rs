@@ -0,0 +1,67 @@
+//! Scrollback captures: a task session's full history saved to a file
+//!
+//! Every pane of the session (Claude, shells, dev servers) is captured with
+//! its whole scrollback, so what happened can still be read after the window
+//! is killed. The kanblam status bar pane is left out.
+
+use anyhow::{anyhow, Result};
+use chrono::Utc;
+use std::path::PathBuf;
+use std::process::Command;
+
+/// Where captures are written
+fn capture_dir() -> PathBuf {
+    dirs::data_local_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("kanblam")
+        .join("scrollback")
+}
+
+/// The full scrollback of every pane of `target` (a session or window),
+/// wrapped lines joined, each pane under a header when there are several
+pub fn capture(target: &str) -> Result<String> {
+    let output = Command::new("tmux")
+        .args(["list-panes", "-t", target, "-F", "#{pane_id}\t#{pane_current_command}\t#{pane_title}"])
+        .output()?;
+    if !output.status.success() {
+        return Err(anyhow!("{}", String::from_utf8_lossy(&output.stderr).trim()));
+    }
+    let panes: Vec<(String, String)> = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.splitn(3, '\t');
+            let id = fields.next()?.to_string();
+            let command = fields.next().unwrap_or_default();
+            let title = fields.next().unwrap_or_default();
+            (command != "kanblam").then(|| (id, format!("{} {}", command, title).trim().to_string()))
+        })
+        .collect();
+
+    let mut text = String::new();
+    for (idx, (pane_id, label)) in panes.iter().enumerate() {
+        let output = Command::new("tmux")
+            .args(["capture-pane", "-p", "-J", "-S", "-", "-E", "-", "-t", pane_id])
+            .output()?;
+        if !output.status.success() {
+            continue;
+        }
+        if panes.len() > 1 {
+            text.push_str(&format!("──── pane {}: {} ────\n", idx + 1, label));
+        }
+        text.push_str(String::from_utf8_lossy(&output.stdout).trim_end());
+        text.push_str("\n\n");
+    }
+    if text.trim().is_empty() {
+        return Err(anyhow!("the session has no output"));
+    }
+    Ok(text)
+}
+
+/// Write a capture of the task `display_id` to a new file, returning its path
+pub fn save(display_id: &str, text: &str) -> std::io::Result<PathBuf> {
+    let dir = capture_dir();
+    std::fs::create_dir_all(&dir)?;
+    let path = dir.join(format!("{}-{}.log", display_id, Utc::now().format("%Y%m%d-%H%M%S")));
+    std::fs::write(&path, text)?;
+    Ok(path)
+}
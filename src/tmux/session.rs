@@ -4,6 +4,8 @@ use anyhow::{anyhow, Result};
 use std::path::PathBuf;
 use std::process::Command;
 
+use super::layout::{PaneCommand, SessionLayout};
+
 /// Switch to a specific pane - handles both same-session and different-session cases
 pub fn switch_to_session(pane_id: &str) -> Result<()> {
     // Get the session name for the target pane
@@ -153,6 +155,7 @@ pub fn get_or_create_project_session(project_slug: &str) -> Result<String> {
         let stderr = String::from_utf8_lossy(&output.stderr);
         return Err(anyhow!("Failed to create session: {}", stderr));
     }
+    super::query::invalidate();
 
     Ok(session_name)
 }
@@ -187,9 +190,9 @@ pub fn create_task_window(
         }
     }
 
-    // Create new window in the session
-    let output = Command::new("tmux")
-        .args([
+    // Create new window in the session (a shell on the host for remote projects)
+    let output = crate::secrets::tmux_pane_output(
+        Command::new("tmux").args([
             "new-window",
             "-t",
             &session_name,
@@ -197,13 +200,16 @@ pub fn create_task_window(
             &window_name,
             "-c",
             &worktree_path.to_string_lossy(),
-        ])
-        .output()?;
+        ]),
+        worktree_path,
+        crate::remote::pane_command(worktree_path, None).into_iter().collect(),
+    )?;
 
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
         return Err(anyhow!("Failed to create window: {}", stderr));
     }
+    super::query::invalidate();
 
     Ok(window_name)
 }
@@ -333,7 +339,7 @@ pub fn get_pane_size(target: &str) -> Result<(u16, u16)> {
 
 /// Open a combined tmux session with three panes:
 /// - Claude on left (pane 0)
-/// - Shell on right (pane 1)
+/// - Shell on right (pane 1), or the project's `layout` panes stacked there
 /// - Statusbar at bottom (pane 2) - minimal height for dev tools
 /// Creates a session named "kb-{short-task-id}"
 ///
@@ -343,6 +349,7 @@ pub fn open_popup(
     worktree_path: &std::path::Path,
     session_id: Option<&str>,
     parent_session: Option<&str>,
+    layout: &SessionLayout,
 ) -> Result<()> {
     // Extract task ID from worktree path (format: .../worktrees/task-{uuid})
     let dir_name = worktree_path
@@ -373,45 +380,32 @@ pub fn open_popup(
 
         // Create new detached session with Claude running in the first pane
         // Use login shell to get user's PATH (so `claude` command is found)
-        let shell_cmd = format!(
-            "cd '{}' && {}",
-            worktree_path.to_string_lossy(),
-            claude_cmd
-        );
+        let shell_cmd = crate::remote::pane_command(worktree_path, Some(&claude_cmd))
+            .unwrap_or_else(|| format!("cd '{}' && {}", worktree_path.to_string_lossy(), claude_cmd));
 
         // Use -x- and -y- to inherit current terminal size instead of default-size
         // This fixes split-window -l not being honored in detached sessions (tmux issue #3060)
-        let output = Command::new("tmux")
-            .args([
+        let output = crate::secrets::tmux_pane_output(
+            Command::new("tmux").args([
                 "new-session",
                 "-d",  // detached
                 "-x-", // use current terminal width
                 "-y-", // use current terminal height
                 "-s", &session_name,
                 "-c", &worktree_path.to_string_lossy(),
-                "bash", "-l", "-c", &shell_cmd,
-            ])
-            .output()?;
+            ]),
+            // Set on the session, so every pane of the layout gets the task's literal variables
+            worktree_path,
+            ["bash", "-l", "-c", &shell_cmd].map(String::from).to_vec(),
+        )?;
 
         if !output.status.success() {
             let stderr = String::from_utf8_lossy(&output.stderr);
             return Err(anyhow!("Failed to create session: {}", stderr));
         }
 
-        // Split horizontally to create right pane with shell
-        let output = Command::new("tmux")
-            .args([
-                "split-window",
-                "-t", &session_name,
-                "-h",  // horizontal split (side by side)
-                "-c", &worktree_path.to_string_lossy(),
-            ])
-            .output()?;
-
-        if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            return Err(anyhow!("Failed to create shell pane: {}", stderr));
-        }
+        // Add the layout's other panes (a shell on the right by default)
+        let pane_ids = create_layout_panes(&session_name, worktree_path, layout)?;
 
         // Create statusbar pane at the bottom spanning full width
         // We need to use tmux's -f flag to create a full-width split
@@ -420,20 +414,24 @@ pub fn open_popup(
             .map(|p| p.to_string_lossy().to_string())
             .unwrap_or_else(|_| "kanblam".to_string());
 
-        // Build statusbar command - include parent session if provided
+        // Build statusbar command - include parent session if provided,
+        // and the palette so the status bar is drawn like the main UI
+        let palette = crate::ui::palette::current().key();
         let statusbar_cmd = match parent_session {
             Some(parent) => format!(
-                "cd '{}' && '{}' statusbar {} --parent '{}'",
+                "cd '{}' && '{}' statusbar {} --parent '{}' --palette {}",
                 worktree_path.to_string_lossy(),
                 kanblam_path,
                 full_task_id,
-                parent
+                parent,
+                palette
             ),
             None => format!(
-                "cd '{}' && '{}' statusbar {}",
+                "cd '{}' && '{}' statusbar {} --palette {}",
                 worktree_path.to_string_lossy(),
                 kanblam_path,
-                full_task_id
+                full_task_id,
+                palette
             ),
         };
 
@@ -455,14 +453,11 @@ pub fn open_popup(
         if !output.status.success() {
             // Statusbar pane creation failed, but that's not critical - continue without it
             let stderr = String::from_utf8_lossy(&output.stderr);
-            eprintln!("Note: Could not create statusbar pane: {}", stderr);
+            crate::diagnostics::warn(crate::diagnostics::Source::Tmux, format!("Could not create statusbar pane: {}", stderr));
         }
 
-        // Select the left pane (Claude) as the active pane
-        // Use {top-left} to select the first pane regardless of base-index
-        let _ = Command::new("tmux")
-            .args(["select-pane", "-t", &format!("{}:.{{top-left}}", session_name)])
-            .output();
+        // Select the layout's focused pane (Claude unless another is marked)
+        select_layout_focus(&session_name, &pane_ids, layout);
 
         // Switch to the new session FIRST - this may cause layout recalculation
         let _ = Command::new("tmux")
@@ -477,11 +472,99 @@ pub fn open_popup(
         let _ = Command::new("tmux")
             .args(["resize-pane", "-t", &format!("{}:.{{bottom}}", session_name), "-y", "2"])
             .output();
+
+        // Sized panes are resized last for the same reason
+        apply_layout_sizes(&pane_ids, layout);
     }
+    super::query::invalidate();
 
     Ok(())
 }
 
+/// Add the panes after Claude's: the first splits off a right column and the
+/// rest are stacked below it. Returns every pane's id, Claude's first.
+fn create_layout_panes(
+    session_name: &str,
+    worktree_path: &std::path::Path,
+    layout: &SessionLayout,
+) -> Result<Vec<String>> {
+    let claude = Command::new("tmux")
+        .args(["display-message", "-p", "-t", &format!("{}:.{{top-left}}", session_name), "#{pane_id}"])
+        .output()?;
+    let mut pane_ids = vec![String::from_utf8_lossy(&claude.stdout).trim().to_string()];
+    // The first local shell is the task's scratch shell, which keeps its state between sessions
+    let mut scratch = (!crate::remote::is_remote(worktree_path))
+        .then(|| super::shell::command(session_name))
+        .flatten();
+
+    for (index, pane) in layout.panes.iter().enumerate().skip(1) {
+        let mut command = Command::new("tmux");
+        command.args([
+            "split-window",
+            "-t", &pane_ids[pane_ids.len() - 1],
+            if index == 1 { "-h" } else { "-v" },
+            "-P", "-F", "#{pane_id}",
+            "-c", &worktree_path.to_string_lossy(),
+        ]);
+        let is_scratch = pane.command == PaneCommand::Shell && scratch.is_some();
+        let pane_command: Vec<String> = match &pane.command {
+            PaneCommand::Run(run) => {
+                // Keep a shell once the command exits so its output stays readable
+                let script = format!("{}; exec \"$SHELL\" -l", run);
+                match crate::remote::pane_command(worktree_path, Some(&script)) {
+                    Some(remote) => vec![remote],
+                    None => vec!["bash".to_string(), "-l".to_string(), "-c".to_string(), script],
+                }
+            }
+            PaneCommand::Shell if is_scratch => scratch.take().into_iter().collect(),
+            PaneCommand::Claude | PaneCommand::Shell => {
+                crate::remote::pane_command(worktree_path, None).into_iter().collect()
+            }
+        };
+        // Each pane reads the task's secrets itself; the session only holds the literal variables
+        let output = crate::secrets::tmux_pane_output(&mut command, worktree_path, pane_command)?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(anyhow!("Failed to create pane {}: {}", index + 1, stderr));
+        }
+        let pane_id = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        if is_scratch {
+            // Title it so it's recognizable with pane-border-status on
+            let _ = Command::new("tmux")
+                .args(["select-pane", "-t", &pane_id, "-T", &format!("{} shell", session_name)])
+                .output();
+        }
+        pane_ids.push(pane_id);
+    }
+
+    Ok(pane_ids)
+}
+
+/// Resize the layout's sized panes: Claude by width, the others by height
+fn apply_layout_sizes(pane_ids: &[String], layout: &SessionLayout) {
+    for (index, (pane_id, pane)) in pane_ids.iter().zip(&layout.panes).enumerate() {
+        let Some(size) = pane.size else {
+            continue;
+        };
+        let axis = if index == 0 { "-x" } else { "-y" };
+        let _ = Command::new("tmux")
+            .args(["resize-pane", "-t", pane_id, axis, &format!("{}%", size)])
+            .output();
+    }
+}
+
+/// Select the pane that should get the keyboard
+fn select_layout_focus(session_name: &str, pane_ids: &[String], layout: &SessionLayout) {
+    // Use {top-left} for Claude's pane regardless of base-index
+    let target = pane_ids.get(layout.focus)
+        .filter(|_| layout.focus > 0)
+        .cloned()
+        .unwrap_or_else(|| format!("{}:.{{top-left}}", session_name));
+    let _ = Command::new("tmux")
+        .args(["select-pane", "-t", &target])
+        .output();
+}
+
 /// Send a key sequence to a tmux pane (for interactive modal)
 pub fn send_key_to_pane(target: &str, key: &str) -> Result<()> {
     let output = Command::new("tmux")
@@ -627,7 +710,7 @@ pub struct DetachedSessionResult {
 /// Open combined tmux session in detached mode (don't switch to it)
 /// Creates a session with three panes:
 /// - Claude on left (pane 0)
-/// - Shell on right (pane 1)
+/// - Shell on right (pane 1), or the project's `layout` panes stacked there
 /// - Statusbar at bottom (pane 2) - minimal height for dev tools
 /// Returns the session name and whether it was newly created
 ///
@@ -637,6 +720,7 @@ pub fn open_popup_detached(
     worktree_path: &std::path::Path,
     session_id: Option<&str>,
     parent_session: Option<&str>,
+    layout: &SessionLayout,
 ) -> Result<DetachedSessionResult> {
     // Extract task ID from worktree path (format: .../worktrees/task-{uuid})
     let dir_name = worktree_path
@@ -663,45 +747,32 @@ pub fn open_popup_detached(
         };
 
         // Create new detached session with Claude running in the first pane
-        let shell_cmd = format!(
-            "cd '{}' && {}",
-            worktree_path.to_string_lossy(),
-            claude_cmd
-        );
+        let shell_cmd = crate::remote::pane_command(worktree_path, Some(&claude_cmd))
+            .unwrap_or_else(|| format!("cd '{}' && {}", worktree_path.to_string_lossy(), claude_cmd));
 
         // Use -x- and -y- to inherit current terminal size instead of default-size
         // This fixes split-window -l not being honored in detached sessions (tmux issue #3060)
-        let output = Command::new("tmux")
-            .args([
+        let output = crate::secrets::tmux_pane_output(
+            Command::new("tmux").args([
                 "new-session",
                 "-d",
                 "-x-", // use current terminal width
                 "-y-", // use current terminal height
                 "-s", &session_name,
                 "-c", &worktree_path.to_string_lossy(),
-                "bash", "-l", "-c", &shell_cmd,
-            ])
-            .output()?;
+            ]),
+            // Set on the session, so every pane of the layout gets the task's literal variables
+            worktree_path,
+            ["bash", "-l", "-c", &shell_cmd].map(String::from).to_vec(),
+        )?;
 
         if !output.status.success() {
             let stderr = String::from_utf8_lossy(&output.stderr);
             return Err(anyhow!("Failed to create session: {}", stderr));
         }
 
-        // Split horizontally to create right pane with shell
-        let output = Command::new("tmux")
-            .args([
-                "split-window",
-                "-t", &session_name,
-                "-h",  // horizontal split (side by side)
-                "-c", &worktree_path.to_string_lossy(),
-            ])
-            .output()?;
-
-        if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            return Err(anyhow!("Failed to create shell pane: {}", stderr));
-        }
+        // Add the layout's other panes (a shell on the right by default)
+        let pane_ids = create_layout_panes(&session_name, worktree_path, layout)?;
 
         // Create statusbar pane at the bottom spanning full width
         // Get the kanblam binary path for the statusbar command
@@ -709,20 +780,24 @@ pub fn open_popup_detached(
             .map(|p| p.to_string_lossy().to_string())
             .unwrap_or_else(|_| "kanblam".to_string());
 
-        // Build statusbar command - include parent session if provided
+        // Build statusbar command - include parent session if provided,
+        // and the palette so the status bar is drawn like the main UI
+        let palette = crate::ui::palette::current().key();
         let statusbar_cmd = match parent_session {
             Some(parent) => format!(
-                "cd '{}' && '{}' statusbar {} --parent '{}'",
+                "cd '{}' && '{}' statusbar {} --parent '{}' --palette {}",
                 worktree_path.to_string_lossy(),
                 kanblam_path,
                 full_task_id,
-                parent
+                parent,
+                palette
             ),
             None => format!(
-                "cd '{}' && '{}' statusbar {}",
+                "cd '{}' && '{}' statusbar {} --palette {}",
                 worktree_path.to_string_lossy(),
                 kanblam_path,
-                full_task_id
+                full_task_id,
+                palette
             ),
         };
 
@@ -744,7 +819,7 @@ pub fn open_popup_detached(
         if !output.status.success() {
             // Statusbar pane creation failed, but that's not critical - continue without it
             let stderr = String::from_utf8_lossy(&output.stderr);
-            eprintln!("Note: Could not create statusbar pane: {}", stderr);
+            crate::diagnostics::warn(crate::diagnostics::Source::Tmux, format!("Could not create statusbar pane: {}", stderr));
         }
 
         // Small delay to let tmux finish creating the pane
@@ -755,14 +830,14 @@ pub fn open_popup_detached(
         let _ = Command::new("tmux")
             .args(["resize-pane", "-t", &format!("{}:.{{bottom}}", session_name), "-y", "2"])
             .output();
+        apply_layout_sizes(&pane_ids, layout);
 
-        // Select the left pane (Claude) as the active pane
-        // Use {top-left} to select the first pane regardless of base-index
-        let _ = Command::new("tmux")
-            .args(["select-pane", "-t", &format!("{}:.{{top-left}}", session_name)])
-            .output();
+        // Select the layout's focused pane (Claude unless another is marked)
+        select_layout_focus(&session_name, &pane_ids, layout);
     }
 
+    super::query::invalidate();
+
     // Don't switch - stay in current session
     Ok(DetachedSessionResult {
         session_name,
@@ -781,10 +856,53 @@ pub fn kill_task_window(project_slug: &str, window_name: &str) -> Result<()> {
 
     // Ignore errors if window doesn't exist
     let _ = output;
+    super::query::invalidate();
 
     Ok(())
 }
 
+/// Restart Claude in the left pane of a task's existing session, leaving the
+/// other panes (and the scratch shell's state) as they are. Returns false if
+/// there's no session to restart it in.
+pub fn respawn_claude_pane(worktree_path: &std::path::Path, session_id: Option<&str>) -> Result<bool> {
+    let session_name = worktree_path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or("claude");
+
+    let check = Command::new("tmux")
+        .args(["has-session", "-t", session_name])
+        .output()?;
+    if !check.status.success() {
+        return Ok(false);
+    }
+
+    let claude_cmd = match session_id {
+        Some(id) => format!("claude --resume {}", id),
+        None => "claude".to_string(),
+    };
+    let shell_cmd = crate::remote::pane_command(worktree_path, Some(&claude_cmd))
+        .unwrap_or_else(|| format!("cd '{}' && {}", worktree_path.to_string_lossy(), claude_cmd));
+
+    let output = crate::secrets::tmux_pane_output(
+        Command::new("tmux").args([
+            "respawn-pane",
+            "-k", // kill the stale Claude first
+            "-t", &format!("{}:.{{top-left}}", session_name),
+            "-c", &worktree_path.to_string_lossy(),
+        ]),
+        worktree_path,
+        ["bash", "-l", "-c", &shell_cmd].map(String::from).to_vec(),
+    )?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(anyhow!("Failed to restart Claude: {}", stderr));
+    }
+    super::query::invalidate();
+
+    Ok(true)
+}
+
 /// Kill any detached tmux sessions associated with a task.
 ///
 /// The `display_id` parameter should be the task's display ID (e.g., "TSKB-a7x"),
@@ -792,10 +910,13 @@ pub fn kill_task_window(project_slug: &str, window_name: &str) -> Result<()> {
 /// This is NOT the task's UUID.
 ///
 /// Silently ignores errors (e.g., if session doesn't exist).
+/// The task's scratch shell state goes with it.
 pub fn kill_task_sessions(display_id: &str) {
     let _ = Command::new("tmux")
         .args(["kill-session", "-t", display_id])
         .output();
+    super::query::invalidate();
+    super::shell::remove_state(display_id);
 }
 
 /// Result of checking Claude CLI activity state in a tmux pane
@@ -823,17 +944,8 @@ pub fn get_claude_cli_state(task_id: &str) -> ClaudeCliState {
     let target = format!("{}:.{{top-left}}", session_name); // Left pane where Claude runs
 
     // Check if session exists
-    let check = Command::new("tmux")
-        .args(["has-session", "-t", &session_name])
-        .output();
-
-    match check {
-        Ok(output) if !output.status.success() => {
-            // Session doesn't exist
-            return ClaudeCliState::NotRunning;
-        }
-        Err(_) => return ClaudeCliState::Unknown,
-        _ => {}
+    if !super::query::snapshot().has_session(&session_name) {
+        return ClaudeCliState::NotRunning;
     }
 
     // Capture the last 20 lines of the pane
@@ -899,6 +1011,7 @@ pub fn kill_claude_cli_session(task_id: &str) -> Result<()> {
     let output = Command::new("tmux")
         .args(["kill-session", "-t", &session_name])
         .output()?;
+    super::query::invalidate();
 
     if !output.status.success() {
         // Session might not exist, which is fine
@@ -911,28 +1024,9 @@ pub fn kill_claude_cli_session(task_id: &str) -> Result<()> {
     Ok(())
 }
 
-/// Check if a task window exists
+/// Check if a task window exists (answered from the shared pane snapshot)
 pub fn task_window_exists(project_slug: &str, window_name: &str) -> bool {
-    let session_name = format!("kc-{}", project_slug);
-
-    let output = Command::new("tmux")
-        .args([
-            "list-windows",
-            "-t",
-            &session_name,
-            "-F",
-            "#{window_name}",
-        ])
-        .output();
-
-    if let Ok(output) = output {
-        if output.status.success() {
-            let windows = String::from_utf8_lossy(&output.stdout);
-            return windows.lines().any(|w| w == window_name);
-        }
-    }
-
-    false
+    super::query::snapshot().has_window(&format!("kc-{}", project_slug), window_name)
 }
 
 /// Capture output from a task window
@@ -958,25 +1052,52 @@ pub fn capture_task_output(project_slug: &str, window_name: &str, lines: u32) ->
     Ok(String::from_utf8_lossy(&output.stdout).to_string())
 }
 
+/// Capture the last `lines` lines of a task window's visible pane
+/// (None if the window doesn't exist)
+pub fn capture_task_tail(project_slug: &str, window_name: &str, lines: u32) -> Option<String> {
+    capture_pane_tail(&format!("kc-{}:{}", project_slug, window_name), lines)
+}
+
+/// Capture the last `lines` lines of any pane target (None if it doesn't exist)
+pub fn capture_pane_tail(target: &str, lines: u32) -> Option<String> {
+    // Panes known to be gone don't cost a capture-pane call
+    super::query::snapshot().pane(target)?;
+    let start = format!("-{}", lines);
+    let output = Command::new("tmux")
+        .args(["capture-pane", "-t", target, "-p", "-J", "-S", &start])
+        .output()
+        .ok()?;
+
+    output.status.success().then(|| String::from_utf8_lossy(&output.stdout).trim_end().to_string())
+}
+
+/// PID of the process running in a pane (None if the pane doesn't exist)
+pub fn pane_pid(target: &str) -> Option<u32> {
+    super::query::snapshot().pane(target)?.pid
+}
+
 /// Open a new pane to the right of the current pane and start a fresh Claude CLI session.
 /// This splits the current pane horizontally and runs `claude` in the new pane.
 pub fn split_pane_with_claude(working_dir: &std::path::Path) -> Result<()> {
     // Split the current pane horizontally (creates pane to the right)
     // -h = horizontal split (side by side)
     // -c = start directory
-    let output = Command::new("tmux")
-        .args([
+    let output = crate::secrets::tmux_pane_output(
+        Command::new("tmux").args([
             "split-window",
             "-h",
             "-c",
             &working_dir.to_string_lossy(),
-        ])
-        .output()?;
+        ]),
+        working_dir,
+        crate::remote::pane_command(working_dir, None).into_iter().collect(),
+    )?;
 
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
         return Err(anyhow!("Failed to split pane: {}", stderr));
     }
+    super::query::invalidate();
 
     // The new pane is now active, send the claude command
     // Use bash -l -c to get login shell environment (for PATH)
@@ -0,0 +1,116 @@
+//! A task's scratch shell: the first shell pane of its interactive session
+//!
+//! The shell keeps its command history, working directory and exported
+//! variables in a per-task directory under kanblam's data dir, saved at every
+//! prompt. A session that was closed or killed starts its shell where the
+//! last one left off, and the Activity tab shows the latest commands.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+use crate::remote::quote;
+
+/// Commands kept in a task's shell history
+const HISTORY_SIZE: usize = 5000;
+
+/// Variables that belong to the pane rather than the task, so aren't restored
+const PANE_VARIABLES: &str = "TMUX|TMUX_PANE|SHLVL|PWD|OLDPWD|_|TERM|COLUMNS|LINES";
+
+/// Parsed history files by path, with the modification time they were read at
+type HistoryCache = HashMap<PathBuf, (SystemTime, Vec<String>)>;
+
+/// Reread when a history file changes, so rendering doesn't reparse it every frame
+static HISTORY_CACHE: Mutex<Option<HistoryCache>> = Mutex::new(None);
+
+/// Where the shell of the task with `display_id` keeps its state
+pub fn state_dir(display_id: &str) -> PathBuf {
+    dirs::data_local_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("kanblam")
+        .join("shells")
+        .join(display_id)
+}
+
+/// The rc file that restores and saves the shell's state
+fn rc_script(dir: &Path) -> String {
+    let file = |name: &str| quote(&dir.join(name).to_string_lossy());
+    let (history, cwd, env) = (file("history"), file("cwd"), file("env"));
+    format!(
+        "[ -f ~/.bashrc ] && . ~/.bashrc\n\
+         HISTFILE={history}\n\
+         HISTSIZE={HISTORY_SIZE}\n\
+         HISTFILESIZE={HISTORY_SIZE}\n\
+         shopt -s histappend\n\
+         history -c; history -r\n\
+         [ -f {env} ] && . {env}\n\
+         [ -d \"$(cat {cwd} 2>/dev/null)\" ] && cd \"$(cat {cwd})\"\n\
+         __kanblam_save() {{\n\
+         \x20 history -a\n\
+         \x20 pwd > {cwd}\n\
+         \x20 export -p | grep -vE '^declare -x ({PANE_VARIABLES})(=|$)' > {env}\n\
+         }}\n\
+         PROMPT_COMMAND=\"__kanblam_save${{PROMPT_COMMAND:+; $PROMPT_COMMAND}}\"\n"
+    )
+}
+
+/// The command starting the scratch shell, with its rc file written. None if
+/// the state dir can't be written (a plain shell is used instead).
+pub fn command(display_id: &str) -> Option<String> {
+    let dir = state_dir(display_id);
+    std::fs::create_dir_all(&dir).ok()?;
+    let rc = dir.join("bashrc");
+    std::fs::write(&rc, rc_script(&dir)).ok()?;
+    Some(format!("bash --rcfile {} -i", quote(&rc.to_string_lossy())))
+}
+
+/// The last `count` commands run in the task's shell, oldest first
+pub fn recent_commands(display_id: &str, count: usize) -> Vec<String> {
+    let path = state_dir(display_id).join("history");
+    let Some(modified) = std::fs::metadata(&path).and_then(|m| m.modified()).ok() else {
+        return Vec::new();
+    };
+    let Ok(mut cache) = HISTORY_CACHE.lock() else {
+        return Vec::new();
+    };
+    let cache = cache.get_or_insert_with(HashMap::new);
+    if cache.get(&path).is_none_or(|(at, _)| *at != modified) {
+        let text = std::fs::read_to_string(&path).unwrap_or_default();
+        cache.insert(path.clone(), (modified, parse_history(&text)));
+    }
+    let commands = &cache[&path].1;
+    commands[commands.len().saturating_sub(count)..].to_vec()
+}
+
+/// Commands in a bash history file, skipping timestamps and blank lines
+fn parse_history(text: &str) -> Vec<String> {
+    text.lines()
+        .filter(|line| !line.trim().is_empty())
+        .filter(|line| !(line.starts_with('#') && line[1..].chars().all(|c| c.is_ascii_digit())))
+        .map(str::to_string)
+        .collect()
+}
+
+/// Forget a task's shell state (when it's merged, discarded or deleted)
+pub fn remove_state(display_id: &str) {
+    let _ = std::fs::remove_dir_all(state_dir(display_id));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_history_and_rc_script() {
+        assert_eq!(
+            parse_history("#1718000000\ncargo test\n\nls -la\n#not a timestamp\n"),
+            vec!["cargo test", "ls -la", "#not a timestamp"],
+        );
+
+        let script = rc_script(Path::new("/data/kanblam/shells/API-a7x"));
+        assert!(script.contains("HISTFILE='/data/kanblam/shells/API-a7x/history'"));
+        assert!(script.contains("pwd > '/data/kanblam/shells/API-a7x/cwd'"));
+        assert!(script.contains("PROMPT_COMMAND=\"__kanblam_save${PROMPT_COMMAND:+; $PROMPT_COMMAND}\""));
+    }
+}
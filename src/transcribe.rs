@@ -0,0 +1,69 @@
+//! Transcription of audio attachments
+//!
+//! A project can configure a shell command (whisper.cpp, openai-whisper, a
+//! cloud CLI...) that prints the transcript of an audio file to stdout. The
+//! text is appended to the task it was attached to.
+
+use anyhow::{anyhow, Result};
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+/// Extensions treated as voice notes
+const AUDIO_EXTENSIONS: &[&str] = &["mp3", "m4a", "wav", "ogg", "oga", "opus", "flac", "webm", "aac"];
+
+/// Whether an attached file should be transcribed
+pub fn is_audio_file(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| AUDIO_EXTENSIONS.iter().any(|a| e.eq_ignore_ascii_case(a)))
+}
+
+/// The shell command for one file: `{file}` is replaced by the quoted path,
+/// or the path is appended when the template doesn't mention it
+fn command_for(template: &str, file: &Path) -> String {
+    let quoted = format!("'{}'", file.display().to_string().replace('\'', r"'\''"));
+    if template.contains("{file}") {
+        template.replace("{file}", &quoted)
+    } else {
+        format!("{} {}", template, quoted)
+    }
+}
+
+/// Run the transcription command on `file` and return what it printed (blocking)
+pub fn transcribe(template: &str, file: &Path, project_dir: &Path) -> Result<String> {
+    let cmd = command_for(template, file);
+    let output = Command::new("sh")
+        .current_dir(project_dir)
+        .args(["-c", &cmd])
+        .stdin(Stdio::null())
+        .output()
+        .map_err(|e| anyhow!("Cannot run `{}`: {}", cmd, e))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let reason = stderr.lines().rev().find(|l| !l.trim().is_empty()).unwrap_or("no output");
+        return Err(anyhow!("{} ({})", reason.trim(), output.status));
+    }
+    let text = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if text.is_empty() {
+        return Err(anyhow!("the command printed nothing"));
+    }
+    Ok(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_command_for_quotes_file() {
+        let file = Path::new("/tmp/it's a note.m4a");
+        assert_eq!(
+            command_for("whisper-cli -nt -f {file}", file),
+            r"whisper-cli -nt -f '/tmp/it'\''s a note.m4a'"
+        );
+        assert_eq!(command_for("transcribe", Path::new("/a.wav")), "transcribe '/a.wav'");
+        assert!(is_audio_file(Path::new("memo.M4A")));
+        assert!(!is_audio_file(Path::new("notes.txt")));
+    }
+}
@@ -0,0 +1,124 @@
+//! OSC 8 hyperlinks over the URLs on screen
+//!
+//! ratatui has no notion of hyperlinks and counts escape sequences as cell
+//! width, so links can't go into the buffer. Instead, after each draw the URLs
+//! visible in the buffer are printed over themselves, wrapped in OSC 8:
+//! terminals that support it make them clickable, the rest ignore the
+//! sequence. Set `KANBLAM_NO_HYPERLINKS` to turn this off.
+
+use std::io::Write;
+
+use ratatui::backend::IntoCrossterm;
+use ratatui::buffer::{Buffer, Cell};
+use ratatui::crossterm::{
+    cursor::MoveTo,
+    queue,
+    style::{Attribute, Print, ResetColor, SetAttribute, SetBackgroundColor, SetForegroundColor},
+};
+use ratatui::style::Modifier;
+
+/// A URL on screen: where it starts, how many cells it covers and where it points
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VisibleLink {
+    pub x: u16,
+    pub y: u16,
+    pub width: u16,
+    pub url: String,
+}
+
+/// Whether to write hyperlinks at all (not on the Linux console or when turned off)
+pub fn enabled() -> bool {
+    std::env::var_os("KANBLAM_NO_HYPERLINKS").is_none()
+        && !matches!(std::env::var("TERM").as_deref(), Ok("linux") | Ok("dumb"))
+}
+
+/// The URLs written in the buffer, one row at a time
+pub fn visible_links(buffer: &Buffer) -> Vec<VisibleLink> {
+    let area = buffer.area;
+    let mut links = Vec::new();
+    for y in area.top()..area.bottom() {
+        // The row's text, with the cell each byte came from
+        let mut row = String::new();
+        let mut cell_of_byte = Vec::new();
+        for x in area.left()..area.right() {
+            let symbol = buffer[(x, y)].symbol();
+            row.push_str(symbol);
+            cell_of_byte.extend(std::iter::repeat_n(x, symbol.len()));
+        }
+        for url in crate::links::find_urls(&row) {
+            let start = url.as_ptr() as usize - row.as_ptr() as usize;
+            let (first, last) = (cell_of_byte[start], cell_of_byte[start + url.len() - 1]);
+            links.push(VisibleLink { x: first, y, width: last - first + 1, url: url.to_string() });
+        }
+    }
+    links
+}
+
+/// The buffer's URLs with the cells they cover, taken while the frame is at hand
+pub fn capture(buffer: &Buffer) -> Vec<(VisibleLink, Vec<Cell>)> {
+    visible_links(buffer)
+        .into_iter()
+        .map(|link| {
+            let cells = (link.x..link.x + link.width).map(|x| buffer[(x, link.y)].clone()).collect();
+            (link, cells)
+        })
+        .collect()
+}
+
+/// Print captured URLs over themselves as OSC 8 hyperlinks
+pub fn write<W: Write>(out: &mut W, links: &[(VisibleLink, Vec<Cell>)]) -> std::io::Result<()> {
+    if links.is_empty() {
+        return Ok(());
+    }
+    for (link, cells) in links {
+        queue!(out, MoveTo(link.x, link.y), Print(format!("\x1b]8;;{}\x1b\\", link.url)))?;
+        for cell in cells {
+            queue!(
+                out,
+                SetAttribute(Attribute::Reset),
+                SetForegroundColor(cell.fg.into_crossterm()),
+                SetBackgroundColor(cell.bg.into_crossterm()),
+            )?;
+            for (modifier, attribute) in [
+                (Modifier::BOLD, Attribute::Bold),
+                (Modifier::DIM, Attribute::Dim),
+                (Modifier::ITALIC, Attribute::Italic),
+                (Modifier::UNDERLINED, Attribute::Underlined),
+                (Modifier::REVERSED, Attribute::Reverse),
+                (Modifier::CROSSED_OUT, Attribute::CrossedOut),
+            ] {
+                if cell.modifier.contains(modifier) {
+                    queue!(out, SetAttribute(attribute))?;
+                }
+            }
+            queue!(out, Print(cell.symbol()))?;
+        }
+        queue!(out, Print("\x1b]8;;\x1b\\"))?;
+    }
+    queue!(out, SetAttribute(Attribute::Reset), ResetColor)?;
+    out.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::layout::Rect;
+
+    #[test]
+    fn test_visible_links() {
+        let mut buffer = Buffer::empty(Rect::new(0, 0, 40, 3));
+        buffer.set_string(0, 0, "PR: https://github.com/a/b/pull/7.", ratatui::style::Style::default());
+        buffer.set_string(2, 2, "界 http://localhost:41000", ratatui::style::Style::default());
+        assert_eq!(visible_links(&buffer), vec![
+            VisibleLink { x: 4, y: 0, width: 29, url: "https://github.com/a/b/pull/7".to_string() },
+            VisibleLink { x: 5, y: 2, width: 22, url: "http://localhost:41000".to_string() },
+        ]);
+
+        let mut out = Vec::new();
+        write(&mut out, &capture(&buffer)).unwrap();
+        let written = String::from_utf8(out).unwrap();
+        assert!(written.contains("\x1b]8;;https://github.com/a/b/pull/7\x1b\\"));
+        // Cell by cell in the cell's style, then the link closes
+        assert!(written.contains("0\x1b]8;;\x1b\\"));
+    }
+}
@@ -1,21 +1,46 @@
 //! Interactive terminal modal for Claude CLI sessions
 //!
 //! This modal renders a tmux pane output with vt100 parsing and allows
-//! users to interact with Claude directly. Ctrl-Esc closes the modal.
+//! users to interact with Claude directly. Ctrl-Esc closes the modal, and
+//! Ctrl+[ freezes the pane in copy mode to select output with vim keys.
+//! On wide terminals the task worktree's diff sits beside the terminal,
+//! refreshed on each hook signal, so changes show up while you talk.
 
+use crate::model::copy_mode::CopyMode;
 use crate::model::InteractiveModal;
 use ratatui::{
-    layout::Rect,
+    layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, Paragraph},
     Frame,
 };
 
+/// Width from which the diff is shown beside the terminal
+const SPLIT_MIN_WIDTH: u16 = 120;
+
 /// Render the interactive terminal modal
 pub fn render_interactive_modal(frame: &mut Frame, modal: &InteractiveModal) {
-    // Use full screen for the terminal
-    let area = frame.area();
+    let full_area = frame.area();
+    frame.render_widget(ratatui::widgets::Clear, full_area);
+
+    // Terminal on the left, the worktree's diff on the right (above the status bar)
+    let area = if full_area.width >= SPLIT_MIN_WIDTH {
+        let split = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
+            .split(Rect { height: full_area.height.saturating_sub(1), ..full_area });
+        render_diff_side(frame, split[1], modal);
+        Rect { height: full_area.height, ..split[0] }
+    } else {
+        full_area
+    };
+
+    if let Some(ref copy) = modal.copy_mode {
+        render_copy_mode(frame, area, copy);
+        render_status_bar(frame, full_area, modal);
+        return;
+    }
 
     // Get the actual tmux pane size to parse content correctly
     let pane_width = crate::tmux::get_pane_size(&modal.tmux_target)
@@ -59,12 +84,82 @@ pub fn render_interactive_modal(frame: &mut Frame, modal: &InteractiveModal) {
         .block(block)
         .style(Style::default().fg(Color::White).bg(Color::Black));
 
-    // Clear area and render
-    frame.render_widget(ratatui::widgets::Clear, area);
     frame.render_widget(terminal_view, area);
 
     // Render status bar at bottom with hints
-    render_status_bar(frame, area, modal);
+    render_status_bar(frame, full_area, modal);
+}
+
+/// Render the frozen pane with the cursor and selection highlighted
+fn render_copy_mode(frame: &mut Frame, area: Rect, copy: &CopyMode) {
+    // Keep the cursor in view, scrolling only once it would leave the bottom
+    let height = area.height.saturating_sub(2).max(1) as usize;
+    let top = (copy.cursor.0 + 1).saturating_sub(height);
+    let selected = Style::default().fg(Color::Black).bg(Color::Yellow);
+    let cursor = Style::default().fg(Color::Black).bg(Color::Cyan);
+
+    let lines: Vec<Line> = copy.rows.iter().enumerate().skip(top).take(height)
+        .map(|(row, text)| {
+            // One span per character is plenty for a screenful of frozen text
+            let mut chars: Vec<char> = text.chars().collect();
+            if row == copy.cursor.0 && chars.len() <= copy.cursor.1 {
+                chars.resize(copy.cursor.1 + 1, ' ');
+            }
+            Line::from(chars.into_iter().enumerate().map(|(col, ch)| {
+                let style = if (row, col) == copy.cursor {
+                    cursor
+                } else if copy.is_selected(row, col) {
+                    selected
+                } else {
+                    Style::default()
+                };
+                Span::styled(ch.to_string(), style)
+            }).collect::<Vec<_>>())
+        })
+        .collect();
+
+    let mode = match (copy.anchor.is_some(), copy.line_wise) {
+        (false, _) => "COPY",
+        (true, false) => "COPY · VISUAL",
+        (true, true) => "COPY · VISUAL LINE",
+    };
+    let block = Block::default()
+        .title(Span::styled(
+            format!(" {} - line {}/{} ", mode, copy.cursor.0 + 1, copy.rows.len()),
+            Style::default().fg(Color::Black).bg(Color::Yellow).add_modifier(Modifier::BOLD),
+        ))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Yellow));
+
+    let view = Paragraph::new(lines)
+        .block(block)
+        .style(Style::default().fg(Color::White).bg(Color::Black));
+    frame.render_widget(view, area);
+}
+
+/// Render the task worktree's changes: totals, changed files and the diff itself
+fn render_diff_side(frame: &mut Frame, area: Rect, modal: &InteractiveModal) {
+    let dim = Style::default().fg(Color::DarkGray);
+    let refreshed = match (modal.diff_loading, modal.diff_refreshed_at) {
+        (true, _) => " refreshing… ".to_string(),
+        (false, Some(at)) => format!(" refreshed {} ", at.with_timezone(&chrono::Local).format("%H:%M:%S")),
+        (false, None) => String::new(),
+    };
+    let block = Block::default()
+        .title(Span::styled(" Changes ", Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)))
+        .title(Line::from(Span::styled(refreshed, dim)).right_aligned())
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan));
+    let height = area.height.saturating_sub(2) as usize;
+
+    let Some(ref diff) = modal.diff else {
+        let waiting = Line::from(Span::styled(" Waiting for the first change…", dim));
+        frame.render_widget(Paragraph::new(vec![Line::from(""), waiting]).block(block), area);
+        return;
+    };
+
+    let lines = super::diff_overview_lines(diff, area.width.saturating_sub(2) as usize, height);
+    frame.render_widget(Paragraph::new(lines).block(block), area);
 }
 
 /// Parse terminal output using vt100 for proper ANSI escape sequence handling
@@ -186,15 +281,32 @@ fn convert_vt100_color(color: vt100::Color) -> Color {
 }
 
 /// Render the status bar with keybindings
-fn render_status_bar(frame: &mut Frame, area: Rect, _modal: &InteractiveModal) {
-    let hints = Line::from(vec![
-        Span::styled(" Ctrl-Esc", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
-        Span::styled(" close  ", Style::default().fg(Color::DarkGray)),
-        Span::styled("PgUp/PgDn", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
-        Span::styled(" scroll  ", Style::default().fg(Color::DarkGray)),
-        Span::styled("All other keys", Style::default().fg(Color::Yellow)),
-        Span::styled(" → Claude ", Style::default().fg(Color::DarkGray)),
-    ]);
+fn render_status_bar(frame: &mut Frame, area: Rect, modal: &InteractiveModal) {
+    let key = Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD);
+    let dim = Style::default().fg(Color::DarkGray);
+    let hints = if modal.copy_mode.is_some() {
+        Line::from(vec![
+            Span::styled(" hjkl w b 0 $ g G", key),
+            Span::styled(" move  ", dim),
+            Span::styled("v/V", key),
+            Span::styled(" select  ", dim),
+            Span::styled("y/Enter", key),
+            Span::styled(" copy  ", dim),
+            Span::styled("Esc/q", key),
+            Span::styled(" back to Claude ", dim),
+        ])
+    } else {
+        Line::from(vec![
+            Span::styled(" Ctrl-Esc", key),
+            Span::styled(" close  ", dim),
+            Span::styled("PgUp/PgDn", key),
+            Span::styled(" scroll  ", dim),
+            Span::styled("Ctrl+[", key),
+            Span::styled(" copy mode  ", dim),
+            Span::styled("All other keys", Style::default().fg(Color::Yellow)),
+            Span::styled(" → Claude ", dim),
+        ])
+    };
 
     let status_area = Rect {
         x: area.x,
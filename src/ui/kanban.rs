@@ -1,10 +1,10 @@
 use crate::app::App;
-use crate::model::{FocusArea, TaskStatus};
+use crate::model::{BoardLayout, ColumnRow, ColumnSort, FocusArea, TaskStatus};
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, List, ListItem, ListState, Paragraph},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
     Frame,
 };
 
@@ -17,22 +17,81 @@ pub struct KanbanHitResult {
     pub task_idx: Option<usize>,
 }
 
-/// Calculate the 6 cell rectangles (2x3 grid) for the kanban board given the outer area.
-/// Returns array of (status, cell_rect) in order:
+/// Calculate the 6 cell rectangles for the kanban board given the outer area,
+/// in the given (resolved) layout. Returns (status, cell_rect) in order:
 /// [Planned, InProgress, Testing, NeedsWork, Review, Done]
-pub fn calculate_kanban_cells(area: Rect) -> [(TaskStatus, Rect); 6] {
-    // Same logic as render_kanban: outer border with title, then 2x3 grid with proportional rows
+///
+/// In the grid every cell is a full column. In the two-column and stacked
+/// layouts only `selected` gets a full cell; the others get a one-line header
+/// (`is_header_cell`).
+pub fn calculate_kanban_cells(area: Rect, layout: BoardLayout, selected: TaskStatus) -> [(TaskStatus, Rect); 6] {
     // Must match render_kanban exactly - including the title (though title doesn't affect inner())
     let block = Block::default()
         .title(" Kanban Board ")
         .borders(Borders::ALL);
     let inner = block.inner(area);
 
+    match layout {
+        BoardLayout::Auto | BoardLayout::Grid => grid_cells(inner),
+        BoardLayout::TwoColumn => {
+            // All six headers down the left (see `column_list_rows`), the selected column beside them
+            let rows = column_list_rows(inner);
+            let open = Rect {
+                x: inner.x + rows[0].width,
+                width: inner.width - rows[0].width,
+                ..inner
+            };
+            let mut idx = 0;
+            TaskStatus::all().map(|status| {
+                let cell = if status == selected { open } else { rows[idx] };
+                idx += 1;
+                (status, cell)
+            })
+        }
+        BoardLayout::Stacked => {
+            // A line per column, the selected one taking the rest of the height
+            let open_height = inner.height.saturating_sub(5);
+            let mut y = inner.y;
+            TaskStatus::all().map(|status| {
+                let height = if status == selected { open_height } else { 1 };
+                let cell = Rect { x: inner.x, y, width: inner.width, height };
+                y += height;
+                (status, cell.intersection(inner))
+            })
+        }
+    }
+}
+
+/// Width of the column list in the two-column layout
+const COLUMN_LIST_WIDTH: u16 = 21;
+
+/// The two-column layout's list rows, one per column in order
+fn column_list_rows(inner: Rect) -> [Rect; 6] {
+    let width = COLUMN_LIST_WIDTH.min(inner.width / 2);
+    let mut y = inner.y;
+    [(); 6].map(|_| {
+        let row = Rect { x: inner.x, y, width, height: 1 }.intersection(inner);
+        y += 1;
+        row
+    })
+}
+
+/// Whether a cell from `calculate_kanban_cells` is a collapsed one-line header
+pub fn is_header_cell(cell: Rect) -> bool {
+    cell.height < 3
+}
+
+/// The 2x3 grid: rows in a 42:17:41 ratio, each at least 3 lines
+fn grid_cells(inner: Rect) -> [(TaskStatus, Rect); 6] {
+    // Middle row (Testing/NeedsWork) is smaller since those columns typically have fewer tasks
+    // Ensure each row has at least 3 lines (2 borders + 1 content line) for usability
     let total_height = inner.height as i32;
-    let min_row_height: u16 = 3;
+    let min_row_height: u16 = 3; // 2 for borders + 1 for at least one task line
 
-    // Calculate row heights with same logic as render
+    // Calculate row heights manually to enforce minimums while preserving proportions
+    // Target ratios: 42:17:41 (total 100)
     let rows = if total_height < (min_row_height * 3) as i32 {
+        // Extremely small: give each row equal share
         Layout::default()
             .direction(Direction::Vertical)
             .constraints([
@@ -42,13 +101,16 @@ pub fn calculate_kanban_cells(area: Rect) -> [(TaskStatus, Rect); 6] {
             ])
             .split(inner)
     } else {
+        // Calculate proportional heights, then enforce minimums
         let mut row1_h = (total_height * 42 / 100) as u16;
         let mut row2_h = (total_height * 17 / 100) as u16;
         let mut row3_h = (total_height - row1_h as i32 - row2_h as i32) as u16;
 
+        // Enforce minimums, stealing from larger rows if needed
         if row2_h < min_row_height {
             let deficit = min_row_height - row2_h;
             row2_h = min_row_height;
+            // Steal proportionally from row1 and row3
             if row1_h > min_row_height && row3_h > min_row_height {
                 let steal_from_1 = deficit / 2;
                 let steal_from_3 = deficit - steal_from_1;
@@ -88,6 +150,9 @@ pub fn calculate_kanban_cells(area: Rect) -> [(TaskStatus, Rect); 6] {
         .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
         .split(rows[2]);
 
+    // Row 1: Planned | InProgress
+    // Row 2: QA | NeedsWork
+    // Row 3: Review | Done
     [
         (TaskStatus::Planned, row1_cols[0]),
         (TaskStatus::InProgress, row1_cols[1]),
@@ -100,14 +165,14 @@ pub fn calculate_kanban_cells(area: Rect) -> [(TaskStatus, Rect); 6] {
 
 /// Hit-test a screen position against the kanban board.
 /// Returns which column/task was clicked, if any.
-pub fn hit_test_kanban(kanban_area: Rect, x: u16, y: u16) -> Option<KanbanHitResult> {
+pub fn hit_test_kanban(kanban_area: Rect, layout: BoardLayout, selected: TaskStatus, x: u16, y: u16) -> Option<KanbanHitResult> {
     // Check if click is within the kanban area at all
     if x < kanban_area.x || x >= kanban_area.x + kanban_area.width ||
        y < kanban_area.y || y >= kanban_area.y + kanban_area.height {
         return None;
     }
 
-    let cells = calculate_kanban_cells(kanban_area);
+    let cells = calculate_kanban_cells(kanban_area, layout, selected);
 
     // Find which cell was clicked
     for (status, cell_rect) in cells {
@@ -139,7 +204,7 @@ pub fn hit_test_kanban(kanban_area: Rect, x: u16, y: u16) -> Option<KanbanHitRes
     None
 }
 
-/// Render the Kanban board with six columns in a 2x3 grid
+/// Render the Kanban board's six columns in the layout that fits its width
 pub fn render_kanban(frame: &mut Frame, area: Rect, app: &App) {
     let is_focused = app.model.ui_state.focus == FocusArea::KanbanBoard;
 
@@ -162,84 +227,144 @@ pub fn render_kanban(frame: &mut Frame, area: Rect, app: &App) {
     let inner = block.inner(area);
     frame.render_widget(block, area);
 
-    // Split into 3 rows x 2 columns for the six statuses
-    // Middle row (Testing/NeedsWork) is smaller since those columns typically have fewer tasks
-    // Ensure each row has at least 3 lines (2 borders + 1 content line) for usability
-    let total_height = inner.height as i32;
-    let min_row_height: u16 = 3; // 2 for borders + 1 for at least one task line
-
-    // Calculate row heights manually to enforce minimums while preserving proportions
-    // Target ratios: 42:17:41 (total 100)
-    let rows = if total_height < (min_row_height * 3) as i32 {
-        // Extremely small: give each row equal share
-        Layout::default()
-            .direction(Direction::Vertical)
-            .constraints([
-                Constraint::Ratio(1, 3),
-                Constraint::Ratio(1, 3),
-                Constraint::Ratio(1, 3),
-            ])
-            .split(inner)
-    } else {
-        // Calculate proportional heights, then enforce minimums
-        let mut row1_h = (total_height * 42 / 100) as u16;
-        let mut row2_h = (total_height * 17 / 100) as u16;
-        let mut row3_h = (total_height - row1_h as i32 - row2_h as i32) as u16;
+    // The project's state file couldn't be read: say so instead of showing an empty board
+    if let Some(error) = app.model.active_project().and_then(|p| p.load_error.as_ref()) {
+        let lines = vec![
+            Line::from(Span::styled(
+                "Couldn't load this project",
+                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+            )),
+            Line::from(""),
+            Line::from(Span::raw(error.clone())),
+            Line::from(""),
+            Line::from(Span::styled(
+                "Its state file was left as it is and your other projects are unaffected. \
+                 Fix or remove the file, then switch back to this tab to load it again.",
+                Style::default().fg(Color::DarkGray),
+            )),
+        ];
+        let message = Paragraph::new(lines).wrap(ratatui::widgets::Wrap { trim: false });
+        frame.render_widget(message, inner);
+        return;
+    }
 
-        // Enforce minimums, stealing from larger rows if needed
-        if row2_h < min_row_height {
-            let deficit = min_row_height - row2_h;
-            row2_h = min_row_height;
-            // Steal proportionally from row1 and row3
-            if row1_h > min_row_height && row3_h > min_row_height {
-                let steal_from_1 = deficit / 2;
-                let steal_from_3 = deficit - steal_from_1;
-                row1_h = row1_h.saturating_sub(steal_from_1).max(min_row_height);
-                row3_h = row3_h.saturating_sub(steal_from_3).max(min_row_height);
-            }
-        }
-        if row1_h < min_row_height {
-            row1_h = min_row_height;
-        }
-        if row3_h < min_row_height {
-            row3_h = min_row_height;
+    let layout = app.model.global_settings.board_layout.resolve(area.width);
+    let selected = app.model.ui_state.selected_column;
+    if layout == BoardLayout::TwoColumn {
+        // The open column is listed too, marked as the one shown
+        let list_idx = TaskStatus::all().iter().position(|&s| s == selected).unwrap_or(0);
+        render_column_header(frame, column_list_rows(inner)[list_idx], app, selected);
+    }
+    for (status, cell) in calculate_kanban_cells(area, layout, selected) {
+        if is_header_cell(cell) {
+            render_column_header(frame, cell, app, status);
+        } else {
+            render_column(frame, cell, app, status);
         }
+    }
 
-        Layout::default()
-            .direction(Direction::Vertical)
-            .constraints([
-                Constraint::Length(row1_h),
-                Constraint::Length(row2_h),
-                Constraint::Length(row3_h),
-            ])
-            .split(inner)
+    render_drag_ghost(frame, area, app);
+}
+
+/// Draw the card being dragged next to the mouse pointer
+fn render_drag_ghost(frame: &mut Frame, area: Rect, app: &App) {
+    let Some(drag) = app.model.ui_state.card_drag.as_ref().filter(|d| d.moved) else {
+        return;
+    };
+    let Some(task) = app.model.active_project()
+        .and_then(|p| p.tasks.iter().find(|t| t.id == drag.task_id))
+    else {
+        return;
     };
 
-    let row1_cols = Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
-        .split(rows[0]);
+    let title = task.short_title.as_ref().unwrap_or(&task.title);
+    let max_width = area.width.saturating_sub(2).min(32);
+    if max_width < 6 || area.height < 3 {
+        return;
+    }
+    let title: String = if title.chars().count() > (max_width - 4) as usize {
+        let mut t: String = title.chars().take((max_width - 7) as usize).collect();
+        t.push_str("...");
+        t
+    } else {
+        title.clone()
+    };
+    let width = (title.chars().count() as u16 + 4).min(max_width);
+
+    // Just right of the pointer, kept inside the board
+    let x = (drag.x + 1).min(area.x + area.width - width);
+    let y = drag.y.min(area.y + area.height - 3);
+    let ghost_area = Rect { x, y, width, height: 3 };
+
+    let can_drop = drag.over.is_some_and(|to| to != drag.from);
+    let border = if can_drop { Color::Yellow } else { Color::DarkGray };
+    let ghost = Paragraph::new(Span::styled(
+        format!(" {}", title),
+        Style::default().fg(Color::White).add_modifier(Modifier::BOLD),
+    ))
+    .block(Block::default().borders(Borders::ALL).border_style(Style::default().fg(border)));
+
+    frame.render_widget(Clear, ghost_area);
+    frame.render_widget(ghost, ghost_area);
+}
 
-    let row2_cols = Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
-        .split(rows[1]);
+/// (number, title, background color, contrasting foreground for selected items)
+/// Note: Accepting/Updating tasks appear in the Review column, so they're styled like Review
+fn column_style(status: TaskStatus) -> (&'static str, &'static str, Color, Color) {
+    match status {
+        TaskStatus::Planned => ("1", "Planned", Color::Blue, Color::White),
+        TaskStatus::InProgress => ("2", "In Progress", Color::Yellow, Color::Black),
+        TaskStatus::Testing => ("3", "QA", Color::Cyan, Color::Black),
+        TaskStatus::NeedsWork => ("4", "Needs Work", Color::Red, Color::White),
+        TaskStatus::Review | TaskStatus::Accepting | TaskStatus::Updating | TaskStatus::Applying => ("5", "Review", Color::Magenta, Color::White),
+        TaskStatus::Done => ("6", "Done", Color::Green, Color::Black),
+    }
+}
 
-    let row3_cols = Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
-        .split(rows[2]);
+/// Render a collapsed column as one line: number, title and task count
+/// (a click selects it, and a dragged card can be dropped on it)
+fn render_column_header(frame: &mut Frame, area: Rect, app: &App, status: TaskStatus) {
+    let (num, title, color, _) = column_style(status);
+    let is_open = app.model.ui_state.selected_column == status;
+    let is_drop_target = app.model.ui_state.card_drag.as_ref()
+        .is_some_and(|d| d.moved && d.over == Some(status) && d.from != status);
+    let task_count = app.model.active_project()
+        .map(|p| p.tasks_by_status(status).len())
+        .unwrap_or(0);
 
-    // Render each column in 2x3 layout:
-    // Row 1: Planned | InProgress
-    // Row 2: QA | NeedsWork
-    // Row 3: Review | Done
-    render_column(frame, row1_cols[0], app, TaskStatus::Planned);
-    render_column(frame, row1_cols[1], app, TaskStatus::InProgress);
-    render_column(frame, row2_cols[0], app, TaskStatus::Testing);
-    render_column(frame, row2_cols[1], app, TaskStatus::NeedsWork);
-    render_column(frame, row3_cols[0], app, TaskStatus::Review);
-    render_column(frame, row3_cols[1], app, TaskStatus::Done);
+    let title_style = if is_drop_target {
+        Style::default().fg(color).add_modifier(Modifier::BOLD | Modifier::REVERSED)
+    } else if is_open {
+        Style::default().fg(color).add_modifier(Modifier::BOLD)
+    } else if task_count > 0 {
+        Style::default().fg(Color::Gray)
+    } else {
+        Style::default().fg(Color::DarkGray)
+    };
+    let line = Line::from(vec![
+        Span::styled(if is_open { "▾" } else { "▸" }, Style::default().fg(color)),
+        Span::styled(format!(" {}", num), Style::default().fg(Color::DarkGray)),
+        Span::styled(format!(" {} ", title), title_style),
+        Span::styled(format!("({})", task_count), Style::default().fg(Color::DarkGray)),
+    ]);
+    frame.render_widget(Paragraph::new(line), area);
+}
+
+/// " by <sort>" after a column's task count when the project sorts its columns
+fn sort_label(app: &App) -> String {
+    match app.model.active_project().map(|p| p.column_sort) {
+        Some(sort) if sort != ColumnSort::Manual => format!(" by {}", sort.name().to_lowercase()),
+        _ => String::new(),
+    }
+}
+
+/// Heading of a tag group in a column grouped by tag
+fn group_heading(tag: Option<&str>, color: Color) -> ListItem<'static> {
+    let name = tag.map_or_else(|| "no tag".to_string(), |tag| format!("#{}", tag));
+    ListItem::new(Line::from(vec![
+        Span::styled("── ", Style::default().fg(Color::DarkGray)),
+        Span::styled(name, Style::default().fg(color).add_modifier(Modifier::DIM)),
+    ]))
 }
 
 /// Render a single column of the Kanban board
@@ -247,18 +372,13 @@ fn render_column(frame: &mut Frame, area: Rect, app: &App, status: TaskStatus) {
     let is_selected = app.model.ui_state.selected_column == status
         && app.model.ui_state.focus == FocusArea::KanbanBoard;
 
-    // (number, title, background color, contrasting foreground for selected items)
-    // Note: Accepting/Updating tasks appear in the Review column, so they're styled like Review
-    let (num, title, color, contrast_fg) = match status {
-        TaskStatus::Planned => ("1", "Planned", Color::Blue, Color::White),
-        TaskStatus::InProgress => ("2", "In Progress", Color::Yellow, Color::Black),
-        TaskStatus::Testing => ("3", "QA", Color::Cyan, Color::Black),
-        TaskStatus::NeedsWork => ("4", "Needs Work", Color::Red, Color::White),
-        TaskStatus::Review | TaskStatus::Accepting | TaskStatus::Updating | TaskStatus::Applying => ("5", "Review", Color::Magenta, Color::White),
-        TaskStatus::Done => ("6", "Done", Color::Green, Color::Black),
-    };
+    let (num, title, color, contrast_fg) = column_style(status);
 
-    let border_style = if is_selected {
+    // Column a dragged card would be dropped into
+    let is_drop_target = app.model.ui_state.card_drag.as_ref()
+        .is_some_and(|d| d.moved && d.over == Some(status) && d.from != status);
+
+    let border_style = if is_selected || is_drop_target {
         Style::default().fg(color).add_modifier(Modifier::BOLD)
     } else {
         Style::default().fg(Color::DarkGray)
@@ -289,12 +409,20 @@ fn render_column(frame: &mut Frame, area: Rect, app: &App, status: TaskStatus) {
                 format!("({})", task_count),
                 Style::default().fg(Color::DarkGray),
             ),
+            Span::styled(
+                sort_label(app),
+                Style::default().fg(Color::DarkGray),
+            ),
         ]))
         .borders(Borders::ALL)
         .border_style(border_style);
 
     let inner = block.inner(area);
 
+    // Tasks past the column's time limit are flagged with how long they've sat here
+    let sla = crate::sla::SlaThresholds::from_setting(app.model.global_settings.sla_thresholds.as_deref());
+    let now = chrono::Utc::now();
+
     // Get tasks for this column
     let tasks: Vec<ListItem> = app
         .model
@@ -327,6 +455,10 @@ fn render_column(frame: &mut Frame, area: Rect, app: &App, status: TaskStatus) {
                         false
                     };
 
+                    let overdue_age = task.time_in_status(now)
+                        .filter(|elapsed| sla.is_overdue(task.status, *elapsed))
+                        .map(crate::sla::format_age);
+
                     // Styles for different parts of the task line
                     // Title gets the main style, brackets are very dim, code is dim
                     let (title_style, bracket_style, code_style) = if is_task_selected {
@@ -349,6 +481,13 @@ fn render_column(frame: &mut Frame, area: Rect, app: &App, status: TaskStatus) {
                             Style::default().fg(Color::DarkGray).add_modifier(Modifier::DIM),
                             Style::default().fg(Color::DarkGray),
                         )
+                    } else if overdue_age.is_some() {
+                        // Stuck past the column's time limit
+                        (
+                            Style::default().fg(Color::LightRed),
+                            Style::default().fg(Color::DarkGray),
+                            Style::default().fg(Color::Gray),
+                        )
                     } else {
                         (
                             Style::default().fg(Color::White),
@@ -497,6 +636,18 @@ fn render_column(frame: &mut Frame, area: Rect, app: &App, status: TaskStatus) {
                             let frame = (app.model.ui_state.animation_frame / 2) % qa_frames.len();
                             (format!("{} ", qa_frames[frame]), false)
                         }
+                        TaskStatus::Review if task.auto_merge => {
+                            // Waiting to merge once CI passes
+                            let glyph = match task.ci_status.as_ref().map(|c| c.state) {
+                                Some(state @ (crate::ci::CiState::Failed | crate::ci::CiState::Passed)) => state.glyph(),
+                                _ => "⧗",
+                            };
+                            (format!("{} ", glyph), false)
+                        }
+                        TaskStatus::Review if task.has_unseen_content() => {
+                            // New activity or diff changes not viewed yet
+                            ("● ".to_string(), false)
+                        }
                         _ => (String::new(), false),
                     }
                     };
@@ -621,10 +772,66 @@ fn render_column(frame: &mut Frame, area: Rect, app: &App, status: TaskStatus) {
                         if !task.images.is_empty() {
                             spans.push(Span::styled(" [img]", bracket_style));
                         }
+                        // Feedback waiting for Claude to finish its turn
+                        let queue_badge = if task.queued_feedback.is_empty() {
+                            String::new()
+                        } else {
+                            format!(" [✉{}]", task.queued_feedback.len())
+                        };
+                        if !queue_badge.is_empty() {
+                            let queue_style = if is_task_selected {
+                                Style::default().fg(contrast_fg).bg(color)
+                            } else {
+                                Style::default().fg(Color::Cyan)
+                            };
+                            spans.push(Span::styled(queue_badge.clone(), queue_style));
+                        }
+                        // Time tracked against the task
+                        let tracked_secs = task.tracked_secs();
+                        let tracked_badge = if tracked_secs >= 60 {
+                            format!(" ({})", crate::model::time_tracking::format_tracked(tracked_secs))
+                        } else {
+                            String::new()
+                        };
+                        if !tracked_badge.is_empty() {
+                            spans.push(Span::styled(tracked_badge.clone(), bracket_style));
+                        }
+                        let age_badge = overdue_age.as_ref().map(|age| format!(" {}", age)).unwrap_or_default();
+                        if !age_badge.is_empty() {
+                            let age_style = if is_task_selected {
+                                Style::default().fg(contrast_fg).bg(color).add_modifier(Modifier::BOLD)
+                            } else {
+                                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)
+                            };
+                            spans.push(Span::styled(age_badge.clone(), age_style));
+                        }
+                        // The last start failed (C shows why)
+                        let failed_badge = if task.start_failure.is_some() { " ✗ start failed" } else { "" };
+                        if !failed_badge.is_empty() {
+                            let failed_style = if is_task_selected {
+                                Style::default().fg(contrast_fg).bg(color).add_modifier(Modifier::BOLD)
+                            } else {
+                                Style::default().fg(Color::Red)
+                            };
+                            spans.push(Span::styled(failed_badge, failed_style));
+                        }
 
                         // Show sync status indicator for tasks with worktrees, right-aligned
+                        // (after the address the task's dev server gets)
                         if task.worktree_path.is_some() {
-                            let (indicator_text, indicator_style) = if task.git_commits_behind > 0 {
+                            let probing = task.git_status_updated_at.is_none()
+                                && app.model.active_project().is_some_and(|p| app.model.ui_state.probing_git.contains(&p.working_dir));
+                            let (indicator_text, indicator_style) = if probing {
+                                // First status since startup still being computed
+                                const PROBE_FRAMES: [char; 4] = ['⠋', '⠙', '⠸', '⠴'];
+                                let frame = (app.model.ui_state.animation_frame / 2) % PROBE_FRAMES.len();
+                                let style = if is_task_selected {
+                                    Style::default().fg(contrast_fg).bg(color)
+                                } else {
+                                    Style::default().fg(Color::DarkGray)
+                                };
+                                (PROBE_FRAMES[frame].to_string(), style)
+                            } else if task.git_commits_behind > 0 {
                                 // Behind main - show how many commits behind
                                 let style = if is_task_selected {
                                     Style::default().fg(contrast_fg).bg(color)
@@ -641,12 +848,33 @@ fn render_column(frame: &mut Frame, area: Rect, app: &App, status: TaskStatus) {
                                 };
                                 ("=".to_string(), style)
                             };
-                            let indicator_len = indicator_text.chars().count();
+                            let indicator_text = match task.ports {
+                                Some(first) => format!("localhost:{} {}", first, indicator_text),
+                                None => indicator_text,
+                            };
+                            // CI of the pushed branch, left of the sync indicator
+                            let ci_badge = task.ci_status.as_ref()
+                                .map(|ci| ci.state.glyph())
+                                .filter(|glyph| !glyph.is_empty())
+                                .map(|glyph| format!("CI{} ", glyph))
+                                .unwrap_or_default();
+                            let ci_style = if is_task_selected {
+                                Style::default().fg(contrast_fg).bg(color)
+                            } else {
+                                match task.ci_status.as_ref().map(|ci| ci.state) {
+                                    Some(crate::ci::CiState::Passed) => Style::default().fg(Color::Green),
+                                    Some(crate::ci::CiState::Failed) => Style::default().fg(Color::Red),
+                                    _ => Style::default().fg(Color::Yellow),
+                                }
+                            };
+                            let indicator_len = indicator_text.chars().count() + ci_badge.chars().count();
 
                             // Calculate current content width to determine padding needed
                             let prefix_len = prefix.chars().count();
                             let img_len = if !task.images.is_empty() { 6 } else { 0 }; // " [img]"
-                            let current_width = prefix_len + id_prefix_len + display_title.chars().count() + img_len;
+                            let current_width = prefix_len + id_prefix_len + display_title.chars().count() + img_len
+                                + queue_badge.chars().count() + tracked_badge.chars().count() + age_badge.chars().count()
+                                + failed_badge.chars().count();
                             let available_width = inner.width as usize;
 
                             // Add padding to push indicator to the right (with 1 space before it)
@@ -654,6 +882,9 @@ fn render_column(frame: &mut Frame, area: Rect, app: &App, status: TaskStatus) {
                             if padding_needed > 0 {
                                 spans.push(Span::styled(" ".repeat(padding_needed), title_style));
                             }
+                            if !ci_badge.is_empty() {
+                                spans.push(Span::styled(ci_badge, ci_style));
+                            }
                             spans.push(Span::styled(indicator_text, indicator_style));
                         }
                     }
@@ -664,6 +895,21 @@ fn render_column(frame: &mut Frame, area: Rect, app: &App, status: TaskStatus) {
         })
         .unwrap_or_default();
 
+    // Tag group headings go between the tasks; rows are counted with them
+    let rows = app.model.active_project()
+        .map(|p| p.column_rows(status))
+        .unwrap_or_default();
+    let task_row = |idx: usize| rows.iter()
+        .position(|row| matches!(row, ColumnRow::Task(i) if *i >= idx))
+        .unwrap_or(rows.len());
+    let mut items = tasks.into_iter();
+    let tasks: Vec<ListItem> = rows.iter()
+        .filter_map(|row| match row {
+            ColumnRow::Group(tag) => Some(group_heading(tag.as_deref(), color)),
+            ColumnRow::Task(_) => items.next(),
+        })
+        .collect();
+
     // Check if there's an active merge celebration animation for this column
     // that should be rendered as a "phantom" entry (task has moved to Done but animation plays)
     let mut tasks = tasks;
@@ -748,7 +994,7 @@ fn render_column(frame: &mut Frame, area: Rect, app: &App, status: TaskStatus) {
                 let phantom_item = ListItem::new(Line::from(spans));
 
                 // Insert at the original index (or append if index is beyond current length)
-                let insert_idx = celebration.task_index.min(tasks.len());
+                let insert_idx = task_row(celebration.task_index).min(tasks.len());
                 tasks.insert(insert_idx, phantom_item);
             }
         }
@@ -771,11 +1017,11 @@ fn render_column(frame: &mut Frame, area: Rect, app: &App, status: TaskStatus) {
 
         // Calculate visual index
         let visual_idx = if is_selected {
-            app.model.ui_state.selected_task_idx
+            app.model.ui_state.selected_task_idx.map(task_row)
         } else {
             // Use saved scroll offset for unselected columns to preserve scroll position
             let saved_offset = app.model.ui_state.column_scroll_offsets[status.index()];
-            Some(saved_offset)
+            Some(task_row(saved_offset))
         };
 
         list_state.select(visual_idx);
@@ -1262,3 +1508,36 @@ fn get_accepting_hints(task: &crate::model::Task) -> Vec<Span<'static>> {
 
     parts
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_narrow_layouts_open_only_the_selected_column() {
+        assert_eq!(BoardLayout::Auto.resolve(120), BoardLayout::Grid);
+        assert_eq!(BoardLayout::Auto.resolve(80), BoardLayout::TwoColumn);
+        assert_eq!(BoardLayout::Auto.resolve(40), BoardLayout::Stacked);
+        assert_eq!(BoardLayout::Grid.resolve(40), BoardLayout::Grid);
+
+        let area = Rect::new(0, 0, 50, 30);
+        for layout in [BoardLayout::TwoColumn, BoardLayout::Stacked] {
+            let cells = calculate_kanban_cells(area, layout, TaskStatus::Testing);
+            for (status, cell) in cells {
+                assert_eq!(is_header_cell(cell), status != TaskStatus::Testing, "{:?} {:?}", layout, status);
+                // A click on a header selects its column, one inside the open column picks a task
+                let hit = hit_test_kanban(area, layout, TaskStatus::Testing, cell.x, cell.y + cell.height / 2).unwrap();
+                assert_eq!(hit.status, status);
+                assert_eq!(hit.task_idx.is_some(), status == TaskStatus::Testing);
+            }
+            // Up and down walk the columns in order
+            assert_eq!(layout.above(TaskStatus::Testing), Some(TaskStatus::InProgress));
+            assert_eq!(layout.below(TaskStatus::Done), None);
+        }
+
+        // Stacked keeps the headers in column order around the open column
+        let stacked = calculate_kanban_cells(area, BoardLayout::Stacked, TaskStatus::Testing);
+        assert!(stacked.windows(2).all(|pair| pair[0].1.y < pair[1].1.y));
+        assert_eq!(stacked[2].1.height, 28 - 5);
+    }
+}
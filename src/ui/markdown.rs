@@ -0,0 +1,213 @@
+//! Rendered markdown for the input preview and the task preview modal
+//!
+//! Covers what task descriptions and specs use: headings, bullet, numbered
+//! and task lists, block quotes, fenced code blocks, rules, and inline bold,
+//! italic, code and links. Anything else is shown as written.
+
+use ratatui::{
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+};
+
+use super::ultrathink;
+
+/// Width of a horizontal rule
+const RULE_WIDTH: usize = 40;
+
+/// Render `text` as styled lines, `base` being the style of paragraph text
+pub fn to_lines(text: &str, base: Style) -> Vec<Line<'static>> {
+    let mut lines = Vec::new();
+    let mut in_code = false;
+    let code_style = Style::default().fg(Color::LightGreen);
+    let gutter_style = Style::default().fg(Color::DarkGray);
+
+    for line in text.lines() {
+        let trimmed = line.trim_start();
+        let indent = line.len() - trimmed.len();
+
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            if !in_code {
+                let lang = trimmed[3..].trim();
+                let label = if lang.is_empty() { "┌".to_string() } else { format!("┌ {}", lang) };
+                lines.push(Line::from(Span::styled(label, gutter_style)));
+            }
+            in_code = !in_code;
+            continue;
+        }
+        if in_code {
+            lines.push(Line::from(vec![
+                Span::styled("│ ", gutter_style),
+                Span::styled(line.to_string(), code_style),
+            ]));
+            continue;
+        }
+
+        if trimmed.is_empty() {
+            lines.push(Line::from(""));
+        } else if let Some((level, heading)) = heading(trimmed) {
+            let style = match level {
+                1 => Style::default().fg(Color::Magenta).add_modifier(Modifier::BOLD | Modifier::UNDERLINED),
+                2 => Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD),
+                _ => base.add_modifier(Modifier::BOLD),
+            };
+            lines.push(Line::from(inline(heading, style)));
+        } else if is_rule(trimmed) {
+            lines.push(Line::from(Span::styled("─".repeat(RULE_WIDTH), gutter_style)));
+        } else if let Some(quote) = trimmed.strip_prefix('>') {
+            let style = Style::default().fg(Color::Yellow);
+            let mut spans = vec![Span::styled("│ ", style)];
+            spans.extend(inline(quote.trim_start(), style));
+            lines.push(Line::from(spans));
+        } else if let Some((marker, item)) = list_item(trimmed) {
+            let mut spans = vec![
+                Span::raw(" ".repeat(indent)),
+                Span::styled(marker, Style::default().fg(Color::Green)),
+            ];
+            spans.extend(inline(item, base));
+            lines.push(Line::from(spans));
+        } else {
+            lines.push(Line::from(inline(line, base)));
+        }
+    }
+    lines
+}
+
+/// `# Title` → (1, "Title")
+fn heading(line: &str) -> Option<(usize, &str)> {
+    let level = line.chars().take_while(|c| *c == '#').count();
+    let rest = line[level..].strip_prefix(' ')?;
+    (1..=6).contains(&level).then_some((level, rest.trim()))
+}
+
+/// `---`, `***` or `___` (spaces allowed)
+fn is_rule(line: &str) -> bool {
+    let marks: String = line.chars().filter(|c| !c.is_whitespace()).collect();
+    marks.len() >= 3
+        && ['-', '*', '_'].iter().any(|m| marks.chars().all(|c| c == *m))
+}
+
+/// The marker to show for a list item and the item's text
+fn list_item(line: &str) -> Option<(String, &str)> {
+    if let Some(item) = ["- ", "* ", "+ "].iter().find_map(|m| line.strip_prefix(m)) {
+        let task = [("[ ] ", "☐ "), ("[x] ", "☑ "), ("[X] ", "☑ ")]
+            .iter()
+            .find_map(|(box_text, mark)| item.strip_prefix(box_text).map(|rest| (mark.to_string(), rest)));
+        return Some(task.unwrap_or(("• ".to_string(), item)));
+    }
+    let digits = line.chars().take_while(|c| c.is_ascii_digit()).count();
+    let rest = &line[digits..];
+    let item = rest.strip_prefix(". ").or_else(|| rest.strip_prefix(") "));
+    match item {
+        Some(item) if digits > 0 => Some((format!("{}. ", &line[..digits]), item)),
+        _ => None,
+    }
+}
+
+/// The text between `open` at the start of `text` and the next `close`,
+/// with the byte length of the whole construct
+fn delimited<'a>(text: &'a str, open: &str, close: &str) -> Option<(&'a str, usize)> {
+    let inner = text.strip_prefix(open)?;
+    let end = inner.find(close)?;
+    let content = &inner[..end];
+    if content.is_empty() || content.starts_with(char::is_whitespace) {
+        return None;
+    }
+    Some((content, open.len() + end + close.len()))
+}
+
+/// Inline styling: **bold**, *italic* / _italic_, `code` and [links](url)
+fn inline(text: &str, base: Style) -> Vec<Span<'static>> {
+    let mut spans = Vec::new();
+    let mut plain = String::new();
+    let flush = |plain: &mut String, spans: &mut Vec<Span<'static>>| {
+        if plain.is_empty() {
+            return;
+        }
+        if ultrathink::contains_ultrathink(plain) {
+            spans.extend(ultrathink::style_line_with_ultrathink(plain, base));
+        } else {
+            spans.push(Span::styled(plain.clone(), base));
+        }
+        plain.clear();
+    };
+
+    let mut rest = text;
+    let mut previous: Option<char> = None;
+    while let Some(ch) = rest.chars().next() {
+        let at_word_start = previous.is_none_or(|p| !p.is_alphanumeric());
+        let styled = match ch {
+            '`' => delimited(rest, "`", "`")
+                .map(|(code, len)| (vec![Span::styled(code.to_string(), Style::default().fg(Color::LightGreen))], len)),
+            '*' if rest.starts_with("**") => delimited(rest, "**", "**")
+                .map(|(bold, len)| (inline(bold, base.add_modifier(Modifier::BOLD)), len)),
+            '*' | '_' if at_word_start => {
+                let marker = &rest[..1];
+                delimited(rest, marker, marker)
+                    .map(|(italic, len)| (inline(italic, base.add_modifier(Modifier::ITALIC)), len))
+            }
+            '[' => delimited(rest, "[", "](").and_then(|(label, label_len)| {
+                let (url, url_len) = delimited(&rest[label_len - 1..], "(", ")")?;
+                let link_style = Style::default().fg(Color::Blue).add_modifier(Modifier::UNDERLINED);
+                let mut link = vec![Span::styled(label.to_string(), link_style)];
+                if url != label {
+                    link.push(Span::styled(format!(" ({})", url), Style::default().fg(Color::DarkGray)));
+                }
+                Some((link, label_len - 1 + url_len))
+            }),
+            _ => None,
+        };
+        match styled {
+            Some((styled, len)) => {
+                flush(&mut plain, &mut spans);
+                spans.extend(styled);
+                previous = rest[..len].chars().next_back();
+                rest = &rest[len..];
+            }
+            None => {
+                plain.push(ch);
+                previous = Some(ch);
+                rest = &rest[ch.len_utf8()..];
+            }
+        }
+    }
+    flush(&mut plain, &mut spans);
+    spans
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(line: &Line) -> String {
+        line.spans.iter().map(|s| s.content.as_ref()).collect()
+    }
+
+    #[test]
+    fn test_render_blocks_and_inline() {
+        let source = "# Login\n\nFix **the** `crash` in [auth](https://x.dev) for snake_case_names\n\
+                      - [ ] write test\n  2. nested step\n> careful\n---\n```rust\nlet x = *y*;\n```";
+        let lines = to_lines(source, Style::default());
+        let rendered: Vec<String> = lines.iter().map(text).collect();
+        assert_eq!(rendered, vec![
+            "Login",
+            "",
+            "Fix the crash in auth (https://x.dev) for snake_case_names",
+            "☐ write test",
+            "  2. nested step",
+            "│ careful",
+            &"─".repeat(RULE_WIDTH),
+            "┌ rust",
+            "│ let x = *y*;",
+        ]);
+
+        assert!(lines[0].spans[0].style.add_modifier.contains(Modifier::UNDERLINED));
+        let bold = lines[2].spans.iter().find(|s| s.content == "the").unwrap();
+        assert!(bold.style.add_modifier.contains(Modifier::BOLD));
+        let link = lines[2].spans.iter().find(|s| s.content == "auth").unwrap();
+        assert_eq!(link.style.fg, Some(Color::Blue));
+
+        let italic = inline("an *important* note", Style::default());
+        assert!(italic[1].style.add_modifier.contains(Modifier::ITALIC));
+        assert_eq!(text(&Line::from(inline("2 * 3 * 4", Style::default()))), "2 * 3 * 4");
+    }
+}
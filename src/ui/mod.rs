@@ -1,7 +1,13 @@
+pub mod hyperlinks;
 mod interactive_modal;
 mod kanban;
+mod markdown;
 pub mod logo;
+mod monitor;
 mod output;
+mod project_bar;
+pub mod palette;
+pub mod snapshot;
 mod status_bar;
 pub mod ultrathink;
 pub mod watcher;
@@ -21,7 +27,10 @@ use ratatui::{
 };
 
 pub use interactive_modal::render_interactive_modal;
-pub use kanban::{hit_test_kanban, render_kanban};
+pub use kanban::{calculate_kanban_cells, hit_test_kanban, is_header_cell, render_kanban};
+pub use monitor::hit_test_monitor;
+pub use output::split_output_pane;
+pub use project_bar::{header_logo_size, hit_test_project_bar, project_bar_area_width, ProjectBarHitResult, PROJECT_KEYS};
 pub use status_bar::render_status_bar;
 pub use welcome::welcome_message_count;
 
@@ -49,6 +58,8 @@ pub fn view(frame: &mut Frame, app: &mut App) {
     // Calculate dynamic input height based on editor content
     // Hide input on welcome screen - it's useless without a project
     let frame_width = frame.area().width.saturating_sub(4) as usize; // Account for borders
+    // The markdown preview takes half the width, so the text wraps sooner
+    let frame_width = if app.model.ui_state.markdown_preview { frame_width / 2 } else { frame_width };
     let input_height = if is_welcome_screen {
         0
     } else {
@@ -59,16 +70,18 @@ pub fn view(frame: &mut Frame, app: &mut App) {
     // Show full 3-line logo header when terminal is wide enough, tall enough,
     // AND the project tabs don't overlap with the logo
     // (mascot overlays the project bar line to save vertical space)
-    let project_bar_width = calculate_project_bar_width(app);
-    let logo_size = logo::get_logo_size_for_project_bar(frame.area().width, frame.area().height, project_bar_width);
+    let logo_size = header_logo_size(app, frame.area().width, frame.area().height);
     let show_full_header = matches!(logo_size, logo::LogoSize::Full | logo::LogoSize::Medium);
     let header_height = if show_full_header { 3 } else { 1 };
 
+    let banner = if is_welcome_screen { None } else { capability_banner(app) };
+
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
             Constraint::Length(header_height),  // Header (project bar + optional logo)
             Constraint::Min(10),                // Main content (Kanban board or Welcome)
+            Constraint::Length(banner.is_some() as u16), // Missing-integration banner
             Constraint::Length(input_height),   // Input area (hidden on welcome screen)
             Constraint::Length(1),              // Status bar
         ])
@@ -89,30 +102,53 @@ pub fn view(frame: &mut Frame, app: &mut App) {
             app.model.ui_state.welcome_bubble_focused,
             app.model.ui_state.is_open_project_dialog_open(),
         );
+    } else if app.model.ui_state.focus == FocusArea::Monitor {
+        // The monitor screen takes the place of the board (and output pane)
+        monitor::render_monitor(frame, chunks[1], app);
     } else {
-        // Render kanban board (full width - tmux handles the split)
-        render_kanban(frame, chunks[1], app);
+        // Render kanban board (full width - tmux handles the split), sharing
+        // the space with the live output pane when it is open: beside the
+        // board on wide terminals, below it otherwise
+        let kanban_area = if let Some(ref pane) = app.model.ui_state.output_pane {
+            let (kanban_area, pane_area) = split_output_pane(chunks[1]);
+            output::render_output(frame, pane_area, app, pane);
+            kanban_area
+        } else {
+            chunks[1]
+        };
+        // Arrow keys and clicks follow the layout the board is drawn in
+        app.model.ui_state.board_layout = app.model.global_settings.board_layout.resolve(kanban_area.width);
+        render_kanban(frame, kanban_area, app);
 
         // Render mascot feet overlapping the kanban border (only when full/medium logo is shown)
         if show_full_header {
             // The feet should be rendered at the top row of the kanban area, right-aligned
             let feet_area = Rect {
-                x: chunks[1].x,
-                y: chunks[1].y,
-                width: chunks[1].width,
+                x: kanban_area.x,
+                y: kanban_area.y,
+                width: kanban_area.width,
                 height: 1,
             };
             logo::render_mascot_feet(frame, feet_area, app.model.ui_state.logo_shimmer_frame, logo_size);
         }
     }
 
+    if let Some(banner) = banner {
+        let line = Line::from(vec![
+            Span::styled(" ⚠ ", Style::default().fg(Color::Black).bg(Color::Yellow)),
+            Span::styled(format!(" {}", banner), Style::default().fg(Color::Yellow)),
+        ]);
+        frame.render_widget(Paragraph::new(line), chunks[2]);
+    }
+
     // Render task input area (skip on welcome screen)
     if !is_welcome_screen {
-        render_input(frame, chunks[2], app);
+        render_input(frame, chunks[3], app);
+        render_slash_completions(frame, chunks[3], app);
     }
 
     // Render status bar (includes git status)
-    render_status_bar(frame, chunks[3], app);
+    render_status_bar(frame, chunks[4], app);
 
     // Render help overlay if active
     if app.model.ui_state.show_help {
@@ -124,6 +160,16 @@ pub fn view(frame: &mut Frame, app: &mut App) {
         render_stats_modal(frame, app);
     }
 
+    // Render debug log viewer if open
+    if let Some(ref viewer) = app.model.ui_state.log_viewer {
+        render_log_viewer(frame, viewer);
+    }
+
+    // Render diagnostics modal if active
+    if app.model.ui_state.show_diagnostics {
+        render_diagnostics_modal(frame, app.model.ui_state.diagnostics_scroll_offset, app.model.ui_state.diagnostics_slow_ops);
+    }
+
     // Render queue dialog if active
     if app.model.ui_state.is_queue_dialog_open() {
         render_queue_dialog(frame, app);
@@ -134,6 +180,16 @@ pub fn view(frame: &mut Frame, app: &mut App) {
         render_task_preview_modal(frame, app);
     }
 
+    // Render session replay over the task preview
+    if let Some(ref replay) = app.model.ui_state.session_replay {
+        render_session_replay(frame, app, replay);
+    }
+
+    // Render the scrollback pager over the task preview
+    if let Some(ref viewer) = app.model.ui_state.scrollback_viewer {
+        output::render_scrollback(frame, viewer);
+    }
+
     // Render open project dialog if active
     if app.model.ui_state.is_open_project_dialog_open() {
         render_open_project_dialog(frame, app);
@@ -149,6 +205,61 @@ pub fn view(frame: &mut Frame, app: &mut App) {
         render_stash_modal(frame, app);
     }
 
+    // Render integrations panel if active
+    if app.model.ui_state.show_integrations {
+        render_integrations(frame, app);
+    }
+
+    // Render quick diff popup if active
+    if let Some(task_id) = app.model.ui_state.quick_diff {
+        render_quick_diff(frame, app, task_id);
+    }
+
+    // Render focus session picker if active
+    if let Some(ref picker) = app.model.ui_state.focus_picker {
+        render_focus_picker(frame, app, picker);
+    }
+
+    // Render project picker if active
+    if let Some(ref picker) = app.model.ui_state.project_picker {
+        render_project_picker(frame, app, picker);
+    }
+
+    // Render workspace picker if active
+    if let Some(ref picker) = app.model.ui_state.workspace_picker {
+        render_workspace_picker(frame, app, picker);
+    }
+
+    // Render dependency graph modal if active
+    if app.model.ui_state.show_dependency_graph {
+        render_dependency_graph(frame, app);
+    }
+
+    // Render release notes modal if active
+    if app.model.ui_state.release_notes.is_some() {
+        render_release_notes(frame, app);
+    }
+
+    // Render "what's new" screen if active
+    if app.model.ui_state.whats_new.is_some() {
+        render_whats_new(frame, app);
+    }
+
+    // Render code search modal if active
+    if app.model.ui_state.code_search.is_some() {
+        render_code_search(frame, app);
+    }
+
+    // Render TODO scanner modal if active
+    if app.model.ui_state.todo_scan.is_some() {
+        render_todo_scan(frame, app);
+    }
+
+    // Render sparse-checkout start prompt if active
+    if app.model.ui_state.sparse_prompt.is_some() {
+        render_sparse_prompt(frame, app);
+    }
+
     // Render sidecar control modal if active
     if app.model.ui_state.is_sidecar_modal_open() {
         render_sidecar_modal(frame, app);
@@ -159,6 +270,31 @@ pub fn view(frame: &mut Frame, app: &mut App) {
         render_md_file_picker(frame, app);
     }
 
+    // Render feedback snippet picker if active
+    if app.model.ui_state.snippet_picker.is_some() {
+        render_snippet_picker(frame, app);
+    }
+
+    // Render link picker if active
+    if app.model.ui_state.link_picker.is_some() {
+        render_link_picker(frame, app);
+    }
+
+    // Render commit message editor if a merge is waiting on it
+    if app.model.ui_state.commit_editor.is_some() {
+        render_commit_editor(frame, app);
+    }
+
+    // Render watcher feed if open
+    if let (Some(selected), Some(project)) = (app.model.ui_state.watcher_feed, app.model.active_project()) {
+        watcher::render_watcher_feed(frame, frame.area(), project, selected);
+    }
+
+    // Render why a task's start failed
+    if app.model.ui_state.start_failure.is_some() {
+        render_start_failure(frame, app);
+    }
+
     // Render watcher insight modal if active
     if app.model.ui_state.show_watcher_insight_modal {
         if let Some(ref project) = app.model.active_project() {
@@ -220,101 +356,6 @@ pub fn calculate_input_height(content: &str, available_width: usize) -> u16 {
     needed_height.clamp(MIN_HEIGHT, MAX_HEIGHT)
 }
 
-/// Calculate the total width needed for the project bar (all tabs)
-/// This includes the +project button, all project tabs with their names, attention badges, and separators
-fn calculate_project_bar_width(app: &App) -> u16 {
-    let num_projects = app.model.projects.len();
-
-    let mut width: usize = 1; // Leading space
-
-    // +project button (index 0)
-    if num_projects < 9 {
-        // " [!] +project " = 14 chars when no projects, " [!] + " = 7 chars otherwise
-        let label_len = if num_projects == 0 { 14 } else { 7 };
-        width += label_len;
-        width += 3; // " │ " separator
-    }
-
-    // Project tabs
-    for (idx, project) in app.model.projects.iter().enumerate() {
-        // Tab text: " [X] name " where X is the shift char
-        if idx + 1 < 10 {
-            // " [X] name " = 6 + name.len()
-            width += 6 + project.name.len();
-        } else {
-            // " name " = 2 + name.len()
-            width += 2 + project.name.len();
-        }
-
-        // Attention badge: " N " where N is the count
-        let attention_count = project.attention_count();
-        if attention_count > 0 {
-            // " N " for single digit, " NN " for double digit, etc.
-            width += 3 + attention_count.to_string().len() - 1;
-        }
-
-        // Separator: " │ "
-        width += 3;
-    }
-
-    width as u16
-}
-
-/// Result of hit-testing a click position against the project bar
-#[derive(Debug, Clone)]
-pub enum ProjectBarHitResult {
-    /// Clicked on the +project button
-    AddProject,
-    /// Clicked on a specific project tab (index into projects list)
-    SwitchProject(usize),
-}
-
-/// Hit-test a screen position against the project bar.
-/// Returns which tab was clicked, if any.
-pub fn hit_test_project_bar(app: &App, x: u16) -> Option<ProjectBarHitResult> {
-    let num_projects = app.model.projects.len();
-    let mut current_x: usize = 1; // Leading space " "
-
-    // +project button (index 0 in tab selection)
-    if num_projects < 9 {
-        let label_len = if num_projects == 0 { 14 } else { 7 }; // " [!] +project " or " [!] + "
-        let button_end = current_x + label_len;
-
-        if (x as usize) >= current_x && (x as usize) < button_end {
-            return Some(ProjectBarHitResult::AddProject);
-        }
-        current_x = button_end + 3; // Skip separator " │ "
-    }
-
-    // Project tabs
-    for (idx, project) in app.model.projects.iter().enumerate() {
-        // Tab text: " [X] name " where X is the shift char
-        let tab_len = if idx + 1 < 10 {
-            6 + project.name.len() // " [X] name "
-        } else {
-            2 + project.name.len() // " name "
-        };
-
-        // Attention badge: " N "
-        let attention_count = project.attention_count();
-        let badge_len = if attention_count > 0 {
-            2 + attention_count.to_string().len() // " N "
-        } else {
-            0
-        };
-
-        let tab_end = current_x + tab_len + badge_len;
-
-        if (x as usize) >= current_x && (x as usize) < tab_end {
-            return Some(ProjectBarHitResult::SwitchProject(idx));
-        }
-
-        current_x = tab_end + 3; // Skip separator " │ "
-    }
-
-    None
-}
-
 /// Render the header area (project bar + optional logo)
 fn render_header(frame: &mut Frame, area: Rect, app: &App, logo_size: logo::LogoSize) {
     let is_welcome_screen = app.model.projects.is_empty();
@@ -323,22 +364,15 @@ fn render_header(frame: &mut Frame, area: Rect, app: &App, logo_size: logo::Logo
         logo::LogoSize::Full | logo::LogoSize::Medium => {
             // Render project bar on top-left (just first line)
             // Use exact logo width - no extra padding
-            let logo_width = if is_welcome_screen {
-                // Welcome screen: just KANBLAM text, no mascot
-                logo::KANBLAM_TEXT_WIDTH
-            } else if matches!(logo_size, logo::LogoSize::Full) {
-                logo::FULL_LOGO_WIDTH
-            } else {
-                logo::MEDIUM_LOGO_WIDTH
-            };
+            let bar_width = project_bar_area_width(app, logo_size, area.width);
 
             let project_bar_area = Rect {
                 x: area.x,
                 y: area.y,
-                width: area.width.saturating_sub(logo_width),
+                width: bar_width,
                 height: 1,
             };
-            render_project_bar(frame, project_bar_area, app);
+            project_bar::render_project_bar(frame, project_bar_area, app);
 
             if is_welcome_screen {
                 // Welcome screen: render just KANBLAM text (no mascot - it's in the center)
@@ -357,7 +391,7 @@ fn render_header(frame: &mut Frame, area: Rect, app: &App, logo_size: logo::Logo
                 if let Some(ref project) = app.model.active_project() {
                     if let Some(ref comment) = project.watcher_comment {
                         // Balloon area stops at logo boundary (no overlap)
-                        let balloon_width = area.width.saturating_sub(logo_width);
+                        let balloon_width = bar_width;
                         if balloon_width > 20 && area.height >= 3 {
                             render_watcher_balloon_inline(frame, Rect {
                                 x: area.x,
@@ -375,7 +409,7 @@ fn render_header(frame: &mut Frame, area: Rect, app: &App, logo_size: logo::Logo
         }
         _ => {
             // Compact mode: project bar with inline branding
-            render_project_bar_with_branding(frame, area, app);
+            project_bar::render_project_bar_with_branding(frame, area, app);
         }
     }
 }
@@ -453,6 +487,8 @@ fn render_watcher_balloon_inline(
     // Build hint spans: (key1, desc1, key2, desc2)
     let (key1, desc1, key2, desc2) = if comment.is_intro {
         ("esc", " dismiss  ", "^w", " disable")
+    } else if comment.insight.is_some() && !comment.insight_seen {
+        ("z", " new insight  ", "esc", " dismiss")
     } else if comment.insight.is_some() {
         ("z", " show more  ", "esc", " dismiss")
     } else {
@@ -549,184 +585,12 @@ fn take_by_display_width(chars: &[char], skip_display_width: usize, take_display
     result
 }
 
-/// Render the project bar at the top of the screen
-fn render_project_bar(frame: &mut Frame, area: Rect, app: &App) {
-    let mut spans = Vec::new();
-    spans.push(Span::raw(" "));
-
-    let is_focused = app.model.ui_state.focus == FocusArea::ProjectTabs;
-    let selected_tab_idx = app.model.ui_state.selected_project_tab_idx;
-    let shift_chars = ['!', '@', '#', '$', '%', '^', '&', '*', '(', ')'];
-    let num_projects = app.model.projects.len();
-
-    // First: Show +project button (index 0 in tab selection)
-    if num_projects < 9 {
-        // Highlight on welcome screen when bubble is not focused, or when normally selected
-        let welcome_bubble_focused = app.model.ui_state.welcome_bubble_focused;
-        let is_tab_selected = (is_focused && selected_tab_idx == 0)
-            || (num_projects == 0 && !welcome_bubble_focused);
-        let style = if is_tab_selected {
-            Style::default()
-                .fg(Color::Black)
-                .bg(Color::Yellow)
-                .add_modifier(Modifier::BOLD)
-        } else {
-            Style::default().fg(Color::DarkGray)
-        };
-        // Show "+project" when no projects exist, just "+" otherwise
-        let label = if num_projects == 0 { " [!] +project " } else { " [!] + " };
-        spans.push(Span::styled(label, style));
-        spans.push(Span::styled(" │ ", Style::default().fg(Color::DarkGray)));
-    }
-
-    // Show existing projects (index 1+ in tab selection)
-    for (idx, project) in app.model.projects.iter().enumerate() {
-        let is_active = idx == app.model.active_project_idx;
-        // Tab index is idx + 1 (since 0 is +project)
-        let is_tab_selected = is_focused && selected_tab_idx == idx + 1;
-        let attention_count = project.attention_count();
-
-        let style = if is_tab_selected {
-            // Highlighted selection (when navigating with arrows in ProjectTabs focus)
-            Style::default()
-                .fg(Color::Black)
-                .bg(Color::Yellow)
-                .add_modifier(Modifier::BOLD)
-        } else if is_active {
-            Style::default()
-                .fg(Color::Black)
-                .bg(Color::Cyan)
-                .add_modifier(Modifier::BOLD)
-        } else {
-            Style::default().fg(Color::Gray)
-        };
-
-        // Keyboard shortcut: @ for first project, # for second, etc. (! is for +project)
-        let tab_text = if idx + 1 < 10 {
-            format!(" [{}] {} ", shift_chars[idx + 1], project.name)
-        } else {
-            format!(" {} ", project.name)
-        };
-
-        spans.push(Span::styled(tab_text, style));
-
-        // Add red badge for projects with tasks needing attention
-        if attention_count > 0 {
-            spans.push(Span::styled(
-                format!(" {} ", attention_count),
-                Style::default()
-                    .fg(Color::White)
-                    .bg(Color::Red)
-                    .add_modifier(Modifier::BOLD),
-            ));
-        }
-
-        spans.push(Span::styled(" │ ", Style::default().fg(Color::DarkGray)));
-    }
-
-    let bar = Paragraph::new(Line::from(spans));
-    frame.render_widget(bar, area);
-}
-
-/// Render the project bar with inline branding on the right
-fn render_project_bar_with_branding(frame: &mut Frame, area: Rect, app: &App) {
-    let green = Color::Rgb(80, 200, 120);
-    let _dark_green = Color::Rgb(60, 150, 90);
-
-    let mut spans = Vec::new();
-    spans.push(Span::raw(" "));
-
-    let is_focused = app.model.ui_state.focus == FocusArea::ProjectTabs;
-    let selected_tab_idx = app.model.ui_state.selected_project_tab_idx;
-    let shift_chars = ['!', '@', '#', '$', '%', '^', '&', '*', '(', ')'];
-    let num_projects = app.model.projects.len();
-
-    // First: Show +project button (index 0 in tab selection)
-    if num_projects < 9 {
-        // Highlight on welcome screen when bubble is not focused, or when normally selected
-        let welcome_bubble_focused = app.model.ui_state.welcome_bubble_focused;
-        let is_tab_selected = (is_focused && selected_tab_idx == 0)
-            || (num_projects == 0 && !welcome_bubble_focused);
-        let style = if is_tab_selected {
-            Style::default()
-                .fg(Color::Black)
-                .bg(Color::Yellow)
-                .add_modifier(Modifier::BOLD)
-        } else {
-            Style::default().fg(Color::DarkGray)
-        };
-        // Show "+project" when no projects exist, just "+" otherwise
-        let label = if num_projects == 0 { " [!] +project " } else { " [!] + " };
-        spans.push(Span::styled(label, style));
-        spans.push(Span::styled(" │ ", Style::default().fg(Color::DarkGray)));
-    }
-
-    // Show existing projects (index 1+ in tab selection)
-    for (idx, project) in app.model.projects.iter().enumerate() {
-        let is_active = idx == app.model.active_project_idx;
-        // Tab index is idx + 1 (since 0 is +project)
-        let is_tab_selected = is_focused && selected_tab_idx == idx + 1;
-        let attention_count = project.attention_count();
-
-        let style = if is_tab_selected {
-            // Highlighted selection (when navigating with arrows in ProjectTabs focus)
-            Style::default()
-                .fg(Color::Black)
-                .bg(Color::Yellow)
-                .add_modifier(Modifier::BOLD)
-        } else if is_active {
-            Style::default()
-                .fg(Color::Black)
-                .bg(Color::Cyan)
-                .add_modifier(Modifier::BOLD)
-        } else {
-            Style::default().fg(Color::Gray)
-        };
-
-        // Keyboard shortcut: @ for first project, # for second, etc. (! is for +project)
-        let tab_text = if idx + 1 < 10 {
-            format!(" [{}] {} ", shift_chars[idx + 1], project.name)
-        } else {
-            format!(" {} ", project.name)
-        };
-
-        spans.push(Span::styled(tab_text, style));
-
-        // Add red badge for projects with tasks needing attention
-        if attention_count > 0 {
-            spans.push(Span::styled(
-                format!(" {} ", attention_count),
-                Style::default()
-                    .fg(Color::White)
-                    .bg(Color::Red)
-                    .add_modifier(Modifier::BOLD),
-            ));
-        }
-
-        spans.push(Span::styled(" │ ", Style::default().fg(Color::DarkGray)));
-    }
-
-    // Calculate remaining space for branding
-    let project_bar_len: usize = spans.iter().map(|s| s.content.chars().count()).sum();
-    let remaining = (area.width as usize).saturating_sub(project_bar_len);
-
-    // Add branding on the right if there's space
-    if remaining >= logo::COMPACT_LOGO_WIDTH as usize {
-        let branding = "KANBLAM";
-        let padding = remaining.saturating_sub(branding.len() + 1);
-        spans.push(Span::raw(" ".repeat(padding)));
-        spans.push(Span::styled(branding, Style::default().fg(green)));
-    }
-
-    let bar = Paragraph::new(Line::from(spans));
-    frame.render_widget(bar, area);
-}
-
 /// Render the task input area using edtui
 fn render_input(frame: &mut Frame, area: Rect, app: &mut App) {
     let is_focused = app.model.ui_state.focus == FocusArea::TaskInput;
     let is_editing_task = app.model.ui_state.editing_task_id.is_some();
     let is_feedback_mode = app.model.ui_state.feedback_task_id.is_some();
+    let is_broadcast_mode = app.model.ui_state.broadcast_feedback;
     let is_note_mode = app.model.ui_state.note_task_id.is_some();
 
     // Check if feedback is for a live (InProgress) task
@@ -738,8 +602,8 @@ fn render_input(frame: &mut Frame, area: Rect, app: &mut App) {
 
     // Choose colors based on focus and mode
     let (border_color, text_color) = if is_focused {
-        let color = if is_live_feedback {
-            Color::Green  // Green for live feedback to running task
+        let color = if is_live_feedback || is_broadcast_mode {
+            Color::Green  // Green for live feedback to running task(s)
         } else if is_feedback_mode {
             Color::Cyan   // Cyan for feedback to paused task
         } else if is_note_mode {
@@ -756,6 +620,7 @@ fn render_input(frame: &mut Frame, area: Rect, app: &mut App) {
 
     // Choose title based on mode
     let pending_count = app.model.ui_state.pending_images.len();
+    let pending_files = app.model.ui_state.pending_attachments.len();
     let title_style = if is_focused {
         Style::default().fg(border_color).add_modifier(Modifier::BOLD)
     } else {
@@ -779,7 +644,12 @@ fn render_input(frame: &mut Frame, area: Rect, app: &mut App) {
         pending_count
     };
 
-    let title = if is_live_feedback {
+    let title = if is_broadcast_mode {
+        let count = app.model.active_project()
+            .map(|p| p.tasks_by_status(TaskStatus::InProgress).len())
+            .unwrap_or(0);
+        Line::from(Span::styled(format!(" Broadcast Feedback → {} task(s) ", count), title_style))
+    } else if is_live_feedback {
         let img_count = app.model.ui_state.feedback_task_id.map(get_task_image_count).unwrap_or(0);
         if img_count > 0 {
             Line::from(Span::styled(format!(" Live Feedback [{}img] ", img_count), title_style))
@@ -802,8 +672,15 @@ fn render_input(frame: &mut Frame, area: Rect, app: &mut App) {
         } else {
             Line::from(Span::styled(" Edit Task ", title_style))
         }
-    } else if pending_count > 0 {
-        Line::from(Span::styled(format!(" New Task [+{} img] ", pending_count), title_style))
+    } else if pending_count > 0 || pending_files > 0 {
+        let mut tags = String::new();
+        if pending_count > 0 {
+            tags.push_str(&format!("[+{} img] ", pending_count));
+        }
+        if pending_files > 0 {
+            tags.push_str(&format!("[+{} file] ", pending_files));
+        }
+        Line::from(Span::styled(format!(" New Task {}", tags), title_style))
     } else {
         Line::from(Span::styled(" New Task ", title_style))
     };
@@ -830,6 +707,25 @@ fn render_input(frame: &mut Frame, area: Rect, app: &mut App) {
     let inner = block.inner(area);
     frame.render_widget(block, area);
 
+    // With the markdown preview on, the rendered text sits beside the editor
+    let text = app.model.ui_state.get_input_text();
+    let inner = if app.model.ui_state.markdown_preview && !text.trim().is_empty() {
+        let halves = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(50), Constraint::Length(1), Constraint::Min(1)])
+            .split(inner);
+        let divider: Vec<Line> = (0..halves[1].height).map(|_| Line::from("│")).collect();
+        frame.render_widget(Paragraph::new(divider).style(Style::default().fg(Color::DarkGray)), halves[1]);
+        let rendered = markdown::to_lines(&text, Style::default().fg(Color::White));
+        frame.render_widget(
+            Paragraph::new(rendered).wrap(ratatui::widgets::Wrap { trim: false }),
+            halves[2],
+        );
+        halves[0]
+    } else {
+        inner
+    };
+
     // Configure the editor theme
     // Hide the status line (Insert/Normal mode indicator) when vim mode is off
     let theme = EditorTheme::default()
@@ -852,6 +748,12 @@ fn render_input(frame: &mut Frame, area: Rect, app: &mut App) {
         .theme(theme)
         .render(inner, frame.buffer_mut());
 
+    if let Some(dictionary) = app.model.global_settings.spell_check.as_deref().and_then(crate::spell::dictionary) {
+        // The vim status line isn't text
+        let text_rows = if app.model.ui_state.vim_mode_enabled { inner.height.saturating_sub(1) } else { inner.height };
+        underline_misspellings(frame.buffer_mut(), Rect { height: text_rows, ..inner }, &dictionary);
+    }
+
     // Render hints at bottom-right of the border
     // Show mode-specific hints when focused
     let key_style = Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD);
@@ -869,15 +771,15 @@ fn render_input(frame: &mut Frame, area: Rect, app: &mut App) {
     );
 
     let (hints, hints_width) = if !is_focused {
-        // When unfocused, show insert hint and paste image hint
+        // When unfocused, show insert hint and paste hint
         (
             Line::from(vec![
                 Span::styled("i", key_style),
                 Span::styled("nsert  ", desc_style),
                 Span::styled("^V", key_style),
-                Span::styled(" img", desc_style),
+                Span::styled(" paste", desc_style),
             ]),
-            14u16,
+            16u16,
         )
     } else if is_insert_mode {
         // INSERT MODE hints
@@ -1017,12 +919,60 @@ fn render_input(frame: &mut Frame, area: Rect, app: &mut App) {
         height: 1,
     };
     frame.render_widget(Paragraph::new(hints), hints_area);
+
+    // Word and character counts at the bottom-left, when they fit beside the hints
+    if !text.trim().is_empty() {
+        let (words, chars) = crate::spell::counts(&text);
+        let counts = format!(" {} word{} · {} char{} ", words, if words == 1 { "" } else { "s" }, chars, if chars == 1 { "" } else { "s" });
+        let counts_width = counts.chars().count() as u16;
+        if counts_width + hints_width + 3 <= area.width {
+            let counts_area = Rect {
+                x: area.x + 1,
+                y: area.y + area.height.saturating_sub(1),
+                width: counts_width,
+                height: 1,
+            };
+            frame.render_widget(Paragraph::new(Span::styled(counts, desc_style)), counts_area);
+        }
+    }
+}
+
+/// Underline the misspelled words drawn in `area`. Words are read back from
+/// the rendered rows, so wrapping and scrolling line up; a word cut by the
+/// wrap is left alone.
+fn underline_misspellings(buf: &mut ratatui::buffer::Buffer, area: Rect, dictionary: &crate::spell::Dictionary) {
+    let style = Style::default().fg(Color::LightRed).add_modifier(Modifier::UNDERLINED);
+    let mut previous_row_full = false;
+    for y in area.top()..area.bottom() {
+        // Characters of the row with the column each is drawn at
+        let cells: Vec<(u16, char)> = (area.left()..area.right())
+            .filter_map(|x| buf[(x, y)].symbol().chars().next().map(|ch| (x, ch)))
+            .collect();
+        let row: String = cells.iter().map(|(_, ch)| ch).collect();
+        let row_full = cells.last().is_some_and(|(x, ch)| *x + 1 == area.right() && !ch.is_whitespace());
+        for (start, len) in crate::spell::misspelled(&row, dictionary) {
+            let wrapped_in = start == 0 && previous_row_full;
+            let wraps_out = start + len == cells.len() && row_full;
+            if wrapped_in || wraps_out {
+                continue;
+            }
+            for (x, _) in &cells[start..start + len] {
+                buf[(*x, y)].set_style(style);
+            }
+        }
+        previous_row_full = row_full;
+    }
 }
 
 /// Render the task preview modal (shown with v/space/enter)
 /// Phase-aware modal showing contextual information and available actions
+/// Screen area of the task preview modal (shared with mouse hit-testing)
+pub fn task_preview_modal_area(screen: Rect) -> Rect {
+    centered_rect(75, 80, screen)
+}
+
 fn render_task_preview_modal(frame: &mut Frame, app: &App) {
-    let area = centered_rect(75, 80, frame.area());
+    let area = task_preview_modal_area(frame.area());
 
     // Calculate available content height for tabs
     // Modal area minus: 2 (borders) + 2 (padding) + 2 (tab bar + empty line)
@@ -1084,6 +1034,9 @@ fn render_task_preview_modal(frame: &mut Frame, app: &App) {
         crate::model::TaskDetailTab::Activity => {
             render_activity_tab(&mut lines, task, &app.model.ui_state, &label_style, &value_style, &dim_style, content_height);
         }
+        crate::model::TaskDetailTab::Queue => {
+            render_queue_tab(&mut lines, task, &app.model.ui_state, &key_style, &dim_style);
+        }
         crate::model::TaskDetailTab::Help => {
             render_help_tab(&mut lines, task, &key_style, &label_style, &dim_style);
         }
@@ -1146,6 +1099,67 @@ fn render_task_detail_tab_bar(current_tab: crate::model::TaskDetailTab) -> Line<
 }
 
 /// Render the General tab content
+/// Tracked time with its latest entries, and the manual entry input (t / T)
+fn render_time_tracked<'a>(
+    lines: &mut Vec<Line<'a>>,
+    task: &crate::model::Task,
+    app: &App,
+    label_style: &Style,
+    value_style: &Style,
+    dim_style: &Style,
+) {
+    use crate::model::time_tracking::{format_tracked, total_secs, TimeSource};
+
+    /// Entries listed below the total
+    const SHOWN_ENTRIES: usize = 4;
+
+    let input = app.model.ui_state.time_entry_input.as_ref().filter(|e| e.task_id == task.id);
+    if task.time_entries.is_empty() && input.is_none() {
+        return;
+    }
+
+    let breakdown: Vec<String> = [TimeSource::Session, TimeSource::Focus, TimeSource::Manual]
+        .into_iter()
+        .map(|source| (source, total_secs(&task.time_entries, Some(source))))
+        .filter(|(_, secs)| *secs > 0)
+        .map(|(source, secs)| format!("{} {}", source.label(), format_tracked(secs)))
+        .collect();
+    let mut total = vec![
+        Span::styled("Tracked: ", *label_style),
+        Span::styled(format_tracked(task.tracked_secs()), Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+    ];
+    if !breakdown.is_empty() {
+        total.push(Span::styled(format!(" ({})", breakdown.join(", ")), *dim_style));
+    }
+    lines.push(Line::from(total));
+
+    let skipped = task.time_entries.len().saturating_sub(SHOWN_ENTRIES);
+    for entry in &task.time_entries[skipped..] {
+        let mut spans = vec![
+            Span::styled(format!("  {} ", format_datetime(entry.started_at)), *dim_style),
+            Span::styled(format!("{:>6}", format_tracked(entry.secs)), *value_style),
+            Span::styled(format!(" {}", entry.source.label()), *dim_style),
+        ];
+        if let Some(ref note) = entry.note {
+            spans.push(Span::styled(format!("  {}", note), *value_style));
+        }
+        lines.push(Line::from(spans));
+    }
+
+    if let Some(input) = input {
+        let label = if input.editing.is_some() { "Edit time: " } else { "Add time: " };
+        lines.push(Line::from(vec![
+            Span::styled("  ⏱ ", Style::default().fg(Color::LightBlue)),
+            Span::styled(label, Style::default().fg(Color::LightBlue).add_modifier(Modifier::BOLD)),
+            Span::styled(format!("{}_", input.input), Style::default().fg(Color::White)),
+        ]));
+        lines.push(Line::from(Span::styled(
+            "    e.g. 1h30m pairing on the API · Enter save · Esc cancel",
+            *dim_style,
+        )));
+    }
+}
+
 fn render_general_tab<'a>(
     lines: &mut Vec<Line<'a>>,
     task: &crate::model::Task,
@@ -1154,8 +1168,13 @@ fn render_general_tab<'a>(
     value_style: &Style,
     dim_style: &Style,
 ) {
+    let markdown_preview = app.model.ui_state.markdown_preview;
+
     // Title (full if short_title exists)
-    if task.short_title.is_some() {
+    if task.short_title.is_some() && markdown_preview {
+        lines.extend(markdown::to_lines(&task.title, Style::default().fg(Color::White)));
+        lines.push(Line::from(""));
+    } else if task.short_title.is_some() {
         let title_style = Style::default().fg(Color::White);
         for title_line in task.title.lines() {
             if ultrathink::contains_ultrathink(title_line) {
@@ -1168,7 +1187,10 @@ fn render_general_tab<'a>(
     }
 
     // Description
-    if !task.description.is_empty() {
+    if !task.description.is_empty() && markdown_preview {
+        lines.extend(markdown::to_lines(&task.description, Style::default().fg(Color::Gray)));
+        lines.push(Line::from(""));
+    } else if !task.description.is_empty() {
         let desc_style = Style::default().fg(Color::Gray);
         for desc_line in task.description.lines() {
             if ultrathink::contains_ultrathink(desc_line) {
@@ -1236,6 +1258,26 @@ fn render_general_tab<'a>(
         }
     }
 
+    // Attached files (stored copies; the original name follows the timestamp prefix)
+    if !task.attachments.is_empty() {
+        lines.push(Line::from(vec![
+            Span::styled("📎 ", *dim_style),
+            Span::styled(format!("{} file(s) attached", task.attachments.len()), Style::default().fg(Color::Cyan)),
+        ]));
+        for path in &task.attachments {
+            let exists = path.is_file();
+            lines.push(Line::from(vec![
+                Span::styled("  ", *dim_style),
+                Span::styled(
+                    path.display().to_string(),
+                    if exists { *value_style } else { Style::default().fg(Color::Red) },
+                ),
+                Span::styled(if exists { "" } else { " (missing)" }, Style::default().fg(Color::Red)),
+            ]));
+        }
+        lines.push(Line::from(""));
+    }
+
     // Phase-specific timing info
     lines.push(Line::from(Span::styled("─".repeat(40), *dim_style)));
 
@@ -1361,6 +1403,10 @@ fn render_general_tab<'a>(
         }
     }
 
+    render_time_tracked(lines, task, app, label_style, value_style, dim_style);
+
+    render_timeline_strip(lines, task, label_style, dim_style);
+
     // Worktree path
     if let Some(ref wt_path) = task.worktree_path {
         lines.push(Line::from(""));
@@ -1369,6 +1415,84 @@ fn render_general_tab<'a>(
             Span::styled(wt_path.display().to_string(), *dim_style),
         ]));
     }
+
+    // Preview deployment
+    if task.preview_deploying {
+        lines.push(Line::from(vec![
+            Span::styled("Preview: ", *label_style),
+            Span::styled("deploying...", Style::default().fg(Color::Yellow)),
+        ]));
+    } else if let Some(ref url) = task.preview_url {
+        lines.push(Line::from(vec![
+            Span::styled("Preview: ", *label_style),
+            Span::styled(url.clone(), Style::default().fg(Color::Cyan)),
+            Span::styled("  y copy URL · b redeploy", *dim_style),
+        ]));
+    }
+}
+
+/// Width of the General tab's timeline strip, matching its separator
+const TIMELINE_WIDTH: usize = 40;
+
+/// Render the task's phases as a proportional strip with a duration legend
+fn render_timeline_strip<'a>(
+    lines: &mut Vec<Line<'a>>,
+    task: &crate::model::Task,
+    label_style: &Style,
+    dim_style: &Style,
+) {
+    use crate::model::TaskStatus;
+
+    let phases = task.timeline(chrono::Utc::now());
+    if phases.is_empty() {
+        return;
+    }
+    let color = |status: TaskStatus| match status {
+        TaskStatus::Planned => Color::Blue,
+        TaskStatus::InProgress => Color::Yellow,
+        _ => Color::Magenta,
+    };
+    let secs: Vec<i64> = phases.iter()
+        .map(|(_, start, end)| end.signed_duration_since(*start).num_seconds())
+        .collect();
+    let total = secs.iter().sum::<i64>().max(1);
+
+    // Every phase gets at least one cell; the longest absorbs rounding
+    let mut widths: Vec<usize> = secs.iter()
+        .map(|s| ((*s as f64 / total as f64) * TIMELINE_WIDTH as f64).round().max(1.0) as usize)
+        .collect();
+    let used: usize = widths.iter().sum();
+    if let Some(longest) = (0..widths.len()).max_by_key(|&i| secs[i]) {
+        widths[longest] = (widths[longest] + TIMELINE_WIDTH).saturating_sub(used).max(1);
+    }
+
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled("Timeline", *label_style)));
+    let mut strip: Vec<Span> = phases.iter().zip(&widths)
+        .map(|((status, _, _), width)| Span::styled("█".repeat(*width), Style::default().fg(color(*status))))
+        .collect();
+    if task.status == TaskStatus::Done {
+        strip.push(Span::styled(" ✓", Style::default().fg(Color::Green)));
+    }
+    lines.push(Line::from(strip));
+
+    let mut legend: Vec<Span> = Vec::new();
+    for (i, (status, start, end)) in phases.iter().enumerate() {
+        if i > 0 {
+            legend.push(Span::styled(" → ", *dim_style));
+        }
+        let label = match status {
+            TaskStatus::InProgress => "In Progress",
+            TaskStatus::Review => "Review",
+            _ => "Planned",
+        };
+        legend.push(Span::styled(label, Style::default().fg(color(*status))));
+        legend.push(Span::styled(
+            format!(" {}", format_duration(end.signed_duration_since(*start))),
+            *dim_style,
+        ));
+    }
+    lines.push(Line::from(legend));
 }
 
 /// Render the Spec tab content with scrolling and subtle scrollbar
@@ -1384,7 +1508,9 @@ fn render_spec_tab<'a>(
 ) {
     if let Some(ref spec) = task.spec {
         let spec_lines: Vec<&str> = spec.lines().collect();
-        let total_lines = spec_lines.len();
+        let rendered = app.model.ui_state.markdown_preview
+            .then(|| markdown::to_lines(spec, Style::default().fg(Color::White)));
+        let total_lines = rendered.as_ref().map_or(spec_lines.len(), Vec::len);
         let scroll_offset = app.model.ui_state.spec_scroll_offset;
         // Calculate visible lines: content_height minus header overhead (hints, position indicator, empty lines, "more below")
         // Header takes ~4-5 lines when scrollable, content area gets the rest minus 2 for "more below" indicator
@@ -1406,7 +1532,9 @@ fn render_spec_tab<'a>(
                 Span::styled("G", *key_style),
                 Span::styled(" jump  ", *dim_style),
                 Span::styled("Ctrl-G", *key_style),
-                Span::styled(" edit", *dim_style),
+                Span::styled(" edit  ", *dim_style),
+                Span::styled("P", *key_style),
+                Span::styled(" preview", *dim_style),
             ]));
 
             // Show scroll position indicator
@@ -1431,56 +1559,63 @@ fn render_spec_tab<'a>(
             // Show edit hint even when content isn't scrollable
             lines.push(Line::from(vec![
                 Span::styled("Ctrl-G", *key_style),
-                Span::styled(" edit", *dim_style),
+                Span::styled(" edit  ", *dim_style),
+                Span::styled("P", *key_style),
+                Span::styled(" preview", *dim_style),
             ]));
             lines.push(Line::from(""));
         }
 
-        // Render visible spec lines with simple markdown styling
-        for line in spec_lines.iter().skip(scroll_offset).take(visible_lines) {
-            let styled_line = if line.starts_with("> ") {
-                // Blockquote - important instruction in yellow/bold
-                let content = &line[2..];
-                let blockquote_style = Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD);
-                let mut spans = vec![Span::styled("│ ", Style::default().fg(Color::Yellow))];
-                // Check for ultrathink in blockquote content
-                if ultrathink::contains_ultrathink(content) {
-                    spans.extend(ultrathink::style_line_with_ultrathink(content, blockquote_style));
-                } else {
-                    spans.push(Span::styled(content.to_string(), blockquote_style));
-                }
-                Line::from(spans)
-            } else if line.starts_with("## ") {
-                // Section headers in cyan bold
-                let header_style = Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD);
-                if ultrathink::contains_ultrathink(line) {
-                    Line::from(ultrathink::style_line_with_ultrathink(line, header_style))
-                } else {
-                    Line::from(Span::styled(line.to_string(), header_style))
-                }
-            } else if line.starts_with("- ") || line.starts_with("* ") {
-                // Bullet points with green bullet
-                let content = &line[2..];
-                let mut spans = vec![Span::styled("• ", Style::default().fg(Color::Green))];
-                // Check for ultrathink in bullet content
-                if ultrathink::contains_ultrathink(content) {
-                    spans.extend(ultrathink::style_line_with_ultrathink(content, Style::default().fg(Color::White)));
-                } else {
-                    spans.push(Span::styled(content.to_string(), Style::default().fg(Color::White)));
-                }
-                Line::from(spans)
-            } else if line.trim().is_empty() {
-                Line::from("")
-            } else {
-                // Regular text - check for ultrathink
-                let text_style = Style::default().fg(Color::White);
-                if ultrathink::contains_ultrathink(line) {
-                    Line::from(ultrathink::style_line_with_ultrathink(line, text_style))
+        // Render visible spec lines, fully rendered in the markdown preview
+        // and with simple markdown styling otherwise
+        if let Some(rendered) = rendered {
+            lines.extend(rendered.into_iter().skip(scroll_offset).take(visible_lines));
+        } else {
+            for line in spec_lines.iter().skip(scroll_offset).take(visible_lines) {
+                let styled_line = if line.starts_with("> ") {
+                    // Blockquote - important instruction in yellow/bold
+                    let content = &line[2..];
+                    let blockquote_style = Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD);
+                    let mut spans = vec![Span::styled("│ ", Style::default().fg(Color::Yellow))];
+                    // Check for ultrathink in blockquote content
+                    if ultrathink::contains_ultrathink(content) {
+                        spans.extend(ultrathink::style_line_with_ultrathink(content, blockquote_style));
+                    } else {
+                        spans.push(Span::styled(content.to_string(), blockquote_style));
+                    }
+                    Line::from(spans)
+                } else if line.starts_with("## ") {
+                    // Section headers in cyan bold
+                    let header_style = Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD);
+                    if ultrathink::contains_ultrathink(line) {
+                        Line::from(ultrathink::style_line_with_ultrathink(line, header_style))
+                    } else {
+                        Line::from(Span::styled(line.to_string(), header_style))
+                    }
+                } else if line.starts_with("- ") || line.starts_with("* ") {
+                    // Bullet points with green bullet
+                    let content = &line[2..];
+                    let mut spans = vec![Span::styled("• ", Style::default().fg(Color::Green))];
+                    // Check for ultrathink in bullet content
+                    if ultrathink::contains_ultrathink(content) {
+                        spans.extend(ultrathink::style_line_with_ultrathink(content, Style::default().fg(Color::White)));
+                    } else {
+                        spans.push(Span::styled(content.to_string(), Style::default().fg(Color::White)));
+                    }
+                    Line::from(spans)
+                } else if line.trim().is_empty() {
+                    Line::from("")
                 } else {
-                    Line::from(Span::styled(line.to_string(), text_style))
-                }
-            };
-            lines.push(styled_line);
+                    // Regular text - check for ultrathink
+                    let text_style = Style::default().fg(Color::White);
+                    if ultrathink::contains_ultrathink(line) {
+                        Line::from(ultrathink::style_line_with_ultrathink(line, text_style))
+                    } else {
+                        Line::from(Span::styled(line.to_string(), text_style))
+                    }
+                };
+                lines.push(styled_line);
+            }
         }
 
         // Show "more below" indicator with subtle scrollbar hint
@@ -1657,6 +1792,71 @@ fn render_git_tab<'a>(
         ]));
     }
 
+    if let Some(first) = task.ports {
+        lines.push(Line::from(vec![
+            Span::styled("Ports: ", *label_style),
+            Span::styled(crate::ports::url(first), Style::default().fg(Color::Cyan)),
+            Span::styled(
+                format!("  ({}-{} as $PORT, $KANBLAM_PORT_RANGE)", first, first + crate::ports::BLOCK_SIZE - 1),
+                *dim_style,
+            ),
+        ]));
+    }
+
+    // Variables the task's sessions and panes run with (literal values hidden)
+    if !task.env.is_empty() {
+        let vars: Vec<String> = task.env.iter()
+            .map(|(key, value)| format!("{}={}", key, crate::secrets::display_value(value)))
+            .collect();
+        lines.push(Line::from(vec![
+            Span::styled("Env: ", *label_style),
+            Span::styled(vars.join("  "), *dim_style),
+        ]));
+    }
+
+    // CI of the pushed branch, job by job
+    if let Some(ref ci) = task.ci_status {
+        let state_style = |state: crate::ci::CiState| match state {
+            crate::ci::CiState::Passed => Style::default().fg(Color::Green),
+            crate::ci::CiState::Failed => Style::default().fg(Color::Red),
+            crate::ci::CiState::NoChecks => *dim_style,
+            _ => Style::default().fg(Color::Yellow),
+        };
+        let mut spans = vec![
+            Span::styled("CI: ", *label_style),
+            Span::styled(format!("{} {}", ci.state.glyph(), ci.state.label()).trim().to_string(), state_style(ci.state)),
+            Span::styled(format!("  at {}", &ci.sha[..ci.sha.len().min(7)]), *dim_style),
+        ];
+        if let Some(ref pr) = ci.pr {
+            spans.push(Span::styled(format!("  PR #{} ({})", pr.number, pr.state.to_lowercase()), *dim_style));
+        }
+        lines.push(Line::from(spans));
+        const SHOWN_JOBS: usize = 6;
+        for (name, state) in ci.checks.iter().take(SHOWN_JOBS) {
+            lines.push(Line::from(vec![
+                Span::styled(format!("  {} ", state.glyph()), state_style(*state)),
+                Span::styled(name.clone(), *dim_style),
+            ]));
+        }
+        if ci.checks.len() > SHOWN_JOBS {
+            lines.push(Line::from(Span::styled(
+                format!("  … {} more (U opens the CI run)", ci.checks.len() - SHOWN_JOBS),
+                *dim_style,
+            )));
+        }
+    }
+
+    // Diff changed since the last time this tab was viewed
+    let diff_seen_before = app.model.ui_state.read_marker.as_ref()
+        .filter(|(id, _)| *id == task.id)
+        .map_or(task.read_state.diff_seen_at, |(_, read)| read.diff_seen_at);
+    if task.diff_changed_at.is_some_and(|changed| diff_seen_before.is_none_or(|seen| changed > seen)) {
+        lines.push(Line::from(Span::styled(
+            "● New changes since you last looked",
+            Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+        )));
+    }
+
     // Show line changes with visual bar (compact)
     let total_changes = task.git_additions + task.git_deletions;
     if total_changes > 0 {
@@ -1676,6 +1876,10 @@ fn render_git_tab<'a>(
             Span::styled(add_bar, Style::default().fg(Color::Green)),
             Span::styled(del_bar, Style::default().fg(Color::Red)),
         ]));
+    } else if task.git_status_updated_at.is_none()
+        && app.model.active_project().is_some_and(|p| app.model.ui_state.probing_git.contains(&p.working_dir))
+    {
+        lines.push(Line::from(Span::styled("Checking for changes…", *dim_style)));
     } else {
         lines.push(Line::from(Span::styled("No changes yet", *dim_style)));
     }
@@ -1691,6 +1895,36 @@ fn render_git_tab<'a>(
         ]));
     }
 
+    // Change summary and risks from the sidecar (S), capped to leave room for the diff
+    if task.summarizing_changes {
+        lines.push(Line::from(Span::styled("Summarizing changes...", *dim_style)));
+    } else if let Some(ref summary) = task.change_summary {
+        let head = app.model.ui_state.git_diff_cache.current_head(task.id).unwrap_or_default();
+        let mut heading = vec![
+            Span::styled("Summary: ", *label_style),
+            Span::styled(
+                format!("{} at {}", summary.created_at.with_timezone(&chrono::Local).format("%b %d %H:%M"),
+                    &summary.head[..summary.head.len().min(7)]),
+                *dim_style,
+            ),
+        ];
+        if summary.is_stale(head) {
+            heading.push(Span::styled("  ⚠ branch moved since (S to refresh)", Style::default().fg(Color::Yellow)));
+        }
+        lines.push(Line::from(heading));
+        let shown = (content_height / 3).max(4);
+        let text: Vec<&str> = summary.text.lines().filter(|l| !l.trim().is_empty()).collect();
+        for line in text.iter().take(shown) {
+            lines.push(Line::from(Span::raw(format!("  {}", line))));
+        }
+        if text.len() > shown {
+            lines.push(Line::from(Span::styled(
+                format!("  … {} more lines", text.len() - shown),
+                *dim_style,
+            )));
+        }
+    }
+
     // Separator and scroll hint
     lines.push(Line::from(Span::styled("─".repeat(50), *dim_style)));
     lines.push(Line::from(vec![
@@ -1705,10 +1939,65 @@ fn render_git_tab<'a>(
         Span::styled("Home", *key_style),
         Span::styled("/", *dim_style),
         Span::styled("End", *key_style),
-        Span::styled(" jump", *dim_style),
+        Span::styled(" jump  ", *dim_style),
+        Span::styled("w", *key_style),
+        Span::styled(" snapshot  ", *dim_style),
+        Span::styled("v", *key_style),
+        Span::styled(if app.model.ui_state.git_history_view { " diff" } else { " history" }, *dim_style),
     ]));
+    if !app.model.ui_state.git_history_view {
+        let unsent = task.unsent_review_comments().count();
+        let mut spans = vec![
+            Span::styled("c", *key_style),
+            Span::styled(" comment on the top line  ", *dim_style),
+            Span::styled("S", *key_style),
+            Span::styled(if task.change_summary.is_some() { " re-summarize  " } else { " summarize  " }, *dim_style),
+        ];
+        if unsent > 0 {
+            spans.push(Span::styled("F", *key_style));
+            spans.push(Span::styled(
+                format!(" send {} comment{} to Claude", unsent, if unsent == 1 { "" } else { "s" }),
+                *dim_style,
+            ));
+        }
+        lines.push(Line::from(spans));
+    }
+
+    // Comment being typed (c), kept above the diff so scrolling can't hide it
+    if let Some(comment) = app.model.ui_state.diff_comment.as_ref().filter(|c| c.task_id == task.id) {
+        let anchor = if comment.location.removed { "removed line" } else { "line" };
+        lines.push(Line::from(vec![
+            Span::styled("✎ ", Style::default().fg(Color::LightBlue)),
+            Span::styled(
+                format!("{} {} {}: ", comment.location.path, anchor, comment.location.line),
+                Style::default().fg(Color::LightBlue).add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(format!("{}_", comment.input), Style::default().fg(Color::White)),
+        ]));
+        lines.push(Line::from(Span::styled(
+            "  Enter save (empty removes it) · Esc cancel",
+            *dim_style,
+        )));
+    }
     lines.push(Line::from(""));
 
+    if app.model.ui_state.git_history_view {
+        let height = content_height.saturating_sub(lines.len());
+        match &app.model.ui_state.git_history {
+            Some((id, Some(Ok(history)))) if *id == task.id => {
+                render_git_history(lines, history, app.model.ui_state.git_diff_scroll_offset, dim_style, height);
+            }
+            Some((id, Some(Err(error)))) if *id == task.id => {
+                lines.push(Line::from(Span::styled(
+                    format!("Error reading history: {}", error),
+                    Style::default().fg(Color::Red),
+                )));
+            }
+            _ => lines.push(Line::from(Span::styled("Loading history...", *dim_style))),
+        }
+        return;
+    }
+
     // Get git diff from cache or show loading message
     let scroll_offset = app.model.ui_state.git_diff_scroll_offset;
 
@@ -1718,28 +2007,106 @@ fn render_git_tab<'a>(
     let header_lines = lines.len();
     let diff_content_height = content_height.saturating_sub(header_lines);
 
-    if let Some((cached_task_id, ref diff_content)) = app.model.ui_state.git_diff_cache {
-        if cached_task_id == task.id {
-            // Parse and render the diff with colors
-            render_git_diff_content(lines, diff_content, scroll_offset, dim_style, diff_content_height);
-        } else {
-            lines.push(Line::from(Span::styled("Loading diff...", *dim_style)));
-        }
+    let cache = &app.model.ui_state.git_diff_cache;
+    if let Some(diff) = cache.current(task.id) {
+        // Render the visible window of the diff with colors
+        render_git_diff_content(lines, diff, &task.review_comments, scroll_offset, dim_style, diff_content_height);
+    } else if let Some(error) = cache.error(task.id) {
+        lines.push(Line::from(Span::styled(error.to_string(), Style::default().fg(Color::Red))));
     } else {
         lines.push(Line::from(Span::styled("Loading diff...", *dim_style)));
     }
 }
 
+/// Render main's history of the task's changed files: per file, whether
+/// someone else is changing it, who wrote it and its latest commits
+fn render_git_history<'a>(
+    lines: &mut Vec<Line<'a>>,
+    history: &crate::worktree::history::TaskHistory,
+    scroll_offset: usize,
+    dim_style: &Style,
+    content_height: usize,
+) {
+    if history.files.is_empty() {
+        lines.push(Line::from(Span::styled("No changed files", *dim_style)));
+        return;
+    }
+
+    let warn_style = Style::default().fg(Color::Yellow);
+    let mut rows: Vec<Line<'a>> = Vec::new();
+    let touched = history.files.iter().filter(|f| f.has_other_work()).count();
+    rows.push(if touched == 0 {
+        Line::from(Span::styled(
+            format!("No one else changed these files on {} since the task branched off", history.base),
+            Style::default().fg(Color::Green),
+        ))
+    } else {
+        Line::from(Span::styled(
+            format!("{} of {} files changed elsewhere since the task branched off", touched, history.files.len()),
+            warn_style.add_modifier(Modifier::BOLD),
+        ))
+    });
+    rows.push(Line::from(""));
+    for file in &history.files {
+        let mut heading = vec![Span::styled(
+            file.path.clone(),
+            Style::default().fg(Color::White).add_modifier(Modifier::BOLD),
+        )];
+        if file.since_branch > 0 {
+            heading.push(Span::styled(
+                format!("  ⚠ {} commit{} on {} since branching",
+                    file.since_branch, if file.since_branch == 1 { "" } else { "s" }, history.base),
+                warn_style,
+            ));
+        }
+        if file.uncommitted {
+            heading.push(Span::styled("  ⚠ uncommitted changes in the main checkout", warn_style));
+        }
+        rows.push(Line::from(heading));
+        rows.push(Line::from(vec![
+            Span::styled("  blame: ", *dim_style),
+            Span::raw(file.blame_summary()),
+        ]));
+        for commit in &file.commits {
+            let marker = if commit.since_branch { Span::styled("  ● ", warn_style) } else { Span::styled("    ", *dim_style) };
+            rows.push(Line::from(vec![
+                marker,
+                Span::styled(format!("{} ", commit.sha), Style::default().fg(Color::Yellow)),
+                Span::raw(commit.subject.clone()),
+                Span::styled(format!("  {}, {}", commit.author, commit.when), *dim_style),
+            ]));
+        }
+        rows.push(Line::from(""));
+    }
+    if history.more_files > 0 {
+        rows.push(Line::from(Span::styled(
+            format!("... and {} more changed files", history.more_files),
+            *dim_style,
+        )));
+    }
+
+    let visible = content_height.max(5);
+    let total = rows.len();
+    lines.extend(rows.into_iter().skip(scroll_offset).take(visible));
+    let remaining = total.saturating_sub(scroll_offset + visible);
+    if remaining > 0 {
+        lines.push(Line::from(Span::styled(
+            format!("... {} more lines below ...", remaining),
+            *dim_style,
+        )));
+    }
+}
+
 /// Parse and render git diff content with syntax highlighting
 fn render_git_diff_content<'a>(
     lines: &mut Vec<Line<'a>>,
-    diff_content: &str,
+    diff: &crate::worktree::diff_cache::CachedDiff,
+    comments: &[crate::model::ReviewComment],
     scroll_offset: usize,
     dim_style: &Style,
     content_height: usize,
 ) {
-    let diff_lines: Vec<&str> = diff_content.lines().collect();
-    let total_lines = diff_lines.len();
+    let total_lines = diff.total_lines();
 
     if total_lines == 0 {
         lines.push(Line::from(Span::styled("No diff content", *dim_style)));
@@ -1771,10 +2138,29 @@ fn render_git_diff_content<'a>(
         lines.push(Line::from(""));
     }
 
-    // Render visible diff lines with colors
-    for line in diff_lines.iter().skip(scroll_offset).take(visible_lines) {
-        let styled_line = style_diff_line(line);
+    // Render visible diff lines with colors, the top one (where comments
+    // attach) highlighted, each followed by its review comments
+    let locations = diff.locations(scroll_offset, visible_lines);
+    for (idx, line) in diff.lines(scroll_offset, visible_lines).enumerate() {
+        let mut styled_line = style_diff_line(line);
+        if idx == 0 {
+            styled_line = styled_line.patch_style(Style::default().bg(Color::Rgb(40, 40, 60)));
+        }
         lines.push(styled_line);
+        let Some(Some(location)) = locations.get(idx) else {
+            continue;
+        };
+        for comment in comments.iter().filter(|c| c.is_at(location)) {
+            let (marker, style) = match (comment.sent_at, comment.resolved_at) {
+                (_, Some(_)) => ("  ✓ ", *dim_style),
+                (Some(_), None) => ("  ➤ ", Style::default().fg(Color::Cyan)),
+                (None, None) => ("  ✎ ", Style::default().fg(Color::Yellow)),
+            };
+            lines.push(Line::from(vec![
+                Span::styled(marker, style),
+                Span::styled(comment.text.clone(), style.add_modifier(Modifier::ITALIC)),
+            ]));
+        }
     }
 
     // Show "more below" indicator if there's more content
@@ -1785,7 +2171,47 @@ fn render_git_diff_content<'a>(
             format!("... {} more lines below ...", remaining),
             *dim_style,
         )));
+    } else if diff.truncated_lines > 0 {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            format!("... {} more lines not shown (diff too large) ...", diff.truncated_lines),
+            Style::default().fg(Color::Yellow),
+        )));
+    }
+}
+
+/// Totals, changed files (up to a third of `height`) and then as much of the
+/// diff as fits
+fn diff_overview_lines(diff: &crate::worktree::diff_cache::CachedDiff, width: usize, height: usize) -> Vec<Line<'static>> {
+    let dim = Style::default().fg(Color::DarkGray);
+    let stats = diff.file_stats();
+    let additions: usize = stats.iter().map(|(_, a, _)| a).sum();
+    let deletions: usize = stats.iter().map(|(_, _, d)| d).sum();
+    let mut lines = vec![Line::from(vec![
+        Span::styled(format!("+{}", additions), Style::default().fg(Color::Green)),
+        Span::styled("/", dim),
+        Span::styled(format!("-{}", deletions), Style::default().fg(Color::Red)),
+        Span::styled(format!(" in {} files", stats.len()), dim),
+    ])];
+
+    // Changed files get up to a third of the height, the diff the rest
+    let file_rows = (height / 3).max(1);
+    let shown = if stats.len() > file_rows { file_rows - 1 } else { stats.len() };
+    for (path, added, removed) in &stats[..shown] {
+        lines.push(Line::from(vec![
+            Span::styled(format!("+{:<4}", added), Style::default().fg(Color::Green)),
+            Span::styled(format!("-{:<4} ", removed), Style::default().fg(Color::Red)),
+            Span::raw(path.clone()),
+        ]));
     }
+    if stats.len() > shown {
+        lines.push(Line::from(Span::styled(format!("… and {} more files", stats.len() - shown), dim)));
+    }
+    lines.push(Line::from(Span::styled("─".repeat(width), dim)));
+
+    let room = height.saturating_sub(lines.len());
+    lines.extend(diff.lines(0, room).map(style_diff_line));
+    lines
 }
 
 /// Style a single diff line with appropriate colors
@@ -1852,6 +2278,44 @@ fn style_diff_line(line: &str) -> Line<'static> {
     ))
 }
 
+/// Bar of the session's context window use, warning as it fills up
+fn context_usage_line(task: &crate::model::Task) -> Line<'static> {
+    const BAR_WIDTH: usize = 20;
+    let usage = task.context_usage();
+    let filled = ((usage * BAR_WIDTH as f64).round() as usize).min(BAR_WIDTH);
+    let color = if usage >= 0.85 {
+        Color::Red
+    } else if usage >= 0.6 {
+        Color::Yellow
+    } else {
+        Color::Green
+    };
+
+    let mut spans = vec![
+        Span::styled("  🧠 ", Style::default().fg(color)),
+        Span::styled("█".repeat(filled), Style::default().fg(color)),
+        Span::styled("░".repeat(BAR_WIDTH - filled), Style::default().fg(Color::DarkGray)),
+        Span::styled(
+            format!(" {}k / {}k ({:.0}%)", task.context_tokens / 1000, crate::model::CONTEXT_WINDOW_TOKENS / 1000, usage * 100.0),
+            Style::default().fg(color),
+        ),
+    ];
+    if task.context_compactions > 0 {
+        spans.push(Span::styled(" │ ", Style::default().fg(Color::DarkGray)));
+        spans.push(Span::styled(
+            format!("{} compaction{}", task.context_compactions, if task.context_compactions == 1 { "" } else { "s" }),
+            Style::default().fg(Color::Yellow),
+        ));
+    }
+    if usage >= 0.85 {
+        spans.push(Span::styled(" │ nearly full, consider a fresh session", Style::default().fg(Color::Red)));
+    }
+    Line::from(spans)
+}
+
+/// Scratch shell commands listed in the Activity tab
+const RECENT_SHELL_COMMANDS: usize = 5;
+
 /// Render the Activity tab content (session info + activity log with full output)
 fn render_activity_tab<'a>(
     lines: &mut Vec<Line<'a>>,
@@ -1945,10 +2409,49 @@ fn render_activity_tab<'a>(
         ]));
     }
 
+    // Context window usage (SDK sessions report it after each turn)
+    if task.context_tokens > 0 || task.context_compactions > 0 {
+        lines.push(context_usage_line(task));
+    }
+
+    // Latest commands from the task's scratch shell
+    let shell_commands = crate::tmux::shell::recent_commands(&task.display_id(), RECENT_SHELL_COMMANDS);
+    if !shell_commands.is_empty() {
+        lines.push(Line::from(""));
+        lines.push(Line::from(vec![
+            Span::styled("  $ ", Style::default().fg(Color::Green)),
+            Span::styled("Shell", Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)),
+        ]));
+        for command in shell_commands {
+            // History is arbitrary text, so cut on characters rather than bytes
+            let command = if command.chars().count() > 60 {
+                format!("{}...", command.chars().take(57).collect::<String>())
+            } else {
+                command
+            };
+            lines.push(Line::from(Span::styled(
+                format!("    {}", command),
+                Style::default().fg(Color::Gray),
+            )));
+        }
+    }
+
+    // Note being typed (n), kept above the log so scrolling can't hide it
+    if let Some(note) = ui_state.activity_note.as_ref().filter(|n| n.task_id == task.id) {
+        lines.push(Line::from(""));
+        lines.push(Line::from(vec![
+            Span::styled("  ✎ ", Style::default().fg(Color::LightBlue)),
+            Span::styled("Note: ", Style::default().fg(Color::LightBlue).add_modifier(Modifier::BOLD)),
+            Span::styled(format!("{}_", note.input), Style::default().fg(Color::White)),
+        ]));
+        lines.push(Line::from(Span::styled("    Enter save · Esc cancel", Style::default().fg(Color::DarkGray))));
+    }
+
     lines.push(Line::from(""));
     lines.push(Line::from(Span::styled("─────────────────────────────────────────────", Style::default().fg(Color::DarkGray))));
 
-    if task.activity_log.is_empty() {
+    let timeline = task.activity_timeline();
+    if timeline.is_empty() {
         lines.push(Line::from(""));
         lines.push(Line::from(vec![
             Span::styled("  ", *dim_style),
@@ -1960,6 +2463,12 @@ fn render_activity_tab<'a>(
         lines.push(Line::from(Span::styled("    • Tool invocations", Style::default().fg(Color::DarkGray))));
         lines.push(Line::from(Span::styled("    • Status changes", Style::default().fg(Color::DarkGray))));
         lines.push(Line::from(Span::styled("    • Session output", Style::default().fg(Color::DarkGray))));
+        lines.push(Line::from(""));
+        lines.push(Line::from(vec![
+            Span::styled("  ", *dim_style),
+            Span::styled("n", Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+            Span::styled(" add a note", Style::default().fg(Color::DarkGray)),
+        ]));
     } else {
         // Timeline view with entries
         let scroll_offset = ui_state.activity_scroll_offset;
@@ -1970,7 +2479,7 @@ fn render_activity_tab<'a>(
         // Each entry takes ~1 line (plus expanded content if expanded), plus scroll indicators (2)
         let header_lines = lines.len(); // Use actual count of lines added so far
         let visible_count = content_height.saturating_sub(header_lines + 2).max(5); // +2 for scroll indicators
-        let total_entries = task.activity_log.len();
+        let total_entries = timeline.len();
         let visible_count = visible_count.min(total_entries);
         let start_idx = scroll_offset.min(total_entries.saturating_sub(visible_count));
         let end_idx = (start_idx + visible_count).min(total_entries);
@@ -1984,7 +2493,12 @@ fn render_activity_tab<'a>(
             ]));
         }
 
-        for (display_idx, entry) in task.activity_log.iter().enumerate().skip(start_idx).take(end_idx - start_idx) {
+        // Read position from before the modal was opened (this view marks everything read)
+        let seen_before = ui_state.read_marker.as_ref()
+            .filter(|(id, _)| *id == task.id)
+            .map_or(task.read_state.activity_seen_at, |(_, read)| read.activity_seen_at);
+
+        for (display_idx, entry) in timeline.iter().enumerate().skip(start_idx).take(end_idx - start_idx) {
             let elapsed = chrono::Utc::now().signed_duration_since(entry.timestamp);
             let time_ago = if elapsed.num_seconds() < 5 {
                 "now".to_string()
@@ -1998,32 +2512,7 @@ fn render_activity_tab<'a>(
                 format!("{}d", elapsed.num_hours() / 24)
             };
 
-            // Categorize with enhanced visual style
-            let (icon, msg_color, is_major) = if entry.message.starts_with("Using ") || entry.message.starts_with("Tool:") {
-                ("🔧", Color::Cyan, false)
-            } else if entry.message.contains("started") || entry.message.contains("Starting") {
-                ("▶", Color::Green, true)
-            } else if entry.message.contains("stopped") {
-                ("⏹", Color::Yellow, true)
-            } else if entry.message.contains("ended") || entry.message.contains("Ended") {
-                ("⏹", Color::DarkGray, true)
-            } else if entry.message.contains("Waiting") || entry.message.contains("input") {
-                ("⏸", Color::Yellow, true)
-            } else if entry.message.contains("Working") {
-                ("⚙", Color::Green, false)
-            } else if entry.message.contains("feedback") || entry.message.contains("Feedback") {
-                ("💬", Color::Magenta, true)
-            } else if entry.message.contains("merge") || entry.message.contains("Merge") || entry.message.contains("Rebasing") {
-                ("🔀", Color::Magenta, true)
-            } else if entry.message.contains("error") || entry.message.contains("failed") || entry.message.contains("cancelled") {
-                ("✗", Color::Red, true)
-            } else if entry.message.contains("success") || entry.message.contains("complete") || entry.message.contains("PASS") {
-                ("✓", Color::Green, true)
-            } else if entry.message.contains("FAIL") {
-                ("✗", Color::Red, true)
-            } else {
-                ("•", Color::White, false)
-            };
+            let (icon, msg_color, is_major) = activity_icon(entry);
 
             // Timeline connector
             let is_last = display_idx == total_entries.saturating_sub(1);
@@ -2059,8 +2548,24 @@ fn render_activity_tab<'a>(
                 spans.push(Span::styled(output_hint, Style::default().fg(Color::Cyan).add_modifier(Modifier::DIM)));
             }
 
+            // Logged since the last time this tab was viewed (your own notes aren't news)
+            if !entry.note && seen_before.is_none_or(|seen| entry.timestamp > seen) {
+                spans.push(Span::styled(" new", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)));
+            }
+
             lines.push(Line::from(spans));
 
+            // Expanded notes show their full text
+            if is_expanded && entry.note {
+                let chars: Vec<char> = entry.message.chars().collect();
+                for chunk in chars.chunks(50) {
+                    lines.push(Line::from(vec![
+                        Span::styled("       ", *dim_style),
+                        Span::styled(chunk.iter().collect::<String>(), Style::default().fg(Color::LightBlue)),
+                    ]));
+                }
+            }
+
             // Show expanded output preview
             if is_expanded && has_output {
                 if let Some(ref output) = entry.full_output {
@@ -2111,39 +2616,276 @@ fn render_activity_tab<'a>(
 
         // Navigation hint at bottom
         lines.push(Line::from(""));
-        if entries_with_output > 0 {
-            lines.push(Line::from(vec![
-                Span::styled("  ", *dim_style),
-                Span::styled("j/k", Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
-                Span::styled(" scroll  ", Style::default().fg(Color::DarkGray)),
-                Span::styled("Enter", Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
-                Span::styled(" expand output", Style::default().fg(Color::DarkGray)),
-            ]));
+        let mut hint = vec![
+            Span::styled("  ", *dim_style),
+            Span::styled("j/k", Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+            Span::styled(" scroll  ", Style::default().fg(Color::DarkGray)),
+        ];
+        if entries_with_output > 0 || !task.activity_notes.is_empty() {
+            hint.push(Span::styled("Enter", Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)));
+            hint.push(Span::styled(" expand  ", Style::default().fg(Color::DarkGray)));
         }
+        hint.push(Span::styled("n", Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)));
+        hint.push(Span::styled(" add note  ", Style::default().fg(Color::DarkGray)));
+        hint.push(Span::styled("w", Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)));
+        hint.push(Span::styled(" export  ", Style::default().fg(Color::DarkGray)));
+        hint.push(Span::styled("p", Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)));
+        hint.push(Span::styled(" replay  ", Style::default().fg(Color::DarkGray)));
+        hint.push(Span::styled("c", Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)));
+        hint.push(Span::styled(" capture scrollback", Style::default().fg(Color::DarkGray)));
+        if !task.scrollback_captures.is_empty() {
+            hint.push(Span::styled("  v", Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)));
+            hint.push(Span::styled(" view it", Style::default().fg(Color::DarkGray)));
+        }
+        lines.push(Line::from(hint));
     }
 }
 
-/// Render the Help tab content (phase-specific actions)
-fn render_help_tab<'a>(
+/// Icon, color and whether an activity entry is a major event (bold)
+fn activity_icon(entry: &crate::model::ActivityLogEntry) -> (&'static str, Color, bool) {
+    if entry.note {
+        ("✎", Color::LightBlue, true)
+    } else if entry.kind == crate::model::ActivityKind::StatusChange {
+        ("⇄", Color::Magenta, true)
+    } else if entry.kind == crate::model::ActivityKind::ConflictResolution {
+        ("⚠", Color::LightRed, true)
+    } else if entry.kind == crate::model::ActivityKind::Error {
+        ("✗", Color::Red, true)
+    } else if entry.kind == crate::model::ActivityKind::Subagent {
+        ("⑂", Color::LightBlue, true)
+    } else if entry.message.starts_with("Using ") || entry.message.starts_with("Tool:") {
+        ("🔧", Color::Cyan, false)
+    } else if entry.message.starts_with("Context compacted") {
+        ("🗜", Color::Yellow, true)
+    } else if entry.message.starts_with("Transcri") {
+        ("🎙", Color::Magenta, true)
+    } else if entry.message.contains("started") || entry.message.contains("Starting") {
+        ("▶", Color::Green, true)
+    } else if entry.message.contains("stopped") {
+        ("⏹", Color::Yellow, true)
+    } else if entry.message.contains("ended") || entry.message.contains("Ended") {
+        ("⏹", Color::DarkGray, true)
+    } else if entry.message.contains("Waiting") || entry.message.contains("input") {
+        ("⏸", Color::Yellow, true)
+    } else if entry.message.contains("Working") {
+        ("⚙", Color::Green, false)
+    } else if entry.message.contains("feedback") || entry.message.contains("Feedback") {
+        ("💬", Color::Magenta, true)
+    } else if entry.message.contains("merge") || entry.message.contains("Merge") || entry.message.contains("Rebasing") {
+        ("🔀", Color::Magenta, true)
+    } else if entry.message.contains("error") || entry.message.contains("failed") || entry.message.contains("cancelled") {
+        ("✗", Color::Red, true)
+    } else if entry.message.contains("success") || entry.message.contains("complete") || entry.message.contains("PASS") {
+        ("✓", Color::Green, true)
+    } else if entry.message.contains("FAIL") {
+        ("✗", Color::Red, true)
+    } else {
+        ("•", Color::White, false)
+    }
+}
+
+/// "+42s", "+5m12s", "+2h03m" since the start of the replayed session
+fn format_replay_offset(offset: chrono::Duration) -> String {
+    let secs = offset.num_seconds().max(0);
+    if secs < 60 {
+        format!("+{}s", secs)
+    } else if secs < 3600 {
+        format!("+{}m{:02}s", secs / 60, secs % 60)
+    } else {
+        format!("+{}h{:02}m", secs / 3600, (secs % 3600) / 60)
+    }
+}
+
+/// Step-by-step replay of a task's session: a scrubber over the whole
+/// timeline, the current event, and the output it added
+fn render_session_replay(frame: &mut Frame, app: &App, replay: &crate::model::SessionReplayState) {
+    let Some(task) = app.model.projects.iter().flat_map(|p| p.tasks.iter()).find(|t| t.id == replay.task_id) else {
+        return;
+    };
+    let timeline = task.activity_timeline();
+    let (Some(first), Some(last)) = (timeline.first(), timeline.last()) else {
+        return;
+    };
+    let position = replay.position.min(timeline.len() - 1);
+    let entry = timeline[position];
+    let dim_style = Style::default().fg(Color::DarkGray);
+
+    let area = centered_rect(85, 85, frame.area());
+    frame.render_widget(ratatui::widgets::Clear, area);
+    let block = Block::default()
+        .title(format!(" Replay: {} ", truncate_string(&task.title, 50)))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // position + scrubber
+            Constraint::Length(3), // event
+            Constraint::Min(3),    // output
+            Constraint::Length(1), // keys
+        ])
+        .split(inner);
+
+    // Scrubber: every event at its point in time, the current one highlighted
+    let width = chunks[0].width.max(1) as usize;
+    let span_ms = (last.timestamp - first.timestamp).num_milliseconds().max(0);
+    let column = |idx: usize, e: &crate::model::ActivityLogEntry| -> usize {
+        if span_ms == 0 {
+            idx * (width - 1) / timeline.len().max(2).saturating_sub(1)
+        } else {
+            ((e.timestamp - first.timestamp).num_milliseconds() as i128 * (width as i128 - 1) / span_ms as i128) as usize
+        }
+    };
+    let mut track: Vec<(&str, Color)> = vec![("─", Color::DarkGray); width];
+    for (idx, e) in timeline.iter().enumerate() {
+        let (_, color, _) = activity_icon(e);
+        track[column(idx, e).min(width - 1)] = ("│", color);
+    }
+    track[column(position, entry).min(width - 1)] = ("●", Color::Yellow);
+
+    let header = vec![
+        Line::from(vec![
+            Span::styled(format!("Event {} of {}", position + 1, timeline.len()), Style::default().add_modifier(Modifier::BOLD)),
+            Span::styled(
+                format!(
+                    "  ·  {}  ·  {}",
+                    entry.timestamp.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M:%S"),
+                    format_replay_offset(entry.timestamp - first.timestamp),
+                ),
+                dim_style,
+            ),
+        ]),
+        Line::from(track.into_iter().map(|(c, color)| Span::styled(c, Style::default().fg(color))).collect::<Vec<_>>()),
+    ];
+    frame.render_widget(Paragraph::new(header), chunks[0]);
+
+    let (icon, color, _) = activity_icon(entry);
+    let kind = if entry.note { "Note" } else { entry.kind.label() };
+    let event = Paragraph::new(Line::from(vec![
+        Span::styled(format!("{} {}: ", icon, kind), Style::default().fg(color).add_modifier(Modifier::BOLD)),
+        Span::raw(entry.message.clone()),
+    ]))
+    .wrap(ratatui::widgets::Wrap { trim: false });
+    frame.render_widget(event, chunks[1]);
+
+    // Output this event added since the previous one
+    let output_block = Block::default()
+        .title(" Output added ")
+        .borders(Borders::TOP)
+        .border_style(dim_style);
+    let output_lines: Vec<Line> = match crate::model::activity::output_added(&timeline, position) {
+        Some(output) if !output.trim().is_empty() => output.trim_matches('\n').lines().map(|l| Line::from(l.to_string())).collect(),
+        _ => vec![Line::from(Span::styled("(no output with this event)", dim_style))],
+    };
+    let visible = chunks[2].height.saturating_sub(1) as usize;
+    let scroll = replay.output_scroll.min(output_lines.len().saturating_sub(visible));
+    frame.render_widget(
+        Paragraph::new(output_lines).block(output_block).scroll((scroll as u16, 0)),
+        chunks[2],
+    );
+
+    let key_style = Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD);
+    let keys = Line::from(vec![
+        Span::styled("h/l", key_style),
+        Span::styled(" step  ", dim_style),
+        Span::styled("H/L", key_style),
+        Span::styled(" ±10  ", dim_style),
+        Span::styled("g/G", key_style),
+        Span::styled(" first/last  ", dim_style),
+        Span::styled("j/k", key_style),
+        Span::styled(" scroll output  ", dim_style),
+        Span::styled("Esc", key_style),
+        Span::styled(" close", dim_style),
+    ]);
+    frame.render_widget(Paragraph::new(keys), chunks[3]);
+}
+
+/// Render the Queue tab: feedback waiting for Claude to finish its turn,
+/// delivered top to bottom, one message per turn
+fn render_queue_tab<'a>(
     lines: &mut Vec<Line<'a>>,
     task: &crate::model::Task,
+    ui_state: &crate::model::UiState,
     key_style: &Style,
-    label_style: &Style,
     dim_style: &Style,
 ) {
-    lines.push(Line::from(Span::styled("Available Actions", Style::default().fg(Color::White).add_modifier(Modifier::BOLD))));
+    if task.queued_feedback.is_empty() {
+        lines.push(Line::from(Span::styled("No queued feedback.", *dim_style)));
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            "Feedback sent while Claude is working waits here (w when asked).",
+            *dim_style,
+        )));
+        return;
+    }
+
+    lines.push(Line::from(vec![
+        Span::styled(format!("{} queued", task.queued_feedback.len()), Style::default().fg(Color::White).add_modifier(Modifier::BOLD)),
+        Span::styled(" · sent in order, one each time Claude finishes", *dim_style),
+    ]));
     lines.push(Line::from(""));
 
-    match task.status {
-        crate::model::TaskStatus::Planned => {
+    let selected = ui_state.queue_selected.min(task.queued_feedback.len() - 1);
+    let edit = ui_state.queue_edit.as_ref().filter(|e| e.task_id == task.id);
+    for (idx, message) in task.queued_feedback.iter().enumerate() {
+        let is_selected = idx == selected;
+        let marker = if is_selected { "▸ " } else { "  " };
+        let style = if is_selected {
+            Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(Color::White)
+        };
+        if let Some(edit) = edit.filter(|_| is_selected) {
             lines.push(Line::from(vec![
-                Span::styled(" s ", *key_style), Span::styled(" Start task with worktree isolation", *label_style),
+                Span::styled(format!("{}{}. ", marker, idx + 1), style),
+                Span::styled(format!("{}_", edit.input), Style::default().fg(Color::White)),
             ]));
+            continue;
+        }
+        for (line_idx, text) in message.lines().enumerate() {
+            let lead = if line_idx == 0 { format!("{}{}. ", marker, idx + 1) } else { "     ".to_string() };
             lines.push(Line::from(vec![
-                Span::styled(" q ", *key_style), Span::styled(" Queue for running session", *label_style),
+                Span::styled(lead, style),
+                Span::styled(text.to_string(), style),
             ]));
-            lines.push(Line::from(vec![
-                Span::styled(" e ", *key_style), Span::styled(" Edit task", *label_style),
+        }
+    }
+
+    lines.push(Line::from(""));
+    let hints: &[(&str, &str)] = if edit.is_some() {
+        &[("Enter", " save (empty drops it)  "), ("Esc", " cancel")]
+    } else {
+        &[("j/k", " select  "), ("J/K", " move  "), ("e", " edit  "), ("d", " delete")]
+    };
+    lines.push(Line::from(hints.iter().flat_map(|(key, label)| {
+        [Span::styled(*key, *key_style), Span::styled(*label, *dim_style)]
+    }).collect::<Vec<_>>()));
+}
+
+/// Render the Help tab content (phase-specific actions)
+fn render_help_tab<'a>(
+    lines: &mut Vec<Line<'a>>,
+    task: &crate::model::Task,
+    key_style: &Style,
+    label_style: &Style,
+    dim_style: &Style,
+) {
+    lines.push(Line::from(Span::styled("Available Actions", Style::default().fg(Color::White).add_modifier(Modifier::BOLD))));
+    lines.push(Line::from(""));
+
+    match task.status {
+        crate::model::TaskStatus::Planned => {
+            lines.push(Line::from(vec![
+                Span::styled(" s ", *key_style), Span::styled(" Start task with worktree isolation", *label_style),
+            ]));
+            lines.push(Line::from(vec![
+                Span::styled(" q ", *key_style), Span::styled(" Queue for running session", *label_style),
+            ]));
+            lines.push(Line::from(vec![
+                Span::styled(" e ", *key_style), Span::styled(" Edit task", *label_style),
             ]));
             lines.push(Line::from(vec![
                 Span::styled(" d ", *key_style), Span::styled(" Delete task", *label_style),
@@ -2206,6 +2948,9 @@ fn render_help_tab<'a>(
             lines.push(Line::from(vec![
                 Span::styled(" o ", *key_style), Span::styled(" Open interactive modal", *label_style),
             ]));
+            lines.push(Line::from(vec![
+                Span::styled(" b ", *key_style), Span::styled(" Preview: deploy the worktree (y copies the URL)", *label_style),
+            ]));
             lines.push(Line::from(vec![
                 Span::styled(" x ", *key_style), Span::styled(" Reset (cleanup and move to Planned)", *label_style),
             ]));
@@ -2248,6 +2993,14 @@ fn render_help_tab<'a>(
         }
     }
 
+    // Time tracking (General tab)
+    lines.push(Line::from(vec![
+        Span::styled(" t ", *key_style), Span::styled(" Add time spent (General tab)", *label_style),
+    ]));
+    lines.push(Line::from(vec![
+        Span::styled(" T ", *key_style), Span::styled(" Correct the last manual time entry", *label_style),
+    ]));
+
     // General navigation help
     lines.push(Line::from(""));
     lines.push(Line::from(Span::styled("─".repeat(40), *dim_style)));
@@ -2268,6 +3021,225 @@ fn render_help_tab<'a>(
 }
 
 /// Render the project statistics modal (triggered by / key)
+/// "0.4ms", "48ms", "1.24s"
+fn format_op_duration(duration: std::time::Duration) -> String {
+    let ms = duration.as_secs_f64() * 1000.0;
+    if ms < 1.0 {
+        format!("{:.1}ms", ms)
+    } else if ms < 1000.0 {
+        format!("{:.0}ms", ms)
+    } else {
+        format!("{:.2}s", ms / 1000.0)
+    }
+}
+
+/// The session's problems (newest first), or with `slow_ops` the latency
+/// histograms and the slowest git commands, sidecar calls and redraws of this
+/// session, with their arguments
+fn render_diagnostics_modal(frame: &mut Frame, scroll_offset: usize, slow_ops: bool) {
+    let area = centered_rect(80, 80, frame.area());
+    let accent_color = Color::Cyan;
+    let dim_style = Style::default().fg(Color::DarkGray);
+
+    let (title, mut lines) = if slow_ops {
+        (" Diagnostics: slow operations this session ", slow_operation_lines())
+    } else {
+        (" Diagnostics: problems this session ", problem_lines())
+    };
+    lines.push(Line::from(Span::styled(
+        if slow_ops {
+            "j/k scroll, Tab problems, any other key to close"
+        } else {
+            "j/k scroll, Tab slow operations, y copy, c clear, any other key to close"
+        },
+        dim_style,
+    )));
+
+    let visible_height = area.height.saturating_sub(2) as usize;
+    let scroll = scroll_offset.min(lines.len().saturating_sub(visible_height));
+    let content = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .title(title)
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(accent_color)),
+        )
+        .scroll((scroll as u16, 0));
+    frame.render_widget(ratatui::widgets::Clear, area);
+    frame.render_widget(content, area);
+}
+
+/// The debug log's newest lines, filtered by level and module
+fn render_log_viewer(frame: &mut Frame, viewer: &crate::model::LogViewerState) {
+    use tracing::Level;
+
+    let area = centered_rect(90, 85, frame.area());
+    let dim_style = Style::default().fg(Color::DarkGray);
+    let key_style = Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD);
+
+    let log = crate::logging::lines(viewer.min_level, viewer.target.as_deref(), viewer.until);
+    let visible_height = area.height.saturating_sub(2) as usize;
+    let scroll = viewer.scroll.min(log.len().saturating_sub(visible_height));
+    let end = log.len() - scroll;
+    let start = end.saturating_sub(visible_height);
+
+    let mut lines: Vec<Line> = Vec::new();
+    if log.is_empty() {
+        lines.push(Line::from(Span::styled(" Nothing logged at this level yet", dim_style)));
+    }
+    for line in &log[start..end] {
+        let level_color = match line.level {
+            Level::ERROR => Color::Red,
+            Level::WARN => Color::Yellow,
+            Level::INFO => Color::Green,
+            _ => Color::DarkGray,
+        };
+        lines.push(Line::from(vec![
+            Span::styled(format!(" {} ", line.at.format("%H:%M:%S%.3f")), dim_style),
+            Span::styled(format!("{:<5} ", line.level), Style::default().fg(level_color)),
+            Span::styled(format!("{}: ", line.target), Style::default().fg(Color::Cyan)),
+            Span::raw(line.message.clone()),
+        ]));
+    }
+
+    let title = format!(
+        " Debug log: {} and up, {}{} ",
+        viewer.min_level,
+        viewer.target.as_deref().unwrap_or("all modules"),
+        if viewer.until.is_some() { " (paused)" } else { "" },
+    );
+    let hints = vec![
+        Span::styled(" l", key_style),
+        Span::styled(" level  ", dim_style),
+        Span::styled("m", key_style),
+        Span::styled(" module  ", dim_style),
+        Span::styled("f", key_style),
+        Span::styled(if viewer.until.is_some() { " follow  " } else { " pause  " }, dim_style),
+        Span::styled("j/k", key_style),
+        Span::styled(" scroll  ", dim_style),
+        Span::styled("Esc", key_style),
+        Span::styled(format!(" close · {} ", crate::logging::log_path().display()), dim_style),
+    ];
+    let content = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .title(title)
+                .title_bottom(Line::from(hints))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Cyan)),
+        );
+    frame.render_widget(ratatui::widgets::Clear, area);
+    frame.render_widget(content, area);
+}
+
+/// Errors and warnings reported this session, newest first
+fn problem_lines() -> Vec<Line<'static>> {
+    use crate::diagnostics::Severity;
+
+    let dim_style = Style::default().fg(Color::DarkGray);
+    let problems = crate::diagnostics::problems();
+    if problems.is_empty() {
+        return vec![Line::from(Span::styled("No problems this session", dim_style)), Line::from("")];
+    }
+
+    let mut lines = Vec::new();
+    for problem in problems {
+        let (glyph, color) = match problem.severity {
+            Severity::Error => ("✗", Color::Red),
+            Severity::Warning => ("!", Color::Yellow),
+        };
+        let mut header = vec![
+            Span::styled(format!("{} ", glyph), Style::default().fg(color).add_modifier(Modifier::BOLD)),
+            Span::styled(format!("{} ", problem.at.format("%H:%M:%S")), dim_style),
+            Span::styled(format!("{:<8}", problem.source.label()), Style::default().fg(Color::Cyan)),
+        ];
+        if problem.count > 1 {
+            header.push(Span::styled(format!("x{} ", problem.count), Style::default().fg(color)));
+        }
+        if let Some(context) = problem.context {
+            header.push(Span::styled(context, dim_style));
+        }
+        lines.push(Line::from(header));
+        for text in problem.message.lines() {
+            lines.push(Line::from(format!("  {}", text)));
+        }
+    }
+    lines.push(Line::from(""));
+    lines
+}
+
+/// Latency histograms and the slowest operations of each kind
+fn slow_operation_lines() -> Vec<Line<'static>> {
+    use crate::timing::BUCKET_BOUNDS_MS;
+
+    let accent_color = Color::Cyan;
+    let dim_style = Style::default().fg(Color::DarkGray);
+    const BAR_WIDTH: usize = 30;
+
+    let mut lines: Vec<Line> = Vec::new();
+
+    // Where the time to the first frame went
+    let phases = crate::timing::startup_phases();
+    if !phases.is_empty() {
+        let total: std::time::Duration = phases.iter().map(|(_, d)| *d).sum();
+        lines.push(Line::from(vec![
+            Span::styled("Startup", Style::default().fg(accent_color).add_modifier(Modifier::BOLD)),
+            Span::styled(format!("  {} to the first frame", format_op_duration(total)), dim_style),
+        ]));
+        for (phase, duration) in phases {
+            lines.push(Line::from(vec![
+                Span::styled(format!("  {:>8} ", format_op_duration(duration)), Style::default().fg(Color::Yellow)),
+                Span::raw(phase),
+            ]));
+        }
+        lines.push(Line::from(""));
+    }
+
+    for report in crate::timing::report() {
+        lines.push(Line::from(vec![
+            Span::styled(report.kind.name(), Style::default().fg(accent_color).add_modifier(Modifier::BOLD)),
+            Span::styled(
+                format!("  {} total · p50 {} · p95 {}", report.count, format_op_duration(report.p50), format_op_duration(report.p95)),
+                dim_style,
+            ),
+        ]));
+        if report.count == 0 {
+            lines.push(Line::from(Span::styled("  Nothing recorded yet", dim_style)));
+            lines.push(Line::from(""));
+            continue;
+        }
+
+        // Histogram of the recent window
+        let max = report.histogram.iter().copied().max().unwrap_or(0).max(1);
+        for (idx, &count) in report.histogram.iter().enumerate() {
+            let bucket = match BUCKET_BOUNDS_MS.get(idx) {
+                Some(bound) if *bound >= 1000 => format!("<{}s", bound / 1000),
+                Some(bound) => format!("<{}ms", bound),
+                None => format!("≥{}s", BUCKET_BOUNDS_MS[BUCKET_BOUNDS_MS.len() - 1] / 1000),
+            };
+            let width = if count == 0 { 0 } else { (count * BAR_WIDTH).div_ceil(max) };
+            let bar_color = if idx + 3 >= report.histogram.len() { Color::Red } else { Color::Green };
+            lines.push(Line::from(vec![
+                Span::styled(format!("  {:>7} ", bucket), dim_style),
+                Span::styled("█".repeat(width), Style::default().fg(bar_color)),
+                Span::styled(format!(" {}", count), dim_style),
+            ]));
+        }
+
+        // Slowest operations with their arguments
+        lines.push(Line::from(Span::styled("  Slowest:", Style::default().add_modifier(Modifier::BOLD))));
+        for sample in &report.slowest {
+            lines.push(Line::from(vec![
+                Span::styled(format!("  {:>8} ", format_op_duration(sample.duration)), Style::default().fg(Color::Yellow)),
+                Span::styled(format!("{} ", sample.at.format("%H:%M:%S")), dim_style),
+                Span::raw(sample.label.clone()),
+            ]));
+        }
+        lines.push(Line::from(""));
+    }
+    lines
+}
+
 fn render_stats_modal(frame: &mut Frame, app: &App) {
     let area = centered_rect(55, 70, frame.area());
 
@@ -2614,6 +3586,41 @@ fn render_stats_modal(frame: &mut Frame, app: &App) {
         }
     }
 
+    // ═══════════════════════════════════════════════════════════════════════
+    // TIME TRACKED (session, focus and manual entries of tasks on the board)
+    // ═══════════════════════════════════════════════════════════════════════
+    let tracked_tasks: Vec<&crate::model::Task> = project.tasks.iter().filter(|t| t.tracked_secs() > 0).collect();
+    if !tracked_tasks.is_empty() {
+        use crate::model::time_tracking::{format_tracked, total_secs, TimeSource};
+
+        let by_source = |source| tracked_tasks.iter().map(|t| total_secs(&t.time_entries, Some(source))).sum::<i64>();
+        lines.push(Line::from(""));
+        lines.push(Line::from(vec![
+            Span::styled("  ⏱ ", Style::default().fg(accent_color)),
+            Span::styled("TIME TRACKED", Style::default().fg(Color::DarkGray)),
+        ]));
+        lines.push(Line::from(vec![
+            Span::styled("     Total:    ", Style::default().fg(Color::DarkGray)),
+            Span::styled(
+                format_tracked(tracked_tasks.iter().map(|t| t.tracked_secs()).sum()),
+                Style::default().fg(accent_color).add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(format!(" across {} task(s)", tracked_tasks.len()), Style::default().fg(Color::DarkGray)),
+        ]));
+        lines.push(Line::from(vec![
+            Span::styled("     ", Style::default()),
+            Span::styled(
+                format!(
+                    "session {} · focus {} · manual {}",
+                    format_tracked(by_source(TimeSource::Session)),
+                    format_tracked(by_source(TimeSource::Focus)),
+                    format_tracked(by_source(TimeSource::Manual)),
+                ),
+                Style::default().fg(Color::DarkGray),
+            ),
+        ]));
+    }
+
     // ═══════════════════════════════════════════════════════════════════════
     // TOKEN USAGE & COST
     // ═══════════════════════════════════════════════════════════════════════
@@ -2764,19 +3771,24 @@ fn format_duration(duration: chrono::Duration) -> String {
 }
 
 /// Render help overlay with scrolling support
-fn render_help(frame: &mut Frame, scroll_offset: usize) {
+/// Screen area of the help modal (shared with mouse hit-testing)
+pub fn help_modal_area(screen: Rect) -> Rect {
     // Minimum width to fit the longest help text line plus borders
     const MIN_WIDTH: u16 = 58;
 
-    let mut area = centered_rect(60, 80, frame.area());
+    let mut area = centered_rect(60, 80, screen);
 
     // Enforce minimum width (centered within screen)
     if area.width < MIN_WIDTH {
-        let screen = frame.area();
         let actual_width = MIN_WIDTH.min(screen.width);
         area.width = actual_width;
         area.x = screen.x + (screen.width.saturating_sub(actual_width)) / 2;
     }
+    area
+}
+
+fn render_help(frame: &mut Frame, scroll_offset: usize) {
+    let area = help_modal_area(frame.area());
 
     let help_text = vec![
         Line::from(Span::styled(
@@ -2803,6 +3815,8 @@ fn render_help(frame: &mut Frame, scroll_offset: usize) {
         Line::from("  r          Move to Review (InProgress/NeedsWork/Done)"),
         Line::from("  x          Reset: cleanup & move to Planned"),
         Line::from("  +/-        Reorder task up/down"),
+        Line::from("  H          Handoff doc (in task details): HANDOFF.md + clipboard"),
+        Line::from("  U          Links: open the task's PR, preview, CI run or issue"),
         Line::from(""),
         Line::from(vec![
             Span::styled("Review Column", Style::default().add_modifier(Modifier::UNDERLINED)),
@@ -2816,11 +3830,14 @@ fn render_help(frame: &mut Frame, scroll_offset: usize) {
         Line::from("  f          Feedback: send follow-up instructions"),
         Line::from("  n          Needs work: move back to Needs Work"),
         Line::from("  o          Open: interactive Claude session"),
+        Line::from("  A          Auto-merge: merge (PR or locally) once CI passes"),
+        Line::from("  b          Preview: deploy the worktree with the preview command"),
         Line::from(""),
         Line::from(vec![
             Span::styled("InProgress Column", Style::default().add_modifier(Modifier::UNDERLINED)),
         ]),
         Line::from("  f          Live feedback: send message to running task"),
+        Line::from("  A          Broadcast: send one message to every running task"),
         Line::from(""),
         Line::from(vec![
             Span::styled("Input Mode", Style::default().add_modifier(Modifier::UNDERLINED)),
@@ -2828,9 +3845,15 @@ fn render_help(frame: &mut Frame, scroll_offset: usize) {
         Line::from("  Enter      Submit task"),
         Line::from("  \\Enter    Newline (line continuation)"),
         Line::from("  Ctrl-O     Insert from .md file (fuzzy picker)"),
+        Line::from("  Ctrl-L     Attach a file (or drop files onto the input)"),
+        Line::from("  Ctrl-T     Feedback: pick a canned snippet"),
+        Line::from("  Alt-1..9   Feedback: insert snippet N"),
         Line::from("  Ctrl-G     Open in external editor"),
-        Line::from("  Ctrl-V     Paste image"),
-        Line::from("  Ctrl-X/U   Remove last / clear all images"),
+        Line::from("  Ctrl-V     Paste image or text (copied files can be attached)"),
+        Line::from("  Ctrl-X/U   Remove last image / clear all attachments"),
+        Line::from("  ↑/↓        Recall earlier submissions (at an empty input)"),
+        Line::from("  /tag /model /base /template /start   Set up a new task (Tab completes)"),
+        Line::from("  Ctrl-P     Markdown preview beside the input (P in the task preview)"),
         Line::from("  Esc        Cancel / unfocus"),
         Line::from(""),
         Line::from(vec![
@@ -2838,13 +3861,20 @@ fn render_help(frame: &mut Frame, scroll_offset: usize) {
         ]),
         Line::from("  !/Shift-1  Open project"),
         Line::from("  @-(/Shift-2-9  Switch to project N"),
+        Line::from("  )/Shift-0  Pick from all projects (type to filter)"),
+        Line::from("  Shift-←/→  Move the selected project tab (or drag it)"),
         Line::from("  Ctrl-D     Close current active project"),
+        Line::from("  Ctrl-O     Workspaces: switch between saved sets of projects"),
         Line::from(""),
         Line::from(vec![
             Span::styled("Sessions", Style::default().add_modifier(Modifier::UNDERLINED)),
         ]),
         Line::from("  o/O        Open task in tmux session (O: detached)"),
         Line::from("  Ctrl-T     Open Claude in project dir (new pane)"),
+        Line::from("  L          Live output pane for the selected in-progress task"),
+        Line::from("             (Tab to focus: j/k scroll, space pause, / search, n/N)"),
+        Line::from("  V          Monitor: tile all in-progress tasks (all projects)"),
+        Line::from("             (h/j/k/l move, Enter show on board, o open window)"),
         Line::from(""),
         Line::from(vec![
             Span::styled("Git", Style::default().add_modifier(Modifier::UNDERLINED)),
@@ -2859,6 +3889,19 @@ fn render_help(frame: &mut Frame, scroll_offset: usize) {
         Line::from("  Ctrl-W     Toggle Mascot advice (on/off)"),
         Line::from("  Ctrl-P     Settings (editor, commands)"),
         Line::from("  /          Project statistics"),
+        Line::from("  ~          Diagnostics: problems this session, slow operations (Tab)"),
+        Line::from("  `          Debug log: filter by level (l) and module (m), follow (f)"),
+        Line::from("  T          Dependency graph (queued/stacked tasks)"),
+        Line::from("  D          Quick diff of selected task (j/k next task)"),
+        Line::from("  E          Integrations (missing tmux/CLI/sidecar/clipboard)"),
+        Line::from("  R          Release notes from merged tasks (#labels)"),
+        Line::from("  Z          Watcher feed: past comments, mute categories (1-6)"),
+        Line::from("  C          Why a Planned task's start failed (s retry, x fresh)"),
+        Line::from("  F          Find in code, create task from matches"),
+        Line::from("  I          TODO/FIXME scanner, create Planned tasks"),
+        Line::from("  X          Cancel running fetch/pull/push"),
+        Line::from("  B          Start a focus session on a task / stop the timer"),
+        Line::from("  W          Write board snapshot (ANSI text + SVG image)"),
         Line::from("  ?          Toggle this help"),
         Line::from(""),
         Line::from(Span::styled(
@@ -2989,17 +4032,24 @@ fn render_open_project_dialog(frame: &mut Frame, app: &App) {
 
     let slot = app.model.ui_state.open_project_dialog_slot.unwrap_or(0);
     let is_creating = app.model.ui_state.create_folder_input.is_some();
+    let quick_open = &app.model.ui_state.quick_open;
+    let recent: Vec<_> = crate::model::recent::ordered(&app.model.global_settings.recent_projects)
+        .into_iter()
+        .take(crate::model::QuickOpen::MAX_SHOWN)
+        .collect();
+    let recent_height = if recent.is_empty() { 0 } else { recent.len() as u16 + 2 };
 
     // Clear area first
     frame.render_widget(ratatui::widgets::Clear, area);
 
-    // Split the area: title, breadcrumb path, columns, create input (optional), hints
+    // Split the area: title, breadcrumb path, recent projects, columns, create input (optional), hints
     let chunks = if is_creating {
         Layout::default()
             .direction(Direction::Vertical)
             .constraints([
                 Constraint::Length(2),  // Title
                 Constraint::Length(1),  // Breadcrumb path
+                Constraint::Length(recent_height), // Recent projects
                 Constraint::Min(8),     // Miller columns
                 Constraint::Length(3),  // Create folder input
                 Constraint::Length(2),  // Hints
@@ -3011,12 +4061,17 @@ fn render_open_project_dialog(frame: &mut Frame, app: &App) {
             .constraints([
                 Constraint::Length(2),  // Title
                 Constraint::Length(1),  // Breadcrumb path
+                Constraint::Length(recent_height), // Recent projects
                 Constraint::Min(10),    // Miller columns
                 Constraint::Length(2),  // Hints
             ])
             .split(area)
     };
 
+    if !recent.is_empty() {
+        render_recent_projects(frame, chunks[2], app, &recent);
+    }
+
     // Render title
     let title = Paragraph::new(Line::from(vec![
         Span::styled(
@@ -3028,27 +4083,40 @@ fn render_open_project_dialog(frame: &mut Frame, app: &App) {
 
     // Render directory browser with Miller columns
     if let Some(ref browser) = app.model.ui_state.directory_browser {
-        // Breadcrumb path display
-        let path_str = browser
-            .cwd()
-            .map(|p| p.display().to_string())
-            .unwrap_or_else(|| "~".to_string());
-        let path_display = Paragraph::new(Line::from(vec![
-            Span::styled(" ", Style::default()),
-            Span::styled(
-                path_str,
-                Style::default().fg(Color::DarkGray),
-            ),
-        ]));
+        // Breadcrumb path display, or the path being typed
+        let path_display = match quick_open.path_input {
+            Some(ref input) => Paragraph::new(Line::from(vec![
+                Span::styled(" Open: ", Style::default().fg(Color::Cyan)),
+                Span::styled(input.as_str(), Style::default().fg(Color::White).add_modifier(Modifier::BOLD)),
+                Span::styled("█", Style::default().fg(Color::White)), // Cursor
+                Span::styled(
+                    format!("  {}", quick_open.candidates.join("  ")),
+                    Style::default().fg(Color::DarkGray),
+                ),
+            ])),
+            None => {
+                let path_str = browser
+                    .cwd()
+                    .map(|p| p.display().to_string())
+                    .unwrap_or_else(|| "~".to_string());
+                Paragraph::new(Line::from(vec![
+                    Span::styled(" ", Style::default()),
+                    Span::styled(
+                        path_str,
+                        Style::default().fg(Color::DarkGray),
+                    ),
+                ]))
+            }
+        };
         frame.render_widget(path_display, chunks[1]);
 
         // Render three Miller columns
-        render_miller_columns(frame, chunks[2], browser, app);
+        render_miller_columns(frame, chunks[3], browser, app);
     }
 
     // Render create folder input if in create mode
     if let Some(ref input) = app.model.ui_state.create_folder_input {
-        let input_area = chunks[3];
+        let input_area = chunks[4];
         let input_widget = Paragraph::new(Line::from(vec![
             Span::styled(" New folder: ", Style::default().fg(Color::Cyan)),
             Span::styled(input.as_str(), Style::default().fg(Color::White).add_modifier(Modifier::BOLD)),
@@ -3067,17 +4135,79 @@ fn render_open_project_dialog(frame: &mut Frame, app: &App) {
             "Enter: Create folder  Esc: Cancel",
             Style::default().fg(Color::DarkGray),
         )));
-        frame.render_widget(hints, chunks[4]);
+        frame.render_widget(hints, chunks[5]);
     } else {
         // Render normal hints
+        let hint_text = if quick_open.path_input.is_some() {
+            "Tab: Complete  Enter: Open project (user@host:/path over SSH)  Esc: Back to browser"
+        } else if quick_open.recent_selected.is_some() {
+            "↑↓: Navigate  Enter/1-9: Open  p: Pin/unpin  d: Forget  Tab: Browser  Esc: Cancel"
+        } else if recent.is_empty() {
+            "↑↓: Navigate  ←→: Columns  Enter: Open project  /,~: Type a path  @: Remote  Esc: Cancel  Type letter to jump"
+        } else {
+            "↑↓: Navigate  ←→: Columns  Enter: Open project  1-9: Recent  Tab: Recent list  /,~: Type a path  @: Remote  Esc: Cancel"
+        };
         let hints = Paragraph::new(Line::from(Span::styled(
-            "↑↓: Navigate  ←→: Columns  Enter: Open project  Esc: Cancel  Type letter to jump",
+            hint_text,
             Style::default().fg(Color::DarkGray),
         )));
-        frame.render_widget(hints, chunks[3]);
+        frame.render_widget(hints, chunks[4]);
     }
 }
 
+/// Render the Recent panel of the open-project dialog: pinned favorites first,
+/// then the most recently opened, each on a number key
+fn render_recent_projects(frame: &mut Frame, area: Rect, app: &App, recent: &[&crate::model::recent::RecentProject]) {
+    let selected = app.model.ui_state.quick_open.recent_selected;
+    let home = dirs::home_dir();
+    let now = chrono::Utc::now();
+    let dim = Style::default().fg(Color::DarkGray);
+
+    let lines: Vec<Line> = recent.iter().enumerate().map(|(idx, entry)| {
+        // Show paths under the home directory as ~/...
+        let path = match home.as_ref().and_then(|h| entry.path.strip_prefix(h).ok()) {
+            Some(rest) => format!("~/{}", rest.display()),
+            None => entry.path.display().to_string(),
+        };
+        let elapsed = now.signed_duration_since(entry.opened_at);
+        let age = if elapsed.num_minutes() < 1 {
+            "just now".to_string()
+        } else if elapsed.num_hours() < 1 {
+            format!("{}m ago", elapsed.num_minutes())
+        } else if elapsed.num_days() < 1 {
+            format!("{}h ago", elapsed.num_hours())
+        } else {
+            format!("{}d ago", elapsed.num_days())
+        };
+        let path_style = if selected == Some(idx) {
+            Style::default().fg(Color::Black).bg(Color::Yellow).add_modifier(Modifier::BOLD)
+        } else if !entry.path.is_dir() {
+            dim.add_modifier(Modifier::CROSSED_OUT)
+        } else {
+            Style::default().fg(Color::White)
+        };
+        let mut spans = vec![
+            Span::styled(format!(" {} ", idx + 1), Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+            Span::styled(if entry.pinned { "★ " } else { "  " }, Style::default().fg(Color::Yellow)),
+            Span::styled(path, path_style),
+            Span::styled(format!("  {}", age), dim),
+        ];
+        if app.model.projects.iter().any(|p| p.location() == entry.path) {
+            spans.push(Span::styled("  (open)", Style::default().fg(Color::Green)));
+        }
+        Line::from(spans)
+    }).collect();
+
+    let border_color = if selected.is_some() { Color::Yellow } else { Color::DarkGray };
+    let panel = Paragraph::new(lines).block(
+        Block::default()
+            .title(" Recent ")
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(border_color)),
+    );
+    frame.render_widget(panel, area);
+}
+
 /// Render Miller columns (directory browser with preview)
 fn render_miller_columns(
     frame: &mut Frame,
@@ -3276,19 +4406,26 @@ fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
         .split(popup_layout[1])[1]
 }
 
-/// Render a confirmation modal for multiline messages (like merge check reports or conflict details)
-fn render_confirmation_modal(frame: &mut Frame, message: &str, scroll_offset: usize, action: &crate::model::PendingAction) {
-    use crate::model::PendingAction;
-
+/// Screen area of the multiline confirmation modal, sized to its message
+/// (shared with mouse hit-testing)
+pub fn confirmation_modal_area(message: &str, screen: Rect) -> Rect {
     // Calculate size based on content
     let line_count = message.lines().count();
     let max_line_width = message.lines().map(|l| l.len()).max().unwrap_or(40);
 
     // Size the modal to fit content with some padding, but cap at 80% height
-    let height_percent = ((line_count + 4) * 100 / frame.area().height as usize).min(80).max(30) as u16;
-    let width_percent = ((max_line_width + 6) * 100 / frame.area().width as usize).min(90).max(50) as u16;
+    let height_percent = ((line_count + 4) * 100 / screen.height as usize).min(80).max(30) as u16;
+    let width_percent = ((max_line_width + 6) * 100 / screen.width as usize).min(90).max(50) as u16;
+
+    centered_rect(width_percent, height_percent, screen)
+}
 
-    let area = centered_rect(width_percent, height_percent, frame.area());
+/// Render a confirmation modal for multiline messages (like merge check reports or conflict details)
+fn render_confirmation_modal(frame: &mut Frame, message: &str, scroll_offset: usize, action: &crate::model::PendingAction) {
+    use crate::model::PendingAction;
+
+    let line_count = message.lines().count();
+    let area = confirmation_modal_area(message, frame.area());
 
     // Check if content is scrollable (more lines than visible area)
     let visible_height = area.height.saturating_sub(2) as usize; // Account for borders
@@ -3306,7 +4443,7 @@ fn render_confirmation_modal(frame: &mut Frame, message: &str, scroll_offset: us
     let error_style = Style::default().fg(Color::LightRed);
 
     // Determine if this is a conflict modal for special styling
-    let is_conflict_modal = matches!(action, PendingAction::ApplyConflict { .. });
+    let is_conflict_modal = matches!(action, PendingAction::ApplyConflict { .. } | PendingAction::RebaseConflict(_));
 
     for line in message.lines() {
         let styled_line = if line.starts_with("===") {
@@ -3351,10 +4488,13 @@ fn render_confirmation_modal(frame: &mut Frame, message: &str, scroll_offset: us
     }
 
     // Determine title based on action type
-    let title = if is_conflict_modal {
-        " Apply Conflict "
-    } else {
-        " Merge Check "
+    let title = match action {
+        PendingAction::ApplyConflict { .. } => " Apply Conflict ",
+        PendingAction::RebaseConflict(_) => " Rebase Conflict ",
+        PendingAction::ReconcileWorktrees(_) => " Worktree Check ",
+        PendingAction::BroadcastFeedback { .. } | PendingAction::BroadcastReport => " Broadcast ",
+        PendingAction::LargePaste { .. } | PendingAction::AttachClipboardFiles { .. } => " Paste ",
+        _ => " Merge Check ",
     };
 
     // Add scroll indicator to title if scrollable
@@ -3582,342 +4722,2555 @@ fn render_config_modal(frame: &mut Frame, app: &App) {
             ]));
         }
         lines.push(Line::from(""));
-    }
 
-    // QA Validation field
-    let is_selected = config.selected_field == ConfigField::QaEnabled;
-    let qa_enabled = config.temp_qa_enabled;
-    let qa_value = if qa_enabled { "On" } else { "Off" };
+        // Watched Columns field
+        {
+            let is_selected = config.selected_field == ConfigField::WatcherColumns;
+            let is_editing = is_selected && config.editing;
 
-    let (prefix, style, value_style) = if is_selected {
-        (
-            "► ",
-            Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
-            if qa_enabled {
-                Style::default().fg(Color::Green)
+            let value = if is_editing {
+                format!("{}_", config.edit_buffer)
             } else {
-                Style::default().fg(Color::Red)
-            }
-        )
-    } else {
-        (
-            "  ",
-            Style::default(),
-            if qa_enabled {
-                Style::default().fg(Color::Green).add_modifier(Modifier::DIM)
+                if config.temp_watcher_columns.is_empty() {
+                    "(main checkout only)".to_string()
+                } else {
+                    crate::watcher_scope::format_columns(&config.temp_watcher_columns)
+                }
+            };
+
+            let (prefix, style, value_style) = if is_selected {
+                (
+                    "► ",
+                    Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+                    if is_editing {
+                        Style::default().fg(Color::Green)
+                    } else {
+                        Style::default().fg(Color::White)
+                    }
+                )
             } else {
-                Style::default().fg(Color::Red).add_modifier(Modifier::DIM)
+                ("  ", Style::default(), Style::default().fg(Color::DarkGray))
+            };
+
+            lines.push(Line::from(vec![
+                Span::styled(prefix, style),
+                Span::styled(format!("{}: ", ConfigField::WatcherColumns.label()), style),
+                Span::styled(value, value_style),
+            ]));
+            if is_selected {
+                lines.push(Line::from(vec![
+                    Span::raw("    "),
+                    Span::styled(ConfigField::WatcherColumns.hint(), Style::default().fg(Color::DarkGray)),
+                ]));
             }
-        )
-    };
+            lines.push(Line::from(""));
+        }
 
-    lines.push(Line::from(vec![
-        Span::styled(prefix, style),
-        Span::styled("QA Validation: ", style),
-        Span::styled(qa_value, value_style),
-        Span::styled(if is_selected { "  (Enter to toggle)" } else { "" }, Style::default().fg(Color::DarkGray)),
-    ]));
-    if is_selected {
-        lines.push(Line::from(vec![
-            Span::raw("    "),
-            Span::styled(ConfigField::QaEnabled.hint(), Style::default().fg(Color::DarkGray)),
+        // Watcher Persona field
+        {
+            let is_selected = config.selected_field == ConfigField::WatcherPersona;
+            let is_editing = is_selected && config.editing;
+
+            let value = if is_editing {
+                format!("{}_", config.edit_buffer)
+            } else {
+                config.temp_watcher_persona.clone().unwrap_or_else(|| "(supportive coding buddy)".to_string())
+            };
+
+            let (prefix, style, value_style) = if is_selected {
+                (
+                    "► ",
+                    Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+                    if is_editing {
+                        Style::default().fg(Color::Green)
+                    } else {
+                        Style::default().fg(Color::White)
+                    }
+                )
+            } else {
+                ("  ", Style::default(), Style::default().fg(Color::DarkGray))
+            };
+
+            lines.push(Line::from(vec![
+                Span::styled(prefix, style),
+                Span::styled(format!("{}: ", ConfigField::WatcherPersona.label()), style),
+                Span::styled(value, value_style),
+            ]));
+            if is_selected {
+                lines.push(Line::from(vec![
+                    Span::raw("    "),
+                    Span::styled(ConfigField::WatcherPersona.hint(), Style::default().fg(Color::DarkGray)),
+                ]));
+            }
+            lines.push(Line::from(""));
+        }
+
+        // Project Interval field
+        {
+            let is_selected = config.selected_field == ConfigField::WatcherInterval;
+            let is_editing = is_selected && config.editing;
+
+            let value = if is_editing {
+                format!("{}_", config.edit_buffer)
+            } else {
+                match config.temp_watcher_interval {
+                    Some(minutes) => format!("{} min", minutes),
+                    None => format!("(Advice Interval, {} min)", config.temp_mascot_interval),
+                }
+            };
+
+            let (prefix, style, value_style) = if is_selected {
+                (
+                    "► ",
+                    Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+                    if is_editing {
+                        Style::default().fg(Color::Green)
+                    } else {
+                        Style::default().fg(Color::White)
+                    }
+                )
+            } else {
+                ("  ", Style::default(), Style::default().fg(Color::DarkGray))
+            };
+
+            lines.push(Line::from(vec![
+                Span::styled(prefix, style),
+                Span::styled(format!("{}: ", ConfigField::WatcherInterval.label()), style),
+                Span::styled(value, value_style),
+            ]));
+            if is_selected {
+                lines.push(Line::from(vec![
+                    Span::raw("    "),
+                    Span::styled(ConfigField::WatcherInterval.hint(), Style::default().fg(Color::DarkGray)),
+                ]));
+            }
+            lines.push(Line::from(""));
+        }
+
+        // Watcher Reads field
+        {
+            let is_selected = config.selected_field == ConfigField::WatcherReads;
+            let mode = config.temp_watcher_reads;
+
+            let (prefix, style, value_style) = if is_selected {
+                (
+                    "► ",
+                    Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+                    Style::default().fg(Color::Cyan)
+                )
+            } else {
+                ("  ", Style::default(), Style::default().fg(Color::DarkGray))
+            };
+
+            lines.push(Line::from(vec![
+                Span::styled(prefix, style),
+                Span::styled(format!("{}: ", ConfigField::WatcherReads.label()), style),
+                Span::styled(mode.name(), value_style),
+                Span::styled(if is_selected { "  (Enter/←/→ to change)" } else { "" }, Style::default().fg(Color::DarkGray)),
+            ]));
+            if is_selected {
+                lines.push(Line::from(vec![
+                    Span::raw("    "),
+                    Span::styled(mode.description(), Style::default().fg(Color::DarkGray)),
+                ]));
+            }
+            lines.push(Line::from(""));
+        }
+    }
+
+    // Native Git Backend field
+    let is_selected = config.selected_field == ConfigField::NativeGit;
+    let native_git = config.temp_native_git;
+
+    let (prefix, style, value_style) = if is_selected {
+        (
+            "► ",
+            Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+            if native_git {
+                Style::default().fg(Color::Green)
+            } else {
+                Style::default().fg(Color::Red)
+            }
+        )
+    } else {
+        (
+            "  ",
+            Style::default(),
+            if native_git {
+                Style::default().fg(Color::Green).add_modifier(Modifier::DIM)
+            } else {
+                Style::default().fg(Color::Red).add_modifier(Modifier::DIM)
+            }
+        )
+    };
+
+    lines.push(Line::from(vec![
+        Span::styled(prefix, style),
+        Span::styled(format!("{}: ", ConfigField::NativeGit.label()), style),
+        Span::styled(if native_git { "On" } else { "Off" }, value_style),
+        Span::styled(if is_selected { "  (Enter to toggle)" } else { "" }, Style::default().fg(Color::DarkGray)),
+    ]));
+    if is_selected {
+        lines.push(Line::from(vec![
+            Span::raw("    "),
+            Span::styled(ConfigField::NativeGit.hint(), Style::default().fg(Color::DarkGray)),
+        ]));
+    }
+    lines.push(Line::from(""));
+
+    // Status Bar Clock field
+    let is_selected = config.selected_field == ConfigField::StatusClock;
+    let status_clock = config.temp_status_clock;
+
+    let (prefix, style, value_style) = if is_selected {
+        (
+            "► ",
+            Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+            if status_clock {
+                Style::default().fg(Color::Green)
+            } else {
+                Style::default().fg(Color::Red)
+            }
+        )
+    } else {
+        (
+            "  ",
+            Style::default(),
+            if status_clock {
+                Style::default().fg(Color::Green).add_modifier(Modifier::DIM)
+            } else {
+                Style::default().fg(Color::Red).add_modifier(Modifier::DIM)
+            }
+        )
+    };
+
+    lines.push(Line::from(vec![
+        Span::styled(prefix, style),
+        Span::styled(format!("{}: ", ConfigField::StatusClock.label()), style),
+        Span::styled(if status_clock { "On" } else { "Off" }, value_style),
+        Span::styled(if is_selected { "  (Enter to toggle)" } else { "" }, Style::default().fg(Color::DarkGray)),
+    ]));
+    if is_selected {
+        lines.push(Line::from(vec![
+            Span::raw("    "),
+            Span::styled(ConfigField::StatusClock.hint(), Style::default().fg(Color::DarkGray)),
+        ]));
+    }
+    lines.push(Line::from(""));
+
+    // Focus/Break Minutes field
+    let is_selected = config.selected_field == ConfigField::FocusMinutes;
+    let is_editing = is_selected && config.editing;
+
+    let minutes_value = if is_editing {
+        if config.edit_buffer.is_empty() {
+            "_".to_string()
+        } else {
+            format!("{}_", config.edit_buffer)
+        }
+    } else {
+        let (focus, brk) = config.temp_focus_minutes;
+        format!("{} / {} min", focus, brk)
+    };
+
+    let (prefix, style, value_style) = if is_selected {
+        (
+            "► ",
+            Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+            if is_editing {
+                Style::default().fg(Color::Green)
+            } else {
+                Style::default().fg(Color::White)
+            }
+        )
+    } else {
+        ("  ", Style::default(), Style::default().fg(Color::DarkGray))
+    };
+
+    lines.push(Line::from(vec![
+        Span::styled(prefix, style),
+        Span::styled(format!("{}: ", ConfigField::FocusMinutes.label()), style),
+        Span::styled(minutes_value, value_style),
+    ]));
+    if is_selected {
+        lines.push(Line::from(vec![
+            Span::raw("    "),
+            Span::styled(ConfigField::FocusMinutes.hint(), Style::default().fg(Color::DarkGray)),
+        ]));
+    }
+    lines.push(Line::from(""));
+
+    // Defer Starts in Focus field
+    let is_selected = config.selected_field == ConfigField::FocusDeferStarts;
+    let focus_defer_starts = config.temp_focus_defer_starts;
+
+    let (prefix, style, value_style) = if is_selected {
+        (
+            "► ",
+            Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+            if focus_defer_starts {
+                Style::default().fg(Color::Green)
+            } else {
+                Style::default().fg(Color::Red)
+            }
+        )
+    } else {
+        (
+            "  ",
+            Style::default(),
+            if focus_defer_starts {
+                Style::default().fg(Color::Green).add_modifier(Modifier::DIM)
+            } else {
+                Style::default().fg(Color::Red).add_modifier(Modifier::DIM)
+            }
+        )
+    };
+
+    lines.push(Line::from(vec![
+        Span::styled(prefix, style),
+        Span::styled(format!("{}: ", ConfigField::FocusDeferStarts.label()), style),
+        Span::styled(if focus_defer_starts { "On" } else { "Off" }, value_style),
+        Span::styled(if is_selected { "  (Enter to toggle)" } else { "" }, Style::default().fg(Color::DarkGray)),
+    ]));
+    if is_selected {
+        lines.push(Line::from(vec![
+            Span::raw("    "),
+            Span::styled(ConfigField::FocusDeferStarts.hint(), Style::default().fg(Color::DarkGray)),
+        ]));
+    }
+    lines.push(Line::from(""));
+
+    // Sound Cues field
+    let is_selected = config.selected_field == ConfigField::SoundCues;
+    let sound_cues = config.temp_sound_cues;
+
+    let (prefix, style, value_style) = if is_selected {
+        (
+            "► ",
+            Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+            if sound_cues {
+                Style::default().fg(Color::Green)
+            } else {
+                Style::default().fg(Color::Red)
+            }
+        )
+    } else {
+        (
+            "  ",
+            Style::default(),
+            if sound_cues {
+                Style::default().fg(Color::Green).add_modifier(Modifier::DIM)
+            } else {
+                Style::default().fg(Color::Red).add_modifier(Modifier::DIM)
+            }
+        )
+    };
+
+    lines.push(Line::from(vec![
+        Span::styled(prefix, style),
+        Span::styled(format!("{}: ", ConfigField::SoundCues.label()), style),
+        Span::styled(if sound_cues { "On" } else { "Off" }, value_style),
+        Span::styled(if is_selected { "  (Enter to toggle)" } else { "" }, Style::default().fg(Color::DarkGray)),
+    ]));
+    if is_selected {
+        lines.push(Line::from(vec![
+            Span::raw("    "),
+            Span::styled(ConfigField::SoundCues.hint(), Style::default().fg(Color::DarkGray)),
+        ]));
+    }
+    lines.push(Line::from(""));
+
+    // Quiet Hours field
+    let is_selected = config.selected_field == ConfigField::QuietHours;
+    let is_editing = is_selected && config.editing;
+
+    let quiet_value = if is_editing {
+        format!("{}_", config.edit_buffer)
+    } else {
+        config.temp_quiet_hours.clone().unwrap_or_else(|| "(never)".to_string())
+    };
+
+    let (prefix, style, value_style) = if is_selected {
+        (
+            "► ",
+            Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+            if is_editing {
+                Style::default().fg(Color::Green)
+            } else {
+                Style::default().fg(Color::White)
+            }
+        )
+    } else {
+        ("  ", Style::default(), Style::default().fg(Color::DarkGray))
+    };
+
+    lines.push(Line::from(vec![
+        Span::styled(prefix, style),
+        Span::styled(format!("{}: ", ConfigField::QuietHours.label()), style),
+        Span::styled(quiet_value, value_style),
+    ]));
+    if is_selected {
+        lines.push(Line::from(vec![
+            Span::raw("    "),
+            Span::styled(ConfigField::QuietHours.hint(), Style::default().fg(Color::DarkGray)),
+        ]));
+    }
+    lines.push(Line::from(""));
+
+    // Column Time Limits field
+    let is_selected = config.selected_field == ConfigField::SlaThresholds;
+    let is_editing = is_selected && config.editing;
+
+    let sla_value = if is_editing {
+        format!("{}_", config.edit_buffer)
+    } else if config.temp_sla_thresholds.is_empty() {
+        "(off)".to_string()
+    } else {
+        config.temp_sla_thresholds.clone()
+    };
+
+    let (prefix, style, value_style) = if is_selected {
+        (
+            "► ",
+            Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+            if is_editing {
+                Style::default().fg(Color::Green)
+            } else {
+                Style::default().fg(Color::White)
+            }
+        )
+    } else {
+        ("  ", Style::default(), Style::default().fg(Color::DarkGray))
+    };
+
+    lines.push(Line::from(vec![
+        Span::styled(prefix, style),
+        Span::styled(format!("{}: ", ConfigField::SlaThresholds.label()), style),
+        Span::styled(sla_value, value_style),
+    ]));
+    if is_selected {
+        lines.push(Line::from(vec![
+            Span::raw("    "),
+            Span::styled(ConfigField::SlaThresholds.hint(), Style::default().fg(Color::DarkGray)),
+        ]));
+    }
+    lines.push(Line::from(""));
+
+    // Webhooks field
+    let is_selected = config.selected_field == ConfigField::Webhooks;
+    let is_editing = is_selected && config.editing;
+
+    let webhooks_value = if is_editing {
+        format!("{}_", config.edit_buffer)
+    } else {
+        config.temp_webhooks.clone().unwrap_or_else(|| "(off)".to_string())
+    };
+
+    let (prefix, style, value_style) = if is_selected {
+        (
+            "► ",
+            Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+            if is_editing {
+                Style::default().fg(Color::Green)
+            } else {
+                Style::default().fg(Color::White)
+            }
+        )
+    } else {
+        ("  ", Style::default(), Style::default().fg(Color::DarkGray))
+    };
+
+    lines.push(Line::from(vec![
+        Span::styled(prefix, style),
+        Span::styled(format!("{}: ", ConfigField::Webhooks.label()), style),
+        Span::styled(webhooks_value, value_style),
+    ]));
+    if is_selected {
+        lines.push(Line::from(vec![
+            Span::raw("    "),
+            Span::styled(ConfigField::Webhooks.hint(), Style::default().fg(Color::DarkGray)),
+        ]));
+    }
+    lines.push(Line::from(""));
+
+    // Idle Redraw Rate field
+    let is_selected = config.selected_field == ConfigField::IdleFps;
+    let is_editing = is_selected && config.editing;
+
+    let fps_value = if is_editing {
+        if config.edit_buffer.is_empty() {
+            "_".to_string()
+        } else {
+            format!("{}_", config.edit_buffer)
+        }
+    } else {
+        format!("{}/s", config.temp_idle_fps)
+    };
+
+    let (prefix, style, value_style) = if is_selected {
+        (
+            "► ",
+            Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+            if is_editing {
+                Style::default().fg(Color::Green)
+            } else {
+                Style::default().fg(Color::White)
+            }
+        )
+    } else {
+        ("  ", Style::default(), Style::default().fg(Color::DarkGray))
+    };
+
+    lines.push(Line::from(vec![
+        Span::styled(prefix, style),
+        Span::styled(format!("{}: ", ConfigField::IdleFps.label()), style),
+        Span::styled(fps_value, value_style),
+    ]));
+    if is_selected {
+        lines.push(Line::from(vec![
+            Span::raw("    "),
+            Span::styled(ConfigField::IdleFps.hint(), Style::default().fg(Color::DarkGray)),
+        ]));
+    }
+    lines.push(Line::from(""));
+
+    // Activity Log Limit field
+    let is_selected = config.selected_field == ConfigField::ActivityRetention;
+    let is_editing = is_selected && config.editing;
+
+    let retention_value = if is_editing {
+        if config.edit_buffer.is_empty() {
+            "_".to_string()
+        } else {
+            format!("{}_", config.edit_buffer)
+        }
+    } else {
+        let (entries, kb) = config.temp_activity_retention;
+        format!("{} entries / {} KB", entries, kb)
+    };
+
+    let (prefix, style, value_style) = if is_selected {
+        (
+            "► ",
+            Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+            if is_editing {
+                Style::default().fg(Color::Green)
+            } else {
+                Style::default().fg(Color::White)
+            }
+        )
+    } else {
+        ("  ", Style::default(), Style::default().fg(Color::DarkGray))
+    };
+
+    lines.push(Line::from(vec![
+        Span::styled(prefix, style),
+        Span::styled(format!("{}: ", ConfigField::ActivityRetention.label()), style),
+        Span::styled(retention_value, value_style),
+    ]));
+    if is_selected {
+        lines.push(Line::from(vec![
+            Span::raw("    "),
+            Span::styled(ConfigField::ActivityRetention.hint(), Style::default().fg(Color::DarkGray)),
+        ]));
+    }
+    lines.push(Line::from(""));
+
+    // Color Palette field
+    {
+        let is_selected = config.selected_field == ConfigField::Palette;
+        let palette = config.temp_palette;
+
+        let (prefix, style, value_style) = if is_selected {
+            (
+                "► ",
+                Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+                Style::default().fg(Color::Cyan)
+            )
+        } else {
+            ("  ", Style::default(), Style::default().fg(Color::DarkGray))
+        };
+
+        lines.push(Line::from(vec![
+            Span::styled(prefix, style),
+            Span::styled(format!("{}: ", ConfigField::Palette.label()), style),
+            Span::styled(palette.name(), value_style),
+            Span::styled(if is_selected { "  (Enter/←/→ to change)" } else { "" }, Style::default().fg(Color::DarkGray)),
+        ]));
+        if is_selected {
+            lines.push(Line::from(vec![
+                Span::raw("    "),
+                Span::styled(palette.description(), Style::default().fg(Color::DarkGray)),
+            ]));
+        }
+        lines.push(Line::from(""));
+    }
+
+    // Board Layout field
+    {
+        let is_selected = config.selected_field == ConfigField::BoardLayout;
+        let layout = config.temp_board_layout;
+
+        let (prefix, style, value_style) = if is_selected {
+            (
+                "► ",
+                Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+                Style::default().fg(Color::Cyan)
+            )
+        } else {
+            ("  ", Style::default(), Style::default().fg(Color::DarkGray))
+        };
+
+        lines.push(Line::from(vec![
+            Span::styled(prefix, style),
+            Span::styled(format!("{}: ", ConfigField::BoardLayout.label()), style),
+            Span::styled(layout.name(), value_style),
+            Span::styled(if is_selected { "  (Enter/←/→ to change)" } else { "" }, Style::default().fg(Color::DarkGray)),
+        ]));
+        if is_selected {
+            lines.push(Line::from(vec![
+                Span::raw("    "),
+                Span::styled(layout.description(), Style::default().fg(Color::DarkGray)),
+            ]));
+        }
+        lines.push(Line::from(""));
+    }
+
+    // Spell Check field
+    let is_selected = config.selected_field == ConfigField::SpellCheck;
+    let is_editing = is_selected && config.editing;
+
+    let spell_value = if is_editing {
+        format!("{}_", config.edit_buffer)
+    } else {
+        config.temp_spell_check.clone().unwrap_or_else(|| "(off)".to_string())
+    };
+
+    let (prefix, style, value_style) = if is_selected {
+        (
+            "► ",
+            Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+            if is_editing {
+                Style::default().fg(Color::Green)
+            } else {
+                Style::default().fg(Color::White)
+            }
+        )
+    } else {
+        ("  ", Style::default(), Style::default().fg(Color::DarkGray))
+    };
+
+    lines.push(Line::from(vec![
+        Span::styled(prefix, style),
+        Span::styled(format!("{}: ", ConfigField::SpellCheck.label()), style),
+        Span::styled(spell_value, value_style),
+    ]));
+    if is_selected {
+        lines.push(Line::from(vec![
+            Span::raw("    "),
+            Span::styled(ConfigField::SpellCheck.hint(), Style::default().fg(Color::DarkGray)),
+        ]));
+    }
+    lines.push(Line::from(""));
+
+    // State Sync Remote field
+    let is_selected = config.selected_field == ConfigField::StateSync;
+    let is_editing = is_selected && config.editing;
+
+    let sync_value = if is_editing {
+        format!("{}_", config.edit_buffer)
+    } else {
+        config.temp_state_sync.clone().unwrap_or_else(|| "(off)".to_string())
+    };
+
+    let (prefix, style, value_style) = if is_selected {
+        (
+            "► ",
+            Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+            if is_editing {
+                Style::default().fg(Color::Green)
+            } else {
+                Style::default().fg(Color::White)
+            }
+        )
+    } else {
+        ("  ", Style::default(), Style::default().fg(Color::DarkGray))
+    };
+
+    lines.push(Line::from(vec![
+        Span::styled(prefix, style),
+        Span::styled(format!("{}: ", ConfigField::StateSync.label()), style),
+        Span::styled(sync_value, value_style),
+    ]));
+    if is_selected {
+        lines.push(Line::from(vec![
+            Span::raw("    "),
+            Span::styled(ConfigField::StateSync.hint(), Style::default().fg(Color::DarkGray)),
+        ]));
+    }
+    lines.push(Line::from(""));
+
+    // QA Validation field
+    let is_selected = config.selected_field == ConfigField::QaEnabled;
+    let qa_enabled = config.temp_qa_enabled;
+    let qa_value = if qa_enabled { "On" } else { "Off" };
+
+    let (prefix, style, value_style) = if is_selected {
+        (
+            "► ",
+            Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+            if qa_enabled {
+                Style::default().fg(Color::Green)
+            } else {
+                Style::default().fg(Color::Red)
+            }
+        )
+    } else {
+        (
+            "  ",
+            Style::default(),
+            if qa_enabled {
+                Style::default().fg(Color::Green).add_modifier(Modifier::DIM)
+            } else {
+                Style::default().fg(Color::Red).add_modifier(Modifier::DIM)
+            }
+        )
+    };
+
+    lines.push(Line::from(vec![
+        Span::styled(prefix, style),
+        Span::styled("QA Validation: ", style),
+        Span::styled(qa_value, value_style),
+        Span::styled(if is_selected { "  (Enter to toggle)" } else { "" }, Style::default().fg(Color::DarkGray)),
+    ]));
+    if is_selected {
+        lines.push(Line::from(vec![
+            Span::raw("    "),
+            Span::styled(ConfigField::QaEnabled.hint(), Style::default().fg(Color::DarkGray)),
+        ]));
+    }
+    lines.push(Line::from(""));
+
+    // Max QA Attempts field (only shown when QA is enabled)
+    if qa_enabled {
+        let is_selected = config.selected_field == ConfigField::MaxQaAttempts;
+        let is_editing = is_selected && config.editing;
+
+        let attempts_value = if is_editing {
+            if config.edit_buffer.is_empty() {
+                "_".to_string()
+            } else {
+                format!("{}_", config.edit_buffer)
+            }
+        } else {
+            config.temp_max_qa_attempts.to_string()
+        };
+
+        let (prefix, style, value_style) = if is_selected {
+            (
+                "► ",
+                Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+                if is_editing {
+                    Style::default().fg(Color::Green)
+                } else {
+                    Style::default().fg(Color::White)
+                }
+            )
+        } else {
+            ("  ", Style::default(), Style::default().fg(Color::DarkGray))
+        };
+
+        lines.push(Line::from(vec![
+            Span::styled(prefix, style),
+            Span::styled(format!("{}: ", ConfigField::MaxQaAttempts.label()), style),
+            Span::styled(attempts_value, value_style),
+        ]));
+        if is_selected {
+            lines.push(Line::from(vec![
+                Span::raw("    "),
+                Span::styled(ConfigField::MaxQaAttempts.hint(), Style::default().fg(Color::DarkGray)),
+            ]));
+        }
+        lines.push(Line::from(""));
+    }
+
+    // Section: Project Settings
+    lines.push(Line::from(vec![
+        Span::styled(
+            format!("Project: {}", project_name),
+            Style::default().fg(Color::Magenta).add_modifier(Modifier::UNDERLINED)
+        ),
+    ]));
+    lines.push(Line::from(""));
+
+    // Apply Strategy field
+    {
+        let is_selected = config.selected_field == ConfigField::ApplyStrategy;
+        let strategy = config.temp_apply_strategy;
+
+        let (prefix, style, value_style) = if is_selected {
+            (
+                "► ",
+                Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+                Style::default().fg(Color::Cyan)
+            )
+        } else {
+            ("  ", Style::default(), Style::default().fg(Color::DarkGray))
+        };
+
+        lines.push(Line::from(vec![
+            Span::styled(prefix, style),
+            Span::styled("Apply Strategy: ", style),
+            Span::styled(strategy.name(), value_style),
+            Span::styled(if is_selected { "  (Enter/←/→ to change)" } else { "" }, Style::default().fg(Color::DarkGray)),
+        ]));
+        if is_selected {
+            lines.push(Line::from(vec![
+                Span::raw("    "),
+                Span::styled(strategy.description(), Style::default().fg(Color::DarkGray)),
+            ]));
+        }
+        lines.push(Line::from(""));
+    }
+
+    // Context Restart field
+    {
+        let is_selected = config.selected_field == ConfigField::ContextRestart;
+        let mode = config.temp_context_restart;
+
+        let (prefix, style, value_style) = if is_selected {
+            (
+                "► ",
+                Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+                Style::default().fg(Color::Cyan)
+            )
+        } else {
+            ("  ", Style::default(), Style::default().fg(Color::DarkGray))
+        };
+
+        lines.push(Line::from(vec![
+            Span::styled(prefix, style),
+            Span::styled(format!("{}: ", ConfigField::ContextRestart.label()), style),
+            Span::styled(mode.name(), value_style),
+            Span::styled(if is_selected { "  (Enter/←/→ to change)" } else { "" }, Style::default().fg(Color::DarkGray)),
+        ]));
+        if is_selected {
+            lines.push(Line::from(vec![
+                Span::raw("    "),
+                Span::styled(mode.description(), Style::default().fg(Color::DarkGray)),
+            ]));
+        }
+        lines.push(Line::from(""));
+    }
+
+    // Commit Messages field
+    {
+        let is_selected = config.selected_field == ConfigField::CommitMessages;
+        let mode = config.temp_commit_messages;
+
+        let (prefix, style, value_style) = if is_selected {
+            (
+                "► ",
+                Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+                Style::default().fg(Color::Cyan)
+            )
+        } else {
+            ("  ", Style::default(), Style::default().fg(Color::DarkGray))
+        };
+
+        lines.push(Line::from(vec![
+            Span::styled(prefix, style),
+            Span::styled(format!("{}: ", ConfigField::CommitMessages.label()), style),
+            Span::styled(mode.name(), value_style),
+            Span::styled(if is_selected { "  (Enter/←/→ to change)" } else { "" }, Style::default().fg(Color::DarkGray)),
+        ]));
+        if is_selected {
+            lines.push(Line::from(vec![
+                Span::raw("    "),
+                Span::styled(mode.description(), Style::default().fg(Color::DarkGray)),
+            ]));
+        }
+        lines.push(Line::from(""));
+    }
+
+    // Column Sort field
+    {
+        let is_selected = config.selected_field == ConfigField::ColumnSort;
+        let sort = config.temp_column_sort;
+
+        let (prefix, style, value_style) = if is_selected {
+            (
+                "► ",
+                Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+                Style::default().fg(Color::Cyan)
+            )
+        } else {
+            ("  ", Style::default(), Style::default().fg(Color::DarkGray))
+        };
+
+        lines.push(Line::from(vec![
+            Span::styled(prefix, style),
+            Span::styled(format!("{}: ", ConfigField::ColumnSort.label()), style),
+            Span::styled(sort.name(), value_style),
+            Span::styled(if is_selected { "  (Enter/←/→ to change)" } else { "" }, Style::default().fg(Color::DarkGray)),
+        ]));
+        if is_selected {
+            lines.push(Line::from(vec![
+                Span::raw("    "),
+                Span::styled(sort.description(), Style::default().fg(Color::DarkGray)),
+            ]));
+        }
+        lines.push(Line::from(""));
+    }
+
+    // Group by Tag field
+    {
+        let is_selected = config.selected_field == ConfigField::GroupByTag;
+        let group_by_tag = config.temp_group_by_tag;
+
+        let (prefix, style, value_style) = if is_selected {
+            (
+                "► ",
+                Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+                Style::default().fg(if group_by_tag { Color::Green } else { Color::Red })
+            )
+        } else {
+            (
+                "  ",
+                Style::default(),
+                Style::default().fg(if group_by_tag { Color::Green } else { Color::Red }).add_modifier(Modifier::DIM)
+            )
+        };
+
+        lines.push(Line::from(vec![
+            Span::styled(prefix, style),
+            Span::styled(format!("{}: ", ConfigField::GroupByTag.label()), style),
+            Span::styled(if group_by_tag { "On" } else { "Off" }, value_style),
+            Span::styled(if is_selected { "  (Enter to toggle)" } else { "" }, Style::default().fg(Color::DarkGray)),
+        ]));
+        if is_selected {
+            lines.push(Line::from(vec![
+                Span::raw("    "),
+                Span::styled(ConfigField::GroupByTag.hint(), Style::default().fg(Color::DarkGray)),
+            ]));
+        }
+        lines.push(Line::from(""));
+    }
+
+    // Worktree Pool field
+    {
+        let is_selected = config.selected_field == ConfigField::WorktreePool;
+        let is_editing = is_selected && config.editing;
+
+        let pool_value = if is_editing {
+            format!("{}_", config.edit_buffer)
+        } else if config.temp_worktree_pool_size == 0 {
+            "Off".to_string()
+        } else {
+            format!("{} ready", config.temp_worktree_pool_size)
+        };
+
+        let (prefix, style, value_style) = if is_selected {
+            (
+                "► ",
+                Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+                if is_editing {
+                    Style::default().fg(Color::Green)
+                } else {
+                    Style::default().fg(Color::White)
+                }
+            )
+        } else {
+            ("  ", Style::default(), Style::default().fg(Color::DarkGray))
+        };
+
+        lines.push(Line::from(vec![
+            Span::styled(prefix, style),
+            Span::styled(format!("{}: ", ConfigField::WorktreePool.label()), style),
+            Span::styled(pool_value, value_style),
+        ]));
+        if is_selected {
+            lines.push(Line::from(vec![
+                Span::raw("    "),
+                Span::styled(ConfigField::WorktreePool.hint(), Style::default().fg(Color::DarkGray)),
+            ]));
+        }
+        lines.push(Line::from(""));
+    }
+
+    // Sparse Checkout field
+    {
+        let is_selected = config.selected_field == ConfigField::SparsePaths;
+        let is_editing = is_selected && config.editing;
+
+        let sparse_value = if is_editing {
+            format!("{}_", config.edit_buffer)
+        } else {
+            config.temp_sparse_paths.clone().unwrap_or_else(|| "(full checkout)".to_string())
+        };
+
+        let (prefix, style, value_style) = if is_selected {
+            (
+                "► ",
+                Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+                if is_editing {
+                    Style::default().fg(Color::Green)
+                } else {
+                    Style::default().fg(Color::White)
+                }
+            )
+        } else {
+            ("  ", Style::default(), Style::default().fg(Color::DarkGray))
+        };
+
+        lines.push(Line::from(vec![
+            Span::styled(prefix, style),
+            Span::styled(format!("{}: ", ConfigField::SparsePaths.label()), style),
+            Span::styled(sparse_value, value_style),
+        ]));
+        if is_selected {
+            lines.push(Line::from(vec![
+                Span::raw("    "),
+                Span::styled(ConfigField::SparsePaths.hint(), Style::default().fg(Color::DarkGray)),
+            ]));
+        }
+        lines.push(Line::from(""));
+    }
+
+    // Shared Caches field
+    {
+        let is_selected = config.selected_field == ConfigField::SharedCaches;
+        let is_editing = is_selected && config.editing;
+
+        let caches_value = if is_editing {
+            format!("{}_", config.edit_buffer)
+        } else {
+            config.temp_shared_caches.clone().unwrap_or_else(|| "(none)".to_string())
+        };
+
+        let (prefix, style, value_style) = if is_selected {
+            (
+                "► ",
+                Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+                if is_editing {
+                    Style::default().fg(Color::Green)
+                } else {
+                    Style::default().fg(Color::White)
+                }
+            )
+        } else {
+            ("  ", Style::default(), Style::default().fg(Color::DarkGray))
+        };
+
+        lines.push(Line::from(vec![
+            Span::styled(prefix, style),
+            Span::styled(format!("{}: ", ConfigField::SharedCaches.label()), style),
+            Span::styled(caches_value, value_style),
+        ]));
+        if is_selected {
+            lines.push(Line::from(vec![
+                Span::raw("    "),
+                Span::styled(ConfigField::SharedCaches.hint(), Style::default().fg(Color::DarkGray)),
+            ]));
+        }
+        lines.push(Line::from(""));
+    }
+
+    // Status Ignore field
+    {
+        let is_selected = config.selected_field == ConfigField::StatusIgnore;
+        let is_editing = is_selected && config.editing;
+
+        let ignore_value = if is_editing {
+            format!("{}_", config.edit_buffer)
+        } else {
+            config.temp_status_ignore.clone().unwrap_or_else(|| "(none)".to_string())
+        };
+
+        let (prefix, style, value_style) = if is_selected {
+            (
+                "► ",
+                Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+                if is_editing {
+                    Style::default().fg(Color::Green)
+                } else {
+                    Style::default().fg(Color::White)
+                }
+            )
+        } else {
+            ("  ", Style::default(), Style::default().fg(Color::DarkGray))
+        };
+
+        lines.push(Line::from(vec![
+            Span::styled(prefix, style),
+            Span::styled(format!("{}: ", ConfigField::StatusIgnore.label()), style),
+            Span::styled(ignore_value, value_style),
+        ]));
+        if is_selected {
+            lines.push(Line::from(vec![
+                Span::raw("    "),
+                Span::styled(ConfigField::StatusIgnore.hint(), Style::default().fg(Color::DarkGray)),
+            ]));
+        }
+        lines.push(Line::from(""));
+    }
+
+    // Auto Versioning field
+    let is_selected = config.selected_field == ConfigField::AutoVersion;
+    let auto_version = config.temp_auto_version;
+
+    let (prefix, style, value_style) = if is_selected {
+        (
+            "► ",
+            Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+            if auto_version {
+                Style::default().fg(Color::Green)
+            } else {
+                Style::default().fg(Color::Red)
+            }
+        )
+    } else {
+        (
+            "  ",
+            Style::default(),
+            if auto_version {
+                Style::default().fg(Color::Green).add_modifier(Modifier::DIM)
+            } else {
+                Style::default().fg(Color::Red).add_modifier(Modifier::DIM)
+            }
+        )
+    };
+
+    lines.push(Line::from(vec![
+        Span::styled(prefix, style),
+        Span::styled(format!("{}: ", ConfigField::AutoVersion.label()), style),
+        Span::styled(if auto_version { "On" } else { "Off" }, value_style),
+        Span::styled(if is_selected { "  (Enter to toggle)" } else { "" }, Style::default().fg(Color::DarkGray)),
+    ]));
+    if is_selected {
+        lines.push(Line::from(vec![
+            Span::raw("    "),
+            Span::styled(ConfigField::AutoVersion.hint(), Style::default().fg(Color::DarkGray)),
+        ]));
+    }
+
+    // Version Rules field (only shown when auto-versioning is enabled)
+    if auto_version {
+        let is_selected = config.selected_field == ConfigField::VersionRules;
+        let is_editing = is_selected && config.editing;
+
+        let rules_value = if is_editing {
+            format!("{}_", config.edit_buffer)
+        } else {
+            config.temp_version_rules.clone()
+                .unwrap_or_else(|| format!("(default: {})", crate::release::version::DEFAULT_VERSION_RULES))
+        };
+
+        let (prefix, style, value_style) = if is_selected {
+            (
+                "► ",
+                Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+                if is_editing {
+                    Style::default().fg(Color::Green)
+                } else {
+                    Style::default().fg(Color::White)
+                }
+            )
+        } else {
+            ("  ", Style::default(), Style::default().fg(Color::DarkGray))
+        };
+
+        lines.push(Line::from(vec![
+            Span::styled(prefix, style),
+            Span::styled(format!("{}: ", ConfigField::VersionRules.label()), style),
+            Span::styled(rules_value, value_style),
+        ]));
+        if is_selected {
+            lines.push(Line::from(vec![
+                Span::raw("    "),
+                Span::styled(ConfigField::VersionRules.hint(), Style::default().fg(Color::DarkGray)),
+            ]));
+        }
+    }
+    lines.push(Line::from(""));
+
+    // Error Log field
+    {
+        let is_selected = config.selected_field == ConfigField::ErrorLog;
+        let is_editing = is_selected && config.editing;
+
+        let source_value = if is_editing {
+            format!("{}_", config.edit_buffer)
+        } else {
+            config.temp_error_log_source.clone().unwrap_or_else(|| "(off)".to_string())
+        };
+
+        let (prefix, style, value_style) = if is_selected {
+            (
+                "► ",
+                Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+                if is_editing {
+                    Style::default().fg(Color::Green)
+                } else {
+                    Style::default().fg(Color::White)
+                }
+            )
+        } else {
+            ("  ", Style::default(), Style::default().fg(Color::DarkGray))
+        };
+
+        lines.push(Line::from(vec![
+            Span::styled(prefix, style),
+            Span::styled(format!("{}: ", ConfigField::ErrorLog.label()), style),
+            Span::styled(source_value, value_style),
+        ]));
+        if is_selected {
+            lines.push(Line::from(vec![
+                Span::raw("    "),
+                Span::styled(ConfigField::ErrorLog.hint(), Style::default().fg(Color::DarkGray)),
+            ]));
+        }
+    }
+
+    // Auto-start Bug Tasks field
+    {
+        let is_selected = config.selected_field == ConfigField::ErrorLogAutoStart;
+        let auto_start = config.temp_error_log_auto_start;
+
+        let (prefix, style, value_style) = if is_selected {
+            (
+                "► ",
+                Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+                if auto_start {
+                    Style::default().fg(Color::Green)
+                } else {
+                    Style::default().fg(Color::Red)
+                }
+            )
+        } else {
+            (
+                "  ",
+                Style::default(),
+                if auto_start {
+                    Style::default().fg(Color::Green).add_modifier(Modifier::DIM)
+                } else {
+                    Style::default().fg(Color::Red).add_modifier(Modifier::DIM)
+                }
+            )
+        };
+
+        lines.push(Line::from(vec![
+            Span::styled(prefix, style),
+            Span::styled(format!("{}: ", ConfigField::ErrorLogAutoStart.label()), style),
+            Span::styled(if auto_start { "On" } else { "Off" }, value_style),
+            Span::styled(if is_selected { "  (Enter to toggle)" } else { "" }, Style::default().fg(Color::DarkGray)),
+        ]));
+        if is_selected {
+            lines.push(Line::from(vec![
+                Span::raw("    "),
+                Span::styled(ConfigField::ErrorLogAutoStart.hint(), Style::default().fg(Color::DarkGray)),
+            ]));
+        }
+        lines.push(Line::from(""));
+    }
+
+    // Feedback Snippets field
+    {
+        let is_selected = config.selected_field == ConfigField::FeedbackSnippets;
+        let is_editing = is_selected && config.editing;
+
+        let snippets_value = if is_editing {
+            format!("{}_", config.edit_buffer)
+        } else if config.temp_feedback_snippets.is_empty() {
+            format!("(defaults, {})", crate::model::DEFAULT_FEEDBACK_SNIPPETS.len())
+        } else {
+            config.temp_feedback_snippets.join(" | ")
+        };
+
+        let (prefix, style, value_style) = if is_selected {
+            (
+                "► ",
+                Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+                if is_editing {
+                    Style::default().fg(Color::Green)
+                } else {
+                    Style::default().fg(Color::White)
+                }
+            )
+        } else {
+            ("  ", Style::default(), Style::default().fg(Color::DarkGray))
+        };
+
+        lines.push(Line::from(vec![
+            Span::styled(prefix, style),
+            Span::styled(format!("{}: ", ConfigField::FeedbackSnippets.label()), style),
+            Span::styled(snippets_value, value_style),
+        ]));
+        if is_selected {
+            lines.push(Line::from(vec![
+                Span::raw("    "),
+                Span::styled(ConfigField::FeedbackSnippets.hint(), Style::default().fg(Color::DarkGray)),
+            ]));
+        }
+        lines.push(Line::from(""));
+    }
+
+    // Transcribe Command field
+    {
+        let is_selected = config.selected_field == ConfigField::TranscribeCommand;
+        let is_editing = is_selected && config.editing;
+
+        let command_value = if is_editing {
+            format!("{}_", config.edit_buffer)
+        } else {
+            config.temp_transcribe_command.clone().unwrap_or_else(|| "(off)".to_string())
+        };
+
+        let (prefix, style, value_style) = if is_selected {
+            (
+                "► ",
+                Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+                if is_editing {
+                    Style::default().fg(Color::Green)
+                } else {
+                    Style::default().fg(Color::White)
+                }
+            )
+        } else {
+            ("  ", Style::default(), Style::default().fg(Color::DarkGray))
+        };
+
+        lines.push(Line::from(vec![
+            Span::styled(prefix, style),
+            Span::styled(format!("{}: ", ConfigField::TranscribeCommand.label()), style),
+            Span::styled(command_value, value_style),
+        ]));
+        if is_selected {
+            lines.push(Line::from(vec![
+                Span::raw("    "),
+                Span::styled(ConfigField::TranscribeCommand.hint(), Style::default().fg(Color::DarkGray)),
+            ]));
+        }
+        lines.push(Line::from(""));
+    }
+
+    // Chat Approvals field
+    {
+        let is_selected = config.selected_field == ConfigField::ChatBridge;
+        let is_editing = is_selected && config.editing;
+
+        let bridge_value = if is_editing {
+            format!("{}_", config.edit_buffer)
+        } else {
+            config.temp_chat_bridge.as_deref()
+                .and_then(crate::chat::ChatBridge::parse)
+                .map_or_else(|| "(off)".to_string(), |bridge| bridge.masked())
+        };
+
+        let (prefix, style, value_style) = if is_selected {
+            (
+                "► ",
+                Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+                if is_editing {
+                    Style::default().fg(Color::Green)
+                } else {
+                    Style::default().fg(Color::White)
+                }
+            )
+        } else {
+            ("  ", Style::default(), Style::default().fg(Color::DarkGray))
+        };
+
+        lines.push(Line::from(vec![
+            Span::styled(prefix, style),
+            Span::styled(format!("{}: ", ConfigField::ChatBridge.label()), style),
+            Span::styled(bridge_value, value_style),
+        ]));
+        if is_selected {
+            lines.push(Line::from(vec![
+                Span::raw("    "),
+                Span::styled(ConfigField::ChatBridge.hint(), Style::default().fg(Color::DarkGray)),
+            ]));
+        }
+        lines.push(Line::from(""));
+    }
+
+    // Session Layout field
+    {
+        let is_selected = config.selected_field == ConfigField::SessionLayout;
+        let is_editing = is_selected && config.editing;
+
+        let layout_value = if is_editing {
+            format!("{}_", config.edit_buffer)
+        } else {
+            config.temp_session_layout.clone().unwrap_or_else(|| "(claude | shell)".to_string())
+        };
+
+        let (prefix, style, value_style) = if is_selected {
+            (
+                "► ",
+                Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+                if is_editing {
+                    Style::default().fg(Color::Green)
+                } else {
+                    Style::default().fg(Color::White)
+                }
+            )
+        } else {
+            ("  ", Style::default(), Style::default().fg(Color::DarkGray))
+        };
+
+        lines.push(Line::from(vec![
+            Span::styled(prefix, style),
+            Span::styled(format!("{}: ", ConfigField::SessionLayout.label()), style),
+            Span::styled(layout_value, value_style),
+        ]));
+        if is_selected {
+            lines.push(Line::from(vec![
+                Span::raw("    "),
+                Span::styled(ConfigField::SessionLayout.hint(), Style::default().fg(Color::DarkGray)),
+            ]));
+        }
+        lines.push(Line::from(""));
+    }
+
+    // Command fields
+    let command_fields = [
+        (ConfigField::CheckCommand, &config.temp_commands.check),
+        (ConfigField::RunCommand, &config.temp_commands.run),
+        (ConfigField::TestCommand, &config.temp_commands.test),
+        (ConfigField::FormatCommand, &config.temp_commands.format),
+        (ConfigField::LintCommand, &config.temp_commands.lint),
+        (ConfigField::PreStartScript, &config.temp_commands.pre_start),
+        (ConfigField::PostMergeScript, &config.temp_commands.post_merge),
+        (ConfigField::PreviewCommand, &config.temp_commands.preview),
+    ];
+
+    for (field, value) in command_fields {
+        let is_selected = config.selected_field == field;
+        let is_editing = is_selected && config.editing;
+
+        let display_value = if is_editing {
+            if config.edit_buffer.is_empty() {
+                "_".to_string()
+            } else {
+                format!("{}_", config.edit_buffer)
+            }
+        } else {
+            let unset = match field {
+                ConfigField::PreStartScript | ConfigField::PostMergeScript | ConfigField::PreviewCommand => "(none)",
+                _ => "(auto-detect)",
+            };
+            value.clone().unwrap_or_else(|| unset.to_string())
+        };
+
+        let (prefix, style, value_style) = if is_selected {
+            (
+                "► ",
+                Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+                if is_editing {
+                    Style::default().fg(Color::Green)
+                } else if value.is_some() {
+                    Style::default().fg(Color::White)
+                } else {
+                    Style::default().fg(Color::DarkGray)
+                }
+            )
+        } else {
+            (
+                "  ",
+                Style::default(),
+                if value.is_some() {
+                    Style::default().fg(Color::DarkGray)
+                } else {
+                    Style::default().fg(Color::Rgb(80, 80, 80))
+                }
+            )
+        };
+
+        lines.push(Line::from(vec![
+            Span::styled(prefix, style),
+            Span::styled(format!("{}: ", field.label()), style),
+            Span::styled(display_value, value_style),
+        ]));
+
+        if is_selected {
+            lines.push(Line::from(vec![
+                Span::raw("    "),
+                Span::styled(field.hint(), Style::default().fg(Color::DarkGray)),
+            ]));
+        }
+    }
+
+    lines.push(Line::from(""));
+    lines.push(Line::from(""));
+
+    // Footer with keybindings
+    let editing_hints = if config.editing {
+        "Enter confirm  Esc cancel"
+    } else {
+        "j/k navigate  Enter/l edit  r reset to defaults  Esc/q save & close"
+    };
+    lines.push(Line::from(Span::styled(
+        editing_hints,
+        Style::default().fg(Color::DarkGray),
+    )));
+
+    // Keep the selected field (and its hint line) in view on short terminals
+    let selected_line = lines.iter()
+        .position(|line| line.spans.first().is_some_and(|span| span.content == "► "))
+        .unwrap_or(0);
+    let visible_height = area.height.saturating_sub(2) as usize;
+    let scroll = (selected_line + 2).saturating_sub(visible_height) as u16;
+
+    let modal = Paragraph::new(lines)
+        .scroll((scroll, 0))
+        .block(
+            Block::default()
+                .title(" Settings ")
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Cyan)),
+        )
+        .style(Style::default().fg(Color::White));
+
+    // Clear area first
+    frame.render_widget(ratatui::widgets::Clear, area);
+    frame.render_widget(modal, area);
+}
+
+/// Render the stash management modal
+fn render_stash_modal(frame: &mut Frame, app: &App) {
+    let area = centered_rect(60, 60, frame.area());
+
+    let Some(project) = app.model.active_project() else {
+        return;
+    };
+
+    let stashes = &project.tracked_stashes;
+    let selected_idx = app.model.ui_state.stash_modal_selected_idx;
+
+    let mut lines = vec![
+        Line::from(Span::styled(
+            "Tracked Stashes",
+            Style::default().add_modifier(Modifier::BOLD),
+        )),
+        Line::from(""),
+    ];
+
+    if stashes.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "No tracked stashes",
+            Style::default().fg(Color::DarkGray),
+        )));
+    } else {
+        let label_style = Style::default().fg(Color::DarkGray);
+        let value_style = Style::default().fg(Color::White);
+        let _key_style = Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD);
+
+        for (idx, stash) in stashes.iter().enumerate() {
+            let is_selected = idx == selected_idx;
+            let prefix = if is_selected { "► " } else { "  " };
+            let style = if is_selected {
+                Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+            };
+
+            // Stash header: icon + short SHA + description
+            lines.push(Line::from(vec![
+                Span::styled(prefix, style),
+                Span::styled("📦 ", style),
+                Span::styled(&stash.stash_sha[..8.min(stash.stash_sha.len())], Style::default().fg(Color::Magenta)),
+                Span::styled(" ", style),
+                Span::styled(&stash.description, style),
+            ]));
+
+            // If selected, show details
+            if is_selected {
+                // Time since created
+                let elapsed = chrono::Utc::now().signed_duration_since(stash.created_at);
+                let time_ago = if elapsed.num_minutes() < 1 {
+                    "just now".to_string()
+                } else if elapsed.num_hours() < 1 {
+                    format!("{}m ago", elapsed.num_minutes())
+                } else if elapsed.num_hours() < 24 {
+                    format!("{}h ago", elapsed.num_hours())
+                } else {
+                    format!("{}d ago", elapsed.num_days())
+                };
+
+                lines.push(Line::from(vec![
+                    Span::raw("      "),
+                    Span::styled("Created: ", label_style),
+                    Span::styled(time_ago, value_style),
+                    Span::styled("  │  ", label_style),
+                    Span::styled(format!("{} files changed", stash.files_changed), value_style),
+                ]));
+
+                if !stash.files_summary.is_empty() {
+                    // Show files summary, truncated if needed
+                    let summary = if stash.files_summary.len() > 40 {
+                        format!("{}...", &stash.files_summary[..37])
+                    } else {
+                        stash.files_summary.clone()
+                    };
+                    lines.push(Line::from(vec![
+                        Span::raw("      "),
+                        Span::styled("Files: ", label_style),
+                        Span::styled(summary, Style::default().fg(Color::Gray)),
+                    ]));
+                }
+
+                lines.push(Line::from(""));
+            }
+        }
+    }
+
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled("─".repeat(40), Style::default().fg(Color::DarkGray))));
+    lines.push(Line::from(""));
+
+    // Key hints
+    let key_style = Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD);
+    let hint_style = Style::default().fg(Color::DarkGray);
+
+    if !stashes.is_empty() {
+        lines.push(Line::from(vec![
+            Span::styled("p", key_style),
+            Span::styled(" pop  ", hint_style),
+            Span::styled("d", key_style),
+            Span::styled(" drop  ", hint_style),
+            Span::styled("j/k", key_style),
+            Span::styled(" navigate  ", hint_style),
+            Span::styled("Esc/S/q", key_style),
+            Span::styled(" close", hint_style),
+        ]));
+    } else {
+        lines.push(Line::from(vec![
+            Span::styled("Esc/S/q", key_style),
+            Span::styled(" close", hint_style),
         ]));
     }
-    lines.push(Line::from(""));
 
-    // Max QA Attempts field (only shown when QA is enabled)
-    if qa_enabled {
-        let is_selected = config.selected_field == ConfigField::MaxQaAttempts;
-        let is_editing = is_selected && config.editing;
+    let modal = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .title(" Stash Manager ")
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Yellow)),
+        )
+        .style(Style::default().fg(Color::White));
 
-        let attempts_value = if is_editing {
-            if config.edit_buffer.is_empty() {
-                "_".to_string()
+    frame.render_widget(ratatui::widgets::Clear, area);
+    frame.render_widget(modal, area);
+}
+
+/// Render the dependency graph modal (tasks linked by queueing or shared sessions)
+fn render_dependency_graph(frame: &mut Frame, app: &App) {
+    let area = centered_rect(70, 70, frame.area());
+
+    let Some(project) = app.model.active_project() else {
+        return;
+    };
+
+    let rows = project.dependency_graph();
+    let selected_idx = app.model.ui_state.dependency_graph_selected_idx;
+    let connector_style = Style::default().fg(Color::DarkGray);
+
+    let mut lines = vec![
+        Line::from(Span::styled(
+            "Task Dependencies",
+            Style::default().add_modifier(Modifier::BOLD),
+        )),
+        Line::from(""),
+    ];
+
+    if rows.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "No queued or stacked tasks",
+            Style::default().fg(Color::DarkGray),
+        )));
+        lines.push(Line::from(Span::styled(
+            "Queue a task with q in its details to build a chain",
+            Style::default().fg(Color::DarkGray),
+        )));
+    } else {
+        for (idx, row) in rows.iter().enumerate() {
+            let Some(task) = project.tasks.iter().find(|t| t.id == row.task_id) else {
+                continue;
+            };
+            let is_selected = idx == selected_idx;
+            let prefix = if is_selected { "► " } else { "  " };
+            let title_style = if is_selected {
+                Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)
             } else {
-                format!("{}_", config.edit_buffer)
+                Style::default()
+            };
+
+            // Tree connectors: ancestors contribute a rail, the node itself a branch
+            let mut tree = String::new();
+            if let Some((own_is_last, ancestors)) = row.ancestors_last.split_last() {
+                for ancestor_is_last in ancestors {
+                    tree.push_str(if *ancestor_is_last { "   " } else { "│  " });
+                }
+                tree.push_str(if *own_is_last { "└─ " } else { "├─ " });
+            }
+
+            let edge = match row.edge {
+                Some(crate::model::DependencyEdge::Queued) => Span::styled("⏳ ", Style::default().fg(Color::Yellow)),
+                Some(crate::model::DependencyEdge::Stacked) => Span::styled("⤷ ", Style::default().fg(Color::Cyan)),
+                None => Span::raw(""),
+            };
+
+            let status_color = match task.status {
+                crate::model::TaskStatus::Planned => Color::Blue,
+                crate::model::TaskStatus::InProgress => Color::Yellow,
+                crate::model::TaskStatus::Testing => Color::Cyan,
+                crate::model::TaskStatus::NeedsWork => Color::Red,
+                crate::model::TaskStatus::Review
+                | crate::model::TaskStatus::Accepting
+                | crate::model::TaskStatus::Updating
+                | crate::model::TaskStatus::Applying => Color::Magenta,
+                crate::model::TaskStatus::Done => Color::Green,
+            };
+
+            lines.push(Line::from(vec![
+                Span::styled(prefix, title_style),
+                Span::styled(tree, connector_style),
+                edge,
+                Span::styled(format!("[{}] ", task.status.label()), Style::default().fg(status_color)),
+                Span::styled(format!("{} ", task.display_id()), Style::default().fg(Color::DarkGray)),
+                Span::styled(task.title.lines().next().unwrap_or("").to_string(), title_style),
+            ]));
+        }
+
+        lines.push(Line::from(""));
+        lines.push(Line::from(vec![
+            Span::styled("⏳", Style::default().fg(Color::Yellow)),
+            Span::styled(" queued to run after parent  ", connector_style),
+            Span::styled("⤷", Style::default().fg(Color::Cyan)),
+            Span::styled(" continued in parent's session", connector_style),
+        ]));
+    }
+
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled("─".repeat(40), Style::default().fg(Color::DarkGray))));
+    lines.push(Line::from(""));
+
+    // Key hints
+    let key_style = Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD);
+    let hint_style = Style::default().fg(Color::DarkGray);
+
+    if !rows.is_empty() {
+        lines.push(Line::from(vec![
+            Span::styled("Enter", key_style),
+            Span::styled(" go to task  ", hint_style),
+            Span::styled("j/k", key_style),
+            Span::styled(" navigate  ", hint_style),
+            Span::styled("Esc/T/q", key_style),
+            Span::styled(" close", hint_style),
+        ]));
+    } else {
+        lines.push(Line::from(vec![
+            Span::styled("Esc/T/q", key_style),
+            Span::styled(" close", hint_style),
+        ]));
+    }
+
+    let modal = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .title(" Dependency Graph ")
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Cyan)),
+        )
+        .style(Style::default().fg(Color::White));
+
+    frame.render_widget(ratatui::widgets::Clear, area);
+    frame.render_widget(modal, area);
+}
+
+/// Banner for a missing integration that what's focused would use: the
+/// clipboard while typing a task, the sidecar, tmux and the claude CLI for the
+/// selected task's sessions
+pub fn capability_banner(app: &App) -> Option<String> {
+    use crate::capabilities::Capability;
+    let needed: &[Capability] = if app.model.ui_state.focus == FocusArea::TaskInput {
+        &[Capability::Clipboard]
+    } else if app.model.ui_state.focus != FocusArea::KanbanBoard || app.model.ui_state.selected_task_idx.is_none() {
+        &[]
+    } else {
+        match app.model.ui_state.selected_column {
+            TaskStatus::Planned => &[Capability::Sidecar],
+            TaskStatus::InProgress | TaskStatus::NeedsWork | TaskStatus::Review => {
+                &[Capability::Sidecar, Capability::Tmux, Capability::ClaudeCli]
+            }
+            _ => &[],
+        }
+    };
+    app.capability_banner(needed)
+}
+
+/// Render the integrations panel: each integration, whether it's available
+/// and how to fix it when it isn't
+fn render_integrations(frame: &mut Frame, app: &App) {
+    use crate::capabilities::Capability;
+
+    let area = centered_rect(70, 60, frame.area());
+    let dim = Style::default().fg(Color::DarkGray);
+    let key_style = Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD);
+    let capabilities = &app.model.ui_state.capabilities;
+
+    let mut lines = vec![Line::from("")];
+    for capability in Capability::ALL {
+        match capabilities.unavailable(capability, app.sidecar_client.is_some()) {
+            None => lines.push(Line::from(vec![
+                Span::styled("  ✓ ", Style::default().fg(Color::Green)),
+                Span::styled(format!("{:<12}", capability.label()), Style::default().add_modifier(Modifier::BOLD)),
+                Span::styled(capability.feature(), dim),
+            ])),
+            Some(why) => {
+                lines.push(Line::from(vec![
+                    Span::styled("  ✗ ", Style::default().fg(Color::Red)),
+                    Span::styled(format!("{:<12}", capability.label()), Style::default().add_modifier(Modifier::BOLD)),
+                    Span::styled(format!("{} disabled: {}", capability.feature(), why), Style::default().fg(Color::Yellow)),
+                ]));
+                lines.push(Line::from(Span::styled(format!("      {}", capability.fix_hint()), dim)));
             }
+        }
+        lines.push(Line::from(""));
+    }
+    lines.push(Line::from(vec![
+        Span::styled("  r", key_style),
+        Span::styled(" check again  ", dim),
+        Span::styled("s", key_style),
+        Span::styled(" sidecar controls  ", dim),
+        Span::styled("Esc", key_style),
+        Span::styled(" close", dim),
+    ]));
+
+    let panel = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .title(" Integrations ")
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Cyan)),
+        )
+        .wrap(ratatui::widgets::Wrap { trim: false });
+
+    frame.render_widget(ratatui::widgets::Clear, area);
+    frame.render_widget(panel, area);
+}
+
+/// Render the quick diff popup: diff stat and first hunks of one task, from
+/// the diff cache when it's there
+/// Set up a focus session: which task it's for and how long a block lasts
+fn render_focus_picker(frame: &mut Frame, app: &App, picker: &crate::model::FocusPicker) {
+    let full = frame.area();
+    let width = 56.min(full.width);
+    let height = 9.min(full.height);
+    let area = Rect::new(
+        full.x + (full.width - width) / 2,
+        full.y + (full.height - height) / 2,
+        width,
+        height,
+    );
+    let dim = Style::default().fg(Color::DarkGray);
+    let key_style = Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD);
+    let value_style = Style::default().fg(Color::White).add_modifier(Modifier::BOLD);
+
+    let task = picker.task_id
+        .and_then(|id| app.model.active_project().and_then(|p| p.tasks.iter().find(|t| t.id == id)));
+    let task_spans = match task {
+        Some(task) => vec![
+            Span::styled(format!("[{}] ", task.display_id()), dim),
+            Span::styled(task.short_title.clone().unwrap_or_else(|| task.title.lines().next().unwrap_or("").to_string()), value_style),
+        ],
+        None => vec![Span::styled("(none - all tasks notify)", dim)],
+    };
+
+    let mut lines = vec![
+        Line::from(""),
+        Line::from([vec![Span::raw("  Task:   ")], task_spans].concat()),
+        Line::from(vec![
+            Span::raw("  Length: "),
+            Span::styled(format!("{} min", picker.minutes), value_style),
+            Span::styled(format!(", then {} min break", app.model.global_settings.break_minutes), dim),
+        ]),
+        Line::from(""),
+    ];
+    if task.is_some() {
+        lines.push(Line::from(Span::styled("  Other tasks stay quiet until the break", dim)));
+    } else {
+        lines.push(Line::from(""));
+    }
+    lines.push(Line::from(""));
+    lines.push(Line::from(vec![
+        Span::styled("  ←/→", key_style),
+        Span::styled(" length  ", dim),
+        Span::styled("t", key_style),
+        Span::styled(" task  ", dim),
+        Span::styled("Enter", key_style),
+        Span::styled(" start  ", dim),
+        Span::styled("Esc", key_style),
+        Span::styled(" cancel", dim),
+    ]));
+
+    let popup = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .title(" Focus Session ")
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Cyan)),
+        )
+        .style(Style::default().fg(Color::White));
+
+    frame.render_widget(ratatui::widgets::Clear, area);
+    frame.render_widget(popup, area);
+}
+
+fn render_project_picker(frame: &mut Frame, app: &App, picker: &crate::model::ProjectPicker) {
+    let full = frame.area();
+    let width = 64.min(full.width);
+    let height = 16.min(full.height);
+    let area = Rect::new(
+        full.x + (full.width - width) / 2,
+        full.y + (full.height - height) / 2,
+        width,
+        height,
+    );
+    let dim = Style::default().fg(Color::DarkGray);
+    let key_style = Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD);
+
+    let matches = picker.matches(&app.model.projects);
+    // Filter line, blank, list, blank, hints
+    let list_rows = (height as usize).saturating_sub(6).max(1);
+    let first = picker.selected.saturating_sub(list_rows - 1);
+
+    let mut lines = vec![
+        Line::from(vec![
+            Span::styled(" > ", key_style),
+            Span::raw(picker.query.clone()),
+            Span::styled("█", Style::default().fg(Color::Cyan)),
+        ]),
+        Line::from(""),
+    ];
+    if matches.is_empty() {
+        lines.push(Line::from(Span::styled("   No matching projects", dim)));
+    }
+    for (row, &idx) in matches.iter().enumerate().skip(first).take(list_rows) {
+        let project = &app.model.projects[idx];
+        let is_selected = row == picker.selected;
+        let name_style = if is_selected {
+            Style::default().fg(Color::Black).bg(Color::Yellow).add_modifier(Modifier::BOLD)
+        } else if idx == app.model.active_project_idx {
+            Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)
         } else {
-            config.temp_max_qa_attempts.to_string()
+            Style::default().fg(Color::White)
         };
+        let key = PROJECT_KEYS.get(idx).map_or_else(|| "    ".to_string(), |k| format!("[{}] ", k));
+        let mut spans = vec![
+            Span::styled(format!(" {}", key), dim),
+            Span::styled(format!(" {} ", project.name), name_style),
+        ];
+        let attention_count = project.unseen_attention_count();
+        if attention_count > 0 {
+            spans.push(Span::styled(
+                format!(" {} ", attention_count),
+                Style::default().fg(Color::White).bg(Color::Red).add_modifier(Modifier::BOLD),
+            ));
+        }
+        spans.push(Span::styled(format!("  {}", project.working_dir.display()), dim));
+        lines.push(Line::from(spans));
+    }
+    while lines.len() < height as usize - 3 {
+        lines.push(Line::from(""));
+    }
+    lines.push(Line::from(vec![
+        Span::styled(" ↑/↓", key_style),
+        Span::styled(" select  ", dim),
+        Span::styled("Enter", key_style),
+        Span::styled(" switch  ", dim),
+        Span::styled("Esc", key_style),
+        Span::styled(" close", dim),
+    ]));
 
-        let (prefix, style, value_style) = if is_selected {
-            (
-                "► ",
-                Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
-                if is_editing {
-                    Style::default().fg(Color::Green)
-                } else {
-                    Style::default().fg(Color::White)
-                }
-            )
+    let popup = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .title(format!(" Projects ({}) ", app.model.projects.len()))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Cyan)),
+        )
+        .style(Style::default().fg(Color::White));
+
+    frame.render_widget(ratatui::widgets::Clear, area);
+    frame.render_widget(popup, area);
+}
+
+fn render_workspace_picker(frame: &mut Frame, app: &App, picker: &crate::model::WorkspacePicker) {
+    let full = frame.area();
+    let width = 60.min(full.width);
+    let height = 14.min(full.height);
+    let area = Rect::new(
+        full.x + (full.width - width) / 2,
+        full.y + (full.height - height) / 2,
+        width,
+        height,
+    );
+    let dim = Style::default().fg(Color::DarkGray);
+    let key_style = Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD);
+    let settings = &app.model.global_settings;
+
+    let mut lines = vec![Line::from("")];
+    // Workspace list, then naming line and hints at the bottom
+    let list_rows = (height as usize).saturating_sub(6).max(1);
+    let first = picker.selected.saturating_sub(list_rows - 1);
+    if settings.workspaces.is_empty() {
+        lines.push(Line::from(Span::styled("  No saved workspaces yet", dim)));
+    }
+    for (idx, workspace) in settings.workspaces.iter().enumerate().skip(first).take(list_rows) {
+        let is_active = settings.active_workspace.as_deref() == Some(workspace.name.as_str());
+        let name_style = if idx == picker.selected && picker.naming.is_none() {
+            Style::default().fg(Color::Black).bg(Color::Yellow).add_modifier(Modifier::BOLD)
+        } else if is_active {
+            Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)
         } else {
-            ("  ", Style::default(), Style::default().fg(Color::DarkGray))
+            Style::default().fg(Color::White)
         };
-
+        let names: Vec<&str> = workspace.projects.iter().map(|p| p.name.as_str()).collect();
         lines.push(Line::from(vec![
-            Span::styled(prefix, style),
-            Span::styled(format!("{}: ", ConfigField::MaxQaAttempts.label()), style),
-            Span::styled(attempts_value, value_style),
+            Span::styled(if is_active { "  ● " } else { "    " }, Style::default().fg(Color::Cyan)),
+            Span::styled(format!(" {} ", workspace.name), name_style),
+            Span::styled(format!("  {}", names.join(", ")), dim),
         ]));
-        if is_selected {
+    }
+    while lines.len() < height as usize - 5 {
+        lines.push(Line::from(""));
+    }
+
+    match picker.naming {
+        Some(ref name) => {
             lines.push(Line::from(vec![
-                Span::raw("    "),
-                Span::styled(ConfigField::MaxQaAttempts.hint(), Style::default().fg(Color::DarkGray)),
+                Span::styled("  Save open projects as: ", dim),
+                Span::raw(name.clone()),
+                Span::styled("█", Style::default().fg(Color::Cyan)),
+            ]));
+            lines.push(Line::from(""));
+            lines.push(Line::from(vec![
+                Span::styled("  Enter", key_style),
+                Span::styled(" save  ", dim),
+                Span::styled("Esc", key_style),
+                Span::styled(" cancel", dim),
+            ]));
+        }
+        None => {
+            lines.push(Line::from(Span::styled(
+                format!("  {} project{} open", app.model.projects.len(), if app.model.projects.len() == 1 { "" } else { "s" }),
+                dim,
+            )));
+            lines.push(Line::from(""));
+            lines.push(Line::from(vec![
+                Span::styled("  Enter", key_style),
+                Span::styled(" switch  ", dim),
+                Span::styled("n", key_style),
+                Span::styled(" new from open  ", dim),
+                Span::styled("u", key_style),
+                Span::styled(" update  ", dim),
+                Span::styled("d", key_style),
+                Span::styled(" delete  ", dim),
+                Span::styled("Esc", key_style),
+                Span::styled(" close", dim),
             ]));
         }
+    }
+
+    let popup = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .title(" Workspaces ")
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Cyan)),
+        )
+        .style(Style::default().fg(Color::White));
+
+    frame.render_widget(ratatui::widgets::Clear, area);
+    frame.render_widget(popup, area);
+}
+
+fn render_quick_diff(frame: &mut Frame, app: &App, task_id: uuid::Uuid) {
+    let area = centered_rect(70, 70, frame.area());
+    let dim = Style::default().fg(Color::DarkGray);
+    let key_style = Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD);
+
+    let task = app.model.active_project().and_then(|p| p.tasks.iter().find(|t| t.id == task_id));
+    let title = task.map_or_else(|| " Quick Diff ".to_string(), |t| format!(" {} · Quick Diff ", t.display_id()));
+
+    let inner_width = area.width.saturating_sub(2) as usize;
+    let inner_height = area.height.saturating_sub(2) as usize;
+    let mut lines = Vec::new();
+    if let Some(task) = task {
+        lines.push(Line::from(Span::styled(
+            task.title.lines().next().unwrap_or("").to_string(),
+            Style::default().add_modifier(Modifier::BOLD),
+        )));
+        lines.push(Line::from(""));
+    }
+
+    // Title and key hints take four rows
+    let body_height = inner_height.saturating_sub(4);
+    let cache = &app.model.ui_state.git_diff_cache;
+    if let Some(diff) = cache.latest(task_id) {
+        if diff.total_lines() == 0 {
+            lines.push(Line::from(Span::styled("No changes", dim)));
+        } else {
+            lines.extend(diff_overview_lines(diff, inner_width, body_height));
+        }
+    } else if let Some(error) = cache.error(task_id) {
+        lines.push(Line::from(Span::styled(error.to_string(), Style::default().fg(Color::Red))));
+    } else if task.is_some_and(|t| t.git_branch.is_none()) {
+        lines.push(Line::from(Span::styled("Not started - no branch yet", dim)));
+    } else {
+        lines.push(Line::from(Span::styled("Loading diff...", dim)));
+    }
+
+    // Key hints on the last row
+    while lines.len() < inner_height.saturating_sub(1) {
         lines.push(Line::from(""));
     }
+    lines.truncate(inner_height.saturating_sub(1));
+    lines.push(Line::from(vec![
+        Span::styled("j/k", key_style),
+        Span::styled(" prev/next task  ", dim),
+        Span::styled("Enter", key_style),
+        Span::styled(" full diff  ", dim),
+        Span::styled("Esc/D", key_style),
+        Span::styled(" close", dim),
+    ]));
+
+    let popup = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .title(title)
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Cyan)),
+        )
+        .style(Style::default().fg(Color::White));
+
+    frame.render_widget(ratatui::widgets::Clear, area);
+    frame.render_widget(popup, area);
+}
+
+/// Render the code search modal (query input, then selectable matches)
+fn render_code_search(frame: &mut Frame, app: &App) {
+    let area = centered_rect(80, 80, frame.area());
+
+    let Some(ref search) = app.model.ui_state.code_search else {
+        return;
+    };
+
+    let key_style = Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD);
+    let hint_style = Style::default().fg(Color::DarkGray);
+    let mut lines: Vec<Line> = Vec::new();
 
-    // Section: Project Settings
     lines.push(Line::from(vec![
-        Span::styled(
-            format!("Project: {}", project_name),
-            Style::default().fg(Color::Magenta).add_modifier(Modifier::UNDERLINED)
-        ),
+        Span::styled("/ ", Style::default().fg(Color::Yellow)),
+        Span::styled(search.query.clone(), Style::default().fg(Color::White)),
+        Span::styled(if search.editing_query { "█" } else { "" }, Style::default().fg(Color::Yellow)),
     ]));
     lines.push(Line::from(""));
 
-    // Apply Strategy field
-    {
-        let is_selected = config.selected_field == ConfigField::ApplyStrategy;
-        let strategy = config.temp_apply_strategy;
-
-        let (prefix, style, value_style) = if is_selected {
-            (
-                "► ",
-                Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
-                Style::default().fg(Color::Cyan)
-            )
-        } else {
-            ("  ", Style::default(), Style::default().fg(Color::DarkGray))
-        };
+    let visible_height = area.height.saturating_sub(6) as usize;
 
-        lines.push(Line::from(vec![
-            Span::styled(prefix, style),
-            Span::styled("Apply Strategy: ", style),
-            Span::styled(strategy.name(), value_style),
-            Span::styled(if is_selected { "  (Enter/←/→ to change)" } else { "" }, Style::default().fg(Color::DarkGray)),
-        ]));
-        if is_selected {
+    if search.searching {
+        lines.push(Line::from(Span::styled("Searching...", hint_style)));
+    } else if let Some(ref error) = search.error {
+        lines.push(Line::from(Span::styled(error.clone(), Style::default().fg(Color::Red))));
+    } else if search.editing_query {
+        lines.push(Line::from(Span::styled(
+            "Regex pattern (smart-case). Searches the main checkout with ripgrep (or git grep).",
+            hint_style,
+        )));
+    } else if search.results.is_empty() {
+        lines.push(Line::from(Span::styled("No matches.", hint_style)));
+    } else {
+        // Keep the cursor in view
+        let scroll_start = search.cursor.saturating_sub(visible_height.saturating_sub(1));
+        for (idx, m) in search.results.iter().enumerate().skip(scroll_start).take(visible_height) {
+            let is_cursor = idx == search.cursor;
+            let checkbox = if search.selected.contains(&idx) { "[x] " } else { "[ ] " };
+            let row_style = if is_cursor {
+                Style::default().bg(Color::DarkGray)
+            } else {
+                Style::default()
+            };
             lines.push(Line::from(vec![
-                Span::raw("    "),
-                Span::styled(strategy.description(), Style::default().fg(Color::DarkGray)),
+                Span::styled(if is_cursor { "► " } else { "  " }, Style::default().fg(Color::Yellow)),
+                Span::styled(checkbox, Style::default().fg(Color::Green)),
+                Span::styled(m.location(), Style::default().fg(Color::Cyan)),
+                Span::styled(format!("  {}", m.text), row_style.fg(Color::White)),
             ]));
         }
-        lines.push(Line::from(""));
     }
 
-    // Command fields
-    let command_fields = [
-        (ConfigField::CheckCommand, &config.temp_commands.check),
-        (ConfigField::RunCommand, &config.temp_commands.run),
-        (ConfigField::TestCommand, &config.temp_commands.test),
-        (ConfigField::FormatCommand, &config.temp_commands.format),
-        (ConfigField::LintCommand, &config.temp_commands.lint),
-    ];
+    let title = if search.results.is_empty() || search.editing_query {
+        " Code Search ".to_string()
+    } else {
+        let capped = if search.results.len() >= crate::search::MAX_RESULTS { "+" } else { "" };
+        format!(" Code Search: {}{} matches, {} selected ", search.results.len(), capped, search.selected.len())
+    };
 
-    for (field, value) in command_fields {
-        let is_selected = config.selected_field == field;
-        let is_editing = is_selected && config.editing;
+    let hints = if search.editing_query {
+        vec![
+            Span::styled(" Enter", key_style),
+            Span::styled(" search  ", hint_style),
+            Span::styled("Esc", key_style),
+            Span::styled(" close ", hint_style),
+        ]
+    } else {
+        vec![
+            Span::styled(" Space", key_style),
+            Span::styled(" select  ", hint_style),
+            Span::styled("a", key_style),
+            Span::styled(" all  ", hint_style),
+            Span::styled("Enter", key_style),
+            Span::styled(" create task  ", hint_style),
+            Span::styled("/", key_style),
+            Span::styled(" edit query  ", hint_style),
+            Span::styled("Esc", key_style),
+            Span::styled(" close ", hint_style),
+        ]
+    };
 
-        let display_value = if is_editing {
-            if config.edit_buffer.is_empty() {
-                "_".to_string()
-            } else {
-                format!("{}_", config.edit_buffer)
-            }
-        } else {
-            value.clone().unwrap_or_else(|| "(auto-detect)".to_string())
-        };
+    let modal = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .title(title)
+                .title_bottom(Line::from(hints))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Cyan)),
+        );
+    frame.render_widget(ratatui::widgets::Clear, area);
+    frame.render_widget(modal, area);
+}
 
-        let (prefix, style, value_style) = if is_selected {
-            (
-                "► ",
-                Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
-                if is_editing {
-                    Style::default().fg(Color::Green)
-                } else if value.is_some() {
-                    Style::default().fg(Color::White)
-                } else {
-                    Style::default().fg(Color::DarkGray)
-                }
-            )
-        } else {
-            (
-                "  ",
-                Style::default(),
-                if value.is_some() {
-                    Style::default().fg(Color::DarkGray)
-                } else {
-                    Style::default().fg(Color::Rgb(80, 80, 80))
-                }
-            )
-        };
+/// Render the TODO/FIXME scanner (grouped by file, or oldest first)
+fn render_todo_scan(frame: &mut Frame, app: &App) {
+    let area = centered_rect(80, 80, frame.area());
 
-        lines.push(Line::from(vec![
-            Span::styled(prefix, style),
-            Span::styled(format!("{}: ", field.label()), style),
-            Span::styled(display_value, value_style),
-        ]));
+    let Some(ref scan) = app.model.ui_state.todo_scan else {
+        return;
+    };
 
-        if is_selected {
-            lines.push(Line::from(vec![
-                Span::raw("    "),
-                Span::styled(field.hint(), Style::default().fg(Color::DarkGray)),
+    let key_style = Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD);
+    let hint_style = Style::default().fg(Color::DarkGray);
+    let mut lines: Vec<Line> = Vec::new();
+    let visible_height = area.height.saturating_sub(2) as usize;
+    let now = chrono::Utc::now();
+
+    if scan.scanning {
+        lines.push(Line::from(Span::styled("Scanning for TODO/FIXME/HACK comments...", hint_style)));
+    } else if let Some(ref error) = scan.error {
+        lines.push(Line::from(Span::styled(error.clone(), Style::default().fg(Color::Red))));
+    } else if scan.items.is_empty() {
+        lines.push(Line::from(Span::styled("No TODO/FIXME/HACK comments found.", hint_style)));
+    } else {
+        // Build all rows first (file headers interleave with items), then scroll to the cursor
+        let mut rows: Vec<Line> = Vec::new();
+        let mut cursor_row = 0;
+        let mut current_file: Option<&str> = None;
+        for (idx, item) in scan.items.iter().enumerate() {
+            if !scan.by_age && current_file != Some(item.location.path.as_str()) {
+                current_file = Some(item.location.path.as_str());
+                rows.push(Line::from(Span::styled(
+                    item.location.path.clone(),
+                    Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD),
+                )));
+            }
+
+            let is_cursor = idx == scan.cursor;
+            if is_cursor {
+                cursor_row = rows.len();
+            }
+            let checkbox = if scan.selected.get(idx).copied().unwrap_or(false) { "[x] " } else { "[ ] " };
+            let marker_color = match item.marker.as_str() {
+                "FIXME" => Color::Red,
+                "HACK" => Color::Magenta,
+                _ => Color::Yellow,
+            };
+            let age = item.age_days(now).map(|d| format!("{:>5}d ", d)).unwrap_or_else(|| "   new ".to_string());
+            let location = if scan.by_age { item.location.location() } else { format!(":{}", item.location.line) };
+            let row_style = if is_cursor { Style::default().bg(Color::DarkGray) } else { Style::default() };
+            rows.push(Line::from(vec![
+                Span::styled(if is_cursor { "► " } else { "  " }, Style::default().fg(Color::Yellow)),
+                Span::styled(checkbox, Style::default().fg(Color::Green)),
+                Span::styled(age, hint_style),
+                Span::styled(format!("{:<6}", item.marker), Style::default().fg(marker_color)),
+                Span::styled(item.text.clone(), row_style.fg(Color::White)),
+                Span::styled(format!("  {}", location), hint_style),
             ]));
         }
+
+        let scroll_start = cursor_row.saturating_sub(visible_height.saturating_sub(1));
+        lines.extend(rows.into_iter().skip(scroll_start).take(visible_height));
     }
 
-    lines.push(Line::from(""));
-    lines.push(Line::from(""));
+    let selected = scan.selected.iter().filter(|&&s| s).count();
+    let grouping = if scan.by_age { "oldest first" } else { "by file" };
+    let title = format!(" TODO Scanner: {} items, {} selected ({}) ", scan.items.len(), selected, grouping);
 
-    // Footer with keybindings
-    let editing_hints = if config.editing {
-        "Enter confirm  Esc cancel"
-    } else {
-        "j/k navigate  Enter/l edit  r reset to defaults  Esc/q save & close"
-    };
-    lines.push(Line::from(Span::styled(
-        editing_hints,
-        Style::default().fg(Color::DarkGray),
-    )));
+    let hints = vec![
+        Span::styled(" Space", key_style),
+        Span::styled(" select  ", hint_style),
+        Span::styled("a", key_style),
+        Span::styled(" all  ", hint_style),
+        Span::styled("g", key_style),
+        Span::styled(" file/age  ", hint_style),
+        Span::styled("Enter", key_style),
+        Span::styled(" create Planned tasks  ", hint_style),
+        Span::styled("Esc", key_style),
+        Span::styled(" close ", hint_style),
+    ];
 
     let modal = Paragraph::new(lines)
         .block(
             Block::default()
-                .title(" Settings ")
+                .title(title)
+                .title_bottom(Line::from(hints))
                 .borders(Borders::ALL)
                 .border_style(Style::default().fg(Color::Cyan)),
-        )
-        .style(Style::default().fg(Color::White));
-
-    // Clear area first
+        );
     frame.render_widget(ratatui::widgets::Clear, area);
     frame.render_widget(modal, area);
 }
 
-/// Render the stash management modal
-fn render_stash_modal(frame: &mut Frame, app: &App) {
-    let area = centered_rect(60, 60, frame.area());
-
-    let Some(project) = app.model.active_project() else {
+/// Render the sparse-checkout prompt shown before a task's worktree is created
+fn render_sparse_prompt(frame: &mut Frame, app: &App) {
+    let Some(ref prompt) = app.model.ui_state.sparse_prompt else {
         return;
     };
 
-    let stashes = &project.tracked_stashes;
-    let selected_idx = app.model.ui_state.stash_modal_selected_idx;
+    let task_title = app.model.active_project()
+        .and_then(|p| p.tasks.iter().find(|t| t.id == prompt.task_id))
+        .map(|t| t.short_title.clone().unwrap_or_else(|| t.title.lines().next().unwrap_or("").to_string()))
+        .unwrap_or_default();
 
-    let mut lines = vec![
+    let key_style = Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD);
+    let hint_style = Style::default().fg(Color::DarkGray);
+    let lines = vec![
         Line::from(Span::styled(
-            "Tracked Stashes",
+            format!("Paths to check out for \"{}\":", task_title),
             Style::default().add_modifier(Modifier::BOLD),
         )),
         Line::from(""),
+        Line::from(vec![
+            Span::styled("> ", Style::default().fg(Color::Yellow)),
+            Span::styled(prompt.input.clone(), Style::default().fg(Color::White)),
+            Span::styled("█", Style::default().fg(Color::Yellow)),
+        ]),
+        Line::from(""),
+        Line::from(Span::styled(
+            "Gitignore-style patterns separated by spaces or commas. Empty = full checkout.",
+            hint_style,
+        )),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled("Enter", key_style),
+            Span::styled(" start  ", hint_style),
+            Span::styled("Ctrl-U", key_style),
+            Span::styled(" clear  ", hint_style),
+            Span::styled("Esc", key_style),
+            Span::styled(" cancel", hint_style),
+        ]),
     ];
 
-    if stashes.is_empty() {
-        lines.push(Line::from(Span::styled(
-            "No tracked stashes",
-            Style::default().fg(Color::DarkGray),
-        )));
-    } else {
-        let label_style = Style::default().fg(Color::DarkGray);
-        let value_style = Style::default().fg(Color::White);
-        let _key_style = Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD);
-
-        for (idx, stash) in stashes.iter().enumerate() {
-            let is_selected = idx == selected_idx;
-            let prefix = if is_selected { "► " } else { "  " };
-            let style = if is_selected {
-                Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)
-            } else {
-                Style::default()
-            };
-
-            // Stash header: icon + short SHA + description
-            lines.push(Line::from(vec![
-                Span::styled(prefix, style),
-                Span::styled("📦 ", style),
-                Span::styled(&stash.stash_sha[..8.min(stash.stash_sha.len())], Style::default().fg(Color::Magenta)),
-                Span::styled(" ", style),
-                Span::styled(&stash.description, style),
-            ]));
-
-            // If selected, show details
-            if is_selected {
-                // Time since created
-                let elapsed = chrono::Utc::now().signed_duration_since(stash.created_at);
-                let time_ago = if elapsed.num_minutes() < 1 {
-                    "just now".to_string()
-                } else if elapsed.num_hours() < 1 {
-                    format!("{}m ago", elapsed.num_minutes())
-                } else if elapsed.num_hours() < 24 {
-                    format!("{}h ago", elapsed.num_hours())
-                } else {
-                    format!("{}d ago", elapsed.num_days())
-                };
-
-                lines.push(Line::from(vec![
-                    Span::raw("      "),
-                    Span::styled("Created: ", label_style),
-                    Span::styled(time_ago, value_style),
-                    Span::styled("  │  ", label_style),
-                    Span::styled(format!("{} files changed", stash.files_changed), value_style),
-                ]));
-
-                if !stash.files_summary.is_empty() {
-                    // Show files summary, truncated if needed
-                    let summary = if stash.files_summary.len() > 40 {
-                        format!("{}...", &stash.files_summary[..37])
-                    } else {
-                        stash.files_summary.clone()
-                    };
-                    lines.push(Line::from(vec![
-                        Span::raw("      "),
-                        Span::styled("Files: ", label_style),
-                        Span::styled(summary, Style::default().fg(Color::Gray)),
-                    ]));
-                }
+    let modal = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .title(" Sparse Checkout ")
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Cyan)),
+        )
+        .wrap(ratatui::widgets::Wrap { trim: false });
+    let area = centered_rect(60, 30, frame.area());
+    frame.render_widget(ratatui::widgets::Clear, area);
+    frame.render_widget(modal, area);
+}
+
+/// Render the "what's new" screen: new features since the last version seen, with
+/// a shortcut to the setting behind the highlighted one
+fn render_whats_new(frame: &mut Frame, app: &App) {
+    let Some(ref whats_new) = app.model.ui_state.whats_new else {
+        return;
+    };
+    let area = centered_rect(60, 70, frame.area());
+
+    let key_style = Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD);
+    let hint_style = Style::default().fg(Color::DarkGray);
+    let mut lines: Vec<Line> = Vec::new();
+    let mut cursor_line = 0;
+    let mut last_version = "";
 
+    for (idx, (version, feature)) in whats_new.features.iter().enumerate() {
+        if *version != last_version {
+            if !lines.is_empty() {
                 lines.push(Line::from(""));
             }
+            lines.push(Line::from(Span::styled(
+                format!("Version {}", version),
+                Style::default().fg(Color::Green).add_modifier(Modifier::BOLD),
+            )));
+            last_version = version;
+        }
+
+        let selected = idx == whats_new.cursor;
+        if selected {
+            cursor_line = lines.len();
+        }
+        let title_style = if selected {
+            Style::default().fg(Color::Black).bg(Color::Cyan).add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(Color::White).add_modifier(Modifier::BOLD)
+        };
+        let mut title = vec![
+            Span::raw(if selected { "▸ " } else { "  " }),
+            Span::styled(feature.title, title_style),
+        ];
+        if let Some(setting) = feature.setting {
+            title.push(Span::styled(format!("  ({})", setting.label()), hint_style));
         }
+        lines.push(Line::from(title));
+        lines.push(Line::from(Span::styled(format!("    {}", feature.summary), Style::default().fg(Color::Gray))));
     }
 
-    lines.push(Line::from(""));
-    lines.push(Line::from(Span::styled("─".repeat(40), Style::default().fg(Color::DarkGray))));
-    lines.push(Line::from(""));
+    let mut hints = vec![
+        Span::styled("j/k", key_style),
+        Span::styled(" move  ", hint_style),
+    ];
+    let has_setting = whats_new.features.get(whats_new.cursor).is_some_and(|(_, f)| f.setting.is_some());
+    if has_setting {
+        hints.push(Span::styled("Enter", key_style));
+        hints.push(Span::styled(" open setting  ", hint_style));
+    }
+    hints.push(Span::styled("Esc", key_style));
+    hints.push(Span::styled(" close", hint_style));
+
+    // Keep the highlighted feature in view
+    let visible = area.height.saturating_sub(2) as usize;
+    let scroll = (cursor_line + 2).saturating_sub(visible) as u16;
+
+    let modal = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .title(" What's New ")
+                .title_bottom(Line::from(hints))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Green)),
+        )
+        .wrap(ratatui::widgets::Wrap { trim: false })
+        .scroll((scroll, 0));
+    frame.render_widget(ratatui::widgets::Clear, area);
+    frame.render_widget(modal, area);
+}
+
+/// Render the release notes modal (starting point input, then Markdown preview)
+fn render_release_notes(frame: &mut Frame, app: &App) {
+    let area = centered_rect(70, 75, frame.area());
+
+    let Some(ref notes) = app.model.ui_state.release_notes else {
+        return;
+    };
 
-    // Key hints
     let key_style = Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD);
     let hint_style = Style::default().fg(Color::DarkGray);
+    let mut lines: Vec<Line> = Vec::new();
 
-    if !stashes.is_empty() {
+    let Some(text) = notes.current_text() else {
+        // Input phase: ask for the tag or date to start from
+        lines.push(Line::from(Span::styled(
+            "Compile notes for tasks merged since:",
+            Style::default().add_modifier(Modifier::BOLD),
+        )));
+        lines.push(Line::from(""));
         lines.push(Line::from(vec![
-            Span::styled("p", key_style),
-            Span::styled(" pop  ", hint_style),
-            Span::styled("d", key_style),
-            Span::styled(" drop  ", hint_style),
-            Span::styled("j/k", key_style),
-            Span::styled(" navigate  ", hint_style),
-            Span::styled("Esc/S/q", key_style),
-            Span::styled(" close", hint_style),
+            Span::styled("> ", Style::default().fg(Color::Yellow)),
+            Span::styled(notes.since_input.clone(), Style::default().fg(Color::White)),
+            Span::styled("█", Style::default().fg(Color::Yellow)),
         ]));
-    } else {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            "A git tag (e.g. v1.2.0) or a date (YYYY-MM-DD). Tasks are grouped by their #labels.",
+            hint_style,
+        )));
+        lines.push(Line::from(""));
         lines.push(Line::from(vec![
-            Span::styled("Esc/S/q", key_style),
-            Span::styled(" close", hint_style),
+            Span::styled("Enter", key_style),
+            Span::styled(" compile  ", hint_style),
+            Span::styled("Esc", key_style),
+            Span::styled(" cancel", hint_style),
         ]));
+
+        let modal = Paragraph::new(lines)
+            .block(
+                Block::default()
+                    .title(" Release Notes ")
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::Green)),
+            )
+            .wrap(ratatui::widgets::Wrap { trim: false });
+        let input_area = centered_rect(60, 30, frame.area());
+        frame.render_widget(ratatui::widgets::Clear, input_area);
+        frame.render_widget(modal, input_area);
+        return;
+    };
+
+    // Preview phase: Markdown with headings highlighted
+    for line in text.lines() {
+        let style = if line.starts_with('#') {
+            Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(Color::White)
+        };
+        lines.push(Line::from(Span::styled(line.to_string(), style)));
+    }
+
+    let title = if notes.polishing {
+        " Release Notes (polishing...) ".to_string()
+    } else if notes.show_polished && notes.polished.is_some() {
+        " Release Notes (polished) ".to_string()
+    } else {
+        format!(" Release Notes since {} ", notes.since_input)
+    };
+
+    let mut hints = vec![
+        Span::styled("w", key_style),
+        Span::styled(format!(" write {}  ", crate::release::RELEASE_NOTES_FILE), hint_style),
+        Span::styled("y", key_style),
+        Span::styled(" copy  ", hint_style),
+        Span::styled("p", key_style),
+        Span::styled(" polish  ", hint_style),
+    ];
+    if notes.polished.is_some() {
+        hints.push(Span::styled("Tab", key_style));
+        hints.push(Span::styled(" raw/polished  ", hint_style));
     }
+    hints.extend([
+        Span::styled("e", key_style),
+        Span::styled(" change start  ", hint_style),
+        Span::styled("Esc", key_style),
+        Span::styled(" close", hint_style),
+    ]);
 
     let modal = Paragraph::new(lines)
         .block(
             Block::default()
-                .title(" Stash Manager ")
+                .title(title)
+                .title_bottom(Line::from(hints))
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::Yellow)),
+                .border_style(Style::default().fg(Color::Green)),
         )
-        .style(Style::default().fg(Color::White));
+        .wrap(ratatui::widgets::Wrap { trim: false })
+        .scroll((notes.scroll_offset as u16, 0));
 
     frame.render_widget(ratatui::widgets::Clear, area);
     frame.render_widget(modal, area);
@@ -4055,6 +7408,312 @@ fn render_sidecar_modal(frame: &mut Frame, app: &App) {
     frame.render_widget(modal_widget, area);
 }
 
+/// Render the feedback snippet picker (Ctrl+T in feedback mode)
+/// Render the slash command autocomplete popup just above the task input
+fn render_slash_completions(frame: &mut Frame, input_area: Rect, app: &App) {
+    let completions = app.slash_completions();
+    if completions.is_empty() {
+        return;
+    }
+    let word_width = completions.iter().map(|c| c.word.chars().count()).max().unwrap_or(0);
+    let lines: Vec<Line> = completions
+        .iter()
+        .enumerate()
+        .map(|(idx, completion)| {
+            let word_style = if idx == 0 {
+                Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(Color::White)
+            };
+            Line::from(vec![
+                Span::styled(format!(" {:<width$} ", completion.word, width = word_width), word_style),
+                Span::styled(format!("{} ", completion.hint), Style::default().fg(Color::DarkGray)),
+            ])
+        })
+        .collect();
+
+    let content_width = lines.iter().map(|l| l.width()).max().unwrap_or(0) as u16;
+    let width = (content_width + 2).max(22).min(input_area.width);
+    let height = (lines.len() as u16 + 2).min(input_area.y);
+    if height < 3 {
+        return;
+    }
+    let area = Rect {
+        x: input_area.x + 1,
+        y: input_area.y - height,
+        width: width.min(input_area.width.saturating_sub(1)),
+        height,
+    };
+    let popup = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Yellow))
+            .title(Span::styled(" Tab ", Style::default().fg(Color::Cyan))),
+    );
+    frame.render_widget(ratatui::widgets::Clear, area);
+    frame.render_widget(popup, area);
+}
+
+fn render_snippet_picker(frame: &mut Frame, app: &App) {
+    let Some(cursor) = app.model.ui_state.snippet_picker else {
+        return;
+    };
+    let Some(project) = app.model.active_project() else {
+        return;
+    };
+    let task = app.model.ui_state.feedback_task_id
+        .and_then(|id| project.tasks.iter().find(|t| t.id == id));
+
+    let area = centered_rect(60, 50, frame.area());
+    let selected_style = Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD);
+    let number_style = Style::default().fg(Color::Cyan);
+    let max_len = area.width.saturating_sub(9) as usize;
+
+    let mut lines: Vec<Line> = vec![Line::from("")];
+    for (idx, snippet) in project.snippets().into_iter().enumerate() {
+        let is_selected = idx == cursor;
+        let prefix = if is_selected { "► " } else { "  " };
+        let number = if idx < 9 { format!("{} ", idx + 1) } else { "  ".to_string() };
+        // Preview with placeholders filled in for the task receiving feedback
+        let text = task.map_or_else(|| snippet.to_string(), |t| t.fill_snippet(snippet));
+        let text = text.lines().next().unwrap_or_default();
+        let text = if text.chars().count() > max_len {
+            format!("{}…", text.chars().take(max_len.saturating_sub(1)).collect::<String>())
+        } else {
+            text.to_string()
+        };
+        let style = if is_selected { selected_style } else { Style::default() };
+        lines.push(Line::from(vec![
+            Span::styled(prefix, style),
+            Span::styled(number, number_style),
+            Span::styled(text, style),
+        ]));
+    }
+
+    let key_style = Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD);
+    let hint_style = Style::default().fg(Color::DarkGray);
+    lines.push(Line::from(""));
+    lines.push(Line::from(vec![
+        Span::styled("  ↑/↓", key_style),
+        Span::styled(" navigate  ", hint_style),
+        Span::styled("Enter/1-9", key_style),
+        Span::styled(" insert  ", hint_style),
+        Span::styled("Esc", key_style),
+        Span::styled(" cancel", hint_style),
+    ]));
+    lines.push(Line::from(Span::styled(
+        "  Edit snippets in Settings (Feedback Snippets)",
+        hint_style,
+    )));
+
+    let modal_widget = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .title(" Feedback Snippets (Ctrl+T) ")
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Cyan)),
+        )
+        .style(Style::default().fg(Color::White));
+
+    frame.render_widget(ratatui::widgets::Clear, area);
+    frame.render_widget(modal_widget, area);
+}
+
+fn render_link_picker(frame: &mut Frame, app: &App) {
+    let Some((task_id, cursor)) = app.model.ui_state.link_picker else {
+        return;
+    };
+    let Some(task) = app.model.projects.iter().flat_map(|p| p.tasks.iter()).find(|t| t.id == task_id) else {
+        return;
+    };
+
+    let area = centered_rect(70, 50, frame.area());
+    let selected_style = Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD);
+    let number_style = Style::default().fg(Color::Cyan);
+    let label_style = Style::default().fg(Color::DarkGray);
+
+    let links = task.links();
+    let label_width = links.iter().map(|(label, _)| label.chars().count()).max().unwrap_or(0);
+    let mut lines: Vec<Line> = vec![Line::from("")];
+    for (idx, (label, url)) in links.iter().enumerate() {
+        let is_selected = idx == cursor;
+        let prefix = if is_selected { "► " } else { "  " };
+        let number = if idx < 9 { format!("{} ", idx + 1) } else { "  ".to_string() };
+        let style = if is_selected { selected_style } else { Style::default() };
+        lines.push(Line::from(vec![
+            Span::styled(prefix, style),
+            Span::styled(number, number_style),
+            Span::styled(format!("{:<width$}  ", label, width = label_width), label_style),
+            Span::styled(url.clone(), style),
+        ]));
+    }
+
+    let key_style = Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD);
+    let hint_style = Style::default().fg(Color::DarkGray);
+    lines.push(Line::from(""));
+    lines.push(Line::from(vec![
+        Span::styled("  ↑/↓", key_style),
+        Span::styled(" navigate  ", hint_style),
+        Span::styled("Enter/1-9", key_style),
+        Span::styled(" open  ", hint_style),
+        Span::styled("y", key_style),
+        Span::styled(" copy  ", hint_style),
+        Span::styled("Esc", key_style),
+        Span::styled(" cancel", hint_style),
+    ]));
+
+    let modal_widget = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .title(format!(" Links: {} ", task.display_id()))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Cyan)),
+        )
+        .style(Style::default().fg(Color::White));
+
+    frame.render_widget(ratatui::widgets::Clear, area);
+    frame.render_widget(modal_widget, area);
+}
+
+/// Render the commit message editor shown before a merge
+fn render_commit_editor(frame: &mut Frame, app: &App) {
+    let Some(ref editor) = app.model.ui_state.commit_editor else {
+        return;
+    };
+    let display_id = app.model.projects.iter()
+        .flat_map(|p| p.tasks.iter())
+        .find(|t| t.id == editor.task_id)
+        .map(|t| t.display_id())
+        .unwrap_or_default();
+
+    let area = centered_rect(70, 50, frame.area());
+    let hint_style = Style::default().fg(Color::DarkGray);
+    let key_style = Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD);
+
+    let mut lines: Vec<Line> = vec![Line::from("")];
+    if let Some(ref note) = editor.note {
+        lines.push(Line::from(Span::styled(format!("  {}", note), Style::default().fg(Color::Yellow))));
+        lines.push(Line::from(""));
+    }
+    if editor.generating {
+        lines.push(Line::from(Span::styled("  Writing a commit message from the diff and spec...", hint_style)));
+    } else {
+        let count = editor.input.split('\n').count();
+        for (idx, text) in editor.input.split('\n').enumerate() {
+            // The subject line stands out; the cursor sits at the end
+            let style = if idx == 0 { Style::default().add_modifier(Modifier::BOLD) } else { Style::default() };
+            let cursor = if idx + 1 == count { "_" } else { "" };
+            lines.push(Line::from(Span::styled(format!("  {}{}", text, cursor), style)));
+        }
+    }
+
+    lines.push(Line::from(""));
+    lines.push(Line::from(vec![
+        Span::styled("  Enter", key_style),
+        Span::styled(if editor.merge_only { " merge (keep in Review)  " } else { " merge and mark done  " }, hint_style),
+        Span::styled("Alt+Enter", key_style),
+        Span::styled(" new line  ", hint_style),
+        Span::styled("Ctrl+U", key_style),
+        Span::styled(" clear  ", hint_style),
+        Span::styled("Esc", key_style),
+        Span::styled(" cancel merge", hint_style),
+    ]));
+
+    let modal_widget = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .title(format!(" Commit message: {} ", display_id))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Cyan)),
+        )
+        .style(Style::default().fg(Color::White))
+        .wrap(ratatui::widgets::Wrap { trim: false });
+
+    frame.render_widget(ratatui::widgets::Clear, area);
+    frame.render_widget(modal_widget, area);
+}
+
+/// Render why a task's start failed, with its retry options
+fn render_start_failure(frame: &mut Frame, app: &App) {
+    let Some((task_id, scroll)) = app.model.ui_state.start_failure else {
+        return;
+    };
+    let Some(task) = app.model.active_project().and_then(|p| p.tasks.iter().find(|t| t.id == task_id)) else {
+        return;
+    };
+    let Some(ref failure) = task.start_failure else {
+        return;
+    };
+
+    let area = centered_rect(75, 70, frame.area());
+    let label_style = Style::default().fg(Color::DarkGray);
+    let hint_style = Style::default().fg(Color::DarkGray);
+    let key_style = Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD);
+
+    let when = failure.failed_at.with_timezone(&chrono::Local).format("%b %d %H:%M").to_string();
+    let attempts = if failure.attempts == 1 {
+        "1 failed start".to_string()
+    } else {
+        format!("{} failed starts in a row", failure.attempts)
+    };
+    let mut lines: Vec<Line> = vec![
+        Line::from(""),
+        Line::from(vec![
+            Span::styled("  Step:      ", label_style),
+            Span::styled(failure.phase.label(), Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
+        ]),
+        Line::from(vec![
+            Span::styled("  When:      ", label_style),
+            Span::raw(format!("{} ({})", when, attempts)),
+        ]),
+        Line::from(vec![
+            Span::styled("  Worktree:  ", label_style),
+            Span::raw(match task.worktree_path {
+                Some(ref path) => format!("{} (kept for the retry)", path.display()),
+                None => "none".to_string(),
+            }),
+        ]),
+        Line::from(""),
+    ];
+    for text in failure.error.lines() {
+        lines.push(Line::from(Span::styled(format!("  {}", text), Style::default().fg(Color::Red))));
+    }
+    if let Some(ref output) = failure.output {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled("  Output:", label_style)));
+        for text in output.lines() {
+            lines.push(Line::from(format!("  {}", text)));
+        }
+    }
+
+    // Keep the last line reachable, no further
+    let visible = area.height.saturating_sub(2) as usize;
+    let scroll = (scroll as usize).min(lines.len().saturating_sub(visible)) as u16;
+
+    let hints = vec![
+        Span::styled(" s", key_style),
+        Span::styled(" retry  ", hint_style),
+        Span::styled("x", key_style),
+        Span::styled(" discard worktree and retry  ", hint_style),
+        Span::styled("j/k", key_style),
+        Span::styled(" scroll  ", hint_style),
+        Span::styled("Esc", key_style),
+        Span::styled(" close ", hint_style),
+    ];
+    let modal = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .title(format!(" Start failed: {} ", task.display_id()))
+                .title_bottom(Line::from(hints))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Red)),
+        )
+        .style(Style::default().fg(Color::White))
+        .scroll((scroll, 0));
+    frame.render_widget(ratatui::widgets::Clear, area);
+    frame.render_widget(modal, area);
+}
+
 /// Render the markdown file picker modal
 fn render_md_file_picker(frame: &mut Frame, app: &App) {
     let picker = match &app.model.ui_state.md_file_picker {
@@ -4074,7 +7733,7 @@ fn render_md_file_picker(frame: &mut Frame, app: &App) {
 
     // Title
     lines.push(Line::from(Span::styled(
-        "Insert Markdown File",
+        if picker.attach { "Attach File" } else { "Insert Markdown File" },
         Style::default().add_modifier(Modifier::BOLD),
     )));
     lines.push(Line::from(""));
@@ -4189,7 +7848,7 @@ fn render_md_file_picker(frame: &mut Frame, app: &App) {
     let modal_widget = Paragraph::new(lines)
         .block(
             Block::default()
-                .title(" Select Markdown File (Ctrl+O) ")
+                .title(if picker.attach { " Attach File (Ctrl+L) " } else { " Select Markdown File (Ctrl+O) " })
                 .borders(Borders::ALL)
                 .border_style(Style::default().fg(Color::Cyan)),
         )
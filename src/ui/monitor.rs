@@ -0,0 +1,166 @@
+use crate::app::App;
+use crate::model::{ClaudeSessionState, MonitorState, Task};
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+    Frame,
+};
+
+/// Render the monitor screen: one tile per in-progress task (across all
+/// projects) showing the latest capture of its terminal window, or its
+/// streamed output when it runs without one
+pub fn render_monitor(frame: &mut Frame, area: Rect, app: &App) {
+    let tasks = app.model.monitor_tasks();
+    let monitor = &app.model.ui_state.monitor;
+
+    let refreshed = match monitor.refreshed_at {
+        Some(at) => format!(" refreshed {}s ago ", at.elapsed().as_secs()),
+        None => " capturing... ".to_string(),
+    };
+    let block = Block::default()
+        .title(Span::styled(
+            format!(" Monitor · {} in progress ", tasks.len()),
+            Style::default().fg(Color::Green).add_modifier(Modifier::BOLD),
+        ))
+        .title(Line::from(Span::styled(refreshed, Style::default().fg(Color::DarkGray))).alignment(Alignment::Right))
+        .title_bottom(Line::from(Span::styled(
+            " h/j/k/l move · Enter show on board · o open window · r refresh · Esc back ",
+            Style::default().fg(Color::DarkGray),
+        )))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Green));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    if tasks.is_empty() {
+        let placeholder = vec![
+            Line::from(""),
+            Line::from(Span::styled("  No tasks in progress", Style::default().fg(Color::DarkGray))),
+        ];
+        frame.render_widget(Paragraph::new(placeholder), inner);
+        return;
+    }
+
+    let show_project = app.model.projects.len() > 1;
+    for (idx, tile) in tile_areas(inner, tasks.len()).into_iter().enumerate() {
+        let (project_idx, task) = tasks[idx];
+        let project_name = show_project.then(|| app.model.projects[project_idx].name.as_str());
+        render_tile(frame, tile, app, task, project_name, idx == monitor.selected);
+    }
+}
+
+/// Areas of the tiles in a grid as square as the tile count allows
+fn tile_areas(area: Rect, tiles: usize) -> Vec<Rect> {
+    let columns = MonitorState::columns(tiles);
+    let rows = tiles.div_ceil(columns);
+    let row_areas = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(vec![Constraint::Ratio(1, rows as u32); rows])
+        .split(area);
+    row_areas.iter()
+        .flat_map(|row| {
+            Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints(vec![Constraint::Ratio(1, columns as u32); columns])
+                .split(*row)
+                .to_vec()
+        })
+        .take(tiles)
+        .collect()
+}
+
+fn render_tile(frame: &mut Frame, area: Rect, app: &App, task: &Task, project_name: Option<&str>, selected: bool) {
+    let border_style = if selected {
+        Style::default().fg(Color::Green)
+    } else {
+        Style::default().fg(Color::DarkGray)
+    };
+    let title_style = if selected {
+        Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)
+    } else {
+        Style::default().fg(Color::White)
+    };
+    let state_color = match task.session_state {
+        ClaudeSessionState::Working | ClaudeSessionState::Continuing => Color::Green,
+        ClaudeSessionState::Creating | ClaudeSessionState::Starting => Color::Yellow,
+        ClaudeSessionState::Paused => Color::Magenta,
+        _ => Color::DarkGray,
+    };
+
+    let mut header = vec![Span::styled(task.session_state.label(), Style::default().fg(state_color))];
+    if let Some(started_at) = task.started_at {
+        header.push(Span::styled(
+            format!(" · {}", super::format_duration(chrono::Utc::now() - started_at)),
+            Style::default().fg(Color::DarkGray),
+        ));
+    }
+    if let Some(name) = project_name {
+        header.push(Span::styled(format!(" · {}", name), Style::default().fg(Color::Cyan)));
+    }
+    if task.tmux_window.is_some() {
+        header.push(Span::styled(" · window", Style::default().fg(Color::DarkGray)));
+    }
+
+    let block = Block::default()
+        .title(Span::styled(format!(" {} {} ", task.display_id(), task.short_title.as_deref().unwrap_or(&task.title)), title_style))
+        .title_bottom(Line::from(header))
+        .borders(Borders::ALL)
+        .border_style(border_style);
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    // The terminal capture when there is one, otherwise the streamed output
+    let text = app.model.ui_state.monitor.captures.get(&task.id)
+        .map(String::as_str)
+        .unwrap_or_else(|| task.live_output_text());
+    let lines: Vec<&str> = text.lines().filter(|l| !l.trim().is_empty()).collect();
+    let height = inner.height as usize;
+    let content: Vec<Line> = if lines.is_empty() {
+        let waiting = task.activity_timeline().last()
+            .map(|entry| entry.message.clone())
+            .unwrap_or_else(|| "Waiting for output...".to_string());
+        vec![Line::from(Span::styled(waiting, Style::default().fg(Color::DarkGray)))]
+    } else {
+        lines[lines.len().saturating_sub(height)..].iter()
+            .map(|line| Line::from(*line))
+            .collect()
+    };
+    frame.render_widget(Paragraph::new(content), inner);
+}
+
+/// Index of the tile at a position, given the area the monitor is drawn in
+/// (uses the same layout as the renderer)
+pub fn hit_test_monitor(area: Rect, tiles: usize, x: u16, y: u16) -> Option<usize> {
+    if tiles == 0 {
+        return None;
+    }
+    let inner = Block::default().borders(Borders::ALL).inner(area);
+    tile_areas(inner, tiles).iter()
+        .position(|tile| tile.contains(ratatui::layout::Position::new(x, y)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tiles_fill_a_square_grid_and_hit_test_matches() {
+        assert_eq!(MonitorState::columns(1), 1);
+        assert_eq!(MonitorState::columns(4), 2);
+        assert_eq!(MonitorState::columns(5), 3);
+
+        let area = Rect::new(0, 0, 62, 22);
+        let tiles = tile_areas(Block::default().borders(Borders::ALL).inner(area), 5);
+        assert_eq!(tiles.len(), 5);
+        // Three across the first row, two on the second
+        assert_eq!(tiles[0].y, tiles[2].y);
+        assert!(tiles[3].y > tiles[0].y);
+        for (idx, tile) in tiles.iter().enumerate() {
+            assert_eq!(hit_test_monitor(area, 5, tile.x, tile.y), Some(idx));
+        }
+        // The border isn't part of any tile
+        assert_eq!(hit_test_monitor(area, 5, 0, 0), None);
+    }
+}
@@ -1,173 +1,204 @@
-#![allow(dead_code)]
-
 use crate::app::App;
-use crate::model::FocusArea;
+use crate::model::{FocusArea, OutputPaneState, ScrollbackViewerState};
 use ratatui::{
-    layout::Rect,
+    layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Paragraph, Wrap},
+    widgets::{Block, Borders, Paragraph},
     Frame,
 };
 
-/// Render the Claude output viewer pane
-pub fn render_output(frame: &mut Frame, area: Rect, app: &App) {
+/// Split the board area between the board and the live output pane:
+/// side by side on wide terminals, stacked otherwise. Returns (board, pane).
+pub fn split_output_pane(area: Rect) -> (Rect, Rect) {
+    let direction = if area.width >= 180 { Direction::Horizontal } else { Direction::Vertical };
+    let split = Layout::default()
+        .direction(direction)
+        .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
+        .split(area);
+    (split[0], split[1])
+}
+
+/// Render the live output pane, tailing the session output of the selected
+/// in-progress task (streamed from the sidecar as it arrives)
+pub fn render_output(frame: &mut Frame, area: Rect, app: &App, pane: &OutputPaneState) {
     let is_focused = app.model.ui_state.focus == FocusArea::OutputViewer;
+    let border_style = if is_focused {
+        Style::default().fg(Color::Green)
+    } else {
+        Style::default().fg(Color::DarkGray)
+    };
+    let title_style = if is_focused {
+        Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)
+    } else {
+        Style::default().fg(Color::DarkGray)
+    };
+
+    let Some(task) = app.model.output_pane_task() else {
+        let block = Block::default()
+            .title(Span::styled(" Live Output ", title_style))
+            .borders(Borders::ALL)
+            .border_style(border_style);
+        let placeholder = vec![
+            Line::from(""),
+            Line::from(Span::styled(
+                "  Select an in-progress task to follow its output",
+                Style::default().fg(Color::DarkGray),
+            )),
+        ];
+        frame.render_widget(Paragraph::new(placeholder).block(block), area);
+        return;
+    };
+
+    let output = task.live_output_text();
+    let lines: Vec<&str> = output.lines().collect();
+    let matches = pane.matching_lines(output);
+    let current_line = pane.current_match.and_then(|idx| matches.get(idx)).copied();
 
-    let (title, content) = if let Some(project) = app.model.active_project() {
-        if let Some(task) = project.in_progress_task() {
-            // Show captured output if available
-            let lines: Vec<Line> = if !project.captured_output.is_empty() {
-                project.captured_output
-                    .lines()
-                    .map(|l| Line::from(l.to_string()))
-                    .collect()
-            } else {
-                get_placeholder_output(&task.title)
-            };
-
-            (
-                format!(" Claude: {} ", truncate(&task.title, 30)),
-                lines,
-            )
-        } else if !project.captured_output.is_empty() {
-            // Show last output even when no task in progress
-            let lines: Vec<Line> = project.captured_output
-                .lines()
-                .map(|l| Line::from(l.to_string()))
-                .collect();
-            (
-                " Claude Output (last run) ".to_string(),
-                lines,
-            )
-        } else {
-            (
-                " Claude Output ".to_string(),
-                vec![
-                    Line::from(""),
-                    Line::from(Span::styled(
-                        "No task in progress",
-                        Style::default().fg(Color::DarkGray),
-                    )),
-                    Line::from(""),
-                    Line::from(Span::styled(
-                        "Select a task and press 's' to start",
-                        Style::default().fg(Color::DarkGray),
-                    )),
-                ],
-            )
+    // Show the lines ending where the view is held (or at the newest line)
+    let height = area.height.saturating_sub(2) as usize;
+    let end = pane.view_end(lines.len());
+    let start = end.saturating_sub(height);
+    let query = pane.search.to_lowercase();
+    let content: Vec<Line> = lines[start..end].iter().enumerate()
+        .map(|(offset, line)| {
+            let is_current = current_line == Some(start + offset);
+            highlight_line(line, &query, is_current)
+        })
+        .collect();
+
+    let state = match pane.paused_at {
+        Some(held_at) => {
+            let new_lines = lines.len().saturating_sub(held_at);
+            let suffix = if new_lines > 0 { format!(", {} new lines", new_lines) } else { String::new() };
+            Span::styled(format!("‖ paused{} ", suffix), Style::default().fg(Color::Yellow))
         }
-    } else {
-        (
-            " Claude Output ".to_string(),
-            vec![
-                Line::from(""),
-                Line::from(Span::styled(
-                    "No project selected",
-                    Style::default().fg(Color::DarkGray),
-                )),
-                Line::from(""),
-                Line::from(Span::styled(
-                    "Add a project or wait for auto-detection",
-                    Style::default().fg(Color::DarkGray),
-                )),
-            ],
-        )
+        None => Span::styled("● following ", Style::default().fg(Color::Green)),
     };
+    let title = Line::from(vec![
+        Span::styled(format!(" Live: {} ", truncate(&task.title, 40)), title_style),
+        state,
+    ]);
 
-    let border_style = if is_focused {
-        Style::default().fg(Color::Green)
+    let footer = if pane.search_editing {
+        Line::from(Span::styled(format!(" /{}_ ", pane.search), Style::default().fg(Color::Yellow)))
+    } else if !pane.search.is_empty() {
+        let position = match pane.current_match {
+            Some(idx) => format!("{}/{}", idx + 1, matches.len()),
+            None => format!("{} matches", matches.len()),
+        };
+        Line::from(Span::styled(
+            format!(" /{}  {}  n/N next/prev ", pane.search, position),
+            Style::default().fg(Color::Yellow),
+        ))
+    } else if is_focused {
+        Line::from(Span::styled(
+            " j/k scroll · space pause · G follow · / search · Esc board ",
+            Style::default().fg(Color::DarkGray),
+        ))
     } else {
-        Style::default().fg(Color::DarkGray)
+        Line::from(Span::styled(" Tab to focus ", Style::default().fg(Color::DarkGray)))
     };
 
     let block = Block::default()
-        .title(Span::styled(
-            &title,
-            if is_focused {
-                Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)
-            } else {
-                Style::default().fg(Color::DarkGray)
-            },
-        ))
+        .title(title)
+        .title_bottom(footer)
         .borders(Borders::ALL)
         .border_style(border_style);
-
-    let output = Paragraph::new(content)
-        .block(block)
-        .wrap(Wrap { trim: false });
-
-    frame.render_widget(output, area);
-
-    // Render status indicator in corner
-    if let Some(project) = app.model.active_project() {
-        if project.in_progress_task().is_some() {
-            let indicator = Span::styled(
-                " RUNNING ",
-                Style::default()
-                    .fg(Color::Black)
-                    .bg(Color::Yellow)
-                    .add_modifier(Modifier::BOLD),
-            );
-            let indicator_area = Rect {
-                x: area.x + area.width.saturating_sub(12),
-                y: area.y,
-                width: 10,
-                height: 1,
-            };
-            frame.render_widget(Paragraph::new(indicator), indicator_area);
-        } else if project.needs_attention {
-            let indicator = Span::styled(
-                " REVIEW ",
-                Style::default()
-                    .fg(Color::White)
-                    .bg(Color::Magenta)
-                    .add_modifier(Modifier::BOLD),
-            );
-            let indicator_area = Rect {
-                x: area.x + area.width.saturating_sub(10),
-                y: area.y,
-                width: 9,
-                height: 1,
-            };
-            frame.render_widget(Paragraph::new(indicator), indicator_area);
-        }
-    }
+    frame.render_widget(Paragraph::new(content).block(block), area);
 }
 
-/// Placeholder output for demo (will be replaced with actual tmux capture)
-fn get_placeholder_output(task_title: &str) -> Vec<Line<'static>> {
-    vec![
-        Line::from(""),
-        Line::from(Span::styled(
-            format!("> claude \"{}\"", task_title),
-            Style::default().fg(Color::Green),
-        )),
-        Line::from(""),
+/// Render the pager over a captured session scrollback, with search like the
+/// live output pane
+pub fn render_scrollback(frame: &mut Frame, viewer: &ScrollbackViewerState) {
+    let area = super::centered_rect(90, 90, frame.area());
+    frame.render_widget(ratatui::widgets::Clear, area);
+
+    let view = &viewer.view;
+    let lines: Vec<&str> = viewer.text.lines().collect();
+    let matches = view.matching_lines(&viewer.text);
+    let current_line = view.current_match.and_then(|idx| matches.get(idx)).copied();
+
+    let height = area.height.saturating_sub(2) as usize;
+    // Never leave the bottom of the pager empty, even on a small terminal
+    let end = view.view_end(lines.len()).max(height.min(lines.len()));
+    let start = end.saturating_sub(height);
+    let query = view.search.to_lowercase();
+    let content: Vec<Line> = lines[start..end].iter().enumerate()
+        .map(|(offset, line)| highlight_line(line, &query, current_line == Some(start + offset)))
+        .collect();
+
+    let file_name = viewer.path.file_name().map_or_else(String::new, |n| n.to_string_lossy().to_string());
+    let title = Line::from(vec![
+        Span::styled(format!(" Scrollback: {} ", file_name), Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+        Span::styled(format!("lines {}-{} of {} ", start + 1, end, lines.len()), Style::default().fg(Color::DarkGray)),
+    ]);
+    let footer = if view.search_editing {
+        Line::from(Span::styled(format!(" /{}_ ", view.search), Style::default().fg(Color::Yellow)))
+    } else if !view.search.is_empty() {
+        let position = match view.current_match {
+            Some(idx) => format!("{}/{}", idx + 1, matches.len()),
+            None => format!("{} matches", matches.len()),
+        };
         Line::from(Span::styled(
-            "Claude is analyzing the task...",
+            format!(" /{}  {}  n/N next/prev · Esc clear ", view.search, position),
             Style::default().fg(Color::Yellow),
-        )),
-        Line::from(""),
-        Line::from("This pane will show live output from the Claude Code"),
-        Line::from("session running in tmux. The output is captured using"),
-        Line::from("`tmux capture-pane` and streamed here in real-time."),
-        Line::from(""),
-        Line::from(Span::styled(
-            "When Claude finishes, the task will automatically move",
-            Style::default().fg(Color::DarkGray),
-        )),
+        ))
+    } else {
         Line::from(Span::styled(
-            "to the Review column and you'll hear a notification.",
+            " j/k scroll · g/G start/end · / search · Esc close ",
             Style::default().fg(Color::DarkGray),
-        )),
-    ]
+        ))
+    };
+
+    let block = Block::default()
+        .title(title)
+        .title_bottom(footer)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan));
+    frame.render_widget(Paragraph::new(content).block(block), area);
+}
+
+/// A line of output with occurrences of the (lowercase) search query highlighted
+fn highlight_line<'a>(line: &'a str, query: &str, is_current: bool) -> Line<'a> {
+    let base = if is_current {
+        Style::default().add_modifier(Modifier::BOLD)
+    } else {
+        Style::default()
+    };
+    let lower = line.to_lowercase();
+    let found: Vec<usize> = if query.is_empty() {
+        Vec::new()
+    } else {
+        lower.match_indices(query).map(|(idx, _)| idx).collect()
+    };
+    // Only highlight when lowercasing kept the byte offsets of the matches intact
+    let aligned = lower.len() == line.len()
+        && found.iter().all(|&idx| line.is_char_boundary(idx) && line.is_char_boundary(idx + query.len()));
+    if found.is_empty() || !aligned {
+        return Line::from(Span::styled(line, base));
+    }
+
+    let highlight = Style::default().fg(Color::Black).bg(if is_current { Color::Yellow } else { Color::Cyan });
+    let mut spans = Vec::new();
+    let mut pos = 0;
+    for idx in found {
+        if idx > pos {
+            spans.push(Span::styled(&line[pos..idx], base));
+        }
+        spans.push(Span::styled(&line[idx..idx + query.len()], highlight));
+        pos = idx + query.len();
+    }
+    if pos < line.len() {
+        spans.push(Span::styled(&line[pos..], base));
+    }
+    Line::from(spans)
 }
 
-/// Truncate a string to a maximum length
+/// Truncate a string to a maximum number of characters
 fn truncate(s: &str, max_len: usize) -> String {
-    if s.len() > max_len {
-        format!("{}...", &s[..max_len.saturating_sub(3)])
+    if s.chars().count() > max_len {
+        format!("{}...", s.chars().take(max_len.saturating_sub(3)).collect::<String>())
     } else {
         s.to_string()
     }
@@ -0,0 +1,208 @@
+//! Colorblind-safe palettes and a monochrome mode, applied to every frame
+//!
+//! Renderers keep drawing in the standard colors; once a frame is drawn its
+//! cells are remapped to the selected palette. The colorblind palettes move
+//! each hue family onto colors from the Okabe–Ito set, tuned per deficiency.
+//! Monochrome drops color entirely: colored backgrounds (selections,
+//! highlights) turn into reversed text, and what was red or yellow (errors,
+//! warnings) is set in bold, red also underlined, next to the glyphs and
+//! labels that already mark status.
+
+use crate::model::Palette;
+use ratatui::buffer::Buffer;
+use ratatui::style::{Color, Modifier};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static PALETTE: AtomicUsize = AtomicUsize::new(0);
+
+/// Select the palette every following frame is drawn in
+pub fn set(palette: Palette) {
+    let idx = Palette::all().iter().position(|p| *p == palette).unwrap_or(0);
+    PALETTE.store(idx, Ordering::Relaxed);
+}
+
+/// The palette frames are drawn in
+pub fn current() -> Palette {
+    Palette::all().get(PALETTE.load(Ordering::Relaxed)).copied().unwrap_or_default()
+}
+
+/// Remap a drawn frame to the current palette
+pub fn apply(buffer: &mut Buffer) {
+    let palette = current();
+    if palette == Palette::Standard {
+        return;
+    }
+    for cell in &mut buffer.content {
+        if palette == Palette::Monochrome {
+            monochrome(cell);
+        } else {
+            cell.fg = recolor(palette, cell.fg);
+            cell.bg = recolor(palette, cell.bg);
+        }
+    }
+}
+
+/// Hue families the standard colors fall into
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Hue {
+    Red,
+    Yellow,
+    Green,
+    Cyan,
+    Blue,
+    Magenta,
+}
+
+/// Hue family and whether it's the light variant, for colors that have a hue
+fn hue_of(color: Color) -> Option<(Hue, bool)> {
+    Some(match color {
+        Color::Red => (Hue::Red, false),
+        Color::LightRed => (Hue::Red, true),
+        Color::Yellow => (Hue::Yellow, false),
+        Color::LightYellow => (Hue::Yellow, true),
+        Color::Green => (Hue::Green, false),
+        Color::LightGreen => (Hue::Green, true),
+        Color::Cyan => (Hue::Cyan, false),
+        Color::LightCyan => (Hue::Cyan, true),
+        Color::Blue => (Hue::Blue, false),
+        Color::LightBlue => (Hue::Blue, true),
+        Color::Magenta => (Hue::Magenta, false),
+        Color::LightMagenta => (Hue::Magenta, true),
+        Color::Rgb(r, g, b) => (rgb_hue(r, g, b)?, false),
+        _ => return None,
+    })
+}
+
+/// Hue family of an RGB color (None for grays)
+fn rgb_hue(r: u8, g: u8, b: u8) -> Option<Hue> {
+    let (r, g, b) = (f32::from(r), f32::from(g), f32::from(b));
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    if max == 0.0 || (max - min) / max < 0.25 {
+        return None;
+    }
+    let delta = max - min;
+    let hue = if max == r {
+        60.0 * ((g - b) / delta).rem_euclid(6.0)
+    } else if max == g {
+        60.0 * ((b - r) / delta + 2.0)
+    } else {
+        60.0 * ((r - g) / delta + 4.0)
+    };
+    Some(match hue {
+        h if !(20.0..330.0).contains(&h) => Hue::Red,
+        h if h < 70.0 => Hue::Yellow,
+        h if h < 165.0 => Hue::Green,
+        h if h < 200.0 => Hue::Cyan,
+        h if h < 260.0 => Hue::Blue,
+        _ => Hue::Magenta,
+    })
+}
+
+/// Replacement for a hue family in a colorblind palette
+fn swatch(palette: Palette, hue: Hue) -> (u8, u8, u8) {
+    match (palette, hue) {
+        // Red and green look alike: red moves to vermillion, green to bluish green
+        (Palette::Deuteranopia, Hue::Red) => (213, 94, 0),
+        (Palette::Protanopia, Hue::Red) => (240, 120, 0), // reds look darker, so brighter
+        (Palette::Deuteranopia | Palette::Protanopia, Hue::Yellow) => (240, 228, 66),
+        (Palette::Deuteranopia | Palette::Protanopia, Hue::Green) => (0, 158, 115),
+        (Palette::Deuteranopia | Palette::Protanopia, Hue::Cyan) => (86, 180, 233),
+        (Palette::Deuteranopia | Palette::Protanopia, Hue::Blue) => (0, 114, 178),
+        (Palette::Deuteranopia | Palette::Protanopia, Hue::Magenta) => (204, 121, 167),
+        // Blue and green, yellow and violet look alike: keep them apart by lightness
+        (Palette::Tritanopia, Hue::Red) => (222, 45, 38),
+        (Palette::Tritanopia, Hue::Yellow) => (255, 150, 40),
+        (Palette::Tritanopia, Hue::Green) => (0, 158, 115),
+        (Palette::Tritanopia, Hue::Cyan) => (150, 220, 240),
+        (Palette::Tritanopia, Hue::Blue) => (40, 60, 150),
+        (Palette::Tritanopia, Hue::Magenta) => (230, 80, 160),
+        (Palette::Standard | Palette::Monochrome, _) => unreachable!("not a colorblind palette"),
+    }
+}
+
+fn recolor(palette: Palette, color: Color) -> Color {
+    let Some((hue, light)) = hue_of(color) else {
+        return color;
+    };
+    let (r, g, b) = swatch(palette, hue);
+    // Keep how bright the original was: light variants lighter, dim RGB tints dimmer
+    let scale = match color {
+        Color::Rgb(or, og, ob) => f32::from(or.max(og).max(ob)) / f32::from(r.max(g).max(b)),
+        _ if light => 1.2,
+        _ => 1.0,
+    };
+    let channel = |c: u8| (f32::from(c) * scale).round().min(255.0) as u8;
+    Color::Rgb(channel(r), channel(g), channel(b))
+}
+
+/// Gray level for a foreground color
+fn gray(color: Color) -> Color {
+    let luminance = match color {
+        Color::Rgb(r, g, b) => (0.299 * f32::from(r) + 0.587 * f32::from(g) + 0.114 * f32::from(b)) / 255.0,
+        Color::Black => 0.0,
+        Color::DarkGray | Color::Blue | Color::Red | Color::Magenta => 0.4,
+        Color::Gray | Color::Green | Color::Cyan | Color::Yellow => 0.6,
+        _ => 1.0,
+    };
+    if luminance >= 0.7 {
+        Color::White
+    } else if luminance >= 0.45 {
+        Color::Gray
+    } else if luminance > 0.05 {
+        Color::DarkGray
+    } else {
+        Color::Black
+    }
+}
+
+fn monochrome(cell: &mut ratatui::buffer::Cell) {
+    match hue_of(cell.fg).map(|(hue, _)| hue) {
+        Some(Hue::Red) => cell.modifier |= Modifier::BOLD | Modifier::UNDERLINED,
+        Some(Hue::Yellow) => cell.modifier |= Modifier::BOLD,
+        _ => {}
+    }
+    if !matches!(cell.bg, Color::Reset | Color::Black) {
+        // Highlighted background: show it as reversed text instead
+        cell.bg = Color::Reset;
+        cell.fg = Color::Reset;
+        cell.modifier |= Modifier::REVERSED;
+    } else if cell.fg != Color::Reset {
+        cell.fg = gray(cell.fg);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_colorblind_palettes_separate_red_and_green() {
+        for palette in [Palette::Deuteranopia, Palette::Protanopia, Palette::Tritanopia] {
+            let red = recolor(palette, Color::Red);
+            let green = recolor(palette, Color::Rgb(0, 255, 136));
+            assert_ne!(red, green);
+            assert!(matches!(red, Color::Rgb(..)));
+        }
+        // Grays and the default color are left alone
+        assert_eq!(recolor(Palette::Deuteranopia, Color::DarkGray), Color::DarkGray);
+        assert_eq!(recolor(Palette::Deuteranopia, Color::Rgb(40, 40, 40)), Color::Rgb(40, 40, 40));
+        assert_eq!(recolor(Palette::Deuteranopia, Color::Reset), Color::Reset);
+    }
+
+    #[test]
+    fn test_monochrome_uses_modifiers_instead_of_color() {
+        let mut error = ratatui::buffer::Cell::default();
+        error.fg = Color::Red;
+        monochrome(&mut error);
+        assert!(error.modifier.contains(Modifier::BOLD | Modifier::UNDERLINED));
+        assert!(hue_of(error.fg).is_none());
+
+        let mut selected = ratatui::buffer::Cell::default();
+        selected.bg = Color::Cyan;
+        selected.fg = Color::Black;
+        monochrome(&mut selected);
+        assert_eq!((selected.fg, selected.bg), (Color::Reset, Color::Reset));
+        assert!(selected.modifier.contains(Modifier::REVERSED));
+    }
+}
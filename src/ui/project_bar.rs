@@ -0,0 +1,313 @@
+//! The project tab strip at the top of the screen
+//!
+//! Tabs are laid out once here and shared by rendering and mouse hit-testing.
+//! When they don't fit, the strip scrolls to keep the selected (or active)
+//! project in view and shows how many tabs are hidden on each side; clicking
+//! one of those counts opens the project picker.
+
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::Paragraph,
+    Frame,
+};
+
+use super::logo;
+use crate::app::App;
+use crate::model::{FocusArea, Project};
+
+/// Shortcut keys for the first projects (! opens a project, ) the picker)
+pub const PROJECT_KEYS: [char; 8] = ['@', '#', '$', '%', '^', '&', '*', '('];
+
+const SEPARATOR: &str = " │ ";
+
+/// Result of hit-testing a click position against the project bar
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProjectBarHitResult {
+    /// Clicked on the +project button
+    AddProject,
+    /// Clicked on a specific project tab (index into projects list)
+    SwitchProject(usize),
+    /// Clicked on a count of tabs scrolled out of view
+    ShowProjectPicker,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SegmentKind {
+    Padding,
+    AddProject,
+    Tab(usize),
+    Badge(usize),
+    Hidden,
+    Separator,
+}
+
+impl SegmentKind {
+    fn hit(self) -> Option<ProjectBarHitResult> {
+        match self {
+            SegmentKind::AddProject => Some(ProjectBarHitResult::AddProject),
+            SegmentKind::Tab(idx) | SegmentKind::Badge(idx) => Some(ProjectBarHitResult::SwitchProject(idx)),
+            SegmentKind::Hidden => Some(ProjectBarHitResult::ShowProjectPicker),
+            SegmentKind::Padding | SegmentKind::Separator => None,
+        }
+    }
+}
+
+fn add_label(num_projects: usize) -> &'static str {
+    // Show "+project" when no projects exist, just "+" otherwise
+    if num_projects == 0 { " [!] +project " } else { " [!] + " }
+}
+
+fn tab_text(idx: usize, project: &Project) -> String {
+    match PROJECT_KEYS.get(idx) {
+        Some(key) => format!(" [{}] {} ", key, project.name),
+        None => format!(" {} ", project.name),
+    }
+}
+
+fn badge_text(project: &Project) -> Option<String> {
+    let attention_count = project.unseen_attention_count();
+    (attention_count > 0).then(|| format!(" {} ", attention_count))
+}
+
+/// Width of a tab, its attention badge and the separator after it
+fn tab_width(idx: usize, project: &Project) -> usize {
+    tab_text(idx, project).chars().count()
+        + badge_text(project).map_or(0, |b| b.chars().count())
+        + SEPARATOR.chars().count()
+}
+
+fn hidden_left_text(count: usize) -> String {
+    format!(" ‹{} ", count)
+}
+
+fn hidden_right_text(count: usize) -> String {
+    format!(" {}› ", count)
+}
+
+/// Calculate the total width needed to show every tab without scrolling.
+/// This includes the +project button, all project tabs with their names,
+/// attention badges, and separators.
+pub fn calculate_project_bar_width(app: &App) -> u16 {
+    let projects = &app.model.projects;
+    let width = 1 + add_label(projects.len()).chars().count() + SEPARATOR.chars().count()
+        + projects.iter().enumerate().map(|(idx, p)| tab_width(idx, p)).sum::<usize>();
+    width.min(u16::MAX as usize) as u16
+}
+
+/// Logo shown in the header: the largest one that leaves room for every tab
+pub fn header_logo_size(app: &App, terminal_width: u16, terminal_height: u16) -> logo::LogoSize {
+    logo::get_logo_size_for_project_bar(terminal_width, terminal_height, calculate_project_bar_width(app))
+}
+
+/// Width left for the project bar next to the logo
+pub fn project_bar_area_width(app: &App, logo_size: logo::LogoSize, terminal_width: u16) -> u16 {
+    let logo_width = match logo_size {
+        // Welcome screen: just KANBLAM text, no mascot
+        logo::LogoSize::Full | logo::LogoSize::Medium if app.model.projects.is_empty() => logo::KANBLAM_TEXT_WIDTH,
+        logo::LogoSize::Full => logo::FULL_LOGO_WIDTH,
+        logo::LogoSize::Medium => logo::MEDIUM_LOGO_WIDTH,
+        // Compact branding is drawn inside the bar, only if there's room left
+        _ => 0,
+    };
+    terminal_width.saturating_sub(logo_width)
+}
+
+/// The project tab kept in view: the one selected in the tab bar, else the active one
+fn anchor_project(app: &App) -> usize {
+    let ui = &app.model.ui_state;
+    if ui.focus == FocusArea::ProjectTabs && ui.selected_project_tab_idx > 0 {
+        ui.selected_project_tab_idx - 1
+    } else {
+        app.model.active_project_idx
+    }
+}
+
+/// Range of project tabs that fit in `width`, keeping the anchor tab in view
+fn visible_range(app: &App, width: usize) -> std::ops::Range<usize> {
+    let projects = &app.model.projects;
+    let n = projects.len();
+    if n == 0 {
+        return 0..0;
+    }
+    let widths: Vec<usize> = projects.iter().enumerate().map(|(idx, p)| tab_width(idx, p)).collect();
+    let prefix = 1 + add_label(n).chars().count() + SEPARATOR.chars().count();
+    let used = |first: usize, last: usize| {
+        let left = if first > 0 { hidden_left_text(first).chars().count() + SEPARATOR.chars().count() } else { 0 };
+        let right = if last + 1 < n { hidden_right_text(n - 1 - last).chars().count() } else { 0 };
+        prefix + left + widths[first..=last].iter().sum::<usize>() + right
+    };
+    if used(0, n - 1) <= width {
+        return 0..n;
+    }
+
+    let anchor = anchor_project(app).min(n - 1);
+    // Scroll only as far as needed to bring the anchor in, then fill the rest
+    let mut first = 0;
+    while first < anchor && used(first, anchor) > width {
+        first += 1;
+    }
+    let mut last = anchor;
+    while last + 1 < n && used(first, last + 1) <= width {
+        last += 1;
+    }
+    while first > 0 && used(first - 1, last) <= width {
+        first -= 1;
+    }
+    first..last + 1
+}
+
+/// The bar's segments, left to right
+fn segments(app: &App, width: u16) -> Vec<(SegmentKind, String)> {
+    let projects = &app.model.projects;
+    let visible = visible_range(app, width as usize);
+
+    let mut segments = vec![
+        (SegmentKind::Padding, " ".to_string()),
+        (SegmentKind::AddProject, add_label(projects.len()).to_string()),
+        (SegmentKind::Separator, SEPARATOR.to_string()),
+    ];
+    if visible.start > 0 {
+        segments.push((SegmentKind::Hidden, hidden_left_text(visible.start)));
+        segments.push((SegmentKind::Separator, SEPARATOR.to_string()));
+    }
+    for idx in visible.clone() {
+        let project = &projects[idx];
+        segments.push((SegmentKind::Tab(idx), tab_text(idx, project)));
+        if let Some(badge) = badge_text(project) {
+            segments.push((SegmentKind::Badge(idx), badge));
+        }
+        segments.push((SegmentKind::Separator, SEPARATOR.to_string()));
+    }
+    if visible.end < projects.len() {
+        segments.push((SegmentKind::Hidden, hidden_right_text(projects.len() - visible.end)));
+    }
+    segments
+}
+
+/// Hit-test a screen position against a project bar `width` columns wide.
+/// Returns which tab was clicked, if any.
+pub fn hit_test_project_bar(app: &App, width: u16, x: u16) -> Option<ProjectBarHitResult> {
+    let mut current_x = 0;
+    for (kind, text) in segments(app, width) {
+        let end = current_x + text.chars().count();
+        if (x as usize) >= current_x && (x as usize) < end {
+            return kind.hit();
+        }
+        current_x = end;
+    }
+    None
+}
+
+fn project_bar_spans(app: &App, width: u16) -> Vec<Span<'static>> {
+    let ui = &app.model.ui_state;
+    let is_focused = ui.focus == FocusArea::ProjectTabs;
+    let selected = Style::default()
+        .fg(Color::Black)
+        .bg(Color::Yellow)
+        .add_modifier(Modifier::BOLD);
+    let dim = Style::default().fg(Color::DarkGray);
+
+    segments(app, width)
+        .into_iter()
+        .map(|(kind, text)| {
+            let style = match kind {
+                SegmentKind::Padding => Style::default(),
+                SegmentKind::AddProject => {
+                    // Highlight on welcome screen when bubble is not focused, or when normally selected
+                    let is_tab_selected = (is_focused && ui.selected_project_tab_idx == 0)
+                        || (app.model.projects.is_empty() && !ui.welcome_bubble_focused);
+                    if is_tab_selected { selected } else { dim }
+                }
+                SegmentKind::Tab(idx) => {
+                    // Tab index is idx + 1 (since 0 is +project)
+                    let style = if is_focused && ui.selected_project_tab_idx == idx + 1 {
+                        selected
+                    } else if idx == app.model.active_project_idx {
+                        Style::default()
+                            .fg(Color::Black)
+                            .bg(Color::Cyan)
+                            .add_modifier(Modifier::BOLD)
+                    } else {
+                        Style::default().fg(Color::Gray)
+                    };
+                    // The tab being dragged to a new position
+                    if ui.project_tab_drag == Some(idx) {
+                        style.add_modifier(Modifier::UNDERLINED)
+                    } else {
+                        style
+                    }
+                }
+                // Red badge for projects with attention tasks that have unviewed content
+                SegmentKind::Badge(_) => Style::default()
+                    .fg(Color::White)
+                    .bg(Color::Red)
+                    .add_modifier(Modifier::BOLD),
+                SegmentKind::Hidden => Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD),
+                SegmentKind::Separator => dim,
+            };
+            Span::styled(text, style)
+        })
+        .collect()
+}
+
+/// Render the project bar at the top of the screen
+pub fn render_project_bar(frame: &mut Frame, area: Rect, app: &App) {
+    let bar = Paragraph::new(Line::from(project_bar_spans(app, area.width)));
+    frame.render_widget(bar, area);
+}
+
+/// Render the project bar with inline branding on the right
+pub fn render_project_bar_with_branding(frame: &mut Frame, area: Rect, app: &App) {
+    let green = Color::Rgb(80, 200, 120);
+    let mut spans = project_bar_spans(app, area.width);
+
+    // Calculate remaining space for branding
+    let project_bar_len: usize = spans.iter().map(|s| s.content.chars().count()).sum();
+    let remaining = (area.width as usize).saturating_sub(project_bar_len);
+
+    // Add branding on the right if there's space
+    if remaining >= logo::COMPACT_LOGO_WIDTH as usize {
+        let branding = "KANBLAM";
+        let padding = remaining.saturating_sub(branding.len() + 1);
+        spans.push(Span::raw(" ".repeat(padding)));
+        spans.push(Span::styled(branding, Style::default().fg(green)));
+    }
+
+    let bar = Paragraph::new(Line::from(spans));
+    frame.render_widget(bar, area);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::AppModel;
+
+    #[test]
+    fn test_tabs_scroll_to_keep_the_active_project_in_view() {
+        let mut model = AppModel::default();
+        for idx in 0..12 {
+            model.projects.push(Project::new(format!("project-{}", idx), format!("/tmp/p{}", idx).into()));
+        }
+        model.active_project_idx = 11;
+        let app = App::with_model(model);
+
+        // Everything fits on a wide screen
+        let wide = calculate_project_bar_width(&app);
+        assert_eq!(visible_range(&app, wide as usize), 0..12);
+
+        // On a narrow one the strip scrolls to the active tab and counts the rest
+        let visible = visible_range(&app, 80);
+        assert!(visible.contains(&11) && visible.start > 0);
+        assert_eq!(hit_test_project_bar(&app, 80, 1), Some(ProjectBarHitResult::AddProject));
+        let mut x = 0;
+        for (kind, text) in segments(&app, 80) {
+            assert_eq!(hit_test_project_bar(&app, 80, x as u16), kind.hit());
+            x += text.chars().count();
+        }
+        assert!(x <= 80);
+        let hidden_x = 1 + add_label(12).chars().count() + SEPARATOR.chars().count();
+        assert_eq!(hit_test_project_bar(&app, 80, hidden_x as u16), Some(ProjectBarHitResult::ShowProjectPicker));
+    }
+}
@@ -0,0 +1,387 @@
+//! Board and diff summary snapshots for sharing
+//!
+//! A snapshot is drawn by the regular renderers into an offscreen buffer of
+//! a fixed virtual size, so it looks the same whatever the terminal size is.
+//! The buffer is then written out twice: as ANSI text (for `cat` in a
+//! terminal or pasting into chat tools that keep escape codes) and as a
+//! standalone SVG image.
+
+use super::palette;
+use crate::app::App;
+use crate::model::Task;
+use crate::worktree::diff_cache::CachedDiff;
+use ratatui::{
+    backend::TestBackend,
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+    Frame, Terminal,
+};
+use std::fmt::Write as _;
+use std::path::PathBuf;
+use unicode_width::UnicodeWidthStr;
+
+/// Virtual size (columns, rows) of board snapshots
+pub const BOARD_SIZE: (u16, u16) = (160, 48);
+
+/// Virtual size (columns, rows) of diff summary snapshots
+pub const DIFF_SUMMARY_SIZE: (u16, u16) = (100, 32);
+
+/// Draw with `draw` into an offscreen buffer of the given size, in the current palette
+fn render_offscreen(size: (u16, u16), draw: impl FnOnce(&mut Frame)) -> Buffer {
+    // The test backend can't fail
+    let Ok(mut terminal) = Terminal::new(TestBackend::new(size.0, size.1));
+    let _ = terminal.draw(|frame| {
+        draw(frame);
+        palette::apply(frame.buffer_mut());
+    });
+    terminal.backend().buffer().clone()
+}
+
+/// The active project's board, under a one-line caption
+pub fn board(app: &App) -> Buffer {
+    render_offscreen(BOARD_SIZE, |frame| {
+        let area = frame.area();
+        let project = app.model.active_project().map_or("no project", |p| p.name.as_str());
+        let caption = Line::from(vec![
+            Span::styled(" kanblam ", Style::default().fg(Color::Black).bg(Color::Cyan).add_modifier(Modifier::BOLD)),
+            Span::styled(format!(" {} ", project), Style::default().fg(Color::White).add_modifier(Modifier::BOLD)),
+            Span::styled(chrono::Local::now().format("%Y-%m-%d %H:%M").to_string(), Style::default().fg(Color::DarkGray)),
+        ]);
+        frame.render_widget(Paragraph::new(caption), Rect { height: 1, ..area });
+        super::render_kanban(frame, Rect { y: area.y + 1, height: area.height - 1, ..area }, app);
+    })
+}
+
+/// A task's branch, change totals and per-file changes (from its loaded diff)
+pub fn diff_summary(task: &Task, diff: Option<&CachedDiff>) -> Buffer {
+    let dim = Style::default().fg(Color::DarkGray);
+    let mut lines = Vec::new();
+    if let Some(ref branch) = task.git_branch {
+        lines.push(Line::from(vec![
+            Span::styled("Branch: ", dim),
+            Span::styled(branch.clone(), Style::default().fg(Color::Green)),
+        ]));
+    }
+    lines.push(Line::from(vec![
+        Span::styled(format!("+{}", task.git_additions), Style::default().fg(Color::Green)),
+        Span::styled("/", dim),
+        Span::styled(format!("-{}", task.git_deletions), Style::default().fg(Color::Red)),
+        Span::styled(format!(" in {} files", task.git_files_changed), dim),
+    ]));
+    lines.push(Line::from(""));
+
+    let (columns, rows) = DIFF_SUMMARY_SIZE;
+    // Borders, padding and the header lines above
+    let room = (rows as usize).saturating_sub(4 + lines.len());
+    match diff.map(CachedDiff::file_stats) {
+        Some(stats) if !stats.is_empty() => {
+            let path_width = (columns as usize).saturating_sub(4 + 24);
+            let shown = if stats.len() > room { room.saturating_sub(1) } else { stats.len() };
+            for (path, additions, deletions) in &stats[..shown] {
+                lines.push(file_line(path, *additions, *deletions, path_width));
+            }
+            if stats.len() > shown {
+                lines.push(Line::from(Span::styled(format!("… and {} more files", stats.len() - shown), dim)));
+            }
+        }
+        Some(_) => lines.push(Line::from(Span::styled("No changes", dim))),
+        None => lines.push(Line::from(Span::styled("Per-file changes appear once the diff has loaded", dim))),
+    }
+
+    let title = format!(" {} · {} ", task.display_id(), task.short_title.as_ref().unwrap_or(&task.title));
+    render_offscreen(DIFF_SUMMARY_SIZE, |frame| {
+        let block = Block::default()
+            .title(Span::styled(title, Style::default().fg(Color::White).add_modifier(Modifier::BOLD)))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Magenta))
+            .padding(ratatui::widgets::Padding::horizontal(1));
+        frame.render_widget(Paragraph::new(lines).block(block), frame.area());
+    })
+}
+
+/// "src/app.rs    +12    -3 ████████" with the path cut from the left to fit
+fn file_line(path: &str, additions: usize, deletions: usize, path_width: usize) -> Line<'static> {
+    let path = if path.width() > path_width {
+        let tail: String = path.chars().rev().take(path_width.saturating_sub(1)).collect::<Vec<_>>().into_iter().rev().collect();
+        format!("…{}", tail)
+    } else {
+        path.to_string()
+    };
+    let total = additions + deletions;
+    let bar = 10usize.min(total);
+    let add_chars = if total == 0 { 0 } else { (additions * bar).div_ceil(total) };
+    Line::from(vec![
+        Span::raw(format!("{:<width$}", path, width = path_width)),
+        Span::styled(format!("{:>6}", format!("+{}", additions)), Style::default().fg(Color::Green)),
+        Span::styled(format!("{:>6} ", format!("-{}", deletions)), Style::default().fg(Color::Red)),
+        Span::styled("█".repeat(add_chars), Style::default().fg(Color::Green)),
+        Span::styled("█".repeat(bar - add_chars), Style::default().fg(Color::Red)),
+    ])
+}
+
+/// Symbols and styles of one row, skipping the cells wide symbols cover
+fn row_cells(buffer: &Buffer, y: u16) -> Vec<(&str, Color, Color, Modifier)> {
+    let area = buffer.area;
+    let mut cells = Vec::new();
+    let mut skip = 0;
+    for x in area.left()..area.right() {
+        if skip > 0 {
+            skip -= 1;
+            continue;
+        }
+        let Some(cell) = buffer.cell((x, y)) else { continue };
+        let symbol = if cell.symbol().is_empty() { " " } else { cell.symbol() };
+        skip = symbol.width().saturating_sub(1);
+        cells.push((symbol, cell.fg, cell.bg, cell.modifier));
+    }
+    cells
+}
+
+/// The buffer as text with ANSI color and style escapes
+pub fn to_ansi(buffer: &Buffer) -> String {
+    let mut out = String::new();
+    for y in buffer.area.top()..buffer.area.bottom() {
+        let mut current = None;
+        for (symbol, fg, bg, modifier) in row_cells(buffer, y) {
+            if current != Some((fg, bg, modifier)) {
+                out.push_str(&sgr(fg, bg, modifier));
+                current = Some((fg, bg, modifier));
+            }
+            out.push_str(symbol);
+        }
+        out.push_str("\x1b[0m\n");
+    }
+    out
+}
+
+fn sgr(fg: Color, bg: Color, modifier: Modifier) -> String {
+    let mut codes = vec!["0".to_string()];
+    for (flag, code) in [
+        (Modifier::BOLD, "1"),
+        (Modifier::DIM, "2"),
+        (Modifier::ITALIC, "3"),
+        (Modifier::UNDERLINED, "4"),
+        (Modifier::REVERSED, "7"),
+        (Modifier::CROSSED_OUT, "9"),
+    ] {
+        if modifier.contains(flag) {
+            codes.push(code.to_string());
+        }
+    }
+    codes.push(color_code(fg, false));
+    codes.push(color_code(bg, true));
+    format!("\x1b[{}m", codes.join(";"))
+}
+
+fn color_code(color: Color, background: bool) -> String {
+    let offset = if background { 10 } else { 0 };
+    let basic = |code: u8| (code + offset).to_string();
+    match color {
+        Color::Reset => basic(39),
+        Color::Black => basic(30),
+        Color::Red => basic(31),
+        Color::Green => basic(32),
+        Color::Yellow => basic(33),
+        Color::Blue => basic(34),
+        Color::Magenta => basic(35),
+        Color::Cyan => basic(36),
+        Color::Gray => basic(37),
+        Color::DarkGray => basic(90),
+        Color::LightRed => basic(91),
+        Color::LightGreen => basic(92),
+        Color::LightYellow => basic(93),
+        Color::LightBlue => basic(94),
+        Color::LightMagenta => basic(95),
+        Color::LightCyan => basic(96),
+        Color::White => basic(97),
+        Color::Indexed(idx) => format!("{};5;{}", 38 + offset, idx),
+        Color::Rgb(r, g, b) => format!("{};2;{};{};{}", 38 + offset, r, g, b),
+    }
+}
+
+/// Width of one cell and height of one row in the SVG, in pixels
+const CELL_WIDTH: f32 = 8.4;
+const ROW_HEIGHT: f32 = 17.0;
+
+/// Default foreground/background (what `Color::Reset` looks like)
+const DEFAULT_FG: (u8, u8, u8) = (0xd0, 0xd0, 0xd0);
+const DEFAULT_BG: (u8, u8, u8) = (0x1c, 0x1c, 0x1c);
+
+/// Cells of a row sharing one style, as drawn in the SVG
+struct Run {
+    start: usize,
+    cells: usize,
+    text: String,
+    fg: (u8, u8, u8),
+    bg: (u8, u8, u8),
+    modifier: Modifier,
+}
+
+/// The buffer as a standalone SVG image
+pub fn to_svg(buffer: &Buffer) -> String {
+    let area = buffer.area;
+    let width = area.width as f32 * CELL_WIDTH;
+    let height = area.height as f32 * ROW_HEIGHT;
+    let mut svg = String::new();
+    let _ = writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width:.0}" height="{height:.0}" viewBox="0 0 {width:.1} {height:.1}">"#
+    );
+    let _ = writeln!(
+        svg,
+        r#"<style>text {{ font-family: "JetBrains Mono", Menlo, Consolas, "DejaVu Sans Mono", monospace; font-size: 14px; white-space: pre; dominant-baseline: central; }}</style>"#
+    );
+    let _ = writeln!(svg, r#"<rect width="100%" height="100%" fill="{}"/>"#, hex(DEFAULT_BG));
+
+    for (row, y) in (area.top()..area.bottom()).enumerate() {
+        let top = row as f32 * ROW_HEIGHT;
+        let mut runs: Vec<Run> = Vec::new();
+        let mut column = 0;
+        for (symbol, fg, bg, modifier) in row_cells(buffer, y) {
+            let (mut fg, mut bg) = (rgb(fg, DEFAULT_FG), rgb(bg, DEFAULT_BG));
+            if modifier.contains(Modifier::REVERSED) {
+                std::mem::swap(&mut fg, &mut bg);
+            }
+            let cells = symbol.width().max(1);
+            match runs.last_mut() {
+                Some(run) if run.fg == fg && run.bg == bg && run.modifier == modifier => {
+                    run.cells += cells;
+                    run.text.push_str(symbol);
+                }
+                _ => runs.push(Run { start: column, cells, text: symbol.to_string(), fg, bg, modifier }),
+            }
+            column += cells;
+        }
+
+        for run in runs.iter().filter(|run| run.bg != DEFAULT_BG) {
+            let _ = writeln!(
+                svg,
+                r#"<rect x="{:.1}" y="{:.1}" width="{:.1}" height="{:.1}" fill="{}"/>"#,
+                run.start as f32 * CELL_WIDTH, top, run.cells as f32 * CELL_WIDTH, ROW_HEIGHT, hex(run.bg)
+            );
+        }
+        for run in runs.iter().filter(|run| !run.text.trim().is_empty()) {
+            let modifier = run.modifier;
+            let mut attrs = format!(r#" fill="{}""#, hex(run.fg));
+            if modifier.contains(Modifier::BOLD) {
+                attrs.push_str(r#" font-weight="bold""#);
+            }
+            if modifier.contains(Modifier::ITALIC) {
+                attrs.push_str(r#" font-style="italic""#);
+            }
+            if modifier.contains(Modifier::DIM) {
+                attrs.push_str(r#" opacity="0.6""#);
+            }
+            let decoration = match (modifier.contains(Modifier::UNDERLINED), modifier.contains(Modifier::CROSSED_OUT)) {
+                (true, true) => Some("underline line-through"),
+                (true, false) => Some("underline"),
+                (false, true) => Some("line-through"),
+                (false, false) => None,
+            };
+            if let Some(decoration) = decoration {
+                let _ = write!(attrs, r#" text-decoration="{}""#, decoration);
+            }
+            // Stretch each run to its cells so columns stay aligned whatever the font
+            let _ = writeln!(
+                svg,
+                r#"<text x="{:.1}" y="{:.1}" textLength="{:.1}" lengthAdjust="spacingAndGlyphs"{}>{}</text>"#,
+                run.start as f32 * CELL_WIDTH, top + ROW_HEIGHT / 2.0, run.cells as f32 * CELL_WIDTH, attrs, escape_xml(&run.text)
+            );
+        }
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// RGB of a color as a typical terminal shows it
+fn rgb(color: Color, default: (u8, u8, u8)) -> (u8, u8, u8) {
+    const ANSI: [(u8, u8, u8); 16] = [
+        (0x00, 0x00, 0x00), (0xcd, 0x31, 0x31), (0x0d, 0xbc, 0x79), (0xe5, 0xe5, 0x10),
+        (0x24, 0x72, 0xc8), (0xbc, 0x3f, 0xbc), (0x11, 0xa8, 0xcd), (0xb0, 0xb0, 0xb0),
+        (0x66, 0x66, 0x66), (0xf1, 0x4c, 0x4c), (0x23, 0xd1, 0x8b), (0xf5, 0xf5, 0x43),
+        (0x3b, 0x8e, 0xea), (0xd6, 0x70, 0xd6), (0x29, 0xb8, 0xdb), (0xf5, 0xf5, 0xf5),
+    ];
+    match color {
+        Color::Reset => default,
+        Color::Black => ANSI[0],
+        Color::Red => ANSI[1],
+        Color::Green => ANSI[2],
+        Color::Yellow => ANSI[3],
+        Color::Blue => ANSI[4],
+        Color::Magenta => ANSI[5],
+        Color::Cyan => ANSI[6],
+        Color::Gray => ANSI[7],
+        Color::DarkGray => ANSI[8],
+        Color::LightRed => ANSI[9],
+        Color::LightGreen => ANSI[10],
+        Color::LightYellow => ANSI[11],
+        Color::LightBlue => ANSI[12],
+        Color::LightMagenta => ANSI[13],
+        Color::LightCyan => ANSI[14],
+        Color::White => ANSI[15],
+        Color::Indexed(idx @ 0..=15) => ANSI[idx as usize],
+        Color::Indexed(idx @ 16..=231) => {
+            // 6x6x6 color cube
+            let level = |v: u8| if v == 0 { 0 } else { 55 + v * 40 };
+            let idx = idx - 16;
+            (level(idx / 36), level((idx / 6) % 6), level(idx % 6))
+        }
+        Color::Indexed(idx) => {
+            let gray = 8 + (idx - 232) * 10;
+            (gray, gray, gray)
+        }
+        Color::Rgb(r, g, b) => (r, g, b),
+    }
+}
+
+fn hex((r, g, b): (u8, u8, u8)) -> String {
+    format!("#{:02x}{:02x}{:02x}", r, g, b)
+}
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+/// Write a snapshot as `<name>-<time>.ans` and `.svg`, returning the SVG's path
+pub fn export(name: &str, buffer: &Buffer) -> std::io::Result<PathBuf> {
+    let dir = dirs::data_local_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("kanblam")
+        .join("snapshots");
+    std::fs::create_dir_all(&dir)?;
+    let stem = format!("{}-{}", name, chrono::Local::now().format("%Y%m%d-%H%M%S"));
+    std::fs::write(dir.join(format!("{}.ans", stem)), to_ansi(buffer))?;
+    let svg_path = dir.join(format!("{}.svg", stem));
+    std::fs::write(&svg_path, to_svg(buffer))?;
+    Ok(svg_path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Buffer {
+        let mut buffer = Buffer::empty(Rect::new(0, 0, 6, 1));
+        buffer.set_string(0, 0, "a<", Style::default().fg(Color::Red).add_modifier(Modifier::BOLD));
+        buffer.set_string(2, 0, "界b", Style::default().bg(Color::Rgb(1, 2, 3)));
+        buffer
+    }
+
+    #[test]
+    fn test_ansi_keeps_styles_and_skips_wide_cells() {
+        let ansi = to_ansi(&sample());
+        assert_eq!(ansi, "\x1b[0;1;31;49ma<\x1b[0;39;48;2;1;2;3m界b\x1b[0;39;49m \x1b[0m\n");
+    }
+
+    #[test]
+    fn test_svg_escapes_text_and_draws_backgrounds() {
+        let svg = to_svg(&sample());
+        assert!(svg.starts_with("<svg "));
+        assert!(svg.contains(r#"font-weight="bold">a&lt;</text>"#));
+        assert!(svg.contains(r##"fill="#010203""##));
+        assert!(svg.trim_end().ends_with("</svg>"));
+    }
+}
@@ -1,4 +1,5 @@
 use crate::app::App;
+use crate::timing::TimedCommand;
 use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
@@ -39,19 +40,93 @@ pub fn render_status_bar(frame: &mut Frame, area: Rect, app: &App) {
         return;
     }
 
+    let mut timer_spans = problem_spans();
+    timer_spans.extend(focus_timer_spans(app));
+    let timer_width: u16 = timer_spans.iter().map(|s| s.width() as u16).sum();
+
     let chunks = Layout::default()
         .direction(Direction::Horizontal)
         .constraints([
-            Constraint::Min(20),      // Project info
-            Constraint::Length(30),   // Summary stats
+            Constraint::Min(20),               // Project info
+            Constraint::Length(timer_width),   // Focus timer / clock (empty when off)
+            Constraint::Length(30),            // Summary stats
         ])
         .split(area);
 
     // Render project info
     render_project_info(frame, chunks[0], app);
 
+    // Render focus timer and clock
+    frame.render_widget(Paragraph::new(Line::from(timer_spans)), chunks[1]);
+
     // Render summary
-    render_summary(frame, chunks[1], app);
+    render_summary(frame, chunks[2], app);
+}
+
+/// `!` with the number of problems reported since the diagnostics modal (~) was
+/// last opened (no spans when there are none)
+fn problem_spans() -> Vec<Span<'static>> {
+    let (unseen, any_error) = crate::diagnostics::unseen();
+    if unseen == 0 {
+        return Vec::new();
+    }
+    let color = if any_error { Color::Red } else { Color::Yellow };
+    vec![Span::styled(
+        format!(" ! {} ", unseen),
+        Style::default().fg(Color::Black).bg(color).add_modifier(Modifier::BOLD),
+    ), Span::raw(" ")]
+}
+
+/// Focus timer countdown and wall clock segment (no spans when both are off)
+fn focus_timer_spans(app: &App) -> Vec<Span<'static>> {
+    use crate::model::FocusPhase;
+
+    let mut spans = Vec::new();
+
+    if let Some(ref timer) = app.model.ui_state.focus_timer {
+        let remaining = timer.remaining_secs(&app.model.global_settings).max(0);
+        let (label, color) = match timer.phase {
+            FocusPhase::Focus => ("Focus", Color::Red),
+            FocusPhase::Break => ("Break", Color::Green),
+        };
+        spans.push(Span::styled(
+            format!(" ● {} {:02}:{:02}", label, remaining / 60, remaining % 60),
+            Style::default().fg(color).add_modifier(Modifier::BOLD),
+        ));
+        if let Some(task_id) = timer.task_id {
+            let display_id = app.model.projects.iter()
+                .flat_map(|p| p.tasks.iter())
+                .find(|t| t.id == task_id)
+                .map(|t| t.display_id());
+            if let Some(display_id) = display_id {
+                spans.push(Span::styled(format!(" {}", display_id), Style::default().fg(color)));
+            }
+        }
+        if timer.completed_blocks > 0 {
+            spans.push(Span::styled(
+                format!(" #{}", timer.completed_blocks),
+                Style::default().fg(Color::DarkGray),
+            ));
+        }
+        // Starts held back until the break
+        let deferred = app.model.ui_state.deferred_starts.len();
+        if deferred > 0 {
+            spans.push(Span::styled(
+                format!(" ⏸{}", deferred),
+                Style::default().fg(Color::Yellow),
+            ));
+        }
+        spans.push(Span::raw(" "));
+    }
+
+    if app.model.global_settings.status_clock {
+        spans.push(Span::styled(
+            format!(" {} ", chrono::Local::now().format("%H:%M")),
+            Style::default().fg(Color::Gray),
+        ));
+    }
+
+    spans
 }
 
 /// Render project info for the current project including git status
@@ -75,6 +150,52 @@ fn render_project_info(frame: &mut Frame, area: Rect, app: &App) {
     let mut spans = Vec::new();
     spans.push(Span::raw(" "));
 
+    if app.safe_mode {
+        spans.push(Span::styled(
+            " SAFE MODE ",
+            Style::default().fg(Color::Black).bg(Color::Yellow).add_modifier(Modifier::BOLD),
+        ));
+        spans.push(Span::raw(" "));
+    }
+
+    // Sidecar down: SDK sessions aren't reporting until it's back
+    if !app.safe_mode {
+        use crate::model::SidecarConnectionStatus;
+        let health = &app.model.ui_state.sidecar_health;
+        let badge = match health.status {
+            SidecarConnectionStatus::Connected => None,
+            SidecarConnectionStatus::Checking => Some((" SIDECAR RECONNECTING ".to_string(), Color::Yellow)),
+            SidecarConnectionStatus::Unresponsive | SidecarConnectionStatus::NotRunning => {
+                let wait = health.next_check.saturating_duration_since(std::time::Instant::now()).as_secs();
+                Some((format!(" SIDECAR DOWN · retry in {}s ", wait), Color::Red))
+            }
+        };
+        if let Some((label, bg)) = badge {
+            spans.push(Span::styled(
+                label,
+                Style::default().fg(Color::Black).bg(bg).add_modifier(Modifier::BOLD),
+            ));
+            spans.push(Span::raw(" "));
+        }
+    }
+
+    // Task changes applied to main: uncommitted, and not to be pushed
+    if let Some(applied_id) = project.applied_task_id {
+        let display_id = project.tasks.iter().find(|t| t.id == applied_id)
+            .map(|t| t.display_id())
+            .unwrap_or_default();
+        let (label, bg) = if project.applied_head_moved {
+            (format!(" APPLIED {} · MAIN MOVED ", display_id), Color::Red)
+        } else {
+            (format!(" APPLIED {} ", display_id), Color::Magenta)
+        };
+        spans.push(Span::styled(
+            label,
+            Style::default().fg(Color::Black).bg(bg).add_modifier(Modifier::BOLD),
+        ));
+        spans.push(Span::raw(" "));
+    }
+
     // Get current git branch
     let branch_name = get_current_branch(&project.working_dir);
 
@@ -134,6 +255,39 @@ fn render_project_info(frame: &mut Frame, area: Rect, app: &App) {
                     Style::default().fg(Color::Green).add_modifier(Modifier::ITALIC),
                 ));
             }
+            crate::model::GitOperation::Merging => {
+                let frame_idx = anim_frame % git_frames.len();
+                spans.push(Span::styled(
+                    format!("{} ", git_frames[frame_idx]),
+                    Style::default().fg(Color::Magenta).add_modifier(Modifier::BOLD),
+                ));
+                spans.push(Span::styled(
+                    "Merging...",
+                    Style::default().fg(Color::Magenta).add_modifier(Modifier::ITALIC),
+                ));
+            }
+        }
+
+        // Progress gauge once git reports a transfer phase
+        if let Some(ref progress) = project.git_progress {
+            const GAUGE_WIDTH: usize = 10;
+            let filled = (progress.percent.min(100) as usize * GAUGE_WIDTH) / 100;
+            spans.push(Span::styled(
+                format!(" {} ", progress.phase),
+                Style::default().fg(Color::DarkGray),
+            ));
+            spans.push(Span::styled("█".repeat(filled), Style::default().fg(Color::Cyan)));
+            spans.push(Span::styled("░".repeat(GAUGE_WIDTH - filled), Style::default().fg(Color::DarkGray)));
+            spans.push(Span::styled(
+                format!(" {}%", progress.percent),
+                Style::default().fg(Color::Cyan),
+            ));
+            if let Some(ref bytes) = progress.bytes {
+                spans.push(Span::styled(format!(" {}", bytes), Style::default().fg(Color::DarkGray)));
+            }
+        }
+        if project.git_cancel.is_some() {
+            spans.push(Span::styled(" (X cancel)", Style::default().fg(Color::DarkGray)));
         }
     } else if project.has_remote {
         // Show ahead/behind status when idle and we have a remote
@@ -249,7 +403,7 @@ fn get_current_branch(working_dir: &std::path::Path) -> Option<String> {
     std::process::Command::new("git")
         .current_dir(working_dir)
         .args(["rev-parse", "--abbrev-ref", "HEAD"])
-        .output()
+        .timed_output()
         .ok()
         .filter(|o| o.status.success())
         .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string())
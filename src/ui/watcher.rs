@@ -1,7 +1,8 @@
 //! Watcher mascot balloon - displays periodic observations from the watcher Claude session
 //!
 //! The inline balloon rendering is in mod.rs (render_watcher_balloon_inline).
-//! This module contains the insight modal for showing full watcher observations.
+//! This module contains the insight modal for showing full watcher observations,
+//! and the feed of past ones.
 
 use ratatui::{
     layout::Rect,
@@ -10,7 +11,8 @@ use ratatui::{
     Frame,
 };
 
-use crate::model::WatcherCommentDisplay;
+use crate::model::{Project, WatcherCommentDisplay};
+use crate::sidecar::protocol::WatcherCategory;
 
 /// KanBlam green (matching logo.rs)
 const KANBLAM_GREEN: Color = Color::Rgb(80, 200, 120);
@@ -83,7 +85,11 @@ pub fn render_watcher_insight_modal(
     let title = format!(" {} ", insight.remark);
 
     // Build the bottom hints
-    let hints = " j/k scroll  p(lan) ^s(tart) esc  ^w toggle ";
+    let hints = if comment.category.is_some() {
+        " j/k scroll  p(lan) ^s(tart) m(ute) esc  ^w toggle "
+    } else {
+        " j/k scroll  p(lan) ^s(tart) esc  ^w toggle "
+    };
 
     // Create the block with title
     let block = Block::default()
@@ -176,6 +182,134 @@ pub fn render_watcher_insight_modal(
     total_content_lines
 }
 
+/// Render the project's watcher feed: a mute toggle per category, past
+/// comments newest first (`selected` counts from the newest), and the
+/// highlighted one's insight below
+pub fn render_watcher_feed(frame: &mut Frame, area: Rect, project: &Project, selected: usize) {
+    use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+
+    let modal_width = area.width.saturating_sub(4).min(100);
+    let modal_height = area.height.saturating_sub(4).min(32);
+    let x = area.x + (area.width.saturating_sub(modal_width)) / 2;
+    let y = area.y + (area.height.saturating_sub(modal_height)) / 2;
+    let modal_area = Rect { x, y, width: modal_width, height: modal_height };
+    frame.render_widget(Clear, modal_area);
+
+    let block = Block::default()
+        .title(format!(" Watcher feed: {} ", project.name))
+        .title_style(Style::default().fg(KANBLAM_GREEN).add_modifier(Modifier::BOLD))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan));
+    let inner = block.inner(modal_area);
+    frame.render_widget(block, modal_area);
+    let width = inner.width.saturating_sub(2) as usize;
+
+    let key_style = Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD);
+    let hint_style = Style::default().fg(Color::DarkGray);
+
+    // Mute toggles: muted categories struck through
+    let mut toggles = vec![Span::styled(" Showing: ", hint_style)];
+    for (idx, category) in WatcherCategory::all().iter().enumerate() {
+        let style = if project.watcher_muted.contains(category) {
+            Style::default().fg(Color::DarkGray).add_modifier(Modifier::CROSSED_OUT)
+        } else {
+            Style::default().fg(Color::White)
+        };
+        toggles.push(Span::styled(format!("{} ", idx + 1), key_style));
+        toggles.push(Span::styled(category.label(), style));
+        toggles.push(Span::raw("  "));
+    }
+    let mut lines: Vec<Line> = vec![Line::from(toggles), Line::from("")];
+
+    // Room left for the list once the toggles, details and hints are placed
+    let detail_height = (inner.height as usize / 3).max(4);
+    let list_height = (inner.height as usize).saturating_sub(detail_height + 4).max(1);
+
+    let entries: Vec<_> = project.watcher_feed.iter().rev().collect();
+    if entries.is_empty() {
+        lines.push(Line::from(Span::styled(
+            " Nothing yet: the watcher's comments will collect here.",
+            hint_style,
+        )));
+    }
+    let offset = selected.saturating_sub(list_height.saturating_sub(1));
+    for (idx, entry) in entries.iter().enumerate().skip(offset).take(list_height) {
+        let is_selected = idx == selected;
+        let style = if is_selected {
+            Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)
+        } else if entry.muted {
+            hint_style
+        } else {
+            Style::default().fg(Color::White)
+        };
+        let time = entry.received_at.with_timezone(&chrono::Local).format("%b %d %H:%M").to_string();
+        let category = entry.category.map_or("", |c| c.label());
+        let task = entry.task_id
+            .and_then(|id| project.tasks.iter().find(|t| t.id == id))
+            .map(|t| format!(" → {} {}", t.display_id(), t.status.label()))
+            .unwrap_or_default();
+        let prefix_len = 2 + time.len() + 2 + 14;
+        let remark_width = width.saturating_sub(prefix_len + task.chars().count() + if entry.muted { 8 } else { 0 });
+        let remark: String = if entry.remark.chars().count() > remark_width {
+            entry.remark.chars().take(remark_width.saturating_sub(1)).collect::<String>() + "…"
+        } else {
+            entry.remark.clone()
+        };
+        let mut spans = vec![
+            Span::styled(if is_selected { "► " } else { "  " }, style),
+            Span::styled(format!("{}  ", time), hint_style),
+            Span::styled(format!("{:<14}", category), Style::default().fg(Color::Cyan)),
+            Span::styled(remark, style),
+        ];
+        if entry.muted {
+            spans.push(Span::styled(" (muted)", hint_style));
+        }
+        if !task.is_empty() {
+            spans.push(Span::styled(task, Style::default().fg(KANBLAM_GREEN)));
+        }
+        lines.push(Line::from(spans));
+    }
+
+    // Details of the highlighted entry, at the bottom of the list
+    while lines.len() < 2 + list_height {
+        lines.push(Line::from(""));
+    }
+    lines.push(Line::from(Span::styled("─".repeat(width), hint_style)));
+    let mut detail: Vec<Line> = Vec::new();
+    if let Some(entry) = entries.get(selected) {
+        let text = entry.description.as_deref().unwrap_or(&entry.remark);
+        for line in wrap_text_simple(text, width.saturating_sub(1)) {
+            detail.push(Line::from(Span::styled(format!(" {}", line), Style::default().fg(Color::White))));
+        }
+        if let Some(ref task) = entry.task {
+            detail.push(Line::from(Span::styled(" Task:", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD))));
+            for line in wrap_text_simple(task, width.saturating_sub(1)) {
+                detail.push(Line::from(Span::styled(format!(" {}", line), Style::default().fg(Color::Gray))));
+            }
+        }
+    }
+    lines.extend(detail.into_iter().take(detail_height));
+    while lines.len() < (inner.height as usize).saturating_sub(1) {
+        lines.push(Line::from(""));
+    }
+
+    lines.push(Line::from(vec![
+        Span::styled(" j/k", key_style),
+        Span::styled(" move  ", hint_style),
+        Span::styled("1-6", key_style),
+        Span::styled(" mute/unmute  ", hint_style),
+        Span::styled("p", key_style),
+        Span::styled(" plan task  ", hint_style),
+        Span::styled("Enter", key_style),
+        Span::styled(" go to task  ", hint_style),
+        Span::styled("Esc", key_style),
+        Span::styled(" close", hint_style),
+    ]));
+
+    let content_area = Rect { x: inner.x + 1, width: inner.width.saturating_sub(1), ..inner };
+    frame.render_widget(Paragraph::new(lines), content_area);
+}
+
 /// Simple word-wrap helper that respects word boundaries
 fn wrap_text_simple(text: &str, max_width: usize) -> Vec<String> {
     let mut lines = Vec::new();
@@ -0,0 +1,100 @@
+//! What the watcher looks at on a project, and as whom
+//!
+//! By default an observation explores the main checkout: recent commits and a
+//! file or two. Per project it can also be pointed at the tasks in some
+//! columns (their titles, and their branches when it may read code), kept to
+//! metadata (git log, diff stats and task titles) instead of reading files,
+//! run on its own interval, and given its own persona.
+
+use serde::{Deserialize, Serialize};
+
+use crate::model::{Project, TaskStatus};
+use crate::sidecar::protocol::{WatchedTask, WatcherScope};
+
+/// What an observation may read
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum WatcherReads {
+    /// Files, diffs and git history
+    #[default]
+    Code,
+    /// Git log, diff stats and task titles only
+    Metadata,
+}
+
+impl WatcherReads {
+    /// Get all modes for UI selection
+    pub fn all() -> &'static [WatcherReads] {
+        &[WatcherReads::Code, WatcherReads::Metadata]
+    }
+
+    /// Get the display name for the mode
+    pub fn name(&self) -> &'static str {
+        match self {
+            WatcherReads::Code => "Code",
+            WatcherReads::Metadata => "Metadata",
+        }
+    }
+
+    /// Get a short description of the mode
+    pub fn description(&self) -> &'static str {
+        match self {
+            WatcherReads::Code => "Reads files and diffs of the main checkout and watched task branches",
+            WatcherReads::Metadata => "Only git log, diff stats and task titles; no file contents",
+        }
+    }
+}
+
+/// Parse "review, in-progress". None if a column is unknown; an empty string
+/// means no columns (main checkout only).
+pub fn parse_columns(spec: &str) -> Option<Vec<TaskStatus>> {
+    let mut columns = Vec::new();
+    for name in spec.split(',').map(str::trim).filter(|n| !n.is_empty()) {
+        let status = crate::sla::parse_column(name)?;
+        if !columns.contains(&status) {
+            columns.push(status);
+        }
+    }
+    Some(columns)
+}
+
+/// Columns as written in the setting ("review, in-progress")
+pub fn format_columns(columns: &[TaskStatus]) -> String {
+    columns.iter()
+        .map(|status| status.label().to_lowercase().replace(' ', "-"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// The project's watcher settings as sent with an observation
+pub fn scope(project: &Project) -> WatcherScope {
+    let reads_code = project.watcher_reads == WatcherReads::Code;
+    let tasks = project.tasks.iter()
+        // Accepting, Updating and Applying tasks sit in the Review column
+        .filter(|task| project.watcher_columns.iter().any(|column| column.index() == task.status.index()))
+        .map(|task| WatchedTask {
+            title: task.title.clone(),
+            column: task.status.label().to_string(),
+            branch: task.git_branch.clone().filter(|_| reads_code && task.worktree_path.is_some()),
+        })
+        .collect();
+    WatcherScope {
+        persona: project.watcher_persona.clone(),
+        metadata_only: !reads_code,
+        tasks,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_columns() {
+        let columns = parse_columns("Review, in progress,review").unwrap();
+        assert_eq!(columns, vec![TaskStatus::Review, TaskStatus::InProgress]);
+        assert_eq!(format_columns(&columns), "review, in-progress");
+        assert_eq!(parse_columns(&format_columns(&columns)), Some(columns));
+        assert_eq!(parse_columns(""), Some(vec![]));
+        assert_eq!(parse_columns("review, later"), None);
+    }
+}
@@ -0,0 +1,211 @@
+//! Outgoing webhooks for task lifecycle events
+//!
+//! Each webhook POSTs JSON to its URL when a task starts, needs input,
+//! completes (reaches Review) or is merged, so events can be piped into Slack,
+//! Discord or n8n without polling. The setting lists them separated by "|":
+//!
+//!   https://n8n.local/hook/kb | https://hooks.slack.com/... on=needs-input,merged {"text": "{{task}}: {{event}}"}
+//!
+//! `on=` limits a webhook to some events (all by default), and a trailing
+//! template replaces the default payload. Its `{{placeholders}}` are filled in
+//! JSON-escaped, so they can go inside string literals. Requests are sent by
+//! `curl` off the UI thread.
+
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use serde_json::json;
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+use crate::model::{LifecycleEvent, TaskStatus};
+
+/// Seconds a webhook request may take
+const TIMEOUT_SECS: u32 = 10;
+
+/// A task lifecycle event webhooks fire on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WebhookEvent {
+    Started,
+    NeedsInput,
+    Completed,
+    Merged,
+}
+
+impl WebhookEvent {
+    pub const ALL: [WebhookEvent; 4] = [
+        WebhookEvent::Started,
+        WebhookEvent::NeedsInput,
+        WebhookEvent::Completed,
+        WebhookEvent::Merged,
+    ];
+
+    /// Name used in the setting and payloads
+    pub fn name(self) -> &'static str {
+        match self {
+            WebhookEvent::Started => "started",
+            WebhookEvent::NeedsInput => "needs-input",
+            WebhookEvent::Completed => "completed",
+            WebhookEvent::Merged => "merged",
+        }
+    }
+
+    fn parse(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|e| e.name() == name)
+    }
+
+    /// The webhook event for a column move; reaching Review is "completed"
+    pub fn from_transition(from: TaskStatus, to: TaskStatus) -> Option<Self> {
+        match LifecycleEvent::from_transition(from, to)? {
+            LifecycleEvent::Started => Some(WebhookEvent::Started),
+            LifecycleEvent::NeedsInput => Some(WebhookEvent::NeedsInput),
+            LifecycleEvent::Review => Some(WebhookEvent::Completed),
+            LifecycleEvent::Merged => Some(WebhookEvent::Merged),
+            LifecycleEvent::Moved => None,
+        }
+    }
+}
+
+/// One configured webhook
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Webhook {
+    pub url: String,
+    /// Events it fires on (empty = all)
+    pub events: Vec<WebhookEvent>,
+    /// Payload template (None = the default JSON)
+    pub template: Option<String>,
+}
+
+impl Webhook {
+    /// Parse the setting: "url [on=event,...] [template] | ...". None if any
+    /// entry is malformed; an empty string means no webhooks.
+    pub fn parse_all(spec: &str) -> Option<Vec<Self>> {
+        spec.split(" | ").map(str::trim).filter(|e| !e.is_empty()).map(Self::parse).collect()
+    }
+
+    fn parse(entry: &str) -> Option<Self> {
+        let (url, mut rest) = entry.split_once(char::is_whitespace).unwrap_or((entry, ""));
+        if !(url.starts_with("http://") || url.starts_with("https://")) {
+            return None;
+        }
+        rest = rest.trim_start();
+        let mut events = Vec::new();
+        if let Some(filter) = rest.strip_prefix("on=") {
+            let (names, after) = filter.split_once(char::is_whitespace).unwrap_or((filter, ""));
+            for name in names.split(',') {
+                events.push(WebhookEvent::parse(name.trim())?);
+            }
+            rest = after.trim_start();
+        }
+        let template = (!rest.is_empty()).then(|| rest.to_string());
+        Some(Self { url: url.to_string(), events, template })
+    }
+
+    pub fn wants(&self, event: WebhookEvent) -> bool {
+        self.events.is_empty() || self.events.contains(&event)
+    }
+
+    /// The request body for `info`
+    pub fn payload(&self, info: &EventInfo) -> String {
+        let Some(template) = &self.template else {
+            return json!({
+                "event": info.event.name(),
+                "project": info.project,
+                "task": {
+                    "id": info.task_id,
+                    "title": info.title,
+                    "status": info.status.label(),
+                    "branch": info.branch,
+                },
+                "timestamp": info.at.to_rfc3339(),
+            }).to_string();
+        };
+        let escape = |value: &str| {
+            let quoted = serde_json::to_string(value).unwrap_or_default();
+            quoted[1..quoted.len() - 1].to_string()
+        };
+        let task = format!("{} {}", info.task_id, info.title);
+        [
+            ("{{event}}", info.event.name()),
+            ("{{project}}", info.project.as_str()),
+            ("{{task}}", task.as_str()),
+            ("{{task_id}}", info.task_id.as_str()),
+            ("{{title}}", info.title.as_str()),
+            ("{{status}}", info.status.label()),
+            ("{{branch}}", info.branch.as_deref().unwrap_or("")),
+            ("{{timestamp}}", &info.at.to_rfc3339()),
+        ]
+        .iter()
+        .fold(template.clone(), |body, (placeholder, value)| body.replace(placeholder, &escape(value)))
+    }
+}
+
+/// What happened to which task, for building payloads
+#[derive(Debug, Clone)]
+pub struct EventInfo {
+    pub event: WebhookEvent,
+    pub project: String,
+    /// Display id, e.g. "TSKB-a7x"
+    pub task_id: String,
+    pub title: String,
+    pub status: TaskStatus,
+    pub branch: Option<String>,
+    pub at: DateTime<Utc>,
+}
+
+/// POST `body` to `url`. Blocks until curl is done, so call it off the UI thread.
+pub fn send(url: &str, body: &str) -> Result<()> {
+    let mut child = Command::new("curl")
+        .args(["-sS", "--fail", "-m", &TIMEOUT_SECS.to_string(), "-X", "POST"])
+        .args(["-H", "Content-Type: application/json", "--data-binary", "@-", url])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| anyhow!("Failed to run curl: {}", e))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(body.as_bytes())?;
+    }
+    let output = child.wait_with_output()?;
+    if !output.status.success() {
+        return Err(anyhow!("{}", String::from_utf8_lossy(&output.stderr).trim()));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_and_fill_webhooks() {
+        let hooks = Webhook::parse_all(
+            r#"https://n8n.local/hook | https://chat.example/x on=needs-input,merged {"text": "{{task}} {{event}} in {{project}}"}"#,
+        ).unwrap();
+        assert_eq!(hooks.len(), 2);
+        assert!(hooks[0].wants(WebhookEvent::Started) && hooks[0].template.is_none());
+        assert!(hooks[1].wants(WebhookEvent::Merged) && !hooks[1].wants(WebhookEvent::Completed));
+
+        let info = EventInfo {
+            event: WebhookEvent::NeedsInput,
+            project: "api".to_string(),
+            task_id: "API-a7x".to_string(),
+            title: "Fix \"login\" bug".to_string(),
+            status: TaskStatus::NeedsWork,
+            branch: Some("claude/API-a7x".to_string()),
+            at: Utc::now(),
+        };
+        assert_eq!(hooks[1].payload(&info), r#"{"text": "API-a7x Fix \"login\" bug needs-input in api"}"#);
+        let default: serde_json::Value = serde_json::from_str(&hooks[0].payload(&info)).unwrap();
+        assert_eq!(default["task"]["branch"], "claude/API-a7x");
+
+        assert_eq!(Webhook::parse_all(""), Some(Vec::new()));
+        assert_eq!(Webhook::parse_all("ftp://x"), None);
+        assert_eq!(Webhook::parse_all("https://x on=deployed"), None);
+        assert_eq!(WebhookEvent::from_transition(TaskStatus::Planned, TaskStatus::InProgress), Some(WebhookEvent::Started));
+        assert_eq!(WebhookEvent::from_transition(TaskStatus::NeedsWork, TaskStatus::InProgress), None);
+        // A rebase or a failed merge stays in Review: not completed again
+        assert_eq!(WebhookEvent::from_transition(TaskStatus::Updating, TaskStatus::Review), None);
+        assert_eq!(WebhookEvent::from_transition(TaskStatus::Accepting, TaskStatus::Review), None);
+        assert_eq!(WebhookEvent::from_transition(TaskStatus::Testing, TaskStatus::Review), Some(WebhookEvent::Completed));
+    }
+}
@@ -0,0 +1,282 @@
+//! Dependency caches shared from the main checkout into new worktrees
+//!
+//! A fresh worktree has no `node_modules`, `target` or virtualenv, so every
+//! task starts with a full install or build. A project can list directories
+//! that new worktrees take from the main checkout instead:
+//!
+//! - `node_modules` (or `node_modules:link`) symlinks the main checkout's
+//!   directory, sharing it outright (right for caches installed from a
+//!   lockfile, like a pnpm-linked `node_modules` or a `.venv`)
+//! - `target:copy` hard-links a copy of it, so the task's builds start warm
+//!   but write their own tree (files are shared until a tool replaces them)
+//!
+//! A directory is only shared when it's a real directory that git ignores in
+//! the main checkout, the worktree doesn't have one yet, and the lockfiles
+//! next to it match between the two; anything else is skipped and reported.
+//! Remote projects and tasks created with `/fresh` get no shared caches.
+
+use std::path::{Component, Path, PathBuf};
+use std::process::Command;
+
+use crate::timing::TimedCommand;
+
+/// Lockfiles whose contents decide whether installed dependencies still fit
+const LOCKFILES: &[&str] = &[
+    "package-lock.json", "pnpm-lock.yaml", "yarn.lock", "bun.lockb",
+    "Cargo.lock", "uv.lock", "poetry.lock", "Pipfile.lock", "requirements.txt",
+];
+
+/// Kept in the worktree's git directory: the caches shared into it
+const RECORD_FILE: &str = "kanblam-shared-caches";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShareMode {
+    /// Symlink to the main checkout's directory
+    Link,
+    /// Hard-linked copy of the main checkout's directory
+    Copy,
+}
+
+/// Split a shared-caches setting ("node_modules .venv target:copy") into
+/// paths and how they're shared. Entries with an unknown mode are returned
+/// as errors so they can be reported rather than silently linked.
+pub fn parse_caches(setting: &str) -> Vec<Result<(String, ShareMode), String>> {
+    setting
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|entry| !entry.is_empty())
+        .map(|entry| {
+            let (path, mode) = match entry.rsplit_once(':') {
+                Some((path, "link")) => (path, ShareMode::Link),
+                Some((path, "copy")) => (path, ShareMode::Copy),
+                Some(_) => return Err(format!("{}: unknown mode (use :link or :copy)", entry)),
+                None => (entry, ShareMode::Link),
+            };
+            Ok((path.trim_end_matches('/').to_string(), mode))
+        })
+        .collect()
+}
+
+/// Whether `path` stays inside the directory it's relative to
+fn is_contained(path: &Path) -> bool {
+    !path.as_os_str().is_empty() && path.components().all(|c| matches!(c, Component::Normal(_)))
+}
+
+fn git_ignores(dir: &Path, path: &Path) -> bool {
+    Command::new("git")
+        .current_dir(dir)
+        .args(["check-ignore", "-q"])
+        .arg(path)
+        .timed_output()
+        .is_ok_and(|output| output.status.success())
+}
+
+/// A lockfile next to the cache that differs between the two checkouts
+fn differing_lockfile(source_parent: &Path, dest_parent: &Path) -> Option<&'static str> {
+    LOCKFILES.iter().copied().find(|name| {
+        std::fs::read(source_parent.join(name)).ok() != std::fs::read(dest_parent.join(name)).ok()
+    })
+}
+
+/// The worktree's own git directory (`.git/worktrees/<name>`), or the
+/// repository's shared one with `common`
+fn worktree_git_dir(worktree: &Path, common: bool) -> Option<PathBuf> {
+    let flag = if common { "--git-common-dir" } else { "--git-dir" };
+    let output = Command::new("git").current_dir(worktree).args(["rev-parse", flag]).timed_output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let dir = PathBuf::from(String::from_utf8_lossy(&output.stdout).trim());
+    Some(if dir.is_absolute() { dir } else { worktree.join(dir) })
+}
+
+/// Keep a symlink out of `git status`: `node_modules/` in .gitignore only
+/// matches directories, and git sees a symlink as a file
+fn exclude_link(worktree: &Path, repo_path: &Path) -> std::io::Result<()> {
+    let Some(common) = worktree_git_dir(worktree, true) else {
+        return Ok(());
+    };
+    let exclude = common.join("info").join("exclude");
+    let pattern = format!("/{}", repo_path.to_string_lossy());
+    let existing = std::fs::read_to_string(&exclude).unwrap_or_default();
+    if existing.lines().any(|line| line == pattern) {
+        return Ok(());
+    }
+    std::fs::create_dir_all(common.join("info"))?;
+    let separator = if existing.is_empty() || existing.ends_with('\n') { "" } else { "\n" };
+    std::fs::write(&exclude, format!("{}{}{}\n", existing, separator, pattern))
+}
+
+/// Recreate `source` at `dest` with every file hard-linked
+fn hard_link_tree(source: &Path, dest: &Path) -> std::io::Result<()> {
+    std::fs::create_dir(dest)?;
+    for entry in std::fs::read_dir(source)? {
+        let entry = entry?;
+        let (from, to) = (entry.path(), dest.join(entry.file_name()));
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() {
+            hard_link_tree(&from, &to)?;
+        } else if file_type.is_symlink() {
+            std::os::unix::fs::symlink(std::fs::read_link(&from)?, &to)?;
+        } else {
+            std::fs::hard_link(&from, &to)?;
+        }
+    }
+    Ok(())
+}
+
+/// Share one cache; `Ok` describes what was done, `Err` why it was skipped
+fn share(main_dir: &Path, worktree: &Path, scope: Option<&Path>, path: &str, mode: ShareMode) -> Result<&'static str, String> {
+    let rel = Path::new(path);
+    if !is_contained(rel) {
+        return Err("not a path inside the project".to_string());
+    }
+    let repo_path = scope.map(|s| s.join(rel)).unwrap_or_else(|| rel.to_path_buf());
+    let (source, dest) = (main_dir.join(&repo_path), worktree.join(&repo_path));
+
+    match std::fs::symlink_metadata(&source) {
+        Ok(meta) if meta.is_dir() => {}
+        Ok(_) => return Err("not a directory in the main checkout".to_string()),
+        Err(_) => return Err("missing in the main checkout".to_string()),
+    }
+    if std::fs::symlink_metadata(&dest).is_ok() {
+        return Err("already in the worktree".to_string());
+    }
+    if !git_ignores(main_dir, &repo_path) {
+        return Err("not ignored by git".to_string());
+    }
+    let (Some(source_parent), Some(dest_parent)) = (source.parent(), dest.parent()) else {
+        return Err("not a path inside the project".to_string());
+    };
+    if let Some(lockfile) = differing_lockfile(source_parent, dest_parent) {
+        return Err(format!("{} differs", lockfile));
+    }
+
+    std::fs::create_dir_all(dest_parent).map_err(|e| e.to_string())?;
+    match mode {
+        ShareMode::Link => {
+            std::os::unix::fs::symlink(&source, &dest).map_err(|e| e.to_string())?;
+            if let Err(e) = exclude_link(worktree, &repo_path) {
+                let _ = std::fs::remove_file(&dest);
+                return Err(e.to_string());
+            }
+            Ok("linked")
+        }
+        ShareMode::Copy => match hard_link_tree(&source, &dest) {
+            Ok(()) => Ok("copied"),
+            Err(e) => {
+                // Hard links can't cross filesystems; leave nothing half-copied
+                let _ = std::fs::remove_dir_all(&dest);
+                Err(e.to_string())
+            }
+        },
+    }
+}
+
+/// Share the caches listed in `setting` from the main checkout (`main_dir`,
+/// the repository root) into a new `worktree`. Returns one line per cache
+/// saying what happened, e.g. "node_modules linked" or "target skipped
+/// (Cargo.lock differs)".
+pub fn share_caches(main_dir: &Path, worktree: &Path, scope: Option<&Path>, setting: &str) -> Vec<String> {
+    if crate::remote::is_remote(worktree) {
+        return Vec::new();
+    }
+    let mut report = Vec::new();
+    let mut shared = Vec::new();
+    for entry in parse_caches(setting) {
+        match entry {
+            Ok((path, mode)) => match share(main_dir, worktree, scope, &path, mode) {
+                Ok(done) => {
+                    report.push(format!("{} {}", path, done));
+                    shared.push(scope.map(|s| s.join(&path)).unwrap_or_else(|| PathBuf::from(&path)));
+                }
+                Err(reason) => report.push(format!("{} skipped ({})", path, reason)),
+            },
+            Err(e) => report.push(e),
+        }
+    }
+    if let (false, Some(git_dir)) = (shared.is_empty(), worktree_git_dir(worktree, false)) {
+        let lines: Vec<String> = shared.iter().map(|p| p.to_string_lossy().into_owned()).collect();
+        let _ = std::fs::write(git_dir.join(RECORD_FILE), lines.join("\n") + "\n");
+    }
+    report
+}
+
+/// Take the shared caches back out of a worktree that's about to be reused,
+/// so the next task starts from the setting it was created with
+pub fn unshare_caches(worktree: &Path) {
+    let Some(record) = worktree_git_dir(worktree, false).map(|dir| dir.join(RECORD_FILE)) else {
+        return;
+    };
+    let Ok(paths) = std::fs::read_to_string(&record) else {
+        return;
+    };
+    for path in paths.lines().map(Path::new).filter(|p| is_contained(p)) {
+        let dest = worktree.join(path);
+        match std::fs::symlink_metadata(&dest) {
+            Ok(meta) if meta.is_symlink() => { let _ = std::fs::remove_file(&dest); }
+            Ok(meta) if meta.is_dir() => { let _ = std::fs::remove_dir_all(&dest); }
+            _ => {}
+        }
+    }
+    let _ = std::fs::remove_file(record);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::worktree::git::test_git;
+
+    #[test]
+    fn test_share_caches() {
+        assert_eq!(parse_caches("node_modules, target:copy .venv/ x:move").len(), 4);
+        assert_eq!(parse_caches("target:copy")[0], Ok(("target".to_string(), ShareMode::Copy)));
+        assert!(parse_caches("x:move")[0].is_err());
+
+        let repo = tempfile::tempdir().unwrap();
+        let dir = repo.path();
+        test_git(dir, &["init", "--quiet"]);
+        test_git(dir, &["config", "user.email", "test@test.com"]);
+        test_git(dir, &["config", "user.name", "Test"]);
+        std::fs::write(dir.join(".gitignore"), "node_modules/\ntarget/\n.venv/\n").unwrap();
+        std::fs::write(dir.join("package-lock.json"), "{}").unwrap();
+        std::fs::create_dir_all(dir.join("src")).unwrap();
+        std::fs::write(dir.join("src").join("main.rs"), "fn main() {}").unwrap();
+        std::fs::create_dir_all(dir.join("py")).unwrap();
+        std::fs::write(dir.join("py").join("uv.lock"), "v1").unwrap();
+        test_git(dir, &["add", "-A"]);
+        test_git(dir, &["commit", "--quiet", "-m", "init"]);
+        for cache in ["node_modules", "target", "py/.venv"] {
+            std::fs::create_dir_all(dir.join(cache).join("inner")).unwrap();
+            std::fs::write(dir.join(cache).join("inner").join("file"), cache).unwrap();
+        }
+        // Installed from an uncommitted lockfile change: no longer fits the worktree's
+        std::fs::write(dir.join("py").join("uv.lock"), "v2").unwrap();
+        std::fs::create_dir_all(dir.join("vendor")).unwrap();
+
+        let worktree = dir.join("worktrees").join("T-1");
+        test_git(dir, &["worktree", "add", "--quiet", "--detach", worktree.to_str().unwrap()]);
+        let report = share_caches(dir, &worktree, None, "node_modules target:copy py/.venv vendor ../etc");
+        assert_eq!(report, vec![
+            "node_modules linked",
+            "target copied",
+            "py/.venv skipped (uv.lock differs)",
+            "vendor skipped (not ignored by git)",
+            "../etc skipped (not a path inside the project)",
+        ]);
+        assert!(std::fs::symlink_metadata(worktree.join("node_modules")).unwrap().is_symlink());
+        let copied = worktree.join("target").join("inner").join("file");
+        assert!(!std::fs::symlink_metadata(worktree.join("target")).unwrap().is_symlink());
+        assert_eq!(std::fs::read_to_string(&copied).unwrap(), "target");
+        assert!(!worktree.join("py").join(".venv").exists());
+
+        // The symlink stays out of git status
+        let status = Command::new("git").current_dir(&worktree).args(["status", "--porcelain"]).output().unwrap();
+        assert!(String::from_utf8_lossy(&status.stdout).trim().is_empty());
+
+        unshare_caches(&worktree);
+        assert!(std::fs::symlink_metadata(worktree.join("node_modules")).is_err());
+        assert!(!worktree.join("target").exists());
+        assert!(dir.join("node_modules").join("inner").join("file").exists());
+        assert!(dir.join("target").join("inner").join("file").exists());
+    }
+}
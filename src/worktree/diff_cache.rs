@@ -0,0 +1,322 @@
+//! Cache of task diffs for the Git tab
+//!
+//! A task's diff is fetched once per branch head and kept as raw text plus an
+//! index of its hunks. Rendering only splits the hunks that intersect the
+//! visible window, so scrolling a huge diff costs the same as a small one.
+//! The cache is bounded both by entry count and total bytes, and a single
+//! diff is truncated at a hunk boundary once it exceeds `MAX_DIFF_BYTES`.
+
+use std::collections::VecDeque;
+use uuid::Uuid;
+
+/// Diffs kept at most (least recently viewed is evicted first)
+const MAX_ENTRIES: usize = 8;
+
+/// Total bytes of diff text kept across all entries
+const MAX_CACHE_BYTES: usize = 16 * 1024 * 1024;
+
+/// Bytes kept for a single diff (the rest is dropped with a note)
+const MAX_DIFF_BYTES: usize = 8 * 1024 * 1024;
+
+/// Identifies one version of a task's diff
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiffKey {
+    pub task_id: Uuid,
+    /// Commit the task branch pointed at when the diff was taken
+    pub head: String,
+}
+
+/// Location of one hunk (or file header block) in the diff text
+#[derive(Debug, Clone, Copy)]
+struct Hunk {
+    /// Byte offset of the first line
+    start: usize,
+    /// Line number of the first line in the whole diff
+    first_line: usize,
+}
+
+/// Where a diff line sits in its file, for review comments
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineLocation {
+    pub path: String,
+    /// Line number in the task's version of the file, or in main's for a removed line
+    pub line: u32,
+    pub removed: bool,
+}
+
+/// A diff with its hunks indexed for windowed rendering
+#[derive(Debug, Clone, Default)]
+pub struct CachedDiff {
+    text: String,
+    hunks: Vec<Hunk>,
+    total_lines: usize,
+    /// Lines added and removed per file, counted once when parsed
+    stats: Vec<(String, usize, usize)>,
+    /// Lines dropped because the diff was over the size limit
+    pub truncated_lines: usize,
+}
+
+impl CachedDiff {
+    /// Index `text`, truncating it at a hunk boundary if it's over the size limit
+    pub fn parse(mut text: String) -> Self {
+        let mut hunks = Vec::new();
+        let mut total_lines = 0;
+        let mut offset = 0;
+        let mut cut_at = None;
+
+        for line in text.split_inclusive('\n') {
+            let starts_block = line.starts_with("diff --git") || line.starts_with("@@");
+            if starts_block || hunks.is_empty() {
+                if offset > MAX_DIFF_BYTES {
+                    cut_at = Some(offset);
+                    break;
+                }
+                hunks.push(Hunk { start: offset, first_line: total_lines });
+            }
+            total_lines += 1;
+            offset += line.len();
+        }
+
+        let mut truncated_lines = 0;
+        if let Some(cut) = cut_at {
+            truncated_lines = text[cut..].lines().count();
+            text.truncate(cut);
+        }
+
+        let stats = count_file_stats(&text);
+        Self { text, hunks, total_lines, stats, truncated_lines }
+    }
+
+    pub fn total_lines(&self) -> usize {
+        self.total_lines
+    }
+
+    pub fn size_bytes(&self) -> usize {
+        self.text.len()
+    }
+
+    /// Lines added and removed per file, in diff order
+    pub fn file_stats(&self) -> &[(String, usize, usize)] {
+        &self.stats
+    }
+
+    /// Up to `count` lines starting at line `offset`, reading only the hunks needed
+    pub fn lines(&self, offset: usize, count: usize) -> impl Iterator<Item = &str> {
+        // Last hunk starting at or before `offset`
+        let idx = self.hunks.partition_point(|h| h.first_line <= offset).saturating_sub(1);
+        let (start, skip) = self.hunks.get(idx)
+            .map(|h| (h.start, offset.saturating_sub(h.first_line)))
+            .unwrap_or((0, 0));
+
+        self.text[start..].lines().skip(skip).take(count)
+    }
+
+    /// Where each of up to `count` lines from line `offset` sits in its file
+    /// (None for file headers, hunk headers and "\ No newline" markers)
+    pub fn locations(&self, offset: usize, count: usize) -> Vec<Option<LineLocation>> {
+        // Line numbers count from the hunk header, the path from the file header
+        let idx = self.hunks.partition_point(|h| h.first_line <= offset).saturating_sub(1);
+        let file_idx = (0..=idx).rev()
+            .find(|&i| self.hunks.get(i).is_some_and(|h| self.text[h.start..].starts_with("diff --git")))
+            .unwrap_or(0);
+        let Some(first) = self.hunks.get(file_idx) else {
+            return Vec::new();
+        };
+
+        let mut path = String::new();
+        let (mut old, mut new, mut in_hunk) = (0, 0, false);
+        let mut locations = Vec::new();
+        let lines = self.text[first.start..].lines().take(offset + count - first.first_line);
+        for (n, line) in lines.enumerate() {
+            let at = |path: &str, line: u32, removed: bool| Some(LineLocation { path: path.to_string(), line, removed });
+            let location = if let Some(header) = line.strip_prefix("diff --git ") {
+                path = header.rsplit_once(" b/").map_or(header, |(_, new)| new).to_string();
+                in_hunk = false;
+                None
+            } else if line.starts_with("@@") {
+                (old, new) = hunk_starts(line).unwrap_or((0, 0));
+                in_hunk = true;
+                None
+            } else if !in_hunk || line.starts_with('\\') {
+                None
+            } else if line.starts_with('+') {
+                new += 1;
+                at(&path, new - 1, false)
+            } else if line.starts_with('-') {
+                old += 1;
+                at(&path, old - 1, true)
+            } else {
+                old += 1;
+                new += 1;
+                at(&path, new - 1, false)
+            };
+            if first.first_line + n >= offset {
+                locations.push(location);
+            }
+        }
+        locations
+    }
+}
+
+/// First old and new line numbers of a hunk header (`@@ -12,5 +14,7 @@`)
+fn hunk_starts(header: &str) -> Option<(u32, u32)> {
+    let mut ranges = header.split_whitespace().skip(1);
+    let start = |range: &str| range.split(',').next()?.parse::<u32>().ok();
+    let old = start(ranges.next()?.strip_prefix('-')?)?;
+    let new = start(ranges.next()?.strip_prefix('+')?)?;
+    Some((old, new))
+}
+
+fn count_file_stats(text: &str) -> Vec<(String, usize, usize)> {
+    let mut stats: Vec<(String, usize, usize)> = Vec::new();
+    // The ---/+++ file header lines come before the first hunk
+    let mut in_hunk = false;
+    for line in text.lines() {
+        if let Some(header) = line.strip_prefix("diff --git ") {
+            let path = header.rsplit_once(" b/").map_or(header, |(_, new)| new);
+            stats.push((path.to_string(), 0, 0));
+            in_hunk = false;
+        } else if line.starts_with("@@") {
+            in_hunk = true;
+        } else if let Some((_, additions, deletions)) = stats.last_mut().filter(|_| in_hunk) {
+            if line.starts_with('+') {
+                *additions += 1;
+            } else if line.starts_with('-') {
+                *deletions += 1;
+            }
+        }
+    }
+    stats
+}
+
+/// LRU cache of parsed diffs, plus which one the Git tab is showing
+#[derive(Debug, Clone, Default)]
+pub struct DiffCache {
+    /// Most recently used first
+    entries: VecDeque<(DiffKey, CachedDiff)>,
+    /// Diff currently shown in the Git tab
+    current: Option<DiffKey>,
+    /// Load error for the current task (shown instead of a diff)
+    error: Option<(Uuid, String)>,
+}
+
+impl DiffCache {
+    /// Show the cached diff for `key`, if there is one
+    pub fn select(&mut self, key: &DiffKey) -> bool {
+        let Some(pos) = self.entries.iter().position(|(k, _)| k == key) else {
+            return false;
+        };
+        if let Some(entry) = self.entries.remove(pos) {
+            self.entries.push_front(entry);
+        }
+        self.current = Some(key.clone());
+        self.error = None;
+        true
+    }
+
+    /// Add a freshly loaded diff and show it, evicting old entries over the limits
+    pub fn insert(&mut self, key: DiffKey, diff: CachedDiff) {
+        // Older heads of the same task are never shown again
+        self.entries.retain(|(k, _)| k.task_id != key.task_id);
+        self.entries.push_front((key.clone(), diff));
+        self.current = Some(key);
+        self.error = None;
+
+        let mut bytes = 0;
+        let mut keep = 0;
+        for (_, diff) in &self.entries {
+            // Always keep the entry being shown
+            if keep > 0 && (keep >= MAX_ENTRIES || bytes + diff.size_bytes() > MAX_CACHE_BYTES) {
+                break;
+            }
+            bytes += diff.size_bytes();
+            keep += 1;
+        }
+        self.entries.truncate(keep);
+    }
+
+    pub fn set_error(&mut self, task_id: Uuid, error: String) {
+        self.current = None;
+        self.error = Some((task_id, error));
+    }
+
+    /// The latest cached diff of `task_id`, shown or not (it may be a head behind)
+    pub fn latest(&self, task_id: Uuid) -> Option<&CachedDiff> {
+        self.entries.iter().find(|(k, _)| k.task_id == task_id).map(|(_, diff)| diff)
+    }
+
+    /// The diff being shown, if it belongs to `task_id`
+    pub fn current(&self, task_id: Uuid) -> Option<&CachedDiff> {
+        let key = self.current.as_ref().filter(|k| k.task_id == task_id)?;
+        self.entries.iter().find(|(k, _)| k == key).map(|(_, diff)| diff)
+    }
+
+    /// Branch head of the diff being shown, if it belongs to `task_id`
+    pub fn current_head(&self, task_id: Uuid) -> Option<&str> {
+        self.current.as_ref().filter(|k| k.task_id == task_id).map(|k| k.head.as_str())
+    }
+
+    pub fn error(&self, task_id: Uuid) -> Option<&str> {
+        self.error.as_ref().filter(|(id, _)| *id == task_id).map(|(_, e)| e.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_diff(files: usize) -> String {
+        (0..files)
+            .map(|i| format!("diff --git a/f{i} b/f{i}\n--- a/f{i}\n+++ b/f{i}\n@@ -1 +1 @@\n-old{i}\n+new{i}\n"))
+            .collect()
+    }
+
+    #[test]
+    fn test_windowed_lines_and_lru() {
+        let diff = CachedDiff::parse(sample_diff(3));
+        assert_eq!(diff.total_lines(), 18);
+        assert_eq!(diff.lines(0, 1).collect::<Vec<_>>(), ["diff --git a/f0 b/f0"]);
+        // Middle of the second file's hunk
+        assert_eq!(diff.lines(10, 2).collect::<Vec<_>>(), ["-old1", "+new1"]);
+        assert_eq!(diff.lines(17, 5).count(), 1);
+
+        let mut cache = DiffCache::default();
+        let task = Uuid::new_v4();
+        let old = DiffKey { task_id: task, head: "a".into() };
+        let new = DiffKey { task_id: task, head: "b".into() };
+        cache.insert(old.clone(), diff.clone());
+        cache.insert(new.clone(), diff);
+        // A new head replaces the old one
+        assert!(!cache.select(&old));
+        assert!(cache.select(&new));
+        assert!(cache.current(task).is_some());
+        assert!(cache.current(Uuid::new_v4()).is_none());
+        // The quick diff still finds a task's diff after another was shown
+        cache.set_error(Uuid::new_v4(), "boom".into());
+        assert!(cache.current(task).is_none());
+        assert!(cache.latest(task).is_some());
+    }
+
+    #[test]
+    fn test_file_stats() {
+        let diff = CachedDiff::parse(format!("{}diff --git a/g b/g\n--- a/g\n+++ b/g\n@@ -1 +1,2 @@\n--- x\n+a\n+b\n", sample_diff(1)));
+        assert_eq!(diff.file_stats(), [("f0".to_string(), 1, 1), ("g".to_string(), 2, 1)]);
+    }
+
+    #[test]
+    fn test_line_locations() {
+        let diff = CachedDiff::parse(format!(
+            "{}diff --git a/src/g.rs b/src/g.rs\n--- a/src/g.rs\n+++ b/src/g.rs\n@@ -10,3 +12,4 @@ fn g()\n ctx\n-gone\n+one\n+two\n",
+            sample_diff(1),
+        ));
+        let at = |path: &str, line: u32, removed: bool| Some(LineLocation { path: path.to_string(), line, removed });
+        // Starting inside the second file: its path and numbering still apply
+        assert_eq!(diff.locations(10, 5), vec![
+            at("src/g.rs", 12, false),
+            at("src/g.rs", 11, true),
+            at("src/g.rs", 13, false),
+            at("src/g.rs", 14, false),
+        ]);
+        assert_eq!(diff.locations(0, 6), vec![None, None, None, None, at("f0", 1, true), at("f0", 1, false)]);
+    }
+}
@@ -0,0 +1,183 @@
+//! Filesystem watching for incremental git status refresh
+//!
+//! Task status (diff stats, ahead/behind) only changes when a ref moves, so
+//! instead of re-running git for every task on a timer we watch the git
+//! metadata that commits and checkouts touch:
+//! - `.git/refs/heads/claude/<id>` -> that task's branch moved
+//! - `.git/worktrees/<name>/HEAD`  -> that task's worktree switched branch
+//! - `.git/HEAD`, other branch refs, `packed-refs` -> main moved, refresh every task
+//!
+//! Bursts of events (a commit writes several files) are debounced into a
+//! single callback per project.
+
+use anyhow::{anyhow, Result};
+use notify::{Config, Event, RecommendedWatcher, RecursiveMode, Watcher};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::{mpsc, Arc, Mutex};
+use std::time::Duration;
+
+/// Quiet period before a burst of events is reported
+const DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// What a filesystem event invalidates
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum StatusChange {
+    /// One task's status (by display_id)
+    Task(String),
+    /// Every task in the project (main moved)
+    All,
+}
+
+/// Watches one project's git metadata and its task worktrees
+pub struct GitStatusWatcher {
+    watcher: RecommendedWatcher,
+    /// display_id -> watched worktree admin dir (`.git/worktrees/<name>`)
+    worktrees: HashMap<String, PathBuf>,
+    /// Admin dir name -> display_id, shared with the event callback
+    names: Arc<Mutex<HashMap<String, String>>>,
+}
+
+impl GitStatusWatcher {
+    /// Start watching `project_dir`. `on_change` receives the display_ids to
+    /// refresh (empty = all tasks) after each debounced burst.
+    pub fn new(project_dir: &Path, on_change: impl Fn(Vec<String>) + Send + 'static) -> Result<Self> {
+        let git_dir = project_dir.join(".git");
+        if !git_dir.is_dir() {
+            return Err(anyhow!("{} has no .git directory", project_dir.display()));
+        }
+
+        let names: Arc<Mutex<HashMap<String, String>>> = Arc::default();
+        let (tx, rx) = mpsc::channel::<StatusChange>();
+
+        let callback_names = names.clone();
+        let callback_git_dir = git_dir.clone();
+        let mut watcher = RecommendedWatcher::new(
+            move |event: notify::Result<Event>| {
+                let Ok(event) = event else { return };
+                let names = callback_names.lock().unwrap_or_else(|e| e.into_inner());
+                for path in &event.paths {
+                    if let Some(change) = classify(&callback_git_dir, path, &names) {
+                        let _ = tx.send(change);
+                    }
+                }
+            },
+            Config::default(),
+        )?;
+        watcher.watch(&git_dir, RecursiveMode::NonRecursive)?;
+        watcher.watch(&git_dir.join("refs").join("heads"), RecursiveMode::Recursive)?;
+
+        // Debounce thread - exits when the watcher (and its sender) is dropped
+        std::thread::spawn(move || {
+            let mut pending: HashSet<StatusChange> = HashSet::new();
+            loop {
+                match rx.recv_timeout(DEBOUNCE) {
+                    Ok(change) => {
+                        pending.insert(change);
+                    }
+                    Err(mpsc::RecvTimeoutError::Timeout) => {
+                        if pending.is_empty() {
+                            continue;
+                        }
+                        let ids = if pending.contains(&StatusChange::All) {
+                            Vec::new()
+                        } else {
+                            pending.iter().filter_map(|c| match c {
+                                StatusChange::Task(id) => Some(id.clone()),
+                                StatusChange::All => None,
+                            }).collect()
+                        };
+                        pending.clear();
+                        on_change(ids);
+                    }
+                    Err(mpsc::RecvTimeoutError::Disconnected) => return,
+                }
+            }
+        });
+
+        Ok(Self { watcher, worktrees: HashMap::new(), names })
+    }
+
+    /// Watch exactly these task worktrees (display_id, worktree path)
+    pub fn sync(&mut self, worktrees: &[(String, PathBuf)]) {
+        let wanted: HashSet<&String> = worktrees.iter().map(|(id, _)| id).collect();
+
+        // Stop watching removed worktrees
+        let removed: Vec<String> = self.worktrees.keys()
+            .filter(|id| !wanted.contains(id))
+            .cloned()
+            .collect();
+        for id in removed {
+            if let Some(admin_dir) = self.worktrees.remove(&id) {
+                let _ = self.watcher.unwatch(&admin_dir);
+            }
+        }
+
+        for (id, wt_path) in worktrees {
+            if self.worktrees.contains_key(id) {
+                continue;
+            }
+            let Some(admin_dir) = worktree_admin_dir(wt_path) else {
+                continue;
+            };
+            if self.watcher.watch(&admin_dir, RecursiveMode::NonRecursive).is_err() {
+                continue;
+            }
+            if let Some(name) = admin_dir.file_name() {
+                let mut names = self.names.lock().unwrap_or_else(|e| e.into_inner());
+                names.insert(name.to_string_lossy().to_string(), id.clone());
+            }
+            self.worktrees.insert(id.clone(), admin_dir);
+        }
+    }
+}
+
+/// The worktree's admin dir in the main repo, from its `.git` file ("gitdir: ...")
+fn worktree_admin_dir(worktree_path: &Path) -> Option<PathBuf> {
+    let content = std::fs::read_to_string(worktree_path.join(".git")).ok()?;
+    let gitdir = content.trim().strip_prefix("gitdir:")?.trim();
+    let path = PathBuf::from(gitdir);
+    Some(if path.is_absolute() { path } else { worktree_path.join(path) })
+}
+
+/// Map a changed path under `.git` to the status it invalidates
+fn classify(git_dir: &Path, path: &Path, names: &HashMap<String, String>) -> Option<StatusChange> {
+    let rel = path.strip_prefix(git_dir).ok()?.to_string_lossy().replace('\\', "/");
+    // Git writes "<ref>.lock" and renames it into place
+    let rel = rel.strip_suffix(".lock").unwrap_or(&rel);
+
+    if let Some(id) = rel.strip_prefix("refs/heads/claude/") {
+        return Some(StatusChange::Task(id.to_string()));
+    }
+    if rel.starts_with("refs/heads/") || rel == "HEAD" || rel == "packed-refs" {
+        return Some(StatusChange::All);
+    }
+    if let Some(rest) = rel.strip_prefix("worktrees/") {
+        let (name, file) = rest.split_once('/')?;
+        if file == "HEAD" {
+            return names.get(name).map(|id| StatusChange::Task(id.clone()));
+        }
+    }
+    // Objects, index, logs, FETCH_HEAD... don't change task status on their own
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify_git_paths() {
+        let git_dir = Path::new("/p/.git");
+        let names = HashMap::from([("ABCD-x1y".to_string(), "ABCD-x1y".to_string())]);
+        let change = |p: &str| classify(git_dir, &git_dir.join(p), &names);
+
+        assert_eq!(change("refs/heads/claude/ABCD-x1y.lock"), Some(StatusChange::Task("ABCD-x1y".to_string())));
+        assert_eq!(change("refs/heads/main"), Some(StatusChange::All));
+        assert_eq!(change("HEAD"), Some(StatusChange::All));
+        assert_eq!(change("worktrees/ABCD-x1y/HEAD"), Some(StatusChange::Task("ABCD-x1y".to_string())));
+        assert_eq!(change("worktrees/ABCD-x1y/index"), None);
+        assert_eq!(change("worktrees/other/HEAD"), None);
+        assert_eq!(change("objects/ab/cdef"), None);
+    }
+}
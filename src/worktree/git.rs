@@ -3,10 +3,13 @@
 #![allow(dead_code)]
 
 use anyhow::{anyhow, Context, Result};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 
+use super::progress::{run_git_with_progress, CancelToken, GitProgress};
+use crate::audit::{self, AuditEntry};
 use crate::model::ProjectCommands;
+use crate::timing::{self, OpKind, TimedCommand};
 
 /// Information about a worktree
 #[derive(Debug, Clone)]
@@ -28,50 +31,56 @@ pub fn get_worktree_path(project_dir: &PathBuf, display_id: &str) -> PathBuf {
 /// Create a new worktree for a task
 ///
 /// Creates a worktree at `{project_dir}/worktrees/{display_id}/`
-/// on branch `claude/{display_id}` based on the current HEAD.
+/// on branch `claude/{display_id}` based on `base` (the current HEAD if None).
 /// display_id should be like "ABBR-xyz" (4-char abbreviation + 3-char suffix)
+///
+/// With `sparse_patterns`, the worktree is added without a checkout and only
+/// the matching paths are materialized.
 pub fn create_worktree(
     project_dir: &PathBuf,
     display_id: &str,
+    sparse_patterns: &[String],
+    base: Option<&str>,
 ) -> Result<PathBuf> {
     let worktree_path = get_worktree_path(project_dir, display_id);
     let branch_name = format!("claude/{}", display_id);
 
-    // Ensure parent directory exists
+    // Ensure parent directory exists (on the remote for SSH projects)
     if let Some(parent) = worktree_path.parent() {
-        std::fs::create_dir_all(parent)?;
+        crate::remote::create_dir_all(parent)?;
     }
 
     // Check if worktree already exists
-    if worktree_path.exists() {
+    if crate::remote::exists(&worktree_path) {
         // Verify it's a valid worktree
         let git_dir = worktree_path.join(".git");
-        if git_dir.exists() {
+        if crate::remote::exists(&git_dir) {
             return Ok(worktree_path);
         }
         // Invalid state - remove and recreate
-        std::fs::remove_dir_all(&worktree_path)?;
+        crate::remote::remove_dir_all(&worktree_path)?;
     }
 
     // Check if branch already exists (from a crashed session)
     let branch_exists = Command::new("git")
         .current_dir(project_dir)
         .args(["rev-parse", "--verify", &branch_name])
-        .output()
+        .timed_output()
         .map(|o| o.status.success())
         .unwrap_or(false);
 
+    // Sparse worktrees skip the full checkout and populate after the patterns are set
+    let no_checkout: &[&str] = if sparse_patterns.is_empty() { &[] } else { &["--no-checkout"] };
+
     if branch_exists {
         // Branch exists, just add the worktree pointing to it
         let output = Command::new("git")
             .current_dir(project_dir)
-            .args([
-                "worktree",
-                "add",
-                &worktree_path.to_string_lossy(),
-                &branch_name,
-            ])
-            .output()?;
+            .args(["worktree", "add"])
+            .args(no_checkout)
+            .arg(&worktree_path)
+            .arg(&branch_name)
+            .timed_output()?;
 
         if !output.status.success() {
             let stderr = String::from_utf8_lossy(&output.stderr);
@@ -81,14 +90,15 @@ pub fn create_worktree(
         // Create new branch and worktree
         let output = Command::new("git")
             .current_dir(project_dir)
+            .args(["worktree", "add"])
+            .args(no_checkout)
             .args([
-                "worktree",
-                "add",
                 "-b",
                 &branch_name,
                 &worktree_path.to_string_lossy(),
             ])
-            .output()?;
+            .args(base)
+            .timed_output()?;
 
         if !output.status.success() {
             let stderr = String::from_utf8_lossy(&output.stderr);
@@ -96,9 +106,67 @@ pub fn create_worktree(
         }
     }
 
+    if !sparse_patterns.is_empty() {
+        apply_sparse_checkout(&worktree_path, sparse_patterns)?;
+        let output = Command::new("git")
+            .current_dir(&worktree_path)
+            .args(["checkout", "--quiet"])
+            .timed_output()?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(anyhow!("Failed to check out sparse worktree: {}", stderr));
+        }
+    }
+
     Ok(worktree_path)
 }
 
+// ============================================================================
+// Sparse checkout
+// ============================================================================
+
+/// Split a sparse profile ("services/api/** shared/, docs/") into patterns.
+/// Patterns are gitignore-style (non-cone mode); commas and whitespace separate them.
+pub fn parse_sparse_patterns(profile: &str) -> Vec<String> {
+    profile
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|p| !p.is_empty())
+        .map(|p| p.to_string())
+        .collect()
+}
+
+/// Restrict a worktree to `patterns`, or restore the full checkout when empty
+pub fn apply_sparse_checkout(worktree_path: &PathBuf, patterns: &[String]) -> Result<()> {
+    let output = if patterns.is_empty() {
+        // Only sparse worktrees need restoring ("list" fails on full ones)
+        let is_sparse = Command::new("git")
+            .current_dir(worktree_path)
+            .args(["sparse-checkout", "list"])
+            .timed_output()
+            .map(|o| o.status.success())
+            .unwrap_or(false);
+        if !is_sparse {
+            return Ok(());
+        }
+        Command::new("git")
+            .current_dir(worktree_path)
+            .args(["sparse-checkout", "disable"])
+            .timed_output()?
+    } else {
+        Command::new("git")
+            .current_dir(worktree_path)
+            .args(["sparse-checkout", "set", "--no-cone"])
+            .args(patterns)
+            .timed_output()?
+    };
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(anyhow!("Failed to apply sparse checkout: {}", stderr));
+    }
+    Ok(())
+}
+
 /// Remove a worktree
 pub fn remove_worktree(project_dir: &PathBuf, worktree_path: &PathBuf) -> Result<()> {
     // Use --force to remove even with uncommitted changes
@@ -110,7 +178,7 @@ pub fn remove_worktree(project_dir: &PathBuf, worktree_path: &PathBuf) -> Result
             "--force",
             &worktree_path.to_string_lossy(),
         ])
-        .output()?;
+        .timed_output()?;
 
     if !output.status.success() {
         // Try manual cleanup if git worktree remove fails
@@ -121,19 +189,230 @@ pub fn remove_worktree(project_dir: &PathBuf, worktree_path: &PathBuf) -> Result
         let _ = Command::new("git")
             .current_dir(project_dir)
             .args(["worktree", "prune"])
-            .output();
+            .timed_output();
     }
 
     Ok(())
 }
 
+// ============================================================================
+// Worktree pool
+// ============================================================================
+
+/// Directory holding pre-warmed worktrees: {project_dir}/worktrees/.pool/
+/// (inside the already-ignored worktrees/ dir)
+pub fn get_pool_dir(project_dir: &Path) -> PathBuf {
+    project_dir.join("worktrees").join(".pool")
+}
+
+/// Ready pool slots (detached worktrees), in a stable order.
+/// Slots still being created live under a `.tmp-` name and are skipped.
+pub fn pool_slots(project_dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(get_pool_dir(project_dir)) else {
+        return Vec::new();
+    };
+    let mut slots: Vec<PathBuf> = entries
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| {
+            p.file_name().is_some_and(|n| n.to_string_lossy().starts_with("slot-"))
+                && p.join(".git").exists()
+        })
+        .collect();
+    slots.sort();
+    slots
+}
+
+/// First unused slot name in the pool dir (checks both ready and in-progress slots)
+fn next_pool_slot_name(pool_dir: &Path) -> String {
+    (0..)
+        .map(|n| format!("slot-{}", n))
+        .find(|name| !pool_dir.join(name).exists() && !pool_dir.join(format!(".tmp-{}", name)).exists())
+        .unwrap_or_default()
+}
+
+/// Top up the pool with detached worktrees at the current HEAD until it holds `size` slots.
+/// Slots use the project's sparse patterns (if any) so taking one rarely has to reshape it.
+/// Returns how many slots were created. Safe to run in the background: slots are
+/// built under a temporary name and only moved into place once fully checked out.
+pub fn warm_worktree_pool(project_dir: &PathBuf, size: usize, sparse_patterns: &[String]) -> Result<usize> {
+    let pool_dir = get_pool_dir(project_dir);
+    std::fs::create_dir_all(&pool_dir)?;
+
+    let mut created = 0;
+    while pool_slots(project_dir).len() < size {
+        let name = next_pool_slot_name(&pool_dir);
+        let tmp_path = pool_dir.join(format!(".tmp-{}", name));
+
+        let no_checkout: &[&str] = if sparse_patterns.is_empty() { &[] } else { &["--no-checkout"] };
+        let output = Command::new("git")
+            .current_dir(project_dir)
+            .args(["worktree", "add", "--detach"])
+            .args(no_checkout)
+            .args([&tmp_path.to_string_lossy(), "HEAD"])
+            .timed_output()?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(anyhow!("Failed to create pooled worktree: {}", stderr));
+        }
+        if !sparse_patterns.is_empty() {
+            apply_sparse_checkout(&tmp_path, sparse_patterns)?;
+            let _ = Command::new("git")
+                .current_dir(&tmp_path)
+                .args(["checkout", "--quiet"])
+                .timed_output();
+        }
+
+        move_worktree(project_dir, &tmp_path, &pool_dir.join(&name))?;
+        created += 1;
+    }
+
+    Ok(created)
+}
+
+/// Take a worktree from the pool for a task: move it into place and check out
+/// a fresh `claude/{display_id}` branch at the main worktree's HEAD.
+///
+/// Returns `Ok(None)` when the pool is empty or the task already has a branch
+/// (e.g. after a crash) - callers fall back to `create_worktree`.
+pub fn take_pooled_worktree(project_dir: &PathBuf, display_id: &str, sparse_patterns: &[String]) -> Result<Option<PathBuf>> {
+    let worktree_path = get_worktree_path(project_dir, display_id);
+    let branch_name = format!("claude/{}", display_id);
+
+    let branch_exists = Command::new("git")
+        .current_dir(project_dir)
+        .args(["rev-parse", "--verify", &branch_name])
+        .timed_output()
+        .map(|o| o.status.success())
+        .unwrap_or(false);
+    if branch_exists || worktree_path.exists() {
+        return Ok(None);
+    }
+
+    let Some(slot) = pool_slots(project_dir).into_iter().next() else {
+        return Ok(None);
+    };
+
+    let head = Command::new("git")
+        .current_dir(project_dir)
+        .args(["rev-parse", "HEAD"])
+        .timed_output()?;
+    if !head.status.success() {
+        return Ok(None);
+    }
+    let head = String::from_utf8_lossy(&head.stdout).trim().to_string();
+
+    move_worktree(project_dir, &slot, &worktree_path)?;
+
+    // Reshape the slot if the task's sparse patterns differ from the pool's
+    if let Err(e) = apply_sparse_checkout(&worktree_path, sparse_patterns) {
+        let _ = remove_worktree(project_dir, &worktree_path);
+        return Err(e);
+    }
+
+    // Only files that differ between the slot's commit and HEAD are touched
+    let output = Command::new("git")
+        .current_dir(&worktree_path)
+        .args(["checkout", "--force", "-B", &branch_name, &head])
+        .timed_output()?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let _ = remove_worktree(project_dir, &worktree_path);
+        return Err(anyhow!("Failed to check out pooled worktree: {}", stderr));
+    }
+
+    // Drop leftovers from the previous task (ignored build output is kept warm)
+    let _ = Command::new("git")
+        .current_dir(&worktree_path)
+        .args(["clean", "-fdq"])
+        .timed_output();
+
+    Ok(Some(worktree_path))
+}
+
+/// Return a task's worktree to the pool if it has room, otherwise remove it.
+/// The worktree is reset and detached so the task branch can be deleted afterwards.
+pub fn release_worktree(project_dir: &PathBuf, worktree_path: &PathBuf, pool_size: usize) -> Result<()> {
+    if worktree_path.exists() {
+        let mut entry = AuditEntry::new("remove_worktree", project_dir)
+            .with_target(&worktree_path.to_string_lossy())
+            .with_shas(audit::rev(worktree_path, "HEAD"), None);
+        if let Some(display_id) = worktree_path.file_name().and_then(|n| n.to_str()) {
+            entry = entry.with_task(display_id);
+        }
+        audit::record(entry);
+    }
+
+    if pool_size == 0 || !worktree_path.exists() || pool_slots(project_dir).len() >= pool_size {
+        return remove_worktree(project_dir, worktree_path);
+    }
+
+    let recycled = (|| -> Result<()> {
+        super::caches::unshare_caches(worktree_path);
+        for args in [
+            &["checkout", "--force", "--detach"][..],
+            &["reset", "--hard", "--quiet"][..],
+            &["clean", "-fdq"][..],
+        ] {
+            let output = Command::new("git")
+                .current_dir(worktree_path)
+                .args(args)
+                .timed_output()?;
+            if !output.status.success() {
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                return Err(anyhow!("git {} failed: {}", args[0], stderr));
+            }
+        }
+        let pool_dir = get_pool_dir(project_dir);
+        std::fs::create_dir_all(&pool_dir)?;
+        move_worktree(project_dir, worktree_path, &pool_dir.join(next_pool_slot_name(&pool_dir)))
+    })();
+
+    // A worktree that can't be recycled is simply removed
+    if recycled.is_err() {
+        remove_worktree(project_dir, worktree_path)?;
+    }
+    Ok(())
+}
+
+/// Remove every pooled worktree (used when the pool is disabled)
+pub fn drain_worktree_pool(project_dir: &PathBuf) -> Result<()> {
+    for slot in pool_slots(project_dir) {
+        remove_worktree(project_dir, &slot)?;
+    }
+    Ok(())
+}
+
+fn move_worktree(project_dir: &Path, from: &Path, to: &Path) -> Result<()> {
+    if let Some(parent) = to.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let output = Command::new("git")
+        .current_dir(project_dir)
+        .args(["worktree", "move", &from.to_string_lossy(), &to.to_string_lossy()])
+        .timed_output()?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(anyhow!("Failed to move worktree: {}", stderr));
+    }
+    Ok(())
+}
+
 /// Check if a worktree has any uncommitted changes (staged or unstaged)
 /// Returns true if there are changes, false if clean
 pub fn has_uncommitted_changes(worktree_path: &PathBuf) -> Result<bool> {
+    if super::native::enabled() {
+        let dirty = timing::time(OpKind::Git, || format!("libgit2 status {}", worktree_path.display()), || {
+            super::native::has_uncommitted_changes(worktree_path)
+        });
+        if let Ok(dirty) = dirty {
+            return Ok(dirty);
+        }
+    }
     let status_output = Command::new("git")
         .current_dir(worktree_path)
         .args(["status", "--porcelain"])
-        .output()?;
+        .timed_output()?;
 
     let status = String::from_utf8_lossy(&status_output.stdout);
     Ok(!status.trim().is_empty())
@@ -158,7 +437,7 @@ pub fn commit_worktree_changes(worktree_path: &PathBuf, display_id: &str) -> Res
     let status_output = Command::new("git")
         .current_dir(worktree_path)
         .args(["status", "--porcelain"])
-        .output()?;
+        .timed_output()?;
 
     let status = String::from_utf8_lossy(&status_output.stdout);
     log(&format!("git status output: '{}'", status.trim()));
@@ -174,7 +453,7 @@ pub fn commit_worktree_changes(worktree_path: &PathBuf, display_id: &str) -> Res
     let add_output = Command::new("git")
         .current_dir(worktree_path)
         .args(["add", "-A"])
-        .output()?;
+        .timed_output()?;
 
     if !add_output.status.success() {
         let stderr = String::from_utf8_lossy(&add_output.stderr);
@@ -186,7 +465,7 @@ pub fn commit_worktree_changes(worktree_path: &PathBuf, display_id: &str) -> Res
     let commit_output = Command::new("git")
         .current_dir(worktree_path)
         .args(["commit", "-m", &commit_msg])
-        .output()?;
+        .timed_output()?;
 
     if !commit_output.status.success() {
         let stderr = String::from_utf8_lossy(&commit_output.stderr);
@@ -212,7 +491,7 @@ pub fn has_changes_to_merge(project_dir: &PathBuf, display_id: &str) -> Result<b
     let merge_base_output = Command::new("git")
         .current_dir(project_dir)
         .args(["merge-base", "HEAD", &branch_name])
-        .output()?;
+        .timed_output()?;
 
     if !merge_base_output.status.success() {
         // Branch might not exist
@@ -225,7 +504,7 @@ pub fn has_changes_to_merge(project_dir: &PathBuf, display_id: &str) -> Result<b
     let log_output = Command::new("git")
         .current_dir(project_dir)
         .args(["log", "--oneline", &format!("{}..{}", merge_base, branch_name)])
-        .output()?;
+        .timed_output()?;
 
     let log = String::from_utf8_lossy(&log_output.stdout);
     Ok(!log.trim().is_empty())
@@ -240,18 +519,19 @@ pub fn commit_main_changes(project_dir: &PathBuf) -> Result<bool> {
     let status_check = Command::new("git")
         .current_dir(project_dir)
         .args(["status", "--porcelain"])
-        .output()?;
+        .timed_output()?;
 
     let status_output = String::from_utf8_lossy(&status_check.stdout);
     if status_output.trim().is_empty() {
         return Ok(false); // Nothing to commit
     }
+    let before = main_head(project_dir);
 
     // Stage all changes
     let add_output = Command::new("git")
         .current_dir(project_dir)
         .args(["add", "-A"])
-        .output()?;
+        .timed_output()?;
 
     if !add_output.status.success() {
         let stderr = String::from_utf8_lossy(&add_output.stderr);
@@ -262,7 +542,7 @@ pub fn commit_main_changes(project_dir: &PathBuf) -> Result<bool> {
     let commit_output = Command::new("git")
         .current_dir(project_dir)
         .args(["commit", "-m", "WIP: uncommitted changes (auto-committed before task merge)"])
-        .output()?;
+        .timed_output()?;
 
     if !commit_output.status.success() {
         let stderr = String::from_utf8_lossy(&commit_output.stderr);
@@ -274,6 +554,9 @@ pub fn commit_main_changes(project_dir: &PathBuf) -> Result<bool> {
         return Err(anyhow!("Failed to commit changes on main: {}", stderr));
     }
 
+    audit::record(AuditEntry::new("commit_main", project_dir)
+        .with_shas(before, main_head(project_dir))
+        .with_detail("auto-committed uncommitted changes before a merge"));
     Ok(true)
 }
 
@@ -285,7 +568,7 @@ pub fn commit_applied_changes(project_dir: &PathBuf, task_title: &str, display_i
     let has_staged = Command::new("git")
         .current_dir(project_dir)
         .args(["diff", "--cached", "--quiet"])
-        .status()
+        .timed_status()
         .map(|s| !s.success())  // exit 1 means there ARE differences
         .unwrap_or(false);
 
@@ -294,11 +577,12 @@ pub fn commit_applied_changes(project_dir: &PathBuf, task_title: &str, display_i
     }
 
     // Commit only staged changes (task's applied changes)
+    let before = main_head(project_dir);
     let commit_msg = format!("Merge task {} from Claude session\n\nTask: {}", display_id, task_title);
     let commit_output = Command::new("git")
         .current_dir(project_dir)
         .args(["commit", "-m", &commit_msg])
-        .output()?;
+        .timed_output()?;
 
     if !commit_output.status.success() {
         let stderr = String::from_utf8_lossy(&commit_output.stderr);
@@ -310,12 +594,23 @@ pub fn commit_applied_changes(project_dir: &PathBuf, task_title: &str, display_i
         return Err(anyhow!("Failed to commit changes: {}", stderr));
     }
 
+    audit::record(AuditEntry::new("commit_applied", project_dir)
+        .with_task(display_id)
+        .with_shas(before, main_head(project_dir)));
     Ok(true)
 }
 
-/// Merge a task branch into the base branch (squash merge)
+/// Merge a task branch into the base branch (squash merge), committed with
+/// `message` or the plain "Merge task ..." one
 /// Requires clean working directory - call commit_main_changes first if needed
-pub fn merge_branch(project_dir: &PathBuf, display_id: &str) -> Result<()> {
+/// Cancelling kills the merge and puts main back as it was.
+pub fn merge_branch(
+    project_dir: &PathBuf,
+    display_id: &str,
+    message: Option<&str>,
+    cancel: &CancelToken,
+    on_progress: &mut dyn FnMut(GitProgress),
+) -> Result<()> {
     let branch_name = format!("claude/{}", display_id);
 
     // Verify working directory is clean
@@ -323,7 +618,7 @@ pub fn merge_branch(project_dir: &PathBuf, display_id: &str) -> Result<()> {
     let status_check = Command::new("git")
         .current_dir(project_dir)
         .args(["status", "--porcelain"])
-        .output()?;
+        .timed_output()?;
 
     if !String::from_utf8_lossy(&status_check.stdout).trim().is_empty() {
         return Err(anyhow!(
@@ -332,11 +627,19 @@ pub fn merge_branch(project_dir: &PathBuf, display_id: &str) -> Result<()> {
     }
 
     // Perform squash merge
-    let output = Command::new("git")
-        .current_dir(project_dir)
-        .args(["merge", "--squash", &branch_name])
-        .output()
-        .context("Failed to run merge")?;
+    let before = main_head(project_dir);
+    let merged = run_git_with_progress(project_dir, &["merge", "--squash", "--progress", &branch_name], cancel, on_progress);
+    let output = match merged {
+        Ok(output) => output,
+        Err(e) => {
+            // Killed mid-merge: put the index and files back
+            let _ = Command::new("git")
+                .current_dir(project_dir)
+                .args(["reset", "--merge"])
+                .timed_output();
+            return Err(e.context("Failed to run merge"));
+        }
+    };
 
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
@@ -344,7 +647,7 @@ pub fn merge_branch(project_dir: &PathBuf, display_id: &str) -> Result<()> {
         let _ = Command::new("git")
             .current_dir(project_dir)
             .args(["merge", "--abort"])
-            .output();
+            .timed_output();
         return Err(anyhow!(
             "Merge failed (conflicts?): {}. Resolve in {} and commit manually.",
             stderr,
@@ -357,21 +660,21 @@ pub fn merge_branch(project_dir: &PathBuf, display_id: &str) -> Result<()> {
     let _ = Command::new("git")
         .current_dir(project_dir)
         .args(["checkout", "HEAD", "--", ".kanblam", ".claude"])
-        .output();
+        .timed_output();
 
     // Check if there are staged changes to commit
     let status_output = Command::new("git")
         .current_dir(project_dir)
         .args(["diff", "--cached", "--quiet"])
-        .output()?;
+        .timed_output()?;
 
     if !status_output.status.success() {
         // There are staged changes, commit them
-        let commit_msg = format!("Merge task {} from Claude session", display_id);
+        let commit_msg = message.map_or_else(|| crate::commit_message::fallback(display_id), str::to_string);
         let output = Command::new("git")
             .current_dir(project_dir)
             .args(["commit", "-m", &commit_msg])
-            .output()?;
+            .timed_output()?;
 
         if !output.status.success() {
             let stderr = String::from_utf8_lossy(&output.stderr);
@@ -379,6 +682,10 @@ pub fn merge_branch(project_dir: &PathBuf, display_id: &str) -> Result<()> {
         }
     }
 
+    audit::record(AuditEntry::new("merge", project_dir)
+        .with_task(display_id)
+        .with_target(&branch_name)
+        .with_shas(before, main_head(project_dir)));
     Ok(())
 }
 
@@ -387,10 +694,11 @@ pub fn delete_branch(project_dir: &PathBuf, display_id: &str) -> Result<()> {
     let branch_name = format!("claude/{}", display_id);
 
     // Use -D to force delete even if not merged
+    let before = branch_head(project_dir, display_id);
     let output = Command::new("git")
         .current_dir(project_dir)
         .args(["branch", "-D", &branch_name])
-        .output()?;
+        .timed_output()?;
 
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
@@ -400,6 +708,12 @@ pub fn delete_branch(project_dir: &PathBuf, display_id: &str) -> Result<()> {
         }
     }
 
+    if before.is_some() {
+        audit::record(AuditEntry::new("delete_branch", project_dir)
+            .with_task(display_id)
+            .with_target(&branch_name)
+            .with_shas(before, None));
+    }
     Ok(())
 }
 
@@ -412,7 +726,7 @@ fn safe_stash_restore(project_dir: &PathBuf, stash_sha: &Option<String>) -> Resu
         let verify = Command::new("git")
             .current_dir(project_dir)
             .args(["rev-parse", "--verify", &format!("{}^{{commit}}", sha)])
-            .output();
+            .timed_output();
 
         match verify {
             Ok(output) if !output.status.success() => {
@@ -434,7 +748,7 @@ fn safe_stash_restore(project_dir: &PathBuf, stash_sha: &Option<String>) -> Resu
         let apply_result = Command::new("git")
             .current_dir(project_dir)
             .args(["stash", "apply", sha])
-            .output();
+            .timed_output();
 
         match apply_result {
             Ok(output) if output.status.success() => {
@@ -442,14 +756,14 @@ fn safe_stash_restore(project_dir: &PathBuf, stash_sha: &Option<String>) -> Resu
                 let drop_result = Command::new("git")
                     .current_dir(project_dir)
                     .args(["stash", "drop", sha])
-                    .output();
+                    .timed_output();
 
                 // Even if drop fails, the changes are restored - just warn
                 if let Ok(drop_output) = drop_result {
                     if !drop_output.status.success() {
                         // Changes restored but stash not dropped - not critical
                         // User can manually drop it with 'git stash drop'
-                        eprintln!("Warning: Stash applied but could not be dropped. Run 'git stash drop {}' manually.", sha);
+                        crate::diagnostics::warn(crate::diagnostics::Source::Git, format!("Stash applied but could not be dropped. Run 'git stash drop {}' manually.", sha));
                     }
                 }
                 Ok(())
@@ -478,7 +792,7 @@ fn get_stash_sha(project_dir: &PathBuf) -> Result<String> {
     let output = Command::new("git")
         .current_dir(project_dir)
         .args(["rev-parse", "stash@{0}"])
-        .output()?;
+        .timed_output()?;
 
     if !output.status.success() {
         return Err(anyhow!("No stash found"));
@@ -533,6 +847,17 @@ pub fn cleanup_applied_state(display_id: &str) {
 /// * `display_id` - The task's display ID (for patch file path and logging)
 /// * `branch_name` - The actual git branch name (e.g., "claude/ABBR-xyz")
 pub fn apply_task_changes(project_dir: &PathBuf, display_id: &str, branch_name: &str) -> Result<Option<String>> {
+    let result = apply_task_changes_inner(project_dir, display_id, branch_name);
+    if result.is_ok() {
+        audit::record(AuditEntry::new("apply", project_dir)
+            .with_task(display_id)
+            .with_target(branch_name)
+            .with_shas(None, branch_head(project_dir, display_id)));
+    }
+    result
+}
+
+fn apply_task_changes_inner(project_dir: &PathBuf, display_id: &str, branch_name: &str) -> Result<Option<String>> {
 
     // Debug logging to file (TUI covers stderr)
     let log_path = std::path::PathBuf::from("/tmp/kanblam-apply.log");
@@ -550,7 +875,7 @@ pub fn apply_task_changes(project_dir: &PathBuf, display_id: &str, branch_name:
     let unmerged_check = Command::new("git")
         .current_dir(project_dir)
         .args(["ls-files", "-u"])
-        .output()?;
+        .timed_output()?;
 
     if !unmerged_check.stdout.is_empty() {
         let unmerged_files = String::from_utf8_lossy(&unmerged_check.stdout);
@@ -565,7 +890,7 @@ pub fn apply_task_changes(project_dir: &PathBuf, display_id: &str, branch_name:
     let conflict_check = Command::new("git")
         .current_dir(project_dir)
         .args(["diff", "--check"])
-        .output();
+        .timed_output();
 
     if let Ok(output) = conflict_check {
         if !output.status.success() {
@@ -587,7 +912,7 @@ pub fn apply_task_changes(project_dir: &PathBuf, display_id: &str, branch_name:
     let status_check = Command::new("git")
         .current_dir(project_dir)
         .args(["status", "--porcelain"])
-        .output()?;
+        .timed_output()?;
 
     // Filter out untracked files (??) and .kanblam/ files - only stash other tracked changes
     let status_output = String::from_utf8_lossy(&status_check.stdout);
@@ -603,7 +928,7 @@ pub fn apply_task_changes(project_dir: &PathBuf, display_id: &str, branch_name:
         let stash_output = Command::new("git")
             .current_dir(project_dir)
             .args(["stash", "push", "-m", &format!("kanblam: before applying task {}", display_id), "--", ".", ":!.kanblam"])
-            .output()?;
+            .timed_output()?;
 
         if !stash_output.status.success() {
             let stderr = String::from_utf8_lossy(&stash_output.stderr);
@@ -630,7 +955,7 @@ pub fn apply_task_changes(project_dir: &PathBuf, display_id: &str, branch_name:
     let merge_base_output = Command::new("git")
         .current_dir(project_dir)
         .args(["merge-base", "HEAD", &branch_name])
-        .output()?;
+        .timed_output()?;
 
     if !merge_base_output.status.success() {
         // Restore stash if we made one - fail if we can't restore
@@ -647,7 +972,7 @@ pub fn apply_task_changes(project_dir: &PathBuf, display_id: &str, branch_name:
     let diff_output = Command::new("git")
         .current_dir(project_dir)
         .args(["diff", &merge_base, &branch_name, "--", ".", ":!.kanblam", ":!.claude"])
-        .output()?;
+        .timed_output()?;
 
     if !diff_output.status.success() {
         // Restore stash if we made one - fail if we can't restore
@@ -673,9 +998,10 @@ pub fn apply_task_changes(project_dir: &PathBuf, display_id: &str, branch_name:
     log(&format!("saved patch to {:?}", patch_path));
 
     // Apply the diff (capture stderr so we can log it)
-    let mut apply_cmd = Command::new("git")
-        .current_dir(project_dir)
-        .args(["apply", "--3way"])
+    let mut apply = Command::new("git");
+    apply.current_dir(project_dir).args(["apply", "--3way"]);
+    super::snapshot::route(&mut apply);
+    let mut apply_cmd = crate::remote::routed(apply)
         .stdin(std::process::Stdio::piped())
         .stdout(std::process::Stdio::piped())
         .stderr(std::process::Stdio::piped())
@@ -709,7 +1035,7 @@ pub fn apply_task_changes(project_dir: &PathBuf, display_id: &str, branch_name:
         let _ = Command::new("git")
             .current_dir(project_dir)
             .args(["reset", "--hard", "HEAD"])
-            .output();
+            .timed_output();
 
         // Restore stash if we made one - use SHA-based restore for reliability
         if let Err(e) = safe_stash_restore(project_dir, &stash_ref) {
@@ -739,7 +1065,7 @@ pub fn apply_task_changes(project_dir: &PathBuf, display_id: &str, branch_name:
     let unmerged_check = Command::new("git")
         .current_dir(project_dir)
         .args(["ls-files", "-u"])
-        .output();
+        .timed_output();
 
     if let Ok(output) = unmerged_check {
         if !output.stdout.is_empty() {
@@ -748,7 +1074,7 @@ pub fn apply_task_changes(project_dir: &PathBuf, display_id: &str, branch_name:
             let _ = Command::new("git")
                 .current_dir(project_dir)
                 .args(["add", "-u"])  // Only stage modified tracked files
-                .output();
+                .timed_output();
         }
     }
 
@@ -758,7 +1084,7 @@ pub fn apply_task_changes(project_dir: &PathBuf, display_id: &str, branch_name:
         let pop_result = Command::new("git")
             .current_dir(project_dir)
             .args(["stash", "pop"])
-            .output();
+            .timed_output();
 
         match pop_result {
             Ok(output) if output.status.success() => {
@@ -795,6 +1121,14 @@ pub enum UnapplyResult {
 /// No stash handling needed - stash was already popped immediately after apply.
 /// Returns Success if the patch was cleanly reversed, NeedsConfirmation if destructive reset is needed.
 pub fn unapply_task_changes(project_dir: &PathBuf, display_id: &str) -> Result<UnapplyResult> {
+    let result = unapply_task_changes_inner(project_dir, display_id);
+    if matches!(result, Ok(UnapplyResult::Success)) {
+        audit::record(AuditEntry::new("unapply", project_dir).with_task(display_id));
+    }
+    result
+}
+
+fn unapply_task_changes_inner(project_dir: &PathBuf, display_id: &str) -> Result<UnapplyResult> {
     let patch_path = get_patch_file_path(display_id);
 
     // If we have a saved patch, try surgical reversal
@@ -806,7 +1140,7 @@ pub fn unapply_task_changes(project_dir: &PathBuf, display_id: &str) -> Result<U
         let has_unstaged = Command::new("git")
             .current_dir(project_dir)
             .args(["diff", "--quiet", "--", ".", ":!.kanblam"])
-            .status()
+            .timed_status()
             .map(|s| !s.success())
             .unwrap_or(false);
 
@@ -816,16 +1150,17 @@ pub fn unapply_task_changes(project_dir: &PathBuf, display_id: &str) -> Result<U
             let stash_result = Command::new("git")
                 .current_dir(project_dir)
                 .args(["stash", "push", "--keep-index", "-m", "kanblam: unapply temp stash", "--", ".", ":!.kanblam"])
-                .output()?;
+                .timed_output()?;
             stash_result.status.success()
         } else {
             false
         };
 
         // Try to reverse the patch
-        let mut apply_cmd = Command::new("git")
-            .current_dir(project_dir)
-            .args(["apply", "-R", "--3way"])
+        let mut apply = Command::new("git");
+        apply.current_dir(project_dir).args(["apply", "-R", "--3way"]);
+        super::snapshot::route(&mut apply);
+        let mut apply_cmd = crate::remote::routed(apply)
             .stdin(std::process::Stdio::piped())
             .stdout(std::process::Stdio::piped())
             .stderr(std::process::Stdio::piped())
@@ -857,7 +1192,7 @@ pub fn unapply_task_changes(project_dir: &PathBuf, display_id: &str) -> Result<U
                 for file in &task_files {
                     reset_cmd.arg(file);
                 }
-                let _ = reset_cmd.output();
+                let _ = reset_cmd.timed_output();
             }
 
             // Clean up the patch file
@@ -868,7 +1203,7 @@ pub fn unapply_task_changes(project_dir: &PathBuf, display_id: &str) -> Result<U
                 let _ = Command::new("git")
                     .current_dir(project_dir)
                     .args(["stash", "pop"])
-                    .output();
+                    .timed_output();
             }
 
             return Ok(UnapplyResult::Success);
@@ -879,7 +1214,7 @@ pub fn unapply_task_changes(project_dir: &PathBuf, display_id: &str) -> Result<U
             let _ = Command::new("git")
                 .current_dir(project_dir)
                 .args(["stash", "pop"])
-                .output();
+                .timed_output();
         }
 
         // Need user confirmation for destructive reset
@@ -919,7 +1254,7 @@ pub fn surgical_unapply_for_stash_conflict(project_dir: &PathBuf, display_id: &s
         let checkout_result = Command::new("git")
             .current_dir(project_dir)
             .args(["checkout", "HEAD", "--", file_path])
-            .output()?;
+            .timed_output()?;
 
         if !checkout_result.status.success() {
             let stderr = String::from_utf8_lossy(&checkout_result.stderr);
@@ -941,7 +1276,7 @@ pub fn force_unapply_task_changes(project_dir: &PathBuf, display_id: &str) -> Re
     let reset_output = Command::new("git")
         .current_dir(project_dir)
         .args(["reset", "--hard", "HEAD"])
-        .output()?;
+        .timed_output()?;
 
     if !reset_output.status.success() {
         let stderr = String::from_utf8_lossy(&reset_output.stderr);
@@ -952,6 +1287,9 @@ pub fn force_unapply_task_changes(project_dir: &PathBuf, display_id: &str) -> Re
     let patch_path = get_patch_file_path(display_id);
     let _ = std::fs::remove_file(&patch_path);
 
+    audit::record(AuditEntry::new("force_unapply", project_dir)
+        .with_task(display_id)
+        .with_detail("reset --hard HEAD on the main worktree"));
     Ok(())
 }
 
@@ -960,7 +1298,7 @@ pub fn list_worktrees(project_dir: &PathBuf) -> Result<Vec<WorktreeInfo>> {
     let output = Command::new("git")
         .current_dir(project_dir)
         .args(["worktree", "list", "--porcelain"])
-        .output()?;
+        .timed_output()?;
 
     if !output.status.success() {
         return Ok(Vec::new());
@@ -1007,17 +1345,41 @@ pub fn is_git_repo(project_dir: &PathBuf) -> bool {
     let output = Command::new("git")
         .current_dir(project_dir)
         .args(["rev-parse", "--git-dir"])
-        .output();
+        .timed_output();
 
     output.map(|o| o.status.success()).unwrap_or(false)
 }
 
+/// Split a directory inside a repository into the repository root and the
+/// directory's path relative to it (None when it is the root). Remote
+/// directories and directories outside any repository come back unchanged.
+pub fn split_scope(dir: &Path) -> (PathBuf, Option<PathBuf>) {
+    if crate::remote::is_remote(dir) {
+        return (dir.to_path_buf(), None);
+    }
+    let output = Command::new("git")
+        .current_dir(dir)
+        .args(["rev-parse", "--show-toplevel"])
+        .timed_output();
+    let root = match output {
+        Ok(o) if o.status.success() => PathBuf::from(String::from_utf8_lossy(&o.stdout).trim()),
+        _ => return (dir.to_path_buf(), None),
+    };
+    let (Ok(canonical_root), Ok(canonical_dir)) = (root.canonicalize(), dir.canonicalize()) else {
+        return (dir.to_path_buf(), None);
+    };
+    match canonical_dir.strip_prefix(&canonical_root) {
+        Ok(scope) if !scope.as_os_str().is_empty() => (canonical_root, Some(scope.to_path_buf())),
+        _ => (dir.to_path_buf(), None),
+    }
+}
+
 /// Check if a git repository has at least one commit
 pub fn has_commits(project_dir: &PathBuf) -> bool {
     let output = Command::new("git")
         .current_dir(project_dir)
         .args(["rev-parse", "HEAD"])
-        .output();
+        .timed_output();
 
     output.map(|o| o.status.success()).unwrap_or(false)
 }
@@ -1027,7 +1389,7 @@ pub fn init_repo(project_dir: &PathBuf) -> Result<()> {
     let output = Command::new("git")
         .current_dir(project_dir)
         .args(["init"])
-        .output()?;
+        .timed_output()?;
 
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
@@ -1046,7 +1408,7 @@ pub fn create_initial_commit(project_dir: &PathBuf) -> Result<()> {
     let add_output = Command::new("git")
         .current_dir(project_dir)
         .args(["add", "-A"])
-        .output()?;
+        .timed_output()?;
 
     if !add_output.status.success() {
         let stderr = String::from_utf8_lossy(&add_output.stderr);
@@ -1057,7 +1419,7 @@ pub fn create_initial_commit(project_dir: &PathBuf) -> Result<()> {
     let commit_output = Command::new("git")
         .current_dir(project_dir)
         .args(["commit", "--allow-empty", "-m", "Initial commit"])
-        .output()?;
+        .timed_output()?;
 
     if !commit_output.status.success() {
         let stderr = String::from_utf8_lossy(&commit_output.stderr);
@@ -1165,8 +1527,42 @@ pub fn ensure_gitignore_has_kanblam_entries(project_dir: &PathBuf) -> Result<()>
     Ok(())
 }
 
-/// Get the diff between main/master and a task branch
-pub fn get_task_diff(project_dir: &PathBuf, display_id: &str) -> Result<String> {
+/// Get the diff between main/master and a task branch, leaving out files
+/// matching the project's `ignore` patterns
+pub fn get_task_diff(project_dir: &PathBuf, display_id: &str, ignore: &[String]) -> Result<String> {
+    let diff = full_task_diff(project_dir, display_id)?;
+    Ok(super::ignore::filter_diff(&diff, ignore))
+}
+
+/// Get everything a task's worktree changed against main/master, committed or
+/// not (untracked files aren't included), leaving out files matching `ignore`
+pub fn get_worktree_diff(project_dir: &PathBuf, worktree_path: &Path, ignore: &[String]) -> Result<String> {
+    let base_branch = find_base_branch(project_dir)?;
+
+    let output = Command::new("git")
+        .current_dir(worktree_path)
+        .args(["diff", &base_branch])
+        .timed_output()?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(anyhow!("Failed to get diff: {}", stderr));
+    }
+
+    let diff = String::from_utf8_lossy(&output.stdout);
+    Ok(super::ignore::filter_diff(&diff, ignore))
+}
+
+fn full_task_diff(project_dir: &PathBuf, display_id: &str) -> Result<String> {
+    if super::native::enabled() {
+        let diff = timing::time(OpKind::Git, || format!("libgit2 diff claude/{}", display_id), || {
+            super::native::task_diff(project_dir, display_id)
+        });
+        if let Ok(diff) = diff {
+            return Ok(diff);
+        }
+    }
+
     let branch_name = format!("claude/{}", display_id);
 
     // Try to find the base branch (main or master)
@@ -1175,7 +1571,7 @@ pub fn get_task_diff(project_dir: &PathBuf, display_id: &str) -> Result<String>
     let output = Command::new("git")
         .current_dir(project_dir)
         .args(["diff", &format!("{}..{}", base_branch, branch_name)])
-        .output()?;
+        .timed_output()?;
 
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
@@ -1185,13 +1581,53 @@ pub fn get_task_diff(project_dir: &PathBuf, display_id: &str) -> Result<String>
     Ok(String::from_utf8_lossy(&output.stdout).to_string())
 }
 
+/// Commit the task branch points at (None if the branch doesn't exist)
+pub fn branch_head(project_dir: &Path, display_id: &str) -> Option<String> {
+    let output = Command::new("git")
+        .current_dir(project_dir)
+        .args(["rev-parse", "--verify", "--quiet", &format!("claude/{}", display_id)])
+        .timed_output()
+        .ok()?;
+    output.status.success().then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Commit the main worktree's HEAD points at (None outside a repo or before the first commit)
+pub fn main_head(project_dir: &Path) -> Option<String> {
+    let output = Command::new("git")
+        .current_dir(project_dir)
+        .args(["rev-parse", "--verify", "--quiet", "HEAD"])
+        .timed_output()
+        .ok()?;
+    output.status.success().then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Run git in `dir` and return its stdout (an error with its stderr if it fails)
+pub(crate) fn git_stdout(dir: &Path, args: &[&str]) -> Result<String> {
+    let output = Command::new("git")
+        .current_dir(dir)
+        .args(args)
+        .timed_output()
+        .with_context(|| format!("Failed to run git {}", args.join(" ")))?;
+    if !output.status.success() {
+        return Err(anyhow!("git {} failed: {}", args.join(" "), String::from_utf8_lossy(&output.stderr).trim()));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Run git in `dir` from a test, failing it with git's stderr
+#[cfg(test)]
+pub(crate) fn test_git(dir: &Path, args: &[&str]) {
+    let output = Command::new("git").current_dir(dir).args(args).output().unwrap();
+    assert!(output.status.success(), "git {:?}: {}", args, String::from_utf8_lossy(&output.stderr));
+}
+
 /// Find the base branch (main or master)
-fn find_base_branch(project_dir: &PathBuf) -> Result<String> {
+pub(crate) fn find_base_branch(project_dir: &PathBuf) -> Result<String> {
     // Check for main first
     let output = Command::new("git")
         .current_dir(project_dir)
         .args(["rev-parse", "--verify", "main"])
-        .output()?;
+        .timed_output()?;
 
     if output.status.success() {
         return Ok("main".to_string());
@@ -1201,7 +1637,7 @@ fn find_base_branch(project_dir: &PathBuf) -> Result<String> {
     let output = Command::new("git")
         .current_dir(project_dir)
         .args(["rev-parse", "--verify", "master"])
-        .output()?;
+        .timed_output()?;
 
     if output.status.success() {
         return Ok("master".to_string());
@@ -1231,7 +1667,7 @@ pub fn is_branch_merged(project_dir: &PathBuf, display_id: &str) -> Result<bool>
     let branch_exists = Command::new("git")
         .current_dir(project_dir)
         .args(["rev-parse", "--verify", &branch_name])
-        .output()
+        .timed_output()
         .map(|o| o.status.success())
         .unwrap_or(false);
 
@@ -1246,7 +1682,7 @@ pub fn is_branch_merged(project_dir: &PathBuf, display_id: &str) -> Result<bool>
     let branch_commits = Command::new("git")
         .current_dir(project_dir)
         .args(["log", "--oneline", &format!("HEAD..{}", branch_name)])
-        .output()
+        .timed_output()
         .context("Failed to check for branch commits")?;
 
     if !branch_commits.status.success() {
@@ -1265,7 +1701,7 @@ pub fn is_branch_merged(project_dir: &PathBuf, display_id: &str) -> Result<bool>
     let diff_check = Command::new("git")
         .current_dir(project_dir)
         .args(["diff", "--quiet", "HEAD", &branch_name])
-        .status()
+        .timed_status()
         .context("Failed to check diff")?;
 
     if !diff_check.success() {
@@ -1288,7 +1724,7 @@ pub fn needs_rebase(project_dir: &PathBuf, display_id: &str) -> Result<bool> {
     let merge_base = Command::new("git")
         .current_dir(project_dir)
         .args(["merge-base", "HEAD", &branch_name])
-        .output()
+        .timed_output()
         .context("Failed to get merge base")?;
 
     if !merge_base.status.success() {
@@ -1300,7 +1736,7 @@ pub fn needs_rebase(project_dir: &PathBuf, display_id: &str) -> Result<bool> {
     let main_head = Command::new("git")
         .current_dir(project_dir)
         .args(["rev-parse", "HEAD"])
-        .output()
+        .timed_output()
         .context("Failed to get HEAD")?;
 
     let merge_base_hash = String::from_utf8_lossy(&merge_base.stdout).trim().to_string();
@@ -1315,13 +1751,29 @@ pub fn needs_rebase(project_dir: &PathBuf, display_id: &str) -> Result<bool> {
 /// Returns Ok(false) if rebase failed due to conflicts (aborted automatically).
 /// Returns Err if something unexpected went wrong.
 pub fn try_fast_rebase(worktree_path: &PathBuf, project_dir: &PathBuf) -> Result<bool> {
+    let before = audit::rev(worktree_path, "HEAD");
+    let result = try_fast_rebase_inner(worktree_path, project_dir);
+    let after = audit::rev(worktree_path, "HEAD");
+    if matches!(result, Ok(true)) && before != after {
+        let mut entry = AuditEntry::new("rebase", project_dir)
+            .with_target(&worktree_path.to_string_lossy())
+            .with_shas(before, after);
+        if let Some(display_id) = worktree_path.file_name().and_then(|n| n.to_str()) {
+            entry = entry.with_task(display_id);
+        }
+        audit::record(entry);
+    }
+    result
+}
+
+fn try_fast_rebase_inner(worktree_path: &PathBuf, project_dir: &PathBuf) -> Result<bool> {
     // SAFETY: Check if a rebase is already in progress (from a previous failed attempt)
     if is_rebase_in_progress(worktree_path) {
         // Abort any existing rebase first
         let _ = Command::new("git")
             .current_dir(worktree_path)
             .args(["rebase", "--abort"])
-            .output();
+            .timed_output();
     }
 
     // First, fetch to make sure we have latest main
@@ -1329,13 +1781,13 @@ pub fn try_fast_rebase(worktree_path: &PathBuf, project_dir: &PathBuf) -> Result
     let _ = Command::new("git")
         .current_dir(project_dir)
         .args(["fetch", "origin", "main"])
-        .output();
+        .timed_output();
 
     // Get the main branch HEAD to rebase onto
     let main_head = Command::new("git")
         .current_dir(project_dir)
         .args(["rev-parse", "HEAD"])
-        .output()
+        .timed_output()
         .context("Failed to get main HEAD")?;
 
     if !main_head.status.success() {
@@ -1348,7 +1800,7 @@ pub fn try_fast_rebase(worktree_path: &PathBuf, project_dir: &PathBuf) -> Result
     let original_head = Command::new("git")
         .current_dir(worktree_path)
         .args(["rev-parse", "HEAD"])
-        .output()
+        .timed_output()
         .ok()
         .filter(|o| o.status.success())
         .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string());
@@ -1357,7 +1809,7 @@ pub fn try_fast_rebase(worktree_path: &PathBuf, project_dir: &PathBuf) -> Result
     let rebase_result = Command::new("git")
         .current_dir(worktree_path)
         .args(["rebase", &main_ref])
-        .output()
+        .timed_output()
         .context("Failed to run rebase")?;
 
     if rebase_result.status.success() {
@@ -1372,10 +1824,9 @@ pub fn try_fast_rebase(worktree_path: &PathBuf, project_dir: &PathBuf) -> Result
             // Parse the check command to get program and args
             let parts: Vec<&str> = check_cmd.split_whitespace().collect();
             if let Some((program, args)) = parts.split_first() {
-                let build_result = Command::new(program)
-                    .current_dir(worktree_path)
-                    .args(args)
-                    .output();
+                let mut build = Command::new(program);
+                build.current_dir(worktree_path).args(args);
+                let build_result = crate::remote::routed(build).output();
 
                 match build_result {
                     Ok(output) if output.status.success() => {
@@ -1389,11 +1840,13 @@ pub fn try_fast_rebase(worktree_path: &PathBuf, project_dir: &PathBuf) -> Result
                             let _ = Command::new("git")
                                 .current_dir(worktree_path)
                                 .args(["reset", "--hard", orig])
-                                .output();
+                                .timed_output();
                         }
                         let stderr = String::from_utf8_lossy(&output.stderr);
-                        eprintln!("Fast rebase succeeded but build failed - falling back to Claude: {}",
-                            stderr.lines().take(5).collect::<Vec<_>>().join("\n"));
+                        crate::diagnostics::warn(crate::diagnostics::Source::Git, format!(
+                            "Fast rebase succeeded but build failed - falling back to Claude: {}",
+                            stderr.lines().take(5).collect::<Vec<_>>().join("\n")
+                        ));
                         return Ok(false); // Fall back to Claude
                     }
                     Err(_) => {
@@ -1415,7 +1868,7 @@ pub fn try_fast_rebase(worktree_path: &PathBuf, project_dir: &PathBuf) -> Result
     let abort_result = Command::new("git")
         .current_dir(worktree_path)
         .args(["rebase", "--abort"])
-        .output();
+        .timed_output();
 
     // Check if abort succeeded (both execution and exit code)
     let abort_ok = match &abort_result {
@@ -1429,7 +1882,7 @@ pub fn try_fast_rebase(worktree_path: &PathBuf, project_dir: &PathBuf) -> Result
             let reset_result = Command::new("git")
                 .current_dir(worktree_path)
                 .args(["reset", "--hard", orig])
-                .output();
+                .timed_output();
 
             if reset_result.is_err() || !reset_result.unwrap().status.success() {
                 // CRITICAL: Could not restore worktree state
@@ -1471,7 +1924,7 @@ pub fn verify_rebase_success(project_dir: &PathBuf, display_id: &str) -> Result<
     let branch_head = Command::new("git")
         .current_dir(project_dir)
         .args(["rev-parse", &branch_name])
-        .output()
+        .timed_output()
         .context("Failed to get branch HEAD")?;
 
     if !branch_head.status.success() {
@@ -1483,7 +1936,7 @@ pub fn verify_rebase_success(project_dir: &PathBuf, display_id: &str) -> Result<
     let is_ancestor = Command::new("git")
         .current_dir(project_dir)
         .args(["merge-base", "--is-ancestor", "HEAD", &branch_name])
-        .status()
+        .timed_status()
         .context("Failed to check ancestry")?;
 
     Ok(is_ancestor.success())
@@ -1547,6 +2000,125 @@ When complete, say "Integration complete - build verified".
 If you cannot resolve issues, explain what's blocking you."#, main_branch)
 }
 
+/// Conflicts a rebase stopped on, left in the worktree for a session to resolve
+#[derive(Debug, Clone, Default)]
+pub struct RebaseConflict {
+    /// Unmerged files (empty if the rebase went through without conflicts)
+    pub files: Vec<String>,
+    /// Conflicting hunks of the unmerged files, with conflict markers
+    pub hunks: String,
+}
+
+/// Most bytes of conflicting hunks kept for a hand-off prompt
+const MAX_CONFLICT_HUNKS: usize = 40_000;
+
+/// Rebase a worktree onto main and leave it stopped at the first conflicting
+/// commit, for a session to resolve and continue. Returns no files if the
+/// rebase went through cleanly (then only the build can be broken).
+pub fn begin_rebase_for_handoff(worktree_path: &PathBuf, project_dir: &PathBuf) -> Result<RebaseConflict> {
+    if is_rebase_in_progress(worktree_path) {
+        let _ = Command::new("git")
+            .current_dir(worktree_path)
+            .args(["rebase", "--abort"])
+            .timed_output();
+    }
+
+    let main_head = Command::new("git")
+        .current_dir(project_dir)
+        .args(["rev-parse", "HEAD"])
+        .timed_output()
+        .context("Failed to get main HEAD")?;
+    if !main_head.status.success() {
+        return Err(anyhow!("Failed to get main HEAD"));
+    }
+    let main_ref = String::from_utf8_lossy(&main_head.stdout).trim().to_string();
+
+    let rebase_result = Command::new("git")
+        .current_dir(worktree_path)
+        .args(["rebase", &main_ref])
+        .timed_output()
+        .context("Failed to run rebase")?;
+    if rebase_result.status.success() {
+        return Ok(RebaseConflict::default());
+    }
+    if !is_rebase_in_progress(worktree_path) {
+        return Err(anyhow!(
+            "Rebase failed: {}",
+            String::from_utf8_lossy(&rebase_result.stderr).trim()
+        ));
+    }
+
+    let unmerged = Command::new("git")
+        .current_dir(worktree_path)
+        .args(["diff", "--name-only", "--diff-filter=U"])
+        .timed_output()
+        .context("Failed to list conflicting files")?;
+    let files: Vec<String> = String::from_utf8_lossy(&unmerged.stdout)
+        .lines()
+        .map(str::to_string)
+        .collect();
+
+    let diff = Command::new("git")
+        .current_dir(worktree_path)
+        .args(["diff", "--diff-filter=U"])
+        .timed_output()
+        .context("Failed to read conflicting hunks")?;
+    let mut hunks = String::from_utf8_lossy(&diff.stdout).into_owned();
+    if hunks.len() > MAX_CONFLICT_HUNKS {
+        let mut cut = MAX_CONFLICT_HUNKS;
+        while !hunks.is_char_boundary(cut) {
+            cut -= 1;
+        }
+        hunks.truncate(cut);
+        hunks.push_str("\n... (truncated - run `git diff` for the rest)\n");
+    }
+
+    Ok(RebaseConflict { files, hunks })
+}
+
+/// Generate a prompt for Claude to finish a rebase that stopped on conflicts,
+/// with the conflicting hunks inline
+pub fn generate_conflict_handoff_prompt(main_branch: &str, conflict: &RebaseConflict) -> String {
+    if conflict.files.is_empty() {
+        return format!(r#"REBASE BUILD FIX: Your task branch was just rebased onto {0} without conflicts, but the project no longer builds against the new {0}.
+
+YOUR JOB:
+1. Run the project's build/check command and read the errors
+2. Adapt your changes to main's new code (new fields, renamed or refactored APIs) - keep main's changes
+3. Commit the fixes: `git add -A && git commit -m "Fix integration with latest {0}"`
+4. Run the build again to confirm it passes
+
+IMPORTANT:
+- Do NOT rebase again and do NOT reset - the rebase is already done
+- NEVER discard work from either side
+
+When complete, say "Rebase complete - build verified"."#, main_branch);
+    }
+
+    let files: String = conflict.files.iter().map(|f| format!("- {}\n", f)).collect();
+    format!(r#"REBASE CONFLICT HAND-OFF: A rebase of your task branch onto {0} stopped on conflicts. The rebase is IN PROGRESS in this worktree - finish it, don't start over.
+
+CONFLICTING FILES:
+{1}
+CONFLICTING HUNKS (from `git diff`):
+```diff
+{2}```
+
+YOUR JOB:
+1. For each conflicting file, read both sides and resolve the conflict so main's changes AND your task's changes are kept
+2. Remove ALL conflict markers (<<<<<<<, =======, >>>>>>>) and `git add <file>` once it's resolved
+3. Continue the rebase: `GIT_EDITOR=true git rebase --continue`
+4. If a later commit conflicts too, repeat for its files (`git diff --name-only --diff-filter=U` lists them)
+5. Once the rebase has finished, run the project's build/check command and fix anything that broke; commit those fixes
+
+IMPORTANT:
+- NEVER run `git rebase --abort`, `git reset` or `git checkout` on the conflicting files - that throws work away
+- If unsure how to combine two approaches, prefer main's structure but keep your functionality
+
+When complete, say "Rebase complete - build verified".
+If you cannot resolve a conflict, explain what's blocking you."#, main_branch, files, conflict.hunks)
+}
+
 /// Generate a prompt for Claude to prepare task changes for applying to main worktree
 /// This is similar to rebase prompt but emphasizes the goal is to test changes in main
 pub fn generate_apply_prompt(main_branch: &str) -> String {
@@ -1652,7 +2224,7 @@ pub fn save_current_changes_as_patch(project_dir: &PathBuf, display_id: &str) ->
     let diff_output = Command::new("git")
         .current_dir(project_dir)
         .args(["diff", "HEAD"])
-        .output()?;
+        .timed_output()?;
 
     if !diff_output.status.success() {
         let stderr = String::from_utf8_lossy(&diff_output.stderr);
@@ -1695,7 +2267,7 @@ pub fn abort_rebase(worktree_path: &PathBuf) -> Result<()> {
     let output = Command::new("git")
         .current_dir(worktree_path)
         .args(["rebase", "--abort"])
-        .output()
+        .timed_output()
         .context("Failed to abort rebase")?;
 
     if !output.status.success() {
@@ -1706,7 +2278,7 @@ pub fn abort_rebase(worktree_path: &PathBuf) -> Result<()> {
 }
 
 /// Git status information for a worktree
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub struct WorktreeGitStatus {
     /// Number of lines added (insertions)
     pub additions: usize,
@@ -1720,8 +2292,32 @@ pub struct WorktreeGitStatus {
     pub commits_behind: usize,
 }
 
-/// Get git status (additions, deletions, commits ahead/behind) for a worktree
-pub fn get_worktree_git_status(project_dir: &PathBuf, display_id: &str) -> Result<WorktreeGitStatus> {
+/// Get git status (additions, deletions, commits ahead/behind) for a worktree.
+/// Uses libgit2 when the native backend is enabled, falling back to git on error.
+/// Files matching the project's `ignore` patterns don't count towards the diff stats.
+pub fn get_worktree_git_status(project_dir: &Path, display_id: &str, ignore: &[String]) -> Result<WorktreeGitStatus> {
+    let native = super::native::enabled().then(|| {
+        timing::time(OpKind::Git, || format!("libgit2 diff stats claude/{}", display_id), || {
+            super::native::worktree_git_status(project_dir, display_id)
+        })
+    });
+    let mut status = match native {
+        Some(Ok(status)) => status,
+        _ => subprocess_worktree_git_status(project_dir, display_id)?,
+    };
+
+    if !ignore.is_empty() {
+        // Shortstat-style totals can't skip paths, so sum the remaining files instead
+        let files = get_worktree_changed_files(project_dir, display_id, ignore)?;
+        status.files_changed = files.len();
+        status.additions = files.iter().map(|f| f.additions).sum();
+        status.deletions = files.iter().map(|f| f.deletions).sum();
+    }
+    Ok(status)
+}
+
+/// `get_worktree_git_status` via git subprocesses
+pub(super) fn subprocess_worktree_git_status(project_dir: &Path, display_id: &str) -> Result<WorktreeGitStatus> {
     let branch_name = format!("claude/{}", display_id);
     let mut status = WorktreeGitStatus::default();
 
@@ -1729,7 +2325,7 @@ pub fn get_worktree_git_status(project_dir: &PathBuf, display_id: &str) -> Resul
     let merge_base_output = Command::new("git")
         .current_dir(project_dir)
         .args(["merge-base", "HEAD", &branch_name])
-        .output()
+        .timed_output()
         .context("Failed to get merge base")?;
 
     if !merge_base_output.status.success() {
@@ -1743,7 +2339,7 @@ pub fn get_worktree_git_status(project_dir: &PathBuf, display_id: &str) -> Resul
     let diff_stat_output = Command::new("git")
         .current_dir(project_dir)
         .args(["diff", "--shortstat", &format!("{}..{}", merge_base, branch_name)])
-        .output()
+        .timed_output()
         .context("Failed to get diff stats")?;
 
     if diff_stat_output.status.success() {
@@ -1771,7 +2367,7 @@ pub fn get_worktree_git_status(project_dir: &PathBuf, display_id: &str) -> Resul
     let ahead_output = Command::new("git")
         .current_dir(project_dir)
         .args(["rev-list", "--count", &format!("HEAD..{}", branch_name)])
-        .output()
+        .timed_output()
         .context("Failed to count commits ahead")?;
 
     if ahead_output.status.success() {
@@ -1783,7 +2379,7 @@ pub fn get_worktree_git_status(project_dir: &PathBuf, display_id: &str) -> Resul
     let behind_output = Command::new("git")
         .current_dir(project_dir)
         .args(["rev-list", "--count", &format!("{}..HEAD", branch_name)])
-        .output()
+        .timed_output()
         .context("Failed to count commits behind")?;
 
     if behind_output.status.success() {
@@ -1813,8 +2409,24 @@ pub struct ChangedFile {
     pub is_renamed: bool,
 }
 
-/// Get list of changed files with their stats for a worktree
-pub fn get_worktree_changed_files(project_dir: &PathBuf, display_id: &str) -> Result<Vec<ChangedFile>> {
+/// Get list of changed files with their stats for a worktree, leaving out
+/// files matching the project's `ignore` patterns
+pub fn get_worktree_changed_files(project_dir: &Path, display_id: &str, ignore: &[String]) -> Result<Vec<ChangedFile>> {
+    let mut files = all_changed_files(project_dir, display_id)?;
+    files.retain(|f| !super::ignore::is_ignored(ignore, &f.path));
+    Ok(files)
+}
+
+fn all_changed_files(project_dir: &Path, display_id: &str) -> Result<Vec<ChangedFile>> {
+    if super::native::enabled() {
+        let files = timing::time(OpKind::Git, || format!("libgit2 changed files claude/{}", display_id), || {
+            super::native::worktree_changed_files(project_dir, display_id)
+        });
+        if let Ok(files) = files {
+            return Ok(files);
+        }
+    }
+
     let branch_name = format!("claude/{}", display_id);
     let mut files = Vec::new();
 
@@ -1822,7 +2434,7 @@ pub fn get_worktree_changed_files(project_dir: &PathBuf, display_id: &str) -> Re
     let merge_base_output = Command::new("git")
         .current_dir(project_dir)
         .args(["merge-base", "HEAD", &branch_name])
-        .output()
+        .timed_output()
         .context("Failed to get merge base")?;
 
     if !merge_base_output.status.success() {
@@ -1835,7 +2447,7 @@ pub fn get_worktree_changed_files(project_dir: &PathBuf, display_id: &str) -> Re
     let numstat_output = Command::new("git")
         .current_dir(project_dir)
         .args(["diff", "--numstat", &format!("{}..{}", merge_base, branch_name)])
-        .output()
+        .timed_output()
         .context("Failed to get diff numstat")?;
 
     if !numstat_output.status.success() {
@@ -1846,7 +2458,7 @@ pub fn get_worktree_changed_files(project_dir: &PathBuf, display_id: &str) -> Re
     let status_output = Command::new("git")
         .current_dir(project_dir)
         .args(["diff", "--name-status", &format!("{}..{}", merge_base, branch_name)])
-        .output()
+        .timed_output()
         .context("Failed to get diff name-status")?;
 
     // Build a map of file statuses
@@ -1910,11 +2522,12 @@ pub struct RemoteStatus {
 
 /// Fetch from remote to update refs (does not modify working directory)
 /// This allows us to check ahead/behind status
-pub fn git_fetch(project_dir: &PathBuf) -> Result<()> {
-    let output = Command::new("git")
-        .current_dir(project_dir)
-        .args(["fetch", "--quiet"])
-        .output()?;
+pub fn git_fetch(
+    project_dir: &PathBuf,
+    cancel: &CancelToken,
+    on_progress: &mut dyn FnMut(GitProgress),
+) -> Result<()> {
+    let output = run_git_with_progress(project_dir, &["fetch", "--progress"], cancel, on_progress)?;
 
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
@@ -1929,12 +2542,25 @@ pub fn git_fetch(project_dir: &PathBuf) -> Result<()> {
 
 /// Get the remote tracking status for the current branch
 /// Returns ahead/behind counts relative to the remote tracking branch
-pub fn get_remote_status(project_dir: &PathBuf) -> Result<RemoteStatus> {
+pub fn get_remote_status(project_dir: &Path) -> Result<RemoteStatus> {
+    if super::native::enabled() {
+        let status = timing::time(OpKind::Git, || format!("libgit2 remote status {}", project_dir.display()), || {
+            super::native::remote_status(project_dir)
+        });
+        if let Ok(status) = status {
+            return Ok(status);
+        }
+    }
+    subprocess_remote_status(project_dir)
+}
+
+/// `get_remote_status` via git subprocesses
+pub(super) fn subprocess_remote_status(project_dir: &Path) -> Result<RemoteStatus> {
     // Get the current branch name
     let branch_output = Command::new("git")
         .current_dir(project_dir)
         .args(["rev-parse", "--abbrev-ref", "HEAD"])
-        .output()?;
+        .timed_output()?;
 
     if !branch_output.status.success() {
         return Ok(RemoteStatus::default());
@@ -1950,7 +2576,7 @@ pub fn get_remote_status(project_dir: &PathBuf) -> Result<RemoteStatus> {
     let tracking_output = Command::new("git")
         .current_dir(project_dir)
         .args(["rev-parse", "--abbrev-ref", &format!("{}@{{upstream}}", branch)])
-        .output()?;
+        .timed_output()?;
 
     if !tracking_output.status.success() {
         // No upstream configured
@@ -1976,7 +2602,7 @@ pub fn get_remote_status(project_dir: &PathBuf) -> Result<RemoteStatus> {
     let rev_list_output = Command::new("git")
         .current_dir(project_dir)
         .args(["rev-list", "--left-right", "--count", &format!("{}...{}", branch, upstream)])
-        .output()?;
+        .timed_output()?;
 
     if !rev_list_output.status.success() {
         return Ok(RemoteStatus {
@@ -2015,7 +2641,7 @@ pub fn git_pull(project_dir: &PathBuf) -> Result<()> {
     let branch_output = Command::new("git")
         .current_dir(project_dir)
         .args(["rev-parse", "--abbrev-ref", "HEAD"])
-        .output()?;
+        .timed_output()?;
 
     let branch = String::from_utf8_lossy(&branch_output.stdout).trim().to_string();
 
@@ -2038,7 +2664,7 @@ pub fn git_pull(project_dir: &PathBuf) -> Result<()> {
     let output = Command::new("git")
         .current_dir(project_dir)
         .args(["pull", "--rebase"])
-        .output()?;
+        .timed_output()?;
 
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
@@ -2049,7 +2675,7 @@ pub fn git_pull(project_dir: &PathBuf) -> Result<()> {
             let _ = Command::new("git")
                 .current_dir(project_dir)
                 .args(["rebase", "--abort"])
-                .output();
+                .timed_output();
             return Err(anyhow!(
                 "Pull failed due to conflicts. The pull has been aborted. Please resolve conflicts manually."
             ));
@@ -2063,12 +2689,19 @@ pub fn git_pull(project_dir: &PathBuf) -> Result<()> {
 
 /// Smart pull that handles .kanblam/tasks.json gracefully
 /// Stashes tasks.json, pulls, then restores local tasks.json (ignoring remote's version)
-pub fn smart_git_pull(project_dir: &PathBuf) -> Result<String> {
+///
+/// The download happens in a cancellable fetch first; the rebase that follows
+/// works on already-fetched objects and is not interrupted.
+pub fn smart_git_pull(
+    project_dir: &PathBuf,
+    cancel: &CancelToken,
+    on_progress: &mut dyn FnMut(GitProgress),
+) -> Result<String> {
     // First check if we're on the main branch
     let branch_output = Command::new("git")
         .current_dir(project_dir)
         .args(["rev-parse", "--abbrev-ref", "HEAD"])
-        .output()?;
+        .timed_output()?;
 
     let branch = String::from_utf8_lossy(&branch_output.stdout).trim().to_string();
 
@@ -2084,7 +2717,7 @@ pub fn smart_git_pull(project_dir: &PathBuf) -> Result<String> {
     let status_output = Command::new("git")
         .current_dir(project_dir)
         .args(["status", "--porcelain"])
-        .output()?;
+        .timed_output()?;
 
     let status = String::from_utf8_lossy(&status_output.stdout);
     let modified_files: Vec<&str> = status.lines()
@@ -2104,22 +2737,30 @@ pub fn smart_git_pull(project_dir: &PathBuf) -> Result<String> {
         ));
     }
 
+    // Download with progress (and cancellation) before touching the working tree
+    let fetch_output = run_git_with_progress(project_dir, &["fetch", "--progress"], cancel, on_progress)?;
+    if !fetch_output.status.success() {
+        let stderr = String::from_utf8_lossy(&fetch_output.stderr);
+        return Err(anyhow!("Pull failed: {}", stderr));
+    }
+
     // Stash tasks.json if it has changes
     let did_stash = if has_tasks_json_changes {
         let stash_output = Command::new("git")
             .current_dir(project_dir)
             .args(["stash", "push", "-m", "kanblam: tasks.json before pull", "--", tasks_json_path])
-            .output()?;
+            .timed_output()?;
         stash_output.status.success()
     } else {
         false
     };
 
     // Perform the pull with rebase
+    let before = main_head(project_dir);
     let pull_output = Command::new("git")
         .current_dir(project_dir)
         .args(["pull", "--rebase"])
-        .output()?;
+        .timed_output()?;
 
     let pull_success = pull_output.status.success();
     let pull_stdout = String::from_utf8_lossy(&pull_output.stdout).to_string();
@@ -2131,26 +2772,26 @@ pub fn smart_git_pull(project_dir: &PathBuf) -> Result<String> {
         let restore_output = Command::new("git")
             .current_dir(project_dir)
             .args(["checkout", "stash@{0}", "--", tasks_json_path])
-            .output()?;
+            .timed_output()?;
 
         if restore_output.status.success() {
             // Unstage tasks.json (checkout stages it, we want it unstaged)
             let _ = Command::new("git")
                 .current_dir(project_dir)
                 .args(["restore", "--staged", tasks_json_path])
-                .output();
+                .timed_output();
 
             // Drop the stash since we've restored what we need
             let _ = Command::new("git")
                 .current_dir(project_dir)
                 .args(["stash", "drop", "stash@{0}"])
-                .output();
+                .timed_output();
         } else {
             // Try regular stash pop as fallback
             let _ = Command::new("git")
                 .current_dir(project_dir)
                 .args(["stash", "pop"])
-                .output();
+                .timed_output();
         }
     }
 
@@ -2161,7 +2802,7 @@ pub fn smart_git_pull(project_dir: &PathBuf) -> Result<String> {
             let _ = Command::new("git")
                 .current_dir(project_dir)
                 .args(["rebase", "--abort"])
-                .output();
+                .timed_output();
             return Err(anyhow!(
                 "Pull failed due to conflicts. The pull has been aborted."
             ));
@@ -2177,17 +2818,26 @@ pub fn smart_git_pull(project_dir: &PathBuf) -> Result<String> {
         format!("Pull successful. {}", pull_stdout.lines().last().unwrap_or(""))
     };
 
+    if before != main_head(project_dir) {
+        audit::record(AuditEntry::new("pull", project_dir)
+            .with_target(&branch)
+            .with_shas(before, main_head(project_dir)));
+    }
     Ok(summary)
 }
 
 /// Push to remote
 /// Only pushes the main branch
-pub fn git_push(project_dir: &PathBuf) -> Result<()> {
+pub fn git_push(
+    project_dir: &PathBuf,
+    cancel: &CancelToken,
+    on_progress: &mut dyn FnMut(GitProgress),
+) -> Result<()> {
     // First check if we're on the main branch
     let branch_output = Command::new("git")
         .current_dir(project_dir)
         .args(["rev-parse", "--abbrev-ref", "HEAD"])
-        .output()?;
+        .timed_output()?;
 
     let branch = String::from_utf8_lossy(&branch_output.stdout).trim().to_string();
 
@@ -2199,11 +2849,9 @@ pub fn git_push(project_dir: &PathBuf) -> Result<()> {
         ));
     }
 
-    // Perform the push
-    let output = Command::new("git")
-        .current_dir(project_dir)
-        .args(["push"])
-        .output()?;
+    // Perform the push (killing it mid-transfer is safe: the remote ref only moves at the end)
+    let before = audit::rev(project_dir, "@{push}");
+    let output = run_git_with_progress(project_dir, &["push", "--progress"], cancel, on_progress)?;
 
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
@@ -2224,9 +2872,50 @@ pub fn git_push(project_dir: &PathBuf) -> Result<()> {
         return Err(anyhow!("Push failed: {}", stderr));
     }
 
+    audit::record(AuditEntry::new("push", project_dir)
+        .with_target(&branch)
+        .with_shas(before, main_head(project_dir)));
     Ok(())
 }
 
+// ============================================================================
+// Tag helpers (release notes, versioning)
+// ============================================================================
+
+/// Get the most recent tag reachable from HEAD (None if the repo has no tags)
+pub fn latest_tag(project_dir: &PathBuf) -> Option<String> {
+    let output = Command::new("git")
+        .current_dir(project_dir)
+        .args(["describe", "--tags", "--abbrev=0"])
+        .timed_output()
+        .ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    let tag = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if tag.is_empty() { None } else { Some(tag) }
+}
+
+/// Get the commit date of a ref (tag, branch or SHA)
+pub fn ref_commit_date(project_dir: &PathBuf, reference: &str) -> Result<chrono::DateTime<chrono::Utc>> {
+    let output = Command::new("git")
+        .current_dir(project_dir)
+        .args(["log", "-1", "--format=%cI", reference, "--"])
+        .timed_output()?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(anyhow!("Unknown ref '{}': {}", reference, stderr.trim()));
+    }
+
+    let date = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    chrono::DateTime::parse_from_rfc3339(&date)
+        .map(|d| d.with_timezone(&chrono::Utc))
+        .with_context(|| format!("Unexpected date for '{}': {}", reference, date))
+}
+
 // ============================================================================
 // Stash tracking functions
 // ============================================================================
@@ -2246,7 +2935,7 @@ pub fn create_tracked_stash(project_dir: &PathBuf, description: &str) -> Result<
     let status_output = Command::new("git")
         .current_dir(project_dir)
         .args(["status", "--porcelain"])
-        .output()?;
+        .timed_output()?;
 
     let status = String::from_utf8_lossy(&status_output.stdout);
     let files: Vec<&str> = status.lines()
@@ -2271,7 +2960,7 @@ pub fn create_tracked_stash(project_dir: &PathBuf, description: &str) -> Result<
     let stash_output = Command::new("git")
         .current_dir(project_dir)
         .args(["stash", "push", "-m", &stash_msg])
-        .output()?;
+        .timed_output()?;
 
     if !stash_output.status.success() {
         let stderr = String::from_utf8_lossy(&stash_output.stderr);
@@ -2282,7 +2971,7 @@ pub fn create_tracked_stash(project_dir: &PathBuf, description: &str) -> Result<
     let sha_output = Command::new("git")
         .current_dir(project_dir)
         .args(["rev-parse", "stash@{0}"])
-        .output()?;
+        .timed_output()?;
 
     if !sha_output.status.success() {
         return Err(anyhow!("Failed to get stash SHA"));
@@ -2306,7 +2995,7 @@ fn find_stash_ref_by_sha(project_dir: &PathBuf, stash_sha: &str) -> Result<Optio
     let output = Command::new("git")
         .current_dir(project_dir)
         .args(["stash", "list", "--format=%H %gd"])
-        .output()?;
+        .timed_output()?;
 
     if !output.status.success() {
         return Ok(None);
@@ -2333,7 +3022,7 @@ pub fn pop_tracked_stash(project_dir: &PathBuf, stash_sha: &str) -> Result<bool>
     let output = Command::new("git")
         .current_dir(project_dir)
         .args(["stash", "pop", &stash_ref])
-        .output()?;
+        .timed_output()?;
 
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
@@ -2359,13 +3048,16 @@ pub fn drop_tracked_stash(project_dir: &PathBuf, stash_sha: &str) -> Result<()>
     let output = Command::new("git")
         .current_dir(project_dir)
         .args(["stash", "drop", &stash_ref])
-        .output()?;
+        .timed_output()?;
 
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
         return Err(anyhow!("Failed to drop stash: {}", stderr));
     }
 
+    audit::record(AuditEntry::new("drop_stash", project_dir)
+        .with_target(&stash_ref)
+        .with_shas(Some(stash_sha.to_string()), None));
     Ok(())
 }
 
@@ -2383,7 +3075,7 @@ pub fn abort_stash_pop_keep_task_changes(project_dir: &PathBuf, display_id: &str
     let stash_files_output = Command::new("git")
         .current_dir(project_dir)
         .args(["stash", "show", "--name-only", "stash@{0}"])
-        .output()?;
+        .timed_output()?;
 
     let stash_files: Vec<String> = String::from_utf8_lossy(&stash_files_output.stdout)
         .lines()
@@ -2407,13 +3099,13 @@ pub fn abort_stash_pop_keep_task_changes(project_dir: &PathBuf, display_id: &str
     let _ = Command::new("git")
         .current_dir(project_dir)
         .args(["checkout", "--ours", "."])
-        .output();
+        .timed_output();
 
     // Unstage everything (stash pop may have staged some changes)
     let _ = Command::new("git")
         .current_dir(project_dir)
         .args(["reset", "HEAD"])
-        .output();
+        .timed_output();
 
     // For files that the stash touched but aren't in the task patch,
     // restore them to HEAD (remove stash-only changes)
@@ -2422,7 +3114,7 @@ pub fn abort_stash_pop_keep_task_changes(project_dir: &PathBuf, display_id: &str
             let _ = Command::new("git")
                 .current_dir(project_dir)
                 .args(["checkout", "HEAD", "--", file])
-                .output();
+                .timed_output();
         }
     }
 
@@ -2434,14 +3126,14 @@ pub fn abort_stash_pop_keep_task_changes(project_dir: &PathBuf, display_id: &str
         let _ = Command::new("git")
             .current_dir(project_dir)
             .args(["checkout", "HEAD", "--", file])
-            .output();
+            .timed_output();
     }
 
     // Re-apply the entire task patch (now on a clean base)
     let apply_output = Command::new("git")
         .current_dir(project_dir)
         .args(["apply", "--3way", patch_path.to_str().unwrap()])
-        .output()?;
+        .timed_output()?;
 
     if !apply_output.status.success() {
         let stderr = String::from_utf8_lossy(&apply_output.stderr);
@@ -2461,7 +3153,7 @@ pub fn get_stash_details(project_dir: &PathBuf, stash_sha: &str) -> Result<(usiz
     let show_output = Command::new("git")
         .current_dir(project_dir)
         .args(["stash", "show", "--stat", &stash_ref])
-        .output()?;
+        .timed_output()?;
 
     let show_str = String::from_utf8_lossy(&show_output.stdout);
     let lines: Vec<&str> = show_str.lines().collect();
@@ -2508,6 +3200,49 @@ mod tests {
         assert_eq!(path, project_dir.join("worktrees").join(display_id));
     }
 
+    #[test]
+    fn test_conflict_handoff_prompt_includes_hunks_or_build_fix() {
+        let conflict = RebaseConflict {
+            files: vec!["src/app.rs".to_string()],
+            hunks: "<<<<<<< HEAD\nlet a = 1;\n=======\nlet a = 2;\n>>>>>>> task\n".to_string(),
+        };
+        let prompt = generate_conflict_handoff_prompt("main", &conflict);
+        assert!(prompt.contains("- src/app.rs"));
+        assert!(prompt.contains("let a = 2;"));
+        assert!(prompt.contains("git rebase --continue"));
+
+        let clean = generate_conflict_handoff_prompt("main", &RebaseConflict::default());
+        assert!(clean.contains("without conflicts"));
+        assert!(!clean.contains("rebase --continue"));
+    }
+
+    #[test]
+    fn test_parse_sparse_patterns() {
+        assert_eq!(
+            parse_sparse_patterns("services/api/**, shared/\n docs/*.md"),
+            vec!["services/api/**", "shared/", "docs/*.md"]
+        );
+        assert!(parse_sparse_patterns("  , ").is_empty());
+    }
+
+    #[test]
+    fn test_pool_slots_skip_unready() {
+        let dir = tempdir().unwrap();
+        let project_dir = dir.path().to_path_buf();
+        let pool_dir = get_pool_dir(&project_dir);
+
+        // Ready slot (has .git), slot still being created, and a stray dir
+        fs::create_dir_all(pool_dir.join("slot-1")).unwrap();
+        fs::write(pool_dir.join("slot-1").join(".git"), "gitdir: x").unwrap();
+        fs::create_dir_all(pool_dir.join(".tmp-slot-0")).unwrap();
+        fs::write(pool_dir.join(".tmp-slot-0").join(".git"), "gitdir: x").unwrap();
+        fs::create_dir_all(pool_dir.join("slot-2")).unwrap();
+
+        assert_eq!(pool_slots(&project_dir), vec![pool_dir.join("slot-1")]);
+        // slot-0 is taken by the in-progress slot, slot-1 and slot-2 exist
+        assert_eq!(next_pool_slot_name(&pool_dir), "slot-3");
+    }
+
     #[test]
     fn test_gitignore_missing_entries_empty_file() {
         let dir = tempdir().unwrap();
@@ -2629,4 +3364,19 @@ mod tests {
         let content = fs::read_to_string(project_dir.join(".gitignore")).unwrap();
         assert_eq!(content, initial, "File should not be modified when entries already present");
     }
+
+    #[test]
+    fn test_split_scope() {
+        let dir = tempdir().unwrap();
+        let root = dir.path().canonicalize().unwrap();
+        let web = root.join("apps").join("web");
+        fs::create_dir_all(&web).unwrap();
+        init_repo(&root).unwrap();
+
+        assert_eq!(split_scope(&web), (root.clone(), Some(PathBuf::from("apps/web"))));
+        assert_eq!(split_scope(&root), (root.clone(), None));
+
+        let plain = tempdir().unwrap();
+        assert_eq!(split_scope(plain.path()), (plain.path().to_path_buf(), None));
+    }
 }
@@ -0,0 +1,203 @@
+//! Main-branch history of the files a task changed
+//!
+//! The Git tab's History view lists, for each file the task's worktree
+//! changed, the latest commits on main touching it (marking the ones made
+//! since the task branched off) and who wrote the file's current lines on
+//! main. With uncommitted edits in the main checkout flagged too, it shows
+//! whether the task's change runs into work someone else has in flight.
+
+use anyhow::Result;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+use super::git::git_stdout;
+
+/// Commits listed per file
+const COMMITS_PER_FILE: usize = 5;
+
+/// Files looked up (the rest are only counted)
+const MAX_FILES: usize = 30;
+
+/// Authors named in a blame summary
+const BLAME_AUTHORS: usize = 3;
+
+/// A commit on main that touched a file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HistoryCommit {
+    pub sha: String,
+    pub author: String,
+    /// Relative date ("3 days ago")
+    pub when: String,
+    pub subject: String,
+    /// Made on main after the task branched off
+    pub since_branch: bool,
+}
+
+/// Main-branch history of one changed file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileHistory {
+    pub path: String,
+    /// Latest commits touching the file, newest first
+    pub commits: Vec<HistoryCommit>,
+    /// Commits touching the file since the task branched off (may be more than listed)
+    pub since_branch: usize,
+    /// (author, lines) of the file on main, most lines first; empty for new files
+    pub blame: Vec<(String, usize)>,
+    /// The main checkout has uncommitted changes to the file
+    pub uncommitted: bool,
+}
+
+impl FileHistory {
+    /// Someone else changed the file since the task branched off, or is changing it
+    pub fn has_other_work(&self) -> bool {
+        self.since_branch > 0 || self.uncommitted
+    }
+
+    /// Who wrote the file's lines on main: "alice 62%, bob 30%, 1 other"
+    pub fn blame_summary(&self) -> String {
+        let total: usize = self.blame.iter().map(|(_, lines)| lines).sum();
+        if total == 0 {
+            return "new file".to_string();
+        }
+        let mut parts: Vec<String> = self.blame.iter()
+            .take(BLAME_AUTHORS)
+            .map(|(author, lines)| format!("{} {}%", author, lines * 100 / total))
+            .collect();
+        let others = self.blame.len().saturating_sub(BLAME_AUTHORS);
+        if others > 0 {
+            parts.push(format!("{} other{}", others, if others == 1 { "" } else { "s" }));
+        }
+        parts.join(", ")
+    }
+}
+
+/// History of the files a task changed
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TaskHistory {
+    /// Main branch the history is read from
+    pub base: String,
+    pub files: Vec<FileHistory>,
+    /// Changed files past the ones looked up
+    pub more_files: usize,
+}
+
+/// History on main of the files changed in `worktree` (committed or not)
+/// since it branched off
+pub fn task_history(project_dir: &Path, worktree: &Path) -> Result<TaskHistory> {
+    let base = super::git::find_base_branch(&project_dir.to_path_buf())?;
+    let merge_base = git_stdout(worktree, &["merge-base", &base, "HEAD"])?.trim().to_string();
+    let changed: Vec<String> = git_stdout(worktree, &["diff", "--name-only", &merge_base])?
+        .lines()
+        .map(str::to_string)
+        .collect();
+    let uncommitted = uncommitted_paths(&git_stdout(project_dir, &["status", "--porcelain", "--untracked-files=no"])?);
+
+    let mut files = Vec::new();
+    for path in changed.iter().take(MAX_FILES) {
+        let since: HashSet<String> = git_stdout(project_dir, &["rev-list", &format!("{}..{}", merge_base, base), "--", path])?
+            .lines()
+            .map(str::to_string)
+            .collect();
+        let log = git_stdout(project_dir, &[
+            "log", &format!("-n{}", COMMITS_PER_FILE), "--format=%H%x1f%an%x1f%ar%x1f%s", &base, "--", path,
+        ])?;
+        // Files main doesn't have can't be blamed
+        let blame = git_stdout(project_dir, &["blame", "--line-porcelain", &base, "--", path])
+            .map(|porcelain| blame_authors(&porcelain))
+            .unwrap_or_default();
+        files.push(FileHistory {
+            path: path.clone(),
+            commits: parse_log(&log, &since),
+            since_branch: since.len(),
+            blame,
+            uncommitted: uncommitted.contains(path),
+        });
+    }
+    Ok(TaskHistory { base, files, more_files: changed.len().saturating_sub(MAX_FILES) })
+}
+
+/// Commits from `git log --format=%H%x1f%an%x1f%ar%x1f%s`
+fn parse_log(log: &str, since: &HashSet<String>) -> Vec<HistoryCommit> {
+    log.lines()
+        .filter_map(|line| {
+            let mut fields = line.splitn(4, '\x1f');
+            let sha = fields.next()?;
+            Some(HistoryCommit {
+                sha: sha[..sha.len().min(7)].to_string(),
+                author: fields.next()?.to_string(),
+                when: fields.next()?.to_string(),
+                subject: fields.next().unwrap_or_default().to_string(),
+                since_branch: since.contains(sha),
+            })
+        })
+        .collect()
+}
+
+/// Lines per author from `git blame --line-porcelain`, most lines first
+fn blame_authors(porcelain: &str) -> Vec<(String, usize)> {
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    for author in porcelain.lines().filter_map(|line| line.strip_prefix("author ")) {
+        *counts.entry(author).or_default() += 1;
+    }
+    let mut authors: Vec<(String, usize)> = counts.into_iter().map(|(a, n)| (a.to_string(), n)).collect();
+    authors.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    authors
+}
+
+/// Paths with changes in `git status --porcelain` (renames by their new path)
+fn uncommitted_paths(status: &str) -> HashSet<String> {
+    status.lines()
+        .filter_map(|line| line.get(3..))
+        .map(|path| path.rsplit(" -> ").next().unwrap_or(path).trim_matches('"').to_string())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::worktree::git::test_git;
+
+    fn commit_as(dir: &Path, author: &str, file: &str, text: &str) {
+        std::fs::write(dir.join(file), text).unwrap();
+        test_git(dir, &["add", file]);
+        test_git(dir, &["-c", &format!("user.name={}", author), "commit", "--quiet", "-m", &format!("{} edits {}", author, file)]);
+    }
+
+    #[test]
+    fn test_task_history() {
+        let repo = tempfile::tempdir().unwrap();
+        let dir = repo.path();
+        test_git(dir, &["init", "--quiet"]);
+        test_git(dir, &["config", "user.email", "test@test.com"]);
+        commit_as(dir, "alice", "app.rs", "a\nb\nc\nd\n");
+        commit_as(dir, "alice", "lib.rs", "x\n");
+
+        let worktree = dir.join("worktrees").join("T-1");
+        test_git(dir, &["worktree", "add", "--quiet", "-b", "claude/T-1", worktree.to_str().unwrap()]);
+        // Main moves on under the task, and someone is editing lib.rs
+        commit_as(dir, "bob", "app.rs", "a\nb\nc\nbob\n");
+        std::fs::write(dir.join("lib.rs"), "x\ny\n").unwrap();
+        // The task changes both files and adds one
+        std::fs::write(worktree.join("app.rs"), "a\ntask\nc\nd\n").unwrap();
+        commit_as(&worktree, "claude", "lib.rs", "task\n");
+        std::fs::write(worktree.join("new.rs"), "new\n").unwrap();
+        test_git(&worktree, &["add", "new.rs"]);
+
+        let history = task_history(dir, &worktree).unwrap();
+        let paths: Vec<&str> = history.files.iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(paths, vec!["app.rs", "lib.rs", "new.rs"]);
+
+        let app = &history.files[0];
+        assert_eq!(app.commits.len(), 2);
+        assert_eq!((app.commits[0].author.as_str(), app.commits[0].since_branch), ("bob", true));
+        assert!(!app.commits[1].since_branch);
+        assert_eq!(app.since_branch, 1);
+        assert_eq!(app.blame_summary(), "alice 75%, bob 25%");
+        assert!(app.has_other_work() && !app.uncommitted);
+
+        let lib = &history.files[1];
+        assert_eq!((lib.since_branch, lib.uncommitted), (0, true));
+        assert_eq!(history.files[2].blame_summary(), "new file");
+        assert!(!history.files[2].has_other_work());
+    }
+}
@@ -0,0 +1,121 @@
+//! Per-project paths left out of status and diff stats
+//!
+//! Generated directories that slipped past `.gitignore` (build output,
+//! vendored code, snapshots) drown a task's real changes in its diff stats.
+//! A project can list extra patterns that the status, changed-file and diff
+//! helpers skip. Patterns are gitignore-style globs:
+//!
+//! - `dist/` matches the directory `dist` and everything below it
+//! - `*.lock` (no slash) matches a file or directory name at any depth
+//! - `src/gen/**` (with a slash) matches from the repository root
+//! - `*` and `?` stay within one path component, `**` crosses components
+
+/// Split an ignore setting ("dist/ *.lock, **/snapshots/") into patterns
+pub fn parse_patterns(setting: &str) -> Vec<String> {
+    setting
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|p| !p.is_empty())
+        .map(|p| p.to_string())
+        .collect()
+}
+
+/// Whether `path` (repository-relative, `/`-separated) matches any pattern
+pub fn is_ignored(patterns: &[String], path: &str) -> bool {
+    patterns.iter().any(|pattern| matches_pattern(pattern, path))
+}
+
+fn matches_pattern(pattern: &str, path: &str) -> bool {
+    let pattern = pattern.trim_start_matches("./").trim_end_matches('/');
+    if pattern.is_empty() {
+        return false;
+    }
+    let anchored = pattern.strip_prefix('/').or_else(|| pattern.contains('/').then_some(pattern));
+    if let Some(anchored) = anchored {
+        // Anchored: the path itself or one of its parent directories matches
+        let mut prefix_end = path.len();
+        loop {
+            if glob_match(anchored.as_bytes(), &path.as_bytes()[..prefix_end]) {
+                return true;
+            }
+            match path[..prefix_end].rfind('/') {
+                Some(idx) => prefix_end = idx,
+                None => return false,
+            }
+        }
+    }
+    // Unanchored: any component of the path matches
+    path.split('/').any(|component| glob_match(pattern.as_bytes(), component.as_bytes()))
+}
+
+/// Glob match where `*`/`?` don't cross `/` and `**` does
+fn glob_match(pattern: &[u8], text: &[u8]) -> bool {
+    match pattern {
+        [] => text.is_empty(),
+        [b'*', b'*', rest @ ..] => {
+            // "**/" also matches zero directories
+            let rest_after_slash = rest.strip_prefix(b"/");
+            if let Some(after) = rest_after_slash {
+                if glob_match(after, text) {
+                    return true;
+                }
+            }
+            (0..=text.len()).any(|i| glob_match(rest, &text[i..]))
+        }
+        [b'*', rest @ ..] => {
+            let limit = text.iter().position(|&c| c == b'/').unwrap_or(text.len());
+            (0..=limit).any(|i| glob_match(rest, &text[i..]))
+        }
+        [b'?', rest @ ..] => matches!(text, [c, tail @ ..] if *c != b'/' && glob_match(rest, tail)),
+        [p, rest @ ..] => matches!(text, [c, tail @ ..] if c == p && glob_match(rest, tail)),
+    }
+}
+
+/// Drop the files matching `patterns` from a unified diff
+pub fn filter_diff(diff: &str, patterns: &[String]) -> String {
+    if patterns.is_empty() {
+        return diff.to_string();
+    }
+    let mut out = String::with_capacity(diff.len());
+    let mut keep = true;
+    for line in diff.split_inclusive('\n') {
+        if let Some(header) = line.strip_prefix("diff --git ") {
+            // "a/<old> b/<new>": test the new path (deleted files keep theirs)
+            let header = header.trim_end();
+            let path = header.rsplit_once(" b/").map_or(header, |(_, new)| new);
+            keep = !is_ignored(patterns, path);
+        }
+        if keep {
+            out.push_str(line);
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_patterns_match_like_gitignore() {
+        let patterns = parse_patterns("dist/ *.lock, src/gen/** /build");
+        assert!(is_ignored(&patterns, "dist/app.js"));
+        assert!(is_ignored(&patterns, "web/dist/app.js"));
+        assert!(is_ignored(&patterns, "Cargo.lock"));
+        assert!(is_ignored(&patterns, "web/package.lock"));
+        assert!(is_ignored(&patterns, "src/gen/api/types.rs"));
+        assert!(is_ignored(&patterns, "build/out.o"));
+        assert!(!is_ignored(&patterns, "web/build/out.o"));
+        assert!(!is_ignored(&patterns, "src/main.rs"));
+        assert!(!is_ignored(&patterns, "distribution.md"));
+        assert!(is_ignored(&parse_patterns("**/snapshots/"), "a/b/snapshots/x.snap"));
+    }
+
+    #[test]
+    fn test_filter_diff_drops_ignored_files() {
+        let diff = "diff --git a/src/main.rs b/src/main.rs\n+fn main() {}\n\
+                    diff --git a/Cargo.lock b/Cargo.lock\n+lots\n";
+        let filtered = filter_diff(diff, &parse_patterns("*.lock"));
+        assert!(filtered.contains("src/main.rs"));
+        assert!(!filtered.contains("Cargo.lock"));
+    }
+}
@@ -0,0 +1,198 @@
+//! Startup integrity check of task worktrees against the saved board
+//!
+//! A worktree can go missing, get pruned from git's list, end up on the wrong
+//! branch or be left mid-rebase while kanblam isn't running. Checking each
+//! task at launch lets those be reconciled up front instead of failing later
+//! in the middle of a merge.
+
+use anyhow::Result;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use uuid::Uuid;
+
+use super::git::{abort_rebase, git_stdout, is_rebase_in_progress, WorktreeInfo};
+use crate::timing::TimedCommand;
+
+/// What's wrong with a task's worktree
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WorktreeProblem {
+    /// The directory is gone; the task's branch may survive it
+    Missing { branch_exists: bool },
+    /// The directory exists but git no longer lists it as a worktree
+    NotRegistered,
+    /// HEAD is detached instead of on the task's branch
+    Detached,
+    /// Another branch is checked out
+    WrongBranch(String),
+    /// A rebase or merge ("rebase"/"merge") was left half-done
+    Interrupted(&'static str),
+}
+
+impl WorktreeProblem {
+    pub fn describe(&self) -> String {
+        match self {
+            WorktreeProblem::Missing { branch_exists: true } => "worktree directory is missing".to_string(),
+            WorktreeProblem::Missing { branch_exists: false } => "worktree and branch are both gone".to_string(),
+            WorktreeProblem::NotRegistered => "directory isn't a registered git worktree".to_string(),
+            WorktreeProblem::Detached => "HEAD is detached".to_string(),
+            WorktreeProblem::WrongBranch(branch) => format!("'{}' is checked out", branch),
+            WorktreeProblem::Interrupted(op) => format!("a {} was left in progress", op),
+        }
+    }
+
+    /// What `fix_issue` will do about it
+    pub fn fix_label(&self) -> &'static str {
+        match self {
+            WorktreeProblem::Missing { branch_exists: true } => "recreate it from the branch",
+            WorktreeProblem::Missing { branch_exists: false } => "reset the task to Planned",
+            WorktreeProblem::NotRegistered => "repair the worktree link",
+            WorktreeProblem::Detached | WorktreeProblem::WrongBranch(_) => "check out the task branch",
+            WorktreeProblem::Interrupted(_) => "abort it",
+        }
+    }
+}
+
+/// A task whose worktree doesn't match what the board recorded
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorktreeIssue {
+    pub task_id: Uuid,
+    /// Display id of the task, for the dialog
+    pub task_label: String,
+    pub project_dir: PathBuf,
+    pub worktree_path: PathBuf,
+    pub branch: String,
+    pub problem: WorktreeProblem,
+}
+
+/// Check one task's worktree against git's worktree list (`list_worktrees`).
+/// `session_busy` skips the rebase/branch checks for tasks whose rebase
+/// session is meant to be mid-operation.
+pub fn check_worktree(
+    project_dir: &Path,
+    worktree_path: &Path,
+    branch: &str,
+    registered: &[WorktreeInfo],
+    session_busy: bool,
+) -> Option<WorktreeProblem> {
+    if !crate::remote::exists(worktree_path) {
+        return Some(WorktreeProblem::Missing { branch_exists: branch_exists(project_dir, branch) });
+    }
+
+    // Remote paths can't be canonicalized here; git lists them as they were added
+    let remote = crate::remote::is_remote(worktree_path);
+    let canonical = worktree_path.canonicalize().ok();
+    let same_path = |path: &Path| if remote { path == worktree_path } else { path.canonicalize().ok() == canonical };
+    let Some(info) = registered.iter().find(|wt| same_path(&wt.path)) else {
+        return Some(WorktreeProblem::NotRegistered);
+    };
+    if session_busy {
+        return None;
+    }
+
+    if is_rebase_in_progress(&worktree_path.to_path_buf()) {
+        return Some(WorktreeProblem::Interrupted("rebase"));
+    }
+    if git_succeeds(worktree_path, &["rev-parse", "-q", "--verify", "MERGE_HEAD"]) {
+        return Some(WorktreeProblem::Interrupted("merge"));
+    }
+    if info.branch.is_empty() {
+        return Some(WorktreeProblem::Detached);
+    }
+    if info.branch != branch {
+        return Some(WorktreeProblem::WrongBranch(info.branch.clone()));
+    }
+    None
+}
+
+/// Apply the fix for an issue (see `WorktreeProblem::fix_label`). Resetting a
+/// task whose work is gone is left to the caller; this only prunes git's record.
+pub fn fix_issue(issue: &WorktreeIssue) -> Result<()> {
+    let path = issue.worktree_path.to_string_lossy().to_string();
+    match &issue.problem {
+        WorktreeProblem::Missing { branch_exists } => {
+            git_stdout(&issue.project_dir, &["worktree", "prune"])?;
+            if *branch_exists {
+                git_stdout(&issue.project_dir, &["worktree", "add", &path, &issue.branch])?;
+            }
+        }
+        WorktreeProblem::NotRegistered => {
+            git_stdout(&issue.project_dir, &["worktree", "repair", &path])?;
+        }
+        WorktreeProblem::Detached | WorktreeProblem::WrongBranch(_) => {
+            git_stdout(&issue.worktree_path, &["checkout", &issue.branch])?;
+        }
+        WorktreeProblem::Interrupted("rebase") => abort_rebase(&issue.worktree_path)?,
+        WorktreeProblem::Interrupted(_) => {
+            git_stdout(&issue.worktree_path, &["merge", "--abort"])?;
+        }
+    }
+    Ok(())
+}
+
+fn branch_exists(project_dir: &Path, branch: &str) -> bool {
+    git_succeeds(project_dir, &["rev-parse", "-q", "--verify", &format!("refs/heads/{}", branch)])
+}
+
+fn git_succeeds(dir: &Path, args: &[&str]) -> bool {
+    Command::new("git")
+        .current_dir(dir)
+        .args(args)
+        .timed_output()
+        .map(|o| o.status.success())
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::worktree::git::list_worktrees;
+
+    fn run(dir: &Path, args: &[&str]) {
+        let status = Command::new("git")
+            .current_dir(dir)
+            .args(["-c", "user.name=t", "-c", "user.email=t@t", "-c", "commit.gpgsign=false"])
+            .args(args)
+            .output()
+            .unwrap()
+            .status;
+        assert!(status.success(), "git {:?} failed", args);
+    }
+
+    #[test]
+    fn test_check_and_fix_worktree_problems() {
+        let dir = tempfile::tempdir().unwrap();
+        let project = dir.path().join("repo");
+        std::fs::create_dir_all(&project).unwrap();
+        run(&project, &["init", "-q", "-b", "main"]);
+        std::fs::write(project.join("a.txt"), "one\n").unwrap();
+        run(&project, &["add", "."]);
+        run(&project, &["commit", "-qm", "init"]);
+
+        let wt = project.join("worktrees").join("TEST-abc");
+        let branch = "claude/TEST-abc";
+        run(&project, &["worktree", "add", "-q", "-b", branch, wt.to_str().unwrap()]);
+        let check = |busy| check_worktree(&project, &wt, branch, &list_worktrees(&project).unwrap(), busy);
+        assert_eq!(check(false), None);
+
+        // Detached HEAD, fixed by checking the branch out again
+        run(&wt, &["checkout", "-q", "--detach"]);
+        assert_eq!(check(false), Some(WorktreeProblem::Detached));
+        assert_eq!(check(true), None);
+        let issue = WorktreeIssue {
+            task_id: Uuid::new_v4(),
+            task_label: "TEST-abc".to_string(),
+            project_dir: project.clone(),
+            worktree_path: wt.clone(),
+            branch: branch.to_string(),
+            problem: WorktreeProblem::Detached,
+        };
+        fix_issue(&issue).unwrap();
+        assert_eq!(check(false), None);
+
+        // Deleted directory, recreated from the surviving branch
+        std::fs::remove_dir_all(&wt).unwrap();
+        assert_eq!(check(false), Some(WorktreeProblem::Missing { branch_exists: true }));
+        fix_issue(&WorktreeIssue { problem: WorktreeProblem::Missing { branch_exists: true }, ..issue }).unwrap();
+        assert_eq!(check(false), None);
+    }
+}
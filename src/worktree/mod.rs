@@ -4,14 +4,24 @@
 //! between concurrent tasks. Changes are tracked on separate branches for easy
 //! review, accept, or discard.
 
+pub mod caches;
+pub mod diff_cache;
+pub mod fswatch;
 pub mod git;
+pub mod history;
+pub mod ignore;
+pub mod integrity;
+pub mod native;
+pub mod progress;
+pub mod snapshot;
 mod settings;
 
 pub use git::{
-    create_worktree, remove_worktree, merge_branch, delete_branch,
-    get_task_diff, apply_task_changes, unapply_task_changes, force_unapply_task_changes,
+    create_worktree, merge_branch, delete_branch,
+    get_task_diff, get_worktree_diff, branch_head, main_head, apply_task_changes, unapply_task_changes, force_unapply_task_changes,
     surgical_unapply_for_stash_conflict, UnapplyResult, cleanup_applied_state,
     needs_rebase, verify_rebase_success, generate_rebase_prompt,
+    begin_rebase_for_handoff, generate_conflict_handoff_prompt, RebaseConflict,
     generate_apply_prompt, generate_stash_conflict_prompt, save_current_changes_as_patch,
     is_rebase_in_progress, try_fast_rebase,
     commit_worktree_changes, has_changes_to_merge, commit_main_changes, commit_applied_changes,
@@ -19,8 +29,15 @@ pub use git::{
     has_uncommitted_changes,
     // Git remote operations
     git_fetch, git_push, smart_git_pull, get_remote_status,
+    // Sparse checkout
+    parse_sparse_patterns,
+    // Worktree pool
+    warm_worktree_pool, take_pooled_worktree, release_worktree, drain_worktree_pool,
+    // Tags
+    latest_tag, ref_commit_date,
     // Stash tracking
     create_tracked_stash, pop_tracked_stash, drop_tracked_stash,
     abort_stash_pop_keep_task_changes, get_stash_details,
 };
+pub use progress::{CancelToken, GitProgress};
 pub use settings::{merge_with_project_settings, pre_trust_worktree, remove_worktree_trust};
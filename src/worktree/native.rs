@@ -0,0 +1,299 @@
+//! In-process git backend (libgit2) for read-only queries
+//!
+//! Status, ahead/behind counts and diff stats are refreshed for every task on
+//! every tick; running them through libgit2 avoids spawning several `git`
+//! processes per worktree. Anything that rewrites history (merge, rebase,
+//! apply) stays on the subprocess path in `git.rs`.
+//!
+//! The backend is off by default. Enabling it runs `benchmark` first and only
+//! switches over when both backends agree and libgit2 is faster.
+
+use anyhow::{anyhow, Result};
+use git2::{BranchType, DiffFindOptions, DiffFormat, Repository, StatusOptions};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+use super::git::{ChangedFile, RemoteStatus, WorktreeGitStatus};
+
+/// Whether read-only queries go through libgit2
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Rounds per backend when benchmarking
+const BENCHMARK_ROUNDS: u32 = 3;
+
+/// Open a local repository. Remote projects fail here so callers fall back to
+/// `git` over SSH (a local checkout at the same path must not answer for them).
+fn open(dir: &Path) -> Result<Repository> {
+    if crate::remote::is_remote(dir) {
+        return Err(anyhow!("{} is on a remote host", dir.display()));
+    }
+    // Snapshot projects keep their repository outside the folder
+    if let Some(git_dir) = super::snapshot::git_dir_for(dir) {
+        let repo = Repository::open(git_dir)?;
+        repo.set_workdir(dir, false)?;
+        return Ok(repo);
+    }
+    Ok(Repository::open(dir)?)
+}
+
+/// Switch the backend used by the status/diff helpers in `git.rs`
+pub fn set_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+pub fn enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+fn branch_commit<'r>(repo: &'r Repository, display_id: &str) -> Result<git2::Commit<'r>> {
+    let branch = repo.find_branch(&format!("claude/{}", display_id), BranchType::Local)?;
+    Ok(branch.get().peel_to_commit()?)
+}
+
+/// Diff from `old` to `new`, with rename detection like `git diff`
+fn tree_diff<'r>(repo: &'r Repository, old: &git2::Commit, new: &git2::Commit) -> Result<git2::Diff<'r>> {
+    let mut diff = repo.diff_tree_to_tree(Some(&old.tree()?), Some(&new.tree()?), None)?;
+    diff.find_similar(Some(DiffFindOptions::new().renames(true)))?;
+    Ok(diff)
+}
+
+/// Same as `git::get_worktree_git_status`
+pub fn worktree_git_status(project_dir: &Path, display_id: &str) -> Result<WorktreeGitStatus> {
+    let repo = open(project_dir)?;
+    let mut status = WorktreeGitStatus::default();
+
+    let head = repo.head()?.peel_to_commit()?;
+    let Ok(branch) = branch_commit(&repo, display_id) else {
+        // Branch might not exist
+        return Ok(status);
+    };
+    let Ok(merge_base) = repo.merge_base(head.id(), branch.id()) else {
+        // No common ancestor
+        return Ok(status);
+    };
+
+    let stats = tree_diff(&repo, &repo.find_commit(merge_base)?, &branch)?.stats()?;
+    status.files_changed = stats.files_changed();
+    status.additions = stats.insertions();
+    status.deletions = stats.deletions();
+
+    let (ahead, behind) = repo.graph_ahead_behind(branch.id(), head.id())?;
+    status.commits_ahead = ahead;
+    status.commits_behind = behind;
+    Ok(status)
+}
+
+/// Same as `git::get_worktree_changed_files`
+pub fn worktree_changed_files(project_dir: &Path, display_id: &str) -> Result<Vec<ChangedFile>> {
+    let repo = open(project_dir)?;
+    let head = repo.head()?.peel_to_commit()?;
+    let Ok(branch) = branch_commit(&repo, display_id) else {
+        return Ok(Vec::new());
+    };
+    let Ok(merge_base) = repo.merge_base(head.id(), branch.id()) else {
+        return Ok(Vec::new());
+    };
+
+    let diff = tree_diff(&repo, &repo.find_commit(merge_base)?, &branch)?;
+    let mut files = Vec::new();
+    for (idx, delta) in diff.deltas().enumerate() {
+        let (_, additions, deletions) = git2::Patch::from_diff(&diff, idx)?
+            .map(|patch| patch.line_stats())
+            .transpose()?
+            .unwrap_or_default();
+        let path = delta.new_file().path().or(delta.old_file().path())
+            .map(|p| p.to_string_lossy().to_string())
+            .unwrap_or_default();
+        files.push(ChangedFile {
+            path,
+            additions,
+            deletions,
+            is_new: delta.status() == git2::Delta::Added,
+            is_deleted: delta.status() == git2::Delta::Deleted,
+            is_renamed: delta.status() == git2::Delta::Renamed,
+        });
+    }
+
+    // Sort by most changes first
+    files.sort_by_key(|f| std::cmp::Reverse(f.additions + f.deletions));
+    Ok(files)
+}
+
+/// Same as `git::get_task_diff` (unified diff from main/master to the task branch)
+pub fn task_diff(project_dir: &Path, display_id: &str) -> Result<String> {
+    let repo = open(project_dir)?;
+    let base = ["main", "master"]
+        .iter()
+        .find_map(|name| repo.find_branch(name, BranchType::Local).ok())
+        .map(|b| b.get().peel_to_commit())
+        .unwrap_or_else(|| repo.head()?.peel_to_commit())?;
+    let branch = branch_commit(&repo, display_id)?;
+
+    let diff = tree_diff(&repo, &base, &branch)?;
+    let mut out = String::new();
+    diff.print(DiffFormat::Patch, |_, _, line| {
+        // Content lines carry their origin ('+', '-', ' ') separately
+        if matches!(line.origin(), '+' | '-' | ' ') {
+            out.push(line.origin());
+        }
+        out.push_str(&String::from_utf8_lossy(line.content()));
+        true
+    })?;
+    Ok(out)
+}
+
+/// Same as `git::get_remote_status`
+pub fn remote_status(project_dir: &Path) -> Result<RemoteStatus> {
+    let repo = open(project_dir)?;
+    let head = repo.head()?;
+    if !head.is_branch() {
+        // Detached HEAD state
+        return Ok(RemoteStatus::default());
+    }
+    let name = head.shorthand().ok_or_else(|| anyhow!("Branch name is not UTF-8"))?;
+    let branch = repo.find_branch(name, BranchType::Local)?;
+    let Ok(upstream) = branch.upstream() else {
+        // No upstream configured
+        return Ok(RemoteStatus::default());
+    };
+
+    // "origin/main" -> ("origin", "main")
+    let upstream_name = upstream.name()?.unwrap_or_default().to_string();
+    let (remote_name, remote_branch) = match upstream_name.split_once('/') {
+        Some((remote, branch)) => (Some(remote.to_string()), Some(branch.to_string())),
+        None => (None, None),
+    };
+
+    let (Some(local), Some(remote)) = (branch.get().target(), upstream.get().target()) else {
+        return Ok(RemoteStatus { has_remote: true, remote_name, remote_branch, ..Default::default() });
+    };
+    let (ahead, behind) = repo.graph_ahead_behind(local, remote)?;
+    Ok(RemoteStatus { ahead, behind, has_remote: true, remote_name, remote_branch })
+}
+
+/// Same as `git::has_uncommitted_changes` (staged, unstaged or untracked)
+pub fn has_uncommitted_changes(worktree_path: &Path) -> Result<bool> {
+    let repo = open(worktree_path)?;
+    let mut opts = StatusOptions::new();
+    opts.include_untracked(true).include_ignored(false);
+    let dirty = !repo.statuses(Some(&mut opts))?.is_empty();
+    Ok(dirty)
+}
+
+/// Timings from running both backends over the same queries
+#[derive(Debug, Clone)]
+pub struct BenchmarkResult {
+    pub worktrees: usize,
+    pub native: Duration,
+    pub subprocess: Duration,
+    /// Queries where the backends disagreed (native is not used if any)
+    pub mismatches: Vec<String>,
+}
+
+impl BenchmarkResult {
+    /// Native is only worth enabling when it's correct and faster
+    pub fn native_wins(&self) -> bool {
+        self.mismatches.is_empty() && self.native < self.subprocess
+    }
+
+    pub fn summary(&self) -> String {
+        format!(
+            "libgit2 {}ms vs git {}ms for {} worktree(s)",
+            self.native.as_millis(),
+            self.subprocess.as_millis(),
+            self.worktrees
+        )
+    }
+}
+
+/// Time the status queries of one refresh (all task worktrees + remote status)
+/// with each backend, and check that they return the same results
+pub fn benchmark(project_dir: &Path, display_ids: &[String]) -> BenchmarkResult {
+    let mut mismatches = Vec::new();
+    let mut native = Duration::ZERO;
+    let mut subprocess = Duration::ZERO;
+
+    for _ in 0..BENCHMARK_ROUNDS {
+        let start = Instant::now();
+        let native_results: Vec<_> = display_ids.iter()
+            .map(|id| worktree_git_status(project_dir, id).ok())
+            .collect();
+        let native_remote = remote_status(project_dir).ok();
+        native += start.elapsed();
+
+        let start = Instant::now();
+        let subprocess_results: Vec<_> = display_ids.iter()
+            .map(|id| super::git::subprocess_worktree_git_status(project_dir, id).ok())
+            .collect();
+        let subprocess_remote = super::git::subprocess_remote_status(project_dir).ok();
+        subprocess += start.elapsed();
+
+        if mismatches.is_empty() {
+            for ((id, a), b) in display_ids.iter().zip(&native_results).zip(&subprocess_results) {
+                if a != b {
+                    mismatches.push(format!("status of {}", id));
+                }
+            }
+            if native_remote.map(|r| (r.ahead, r.behind)) != subprocess_remote.map(|r| (r.ahead, r.behind)) {
+                mismatches.push("remote ahead/behind".to_string());
+            }
+        }
+    }
+
+    BenchmarkResult {
+        worktrees: display_ids.len(),
+        native: native / BENCHMARK_ROUNDS,
+        subprocess: subprocess / BENCHMARK_ROUNDS,
+        mismatches,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::process::Command;
+
+    fn git(dir: &Path, args: &[&str]) {
+        let status = Command::new("git")
+            .current_dir(dir)
+            .args(["-c", "user.name=t", "-c", "user.email=t@t", "-c", "commit.gpgsign=false"])
+            .args(args)
+            .output()
+            .unwrap()
+            .status;
+        assert!(status.success(), "git {:?} failed", args);
+    }
+
+    #[test]
+    fn test_status_matches_subprocess() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().to_path_buf();
+        git(&path, &["init", "-q", "-b", "main"]);
+        std::fs::write(path.join("a.txt"), "one\ntwo\n").unwrap();
+        git(&path, &["add", "."]);
+        git(&path, &["commit", "-qm", "init"]);
+
+        git(&path, &["checkout", "-qb", "claude/TEST-abc"]);
+        std::fs::write(path.join("a.txt"), "one\nthree\nfour\n").unwrap();
+        std::fs::write(path.join("b.txt"), "new\n").unwrap();
+        git(&path, &["add", "."]);
+        git(&path, &["commit", "-qm", "task"]);
+
+        git(&path, &["checkout", "-q", "main"]);
+        std::fs::write(path.join("c.txt"), "main\n").unwrap();
+        git(&path, &["add", "."]);
+        git(&path, &["commit", "-qm", "main moved"]);
+
+        let native = worktree_git_status(&path, "TEST-abc").unwrap();
+        let subprocess = super::super::git::subprocess_worktree_git_status(&path, "TEST-abc").unwrap();
+        assert_eq!(native, subprocess);
+        assert_eq!((native.files_changed, native.additions, native.deletions), (2, 3, 1));
+        assert_eq!((native.commits_ahead, native.commits_behind), (1, 1));
+
+        let files = worktree_changed_files(&path, "TEST-abc").unwrap();
+        assert_eq!(files.len(), 2);
+        assert!(files.iter().any(|f| f.path == "b.txt" && f.is_new));
+        assert!(task_diff(&path, "TEST-abc").unwrap().contains("+three"));
+    }
+}
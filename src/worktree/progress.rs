@@ -0,0 +1,188 @@
+//! Progress reporting and cancellation for long-running git commands
+//!
+//! Network operations (fetch/pull/push) run git with `--progress` and parse
+//! the `\r`-separated stderr updates into structured `GitProgress` events.
+//! A `CancelToken` lets the UI kill the subprocess mid-transfer.
+
+use anyhow::{anyhow, Result};
+use std::io::Read;
+use std::path::PathBuf;
+use std::process::{Command, Output, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc};
+use std::time::Duration;
+
+/// How often the cancel flag is checked while git is silent
+const CANCEL_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// A progress update from a running git command
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GitProgress {
+    /// e.g. "Receiving objects", "Writing objects", "Resolving deltas"
+    pub phase: String,
+    pub percent: u8,
+    /// Transferred size as reported by git (e.g. "1.20 MiB")
+    pub bytes: Option<String>,
+}
+
+/// Shared flag used to cancel a running git operation
+#[derive(Debug, Clone, Default)]
+pub struct CancelToken(Arc<AtomicBool>);
+
+impl CancelToken {
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::SeqCst)
+    }
+}
+
+/// Error returned when an operation was cancelled via its `CancelToken`
+#[derive(Debug)]
+pub struct Cancelled;
+
+impl std::fmt::Display for Cancelled {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Cancelled")
+    }
+}
+
+impl std::error::Error for Cancelled {}
+
+/// Parse one git progress line, e.g.
+/// "Receiving objects:  45% (450/1000), 1.20 MiB | 2.00 MiB/s"
+/// "remote: Counting objects: 100% (12/12), done."
+pub fn parse_progress_line(line: &str) -> Option<GitProgress> {
+    let line = line.trim().trim_start_matches("remote:").trim();
+    let (phase, rest) = line.split_once(':')?;
+    if phase.is_empty() || !phase.chars().all(|c| c.is_alphabetic() || c == ' ') {
+        return None;
+    }
+
+    let rest = rest.trim();
+    let percent = rest
+        .split_once('%')
+        .and_then(|(p, _)| p.trim().parse::<u8>().ok())?;
+
+    // Size is the part after the counts: "..., 1.20 MiB | 2.00 MiB/s"
+    let bytes = rest
+        .split_once("), ")
+        .map(|(_, size)| size.split('|').next().unwrap_or("").trim().trim_end_matches(", done."))
+        .filter(|size| !size.is_empty() && *size != "done.")
+        .map(|size| size.to_string());
+
+    Some(GitProgress { phase: phase.trim().to_string(), percent, bytes })
+}
+
+/// Run a git command, reporting progress from stderr and honouring `cancel`.
+/// Add `--progress` to `args` - git only reports progress to a terminal otherwise.
+pub fn run_git_with_progress(
+    dir: &PathBuf,
+    args: &[&str],
+    cancel: &CancelToken,
+    on_progress: &mut dyn FnMut(GitProgress),
+) -> Result<Output> {
+    let mut git = Command::new("git");
+    git.current_dir(dir).args(args);
+    let mut child = crate::remote::routed(git)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+
+    // Read both pipes on helper threads so git never blocks on a full pipe
+    let mut stdout = child.stdout.take().ok_or_else(|| anyhow!("No stdout"))?;
+    let stdout_reader = std::thread::spawn(move || {
+        let mut buf = Vec::new();
+        let _ = stdout.read_to_end(&mut buf);
+        buf
+    });
+
+    let mut stderr = child.stderr.take().ok_or_else(|| anyhow!("No stderr"))?;
+    let (tx, rx) = mpsc::channel::<String>();
+    std::thread::spawn(move || {
+        // Progress lines are terminated by \r (updates) or \n (final)
+        let mut line = Vec::new();
+        let mut byte = [0u8; 1];
+        while let Ok(1) = stderr.read(&mut byte) {
+            if byte[0] == b'\r' || byte[0] == b'\n' {
+                if !line.is_empty() && tx.send(String::from_utf8_lossy(&line).to_string()).is_err() {
+                    return;
+                }
+                line.clear();
+            } else {
+                line.push(byte[0]);
+            }
+        }
+        if !line.is_empty() {
+            let _ = tx.send(String::from_utf8_lossy(&line).to_string());
+        }
+    });
+
+    let mut stderr_lines: Vec<String> = Vec::new();
+    let mut last: Option<GitProgress> = None;
+    loop {
+        if cancel.is_cancelled() {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err(Cancelled.into());
+        }
+
+        match rx.recv_timeout(CANCEL_POLL_INTERVAL) {
+            Ok(line) => {
+                if let Some(progress) = parse_progress_line(&line) {
+                    // Only report changes (git repeats lines many times per second)
+                    if last.as_ref() != Some(&progress) {
+                        on_progress(progress.clone());
+                        last = Some(progress);
+                    }
+                } else {
+                    stderr_lines.push(line);
+                }
+            }
+            Err(mpsc::RecvTimeoutError::Timeout) => continue,
+            Err(mpsc::RecvTimeoutError::Disconnected) => break,
+        }
+    }
+
+    let status = child.wait()?;
+    let stdout = stdout_reader.join().unwrap_or_default();
+    Ok(Output {
+        status,
+        stdout,
+        stderr: stderr_lines.join("\n").into_bytes(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_receiving_objects() {
+        let p = parse_progress_line("Receiving objects:  45% (450/1000), 1.20 MiB | 2.00 MiB/s").unwrap();
+        assert_eq!(p.phase, "Receiving objects");
+        assert_eq!(p.percent, 45);
+        assert_eq!(p.bytes.as_deref(), Some("1.20 MiB"));
+    }
+
+    #[test]
+    fn test_parse_remote_and_done_lines() {
+        let p = parse_progress_line("remote: Counting objects: 100% (12/12), done.").unwrap();
+        assert_eq!(p.phase, "Counting objects");
+        assert_eq!(p.percent, 100);
+        assert_eq!(p.bytes, None);
+
+        let p = parse_progress_line("Writing objects: 100% (3/3), 280 bytes | 280.00 KiB/s, done.").unwrap();
+        assert_eq!(p.bytes.as_deref(), Some("280 bytes"));
+    }
+
+    #[test]
+    fn test_parse_ignores_other_output() {
+        assert!(parse_progress_line("From github.com:org/repo").is_none());
+        assert!(parse_progress_line("fatal: could not read Username").is_none());
+        assert!(parse_progress_line("To github.com:org/repo.git").is_none());
+    }
+}
@@ -4,27 +4,31 @@
 
 use anyhow::Result;
 use serde_json::{json, Value};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use uuid::Uuid;
 
-/// Get the path to Claude's global config file
-fn get_claude_config_path() -> PathBuf {
-    dirs::home_dir()
-        .unwrap_or_else(|| PathBuf::from("."))
-        .join(".claude.json")
+/// Claude's global config file, in the home directory
+const CLAUDE_CONFIG: &str = ".claude.json";
+
+/// Command the hooks run: this binary, or `kanblam` from PATH on a remote host
+fn kanblam_bin(worktree_path: &Path) -> String {
+    if crate::remote::is_remote(worktree_path) {
+        return "kanblam".to_string();
+    }
+    std::env::current_exe()
+        .unwrap_or_else(|_| PathBuf::from("kanblam"))
+        .to_string_lossy()
+        .to_string()
 }
 
 /// Pre-trust a worktree directory in Claude's global config
 /// This prevents the "Do you trust this folder?" dialog
+/// (on the remote host's config for remote projects)
 pub fn pre_trust_worktree(worktree_path: &PathBuf) -> Result<()> {
-    let config_path = get_claude_config_path();
-
     // Read existing config or create new one
-    let mut config: Value = if config_path.exists() {
-        let content = std::fs::read_to_string(&config_path)?;
-        serde_json::from_str(&content).unwrap_or_else(|_| json!({}))
-    } else {
-        json!({})
+    let mut config: Value = match crate::remote::read_home_file(worktree_path, CLAUDE_CONFIG)? {
+        Some(content) => serde_json::from_str(&content).unwrap_or_else(|_| json!({})),
+        None => json!({}),
     };
 
     // Ensure projects object exists
@@ -57,20 +61,16 @@ pub fn pre_trust_worktree(worktree_path: &PathBuf) -> Result<()> {
 
     // Write back the config
     let content = serde_json::to_string_pretty(&config)?;
-    std::fs::write(&config_path, content)?;
+    crate::remote::write_home_file(worktree_path, CLAUDE_CONFIG, &content)?;
 
     Ok(())
 }
 
 /// Remove trust entry for a worktree from Claude's global config
 pub fn remove_worktree_trust(worktree_path: &PathBuf) -> Result<()> {
-    let config_path = get_claude_config_path();
-
-    if !config_path.exists() {
+    let Some(content) = crate::remote::read_home_file(worktree_path, CLAUDE_CONFIG)? else {
         return Ok(());
-    }
-
-    let content = std::fs::read_to_string(&config_path)?;
+    };
     let mut config: Value = serde_json::from_str(&content)?;
 
     // Get the absolute path as the key
@@ -87,7 +87,7 @@ pub fn remove_worktree_trust(worktree_path: &PathBuf) -> Result<()> {
 
     // Write back the config
     let content = serde_json::to_string_pretty(&config)?;
-    std::fs::write(&config_path, content)?;
+    crate::remote::write_home_file(worktree_path, CLAUDE_CONFIG, &content)?;
 
     Ok(())
 }
@@ -104,13 +104,10 @@ pub fn setup_claude_settings(
     _project_dir: &PathBuf, // For future: copy project settings
 ) -> Result<()> {
     let claude_dir = worktree_path.join(".claude");
-    std::fs::create_dir_all(&claude_dir)?;
+    crate::remote::create_dir_all(&claude_dir)?;
 
     // Get the absolute path to the kanblam binary
-    let kanblam_bin = std::env::current_exe()
-        .unwrap_or_else(|_| PathBuf::from("kanblam"))
-        .to_string_lossy()
-        .to_string();
+    let kanblam_bin = kanblam_bin(worktree_path);
 
     // Build settings JSON with correct Claude Code format
     // Permissions: use tool names without parentheses for "allow all"
@@ -138,7 +135,13 @@ pub fn setup_claude_settings(
             "PreToolUse": [{
                 "hooks": [{
                     "type": "command",
-                    "command": format!("{} signal working {}", kanblam_bin, task_id)
+                    "command": format!("{} signal tool-start {}", kanblam_bin, task_id)
+                }]
+            }],
+            "PostToolUse": [{
+                "hooks": [{
+                    "type": "command",
+                    "command": format!("{} signal tool-finish {}", kanblam_bin, task_id)
                 }]
             }]
         }
@@ -146,32 +149,39 @@ pub fn setup_claude_settings(
 
     let settings_path = claude_dir.join("settings.json");
     let content = serde_json::to_string_pretty(&settings)?;
-    std::fs::write(&settings_path, content)?;
+    crate::remote::write(&settings_path, content)?;
 
     // Also create a .claudeignore if it doesn't exist, to prevent Claude
     // from wandering outside the worktree
     let claudeignore_path = worktree_path.join(".claudeignore");
-    if !claudeignore_path.exists() {
+    if !crate::remote::exists(&claudeignore_path) {
         // Ignore parent directories that might be symlinked
-        std::fs::write(&claudeignore_path, "# Kanblam worktree - stay within this directory\n")?;
+        crate::remote::write(&claudeignore_path, "# Kanblam worktree - stay within this directory\n")?;
     }
 
     Ok(())
 }
 
-/// Merge project's existing Claude settings with worktree settings
+/// Tools pre-approved for the restricted agent profile (Bash and Write need approval)
+const RESTRICTED_ALLOW: [&str; 4] = ["Read", "Edit", "Grep", "Glob"];
+
+/// Always denied for the restricted agent profile
+const RESTRICTED_DENY: [&str; 3] = ["Bash(git push:*)", "Bash(rm -rf:*)", "WebFetch"];
+
+/// Merge project's existing Claude settings with worktree settings.
+/// `restricted` applies the restricted agent profile (used for auto-started bug tasks);
+/// `model` overrides the project's model for this task.
 pub fn merge_with_project_settings(
     worktree_path: &PathBuf,
     project_dir: &PathBuf,
     task_id: Uuid,
+    restricted: bool,
+    model: Option<&str>,
 ) -> Result<()> {
     let project_settings_path = project_dir.join(".claude").join("settings.json");
 
     // Get the absolute path to the kanblam binary
-    let kanblam_bin = std::env::current_exe()
-        .unwrap_or_else(|_| PathBuf::from("kanblam"))
-        .to_string_lossy()
-        .to_string();
+    let kanblam_bin = kanblam_bin(worktree_path);
 
     // Start with our base settings (correct Claude Code format)
     let mut settings = json!({
@@ -228,17 +238,30 @@ pub fn merge_with_project_settings(
             "PreToolUse": [{
                 "hooks": [{
                     "type": "command",
-                    "command": format!("{} signal working {}", kanblam_bin, task_id)
+                    "command": format!("{} signal tool-start {}", kanblam_bin, task_id)
+                }]
+            }],
+            "PostToolUse": [{
+                "hooks": [{
+                    "type": "command",
+                    "command": format!("{} signal tool-finish {}", kanblam_bin, task_id)
                 }]
             }]
         }
     });
 
+    if restricted {
+        settings["permissions"] = json!({
+            "allow": RESTRICTED_ALLOW,
+            "deny": RESTRICTED_DENY,
+        });
+    }
+
     // If project has settings, merge ONLY non-hook settings
     // IMPORTANT: Do NOT merge hooks - worktrees have their own task-specific hooks.
     // Merging project hooks would add duplicate hooks with wrong task IDs.
-    if project_settings_path.exists() {
-        if let Ok(content) = std::fs::read_to_string(&project_settings_path) {
+    if crate::remote::exists(&project_settings_path) {
+        if let Ok(content) = crate::remote::read_to_string(&project_settings_path) {
             if let Ok(project_settings) = serde_json::from_str::<Value>(&content) {
                 // Merge specific fields from project settings
                 if let Some(obj) = project_settings.as_object() {
@@ -254,7 +277,14 @@ pub fn merge_with_project_settings(
                                 // Project can restrict via deny list
                                 if let Some(perms) = value.get("deny") {
                                     if let Some(our_perms) = settings["permissions"].as_object_mut() {
-                                        our_perms.insert("deny".to_string(), perms.clone());
+                                        if restricted {
+                                            // Keep the profile's denials on top of the project's
+                                            let mut deny = perms.as_array().cloned().unwrap_or_default();
+                                            deny.extend(RESTRICTED_DENY.iter().map(|d| json!(d)));
+                                            our_perms.insert("deny".to_string(), json!(deny));
+                                        } else {
+                                            our_perms.insert("deny".to_string(), perms.clone());
+                                        }
                                     }
                                 }
                             }
@@ -271,12 +301,16 @@ pub fn merge_with_project_settings(
         }
     }
 
+    if let Some(model) = model {
+        settings["model"] = json!(model);
+    }
+
     // Write the merged settings
     let claude_dir = worktree_path.join(".claude");
-    std::fs::create_dir_all(&claude_dir)?;
+    crate::remote::create_dir_all(&claude_dir)?;
     let settings_path = claude_dir.join("settings.json");
     let content = serde_json::to_string_pretty(&settings)?;
-    std::fs::write(&settings_path, content)?;
+    crate::remote::write(&settings_path, content)?;
 
     Ok(())
 }
@@ -0,0 +1,126 @@
+//! Snapshot isolation for folders that aren't git repositories
+//!
+//! A plain folder opened in snapshot mode gets a shadow repository under
+//! kanblam's data directory rather than a `.git` of its own. Git commands run
+//! in the folder are pointed at the shadow (`GIT_DIR`/`GIT_WORK_TREE`), so task
+//! worktrees, diffs, apply and merge behave as in any repository; the folder
+//! only gains the `worktrees/` directory tasks run in. The folder's state is
+//! committed to the shadow as a snapshot before a task branches off and before
+//! a task's changes come back, so both start from what's on disk.
+
+use anyhow::{anyhow, Result};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use super::git::git_stdout;
+use crate::timing::TimedCommand;
+
+/// Left out of snapshots (kanblam's own files and the task worktrees)
+const EXCLUDES: &[&str] = &[".kanblam/", ".claude/", "worktrees/"];
+
+/// Where the shadow repository of `project_dir` lives
+pub fn shadow_dir(project_dir: &Path) -> Option<PathBuf> {
+    let name = project_dir.to_string_lossy().replace('%', "%25").replace('/', "%2F");
+    Some(dirs::data_local_dir()?.join("kanblam").join("snapshots").join(name))
+}
+
+/// The shadow repository of `dir`, when `dir` is a snapshot project's folder
+pub fn git_dir_for(dir: &Path) -> Option<PathBuf> {
+    if dir.join(".git").exists() || crate::remote::is_remote(dir) {
+        return None;
+    }
+    shadow_dir(dir).filter(|shadow| shadow.join("HEAD").is_file())
+}
+
+pub fn is_snapshot_project(dir: &Path) -> bool {
+    git_dir_for(dir).is_some()
+}
+
+/// Point a git command run in a snapshot project's folder at its shadow
+pub fn route(command: &mut Command) {
+    if command.get_program() != "git" {
+        return;
+    }
+    let Some(dir) = command.get_current_dir().map(Path::to_path_buf) else {
+        return;
+    };
+    if let Some(git_dir) = git_dir_for(&dir) {
+        command.env("GIT_DIR", git_dir).env("GIT_WORK_TREE", dir);
+    }
+}
+
+/// Create the shadow repository of `project_dir` and take the first snapshot
+pub fn init(project_dir: &Path) -> Result<PathBuf> {
+    if project_dir.join(".git").exists() {
+        return Err(anyhow!("{} is already a git repository", project_dir.display()));
+    }
+    let git_dir = shadow_dir(project_dir).ok_or_else(|| anyhow!("No data directory for the snapshot repository"))?;
+    std::fs::create_dir_all(&git_dir)?;
+
+    let output = Command::new("git").args(["init", "--quiet", "--bare"]).arg(&git_dir).timed_output()?;
+    if !output.status.success() {
+        return Err(anyhow!("Failed to create snapshot repository: {}", String::from_utf8_lossy(&output.stderr).trim()));
+    }
+    // Bare only so git doesn't expect a work tree next to it; the folder is the work tree
+    git_stdout(&git_dir, &["config", "core.bare", "false"])?;
+    // Folders without git often mean no git identity either; commits here need one
+    if git_stdout(&git_dir, &["config", "user.email"]).is_err() {
+        git_stdout(&git_dir, &["config", "user.name", "kanblam"])?;
+        git_stdout(&git_dir, &["config", "user.email", "kanblam@localhost"])?;
+    }
+    std::fs::create_dir_all(git_dir.join("info"))?;
+    std::fs::write(git_dir.join("info").join("exclude"), EXCLUDES.join("\n") + "\n")?;
+
+    take(project_dir)?;
+    Ok(git_dir)
+}
+
+/// Commit the folder's current state to its shadow repository. Returns
+/// whether anything changed since the last snapshot (always false for
+/// folders that aren't snapshot projects).
+pub fn take(project_dir: &Path) -> Result<bool> {
+    if !is_snapshot_project(project_dir) {
+        return Ok(false);
+    }
+    git_stdout(project_dir, &["add", "-A"])?;
+    let has_head = git_stdout(project_dir, &["rev-parse", "--verify", "--quiet", "HEAD"]).is_ok();
+    if has_head && git_stdout(project_dir, &["diff", "--cached", "--quiet"]).is_ok() {
+        return Ok(false);
+    }
+    let message = format!("Snapshot {}", chrono::Local::now().format("%Y-%m-%d %H:%M:%S"));
+    git_stdout(project_dir, &["commit", "--quiet", "--allow-empty", "--no-verify", "-m", &message])?;
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_snapshots_and_routing() {
+        let folder = tempfile::tempdir().unwrap();
+        let dir = folder.path().to_path_buf();
+        std::fs::write(dir.join("notes.txt"), "first").unwrap();
+        assert!(!is_snapshot_project(&dir));
+
+        let git_dir = init(&dir).unwrap();
+        assert!(is_snapshot_project(&dir));
+        assert!(!dir.join(".git").exists());
+        assert!(crate::worktree::git::is_git_repo(&dir));
+        assert!(!take(&dir).unwrap());
+
+        std::fs::write(dir.join("notes.txt"), "second").unwrap();
+        std::fs::create_dir_all(dir.join(".kanblam")).unwrap();
+        std::fs::write(dir.join(".kanblam").join("tasks.json"), "{}").unwrap();
+        assert!(take(&dir).unwrap());
+        let files = git_stdout(&dir, &["ls-files"]).unwrap();
+        assert_eq!(files.trim(), "notes.txt");
+
+        // Worktrees link back to the shadow through their own .git file
+        let worktree = crate::worktree::create_worktree(&dir, "SNAP-abc", &[], None).unwrap();
+        assert_eq!(std::fs::read_to_string(worktree.join("notes.txt")).unwrap(), "second");
+        assert!(!is_snapshot_project(&worktree));
+
+        std::fs::remove_dir_all(&git_dir).unwrap();
+    }
+}